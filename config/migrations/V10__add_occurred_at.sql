@@ -0,0 +1,9 @@
+-- Declared occurrence times for backfilled historical records.
+-- `timestamp` / `created_at_ts` keep meaning "recorded at"; NULL means the
+-- occurrence was not declared and equals the recorded time.
+
+ALTER TABLE events ADD COLUMN IF NOT EXISTS occurred_at_ts BIGINT;
+CREATE INDEX IF NOT EXISTS idx_events_occurred_at_ts ON events (occurred_at_ts);
+
+ALTER TABLE items ADD COLUMN IF NOT EXISTS first_occurred_at_ts BIGINT;
+ALTER TABLE items ADD COLUMN IF NOT EXISTS last_occurred_at_ts BIGINT;
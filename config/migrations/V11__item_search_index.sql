@@ -0,0 +1,13 @@
+-- Search index for GET /api/items/search.
+-- `search_vector` covers enriched_data keys and scalar values with the `simple`
+-- configuration (lowercased words, no stemming), matching the in-memory tokenizer.
+
+ALTER TABLE items ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        jsonb_to_tsvector('simple', COALESCE(enriched_data, '{}'::jsonb), '["string", "numeric", "boolean", "key"]')
+    ) STORED;
+CREATE INDEX IF NOT EXISTS idx_items_search_vector ON items USING GIN (search_vector);
+
+CREATE INDEX IF NOT EXISTS idx_items_status_created ON items (status, created_at_ts, dfid);
+CREATE INDEX IF NOT EXISTS idx_item_identifiers_key_value ON item_identifiers (key, value, dfid);
+CREATE INDEX IF NOT EXISTS idx_circuit_items_circuit_dfid ON circuit_items (circuit_id, dfid);
@@ -0,0 +1,4 @@
+-- Per-user locale (BCP 47 tag) for localized notifications and emails.
+-- NULL means the server's DEFAULT_LOCALE.
+
+ALTER TABLE user_accounts ADD COLUMN IF NOT EXISTS locale TEXT;
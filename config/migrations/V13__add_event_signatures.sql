@@ -0,0 +1,7 @@
+-- Ed25519 signatures of the users asserting events. NULL for unsigned events.
+-- Signatures cover the event's signing hash, never server-assigned fields.
+
+ALTER TABLE events ADD COLUMN IF NOT EXISTS signature TEXT;
+ALTER TABLE events ADD COLUMN IF NOT EXISTS signer_public_key TEXT;
+CREATE INDEX IF NOT EXISTS idx_events_signer_public_key ON events (signer_public_key)
+    WHERE signer_public_key IS NOT NULL;
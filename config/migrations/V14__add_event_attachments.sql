@@ -0,0 +1,4 @@
+-- Files attached to events (lab certificates, photos). The content lives on
+-- IPFS; each entry keeps its CID, BLAKE3 content hash and mime type.
+
+ALTER TABLE events ADD COLUMN IF NOT EXISTS attachments JSONB NOT NULL DEFAULT '[]'::jsonb;
@@ -0,0 +1,9 @@
+-- Causal links between events. caused_by points at the event that led to this
+-- one; correlation_id groups the events recorded by one operation (a circuit
+-- push, the verification of a receipt).
+
+ALTER TABLE events ADD COLUMN IF NOT EXISTS caused_by UUID;
+ALTER TABLE events ADD COLUMN IF NOT EXISTS correlation_id UUID;
+
+CREATE INDEX IF NOT EXISTS idx_events_caused_by ON events(caused_by) WHERE caused_by IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_events_correlation_id ON events(correlation_id) WHERE correlation_id IS NOT NULL;
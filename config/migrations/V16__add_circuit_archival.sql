@@ -0,0 +1,7 @@
+-- Archived circuits keep their history and are loaded like any other circuit;
+-- listings leave them out. archived_at_ts/archived_by are cleared on restore.
+
+ALTER TABLE circuits ADD COLUMN IF NOT EXISTS archived_at_ts BIGINT;
+ALTER TABLE circuits ADD COLUMN IF NOT EXISTS archived_by VARCHAR(255);
+
+CREATE INDEX IF NOT EXISTS idx_circuits_archived ON circuits(status) WHERE status = 'Archived';
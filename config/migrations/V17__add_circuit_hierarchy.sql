@@ -0,0 +1,7 @@
+-- Parent/child circuits (a national body over regional co-ops). parent_access is
+-- what members of the parent and its ancestors get in the child: 'read' or 'none'.
+
+ALTER TABLE circuits ADD COLUMN IF NOT EXISTS parent_circuit_id UUID;
+ALTER TABLE circuits ADD COLUMN IF NOT EXISTS parent_access VARCHAR(16) NOT NULL DEFAULT 'read';
+
+CREATE INDEX IF NOT EXISTS idx_circuits_parent ON circuits(parent_circuit_id) WHERE parent_circuit_id IS NOT NULL;
@@ -0,0 +1,23 @@
+-- Write-ahead log of adapter (IPFS/Stellar) writes. An intent is recorded before
+-- the adapter is called and completed once the item's storage record exists, so
+-- intents still pending after a crash name the writes that have to be resumed.
+
+CREATE TABLE IF NOT EXISTS adapter_intents (
+    intent_id UUID PRIMARY KEY,
+    dfid VARCHAR(255) NOT NULL,
+    circuit_id UUID,
+    adapter_type VARCHAR(64) NOT NULL,
+    operation VARCHAR(32) NOT NULL,
+    is_new_dfid BOOLEAN NOT NULL DEFAULT FALSE,
+    requested_by VARCHAR(255) NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    storage_location JSONB,
+    created_at_ts BIGINT NOT NULL,
+    updated_at_ts BIGINT NOT NULL,
+    completed_at_ts BIGINT
+);
+
+CREATE INDEX IF NOT EXISTS idx_adapter_intents_incomplete ON adapter_intents(created_at_ts) WHERE status <> 'completed';
+CREATE INDEX IF NOT EXISTS idx_adapter_intents_dfid ON adapter_intents(dfid);
@@ -0,0 +1,6 @@
+-- Optional per-item expiry inside a circuit (e.g. supplier data visible to a
+-- buyer for 90 days). Expired items are removed by a scheduled task.
+
+ALTER TABLE circuit_items ADD COLUMN IF NOT EXISTS expires_at_ts BIGINT;
+
+CREATE INDEX IF NOT EXISTS idx_circuit_items_expires_at ON circuit_items(expires_at_ts) WHERE expires_at_ts IS NOT NULL;
@@ -0,0 +1,4 @@
+-- Ownership handover waiting for the new owner to accept (JSON: new_owner_id,
+-- initiated_by, initiated_at, expires_at); NULL when none is pending.
+
+ALTER TABLE circuits ADD COLUMN IF NOT EXISTS pending_ownership_transfer JSONB;
@@ -0,0 +1,5 @@
+-- Join requests of a circuit with their decisions (JSON array: requester_id,
+-- requested_at, message, status, decided_by, decided_at, granted_role,
+-- decision_reason), so the approval queue survives restarts.
+
+ALTER TABLE circuits ADD COLUMN IF NOT EXISTS join_requests JSONB NOT NULL DEFAULT '[]'::jsonb;
@@ -0,0 +1,18 @@
+-- Groth16 proving and verifying keys per circuit (generated or imported from
+-- a ceremony). Proofs reference the artifact they were produced with, so
+-- retired keys are kept to verify them.
+
+CREATE TABLE IF NOT EXISTS zk_setup_artifacts (
+    artifact_id VARCHAR(64) PRIMARY KEY,
+    circuit_type TEXT NOT NULL,
+    circuit_version VARCHAR(64) NOT NULL,
+    proving_key BYTEA NOT NULL,
+    verifying_key BYTEA NOT NULL,
+    source VARCHAR(32) NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_zk_setup_artifacts_active ON zk_setup_artifacts(circuit_type) WHERE active;
+
+ALTER TABLE zk_proofs ADD COLUMN IF NOT EXISTS setup_artifact_id VARCHAR(64);
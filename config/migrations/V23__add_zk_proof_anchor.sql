@@ -0,0 +1,4 @@
+-- Stellar anchor of a verified proof: network, IPCM key, published digest and
+-- transaction hash (see zk_proof_engine::ProofAnchor). NULL until anchored.
+
+ALTER TABLE zk_proofs ADD COLUMN IF NOT EXISTS anchor JSONB;
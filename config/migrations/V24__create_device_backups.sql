@@ -0,0 +1,17 @@
+-- Encrypted local database backups uploaded by field devices. Only metadata
+-- is kept here; the blob is written under DEVICE_BACKUP_DIR on the API host.
+
+CREATE TABLE IF NOT EXISTS device_backups (
+    backup_id UUID PRIMARY KEY,
+    user_id VARCHAR(255) NOT NULL,
+    device_id VARCHAR(128) NOT NULL,
+    version INTEGER NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    content_hash VARCHAR(128) NOT NULL,
+    encryption VARCHAR(64) NOT NULL,
+    blob_path TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (user_id, device_id, version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_device_backups_user ON device_backups(user_id, device_id);
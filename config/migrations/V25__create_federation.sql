@@ -0,0 +1,41 @@
+-- Federation between deployments: peers with their per-circuit cursors, the
+-- origin node of every imported record, and remote versions held back as
+-- conflicts.
+
+CREATE TABLE IF NOT EXISTS federation_peers (
+    peer_id UUID PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    node_id VARCHAR(255) NOT NULL UNIQUE,
+    public_key VARCHAR(64) NOT NULL,
+    url TEXT NOT NULL,
+    circuits JSONB NOT NULL DEFAULT '[]',
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    last_synced_at TIMESTAMPTZ,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS federation_provenance (
+    record_key VARCHAR(512) PRIMARY KEY,
+    origin_node VARCHAR(255) NOT NULL,
+    content_hash VARCHAR(128) NOT NULL,
+    signature VARCHAR(128) NOT NULL,
+    peer_id UUID NOT NULL,
+    imported_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS federation_conflicts (
+    conflict_id UUID PRIMARY KEY,
+    peer_id UUID NOT NULL,
+    local_circuit_id UUID NOT NULL,
+    record_key VARCHAR(512) NOT NULL,
+    local_hash VARCHAR(128) NOT NULL,
+    remote JSONB NOT NULL,
+    status VARCHAR(32) NOT NULL,
+    detected_at TIMESTAMPTZ NOT NULL,
+    resolved_by VARCHAR(255),
+    resolved_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_federation_conflicts_status ON federation_conflicts(status, detected_at);
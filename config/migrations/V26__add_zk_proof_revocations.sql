@@ -0,0 +1,16 @@
+-- Validity windows on ZK proofs and the registry of revoked proofs, kept per
+-- prover workspace.
+
+ALTER TABLE zk_proofs ADD COLUMN IF NOT EXISTS valid_from TIMESTAMPTZ;
+
+CREATE TABLE IF NOT EXISTS zk_proof_revocations (
+    proof_id UUID PRIMARY KEY,
+    prover_id VARCHAR(255) NOT NULL,
+    workspace_id VARCHAR(255),
+    revoked_by VARCHAR(255) NOT NULL,
+    reason TEXT NOT NULL,
+    revoked_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_zk_proof_revocations_workspace
+    ON zk_proof_revocations(workspace_id);
@@ -0,0 +1,28 @@
+-- Selective disclosure of item attributes: each item's current salted
+-- attribute commitments (the root is notarized for the item) and the
+-- disclosures owners hand out, looked up by the BLAKE3 hash of their token.
+
+CREATE TABLE IF NOT EXISTS item_attribute_commitments (
+    dfid VARCHAR(255) PRIMARY KEY,
+    commitments JSONB NOT NULL,
+    notarization_id UUID NOT NULL,
+    created_by VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS selective_disclosures (
+    disclosure_id UUID PRIMARY KEY,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    dfid VARCHAR(255) NOT NULL,
+    root VARCHAR(128) NOT NULL,
+    notarization_id UUID NOT NULL,
+    attributes JSONB NOT NULL,
+    recipient VARCHAR(255),
+    created_by VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_selective_disclosures_created_by
+    ON selective_disclosures(created_by);
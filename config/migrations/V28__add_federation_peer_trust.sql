@@ -0,0 +1,5 @@
+-- Trust state of federation peers. Peers registered before handshakes existed
+-- were entered by an admin with their key and stay trusted; the value is the
+-- JSON string of the state.
+
+ALTER TABLE federation_peers ADD COLUMN IF NOT EXISTS trust VARCHAR(16) NOT NULL DEFAULT '"trusted"';
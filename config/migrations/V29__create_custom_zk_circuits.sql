@@ -0,0 +1,13 @@
+-- Custom ZK circuits registered by workspaces: a constraint template with its
+-- parameters and input schema, stored whole as JSON. Definitions are never
+-- updated, since proofs made with them depend on them.
+
+CREATE TABLE IF NOT EXISTS custom_zk_circuits (
+    circuit_id VARCHAR(64) PRIMARY KEY,
+    workspace_id VARCHAR(255) NOT NULL,
+    definition JSONB NOT NULL,
+    created_by VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_custom_zk_circuits_workspace ON custom_zk_circuits(workspace_id);
@@ -0,0 +1,15 @@
+-- Asynchronous ZK proof generation jobs. Private inputs are kept in memory by
+-- the worker queue only and never stored; jobs a restart interrupted are
+-- marked failed on startup.
+
+CREATE TABLE IF NOT EXISTS zk_proof_jobs (
+    job_id UUID PRIMARY KEY,
+    prover_id VARCHAR(255) NOT NULL,
+    status VARCHAR(32) NOT NULL,
+    job JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_zk_proof_jobs_prover ON zk_proof_jobs(prover_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_zk_proof_jobs_status ON zk_proof_jobs(status);
@@ -0,0 +1,26 @@
+-- Per-workspace API usage anomaly detection: hourly usage per endpoint, the
+-- tunable thresholds of each workspace and the anomalies raised.
+
+CREATE TABLE IF NOT EXISTS workspace_usage_windows (
+    workspace_id VARCHAR(255) NOT NULL,
+    hour_start TIMESTAMPTZ NOT NULL,
+    usage JSONB NOT NULL,
+    PRIMARY KEY (workspace_id, hour_start)
+);
+
+CREATE TABLE IF NOT EXISTS usage_anomaly_baselines (
+    workspace_id VARCHAR(255) PRIMARY KEY,
+    baseline JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS usage_anomalies (
+    anomaly_id UUID PRIMARY KEY,
+    workspace_id VARCHAR(255) NOT NULL,
+    kind VARCHAR(32) NOT NULL,
+    anomaly JSONB NOT NULL,
+    detected_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_usage_anomalies_workspace ON usage_anomalies(workspace_id, detected_at DESC);
+CREATE INDEX IF NOT EXISTS idx_usage_anomalies_detected ON usage_anomalies(detected_at DESC);
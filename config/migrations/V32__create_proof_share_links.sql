@@ -0,0 +1,13 @@
+-- Signed, expiring share links to ZK proofs. The link token is derived from
+-- the share id and expiry with the server secret and is never stored.
+
+CREATE TABLE IF NOT EXISTS proof_share_links (
+    share_id UUID PRIMARY KEY,
+    proof_id UUID NOT NULL,
+    created_by VARCHAR(255) NOT NULL,
+    link JSONB NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_proof_share_links_proof ON proof_share_links(proof_id);
@@ -0,0 +1,8 @@
+-- The dedup strategy verification uses for each workspace's entries. Circuits
+-- override it through their alias config.
+
+CREATE TABLE IF NOT EXISTS workspace_dedup_configs (
+    workspace_id VARCHAR(255) PRIMARY KEY,
+    config JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,28 @@
+-- Time series of high-frequency sensor readings linked to items. Raw readings
+-- are kept as one JSONB block per series and hour; rollups hold per-minute,
+-- per-hour and per-day aggregates for range queries over long periods.
+
+CREATE TABLE IF NOT EXISTS sensor_series (
+    dfid VARCHAR(255) NOT NULL,
+    series VARCHAR(64) NOT NULL,
+    data JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (dfid, series)
+);
+
+CREATE TABLE IF NOT EXISTS sensor_chunks (
+    dfid VARCHAR(255) NOT NULL,
+    series VARCHAR(64) NOT NULL,
+    chunk_start TIMESTAMPTZ NOT NULL,
+    readings JSONB NOT NULL,
+    PRIMARY KEY (dfid, series, chunk_start)
+);
+
+CREATE TABLE IF NOT EXISTS sensor_rollups (
+    dfid VARCHAR(255) NOT NULL,
+    series VARCHAR(64) NOT NULL,
+    resolution VARCHAR(16) NOT NULL,
+    bucket_start TIMESTAMPTZ NOT NULL,
+    rollup JSONB NOT NULL,
+    PRIMARY KEY (dfid, series, resolution, bucket_start)
+);
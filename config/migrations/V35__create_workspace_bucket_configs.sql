@@ -0,0 +1,8 @@
+-- Enterprise workspaces' own S3-compatible buckets for attachments and exports.
+-- Only object references and hashes are kept in the engine's database.
+
+CREATE TABLE IF NOT EXISTS workspace_bucket_configs (
+    workspace_id VARCHAR(255) PRIMARY KEY,
+    config JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,8 @@
+-- Daily DFID sequence shared by every generator. Each one leases a range of
+-- numbers at a time, so replicas and regions never issue the same DFID.
+
+CREATE TABLE IF NOT EXISTS dfid_sequence_leases (
+    day CHAR(8) PRIMARY KEY,
+    next_sequence BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
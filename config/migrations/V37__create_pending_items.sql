@@ -0,0 +1,12 @@
+-- Entries verification, ingestion or lifecycle checks could not settle on
+-- their own, kept until a reviewer resolves them in the review queue.
+
+CREATE TABLE IF NOT EXISTS pending_items (
+    pending_id UUID PRIMARY KEY,
+    workspace_id VARCHAR(255),
+    item JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_pending_items_workspace ON pending_items(workspace_id);
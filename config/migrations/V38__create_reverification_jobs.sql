@@ -0,0 +1,19 @@
+-- Re-verification jobs queued by identifier mapping corrections and the
+-- merge proposals they produce for review.
+
+CREATE TABLE IF NOT EXISTS reverification_jobs (
+    job_id UUID PRIMARY KEY,
+    job JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS merge_proposals (
+    proposal_id UUID PRIMARY KEY,
+    job_id UUID NOT NULL,
+    status VARCHAR(32) NOT NULL,
+    proposal JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_merge_proposals_job ON merge_proposals(job_id);
+CREATE INDEX IF NOT EXISTS idx_merge_proposals_status ON merge_proposals(status);
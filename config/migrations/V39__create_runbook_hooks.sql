@@ -0,0 +1,11 @@
+-- Runbook automation hooks: remediation actions fired when an operational
+-- incident is detected. Each automated action is recorded in the audit log.
+
+CREATE TABLE IF NOT EXISTS runbook_hooks (
+    hook_id UUID PRIMARY KEY,
+    trigger VARCHAR(32) NOT NULL,
+    hook JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_runbook_hooks_trigger ON runbook_hooks(trigger);
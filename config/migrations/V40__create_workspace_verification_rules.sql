@@ -0,0 +1,8 @@
+-- Declarative rules verification applies to each workspace's submissions:
+-- required identifiers and payload fields, and sources held for review.
+
+CREATE TABLE IF NOT EXISTS workspace_verification_rules (
+    workspace_id VARCHAR(255) PRIMARY KEY,
+    rules JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,14 @@
+-- Agreements between two consenting circuits to share a dedup namespace, so
+-- the same identifier set pushed to either resolves to one DFID.
+
+CREATE TABLE IF NOT EXISTS dedup_sharing_agreements (
+    agreement_id UUID PRIMARY KEY,
+    circuit_a UUID NOT NULL,
+    circuit_b UUID NOT NULL,
+    status VARCHAR(32) NOT NULL,
+    agreement JSONB NOT NULL,
+    proposed_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_dedup_sharing_circuit_a ON dedup_sharing_agreements(circuit_a);
+CREATE INDEX IF NOT EXISTS idx_dedup_sharing_circuit_b ON dedup_sharing_agreements(circuit_b);
@@ -0,0 +1,11 @@
+-- Reports of the reconciler comparing circuit items with PushedToCircuit and
+-- removal events, listing the discrepancies found and which were repaired.
+
+CREATE TABLE IF NOT EXISTS membership_reconciliation_reports (
+    run_id UUID PRIMARY KEY,
+    policy VARCHAR(32) NOT NULL,
+    report JSONB NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_membership_reconciliation_started ON membership_reconciliation_reports(started_at);
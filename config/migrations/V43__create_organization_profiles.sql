@@ -0,0 +1,9 @@
+-- Directory profiles of the organizations taking part in circuits, keyed by
+-- the user that owns them.
+
+CREATE TABLE IF NOT EXISTS organization_profiles (
+    user_id VARCHAR(255) PRIMARY KEY,
+    workspace_id VARCHAR(255),
+    profile JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,13 @@
+-- Signed member attestations. Revocation updates the stored attestation in
+-- place; the signature and content hash never change.
+
+CREATE TABLE IF NOT EXISTS attestations (
+    attestation_id UUID PRIMARY KEY,
+    attester_id VARCHAR(255) NOT NULL,
+    subject_id VARCHAR(255) NOT NULL,
+    attestation JSONB NOT NULL,
+    issued_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_attestations_attester ON attestations(attester_id);
+CREATE INDEX IF NOT EXISTS idx_attestations_subject ON attestations(subject_id);
@@ -0,0 +1,10 @@
+-- Named audit query expressions kept for recurring investigations.
+
+CREATE TABLE IF NOT EXISTS saved_audit_queries (
+    query_id UUID PRIMARY KEY,
+    owner_id VARCHAR(255) NOT NULL,
+    query JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_saved_audit_queries_owner ON saved_audit_queries(owner_id);
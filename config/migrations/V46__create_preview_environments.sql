@@ -0,0 +1,10 @@
+-- Staging namespaces holding configuration clones of production circuits.
+
+CREATE TABLE IF NOT EXISTS preview_environments (
+    preview_id UUID PRIMARY KEY,
+    owner_id VARCHAR(255) NOT NULL,
+    preview JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_preview_environments_owner ON preview_environments(owner_id);
@@ -0,0 +1,32 @@
+-- Legacy ERP connectors: their configuration, reusable mapping templates,
+-- run state between polls and the run/record errors shown on the dashboard.
+
+CREATE TABLE IF NOT EXISTS connector_configs (
+    connector_id UUID PRIMARY KEY,
+    owner_id VARCHAR(255) NOT NULL,
+    config JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS mapping_templates (
+    template_id UUID PRIMARY KEY,
+    owner_id VARCHAR(255) NOT NULL,
+    template JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_mapping_templates_owner ON mapping_templates(owner_id);
+
+CREATE TABLE IF NOT EXISTS connector_states (
+    connector_id UUID PRIMARY KEY,
+    state JSONB NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS connector_errors (
+    error_id UUID PRIMARY KEY,
+    connector_id UUID NOT NULL,
+    error JSONB NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_connector_errors_connector ON connector_errors(connector_id, occurred_at DESC);
@@ -0,0 +1,25 @@
+-- Per-circuit change feed. change_feed_sequences hands out the gap-free
+-- sequence of the next record; the row lock taken by the increment
+-- serializes concurrent appends to the same circuit.
+
+CREATE TABLE IF NOT EXISTS change_feed_sequences (
+    circuit_id UUID PRIMARY KEY,
+    last_sequence BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS change_records (
+    circuit_id UUID NOT NULL,
+    sequence BIGINT NOT NULL,
+    record JSONB NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (circuit_id, sequence)
+);
+
+CREATE TABLE IF NOT EXISTS change_feed_subscriptions (
+    subscription_id UUID PRIMARY KEY,
+    circuit_id UUID NOT NULL,
+    subscription JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_change_feed_subscriptions_circuit ON change_feed_subscriptions(circuit_id);
@@ -0,0 +1,7 @@
+-- Admin announcements and their per-recipient notification deliveries.
+
+CREATE TABLE IF NOT EXISTS announcements (
+    announcement_id UUID PRIMARY KEY,
+    announcement JSONB NOT NULL,
+    publish_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,7 @@
+-- Latest engagement snapshot per workspace, replaced on every recompute.
+
+CREATE TABLE IF NOT EXISTS workspace_engagement (
+    workspace_id VARCHAR(255) PRIMARY KEY,
+    engagement JSONB NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,10 @@
+-- Asynchronous workspace data export jobs and their progress.
+
+CREATE TABLE IF NOT EXISTS data_export_jobs (
+    job_id UUID PRIMARY KEY,
+    requested_by VARCHAR(255) NOT NULL,
+    job JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_data_export_jobs_requested_by ON data_export_jobs(requested_by);
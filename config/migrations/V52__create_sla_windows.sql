@@ -0,0 +1,10 @@
+-- Hourly availability counters per service component.
+
+CREATE TABLE IF NOT EXISTS sla_windows (
+    component VARCHAR(32) NOT NULL,
+    hour_start TIMESTAMPTZ NOT NULL,
+    sla_window JSONB NOT NULL,
+    PRIMARY KEY (component, hour_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sla_windows_hour_start ON sla_windows(hour_start);
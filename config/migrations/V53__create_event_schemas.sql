@@ -0,0 +1,10 @@
+-- Versioned JSON schemas for event metadata, per circuit and event type.
+
+CREATE TABLE IF NOT EXISTS event_schemas (
+    schema_id UUID PRIMARY KEY,
+    circuit_id UUID NOT NULL,
+    version INTEGER NOT NULL,
+    schema JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_schemas_circuit ON event_schemas(circuit_id, version);
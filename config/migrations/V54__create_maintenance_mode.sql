@@ -0,0 +1,7 @@
+-- The platform-wide maintenance window. A single row, replaced on update.
+
+CREATE TABLE IF NOT EXISTS maintenance_mode (
+    singleton BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (singleton),
+    mode JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
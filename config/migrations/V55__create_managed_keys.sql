@@ -0,0 +1,9 @@
+-- Platform keys generated in a key ceremony. Only public material is stored;
+-- the secret exists as Shamir shares held by the custodians.
+
+CREATE TABLE IF NOT EXISTS managed_keys (
+    key_id UUID PRIMARY KEY,
+    fingerprint VARCHAR(64) NOT NULL,
+    managed_key JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,8 @@
+-- Circuit-registered event type names, accepted as `EventType::Custom`.
+
+CREATE TABLE IF NOT EXISTS custom_event_types (
+    circuit_id UUID NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    event_type JSONB NOT NULL,
+    PRIMARY KEY (circuit_id, name)
+);
@@ -0,0 +1,18 @@
+-- Notarized document hashes and the Merkle batches that anchor them.
+
+CREATE TABLE IF NOT EXISTS document_notarizations (
+    notarization_id UUID PRIMARY KEY,
+    document_hash VARCHAR(128) NOT NULL,
+    batch_id UUID,
+    notarization JSONB NOT NULL,
+    submitted_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_notarizations_hash ON document_notarizations(document_hash);
+
+CREATE TABLE IF NOT EXISTS notarization_batches (
+    batch_id UUID PRIMARY KEY,
+    merkle_root VARCHAR(128) NOT NULL,
+    batch JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
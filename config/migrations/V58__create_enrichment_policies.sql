@@ -0,0 +1,7 @@
+-- Per-workspace merge policies applied when enrichment overwrites fields.
+
+CREATE TABLE IF NOT EXISTS enrichment_policies (
+    workspace_id VARCHAR(255) PRIMARY KEY,
+    policy JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,9 @@
+-- Public keys registered to verify signed events. Revocation is recorded on
+-- the key; revoked keys are kept so older signatures can still be checked.
+
+CREATE TABLE IF NOT EXISTS event_signing_keys (
+    key_id UUID PRIMARY KEY,
+    public_key VARCHAR(128) NOT NULL,
+    signing_key JSONB NOT NULL,
+    registered_at TIMESTAMPTZ NOT NULL
+);
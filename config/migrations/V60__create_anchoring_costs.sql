@@ -0,0 +1,23 @@
+-- Anchoring SLAs per circuit, the latest fee sample per network and the
+-- spend of every anchoring transaction.
+
+CREATE TABLE IF NOT EXISTS anchoring_slas (
+    circuit_id UUID PRIMARY KEY,
+    sla JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS anchoring_fee_samples (
+    network VARCHAR(32) PRIMARY KEY,
+    sample JSONB NOT NULL,
+    sampled_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS anchoring_cost_records (
+    record_id UUID PRIMARY KEY,
+    circuit_id UUID,
+    record JSONB NOT NULL,
+    anchored_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_anchoring_cost_records_anchored_at ON anchoring_cost_records(anchored_at);
@@ -0,0 +1,15 @@
+-- Workspace lifecycle stage definitions and the stage history of each item.
+
+CREATE TABLE IF NOT EXISTS lifecycle_definitions (
+    workspace_id VARCHAR(255) PRIMARY KEY,
+    definition JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS item_lifecycles (
+    dfid VARCHAR(255) PRIMARY KEY,
+    workspace_id VARCHAR(255) NOT NULL,
+    lifecycle JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_item_lifecycles_workspace ON item_lifecycles(workspace_id);
@@ -0,0 +1,16 @@
+-- Circuit access review reports and the schedules that generate them.
+
+CREATE TABLE IF NOT EXISTS circuit_access_reports (
+    report_id UUID PRIMARY KEY,
+    circuit_id UUID NOT NULL,
+    report JSONB NOT NULL,
+    generated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_circuit_access_reports_circuit ON circuit_access_reports(circuit_id);
+
+CREATE TABLE IF NOT EXISTS access_review_schedules (
+    circuit_id UUID PRIMARY KEY,
+    schedule JSONB NOT NULL,
+    next_run_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,28 @@
+-- Circuit-scoped tokens issued to external partners. Only the BLAKE3 hash of
+-- a token is stored; usage keeps an audit trail of every authenticated call.
+
+CREATE TABLE IF NOT EXISTS partner_tokens (
+    token_id UUID PRIMARY KEY,
+    circuit_id UUID NOT NULL,
+    partner_name VARCHAR(255) NOT NULL,
+    token_hash VARCHAR(128) NOT NULL UNIQUE,
+    token_prefix VARCHAR(32) NOT NULL,
+    created_by VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ,
+    revoked_by VARCHAR(255),
+    last_used_at TIMESTAMPTZ,
+    use_count BIGINT NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_partner_tokens_circuit ON partner_tokens(circuit_id);
+
+CREATE TABLE IF NOT EXISTS partner_token_usage (
+    usage_id UUID PRIMARY KEY,
+    token_id UUID NOT NULL,
+    usage JSONB NOT NULL,
+    used_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_partner_token_usage_token ON partner_token_usage(token_id, used_at DESC);
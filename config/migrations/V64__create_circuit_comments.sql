@@ -0,0 +1,11 @@
+-- Comment threads on circuit events and pending operations.
+
+CREATE TABLE IF NOT EXISTS circuit_comments (
+    comment_id UUID PRIMARY KEY,
+    circuit_id UUID NOT NULL,
+    target JSONB NOT NULL,
+    comment JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_circuit_comments_circuit ON circuit_comments(circuit_id, created_at);
@@ -0,0 +1,10 @@
+-- Periodic data quality reports per circuit.
+
+CREATE TABLE IF NOT EXISTS data_quality_reports (
+    report_id UUID PRIMARY KEY,
+    circuit_id UUID NOT NULL,
+    report JSONB NOT NULL,
+    generated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_data_quality_reports_circuit ON data_quality_reports(circuit_id, generated_at DESC);
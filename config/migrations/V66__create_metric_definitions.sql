@@ -0,0 +1,10 @@
+-- Custom dashboard metrics defined per workspace.
+
+CREATE TABLE IF NOT EXISTS metric_definitions (
+    metric_id UUID PRIMARY KEY,
+    workspace_id VARCHAR(255) NOT NULL,
+    definition JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_metric_definitions_workspace ON metric_definitions(workspace_id);
@@ -0,0 +1,4 @@
+-- Item a ZK proof is about, recorded only when the prover could read it on
+-- submission; share links open that item's timeline.
+
+ALTER TABLE zk_proofs ADD COLUMN IF NOT EXISTS item_dfid VARCHAR(255);
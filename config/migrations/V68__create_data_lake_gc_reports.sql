@@ -0,0 +1,9 @@
+-- Reports of data lake garbage collection runs.
+
+CREATE TABLE IF NOT EXISTS data_lake_gc_reports (
+    run_id UUID PRIMARY KEY,
+    report JSONB NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_data_lake_gc_reports_started ON data_lake_gc_reports(started_at DESC);
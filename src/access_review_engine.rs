@@ -0,0 +1,553 @@
+//! Circuit access reviews.
+//!
+//! A report lists every principal with effective access to a circuit and how it
+//! was granted: the owner, members through their role, API keys acting for
+//! members, recipients of items shared out of the circuit, partners holding a
+//! circuit-scoped token, and the public when the circuit publishes items. Reports are generated on demand or on a
+//! per-circuit schedule, and kept so reviewers can compare them over time.
+//!
+//! An API key reaches only what its member can, narrowed by the key's scopes:
+//! without `admin` it cannot manage the circuit, and a read-only key can only
+//! pull and audit.
+
+use crate::api_key_engine::ApiKey;
+use crate::api_key_storage::ApiKeyStorage;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    AccessGrant, AccessPrincipalKind, AccessReviewSchedule, Circuit, CircuitAccessReport,
+    MemberRole, Notification, NotificationType, Permission,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const MAX_INTERVAL_DAYS: u32 = 366;
+/// Reports kept per circuit; older ones are dropped as new ones are stored
+pub const MAX_REPORTS_PER_CIRCUIT: usize = 100;
+const PUBLIC_PRINCIPAL: &str = "public";
+
+#[derive(Debug)]
+pub enum AccessReviewError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+    NotFound(String),
+}
+
+impl From<StorageError> for AccessReviewError {
+    fn from(err: StorageError) -> Self {
+        AccessReviewError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for AccessReviewError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccessReviewError::StorageError(e) => write!(f, "Storage error: {e}"),
+            AccessReviewError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            AccessReviewError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            AccessReviewError::NotFound(e) => write!(f, "Not found: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AccessReviewError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccessReviewScheduleInput {
+    pub interval_days: u32,
+    /// First run; defaults to now
+    pub starts_at: Option<DateTime<Utc>>,
+}
+
+const MANAGEMENT_PERMISSIONS: [Permission; 5] = [
+    Permission::Invite,
+    Permission::ManageMembers,
+    Permission::ManagePermissions,
+    Permission::Delete,
+    Permission::ManageRoles,
+];
+
+fn permission_names(permissions: &[Permission]) -> Vec<String> {
+    permissions.iter().map(|p| format!("{p:?}")).collect()
+}
+
+/// What a key may do in a circuit where its member holds `member_permissions`
+fn api_key_permissions(key: &ApiKey, member_permissions: &[Permission]) -> Vec<Permission> {
+    member_permissions
+        .iter()
+        .filter(|permission| {
+            key.permissions.admin
+                || (key.permissions.write && !MANAGEMENT_PERMISSIONS.contains(permission))
+                || (key.permissions.read
+                    && matches!(permission, Permission::Pull | Permission::Audit))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Everyone with access to `circuit`, ordered by kind and principal
+pub fn collect_grants<S: StorageBackend + ?Sized>(
+    storage: &S,
+    circuit: &Circuit,
+    api_keys: &[ApiKey],
+    now: DateTime<Utc>,
+) -> Result<Vec<AccessGrant>, StorageError> {
+    let mut grants = Vec::new();
+
+    if circuit.get_member(&circuit.owner_id).is_none() {
+        grants.push(AccessGrant {
+            kind: AccessPrincipalKind::Owner,
+            principal_id: circuit.owner_id.clone(),
+            granted_by: None,
+            permissions: vec!["all".to_string()],
+            items: Vec::new(),
+            granted_via: "circuit owner".to_string(),
+            granted_at: Some(circuit.created_timestamp),
+            expires_at: None,
+        });
+    }
+    for member in &circuit.members {
+        let is_owner = member.member_id == circuit.owner_id || member.role == MemberRole::Owner;
+        let granted_via = match &member.custom_role_name {
+            Some(role) => format!("custom role {role}"),
+            None if is_owner => "circuit owner".to_string(),
+            None => format!("role {:?}", member.role),
+        };
+        grants.push(AccessGrant {
+            kind: if is_owner {
+                AccessPrincipalKind::Owner
+            } else {
+                AccessPrincipalKind::Member
+            },
+            principal_id: member.member_id.clone(),
+            granted_by: None,
+            permissions: permission_names(&member.permissions),
+            items: Vec::new(),
+            granted_via,
+            granted_at: Some(member.joined_timestamp),
+            expires_at: None,
+        });
+
+        for key in api_keys.iter().filter(|key| {
+            key.original_user_id == member.member_id
+                && key.is_active
+                && key.expires_at.is_none_or(|expires_at| expires_at > now)
+        }) {
+            let permissions = api_key_permissions(key, &member.permissions);
+            if permissions.is_empty() {
+                continue;
+            }
+            grants.push(AccessGrant {
+                kind: AccessPrincipalKind::ApiKey,
+                principal_id: key.id.to_string(),
+                granted_by: Some(member.member_id.clone()),
+                permissions: permission_names(&permissions),
+                items: Vec::new(),
+                granted_via: format!("API key {} ({}…)", key.name, key.key_prefix),
+                granted_at: Some(key.created_at),
+                expires_at: key.expires_at,
+            });
+        }
+    }
+
+    for circuit_item in storage.get_circuit_items(&circuit.circuit_id)? {
+        for share in storage.get_shares_for_item(&circuit_item.dfid)? {
+            grants.push(AccessGrant {
+                kind: AccessPrincipalKind::ItemShare,
+                principal_id: share.recipient_user_id,
+                granted_by: Some(share.shared_by.clone()),
+                permissions: share
+                    .permissions
+                    .unwrap_or_else(|| vec!["read".to_string()]),
+                items: vec![share.dfid],
+                granted_via: format!("item shared by {}", share.shared_by),
+                granted_at: Some(share.shared_at),
+                expires_at: None,
+            });
+        }
+    }
+
+    for token in storage
+        .list_partner_tokens(&circuit.circuit_id)?
+        .into_iter()
+        .filter(|token| token.is_active(now))
+    {
+        grants.push(AccessGrant {
+            kind: AccessPrincipalKind::PartnerToken,
+            principal_id: token.token_id.to_string(),
+            granted_by: Some(token.created_by.clone()),
+            permissions: vec!["read".to_string()],
+            items: Vec::new(),
+            granted_via: format!(
+                "partner token for {} ({}…)",
+                token.partner_name, token.token_prefix
+            ),
+            granted_at: Some(token.created_at),
+            expires_at: Some(token.expires_at),
+        });
+    }
+
+    if circuit.is_publicly_accessible() {
+        if let Some(settings) = &circuit.public_settings {
+            grants.push(AccessGrant {
+                kind: AccessPrincipalKind::Public,
+                principal_id: PUBLIC_PRINCIPAL.to_string(),
+                granted_by: None,
+                permissions: vec!["read".to_string()],
+                items: settings.published_items.clone(),
+                granted_via: format!("{:?} public access", settings.access_mode),
+                granted_at: settings.public_since,
+                expires_at: None,
+            });
+        }
+    }
+
+    grants.sort_by(|a, b| {
+        (a.kind, &a.principal_id, &a.items).cmp(&(b.kind, &b.principal_id, &b.items))
+    });
+    Ok(grants)
+}
+
+pub struct AccessReviewEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> AccessReviewEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Owners, auditors and member managers review access; so do platform admins
+    fn reviewable_circuit(
+        &self,
+        circuit_id: &Uuid,
+        user_id: &str,
+    ) -> Result<Circuit, AccessReviewError> {
+        let circuit = self
+            .storage
+            .get_circuit(circuit_id)?
+            .ok_or_else(|| AccessReviewError::NotFound(format!("Circuit {circuit_id}")))?;
+        let may_review = circuit.owner_id == user_id
+            || circuit.has_permission(user_id, &Permission::Audit)
+            || circuit.has_permission(user_id, &Permission::ManageMembers)
+            || self
+                .storage
+                .get_user_account(user_id)?
+                .is_some_and(|account| account.is_admin);
+        if !may_review {
+            return Err(AccessReviewError::PermissionDenied(
+                "Only the owner, auditors and member managers can review circuit access"
+                    .to_string(),
+            ));
+        }
+        Ok(circuit)
+    }
+
+    fn store_report(
+        &self,
+        circuit: &Circuit,
+        api_keys: &[ApiKey],
+        generated_by: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<CircuitAccessReport, AccessReviewError> {
+        let report = CircuitAccessReport {
+            report_id: Uuid::new_v4(),
+            circuit_id: circuit.circuit_id,
+            circuit_name: circuit.name.clone(),
+            generated_at: now,
+            generated_by: generated_by.map(str::to_string),
+            grants: collect_grants(&self.storage, circuit, api_keys, now)?,
+        };
+        self.storage.store_access_report(&report)?;
+
+        let mut reports = self.storage.list_access_reports(&circuit.circuit_id)?;
+        if reports.len() > MAX_REPORTS_PER_CIRCUIT {
+            reports.sort_by_key(|r| std::cmp::Reverse(r.generated_at));
+            for old in &reports[MAX_REPORTS_PER_CIRCUIT..] {
+                self.storage.delete_access_report(&old.report_id)?;
+            }
+        }
+        Ok(report)
+    }
+
+    pub fn generate_report(
+        &self,
+        circuit_id: &Uuid,
+        user_id: &str,
+        api_keys: &[ApiKey],
+        now: DateTime<Utc>,
+    ) -> Result<CircuitAccessReport, AccessReviewError> {
+        let circuit = self.reviewable_circuit(circuit_id, user_id)?;
+        self.store_report(&circuit, api_keys, Some(user_id), now)
+    }
+
+    pub fn get_report(
+        &self,
+        circuit_id: &Uuid,
+        report_id: &Uuid,
+        user_id: &str,
+    ) -> Result<CircuitAccessReport, AccessReviewError> {
+        self.reviewable_circuit(circuit_id, user_id)?;
+        self.storage
+            .get_access_report(report_id)?
+            .filter(|report| report.circuit_id == *circuit_id)
+            .ok_or_else(|| AccessReviewError::NotFound(format!("Access report {report_id}")))
+    }
+
+    /// Newest first
+    pub fn list_reports(
+        &self,
+        circuit_id: &Uuid,
+        user_id: &str,
+    ) -> Result<Vec<CircuitAccessReport>, AccessReviewError> {
+        self.reviewable_circuit(circuit_id, user_id)?;
+        let mut reports = self.storage.list_access_reports(circuit_id)?;
+        reports.sort_by_key(|r| std::cmp::Reverse(r.generated_at));
+        Ok(reports)
+    }
+
+    pub fn get_schedule(
+        &self,
+        circuit_id: &Uuid,
+        user_id: &str,
+    ) -> Result<Option<AccessReviewSchedule>, AccessReviewError> {
+        self.reviewable_circuit(circuit_id, user_id)?;
+        Ok(self.storage.get_access_review_schedule(circuit_id)?)
+    }
+
+    pub fn set_schedule(
+        &self,
+        circuit_id: &Uuid,
+        input: AccessReviewScheduleInput,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<AccessReviewSchedule, AccessReviewError> {
+        self.reviewable_circuit(circuit_id, user_id)?;
+        if input.interval_days == 0 || input.interval_days > MAX_INTERVAL_DAYS {
+            return Err(AccessReviewError::ValidationError(format!(
+                "interval_days must be between 1 and {MAX_INTERVAL_DAYS}"
+            )));
+        }
+        let last_report_id = self
+            .storage
+            .get_access_review_schedule(circuit_id)?
+            .and_then(|schedule| schedule.last_report_id);
+        let schedule = AccessReviewSchedule {
+            circuit_id: *circuit_id,
+            interval_days: input.interval_days,
+            next_run_at: input.starts_at.unwrap_or(now),
+            last_report_id,
+            updated_by: user_id.to_string(),
+            updated_at: now,
+        };
+        self.storage.store_access_review_schedule(&schedule)?;
+        Ok(schedule)
+    }
+
+    pub fn delete_schedule(
+        &self,
+        circuit_id: &Uuid,
+        user_id: &str,
+    ) -> Result<(), AccessReviewError> {
+        self.reviewable_circuit(circuit_id, user_id)?;
+        Ok(self.storage.delete_access_review_schedule(circuit_id)?)
+    }
+
+    /// Generate the reports whose schedule is due and tell each circuit owner.
+    /// Schedules of deleted circuits are dropped.
+    pub fn run_due(
+        &self,
+        api_keys: &[ApiKey],
+        now: DateTime<Utc>,
+    ) -> Result<Vec<CircuitAccessReport>, AccessReviewError> {
+        let mut reports = Vec::new();
+        for mut schedule in self.storage.list_access_review_schedules()? {
+            if schedule.next_run_at > now {
+                continue;
+            }
+            let Some(circuit) = self.storage.get_circuit(&schedule.circuit_id)? else {
+                self.storage
+                    .delete_access_review_schedule(&schedule.circuit_id)?;
+                continue;
+            };
+            let report = self.store_report(&circuit, api_keys, None, now)?;
+
+            let interval = Duration::days(i64::from(schedule.interval_days));
+            while schedule.next_run_at <= now {
+                schedule.next_run_at += interval;
+            }
+            schedule.last_report_id = Some(report.report_id);
+            self.storage.store_access_review_schedule(&schedule)?;
+
+            let notification = Notification::new(
+                circuit.owner_id.clone(),
+                NotificationType::AccessReviewReady,
+                "Access review ready".to_string(),
+                format!(
+                    "{} principals have access to {}",
+                    report.grants.len(),
+                    circuit.name
+                ),
+                serde_json::json!({
+                    "circuit_id": circuit.circuit_id,
+                    "report_id": report.report_id,
+                }),
+            );
+            if let Err(e) = self.storage.store_notification(&notification) {
+                tracing::warn!("Failed to notify access review of {}: {}", circuit.name, e);
+            }
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+
+    pub fn spawn_scheduler<K>(
+        storage: S,
+        api_key_storage: Arc<K>,
+        tick: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        S: Send + Sync + 'static,
+        K: ApiKeyStorage + 'static,
+    {
+        tokio::spawn(async move {
+            let engine = AccessReviewEngine::new(storage);
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                let api_keys = match api_key_storage.list_api_keys().await {
+                    Ok(keys) => keys,
+                    Err(e) => {
+                        tracing::warn!("⚠️  Skipping access reviews, API keys unavailable: {}", e);
+                        continue;
+                    }
+                };
+                match engine.run_due(&api_keys, Utc::now()) {
+                    Ok(reports) if !reports.is_empty() => {
+                        tracing::info!("🔐 Generated {} scheduled access reviews", reports.len())
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("⚠️  Failed to run access reviews: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_key_engine::{ApiKeyPermissions, OrganizationType};
+    use crate::storage::InMemoryStorage;
+    use crate::types::ItemShare;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    fn api_key(owner: &str, permissions: ApiKeyPermissions) -> ApiKey {
+        ApiKey {
+            id: Uuid::new_v4(),
+            name: "gateway".to_string(),
+            key_hash: "hash".to_string(),
+            key_prefix: "dfm_abc".to_string(),
+            created_by: Uuid::new_v4(),
+            original_user_id: owner.to_string(),
+            organization_type: OrganizationType::Producer,
+            organization_id: None,
+            permissions,
+            allowed_endpoints: Vec::new(),
+            is_active: true,
+            last_used_at: None,
+            usage_count: 0,
+            rate_limit_per_hour: 1000,
+            created_at: Utc::now(),
+            expires_at: None,
+            notes: None,
+            allowed_ips: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_report_lists_members_keys_and_shares() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let mut circuit = Circuit::new(
+            "Coffee".to_string(),
+            "Specialty lots".to_string(),
+            "owner".to_string(),
+        );
+        circuit.add_member("grower".to_string(), MemberRole::Member);
+        storage.store_circuit(&circuit).unwrap();
+        let circuit_item = crate::types::CircuitItem::new(
+            "DFID-LOT".to_string(),
+            circuit.circuit_id,
+            "grower".to_string(),
+            Vec::new(),
+        );
+        storage.store_circuit_item(&circuit_item).unwrap();
+        storage
+            .store_item_share(&ItemShare::new(
+                "DFID-LOT".to_string(),
+                "grower".to_string(),
+                "roaster".to_string(),
+                None,
+            ))
+            .unwrap();
+
+        let read_only = ApiKeyPermissions {
+            read: true,
+            write: false,
+            admin: false,
+            custom: HashMap::new(),
+        };
+        let keys = vec![
+            api_key("grower", read_only),
+            api_key("stranger", ApiKeyPermissions::admin()),
+        ];
+
+        let engine = AccessReviewEngine::new(Arc::clone(&storage));
+        assert!(matches!(
+            engine.generate_report(&circuit.circuit_id, "grower", &keys, Utc::now()),
+            Err(AccessReviewError::PermissionDenied(_))
+        ));
+        let report = engine
+            .generate_report(&circuit.circuit_id, "owner", &keys, Utc::now())
+            .unwrap();
+        let kinds: Vec<(AccessPrincipalKind, &str)> = report
+            .grants
+            .iter()
+            .map(|g| (g.kind, g.principal_id.as_str()))
+            .collect();
+        assert_eq!(kinds[0], (AccessPrincipalKind::Owner, "owner"));
+        assert_eq!(kinds[1], (AccessPrincipalKind::Member, "grower"));
+        assert_eq!(kinds[2].0, AccessPrincipalKind::ApiKey);
+        assert_eq!(kinds[3], (AccessPrincipalKind::ItemShare, "roaster"));
+        assert_eq!(kinds.len(), 4);
+        assert_eq!(report.grants[2].granted_by.as_deref(), Some("grower"));
+        assert!(report.grants[2].permissions.iter().all(|p| p == "Pull"));
+
+        // A due schedule produces a report and notifies the owner
+        engine
+            .set_schedule(
+                &circuit.circuit_id,
+                AccessReviewScheduleInput {
+                    interval_days: 90,
+                    starts_at: None,
+                },
+                "owner",
+                Utc::now(),
+            )
+            .unwrap();
+        let scheduled = engine.run_due(&keys, Utc::now()).unwrap();
+        assert_eq!(scheduled.len(), 1);
+        assert!(scheduled[0].generated_by.is_none());
+        assert!(engine.run_due(&keys, Utc::now()).unwrap().is_empty());
+        assert_eq!(
+            engine
+                .list_reports(&circuit.circuit_id, "owner")
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Write-ahead log of adapter (IPFS/Stellar) writes.
+//!
+//! Storing an item on an adapter and adding the storage record that points at
+//! the result are two steps, and a crash between them leaves the item written
+//! with no record of where, or not written at all. Each adapter write is
+//! preceded by a pending [`AdapterIntent`] and the intent is completed once the
+//! storage record is in place, so after a restart the intents that are still
+//! incomplete are exactly the writes to resume (see
+//! `CircuitsEngine::resume_adapter_intents`).
+
+use crate::adapters::base::StorageLocation;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{AdapterIntent, AdapterIntentStatus, AdapterOperation, AdapterType};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// Intents are given up on after this many attempts and left for an operator
+pub const MAX_INTENT_ATTEMPTS: u32 = 5;
+/// Pending intents updated more recently than this may still be in flight
+pub const IN_FLIGHT_GRACE_SECS: i64 = 600;
+
+/// Record that `adapter_type` is about to be written for `dfid`
+pub fn record_intent<S: StorageBackend + ?Sized>(
+    storage: &S,
+    dfid: &str,
+    circuit_id: Option<Uuid>,
+    adapter_type: AdapterType,
+    operation: AdapterOperation,
+    is_new_dfid: bool,
+    requested_by: &str,
+) -> Result<AdapterIntent, StorageError> {
+    let now = Utc::now();
+    let intent = AdapterIntent {
+        intent_id: Uuid::new_v4(),
+        dfid: dfid.to_string(),
+        circuit_id,
+        adapter_type,
+        operation,
+        is_new_dfid,
+        requested_by: requested_by.to_string(),
+        status: AdapterIntentStatus::Pending,
+        attempts: 1,
+        last_error: None,
+        storage_location: None,
+        created_at: now,
+        updated_at: now,
+        completed_at: None,
+    };
+    storage.store_adapter_intent(&intent)?;
+    Ok(intent)
+}
+
+/// Mark `intent` pending again before retrying its write
+pub fn begin_retry<S: StorageBackend + ?Sized>(
+    storage: &S,
+    intent: &mut AdapterIntent,
+) -> Result<(), StorageError> {
+    intent.status = AdapterIntentStatus::Pending;
+    intent.attempts += 1;
+    intent.updated_at = Utc::now();
+    storage.store_adapter_intent(intent)
+}
+
+/// `location` is None when the write turned out to have nothing to store
+pub fn complete_intent<S: StorageBackend + ?Sized>(
+    storage: &S,
+    intent: &mut AdapterIntent,
+    location: Option<&StorageLocation>,
+) -> Result<(), StorageError> {
+    let now = Utc::now();
+    intent.status = AdapterIntentStatus::Completed;
+    intent.storage_location = location.cloned();
+    intent.last_error = None;
+    intent.updated_at = now;
+    intent.completed_at = Some(now);
+    storage.store_adapter_intent(intent)
+}
+
+pub fn fail_intent<S: StorageBackend + ?Sized>(
+    storage: &S,
+    intent: &mut AdapterIntent,
+    error: &str,
+) -> Result<(), StorageError> {
+    intent.status = AdapterIntentStatus::Failed;
+    intent.last_error = Some(error.to_string());
+    intent.updated_at = Utc::now();
+    storage.store_adapter_intent(intent)
+}
+
+/// Incomplete intents that can be retried at `now`: failed ones, and pending
+/// ones idle for longer than `grace` (their process most likely died)
+pub fn resumable_intents<S: StorageBackend + ?Sized>(
+    storage: &S,
+    now: DateTime<Utc>,
+    grace: Duration,
+) -> Result<Vec<AdapterIntent>, StorageError> {
+    Ok(storage
+        .get_incomplete_adapter_intents()?
+        .into_iter()
+        .filter(|intent| intent.attempts < MAX_INTENT_ATTEMPTS)
+        .filter(|intent| {
+            intent.status == AdapterIntentStatus::Failed || now - intent.updated_at >= grace
+        })
+        .collect())
+}
+
+/// Location of a storage record `intent` already produced, when the process
+/// stopped after adding the record but before completing the intent
+pub fn recorded_location<S: StorageBackend + ?Sized>(
+    storage: &S,
+    intent: &AdapterIntent,
+) -> Result<Option<StorageLocation>, StorageError> {
+    let Some(history) = storage.get_storage_history(&intent.dfid)? else {
+        return Ok(None);
+    };
+    Ok(history
+        .storage_records
+        .into_iter()
+        .filter(|record| record.adapter_type == intent.adapter_type)
+        .filter(|record| record.stored_at.timestamp() >= intent.created_at.timestamp())
+        .map(|record| record.storage_location)
+        .next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+
+    #[test]
+    fn test_incomplete_intents_are_resumable_once_idle() {
+        let storage = InMemoryStorage::new();
+        let mut done = record_intent(
+            &storage,
+            "DFID-1",
+            None,
+            AdapterType::IpfsIpfs,
+            AdapterOperation::StorageMigration,
+            false,
+            "farmer",
+        )
+        .unwrap();
+        let mut failed = record_intent(
+            &storage,
+            "DFID-2",
+            None,
+            AdapterType::IpfsIpfs,
+            AdapterOperation::StorageMigration,
+            false,
+            "farmer",
+        )
+        .unwrap();
+        let pending = record_intent(
+            &storage,
+            "DFID-3",
+            None,
+            AdapterType::IpfsIpfs,
+            AdapterOperation::CircuitPush,
+            true,
+            "farmer",
+        )
+        .unwrap();
+
+        let location = StorageLocation::IPFS {
+            cid: "bafy".to_string(),
+            pinned: true,
+        };
+        complete_intent(&storage, &mut done, Some(&location)).unwrap();
+        fail_intent(&storage, &mut failed, "gateway timeout").unwrap();
+
+        let incomplete = storage.get_incomplete_adapter_intents().unwrap();
+        assert_eq!(incomplete.len(), 2);
+
+        // The pending write may still be in flight until the grace period passes
+        let grace = Duration::seconds(IN_FLIGHT_GRACE_SECS);
+        let now = Utc::now();
+        let ids: Vec<Uuid> = resumable_intents(&storage, now, grace)
+            .unwrap()
+            .iter()
+            .map(|i| i.intent_id)
+            .collect();
+        assert_eq!(ids, [failed.intent_id]);
+        let resumable = resumable_intents(&storage, now + grace, grace).unwrap();
+        assert_eq!(resumable.len(), 2);
+        assert!(resumable.iter().any(|i| i.intent_id == pending.intent_id));
+
+        // Retries stop after the attempt limit
+        for _ in 1..MAX_INTENT_ATTEMPTS {
+            begin_retry(&storage, &mut failed).unwrap();
+            fail_intent(&storage, &mut failed, "gateway timeout").unwrap();
+        }
+        assert!(resumable_intents(&storage, now, grace).unwrap().is_empty());
+    }
+}
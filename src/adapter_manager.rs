@@ -1,21 +1,127 @@
+use crate::adapters::metrics::{self, adapter_metrics};
+use crate::adapters::{
+    AdapterInstance, AdapterResult, IpfsIpfsAdapter, LocalStellarAdapter,
+    StellarMainnetIpfsAdapter, StellarTestnetIpfsAdapter, StorageAdapter,
+};
+use crate::key_ceremony_engine::check_stellar_signer;
 use crate::logging::LoggingEngine;
-use crate::storage::StorageBackend;
+use crate::scaling_signals;
+use crate::sla_engine;
+use crate::storage::{StorageBackend, StorageError};
 use crate::types::{
-    AdapterConfig, AdapterConnectionDetails, AdapterTestResult, AdapterType, AuthType,
-    ConnectionTestResult, ContractConfigs, ContractInfo, ContractTestResult, TestStatus,
+    AdapterConfig, AdapterConnectionDetails, AdapterDryRunReport, AdapterTestResult, AdapterType,
+    AuthType, CircuitAdapterConfig, CircuitDryRunReport, ConnectionTestResult, ContractConfigs,
+    ContractInfo, ContractTestResult, Event, Item, ReplicaWriteResult, ReplicationPolicy,
+    RetryPolicy, SlaComponent, StorageRecord, TestStatus, WorkQueue,
 };
 use chrono::Utc;
+use rand::Rng;
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use uuid::Uuid;
 
 pub struct AdapterManager<S: StorageBackend> {
-    storage: S,
+    storage: Arc<S>,
     logger: Arc<Mutex<LoggingEngine>>,
+    retry_policy: RetryPolicy,
+}
+
+/// Result of writing an item under a circuit's replication policy
+#[derive(Debug)]
+pub struct ReplicationOutcome {
+    pub policy: ReplicationPolicy,
+    /// Adapter whose result is used as the item's storage location
+    pub primary_adapter: AdapterType,
+    pub primary_result: AdapterResult<String>,
+    /// Results of every synchronous write, including the primary
+    pub replicas: Vec<ReplicaWriteResult>,
+    /// Mirrors still being written in the background (PrimaryAsyncMirror)
+    pub pending_mirrors: Vec<AdapterType>,
 }
 
 impl<S: StorageBackend> AdapterManager<S> {
     pub fn new(storage: S, logger: Arc<Mutex<LoggingEngine>>) -> Self {
-        Self { storage, logger }
+        Self {
+            storage: Arc::new(storage),
+            logger,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Override the default backoff used for adapter calls
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Retry policy for an adapter type. The attempt count comes from the active
+    /// adapter configuration when one exists, the backoff from the manager.
+    pub fn retry_policy_for(&self, adapter_type: &AdapterType) -> RetryPolicy {
+        let configured_attempts = self
+            .storage
+            .get_adapter_configs_by_type(adapter_type)
+            .ok()
+            .and_then(|configs| configs.into_iter().find(|c| c.is_active))
+            .map(|c| RetryPolicy::from_connection_details(&c.connection_details).max_attempts);
+
+        RetryPolicy {
+            max_attempts: configured_attempts.unwrap_or(self.retry_policy.max_attempts),
+            ..self.retry_policy.clone()
+        }
+    }
+
+    /// Create the adapter for `adapter_type` and store the item, retrying transient failures
+    pub async fn store_new_item_with_retry(
+        &self,
+        adapter_type: &AdapterType,
+        item: &Item,
+        is_new_dfid: bool,
+        creator: &str,
+    ) -> Result<AdapterResult<String>, AdapterManagerError> {
+        let instance = self.create_adapter_instance(adapter_type)?;
+        let policy = self.retry_policy_for(adapter_type);
+        store_new_item_with_policy(&instance, &policy, item, is_new_dfid, creator)
+            .await
+            .map_err(|e| {
+                AdapterManagerError::StorageError(format!(
+                    "Failed to upload to {adapter_type:?}: {e}"
+                ))
+            })
+    }
+
+    /// Store the item on `instance`, retrying under the write retry policy
+    pub async fn store_item_with_retry(
+        &self,
+        instance: &AdapterInstance,
+        item: &Item,
+    ) -> Result<AdapterResult<String>, StorageError> {
+        let adapter_type = instance.adapter_type();
+        write_with_retry(
+            &self.retry_policy_for(&adapter_type),
+            &format!("{adapter_type:?}::store_item"),
+            || instance.store_item(item),
+            || instance.find_stored_item(item),
+        )
+        .await
+    }
+
+    /// Store the event on `instance`, retrying under the write retry policy
+    pub async fn store_event_with_retry(
+        &self,
+        instance: &AdapterInstance,
+        event: &Event,
+        item_id: &str,
+    ) -> Result<AdapterResult<String>, StorageError> {
+        let adapter_type = instance.adapter_type();
+        write_with_retry(
+            &self.retry_policy_for(&adapter_type),
+            &format!("{adapter_type:?}::store_event"),
+            || instance.store_event(event, item_id),
+            || instance.find_stored_event(event, item_id),
+        )
+        .await
     }
 
     /// Create a new adapter configuration
@@ -273,6 +379,7 @@ impl<S: StorageBackend> AdapterManager<S> {
             contract_tests,
             error_message: None,
             latency_ms: Some(latency_ms),
+            metrics: Some(adapter_metrics(&config.adapter_type)),
         };
 
         // Store test result
@@ -295,6 +402,502 @@ impl<S: StorageBackend> AdapterManager<S> {
         Ok(result)
     }
 
+    /// Build an adapter instance using the active configuration for its type
+    pub fn create_adapter_instance(
+        &self,
+        adapter_type: &AdapterType,
+    ) -> Result<AdapterInstance, AdapterManagerError> {
+        let config = self
+            .storage
+            .get_adapter_configs_by_type(adapter_type)
+            .map_err(|e| AdapterManagerError::StorageError(e.to_string()))?
+            .into_iter()
+            .find(|c| c.is_active);
+
+        self.build_adapter_instance(adapter_type, config.as_ref())
+    }
+
+    /// Build an adapter instance from a specific configuration, active or not
+    pub fn create_adapter_instance_for_config(
+        &self,
+        config: &AdapterConfig,
+    ) -> Result<AdapterInstance, AdapterManagerError> {
+        self.build_adapter_instance(&config.adapter_type, Some(config))
+    }
+
+    /// Stellar adapters are refused while their keypair is not the active
+    /// managed key for the network
+    fn build_adapter_instance(
+        &self,
+        adapter_type: &AdapterType,
+        config: Option<&AdapterConfig>,
+    ) -> Result<AdapterInstance, AdapterManagerError> {
+        let instance = match adapter_type {
+            AdapterType::IpfsIpfs => {
+                IpfsIpfsAdapter::new_with_config(config).map(AdapterInstance::IpfsIpfs)
+            }
+            AdapterType::StellarTestnetIpfs => StellarTestnetIpfsAdapter::new_with_config(config)
+                .map(AdapterInstance::StellarTestnetIpfs),
+            AdapterType::StellarMainnetIpfs => StellarMainnetIpfsAdapter::new_with_config(config)
+                .map(AdapterInstance::StellarMainnetIpfs),
+            AdapterType::StellarTestnetLocal | AdapterType::StellarMainnetLocal => {
+                LocalStellarAdapter::new_with_config(adapter_type.clone(), config)
+                    .map(AdapterInstance::LocalStellar)
+            }
+            _ => {
+                return Err(AdapterManagerError::ValidationError(format!(
+                    "Unsupported adapter type: {adapter_type:?}"
+                )))
+            }
+        };
+
+        let instance = instance.map_err(|e| {
+            AdapterManagerError::StorageError(format!(
+                "Failed to create {adapter_type:?} adapter: {e}"
+            ))
+        })?;
+        if let Some(client) = instance.stellar_client() {
+            check_stellar_signer(self.storage.as_ref(), client)
+                .map_err(|e| AdapterManagerError::ValidationError(e.to_string()))?;
+        }
+        Ok(instance)
+    }
+
+    /// Make a configuration the active one for its adapter type and build its instance.
+    /// Other active configurations of the same type are deactivated so pushes pick this one.
+    pub fn activate_adapter_config(
+        &mut self,
+        config_id: &Uuid,
+    ) -> Result<(AdapterConfig, AdapterInstance), AdapterManagerError> {
+        let mut config = self.get_adapter_config(config_id)?;
+
+        // Build first so a broken configuration never becomes active
+        let instance = self.create_adapter_instance_for_config(&config)?;
+
+        let siblings = self
+            .storage
+            .get_adapter_configs_by_type(&config.adapter_type)
+            .map_err(|e| AdapterManagerError::StorageError(e.to_string()))?;
+        for mut sibling in siblings
+            .into_iter()
+            .filter(|c| c.is_active && c.config_id != *config_id)
+        {
+            sibling.is_active = false;
+            sibling.updated_at = Utc::now();
+            self.storage
+                .update_adapter_config(&sibling)
+                .map_err(|e| AdapterManagerError::StorageError(e.to_string()))?;
+        }
+
+        config.is_active = true;
+        config.updated_at = Utc::now();
+        self.storage
+            .update_adapter_config(&config)
+            .map_err(|e| AdapterManagerError::StorageError(e.to_string()))?;
+
+        self.logger
+            .lock()
+            .unwrap()
+            .info(
+                "adapter_manager",
+                "adapter_activated",
+                "Adapter configuration activated",
+            )
+            .with_context("config_id", config_id.to_string())
+            .with_context("type", format!("{:?}", config.adapter_type));
+
+        Ok((config, instance))
+    }
+
+    /// Validate a candidate circuit adapter configuration against a sample item without
+    /// writing anything: checks the replication policy, each adapter's stored configuration
+    /// and connectivity, and reports the writes and estimated fees a real push would incur.
+    pub async fn dry_run_circuit_config(
+        &self,
+        adapter_config: &CircuitAdapterConfig,
+        item: &Item,
+        is_new_dfid: bool,
+    ) -> Result<CircuitDryRunReport, AdapterManagerError> {
+        let primary = match &adapter_config.adapter_type {
+            None | Some(AdapterType::None) => {
+                return Err(AdapterManagerError::ValidationError(
+                    "Circuit has no storage adapter configured (adapter type: None)".to_string(),
+                ))
+            }
+            Some(adapter_type) => adapter_type.clone(),
+        };
+
+        let mut targets = vec![primary.clone()];
+        if adapter_config.replication_policy != ReplicationPolicy::Single {
+            for adapter in &adapter_config.mirror_adapters {
+                if *adapter != primary && !targets.contains(adapter) {
+                    targets.push(adapter.clone());
+                }
+            }
+        }
+
+        let mut errors = Vec::new();
+        match adapter_config.replication_policy {
+            ReplicationPolicy::Single => {}
+            ReplicationPolicy::Quorum { min_successes }
+                if min_successes == 0 || min_successes > targets.len() =>
+            {
+                errors.push(format!(
+                    "Quorum of {min_successes} cannot be met with {} adapters",
+                    targets.len()
+                ));
+            }
+            _ if targets.len() < 2 => {
+                errors.push("Replication requires at least one mirror adapter".to_string());
+            }
+            _ => {}
+        }
+
+        let reports = targets
+            .iter()
+            .map(|adapter_type| self.dry_run_adapter(adapter_type, item, is_new_dfid));
+        let adapters = futures::future::join_all(reports).await;
+
+        // Only reported when every blockchain adapter could price its writes
+        let priced: Vec<_> = adapters
+            .iter()
+            .filter(|r| r.adapter_type.requires_blockchain())
+            .map(|r| r.estimated_fee_stroops)
+            .collect();
+        let estimated_fee_stroops = if priced.is_empty() {
+            None
+        } else {
+            priced.into_iter().sum::<Option<u64>>()
+        };
+        let ready = errors.is_empty() && adapters.iter().all(|r| r.is_ready());
+
+        self.logger
+            .lock()
+            .unwrap()
+            .info(
+                "adapter_manager",
+                "circuit_config_dry_run",
+                "Circuit adapter configuration dry run completed",
+            )
+            .with_context("circuit_id", adapter_config.circuit_id.to_string())
+            .with_context("adapters", targets.len().to_string())
+            .with_context("ready", ready.to_string());
+
+        Ok(CircuitDryRunReport {
+            circuit_id: adapter_config.circuit_id,
+            dfid: item.dfid.clone(),
+            replication_policy: adapter_config.replication_policy.clone(),
+            adapters,
+            estimated_fee_stroops,
+            errors,
+            ready,
+            generated_at: Utc::now(),
+        })
+    }
+
+    /// Dry-run a single adapter type using its active configuration, folding build and
+    /// validation failures into the report instead of returning them
+    async fn dry_run_adapter(
+        &self,
+        adapter_type: &AdapterType,
+        item: &Item,
+        is_new_dfid: bool,
+    ) -> AdapterDryRunReport {
+        let config = match self.storage.get_adapter_configs_by_type(adapter_type) {
+            Ok(configs) => configs.into_iter().find(|c| c.is_active),
+            Err(e) => {
+                let mut report = AdapterDryRunReport::new(adapter_type.clone());
+                report.config_error(format!("Failed to load adapter configuration: {e}"));
+                return report;
+            }
+        };
+
+        let mut config_errors = Vec::new();
+        if let Some(config) = &config {
+            if let Err(e) = self.validate_connection_details(&config.connection_details) {
+                config_errors.push(e.to_string());
+            }
+            if let Some(contracts) = &config.contract_configs {
+                if let Err(e) = self.validate_contract_configs(contracts) {
+                    config_errors.push(e.to_string());
+                }
+            }
+        }
+
+        let mut report = match self.build_adapter_instance(adapter_type, config.as_ref()) {
+            Ok(instance) => instance
+                .dry_run_store_item(item, is_new_dfid)
+                .await
+                .unwrap_or_else(|e| {
+                    let mut report = AdapterDryRunReport::new(adapter_type.clone());
+                    report.errors.push(e.to_string());
+                    report
+                }),
+            Err(e) => {
+                let mut report = AdapterDryRunReport::new(adapter_type.clone());
+                report.config_error(e.to_string());
+                report
+            }
+        };
+
+        report.config_id = config.as_ref().map(|c| c.config_id);
+        if config.is_none() {
+            report
+                .warnings
+                .push("No active configuration; environment defaults would be used".to_string());
+        }
+        for error in config_errors {
+            report.config_error(error);
+        }
+        report
+    }
+
+    /// Write a newly pushed item to the circuit's adapters according to its replication policy.
+    /// Background mirror writes record their own StorageRecord against `dfid` when they finish.
+    pub async fn replicate_new_item(
+        &self,
+        dfid: &str,
+        adapter_config: &CircuitAdapterConfig,
+        item: &Item,
+        is_new_dfid: bool,
+        creator: &str,
+    ) -> Result<ReplicationOutcome, AdapterManagerError>
+    where
+        S: 'static,
+    {
+        let primary = match &adapter_config.adapter_type {
+            None | Some(AdapterType::None) => {
+                return Err(AdapterManagerError::ValidationError(
+                    "Circuit has no storage adapter configured (adapter type: None)".to_string(),
+                ))
+            }
+            Some(adapter_type) => adapter_type.clone(),
+        };
+
+        let mirrors: Vec<AdapterType> =
+            if adapter_config.replication_policy == ReplicationPolicy::Single {
+                Vec::new()
+            } else {
+                let mut mirrors = Vec::new();
+                for adapter in &adapter_config.mirror_adapters {
+                    if *adapter != primary && !mirrors.contains(adapter) {
+                        mirrors.push(adapter.clone());
+                    }
+                }
+                mirrors
+            };
+
+        let policy = adapter_config.replication_policy.clone();
+        let (sync_targets, async_targets) = match policy {
+            ReplicationPolicy::PrimaryAsyncMirror => (vec![primary.clone()], mirrors),
+            _ => {
+                let mut targets = vec![primary.clone()];
+                targets.extend(mirrors);
+                (targets, Vec::new())
+            }
+        };
+
+        let required_successes = match policy {
+            ReplicationPolicy::Quorum { min_successes } => {
+                if min_successes == 0 || min_successes > sync_targets.len() {
+                    return Err(AdapterManagerError::ValidationError(format!(
+                        "Quorum of {min_successes} cannot be met with {} adapters",
+                        sync_targets.len()
+                    )));
+                }
+                min_successes
+            }
+            _ => sync_targets.len(),
+        };
+
+        let writes = sync_targets.iter().map(|adapter_type| async move {
+            let start = std::time::Instant::now();
+            let result = self
+                .store_new_item_with_retry(adapter_type, item, is_new_dfid, creator)
+                .await
+                .map_err(|e| e.to_string());
+            (
+                adapter_type.clone(),
+                result,
+                start.elapsed().as_millis() as u64,
+            )
+        });
+        let results = futures::future::join_all(writes).await;
+
+        let mut replicas = Vec::with_capacity(results.len());
+        let mut successes: Vec<(AdapterType, AdapterResult<String>)> = Vec::new();
+        let mut errors = Vec::new();
+        for (adapter_type, result, latency_ms) in results {
+            let is_primary = adapter_type == primary;
+            match result {
+                Ok(adapter_result) => {
+                    replicas.push(ReplicaWriteResult {
+                        adapter_type: adapter_type.clone(),
+                        is_primary,
+                        success: true,
+                        storage_location: Some(adapter_result.metadata.item_location.clone()),
+                        error: None,
+                        latency_ms,
+                        completed_at: Utc::now(),
+                    });
+                    successes.push((adapter_type, adapter_result));
+                }
+                Err(error) => {
+                    errors.push(error.clone());
+                    replicas.push(ReplicaWriteResult {
+                        adapter_type,
+                        is_primary,
+                        success: false,
+                        storage_location: None,
+                        error: Some(error),
+                        latency_ms,
+                        completed_at: Utc::now(),
+                    });
+                }
+            }
+        }
+
+        self.logger
+            .lock()
+            .unwrap()
+            .info(
+                "adapter_manager",
+                "item_replicated",
+                "Item written under replication policy",
+            )
+            .with_context("dfid", dfid.to_string())
+            .with_context("policy", format!("{policy:?}"))
+            .with_context("successes", successes.len().to_string())
+            .with_context("failures", errors.len().to_string());
+
+        if successes.len() < required_successes {
+            return Err(AdapterManagerError::ReplicationFailed(format!(
+                "{} of {} adapter writes succeeded ({} required): {}",
+                successes.len(),
+                sync_targets.len(),
+                required_successes,
+                errors.join("; ")
+            )));
+        }
+
+        // Prefer the primary's location; under quorum fall back to the first successful mirror
+        let index = successes
+            .iter()
+            .position(|(adapter_type, _)| *adapter_type == primary)
+            .unwrap_or(0);
+        let (primary_adapter, primary_result) = successes.swap_remove(index);
+
+        for adapter_type in &async_targets {
+            self.spawn_mirror_write(
+                dfid,
+                adapter_type.clone(),
+                item.clone(),
+                creator,
+                adapter_config,
+            );
+        }
+
+        Ok(ReplicationOutcome {
+            policy,
+            primary_adapter,
+            primary_result,
+            replicas,
+            pending_mirrors: async_targets,
+        })
+    }
+
+    /// Write the item to a mirror in the background. Every outcome is counted
+    /// under the adapter's `mirror_write` metrics; failures are also logged,
+    /// successes added to the item's storage history.
+    fn spawn_mirror_write(
+        &self,
+        dfid: &str,
+        adapter_type: AdapterType,
+        item: Item,
+        creator: &str,
+        adapter_config: &CircuitAdapterConfig,
+    ) -> Option<tokio::task::JoinHandle<()>>
+    where
+        S: 'static,
+    {
+        let instance = match self.create_adapter_instance(&adapter_type) {
+            Ok(instance) => instance,
+            Err(e) => {
+                record_mirror_failure(
+                    &self.logger,
+                    &adapter_type,
+                    dfid,
+                    &e.to_string(),
+                    Duration::ZERO,
+                );
+                return None;
+            }
+        };
+        let logger = Arc::clone(&self.logger);
+
+        let policy = self.retry_policy_for(&adapter_type);
+        let storage = Arc::clone(&self.storage);
+        let dfid = dfid.to_string();
+        let creator = creator.to_string();
+        let circuit_id = adapter_config.circuit_id.to_string();
+
+        scaling_signals::record_enqueued(WorkQueue::AnchoringOutbox, 1);
+        Some(tokio::spawn(async move {
+            let start = std::time::Instant::now();
+            let result =
+                store_new_item_with_policy(&instance, &policy, &item, false, &creator).await;
+            scaling_signals::record_completed(
+                WorkQueue::AnchoringOutbox,
+                start.elapsed(),
+                result.is_ok(),
+            );
+            sla_engine::record_outcome(SlaComponent::Anchoring, result.is_ok());
+            let latency_ms = start.elapsed().as_millis() as u64;
+
+            let adapter_result = match result {
+                Ok(adapter_result) => adapter_result,
+                Err(e) => {
+                    record_mirror_failure(
+                        &logger,
+                        &adapter_type,
+                        &dfid,
+                        &e.to_string(),
+                        start.elapsed(),
+                    );
+                    return;
+                }
+            };
+            metrics::record_operation(&adapter_type, "mirror_write", start.elapsed(), None);
+
+            let location = adapter_result.metadata.item_location;
+            let replica = ReplicaWriteResult {
+                adapter_type: adapter_type.clone(),
+                is_primary: false,
+                success: true,
+                storage_location: Some(location.clone()),
+                error: None,
+                latency_ms,
+                completed_at: Utc::now(),
+            };
+
+            let mut metadata = HashMap::new();
+            metadata.insert("replica_results".to_string(), serde_json::json!([replica]));
+            let record = StorageRecord {
+                adapter_type,
+                storage_location: location,
+                stored_at: Utc::now(),
+                triggered_by: "replication_mirror".to_string(),
+                triggered_by_id: Some(circuit_id),
+                events_range: None,
+                is_active: true,
+                metadata,
+            };
+            if let Err(e) = storage.add_storage_record(&dfid, record) {
+                tracing::warn!("⚠️  Failed to record mirror storage for {}: {}", dfid, e);
+            }
+        }))
+    }
+
     // Private helper methods
 
     fn validate_connection_details(
@@ -439,6 +1042,206 @@ impl<S: StorageBackend> AdapterManager<S> {
     }
 }
 
+/// Whether a failed adapter call certainly never reached the service: the
+/// connection was refused, the host did not resolve, or the service turned the
+/// request away (429, 503) before acting on it. Only these are retried blindly.
+pub fn is_retryable(error: &StorageError) -> bool {
+    let msg = match error {
+        StorageError::ConnectionError(msg)
+        | StorageError::WriteError(msg)
+        | StorageError::ReadError(msg)
+        | StorageError::IoError(msg) => msg.to_lowercase(),
+        _ => return false,
+    };
+    [
+        "connection refused",
+        "dns error",
+        "failed to lookup address",
+        "name or service not known",
+        "no such host",
+        "too many requests",
+        "rate limit",
+        "429",
+        "service unavailable",
+        "503",
+    ]
+    .iter()
+    .any(|needle| msg.contains(needle))
+}
+
+/// Whether a failure leaves it unknown if the request was applied: it timed
+/// out, the connection dropped after sending, or the service failed midway
+fn outcome_unknown(error: &StorageError) -> bool {
+    match error {
+        StorageError::ConnectionError(_) | StorageError::IoError(_) => true,
+        StorageError::WriteError(msg) | StorageError::ReadError(msg) => {
+            let msg = msg.to_lowercase();
+            [
+                "timeout",
+                "timed out",
+                "connection",
+                "temporarily",
+                "500",
+                "502",
+                "504",
+            ]
+            .iter()
+            .any(|needle| msg.contains(needle))
+        }
+        _ => false,
+    }
+}
+
+/// Delay before retry number `retry` (1-based) with jitter applied
+pub fn retry_delay(policy: &RetryPolicy, retry: u32) -> Duration {
+    let base = policy.base_delay_ms(retry) as f64;
+    let jitter = policy.jitter.clamp(0.0, 1.0);
+    let factor = if jitter > 0.0 {
+        rand::thread_rng().gen_range((1.0 - jitter)..=(1.0 + jitter))
+    } else {
+        1.0
+    };
+    Duration::from_millis((base * factor) as u64)
+}
+
+/// Run an adapter call, retrying failures that never reached the service with
+/// exponential backoff. Writes go through `write_with_retry`.
+pub async fn call_with_retry<T, F, Fut>(
+    policy: &RetryPolicy,
+    operation: &str,
+    mut call: F,
+) -> Result<T, StorageError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, StorageError>>,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match call().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < max_attempts && is_retryable(&e) => {
+                let delay = retry_delay(policy, attempt);
+                tracing::warn!(
+                    "⚠️  {} failed (attempt {}/{}), retrying in {:?}: {}",
+                    operation,
+                    attempt,
+                    max_attempts,
+                    delay,
+                    e
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn record_mirror_failure(
+    logger: &Mutex<LoggingEngine>,
+    adapter_type: &AdapterType,
+    dfid: &str,
+    error: &str,
+    elapsed: Duration,
+) {
+    tracing::error!(
+        "❌ Mirror write to {:?} failed for {}: {}",
+        adapter_type,
+        dfid,
+        error
+    );
+    metrics::record_operation(adapter_type, "mirror_write", elapsed, Some(error));
+    logger
+        .lock()
+        .unwrap()
+        .error(
+            "adapter_manager",
+            "mirror_write_failed",
+            format!("Mirror write to {adapter_type:?} failed for {dfid}: {error}"),
+        )
+        .with_context("dfid", dfid.to_string())
+        .with_context("adapter_type", format!("{adapter_type:?}"));
+}
+
+/// Run an adapter write under the retry policy. Failures that never reached
+/// the service are retried; after an ambiguous one, `landed` checks whether
+/// the write was applied anyway and its result is used instead of writing
+/// again. When that check fails too, the original error is returned rather
+/// than risking a duplicate write.
+pub async fn write_with_retry<T, W, WF, L, LF>(
+    policy: &RetryPolicy,
+    operation: &str,
+    mut write: W,
+    mut landed: L,
+) -> Result<T, StorageError>
+where
+    W: FnMut() -> WF,
+    WF: Future<Output = Result<T, StorageError>>,
+    L: FnMut() -> LF,
+    LF: Future<Output = Result<Option<T>, StorageError>>,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        let e = match write().await {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+        if attempt >= max_attempts {
+            return Err(e);
+        }
+        if !is_retryable(&e) {
+            if !outcome_unknown(&e) {
+                return Err(e);
+            }
+            match landed().await {
+                Ok(Some(value)) => {
+                    tracing::info!("✅ {} reported an error but was applied: {}", operation, e);
+                    return Ok(value);
+                }
+                Ok(None) => {}
+                Err(check) => {
+                    tracing::warn!(
+                        "⚠️  {} failed and whether it was applied is unknown ({}), not retrying: {}",
+                        operation,
+                        check,
+                        e
+                    );
+                    return Err(e);
+                }
+            }
+        }
+        let delay = retry_delay(policy, attempt);
+        tracing::warn!(
+            "⚠️  {} failed (attempt {}/{}), retrying in {:?}: {}",
+            operation,
+            attempt,
+            max_attempts,
+            delay,
+            e
+        );
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+async fn store_new_item_with_policy(
+    instance: &AdapterInstance,
+    policy: &RetryPolicy,
+    item: &Item,
+    is_new_dfid: bool,
+    creator: &str,
+) -> Result<AdapterResult<String>, StorageError> {
+    write_with_retry(
+        policy,
+        &format!("{:?}::store_new_item", instance.adapter_type()),
+        || instance.store_new_item(item, is_new_dfid, creator),
+        || instance.find_stored_item(item),
+    )
+    .await
+}
+
 #[derive(Debug)]
 pub enum AdapterManagerError {
     StorageError(String),
@@ -447,6 +1250,7 @@ pub enum AdapterManagerError {
     ValidationError(String),
     CannotDeleteDefault,
     TestFailed(String),
+    ReplicationFailed(String),
 }
 
 impl std::fmt::Display for AdapterManagerError {
@@ -462,8 +1266,305 @@ impl std::fmt::Display for AdapterManagerError {
                 write!(f, "Cannot delete the default adapter")
             }
             AdapterManagerError::TestFailed(msg) => write!(f, "Adapter test failed: {msg}"),
+            AdapterManagerError::ReplicationFailed(msg) => write!(f, "Replication failed: {msg}"),
         }
     }
 }
 
 impl std::error::Error for AdapterManagerError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 5,
+            backoff_multiplier: 2.0,
+            jitter: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_retryable_classification() {
+        assert!(is_retryable(&StorageError::ConnectionError(
+            "tcp connect error: Connection refused (os error 111)".to_string()
+        )));
+        assert!(is_retryable(&StorageError::WriteError(
+            "Horizon returned 503 Service Unavailable".to_string()
+        )));
+        // The request may have been applied before the connection dropped
+        assert!(!is_retryable(&StorageError::ConnectionError(
+            "reset by peer".to_string()
+        )));
+        assert!(!is_retryable(&StorageError::WriteError(
+            "request timed out".to_string()
+        )));
+        assert!(!is_retryable(&StorageError::WriteError(
+            "contract rejected invocation".to_string()
+        )));
+        assert!(!is_retryable(&StorageError::ConfigurationError(
+            "missing key".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.base_delay_ms(1), 250);
+        assert_eq!(policy.base_delay_ms(2), 500);
+        assert_eq!(policy.base_delay_ms(20), policy.max_backoff_ms);
+    }
+
+    #[tokio::test]
+    async fn test_call_with_retry_recovers_from_transient_errors() {
+        let calls = AtomicU32::new(0);
+        let result = call_with_retry(&fast_policy(3), "test", || {
+            let attempt = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            async move {
+                if attempt < 3 {
+                    Err(StorageError::ConnectionError(
+                        "connection refused".to_string(),
+                    ))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_call_with_retry_stops_on_permanent_errors() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), StorageError> = call_with_retry(&fast_policy(5), "test", || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Err(StorageError::ConfigurationError("bad key".to_string())) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_write_with_retry_checks_ambiguous_failures() {
+        let timed_out = || async { Err(StorageError::WriteError("request timed out".to_string())) };
+
+        // The write landed: its result is used and it is not sent again
+        let writes = AtomicU32::new(0);
+        let result = write_with_retry(
+            &fast_policy(5),
+            "test",
+            || {
+                writes.fetch_add(1, Ordering::SeqCst);
+                timed_out()
+            },
+            || async { Ok(Some("tx-landed")) },
+        )
+        .await;
+        assert_eq!(result.unwrap(), "tx-landed");
+        assert_eq!(writes.load(Ordering::SeqCst), 1);
+
+        // It did not land: the write is retried
+        let writes = AtomicU32::new(0);
+        let result: Result<&str, StorageError> = write_with_retry(
+            &fast_policy(3),
+            "test",
+            || {
+                writes.fetch_add(1, Ordering::SeqCst);
+                timed_out()
+            },
+            || async { Ok(None) },
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(writes.load(Ordering::SeqCst), 3);
+
+        // Whether it landed is unknown: no blind retry
+        let writes = AtomicU32::new(0);
+        let result: Result<&str, StorageError> = write_with_retry(
+            &fast_policy(5),
+            "test",
+            || {
+                writes.fetch_add(1, Ordering::SeqCst);
+                timed_out()
+            },
+            || async { Err(StorageError::ReadError("lookup failed".to_string())) },
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(writes.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_activate_adapter_config_deactivates_siblings() {
+        let storage = Arc::new(Mutex::new(crate::storage::InMemoryStorage::new()));
+        let mut manager = AdapterManager::new(storage, Arc::new(Mutex::new(LoggingEngine::new())));
+
+        let details = AdapterConnectionDetails {
+            endpoint: "http://localhost:5001".to_string(),
+            ..Default::default()
+        };
+        let first = manager
+            .create_adapter_config(
+                "ipfs-a".to_string(),
+                "first node".to_string(),
+                AdapterType::IpfsIpfs,
+                details.clone(),
+                None,
+                "admin".to_string(),
+            )
+            .unwrap();
+        let second = manager
+            .create_adapter_config(
+                "ipfs-b".to_string(),
+                "second node".to_string(),
+                AdapterType::IpfsIpfs,
+                details,
+                None,
+                "admin".to_string(),
+            )
+            .unwrap();
+
+        let (activated, instance) = manager.activate_adapter_config(&second.config_id).unwrap();
+        assert!(activated.is_active);
+        assert_eq!(instance.adapter_type(), AdapterType::IpfsIpfs);
+        assert!(
+            !manager
+                .get_adapter_config(&first.config_id)
+                .unwrap()
+                .is_active
+        );
+
+        assert!(matches!(
+            manager.activate_adapter_config(&Uuid::new_v4()),
+            Err(AdapterManagerError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_reports_unreachable_adapter_without_writing() {
+        let storage = Arc::new(Mutex::new(crate::storage::InMemoryStorage::new()));
+        let mut manager = AdapterManager::new(storage, Arc::new(Mutex::new(LoggingEngine::new())));
+        let config = manager
+            .create_adapter_config(
+                "ipfs-offline".to_string(),
+                "unreachable node".to_string(),
+                AdapterType::IpfsIpfs,
+                AdapterConnectionDetails {
+                    endpoint: "http://127.0.0.1:1".to_string(),
+                    ..Default::default()
+                },
+                None,
+                "admin".to_string(),
+            )
+            .unwrap();
+
+        let circuit_config = CircuitAdapterConfig {
+            circuit_id: Uuid::new_v4(),
+            adapter_type: Some(AdapterType::IpfsIpfs),
+            configured_by: "owner".to_string(),
+            configured_at: Utc::now(),
+            requires_approval: false,
+            auto_migrate_existing: false,
+            sponsor_adapter_access: false,
+            replication_policy: ReplicationPolicy::Quorum { min_successes: 2 },
+            mirror_adapters: Vec::new(),
+        };
+        let item = Item::new("DFID-TEST".to_string(), Vec::new(), Uuid::new_v4());
+
+        let report = manager
+            .dry_run_circuit_config(&circuit_config, &item, true)
+            .await
+            .unwrap();
+        assert!(!report.ready);
+        assert_eq!(report.errors.len(), 1); // quorum of 2 with a single adapter
+        assert_eq!(report.estimated_fee_stroops, None);
+
+        let adapter = &report.adapters[0];
+        assert_eq!(adapter.config_id, Some(config.config_id));
+        assert!(adapter.config_valid);
+        assert!(!adapter.connectivity_ok);
+        assert_eq!(adapter.planned_writes[0].operation, "upload_json");
+
+        let unconfigured = CircuitAdapterConfig {
+            adapter_type: None,
+            ..circuit_config
+        };
+        assert!(matches!(
+            manager
+                .dry_run_circuit_config(&unconfigured, &item, true)
+                .await,
+            Err(AdapterManagerError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failed_mirror_write_is_recorded_and_logged() {
+        let storage = Arc::new(Mutex::new(crate::storage::InMemoryStorage::new()));
+        let logger = Arc::new(Mutex::new(LoggingEngine::new()));
+        let mut manager = AdapterManager::new(Arc::clone(&storage), Arc::clone(&logger))
+            .with_retry_policy(fast_policy(2));
+        let config = manager
+            .create_adapter_config(
+                "ipfs-mirror".to_string(),
+                "unreachable mirror".to_string(),
+                AdapterType::IpfsIpfs,
+                AdapterConnectionDetails {
+                    endpoint: "http://127.0.0.1:1".to_string(),
+                    ..Default::default()
+                },
+                None,
+                "admin".to_string(),
+            )
+            .unwrap();
+        manager.activate_adapter_config(&config.config_id).unwrap();
+
+        let circuit_config = CircuitAdapterConfig {
+            circuit_id: Uuid::new_v4(),
+            adapter_type: Some(AdapterType::StellarMainnetIpfs),
+            configured_by: "owner".to_string(),
+            configured_at: Utc::now(),
+            requires_approval: false,
+            auto_migrate_existing: false,
+            sponsor_adapter_access: false,
+            replication_policy: ReplicationPolicy::PrimaryAsyncMirror,
+            mirror_adapters: vec![AdapterType::IpfsIpfs],
+        };
+        let item = Item::new("DFID-MIRROR".to_string(), Vec::new(), Uuid::new_v4());
+
+        manager
+            .spawn_mirror_write(
+                "DFID-MIRROR",
+                AdapterType::IpfsIpfs,
+                item,
+                "creator",
+                &circuit_config,
+            )
+            .expect("mirror write spawned")
+            .await
+            .unwrap();
+
+        let mirror = adapter_metrics(&AdapterType::IpfsIpfs);
+        assert!(mirror.operations["mirror_write"].failures >= 1);
+        assert!(storage
+            .get_storage_history("DFID-MIRROR")
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            logger
+                .lock()
+                .unwrap()
+                .get_logs_by_event_type("mirror_write_failed")
+                .len(),
+            1
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::hashing::HashAlgorithm;
 use crate::storage::StorageError;
 use crate::types::*;
 use async_trait::async_trait;
@@ -37,6 +38,48 @@ pub enum StorageLocation {
     },
 }
 
+/// IPFS locations of an event's attachments, reported next to the event's own
+/// locations when it is anchored
+pub fn attachment_locations(event: &Event, pinned: bool) -> Vec<StorageLocation> {
+    event
+        .attachments
+        .iter()
+        .map(|attachment| StorageLocation::IPFS {
+            cid: attachment.cid.clone(),
+            pinned,
+        })
+        .collect()
+}
+
+/// Pin every attachment of the event; false if any pin failed
+pub async fn pin_attachments(ipfs: &crate::ipfs_client::IpfsClient, event: &Event) -> bool {
+    let mut pinned = true;
+    for attachment in &event.attachments {
+        if let Err(e) = ipfs.pin(&attachment.cid).await {
+            tracing::warn!(
+                "Failed to pin attachment {} of event {}: {}",
+                attachment.cid,
+                event.event_id,
+                e
+            );
+            pinned = false;
+        }
+    }
+    pinned
+}
+
+/// Whether two items serialize identically. Going through `Value` sorts map
+/// keys, so field order does not matter.
+fn same_content(a: &Item, b: &Item) -> bool {
+    let hash = |item: &Item| {
+        serde_json::to_value(item)
+            .and_then(|v| serde_json::to_vec(&v))
+            .ok()
+            .map(|bytes| HashAlgorithm::Blake3.hash(&bytes))
+    };
+    matches!((hash(a), hash(b)), (Some(a), Some(b)) if a == b)
+}
+
 #[derive(Debug)]
 pub struct AdapterResult<T> {
     pub data: T,
@@ -53,6 +96,12 @@ impl<T> AdapterResult<T> {
 pub trait StorageAdapter: Send + Sync {
     fn adapter_type(&self) -> AdapterType;
 
+    /// Client the adapter anchors on Stellar with, so its signing key can be
+    /// checked against the active managed key
+    fn stellar_client(&self) -> Option<&crate::stellar_client::StellarClient> {
+        None
+    }
+
     async fn store_item(&self, item: &Item) -> Result<AdapterResult<String>, StorageError>;
 
     /// Store item with knowledge of whether this is a new DFID (for NFT minting)
@@ -76,6 +125,34 @@ pub trait StorageAdapter: Send + Sync {
 
     async fn get_item(&self, item_id: &str) -> Result<Option<AdapterResult<Item>>, StorageError>;
 
+    /// Whether an earlier write of exactly this item reached the adapter even
+    /// though its response was lost: what the adapter holds for the DFID has
+    /// the same content hash. Consulted before retrying an ambiguous failure.
+    async fn find_stored_item(
+        &self,
+        item: &Item,
+    ) -> Result<Option<AdapterResult<String>>, StorageError> {
+        Ok(self
+            .get_item(&item.dfid)
+            .await?
+            .filter(|stored| same_content(&stored.data, item))
+            .map(|stored| AdapterResult::new(item.dfid.clone(), stored.metadata)))
+    }
+
+    /// Same as `find_stored_item`, for an event stored under `item_id`
+    async fn find_stored_event(
+        &self,
+        event: &Event,
+        item_id: &str,
+    ) -> Result<Option<AdapterResult<String>>, StorageError> {
+        let _ = item_id;
+        Ok(self
+            .get_event(&event.event_id.to_string())
+            .await?
+            .filter(|stored| stored.data.content_hash == event.content_hash)
+            .map(|stored| AdapterResult::new(event.event_id.to_string(), stored.metadata)))
+    }
+
     async fn get_event(&self, event_id: &str)
         -> Result<Option<AdapterResult<Event>>, StorageError>;
 
@@ -87,6 +164,35 @@ pub trait StorageAdapter: Send + Sync {
     async fn sync_status(&self) -> Result<SyncStatus, StorageError>;
 
     async fn health_check(&self) -> Result<bool, StorageError>;
+
+    /// Validate configuration and connectivity and report what store_new_item would write,
+    /// without writing anything. Adapters override this to add their own checks and fees.
+    async fn dry_run_store_item(
+        &self,
+        item: &Item,
+        is_new_dfid: bool,
+    ) -> Result<AdapterDryRunReport, StorageError> {
+        let _ = is_new_dfid;
+        let mut report = AdapterDryRunReport::new(self.adapter_type());
+        match self.health_check().await {
+            Ok(healthy) => report.connectivity_ok = healthy,
+            Err(e) => report.errors.push(e.to_string()),
+        }
+        report.planned_writes.push(PlannedWrite {
+            target: self.adapter_type().to_string(),
+            operation: "store_item".to_string(),
+            payload_bytes: Some(payload_size(item)?),
+            description: format!("Store item {}", item.dfid),
+        });
+        Ok(report)
+    }
+}
+
+/// Size of the JSON document an adapter would upload for `value`
+pub fn payload_size<T: Serialize>(value: &T) -> Result<usize, StorageError> {
+    serde_json::to_vec(value)
+        .map(|bytes| bytes.len())
+        .map_err(StorageError::SerializationError)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
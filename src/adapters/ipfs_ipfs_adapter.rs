@@ -14,15 +14,33 @@ pub struct IpfsIpfsAdapter {
 
 impl IpfsIpfsAdapter {
     pub fn new() -> Result<Self, StorageError> {
-        // Get configuration from environment or use defaults
-        let ipfs_endpoint =
-            std::env::var("IPFS_ENDPOINT").unwrap_or_else(|_| "http://localhost:5001".to_string());
+        Self::new_with_config(None)
+    }
+
+    pub fn new_with_config(config: Option<&AdapterConfig>) -> Result<Self, StorageError> {
+        // Database config wins; environment variables remain the fallback
+        let (ipfs_endpoint, api_key, secret_key) = if let Some(cfg) = config {
+            let endpoint = if cfg.connection_details.endpoint.is_empty() {
+                "http://localhost:5001".to_string()
+            } else {
+                cfg.connection_details.endpoint.clone()
+            };
+            (
+                endpoint,
+                cfg.connection_details.api_key.clone(),
+                cfg.connection_details.secret_key.clone(),
+            )
+        } else {
+            (
+                std::env::var("IPFS_ENDPOINT")
+                    .unwrap_or_else(|_| "http://localhost:5001".to_string()),
+                std::env::var("PINATA_API_KEY").ok(),
+                std::env::var("PINATA_SECRET_KEY").ok(),
+            )
+        };
 
         // Initialize IPFS client (prefer Pinata if configured, fallback to local)
-        let ipfs_client = if let (Ok(api_key), Ok(secret)) = (
-            std::env::var("PINATA_API_KEY"),
-            std::env::var("PINATA_SECRET_KEY"),
-        ) {
+        let ipfs_client = if let (Some(api_key), Some(secret)) = (api_key, secret_key) {
             IpfsClient::with_pinata(api_key, secret).map_err(|e| {
                 StorageError::ConnectionError(format!("Failed to configure Pinata: {e}"))
             })?
@@ -85,8 +103,12 @@ impl StorageAdapter for IpfsIpfsAdapter {
             StorageError::WriteError(format!("Failed to upload event to IPFS: {e}"))
         })?;
 
-        // Create metadata with CID
-        let metadata = self.create_metadata(&cid);
+        // Create metadata with CID; attachments are already on IPFS, keep them pinned
+        let mut metadata = self.create_metadata(&cid);
+        let pinned = pin_attachments(&self.ipfs_client, event).await;
+        metadata
+            .event_locations
+            .extend(attachment_locations(event, pinned));
 
         Ok(AdapterResult::new(event.event_id.to_string(), metadata))
     }
@@ -103,6 +125,24 @@ impl StorageAdapter for IpfsIpfsAdapter {
         }
     }
 
+    /// Items are looked up by CID here, not DFID, so an earlier write cannot be
+    /// found. None is needed: re-adding content-addressed data creates nothing
+    /// new, so retrying is always safe.
+    async fn find_stored_item(
+        &self,
+        _item: &Item,
+    ) -> Result<Option<AdapterResult<String>>, StorageError> {
+        Ok(None)
+    }
+
+    async fn find_stored_event(
+        &self,
+        _event: &Event,
+        _item_id: &str,
+    ) -> Result<Option<AdapterResult<String>>, StorageError> {
+        Ok(None)
+    }
+
     async fn get_event(
         &self,
         event_id: &str,
@@ -164,6 +204,29 @@ impl StorageAdapter for IpfsIpfsAdapter {
         })
     }
 
+    async fn dry_run_store_item(
+        &self,
+        item: &Item,
+        _is_new_dfid: bool,
+    ) -> Result<AdapterDryRunReport, StorageError> {
+        let mut report = AdapterDryRunReport::new(AdapterType::IpfsIpfs);
+        match self.ipfs_client.health_check().await {
+            Ok(connected) => report.connectivity_ok = connected,
+            Err(e) => report.errors.push(format!("IPFS health check failed: {e}")),
+        }
+        if !report.connectivity_ok && report.errors.is_empty() {
+            report.errors.push("IPFS node is not reachable".to_string());
+        }
+
+        report.planned_writes.push(PlannedWrite {
+            target: "ipfs".to_string(),
+            operation: "upload_json".to_string(),
+            payload_bytes: Some(payload_size(item)?),
+            description: format!("Upload item {} and pin the resulting CID", item.dfid),
+        });
+        Ok(report)
+    }
+
     async fn health_check(&self) -> Result<bool, StorageError> {
         self.ipfs_client
             .health_check()
@@ -0,0 +1,684 @@
+//! Stellar-anchored storage for deployments that keep payloads on-premise.
+//!
+//! Items and events are written as AES-GCM encrypted files to a local directory,
+//! content-addressed by the hash of their plaintext JSON. That hash takes
+//! the place of an IPFS CID: it is minted into the NFT, registered in the IPCM
+//! contract and reported as the item's `asset_id`, so storage history and the
+//! CID timeline work exactly as they do for the IPFS-backed adapters.
+
+use crate::adapters::base::*;
+use crate::hashing;
+use crate::stellar_client::{
+    StellarClient, StellarNetwork, MAINNET_IPCM_CONTRACT, TESTNET_IPCM_CONTRACT,
+};
+use crate::storage::{EncryptedData, EncryptionKey, StorageError};
+use crate::types::*;
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const DEFAULT_STORAGE_PATH: &str = "data/local-stellar";
+
+#[derive(Clone)]
+pub struct LocalStellarAdapter {
+    adapter_type: AdapterType,
+    stellar_client: Arc<StellarClient>,
+    base_path: PathBuf,
+    encryption_key: EncryptionKey,
+    contract_address: String,
+    /// Use on-chain storage (false = event-only mode, default; true = full storage mode)
+    use_onchain_storage: bool,
+}
+
+impl std::fmt::Debug for LocalStellarAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalStellarAdapter")
+            .field("adapter_type", &self.adapter_type)
+            .field("stellar_client", &self.stellar_client)
+            .field("base_path", &self.base_path)
+            .field("contract_address", &self.contract_address)
+            .field("use_onchain_storage", &self.use_onchain_storage)
+            .finish()
+    }
+}
+
+impl LocalStellarAdapter {
+    /// Build an adapter for `StellarTestnetLocal` or `StellarMainnetLocal`.
+    ///
+    /// The storage directory comes from the config endpoint and the hex-encoded
+    /// 32-byte encryption key from the config secret key, falling back to the
+    /// `LOCAL_STORAGE_PATH` and `LOCAL_STORAGE_ENCRYPTION_KEY` env vars.
+    pub fn new_with_config(
+        adapter_type: AdapterType,
+        config: Option<&AdapterConfig>,
+    ) -> Result<Self, StorageError> {
+        let (network, env_prefix, default_contract, default_source_account) = match adapter_type {
+            AdapterType::StellarTestnetLocal => (
+                StellarNetwork::Testnet,
+                "STELLAR_TESTNET",
+                TESTNET_IPCM_CONTRACT,
+                "defarm-admin-testnet",
+            ),
+            AdapterType::StellarMainnetLocal => (
+                StellarNetwork::Mainnet,
+                "STELLAR_MAINNET",
+                MAINNET_IPCM_CONTRACT,
+                "defarm-admin-mainnet",
+            ),
+            other => {
+                return Err(StorageError::ConfigurationError(format!(
+                    "{other} is not a local Stellar adapter type"
+                )))
+            }
+        };
+
+        let header = |name: &str| {
+            config.and_then(|c| c.connection_details.custom_headers.get(name).cloned())
+        };
+
+        let base_path = config
+            .map(|c| c.connection_details.endpoint.trim())
+            .filter(|endpoint| !endpoint.is_empty())
+            .map(|endpoint| endpoint.trim_start_matches("file://").to_string())
+            .or_else(|| std::env::var("LOCAL_STORAGE_PATH").ok())
+            .unwrap_or_else(|| DEFAULT_STORAGE_PATH.to_string());
+
+        let encryption_key = config
+            .and_then(|c| c.connection_details.secret_key.clone())
+            .or_else(|| std::env::var("LOCAL_STORAGE_ENCRYPTION_KEY").ok())
+            .ok_or_else(|| {
+                StorageError::ConfigurationError(
+                    "Local Stellar adapter requires an encryption key".to_string(),
+                )
+            })
+            .and_then(|hex_key| EncryptionKey::from_hex(&hex_key))?;
+
+        // Environment variable takes precedence over database config, as for the IPFS adapters
+        let contract_address = std::env::var(format!("{env_prefix}_IPCM_CONTRACT"))
+            .ok()
+            .or_else(|| {
+                config
+                    .and_then(|c| c.contract_configs.as_ref())
+                    .and_then(|cc| cc.ipcm_contract.as_ref())
+                    .map(|ci| ci.contract_address.clone())
+            })
+            .unwrap_or_else(|| default_contract.to_string());
+
+        let mut stellar_client = StellarClient::new(network, contract_address.clone());
+
+        let stellar_secret =
+            header("stellar_secret").or_else(|| std::env::var(format!("{env_prefix}_SECRET")).ok());
+        if let Some(secret_key) = stellar_secret {
+            stellar_client = stellar_client.with_keypair(&secret_key).map_err(|e| {
+                StorageError::ConfigurationError(format!("Invalid Stellar keypair: {e}"))
+            })?;
+        }
+
+        let nft_contract = header("nft_contract")
+            .or_else(|| std::env::var(format!("{env_prefix}_NFT_CONTRACT")).ok());
+        if let Some(nft_contract) = nft_contract {
+            stellar_client = stellar_client.with_nft_contract(nft_contract);
+        } else {
+            tracing::warn!(
+                "⚠️  {} adapter: No NFT contract configured (NFT minting will fail)",
+                adapter_type
+            );
+        }
+
+        let interface_address = header("interface_address").unwrap_or_else(|| {
+            std::env::var("DEFARM_OWNER_WALLET")
+                .unwrap_or_else(|_| "STELLAR_WALLET_PLACEHOLDER".to_string())
+        });
+        let source_account_identity =
+            header("source_account_identity").unwrap_or_else(|| default_source_account.to_string());
+        stellar_client = stellar_client
+            .with_interface_address(interface_address)
+            .with_source_account(source_account_identity);
+
+        let use_onchain_storage = header("use_onchain_storage")
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or(false);
+
+        tracing::info!(
+            "📁 {} adapter storing encrypted payloads under {}",
+            adapter_type,
+            base_path
+        );
+
+        Ok(Self {
+            adapter_type,
+            stellar_client: Arc::new(stellar_client),
+            base_path: PathBuf::from(base_path),
+            encryption_key,
+            contract_address,
+            use_onchain_storage,
+        })
+    }
+
+    /// Register a file hash on Stellar using either full storage or event-only mode
+    async fn register_on_stellar(
+        &self,
+        dfid: &str,
+        file_hash: &str,
+    ) -> Result<String, crate::stellar_client::StellarError> {
+        if self.use_onchain_storage {
+            self.stellar_client.update_ipcm(dfid, file_hash).await
+        } else {
+            self.stellar_client.emit_update_event(dfid, file_hash).await
+        }
+    }
+
+    /// Encrypt and write a payload, returning the hash of its plaintext JSON
+    fn write_object<T: Serialize>(&self, value: &T) -> Result<String, StorageError> {
+        let plaintext = serde_json::to_vec(value)?;
+        let file_hash = hashing::hash(&plaintext);
+
+        let path = self.object_path(&file_hash);
+        // Content-addressed: an existing file already holds this exact payload
+        if path.exists() {
+            return Ok(file_hash);
+        }
+
+        let encrypted = self.encryption_key.encrypt(&plaintext)?;
+        write_atomic(&path, &serde_json::to_vec(&encrypted)?)?;
+        Ok(file_hash)
+    }
+
+    /// Read and decrypt a payload, rejecting files whose content no longer matches their hash
+    fn read_object<T: DeserializeOwned>(&self, file_hash: &str) -> Result<Option<T>, StorageError> {
+        let path = self.object_path(file_hash);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let encrypted: EncryptedData = serde_json::from_slice(&fs::read(path)?)?;
+        let plaintext = self.encryption_key.decrypt(&encrypted)?;
+        let actual_hash = hashing::algorithm_of(file_hash).hash(&plaintext);
+        if actual_hash != file_hash {
+            return Err(StorageError::ReadError(format!(
+                "Local file {file_hash} failed integrity check (content hash {actual_hash})"
+            )));
+        }
+
+        Ok(Some(serde_json::from_slice(&plaintext)?))
+    }
+
+    fn object_path(&self, file_hash: &str) -> PathBuf {
+        self.base_path
+            .join("objects")
+            .join(format!("{file_hash}.enc"))
+    }
+
+    fn item_ref_path(&self, dfid: &str) -> PathBuf {
+        self.base_path.join("items").join(ref_name(dfid))
+    }
+
+    fn event_ref_path(&self, event_id: &str) -> PathBuf {
+        self.base_path.join("events").join(ref_name(event_id))
+    }
+
+    fn item_events_dir(&self, dfid: &str) -> PathBuf {
+        self.base_path.join("item_events").join(ref_name(dfid))
+    }
+
+    fn storage_writable(&self) -> bool {
+        fs::create_dir_all(self.base_path.join("objects")).is_ok()
+            && !fs::metadata(&self.base_path)
+                .map(|m| m.permissions().readonly())
+                .unwrap_or(true)
+    }
+
+    fn create_metadata(&self, stellar_tx: &str, file_hash: &str) -> StorageMetadata {
+        let now = Utc::now();
+        StorageMetadata {
+            adapter_type: self.adapter_type.clone(),
+            item_location: StorageLocation::Stellar {
+                transaction_id: stellar_tx.to_string(),
+                contract_address: self.contract_address.clone(),
+                asset_id: Some(file_hash.to_string()),
+            },
+            event_locations: vec![StorageLocation::Local {
+                id: file_hash.to_string(),
+            }],
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Create metadata with both NFT mint transaction and IPCM update transaction
+    fn create_metadata_with_nft(
+        &self,
+        nft_tx: &str,
+        ipcm_tx: &str,
+        file_hash: &str,
+    ) -> StorageMetadata {
+        let mut metadata = self.create_metadata(ipcm_tx, file_hash);
+        metadata.event_locations.insert(
+            0,
+            StorageLocation::Stellar {
+                transaction_id: nft_tx.to_string(),
+                contract_address: self
+                    .stellar_client
+                    .get_nft_contract_address()
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "NFT_CONTRACT".to_string()),
+                asset_id: None,
+            },
+        );
+        metadata
+    }
+
+    fn network_name(&self) -> &'static str {
+        match self.adapter_type {
+            AdapterType::StellarMainnetLocal => "mainnet",
+            _ => "testnet",
+        }
+    }
+}
+
+/// Filesystem-safe name for a DFID or event id
+fn ref_name(id: &str) -> String {
+    id.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn read_ref(path: &Path) -> Result<Option<String>, StorageError> {
+    match fs::read_to_string(path) {
+        Ok(hash) => Ok(Some(hash.trim().to_string())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Write via a temporary file so readers never observe a partial payload
+fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), StorageError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[async_trait]
+impl StorageAdapter for LocalStellarAdapter {
+    fn adapter_type(&self) -> AdapterType {
+        self.adapter_type.clone()
+    }
+
+    fn stellar_client(&self) -> Option<&StellarClient> {
+        Some(&self.stellar_client)
+    }
+
+    async fn store_item(&self, item: &Item) -> Result<AdapterResult<String>, StorageError> {
+        // Step 1: Write the encrypted item to local storage
+        let file_hash = self.write_object(item)?;
+
+        // Step 2: Anchor the file hash on Stellar
+        let tx_hash = self
+            .register_on_stellar(&item.dfid, &file_hash)
+            .await
+            .map_err(|e| StorageError::WriteError(format!("Failed to register on Stellar: {e}")))?;
+
+        // Step 3: Only point the item at the new file once it is anchored
+        write_atomic(&self.item_ref_path(&item.dfid), file_hash.as_bytes())?;
+
+        let metadata = self.create_metadata(&tx_hash, &file_hash);
+        Ok(AdapterResult::new(item.dfid.clone(), metadata))
+    }
+
+    /// Store item with NFT minting for new DFIDs
+    async fn store_new_item(
+        &self,
+        item: &Item,
+        is_new_dfid: bool,
+        creator: &str,
+    ) -> Result<AdapterResult<String>, StorageError> {
+        if !is_new_dfid {
+            return self.store_item(item).await;
+        }
+
+        let canonical_identifiers: Vec<String> = item
+            .identifiers
+            .iter()
+            .filter_map(|id| {
+                if let crate::identifier_types::IdentifierType::Canonical { .. } = id.id_type {
+                    Some(format!("{}:{}:{}", id.namespace, id.key, id.value))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let file_hash = self.write_object(item)?;
+
+        let nft_tx_hash = self
+            .stellar_client
+            .mint_nft(
+                &item.dfid,
+                creator,
+                canonical_identifiers,
+                Some(&file_hash),
+                None,
+            )
+            .await
+            .map_err(|e| StorageError::WriteError(format!("Failed to mint NFT on Stellar: {e}")))?;
+
+        let ipcm_tx_hash = self
+            .register_on_stellar(&item.dfid, &file_hash)
+            .await
+            .map_err(|e| StorageError::WriteError(format!("Failed to register on Stellar: {e}")))?;
+
+        write_atomic(&self.item_ref_path(&item.dfid), file_hash.as_bytes())?;
+
+        tracing::info!(
+            "🎨 NFT minted for new DFID: {} (TX: {}, file hash: {})",
+            item.dfid,
+            nft_tx_hash,
+            file_hash
+        );
+
+        let metadata = self.create_metadata_with_nft(&nft_tx_hash, &ipcm_tx_hash, &file_hash);
+        Ok(AdapterResult::new(item.dfid.clone(), metadata))
+    }
+
+    async fn store_event(
+        &self,
+        event: &Event,
+        item_id: &str,
+    ) -> Result<AdapterResult<String>, StorageError> {
+        let file_hash = self.write_object(event)?;
+
+        let event_key = format!("event:{}:{}", item_id, event.event_id);
+        let tx_hash = self
+            .register_on_stellar(&event_key, &file_hash)
+            .await
+            .map_err(|e| {
+                StorageError::WriteError(format!("Failed to register event on Stellar: {e}"))
+            })?;
+
+        let event_id = event.event_id.to_string();
+        write_atomic(&self.event_ref_path(&event_id), file_hash.as_bytes())?;
+        write_atomic(
+            &self.item_events_dir(item_id).join(ref_name(&event_id)),
+            file_hash.as_bytes(),
+        )?;
+
+        // Attachments stay on IPFS; the stored event already lists their CIDs
+        let mut metadata = self.create_metadata(&tx_hash, &file_hash);
+        metadata
+            .event_locations
+            .extend(attachment_locations(event, false));
+        Ok(AdapterResult::new(event_id, metadata))
+    }
+
+    async fn get_item(&self, item_id: &str) -> Result<Option<AdapterResult<Item>>, StorageError> {
+        let Some(file_hash) = read_ref(&self.item_ref_path(item_id))? else {
+            return Ok(None);
+        };
+
+        Ok(self
+            .read_object::<Item>(&file_hash)?
+            .map(|item| AdapterResult::new(item, self.create_metadata("read_only", &file_hash))))
+    }
+
+    async fn get_event(
+        &self,
+        event_id: &str,
+    ) -> Result<Option<AdapterResult<Event>>, StorageError> {
+        let Some(file_hash) = read_ref(&self.event_ref_path(event_id))? else {
+            return Ok(None);
+        };
+
+        Ok(self
+            .read_object::<Event>(&file_hash)?
+            .map(|event| AdapterResult::new(event, self.create_metadata("read_only", &file_hash))))
+    }
+
+    async fn get_item_events(
+        &self,
+        item_id: &str,
+    ) -> Result<Vec<AdapterResult<Event>>, StorageError> {
+        let dir = self.item_events_dir(item_id);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut events = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let Some(file_hash) = read_ref(&entry?.path())? else {
+                continue;
+            };
+            if let Some(event) = self.read_object::<Event>(&file_hash)? {
+                events.push(AdapterResult::new(
+                    event,
+                    self.create_metadata("read_only", &file_hash),
+                ));
+            }
+        }
+        events.sort_by_key(|e| e.data.timestamp);
+        Ok(events)
+    }
+
+    async fn sync_status(&self) -> Result<SyncStatus, StorageError> {
+        let storage_writable = self.storage_writable();
+        let stellar_connected = self.stellar_client.health_check().await.unwrap_or(false);
+        let is_synced = storage_writable && stellar_connected;
+
+        let mut details = HashMap::new();
+        details.insert(
+            "implementation_status".to_string(),
+            serde_json::Value::String("production".to_string()),
+        );
+        details.insert(
+            "stellar_network".to_string(),
+            serde_json::Value::String(self.network_name().to_string()),
+        );
+        details.insert(
+            "contract_address".to_string(),
+            serde_json::Value::String(self.contract_address.clone()),
+        );
+        details.insert(
+            "storage_path".to_string(),
+            serde_json::Value::String(self.base_path.display().to_string()),
+        );
+        details.insert(
+            "storage_writable".to_string(),
+            serde_json::Value::Bool(storage_writable),
+        );
+        details.insert(
+            "stellar_connected".to_string(),
+            serde_json::Value::Bool(stellar_connected),
+        );
+
+        Ok(SyncStatus {
+            adapter_type: self.adapter_type.clone(),
+            is_synced,
+            pending_operations: 0,
+            last_sync: if is_synced { Some(Utc::now()) } else { None },
+            error_count: 0,
+            details,
+        })
+    }
+
+    async fn dry_run_store_item(
+        &self,
+        item: &Item,
+        is_new_dfid: bool,
+    ) -> Result<AdapterDryRunReport, StorageError> {
+        let mut report = AdapterDryRunReport::new(self.adapter_type.clone());
+
+        if !self.stellar_client.has_keypair() {
+            report.config_error("No Stellar keypair configured; transactions cannot be signed");
+        }
+        if is_new_dfid && self.stellar_client.get_nft_contract_address().is_none() {
+            report.config_error("No NFT contract configured; minting a new DFID would fail");
+        }
+
+        let storage_writable = self.storage_writable();
+        let stellar_connected = self.stellar_client.health_check().await.unwrap_or(false);
+        report.connectivity_ok = storage_writable && stellar_connected;
+        if !storage_writable {
+            report.errors.push(format!(
+                "Local storage directory {} is not writable",
+                self.base_path.display()
+            ));
+        }
+        if !stellar_connected {
+            report.errors.push(format!(
+                "Stellar {} Horizon is not reachable",
+                self.network_name()
+            ));
+        }
+
+        report.planned_writes.push(PlannedWrite {
+            target: "local_file".to_string(),
+            operation: "write_encrypted".to_string(),
+            payload_bytes: Some(payload_size(item)?),
+            description: format!(
+                "Encrypt item {} into {}",
+                item.dfid,
+                self.base_path.join("objects").display()
+            ),
+        });
+
+        let mut invocations = 1;
+        if is_new_dfid {
+            invocations += 1;
+            report.planned_writes.push(PlannedWrite {
+                target: "stellar_nft".to_string(),
+                operation: "mint_nft".to_string(),
+                payload_bytes: None,
+                description: format!(
+                    "Mint NFT for {} on {}",
+                    item.dfid,
+                    self.stellar_client
+                        .get_nft_contract_address()
+                        .unwrap_or("unconfigured NFT contract")
+                ),
+            });
+        }
+
+        let operation = if self.use_onchain_storage {
+            "update_ipcm"
+        } else {
+            "emit_update_event"
+        };
+        report.planned_writes.push(PlannedWrite {
+            target: "stellar_ipcm".to_string(),
+            operation: operation.to_string(),
+            payload_bytes: None,
+            description: format!(
+                "Register the file hash for {} on IPCM contract {}",
+                item.dfid, self.contract_address
+            ),
+        });
+
+        match self.stellar_client.estimate_fee_stroops(invocations).await {
+            Ok(fee) => report.estimated_fee_stroops = Some(fee),
+            Err(e) => report
+                .warnings
+                .push(format!("Could not estimate Stellar fees: {e}")),
+        }
+
+        Ok(report)
+    }
+
+    async fn health_check(&self) -> Result<bool, StorageError> {
+        let stellar_health = self.stellar_client.health_check().await.unwrap_or(false);
+        Ok(self.storage_writable() && stellar_health)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_adapter(dir: &Path) -> LocalStellarAdapter {
+        let connection_details = AdapterConnectionDetails {
+            endpoint: dir.display().to_string(),
+            secret_key: Some("11".repeat(32)),
+            ..Default::default()
+        };
+        let config = AdapterConfig::new(
+            "local".to_string(),
+            "Local test storage".to_string(),
+            AdapterType::StellarTestnetLocal,
+            connection_details,
+            "admin".to_string(),
+        );
+        LocalStellarAdapter::new_with_config(AdapterType::StellarTestnetLocal, Some(&config))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_encrypted_objects_are_content_addressed() {
+        let dir = std::env::temp_dir().join(format!("local-stellar-{}", uuid::Uuid::new_v4()));
+        let adapter = test_adapter(&dir);
+
+        let item = Item::new("DFID-LOCAL-1".to_string(), vec![], uuid::Uuid::new_v4());
+        let file_hash = adapter.write_object(&item).unwrap();
+        assert_eq!(
+            file_hash,
+            blake3::hash(&serde_json::to_vec(&item).unwrap())
+                .to_hex()
+                .to_string()
+        );
+        assert_eq!(adapter.write_object(&item).unwrap(), file_hash);
+
+        // Payload is encrypted at rest
+        let on_disk = fs::read(adapter.object_path(&file_hash)).unwrap();
+        assert!(!String::from_utf8_lossy(&on_disk).contains("DFID-LOCAL-1"));
+
+        let restored: Item = adapter.read_object(&file_hash).unwrap().unwrap();
+        assert_eq!(restored.dfid, item.dfid);
+        assert!(adapter
+            .read_object::<Item>(&"0".repeat(64))
+            .unwrap()
+            .is_none());
+
+        // A file swapped in under the wrong hash fails the integrity check
+        let other = Item::new("DFID-LOCAL-2".to_string(), vec![], uuid::Uuid::new_v4());
+        let other_hash = adapter.write_object(&other).unwrap();
+        fs::copy(
+            adapter.object_path(&other_hash),
+            adapter.object_path(&file_hash),
+        )
+        .unwrap();
+        assert!(adapter.read_object::<Item>(&file_hash).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_requires_encryption_key_and_local_type() {
+        let config = AdapterConfig::new(
+            "local".to_string(),
+            "No key".to_string(),
+            AdapterType::StellarMainnetLocal,
+            AdapterConnectionDetails::default(),
+            "admin".to_string(),
+        );
+        if std::env::var("LOCAL_STORAGE_ENCRYPTION_KEY").is_err() {
+            assert!(LocalStellarAdapter::new_with_config(
+                AdapterType::StellarMainnetLocal,
+                Some(&config)
+            )
+            .is_err());
+        }
+        assert!(LocalStellarAdapter::new_with_config(AdapterType::IpfsIpfs, None).is_err());
+    }
+}
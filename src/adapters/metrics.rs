@@ -0,0 +1,109 @@
+//! Process-wide latency and error metrics for storage adapters.
+//!
+//! Adapter instances are created per request, so counters live in a shared
+//! registry keyed by adapter type rather than on the instances themselves.
+
+use crate::sla_engine;
+use crate::types::{AdapterMetrics, AdapterType, SlaComponent};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+const MAX_ERROR_LENGTH: usize = 500;
+
+fn registry() -> &'static Mutex<HashMap<AdapterType, AdapterMetrics>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<AdapterType, AdapterMetrics>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Record the outcome of one adapter operation
+pub fn record_operation(
+    adapter_type: &AdapterType,
+    operation: &str,
+    latency: Duration,
+    error: Option<&str>,
+) {
+    let latency_ms = latency.as_millis() as u64;
+    let now = Utc::now();
+    sla_engine::record_outcome(SlaComponent::Adapters, error.is_none());
+
+    let mut metrics = registry().lock().unwrap();
+    let adapter = metrics
+        .entry(adapter_type.clone())
+        .or_insert_with(|| AdapterMetrics::new(adapter_type.clone()));
+    let op = adapter.operations.entry(operation.to_string()).or_default();
+
+    op.calls += 1;
+    op.total_latency_ms += latency_ms;
+    op.last_latency_ms = latency_ms;
+    op.max_latency_ms = op.max_latency_ms.max(latency_ms);
+
+    match error {
+        Some(error) => {
+            op.failures += 1;
+            op.last_error = Some(error.chars().take(MAX_ERROR_LENGTH).collect());
+            op.last_error_at = Some(now);
+        }
+        None => {
+            op.successes += 1;
+            op.last_success_at = Some(now);
+        }
+    }
+}
+
+/// Metrics for one adapter type; empty if it has not been used yet
+pub fn adapter_metrics(adapter_type: &AdapterType) -> AdapterMetrics {
+    registry()
+        .lock()
+        .unwrap()
+        .get(adapter_type)
+        .cloned()
+        .unwrap_or_else(|| AdapterMetrics::new(adapter_type.clone()))
+}
+
+/// Metrics for every adapter type that has recorded at least one operation
+pub fn all_adapter_metrics() -> Vec<AdapterMetrics> {
+    let mut all: Vec<AdapterMetrics> = registry().lock().unwrap().values().cloned().collect();
+    all.sort_by_key(|m| m.adapter_type.to_string());
+    all
+}
+
+pub fn reset_adapter_metrics(adapter_type: &AdapterType) {
+    registry().lock().unwrap().remove(adapter_type);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_latency_and_errors() {
+        let adapter_type = AdapterType::Custom("metrics-test".to_string());
+        reset_adapter_metrics(&adapter_type);
+
+        record_operation(&adapter_type, "store_item", Duration::from_millis(10), None);
+        record_operation(
+            &adapter_type,
+            "store_item",
+            Duration::from_millis(30),
+            Some("gateway timeout"),
+        );
+        record_operation(&adapter_type, "get_item", Duration::from_millis(5), None);
+
+        let metrics = adapter_metrics(&adapter_type);
+        let store = &metrics.operations["store_item"];
+        assert_eq!(store.calls, 2);
+        assert_eq!(store.failures, 1);
+        assert_eq!(store.max_latency_ms, 30);
+        assert_eq!(store.average_latency_ms(), 20.0);
+        assert_eq!(metrics.total_calls(), 3);
+
+        let (operation, last) = metrics.last_error().unwrap();
+        assert_eq!(operation, "store_item");
+        assert_eq!(last.last_error.as_deref(), Some("gateway timeout"));
+
+        reset_adapter_metrics(&adapter_type);
+        assert_eq!(adapter_metrics(&adapter_type).total_calls(), 0);
+    }
+}
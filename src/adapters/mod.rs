@@ -1,6 +1,8 @@
 pub mod base;
 pub mod config;
 pub mod ipfs_ipfs_adapter;
+pub mod local_stellar_adapter;
+pub mod metrics;
 pub mod stellar_mainnet_ipfs_adapter;
 pub mod stellar_testnet_ipfs_adapter;
 
@@ -10,18 +12,21 @@ pub use config::{
     StellarNetwork,
 };
 pub use ipfs_ipfs_adapter::*;
+pub use local_stellar_adapter::*;
 pub use stellar_mainnet_ipfs_adapter::*;
 pub use stellar_testnet_ipfs_adapter::*;
 
 use crate::storage::StorageError;
 use crate::types::*;
 use std::collections::HashMap;
+use std::future::Future;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum AdapterInstance {
     IpfsIpfs(IpfsIpfsAdapter),
     StellarTestnetIpfs(StellarTestnetIpfsAdapter),
     StellarMainnetIpfs(StellarMainnetIpfsAdapter),
+    LocalStellar(LocalStellarAdapter),
 }
 
 impl AdapterInstance {
@@ -30,8 +35,31 @@ impl AdapterInstance {
             AdapterInstance::IpfsIpfs(adapter) => adapter.adapter_type(),
             AdapterInstance::StellarTestnetIpfs(adapter) => adapter.adapter_type(),
             AdapterInstance::StellarMainnetIpfs(adapter) => adapter.adapter_type(),
+            AdapterInstance::LocalStellar(adapter) => adapter.adapter_type(),
         }
     }
+
+    /// Latency and error metrics recorded for this adapter's type
+    pub fn adapter_metrics(&self) -> AdapterMetrics {
+        metrics::adapter_metrics(&self.adapter_type())
+    }
+
+    async fn instrumented<T>(
+        &self,
+        operation: &str,
+        call: impl Future<Output = Result<T, StorageError>>,
+    ) -> Result<T, StorageError> {
+        let start = std::time::Instant::now();
+        let result = call.await;
+        let error = result.as_ref().err().map(|e| e.to_string());
+        metrics::record_operation(
+            &self.adapter_type(),
+            operation,
+            start.elapsed(),
+            error.as_deref(),
+        );
+        result
+    }
 }
 
 #[async_trait::async_trait]
@@ -41,34 +69,54 @@ impl StorageAdapter for AdapterInstance {
             AdapterInstance::IpfsIpfs(adapter) => adapter.adapter_type(),
             AdapterInstance::StellarTestnetIpfs(adapter) => adapter.adapter_type(),
             AdapterInstance::StellarMainnetIpfs(adapter) => adapter.adapter_type(),
+            AdapterInstance::LocalStellar(adapter) => adapter.adapter_type(),
         }
     }
 
-    async fn store_item(&self, item: &Item) -> Result<AdapterResult<String>, StorageError> {
+    fn stellar_client(&self) -> Option<&crate::stellar_client::StellarClient> {
         match self {
-            AdapterInstance::IpfsIpfs(adapter) => adapter.store_item(item).await,
-            AdapterInstance::StellarTestnetIpfs(adapter) => adapter.store_item(item).await,
-            AdapterInstance::StellarMainnetIpfs(adapter) => adapter.store_item(item).await,
+            AdapterInstance::IpfsIpfs(adapter) => adapter.stellar_client(),
+            AdapterInstance::StellarTestnetIpfs(adapter) => adapter.stellar_client(),
+            AdapterInstance::StellarMainnetIpfs(adapter) => adapter.stellar_client(),
+            AdapterInstance::LocalStellar(adapter) => adapter.stellar_client(),
         }
     }
 
+    async fn store_item(&self, item: &Item) -> Result<AdapterResult<String>, StorageError> {
+        self.instrumented("store_item", async {
+            match self {
+                AdapterInstance::IpfsIpfs(adapter) => adapter.store_item(item).await,
+                AdapterInstance::StellarTestnetIpfs(adapter) => adapter.store_item(item).await,
+                AdapterInstance::StellarMainnetIpfs(adapter) => adapter.store_item(item).await,
+                AdapterInstance::LocalStellar(adapter) => adapter.store_item(item).await,
+            }
+        })
+        .await
+    }
+
     async fn store_new_item(
         &self,
         item: &Item,
         is_new_dfid: bool,
         creator: &str,
     ) -> Result<AdapterResult<String>, StorageError> {
-        match self {
-            AdapterInstance::IpfsIpfs(adapter) => {
-                adapter.store_new_item(item, is_new_dfid, creator).await
-            }
-            AdapterInstance::StellarTestnetIpfs(adapter) => {
-                adapter.store_new_item(item, is_new_dfid, creator).await
-            }
-            AdapterInstance::StellarMainnetIpfs(adapter) => {
-                adapter.store_new_item(item, is_new_dfid, creator).await
+        self.instrumented("store_new_item", async {
+            match self {
+                AdapterInstance::IpfsIpfs(adapter) => {
+                    adapter.store_new_item(item, is_new_dfid, creator).await
+                }
+                AdapterInstance::StellarTestnetIpfs(adapter) => {
+                    adapter.store_new_item(item, is_new_dfid, creator).await
+                }
+                AdapterInstance::StellarMainnetIpfs(adapter) => {
+                    adapter.store_new_item(item, is_new_dfid, creator).await
+                }
+                AdapterInstance::LocalStellar(adapter) => {
+                    adapter.store_new_item(item, is_new_dfid, creator).await
+                }
             }
-        }
+        })
+        .await
     }
 
     async fn store_event(
@@ -76,60 +124,164 @@ impl StorageAdapter for AdapterInstance {
         event: &Event,
         item_id: &str,
     ) -> Result<AdapterResult<String>, StorageError> {
-        match self {
-            AdapterInstance::IpfsIpfs(adapter) => adapter.store_event(event, item_id).await,
-            AdapterInstance::StellarTestnetIpfs(adapter) => {
-                adapter.store_event(event, item_id).await
-            }
-            AdapterInstance::StellarMainnetIpfs(adapter) => {
-                adapter.store_event(event, item_id).await
+        self.instrumented("store_event", async {
+            match self {
+                AdapterInstance::IpfsIpfs(adapter) => adapter.store_event(event, item_id).await,
+                AdapterInstance::StellarTestnetIpfs(adapter) => {
+                    adapter.store_event(event, item_id).await
+                }
+                AdapterInstance::StellarMainnetIpfs(adapter) => {
+                    adapter.store_event(event, item_id).await
+                }
+                AdapterInstance::LocalStellar(adapter) => adapter.store_event(event, item_id).await,
             }
-        }
+        })
+        .await
     }
 
     async fn get_item(&self, item_id: &str) -> Result<Option<AdapterResult<Item>>, StorageError> {
-        match self {
-            AdapterInstance::IpfsIpfs(adapter) => adapter.get_item(item_id).await,
-            AdapterInstance::StellarTestnetIpfs(adapter) => adapter.get_item(item_id).await,
-            AdapterInstance::StellarMainnetIpfs(adapter) => adapter.get_item(item_id).await,
-        }
+        self.instrumented("get_item", async {
+            match self {
+                AdapterInstance::IpfsIpfs(adapter) => adapter.get_item(item_id).await,
+                AdapterInstance::StellarTestnetIpfs(adapter) => adapter.get_item(item_id).await,
+                AdapterInstance::StellarMainnetIpfs(adapter) => adapter.get_item(item_id).await,
+                AdapterInstance::LocalStellar(adapter) => adapter.get_item(item_id).await,
+            }
+        })
+        .await
+    }
+
+    async fn find_stored_item(
+        &self,
+        item: &Item,
+    ) -> Result<Option<AdapterResult<String>>, StorageError> {
+        self.instrumented("find_stored_item", async {
+            match self {
+                AdapterInstance::IpfsIpfs(adapter) => adapter.find_stored_item(item).await,
+                AdapterInstance::StellarTestnetIpfs(adapter) => {
+                    adapter.find_stored_item(item).await
+                }
+                AdapterInstance::StellarMainnetIpfs(adapter) => {
+                    adapter.find_stored_item(item).await
+                }
+                AdapterInstance::LocalStellar(adapter) => adapter.find_stored_item(item).await,
+            }
+        })
+        .await
+    }
+
+    async fn find_stored_event(
+        &self,
+        event: &Event,
+        item_id: &str,
+    ) -> Result<Option<AdapterResult<String>>, StorageError> {
+        self.instrumented("find_stored_event", async {
+            match self {
+                AdapterInstance::IpfsIpfs(adapter) => {
+                    adapter.find_stored_event(event, item_id).await
+                }
+                AdapterInstance::StellarTestnetIpfs(adapter) => {
+                    adapter.find_stored_event(event, item_id).await
+                }
+                AdapterInstance::StellarMainnetIpfs(adapter) => {
+                    adapter.find_stored_event(event, item_id).await
+                }
+                AdapterInstance::LocalStellar(adapter) => {
+                    adapter.find_stored_event(event, item_id).await
+                }
+            }
+        })
+        .await
     }
 
     async fn get_event(
         &self,
         event_id: &str,
     ) -> Result<Option<AdapterResult<Event>>, StorageError> {
-        match self {
-            AdapterInstance::IpfsIpfs(adapter) => adapter.get_event(event_id).await,
-            AdapterInstance::StellarTestnetIpfs(adapter) => adapter.get_event(event_id).await,
-            AdapterInstance::StellarMainnetIpfs(adapter) => adapter.get_event(event_id).await,
-        }
+        self.instrumented("get_event", async {
+            match self {
+                AdapterInstance::IpfsIpfs(adapter) => adapter.get_event(event_id).await,
+                AdapterInstance::StellarTestnetIpfs(adapter) => adapter.get_event(event_id).await,
+                AdapterInstance::StellarMainnetIpfs(adapter) => adapter.get_event(event_id).await,
+                AdapterInstance::LocalStellar(adapter) => adapter.get_event(event_id).await,
+            }
+        })
+        .await
     }
 
     async fn get_item_events(
         &self,
         item_id: &str,
     ) -> Result<Vec<AdapterResult<Event>>, StorageError> {
-        match self {
-            AdapterInstance::IpfsIpfs(adapter) => adapter.get_item_events(item_id).await,
-            AdapterInstance::StellarTestnetIpfs(adapter) => adapter.get_item_events(item_id).await,
-            AdapterInstance::StellarMainnetIpfs(adapter) => adapter.get_item_events(item_id).await,
-        }
+        self.instrumented("get_item_events", async {
+            match self {
+                AdapterInstance::IpfsIpfs(adapter) => adapter.get_item_events(item_id).await,
+                AdapterInstance::StellarTestnetIpfs(adapter) => {
+                    adapter.get_item_events(item_id).await
+                }
+                AdapterInstance::StellarMainnetIpfs(adapter) => {
+                    adapter.get_item_events(item_id).await
+                }
+                AdapterInstance::LocalStellar(adapter) => adapter.get_item_events(item_id).await,
+            }
+        })
+        .await
     }
 
     async fn sync_status(&self) -> Result<SyncStatus, StorageError> {
-        match self {
-            AdapterInstance::IpfsIpfs(adapter) => adapter.sync_status().await,
-            AdapterInstance::StellarTestnetIpfs(adapter) => adapter.sync_status().await,
-            AdapterInstance::StellarMainnetIpfs(adapter) => adapter.sync_status().await,
-        }
+        self.instrumented("sync_status", async {
+            match self {
+                AdapterInstance::IpfsIpfs(adapter) => adapter.sync_status().await,
+                AdapterInstance::StellarTestnetIpfs(adapter) => adapter.sync_status().await,
+                AdapterInstance::StellarMainnetIpfs(adapter) => adapter.sync_status().await,
+                AdapterInstance::LocalStellar(adapter) => adapter.sync_status().await,
+            }
+        })
+        .await
     }
 
     async fn health_check(&self) -> Result<bool, StorageError> {
-        match self {
+        let start = std::time::Instant::now();
+        let result = match self {
             AdapterInstance::IpfsIpfs(adapter) => adapter.health_check().await,
             AdapterInstance::StellarTestnetIpfs(adapter) => adapter.health_check().await,
             AdapterInstance::StellarMainnetIpfs(adapter) => adapter.health_check().await,
+            AdapterInstance::LocalStellar(adapter) => adapter.health_check().await,
+        };
+        // An unhealthy result counts as a failed call
+        let error = match &result {
+            Ok(true) => None,
+            Ok(false) => Some("Adapter reported unhealthy".to_string()),
+            Err(e) => Some(e.to_string()),
+        };
+        metrics::record_operation(
+            &self.adapter_type(),
+            "health_check",
+            start.elapsed(),
+            error.as_deref(),
+        );
+        result
+    }
+
+    // Dry runs are deliberately left out of adapter metrics
+    async fn dry_run_store_item(
+        &self,
+        item: &Item,
+        is_new_dfid: bool,
+    ) -> Result<AdapterDryRunReport, StorageError> {
+        match self {
+            AdapterInstance::IpfsIpfs(adapter) => {
+                adapter.dry_run_store_item(item, is_new_dfid).await
+            }
+            AdapterInstance::StellarTestnetIpfs(adapter) => {
+                adapter.dry_run_store_item(item, is_new_dfid).await
+            }
+            AdapterInstance::StellarMainnetIpfs(adapter) => {
+                adapter.dry_run_store_item(item, is_new_dfid).await
+            }
+            AdapterInstance::LocalStellar(adapter) => {
+                adapter.dry_run_store_item(item, is_new_dfid).await
+            }
         }
     }
 }
@@ -137,6 +289,8 @@ impl StorageAdapter for AdapterInstance {
 #[derive(Debug)]
 pub struct AdapterRegistry {
     adapters: HashMap<AdapterType, AdapterInstance>,
+    /// Configuration each runtime-registered adapter was built from
+    adapter_configs: HashMap<AdapterType, uuid::Uuid>,
     client_permissions: HashMap<String, Vec<AdapterType>>,
 }
 
@@ -150,15 +304,40 @@ impl AdapterRegistry {
     pub fn new() -> Self {
         Self {
             adapters: HashMap::new(),
+            adapter_configs: HashMap::new(),
             client_permissions: HashMap::new(),
         }
     }
 
     pub fn register_adapter(&mut self, adapter: AdapterInstance) {
         let adapter_type = adapter.adapter_type();
+        self.adapter_configs.remove(&adapter_type);
         self.adapters.insert(adapter_type, adapter);
     }
 
+    /// Register (or replace) the adapter for its type, remembering the source configuration
+    pub fn register_configured_adapter(&mut self, adapter: AdapterInstance, config_id: uuid::Uuid) {
+        let adapter_type = adapter.adapter_type();
+        self.adapter_configs.insert(adapter_type.clone(), config_id);
+        self.adapters.insert(adapter_type, adapter);
+    }
+
+    pub fn unregister_adapter(&mut self, adapter_type: &AdapterType) -> Option<AdapterInstance> {
+        self.adapter_configs.remove(adapter_type);
+        self.adapters.remove(adapter_type)
+    }
+
+    /// Registered adapter types with the configuration they were built from, if any
+    pub fn registered_adapters(&self) -> Vec<(AdapterType, Option<uuid::Uuid>)> {
+        let mut registered: Vec<_> = self
+            .adapters
+            .keys()
+            .map(|t| (t.clone(), self.adapter_configs.get(t).copied()))
+            .collect();
+        registered.sort_by_key(|(t, _)| t.to_string());
+        registered
+    }
+
     pub fn set_client_permissions(&mut self, client_id: String, adapters: Vec<AdapterType>) {
         self.client_permissions.insert(client_id, adapters);
     }
@@ -68,9 +68,8 @@ impl StellarMainnetIpfsAdapter {
                 .get("interface_address")
                 .cloned()
                 .unwrap_or_else(|| {
-                    std::env::var("DEFARM_OWNER_WALLET").unwrap_or_else(|_| {
-                        "STELLAR_WALLET_PLACEHOLDER".to_string()
-                    })
+                    std::env::var("DEFARM_OWNER_WALLET")
+                        .unwrap_or_else(|_| "STELLAR_WALLET_PLACEHOLDER".to_string())
                 });
             let source_account_identity = cfg
                 .connection_details
@@ -97,9 +96,8 @@ impl StellarMainnetIpfsAdapter {
             let api_key = std::env::var("PINATA_API_KEY").ok();
             let secret_key = std::env::var("PINATA_SECRET_KEY").ok();
             let stellar_secret = std::env::var("STELLAR_MAINNET_SECRET").ok();
-            let interface_address = std::env::var("DEFARM_OWNER_WALLET").unwrap_or_else(|_| {
-                "STELLAR_WALLET_PLACEHOLDER".to_string()
-            });
+            let interface_address = std::env::var("DEFARM_OWNER_WALLET")
+                .unwrap_or_else(|_| "STELLAR_WALLET_PLACEHOLDER".to_string());
             let source_account_identity = "defarm-admin-mainnet".to_string();
 
             (
@@ -268,6 +266,10 @@ impl StorageAdapter for StellarMainnetIpfsAdapter {
         AdapterType::StellarMainnetIpfs
     }
 
+    fn stellar_client(&self) -> Option<&StellarClient> {
+        Some(&self.stellar_client)
+    }
+
     async fn store_item(&self, item: &Item) -> Result<AdapterResult<String>, StorageError> {
         // Step 1: Upload item to IPFS
         let cid = self
@@ -371,8 +373,12 @@ impl StorageAdapter for StellarMainnetIpfsAdapter {
                 StorageError::WriteError(format!("Failed to register event on Stellar: {e}"))
             })?;
 
-        // Step 3: Create metadata
-        let metadata = self.create_metadata(&tx_hash, &cid);
+        // Step 3: Create metadata, referencing the event's attachments
+        let mut metadata = self.create_metadata(&tx_hash, &cid);
+        let pinned = pin_attachments(&self.ipfs_client, event).await;
+        metadata
+            .event_locations
+            .extend(attachment_locations(event, pinned));
 
         Ok(AdapterResult::new(event.event_id.to_string(), metadata))
     }
@@ -402,6 +408,19 @@ impl StorageAdapter for StellarMainnetIpfsAdapter {
         }
     }
 
+    /// Events are registered under `event:<item>:<event id>`
+    async fn find_stored_event(
+        &self,
+        event: &Event,
+        item_id: &str,
+    ) -> Result<Option<AdapterResult<String>>, StorageError> {
+        Ok(self
+            .get_event(&format!("event:{}:{}", item_id, event.event_id))
+            .await?
+            .filter(|stored| stored.data.content_hash == event.content_hash)
+            .map(|stored| AdapterResult::new(event.event_id.to_string(), stored.metadata)))
+    }
+
     async fn get_event(
         &self,
         event_id: &str,
@@ -485,6 +504,81 @@ impl StorageAdapter for StellarMainnetIpfsAdapter {
         })
     }
 
+    async fn dry_run_store_item(
+        &self,
+        item: &Item,
+        is_new_dfid: bool,
+    ) -> Result<AdapterDryRunReport, StorageError> {
+        let mut report = AdapterDryRunReport::new(AdapterType::StellarMainnetIpfs);
+
+        if !self.stellar_client.has_keypair() {
+            report.config_error("No Stellar keypair configured; transactions cannot be signed");
+        }
+        if is_new_dfid && self.stellar_client.get_nft_contract_address().is_none() {
+            report.config_error("No NFT contract configured; minting a new DFID would fail");
+        }
+
+        let ipfs_connected = self.ipfs_client.health_check().await.unwrap_or(false);
+        let stellar_connected = self.stellar_client.health_check().await.unwrap_or(false);
+        report.connectivity_ok = ipfs_connected && stellar_connected;
+        if !ipfs_connected {
+            report.errors.push("IPFS node is not reachable".to_string());
+        }
+        if !stellar_connected {
+            report
+                .errors
+                .push("Stellar mainnet Horizon is not reachable".to_string());
+        }
+
+        report.planned_writes.push(PlannedWrite {
+            target: "ipfs".to_string(),
+            operation: "upload_json".to_string(),
+            payload_bytes: Some(payload_size(item)?),
+            description: format!("Upload item {} and pin the resulting CID", item.dfid),
+        });
+
+        let mut invocations = 1;
+        if is_new_dfid {
+            invocations += 1;
+            report.planned_writes.push(PlannedWrite {
+                target: "stellar_nft".to_string(),
+                operation: "mint_nft".to_string(),
+                payload_bytes: None,
+                description: format!(
+                    "Mint NFT for {} on {}",
+                    item.dfid,
+                    self.stellar_client
+                        .get_nft_contract_address()
+                        .unwrap_or("unconfigured NFT contract")
+                ),
+            });
+        }
+
+        let operation = if self.use_onchain_storage {
+            "update_ipcm"
+        } else {
+            "emit_update_event"
+        };
+        report.planned_writes.push(PlannedWrite {
+            target: "stellar_ipcm".to_string(),
+            operation: operation.to_string(),
+            payload_bytes: None,
+            description: format!(
+                "Register the item CID for {} on IPCM contract {}",
+                item.dfid, self.contract_address
+            ),
+        });
+
+        match self.stellar_client.estimate_fee_stroops(invocations).await {
+            Ok(fee) => report.estimated_fee_stroops = Some(fee),
+            Err(e) => report
+                .warnings
+                .push(format!("Could not estimate Stellar fees: {e}")),
+        }
+
+        Ok(report)
+    }
+
     async fn health_check(&self) -> Result<bool, StorageError> {
         let ipfs_health = self.ipfs_client.health_check().await.unwrap_or(false);
         let stellar_health = self.stellar_client.health_check().await.unwrap_or(false);
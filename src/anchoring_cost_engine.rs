@@ -0,0 +1,649 @@
+//! Choosing when and how to anchor, and what anchoring costs.
+//!
+//! Each circuit may declare an [`AnchoringSla`]: how long pushed data may wait
+//! before it is on chain, and how much it may cost per anchored event. The
+//! optimizer turns the SLA, the circuit's recent event rate and the latest fee
+//! samples into a plan (network, fee percentile, batch size, flush interval).
+//! Every anchoring write is recorded with its estimated fee, which feeds the
+//! cost report and its monthly projection.
+
+use crate::stellar_client::{
+    StellarClient, StellarNetwork, BASE_FEE_STROOPS, MAINNET_IPCM_CONTRACT,
+    SOROBAN_RESOURCE_FEE_ESTIMATE_STROOPS, TESTNET_IPCM_CONTRACT,
+};
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    AnchoringCostPeriod, AnchoringCostRecord, AnchoringCostReport, AnchoringNetwork, AnchoringPlan,
+    AnchoringSla, Circuit, FeeSample, FeeStrategy, Permission,
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use uuid::Uuid;
+
+pub const STROOPS_PER_XLM: u64 = 10_000_000;
+/// Anchor delay assumed for circuits without an SLA
+pub const DEFAULT_MAX_ANCHOR_DELAY_SECS: u64 = 3600;
+/// Records anchored under one root at most
+pub const MAX_BATCH_SIZE: u64 = 1000;
+/// Part of the delay budget kept for the transaction to close (about two ledgers)
+pub const CONFIRMATION_ALLOWANCE_SECS: u64 = 10;
+const MIN_FLUSH_INTERVAL_SECS: u64 = 5;
+pub const MAX_REPORT_DAYS: u32 = 366;
+
+#[derive(Debug)]
+pub enum AnchoringCostError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+    NotFound(String),
+}
+
+impl From<StorageError> for AnchoringCostError {
+    fn from(err: StorageError) -> Self {
+        AnchoringCostError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for AnchoringCostError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnchoringCostError::StorageError(e) => write!(f, "Storage error: {e}"),
+            AnchoringCostError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            AnchoringCostError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            AnchoringCostError::NotFound(e) => write!(f, "Not found: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AnchoringCostError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnchoringSlaInput {
+    pub max_anchor_delay_secs: u64,
+    pub max_fee_per_event_stroops: Option<u64>,
+    #[serde(default)]
+    pub allowed_networks: Vec<AnchoringNetwork>,
+}
+
+/// Fees assumed for a network that has not been sampled yet
+fn base_fee_sample(network: AnchoringNetwork, now: DateTime<Utc>) -> FeeSample {
+    FeeSample {
+        network,
+        p10_stroops: BASE_FEE_STROOPS,
+        p50_stroops: BASE_FEE_STROOPS,
+        p90_stroops: BASE_FEE_STROOPS,
+        sampled_at: now,
+    }
+}
+
+/// Tight delays bid high so the batch is not left waiting for a ledger
+/// with room; loose delays can wait out fee spikes
+fn fee_strategy_for_delay(max_anchor_delay_secs: u64) -> FeeStrategy {
+    if max_anchor_delay_secs < 60 {
+        FeeStrategy::Priority
+    } else if max_anchor_delay_secs >= 3600 {
+        FeeStrategy::Economy
+    } else {
+        FeeStrategy::Standard
+    }
+}
+
+/// Plan the cheapest anchoring that meets `sla` at `events_per_hour`.
+/// `networks` are the candidates; networks missing from `fees` are priced
+/// at the base fee.
+pub fn optimize(
+    sla: &AnchoringSla,
+    networks: &[AnchoringNetwork],
+    fees: &[FeeSample],
+    events_per_hour: f64,
+    now: DateTime<Utc>,
+) -> Result<AnchoringPlan, AnchoringCostError> {
+    if networks.is_empty() {
+        return Err(AnchoringCostError::ValidationError(
+            "No network to anchor on".to_string(),
+        ));
+    }
+    let mut reasons = Vec::new();
+
+    let fee_strategy = fee_strategy_for_delay(sla.max_anchor_delay_secs);
+    reasons.push(format!(
+        "{fee_strategy:?} fees for a maximum delay of {}s",
+        sla.max_anchor_delay_secs
+    ));
+
+    let (network, fee_per_batch) = networks
+        .iter()
+        .map(|network| {
+            let sample = fees
+                .iter()
+                .find(|sample| sample.network == *network)
+                .cloned()
+                .unwrap_or_else(|| base_fee_sample(*network, now));
+            (
+                *network,
+                sample.inclusion_fee(fee_strategy) + SOROBAN_RESOURCE_FEE_ESTIMATE_STROOPS,
+            )
+        })
+        .min_by_key(|(_, fee)| *fee)
+        .expect("networks is not empty");
+    if networks.len() > 1 {
+        reasons.push(format!("{network:?} is the cheapest allowed network"));
+    }
+
+    let mut flush_interval_secs = sla
+        .max_anchor_delay_secs
+        .saturating_sub(CONFIRMATION_ALLOWANCE_SECS)
+        .max(MIN_FLUSH_INTERVAL_SECS);
+    let events_per_hour = events_per_hour.max(0.0);
+    let expected_per_interval = events_per_hour * flush_interval_secs as f64 / 3600.0;
+    let batch_size = if expected_per_interval > MAX_BATCH_SIZE as f64 {
+        // Batches fill up before the delay runs out; flush them when full
+        flush_interval_secs = ((MAX_BATCH_SIZE as f64 * 3600.0 / events_per_hour) as u64)
+            .max(MIN_FLUSH_INTERVAL_SECS);
+        reasons.push(format!(
+            "Batches reach {MAX_BATCH_SIZE} events every {flush_interval_secs}s"
+        ));
+        MAX_BATCH_SIZE
+    } else {
+        (expected_per_interval.ceil() as u64).max(1)
+    };
+    let fee_per_event = fee_per_batch.div_ceil(batch_size);
+
+    let within_fee_cap = match sla.max_fee_per_event_stroops {
+        Some(cap) if fee_per_event > cap => {
+            let needed = fee_per_batch.div_ceil(cap.max(1));
+            reasons.push(format!(
+                "Fee cap of {cap} stroops/event needs batches of {needed} events; \
+                 raise the maximum delay or the cap"
+            ));
+            false
+        }
+        _ => true,
+    };
+
+    Ok(AnchoringPlan {
+        circuit_id: sla.circuit_id,
+        network,
+        fee_strategy,
+        batch_size,
+        flush_interval_secs,
+        expected_events_per_hour: events_per_hour,
+        estimated_fee_per_batch_stroops: fee_per_batch,
+        estimated_fee_per_event_stroops: fee_per_event,
+        within_fee_cap,
+        reasons,
+        planned_at: now,
+    })
+}
+
+/// Record the spend of an anchoring write, priced with the network's latest
+/// fee sample at the standard percentile
+pub fn record_anchoring<S: StorageBackend + ?Sized>(
+    storage: &S,
+    circuit_id: Option<Uuid>,
+    network: AnchoringNetwork,
+    transactions: u32,
+    anchored_events: u64,
+    now: DateTime<Utc>,
+) -> Result<AnchoringCostRecord, StorageError> {
+    let sample = storage
+        .get_latest_fee_sample(&network)?
+        .unwrap_or_else(|| base_fee_sample(network, now));
+    let record = AnchoringCostRecord {
+        record_id: Uuid::new_v4(),
+        circuit_id,
+        network,
+        transactions,
+        anchored_events,
+        fee_stroops: u64::from(transactions)
+            * (sample.inclusion_fee(FeeStrategy::Standard) + SOROBAN_RESOURCE_FEE_ESTIMATE_STROOPS),
+        anchored_at: now,
+    };
+    storage.store_anchoring_cost_record(&record)?;
+    Ok(record)
+}
+
+fn fee_per_event(fee_stroops: u64, anchored_events: u64) -> Option<f64> {
+    (anchored_events > 0).then(|| fee_stroops as f64 / anchored_events as f64)
+}
+
+pub struct AnchoringCostEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> AnchoringCostEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    fn is_admin(&self, user_id: &str) -> Result<bool, AnchoringCostError> {
+        Ok(self
+            .storage
+            .get_user_account(user_id)?
+            .is_some_and(|account| account.is_admin))
+    }
+
+    fn member_circuit(
+        &self,
+        circuit_id: &Uuid,
+        user_id: &str,
+    ) -> Result<Circuit, AnchoringCostError> {
+        let circuit = self
+            .storage
+            .get_circuit(circuit_id)?
+            .ok_or_else(|| AnchoringCostError::NotFound(format!("Circuit {circuit_id}")))?;
+        if !circuit.is_member(user_id) && !self.is_admin(user_id)? {
+            return Err(AnchoringCostError::PermissionDenied(
+                "Not a member of this circuit".to_string(),
+            ));
+        }
+        Ok(circuit)
+    }
+
+    /// The circuit's SLA, or the default one if none was set
+    pub fn get_sla(
+        &self,
+        circuit_id: &Uuid,
+        user_id: &str,
+    ) -> Result<AnchoringSla, AnchoringCostError> {
+        let circuit = self.member_circuit(circuit_id, user_id)?;
+        self.sla_for(&circuit)
+    }
+
+    fn sla_for(&self, circuit: &Circuit) -> Result<AnchoringSla, AnchoringCostError> {
+        Ok(self
+            .storage
+            .get_anchoring_sla(&circuit.circuit_id)?
+            .unwrap_or_else(|| AnchoringSla {
+                circuit_id: circuit.circuit_id,
+                max_anchor_delay_secs: DEFAULT_MAX_ANCHOR_DELAY_SECS,
+                max_fee_per_event_stroops: None,
+                allowed_networks: Vec::new(),
+                updated_by: circuit.owner_id.clone(),
+                updated_at: circuit.created_timestamp,
+            }))
+    }
+
+    /// Owners, members allowed to manage permissions, and admins set SLAs
+    pub fn set_sla(
+        &self,
+        circuit_id: &Uuid,
+        input: AnchoringSlaInput,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<AnchoringSla, AnchoringCostError> {
+        let circuit = self
+            .storage
+            .get_circuit(circuit_id)?
+            .ok_or_else(|| AnchoringCostError::NotFound(format!("Circuit {circuit_id}")))?;
+        if circuit.owner_id != user_id
+            && !circuit.has_permission(user_id, &Permission::ManagePermissions)
+            && !self.is_admin(user_id)?
+        {
+            return Err(AnchoringCostError::PermissionDenied(
+                "Only circuit managers can set the anchoring SLA".to_string(),
+            ));
+        }
+        if input.max_anchor_delay_secs < MIN_FLUSH_INTERVAL_SECS + CONFIRMATION_ALLOWANCE_SECS {
+            return Err(AnchoringCostError::ValidationError(format!(
+                "max_anchor_delay_secs must be at least {}",
+                MIN_FLUSH_INTERVAL_SECS + CONFIRMATION_ALLOWANCE_SECS
+            )));
+        }
+        if input.max_fee_per_event_stroops == Some(0) {
+            return Err(AnchoringCostError::ValidationError(
+                "max_fee_per_event_stroops must be positive".to_string(),
+            ));
+        }
+
+        let mut allowed_networks = Vec::new();
+        for network in input.allowed_networks {
+            if !allowed_networks.contains(&network) {
+                allowed_networks.push(network);
+            }
+        }
+        let sla = AnchoringSla {
+            circuit_id: *circuit_id,
+            max_anchor_delay_secs: input.max_anchor_delay_secs,
+            max_fee_per_event_stroops: input.max_fee_per_event_stroops,
+            allowed_networks,
+            updated_by: user_id.to_string(),
+            updated_at: now,
+        };
+        self.storage.store_anchoring_sla(&sla)?;
+        Ok(sla)
+    }
+
+    /// Events recorded on the circuit's items over the last day, per hour
+    fn events_per_hour(
+        &self,
+        circuit_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<f64, AnchoringCostError> {
+        let dfids: HashSet<String> = self
+            .storage
+            .get_circuit_items(circuit_id)?
+            .into_iter()
+            .map(|item| item.dfid)
+            .collect();
+        if dfids.is_empty() {
+            return Ok(0.0);
+        }
+        let recent = self
+            .storage
+            .get_events_in_time_range(now - Duration::hours(24), now)?
+            .into_iter()
+            .filter(|event| dfids.contains(&event.dfid))
+            .count();
+        Ok(recent as f64 / 24.0)
+    }
+
+    pub fn plan(
+        &self,
+        circuit_id: &Uuid,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<AnchoringPlan, AnchoringCostError> {
+        let circuit = self.member_circuit(circuit_id, user_id)?;
+        let sla = self.sla_for(&circuit)?;
+        let networks = if sla.allowed_networks.is_empty() {
+            circuit
+                .adapter_config
+                .as_ref()
+                .and_then(|config| config.adapter_type.as_ref())
+                .and_then(AnchoringNetwork::for_adapter)
+                .into_iter()
+                .collect()
+        } else {
+            sla.allowed_networks.clone()
+        };
+        if networks.is_empty() {
+            return Err(AnchoringCostError::ValidationError(
+                "Circuit does not anchor on a blockchain network".to_string(),
+            ));
+        }
+
+        let mut fees = Vec::new();
+        for network in &networks {
+            if let Some(sample) = self.storage.get_latest_fee_sample(network)? {
+                fees.push(sample);
+            }
+        }
+        let events_per_hour = self.events_per_hour(circuit_id, now)?;
+        optimize(&sla, &networks, &fees, events_per_hour, now)
+    }
+
+    /// Daily anchoring spend over the last `days`. Circuit reports are open to
+    /// members; the report across all anchoring is admin-only.
+    pub fn cost_report(
+        &self,
+        circuit_id: Option<Uuid>,
+        network: Option<AnchoringNetwork>,
+        days: u32,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<AnchoringCostReport, AnchoringCostError> {
+        if days == 0 || days > MAX_REPORT_DAYS {
+            return Err(AnchoringCostError::ValidationError(format!(
+                "days must be between 1 and {MAX_REPORT_DAYS}"
+            )));
+        }
+        match circuit_id {
+            Some(circuit_id) => {
+                self.member_circuit(&circuit_id, user_id)?;
+            }
+            None => {
+                if !self.is_admin(user_id)? {
+                    return Err(AnchoringCostError::PermissionDenied(
+                        "Only admins can see spend across all circuits".to_string(),
+                    ));
+                }
+            }
+        }
+
+        let from = now - Duration::days(i64::from(days));
+        let mut by_day: BTreeMap<NaiveDate, (u64, u64, u64)> = BTreeMap::new();
+        for record in self.storage.list_anchoring_cost_records(from)? {
+            if record.anchored_at > now
+                || circuit_id.is_some_and(|id| record.circuit_id != Some(id))
+                || network.is_some_and(|n| record.network != n)
+            {
+                continue;
+            }
+            let day = by_day.entry(record.anchored_at.date_naive()).or_default();
+            day.0 += u64::from(record.transactions);
+            day.1 += record.anchored_events;
+            day.2 += record.fee_stroops;
+        }
+
+        let periods: Vec<AnchoringCostPeriod> = by_day
+            .into_iter()
+            .map(
+                |(date, (transactions, anchored_events, fee_stroops))| AnchoringCostPeriod {
+                    date,
+                    transactions,
+                    anchored_events,
+                    fee_stroops,
+                    fee_per_event_stroops: fee_per_event(fee_stroops, anchored_events),
+                },
+            )
+            .collect();
+        let total_transactions = periods.iter().map(|p| p.transactions).sum();
+        let total_anchored_events = periods.iter().map(|p| p.anchored_events).sum();
+        let total_fee_stroops: u64 = periods.iter().map(|p| p.fee_stroops).sum();
+        let projected_monthly_fee_stroops = total_fee_stroops * 30 / u64::from(days);
+
+        Ok(AnchoringCostReport {
+            circuit_id,
+            network,
+            from,
+            to: now,
+            periods,
+            total_transactions,
+            total_anchored_events,
+            total_fee_stroops,
+            fee_per_event_stroops: fee_per_event(total_fee_stroops, total_anchored_events),
+            projected_monthly_fee_stroops,
+            projected_monthly_xlm: projected_monthly_fee_stroops as f64 / STROOPS_PER_XLM as f64,
+            generated_at: now,
+        })
+    }
+}
+
+impl<S: StorageBackend + Send + 'static> AnchoringCostEngine<S> {
+    /// Sample both Stellar networks' fees every `tick`
+    pub fn spawn_fee_sampler(storage: S, tick: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let clients = [
+                (
+                    AnchoringNetwork::StellarTestnet,
+                    StellarClient::new(StellarNetwork::Testnet, TESTNET_IPCM_CONTRACT.to_string()),
+                ),
+                (
+                    AnchoringNetwork::StellarMainnet,
+                    StellarClient::new(StellarNetwork::Mainnet, MAINNET_IPCM_CONTRACT.to_string()),
+                ),
+            ];
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                for (network, client) in &clients {
+                    let stats = match client.fee_stats().await {
+                        Ok(stats) => stats,
+                        Err(e) => {
+                            tracing::warn!("⚠️  Fee sampling failed for {:?}: {}", network, e);
+                            continue;
+                        }
+                    };
+                    let sample = FeeSample {
+                        network: *network,
+                        p10_stroops: stats.p10,
+                        p50_stroops: stats.p50,
+                        p90_stroops: stats.p90,
+                        sampled_at: Utc::now(),
+                    };
+                    if let Err(e) = storage.store_fee_sample(&sample) {
+                        tracing::warn!("⚠️  Failed to store fee sample: {}", e);
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{AccountStatus, TierLimits, UserAccount, UserTier};
+    use std::sync::{Arc, Mutex};
+
+    fn sla(max_anchor_delay_secs: u64, cap: Option<u64>) -> AnchoringSla {
+        AnchoringSla {
+            circuit_id: Uuid::new_v4(),
+            max_anchor_delay_secs,
+            max_fee_per_event_stroops: cap,
+            allowed_networks: Vec::new(),
+            updated_by: "owner".to_string(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn fees(now: DateTime<Utc>) -> Vec<FeeSample> {
+        vec![
+            FeeSample {
+                network: AnchoringNetwork::StellarMainnet,
+                p10_stroops: 100,
+                p50_stroops: 5_000,
+                p90_stroops: 50_000,
+                sampled_at: now,
+            },
+            FeeSample {
+                network: AnchoringNetwork::StellarTestnet,
+                p10_stroops: 200,
+                p50_stroops: 200,
+                p90_stroops: 200,
+                sampled_at: now,
+            },
+        ]
+    }
+
+    const BOTH: [AnchoringNetwork; 2] = [
+        AnchoringNetwork::StellarTestnet,
+        AnchoringNetwork::StellarMainnet,
+    ];
+
+    #[test]
+    fn test_plan_batches_to_delay() {
+        let now = Utc::now();
+
+        // An hour of slack at 360 events/hour: economy fees, batches of ~359
+        let plan = optimize(&sla(3600, None), &BOTH, &fees(now), 360.0, now).unwrap();
+        assert_eq!(plan.fee_strategy, FeeStrategy::Economy);
+        assert_eq!(plan.network, AnchoringNetwork::StellarMainnet);
+        assert_eq!(plan.flush_interval_secs, 3590);
+        assert_eq!(plan.batch_size, 359);
+        assert!(plan.within_fee_cap);
+    }
+
+    #[test]
+    fn test_short_delay_cannot_meet_a_tight_fee_cap() {
+        let now = Utc::now();
+
+        // Thirty seconds of slack cannot spread the fee under a tight cap
+        let plan = optimize(&sla(30, Some(100)), &BOTH, &fees(now), 360.0, now).unwrap();
+        assert_eq!(plan.fee_strategy, FeeStrategy::Priority);
+        assert_eq!(plan.network, AnchoringNetwork::StellarTestnet);
+        assert_eq!(plan.batch_size, 2);
+        assert!(!plan.within_fee_cap);
+    }
+
+    #[test]
+    fn test_busy_circuit_fills_batches_before_the_delay() {
+        let now = Utc::now();
+
+        let plan = optimize(&sla(3600, None), &BOTH, &fees(now), 36_000.0, now).unwrap();
+        assert_eq!(plan.batch_size, MAX_BATCH_SIZE);
+        assert_eq!(plan.flush_interval_secs, 100);
+    }
+
+    #[test]
+    fn test_plan_needs_a_network() {
+        let now = Utc::now();
+
+        assert!(matches!(
+            optimize(&sla(3600, None), &[], &fees(now), 360.0, now),
+            Err(AnchoringCostError::ValidationError(_))
+        ));
+    }
+
+    /// Storage with an admin, the mainnet fee sample and three anchoring
+    /// transactions over two days
+    fn anchored(now: DateTime<Utc>) -> Arc<Mutex<InMemoryStorage>> {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let admin = UserAccount {
+            user_id: "admin".to_string(),
+            username: "admin".to_string(),
+            email: "admin@example.com".to_string(),
+            password_hash: "hash".to_string(),
+            limits: TierLimits::for_tier(&UserTier::Admin),
+            tier: UserTier::Admin,
+            status: AccountStatus::Active,
+            credits: 0,
+            created_at: now,
+            updated_at: now,
+            last_login: None,
+            subscription: None,
+            is_admin: true,
+            workspace_id: None,
+            available_adapters: None,
+            locale: None,
+        };
+        storage.store_user_account(&admin).unwrap();
+        storage.store_fee_sample(&fees(now)[0]).unwrap();
+        record_anchoring(
+            &storage,
+            None,
+            AnchoringNetwork::StellarMainnet,
+            2,
+            10,
+            now - Duration::days(1),
+        )
+        .unwrap();
+        record_anchoring(&storage, None, AnchoringNetwork::StellarMainnet, 1, 5, now).unwrap();
+        storage
+    }
+
+    #[test]
+    fn test_cost_report_sums_spend_per_day() {
+        let now = Utc::now();
+        let engine = AnchoringCostEngine::new(anchored(now));
+
+        let report = engine.cost_report(None, None, 30, "admin", now).unwrap();
+        let per_tx = 5_000 + SOROBAN_RESOURCE_FEE_ESTIMATE_STROOPS;
+        assert_eq!(report.periods.len(), 2);
+        assert_eq!(report.total_transactions, 3);
+        assert_eq!(report.total_anchored_events, 15);
+        assert_eq!(report.total_fee_stroops, 3 * per_tx);
+        assert_eq!(report.projected_monthly_fee_stroops, 3 * per_tx);
+    }
+
+    #[test]
+    fn test_invalid_cost_report_requests_are_refused() {
+        let now = Utc::now();
+        let engine = AnchoringCostEngine::new(anchored(now));
+
+        // Spend across all circuits is admin-only
+        assert!(matches!(
+            engine.cost_report(None, None, 30, "someone", now),
+            Err(AnchoringCostError::PermissionDenied(_))
+        ));
+        for days in [0, MAX_REPORT_DAYS + 1] {
+            assert!(matches!(
+                engine.cost_report(None, None, days, "admin", now),
+                Err(AnchoringCostError::ValidationError(_))
+            ));
+        }
+    }
+}
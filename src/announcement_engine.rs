@@ -0,0 +1,574 @@
+//! Admin-authored announcements (maintenance windows, new features, policy changes).
+//!
+//! An announcement targets everyone, a set of tiers, a workspace or a circuit's
+//! members. The audience is resolved when it is published, either immediately or
+//! by the scheduler once `publish_at` passes, and each recipient gets an
+//! `Announcement` notification. Read state is the notification's, so marking
+//! either one read is the same thing.
+
+use crate::live_stream::{LiveRecord, LiveStream};
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    AccountStatus, Announcement, AnnouncementAudience, AnnouncementCategory, AnnouncementStatus,
+    Notification, NotificationType,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+pub const MAX_TITLE_LEN: usize = 200;
+pub const MAX_MESSAGE_LEN: usize = 5000;
+
+#[derive(Debug)]
+pub enum AnnouncementError {
+    StorageError(StorageError),
+    ValidationError(String),
+    NotFound(String),
+    /// The announcement's status does not allow the operation
+    InvalidState(String),
+}
+
+impl From<StorageError> for AnnouncementError {
+    fn from(err: StorageError) -> Self {
+        AnnouncementError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for AnnouncementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnnouncementError::StorageError(e) => write!(f, "Storage error: {e}"),
+            AnnouncementError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            AnnouncementError::NotFound(e) => write!(f, "Not found: {e}"),
+            AnnouncementError::InvalidState(e) => write!(f, "Invalid state: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AnnouncementError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnnouncementInput {
+    pub title: String,
+    pub message: String,
+    pub category: Option<AnnouncementCategory>,
+    pub audience: AnnouncementAudience,
+    /// Defaults to now, publishing immediately
+    pub publish_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Changes to a scheduled announcement
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AnnouncementUpdate {
+    pub title: Option<String>,
+    pub message: Option<String>,
+    pub category: Option<AnnouncementCategory>,
+    pub audience: Option<AnnouncementAudience>,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Delivery and read counts of a published announcement
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct AnnouncementStats {
+    pub recipients: usize,
+    pub read: usize,
+    pub unread: usize,
+}
+
+/// An announcement as one recipient sees it
+#[derive(Debug, Clone, Serialize)]
+pub struct UserAnnouncement {
+    pub announcement_id: Uuid,
+    pub title: String,
+    pub message: String,
+    pub category: AnnouncementCategory,
+    pub published_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub notification_id: String,
+    pub read: bool,
+}
+
+pub struct AnnouncementEngine<S: StorageBackend> {
+    storage: S,
+    live_stream: Option<LiveStream>,
+}
+
+impl<S: StorageBackend> AnnouncementEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            live_stream: None,
+        }
+    }
+
+    /// Publish delivered notifications to `/api/stream` subscribers
+    pub fn with_live_stream(mut self, live_stream: LiveStream) -> Self {
+        self.live_stream = Some(live_stream);
+        self
+    }
+
+    pub fn create_announcement(
+        &self,
+        admin_user_id: &str,
+        input: AnnouncementInput,
+        now: DateTime<Utc>,
+    ) -> Result<Announcement, AnnouncementError> {
+        let announcement = Announcement {
+            announcement_id: Uuid::new_v4(),
+            title: input.title.trim().to_string(),
+            message: input.message.trim().to_string(),
+            category: input.category.unwrap_or(AnnouncementCategory::General),
+            audience: input.audience,
+            created_by: admin_user_id.to_string(),
+            publish_at: input.publish_at.unwrap_or(now),
+            expires_at: input.expires_at,
+            status: AnnouncementStatus::Scheduled,
+            published_at: None,
+            deliveries: HashMap::new(),
+            created_at: now,
+            updated_at: now,
+        };
+        self.validate(&announcement)?;
+        self.storage.store_announcement(&announcement)?;
+        Ok(announcement)
+    }
+
+    pub fn update_announcement(
+        &self,
+        announcement_id: &Uuid,
+        update: AnnouncementUpdate,
+        now: DateTime<Utc>,
+    ) -> Result<Announcement, AnnouncementError> {
+        let mut announcement = self.get_announcement(announcement_id)?;
+        if announcement.status != AnnouncementStatus::Scheduled {
+            return Err(AnnouncementError::InvalidState(
+                "Only scheduled announcements can be edited".to_string(),
+            ));
+        }
+        if let Some(title) = update.title {
+            announcement.title = title.trim().to_string();
+        }
+        if let Some(message) = update.message {
+            announcement.message = message.trim().to_string();
+        }
+        if let Some(category) = update.category {
+            announcement.category = category;
+        }
+        if let Some(audience) = update.audience {
+            announcement.audience = audience;
+        }
+        if let Some(publish_at) = update.publish_at {
+            announcement.publish_at = publish_at;
+        }
+        if let Some(expires_at) = update.expires_at {
+            announcement.expires_at = Some(expires_at);
+        }
+        self.validate(&announcement)?;
+        announcement.updated_at = now;
+        self.storage.store_announcement(&announcement)?;
+        Ok(announcement)
+    }
+
+    /// Stop a scheduled announcement from going out, or hide a published one from
+    /// recipients' announcement lists
+    pub fn cancel_announcement(
+        &self,
+        announcement_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Announcement, AnnouncementError> {
+        let mut announcement = self.get_announcement(announcement_id)?;
+        if announcement.status == AnnouncementStatus::Cancelled {
+            return Err(AnnouncementError::InvalidState(
+                "Announcement is already cancelled".to_string(),
+            ));
+        }
+        announcement.status = AnnouncementStatus::Cancelled;
+        announcement.updated_at = now;
+        self.storage.store_announcement(&announcement)?;
+        Ok(announcement)
+    }
+
+    pub fn get_announcement(
+        &self,
+        announcement_id: &Uuid,
+    ) -> Result<Announcement, AnnouncementError> {
+        self.storage
+            .get_announcement(announcement_id)?
+            .ok_or_else(|| AnnouncementError::NotFound(format!("Announcement {announcement_id}")))
+    }
+
+    /// All announcements, latest `publish_at` first
+    pub fn list_announcements(
+        &self,
+        status: Option<AnnouncementStatus>,
+    ) -> Result<Vec<Announcement>, AnnouncementError> {
+        Ok(self
+            .storage
+            .list_announcements()?
+            .into_iter()
+            .filter(|a| status.is_none_or(|status| a.status == status))
+            .collect())
+    }
+
+    pub fn stats(&self, announcement: &Announcement) -> AnnouncementStats {
+        let read = announcement
+            .deliveries
+            .values()
+            .filter(|notification_id| self.notification_read(notification_id))
+            .count();
+        AnnouncementStats {
+            recipients: announcement.deliveries.len(),
+            read,
+            unread: announcement.deliveries.len() - read,
+        }
+    }
+
+    /// Deliver a scheduled announcement to its audience now, returning the
+    /// notifications created
+    pub fn publish(
+        &self,
+        announcement_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Notification>, AnnouncementError> {
+        let mut announcement = self.get_announcement(announcement_id)?;
+        if announcement.status != AnnouncementStatus::Scheduled {
+            return Err(AnnouncementError::InvalidState(
+                "Only scheduled announcements can be published".to_string(),
+            ));
+        }
+
+        let mut notifications = Vec::new();
+        for user_id in self.resolve_recipients(&announcement.audience)? {
+            let notification = Notification::new(
+                user_id.clone(),
+                NotificationType::Announcement,
+                announcement.title.clone(),
+                announcement.message.clone(),
+                json!({
+                    "announcement_id": announcement.announcement_id,
+                    "category": announcement.category,
+                    "expires_at": announcement.expires_at,
+                }),
+            );
+            self.storage.store_notification(&notification)?;
+            if let Some(live_stream) = &self.live_stream {
+                live_stream.publish(LiveRecord::Notification(notification.clone()));
+            }
+            announcement
+                .deliveries
+                .insert(user_id, notification.id.clone());
+            notifications.push(notification);
+        }
+
+        announcement.status = AnnouncementStatus::Published;
+        announcement.published_at = Some(now);
+        announcement.updated_at = now;
+        self.storage.store_announcement(&announcement)?;
+        Ok(notifications)
+    }
+
+    /// Publish every scheduled announcement whose time has come
+    pub fn publish_due(&self, now: DateTime<Utc>) -> Result<Vec<Notification>, AnnouncementError> {
+        let mut notifications = Vec::new();
+        for announcement in self.list_announcements(Some(AnnouncementStatus::Scheduled))? {
+            if announcement.publish_at > now {
+                continue;
+            }
+            match self.publish(&announcement.announcement_id, now) {
+                Ok(delivered) => notifications.extend(delivered),
+                Err(e) => tracing::warn!(
+                    "⚠️  Failed to publish announcement {}: {}",
+                    announcement.announcement_id,
+                    e
+                ),
+            }
+        }
+        Ok(notifications)
+    }
+
+    /// Published, unexpired announcements delivered to a user
+    pub fn list_for_user(
+        &self,
+        user_id: &str,
+        unread_only: bool,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<UserAnnouncement>, AnnouncementError> {
+        Ok(self
+            .list_announcements(Some(AnnouncementStatus::Published))?
+            .into_iter()
+            .filter(|a| !a.is_expired(now))
+            .filter_map(|a| {
+                let notification_id = a.deliveries.get(user_id)?.clone();
+                let read = self.notification_read(&notification_id);
+                (!unread_only || !read).then_some(UserAnnouncement {
+                    announcement_id: a.announcement_id,
+                    title: a.title,
+                    message: a.message,
+                    category: a.category,
+                    published_at: a.published_at,
+                    expires_at: a.expires_at,
+                    notification_id,
+                    read,
+                })
+            })
+            .collect())
+    }
+
+    pub fn mark_read(
+        &self,
+        user_id: &str,
+        announcement_id: &Uuid,
+    ) -> Result<(), AnnouncementError> {
+        let announcement = self.get_announcement(announcement_id)?;
+        let not_delivered =
+            || AnnouncementError::NotFound(format!("Announcement {announcement_id}"));
+        let notification_id = announcement
+            .deliveries
+            .get(user_id)
+            .ok_or_else(not_delivered)?;
+        // A dismissed (deleted) notification already counts as read
+        if let Some(mut notification) = self.storage.get_notification(notification_id)? {
+            if !notification.read {
+                notification.mark_as_read();
+                self.storage.update_notification(&notification)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Run `publish_due` every `tick`, handing each batch of new notifications to
+    /// `on_published` (e.g. the WebSocket fan-out)
+    pub fn spawn_scheduler<F>(
+        self,
+        tick: std::time::Duration,
+        on_published: F,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        S: Send + Sync + 'static,
+        F: Fn(Vec<Notification>) + Send + 'static,
+    {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                match self.publish_due(Utc::now()) {
+                    Ok(notifications) if !notifications.is_empty() => {
+                        tracing::info!(
+                            "📣 Delivered {} announcement notifications",
+                            notifications.len()
+                        );
+                        on_published(notifications);
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("⚠️  Failed to list due announcements: {}", e),
+                }
+            }
+        })
+    }
+
+    fn notification_read(&self, notification_id: &str) -> bool {
+        match self.storage.get_notification(notification_id) {
+            Ok(Some(notification)) => notification.read,
+            Ok(None) => true,
+            Err(_) => false,
+        }
+    }
+
+    /// Active accounts in the audience; suspended and banned users are skipped
+    fn resolve_recipients(
+        &self,
+        audience: &AnnouncementAudience,
+    ) -> Result<Vec<String>, AnnouncementError> {
+        let users: Vec<_> = self
+            .storage
+            .list_user_accounts()?
+            .into_iter()
+            .filter(|u| !matches!(u.status, AccountStatus::Suspended | AccountStatus::Banned))
+            .collect();
+        let mut recipients: Vec<String> = match audience {
+            AnnouncementAudience::All => users.into_iter().map(|u| u.user_id).collect(),
+            AnnouncementAudience::Tiers { tiers } => users
+                .into_iter()
+                .filter(|u| tiers.contains(&u.tier))
+                .map(|u| u.user_id)
+                .collect(),
+            AnnouncementAudience::Workspace { workspace_id } => users
+                .into_iter()
+                .filter(|u| u.workspace_id.as_deref() == Some(workspace_id.as_str()))
+                .map(|u| u.user_id)
+                .collect(),
+            AnnouncementAudience::Circuit { circuit_id } => {
+                let circuit = self
+                    .storage
+                    .get_circuit(circuit_id)?
+                    .ok_or_else(|| AnnouncementError::NotFound(format!("Circuit {circuit_id}")))?;
+                let members: HashSet<String> =
+                    circuit.members.into_iter().map(|m| m.member_id).collect();
+                users
+                    .into_iter()
+                    .filter(|u| members.contains(&u.user_id))
+                    .map(|u| u.user_id)
+                    .collect()
+            }
+        };
+        recipients.sort();
+        Ok(recipients)
+    }
+
+    fn validate(&self, announcement: &Announcement) -> Result<(), AnnouncementError> {
+        let invalid = |msg: &str| Err(AnnouncementError::ValidationError(msg.to_string()));
+        if announcement.title.is_empty() || announcement.title.len() > MAX_TITLE_LEN {
+            return invalid(&format!(
+                "title must be between 1 and {MAX_TITLE_LEN} characters"
+            ));
+        }
+        if announcement.message.is_empty() || announcement.message.len() > MAX_MESSAGE_LEN {
+            return invalid(&format!(
+                "message must be between 1 and {MAX_MESSAGE_LEN} characters"
+            ));
+        }
+        if announcement
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= announcement.publish_at)
+        {
+            return invalid("expires_at must be after publish_at");
+        }
+        match &announcement.audience {
+            AnnouncementAudience::All => {}
+            AnnouncementAudience::Tiers { tiers } if tiers.is_empty() => {
+                return invalid("tiers audience needs at least one tier");
+            }
+            AnnouncementAudience::Tiers { .. } => {}
+            AnnouncementAudience::Workspace { workspace_id } if workspace_id.trim().is_empty() => {
+                return invalid("workspace_id is required");
+            }
+            AnnouncementAudience::Workspace { .. } => {}
+            AnnouncementAudience::Circuit { circuit_id } => {
+                if self.storage.get_circuit(circuit_id)?.is_none() {
+                    return Err(AnnouncementError::NotFound(format!("Circuit {circuit_id}")));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{TierLimits, UserAccount, UserTier};
+    use chrono::Duration;
+    use std::sync::{Arc, Mutex};
+
+    fn store_user(storage: &Arc<Mutex<InMemoryStorage>>, user_id: &str, tier: UserTier) {
+        let user = UserAccount {
+            user_id: user_id.to_string(),
+            username: user_id.to_string(),
+            email: format!("{user_id}@example.com"),
+            password_hash: "hash".to_string(),
+            limits: TierLimits::for_tier(&tier),
+            tier,
+            status: AccountStatus::Active,
+            credits: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_login: None,
+            subscription: None,
+            is_admin: false,
+            workspace_id: None,
+            available_adapters: None,
+            locale: None,
+        };
+        storage.store_user_account(&user).unwrap();
+    }
+
+    #[test]
+    fn test_scheduled_tier_announcement_delivery_and_reads() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        store_user(&storage, "alice", UserTier::Professional);
+        store_user(&storage, "bob", UserTier::Enterprise);
+        store_user(&storage, "carol", UserTier::Basic);
+        let engine = AnnouncementEngine::new(Arc::clone(&storage));
+
+        let now = Utc::now();
+        let announcement = engine
+            .create_announcement(
+                "admin",
+                AnnouncementInput {
+                    title: "Maintenance window".to_string(),
+                    message: "Storage adapters are read-only on Sunday 02:00 UTC".to_string(),
+                    category: Some(AnnouncementCategory::Maintenance),
+                    audience: AnnouncementAudience::Tiers {
+                        tiers: vec![UserTier::Professional, UserTier::Enterprise],
+                    },
+                    publish_at: Some(now + Duration::hours(1)),
+                    expires_at: Some(now + Duration::days(2)),
+                },
+                now,
+            )
+            .unwrap();
+        let id = announcement.announcement_id;
+
+        assert!(engine.publish_due(now).unwrap().is_empty());
+        assert!(engine
+            .list_for_user("alice", false, now)
+            .unwrap()
+            .is_empty());
+
+        let later = now + Duration::hours(2);
+        let delivered = engine.publish_due(later).unwrap();
+        let mut recipients: Vec<&str> = delivered.iter().map(|n| n.user_id.as_str()).collect();
+        recipients.sort();
+        assert_eq!(recipients, vec!["alice", "bob"]);
+        assert!(matches!(
+            engine.publish(&id, later),
+            Err(AnnouncementError::InvalidState(_))
+        ));
+
+        engine.mark_read("alice", &id).unwrap();
+        assert!(matches!(
+            engine.mark_read("carol", &id),
+            Err(AnnouncementError::NotFound(_))
+        ));
+        let published = engine.get_announcement(&id).unwrap();
+        assert_eq!(
+            engine.stats(&published),
+            AnnouncementStats {
+                recipients: 2,
+                read: 1,
+                unread: 1
+            }
+        );
+        assert!(engine
+            .list_for_user("alice", true, later)
+            .unwrap()
+            .is_empty());
+        assert_eq!(engine.list_for_user("bob", true, later).unwrap().len(), 1);
+        assert!(engine
+            .list_for_user("bob", false, now + Duration::days(3))
+            .unwrap()
+            .is_empty());
+
+        let invalid = engine.create_announcement(
+            "admin",
+            AnnouncementInput {
+                title: " ".to_string(),
+                message: "x".to_string(),
+                category: None,
+                audience: AnnouncementAudience::All,
+                publish_at: None,
+                expires_at: None,
+            },
+            now,
+        );
+        assert!(matches!(
+            invalid,
+            Err(AnnouncementError::ValidationError(_))
+        ));
+    }
+}
@@ -0,0 +1,538 @@
+//! Retention-aware anonymization of personal data.
+//!
+//! Items and events have to be kept for traceability long after the personal
+//! data in them may be. Instead of deleting them, a pass masks the values of
+//! personal data fields (`owner_name`, `email`, `cpf`, ...) in item data and
+//! event metadata once the record is older than the retention window, at any
+//! depth of nesting. DFIDs, identifiers, event links, content hashes and
+//! signatures are kept as recorded, so lineage and anchored proofs still
+//! resolve; the hashes attest to the original content, which can no longer be
+//! re-derived from what is stored.
+//!
+//! Every item touched gets an `Anonymized` event naming the fields masked (never
+//! their values), and GDPR compliance reports list those events as evidence.
+//! The scheduled pass uses the policy from the environment
+//! (`ANONYMIZATION_RETENTION_DAYS`, `ANONYMIZATION_PII_FIELDS`); admins can run
+//! it on demand, or as a dry run to see what would be masked.
+
+use crate::events_engine::EventsEngine;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    AuditSeverity, ComplianceFinding, Event, EventType, EventVisibility, Item, TimeAxis,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use uuid::Uuid;
+
+pub const DEFAULT_RETENTION_DAYS: u32 = 365;
+/// Personal data of the last month is never anonymized, whatever the policy
+pub const MIN_RETENTION_DAYS: u32 = 30;
+/// What a masked value is replaced with
+pub const ANONYMIZED_VALUE: &str = "[anonymized]";
+/// Source of the `Anonymized` events a pass records
+const ANONYMIZATION_SOURCE: &str = "anonymization";
+
+/// Field names treated as personal data unless the policy says otherwise;
+/// matched case-insensitively
+pub const DEFAULT_PII_FIELDS: [&str; 16] = [
+    "name",
+    "full_name",
+    "owner_name",
+    "farmer_name",
+    "producer_name",
+    "contact_name",
+    "email",
+    "phone",
+    "mobile",
+    "cpf",
+    "rg",
+    "national_id",
+    "tax_id",
+    "address",
+    "birth_date",
+    "date_of_birth",
+];
+
+#[derive(Debug)]
+pub enum AnonymizationError {
+    StorageError(StorageError),
+    ValidationError(String),
+    EventError(String),
+}
+
+impl From<StorageError> for AnonymizationError {
+    fn from(err: StorageError) -> Self {
+        AnonymizationError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for AnonymizationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnonymizationError::StorageError(e) => write!(f, "Storage error: {e}"),
+            AnonymizationError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            AnonymizationError::EventError(e) => write!(f, "Event error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AnonymizationError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnonymizationPolicy {
+    pub retention_days: u32,
+    /// Clock records are aged by; items by their latest activity on it
+    pub axis: TimeAxis,
+    pub pii_fields: Vec<String>,
+}
+
+impl Default for AnonymizationPolicy {
+    fn default() -> Self {
+        Self {
+            retention_days: DEFAULT_RETENTION_DAYS,
+            axis: TimeAxis::Occurred,
+            pii_fields: DEFAULT_PII_FIELDS.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+}
+
+impl AnonymizationPolicy {
+    /// `ANONYMIZATION_RETENTION_DAYS` and `ANONYMIZATION_PII_FIELDS` (comma
+    /// separated); unset or invalid values fall back to the defaults
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let pii_fields: Vec<String> = std::env::var("ANONYMIZATION_PII_FIELDS")
+            .map(|v| {
+                v.split(',')
+                    .map(|f| f.trim().to_string())
+                    .filter(|f| !f.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            retention_days: std::env::var("ANONYMIZATION_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.retention_days),
+            axis: default.axis,
+            pii_fields: if pii_fields.is_empty() {
+                default.pii_fields
+            } else {
+                pii_fields
+            },
+        }
+    }
+
+    fn validate(&self) -> Result<(), AnonymizationError> {
+        if self.retention_days < MIN_RETENTION_DAYS {
+            return Err(AnonymizationError::ValidationError(format!(
+                "Retention must be at least {MIN_RETENTION_DAYS} days"
+            )));
+        }
+        if self.pii_fields.iter().all(|f| f.trim().is_empty()) {
+            return Err(AnonymizationError::ValidationError(
+                "At least one personal data field is required".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn field_set(&self) -> HashSet<String> {
+        self.pii_fields
+            .iter()
+            .map(|f| f.trim().to_lowercase())
+            .filter(|f| !f.is_empty())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnonymizationReport {
+    pub run_id: Uuid,
+    pub run_at: DateTime<Utc>,
+    pub retention_days: u32,
+    pub dry_run: bool,
+    pub items_anonymized: usize,
+    pub events_anonymized: usize,
+    pub fields_masked: usize,
+    /// Items whose data or events were (or, in a dry run, would be) masked
+    pub dfids: Vec<String>,
+    /// The `Anonymized` events recorded; empty in a dry run
+    pub anonymized_event_ids: Vec<Uuid>,
+}
+
+/// What a pass masks on one item
+#[derive(Default)]
+struct ItemChanges {
+    item: Option<Item>,
+    events: Vec<Event>,
+    fields: BTreeSet<String>,
+    masked: usize,
+}
+
+pub struct AnonymizationEngine<S: StorageBackend> {
+    storage: S,
+    events: EventsEngine<S>,
+}
+
+impl<S: StorageBackend + Clone + 'static> AnonymizationEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            events: EventsEngine::new(storage.clone()),
+            storage,
+        }
+    }
+
+    /// Mask personal data in items and events older than the retention window.
+    /// A dry run changes nothing and reports what would be masked.
+    pub fn run(
+        &mut self,
+        policy: &AnonymizationPolicy,
+        dry_run: bool,
+        actor: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<AnonymizationReport, AnonymizationError> {
+        policy.validate()?;
+        let fields = policy.field_set();
+        let cutoff = now - Duration::days(i64::from(policy.retention_days));
+        let mut changes: BTreeMap<String, ItemChanges> = BTreeMap::new();
+
+        for mut item in self.storage.list_items()? {
+            let last_activity = match policy.axis {
+                TimeAxis::Recorded => item.last_modified,
+                TimeAxis::Occurred => item.last_occurred_at.unwrap_or(item.last_modified),
+            };
+            if last_activity >= cutoff {
+                continue;
+            }
+            let mut masked = BTreeSet::new();
+            let count = mask_map(&mut item.enriched_data, &fields, "", &mut masked);
+            if count > 0 {
+                let entry = changes.entry(item.dfid.clone()).or_default();
+                entry.fields.extend(masked);
+                entry.masked += count;
+                entry.item = Some(item);
+            }
+        }
+
+        for mut event in self.storage.list_events()? {
+            if event.event_type == EventType::Anonymized || event.time_on(policy.axis) >= cutoff {
+                continue;
+            }
+            let mut masked = BTreeSet::new();
+            let count = mask_map(&mut event.metadata, &fields, "", &mut masked);
+            if count > 0 {
+                let entry = changes.entry(event.dfid.clone()).or_default();
+                entry.fields.extend(masked);
+                entry.masked += count;
+                entry.events.push(event);
+            }
+        }
+
+        let mut report = AnonymizationReport {
+            run_id: Uuid::new_v4(),
+            run_at: now,
+            retention_days: policy.retention_days,
+            dry_run,
+            items_anonymized: 0,
+            events_anonymized: 0,
+            fields_masked: 0,
+            dfids: Vec::new(),
+            anonymized_event_ids: Vec::new(),
+        };
+        for (dfid, change) in changes {
+            report.items_anonymized += 1;
+            report.events_anonymized += change.events.len();
+            report.fields_masked += change.masked;
+            if !dry_run {
+                let event_id = self.apply(&dfid, change, &report, actor.as_deref())?;
+                report.anonymized_event_ids.push(event_id);
+            }
+            report.dfids.push(dfid);
+        }
+        Ok(report)
+    }
+
+    fn apply(
+        &mut self,
+        dfid: &str,
+        change: ItemChanges,
+        report: &AnonymizationReport,
+        actor: Option<&str>,
+    ) -> Result<Uuid, AnonymizationError> {
+        if let Some(mut item) = change.item {
+            item.last_modified = report.run_at;
+            self.storage.update_item(&item)?;
+        }
+        for event in &change.events {
+            self.storage.update_event(event)?;
+        }
+
+        let mut metadata = HashMap::from([
+            (
+                "run_id".to_string(),
+                Value::String(report.run_id.to_string()),
+            ),
+            (
+                "fields".to_string(),
+                serde_json::json!(change.fields.into_iter().collect::<Vec<_>>()),
+            ),
+            (
+                "fields_masked".to_string(),
+                serde_json::json!(change.masked),
+            ),
+            (
+                "events_anonymized".to_string(),
+                serde_json::json!(change.events.iter().map(|e| e.event_id).collect::<Vec<_>>()),
+            ),
+            (
+                "retention_days".to_string(),
+                serde_json::json!(report.retention_days),
+            ),
+        ]);
+        if let Some(actor) = actor {
+            metadata.insert("requested_by".to_string(), Value::String(actor.to_string()));
+        }
+        let result = self
+            .events
+            .create_event_with_metadata(
+                dfid.to_string(),
+                EventType::Anonymized,
+                ANONYMIZATION_SOURCE.to_string(),
+                EventVisibility::Private,
+                metadata,
+            )
+            .map_err(|e| AnonymizationError::EventError(e.to_string()))?;
+        Ok(result.event.event_id)
+    }
+}
+
+impl<S: StorageBackend + Clone + Send + Sync + 'static> AnonymizationEngine<S> {
+    /// Anonymize under the environment's policy every `tick`
+    pub fn spawn_scheduler(storage: S, tick: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let policy = AnonymizationPolicy::from_env();
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                let storage = storage.clone();
+                let policy = policy.clone();
+                let run = tokio::task::spawn_blocking(move || {
+                    AnonymizationEngine::new(storage).run(&policy, false, None, Utc::now())
+                })
+                .await;
+                match run {
+                    Ok(Ok(report)) if report.items_anonymized > 0 => tracing::info!(
+                        "🕶️  Anonymized {} fields on {} items ({} events)",
+                        report.fields_masked,
+                        report.items_anonymized,
+                        report.events_anonymized
+                    ),
+                    Ok(Ok(_)) => {}
+                    Ok(Err(e)) => tracing::warn!("⚠️  Anonymization failed: {}", e),
+                    Err(e) => tracing::warn!("⚠️  Anonymization task failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+/// Compliance finding listing the `Anonymized` events recorded in the period;
+/// `None` when there were none
+pub fn anonymization_finding<S: StorageBackend + ?Sized>(
+    storage: &S,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+) -> Result<Option<ComplianceFinding>, StorageError> {
+    let events: Vec<Event> = storage
+        .get_events_by_type(EventType::Anonymized)?
+        .into_iter()
+        .filter(|e| e.timestamp >= period_start && e.timestamp <= period_end)
+        .collect();
+    if events.is_empty() {
+        return Ok(None);
+    }
+    let items: HashSet<&str> = events.iter().map(|e| e.dfid.as_str()).collect();
+    let fields_masked: u64 = events
+        .iter()
+        .filter_map(|e| e.metadata.get("fields_masked").and_then(Value::as_u64))
+        .sum();
+    Ok(Some(ComplianceFinding {
+        finding_id: Uuid::new_v4(),
+        finding_type: "data_anonymization".to_string(),
+        description: format!(
+            "Personal data past retention was anonymized on {} items ({} fields masked)",
+            items.len(),
+            fields_masked
+        ),
+        severity: AuditSeverity::Low,
+        evidence: events.iter().map(|e| e.event_id).collect(),
+        recommendation: None,
+    }))
+}
+
+/// Mask personal data fields of `map` and of objects nested in it; returns how
+/// many values were masked and adds their dotted paths to `masked`
+fn mask_map(
+    map: &mut HashMap<String, Value>,
+    fields: &HashSet<String>,
+    prefix: &str,
+    masked: &mut BTreeSet<String>,
+) -> usize {
+    map.iter_mut()
+        .map(|(key, value)| mask_entry(key, value, fields, prefix, masked))
+        .sum()
+}
+
+fn mask_entry(
+    key: &str,
+    value: &mut Value,
+    fields: &HashSet<String>,
+    prefix: &str,
+    masked: &mut BTreeSet<String>,
+) -> usize {
+    let path = if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{prefix}.{key}")
+    };
+    if fields.contains(&key.to_lowercase()) {
+        return mask_value(value, &path, masked);
+    }
+    match value {
+        Value::Object(object) => object
+            .iter_mut()
+            .map(|(key, value)| mask_entry(key, value, fields, &path, masked))
+            .sum(),
+        Value::Array(values) => values
+            .iter_mut()
+            .map(|value| match value {
+                Value::Object(object) => object
+                    .iter_mut()
+                    .map(|(key, value)| mask_entry(key, value, fields, &path, masked))
+                    .sum(),
+                _ => 0,
+            })
+            .sum(),
+        _ => 0,
+    }
+}
+
+/// Replace a personal data value, leaving nulls and already masked values be
+fn mask_value(value: &mut Value, path: &str, masked: &mut BTreeSet<String>) -> usize {
+    match value {
+        Value::Null => 0,
+        Value::String(s) if s == ANONYMIZED_VALUE => 0,
+        _ => {
+            *value = Value::String(ANONYMIZED_VALUE.to_string());
+            masked.insert(path.to_string());
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identifier_types::Identifier;
+    use crate::storage::InMemoryStorage;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_anonymization_masks_expired_pii_and_keeps_links() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let now = Utc::now();
+        let long_ago = now - Duration::days(800);
+
+        let mut old = Item::new(
+            "DFID-OLD".to_string(),
+            vec![Identifier::new("lot", "L-1")],
+            Uuid::new_v4(),
+        );
+        old.last_modified = long_ago;
+        old.enriched_data = HashMap::from([
+            ("owner_name".to_string(), serde_json::json!("Maria Silva")),
+            ("weight_kg".to_string(), serde_json::json!(1200)),
+            (
+                "buyer".to_string(),
+                serde_json::json!({"Email": "maria@farm.example", "country": "BR"}),
+            ),
+        ]);
+        storage.store_item(&old).unwrap();
+        let mut recent = Item::new(
+            "DFID-NEW".to_string(),
+            vec![Identifier::new("lot", "L-2")],
+            Uuid::new_v4(),
+        );
+        recent.enriched_data =
+            HashMap::from([("owner_name".to_string(), serde_json::json!("João Souza"))]);
+        storage.store_item(&recent).unwrap();
+
+        let mut events = EventsEngine::new(Arc::clone(&storage));
+        let mut event = events
+            .create_event_with_metadata(
+                "DFID-OLD".to_string(),
+                EventType::Enriched,
+                "test".to_string(),
+                EventVisibility::Private,
+                HashMap::from([("phone".to_string(), serde_json::json!("+55 11 99999"))]),
+            )
+            .unwrap()
+            .event;
+        event.timestamp = long_ago;
+        storage.update_event(&event).unwrap();
+
+        let policy = AnonymizationPolicy {
+            retention_days: 365,
+            axis: TimeAxis::Recorded,
+            ..Default::default()
+        };
+        let mut engine = AnonymizationEngine::new(Arc::clone(&storage));
+        let preview = engine.run(&policy, true, None, now).unwrap();
+        assert_eq!(preview.dfids, vec!["DFID-OLD".to_string()]);
+        assert_eq!(preview.fields_masked, 3);
+        let untouched = storage.get_item_by_dfid("DFID-OLD").unwrap().unwrap();
+        assert_eq!(untouched.enriched_data["owner_name"], "Maria Silva");
+
+        let report = engine
+            .run(&policy, false, Some("admin".to_string()), now)
+            .unwrap();
+        assert_eq!((report.items_anonymized, report.events_anonymized), (1, 1));
+        let item = storage.get_item_by_dfid("DFID-OLD").unwrap().unwrap();
+        assert_eq!(item.enriched_data["owner_name"], ANONYMIZED_VALUE);
+        assert_eq!(item.enriched_data["buyer"]["Email"], ANONYMIZED_VALUE);
+        assert_eq!(item.enriched_data["buyer"]["country"], "BR");
+        assert_eq!(item.enriched_data["weight_kg"], 1200);
+        assert_eq!(item.identifiers, old.identifiers);
+        let masked = storage.get_event(&event.event_id).unwrap().unwrap();
+        assert_eq!(masked.metadata["phone"], ANONYMIZED_VALUE);
+        assert_eq!(masked.content_hash, event.content_hash);
+        let recent = storage.get_item_by_dfid("DFID-NEW").unwrap().unwrap();
+        assert_eq!(recent.enriched_data["owner_name"], "João Souza");
+
+        let anonymized = storage
+            .get_event(&report.anonymized_event_ids[0])
+            .unwrap()
+            .unwrap();
+        assert_eq!(anonymized.event_type, EventType::Anonymized);
+        assert!(!serde_json::to_string(&anonymized.metadata)
+            .unwrap()
+            .contains("Maria"));
+        // A second pass finds nothing left to mask
+        assert_eq!(
+            engine
+                .run(&policy, false, None, now)
+                .unwrap()
+                .items_anonymized,
+            0
+        );
+
+        let finding =
+            anonymization_finding(&storage, now - Duration::days(1), now + Duration::days(1))
+                .unwrap()
+                .unwrap();
+        assert_eq!(finding.evidence, report.anonymized_event_ids);
+    }
+}
@@ -0,0 +1,163 @@
+//! Reports of who has access to a circuit, generated on demand or on a
+//! per-circuit review schedule.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::access_review_engine::{
+    AccessReviewEngine, AccessReviewError, AccessReviewScheduleInput,
+};
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::api_key_storage::ApiKeyStorage;
+use crate::auth_middleware::AuthenticatedUser;
+
+/// Mounted at `/api/access-reviews`
+pub fn access_review_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/circuits/:circuit_id/reports",
+            get(list_reports).post(generate_report),
+        )
+        .route("/circuits/:circuit_id/reports/:report_id", get(get_report))
+        .route(
+            "/circuits/:circuit_id/schedule",
+            get(get_schedule).put(set_schedule).delete(delete_schedule),
+        )
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> AccessReviewEngine<SharedStorage> {
+    AccessReviewEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn access_review_error_response(e: AccessReviewError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        AccessReviewError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        AccessReviewError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        AccessReviewError::NotFound(_) => StatusCode::NOT_FOUND,
+        AccessReviewError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn generate_report(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let api_keys = app_state
+        .api_key_storage
+        .list_api_keys()
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Failed to load API keys: {e}")})),
+            )
+        })?;
+    let report = engine(&app_state)
+        .generate_report(&circuit_id, &user_id, &api_keys, Utc::now())
+        .map_err(access_review_error_response)?;
+
+    tracing::info!(
+        "🔐 Access review of circuit {} generated by {} ({} grants)",
+        circuit_id,
+        user_id,
+        report.grants.len()
+    );
+    Ok(Json(json!({
+        "success": true,
+        "report": report
+    })))
+}
+
+async fn list_reports(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let reports = engine(&app_state)
+        .list_reports(&circuit_id, &user_id)
+        .map_err(access_review_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": reports.len(),
+        "reports": reports
+    })))
+}
+
+async fn get_report(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path((circuit_id, report_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let report = engine(&app_state)
+        .get_report(&circuit_id, &report_id, &user_id)
+        .map_err(access_review_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "report": report
+    })))
+}
+
+async fn get_schedule(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let schedule = engine(&app_state)
+        .get_schedule(&circuit_id, &user_id)
+        .map_err(access_review_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "schedule": schedule
+    })))
+}
+
+async fn set_schedule(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+    Json(input): Json<AccessReviewScheduleInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let schedule = engine(&app_state)
+        .set_schedule(&circuit_id, input, &user_id, Utc::now())
+        .map_err(access_review_error_response)?;
+
+    tracing::info!(
+        "🔐 Access reviews of circuit {} scheduled every {} days by {}",
+        circuit_id,
+        schedule.interval_days,
+        user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "schedule": schedule
+    })))
+}
+
+async fn delete_schedule(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    engine(&app_state)
+        .delete_schedule(&circuit_id, &user_id)
+        .map_err(access_review_error_response)?;
+
+    Ok(Json(json!({
+        "success": true
+    })))
+}
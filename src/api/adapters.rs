@@ -2,23 +2,32 @@ use axum::{
     extract::{Extension, Path, Query, State},
     http::StatusCode,
     response::Json,
-    routing::{get, post},
+    routing::{get, post, put},
     Router,
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
 
+use crate::adapter_manager::{AdapterManager, AdapterManagerError};
+use crate::adapters::metrics::{adapter_metrics, all_adapter_metrics};
 use crate::adapters::{
-    AdapterInstance, IpfsIpfsAdapter, StellarMainnetIpfsAdapter, StellarTestnetIpfsAdapter,
-    StorageAdapter,
+    AdapterInstance, IpfsIpfsAdapter, LocalStellarAdapter, StellarMainnetIpfsAdapter,
+    StellarTestnetIpfsAdapter, StorageAdapter,
 };
+use crate::api::admin::{verify_admin, CreateAdapterConfigRequest, UpdateAdapterConfigRequest};
 use crate::api::auth::Claims;
 use crate::api::shared_state::AppState;
+use crate::logging::LoggingEngine;
+use crate::postgres_storage_with_cache::PostgresStorageWithCache;
 use crate::storage::{StorageBackend, StorageError};
 use crate::storage_helpers::{with_storage, StorageLockError};
-use crate::types::{AdapterType, StorageBackendType, UserTier};
+use crate::types::{AdapterMetrics, AdapterType, StorageBackendType, UserTier};
+
+// Type alias matching SharedStorage from shared_state
+type SharedStorage = Arc<Mutex<PostgresStorageWithCache>>;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AdapterInfo {
@@ -200,15 +209,15 @@ async fn select_adapter(
 async fn get_adapter_status(
     Path(adapter_type_str): Path<String>,
     Query(_params): Query<AdapterQuery>,
-    State(_app_state): State<Arc<AppState>>,
+    State(app_state): State<Arc<AppState>>,
 ) -> Result<Json<Value>, StatusCode> {
     let adapter_type = match AdapterType::from_string(&adapter_type_str) {
         Ok(adapter_type) => adapter_type,
         Err(_) => return Err(StatusCode::BAD_REQUEST),
     };
 
-    // Create a temporary adapter instance to check status
-    let adapter_instance = match create_adapter_instance(&adapter_type) {
+    // Prefer the hot-registered instance, otherwise build a temporary one
+    let adapter_instance = match resolve_adapter_instance(&app_state, &adapter_type).await {
         Ok(instance) => instance,
         Err(e) => {
             return Ok(Json(json!({
@@ -235,14 +244,14 @@ async fn get_adapter_status(
 
 async fn health_check_adapter(
     Path(adapter_type_str): Path<String>,
-    State(_app_state): State<Arc<AppState>>,
+    State(app_state): State<Arc<AppState>>,
 ) -> Result<Json<Value>, StatusCode> {
     let adapter_type = match AdapterType::from_string(&adapter_type_str) {
         Ok(adapter_type) => adapter_type,
         Err(_) => return Err(StatusCode::BAD_REQUEST),
     };
 
-    let adapter_instance = match create_adapter_instance(&adapter_type) {
+    let adapter_instance = match resolve_adapter_instance(&app_state, &adapter_type).await {
         Ok(instance) => instance,
         Err(e) => {
             return Ok(Json(json!({
@@ -253,23 +262,85 @@ async fn health_check_adapter(
         }
     };
 
-    match adapter_instance.health_check().await {
+    let result = adapter_instance.health_check().await;
+    let metrics = adapter_instance.adapter_metrics();
+
+    match result {
         Ok(healthy) => Ok(Json(json!({
             "success": true,
             "adapter_type": adapter_type,
             "healthy": healthy,
-            "checked_at": Utc::now()
+            "checked_at": Utc::now(),
+            "metrics": metrics_summary(&metrics)
         }))),
         Err(e) => Ok(Json(json!({
             "success": false,
             "adapter_type": adapter_type,
             "healthy": false,
             "error": format!("Health check failed: {}", e),
-            "checked_at": Utc::now()
+            "checked_at": Utc::now(),
+            "metrics": metrics_summary(&metrics)
         }))),
     }
 }
 
+fn metrics_summary(metrics: &AdapterMetrics) -> Value {
+    let last_error = metrics.last_error().map(|(operation, op)| {
+        json!({
+            "operation": operation,
+            "error": op.last_error,
+            "at": op.last_error_at
+        })
+    });
+
+    json!({
+        "adapter_type": metrics.adapter_type,
+        "since": metrics.since,
+        "total_calls": metrics.total_calls(),
+        "total_failures": metrics.total_failures(),
+        "error_rate": metrics.error_rate(),
+        "last_error": last_error,
+        "operations": metrics.operations.iter().map(|(name, op)| {
+            (name.clone(), json!({
+                "calls": op.calls,
+                "successes": op.successes,
+                "failures": op.failures,
+                "average_latency_ms": op.average_latency_ms(),
+                "max_latency_ms": op.max_latency_ms,
+                "last_latency_ms": op.last_latency_ms,
+                "error_rate": op.error_rate(),
+                "last_error": op.last_error,
+                "last_error_at": op.last_error_at,
+                "last_success_at": op.last_success_at
+            }))
+        }).collect::<serde_json::Map<String, Value>>()
+    })
+}
+
+async fn list_adapter_metrics(
+    State(_app_state): State<Arc<AppState>>,
+) -> Result<Json<Value>, StatusCode> {
+    let metrics: Vec<Value> = all_adapter_metrics().iter().map(metrics_summary).collect();
+
+    Ok(Json(json!({
+        "success": true,
+        "adapters": metrics
+    })))
+}
+
+async fn get_adapter_metrics(
+    Path(adapter_type_str): Path<String>,
+    State(_app_state): State<Arc<AppState>>,
+) -> Result<Json<Value>, StatusCode> {
+    let adapter_type =
+        AdapterType::from_string(&adapter_type_str).map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "metrics": metrics_summary(&adapter_metrics(&adapter_type))
+    })))
+}
+
 async fn get_adapter_templates() -> Result<Json<Value>, StatusCode> {
     let templates = json!({
         "local-local": {
@@ -354,17 +425,247 @@ pub fn create_adapter_instance(
         AdapterType::StellarMainnetIpfs => Ok(AdapterInstance::StellarMainnetIpfs(
             StellarMainnetIpfsAdapter::new()?,
         )),
+        AdapterType::StellarTestnetLocal | AdapterType::StellarMainnetLocal => {
+            Ok(AdapterInstance::LocalStellar(
+                LocalStellarAdapter::new_with_config(adapter_type.clone(), None)?,
+            ))
+        }
         AdapterType::Custom(_) => Ok(AdapterInstance::IpfsIpfs(IpfsIpfsAdapter::new()?)), // Fallback to IpfsIpfs
         _ => Ok(AdapterInstance::IpfsIpfs(IpfsIpfsAdapter::new()?)), // Fallback to IpfsIpfs
     }
 }
 
+/// Adapter registered at runtime for this type, or a fresh instance from the environment
+async fn resolve_adapter_instance(
+    app_state: &AppState,
+    adapter_type: &AdapterType,
+) -> Result<AdapterInstance, StorageError> {
+    if let Some(instance) = app_state
+        .adapter_registry
+        .read()
+        .await
+        .get_adapter(adapter_type)
+    {
+        return Ok(instance.clone());
+    }
+    create_adapter_instance(adapter_type)
+}
+
+// ============================================================================
+// RUNTIME ADAPTER REGISTRATION
+// ============================================================================
+
+fn require_admin(
+    app_state: &Arc<AppState>,
+    claims: Option<Extension<Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+) -> Result<String, (StatusCode, Json<Value>)> {
+    let user_id = if let Some(Extension(claims)) = claims {
+        claims.user_id.clone()
+    } else if let Some(Extension(ctx)) = api_key_ctx {
+        ctx.user_id.to_string()
+    } else {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({"error": "Authentication required. Use JWT token or API key."})),
+        ));
+    };
+
+    verify_admin(&user_id, app_state)?;
+    Ok(user_id)
+}
+
+fn parse_config_id(config_id: &str) -> Result<Uuid, (StatusCode, Json<Value>)> {
+    Uuid::parse_str(config_id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Invalid UUID format"})),
+        )
+    })
+}
+
+fn adapter_manager(app_state: &AppState) -> AdapterManager<SharedStorage> {
+    let logger = Arc::new(Mutex::new(LoggingEngine::new()));
+    AdapterManager::new(Arc::clone(&app_state.shared_storage), logger)
+}
+
+fn adapter_manager_error_response(e: AdapterManagerError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        AdapterManagerError::NotFound => StatusCode::NOT_FOUND,
+        AdapterManagerError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        AdapterManagerError::DuplicateName(_) => StatusCode::CONFLICT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn create_runtime_config(
+    State(app_state): State<Arc<AppState>>,
+    claims: Option<Extension<Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+    Json(request): Json<CreateAdapterConfigRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let admin_user_id = require_admin(&app_state, claims, api_key_ctx)?;
+
+    let config = adapter_manager(&app_state)
+        .create_adapter_config(
+            request.name,
+            request.description,
+            request.adapter_type,
+            request.connection_details,
+            request.contract_configs,
+            admin_user_id,
+        )
+        .map_err(adapter_manager_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Adapter configuration created; test and activate it to register",
+        "config": config
+    })))
+}
+
+async fn update_runtime_config(
+    Path(config_id): Path<String>,
+    State(app_state): State<Arc<AppState>>,
+    claims: Option<Extension<Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+    Json(request): Json<UpdateAdapterConfigRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    require_admin(&app_state, claims, api_key_ctx)?;
+    let config_uuid = parse_config_id(&config_id)?;
+
+    let mut manager = adapter_manager(&app_state);
+    let config = manager
+        .update_adapter_config(
+            &config_uuid,
+            request.name,
+            request.description,
+            request.connection_details,
+            request.contract_configs,
+            request.is_active,
+        )
+        .map_err(adapter_manager_error_response)?;
+
+    // Keep the registry in sync when the registered configuration changes
+    let mut registry = app_state.adapter_registry.write().await;
+    let registered = registry
+        .registered_adapters()
+        .into_iter()
+        .any(|(_, id)| id == Some(config_uuid));
+    let mut reregistered = false;
+    if registered {
+        if config.is_active {
+            let instance = manager
+                .create_adapter_instance_for_config(&config)
+                .map_err(adapter_manager_error_response)?;
+            registry.register_configured_adapter(instance, config_uuid);
+            reregistered = true;
+        } else {
+            registry.unregister_adapter(&config.adapter_type);
+        }
+    }
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Adapter configuration updated successfully",
+        "reregistered": reregistered,
+        "config": config
+    })))
+}
+
+async fn test_runtime_config(
+    Path(config_id): Path<String>,
+    State(app_state): State<Arc<AppState>>,
+    claims: Option<Extension<Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    require_admin(&app_state, claims, api_key_ctx)?;
+    let config_uuid = parse_config_id(&config_id)?;
+
+    let result = adapter_manager(&app_state)
+        .test_adapter(&config_uuid)
+        .await
+        .map_err(adapter_manager_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "test_result": result
+    })))
+}
+
+async fn activate_runtime_config(
+    Path(config_id): Path<String>,
+    State(app_state): State<Arc<AppState>>,
+    claims: Option<Extension<Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    require_admin(&app_state, claims, api_key_ctx)?;
+    let config_uuid = parse_config_id(&config_id)?;
+
+    let (config, instance) = adapter_manager(&app_state)
+        .activate_adapter_config(&config_uuid)
+        .map_err(adapter_manager_error_response)?;
+
+    app_state
+        .adapter_registry
+        .write()
+        .await
+        .register_configured_adapter(instance, config_uuid);
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Adapter configuration activated and registered",
+        "adapter_type": config.adapter_type,
+        "config": config
+    })))
+}
+
+async fn list_registered_adapters(
+    State(app_state): State<Arc<AppState>>,
+    claims: Option<Extension<Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    require_admin(&app_state, claims, api_key_ctx)?;
+
+    let registered: Vec<Value> = app_state
+        .adapter_registry
+        .read()
+        .await
+        .registered_adapters()
+        .into_iter()
+        .map(|(adapter_type, config_id)| {
+            json!({
+                "adapter_type": adapter_type,
+                "config_id": config_id
+            })
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "success": true,
+        "count": registered.len(),
+        "adapters": registered
+    })))
+}
+
 pub fn adapter_routes(app_state: Arc<AppState>) -> Router {
     Router::new()
         .route("/", get(list_available_adapters))
         .route("/select", post(select_adapter))
         .route("/templates", get(get_adapter_templates))
         .route("/:adapter_type/status", get(get_adapter_status))
+        .route("/metrics", get(list_adapter_metrics))
         .route("/:adapter_type/health", get(health_check_adapter))
+        .route("/:adapter_type/metrics", get(get_adapter_metrics))
+        // Runtime adapter registration (admin only)
+        .route("/configs", post(create_runtime_config))
+        .route("/configs/:config_id", put(update_runtime_config))
+        .route("/configs/:config_id/test", post(test_runtime_config))
+        .route(
+            "/configs/:config_id/activate",
+            post(activate_runtime_config),
+        )
+        .route("/registry", get(list_registered_adapters))
         .with_state(app_state)
 }
@@ -1,6 +1,7 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
+    middleware,
     response::Json,
     routing::{get, post, put},
     Extension, Router,
@@ -15,7 +16,9 @@ use crate::adapter_manager::AdapterManager;
 use crate::api::auth::validate_password_complexity;
 use crate::api::auth::Claims;
 use crate::api::shared_state::AppState;
+use crate::auth_middleware::{admin_auth_middleware, AdminUser};
 use crate::credit_manager::CreditEngine;
+use crate::i18n::{LocaleError, LocalePack, Localizer};
 use crate::logging::LoggingEngine;
 use crate::storage::StorageBackend;
 use crate::storage_helpers::{with_storage, StorageLockError};
@@ -30,7 +33,10 @@ use bcrypt::{hash, DEFAULT_COST};
 // ============================================================================
 
 /// Verify that the authenticated user is an admin
-fn verify_admin(user_id: &str, app_state: &Arc<AppState>) -> Result<(), (StatusCode, Json<Value>)> {
+pub(crate) fn verify_admin(
+    user_id: &str,
+    app_state: &Arc<AppState>,
+) -> Result<(), (StatusCode, Json<Value>)> {
     let user = with_storage(
         &app_state.shared_storage,
         "admin::verify_admin::get_user",
@@ -86,6 +92,12 @@ pub struct UpdateUserRequest {
     pub available_adapters: Option<Vec<AdapterType>>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TierChangeRequest {
+    pub tier: UserTier,
+    pub reason: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreditAdjustmentRequest {
     pub amount: i64,
@@ -219,6 +231,7 @@ async fn create_user(
         is_admin: false,
         workspace_id: request.workspace_id.clone(),
         available_adapters: None, // Use tier defaults
+        locale: None,
     };
 
     // Check if username or email already exists, then store user and record action
@@ -1293,11 +1306,32 @@ async fn get_admin_actions(
         .get("limit")
         .and_then(|s| s.parse().ok())
         .unwrap_or(100);
+    let action_type = params.get("action_type").map(|s| s.as_str());
+    if let Some(action_type) = action_type {
+        if serde_json::from_value::<AdminActionType>(json!(action_type)).is_err() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": format!("Unknown action_type '{}'", action_type)})),
+            ));
+        }
+    }
 
     let actions = with_storage(
         &app_state.shared_storage,
         "admin::get_admin_actions::retrieve",
-        |storage| Ok(storage.get_admin_actions(admin_id, Some(limit))?),
+        |storage| match action_type {
+            Some(action_type) => {
+                let mut actions: Vec<AdminAction> = storage
+                    .get_admin_actions_by_type(action_type)?
+                    .into_iter()
+                    .filter(|a| admin_id.is_none_or(|id| a.admin_user_id == id))
+                    .collect();
+                actions.sort_by_key(|a| std::cmp::Reverse(a.timestamp));
+                actions.truncate(limit);
+                Ok(actions)
+            }
+            None => Ok(storage.get_admin_actions(admin_id, Some(limit))?),
+        },
     )
     .map_err(|e| match e {
         StorageLockError::Timeout => (
@@ -1317,6 +1351,139 @@ async fn get_admin_actions(
     })))
 }
 
+async fn get_system_statistics(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let statistics = with_storage(
+        &app_state.shared_storage,
+        "admin::get_system_statistics",
+        |storage| Ok(storage.get_system_statistics()?),
+    )
+    .map_err(|e| match e {
+        StorageLockError::Timeout => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"error": "Storage timeout, please retry"})),
+        ),
+        StorageLockError::Other(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("Failed to get system statistics: {}", err)})),
+        ),
+    })?;
+
+    Ok(Json(json!({
+        "success": true,
+        "statistics": statistics
+    })))
+}
+
+/// Move a user to another tier, resetting their limits to the tier defaults
+async fn change_user_tier(
+    Path(user_id): Path<String>,
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Json(request): Json<TierChangeRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    // Phase 1: Synchronous storage operations - extract all owned data
+    let (user_clone, old_tier, admin_username) = with_storage(
+        &app_state.shared_storage,
+        "admin::change_user_tier::modify_and_record",
+        |storage| {
+            let mut user = storage
+                .get_user_account(&user_id)?
+                .ok_or("User not found")?;
+
+            let old_tier = user.tier.clone();
+            user.tier = request.tier.clone();
+            user.limits = TierLimits::for_tier(&request.tier);
+            user.updated_at = Utc::now();
+            storage.update_user_account(&user)?;
+
+            let admin_action = AdminAction {
+                action_id: Uuid::new_v4().to_string(),
+                admin_user_id: admin_user_id.clone(),
+                action_type: AdminActionType::TierChanged,
+                target_user_id: Some(user_id.clone()),
+                target_resource_id: None,
+                details: {
+                    let mut map = std::collections::HashMap::new();
+                    map.insert("old_tier".to_string(), serde_json::json!(old_tier));
+                    map.insert("new_tier".to_string(), serde_json::json!(request.tier));
+                    if let Some(reason) = &request.reason {
+                        map.insert("reason".to_string(), serde_json::json!(reason));
+                    }
+                    map
+                },
+                timestamp: Utc::now(),
+                ip_address: None,
+            };
+            storage.record_admin_action(&admin_action)?;
+
+            let admin_username = storage
+                .get_user_account(&admin_user_id)?
+                .map(|u| u.username)
+                .unwrap_or_else(|| "Admin".to_string());
+
+            Ok((user, old_tier, admin_username))
+        },
+    )
+    .map_err(|e| match e {
+        StorageLockError::Timeout => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"error": "Storage timeout, please retry"})),
+        ),
+        StorageLockError::Other(err) => {
+            if err.contains("User not found") {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({"error": "User not found"})),
+                );
+            }
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Failed to change tier: {}", err)})),
+            )
+        }
+    })?;
+
+    // PostgreSQL persistence - synchronous for read-after-write consistency
+    {
+        let pg_lock = app_state.postgres_persistence.read().await;
+        if let Some(pg_instance) = &*pg_lock {
+            if let Err(e) = pg_instance.persist_user(&user_clone).await {
+                tracing::warn!("Failed to persist tier change to PostgreSQL: {}", e);
+            }
+        }
+    }
+
+    // Phase 2: Async operations (notifications, etc.) - no storage lock needed
+    let changes = format!("tier: {:?} -> {:?}", old_tier, user_clone.tier);
+    {
+        let notification_engine = app_state.notification_engine.write().await;
+        if let Ok(notification) = notification_engine.create_account_updated_notification(
+            &user_id,
+            &admin_username,
+            &changes,
+        ) {
+            let _ =
+                app_state
+                    .notification_tx
+                    .send(crate::api::notifications::NotificationMessage {
+                        msg_type: "notification".to_string(),
+                        notification,
+                    });
+        }
+    }
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Tier changed successfully",
+        "old_tier": old_tier,
+        "tier": user_clone.tier,
+        "limits": user_clone.limits
+    })))
+}
+
 // ============================================================================
 // ADAPTER CONFIGURATION HANDLERS
 // ============================================================================
@@ -1602,17 +1769,181 @@ async fn set_default_adapter(
 //     }
 // }
 
+// ============================================================================
+// LOCALE PACK HANDLERS
+// ============================================================================
+
+fn locale_error_response(e: LocaleError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        LocaleError::InvalidLocale(_) | LocaleError::InvalidPack(_) => StatusCode::BAD_REQUEST,
+        LocaleError::NotFound(_) => StatusCode::NOT_FOUND,
+        LocaleError::IoError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// Events rejected for exceeding their tier's payload limits since startup
+async fn get_payload_rejections(
+    AdminUser(_admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let rejections = crate::payload_limits::rejection_counts();
+    Ok(Json(json!({
+        "success": true,
+        "total": rejections.iter().map(|r| r.rejections).sum::<u64>(),
+        "rejections": rejections
+    })))
+}
+
+/// Adapter writes that were started and never completed
+async fn list_adapter_intents(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let intents = app_state
+        .circuits_engine
+        .read()
+        .await
+        .get_incomplete_adapter_intents()
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": e.to_string()})),
+            )
+        })?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": intents.len(),
+        "intents": intents
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResumeAdapterIntentsRequest {
+    /// Pending intents idle for less than this are assumed to be in flight
+    pub grace_secs: Option<i64>,
+}
+
+/// Retry incomplete adapter writes now instead of waiting for a restart
+async fn resume_adapter_intents(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Json(request): Json<ResumeAdapterIntentsRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let grace = chrono::Duration::seconds(
+        request
+            .grace_secs
+            .unwrap_or(crate::adapter_intent_log::IN_FLIGHT_GRACE_SECS)
+            .max(0),
+    );
+    let resumed = app_state
+        .circuits_engine
+        .read()
+        .await
+        .resume_adapter_intents(grace)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": e.to_string()})),
+            )
+        })?;
+    let completed = resumed.iter().filter(|intent| intent.is_complete()).count();
+
+    tracing::info!(
+        "🔁 {} resumed {} adapter intents ({} completed)",
+        admin_user_id,
+        resumed.len(),
+        completed
+    );
+    Ok(Json(json!({
+        "success": true,
+        "resumed": resumed.len(),
+        "completed": completed,
+        "intents": resumed
+    })))
+}
+
+async fn list_locales(
+    AdminUser(_admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let localizer = Localizer::global();
+    let locales: Vec<Value> = localizer
+        .locales()
+        .into_iter()
+        .filter_map(|locale| localizer.pack(&locale))
+        .map(|pack| {
+            json!({
+                "locale": pack.locale,
+                "fallback": pack.fallback,
+                "message_count": pack.messages.len(),
+            })
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "success": true,
+        "default_locale": localizer.default_locale(),
+        "locales": locales
+    })))
+}
+
+async fn get_locale_pack(
+    AdminUser(_admin_user_id): AdminUser,
+    Path(locale): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let pack = Localizer::global()
+        .pack(&locale)
+        .ok_or_else(|| locale_error_response(LocaleError::NotFound(locale)))?;
+
+    Ok(Json(json!({
+        "success": true,
+        "pack": pack
+    })))
+}
+
+/// Install or replace a locale pack; it takes effect for the next notification
+async fn put_locale_pack(
+    AdminUser(_admin_user_id): AdminUser,
+    Path(locale): Path<String>,
+    Json(mut pack): Json<LocalePack>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    pack.locale = locale;
+    let localizer = Localizer::global();
+    localizer.install(pack).map_err(locale_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "locales": localizer.locales()
+    })))
+}
+
+async fn delete_locale_pack(
+    AdminUser(_admin_user_id): AdminUser,
+    Path(locale): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let localizer = Localizer::global();
+    localizer.remove(&locale).map_err(locale_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "locales": localizer.locales()
+    })))
+}
+
 // ============================================================================
 // ROUTER SETUP
 // ============================================================================
 
-pub fn admin_routes() -> Router<Arc<AppState>> {
+/// Admin routes; every route requires an active admin (see [`admin_auth_middleware`])
+pub fn admin_routes(app_state: Arc<AppState>) -> Router {
     Router::new()
         // User management
         .route("/users", get(list_users).post(create_user))
         .route("/users/:user_id", get(get_user).put(update_user))
         .route("/users/:user_id/freeze", put(freeze_user))
         .route("/users/:user_id/unfreeze", put(unfreeze_user))
+        .route("/users/:user_id/tier", put(change_user_tier))
         .route("/users/:user_id/credits", post(adjust_user_credits))
         .route(
             "/users/:user_id/credits/history",
@@ -1622,6 +1953,8 @@ pub fn admin_routes() -> Router<Arc<AppState>> {
         // Dashboard and monitoring
         .route("/dashboard/stats", get(get_admin_dashboard_stats))
         .route("/actions", get(get_admin_actions))
+        .route("/system/statistics", get(get_system_statistics))
+        .route("/events/payload-rejections", get(get_payload_rejections))
         // Adapter configuration management
         .route(
             "/adapters",
@@ -1637,6 +1970,83 @@ pub fn admin_routes() -> Router<Arc<AppState>> {
             "/adapters/:config_id/set-default",
             post(set_default_adapter),
         )
-    // Implementation pending
-    // .route("/adapters/:config_id/test", post(test_adapter_config))
+        // Write-ahead log of adapter writes
+        .route("/adapter-intents", get(list_adapter_intents))
+        .route("/adapter-intents/resume", post(resume_adapter_intents))
+        // Notification and email locale packs
+        .route("/locales", get(list_locales))
+        .route(
+            "/locales/:locale",
+            get(get_locale_pack)
+                .put(put_locale_pack)
+                .delete(delete_locale_pack),
+        )
+        // Implementation pending
+        // .route("/adapters/:config_id/test", post(test_adapter_config))
+        // Broadcast announcements
+        .nest(
+            "/announcements",
+            crate::api::announcements::admin_announcement_routes(),
+        )
+        // Monthly SLA attainment
+        .nest("/sla", crate::api::status::admin_sla_routes())
+        // Signed, encrypted configuration export/import between environments
+        .nest(
+            "/config-bundles",
+            crate::api::config_bundles::admin_config_bundle_routes(),
+        )
+        // Write freeze for planned maintenance
+        .nest(
+            "/maintenance",
+            crate::api::maintenance::admin_maintenance_routes(),
+        )
+        // Key ceremonies for anchoring and audit signing keys
+        .nest(
+            "/keys",
+            crate::api::key_ceremonies::admin_key_ceremony_routes(),
+        )
+        // Groth16 keys from an external trusted setup ceremony
+        .nest("/zk-setup", crate::api::zk_proofs::admin_zk_setup_routes())
+        // Masking of personal data past retention in items and events
+        .nest(
+            "/anonymization",
+            crate::api::anonymization::admin_anonymization_routes(),
+        )
+        // Archiving or deletion of orphaned data lake entries and receipts
+        .nest(
+            "/data-lake-gc",
+            crate::api::data_lake_gc::admin_data_lake_gc_routes(),
+        )
+        // Peers and conflicts of federation with other deployments
+        .nest(
+            "/federation",
+            crate::api::federation::admin_federation_routes(),
+        )
+        // Per-workspace API usage anomalies and their thresholds
+        .nest(
+            "/usage-anomalies",
+            crate::api::usage_anomalies::admin_usage_anomaly_routes(),
+        )
+        // Background verification of pending data lake entries
+        .nest(
+            "/verification-scheduler",
+            crate::api::verification_scheduler::admin_verification_scheduler_routes(),
+        )
+        // Identifier mapping corrections and the merge proposals they lead to
+        .nest(
+            "/reverification",
+            crate::api::reverification::admin_reverification_routes(),
+        )
+        // Automated remediation of operational incidents
+        .nest("/runbooks", crate::api::runbooks::admin_runbook_routes())
+        // Consistency of circuit items with push and removal events
+        .nest(
+            "/membership-reconciliation",
+            crate::api::membership_reconciliation::admin_membership_reconciliation_routes(),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            admin_auth_middleware,
+        ))
+        .with_state(app_state)
 }
@@ -0,0 +1,124 @@
+//! Per-circuit anchoring SLAs, the optimizer's plan for meeting them, and
+//! reports of on-chain anchoring spend.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::anchoring_cost_engine::{AnchoringCostEngine, AnchoringCostError, AnchoringSlaInput};
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::types::AnchoringNetwork;
+
+/// Mounted at `/api/anchoring`
+pub fn anchoring_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/circuits/:circuit_id/sla", get(get_sla).put(set_sla))
+        .route("/circuits/:circuit_id/plan", get(get_plan))
+        .route("/costs", get(get_cost_report))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> AnchoringCostEngine<SharedStorage> {
+    AnchoringCostEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn anchoring_error_response(e: AnchoringCostError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        AnchoringCostError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        AnchoringCostError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        AnchoringCostError::NotFound(_) => StatusCode::NOT_FOUND,
+        AnchoringCostError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CostReportQuery {
+    /// Omitted for spend across all anchoring (admins only)
+    pub circuit_id: Option<Uuid>,
+    pub network: Option<AnchoringNetwork>,
+    pub days: Option<u32>,
+}
+
+async fn get_sla(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let sla = engine(&app_state)
+        .get_sla(&circuit_id, &user_id)
+        .map_err(anchoring_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "sla": sla
+    })))
+}
+
+async fn set_sla(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+    Json(input): Json<AnchoringSlaInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let sla = engine(&app_state)
+        .set_sla(&circuit_id, input, &user_id, Utc::now())
+        .map_err(anchoring_error_response)?;
+
+    tracing::info!(
+        "⚓ Anchoring SLA of circuit {} set by {}",
+        circuit_id,
+        user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "sla": sla
+    })))
+}
+
+async fn get_plan(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let plan = engine(&app_state)
+        .plan(&circuit_id, &user_id, Utc::now())
+        .map_err(anchoring_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "plan": plan
+    })))
+}
+
+/// Cost per anchored event by day, with the projected monthly spend
+async fn get_cost_report(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Query(query): Query<CostReportQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let report = engine(&app_state)
+        .cost_report(
+            query.circuit_id,
+            query.network,
+            query.days.unwrap_or(30),
+            &user_id,
+            Utc::now(),
+        )
+        .map_err(anchoring_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "report": report
+    })))
+}
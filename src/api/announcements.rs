@@ -0,0 +1,253 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::announcement_engine::{
+    AnnouncementEngine, AnnouncementError, AnnouncementInput, AnnouncementUpdate,
+};
+use crate::api::notifications::NotificationMessage;
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::{AdminUser, AuthenticatedUser};
+use crate::types::{Announcement, AnnouncementStatus, Notification};
+
+#[derive(Debug, Deserialize)]
+pub struct UserAnnouncementsQuery {
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminAnnouncementsQuery {
+    pub status: Option<AnnouncementStatus>,
+}
+
+/// Recipient routes, mounted at `/api/announcements`
+pub fn announcement_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(list_my_announcements))
+        .route("/:announcement_id/read", post(mark_announcement_read))
+        .with_state(app_state)
+}
+
+/// Authoring routes, nested under the admin-guarded `/api/admin/announcements`
+pub fn admin_announcement_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_announcements).post(create_announcement))
+        .route(
+            "/:announcement_id",
+            get(get_announcement).put(update_announcement),
+        )
+        .route("/:announcement_id/publish", post(publish_announcement))
+        .route("/:announcement_id/cancel", post(cancel_announcement))
+}
+
+fn engine(app_state: &AppState) -> AnnouncementEngine<SharedStorage> {
+    AnnouncementEngine::new(Arc::clone(&app_state.shared_storage))
+        .with_live_stream(app_state.live_stream.clone())
+}
+
+fn announcement_error_response(e: AnnouncementError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        AnnouncementError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        AnnouncementError::NotFound(_) => StatusCode::NOT_FOUND,
+        AnnouncementError::InvalidState(_) => StatusCode::CONFLICT,
+        AnnouncementError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+fn parse_id(id: &str) -> Result<Uuid, (StatusCode, Json<Value>)> {
+    Uuid::parse_str(id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Invalid announcement ID format"})),
+        )
+    })
+}
+
+/// Push freshly delivered announcement notifications to WebSocket clients
+pub fn broadcast_notifications(app_state: &AppState, notifications: Vec<Notification>) {
+    for notification in notifications {
+        let _ = app_state.notification_tx.send(NotificationMessage {
+            msg_type: "notification".to_string(),
+            notification,
+        });
+    }
+}
+
+fn announcement_json(
+    engine: &AnnouncementEngine<SharedStorage>,
+    announcement: Announcement,
+) -> Value {
+    let stats = engine.stats(&announcement);
+    let mut value = json!(announcement);
+    // Per-recipient notification ids are internal; admins get the counts
+    if let Some(object) = value.as_object_mut() {
+        object.remove("deliveries");
+    }
+    value["stats"] = json!(stats);
+    value
+}
+
+async fn list_my_announcements(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Query(query): Query<UserAnnouncementsQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let announcements = engine(&app_state)
+        .list_for_user(&user_id, query.unread_only, Utc::now())
+        .map_err(announcement_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": announcements.len(),
+        "announcements": announcements
+    })))
+}
+
+async fn mark_announcement_read(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(announcement_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let announcement_id = parse_id(&announcement_id)?;
+    engine(&app_state)
+        .mark_read(&user_id, &announcement_id)
+        .map_err(announcement_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Announcement marked as read"
+    })))
+}
+
+async fn list_announcements(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Query(query): Query<AdminAnnouncementsQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = engine(&app_state);
+    let announcements = engine
+        .list_announcements(query.status)
+        .map_err(announcement_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": announcements.len(),
+        "announcements": announcements
+            .into_iter()
+            .map(|a| announcement_json(&engine, a))
+            .collect::<Vec<_>>()
+    })))
+}
+
+/// Create an announcement; without a future `publish_at` it is delivered right away
+async fn create_announcement(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Json(request): Json<AnnouncementInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = engine(&app_state);
+    let now = Utc::now();
+    let mut announcement = engine
+        .create_announcement(&admin_user_id, request, now)
+        .map_err(announcement_error_response)?;
+
+    if announcement.publish_at <= now {
+        let notifications = engine
+            .publish(&announcement.announcement_id, now)
+            .map_err(announcement_error_response)?;
+        broadcast_notifications(&app_state, notifications);
+        announcement = engine
+            .get_announcement(&announcement.announcement_id)
+            .map_err(announcement_error_response)?;
+    }
+
+    Ok(Json(json!({
+        "success": true,
+        "announcement": announcement_json(&engine, announcement)
+    })))
+}
+
+async fn get_announcement(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Path(announcement_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let announcement_id = parse_id(&announcement_id)?;
+    let engine = engine(&app_state);
+    let announcement = engine
+        .get_announcement(&announcement_id)
+        .map_err(announcement_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "announcement": announcement_json(&engine, announcement)
+    })))
+}
+
+async fn update_announcement(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Path(announcement_id): Path<String>,
+    Json(request): Json<AnnouncementUpdate>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let announcement_id = parse_id(&announcement_id)?;
+    let engine = engine(&app_state);
+    let announcement = engine
+        .update_announcement(&announcement_id, request, Utc::now())
+        .map_err(announcement_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "announcement": announcement_json(&engine, announcement)
+    })))
+}
+
+/// Deliver a scheduled announcement now instead of at its `publish_at`
+async fn publish_announcement(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Path(announcement_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let announcement_id = parse_id(&announcement_id)?;
+    let engine = engine(&app_state);
+    let notifications = engine
+        .publish(&announcement_id, Utc::now())
+        .map_err(announcement_error_response)?;
+    broadcast_notifications(&app_state, notifications);
+    let announcement = engine
+        .get_announcement(&announcement_id)
+        .map_err(announcement_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "announcement": announcement_json(&engine, announcement)
+    })))
+}
+
+async fn cancel_announcement(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Path(announcement_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let announcement_id = parse_id(&announcement_id)?;
+    let engine = engine(&app_state);
+    let announcement = engine
+        .cancel_announcement(&announcement_id, Utc::now())
+        .map_err(announcement_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "announcement": announcement_json(&engine, announcement)
+    })))
+}
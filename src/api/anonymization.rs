@@ -0,0 +1,76 @@
+//! Anonymization of personal data past retention, under the admin-guarded
+//! `/api/admin/anonymization`. A run without a body uses the scheduled pass's
+//! policy.
+
+use axum::{extract::State, http::StatusCode, response::Json, routing::post, Router};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::anonymization_engine::{AnonymizationEngine, AnonymizationError, AnonymizationPolicy};
+use crate::api::shared_state::AppState;
+use crate::auth_middleware::AdminUser;
+use crate::types::TimeAxis;
+
+pub fn admin_anonymization_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/run", post(run_anonymization))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RunAnonymizationRequest {
+    pub retention_days: Option<u32>,
+    pub axis: Option<TimeAxis>,
+    /// Replaces the default personal data fields
+    pub pii_fields: Option<Vec<String>>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+fn anonymization_error_response(e: AnonymizationError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        AnonymizationError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        AnonymizationError::StorageError(_) | AnonymizationError::EventError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// Mask personal data now, or with `dry_run` only report what would be masked
+async fn run_anonymization(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    request: Option<Json<RunAnonymizationRequest>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let default = AnonymizationPolicy::from_env();
+    let policy = AnonymizationPolicy {
+        retention_days: request.retention_days.unwrap_or(default.retention_days),
+        axis: request.axis.unwrap_or(default.axis),
+        pii_fields: request.pii_fields.unwrap_or(default.pii_fields),
+    };
+
+    let report = AnonymizationEngine::new(Arc::clone(&app_state.shared_storage))
+        .run(
+            &policy,
+            request.dry_run,
+            Some(admin_user_id.clone()),
+            Utc::now(),
+        )
+        .map_err(anonymization_error_response)?;
+
+    if !report.dry_run {
+        tracing::info!(
+            "🕶️  {} anonymized {} fields on {} items ({} events)",
+            admin_user_id,
+            report.fields_masked,
+            report.items_anonymized,
+            report.events_anonymized
+        );
+    }
+    Ok(Json(json!({
+        "success": true,
+        "report": report
+    })))
+}
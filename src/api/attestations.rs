@@ -0,0 +1,233 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::shared_state::AppState;
+use crate::attestation_engine::{
+    signing_hash, AttestationDraft, AttestationEngine, AttestationError, SignedAttestationRequest,
+};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::storage::StorageBackend;
+
+#[derive(Debug, Deserialize)]
+pub struct AttestationListParams {
+    pub claim_type: Option<String>,
+    #[serde(default)]
+    pub active_only: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RevokeAttestationRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PolicyCheckParams {
+    pub subject_id: Option<String>,
+}
+
+pub fn attestation_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", post(create_attestation))
+        .route("/signing-hash", post(get_signing_hash))
+        .route("/:attestation_id", get(get_attestation))
+        .route("/:attestation_id/revoke", post(revoke_attestation))
+        .route("/subjects/:user_id", get(list_for_subject))
+        .route("/attesters/:user_id", get(list_by_attester))
+        .route("/circuits/:circuit_id/policy-check", get(check_push_policy))
+        .with_state(app_state)
+}
+
+fn attestation_error_response(e: AttestationError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        AttestationError::ValidationError(_) | AttestationError::InvalidSignature(_) => {
+            StatusCode::BAD_REQUEST
+        }
+        AttestationError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        AttestationError::NotFound(_) => StatusCode::NOT_FOUND,
+        AttestationError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+fn parse_uuid(value: &str, what: &str) -> Result<Uuid, (StatusCode, Json<Value>)> {
+    Uuid::parse_str(value).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Invalid {} format", what)})),
+        )
+    })
+}
+
+/// Returns the hash the caller must sign with their ed25519 key before submitting
+async fn get_signing_hash(
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(draft): Json<AttestationDraft>,
+) -> Json<Value> {
+    let signing_hash = signing_hash(&user_id, &draft);
+
+    Json(json!({
+        "success": true,
+        "attester_id": user_id,
+        "signing_hash": signing_hash
+    }))
+}
+
+async fn create_attestation(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(payload): Json<SignedAttestationRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = AttestationEngine::new(Arc::clone(&app_state.shared_storage));
+    let attestation = engine
+        .create_attestation(&user_id, payload)
+        .map_err(attestation_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "attestation": attestation
+    })))
+}
+
+async fn get_attestation(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(attestation_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let attestation_id = parse_uuid(&attestation_id, "attestation ID")?;
+    let engine = AttestationEngine::new(Arc::clone(&app_state.shared_storage));
+    let attestation = engine
+        .get_attestation(&attestation_id, &user_id)
+        .map_err(attestation_error_response)?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "Attestation not found"})),
+            )
+        })?;
+
+    Ok(Json(json!({
+        "success": true,
+        "active": attestation.is_active(),
+        "attestation": attestation
+    })))
+}
+
+async fn revoke_attestation(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(attestation_id): Path<String>,
+    Json(payload): Json<RevokeAttestationRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let attestation_id = parse_uuid(&attestation_id, "attestation ID")?;
+    let engine = AttestationEngine::new(Arc::clone(&app_state.shared_storage));
+    let attestation = engine
+        .revoke_attestation(&attestation_id, &user_id, payload.reason)
+        .map_err(attestation_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "attestation": attestation
+    })))
+}
+
+async fn list_for_subject(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(subject_id): Path<String>,
+    Query(params): Query<AttestationListParams>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = AttestationEngine::new(Arc::clone(&app_state.shared_storage));
+    let attestations = engine
+        .list_for_subject(
+            &subject_id,
+            &user_id,
+            params.claim_type.as_deref(),
+            params.active_only,
+        )
+        .map_err(attestation_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": attestations.len(),
+        "attestations": attestations
+    })))
+}
+
+async fn list_by_attester(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(attester_id): Path<String>,
+    Query(params): Query<AttestationListParams>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = AttestationEngine::new(Arc::clone(&app_state.shared_storage));
+    let attestations = engine
+        .list_by_attester(
+            &attester_id,
+            &user_id,
+            params.claim_type.as_deref(),
+            params.active_only,
+        )
+        .map_err(attestation_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": attestations.len(),
+        "attestations": attestations
+    })))
+}
+
+/// Evaluate a circuit's attestation push policy for a member (defaults to the caller)
+async fn check_push_policy(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<String>,
+    Query(params): Query<PolicyCheckParams>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuit_id = parse_uuid(&circuit_id, "circuit ID")?;
+    let circuit = app_state
+        .shared_storage
+        .get_circuit(&circuit_id)
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Failed to get circuit: {}", e)})),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "Circuit not found"})),
+            )
+        })?;
+
+    if !circuit.is_member(&user_id) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": "Only circuit members can evaluate its push policy"})),
+        ));
+    }
+
+    let subject_id = params.subject_id.unwrap_or(user_id);
+    let engine = AttestationEngine::new(Arc::clone(&app_state.shared_storage));
+    let missing = engine
+        .missing_required_attestations(&circuit, &subject_id)
+        .map_err(attestation_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "circuit_id": circuit_id,
+        "subject_id": subject_id,
+        "required": circuit.permissions.required_attestations,
+        "missing": missing,
+        "satisfied": missing.is_empty()
+    })))
+}
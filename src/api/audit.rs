@@ -12,9 +12,11 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::audit_query_language::parse_audit_expression;
+use crate::auth_middleware::AuthenticatedUser;
 use crate::{
-    api::shared_state::AppState, AuditEventMetadata, AuditEventType, AuditOutcome, AuditQuery,
-    AuditSeverity, AuditSortBy, ComplianceInfo, ComplianceReportType, ComplianceScope,
+    api::shared_state::AppState, AuditError, AuditEventMetadata, AuditEventType, AuditOutcome,
+    AuditQuery, AuditSeverity, AuditSortBy, ComplianceInfo, ComplianceReportType, ComplianceScope,
     ExportFormat, IncidentCategory, SortOrder, StorageBackend,
 };
 
@@ -76,7 +78,7 @@ pub struct LogDataAccessRequest {
     pub metadata: Option<AuditEventMetadataRequest>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct AuditQueryRequest {
     pub user_id: Option<String>,
     pub event_types: Option<Vec<String>>,
@@ -91,6 +93,19 @@ pub struct AuditQueryRequest {
     pub offset: Option<u32>,
     pub sort_by: Option<String>, // "timestamp", "severity", "event_type"
     pub sort_order: Option<String>, // "asc", "desc"
+    pub expression: Option<String>, // e.g. "severity >= high AND action ~ login"
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSavedQueryRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub expression: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ValidateExpressionRequest {
+    pub expression: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -448,6 +463,143 @@ pub async fn get_event_by_id(
     }
 }
 
+// Audit Query Language Endpoints
+fn audit_error_response(e: AuditError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        AuditError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        AuditError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        AuditError::NotFound(_) => StatusCode::NOT_FOUND,
+        AuditError::StorageError(_) | AuditError::ProcessingError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+fn parse_query_id(query_id: &str) -> Result<Uuid, (StatusCode, Json<Value>)> {
+    Uuid::parse_str(query_id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Invalid query ID format"})),
+        )
+    })
+}
+
+pub async fn validate_expression(
+    Json(request): Json<ValidateExpressionRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let filter = parse_audit_expression(&request.expression).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": e.message,
+                "position": e.position
+            })),
+        )
+    })?;
+
+    Ok(Json(json!({
+        "success": true,
+        "filter": filter
+    })))
+}
+
+pub async fn create_saved_query(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(request): Json<CreateSavedQueryRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let saved = state
+        .audit_engine
+        .create_saved_query(
+            &user_id,
+            request.name,
+            request.description,
+            request.expression,
+        )
+        .map_err(audit_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": saved
+    })))
+}
+
+pub async fn list_saved_queries(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let queries = state
+        .audit_engine
+        .list_saved_queries(&user_id)
+        .map_err(audit_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": queries
+    })))
+}
+
+pub async fn get_saved_query(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(query_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let query_id = parse_query_id(&query_id)?;
+    let saved = state
+        .audit_engine
+        .get_saved_query(&query_id, &user_id)
+        .map_err(audit_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": saved
+    })))
+}
+
+pub async fn delete_saved_query(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(query_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let query_id = parse_query_id(&query_id)?;
+    state
+        .audit_engine
+        .delete_saved_query(&query_id, &user_id)
+        .map_err(audit_error_response)?;
+
+    Ok(Json(json!({"success": true})))
+}
+
+/// Run a saved query; an optional body narrows it with the regular query filters
+pub async fn run_saved_query(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(query_id): Path<String>,
+    request: Option<Json<AuditQueryRequest>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let query_id = parse_query_id(&query_id)?;
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    let base_query = convert_audit_query(request)
+        .map_err(|status| (status, Json(json!({"error": "Invalid query filters"}))))?;
+
+    let (saved, events) = state
+        .audit_engine
+        .run_saved_query(&query_id, &user_id, &base_query)
+        .map_err(audit_error_response)?;
+
+    let event_responses: Vec<AuditEventResponse> = events
+        .into_iter()
+        .map(convert_audit_event_to_response)
+        .collect();
+
+    Ok(Json(json!({
+        "success": true,
+        "query": saved,
+        "data": event_responses
+    })))
+}
+
 // Security Incident Endpoints
 pub async fn create_security_incident(
     State(state): State<Arc<AppState>>,
@@ -773,6 +925,7 @@ fn parse_incident_category(category: &str) -> Result<IncidentCategory, StatusCod
         "system-compromise" => Ok(IncidentCategory::SystemCompromise),
         "policy-violation" => Ok(IncidentCategory::PolicyViolation),
         "denial-of-service" => Ok(IncidentCategory::DenialOfService),
+        "service-degradation" => Ok(IncidentCategory::ServiceDegradation),
         _ => Err(StatusCode::BAD_REQUEST),
     }
 }
@@ -884,6 +1037,12 @@ fn convert_audit_query(request: AuditQueryRequest) -> Result<AuditQuery, StatusC
 
     let compliance = request.compliance.map(|c| convert_compliance(&c));
 
+    let filter = request
+        .expression
+        .as_deref()
+        .map(|e| parse_audit_expression(e).map_err(|_| StatusCode::BAD_REQUEST))
+        .transpose()?;
+
     Ok(AuditQuery {
         user_id: request.user_id,
         event_types,
@@ -898,6 +1057,7 @@ fn convert_audit_query(request: AuditQueryRequest) -> Result<AuditQuery, StatusC
         offset: request.offset,
         sort_by,
         sort_order,
+        filter,
     })
 }
 
@@ -1132,6 +1292,17 @@ pub fn audit_routes(app_state: Arc<AppState>) -> Router {
         .route("/audit/events/query", post(query_events))
         .route("/audit/events/:event_id", get(get_event_by_id))
         .route("/audit/events/user/:user_id", get(get_events_by_user))
+        // Query language and saved queries
+        .route("/audit/queries/validate", post(validate_expression))
+        .route(
+            "/audit/queries/saved",
+            post(create_saved_query).get(list_saved_queries),
+        )
+        .route(
+            "/audit/queries/saved/:query_id",
+            get(get_saved_query).delete(delete_saved_query),
+        )
+        .route("/audit/queries/saved/:query_id/run", post(run_saved_query))
         // Security incidents
         .route("/audit/incidents", post(create_security_incident))
         .route("/audit/incidents", get(list_security_incidents))
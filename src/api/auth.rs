@@ -1,6 +1,7 @@
 use crate::api::shared_state::AppState;
 use crate::auth_middleware::jwt_auth_middleware;
 use crate::http_utils::svc_unavailable_retry;
+use crate::i18n::{normalize_locale, Localizer};
 use crate::storage::StorageBackend;
 use crate::storage_helpers::{with_storage, with_storage_traced, StorageLockError};
 use crate::types::{
@@ -94,6 +95,13 @@ pub struct UserProfile {
     pub email: String,
     pub created_at: i64,
     pub workspace_id: Option<String>,
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateProfileRequest {
+    /// BCP 47 tag such as "pt-BR"; null resets to the server default
+    pub locale: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -234,7 +242,7 @@ pub fn auth_routes(app_state: Arc<AppState>) -> Router {
 
     // Protected routes requiring JWT authentication
     let protected_routes = Router::new()
-        .route("/profile", get(get_profile))
+        .route("/profile", get(get_profile).put(update_profile))
         .route("/refresh", post(refresh_token))
         .route_layer(middleware::from_fn_with_state(
             app_state.clone(),
@@ -339,6 +347,7 @@ async fn forgot_password(
                 &user.email,
                 &user.username,
                 &plaintext_token,
+                user.locale.as_deref(),
             )
             .await
             {
@@ -695,6 +704,7 @@ async fn register(
         is_admin: false,
         workspace_id: workspace_id.clone(),
         available_adapters: None, // Use tier defaults
+        locale: None,
     };
 
     // Store user account and initial credit using non-blocking storage helper
@@ -804,6 +814,7 @@ async fn get_profile(
             email: user.email,
             created_at: user.created_at.timestamp(),
             workspace_id: user.workspace_id,
+            locale: user.locale,
         }));
     }
 
@@ -813,6 +824,72 @@ async fn get_profile(
     ))
 }
 
+async fn update_profile(
+    State((_auth, app_state)): State<(Arc<AuthState>, Arc<AppState>)>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<UpdateProfileRequest>,
+) -> Result<Json<UserProfile>, (StatusCode, Json<Value>)> {
+    let user_id = claims.user_id.clone();
+
+    let locale = match payload.locale {
+        Some(tag) => {
+            let localizer = Localizer::global();
+            match normalize_locale(&tag).filter(|locale| localizer.supports(locale)) {
+                Some(locale) => Some(locale),
+                None => {
+                    return Err((
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({
+                            "error": format!("Unsupported locale: {tag}"),
+                            "available_locales": localizer.locales(),
+                        })),
+                    ))
+                }
+            }
+        }
+        None => None,
+    };
+
+    let user_opt = with_storage(
+        &app_state.shared_storage,
+        "auth_update_profile",
+        |storage| {
+            let Some(mut user) = storage.get_user_account(&user_id)? else {
+                return Ok(None);
+            };
+            user.locale = locale;
+            user.updated_at = Utc::now();
+            storage.update_user_account(&user)?;
+            Ok(Some(user))
+        },
+    )
+    .map_err(|e| match e {
+        StorageLockError::Timeout => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"error": "Service temporarily busy, please retry"})),
+        ),
+        StorageLockError::Other(msg) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": msg})),
+        ),
+    })?;
+
+    match user_opt {
+        Some(user) => Ok(Json(UserProfile {
+            user_id: user.user_id,
+            username: user.username,
+            email: user.email,
+            created_at: user.created_at.timestamp(),
+            workspace_id: user.workspace_id,
+            locale: user.locale,
+        })),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "User not found"})),
+        )),
+    }
+}
+
 async fn refresh_token(
     State((auth, app_state)): State<(Arc<AuthState>, Arc<AppState>)>,
     Extension(claims): Extension<Claims>,
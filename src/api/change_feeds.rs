@@ -0,0 +1,194 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::shared_state::AppState;
+use crate::auth_middleware::AuthenticatedUser;
+use crate::change_feed_engine::{
+    ChangeFeedEngine, ChangeFeedError, ChangeFeedInput, ChangeFeedUpdate,
+};
+use crate::types::ChangeFeedSubscription;
+
+#[derive(Debug, Deserialize)]
+pub struct ChangesQuery {
+    /// Return records after this sequence (0 = from the start of the feed)
+    #[serde(default)]
+    pub cursor: u64,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReplayRequest {
+    pub cursor: u64,
+}
+
+pub fn change_feed_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/circuits/:circuit_id", get(read_changes))
+        .route(
+            "/circuits/:circuit_id/subscriptions",
+            get(list_subscriptions).post(create_subscription),
+        )
+        .route(
+            "/:subscription_id",
+            get(get_subscription)
+                .put(update_subscription)
+                .delete(delete_subscription),
+        )
+        .route("/:subscription_id/replay", post(replay_subscription))
+        .with_state(app_state)
+}
+
+fn change_feed_error_response(e: ChangeFeedError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        ChangeFeedError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        ChangeFeedError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        ChangeFeedError::NotFound(_) => StatusCode::NOT_FOUND,
+        ChangeFeedError::CursorExpired(_) => StatusCode::GONE,
+        ChangeFeedError::DeliveryError(_) => StatusCode::BAD_GATEWAY,
+        ChangeFeedError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+fn parse_id(id: &str, what: &str) -> Result<Uuid, (StatusCode, Json<Value>)> {
+    Uuid::parse_str(id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Invalid {what} ID format")})),
+        )
+    })
+}
+
+/// Subscriptions are returned without their signing secret
+fn subscription_json(mut subscription: ChangeFeedSubscription) -> Value {
+    let has_secret = subscription.secret.take().is_some();
+    let mut value = json!(subscription);
+    value["has_secret"] = json!(has_secret);
+    value
+}
+
+async fn read_changes(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<String>,
+    Query(query): Query<ChangesQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuit_id = parse_id(&circuit_id, "circuit")?;
+    let batch = ChangeFeedEngine::new(Arc::clone(&app_state.shared_storage))
+        .read_changes(&user_id, &circuit_id, query.cursor, query.limit)
+        .map_err(change_feed_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "batch": batch
+    })))
+}
+
+async fn create_subscription(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<String>,
+    Json(request): Json<ChangeFeedInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuit_id = parse_id(&circuit_id, "circuit")?;
+    let subscription = ChangeFeedEngine::new(Arc::clone(&app_state.shared_storage))
+        .create_subscription(&user_id, &circuit_id, request)
+        .map_err(change_feed_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "subscription": subscription_json(subscription)
+    })))
+}
+
+async fn list_subscriptions(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuit_id = parse_id(&circuit_id, "circuit")?;
+    let subscriptions = ChangeFeedEngine::new(Arc::clone(&app_state.shared_storage))
+        .list_subscriptions(&user_id, &circuit_id)
+        .map_err(change_feed_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": subscriptions.len(),
+        "subscriptions": subscriptions.into_iter().map(subscription_json).collect::<Vec<_>>()
+    })))
+}
+
+async fn get_subscription(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(subscription_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let subscription_id = parse_id(&subscription_id, "subscription")?;
+    let subscription = ChangeFeedEngine::new(Arc::clone(&app_state.shared_storage))
+        .get_subscription(&user_id, &subscription_id)
+        .map_err(change_feed_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "subscription": subscription_json(subscription)
+    })))
+}
+
+async fn update_subscription(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(subscription_id): Path<String>,
+    Json(request): Json<ChangeFeedUpdate>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let subscription_id = parse_id(&subscription_id, "subscription")?;
+    let subscription = ChangeFeedEngine::new(Arc::clone(&app_state.shared_storage))
+        .update_subscription(&user_id, &subscription_id, request)
+        .map_err(change_feed_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "subscription": subscription_json(subscription)
+    })))
+}
+
+async fn delete_subscription(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(subscription_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let subscription_id = parse_id(&subscription_id, "subscription")?;
+    ChangeFeedEngine::new(Arc::clone(&app_state.shared_storage))
+        .delete_subscription(&user_id, &subscription_id)
+        .map_err(change_feed_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Change feed subscription deleted"
+    })))
+}
+
+async fn replay_subscription(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(subscription_id): Path<String>,
+    Json(request): Json<ReplayRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let subscription_id = parse_id(&subscription_id, "subscription")?;
+    let subscription = ChangeFeedEngine::new(Arc::clone(&app_state.shared_storage))
+        .replay_from(&user_id, &subscription_id, request.cursor)
+        .map_err(change_feed_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "subscription": subscription_json(subscription)
+    })))
+}
@@ -0,0 +1,237 @@
+//! Public directory of circuits that opted in (`listed_in_directory` in their
+//! public settings), so open traceability networks can be found and joined.
+//!
+//! Listings are searchable by free text, region and item category without an
+//! account. Each listing says how to join: signed-in users send a join request
+//! to `/api/circuits/:id/public/join`, which is approved automatically for open
+//! circuits and needs the access password for protected ones.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::Json,
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::public_items::public_rate_limit_middleware;
+use crate::api::shared_state::AppState;
+use crate::storage::StorageBackend;
+use crate::storage_helpers::{with_storage, StorageLockError};
+use crate::types::{Circuit, CircuitDirectoryEntry};
+
+const DEFAULT_DIRECTORY_LIMIT: usize = 50;
+const MAX_DIRECTORY_LIMIT: usize = 200;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DirectoryQuery {
+    /// Free text matched against name, description and tagline
+    pub q: Option<String>,
+    pub region: Option<String>,
+    pub category: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+/// Mounted at `/api/public/circuits`
+pub fn circuit_directory_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(search_directory))
+        .route("/:circuit_id", get(get_directory_entry))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            public_rate_limit_middleware,
+        ))
+        .with_state(app_state)
+}
+
+/// Listings matching `query`, largest circuits first
+fn directory_listing(circuits: &[Circuit], query: &DirectoryQuery) -> Vec<CircuitDirectoryEntry> {
+    let mut entries: Vec<CircuitDirectoryEntry> = circuits
+        .iter()
+        .filter_map(Circuit::directory_entry)
+        .filter(|entry| {
+            entry.matches(
+                query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()),
+                query.region.as_deref(),
+                query.category.as_deref(),
+            )
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        b.member_count
+            .cmp(&a.member_count)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    entries
+}
+
+fn entry_json(entry: &CircuitDirectoryEntry) -> Value {
+    json!({
+        "circuit": entry,
+        "join": {
+            "mode": entry.join_mode,
+            "path": format!("/api/circuits/{}/public/join", entry.circuit_id),
+        }
+    })
+}
+
+fn load_circuits(app_state: &AppState) -> Result<Vec<Circuit>, (StatusCode, Json<Value>)> {
+    with_storage(
+        &app_state.shared_storage,
+        "circuit_directory::load_circuits",
+        |storage| Ok(storage.list_circuits()?),
+    )
+    .map_err(|e| match e {
+        StorageLockError::Timeout => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"error": "Storage timeout, please retry"})),
+        ),
+        StorageLockError::Other(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("Failed to load circuits: {}", err)})),
+        ),
+    })
+}
+
+async fn search_directory(
+    State(app_state): State<Arc<AppState>>,
+    Query(query): Query<DirectoryQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuits = load_circuits(&app_state)?;
+    let entries = directory_listing(&circuits, &query);
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_DIRECTORY_LIMIT)
+        .clamp(1, MAX_DIRECTORY_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+    let page: Vec<Value> = entries
+        .iter()
+        .skip(offset)
+        .take(limit)
+        .map(entry_json)
+        .collect();
+
+    Ok(Json(json!({
+        "success": true,
+        "total": entries.len(),
+        "count": page.len(),
+        "offset": offset,
+        "circuits": page
+    })))
+}
+
+async fn get_directory_entry(
+    State(app_state): State<Arc<AppState>>,
+    Path(circuit_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuits = load_circuits(&app_state)?;
+    // Unlisted and unknown circuits look the same from outside
+    let entry = circuits
+        .iter()
+        .find(|circuit| circuit.circuit_id == circuit_id)
+        .and_then(Circuit::directory_entry)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "Circuit not listed in the directory"})),
+            )
+        })?;
+
+    let mut body = entry_json(&entry);
+    body["success"] = json!(true);
+    Ok(Json(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DirectoryJoinMode, PublicAccessMode, PublicSettings};
+
+    fn circuit(name: &str, region: &str, categories: &[&str], members: usize) -> Circuit {
+        let mut circuit = Circuit::new(
+            name.to_string(),
+            format!("{name} network"),
+            "owner".to_string(),
+        );
+        for n in 1..members {
+            circuit.add_member(format!("member-{n}"), crate::types::MemberRole::Member);
+        }
+        circuit.permissions.allow_public_visibility = true;
+        circuit.public_settings = Some(PublicSettings {
+            access_mode: PublicAccessMode::Public,
+            scheduled_date: None,
+            access_password: None,
+            public_name: None,
+            public_description: None,
+            primary_color: None,
+            secondary_color: None,
+            logo_url: None,
+            tagline: None,
+            footer_text: None,
+            published_items: Vec::new(),
+            auto_approve_members: true,
+            auto_publish_pushed_items: false,
+            show_encrypted_events: false,
+            required_event_types: None,
+            data_quality_rules: None,
+            export_permissions: None,
+            public_since: None,
+            listed_in_directory: true,
+            region: Some(region.to_string()),
+            item_categories: categories.iter().map(|c| c.to_string()).collect(),
+        });
+        circuit
+    }
+
+    #[test]
+    fn test_directory_lists_opted_in_public_circuits_by_size() {
+        let cattle = circuit("Cerrado Cattle", "BR-MT", &["cattle"], 3);
+        let soy = circuit("Soy Alliance", "BR-MT", &["soy", "corn"], 5);
+        let mut protected = circuit("Coffee Growers", "CO", &["coffee"], 1);
+        if let Some(settings) = protected.public_settings.as_mut() {
+            settings.access_mode = PublicAccessMode::Protected;
+            settings.access_password = Some("s3cret".to_string());
+        }
+        let mut unlisted = circuit("Private Feedlot", "BR-MT", &["cattle"], 8);
+        if let Some(settings) = unlisted.public_settings.as_mut() {
+            settings.listed_in_directory = false;
+        }
+        let mut private = circuit("Hidden Co-op", "BR-MT", &["cattle"], 8);
+        private.permissions.allow_public_visibility = false;
+        let circuits = vec![cattle, soy, protected, unlisted, private];
+
+        let names = |query: &DirectoryQuery| -> Vec<String> {
+            directory_listing(&circuits, query)
+                .into_iter()
+                .map(|entry| entry.name)
+                .collect()
+        };
+        assert_eq!(
+            names(&DirectoryQuery::default()),
+            ["Soy Alliance", "Cerrado Cattle", "Coffee Growers"]
+        );
+        let in_region = DirectoryQuery {
+            region: Some("br-mt".to_string()),
+            category: Some("CATTLE".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(names(&in_region), ["Cerrado Cattle"]);
+        let text = DirectoryQuery {
+            q: Some("coffee".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(names(&text), ["Coffee Growers"]);
+
+        let entries = directory_listing(&circuits, &DirectoryQuery::default());
+        assert_eq!(entries[0].member_count, 5);
+        assert_eq!(entries[0].join_mode, DirectoryJoinMode::Open);
+        assert_eq!(entries[2].join_mode, DirectoryJoinMode::Password);
+    }
+}
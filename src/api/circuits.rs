@@ -13,7 +13,11 @@ use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 use crate::api::auth::Claims;
+use crate::api::dto::{EventDto, IdentifierDto, ItemDto};
+use crate::api::events::parse_event_type;
 use crate::api::items::{build_identifiers, IdentifierRequest};
+use crate::circuits_engine::CircuitsError;
+use crate::events_engine::{CustomEventTypeInput, EventsError};
 use crate::identifier_types::CircuitAliasConfig;
 use crate::postgres_storage_with_cache::PostgresStorageWithCache;
 use crate::snapshot_types::{SnapshotEntityType, SnapshotOperation, StateSnapshot};
@@ -21,10 +25,12 @@ use crate::storage::StorageBackend;
 use crate::storage_helpers::{with_storage, StorageLockError};
 use crate::types::{
     Activity, AdapterType, BatchPushItemResult, BatchPushResult, CircuitItem, CircuitPermissions,
-    CustomRole, Item, Permission, PublicSettings, UserActivity, UserActivityCategory,
-    UserActivityType, UserResourceType,
+    CustomRole, IngestionPriority, Item, JoinRequest, OwnershipTransfer, ParentAccess, Permission,
+    PublicSettings, ReplicationPolicy, RequiredAttestation, SchemaViolation, UserActivity,
+    UserActivityCategory, UserActivityType, UserResourceType,
 };
-use crate::webhook_engine::WebhookEngine;
+use crate::webhook_encryption;
+use crate::webhook_engine::{WebhookEngine, WebhookError};
 use crate::{Circuit, CircuitOperation, CircuitsEngine, ItemsEngine, MemberRole};
 
 type SharedStorage = Arc<Mutex<PostgresStorageWithCache>>;
@@ -80,7 +86,7 @@ pub struct RejectOperationRequest {
 
 #[derive(Debug, Serialize)]
 pub struct PendingItemPreview {
-    pub identifiers: Vec<IdentifierRequest>,
+    pub identifiers: Vec<IdentifierDto>,
     pub enriched_data: serde_json::Value,
 }
 
@@ -137,13 +143,13 @@ pub struct JoinCircuitRequest {
 
 #[derive(Debug, Deserialize)]
 pub struct ApproveJoinRequest {
-    pub admin_id: String,
     pub role: String,
+    // Note: the approving admin is extracted automatically from JWT token
 }
 
 #[derive(Debug, Deserialize)]
 pub struct RejectJoinRequest {
-    pub admin_id: String,
+    pub reason: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -152,6 +158,25 @@ pub struct JoinRequestResponse {
     pub requested_at: i64,
     pub message: Option<String>,
     pub status: String,
+    pub decided_by: Option<String>,
+    pub decided_at: Option<i64>,
+    pub granted_role: Option<String>,
+    pub decision_reason: Option<String>,
+}
+
+impl From<JoinRequest> for JoinRequestResponse {
+    fn from(request: JoinRequest) -> Self {
+        Self {
+            requester_id: request.requester_id,
+            requested_at: request.requested_at.timestamp(),
+            message: request.message,
+            status: format!("{:?}", request.status),
+            decided_by: request.decided_by,
+            decided_at: request.decided_at.map(|t| t.timestamp()),
+            granted_role: request.granted_role.map(|role| format!("{role:?}")),
+            decision_reason: request.decision_reason,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -159,6 +184,8 @@ pub struct CircuitListQuery {
     pub user_id: Option<String>,
     pub include_public: Option<bool>,
     pub status: Option<String>,
+    /// Archived circuits are left out unless asked for here or with `status=archived`
+    pub include_archived: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -174,6 +201,7 @@ pub struct UpdateCircuitPermissions {
     pub require_approval_for_push: Option<bool>,
     pub require_approval_for_pull: Option<bool>,
     pub allow_public_visibility: Option<bool>,
+    pub required_attestations: Option<Vec<RequiredAttestation>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -227,6 +255,11 @@ pub struct CircuitResponse {
     pub pending_requests: Vec<JoinRequestResponse>,
     pub custom_roles: Vec<CustomRoleResponse>,
     pub public_settings: Option<PublicSettings>,
+    pub archived_at: Option<i64>,
+    pub archived_by: Option<String>,
+    pub parent_circuit_id: Option<String>,
+    pub parent_access: ParentAccess,
+    pub pending_ownership_transfer: Option<OwnershipTransfer>,
 }
 
 #[derive(Debug, Serialize)]
@@ -270,6 +303,9 @@ pub struct PublicSettingsRequest {
     pub required_event_types: Option<String>,
     pub data_quality_rules: Option<String>,
     pub export_permissions: Option<String>,
+    pub listed_in_directory: Option<bool>,
+    pub region: Option<String>,
+    pub item_categories: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -341,6 +377,9 @@ pub struct BatchPushItem {
 #[derive(Debug, Deserialize)]
 pub struct BatchPushRequest {
     pub items: Vec<BatchPushItem>,
+    /// Use `bulk` for historical imports so they never delay live pushes
+    #[serde(default)]
+    pub priority: IngestionPriority,
     // Note: requester_id is now extracted automatically from JWT token
 }
 
@@ -349,6 +388,8 @@ pub struct PushLocalItemRequest {
     pub local_id: String,
     pub identifiers: Option<Vec<IdentifierRequest>>,
     pub enriched_data: Option<std::collections::HashMap<String, serde_json::Value>>,
+    #[serde(default)]
+    pub priority: IngestionPriority,
     // Note: requester_id is now extracted automatically from JWT token
     // No need to include it in the request body anymore
 }
@@ -394,6 +435,21 @@ pub struct PushEventsResponse {
     pub events_pushed: usize,
     pub events_deduplicated: usize,
     pub events: Vec<PushedEventInfo>,
+    /// Local events whose metadata failed the circuit's event schema
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rejected_events: Vec<RejectedEventInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RejectedEventInfo {
+    pub local_event_id: String,
+    pub violations: Vec<SchemaViolation>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterEventSchemaRequest {
+    pub event_type: String,
+    pub schema: serde_json::Value,
 }
 
 // Removed redundant persistence functions - CircuitsEngine handles persistence through PostgresStorageWithCache
@@ -418,6 +474,24 @@ pub struct GetAdapterConfigResponse {
     pub auto_migrate_existing: bool,
     pub configured_by: String,
     pub configured_at: String,
+    pub replication_policy: ReplicationPolicy,
+    pub mirror_adapters: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetReplicationPolicyRequest {
+    pub replication_policy: ReplicationPolicy,
+    #[serde(default)]
+    pub mirror_adapters: Vec<String>,
+}
+
+/// Candidate adapter configuration to dry-run; unset fields keep the circuit's current values
+#[derive(Debug, Deserialize)]
+pub struct DryRunAdapterConfigRequest {
+    pub adapter_type: Option<String>,
+    pub replication_policy: Option<ReplicationPolicy>,
+    pub mirror_adapters: Option<Vec<String>>,
+    pub dfid: Option<String>, // Sample item; a synthetic new item is used when omitted
 }
 
 #[derive(Debug, Deserialize)]
@@ -432,7 +506,10 @@ pub struct SetAdapterConfigRequest {
 }
 
 use super::shared_state::AppState;
-use crate::types::{HttpMethod, PostActionTrigger, WebhookAuthType, WebhookConfig};
+use crate::types::{
+    HttpMethod, PostActionTrigger, WebhookAuthType, WebhookConfig, WebhookEncryptionKey,
+    WebhookFilter,
+};
 
 // ============================================================================
 // PUBLIC CIRCUIT INFO (for unauthenticated access)
@@ -484,6 +561,10 @@ pub struct CreateWebhookRequest {
     pub auth_type: Option<String>, // "None", "BearerToken", "ApiKey", "BasicAuth", "CustomHeader"
     pub auth_credentials: Option<String>,
     pub enabled: Option<bool>,
+    /// RSA public JWK; payloads are then delivered as a compact JWE
+    pub encryption_key: Option<WebhookEncryptionKey>,
+    /// Only deliver matching triggers; omitted to receive everything
+    pub filter: Option<WebhookFilter>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -495,6 +576,12 @@ pub struct UpdateWebhookRequest {
     pub auth_type: Option<String>,
     pub auth_credentials: Option<String>,
     pub enabled: Option<bool>,
+    pub encryption_key: Option<WebhookEncryptionKey>,
+    /// Go back to plaintext JSON payloads
+    #[serde(default)]
+    pub remove_encryption_key: bool,
+    /// Replaces the filter; an empty filter receives everything again
+    pub filter: Option<WebhookFilter>,
 }
 
 pub fn circuit_routes(app_state: Arc<AppState>) -> Router {
@@ -508,11 +595,37 @@ pub fn circuit_routes(app_state: Arc<AppState>) -> Router {
         .route("/:id/push/:dfid", post(push_item))
         .route("/:id/push-local", post(push_local_item))
         .route("/:id/push-events", post(push_events_to_circuit))
+        .route(
+            "/:id/event-schemas",
+            get(list_event_schemas).post(register_event_schema),
+        )
+        .route(
+            "/:id/event-types",
+            get(list_custom_event_types).post(register_custom_event_type),
+        )
+        .route("/:id/event-types/:name", delete(remove_custom_event_type))
         .route("/:id/pull/:dfid", post(pull_item))
+        .route("/:id/forward/:dfid", post(forward_item))
         .route("/:id/operations", get(get_circuit_operations))
         .route("/:id/operations/pending", get(get_pending_operations))
         .route("/operations/:operation_id/approve", post(approve_operation))
         .route("/:id/deactivate", put(deactivate_circuit))
+        .route("/:id/archive", post(archive_circuit))
+        .route("/:id/restore", post(restore_circuit))
+        .route(
+            "/:id/ownership-transfer",
+            post(initiate_ownership_transfer).delete(cancel_ownership_transfer),
+        )
+        .route(
+            "/:id/ownership-transfer/accept",
+            post(accept_ownership_transfer),
+        )
+        .route("/archived", get(list_archived_circuits))
+        .route("/:id/parent", put(set_parent_circuit))
+        .route("/:id/children", get(get_child_circuits))
+        .route("/:id/ancestors", get(get_ancestor_circuits))
+        .route("/:id/members/effective", get(get_effective_members))
+        .route("/:id/rollup/items", get(get_rollup_items))
         .route("/:id/requests", post(request_to_join_circuit))
         .route("/:id/requests/pending", get(get_pending_join_requests))
         .route(
@@ -533,6 +646,7 @@ pub fn circuit_routes(app_state: Arc<AppState>) -> Router {
         .route("/:id/public/join", post(join_public_circuit))
         .route("/:id/activities", get(get_circuit_activities))
         .route("/:id/items", get(get_circuit_items))
+        .route("/:id/items/:dfid/expiry", put(set_item_expiry))
         .route("/:id/push/batch", post(batch_push_items))
         .route("/:id/pending-items", get(get_circuit_pending_items))
         .route(
@@ -545,6 +659,11 @@ pub fn circuit_routes(app_state: Arc<AppState>) -> Router {
         )
         .route("/:id/adapter", get(get_circuit_adapter_config))
         .route("/:id/adapter", put(set_circuit_adapter_config))
+        .route(
+            "/:id/adapter/replication",
+            put(set_circuit_replication_policy),
+        )
+        .route("/:id/adapter/dry-run", post(dry_run_circuit_adapter_config))
         .route("/:id/visibility/toggle", put(toggle_circuit_visibility))
         // Webhook configuration routes
         .route("/:id/post-actions", get(get_post_action_settings))
@@ -564,6 +683,10 @@ pub fn circuit_routes(app_state: Arc<AppState>) -> Router {
             post(test_webhook),
         )
         .route("/:id/post-actions/deliveries", get(get_webhook_deliveries))
+        .route(
+            "/:id/post-actions/deliveries/:delivery_id/replay",
+            post(replay_webhook_delivery),
+        )
         .route("/list", get(list_circuits))
         .route("/member/:member_id", get(get_circuits_for_member))
         .with_state(app_state)
@@ -655,7 +778,7 @@ fn create_item_snapshot(
         "events": events.iter().map(|e| {
             serde_json::json!({
                 "event_id": e.event_id.to_string(),
-                "event_type": format!("{:?}", e.event_type),
+                "event_type": e.event_type.to_string(),
                 "timestamp": e.timestamp,
                 "source": e.source,
                 "metadata": e.metadata,
@@ -821,6 +944,9 @@ fn build_public_settings_from_request(
         data_quality_rules: request.data_quality_rules.clone(),
         export_permissions,
         public_since: None, // Will be set automatically when circuit becomes public
+        listed_in_directory: request.listed_in_directory.unwrap_or(false),
+        region: request.region.clone(),
+        item_categories: request.item_categories.clone().unwrap_or_default(),
     })
 }
 
@@ -859,12 +985,7 @@ fn circuit_to_response(circuit: Circuit) -> CircuitResponse {
             .pending_requests
             .into_iter()
             .filter(|req| matches!(req.status, crate::types::JoinRequestStatus::Pending))
-            .map(|req| JoinRequestResponse {
-                requester_id: req.requester_id,
-                requested_at: req.requested_at.timestamp(),
-                message: req.message,
-                status: format!("{:?}", req.status),
-            })
+            .map(JoinRequestResponse::from)
             .collect(),
         custom_roles: circuit
             .custom_roles
@@ -875,6 +996,11 @@ fn circuit_to_response(circuit: Circuit) -> CircuitResponse {
             })
             .collect(),
         public_settings: circuit.public_settings,
+        archived_at: circuit.archived_at.map(|t| t.timestamp()),
+        archived_by: circuit.archived_by,
+        parent_circuit_id: circuit.parent_circuit_id.map(|id| id.to_string()),
+        parent_access: circuit.parent_access,
+        pending_ownership_transfer: circuit.pending_ownership_transfer,
     }
 }
 
@@ -975,6 +1101,7 @@ async fn create_circuit(
         require_approval_for_push: require_approval_for_push.unwrap_or(false),
         require_approval_for_pull: require_approval_for_pull.unwrap_or(false),
         allow_public_visibility: should_enable_public,
+        required_attestations: Vec::new(),
     });
 
     // Build public settings if provided
@@ -1156,6 +1283,7 @@ async fn push_item(
                     crate::circuits_engine::CircuitsError::CircuitNotFound
                     | crate::circuits_engine::CircuitsError::NotFound => StatusCode::NOT_FOUND,
                     crate::circuits_engine::CircuitsError::ItemNotFound => StatusCode::NOT_FOUND,
+                    crate::circuits_engine::CircuitsError::CircuitArchived => StatusCode::CONFLICT,
                     _ => StatusCode::INTERNAL_SERVER_ERROR,
                 };
                 (status_code, Json(json!({"error": e.to_string()})))
@@ -1199,23 +1327,91 @@ async fn pull_item(
             .unwrap_or_else(|_| Vec::new())
     };
 
+    let events_count = events.len();
+    let events: Vec<EventDto> = events.into_iter().map(EventDto::from).collect();
+
     Ok(Json(json!({
-        "item": {
-            "dfid": item.dfid,
-            "identifiers": item.identifiers,
-            "enriched_data": item.enriched_data,
-            "creation_timestamp": item.creation_timestamp.timestamp(),
-            "last_modified": item.last_modified.timestamp(),
-            "source_entries": item.source_entries,
-            "confidence_score": item.confidence_score,
-            "status": format!("{:?}", item.status)
-        },
+        "item": ItemDto::from(item),
         "events": events,
-        "events_count": events.len(),
+        "events_count": events_count,
         "operation": operation_to_response(operation)
     })))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ForwardItemRequest {
+    pub target_circuit_id: Uuid,
+}
+
+/// Push an item of this circuit into another circuit, keeping it here
+async fn forward_item(
+    State(state): State<Arc<AppState>>,
+    Path((circuit_id, dfid)): Path<(Uuid, String)>,
+    AuthenticatedUser(requester_id): AuthenticatedUser,
+    Json(payload): Json<ForwardItemRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let forward = {
+        let mut engine = lock_circuits_engine(&state).await?;
+        engine
+            .forward_item_to_circuit(
+                &dfid,
+                &circuit_id,
+                &payload.target_circuit_id,
+                &requester_id,
+            )
+            .await
+            .map_err(circuits_error_response)?
+    };
+
+    Ok(Json(json!({
+        "success": true,
+        "dfid": forward.dfid,
+        "source_circuit_id": forward.source_circuit_id,
+        "target_circuit_id": forward.target_circuit_id,
+        "forward_event_id": forward.forward_event_id,
+        "operation": operation_to_response(forward.operation)
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetItemExpiryRequest {
+    /// Days from now the item stays in the circuit
+    pub ttl_days: Option<u32>,
+    pub expires_at: Option<chrono::DateTime<Utc>>,
+}
+
+/// Limit how long an item stays in the circuit; an empty body removes the limit
+async fn set_item_expiry(
+    State(state): State<Arc<AppState>>,
+    Path((circuit_id, dfid)): Path<(Uuid, String)>,
+    AuthenticatedUser(requester_id): AuthenticatedUser,
+    Json(payload): Json<SetItemExpiryRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let expires_at = match (payload.ttl_days, payload.expires_at) {
+        (Some(_), Some(_)) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "Provide either ttl_days or expires_at, not both"})),
+            ))
+        }
+        (Some(days), None) => Some(Utc::now() + chrono::Duration::days(i64::from(days))),
+        (None, expires_at) => expires_at,
+    };
+
+    let item = {
+        let engine = lock_circuits_engine(&state).await?;
+        engine
+            .set_circuit_item_expiry(&circuit_id, &dfid, expires_at, &requester_id)
+            .await
+            .map_err(circuits_error_response)?
+    };
+
+    Ok(Json(json!({
+        "success": true,
+        "item": item
+    })))
+}
+
 async fn push_local_item(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
@@ -1233,6 +1429,7 @@ async fn push_local_item(
         local_id: local_id_str,
         identifiers: identifier_requests,
         enriched_data,
+        priority,
     } = payload;
 
     let local_id = Uuid::parse_str(&local_id_str).map_err(|_| {
@@ -1255,12 +1452,13 @@ async fn push_local_item(
         let mut engine = lock_circuits_engine(&state).await?;
 
         engine
-            .push_local_item_to_circuit(
+            .push_local_item_to_circuit_with_priority(
                 &local_id,
                 identifiers,
                 enriched_data,
                 &circuit_id,
                 &requester_id, // Extracted from JWT token automatically
+                priority,
             )
             .await
             .map_err(|e| {
@@ -1622,6 +1820,12 @@ async fn push_events_to_circuit(
                         ),
                     ));
                 }
+                if c.is_archived() {
+                    return Err((
+                        StatusCode::CONFLICT,
+                        Json(json!({"error": CircuitsError::CircuitArchived.to_string()})),
+                    ));
+                }
             }
             None => {
                 return Err((
@@ -1635,6 +1839,7 @@ async fn push_events_to_circuit(
     let mut pushed_events = Vec::new();
     let mut events_pushed = 0;
     let mut events_deduplicated = 0;
+    let mut rejected_events = Vec::new();
 
     // Push each local event to the circuit
     for local_event_id_str in &payload.local_event_ids {
@@ -1667,6 +1872,12 @@ async fn push_events_to_circuit(
                     original_event_id: result.original_event_id.map(|id| id.to_string()),
                 });
             }
+            Err(EventsError::SchemaViolation(violations)) => {
+                rejected_events.push(RejectedEventInfo {
+                    local_event_id: local_event_id.to_string(),
+                    violations,
+                });
+            }
             Err(e) => {
                 tracing::warn!(
                     "Failed to push local event {} to circuit {}: {}",
@@ -1685,9 +1896,150 @@ async fn push_events_to_circuit(
         events_pushed,
         events_deduplicated,
         events: pushed_events,
+        rejected_events,
     }))
 }
 
+fn events_error_response(e: EventsError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        EventsError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        EventsError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        EventsError::NotFound => StatusCode::NOT_FOUND,
+        EventsError::SchemaViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        EventsError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+        EventsError::StorageError(_) | EventsError::EncryptionError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// Register a new JSON Schema version for an event type's metadata in this
+/// circuit. Events for the circuit's items must match the latest version.
+async fn register_event_schema(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(id): Path<String>,
+    Json(payload): Json<RegisterEventSchemaRequest>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    let circuit_id = Uuid::parse_str(&id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Invalid circuit ID format"})),
+        )
+    })?;
+    let event_type = parse_event_type(&payload.event_type)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
+
+    let events_engine = state.events_engine.read().await;
+    let schema = events_engine
+        .register_event_schema(circuit_id, event_type, payload.schema, &user_id)
+        .map_err(events_error_response)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "success": true,
+            "schema": schema
+        })),
+    ))
+}
+
+async fn list_event_schemas(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuit_id = Uuid::parse_str(&id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Invalid circuit ID format"})),
+        )
+    })?;
+
+    let events_engine = state.events_engine.read().await;
+    let schemas = events_engine
+        .list_event_schemas(&circuit_id)
+        .map_err(events_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": schemas.len(),
+        "schemas": schemas
+    })))
+}
+
+/// Allow a domain event type such as "Harvested" for this circuit's items, or
+/// update how it is displayed
+async fn register_custom_event_type(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(id): Path<String>,
+    Json(payload): Json<CustomEventTypeInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuit_id = Uuid::parse_str(&id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Invalid circuit ID format"})),
+        )
+    })?;
+
+    let events_engine = state.events_engine.read().await;
+    let event_type = events_engine
+        .register_custom_event_type(circuit_id, payload, &user_id)
+        .map_err(events_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "event_type": event_type
+    })))
+}
+
+async fn list_custom_event_types(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuit_id = Uuid::parse_str(&id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Invalid circuit ID format"})),
+        )
+    })?;
+
+    let events_engine = state.events_engine.read().await;
+    let event_types = events_engine
+        .list_custom_event_types(&circuit_id)
+        .map_err(events_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": event_types.len(),
+        "event_types": event_types
+    })))
+}
+
+async fn remove_custom_event_type(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path((id, name)): Path<(String, String)>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuit_id = Uuid::parse_str(&id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Invalid circuit ID format"})),
+        )
+    })?;
+
+    let events_engine = state.events_engine.read().await;
+    events_engine
+        .remove_custom_event_type(circuit_id, &name, &user_id)
+        .map_err(events_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "removed": name
+    })))
+}
+
 async fn get_circuit_operations(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
@@ -1817,6 +2169,275 @@ async fn deactivate_circuit(
     }
 }
 
+fn circuits_error_response(e: CircuitsError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        CircuitsError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        CircuitsError::CircuitNotFound
+        | CircuitsError::NotFound
+        | CircuitsError::ItemNotFound
+        | CircuitsError::MemberNotFound => StatusCode::NOT_FOUND,
+        CircuitsError::AdapterPermissionDenied(_) => StatusCode::FORBIDDEN,
+        CircuitsError::ValidationError(_) | CircuitsError::CircuitArchived => StatusCode::CONFLICT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn persist_circuit_change(state: &AppState, circuit: &Circuit, action: &str) {
+    let pg_lock = state.postgres_persistence.read().await;
+    if let Some(pg_instance) = &*pg_lock {
+        if let Err(e) = pg_instance.persist_circuit(circuit).await {
+            tracing::warn!(
+                "Failed to persist circuit {} {} to PostgreSQL: {}",
+                circuit.circuit_id,
+                action,
+                e
+            );
+        } else {
+            tracing::info!(
+                "Circuit {} {} persisted to PostgreSQL",
+                circuit.circuit_id,
+                action
+            );
+        }
+    }
+}
+
+async fn archive_circuit(
+    State(state): State<Arc<AppState>>,
+    Path(circuit_id): Path<Uuid>,
+    AuthenticatedUser(requester_id): AuthenticatedUser,
+) -> Result<Json<CircuitResponse>, (StatusCode, Json<Value>)> {
+    let circuit = {
+        let mut engine = lock_circuits_engine(&state).await?;
+        engine
+            .archive_circuit(&circuit_id, &requester_id)
+            .await
+            .map_err(circuits_error_response)?
+    };
+
+    // Await before returning for read-after-write consistency
+    persist_circuit_change(&state, &circuit, "archival").await;
+    Ok(Json(circuit_to_response(circuit)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InitiateOwnershipTransferRequest {
+    pub new_owner_id: String,
+}
+
+/// Offer ownership to another member; it changes hands once they accept
+async fn initiate_ownership_transfer(
+    State(state): State<Arc<AppState>>,
+    Path(circuit_id): Path<Uuid>,
+    AuthenticatedUser(requester_id): AuthenticatedUser,
+    Json(payload): Json<InitiateOwnershipTransferRequest>,
+) -> Result<Json<CircuitResponse>, (StatusCode, Json<Value>)> {
+    let circuit = {
+        let mut engine = lock_circuits_engine(&state).await?;
+        engine
+            .initiate_ownership_transfer(&circuit_id, &payload.new_owner_id, &requester_id)
+            .await
+            .map_err(circuits_error_response)?
+    };
+
+    persist_circuit_change(&state, &circuit, "ownership transfer").await;
+    Ok(Json(circuit_to_response(circuit)))
+}
+
+async fn accept_ownership_transfer(
+    State(state): State<Arc<AppState>>,
+    Path(circuit_id): Path<Uuid>,
+    AuthenticatedUser(requester_id): AuthenticatedUser,
+) -> Result<Json<CircuitResponse>, (StatusCode, Json<Value>)> {
+    let circuit = {
+        let mut engine = lock_circuits_engine(&state).await?;
+        engine
+            .accept_ownership_transfer(&circuit_id, &requester_id)
+            .await
+            .map_err(circuits_error_response)?
+    };
+
+    persist_circuit_change(&state, &circuit, "ownership transfer").await;
+    Ok(Json(circuit_to_response(circuit)))
+}
+
+/// Withdrawn by the owner or declined by the proposed owner
+async fn cancel_ownership_transfer(
+    State(state): State<Arc<AppState>>,
+    Path(circuit_id): Path<Uuid>,
+    AuthenticatedUser(requester_id): AuthenticatedUser,
+) -> Result<Json<CircuitResponse>, (StatusCode, Json<Value>)> {
+    let circuit = {
+        let mut engine = lock_circuits_engine(&state).await?;
+        engine
+            .cancel_ownership_transfer(&circuit_id, &requester_id)
+            .await
+            .map_err(circuits_error_response)?
+    };
+
+    persist_circuit_change(&state, &circuit, "ownership transfer").await;
+    Ok(Json(circuit_to_response(circuit)))
+}
+
+async fn restore_circuit(
+    State(state): State<Arc<AppState>>,
+    Path(circuit_id): Path<Uuid>,
+    AuthenticatedUser(requester_id): AuthenticatedUser,
+) -> Result<Json<CircuitResponse>, (StatusCode, Json<Value>)> {
+    let circuit = {
+        let mut engine = lock_circuits_engine(&state).await?;
+        engine
+            .restore_circuit(&circuit_id, &requester_id)
+            .await
+            .map_err(circuits_error_response)?
+    };
+
+    persist_circuit_change(&state, &circuit, "restore").await;
+    Ok(Json(circuit_to_response(circuit)))
+}
+
+/// The caller's archived circuits, which the default listings leave out
+async fn list_archived_circuits(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Vec<CircuitResponse>>, (StatusCode, Json<Value>)> {
+    let engine = lock_circuits_engine(&state).await?;
+    let circuits = engine
+        .get_archived_circuits_for_member(&user_id)
+        .map_err(circuits_error_response)?;
+
+    Ok(Json(
+        circuits.into_iter().map(circuit_to_response).collect(),
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetParentCircuitRequest {
+    /// None detaches the circuit from its parent
+    pub parent_circuit_id: Option<Uuid>,
+    #[serde(default)]
+    pub parent_access: ParentAccess,
+}
+
+async fn set_parent_circuit(
+    State(state): State<Arc<AppState>>,
+    Path(circuit_id): Path<Uuid>,
+    AuthenticatedUser(requester_id): AuthenticatedUser,
+    Json(payload): Json<SetParentCircuitRequest>,
+) -> Result<Json<CircuitResponse>, (StatusCode, Json<Value>)> {
+    let circuit = {
+        let mut engine = lock_circuits_engine(&state).await?;
+        engine
+            .set_parent_circuit(
+                &circuit_id,
+                payload.parent_circuit_id,
+                payload.parent_access,
+                &requester_id,
+            )
+            .await
+            .map_err(circuits_error_response)?
+    };
+
+    persist_circuit_change(&state, &circuit, "parent change").await;
+    Ok(Json(circuit_to_response(circuit)))
+}
+
+/// The circuit, once the user is known to be able to read it
+fn readable_circuit(
+    engine: &CircuitsEngine<SharedStorage>,
+    circuit_id: &Uuid,
+    user_id: &str,
+) -> Result<Circuit, (StatusCode, Json<Value>)> {
+    let circuit = engine
+        .get_circuit(circuit_id)
+        .map_err(circuits_error_response)?
+        .ok_or_else(|| circuits_error_response(CircuitsError::CircuitNotFound))?;
+    if !engine
+        .can_read_circuit(&circuit, user_id)
+        .map_err(circuits_error_response)?
+    {
+        return Err(circuits_error_response(CircuitsError::PermissionDenied(
+            "User cannot read this circuit".to_string(),
+        )));
+    }
+    Ok(circuit)
+}
+
+async fn get_child_circuits(
+    State(state): State<Arc<AppState>>,
+    Path(circuit_id): Path<Uuid>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Vec<CircuitResponse>>, (StatusCode, Json<Value>)> {
+    let engine = lock_circuits_engine(&state).await?;
+    readable_circuit(&engine, &circuit_id, &user_id)?;
+    let children = engine
+        .get_child_circuits(&circuit_id)
+        .map_err(circuits_error_response)?;
+
+    Ok(Json(
+        children
+            .into_iter()
+            .filter(|circuit| !circuit.is_archived())
+            .map(circuit_to_response)
+            .collect(),
+    ))
+}
+
+/// Parent, grandparent and so on up to the root
+async fn get_ancestor_circuits(
+    State(state): State<Arc<AppState>>,
+    Path(circuit_id): Path<Uuid>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Vec<CircuitResponse>>, (StatusCode, Json<Value>)> {
+    let engine = lock_circuits_engine(&state).await?;
+    readable_circuit(&engine, &circuit_id, &user_id)?;
+    let ancestors = engine
+        .get_ancestor_circuits(&circuit_id)
+        .map_err(circuits_error_response)?;
+
+    Ok(Json(
+        ancestors.into_iter().map(circuit_to_response).collect(),
+    ))
+}
+
+/// Direct members and those inherited from ancestors
+async fn get_effective_members(
+    State(state): State<Arc<AppState>>,
+    Path(circuit_id): Path<Uuid>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = lock_circuits_engine(&state).await?;
+    readable_circuit(&engine, &circuit_id, &user_id)?;
+    let members = engine
+        .get_effective_members(&circuit_id)
+        .map_err(circuits_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": members.len(),
+        "members": members
+    })))
+}
+
+/// Items of the circuit and of the descendants that roll up into it
+async fn get_rollup_items(
+    State(state): State<Arc<AppState>>,
+    Path(circuit_id): Path<Uuid>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = lock_circuits_engine(&state).await?;
+    let items = engine
+        .get_rollup_items(&circuit_id, &user_id)
+        .map_err(circuits_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": items.len(),
+        "items": items
+    })))
+}
+
 async fn list_circuits(
     State(state): State<Arc<AppState>>,
     Query(params): Query<CircuitListQuery>,
@@ -1856,6 +2477,15 @@ async fn list_circuits(
                 circuits.retain(|circuit| circuit.permissions.allow_public_visibility);
             }
 
+            let wants_archived = params.include_archived.unwrap_or(false)
+                || params
+                    .status
+                    .as_deref()
+                    .is_some_and(|status| status.eq_ignore_ascii_case("archived"));
+            if !wants_archived {
+                circuits.retain(|circuit| !circuit.is_archived());
+            }
+
             // Apply status filter
             if let Some(status_str) = &params.status {
                 circuits.retain(|circuit| {
@@ -1882,8 +2512,11 @@ async fn get_circuits_for_member(
 
     match engine.get_circuits_for_member(&member_id) {
         Ok(circuits) => {
-            let response: Vec<CircuitResponse> =
-                circuits.into_iter().map(circuit_to_response).collect();
+            let response: Vec<CircuitResponse> = circuits
+                .into_iter()
+                .filter(|circuit| !circuit.is_archived())
+                .map(circuit_to_response)
+                .collect();
             Ok(Json(response))
         }
         Err(e) => Err((
@@ -1913,6 +2546,9 @@ async fn request_to_join_circuit(
         .await
     {
         Ok(circuit) => {
+            drop(engine);
+            persist_circuit_change(&state, &circuit, "join request").await;
+
             // Send notifications to circuit owner and admins
             {
                 let notification_engine = state.notification_engine.write().await;
@@ -1965,49 +2601,38 @@ async fn request_to_join_circuit(
 
             Ok(Json(circuit_to_response(circuit)))
         }
-        Err(e) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(json!({"error": format!("Failed to submit join request: {}", e)})),
-        )),
+        Err(e) => Err(circuits_error_response(e)),
     }
 }
 
 async fn get_pending_join_requests(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
 ) -> Result<Json<Vec<JoinRequestResponse>>, (StatusCode, Json<Value>)> {
     let circuit_id = Uuid::parse_str(&id).map_err(|_| {
         (
             StatusCode::BAD_REQUEST,
             Json(json!({"error": "Invalid circuit ID format"})),
-        )
-    })?;
-
-    let engine = lock_circuits_engine(&state).await?;
-
-    match engine.get_pending_join_requests(&circuit_id) {
-        Ok(requests) => {
-            let response: Vec<JoinRequestResponse> = requests
-                .into_iter()
-                .map(|req| JoinRequestResponse {
-                    requester_id: req.requester_id,
-                    requested_at: req.requested_at.timestamp(),
-                    message: req.message,
-                    status: format!("{:?}", req.status),
-                })
-                .collect();
-            Ok(Json(response))
-        }
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({"error": format!("Failed to get pending requests: {}", e)})),
-        )),
-    }
+        )
+    })?;
+
+    let engine = lock_circuits_engine(&state).await?;
+    let requests = engine
+        .get_pending_join_requests(&circuit_id, &user_id)
+        .map_err(circuits_error_response)?;
+    Ok(Json(
+        requests
+            .into_iter()
+            .map(JoinRequestResponse::from)
+            .collect(),
+    ))
 }
 
 async fn approve_join_request(
     State(state): State<Arc<AppState>>,
     Path((id, requester_id)): Path<(String, String)>,
+    AuthenticatedUser(admin_id): AuthenticatedUser,
     Json(payload): Json<ApproveJoinRequest>,
 ) -> Result<Json<CircuitResponse>, (StatusCode, Json<Value>)> {
     let circuit_id = Uuid::parse_str(&id).map_err(|_| {
@@ -2021,66 +2646,41 @@ async fn approve_join_request(
         .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
 
     let mut engine = lock_circuits_engine(&state).await?;
-
-    match engine
-        .approve_join_request(&circuit_id, &requester_id, &payload.admin_id, role)
+    let circuit = engine
+        .approve_join_request(&circuit_id, &requester_id, &admin_id, role)
         .await
-    {
-        Ok(circuit) => {
-            // Clone circuit for PostgreSQL persistence
-            let circuit_clone = circuit.clone();
+        .map_err(circuits_error_response)?;
 
-            // Release engine lock before async operations
-            drop(engine);
-
-            // PostgreSQL persistence - AWAIT before returning for read-after-write consistency
-            let pg_lock = state.postgres_persistence.read().await;
-            if let Some(pg_instance) = &*pg_lock {
-                if let Err(e) = pg_instance.persist_circuit(&circuit_clone).await {
-                    tracing::warn!(
-                        "Failed to persist join request approval to PostgreSQL: {}",
-                        e
-                    );
-                } else {
-                    tracing::info!(
-                        "Circuit {} join approval persisted to PostgreSQL",
-                        circuit_clone.circuit_id
-                    );
-                }
-            }
-            drop(pg_lock);
+    // Release engine lock before async operations
+    drop(engine);
+    persist_circuit_change(&state, &circuit, "join approval").await;
 
-            // Create and broadcast notification to the requester
-            {
-                let notification_engine = state.notification_engine.write().await;
-                if let Ok(notification) = notification_engine.create_join_approved_notification(
-                    &requester_id,
-                    &circuit_id.to_string(),
-                    &circuit.name,
-                    &payload.admin_id,
-                    &payload.role,
-                ) {
-                    // Broadcast via WebSocket
-                    let _ = state.notification_tx.send(
-                        crate::api::notifications::NotificationMessage {
-                            msg_type: "notification".to_string(),
-                            notification: notification.clone(),
-                        },
-                    );
-                }
-            }
-            Ok(Json(circuit_to_response(circuit)))
+    // Create and broadcast notification to the requester
+    {
+        let notification_engine = state.notification_engine.write().await;
+        if let Ok(notification) = notification_engine.create_join_approved_notification(
+            &requester_id,
+            &circuit_id.to_string(),
+            &circuit.name,
+            &admin_id,
+            &payload.role,
+        ) {
+            // Broadcast via WebSocket
+            let _ = state
+                .notification_tx
+                .send(crate::api::notifications::NotificationMessage {
+                    msg_type: "notification".to_string(),
+                    notification: notification.clone(),
+                });
         }
-        Err(e) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(json!({"error": format!("Failed to approve join request: {}", e)})),
-        )),
     }
+    Ok(Json(circuit_to_response(circuit)))
 }
 
 async fn reject_join_request(
     State(state): State<Arc<AppState>>,
     Path((id, requester_id)): Path<(String, String)>,
+    AuthenticatedUser(admin_id): AuthenticatedUser,
     Json(payload): Json<RejectJoinRequest>,
 ) -> Result<Json<CircuitResponse>, (StatusCode, Json<Value>)> {
     let circuit_id = Uuid::parse_str(&id).map_err(|_| {
@@ -2090,61 +2690,40 @@ async fn reject_join_request(
         )
     })?;
 
+    let reason = payload
+        .reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
     let mut engine = lock_circuits_engine(&state).await?;
-
-    match engine
-        .reject_join_request(&circuit_id, &requester_id, &payload.admin_id)
+    let circuit = engine
+        .reject_join_request(&circuit_id, &requester_id, &admin_id, reason.clone())
         .await
-    {
-        Ok(circuit) => {
-            // Clone circuit for PostgreSQL persistence
-            let circuit_clone = circuit.clone();
-
-            // Release engine lock before async operations
-            drop(engine);
+        .map_err(circuits_error_response)?;
 
-            // PostgreSQL persistence - AWAIT before returning for read-after-write consistency
-            let pg_lock = state.postgres_persistence.read().await;
-            if let Some(pg_instance) = &*pg_lock {
-                if let Err(e) = pg_instance.persist_circuit(&circuit_clone).await {
-                    tracing::warn!(
-                        "Failed to persist join request rejection to PostgreSQL: {}",
-                        e
-                    );
-                } else {
-                    tracing::info!(
-                        "Circuit {} join rejection persisted to PostgreSQL",
-                        circuit_clone.circuit_id
-                    );
-                }
-            }
-            drop(pg_lock);
+    // Release engine lock before async operations
+    drop(engine);
+    persist_circuit_change(&state, &circuit, "join rejection").await;
 
-            // Create and broadcast notification to the requester
-            {
-                let notification_engine = state.notification_engine.write().await;
-                if let Ok(notification) = notification_engine.create_join_rejected_notification(
-                    &requester_id,
-                    &circuit_id.to_string(),
-                    &circuit.name,
-                    &payload.admin_id,
-                ) {
-                    // Broadcast via WebSocket
-                    let _ = state.notification_tx.send(
-                        crate::api::notifications::NotificationMessage {
-                            msg_type: "notification".to_string(),
-                            notification: notification.clone(),
-                        },
-                    );
-                }
-            }
-            Ok(Json(circuit_to_response(circuit)))
+    // Create and broadcast notification to the requester
+    {
+        let notification_engine = state.notification_engine.write().await;
+        if let Ok(notification) = notification_engine.create_join_rejected_notification(
+            &requester_id,
+            &circuit_id.to_string(),
+            &circuit.name,
+            &admin_id,
+            reason.as_deref(),
+        ) {
+            // Broadcast via WebSocket
+            let _ = state
+                .notification_tx
+                .send(crate::api::notifications::NotificationMessage {
+                    msg_type: "notification".to_string(),
+                    notification: notification.clone(),
+                });
         }
-        Err(e) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(json!({"error": format!("Failed to reject join request: {}", e)})),
-        )),
     }
+    Ok(Json(circuit_to_response(circuit)))
 }
 
 async fn update_circuit(
@@ -2190,6 +2769,9 @@ async fn update_circuit(
             allow_public_visibility: update_perms
                 .allow_public_visibility
                 .unwrap_or(current_circuit.permissions.allow_public_visibility),
+            required_attestations: update_perms
+                .required_attestations
+                .unwrap_or_else(|| current_circuit.permissions.required_attestations.clone()),
         })
     } else {
         None
@@ -2583,7 +3165,7 @@ async fn get_public_circuit(
                         "events": item.events.iter().map(|e| json!({
                             "event_id": e.event_id.to_string(),
                             "dfid": e.dfid,
-                            "event_type": format!("{:?}", e.event_type),
+                            "event_type": e.event_type.to_string(),
                             "source": e.source,
                             "visibility": format!("{:?}", e.visibility),
                             "timestamp": e.timestamp.to_rfc3339(),
@@ -2793,7 +3375,12 @@ async fn batch_push_items(
         let result = async {
             let mut engine = lock_circuits_engine(&state).await?;
             engine
-                .push_item_to_circuit(&dfid, &circuit_id_copy, &requester_id_clone)
+                .push_item_to_circuit_with_priority(
+                    &dfid,
+                    &circuit_id_copy,
+                    &requester_id_clone,
+                    payload.priority,
+                )
                 .await
                 .map_err(|e| {
                     (
@@ -2867,7 +3454,7 @@ async fn get_circuit_pending_items(
                     identifiers: item
                         .identifiers
                         .into_iter()
-                        .map(|identifier| IdentifierRequest::from_identifier(&identifier))
+                        .map(|identifier| IdentifierDto::from(&identifier))
                         .collect(),
                     enriched_data: serde_json::to_value(item.enriched_data)
                         .unwrap_or(serde_json::json!({})),
@@ -3015,6 +3602,12 @@ async fn get_circuit_adapter_config(
                     auto_migrate_existing: adapter_config.auto_migrate_existing,
                     configured_by: adapter_config.configured_by,
                     configured_at: adapter_config.configured_at.to_rfc3339(),
+                    replication_policy: adapter_config.replication_policy,
+                    mirror_adapters: adapter_config
+                        .mirror_adapters
+                        .iter()
+                        .map(adapter_type_to_string)
+                        .collect(),
                 }))
             } else {
                 // This branch should never be reached now that circuits are initialized with default adapter_config
@@ -3026,6 +3619,8 @@ async fn get_circuit_adapter_config(
                     auto_migrate_existing: false,
                     configured_by: "system".to_string(),
                     configured_at: chrono::Utc::now().to_rfc3339(),
+                    replication_policy: ReplicationPolicy::Single,
+                    mirror_adapters: Vec::new(),
                 }))
             }
         }
@@ -3041,6 +3636,158 @@ async fn get_circuit_adapter_config(
 }
 
 /// Helper function to convert AdapterType enum to hyphenated string format
+async fn set_circuit_replication_policy(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(payload): Json<SetReplicationPolicyRequest>,
+) -> Result<Json<GetAdapterConfigResponse>, (StatusCode, Json<Value>)> {
+    let circuit_id = Uuid::parse_str(&id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Invalid circuit ID format"})),
+        )
+    })?;
+
+    let mirror_adapters = payload
+        .mirror_adapters
+        .iter()
+        .map(|a| AdapterType::from_string(a))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": format!("Invalid adapter type: {}", e)})),
+            )
+        })?;
+
+    let (adapter_config, circuit) = {
+        let mut engine = lock_circuits_engine(&state).await?;
+        let adapter_config = engine
+            .set_circuit_replication_policy(
+                &circuit_id,
+                &user_id,
+                payload.replication_policy,
+                mirror_adapters,
+            )
+            .await
+            .map_err(|e| {
+                let status = match &e {
+                    crate::circuits_engine::CircuitsError::PermissionDenied(_) => {
+                        StatusCode::FORBIDDEN
+                    }
+                    crate::circuits_engine::CircuitsError::CircuitNotFound => StatusCode::NOT_FOUND,
+                    crate::circuits_engine::CircuitsError::ValidationError(_) => {
+                        StatusCode::BAD_REQUEST
+                    }
+                    _ => StatusCode::INTERNAL_SERVER_ERROR,
+                };
+                (status, Json(json!({"error": e.to_string()})))
+            })?;
+        let circuit = engine.get_circuit(&circuit_id).ok().flatten();
+        (adapter_config, circuit)
+    };
+
+    // PostgreSQL persistence - AWAIT before returning for read-after-write consistency
+    if let Some(circuit) = circuit {
+        let pg_lock = state.postgres_persistence.read().await;
+        if let Some(pg_instance) = &*pg_lock {
+            if let Err(e) = pg_instance.persist_circuit(&circuit).await {
+                tracing::warn!(
+                    "Failed to persist circuit replication policy to PostgreSQL: {}",
+                    e
+                );
+            }
+        }
+    }
+
+    Ok(Json(GetAdapterConfigResponse {
+        adapter_type: adapter_config
+            .adapter_type
+            .map(|adapter| adapter_type_to_string(&adapter)),
+        sponsor_adapter_access: adapter_config.sponsor_adapter_access,
+        requires_approval: adapter_config.requires_approval,
+        auto_migrate_existing: adapter_config.auto_migrate_existing,
+        configured_by: adapter_config.configured_by,
+        configured_at: adapter_config.configured_at.to_rfc3339(),
+        replication_policy: adapter_config.replication_policy,
+        mirror_adapters: adapter_config
+            .mirror_adapters
+            .iter()
+            .map(adapter_type_to_string)
+            .collect(),
+    }))
+}
+
+/// Validate a candidate adapter configuration without writing anything, so it can be
+/// checked before a production circuit is switched to it
+async fn dry_run_circuit_adapter_config(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(payload): Json<DryRunAdapterConfigRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuit_id = Uuid::parse_str(&id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Invalid circuit ID format"})),
+        )
+    })?;
+
+    let invalid_adapter = |e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Invalid adapter type: {}", e)})),
+        )
+    };
+    let adapter_type = payload
+        .adapter_type
+        .as_deref()
+        .map(AdapterType::from_string)
+        .transpose()
+        .map_err(invalid_adapter)?;
+    let mirror_adapters = payload
+        .mirror_adapters
+        .map(|mirrors| {
+            mirrors
+                .iter()
+                .map(|a| AdapterType::from_string(a))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()
+        .map_err(invalid_adapter)?;
+
+    let engine = state.circuits_engine.read().await;
+    let report = engine
+        .dry_run_circuit_adapter_config(
+            &circuit_id,
+            &user_id,
+            adapter_type,
+            payload.replication_policy,
+            mirror_adapters,
+            payload.dfid.as_deref(),
+        )
+        .await
+        .map_err(|e| {
+            let status = match &e {
+                crate::circuits_engine::CircuitsError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+                crate::circuits_engine::CircuitsError::CircuitNotFound
+                | crate::circuits_engine::CircuitsError::ItemNotFound => StatusCode::NOT_FOUND,
+                crate::circuits_engine::CircuitsError::ValidationError(_) => {
+                    StatusCode::BAD_REQUEST
+                }
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (status, Json(json!({"error": e.to_string()})))
+        })?;
+
+    Ok(Json(json!({
+        "success": true,
+        "dry_run": true,
+        "report": report
+    })))
+}
+
 fn adapter_type_to_string(adapter: &AdapterType) -> String {
     adapter.to_string()
 }
@@ -3157,6 +3904,12 @@ async fn set_circuit_adapter_config(
                 auto_migrate_existing: adapter_config.auto_migrate_existing,
                 configured_by: adapter_config.configured_by,
                 configured_at: adapter_config.configured_at.to_rfc3339(),
+                replication_policy: adapter_config.replication_policy,
+                mirror_adapters: adapter_config
+                    .mirror_adapters
+                    .iter()
+                    .map(adapter_type_to_string)
+                    .collect(),
             }))
         }
         Err(e) => {
@@ -3281,6 +4034,8 @@ async fn update_post_action_settings(
             "item_approved" => Some(PostActionTrigger::ItemApproved),
             "item_tokenized" => Some(PostActionTrigger::ItemTokenized),
             "item_published" => Some(PostActionTrigger::ItemPublished),
+            "proof_revoked" => Some(PostActionTrigger::ProofRevoked),
+            "proof_generated" => Some(PostActionTrigger::ProofGenerated),
             _ => None,
         })
         .collect();
@@ -3341,6 +4096,22 @@ async fn create_webhook(
             Json(json!({"error": e.to_string()})),
         )
     })?;
+    if let Some(key) = &request.encryption_key {
+        webhook_encryption::public_key(key).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": e.to_string()})),
+            )
+        })?;
+    }
+    if let Some(filter) = &request.filter {
+        WebhookEngine::<PostgresStorageWithCache>::validate_filter(filter).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": e.to_string()})),
+            )
+        })?;
+    }
 
     let mut circuit = with_storage(
         &state.shared_storage,
@@ -3402,6 +4173,8 @@ async fn create_webhook(
 
     webhook.auth_credentials = request.auth_credentials;
     webhook.enabled = request.enabled.unwrap_or(true);
+    webhook.encryption_key = request.encryption_key;
+    webhook.filter = request.filter.unwrap_or_default();
 
     // Add webhook to circuit
     let mut settings = circuit.post_action_settings.unwrap_or_default();
@@ -3589,6 +4362,27 @@ async fn update_webhook(
     if let Some(enabled) = request.enabled {
         webhook.enabled = enabled;
     }
+    if request.remove_encryption_key {
+        webhook.encryption_key = None;
+    }
+    if let Some(key) = request.encryption_key {
+        webhook_encryption::public_key(&key).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": e.to_string()})),
+            )
+        })?;
+        webhook.encryption_key = Some(key);
+    }
+    if let Some(filter) = request.filter {
+        WebhookEngine::<PostgresStorageWithCache>::validate_filter(&filter).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": e.to_string()})),
+            )
+        })?;
+        webhook.filter = filter;
+    }
 
     webhook.updated_at = Utc::now();
     circuit.post_action_settings = Some(settings);
@@ -3868,6 +4662,74 @@ async fn get_webhook_deliveries(
     })))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ReplayDeliveryRequest {
+    pub target_url: String,
+}
+
+/// Re-send a past delivery's payload to a test URL, marked as a replay
+async fn replay_webhook_delivery(
+    State(state): State<Arc<AppState>>,
+    Path((circuit_id, delivery_id)): Path<(Uuid, Uuid)>,
+    Extension(claims): Extension<Claims>,
+    Json(request): Json<ReplayDeliveryRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuit = with_storage(
+        &state.shared_storage,
+        "circuits::replay_webhook_delivery::get_circuit",
+        |storage| {
+            storage
+                .get_circuit(&circuit_id)
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
+        },
+    )
+    .map_err(|e| match e {
+        StorageLockError::Timeout => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"error": "Storage temporarily unavailable"})),
+        ),
+        StorageLockError::Other(msg) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": msg})),
+        ),
+    })?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({"error": "Circuit not found"})),
+    ))?;
+
+    if !circuit.has_permission(&claims.user_id, &Permission::ManagePermissions) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": "Permission denied"})),
+        ));
+    }
+
+    let replay = WebhookEngine::new(Arc::clone(&state.shared_storage))
+        .replay_delivery(&circuit_id, &delivery_id, &request.target_url)
+        .await
+        .map_err(|e| {
+            let status = match &e {
+                WebhookError::ValidationError(_) => StatusCode::BAD_REQUEST,
+                WebhookError::ConfigurationError(_) => StatusCode::NOT_FOUND,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (status, Json(json!({"error": e.to_string()})))
+        })?;
+
+    tracing::info!(
+        "🔁 Delivery {} replayed to {} by {} ({:?})",
+        delivery_id,
+        replay.target_url,
+        claims.user_id,
+        replay.response_code
+    );
+    Ok(Json(json!({
+        "success": true,
+        "replay": replay
+    })))
+}
+
 /// Toggle circuit visibility between public and private
 #[axum::debug_handler]
 async fn toggle_circuit_visibility(
@@ -3915,6 +4777,7 @@ async fn toggle_circuit_visibility(
         require_approval_for_push: circuit.permissions.require_approval_for_push,
         require_approval_for_pull: circuit.permissions.require_approval_for_pull,
         allow_public_visibility: new_visibility,
+        required_attestations: circuit.permissions.required_attestations.clone(),
     };
 
     // Update the circuit
@@ -0,0 +1,165 @@
+//! Comment threads on circuit events and pending operations, visible to
+//! circuit members only. `@member_id` mentions notify the member.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{delete, get},
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::comment_engine::{AddCommentInput, CommentEngine, CommentError};
+use crate::types::CommentTarget;
+
+/// Mounted at `/api/comments`
+pub fn comment_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/circuits/:circuit_id/events/:event_id",
+            get(list_event_comments).post(add_event_comment),
+        )
+        .route(
+            "/circuits/:circuit_id/operations/:operation_id",
+            get(list_operation_comments).post(add_operation_comment),
+        )
+        .route(
+            "/circuits/:circuit_id/comments/:comment_id",
+            delete(delete_comment),
+        )
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> CommentEngine<SharedStorage> {
+    CommentEngine::new(Arc::clone(&app_state.shared_storage))
+        .with_live_stream(app_state.live_stream.clone())
+}
+
+fn comment_error_response(e: CommentError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        CommentError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        CommentError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        CommentError::NotFound(_) => StatusCode::NOT_FOUND,
+        CommentError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+fn add_comment(
+    app_state: &AppState,
+    circuit_id: &Uuid,
+    target: CommentTarget,
+    input: AddCommentInput,
+    user_id: &str,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let comment = engine(app_state)
+        .add(circuit_id, target, input, user_id, Utc::now())
+        .map_err(comment_error_response)?;
+
+    tracing::info!(
+        "💬 Comment {} added to circuit {} by {} ({} mentions)",
+        comment.comment_id,
+        circuit_id,
+        user_id,
+        comment.mentions.len()
+    );
+    Ok(Json(json!({
+        "success": true,
+        "comment": comment
+    })))
+}
+
+fn list_comments(
+    app_state: &AppState,
+    circuit_id: &Uuid,
+    target: CommentTarget,
+    user_id: &str,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let comments = engine(app_state)
+        .list(circuit_id, &target, user_id)
+        .map_err(comment_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "target": target,
+        "count": comments.len(),
+        "comments": comments
+    })))
+}
+
+async fn add_event_comment(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path((circuit_id, event_id)): Path<(Uuid, Uuid)>,
+    Json(input): Json<AddCommentInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    add_comment(
+        &app_state,
+        &circuit_id,
+        CommentTarget::Event { event_id },
+        input,
+        &user_id,
+    )
+}
+
+async fn list_event_comments(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path((circuit_id, event_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    list_comments(
+        &app_state,
+        &circuit_id,
+        CommentTarget::Event { event_id },
+        &user_id,
+    )
+}
+
+async fn add_operation_comment(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path((circuit_id, operation_id)): Path<(Uuid, Uuid)>,
+    Json(input): Json<AddCommentInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    add_comment(
+        &app_state,
+        &circuit_id,
+        CommentTarget::PendingOperation { operation_id },
+        input,
+        &user_id,
+    )
+}
+
+async fn list_operation_comments(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path((circuit_id, operation_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    list_comments(
+        &app_state,
+        &circuit_id,
+        CommentTarget::PendingOperation { operation_id },
+        &user_id,
+    )
+}
+
+async fn delete_comment(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path((circuit_id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let comment = engine(&app_state)
+        .delete(&circuit_id, &comment_id, &user_id)
+        .map_err(comment_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "comment_id": comment.comment_id
+    })))
+}
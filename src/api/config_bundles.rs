@@ -0,0 +1,85 @@
+//! Configuration bundle export/import, nested under the admin-guarded
+//! `/api/admin/config-bundles`.
+
+use axum::{extract::State, http::StatusCode, response::Json, routing::post, Router};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AdminUser;
+use crate::config_bundle_engine::{
+    BundleKeys, ConfigBundleEngine, ConfigBundleError, ConfigImportRequest,
+};
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ConfigExportRequest {
+    /// Label recorded in the bundle, e.g. "production"
+    pub source_environment: Option<String>,
+}
+
+pub fn admin_config_bundle_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/export", post(export_bundle))
+        .route("/import", post(import_bundle))
+}
+
+fn engine(app_state: &AppState) -> ConfigBundleEngine<SharedStorage> {
+    ConfigBundleEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn config_bundle_error_response(e: ConfigBundleError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        ConfigBundleError::ValidationError(_) | ConfigBundleError::InvalidBundle(_) => {
+            StatusCode::BAD_REQUEST
+        }
+        // Missing keys are a deployment problem, not a bad request
+        ConfigBundleError::KeyError(_) => StatusCode::SERVICE_UNAVAILABLE,
+        ConfigBundleError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn export_bundle(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    request: Option<Json<ConfigExportRequest>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    let keys = BundleKeys::from_env().map_err(config_bundle_error_response)?;
+    let bundle = engine(&app_state)
+        .export(
+            &keys,
+            &admin_user_id,
+            request.source_environment,
+            Utc::now(),
+        )
+        .map_err(config_bundle_error_response)?;
+
+    tracing::info!("📦 Config bundle exported by {}", admin_user_id);
+    Ok(Json(json!({
+        "success": true,
+        "bundle": bundle
+    })))
+}
+
+/// Apply a bundle exported by a trusted deployment; `dry_run` previews it
+async fn import_bundle(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Json(request): Json<ConfigImportRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let keys = BundleKeys::from_env().map_err(config_bundle_error_response)?;
+    let summary = engine(&app_state)
+        .import(&keys, request)
+        .map_err(config_bundle_error_response)?;
+
+    if !summary.dry_run {
+        tracing::info!("📦 Config bundle imported by {}", admin_user_id);
+    }
+    Ok(Json(json!({
+        "success": true,
+        "summary": summary
+    })))
+}
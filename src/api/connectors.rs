@@ -0,0 +1,330 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::shared_state::AppState;
+use crate::auth_middleware::AuthenticatedUser;
+use crate::connectors::{
+    draft_template, template_issues, ConnectorEngine, ConnectorError, ConnectorInput,
+    MappingTemplateInput, PreviewSample,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct DraftPreviewRequest {
+    pub template: MappingTemplateInput,
+    #[serde(flatten)]
+    pub sample: PreviewSample,
+}
+
+pub fn connector_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(list_connectors).post(create_connector))
+        .route("/dashboard", get(list_dashboards))
+        .route("/templates", get(list_templates).post(create_template))
+        .route("/templates/validate", post(validate_template))
+        .route("/templates/preview", post(preview_draft_template))
+        .route(
+            "/templates/:template_id",
+            get(get_template)
+                .put(update_template)
+                .delete(delete_template),
+        )
+        .route("/templates/:template_id/preview", post(preview_template))
+        .route(
+            "/:connector_id",
+            get(get_connector)
+                .put(update_connector)
+                .delete(delete_connector),
+        )
+        .route("/:connector_id/run", post(run_connector))
+        .route("/:connector_id/dashboard", get(get_dashboard))
+        .with_state(app_state)
+}
+
+fn connector_error_response(e: ConnectorError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        ConnectorError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        ConnectorError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        ConnectorError::NotFound(_) => StatusCode::NOT_FOUND,
+        ConnectorError::Conflict(_) => StatusCode::CONFLICT,
+        ConnectorError::SourceError(_) => StatusCode::BAD_GATEWAY,
+        ConnectorError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+fn parse_id(id: &str, what: &str) -> Result<Uuid, (StatusCode, Json<Value>)> {
+    Uuid::parse_str(id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Invalid {what} ID format")})),
+        )
+    })
+}
+
+async fn create_template(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(request): Json<MappingTemplateInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let template = ConnectorEngine::new(Arc::clone(&app_state.shared_storage))
+        .create_template(&user_id, request)
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "template": template
+    })))
+}
+
+async fn list_templates(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let templates = ConnectorEngine::new(Arc::clone(&app_state.shared_storage))
+        .list_templates(&user_id)
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": templates.len(),
+        "templates": templates
+    })))
+}
+
+async fn get_template(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(template_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let template_id = parse_id(&template_id, "template")?;
+    let template = ConnectorEngine::new(Arc::clone(&app_state.shared_storage))
+        .get_template(&user_id, &template_id)
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "template": template
+    })))
+}
+
+async fn update_template(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(template_id): Path<String>,
+    Json(request): Json<MappingTemplateInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let template_id = parse_id(&template_id, "template")?;
+    let template = ConnectorEngine::new(Arc::clone(&app_state.shared_storage))
+        .update_template(&user_id, &template_id, request)
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "template": template
+    })))
+}
+
+async fn delete_template(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(template_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let template_id = parse_id(&template_id, "template")?;
+    ConnectorEngine::new(Arc::clone(&app_state.shared_storage))
+        .delete_template(&user_id, &template_id)
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "template_id": template_id
+    })))
+}
+
+/// Report every problem with a template draft without saving it
+async fn validate_template(
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(request): Json<MappingTemplateInput>,
+) -> Json<Value> {
+    let issues = template_issues(&draft_template(&user_id, request));
+    Json(json!({
+        "success": true,
+        "valid": issues.is_empty(),
+        "issues": issues
+    }))
+}
+
+/// Run a saved template against sample data and return the receipts it would create
+async fn preview_template(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(template_id): Path<String>,
+    Json(request): Json<PreviewSample>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let template_id = parse_id(&template_id, "template")?;
+    let previews = ConnectorEngine::new(Arc::clone(&app_state.shared_storage))
+        .preview_template(&user_id, &template_id, request)
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": previews.len(),
+        "previews": previews
+    })))
+}
+
+async fn preview_draft_template(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(request): Json<DraftPreviewRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let previews = ConnectorEngine::new(Arc::clone(&app_state.shared_storage))
+        .preview_draft_template(&user_id, request.template, request.sample)
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": previews.len(),
+        "previews": previews
+    })))
+}
+
+async fn create_connector(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(request): Json<ConnectorInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let connector = ConnectorEngine::new(Arc::clone(&app_state.shared_storage))
+        .create_connector(&user_id, request)
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "connector": connector
+    })))
+}
+
+async fn list_connectors(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let connectors = ConnectorEngine::new(Arc::clone(&app_state.shared_storage))
+        .list_connectors(&user_id)
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": connectors.len(),
+        "connectors": connectors
+    })))
+}
+
+async fn get_connector(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(connector_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let connector_id = parse_id(&connector_id, "connector")?;
+    let connector = ConnectorEngine::new(Arc::clone(&app_state.shared_storage))
+        .get_connector(&user_id, &connector_id)
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "connector": connector
+    })))
+}
+
+async fn update_connector(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(connector_id): Path<String>,
+    Json(request): Json<ConnectorInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let connector_id = parse_id(&connector_id, "connector")?;
+    let connector = ConnectorEngine::new(Arc::clone(&app_state.shared_storage))
+        .update_connector(&user_id, &connector_id, request)
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "connector": connector
+    })))
+}
+
+async fn delete_connector(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(connector_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let connector_id = parse_id(&connector_id, "connector")?;
+    ConnectorEngine::new(Arc::clone(&app_state.shared_storage))
+        .delete_connector(&user_id, &connector_id)
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "connector_id": connector_id
+    })))
+}
+
+/// Trigger a run now instead of waiting for the schedule
+async fn run_connector(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(connector_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let connector_id = parse_id(&connector_id, "connector")?;
+    let engine = ConnectorEngine::new(Arc::clone(&app_state.shared_storage));
+    engine
+        .get_connector(&user_id, &connector_id)
+        .map_err(connector_error_response)?;
+    let summary = engine
+        .run_connector(&connector_id)
+        .await
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "run": summary
+    })))
+}
+
+async fn get_dashboard(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(connector_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let connector_id = parse_id(&connector_id, "connector")?;
+    let dashboard = ConnectorEngine::new(Arc::clone(&app_state.shared_storage))
+        .dashboard(&user_id, &connector_id)
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "dashboard": dashboard
+    })))
+}
+
+async fn list_dashboards(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let dashboards = ConnectorEngine::new(Arc::clone(&app_state.shared_storage))
+        .dashboards(&user_id)
+        .map_err(connector_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": dashboards.len(),
+        "dashboards": dashboards
+    })))
+}
@@ -0,0 +1,176 @@
+//! Custom dashboard metrics of a workspace: definitions managed by the
+//! workspace owner, and a query endpoint returning their series for charts.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, put},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::dashboard_metrics_engine::{
+    DashboardMetricsEngine, MetricDefinitionInput, MetricsError,
+};
+
+/// Mounted at `/api/dashboard-metrics`
+pub fn dashboard_metric_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/workspaces/:workspace_id/metrics",
+            get(list_metrics).post(define_metric),
+        )
+        .route(
+            "/workspaces/:workspace_id/metrics/:metric_id",
+            put(update_metric).delete(delete_metric),
+        )
+        .route("/workspaces/:workspace_id/query", get(query_metrics))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> DashboardMetricsEngine<SharedStorage> {
+    DashboardMetricsEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn metrics_error_response(e: MetricsError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        MetricsError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        MetricsError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        MetricsError::NotFound(_) => StatusCode::NOT_FOUND,
+        MetricsError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn list_metrics(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let metrics = engine(&app_state)
+        .list(&user_id, &workspace_id)
+        .map_err(metrics_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": metrics.len(),
+        "metrics": metrics
+    })))
+}
+
+async fn define_metric(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+    Json(input): Json<MetricDefinitionInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let metric = engine(&app_state)
+        .define(&user_id, &workspace_id, input, Utc::now())
+        .map_err(metrics_error_response)?;
+
+    tracing::info!(
+        "📈 Metric '{}' defined for workspace {} by {}",
+        metric.name,
+        workspace_id,
+        user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "metric": metric
+    })))
+}
+
+async fn update_metric(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path((workspace_id, metric_id)): Path<(String, Uuid)>,
+    Json(input): Json<MetricDefinitionInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let metric = engine(&app_state)
+        .update(&user_id, &workspace_id, &metric_id, input, Utc::now())
+        .map_err(metrics_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "metric": metric
+    })))
+}
+
+async fn delete_metric(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path((workspace_id, metric_id)): Path<(String, Uuid)>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    engine(&app_state)
+        .delete(&user_id, &workspace_id, &metric_id)
+        .map_err(metrics_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "metric_id": metric_id
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MetricsQuery {
+    /// Comma separated metric ids; all of the workspace's metrics when absent
+    pub metrics: Option<String>,
+    /// Unix timestamps; `to` defaults to now
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+}
+
+fn timestamp(value: i64, name: &str) -> Result<DateTime<Utc>, (StatusCode, Json<Value>)> {
+    DateTime::from_timestamp(value, 0).ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Invalid {name} timestamp")})),
+        )
+    })
+}
+
+async fn query_metrics(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+    Query(query): Query<MetricsQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let metric_ids = query
+        .metrics
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            Uuid::parse_str(id).map_err(|_| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": format!("Invalid metric id: {id}")})),
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let from = query.from.map(|from| timestamp(from, "from")).transpose()?;
+    let to = match query.to {
+        Some(to) => timestamp(to, "to")?,
+        None => Utc::now(),
+    };
+
+    let series = engine(&app_state)
+        .query(&user_id, &workspace_id, &metric_ids, from, to)
+        .map_err(metrics_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": series.len(),
+        "series": series
+    })))
+}
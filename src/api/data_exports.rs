@@ -0,0 +1,182 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Redirect, Response},
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::io::AsyncReadExt;
+use uuid::Uuid;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::data_export_engine::{DataExportEngine, DataExportError, DataExportRequest};
+use crate::types::{DataExportJob, DataExportStatus};
+use crate::workspace_bucket_engine::WorkspaceBucketEngine;
+
+const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Mounted at `/api/exports`
+pub fn data_export_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(list_exports).post(create_export))
+        .route("/:job_id", get(get_export))
+        .route("/:job_id/download", get(download_export))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> DataExportEngine<SharedStorage> {
+    DataExportEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn export_error_response(e: DataExportError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        DataExportError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        DataExportError::NotFound(_) => StatusCode::NOT_FOUND,
+        DataExportError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        DataExportError::TooManyJobs(_) => StatusCode::TOO_MANY_REQUESTS,
+        DataExportError::StorageError(_) | DataExportError::IoError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+fn parse_id(id: &str) -> Result<Uuid, (StatusCode, Json<Value>)> {
+    Uuid::parse_str(id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Invalid export job ID format"})),
+        )
+    })
+}
+
+fn job_json(job: &DataExportJob) -> Value {
+    let mut value = json!(job);
+    if let Some(object) = value.as_object_mut() {
+        object.remove("output_path");
+    }
+    value["progress"] = json!(job.progress());
+    value["file_name"] = json!(job.file_name());
+    if job.status == DataExportStatus::Completed {
+        value["download_url"] = json!(format!("/api/exports/{}/download", job.job_id));
+    }
+    value
+}
+
+/// Queue an export; poll `GET /api/exports/:job_id` until it completes
+async fn create_export(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(request): Json<DataExportRequest>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    let engine = engine(&app_state);
+    let job = engine
+        .create_job(&user_id, request, Utc::now())
+        .map_err(export_error_response)?;
+    engine.spawn_job(job.job_id);
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "success": true,
+            "export": job_json(&job)
+        })),
+    ))
+}
+
+async fn list_exports(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let jobs = engine(&app_state)
+        .list_jobs(&user_id)
+        .map_err(export_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": jobs.len(),
+        "exports": jobs.iter().map(job_json).collect::<Vec<_>>()
+    })))
+}
+
+async fn get_export(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(job_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let job_id = parse_id(&job_id)?;
+    let job = engine(&app_state)
+        .get_job(&user_id, &job_id)
+        .map_err(export_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "export": job_json(&job)
+    })))
+}
+
+/// Stream a completed export file in chunks, or redirect to it in the
+/// workspace's bucket
+async fn download_export(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(job_id): Path<String>,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let job_id = parse_id(&job_id)?;
+    let job = engine(&app_state)
+        .get_job(&user_id, &job_id)
+        .map_err(export_error_response)?;
+    if let (DataExportStatus::Completed, Some(object)) = (job.status, job.bucket_object.as_ref()) {
+        let download = WorkspaceBucketEngine::new(Arc::clone(&app_state.shared_storage))
+            .presign_download(&job.workspace_id, object, Utc::now())
+            .map_err(|e| (StatusCode::GONE, Json(json!({"error": e.to_string()}))))?;
+        return Ok(Redirect::temporary(&download.url).into_response());
+    }
+    let (DataExportStatus::Completed, Some(output_path)) = (job.status, job.output_path.as_ref())
+    else {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Export is not ready for download",
+                "status": job.status,
+                "progress": job.progress()
+            })),
+        ));
+    };
+
+    let file = tokio::fs::File::open(output_path).await.map_err(|e| {
+        (
+            StatusCode::GONE,
+            Json(json!({"error": format!("Export file is no longer available: {}", e)})),
+        )
+    })?;
+    let stream = futures::stream::unfold(file, |mut file| async move {
+        let mut buffer = vec![0u8; DOWNLOAD_CHUNK_SIZE];
+        match file.read(&mut buffer).await {
+            Ok(0) => None,
+            Ok(n) => {
+                buffer.truncate(n);
+                Some((Ok::<_, std::io::Error>(Bytes::from(buffer)), file))
+            }
+            Err(e) => Some((Err(e), file)),
+        }
+    });
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, job.format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", job.file_name()),
+            ),
+            (header::CONTENT_LENGTH, job.size_bytes.to_string()),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response())
+}
@@ -0,0 +1,104 @@
+//! Garbage collection of orphaned data lake entries and receipts, under the
+//! admin-guarded `/api/admin/data-lake-gc`. A run without a body uses the
+//! scheduled run's policy.
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AdminUser;
+use crate::data_lake_gc_engine::{DataLakeGcEngine, DataLakeGcError, DataLakeGcPolicy};
+use crate::ipfs_client::IpfsClient;
+use crate::types::DataLakeGcAction;
+
+pub fn admin_data_lake_gc_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/run", post(run_gc))
+        .route("/reports", get(list_reports))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RunGcRequest {
+    pub retention_days: Option<u32>,
+    pub action: Option<DataLakeGcAction>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+fn engine(app_state: &AppState) -> DataLakeGcEngine<SharedStorage> {
+    DataLakeGcEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn gc_error_response(e: DataLakeGcError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        DataLakeGcError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        DataLakeGcError::ArchiveError(_) => StatusCode::BAD_GATEWAY,
+        DataLakeGcError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// Collect orphans now, or with `dry_run` only report what would be collected
+async fn run_gc(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    request: Option<Json<RunGcRequest>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let default = DataLakeGcPolicy::from_env();
+    let policy = DataLakeGcPolicy {
+        retention_days: request.retention_days.unwrap_or(default.retention_days),
+        action: request.action.unwrap_or(default.action),
+    };
+
+    let mut engine = engine(&app_state);
+    if policy.action == DataLakeGcAction::Archive && !request.dry_run {
+        let ipfs = IpfsClient::from_env()
+            .map_err(|e| gc_error_response(DataLakeGcError::ArchiveError(e.to_string())))?;
+        engine = engine.with_ipfs(ipfs);
+    }
+    let report = engine
+        .run(policy, request.dry_run, Some(admin_user_id.clone()))
+        .await
+        .map_err(gc_error_response)?;
+
+    if !report.dry_run {
+        tracing::info!(
+            "🧹 {} collected {} data lake entries and {} receipts ({} bytes)",
+            admin_user_id,
+            report.entries_removed,
+            report.receipts_removed,
+            report.reclaimed_bytes
+        );
+    }
+
+    Ok(Json(json!({
+        "success": true,
+        "report": report
+    })))
+}
+
+async fn list_reports(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let reports = engine(&app_state)
+        .list_reports()
+        .map_err(gc_error_response)?;
+    let reclaimed_bytes: u64 = reports.iter().map(|r| r.reclaimed_bytes).sum();
+
+    Ok(Json(json!({
+        "success": true,
+        "count": reports.len(),
+        "reclaimed_bytes": reclaimed_bytes,
+        "reports": reports
+    })))
+}
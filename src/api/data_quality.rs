@@ -0,0 +1,117 @@
+//! Data quality scores of circuits: the latest report, its trend history, and
+//! reports run on demand. Reports are also produced weekly, with a digest to
+//! the circuit owner.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::data_quality_engine::{DataQualityEngine, DataQualityError};
+
+/// Mounted at `/api/data-quality`
+pub fn data_quality_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/circuits/:circuit_id", get(get_latest_report))
+        .route("/circuits/:circuit_id/history", get(get_report_history))
+        .route("/circuits/:circuit_id/run", post(run_report))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> DataQualityEngine<SharedStorage> {
+    DataQualityEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn data_quality_error_response(e: DataQualityError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        DataQualityError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        DataQualityError::NotFound(_) => StatusCode::NOT_FOUND,
+        DataQualityError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn get_latest_report(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let report = engine(&app_state)
+        .latest(&circuit_id, &user_id)
+        .map_err(data_quality_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "report": report
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    pub limit: Option<usize>,
+}
+
+async fn get_report_history(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let reports = engine(&app_state)
+        .history(&circuit_id, &user_id, query.limit)
+        .map_err(data_quality_error_response)?;
+    // Oldest first, ready to chart
+    let trend: Vec<Value> = reports
+        .iter()
+        .rev()
+        .map(|report| {
+            json!({
+                "generated_at": report.generated_at,
+                "score": report.score,
+                "identifier_completeness": report.identifier_completeness,
+                "event_coverage": report.event_coverage,
+                "verification_failure_rate": report.verification_failure_rate,
+                "issue_count": report.issues.len(),
+            })
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "success": true,
+        "count": reports.len(),
+        "trend": trend,
+        "reports": reports
+    })))
+}
+
+async fn run_report(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let report = engine(&app_state)
+        .generate_report(&circuit_id, &user_id, Utc::now())
+        .map_err(data_quality_error_response)?;
+
+    tracing::info!(
+        "📊 Data quality of circuit {} scored {:?} for {} ({} issues)",
+        circuit_id,
+        report.score,
+        user_id,
+        report.issues.len()
+    );
+    Ok(Json(json!({
+        "success": true,
+        "report": report
+    })))
+}
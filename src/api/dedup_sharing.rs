@@ -0,0 +1,136 @@
+//! Agreements between two circuits to share a dedup namespace. One circuit
+//! proposes, the other accepts; either may revoke. Consent comes from each
+//! circuit's owner or a member who may manage its permissions.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::dedup_sharing_engine::{DedupSharingEngine, DedupSharingError};
+
+/// Mounted at `/api/dedup-sharing`
+pub fn dedup_sharing_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/circuits/:circuit_id",
+            get(list_agreements).post(propose_agreement),
+        )
+        .route("/:agreement_id/accept", post(accept_agreement))
+        .route("/:agreement_id/revoke", post(revoke_agreement))
+        .with_state(app_state)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProposeAgreementRequest {
+    pub partner_circuit_id: Uuid,
+}
+
+fn engine(app_state: &AppState) -> DedupSharingEngine<SharedStorage> {
+    DedupSharingEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn dedup_sharing_error_response(e: DedupSharingError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        DedupSharingError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        DedupSharingError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        DedupSharingError::NotFound(_) => StatusCode::NOT_FOUND,
+        DedupSharingError::Conflict(_) => StatusCode::CONFLICT,
+        DedupSharingError::StorageError(_) | DedupSharingError::AuditError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn list_agreements(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let agreements = engine(&app_state)
+        .list(&user_id, &circuit_id)
+        .map_err(dedup_sharing_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": agreements.len(),
+        "agreements": agreements
+    })))
+}
+
+async fn propose_agreement(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+    Json(request): Json<ProposeAgreementRequest>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    let agreement = engine(&app_state)
+        .propose(
+            &user_id,
+            &circuit_id,
+            &request.partner_circuit_id,
+            Utc::now(),
+        )
+        .map_err(dedup_sharing_error_response)?;
+
+    tracing::info!(
+        "🤝 {} proposed sharing dedup between circuits {} and {}",
+        user_id,
+        circuit_id,
+        request.partner_circuit_id
+    );
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "success": true,
+            "agreement": agreement
+        })),
+    ))
+}
+
+/// New pushes to either circuit resolve through the shared namespace
+async fn accept_agreement(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(agreement_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let agreement = engine(&app_state)
+        .accept(&user_id, &agreement_id, Utc::now())
+        .map_err(dedup_sharing_error_response)?;
+
+    tracing::info!(
+        "🤝 Dedup sharing agreement {} accepted by {}",
+        agreement_id,
+        user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "agreement": agreement
+    })))
+}
+
+async fn revoke_agreement(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(agreement_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let agreement = engine(&app_state)
+        .revoke(&user_id, &agreement_id, Utc::now())
+        .map_err(dedup_sharing_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "agreement": agreement
+    })))
+}
@@ -0,0 +1,74 @@
+//! Per-workspace choice of how verification matches incoming entries to items.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::dedup_strategy::{DedupConfigEngine, DedupConfigError, DedupConfigInput};
+
+/// Mounted at `/api/dedup-strategies`
+pub fn dedup_strategy_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:workspace_id", get(get_config).put(set_config))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> DedupConfigEngine<SharedStorage> {
+    DedupConfigEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn dedup_config_error_response(e: DedupConfigError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        DedupConfigError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        DedupConfigError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        DedupConfigError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn get_config(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let config = engine(&app_state)
+        .get_config(&user_id, &workspace_id)
+        .map_err(dedup_config_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "config": config
+    })))
+}
+
+/// Circuits whose alias config names a strategy keep using their own
+async fn set_config(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+    Json(input): Json<DedupConfigInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let config = engine(&app_state)
+        .set_config(&user_id, &workspace_id, input, Utc::now())
+        .map_err(dedup_config_error_response)?;
+
+    tracing::info!(
+        "🧬 Dedup strategy of workspace {} set to {} by {}",
+        workspace_id,
+        config.strategy.as_str(),
+        user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "config": config
+    })))
+}
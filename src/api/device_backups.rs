@@ -0,0 +1,249 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::{DefaultBodyLimit, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::io::AsyncReadExt;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::device_backup_engine::{configured_backup_dir, DeviceBackupEngine, DeviceBackupError};
+use crate::types::DeviceBackup;
+
+const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024;
+/// Cipher the client encrypted the backup with
+const ENCRYPTION_HEADER: &str = "x-backup-encryption";
+/// BLAKE3 hex of the uploaded blob, checked on upload and returned on restore
+const CONTENT_HASH_HEADER: &str = "x-content-hash";
+
+/// Mounted at `/api/device-backups`. Upload with `POST /:device_id` and the
+/// encrypted blob as the body; restore with `GET /:device_id/latest` or
+/// `GET /:device_id/:version`.
+pub fn device_backup_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(list_backups))
+        .route(
+            "/:device_id",
+            // The engine streams the body and enforces the size limit itself
+            get(list_device_backups)
+                .post(upload_backup)
+                .layer(DefaultBodyLimit::disable()),
+        )
+        .route(
+            "/:device_id/:version",
+            get(download_backup).delete(delete_backup),
+        )
+        .with_state(app_state)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BackupListQuery {
+    pub device_id: Option<String>,
+}
+
+fn engine(
+    app_state: &AppState,
+) -> Result<DeviceBackupEngine<SharedStorage>, (StatusCode, Json<Value>)> {
+    let backup_dir = configured_backup_dir().map_err(backup_error_response)?;
+    Ok(DeviceBackupEngine::new(
+        Arc::clone(&app_state.shared_storage),
+        backup_dir,
+    ))
+}
+
+fn backup_error_response(e: DeviceBackupError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        DeviceBackupError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        DeviceBackupError::NotFound(_) => StatusCode::NOT_FOUND,
+        DeviceBackupError::QuotaExceeded(_) => StatusCode::PAYLOAD_TOO_LARGE,
+        DeviceBackupError::NotConfigured(_) => StatusCode::SERVICE_UNAVAILABLE,
+        DeviceBackupError::StorageError(_) | DeviceBackupError::IoError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// `latest` or a version number
+fn parse_version(version: &str) -> Result<Option<u32>, (StatusCode, Json<Value>)> {
+    if version == "latest" {
+        return Ok(None);
+    }
+    version.parse().map(Some).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Version must be a number or 'latest'"})),
+        )
+    })
+}
+
+fn backup_json(backup: &DeviceBackup) -> Value {
+    let mut value = json!(backup);
+    if let Some(object) = value.as_object_mut() {
+        object.remove("blob_path");
+    }
+    value["download_url"] = json!(format!(
+        "/api/device-backups/{}/{}",
+        backup.device_id, backup.version
+    ));
+    value
+}
+
+async fn upload_backup(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(device_id): Path<String>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    let header_value = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let encryption = header_value(ENCRYPTION_HEADER).ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Missing {} header", ENCRYPTION_HEADER)})),
+        )
+    })?;
+    let backup = engine(&app_state)?
+        .upload(
+            &user_id,
+            &device_id,
+            encryption,
+            header_value(CONTENT_HASH_HEADER),
+            body.into_data_stream(),
+            Utc::now(),
+        )
+        .await
+        .map_err(backup_error_response)?;
+
+    tracing::info!(
+        "💾 Backup v{} of device {} ({} bytes) uploaded by {}",
+        backup.version,
+        backup.device_id,
+        backup.size_bytes,
+        user_id
+    );
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "success": true,
+            "backup": backup_json(&backup)
+        })),
+    ))
+}
+
+/// The caller's backups across devices, optionally of one device
+async fn list_backups(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Query(query): Query<BackupListQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let backups = engine(&app_state)?
+        .list(&user_id, query.device_id.as_deref())
+        .map_err(backup_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": backups.len(),
+        "backups": backups.iter().map(backup_json).collect::<Vec<_>>()
+    })))
+}
+
+async fn list_device_backups(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(device_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    list_backups(
+        State(app_state),
+        AuthenticatedUser(user_id),
+        Query(BackupListQuery {
+            device_id: Some(device_id),
+        }),
+    )
+    .await
+}
+
+/// Stream a backup blob back to the device restoring it
+async fn download_backup(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path((device_id, version)): Path<(String, String)>,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let version = parse_version(&version)?;
+    let backup = engine(&app_state)?
+        .get(&user_id, &device_id, version)
+        .map_err(backup_error_response)?;
+
+    let file = tokio::fs::File::open(&backup.blob_path)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::GONE,
+                Json(json!({"error": format!("Backup blob is no longer available: {}", e)})),
+            )
+        })?;
+    let stream = futures::stream::unfold(file, |mut file| async move {
+        let mut buffer = vec![0u8; DOWNLOAD_CHUNK_SIZE];
+        match file.read(&mut buffer).await {
+            Ok(0) => None,
+            Ok(n) => {
+                buffer.truncate(n);
+                Some((Ok::<_, std::io::Error>(Bytes::from(buffer)), file))
+            }
+            Err(e) => Some((Err(e), file)),
+        }
+    });
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}-v{}.bin\"",
+                    backup.device_id, backup.version
+                ),
+            ),
+            (header::CONTENT_LENGTH, backup.size_bytes.to_string()),
+            (header::ETAG, format!("\"{}\"", backup.content_hash)),
+            (
+                header::HeaderName::from_static(CONTENT_HASH_HEADER),
+                backup.content_hash.clone(),
+            ),
+            (
+                header::HeaderName::from_static(ENCRYPTION_HEADER),
+                backup.encryption.clone(),
+            ),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response())
+}
+
+async fn delete_backup(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path((device_id, version)): Path<(String, String)>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let Some(version) = parse_version(&version)? else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Delete a backup by its version number"})),
+        ));
+    };
+    let backup = engine(&app_state)?
+        .delete(&user_id, &device_id, version)
+        .map_err(backup_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "deleted": backup_json(&backup)
+    })))
+}
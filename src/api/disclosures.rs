@@ -0,0 +1,176 @@
+//! Selective disclosure of item attributes. Owners create and revoke
+//! disclosures under `/api/disclosures`; recipients open them with their token
+//! and verify packages under the public `/api/public/disclosures`.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    middleware,
+    response::Json,
+    routing::{delete, get, post},
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::public_items::public_rate_limit_middleware;
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::disclosure_engine::{
+    CreateDisclosureInput, DisclosureEngine, DisclosureError, DisclosurePackage,
+};
+use crate::notarization_engine::NotarizationError;
+use crate::types::SelectiveDisclosure;
+
+/// Mounted at `/api/disclosures`
+pub fn disclosure_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(list_disclosures).post(create_disclosure))
+        .route("/:disclosure_id", delete(revoke_disclosure))
+        .with_state(app_state)
+}
+
+/// Mounted at `/api/public/disclosures`, rate limited per IP like public items
+pub fn public_disclosure_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/verify", post(verify_package))
+        .route("/:token", get(open_disclosure))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            public_rate_limit_middleware,
+        ))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> DisclosureEngine<SharedStorage> {
+    DisclosureEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn disclosure_error_response(e: DisclosureError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        DisclosureError::ValidationError(_)
+        | DisclosureError::NotarizationError(NotarizationError::ValidationError(_)) => {
+            StatusCode::BAD_REQUEST
+        }
+        DisclosureError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        DisclosureError::NotFound(_)
+        | DisclosureError::InvalidToken
+        | DisclosureError::NotarizationError(NotarizationError::NotFound(_)) => {
+            StatusCode::NOT_FOUND
+        }
+        DisclosureError::StorageError(_) | DisclosureError::NotarizationError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// The owner's view: everything but the token hash
+fn disclosure_json(disclosure: &SelectiveDisclosure) -> Value {
+    let mut value = json!(disclosure);
+    if let Some(object) = value.as_object_mut() {
+        object.remove("token_hash");
+        object.insert(
+            "fields".to_string(),
+            json!(disclosure
+                .attributes
+                .iter()
+                .map(|attribute| &attribute.field)
+                .collect::<Vec<_>>()),
+        );
+    }
+    value
+}
+
+async fn create_disclosure(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(input): Json<CreateDisclosureInput>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    let mut events = app_state.events_engine.write().await;
+    let (token, disclosure) = engine(&app_state)
+        .create(&mut events, &user_id, input, Utc::now())
+        .map_err(disclosure_error_response)?;
+    drop(events);
+
+    tracing::info!(
+        "🔏 {} disclosed {} attribute(s) of {}",
+        user_id,
+        disclosure.attributes.len(),
+        disclosure.dfid
+    );
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "success": true,
+            "token": token,
+            "share_url": format!("/api/public/disclosures/{token}"),
+            "disclosure": disclosure_json(&disclosure)
+        })),
+    ))
+}
+
+async fn list_disclosures(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let disclosures = engine(&app_state)
+        .list(&user_id)
+        .map_err(disclosure_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": disclosures.len(),
+        "disclosures": disclosures.iter().map(disclosure_json).collect::<Vec<_>>()
+    })))
+}
+
+async fn revoke_disclosure(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(disclosure_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let disclosure = engine(&app_state)
+        .revoke(&user_id, &disclosure_id, Utc::now())
+        .map_err(disclosure_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "disclosure": disclosure_json(&disclosure)
+    })))
+}
+
+/// The disclosed attributes behind a token, verified
+async fn open_disclosure(
+    State(app_state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = engine(&app_state);
+    let package = engine
+        .open(&token, Utc::now())
+        .map_err(disclosure_error_response)?;
+    let verification = engine.verify(&package).map_err(disclosure_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "disclosure": package,
+        "verification": verification
+    })))
+}
+
+/// Verify a package received earlier, e.g. after the token expired
+async fn verify_package(
+    State(app_state): State<Arc<AppState>>,
+    Json(package): Json<DisclosurePackage>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let verification = engine(&app_state)
+        .verify(&package)
+        .map_err(disclosure_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "verification": verification
+    })))
+}
@@ -0,0 +1,264 @@
+//! Response types handed to API clients and SDKs.
+//!
+//! Handlers serialize these instead of the storage models (`Item`, `Event`, ...),
+//! so a model can gain, rename or restructure fields without changing what clients
+//! receive. Each API version has its own module of DTOs plus `From` conversions
+//! from the models; a change to the wire format goes into a new version module and
+//! the handler picks the module through the [`ApiVersion`](super::versioning::ApiVersion)
+//! extractor. v2 has not diverged from v1 yet, so both are answered with [`v1`].
+
+pub use v1::{AttachmentDto, EventDto, IdentifierDto, ItemDto};
+
+pub mod v1 {
+    use chrono::{DateTime, Utc};
+    use serde::Serialize;
+    use std::collections::HashMap;
+
+    use crate::identifier_types::IdentifierType;
+    use crate::types::{BucketObjectRef, EventAttachment};
+    use crate::{Event, Identifier, Item};
+
+    /// An identifier, in the same shape `POST /api/items` accepts
+    #[derive(Debug, Clone, Serialize)]
+    pub struct IdentifierDto {
+        pub namespace: String,
+        pub key: String,
+        pub value: String,
+        /// "Canonical" or "Contextual"
+        pub id_type: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub scope: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub verified: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub verification_date: Option<DateTime<Utc>>,
+    }
+
+    impl From<&Identifier> for IdentifierDto {
+        fn from(identifier: &Identifier) -> Self {
+            let (id_type, scope, verified, verification_date) = match &identifier.id_type {
+                IdentifierType::Canonical {
+                    verified,
+                    verification_date,
+                    ..
+                } => ("Canonical", None, Some(*verified), *verification_date),
+                IdentifierType::Contextual { scope } => {
+                    ("Contextual", Some(scope.clone()), None, None)
+                }
+            };
+            Self {
+                namespace: identifier.namespace.clone(),
+                key: identifier.key.clone(),
+                value: identifier.value.clone(),
+                id_type: id_type.to_string(),
+                scope,
+                verified,
+                verification_date,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize)]
+    pub struct ItemDto {
+        pub dfid: String,
+        pub identifiers: Vec<IdentifierDto>,
+        pub enriched_data: HashMap<String, serde_json::Value>,
+        /// Unix seconds
+        pub creation_timestamp: i64,
+        pub last_modified: i64,
+        pub source_entries: Vec<String>,
+        pub confidence_score: f64,
+        pub status: String,
+        /// Earliest/latest declared occurrence of the item's data (backfilled history)
+        pub first_occurred_at: Option<i64>,
+        pub last_occurred_at: Option<i64>,
+    }
+
+    impl From<Item> for ItemDto {
+        fn from(item: Item) -> Self {
+            let Item {
+                dfid,
+                identifiers,
+                enriched_data,
+                creation_timestamp,
+                last_modified,
+                source_entries,
+                confidence_score,
+                status,
+                first_occurred_at,
+                last_occurred_at,
+                ..
+            } = item;
+
+            Self {
+                dfid,
+                identifiers: identifiers.iter().map(IdentifierDto::from).collect(),
+                enriched_data,
+                creation_timestamp: creation_timestamp.timestamp(),
+                last_modified: last_modified.timestamp(),
+                source_entries: source_entries.iter().map(|id| id.to_string()).collect(),
+                confidence_score,
+                status: format!("{status:?}"),
+                first_occurred_at: first_occurred_at.map(|t| t.timestamp()),
+                last_occurred_at: last_occurred_at.map(|t| t.timestamp()),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize)]
+    pub struct AttachmentDto {
+        pub attachment_id: String,
+        pub cid: String,
+        /// Content hash; `sha256:<hex>` for bucket objects
+        pub content_hash: String,
+        pub mime_type: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub file_name: Option<String>,
+        pub size: u64,
+        pub uploaded_by: String,
+        pub uploaded_at: DateTime<Utc>,
+        /// Set when the file is in the workspace's own bucket instead of IPFS
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub bucket_object: Option<BucketObjectRef>,
+    }
+
+    impl From<EventAttachment> for AttachmentDto {
+        fn from(attachment: EventAttachment) -> Self {
+            Self {
+                attachment_id: attachment.attachment_id.to_string(),
+                cid: attachment.cid,
+                content_hash: attachment.content_hash,
+                mime_type: attachment.mime_type,
+                file_name: attachment.file_name,
+                size: attachment.size,
+                uploaded_by: attachment.uploaded_by,
+                uploaded_at: attachment.uploaded_at,
+                bucket_object: attachment.bucket_object,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize)]
+    pub struct EventDto {
+        pub event_id: String,
+        pub dfid: String,
+        pub event_type: String,
+        /// Unix seconds when the event was recorded
+        pub timestamp: i64,
+        pub source: String,
+        pub metadata: HashMap<String, serde_json::Value>,
+        pub is_encrypted: bool,
+        pub visibility: String,
+        /// Declared occurrence time; `None` means it happened when it was recorded (`timestamp`)
+        pub occurred_at: Option<i64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub signature: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub signer_public_key: Option<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub attachments: Vec<AttachmentDto>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub caused_by: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub correlation_id: Option<String>,
+    }
+
+    impl From<Event> for EventDto {
+        fn from(event: Event) -> Self {
+            Self {
+                event_id: event.event_id.to_string(),
+                dfid: event.dfid,
+                event_type: event.event_type.to_string(),
+                timestamp: event.timestamp.timestamp(),
+                source: event.source,
+                metadata: event.metadata,
+                is_encrypted: event.is_encrypted,
+                visibility: format!("{:?}", event.visibility),
+                occurred_at: event.occurred_at.map(|t| t.timestamp()),
+                signature: event.signature,
+                signer_public_key: event.signer_public_key,
+                attachments: event
+                    .attachments
+                    .into_iter()
+                    .map(AttachmentDto::from)
+                    .collect(),
+                caused_by: event.caused_by.map(|id| id.to_string()),
+                correlation_id: event.correlation_id.map(|id| id.to_string()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, EventType, EventVisibility, Identifier, Item};
+    use uuid::Uuid;
+
+    fn keys(value: &serde_json::Value) -> Vec<&str> {
+        let mut keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn test_v1_wire_format_is_pinned() {
+        let item = Item::new(
+            "DFID-20260101-000001-ABCD".to_string(),
+            vec![
+                Identifier::canonical("bovino", "sisbov", "BR123"),
+                Identifier::contextual_with_scope("generic", "lot", "L-7", "farm"),
+            ],
+            Uuid::new_v4(),
+        );
+        let json = serde_json::to_value(ItemDto::from(item)).unwrap();
+        assert_eq!(
+            keys(&json),
+            [
+                "confidence_score",
+                "creation_timestamp",
+                "dfid",
+                "enriched_data",
+                "first_occurred_at",
+                "identifiers",
+                "last_modified",
+                "last_occurred_at",
+                "source_entries",
+                "status",
+            ]
+        );
+        assert_eq!(json["status"], "Active");
+        assert_eq!(json["identifiers"][0]["id_type"], "Canonical");
+        assert_eq!(json["identifiers"][0]["verified"], false);
+        assert_eq!(json["identifiers"][1]["scope"], "farm");
+        assert!(json["identifiers"][1].get("verified").is_none());
+
+        let event = Event::new(
+            "DFID-20260101-000001-ABCD".to_string(),
+            EventType::Created,
+            "user-1".to_string(),
+            EventVisibility::Public,
+        );
+        let json = serde_json::to_value(EventDto::from(event)).unwrap();
+        assert_eq!(
+            keys(&json),
+            [
+                "dfid",
+                "event_id",
+                "event_type",
+                "is_encrypted",
+                "metadata",
+                "occurred_at",
+                "source",
+                "timestamp",
+                "visibility",
+            ]
+        );
+        assert!(json["timestamp"].is_i64());
+    }
+}
@@ -0,0 +1,80 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::api::shared_state::AppState;
+use crate::auth_middleware::AuthenticatedUser;
+use crate::engagement_engine::{EngagementEngine, EngagementError};
+
+/// Mounted at `/api/engagement`; visible to the workspace owner and admins
+pub fn engagement_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(get_my_workspace_engagement))
+        .route("/:workspace_id", get(get_workspace_engagement))
+        .with_state(app_state)
+}
+
+fn engagement_error_response(e: EngagementError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        EngagementError::NotFound(_) => StatusCode::NOT_FOUND,
+        EngagementError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+fn workspace_engagement(
+    app_state: &AppState,
+    user_id: &str,
+    workspace_id: &str,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = EngagementEngine::new(Arc::clone(&app_state.shared_storage));
+    if !engine
+        .can_view(user_id, workspace_id)
+        .map_err(engagement_error_response)?
+    {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": "Only the workspace owner can view engagement metrics"})),
+        ));
+    }
+    let engagement = engine
+        .latest(workspace_id, Utc::now())
+        .map_err(engagement_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "engagement": engagement
+    })))
+}
+
+/// Engagement of the caller's own workspace
+async fn get_my_workspace_engagement(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let workspace_id = EngagementEngine::new(Arc::clone(&app_state.shared_storage))
+        .user_workspace(&user_id)
+        .map_err(engagement_error_response)?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "User has no workspace"})),
+            )
+        })?;
+    workspace_engagement(&app_state, &user_id, &workspace_id)
+}
+
+async fn get_workspace_engagement(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    workspace_engagement(&app_state, &user_id, &workspace_id)
+}
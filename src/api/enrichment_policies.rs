@@ -0,0 +1,75 @@
+//! Per-workspace merge policies applied when data for an existing item arrives.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::enrichment_policy_engine::{
+    EnrichmentPolicyEngine, EnrichmentPolicyError, EnrichmentPolicyInput,
+};
+
+/// Mounted at `/api/enrichment-policies`
+pub fn enrichment_policy_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:workspace_id", get(get_policy).put(set_policy))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> EnrichmentPolicyEngine<SharedStorage> {
+    EnrichmentPolicyEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn enrichment_policy_error_response(e: EnrichmentPolicyError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        EnrichmentPolicyError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        EnrichmentPolicyError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        EnrichmentPolicyError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn get_policy(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let policy = engine(&app_state)
+        .get_policy(&user_id, &workspace_id)
+        .map_err(enrichment_policy_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "policy": policy
+    })))
+}
+
+/// Replace the workspace's policy; fields without an override use `default_policy`
+async fn set_policy(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+    Json(input): Json<EnrichmentPolicyInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let policy = engine(&app_state)
+        .set_policy(&user_id, &workspace_id, input, Utc::now())
+        .map_err(enrichment_policy_error_response)?;
+
+    tracing::info!(
+        "🧩 Enrichment policy of workspace {} updated by {}",
+        workspace_id,
+        user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "policy": policy
+    })))
+}
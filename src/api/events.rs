@@ -1,19 +1,32 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    body::Bytes,
+    extract::{DefaultBodyLimit, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{Json, Redirect},
     routing::{get, post},
     Extension, Router,
 };
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use super::dto::EventDto;
 use super::shared_state::AppState;
+use crate::event_signing_engine::{EventSignatureInput, EventSigningEngine};
+use crate::events_engine::{upload_attachment, BulkEventInput, EventsError, MAX_ATTACHMENT_BYTES};
+use crate::ipfs_client::IpfsClient;
+use crate::notarization_engine::{NotarizationEngine, NotarizeInput};
+use crate::pagination::{Page, PageParams};
+use crate::payload_limits::{self, PayloadLimitError};
 use crate::snapshot_types::{SnapshotEntityType, SnapshotOperation, StateSnapshot};
 use crate::storage::StorageBackend;
+use crate::types::TimeAxis;
+use crate::workspace_bucket_engine::{
+    BucketAttachmentInput, WorkspaceBucketEngine, WorkspaceBucketError,
+};
 use crate::{Event, EventType, EventVisibility};
 
 #[derive(Debug, Deserialize)]
@@ -23,18 +36,35 @@ pub struct CreateEventRequest {
     // Note: 'source' field removed - now auto-populated from authentication context
     pub visibility: String,
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Unix seconds when the event actually happened, for backfilled history
+    pub occurred_at: Option<i64>,
+    /// Hex ed25519 signature over the event's signing hash (see `/signing-hash`);
+    /// given together with `signer_public_key`
+    pub signature: Option<String>,
+    pub signer_public_key: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct EventResponse {
-    pub event_id: String,
+/// Events uploaded in one call, e.g. a sensor gateway's daily readings
+#[derive(Debug, Deserialize)]
+pub struct BatchEventRequest {
+    pub events: Vec<CreateEventRequest>,
+}
+
+/// Draft of an event to be signed, as it will be sent to `POST /api/events`
+#[derive(Debug, Deserialize)]
+pub struct SigningHashRequest {
     pub dfid: String,
     pub event_type: String,
-    pub timestamp: i64,
-    pub source: String,
-    pub metadata: HashMap<String, serde_json::Value>,
-    pub is_encrypted: bool,
-    pub visibility: String,
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub occurred_at: Option<i64>,
+}
+
+/// Query of an attachment upload; the body is the file itself
+#[derive(Debug, Deserialize)]
+pub struct AttachmentQuery {
+    pub file_name: Option<String>,
+    /// Overrides the request's Content-Type header
+    pub content_type: Option<String>,
 }
 
 /// Response for event creation with deduplication info
@@ -48,6 +78,11 @@ pub struct CreateEventResponse {
     pub metadata: HashMap<String, serde_json::Value>,
     pub is_encrypted: bool,
     pub visibility: String,
+    pub occurred_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer_public_key: Option<String>,
     /// True if this event was deduplicated (already existed)
     pub was_deduplicated: bool,
     /// If deduplicated, the ID of the original event
@@ -62,6 +97,9 @@ pub struct EventQueryParams {
     pub end_date: Option<i64>,
     pub event_type: Option<String>,
     pub visibility: Option<String>,
+    /// Clock that `start_date`/`end_date` apply to (default: recorded)
+    #[serde(default)]
+    pub axis: TimeAxis,
 }
 
 /// Request for creating a local event (no DFID yet)
@@ -88,20 +126,44 @@ pub struct LocalEventResponse {
 pub fn event_routes(app_state: Arc<AppState>) -> Router {
     Router::new()
         .route("/", post(create_event))
+        .route("/batch", post(create_event_batch))
+        .route("/signing-hash", post(get_signing_hash))
+        .route("/limits", get(get_payload_limits))
         .route("/local", post(create_local_event))
         .route("/local/:local_event_id", get(get_local_event))
         .route("/item/:dfid", get(get_events_for_item))
         .route("/type/:event_type", get(get_events_by_type))
         .route("/visibility/:visibility", get(get_events_by_visibility))
         .route("/timeline", get(get_events_timeline))
+        .route("/analytics/daily", get(get_daily_event_counts))
         .route("/public", get(get_public_events))
         .route("/private", get(get_private_events))
         .route("/:event_id", get(get_event))
         .route("/:event_id/metadata", post(add_event_metadata))
+        .route("/:event_id/signature", get(verify_event_signature))
+        .route("/:event_id/chain", get(get_event_chain))
+        .route(
+            "/:event_id/attachments",
+            get(list_event_attachments)
+                .post(attach_file)
+                .layer(DefaultBodyLimit::max(MAX_ATTACHMENT_BYTES)),
+        )
+        .route(
+            "/:event_id/attachments/bucket-uploads",
+            post(presign_bucket_attachment),
+        )
+        .route(
+            "/:event_id/attachments/bucket-uploads/:attachment_id",
+            post(confirm_bucket_attachment),
+        )
+        .route(
+            "/:event_id/attachments/:attachment_id/download",
+            get(download_bucket_attachment),
+        )
         .with_state(app_state)
 }
 
-fn parse_event_type(event_type_str: &str) -> Result<EventType, String> {
+pub(crate) fn parse_event_type(event_type_str: &str) -> Result<EventType, String> {
     match event_type_str.to_lowercase().as_str() {
         "created" => Ok(EventType::Created),
         "enriched" => Ok(EventType::Enriched),
@@ -111,10 +173,24 @@ fn parse_event_type(event_type_str: &str) -> Result<EventType, String> {
         "pulledfromcircuit" => Ok(EventType::PulledFromCircuit),
         "updated" => Ok(EventType::Updated),
         "statuschanged" => Ok(EventType::StatusChanged),
+        "documentanchored" => Ok(EventType::DocumentAnchored),
+        "forwardedtocircuit" => Ok(EventType::ForwardedToCircuit),
+        "removedfromcircuit" => Ok(EventType::RemovedFromCircuit),
+        "anonymized" => Ok(EventType::Anonymized),
+        // Circuits register custom types; whether this one is registered is
+        // checked where the event is recorded
+        _ if is_custom_type_name(event_type_str) => {
+            Ok(EventType::Custom(event_type_str.to_string()))
+        }
         _ => Err(format!("Invalid event type: {event_type_str}")),
     }
 }
 
+fn is_custom_type_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 fn parse_event_visibility(visibility_str: &str) -> Result<EventVisibility, String> {
     match visibility_str.to_lowercase().as_str() {
         "public" => Ok(EventVisibility::Public),
@@ -124,16 +200,54 @@ fn parse_event_visibility(visibility_str: &str) -> Result<EventVisibility, Strin
     }
 }
 
-fn event_to_response(event: Event) -> EventResponse {
-    EventResponse {
-        event_id: event.event_id.to_string(),
-        dfid: event.dfid,
-        event_type: format!("{:?}", event.event_type),
-        timestamp: event.timestamp.timestamp(),
-        source: event.source,
-        metadata: event.metadata,
-        is_encrypted: event.is_encrypted,
-        visibility: format!("{:?}", event.visibility),
+/// Source of an event created by the caller: the JWT or API key user
+fn request_source(
+    claims: Option<Extension<crate::api::auth::Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+) -> Result<String, (StatusCode, Json<Value>)> {
+    if let Some(Extension(claims)) = claims {
+        Ok(claims.user_id.clone())
+    } else if let Some(Extension(ctx)) = api_key_ctx {
+        Ok(ctx.user_id.to_string())
+    } else {
+        Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({"error": "Authentication required. Use JWT token or API key."})),
+        ))
+    }
+}
+
+fn parse_occurred_at(
+    occurred_at: Option<i64>,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>, (StatusCode, Json<Value>)> {
+    match occurred_at {
+        Some(ts) => Ok(Some(chrono::DateTime::from_timestamp(ts, 0).ok_or_else(
+            || {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": "Invalid occurred_at timestamp"})),
+                )
+            },
+        )?)),
+        None => Ok(None),
+    }
+}
+
+/// Fetch one page of events for a list route, mapping errors to responses
+fn events_page(
+    params: &PageParams,
+    fetch: impl FnOnce(Option<crate::pagination::PageCursor>, usize) -> Result<Page<Event>, EventsError>,
+) -> Result<Json<Page<EventDto>>, (StatusCode, Json<Value>)> {
+    let after = params
+        .after()
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
+
+    match fetch(after, params.page_size()) {
+        Ok(page) => Ok(Json(page.map(EventDto::from))),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("Failed to get events: {}", e)})),
+        )),
     }
 }
 
@@ -170,7 +284,7 @@ fn create_item_snapshot_for_event(
         })),
         "trigger_event": {
             "event_id": event.event_id.to_string(),
-            "event_type": format!("{:?}", event.event_type),
+            "event_type": event.event_type.to_string(),
             "timestamp": event.timestamp,
             "source": event.source,
             "metadata": event.metadata,
@@ -178,7 +292,7 @@ fn create_item_snapshot_for_event(
         "events": all_events.iter().map(|e| {
             serde_json::json!({
                 "event_id": e.event_id.to_string(),
-                "event_type": format!("{:?}", e.event_type),
+                "event_type": e.event_type.to_string(),
                 "timestamp": e.timestamp,
                 "source": e.source,
                 "metadata": e.metadata,
@@ -216,6 +330,25 @@ fn create_item_snapshot_for_event(
         EventType::PulledFromCircuit => SnapshotOperation::ItemEnriched {
             fields: vec!["pulled_from_circuit".to_string()],
         },
+        EventType::ForwardedToCircuit => SnapshotOperation::ItemEnriched {
+            fields: vec!["forwarded_to_circuit".to_string()],
+        },
+        EventType::RemovedFromCircuit => SnapshotOperation::ItemEnriched {
+            fields: vec!["removed_from_circuit".to_string()],
+        },
+        EventType::Anonymized => SnapshotOperation::ItemEnriched {
+            fields: vec!["anonymized".to_string()],
+        },
+        EventType::DocumentAnchored => SnapshotOperation::ItemEventAdded {
+            event_id: event.event_id.to_string(),
+            event_type: event.event_type.to_string(),
+            event_category: Some("notarization".to_string()),
+        },
+        EventType::Custom(ref name) => SnapshotOperation::ItemEventAdded {
+            event_id: event.event_id.to_string(),
+            event_type: name.clone(),
+            event_category: Some("custom".to_string()),
+        },
     };
 
     // Create the snapshot
@@ -237,7 +370,7 @@ fn create_item_snapshot_for_event(
         )
         .with_metadata(
             "trigger_event_type".to_string(),
-            serde_json::json!(format!("{:?}", event.event_type)),
+            serde_json::json!(event.event_type.to_string()),
         )
         .with_metadata("triggered_by".to_string(), serde_json::json!(user_id))
         .with_computed_hash();
@@ -263,15 +396,20 @@ async fn create_event(
         .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
 
     // Auto-populate source from authenticated context (JWT or API key)
-    let source = if let Some(Extension(claims)) = claims {
-        claims.user_id.clone()
-    } else if let Some(Extension(ctx)) = api_key_ctx {
-        ctx.user_id.to_string()
-    } else {
-        return Err((
-            StatusCode::UNAUTHORIZED,
-            Json(json!({"error": "Authentication required. Use JWT token or API key."})),
-        ));
+    let source = request_source(claims, api_key_ctx)?;
+    let occurred_at = parse_occurred_at(payload.occurred_at)?;
+    let signature = match (payload.signature, payload.signer_public_key) {
+        (Some(signature), Some(signer_public_key)) => Some(EventSignatureInput {
+            signer_public_key,
+            signature,
+        }),
+        (None, None) => None,
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "signature and signer_public_key must be given together"})),
+            ))
+        }
     };
 
     let user_id = source.clone(); // Keep user_id for snapshot creation
@@ -282,8 +420,26 @@ async fn create_event(
     // Use create_event_with_metadata for automatic deduplication
     let metadata = payload.metadata.unwrap_or_default();
 
-    match engine.create_event_with_metadata(payload.dfid, event_type, source, visibility, metadata)
-    {
+    let created = match signature {
+        Some(signature) => engine.create_signed_event(
+            payload.dfid,
+            event_type,
+            source,
+            visibility,
+            metadata,
+            occurred_at,
+            signature,
+        ),
+        None => engine.create_event_with_occurred_at(
+            payload.dfid,
+            event_type,
+            source,
+            visibility,
+            metadata,
+            occurred_at,
+        ),
+    };
+    match created {
         Ok(result) => {
             let event = result.event.clone();
 
@@ -325,7 +481,7 @@ async fn create_event(
                             snapshot.snapshot_id,
                             dfid_for_snapshot,
                             event.event_id,
-                            format!("{:?}", event.event_type)
+                            event.event_type.to_string()
                         );
                     }
                     Err(e) => {
@@ -347,17 +503,38 @@ async fn create_event(
             Ok(Json(CreateEventResponse {
                 event_id: event.event_id.to_string(),
                 dfid: event.dfid.clone(),
-                event_type: format!("{:?}", event.event_type),
+                event_type: event.event_type.to_string(),
                 timestamp: event.timestamp.timestamp(),
                 source: event.source.clone(),
                 metadata: event.metadata.clone(),
                 is_encrypted: event.is_encrypted,
                 visibility: format!("{:?}", event.visibility),
+                occurred_at: event.occurred_at.map(|t| t.timestamp()),
+                signature: event.signature.clone(),
+                signer_public_key: event.signer_public_key.clone(),
                 was_deduplicated: result.was_deduplicated,
                 original_event_id: result.original_event_id.map(|id| id.to_string()),
                 content_hash: event.content_hash.clone(),
             }))
         }
+        Err(crate::events_engine::EventsError::SchemaViolation(violations)) => Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({
+                "error": "Event metadata does not match the circuit's event schema",
+                "violations": violations
+            })),
+        )),
+        Err(crate::events_engine::EventsError::PayloadTooLarge(e)) => {
+            Err(payload_too_large_response(&e))
+        }
+        Err(e @ crate::events_engine::EventsError::ValidationError(_)) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Failed to create event: {}", e)})),
+        )),
+        Err(e @ crate::events_engine::EventsError::PermissionDenied(_)) => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": format!("Failed to create event: {}", e)})),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({"error": format!("Failed to create event: {}", e)})),
@@ -365,73 +542,542 @@ async fn create_event(
     }
 }
 
-async fn get_events_for_item(
+/// Validate the batch as a whole, store its events and notarize the Merkle root
+/// over them, which the notarization anchorer writes on chain with its next batch
+async fn create_event_batch(
     State(state): State<Arc<AppState>>,
-    Path(dfid): Path<String>,
-) -> Result<Json<Vec<EventResponse>>, (StatusCode, Json<Value>)> {
-    let engine = state.events_engine.write().await;
+    claims: Option<Extension<crate::api::auth::Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+    Json(request): Json<BatchEventRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let source = request_source(claims, api_key_ctx)?;
+    let bad_request = |index: usize, e: &str| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Event {index}: {e}")})),
+        )
+    };
 
-    match engine.get_events_for_item(&dfid) {
-        Ok(events) => {
-            let response: Vec<EventResponse> = events.into_iter().map(event_to_response).collect();
-            Ok(Json(response))
+    let mut inputs = Vec::with_capacity(request.events.len());
+    for (index, entry) in request.events.into_iter().enumerate() {
+        let event_type = parse_event_type(&entry.event_type).map_err(|e| bad_request(index, &e))?;
+        let visibility =
+            parse_event_visibility(&entry.visibility).map_err(|e| bad_request(index, &e))?;
+        let occurred_at = parse_occurred_at(entry.occurred_at).map_err(|(status, Json(body))| {
+            (
+                status,
+                Json(json!({"error": format!("Event {index}: {}", body["error"].as_str().unwrap_or_default())})),
+            )
+        })?;
+        let signature = match (entry.signature, entry.signer_public_key) {
+            (Some(signature), Some(signer_public_key)) => Some(EventSignatureInput {
+                signer_public_key,
+                signature,
+            }),
+            (None, None) => None,
+            _ => {
+                return Err(bad_request(
+                    index,
+                    "signature and signer_public_key must be given together",
+                ))
+            }
+        };
+        inputs.push(BulkEventInput {
+            dfid: entry.dfid,
+            event_type,
+            visibility,
+            metadata: entry.metadata.unwrap_or_default(),
+            occurred_at,
+            signature,
+        });
+    }
+
+    let mut engine = state.events_engine.write().await;
+    let bulk = engine
+        .create_events_bulk(source.clone(), inputs)
+        .map_err(|e| match e {
+            EventsError::SchemaViolation(violations) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(json!({
+                    "error": "Event metadata does not match the circuit's event schema",
+                    "violations": violations
+                })),
+            ),
+            EventsError::PayloadTooLarge(e) => payload_too_large_response(&e),
+            e @ EventsError::ValidationError(_) => (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": format!("Failed to create events: {}", e)})),
+            ),
+            e @ EventsError::PermissionDenied(_) => (
+                StatusCode::FORBIDDEN,
+                Json(json!({"error": format!("Failed to create events: {}", e)})),
+            ),
+            e => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Failed to create events: {}", e)})),
+            ),
+        })?;
+
+    let notarization = match &bulk.merkle_root {
+        Some(root) => NotarizationEngine::new(Arc::clone(&state.shared_storage))
+            .notarize(
+                &mut engine,
+                root.as_bytes(),
+                NotarizeInput {
+                    file_name: Some(format!("event-batch-{}.root", Utc::now().timestamp())),
+                    content_type: Some("text/plain".to_string()),
+                    dfid: None,
+                },
+                &source,
+                Utc::now(),
+            )
+            .map_err(|e| tracing::warn!("Failed to notarize event batch root {}: {}", root, e))
+            .ok(),
+        None => None,
+    };
+    drop(engine);
+
+    let created: Vec<Event> = bulk
+        .results
+        .iter()
+        .filter(|result| !result.was_deduplicated)
+        .map(|result| result.event.clone())
+        .collect();
+    let to_persist = created.clone();
+    let state_clone = Arc::clone(&state);
+    tokio::spawn(async move {
+        let pg_lock = state_clone.postgres_persistence.read().await;
+        if let Some(pg) = &*pg_lock {
+            for event in &to_persist {
+                if let Err(e) = pg.persist_event(event).await {
+                    tracing::warn!(
+                        "Failed to persist event {} to PostgreSQL: {}",
+                        event.event_id,
+                        e
+                    );
+                }
+            }
         }
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({"error": format!("Failed to get events: {}", e)})),
-        )),
+    });
+
+    // One snapshot per item, after the last of its new events
+    let mut latest_per_item: HashMap<&str, &Event> = HashMap::new();
+    for event in &created {
+        latest_per_item.insert(event.dfid.as_str(), event);
     }
+    {
+        let storage = state.shared_storage.lock().unwrap();
+        for (dfid, event) in latest_per_item {
+            let all_events = storage.get_events_by_dfid(dfid).unwrap_or_default();
+            if let Err(e) =
+                create_item_snapshot_for_event(&*storage, dfid, event, &all_events, &source)
+            {
+                tracing::warn!(
+                    "Failed to create snapshot for item {} after event batch: {}. Events were still created.",
+                    dfid,
+                    e
+                );
+            }
+        }
+    }
+
+    tracing::info!(
+        "📦 Event batch from {}: {} created, {} deduplicated",
+        source,
+        created.len(),
+        bulk.results.len() - created.len()
+    );
+    Ok(Json(json!({
+        "success": true,
+        "created": created.len(),
+        "deduplicated": bulk.results.len() - created.len(),
+        "events": bulk
+            .results
+            .iter()
+            .map(|result| json!({
+                "event_id": result.event.event_id,
+                "dfid": result.event.dfid,
+                "content_hash": result.event.content_hash,
+                "was_deduplicated": result.was_deduplicated,
+            }))
+            .collect::<Vec<_>>(),
+        "anchor": bulk.merkle_root.map(|root| json!({
+            "merkle_root": root,
+            "notarization_id": notarization.as_ref().map(|n| n.notarization_id),
+            "status": notarization.as_ref().map(|n| n.status),
+        })),
+    })))
+}
+
+async fn list_event_attachments(
+    State(state): State<Arc<AppState>>,
+    Path(event_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let event = find_event(&state, &event_id).await?;
+    Ok(Json(json!({
+        "success": true,
+        "event_id": event_id,
+        "attachments": event.attachments
+    })))
+}
+
+/// Upload the request body to IPFS and attach it to the caller's event
+async fn attach_file(
+    State(state): State<Arc<AppState>>,
+    claims: Option<Extension<crate::api::auth::Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+    Path(event_id): Path<Uuid>,
+    Query(query): Query<AttachmentQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let source = request_source(claims, api_key_ctx)?;
+    // Check ownership before spending an upload on it
+    let event = find_event(&state, &event_id).await?;
+    if event.source != source {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": "Only the event's source can attach files to it"})),
+        ));
+    }
+
+    let mime_type = query.content_type.or_else(|| {
+        headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    });
+    let ipfs = IpfsClient::from_env().map_err(|e| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"error": format!("IPFS is not available: {}", e)})),
+        )
+    })?;
+    let attachment = upload_attachment(&ipfs, body.to_vec(), mime_type, query.file_name, &source)
+        .await
+        .map_err(attachment_error_response)?;
+
+    let event = state
+        .events_engine
+        .write()
+        .await
+        .add_attachment(&event_id, attachment.clone())
+        .map_err(attachment_error_response)?;
+
+    tracing::info!(
+        "📎 Attachment {} ({} bytes) added to event {} by {}",
+        attachment.cid,
+        attachment.size,
+        event_id,
+        source
+    );
+    Ok(Json(json!({
+        "success": true,
+        "attachment": attachment,
+        "event": EventDto::from(event)
+    })))
+}
+
+fn bucket_error_response(e: WorkspaceBucketError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        WorkspaceBucketError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        WorkspaceBucketError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        WorkspaceBucketError::NotConfigured(_) => StatusCode::CONFLICT,
+        WorkspaceBucketError::TransferError(_) => StatusCode::BAD_GATEWAY,
+        WorkspaceBucketError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// Presigned PUT into the caller's workspace bucket for a file of their
+/// event. Send the file with the returned headers, then confirm it.
+async fn presign_bucket_attachment(
+    State(state): State<Arc<AppState>>,
+    claims: Option<Extension<crate::api::auth::Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+    Path(event_id): Path<Uuid>,
+    Json(input): Json<BucketAttachmentInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let source = request_source(claims, api_key_ctx)?;
+    let event = find_event(&state, &event_id).await?;
+    if event.source != source {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": "Only the event's source can attach files to it"})),
+        ));
+    }
+
+    let upload = WorkspaceBucketEngine::new(Arc::clone(&state.shared_storage))
+        .presign_attachment_upload(&source, &event_id, &input, Utc::now())
+        .map_err(bucket_error_response)?;
+    Ok(Json(json!({
+        "success": true,
+        "attachment_id": upload.attachment_id,
+        "object_key": upload.object_key,
+        "upload": upload.upload,
+        "confirm_url": format!(
+            "/api/events/{}/attachments/bucket-uploads/{}",
+            event_id, upload.attachment_id
+        )
+    })))
+}
+
+/// Attach a file uploaded through `presign_bucket_attachment`, once the
+/// bucket holds it with the declared size and checksum
+async fn confirm_bucket_attachment(
+    State(state): State<Arc<AppState>>,
+    claims: Option<Extension<crate::api::auth::Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+    Path((event_id, attachment_id)): Path<(Uuid, Uuid)>,
+    Json(input): Json<BucketAttachmentInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let source = request_source(claims, api_key_ctx)?;
+    let attachment = WorkspaceBucketEngine::new(Arc::clone(&state.shared_storage))
+        .confirm_attachment(&source, &event_id, attachment_id, &input)
+        .await
+        .map_err(bucket_error_response)?;
+
+    let event = state
+        .events_engine
+        .write()
+        .await
+        .add_attachment(&event_id, attachment.clone())
+        .map_err(attachment_error_response)?;
+
+    tracing::info!(
+        "📎 Bucket attachment {} ({} bytes) added to event {} by {}",
+        attachment.attachment_id,
+        attachment.size,
+        event_id,
+        source
+    );
+    Ok(Json(json!({
+        "success": true,
+        "attachment": attachment,
+        "event": EventDto::from(event)
+    })))
+}
+
+/// Redirect to a short-lived URL of an attachment kept in a workspace bucket
+async fn download_bucket_attachment(
+    State(state): State<Arc<AppState>>,
+    claims: Option<Extension<crate::api::auth::Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+    Path((event_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Redirect, (StatusCode, Json<Value>)> {
+    let user_id = request_source(claims, api_key_ctx)?;
+    let event = find_event(&state, &event_id).await?;
+    let attachment = event
+        .attachments
+        .iter()
+        .find(|attachment| attachment.attachment_id == attachment_id)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "Attachment not found"})),
+            )
+        })?;
+
+    let download = WorkspaceBucketEngine::new(Arc::clone(&state.shared_storage))
+        .presign_attachment_download(&user_id, attachment, Utc::now())
+        .map_err(bucket_error_response)?;
+    Ok(Redirect::temporary(&download.url))
+}
+
+/// Payload limits that apply to events the caller creates
+async fn get_payload_limits(
+    State(state): State<Arc<AppState>>,
+    claims: Option<Extension<crate::api::auth::Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let source = request_source(claims, api_key_ctx)?;
+    let (tier, limits) = {
+        let storage = state.shared_storage.lock().unwrap();
+        payload_limits::limits_for_source(&*storage, &source)
+    };
+
+    Ok(Json(json!({
+        "success": true,
+        "tier": tier,
+        "limits": limits
+    })))
+}
+
+/// The limit exceeded, with the measured value and the maximum of the caller's tier
+fn payload_too_large_response(e: &PayloadLimitError) -> (StatusCode, Json<Value>) {
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(json!({
+            "error": format!("Event exceeds the payload limits of your tier: {}", e),
+            "limit": e
+        })),
+    )
+}
+
+fn attachment_error_response(e: EventsError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        EventsError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        EventsError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+        EventsError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        EventsError::NotFound => StatusCode::NOT_FOUND,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (
+        status,
+        Json(json!({"error": format!("Failed to attach file: {}", e)})),
+    )
+}
+
+async fn find_event(state: &AppState, event_id: &Uuid) -> Result<Event, (StatusCode, Json<Value>)> {
+    state
+        .events_engine
+        .read()
+        .await
+        .get_event(event_id)
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Failed to get event: {}", e)})),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "Event not found"})),
+            )
+        })
+}
+
+/// Hash the caller signs to create this event as a signed event
+async fn get_signing_hash(
+    claims: Option<Extension<crate::api::auth::Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+    Json(payload): Json<SigningHashRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let event_type = parse_event_type(&payload.event_type)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
+    let source = request_source(claims, api_key_ctx)?;
+    let occurred_at = parse_occurred_at(payload.occurred_at)?;
+    let metadata = payload.metadata.unwrap_or_default();
+
+    let signing_hash =
+        Event::calculate_signing_hash(&payload.dfid, &event_type, &source, &metadata, occurred_at);
+    Ok(Json(json!({
+        "signing_hash": signing_hash,
+        "source": source
+    })))
+}
+
+/// Check a stored event's signature and the registration of the key behind it
+async fn verify_event_signature(
+    State(state): State<Arc<AppState>>,
+    Path(event_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let event = find_event(&state, &event_id).await?;
+
+    let verification = EventSigningEngine::new(Arc::clone(&state.shared_storage))
+        .verify_event(&event)
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": e.to_string()})),
+            )
+        })?;
+    Ok(Json(json!({
+        "success": true,
+        "verification": verification
+    })))
+}
+
+/// What led to an event and what followed from it, for audits of why an item
+/// was pushed, pulled or enriched
+async fn get_event_chain(
+    State(state): State<Arc<AppState>>,
+    Path(event_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let chain = state
+        .events_engine
+        .read()
+        .await
+        .event_chain(&event_id)
+        .map_err(|e| match e {
+            EventsError::NotFound => (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "Event not found"})),
+            ),
+            e => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Failed to walk event chain: {}", e)})),
+            ),
+        })?;
+
+    let responses =
+        |events: Vec<Event>| -> Vec<EventDto> { events.into_iter().map(EventDto::from).collect() };
+    Ok(Json(json!({
+        "success": true,
+        "event": EventDto::from(chain.event),
+        "ancestors": responses(chain.ancestors),
+        "descendants": responses(chain.descendants),
+        "correlated": responses(chain.correlated),
+        "truncated": chain.truncated
+    })))
+}
+
+async fn get_events_for_item(
+    State(state): State<Arc<AppState>>,
+    Path(dfid): Path<String>,
+    Query(params): Query<PageParams>,
+) -> Result<Json<Page<EventDto>>, (StatusCode, Json<Value>)> {
+    let engine = state.events_engine.read().await;
+
+    events_page(&params, |after, limit| {
+        engine.list_events_page(Some(&dfid), after, limit, |_| true)
+    })
 }
 
 async fn get_events_by_type(
     State(state): State<Arc<AppState>>,
     Path(event_type_str): Path<String>,
-) -> Result<Json<Vec<EventResponse>>, (StatusCode, Json<Value>)> {
+    Query(params): Query<PageParams>,
+) -> Result<Json<Page<EventDto>>, (StatusCode, Json<Value>)> {
     let event_type = parse_event_type(&event_type_str)
         .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
 
-    let engine = state.events_engine.write().await;
+    let engine = state.events_engine.read().await;
 
-    match engine.get_events_by_type(event_type) {
-        Ok(events) => {
-            let response: Vec<EventResponse> = events.into_iter().map(event_to_response).collect();
-            Ok(Json(response))
-        }
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({"error": format!("Failed to get events: {}", e)})),
-        )),
-    }
+    events_page(&params, |after, limit| {
+        engine.list_events_page(None, after, limit, |event| event.event_type == event_type)
+    })
 }
 
 async fn get_events_by_visibility(
     State(state): State<Arc<AppState>>,
     Path(visibility_str): Path<String>,
-) -> Result<Json<Vec<EventResponse>>, (StatusCode, Json<Value>)> {
+    Query(params): Query<PageParams>,
+) -> Result<Json<Page<EventDto>>, (StatusCode, Json<Value>)> {
     let visibility = parse_event_visibility(&visibility_str)
         .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
 
-    let engine = state.events_engine.write().await;
+    let engine = state.events_engine.read().await;
 
-    match engine.get_events_by_visibility(visibility) {
-        Ok(events) => {
-            let response: Vec<EventResponse> = events.into_iter().map(event_to_response).collect();
-            Ok(Json(response))
-        }
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({"error": format!("Failed to get events: {}", e)})),
-        )),
-    }
+    events_page(&params, |after, limit| {
+        engine.list_events_page(None, after, limit, |event| event.visibility == visibility)
+    })
 }
 
 async fn get_events_timeline(
     State(state): State<Arc<AppState>>,
     Query(params): Query<EventQueryParams>,
-) -> Result<Json<Vec<EventResponse>>, (StatusCode, Json<Value>)> {
+) -> Result<Json<Vec<EventDto>>, (StatusCode, Json<Value>)> {
+    let event_type = params
+        .event_type
+        .as_deref()
+        .map(parse_event_type)
+        .transpose()
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
     let engine = state.events_engine.write().await;
 
-    match (params.start_date, params.end_date) {
+    let events = match (params.start_date, params.end_date) {
         (Some(start), Some(end)) => {
             let start_dt = chrono::DateTime::from_timestamp(start, 0).ok_or_else(|| {
                 (
@@ -445,74 +1091,94 @@ async fn get_events_timeline(
                     Json(json!({"error": "Invalid end_date timestamp"})),
                 )
             })?;
-
-            match engine.get_events_in_time_range(start_dt, end_dt) {
-                Ok(events) => {
-                    let response: Vec<EventResponse> =
-                        events.into_iter().map(event_to_response).collect();
-                    Ok(Json(response))
-                }
-                Err(e) => Err((
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(json!({"error": format!("Failed to get events: {}", e)})),
-                )),
-            }
-        }
-        _ => {
-            // Return all events if no time range specified
-            match engine.list_all_events() {
-                Ok(events) => {
-                    let response: Vec<EventResponse> =
-                        events.into_iter().map(event_to_response).collect();
-                    Ok(Json(response))
-                }
-                Err(e) => Err((
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(json!({"error": format!("Failed to get events: {}", e)})),
-                )),
-            }
+            engine.get_events_in_time_range_on(start_dt, end_dt, params.axis)
         }
+        // Return all events if no time range specified
+        _ => engine.list_all_events(),
     }
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("Failed to get events: {}", e)})),
+        )
+    })?;
+
+    let response: Vec<EventDto> = events
+        .into_iter()
+        .filter(|event| event_type.as_ref().is_none_or(|t| event.event_type == *t))
+        .map(EventDto::from)
+        .collect();
+    Ok(Json(response))
 }
 
-async fn get_public_events(
+/// Per-day event counts on the requested axis; use `axis=occurred` to chart
+/// backfilled history by when it happened rather than when it was imported
+async fn get_daily_event_counts(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<EventResponse>>, (StatusCode, Json<Value>)> {
-    let engine = state.events_engine.write().await;
+    Query(params): Query<EventQueryParams>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let (Some(start), Some(end)) = (params.start_date, params.end_date) else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "start_date and end_date are required"})),
+        ));
+    };
+    let (Some(start_dt), Some(end_dt)) = (
+        chrono::DateTime::from_timestamp(start, 0),
+        chrono::DateTime::from_timestamp(end, 0),
+    ) else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Invalid start_date or end_date timestamp"})),
+        ));
+    };
 
-    match engine.get_public_events() {
-        Ok(events) => {
-            let response: Vec<EventResponse> = events.into_iter().map(event_to_response).collect();
-            Ok(Json(response))
-        }
+    let engine = state.events_engine.read().await;
+    match engine.daily_event_counts(start_dt, end_dt, params.axis) {
+        Ok(counts) => Ok(Json(json!({
+            "axis": params.axis,
+            "days": counts
+                .into_iter()
+                .map(|(day, count)| json!({"date": day.to_string(), "count": count}))
+                .collect::<Vec<_>>(),
+        }))),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({"error": format!("Failed to get events: {}", e)})),
+            Json(json!({"error": format!("Failed to count events: {}", e)})),
         )),
     }
 }
 
+async fn get_public_events(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<PageParams>,
+) -> Result<Json<Page<EventDto>>, (StatusCode, Json<Value>)> {
+    let engine = state.events_engine.read().await;
+
+    events_page(&params, |after, limit| {
+        engine.list_events_page(None, after, limit, |event| {
+            event.visibility == EventVisibility::Public
+        })
+    })
+}
+
 async fn get_private_events(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<EventResponse>>, (StatusCode, Json<Value>)> {
-    let engine = state.events_engine.write().await;
+    Query(params): Query<PageParams>,
+) -> Result<Json<Page<EventDto>>, (StatusCode, Json<Value>)> {
+    let engine = state.events_engine.read().await;
 
-    match engine.get_private_events() {
-        Ok(events) => {
-            let response: Vec<EventResponse> = events.into_iter().map(event_to_response).collect();
-            Ok(Json(response))
-        }
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({"error": format!("Failed to get events: {}", e)})),
-        )),
-    }
+    events_page(&params, |after, limit| {
+        engine.list_events_page(None, after, limit, |event| {
+            event.visibility == EventVisibility::Private
+        })
+    })
 }
 
 async fn get_event(
     State(state): State<Arc<AppState>>,
     Path(event_id): Path<String>,
-) -> Result<Json<EventResponse>, (StatusCode, Json<Value>)> {
+) -> Result<Json<EventDto>, (StatusCode, Json<Value>)> {
     let event_uuid = Uuid::parse_str(&event_id).map_err(|_| {
         (
             StatusCode::BAD_REQUEST,
@@ -523,7 +1189,7 @@ async fn get_event(
     let engine = state.events_engine.write().await;
 
     match engine.get_event(&event_uuid) {
-        Ok(Some(event)) => Ok(Json(event_to_response(event))),
+        Ok(Some(event)) => Ok(Json(EventDto::from(event))),
         Ok(None) => Err((
             StatusCode::NOT_FOUND,
             Json(json!({"error": "Event not found"})),
@@ -539,7 +1205,7 @@ async fn add_event_metadata(
     State(state): State<Arc<AppState>>,
     Path(event_id): Path<String>,
     Json(metadata): Json<HashMap<String, serde_json::Value>>,
-) -> Result<Json<EventResponse>, (StatusCode, Json<Value>)> {
+) -> Result<Json<EventDto>, (StatusCode, Json<Value>)> {
     let event_uuid = Uuid::parse_str(&event_id).map_err(|_| {
         (
             StatusCode::BAD_REQUEST,
@@ -550,7 +1216,11 @@ async fn add_event_metadata(
     let mut engine = state.events_engine.write().await;
 
     match engine.add_event_metadata(&event_uuid, metadata) {
-        Ok(event) => Ok(Json(event_to_response(event))),
+        Ok(event) => Ok(Json(EventDto::from(event))),
+        Err(e @ EventsError::ValidationError(_)) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Failed to add metadata: {}", e)})),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({"error": format!("Failed to add metadata: {}", e)})),
@@ -598,7 +1268,7 @@ async fn create_local_event(
             Ok(Json(LocalEventResponse {
                 event_id: event.event_id.to_string(),
                 local_event_id,
-                event_type: format!("{:?}", event.event_type),
+                event_type: event.event_type.to_string(),
                 timestamp: event.timestamp.timestamp(),
                 source: event.source,
                 metadata: event.metadata,
@@ -637,7 +1307,7 @@ async fn get_local_event(
             Ok(Json(LocalEventResponse {
                 event_id: event.event_id.to_string(),
                 local_event_id,
-                event_type: format!("{:?}", event.event_type),
+                event_type: event.event_type.to_string(),
                 timestamp: event.timestamp.timestamp(),
                 source: event.source,
                 metadata: event.metadata,
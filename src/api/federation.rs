@@ -0,0 +1,379 @@
+//! Federation with other deployments. Nodes introduce themselves and peers pull
+//! circuit changes on the public `/api/federation` routes with node-signed
+//! requests; admins manage peers, their trust and circuit agreements, and
+//! conflicts under `/api/admin/federation`.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    middleware,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::public_items::public_rate_limit_middleware;
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AdminUser;
+use crate::federation_engine::{
+    key_fingerprint, ConflictResolution, FederationEngine, FederationError, FederationPeerInput,
+    FederationPeerUpdate, NODE_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use crate::types::{FederationConflictStatus, FederationHandshake, FederationPeer};
+
+/// Mounted at `/api/federation`, outside JWT auth: peers authenticate with
+/// their node key. Handshakes come from nodes nobody trusts yet, so they are
+/// rate limited per IP.
+pub fn federation_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/handshake", post(accept_handshake))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            public_rate_limit_middleware,
+        ))
+        .route("/node", get(get_node))
+        .route("/circuits/:circuit_id/changes", get(serve_changes))
+        .with_state(app_state)
+}
+
+pub fn admin_federation_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/peers", get(list_peers).post(register_peer))
+        .route(
+            "/peers/:peer_id",
+            get(get_peer).put(update_peer).delete(delete_peer),
+        )
+        .route("/peers/:peer_id/sync", post(sync_peer))
+        .route("/peers/:peer_id/trust", post(trust_peer))
+        .route("/peers/:peer_id/revoke", post(revoke_peer))
+        .route("/handshake", post(initiate_handshake))
+        .route("/conflicts", get(list_conflicts))
+        .route("/conflicts/:conflict_id/resolve", post(resolve_conflict))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChangesQuery {
+    #[serde(default)]
+    pub cursor: u64,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConflictsQuery {
+    pub status: Option<FederationConflictStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResolveConflictRequest {
+    pub resolution: ConflictResolution,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HandshakeRequest {
+    /// Base URL of the deployment to introduce this node to
+    pub url: String,
+}
+
+fn engine(app_state: &AppState) -> FederationEngine<SharedStorage> {
+    FederationEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn federation_error_response(e: FederationError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        FederationError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        FederationError::NotFound(_) => StatusCode::NOT_FOUND,
+        FederationError::NotConfigured(_) => StatusCode::SERVICE_UNAVAILABLE,
+        FederationError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+        FederationError::CursorExpired(_) => StatusCode::GONE,
+        FederationError::SyncError(_) => StatusCode::BAD_GATEWAY,
+        FederationError::StorageError(_) | FederationError::AuditError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// A peer with its key fingerprint, for admins to compare out of band
+fn peer_json(peer: &FederationPeer) -> Value {
+    let mut value = json!(peer);
+    value["fingerprint"] = json!(key_fingerprint(&peer.public_key));
+    value
+}
+
+/// This node's id, public key and fingerprint, for peers to register it
+async fn get_node(
+    State(app_state): State<Arc<AppState>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = engine(&app_state);
+    let node = engine.node().map_err(federation_error_response)?;
+    Ok(Json(json!({
+        "node_id": node.node_id,
+        "public_key": node.public_key(),
+        "fingerprint": node.fingerprint(),
+        "url": node.url
+    })))
+}
+
+/// Another node introducing itself; it is kept as a pending peer until an
+/// admin trusts it
+async fn accept_handshake(
+    State(app_state): State<Arc<AppState>>,
+    Json(handshake): Json<FederationHandshake>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let reply = engine(&app_state)
+        .accept_handshake(&handshake, Utc::now())
+        .map_err(federation_error_response)?;
+
+    tracing::info!(
+        "🤝 Handshake from federation node {} ({})",
+        handshake.node_id,
+        key_fingerprint(&handshake.public_key)
+    );
+    Ok(Json(json!(reply)))
+}
+
+/// A page of a shared circuit's changes, for the peer that signed the request
+async fn serve_changes(
+    State(app_state): State<Arc<AppState>>,
+    Path(circuit_id): Path<Uuid>,
+    Query(query): Query<ChangesQuery>,
+    headers: HeaderMap,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let header_value = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| {
+                federation_error_response(FederationError::Unauthorized(format!(
+                    "Missing {name} header"
+                )))
+            })
+    };
+    let node_id = header_value(NODE_HEADER)?;
+    let signature = header_value(SIGNATURE_HEADER)?;
+    let timestamp = header_value(TIMESTAMP_HEADER)?.parse().map_err(|_| {
+        federation_error_response(FederationError::Unauthorized(format!(
+            "{TIMESTAMP_HEADER} must be unix seconds"
+        )))
+    })?;
+
+    let engine = engine(&app_state);
+    let peer = engine
+        .authenticate(
+            node_id,
+            timestamp,
+            signature,
+            &circuit_id,
+            query.cursor,
+            Utc::now(),
+        )
+        .map_err(federation_error_response)?;
+    let batch = engine
+        .serve_changes(&peer, &circuit_id, query.cursor, query.limit)
+        .map_err(federation_error_response)?;
+    Ok(Json(json!(batch)))
+}
+
+async fn register_peer(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Json(input): Json<FederationPeerInput>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    let peer = engine(&app_state)
+        .register_peer(input, &admin_user_id)
+        .map_err(federation_error_response)?;
+
+    tracing::info!(
+        "🤝 {} registered federation peer {} ({})",
+        admin_user_id,
+        peer.name,
+        peer.node_id
+    );
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "success": true,
+            "peer": peer_json(&peer)
+        })),
+    ))
+}
+
+async fn list_peers(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let peers = engine(&app_state)
+        .list_peers()
+        .map_err(federation_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": peers.len(),
+        "peers": peers.iter().map(peer_json).collect::<Vec<_>>()
+    })))
+}
+
+async fn get_peer(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Path(peer_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let peer = engine(&app_state)
+        .get_peer(&peer_id)
+        .map_err(federation_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "peer": peer_json(&peer)
+    })))
+}
+
+async fn update_peer(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Path(peer_id): Path<Uuid>,
+    Json(update): Json<FederationPeerUpdate>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let peer = engine(&app_state)
+        .update_peer(&peer_id, update, &admin_user_id)
+        .map_err(federation_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "peer": peer_json(&peer)
+    })))
+}
+
+async fn delete_peer(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Path(peer_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    engine(&app_state)
+        .delete_peer(&peer_id, &admin_user_id)
+        .map_err(federation_error_response)?;
+
+    tracing::info!("🤝 {} removed federation peer {}", admin_user_id, peer_id);
+    Ok(Json(json!({
+        "success": true,
+        "peer_id": peer_id
+    })))
+}
+
+/// Trust a pending peer once its fingerprint is confirmed out of band
+async fn trust_peer(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Path(peer_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let peer = engine(&app_state)
+        .trust_peer(&peer_id, &admin_user_id)
+        .map_err(federation_error_response)?;
+
+    tracing::info!(
+        "🤝 {} trusted federation peer {} ({})",
+        admin_user_id,
+        peer.name,
+        peer.node_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "peer": peer_json(&peer)
+    })))
+}
+
+async fn revoke_peer(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Path(peer_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let peer = engine(&app_state)
+        .revoke_peer(&peer_id, &admin_user_id)
+        .map_err(federation_error_response)?;
+
+    tracing::warn!(
+        "⚠️  {} revoked federation peer {} ({})",
+        admin_user_id,
+        peer.name,
+        peer.node_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "peer": peer_json(&peer)
+    })))
+}
+
+/// Introduce this node to another deployment; both sides keep the other as a
+/// pending peer
+async fn initiate_handshake(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Json(request): Json<HandshakeRequest>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    let peer = engine(&app_state)
+        .initiate_handshake(&request.url, &admin_user_id)
+        .await
+        .map_err(federation_error_response)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "success": true,
+            "peer": peer_json(&peer)
+        })),
+    ))
+}
+
+/// Pull the peer's shared circuits now instead of waiting for the sync worker
+async fn sync_peer(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Path(peer_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuits = engine(&app_state)
+        .sync_peer(&peer_id)
+        .await
+        .map_err(federation_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "circuits": circuits
+    })))
+}
+
+async fn list_conflicts(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Query(query): Query<ConflictsQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let conflicts = engine(&app_state)
+        .list_conflicts(query.status)
+        .map_err(federation_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": conflicts.len(),
+        "conflicts": conflicts
+    })))
+}
+
+async fn resolve_conflict(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Path(conflict_id): Path<Uuid>,
+    Json(request): Json<ResolveConflictRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let conflict = engine(&app_state)
+        .resolve_conflict(&conflict_id, request.resolution, &admin_user_id)
+        .map_err(federation_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "conflict": conflict
+    })))
+}
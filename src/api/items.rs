@@ -1,6 +1,7 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
+    middleware,
     response::Json,
     routing::{delete, get, post, put},
     Extension, Router,
@@ -10,11 +11,19 @@ use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::identifier_types::{namespaces, IdentifierType};
+use crate::api::dto::{IdentifierDto, ItemDto};
+use crate::auth_middleware::{admin_auth_middleware, AdminUser};
+use crate::events_engine::EventsError;
+use crate::identifier_types::namespaces;
 use crate::items_engine::ResolutionAction;
+use crate::pagination::{page_size, Page, PageCursor};
+use crate::snapshot_types::StateSnapshot;
 use crate::storage::StorageBackend;
 use crate::storage_helpers::{with_storage, StorageLockError};
-use crate::types::{UserActivity, UserActivityCategory, UserActivityType, UserResourceType};
+use crate::types::{
+    IdentifierFilter, ItemProjection, ItemSearchQuery, ItemSummary, UserActivity,
+    UserActivityCategory, UserActivityType, UserResourceType,
+};
 use crate::{Identifier, Item, ItemStatus, PendingItem, PendingReason};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
@@ -74,33 +83,6 @@ impl IdentifierRequest {
             other => Err(format!("Invalid id_type '{other}'")),
         }
     }
-
-    pub fn from_identifier(identifier: &Identifier) -> Self {
-        match identifier.id_type.clone() {
-            IdentifierType::Canonical {
-                verified,
-                verification_date,
-                ..
-            } => Self {
-                namespace: Some(identifier.namespace.clone()),
-                key: identifier.key.clone(),
-                value: identifier.value.clone(),
-                id_type: Some("Canonical".to_string()),
-                scope: None,
-                verified: Some(verified),
-                verification_date,
-            },
-            IdentifierType::Contextual { scope } => Self {
-                namespace: Some(identifier.namespace.clone()),
-                key: identifier.key.clone(),
-                value: identifier.value.clone(),
-                id_type: Some("Contextual".to_string()),
-                scope: Some(scope),
-                verified: None,
-                verification_date: None,
-            },
-        }
-    }
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -271,8 +253,8 @@ pub struct SplitItemRequest {
 
 #[derive(Debug, Serialize)]
 pub struct SplitItemResponse {
-    pub original_item: ItemResponse,
-    pub new_item: ItemResponse,
+    pub original_item: ItemDto,
+    pub new_item: ItemDto,
 }
 
 #[derive(Debug, Deserialize)]
@@ -280,18 +262,110 @@ pub struct ItemQueryParams {
     pub identifier_key: Option<String>,
     pub identifier_value: Option<String>,
     pub status: Option<String>,
+    /// `summary` for only dfid, status and timestamps; `full` by default
+    pub fields: Option<String>,
+    /// Page size (default 50, max 200)
     pub limit: Option<usize>,
+    /// Opaque `next_cursor` from the previous page
+    pub cursor: Option<String>,
 }
 
+/// A page of whole items, or of summaries when `fields=summary`
 #[derive(Debug, Serialize)]
-pub struct ItemResponse {
-    pub dfid: String,
-    pub identifiers: Vec<IdentifierRequest>,
-    pub enriched_data: HashMap<String, serde_json::Value>,
-    pub creation_timestamp: i64,
-    pub last_modified: i64,
-    pub source_entries: Vec<String>,
-    pub status: String,
+#[serde(untagged)]
+pub enum ItemPage {
+    Full(Page<ItemDto>),
+    Summary(Page<ItemSummary>),
+}
+
+fn parse_projection(fields: Option<&str>) -> Result<ItemProjection, String> {
+    match fields {
+        None | Some("full") => Ok(ItemProjection::Full),
+        Some("summary") => Ok(ItemProjection::Summary),
+        Some(other) => Err(format!(
+            "Unknown fields value '{other}'; use 'full' or 'summary'"
+        )),
+    }
+}
+
+/// `GET /api/items/search` parameters; all given criteria must match
+#[derive(Debug, Deserialize)]
+pub struct ItemSearchParams {
+    /// Free text over enriched_data; every word must match (as a prefix)
+    pub q: Option<String>,
+    pub identifier_key: Option<String>,
+    pub identifier_value: Option<String>,
+    /// Extra identifier filters: comma-separated `key` or `key:value`
+    pub identifiers: Option<String>,
+    pub status: Option<String>,
+    /// RFC 3339 bounds on the creation time (inclusive)
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    /// RFC 3339 bounds on the declared occurrence span (overlap)
+    pub occurred_from: Option<DateTime<Utc>>,
+    pub occurred_to: Option<DateTime<Utc>>,
+    /// Only items pushed to this circuit (the caller must be a member)
+    pub circuit_id: Option<Uuid>,
+    /// `summary` for only dfid, status and timestamps; `full` by default
+    pub fields: Option<String>,
+    /// Page size (default 50, max 200)
+    pub limit: Option<usize>,
+    /// Opaque `next_cursor` from the previous page
+    pub cursor: Option<String>,
+}
+
+impl ItemSearchParams {
+    fn into_query(self) -> Result<ItemSearchQuery, String> {
+        let mut identifiers = Vec::new();
+        if let Some(key) = self.identifier_key {
+            identifiers.push(IdentifierFilter {
+                key,
+                value: self.identifier_value,
+            });
+        } else if self.identifier_value.is_some() {
+            return Err("identifier_value requires identifier_key".to_string());
+        }
+        for filter in self
+            .identifiers
+            .iter()
+            .flat_map(|list| list.split(','))
+            .map(str::trim)
+            .filter(|filter| !filter.is_empty())
+        {
+            identifiers.push(match filter.split_once(':') {
+                Some((key, value)) => IdentifierFilter {
+                    key: key.to_string(),
+                    value: Some(value.to_string()),
+                },
+                None => IdentifierFilter {
+                    key: filter.to_string(),
+                    value: None,
+                },
+            });
+        }
+
+        if let (Some(from), Some(to)) = (self.created_from, self.created_to) {
+            if from > to {
+                return Err("created_from must not be after created_to".to_string());
+            }
+        }
+        if let (Some(from), Some(to)) = (self.occurred_from, self.occurred_to) {
+            if from > to {
+                return Err("occurred_from must not be after occurred_to".to_string());
+            }
+        }
+
+        Ok(ItemSearchQuery {
+            text: self.q.filter(|q| !q.trim().is_empty()),
+            identifiers,
+            status: self.status.as_deref().map(parse_item_status).transpose()?,
+            created_from: self.created_from,
+            created_to: self.created_to,
+            occurred_from: self.occurred_from,
+            occurred_to: self.occurred_to,
+            circuit_id: self.circuit_id,
+        })
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -304,7 +378,7 @@ pub struct CreateItemsBatchResponse {
 #[derive(Debug, Serialize)]
 pub struct BatchItemResult {
     pub success: bool,
-    pub item: Option<ItemResponse>,
+    pub item: Option<ItemDto>,
     pub error: Option<String>,
 }
 
@@ -327,7 +401,7 @@ pub struct ShareItemRequest {
 #[derive(Debug, Serialize)]
 pub struct PendingItemResponse {
     pub id: String,
-    pub identifiers: Vec<IdentifierRequest>,
+    pub identifiers: Vec<IdentifierDto>,
     pub enriched_data: Option<HashMap<String, serde_json::Value>>,
     pub source_entry: String,
     pub reason: String,
@@ -347,7 +421,7 @@ pub struct ResolvePendingItemRequest {
 #[derive(Debug, Serialize)]
 pub struct ResolvePendingItemResponse {
     pub success: bool,
-    pub item: Option<ItemResponse>,
+    pub item: Option<ItemDto>,
     pub message: String,
 }
 
@@ -369,7 +443,7 @@ pub struct ShareItemResponse {
 #[derive(Debug, Serialize)]
 pub struct SharedItemListResponse {
     pub share_id: String,
-    pub item: ItemResponse,
+    pub item: ItemDto,
     pub shared_by: String,
     pub shared_at: i64,
     pub permissions: Option<Vec<String>>,
@@ -414,6 +488,22 @@ pub fn item_routes(app_state: Arc<AppState>) -> Router {
         .route("/pending/:id", get(get_pending_item))
         .route("/pending/:id/resolve", post(resolve_pending_item))
         .route("/:dfid/storage-history", get(get_storage_history))
+        .route("/:dfid/compacted", get(get_compacted_item))
+        .route(
+            "/:dfid/compact",
+            post(compact_item).route_layer(middleware::from_fn_with_state(
+                app_state.clone(),
+                admin_auth_middleware,
+            )),
+        )
+        // Recovery tool, so admin-only
+        .route(
+            "/:dfid/rebuild",
+            post(rebuild_item).route_layer(middleware::from_fn_with_state(
+                app_state.clone(),
+                admin_auth_middleware,
+            )),
+        )
         .with_state(app_state)
 }
 
@@ -427,35 +517,6 @@ fn parse_item_status(status_str: &str) -> Result<ItemStatus, String> {
     }
 }
 
-fn item_to_response(item: Item) -> ItemResponse {
-    let Item {
-        dfid,
-        identifiers,
-        enriched_data,
-        creation_timestamp,
-        last_modified,
-        source_entries,
-        status,
-        ..
-    } = item;
-
-    ItemResponse {
-        dfid,
-        identifiers: identifiers
-            .into_iter()
-            .map(|id| IdentifierRequest::from_identifier(&id))
-            .collect(),
-        enriched_data,
-        creation_timestamp: creation_timestamp.timestamp(),
-        last_modified: last_modified.timestamp(),
-        source_entries: source_entries
-            .into_iter()
-            .map(|uuid| uuid.to_string())
-            .collect(),
-        status: format!("{status:?}"),
-    }
-}
-
 pub fn build_identifiers(requests: Vec<IdentifierRequest>) -> Result<Vec<Identifier>, String> {
     requests
         .into_iter()
@@ -468,9 +529,9 @@ async fn create_item(
     claims: Option<Extension<crate::api::auth::Claims>>,
     api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
     Json(payload): Json<CreateItemRequest>,
-) -> Result<Json<ItemResponse>, (StatusCode, Json<Value>)> {
+) -> Result<Json<ItemDto>, (StatusCode, Json<Value>)> {
     // Auto-populate user_id from authenticated context (JWT or API key)
-    let _user_id = if let Some(Extension(claims)) = claims {
+    let user_id = if let Some(Extension(claims)) = claims {
         claims.user_id.clone()
     } else if let Some(Extension(ctx)) = api_key_ctx {
         ctx.user_id.to_string()
@@ -498,10 +559,11 @@ async fn create_item(
             )
         })?;
 
-        match engine.create_item_with_generated_dfid(
+        match engine.create_item_with_generated_dfid_as(
             identifiers,
             source_entry,
             payload.enriched_data,
+            Some(&user_id),
         ) {
             Ok(item) => item,
             Err(e) => {
@@ -530,7 +592,7 @@ async fn create_item(
         }
     });
 
-    Ok(Json(item_to_response(item)))
+    Ok(Json(ItemDto::from(item)))
 }
 
 async fn create_items_batch(
@@ -540,7 +602,7 @@ async fn create_items_batch(
     Json(payload): Json<CreateItemsBatchRequest>,
 ) -> Result<Json<CreateItemsBatchResponse>, (StatusCode, Json<Value>)> {
     // Auto-populate user_id from authenticated context (JWT or API key)
-    let _user_id = if let Some(Extension(claims)) = claims {
+    let user_id = if let Some(Extension(claims)) = claims {
         claims.user_id.clone()
     } else if let Some(Extension(ctx)) = api_key_ctx {
         ctx.user_id.to_string()
@@ -592,13 +654,18 @@ async fn create_items_batch(
                 }
             };
 
-            match engine.create_item_with_generated_dfid(identifiers, source_entry, enriched_data) {
+            match engine.create_item_with_generated_dfid_as(
+                identifiers,
+                source_entry,
+                enriched_data,
+                Some(&user_id),
+            ) {
                 Ok(item) => {
                     success_count += 1;
                     items_to_persist.push(item.clone());
                     results.push(BatchItemResult {
                         success: true,
-                        item: Some(item_to_response(item)),
+                        item: Some(ItemDto::from(item)),
                         error: None,
                     });
                 }
@@ -644,7 +711,7 @@ async fn get_item(
     claims: Option<Extension<crate::api::auth::Claims>>,
     api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
     Path(dfid): Path<String>,
-) -> Result<Json<ItemResponse>, (StatusCode, Json<Value>)> {
+) -> Result<Json<ItemDto>, (StatusCode, Json<Value>)> {
     // Auto-populate user_id from authenticated context (JWT or API key)
     let _user_id = if let Some(Extension(claims)) = claims {
         claims.user_id.clone()
@@ -660,7 +727,7 @@ async fn get_item(
     let engine = state.items_engine.write().await;
 
     match engine.get_item(&dfid) {
-        Ok(Some(item)) => Ok(Json(item_to_response(item))),
+        Ok(Some(item)) => Ok(Json(ItemDto::from(item))),
         Ok(None) => Err((
             StatusCode::NOT_FOUND,
             Json(json!({"error": "Item not found"})),
@@ -678,9 +745,9 @@ async fn update_item(
     api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
     Path(dfid): Path<String>,
     Json(payload): Json<UpdateItemRequest>,
-) -> Result<Json<ItemResponse>, (StatusCode, Json<Value>)> {
+) -> Result<Json<ItemDto>, (StatusCode, Json<Value>)> {
     // Auto-populate user_id from authenticated context (JWT or API key)
-    let _user_id = if let Some(Extension(claims)) = claims {
+    let user_id = if let Some(Extension(claims)) = claims {
         claims.user_id.clone()
     } else if let Some(Extension(ctx)) = api_key_ctx {
         ctx.user_id.to_string()
@@ -702,7 +769,9 @@ async fn update_item(
         // Update enriched data if provided
         if let Some(enriched_data) = enriched_data {
             let source_entry = uuid::Uuid::new_v4(); // Generate a new UUID for the enrichment
-            if let Err(e) = engine.enrich_item(&dfid, enriched_data, source_entry) {
+            if let Err(e) =
+                engine.enrich_item_as(&dfid, enriched_data, source_entry, Some(&user_id))
+            {
                 return Err((
                     StatusCode::BAD_REQUEST,
                     Json(json!({"error": format!("Failed to enrich item: {}", e)})),
@@ -761,7 +830,7 @@ async fn update_item(
         }
     });
 
-    Ok(Json(item_to_response(updated_item)))
+    Ok(Json(ItemDto::from(updated_item)))
 }
 
 async fn delete_item(
@@ -798,7 +867,7 @@ async fn list_items(
     claims: Option<Extension<crate::api::auth::Claims>>,
     api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
     Query(params): Query<ItemQueryParams>,
-) -> Result<Json<Vec<ItemResponse>>, (StatusCode, Json<Value>)> {
+) -> Result<Json<ItemPage>, (StatusCode, Json<Value>)> {
     // Auto-populate user_id from authenticated context (JWT or API key)
     let _user_id = if let Some(Extension(claims)) = claims {
         claims.user_id.clone()
@@ -811,37 +880,63 @@ async fn list_items(
         ));
     };
 
-    let engine = state.items_engine.write().await;
-
-    match engine.list_items() {
-        Ok(mut items) => {
-            // Apply filters
-            if let Some(status_str) = params.status {
-                if let Ok(status) = parse_item_status(&status_str) {
-                    items.retain(|item| item.status == status);
-                }
-            }
-
-            if let Some(key) = &params.identifier_key {
-                if let Some(value) = &params.identifier_value {
-                    items.retain(|item| {
-                        item.identifiers
-                            .iter()
-                            .any(|id| id.key == *key && id.value == *value)
-                    });
-                } else {
-                    items.retain(|item| item.identifiers.iter().any(|id| id.key == *key));
-                }
-            }
-
-            // Apply limit
-            if let Some(limit) = params.limit {
-                items.truncate(limit);
-            }
+    let after = params
+        .cursor
+        .as_deref()
+        .map(PageCursor::decode)
+        .transpose()
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
+    let status = match params.status.as_deref() {
+        Some(status_str) => parse_item_status(status_str).ok(),
+        None => None,
+    };
+    let projection = parse_projection(params.fields.as_deref())
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
+
+    let engine = state.items_engine.read().await;
+
+    // Summaries are filtered by the storage query, which never reads identifiers
+    if projection == ItemProjection::Summary {
+        let query = ItemSearchQuery {
+            identifiers: params
+                .identifier_key
+                .clone()
+                .map(|key| IdentifierFilter {
+                    key,
+                    value: params.identifier_value.clone(),
+                })
+                .into_iter()
+                .collect(),
+            status,
+            ..Default::default()
+        };
+        return engine
+            .search_item_summaries(&query, after, page_size(params.limit))
+            .map(|page| Json(ItemPage::Summary(page)))
+            .map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({"error": format!("Failed to list items: {}", e)})),
+                )
+            });
+    }
 
-            let response: Vec<ItemResponse> = items.into_iter().map(item_to_response).collect();
-            Ok(Json(response))
+    let keep = |item: &Item| {
+        if status.as_ref().is_some_and(|status| item.status != *status) {
+            return false;
+        }
+        match (&params.identifier_key, &params.identifier_value) {
+            (Some(key), Some(value)) => item
+                .identifiers
+                .iter()
+                .any(|id| id.key == *key && id.value == *value),
+            (Some(key), None) => item.identifiers.iter().any(|id| id.key == *key),
+            _ => true,
         }
+    };
+
+    match engine.list_items_page(after, page_size(params.limit), keep) {
+        Ok(page) => Ok(Json(ItemPage::Full(page.map(ItemDto::from)))),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({"error": format!("Failed to list items: {}", e)})),
@@ -855,9 +950,9 @@ async fn merge_items(
     api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
     Path(primary_dfid): Path<String>,
     Json(secondary_dfid): Json<String>,
-) -> Result<Json<ItemResponse>, (StatusCode, Json<Value>)> {
+) -> Result<Json<ItemDto>, (StatusCode, Json<Value>)> {
     // Auto-populate user_id from authenticated context (JWT or API key)
-    let _user_id = if let Some(Extension(claims)) = claims {
+    let user_id = if let Some(Extension(claims)) = claims {
         claims.user_id.clone()
     } else if let Some(Extension(ctx)) = api_key_ctx {
         ctx.user_id.to_string()
@@ -888,7 +983,7 @@ async fn merge_items(
                     ),
                 }
 
-                (item_to_response(primary_item), items_to_persist)
+                (ItemDto::from(primary_item), items_to_persist)
             }
             Err(e) => {
                 return Err((
@@ -899,6 +994,20 @@ async fn merge_items(
         }
     };
 
+    // Recorded on the surviving item so its timeline reaches the merged one
+    if let Err(e) = state.events_engine.write().await.create_item_merged_event(
+        primary_dfid.clone(),
+        secondary_dfid.clone(),
+        user_id,
+    ) {
+        tracing::warn!(
+            "Failed to record merge of {} into {}: {}",
+            secondary_dfid,
+            primary_dfid,
+            e
+        );
+    }
+
     let postgres_persistence = Arc::clone(&state.postgres_persistence);
     tokio::spawn(async move {
         let pg_lock = postgres_persistence.read().await;
@@ -952,8 +1061,8 @@ async fn split_item(
 
                 (
                     SplitItemResponse {
-                        original_item: item_to_response(original_item),
-                        new_item: item_to_response(new_item),
+                        original_item: ItemDto::from(original_item),
+                        new_item: ItemDto::from(new_item),
                     },
                     items_to_persist,
                 )
@@ -989,7 +1098,7 @@ async fn deprecate_item(
     claims: Option<Extension<crate::api::auth::Claims>>,
     api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
     Path(dfid): Path<String>,
-) -> Result<Json<ItemResponse>, (StatusCode, Json<Value>)> {
+) -> Result<Json<ItemDto>, (StatusCode, Json<Value>)> {
     // Auto-populate user_id from authenticated context (JWT or API key)
     let _user_id = if let Some(Extension(claims)) = claims {
         claims.user_id.clone()
@@ -1033,17 +1142,80 @@ async fn deprecate_item(
         }
     });
 
-    Ok(Json(item_to_response(item)))
+    Ok(Json(ItemDto::from(item)))
 }
 
 async fn search_items(
     State(state): State<Arc<AppState>>,
     claims: Option<Extension<crate::api::auth::Claims>>,
     api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
-    Query(params): Query<ItemQueryParams>,
-) -> Result<Json<Vec<ItemResponse>>, (StatusCode, Json<Value>)> {
-    // Reuse list_items logic for search (which now includes authentication)
-    list_items(State(state), claims, api_key_ctx, Query(params)).await
+    Query(params): Query<ItemSearchParams>,
+) -> Result<Json<ItemPage>, (StatusCode, Json<Value>)> {
+    // Auto-populate user_id from authenticated context (JWT or API key)
+    let user_id = if let Some(Extension(claims)) = claims {
+        claims.user_id.clone()
+    } else if let Some(Extension(ctx)) = api_key_ctx {
+        ctx.user_id.to_string()
+    } else {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({"error": "Authentication required. Use JWT token or API key."})),
+        ));
+    };
+
+    let after = params
+        .cursor
+        .as_deref()
+        .map(PageCursor::decode)
+        .transpose()
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
+    let limit = page_size(params.limit);
+    let projection = parse_projection(params.fields.as_deref())
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
+    let query = params
+        .into_query()
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
+
+    if let Some(circuit_id) = &query.circuit_id {
+        let circuit = state
+            .shared_storage
+            .get_circuit(circuit_id)
+            .map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({"error": format!("Failed to load circuit: {}", e)})),
+                )
+            })?
+            .ok_or_else(|| {
+                (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({"error": "Circuit not found"})),
+                )
+            })?;
+        if !circuit.is_member(&user_id) {
+            return Err((
+                StatusCode::FORBIDDEN,
+                Json(json!({"error": "Not a member of this circuit"})),
+            ));
+        }
+    }
+
+    let engine = state.items_engine.read().await;
+    let page = match projection {
+        ItemProjection::Full => engine
+            .search_items(&query, after, limit)
+            .map(|page| ItemPage::Full(page.map(ItemDto::from))),
+        ItemProjection::Summary => engine
+            .search_item_summaries(&query, after, limit)
+            .map(ItemPage::Summary),
+    };
+    match page {
+        Ok(page) => Ok(Json(page)),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("Failed to search items: {}", e)})),
+        )),
+    }
 }
 
 async fn get_item_stats(
@@ -1108,7 +1280,7 @@ async fn get_items_by_identifier(
     claims: Option<Extension<crate::api::auth::Claims>>,
     api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
     Path((key, value)): Path<(String, String)>,
-) -> Result<Json<Vec<ItemResponse>>, (StatusCode, Json<Value>)> {
+) -> Result<Json<Vec<ItemDto>>, (StatusCode, Json<Value>)> {
     // Auto-populate user_id from authenticated context (JWT or API key)
     let _user_id = if let Some(Extension(claims)) = claims {
         claims.user_id.clone()
@@ -1126,7 +1298,7 @@ async fn get_items_by_identifier(
 
     match engine.find_items_by_identifier(&identifier) {
         Ok(items) => {
-            let response: Vec<ItemResponse> = items.into_iter().map(item_to_response).collect();
+            let response: Vec<ItemDto> = items.into_iter().map(ItemDto::from).collect();
             Ok(Json(response))
         }
         Err(e) => Err((
@@ -1250,7 +1422,7 @@ pub async fn get_shared_items_for_user(
                 .into_iter()
                 .map(|shared_item| SharedItemListResponse {
                     share_id: shared_item.share_id,
-                    item: item_to_response(shared_item.item),
+                    item: ItemDto::from(shared_item.item),
                     shared_by: shared_item.shared_by,
                     shared_at: shared_item.shared_at.timestamp(),
                     permissions: shared_item.permissions,
@@ -1425,7 +1597,7 @@ async fn resolve_pending_item(
     match engine.resolve_pending_item(&pending_id, resolution_action) {
         Ok(Some(item)) => Ok(Json(ResolvePendingItemResponse {
             success: true,
-            item: Some(item_to_response(item)),
+            item: Some(ItemDto::from(item)),
             message: "Pending item resolved and created successfully".to_string(),
         })),
         Ok(None) => Ok(Json(ResolvePendingItemResponse {
@@ -1448,7 +1620,7 @@ fn pending_item_to_response(pending_item: PendingItem) -> PendingItemResponse {
         identifiers: pending_item
             .identifiers
             .into_iter()
-            .map(|id| IdentifierRequest::from_identifier(&id))
+            .map(|id| IdentifierDto::from(&id))
             .collect(),
         enriched_data: pending_item.enriched_data,
         source_entry: pending_item.source_entry.to_string(),
@@ -2248,3 +2420,153 @@ async fn get_storage_history(
         )),
     }
 }
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RebuildItemRequest {
+    /// Report what the rebuild would change without storing it
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Rebuild an item's enriched state by replaying its event history, e.g. after
+/// bad enrichment logic or storage corruption. The previous enriched data is
+/// returned so fields the events do not account for can be restored.
+async fn rebuild_item(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Path(dfid): Path<String>,
+    request: Option<Json<RebuildItemRequest>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let dry_run = request.map(|Json(r)| r.dry_run).unwrap_or_default();
+    let previous = {
+        let engine = state.items_engine.read().await;
+        engine.get_item(&dfid).map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Failed to load item: {}", e)})),
+            )
+        })?
+    };
+
+    let replay = {
+        let engine = state.events_engine.read().await;
+        engine.rebuild_item(&dfid, dry_run).map_err(|e| match e {
+            EventsError::NotFound => (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "Item not found"})),
+            ),
+            e => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Failed to rebuild item: {}", e)})),
+            ),
+        })?
+    };
+
+    if !dry_run {
+        tracing::info!(
+            "🔁 Item {} rebuilt from {} events by {}",
+            dfid,
+            replay.events_applied,
+            admin_user_id
+        );
+    }
+    Ok(Json(json!({
+        "success": true,
+        "dry_run": dry_run,
+        "rebuild": replay,
+        "previous_enriched_data": previous.map(|item| item.enriched_data)
+    })))
+}
+
+/// Load an item from its latest compaction snapshot plus the events after it
+async fn get_compacted_item(
+    State(state): State<Arc<AppState>>,
+    claims: Option<Extension<crate::api::auth::Claims>>,
+    api_key_ctx: Option<Extension<crate::api_key_middleware::ApiKeyContext>>,
+    Path(dfid): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    if claims.is_none() && api_key_ctx.is_none() {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({"error": "Authentication required. Use JWT token or API key."})),
+        ));
+    }
+
+    let compacted = {
+        let engine = state.events_engine.read().await;
+        engine.load_compacted_item(&dfid).map_err(|e| match e {
+            EventsError::NotFound => (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "Item not found"})),
+            ),
+            e => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Failed to load item: {}", e)})),
+            ),
+        })?
+    };
+
+    Ok(Json(json!({
+        "success": true,
+        "item": ItemDto::from(compacted.item),
+        "snapshot": compacted.snapshot.as_ref().map(compaction_json),
+        "tail_events": compacted.tail_events.len(),
+        "skipped": compacted.skipped
+    })))
+}
+
+/// Fold an item's events since its last compaction into a new snapshot now,
+/// instead of waiting for the background compactor
+async fn compact_item(
+    State(state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Path(dfid): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = state.events_engine.read().await;
+    let snapshot = engine
+        .compact_item(&dfid, &admin_user_id)
+        .map_err(|e| match e {
+            EventsError::NotFound => (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "Item not found"})),
+            ),
+            e => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Failed to compact item: {}", e)})),
+            ),
+        })?;
+    let Some(mut snapshot) = snapshot else {
+        return Ok(Json(json!({
+            "success": true,
+            "compacted": false,
+            "message": "No events since the last compaction"
+        })));
+    };
+
+    // The snapshot is already stored; a failed adapter write only leaves it
+    // without a CID
+    let adapter_error = match engine.store_compaction_on_adapter(&snapshot).await {
+        Ok(cid) => {
+            snapshot.ipfs_cid = cid;
+            None
+        }
+        Err(e) => Some(e.to_string()),
+    };
+    Ok(Json(json!({
+        "success": true,
+        "compacted": true,
+        "snapshot": compaction_json(&snapshot),
+        "adapter_error": adapter_error
+    })))
+}
+
+fn compaction_json(snapshot: &StateSnapshot) -> Value {
+    json!({
+        "snapshot_id": snapshot.snapshot_id,
+        "version": snapshot.version,
+        "parent_hash": snapshot.parent_hash,
+        "operation": snapshot.operation,
+        "ipfs_cid": snapshot.ipfs_cid,
+        "timestamp": snapshot.timestamp
+    })
+}
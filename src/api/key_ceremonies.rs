@@ -0,0 +1,192 @@
+//! Key ceremonies for anchoring and audit signing keys, nested under the
+//! admin-guarded `/api/admin/keys`. Shares, backups and restored secrets are
+//! only ever returned in responses; the server keeps the public half.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AdminUser;
+use crate::key_ceremony_engine::{
+    GenerateKeyInput, KeyCeremonyEngine, KeyCeremonyError, RestoreKeyInput,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct BackupKeyRequest {
+    pub shares: Vec<String>,
+    pub passphrase: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConfirmKeyRequest {
+    /// Fingerprint as the confirming admin read it from the ceremony output
+    pub fingerprint: String,
+}
+
+pub fn admin_key_ceremony_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_keys).post(generate_key))
+        .route("/:key_id", get(get_key))
+        .route("/:key_id/backup", post(backup_key))
+        .route("/:key_id/restore", post(restore_key))
+        .route("/:key_id/confirm", post(confirm_key))
+        .route("/:key_id/activate", post(activate_key))
+}
+
+fn engine(app_state: &AppState) -> KeyCeremonyEngine<SharedStorage> {
+    KeyCeremonyEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn key_ceremony_error_response(e: KeyCeremonyError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        KeyCeremonyError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        KeyCeremonyError::NotFound(_) => StatusCode::NOT_FOUND,
+        KeyCeremonyError::StorageError(_) | KeyCeremonyError::AuditError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn list_keys(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let keys = engine(&app_state)
+        .list_keys()
+        .map_err(key_ceremony_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "keys": keys
+    })))
+}
+
+async fn get_key(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Path(key_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let key = engine(&app_state)
+        .get_key(&key_id)
+        .map_err(key_ceremony_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "key": key
+    })))
+}
+
+/// Run a ceremony: generate a key and return its shares, once
+async fn generate_key(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Json(input): Json<GenerateKeyInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let generated = engine(&app_state)
+        .generate_key(&admin_user_id, input, Utc::now())
+        .map_err(key_ceremony_error_response)?;
+
+    tracing::info!(
+        "🔑 Key {} generated by {} (fingerprint {})",
+        generated.key.key_id,
+        admin_user_id,
+        generated.key.fingerprint
+    );
+    Ok(Json(json!({
+        "success": true,
+        "key": generated.key,
+        "shares": generated.shares,
+        "backup": generated.backup,
+        "warning": "Shares and backup are not stored; distribute them to custodians now"
+    })))
+}
+
+async fn backup_key(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Path(key_id): Path<Uuid>,
+    Json(request): Json<BackupKeyRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let backup = engine(&app_state)
+        .backup_key(
+            &admin_user_id,
+            &key_id,
+            &request.shares,
+            &request.passphrase,
+        )
+        .map_err(key_ceremony_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "backup": backup
+    })))
+}
+
+async fn restore_key(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Path(key_id): Path<Uuid>,
+    Json(input): Json<RestoreKeyInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let secret = engine(&app_state)
+        .restore_key(&admin_user_id, &key_id, input)
+        .map_err(key_ceremony_error_response)?;
+
+    tracing::warn!("🔑 Key {} restored by {}", key_id, admin_user_id);
+    Ok(Json(json!({
+        "success": true,
+        "key_id": key_id,
+        "secret": secret
+    })))
+}
+
+async fn confirm_key(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Path(key_id): Path<Uuid>,
+    Json(request): Json<ConfirmKeyRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let key = engine(&app_state)
+        .confirm_key(&admin_user_id, &key_id, &request.fingerprint, Utc::now())
+        .map_err(key_ceremony_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "key": key,
+        "confirmations_remaining": key
+            .required_confirmations
+            .saturating_sub(key.confirmations.len() as u32)
+    })))
+}
+
+async fn activate_key(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Path(key_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let key = engine(&app_state)
+        .activate_key(&admin_user_id, &key_id, Utc::now())
+        .map_err(key_ceremony_error_response)?;
+
+    tracing::info!(
+        "🔑 Key {} activated for {:?} by {}",
+        key.key_id,
+        key.purpose,
+        admin_user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "key": key
+    })))
+}
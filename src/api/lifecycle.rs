@@ -0,0 +1,147 @@
+//! Workspace lifecycle definitions and the stage each item has reached.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::lifecycle_engine::{LifecycleDefinitionInput, LifecycleEngine, LifecycleError};
+
+/// Mounted at `/api/lifecycle`
+pub fn lifecycle_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/workspaces/:workspace_id",
+            get(get_definition).put(set_definition),
+        )
+        .route("/workspaces/:workspace_id/items", get(list_items))
+        .route("/items/:dfid", get(get_item_lifecycle))
+        .route("/items/:dfid/stage", post(set_item_stage))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> LifecycleEngine<SharedStorage> {
+    LifecycleEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn lifecycle_error_response(e: LifecycleError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        LifecycleError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        LifecycleError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        LifecycleError::NotFound(_) => StatusCode::NOT_FOUND,
+        LifecycleError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StageQuery {
+    pub stage: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetStageRequest {
+    pub stage: String,
+}
+
+async fn get_definition(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let lifecycle = engine(&app_state)
+        .get_definition(&user_id, &workspace_id)
+        .map_err(lifecycle_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "lifecycle": lifecycle
+    })))
+}
+
+async fn set_definition(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+    Json(input): Json<LifecycleDefinitionInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let lifecycle = engine(&app_state)
+        .set_definition(&user_id, &workspace_id, input, Utc::now())
+        .map_err(lifecycle_error_response)?;
+
+    tracing::info!(
+        "🔁 Lifecycle of workspace {} set by {} ({} stages)",
+        workspace_id,
+        user_id,
+        lifecycle.stages.len()
+    );
+    Ok(Json(json!({
+        "success": true,
+        "lifecycle": lifecycle
+    })))
+}
+
+/// Items of the workspace, optionally only those in `?stage=`
+async fn list_items(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+    Query(query): Query<StageQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let items = engine(&app_state)
+        .list_items(&user_id, &workspace_id, query.stage.as_deref())
+        .map_err(lifecycle_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": items.len(),
+        "items": items
+    })))
+}
+
+async fn get_item_lifecycle(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(_user_id): AuthenticatedUser,
+    Path(dfid): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let lifecycle = engine(&app_state)
+        .get_item_lifecycle(&dfid)
+        .map_err(lifecycle_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "lifecycle": lifecycle
+    })))
+}
+
+/// Set the stage by hand, settling out-of-order moves held for review
+async fn set_item_stage(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(dfid): Path<String>,
+    Json(request): Json<SetStageRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let lifecycle = engine(&app_state)
+        .set_stage(&user_id, &dfid, &request.stage, Utc::now())
+        .map_err(lifecycle_error_response)?;
+
+    tracing::info!(
+        "🔁 {} moved to stage {} by {}",
+        dfid,
+        request.stage,
+        user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "lifecycle": lifecycle
+    })))
+}
@@ -0,0 +1,144 @@
+//! Maintenance mode. Admins set the window at `/api/admin/maintenance`; anyone
+//! can read it at `/api/status/maintenance`. While it is active,
+//! `maintenance_mode_middleware` rejects writes with a 503.
+
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::api::announcements::broadcast_notifications;
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AdminUser;
+use crate::maintenance_engine::{self, MaintenanceEngine, MaintenanceError, MaintenanceInput};
+use crate::types::MaintenanceMode;
+
+/// Public route, merged into `/api/status`
+pub fn maintenance_status_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/maintenance", get(get_maintenance_status))
+}
+
+/// Nested under the admin-guarded `/api/admin/maintenance`
+pub fn admin_maintenance_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/", get(get_maintenance).put(set_maintenance))
+}
+
+/// Rejects mutating requests while a maintenance window is active
+pub async fn maintenance_mode_middleware(
+    State(app_state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if maintenance_engine::is_exempt(request.method(), request.uri().path()) {
+        return next.run(request).await;
+    }
+    match engine(&app_state).active_window(Utc::now()) {
+        Ok(Some(mode)) => maintenance_response(&mode),
+        Ok(None) => next.run(request).await,
+        Err(e) => {
+            // An unreadable flag must not take the whole API down with it
+            tracing::warn!("⚠️  Could not read maintenance mode: {}", e);
+            next.run(request).await
+        }
+    }
+}
+
+fn maintenance_response(mode: &MaintenanceMode) -> Response {
+    let body = Json(json!({
+        "error": "Service is in maintenance mode; writes are temporarily disabled",
+        "maintenance": mode_json(mode)
+    }));
+    let retry_after = mode
+        .ends_at
+        .map(|ends_at| (ends_at - Utc::now()).num_seconds().max(1));
+    match retry_after {
+        Some(seconds) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, seconds.to_string())],
+            body,
+        )
+            .into_response(),
+        None => (StatusCode::SERVICE_UNAVAILABLE, body).into_response(),
+    }
+}
+
+fn engine(app_state: &AppState) -> MaintenanceEngine<SharedStorage> {
+    MaintenanceEngine::new(Arc::clone(&app_state.shared_storage))
+        .with_live_stream(app_state.live_stream.clone())
+}
+
+fn maintenance_error_response(e: MaintenanceError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        MaintenanceError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        MaintenanceError::StorageError(_) | MaintenanceError::AnnouncementError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// The window as clients see it; who set it stays internal
+fn mode_json(mode: &MaintenanceMode) -> Value {
+    json!({
+        "active": mode.is_active(Utc::now()),
+        "enabled": mode.enabled,
+        "message": mode.message,
+        "starts_at": mode.starts_at,
+        "ends_at": mode.ends_at
+    })
+}
+
+async fn get_maintenance_status(
+    State(app_state): State<Arc<AppState>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let mode = engine(&app_state)
+        .current()
+        .map_err(maintenance_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "maintenance": mode_json(&mode)
+    })))
+}
+
+async fn get_maintenance(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let mode = engine(&app_state)
+        .current()
+        .map_err(maintenance_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "active": mode.is_active(Utc::now()),
+        "maintenance": mode
+    })))
+}
+
+/// Enable, schedule or lift maintenance mode
+async fn set_maintenance(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Json(input): Json<MaintenanceInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let (mode, notifications) = engine(&app_state)
+        .update(&admin_user_id, input, Utc::now())
+        .map_err(maintenance_error_response)?;
+    let notified = notifications.len();
+    broadcast_notifications(&app_state, notifications);
+
+    Ok(Json(json!({
+        "success": true,
+        "active": mode.is_active(Utc::now()),
+        "maintenance": mode,
+        "users_notified": notified
+    })))
+}
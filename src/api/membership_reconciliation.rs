@@ -0,0 +1,89 @@
+//! Reconciliation of circuit items with the circuit events of the event log,
+//! under the admin-guarded `/api/admin/membership-reconciliation`. A run
+//! without a body uses the scheduled run's policy.
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AdminUser;
+use crate::membership_reconciliation_engine::{
+    MembershipReconciliationEngine, MembershipReconciliationError,
+};
+use crate::types::MembershipRepairPolicy;
+
+pub fn admin_membership_reconciliation_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/run", post(run_reconciliation))
+        .route("/reports", get(list_reports))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RunReconciliationRequest {
+    pub policy: Option<MembershipRepairPolicy>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+fn engine(app_state: &AppState) -> MembershipReconciliationEngine<SharedStorage> {
+    MembershipReconciliationEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn reconciliation_error_response(e: MembershipReconciliationError) -> (StatusCode, Json<Value>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({"error": e.to_string()})),
+    )
+}
+
+/// Compare and repair now, or with `dry_run` only report discrepancies
+async fn run_reconciliation(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    request: Option<Json<RunReconciliationRequest>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let policy = request
+        .policy
+        .unwrap_or_else(MembershipRepairPolicy::from_env);
+
+    let report = engine(&app_state)
+        .run(policy, request.dry_run, Some(admin_user_id.clone()))
+        .map_err(reconciliation_error_response)?;
+
+    if !report.dry_run && !report.discrepancies.is_empty() {
+        tracing::info!(
+            "🔍 {} reconciled circuit membership: {} discrepancies, {} repaired",
+            admin_user_id,
+            report.discrepancies.len(),
+            report.repaired
+        );
+    }
+    Ok(Json(json!({
+        "success": true,
+        "report": report
+    })))
+}
+
+async fn list_reports(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let reports = engine(&app_state)
+        .list_reports()
+        .map_err(reconciliation_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": reports.len(),
+        "reports": reports
+    })))
+}
@@ -1,42 +1,123 @@
+pub mod access_reviews;
 pub mod activities;
 pub mod adapters;
 pub mod admin;
+pub mod anchoring;
+pub mod announcements;
+pub mod anonymization;
 pub mod api_keys;
+pub mod attestations;
 pub mod audit;
 pub mod auth;
+pub mod change_feeds;
+pub mod circuit_directory;
 pub mod circuits;
+pub mod comments;
+pub mod config_bundles;
+pub mod connectors;
+pub mod dashboard_metrics;
+pub mod data_exports;
+pub mod data_lake_gc;
+pub mod data_quality;
+pub mod dedup_sharing;
+pub mod dedup_strategies;
+pub mod device_backups;
+pub mod disclosures;
+pub mod dto;
+pub mod engagement;
+pub mod enrichment_policies;
 pub mod events;
+pub mod federation;
 pub mod items;
+pub mod key_ceremonies;
+pub mod lifecycle;
+pub mod maintenance;
+pub mod membership_reconciliation;
 pub mod merkle;
+pub mod notarizations;
 pub mod notifications;
+pub mod organizations;
+pub mod partner_tokens;
+pub mod previews;
+pub mod proof_shares;
+pub mod provenance;
+pub mod public_items;
 pub mod receipts;
+pub mod reverification;
+pub mod review_queue;
+pub mod runbooks;
+pub mod sensors;
 pub mod shared_state;
+pub mod signing_keys;
 pub mod snapshots;
+pub mod status;
 pub mod storage_history;
+pub mod stream;
 pub mod test_blockchain;
 pub mod timeline;
+pub mod usage_anomalies;
 pub mod user_activity;
 pub mod user_credits;
+pub mod verification_rules;
+pub mod verification_scheduler;
+pub mod versioning;
+pub mod workspace_buckets;
 pub mod workspaces;
 pub mod zk_proofs;
 
+pub use access_reviews::access_review_routes;
 pub use activities::activity_routes;
 pub use adapters::adapter_routes;
 pub use admin::admin_routes;
+pub use anchoring::anchoring_routes;
+pub use announcements::announcement_routes;
 pub use api_keys::api_key_routes;
+pub use attestations::attestation_routes;
 pub use audit::audit_routes;
 pub use auth::auth_routes;
+pub use change_feeds::change_feed_routes;
+pub use circuit_directory::circuit_directory_routes;
 pub use circuits::circuit_routes;
+pub use comments::comment_routes;
+pub use connectors::connector_routes;
+pub use dashboard_metrics::dashboard_metric_routes;
+pub use data_exports::data_export_routes;
+pub use data_quality::data_quality_routes;
+pub use dedup_sharing::dedup_sharing_routes;
+pub use dedup_strategies::dedup_strategy_routes;
+pub use device_backups::device_backup_routes;
+pub use disclosures::{disclosure_routes, public_disclosure_routes};
+pub use engagement::engagement_routes;
+pub use enrichment_policies::enrichment_policy_routes;
 pub use events::event_routes;
+pub use federation::federation_routes;
 pub use items::item_routes;
+pub use lifecycle::lifecycle_routes;
+pub use maintenance::maintenance_mode_middleware;
 pub use merkle::{merkle_routes, public_merkle_routes};
+pub use notarizations::{notarization_routes, public_notarization_routes};
 pub use notifications::{notifications_rest_routes, notifications_ws_route};
+pub use organizations::organization_routes;
+pub use partner_tokens::{partner_access_routes, partner_token_routes};
+pub use previews::preview_routes;
+pub use proof_shares::public_proof_share_routes;
+pub use provenance::provenance_routes;
+pub use public_items::public_item_routes;
 pub use receipts::receipt_routes;
+pub use review_queue::review_queue_routes;
+pub use sensors::sensor_routes;
+pub use signing_keys::signing_key_routes;
 pub use snapshots::{create_public_snapshot_routes, create_snapshot_routes};
+pub use status::{sla_tracking_middleware, status_routes};
 pub use storage_history::{public_storage_history_routes, storage_history_routes};
+pub use stream::stream_routes;
 pub use test_blockchain::test_blockchain_routes;
 pub use timeline::{get_indexing_progress, get_item_timeline, get_timeline_entry, TimelineState};
+pub use usage_anomalies::usage_tracking_middleware;
 pub use user_activity::user_activity_routes;
 pub use user_credits::routes as user_credits_routes;
+pub use verification_rules::verification_rule_routes;
+pub use versioning::{api_version_middleware, ApiVersion, VersioningConfig};
+pub use workspace_buckets::workspace_bucket_routes;
 pub use workspaces::workspace_routes;
 pub use zk_proofs::zk_proof_routes;
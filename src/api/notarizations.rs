@@ -0,0 +1,156 @@
+//! Document notarization. Uploads are raw request bodies; only their hash is
+//! kept. Verification is public and shows when and under which root a document
+//! was anchored, never who submitted it or which item it belongs to.
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::public_items::public_rate_limit_middleware;
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::events_engine::EventsError;
+use crate::notarization_engine::{
+    NotarizationEngine, NotarizationError, NotarizationVerification, NotarizeInput,
+};
+
+/// Mounted at `/api/notarizations`
+pub fn notarization_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", post(notarize_document))
+        .route("/:notarization_id", get(get_notarization))
+        .with_state(app_state)
+}
+
+/// Mounted at `/api/public/notarizations`, rate limited per IP like public items
+pub fn public_notarization_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/verify", post(verify_document))
+        .route("/verify/:document_hash", get(verify_hash))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            public_rate_limit_middleware,
+        ))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> NotarizationEngine<SharedStorage> {
+    NotarizationEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn notarization_error_response(e: NotarizationError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        NotarizationError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        NotarizationError::NotFound(_) => StatusCode::NOT_FOUND,
+        NotarizationError::EventsError(EventsError::StorageError(_))
+        | NotarizationError::StorageError(_)
+        | NotarizationError::AnchoringError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        NotarizationError::EventsError(_) => StatusCode::BAD_REQUEST,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// Notarize the request body. `?dfid=` links it to an item, `?file_name=` is
+/// kept for the submitter; the content type is taken from the header unless
+/// given as `?content_type=`.
+async fn notarize_document(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Query(mut input): Query<NotarizeInput>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    if input.content_type.is_none() {
+        input.content_type = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+    }
+
+    let mut events = app_state.events_engine.write().await;
+    let notarization = engine(&app_state)
+        .notarize(&mut events, &body, input, &user_id, Utc::now())
+        .map_err(notarization_error_response)?;
+    drop(events);
+
+    tracing::info!(
+        "📜 Document {} notarized by {} ({} bytes)",
+        notarization.document_hash,
+        user_id,
+        notarization.size
+    );
+    Ok(Json(json!({
+        "success": true,
+        "notarization": notarization,
+        "verify_url": format!("/api/public/notarizations/verify/{}", notarization.document_hash)
+    })))
+}
+
+async fn get_notarization(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(notarization_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let notarization = engine(&app_state)
+        .get_notarization(&notarization_id)
+        .map_err(notarization_error_response)?;
+    // Other users' notarizations are only visible through verification
+    if notarization.submitted_by != user_id {
+        return Err(notarization_error_response(NotarizationError::NotFound(
+            format!("Notarization {notarization_id}"),
+        )));
+    }
+
+    Ok(Json(json!({
+        "success": true,
+        "notarization": notarization
+    })))
+}
+
+async fn verify_document(
+    State(app_state): State<Arc<AppState>>,
+    body: Bytes,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let verification = engine(&app_state)
+        .verify_document(&body)
+        .map_err(notarization_error_response)?;
+    Ok(Json(public_verification_json(&verification)))
+}
+
+async fn verify_hash(
+    State(app_state): State<Arc<AppState>>,
+    Path(document_hash): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let verification = engine(&app_state)
+        .verify_hash(&document_hash)
+        .map_err(notarization_error_response)?;
+    Ok(Json(public_verification_json(&verification)))
+}
+
+fn public_verification_json(verification: &NotarizationVerification) -> Value {
+    json!({
+        "document_hash": verification.document_hash,
+        "notarized": !verification.matches.is_empty(),
+        "anchored": verification.anchored,
+        "notarizations": verification.matches.iter().map(|m| json!({
+            "notarization_id": m.notarization.notarization_id,
+            "status": m.notarization.status,
+            "submitted_at": m.notarization.submitted_at,
+            "anchored_at": m.notarization.anchored_at,
+            "proof": m.notarization.proof,
+            "proof_valid": m.proof_valid,
+            "merkle_root": m.batch.as_ref().map(|b| &b.merkle_root),
+            "locations": m.batch.as_ref().map(|b| &b.locations),
+        })).collect::<Vec<_>>()
+    })
+}
@@ -0,0 +1,196 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, put},
+    Extension, Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::auth::Claims;
+use crate::api::shared_state::AppState;
+use crate::auth_middleware::AuthenticatedUser;
+use crate::organization_engine::{
+    OrganizationEngine, OrganizationError, OrganizationProfileUpdate, OrganizationSearchQuery,
+};
+use crate::storage::StorageBackend;
+use crate::types::OrganizationKind;
+
+#[derive(Debug, Deserialize)]
+pub struct DirectorySearchParams {
+    pub q: Option<String>,
+    pub org_type: Option<OrganizationKind>,
+    pub region: Option<String>,
+    pub certification: Option<String>,
+    pub limit: Option<usize>,
+}
+
+pub fn organization_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/profiles", get(search_profiles))
+        .route(
+            "/profiles/me",
+            put(upsert_my_profile).delete(delete_my_profile),
+        )
+        .route("/profiles/:user_id", get(get_profile))
+        .route("/circuits/:circuit_id/members", get(get_circuit_members))
+        .with_state(app_state)
+}
+
+fn organization_error_response(e: OrganizationError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        OrganizationError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        OrganizationError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        OrganizationError::NotFound(_) => StatusCode::NOT_FOUND,
+        OrganizationError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// Resolve the caller's workspace from the JWT, falling back to the stored account
+fn resolve_workspace(
+    app_state: &AppState,
+    user_id: &str,
+    claims: Option<&Claims>,
+) -> Result<Option<String>, (StatusCode, Json<Value>)> {
+    if let Some(workspace_id) = claims.and_then(|c| c.workspace_id.clone()) {
+        return Ok(Some(workspace_id));
+    }
+
+    let account = app_state
+        .shared_storage
+        .get_user_account(user_id)
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Failed to load user account: {}", e)})),
+            )
+        })?;
+
+    Ok(account.and_then(|a| a.workspace_id))
+}
+
+async fn search_profiles(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    claims: Option<Extension<Claims>>,
+    Query(params): Query<DirectorySearchParams>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let workspace_id = resolve_workspace(&app_state, &user_id, claims.as_deref())?;
+    let engine = OrganizationEngine::new(Arc::clone(&app_state.shared_storage));
+
+    let query = OrganizationSearchQuery {
+        workspace_id,
+        text: params.q,
+        org_type: params.org_type,
+        region: params.region,
+        certification: params.certification,
+        limit: params.limit,
+    };
+
+    let profiles = engine.search(&query).map_err(organization_error_response)?;
+    let results: Vec<Value> = profiles
+        .iter()
+        .map(|p| json!({"profile": p, "summary": p.summary()}))
+        .collect();
+
+    Ok(Json(json!({
+        "success": true,
+        "count": results.len(),
+        "profiles": results
+    })))
+}
+
+async fn upsert_my_profile(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    claims: Option<Extension<Claims>>,
+    Json(payload): Json<OrganizationProfileUpdate>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let workspace_id = resolve_workspace(&app_state, &user_id, claims.as_deref())?;
+    let engine = OrganizationEngine::new(Arc::clone(&app_state.shared_storage));
+
+    let profile = engine
+        .upsert_profile(&user_id, workspace_id, payload)
+        .map_err(organization_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "summary": profile.summary(),
+        "profile": profile
+    })))
+}
+
+async fn delete_my_profile(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = OrganizationEngine::new(Arc::clone(&app_state.shared_storage));
+    engine
+        .delete_profile(&user_id)
+        .map_err(organization_error_response)?;
+
+    Ok(Json(json!({"success": true})))
+}
+
+async fn get_profile(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(requester_id): AuthenticatedUser,
+    claims: Option<Extension<Claims>>,
+    Path(user_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = OrganizationEngine::new(Arc::clone(&app_state.shared_storage));
+    let profile = engine
+        .get_profile(&user_id)
+        .map_err(organization_error_response)?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "Profile not found"})),
+            )
+        })?;
+
+    // Profiles are only visible inside the owner's workspace
+    if requester_id != user_id {
+        let workspace_id = resolve_workspace(&app_state, &requester_id, claims.as_deref())?;
+        if workspace_id.is_none() || profile.workspace_id != workspace_id {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "Profile not found"})),
+            ));
+        }
+    }
+
+    Ok(Json(json!({
+        "success": true,
+        "summary": profile.summary(),
+        "profile": profile
+    })))
+}
+
+async fn get_circuit_members(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(requester_id): AuthenticatedUser,
+    Path(circuit_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuit_id = Uuid::parse_str(&circuit_id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Invalid circuit ID format"})),
+        )
+    })?;
+
+    let engine = OrganizationEngine::new(Arc::clone(&app_state.shared_storage));
+    let members = engine
+        .get_circuit_member_profiles(&circuit_id, &requester_id)
+        .map_err(organization_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "circuit_id": circuit_id,
+        "members": members
+    })))
+}
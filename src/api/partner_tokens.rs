@@ -0,0 +1,259 @@
+//! Circuit-scoped partner tokens: minted and audited by the circuit owner, and
+//! used by external partners to read the circuit's items without an account.
+//!
+//! Partners send the token as `Authorization: Bearer dfp_…` or
+//! `X-Partner-Token`; the partner API is read-only, rate limited per client IP,
+//! and every request is logged against the token.
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    middleware,
+    response::Json,
+    routing::{delete, get},
+    Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::dto::{EventDto, ItemDto};
+use crate::api::public_items::{public_rate_limit_middleware, ClientIp};
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::partner_token_engine::{MintPartnerTokenInput, PartnerTokenEngine, PartnerTokenError};
+use crate::types::{Circuit, PartnerToken};
+
+const PARTNER_TOKEN_HEADER: &str = "x-partner-token";
+
+/// Owner endpoints, mounted at `/api/partner-tokens`
+pub fn partner_token_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/circuits/:circuit_id/tokens",
+            get(list_tokens).post(mint_token),
+        )
+        .route(
+            "/circuits/:circuit_id/tokens/:token_id",
+            delete(revoke_token),
+        )
+        .route(
+            "/circuits/:circuit_id/tokens/:token_id/usage",
+            get(get_token_usage),
+        )
+        .with_state(app_state)
+}
+
+/// Partner API, mounted at `/api/partner` outside JWT authentication
+pub fn partner_access_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/circuit", get(get_partner_circuit))
+        .route("/items", get(list_partner_items))
+        .route("/items/:dfid", get(get_partner_item))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            public_rate_limit_middleware,
+        ))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> PartnerTokenEngine<SharedStorage> {
+    PartnerTokenEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn partner_token_error_response(e: PartnerTokenError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        PartnerTokenError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        PartnerTokenError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        PartnerTokenError::NotFound(_) => StatusCode::NOT_FOUND,
+        PartnerTokenError::InvalidToken => StatusCode::UNAUTHORIZED,
+        PartnerTokenError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn mint_token(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+    Json(input): Json<MintPartnerTokenInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let (token, secret) = engine(&app_state)
+        .mint(&circuit_id, input, &user_id, Utc::now())
+        .map_err(partner_token_error_response)?;
+
+    tracing::info!(
+        "🤝 Partner token {} for {} minted on circuit {} by {}",
+        token.token_prefix,
+        token.partner_name,
+        circuit_id,
+        user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "token": secret,
+        "partner_token": token,
+        "warning": "Store this token securely. It will not be shown again."
+    })))
+}
+
+async fn list_tokens(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let tokens = engine(&app_state)
+        .list(&circuit_id, &user_id)
+        .map_err(partner_token_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": tokens.len(),
+        "partner_tokens": tokens
+    })))
+}
+
+async fn revoke_token(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path((circuit_id, token_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let token = engine(&app_state)
+        .revoke(&circuit_id, &token_id, &user_id, Utc::now())
+        .map_err(partner_token_error_response)?;
+
+    tracing::info!(
+        "🤝 Partner token {} on circuit {} revoked by {}",
+        token.token_prefix,
+        circuit_id,
+        user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "partner_token": token
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UsageQuery {
+    pub limit: Option<usize>,
+}
+
+async fn get_token_usage(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path((circuit_id, token_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<UsageQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let usage = engine(&app_state)
+        .usage(&circuit_id, &token_id, &user_id, query.limit)
+        .map_err(partner_token_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": usage.len(),
+        "usage": usage
+    })))
+}
+
+fn token_from_headers(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(PARTNER_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| {
+            headers
+                .get("authorization")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+        })
+        .map(str::trim)
+}
+
+/// Authenticate the partner and log the request against its token
+fn authorize_partner(
+    app_state: &AppState,
+    headers: &HeaderMap,
+    client_ip: ClientIp,
+    action: &str,
+    dfid: Option<&str>,
+) -> Result<(PartnerToken, Circuit), (StatusCode, Json<Value>)> {
+    let secret = token_from_headers(headers)
+        .ok_or_else(|| partner_token_error_response(PartnerTokenError::InvalidToken))?;
+    let engine = engine(app_state);
+    let now = Utc::now();
+    let (token, circuit) = engine
+        .authenticate(secret, now)
+        .map_err(partner_token_error_response)?;
+
+    if let Err(e) = engine.record_use(&token, action, dfid, Some(client_ip.0.to_string()), now) {
+        tracing::warn!(
+            "Failed to log use of partner token {}: {}",
+            token.token_prefix,
+            e
+        );
+    }
+    Ok((token, circuit))
+}
+
+async fn get_partner_circuit(
+    State(app_state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Extension(client_ip): Extension<ClientIp>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let (token, circuit) = authorize_partner(&app_state, &headers, client_ip, "get_circuit", None)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "circuit": {
+            "circuit_id": circuit.circuit_id,
+            "name": circuit.name,
+            "description": circuit.description,
+        },
+        "partner_name": token.partner_name,
+        "expires_at": token.expires_at
+    })))
+}
+
+async fn list_partner_items(
+    State(app_state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Extension(client_ip): Extension<ClientIp>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let (token, _) = authorize_partner(&app_state, &headers, client_ip, "list_items", None)?;
+    let items = engine(&app_state)
+        .items(&token)
+        .map_err(partner_token_error_response)?;
+    let items: Vec<Value> = items
+        .iter()
+        .map(|item| json!({"dfid": item.dfid, "pushed_at": item.pushed_at}))
+        .collect();
+
+    Ok(Json(json!({
+        "success": true,
+        "circuit_id": token.circuit_id,
+        "count": items.len(),
+        "items": items
+    })))
+}
+
+async fn get_partner_item(
+    State(app_state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Extension(client_ip): Extension<ClientIp>,
+    Path(dfid): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let (token, _) = authorize_partner(&app_state, &headers, client_ip, "get_item", Some(&dfid))?;
+    let (item, events) = engine(&app_state)
+        .item(&token, &dfid)
+        .map_err(partner_token_error_response)?;
+
+    let events: Vec<EventDto> = events.into_iter().map(EventDto::from).collect();
+
+    Ok(Json(json!({
+        "success": true,
+        "item": ItemDto::from(item),
+        "events": events
+    })))
+}
@@ -0,0 +1,210 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::shared_state::AppState;
+use crate::auth_middleware::AuthenticatedUser;
+use crate::preview_engine::{PreviewEngine, PreviewError};
+use crate::types::Circuit;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CreatePreviewRequest {
+    pub namespace: Option<String>,
+    pub circuit_ids: Option<Vec<Uuid>>, // Defaults to every circuit the caller owns
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PromotePreviewRequest {
+    #[serde(default)]
+    pub force: bool,
+}
+
+pub fn preview_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(list_previews).post(create_preview))
+        .route("/:preview_id", get(get_preview))
+        .route("/:preview_id/diff", get(diff_preview))
+        .route("/:preview_id/promote", post(promote_preview))
+        .route("/:preview_id/discard", post(discard_preview))
+        .with_state(app_state)
+}
+
+fn preview_error_response(e: PreviewError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        PreviewError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        PreviewError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        PreviewError::NotFound(_) => StatusCode::NOT_FOUND,
+        PreviewError::Conflict(_) => StatusCode::CONFLICT,
+        PreviewError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+fn parse_preview_id(preview_id: &str) -> Result<Uuid, (StatusCode, Json<Value>)> {
+    Uuid::parse_str(preview_id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Invalid preview ID format"})),
+        )
+    })
+}
+
+/// Mirror circuit changes to PostgreSQL so previews survive restarts
+async fn persist_circuits(app_state: &AppState, circuits: &[Circuit]) {
+    let pg_lock = app_state.postgres_persistence.read().await;
+    if let Some(pg) = &*pg_lock {
+        for circuit in circuits {
+            if let Err(e) = pg.persist_circuit(circuit).await {
+                tracing::warn!(
+                    "Failed to persist circuit {} to PostgreSQL: {}",
+                    circuit.circuit_id,
+                    e
+                );
+            }
+        }
+    }
+}
+
+fn load_circuits(app_state: &AppState, ids: impl Iterator<Item = Uuid>) -> Vec<Circuit> {
+    use crate::storage::StorageBackend;
+    ids.filter_map(|id| app_state.shared_storage.get_circuit(&id).ok().flatten())
+        .collect()
+}
+
+async fn create_preview(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    payload: Option<Json<CreatePreviewRequest>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let Json(request) = payload.unwrap_or_default();
+    let engine = PreviewEngine::new(Arc::clone(&app_state.shared_storage));
+
+    let preview = engine
+        .create_preview(&user_id, request.namespace, request.circuit_ids)
+        .map_err(preview_error_response)?;
+
+    let clones = load_circuits(
+        &app_state,
+        preview.circuits.iter().map(|c| c.preview_circuit_id),
+    );
+    persist_circuits(&app_state, &clones).await;
+
+    Ok(Json(json!({
+        "success": true,
+        "preview": preview
+    })))
+}
+
+async fn list_previews(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = PreviewEngine::new(Arc::clone(&app_state.shared_storage));
+    let previews = engine
+        .list_previews(&user_id)
+        .map_err(preview_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": previews.len(),
+        "previews": previews
+    })))
+}
+
+async fn get_preview(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(preview_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let preview_id = parse_preview_id(&preview_id)?;
+    let engine = PreviewEngine::new(Arc::clone(&app_state.shared_storage));
+    let preview = engine
+        .get_preview(&preview_id, &user_id)
+        .map_err(preview_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "preview": preview
+    })))
+}
+
+async fn diff_preview(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(preview_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let preview_id = parse_preview_id(&preview_id)?;
+    let engine = PreviewEngine::new(Arc::clone(&app_state.shared_storage));
+    let diff = engine
+        .diff_preview(&preview_id, &user_id)
+        .map_err(preview_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "change_count": diff.changes.len(),
+        "diff": diff
+    })))
+}
+
+async fn promote_preview(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(preview_id): Path<String>,
+    payload: Option<Json<PromotePreviewRequest>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let preview_id = parse_preview_id(&preview_id)?;
+    let Json(request) = payload.unwrap_or_default();
+    let engine = PreviewEngine::new(Arc::clone(&app_state.shared_storage));
+
+    let (diff, updated) = engine
+        .promote_preview(&preview_id, &user_id, request.force)
+        .map_err(preview_error_response)?;
+    let preview = engine
+        .get_preview(&preview_id, &user_id)
+        .map_err(preview_error_response)?;
+
+    let mut changed = updated;
+    changed.extend(load_circuits(
+        &app_state,
+        preview.circuits.iter().map(|c| c.preview_circuit_id),
+    ));
+    persist_circuits(&app_state, &changed).await;
+
+    Ok(Json(json!({
+        "success": true,
+        "applied_changes": diff.changes.len(),
+        "diff": diff,
+        "preview": preview
+    })))
+}
+
+async fn discard_preview(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(preview_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let preview_id = parse_preview_id(&preview_id)?;
+    let engine = PreviewEngine::new(Arc::clone(&app_state.shared_storage));
+    let preview = engine
+        .discard_preview(&preview_id, &user_id)
+        .map_err(preview_error_response)?;
+
+    let clones = load_circuits(
+        &app_state,
+        preview.circuits.iter().map(|c| c.preview_circuit_id),
+    );
+    persist_circuits(&app_state, &clones).await;
+
+    Ok(Json(json!({
+        "success": true,
+        "preview": preview
+    })))
+}
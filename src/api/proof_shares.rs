@@ -0,0 +1,146 @@
+//! Share links to ZK proofs. Provers create and revoke links under
+//! `/api/proofs/:proof_id/shares`; anyone holding a link opens it under the
+//! public `/api/public/proofs`.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    middleware,
+    response::Json,
+    routing::{delete, get},
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::public_items::public_rate_limit_middleware;
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::proof_share_engine::{
+    share_link_key, CreateProofShareInput, ProofShareEngine, ProofShareError,
+};
+use crate::types::ProofShareLink;
+use crate::zk_proof_engine::ZkProofError;
+
+/// Merged into `/api/proofs`
+pub fn proof_share_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/:proof_id/shares",
+            get(list_proof_shares).post(create_proof_share),
+        )
+        .route("/shares/:share_id", delete(revoke_proof_share))
+}
+
+/// Mounted at `/api/public/proofs`, rate limited per IP like public items
+pub fn public_proof_share_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:token", get(open_proof_share))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            public_rate_limit_middleware,
+        ))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> ProofShareEngine<SharedStorage> {
+    ProofShareEngine::new(
+        Arc::clone(&app_state.shared_storage),
+        share_link_key(&app_state.jwt_secret),
+    )
+}
+
+fn proof_share_error_response(e: ProofShareError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        ProofShareError::ValidationError(_)
+        | ProofShareError::ZkProofError(ZkProofError::InvalidInput(_)) => StatusCode::BAD_REQUEST,
+        ProofShareError::NotFound(_) | ProofShareError::InvalidToken => StatusCode::NOT_FOUND,
+        ProofShareError::StorageError(_) | ProofShareError::ZkProofError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+fn share_url(token: &str) -> String {
+    format!("/api/public/proofs/{token}")
+}
+
+/// The prover's view of a link, with its URL
+fn share_json(engine: &ProofShareEngine<SharedStorage>, link: &ProofShareLink) -> Value {
+    let mut value = json!(link);
+    value["share_url"] = json!(share_url(&engine.token(link)));
+    value
+}
+
+async fn create_proof_share(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(proof_id): Path<Uuid>,
+    Json(input): Json<CreateProofShareInput>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    let engine = engine(&app_state);
+    let (token, link) = engine
+        .create(&user_id, &proof_id, input, Utc::now())
+        .map_err(proof_share_error_response)?;
+
+    tracing::info!("🔗 {} shared proof {}", user_id, proof_id);
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "success": true,
+            "token": token,
+            "share_url": share_url(&token),
+            "share": link
+        })),
+    ))
+}
+
+async fn list_proof_shares(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(proof_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = engine(&app_state);
+    let links = engine
+        .list(&user_id, &proof_id)
+        .map_err(proof_share_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": links.len(),
+        "shares": links.iter().map(|link| share_json(&engine, link)).collect::<Vec<_>>()
+    })))
+}
+
+async fn revoke_proof_share(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(share_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let link = engine(&app_state)
+        .revoke(&user_id, &share_id, Utc::now())
+        .map_err(proof_share_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "share": link
+    })))
+}
+
+/// The proof, its public inputs and the item's public timeline behind a link
+async fn open_proof_share(
+    State(app_state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let shared = engine(&app_state)
+        .open(&token, Utc::now())
+        .map_err(proof_share_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "shared_proof": shared
+    })))
+}
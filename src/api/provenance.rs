@@ -0,0 +1,110 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+use crate::api::shared_state::AppState;
+use crate::auth_middleware::AuthenticatedUser;
+use crate::postgres_storage_with_cache::PostgresStorageWithCache;
+use crate::provenance_engine::{
+    manifest_signing_key, verify_manifest, ProvenanceEngine, ProvenanceError, ProvenanceManifest,
+};
+
+// Type alias matching SharedStorage from shared_state
+type SharedStorage = Arc<Mutex<PostgresStorageWithCache>>;
+
+pub fn provenance_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/signing-key", get(get_signing_key))
+        .route("/verify", post(verify))
+        .route("/:dfid/manifest", get(get_manifest))
+        .route("/:dfid/timeline", get(get_timeline))
+        .with_state(app_state)
+}
+
+fn provenance_error_response(e: ProvenanceError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        ProvenanceError::NotFound(_) => StatusCode::NOT_FOUND,
+        ProvenanceError::InvalidManifest(_) => StatusCode::BAD_REQUEST,
+        ProvenanceError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        ProvenanceError::StorageError(_) | ProvenanceError::SigningKeyError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+fn engine(app_state: &AppState) -> ProvenanceEngine<SharedStorage> {
+    ProvenanceEngine::new(
+        Arc::clone(&app_state.shared_storage),
+        manifest_signing_key(&app_state.jwt_secret),
+    )
+}
+
+/// Public key auditors use to verify archived manifests
+async fn get_signing_key(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(_user_id): AuthenticatedUser,
+) -> Json<Value> {
+    Json(json!({
+        "success": true,
+        "algorithm": "ed25519",
+        "public_key": engine(&app_state).signer_public_key()
+    }))
+}
+
+async fn get_manifest(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(dfid): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let manifest = engine(&app_state)
+        .build_manifest(&dfid, &user_id)
+        .map_err(provenance_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "entry_count": manifest.entries.len(),
+        "manifest": manifest
+    })))
+}
+
+/// Events of the item and of every item merged into it, interleaved, each
+/// attributed to the DFID it was recorded on
+async fn get_timeline(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(dfid): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let timeline = engine(&app_state)
+        .timeline(&dfid, &user_id)
+        .map_err(provenance_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "event_count": timeline.events.len(),
+        "timeline": timeline
+    })))
+}
+
+/// Verify a manifest's integrity and whether this server signed it
+async fn verify(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(_user_id): AuthenticatedUser,
+    Json(manifest): Json<ProvenanceManifest>,
+) -> Json<Value> {
+    let server_key = engine(&app_state).signer_public_key();
+    let result = verify_manifest(&manifest, None);
+
+    Json(json!({
+        "success": true,
+        "valid": result.is_ok(),
+        "signed_by_this_server": manifest.signer_public_key.eq_ignore_ascii_case(&server_key),
+        "error": result.err().map(|e| e.to_string())
+    }))
+}
@@ -0,0 +1,462 @@
+//! Unauthenticated traceability view of an item, e.g. behind a QR code on a tag.
+//!
+//! An item is visible once it is in `published_items` of a publicly accessible
+//! circuit (protected circuits need `?password=`). Only `Public`, non-local events
+//! are returned; encrypted ones only when a granting circuit sets
+//! `show_encrypted_events`, and then without their metadata. Requests are rate
+//! limited per client IP: the connecting address, or the forwarded one when the
+//! connection comes from a proxy listed in `TRUSTED_PROXY_ADDRESSES`. Events of items merged into this one are included,
+//! each attributed to the DFID it was recorded on.
+
+use axum::{
+    extract::{ConnectInfo, Path, Query, Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use crate::api::shared_state::AppState;
+use crate::merge_lineage::aggregated_timeline;
+use crate::rate_limiter::RateLimitConfig;
+use crate::storage::StorageBackend;
+use crate::storage_helpers::{with_storage, StorageLockError};
+use crate::types::{Circuit, Event, EventVisibility, Item, PublicAccessMode};
+
+const DEFAULT_REQUESTS_PER_MINUTE: u32 = 30;
+const DEFAULT_REQUESTS_PER_HOUR: u32 = 600;
+
+#[derive(Debug, Deserialize)]
+pub struct PublicItemQuery {
+    /// Access password of a protected circuit
+    pub password: Option<String>,
+}
+
+/// Mounted at `/api/public/items`
+pub fn public_item_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:dfid", get(get_public_item))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            public_rate_limit_middleware,
+        ))
+        .with_state(app_state)
+}
+
+/// Per-IP limits, overridable with `PUBLIC_ITEM_RATE_LIMIT_PER_MINUTE` and
+/// `PUBLIC_ITEM_RATE_LIMIT_PER_HOUR`
+fn rate_limit_config() -> RateLimitConfig {
+    let env_limit = |name: &str, default: u32| {
+        std::env::var(name)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default)
+    };
+    RateLimitConfig::new(env_limit(
+        "PUBLIC_ITEM_RATE_LIMIT_PER_HOUR",
+        DEFAULT_REQUESTS_PER_HOUR,
+    ))
+    .with_minute_limit(env_limit(
+        "PUBLIC_ITEM_RATE_LIMIT_PER_MINUTE",
+        DEFAULT_REQUESTS_PER_MINUTE,
+    ))
+}
+
+/// Client address resolved by `public_rate_limit_middleware`, available to
+/// handlers behind it as a request extension
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ClientIp(pub IpAddr);
+
+/// Proxies whose `X-Forwarded-For` is believed, from the comma-separated
+/// `TRUSTED_PROXY_ADDRESSES`; none by default
+fn trusted_proxies() -> Vec<IpAddr> {
+    std::env::var("TRUSTED_PROXY_ADDRESSES")
+        .map(|addresses| {
+            addresses
+                .split(',')
+                .filter_map(|address| address.trim().parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The connecting address, unless it is a trusted proxy: then the nearest
+/// forwarded hop that is not one. Hops further left were supplied by the
+/// client and are never used.
+fn client_ip(peer: IpAddr, headers: &HeaderMap, trusted: &[IpAddr]) -> IpAddr {
+    if !trusted.contains(&peer) {
+        return peer;
+    }
+    let hops: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .collect();
+    for hop in hops.into_iter().rev() {
+        match hop.trim().parse::<IpAddr>() {
+            Ok(ip) if trusted.contains(&ip) => continue,
+            Ok(ip) => return ip,
+            Err(_) => break,
+        }
+    }
+    peer
+}
+
+fn rate_limiter_unavailable(e: impl std::fmt::Display) -> Response {
+    tracing::error!("Public rate limiter unavailable: {}", e);
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({
+            "error": "rate_limiter_unavailable",
+            "message": "Please retry later"
+        })),
+    )
+        .into_response()
+}
+
+/// Requests are refused while the limiter cannot be consulted. Needs the
+/// server to be started with `ConnectInfo<SocketAddr>`.
+pub(crate) async fn public_rate_limit_middleware(
+    State(app_state): State<Arc<AppState>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let client_ip = client_ip(peer.ip(), &headers, &trusted_proxies());
+    let limiter = &app_state.public_rate_limiter;
+    let result = match limiter.check_rate_limit(client_ip, &rate_limit_config()) {
+        Ok(result) => result,
+        Err(e) => return rate_limiter_unavailable(e),
+    };
+
+    if !result.allowed {
+        let retry_after = result.retry_after_seconds.unwrap_or(60);
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [
+                ("Retry-After", retry_after.to_string()),
+                ("X-RateLimit-Limit", result.limit.to_string()),
+                ("X-RateLimit-Remaining", "0".to_string()),
+            ],
+            Json(json!({
+                "error": "rate_limit_exceeded",
+                "message": "Too many requests, please retry later",
+                "retry_after": retry_after
+            })),
+        )
+            .into_response();
+    }
+    if let Err(e) = limiter.record_request(client_ip) {
+        return rate_limiter_unavailable(e);
+    }
+
+    request.extensions_mut().insert(ClientIp(client_ip));
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert("X-RateLimit-Limit", HeaderValue::from(result.limit));
+    headers.insert(
+        "X-RateLimit-Remaining",
+        HeaderValue::from(result.remaining.saturating_sub(1)),
+    );
+    response
+}
+
+#[derive(Debug, PartialEq)]
+enum PublicItemDenied {
+    /// Not published anywhere the caller can see
+    NotPublished,
+    /// Published only in protected circuits and no matching password was given
+    PasswordRequired,
+}
+
+/// Circuits that publish `dfid` to this caller
+fn granting_circuits<'a>(
+    circuits: &'a [Circuit],
+    dfid: &str,
+    password: Option<&str>,
+) -> Result<Vec<&'a Circuit>, PublicItemDenied> {
+    let mut password_required = false;
+    let granting: Vec<&Circuit> = circuits
+        .iter()
+        .filter(|circuit| circuit.is_publicly_accessible())
+        .filter(|circuit| {
+            let Some(settings) = &circuit.public_settings else {
+                return false;
+            };
+            if !settings.published_items.iter().any(|d| d == dfid) {
+                return false;
+            }
+            match settings.access_mode {
+                PublicAccessMode::Protected => {
+                    let granted = settings.access_password.is_some()
+                        && settings.access_password.as_deref() == password;
+                    password_required |= !granted;
+                    granted
+                }
+                PublicAccessMode::Public | PublicAccessMode::Scheduled => true,
+            }
+        })
+        .collect();
+
+    match (granting.is_empty(), password_required) {
+        (false, _) => Ok(granting),
+        (true, true) => Err(PublicItemDenied::PasswordRequired),
+        (true, false) => Err(PublicItemDenied::NotPublished),
+    }
+}
+
+fn public_event_json(event: &Event) -> Value {
+    json!({
+        "event_id": event.event_id,
+        "source_dfid": event.dfid,
+        "event_type": event.event_type.to_string(),
+        "timestamp": event.timestamp,
+        "occurred_at": event.occurred_at,
+        "is_encrypted": event.is_encrypted,
+        "metadata": if event.is_encrypted { Value::Null } else { json!(event.metadata) },
+        "content_hash": event.content_hash,
+    })
+}
+
+fn public_item_view(item: &Item, granting: &[&Circuit], events: &[Event]) -> Value {
+    let show_encrypted = granting.iter().any(|circuit| {
+        circuit
+            .public_settings
+            .as_ref()
+            .is_some_and(|s| s.show_encrypted_events)
+    });
+    let mut events: Vec<&Event> = events
+        .iter()
+        .filter(|event| {
+            matches!(event.visibility, EventVisibility::Public)
+                && !event.is_local
+                && (!event.is_encrypted || show_encrypted)
+        })
+        .collect();
+    events.sort_by_key(|event| event.occurred_at.unwrap_or(event.timestamp));
+    let mut merged_items: Vec<&str> = events
+        .iter()
+        .map(|event| event.dfid.as_str())
+        .filter(|dfid| *dfid != item.dfid)
+        .collect();
+    merged_items.sort_unstable();
+    merged_items.dedup();
+
+    let circuits: Vec<Value> = granting
+        .iter()
+        .filter_map(|circuit| circuit.get_public_info())
+        .map(|info| {
+            json!({
+                "circuit_id": info.circuit_id,
+                "public_name": info.public_name,
+                "public_description": info.public_description,
+                "logo_url": info.logo_url,
+                "primary_color": info.primary_color,
+                "secondary_color": info.secondary_color,
+                "tagline": info.tagline,
+                "footer_text": info.footer_text,
+            })
+        })
+        .collect();
+
+    json!({
+        "dfid": item.dfid,
+        "status": item.status,
+        "identifiers": item
+            .identifiers
+            .iter()
+            .map(|id| json!({"key": id.key, "value": id.value}))
+            .collect::<Vec<_>>(),
+        "created_at": item.creation_timestamp,
+        "circuits": circuits,
+        "merged_items": merged_items,
+        "event_count": events.len(),
+        "events": events.into_iter().map(public_event_json).collect::<Vec<_>>(),
+    })
+}
+
+async fn get_public_item(
+    State(app_state): State<Arc<AppState>>,
+    Path(dfid): Path<String>,
+    Query(query): Query<PublicItemQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let (item, circuits, events) = with_storage(
+        &app_state.shared_storage,
+        "public_items::get_public_item",
+        |storage| {
+            let Some(item) = storage.get_item_by_dfid(&dfid)? else {
+                return Ok((None, Vec::new(), Vec::new()));
+            };
+            let circuits = storage.list_circuits()?;
+            let events: Vec<Event> = aggregated_timeline(storage, &dfid)?
+                .events
+                .into_iter()
+                .map(|entry| entry.event)
+                .collect();
+            Ok((Some(item), circuits, events))
+        },
+    )
+    .map_err(|e| match e {
+        StorageLockError::Timeout => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"error": "Storage timeout, please retry"})),
+        ),
+        StorageLockError::Other(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("Failed to load item: {}", err)})),
+        ),
+    })?;
+
+    // Unpublished and unknown items look the same from outside
+    let not_available = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Item not available publicly"})),
+        )
+    };
+    let item = item.ok_or_else(not_available)?;
+    let granting = match granting_circuits(&circuits, &dfid, query.password.as_deref()) {
+        Ok(granting) => granting,
+        Err(PublicItemDenied::NotPublished) => return Err(not_available()),
+        Err(PublicItemDenied::PasswordRequired) => {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": "Access password required",
+                    "requires_password": true
+                })),
+            ))
+        }
+    };
+
+    Ok(Json(json!({
+        "success": true,
+        "item": public_item_view(&item, &granting, &events)
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EventType, Identifier, PublicSettings};
+
+    fn circuit(mode: PublicAccessMode, published: &[&str], password: Option<&str>) -> Circuit {
+        let mut circuit = Circuit::new(
+            "Nelore Premium".to_string(),
+            "Traceable beef".to_string(),
+            "owner".to_string(),
+        );
+        circuit.permissions.allow_public_visibility = true;
+        circuit.public_settings = Some(PublicSettings {
+            access_mode: mode,
+            scheduled_date: None,
+            access_password: password.map(str::to_string),
+            public_name: None,
+            public_description: None,
+            primary_color: None,
+            secondary_color: None,
+            logo_url: None,
+            tagline: None,
+            footer_text: None,
+            published_items: published.iter().map(|d| d.to_string()).collect(),
+            auto_approve_members: false,
+            auto_publish_pushed_items: false,
+            show_encrypted_events: false,
+            required_event_types: None,
+            data_quality_rules: None,
+            export_permissions: None,
+            public_since: None,
+            listed_in_directory: false,
+            region: None,
+            item_categories: Vec::new(),
+        });
+        circuit
+    }
+
+    #[test]
+    fn test_public_view_respects_publication_password_and_visibility() {
+        let open = circuit(PublicAccessMode::Public, &["DFID-1"], None);
+        let protected = circuit(PublicAccessMode::Protected, &["DFID-2"], Some("s3cret"));
+        let circuits = vec![open, protected];
+
+        assert_eq!(
+            granting_circuits(&circuits, "DFID-1", None).unwrap().len(),
+            1
+        );
+        assert_eq!(
+            granting_circuits(&circuits, "DFID-2", None).unwrap_err(),
+            PublicItemDenied::PasswordRequired
+        );
+        assert_eq!(
+            granting_circuits(&circuits, "DFID-2", Some("wrong")).unwrap_err(),
+            PublicItemDenied::PasswordRequired
+        );
+        assert_eq!(
+            granting_circuits(&circuits, "DFID-2", Some("s3cret"))
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            granting_circuits(&circuits, "DFID-3", None).unwrap_err(),
+            PublicItemDenied::NotPublished
+        );
+
+        let item = Item::new(
+            "DFID-1".to_string(),
+            vec![Identifier::contextual("bovino", "sisbov", "BR001")],
+            uuid::Uuid::new_v4(),
+        );
+        let event = |visibility: EventVisibility, encrypted: bool| {
+            let mut event = Event::new(
+                "DFID-1".to_string(),
+                EventType::Created,
+                "farm".to_string(),
+                visibility,
+            );
+            event.is_encrypted = encrypted;
+            event
+        };
+        let mut local = event(EventVisibility::Public, false);
+        local.is_local = true;
+        let events = vec![
+            event(EventVisibility::Public, false),
+            event(EventVisibility::Private, false),
+            event(EventVisibility::Public, true),
+            local,
+        ];
+        let granting = granting_circuits(&circuits, "DFID-1", None).unwrap();
+        let view = public_item_view(&item, &granting, &events);
+        assert_eq!(view["event_count"], 1);
+        assert_eq!(view["circuits"][0]["public_name"], "Nelore Premium");
+        assert_eq!(view["identifiers"][0]["value"], "BR001");
+    }
+
+    #[test]
+    fn test_client_ip_trusts_forwarded_only_from_proxies() {
+        let proxy: IpAddr = "10.0.0.2".parse().unwrap();
+        let peer: IpAddr = "203.0.113.7".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("1.1.1.1, 198.51.100.4"),
+        );
+
+        // A direct client cannot pick its bucket with a forged header
+        assert_eq!(client_ip(peer, &headers, &[proxy]), peer);
+        // Behind the proxy, the hop it appended is used, not the client-supplied one
+        assert_eq!(
+            client_ip(proxy, &headers, &[proxy]),
+            "198.51.100.4".parse::<IpAddr>().unwrap()
+        );
+        // A proxy that forwarded nothing is keyed on itself
+        assert_eq!(client_ip(proxy, &HeaderMap::new(), &[proxy]), proxy);
+    }
+}
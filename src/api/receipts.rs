@@ -1,5 +1,6 @@
 use axum::{
-    extract::{Path, State},
+    body::Body,
+    extract::{Path, Query, State},
     http::StatusCode,
     response::Json,
     routing::{get, post},
@@ -11,14 +12,53 @@ use serde_json::{json, Value};
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::api::dto::IdentifierDto;
 use crate::api::items::{build_identifiers, IdentifierRequest};
 use crate::api::shared_state::AppState;
+use crate::auth_middleware::AuthenticatedUser;
+use crate::dfid_engine::DfidEngine;
+use crate::hashing::{self, StreamHashError};
+use crate::storage::StorageBackend;
 use crate::storage_helpers::{with_lock_mut, StorageLockError};
+use crate::types::{Identifier, IngestionPriority, Receipt, SubmissionOrigin};
+use crate::verification_engine::VerificationEngine;
+use crate::verification_rules::RuleVerdict;
+
+/// Largest body accepted by the streaming endpoints
+pub const MAX_STREAMED_RECEIPT_BYTES: usize = 16 * 1024 * 1024 * 1024;
 
 #[derive(Debug, Deserialize)]
 pub struct CreateReceiptRequest {
     pub data: String, // Base64 encoded data
     pub identifiers: Vec<IdentifierRequest>,
+    /// `realtime` (default) for live events, `bulk` for historical backfills
+    #[serde(default)]
+    pub priority: IngestionPriority,
+    /// Unix seconds when the data was originally produced, for historical migrations
+    pub occurred_at: Option<i64>,
+    /// Where the data comes from, matched by the workspace's verification rules
+    pub source: Option<String>,
+}
+
+/// A submission to dry-run through verification
+#[derive(Debug, Deserialize)]
+pub struct PreviewReceiptRequest {
+    pub data: String, // Base64 encoded data
+    pub identifiers: Vec<IdentifierRequest>,
+    /// Circuit the data is meant for, whose dedup strategy may differ from the workspace's
+    pub circuit_id: Option<Uuid>,
+    pub source: Option<String>,
+}
+
+/// Query of a streamed receipt; the body is the raw data, hashed as it arrives
+#[derive(Debug, Deserialize)]
+pub struct StreamReceiptQuery {
+    /// JSON array of identifiers, as in `CreateReceiptRequest`
+    pub identifiers: String,
+    #[serde(default)]
+    pub priority: IngestionPriority,
+    pub occurred_at: Option<i64>,
+    pub source: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -27,7 +67,10 @@ pub struct ReceiptResponse {
     pub hash: String,
     pub timestamp: i64,
     pub data_size: usize,
-    pub identifiers: Vec<IdentifierRequest>,
+    pub identifiers: Vec<IdentifierDto>,
+    pub priority: IngestionPriority,
+    /// Declared occurrence time; `None` means it happened when it was recorded (`timestamp`)
+    pub occurred_at: Option<i64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -42,8 +85,11 @@ pub struct VerificationResponse {
 pub fn receipt_routes(app_state: Arc<AppState>) -> Router {
     Router::new()
         .route("/", post(create_receipt))
+        .route("/stream", post(create_streamed_receipt))
+        .route("/preview", post(preview_receipt))
         .route("/:id", get(get_receipt))
         .route("/:id/verify", post(verify_receipt))
+        .route("/:id/verify/stream", post(verify_streamed_receipt))
         .route("/search/identifier", post(search_by_identifier))
         .route("/search/key/:key", get(search_by_key))
         .route("/search/value/:value", get(search_by_value))
@@ -53,6 +99,7 @@ pub fn receipt_routes(app_state: Arc<AppState>) -> Router {
 
 async fn create_receipt(
     State(state): State<Arc<AppState>>,
+    user: Option<AuthenticatedUser>,
     Json(payload): Json<CreateReceiptRequest>,
 ) -> Result<Json<ReceiptResponse>, (StatusCode, Json<Value>)> {
     // Decode base64 data
@@ -80,16 +127,141 @@ async fn create_receipt(
         ));
     }
 
+    let occurred_at = match payload.occurred_at {
+        Some(ts) => Some(chrono::DateTime::from_timestamp(ts, 0).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "Invalid occurred_at timestamp"})),
+            )
+        })?),
+        None => None,
+    };
+
+    let origin = submission_origin(&state, user, payload.source)?;
+    check_verification_rules(&state, &origin, &identifiers, Some(&data))?;
+
     let receipt = with_lock_mut(
         &state.receipt_engine,
         "receipts::create_receipt::process_data",
         |engine| {
             engine
-                .process_data(&data, identifiers.clone())
+                .process_data_from(
+                    &data,
+                    identifiers.clone(),
+                    payload.priority,
+                    occurred_at,
+                    origin.clone(),
+                )
                 .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
         },
     )
-    .map_err(|e| match e {
+    .map_err(process_error_response)?;
+
+    Ok(Json(receipt_response(receipt)))
+}
+
+/// Whether a submission would create an item, enrich one or wait for review,
+/// judged with the caller's workspace settings; nothing is stored
+async fn preview_receipt(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(payload): Json<PreviewReceiptRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let data = general_purpose::STANDARD
+        .decode(&payload.data)
+        .map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "Invalid base64 data"})),
+            )
+        })?;
+    let identifiers = build_identifiers(payload.identifiers).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Invalid identifier payload: {}", e)})),
+        )
+    })?;
+    if identifiers.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "At least one identifier is required"})),
+        ));
+    }
+
+    let internal_error = |e: String| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("Preview failed: {}", e)})),
+        )
+    };
+    let origin = submission_origin(&state, Some(AuthenticatedUser(user_id)), payload.source)?;
+    let preview = VerificationEngine::new(Arc::clone(&state.shared_storage), DfidEngine::new())
+        .with_dedup_scope(origin.workspace_id.as_deref(), payload.circuit_id.as_ref())
+        .and_then(|engine| engine.preview_verification(&data, &identifiers, &origin))
+        .map_err(|e| internal_error(e.to_string()))?;
+
+    Ok(Json(json!({
+        "success": true,
+        "preview": preview
+    })))
+}
+
+/// The declared source and the submitter's workspace, whose verification
+/// rules apply to the submission
+fn submission_origin(
+    state: &AppState,
+    user: Option<AuthenticatedUser>,
+    source: Option<String>,
+) -> Result<SubmissionOrigin, (StatusCode, Json<Value>)> {
+    let workspace_id = match user {
+        Some(AuthenticatedUser(user_id)) => state
+            .shared_storage
+            .get_user_account(&user_id)
+            .map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({"error": format!("Storage error: {}", e)})),
+                )
+            })?
+            .and_then(|account| account.workspace_id),
+        None => None,
+    };
+    Ok(SubmissionOrigin {
+        source: source.filter(|s| !s.trim().is_empty()),
+        workspace_id,
+    })
+}
+
+/// Refuse submissions that break the workspace's verification rules before
+/// anything is stored; rules sending the source to review apply later
+fn check_verification_rules(
+    state: &AppState,
+    origin: &SubmissionOrigin,
+    identifiers: &[Identifier],
+    payload: Option<&[u8]>,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let verdict = VerificationEngine::new(Arc::clone(&state.shared_storage), DfidEngine::new())
+        .check_rules(origin, identifiers, payload)
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Rule check failed: {}", e)})),
+            )
+        })?;
+    match verdict {
+        RuleVerdict::Reject { violations } => Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({
+                "error": "Submission breaks the workspace's verification rules",
+                "violations": violations
+            })),
+        )),
+        RuleVerdict::Accept | RuleVerdict::ManualReview { .. } => Ok(()),
+    }
+}
+
+fn process_error_response(e: StorageLockError) -> (StatusCode, Json<Value>) {
+    match e {
         StorageLockError::Timeout => (
             StatusCode::SERVICE_UNAVAILABLE,
             Json(json!({"error": "Service temporarily unavailable, please retry"})),
@@ -100,6 +272,8 @@ async fn create_receipt(
                     StatusCode::BAD_REQUEST,
                     Json(json!({"error": "At least one identifier is required"})),
                 )
+            } else if msg.contains("Implausible occurred_at") {
+                (StatusCode::BAD_REQUEST, Json(json!({"error": msg})))
             } else {
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
@@ -107,9 +281,11 @@ async fn create_receipt(
                 )
             }
         }
-    })?;
+    }
+}
 
-    let response = ReceiptResponse {
+fn receipt_response(receipt: Receipt) -> ReceiptResponse {
+    ReceiptResponse {
         id: receipt.id.to_string(),
         hash: receipt.hash,
         timestamp: receipt.timestamp.timestamp(),
@@ -117,10 +293,139 @@ async fn create_receipt(
         identifiers: receipt
             .identifiers
             .into_iter()
-            .map(|id| IdentifierRequest::from_identifier(&id))
+            .map(|id| IdentifierDto::from(&id))
             .collect(),
+        priority: receipt.priority,
+        occurred_at: receipt.occurred_at.map(|t| t.timestamp()),
+    }
+}
+
+fn stream_error_response(e: StreamHashError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        StreamHashError::TooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+        StreamHashError::Read(_) => StatusCode::BAD_REQUEST,
     };
-    Ok(Json(response))
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// Receipt for a raw body of any size: the data is hashed as it arrives and
+/// never held in memory
+async fn create_streamed_receipt(
+    State(state): State<Arc<AppState>>,
+    user: Option<AuthenticatedUser>,
+    Query(query): Query<StreamReceiptQuery>,
+    body: Body,
+) -> Result<Json<ReceiptResponse>, (StatusCode, Json<Value>)> {
+    // Everything about the request is checked before the body is read
+    let identifiers = serde_json::from_str::<Vec<IdentifierRequest>>(&query.identifiers)
+        .map_err(|e| e.to_string())
+        .and_then(|identifiers| build_identifiers(identifiers).map_err(|e| e.to_string()))
+        .map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": format!("Invalid identifier payload: {}", e)})),
+            )
+        })?;
+    if identifiers.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "At least one identifier is required"})),
+        ));
+    }
+    let occurred_at = match query.occurred_at {
+        Some(ts) => Some(chrono::DateTime::from_timestamp(ts, 0).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "Invalid occurred_at timestamp"})),
+            )
+        })?),
+        None => None,
+    };
+    // The body is never held, so payload field rules cannot be checked here
+    let origin = submission_origin(&state, user, query.source.clone())?;
+    check_verification_rules(&state, &origin, &identifiers, None)?;
+
+    let digest = hashing::default_algorithm()
+        .hash_stream(body.into_data_stream(), Some(MAX_STREAMED_RECEIPT_BYTES))
+        .await
+        .map_err(stream_error_response)?;
+
+    let receipt = with_lock_mut(
+        &state.receipt_engine,
+        "receipts::create_streamed_receipt::process_digest",
+        |engine| {
+            engine
+                .process_digest_from(
+                    digest.clone(),
+                    identifiers.clone(),
+                    query.priority,
+                    occurred_at,
+                    origin.clone(),
+                )
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
+        },
+    )
+    .map_err(process_error_response)?;
+
+    Ok(Json(receipt_response(receipt)))
+}
+
+/// Verify a raw body of any size against a receipt, hashing it as it arrives
+async fn verify_streamed_receipt(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    body: Body,
+) -> Result<Json<VerificationResponse>, (StatusCode, Json<Value>)> {
+    let lock_error = |e: StorageLockError| match e {
+        StorageLockError::Timeout => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"error": "Service temporarily unavailable, please retry"})),
+        ),
+        StorageLockError::Other(msg) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("Verification error: {}", msg)})),
+        ),
+    };
+    let receipt = with_lock_mut(
+        &state.receipt_engine,
+        "receipts::verify_streamed_receipt::get_receipt",
+        |engine| {
+            engine
+                .get_receipt(&id)
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
+        },
+    )
+    .map_err(lock_error)?
+    .ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Receipt not found"})),
+        )
+    })?;
+
+    // Hashed with the receipt's algorithm so receipts predating a switch still verify
+    let digest = hashing::algorithm_of(&receipt.hash)
+        .hash_stream(body.into_data_stream(), Some(MAX_STREAMED_RECEIPT_BYTES))
+        .await
+        .map_err(stream_error_response)?;
+    let is_valid = with_lock_mut(
+        &state.receipt_engine,
+        "receipts::verify_streamed_receipt::verify_digest",
+        |engine| {
+            engine
+                .verify_digest(&id, &digest)
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
+        },
+    )
+    .map_err(lock_error)?;
+
+    Ok(Json(VerificationResponse {
+        is_valid,
+        receipt_id: id.to_string(),
+        original_hash: receipt.hash,
+        provided_hash: digest.hash,
+        timestamp: receipt.timestamp.timestamp(),
+    }))
 }
 
 async fn get_receipt(
@@ -164,8 +469,10 @@ async fn get_receipt(
                 identifiers: receipt
                     .identifiers
                     .into_iter()
-                    .map(|id| IdentifierRequest::from_identifier(&id))
+                    .map(|id| IdentifierDto::from(&id))
                     .collect(),
+                priority: receipt.priority,
+                occurred_at: receipt.occurred_at.map(|t| t.timestamp()),
             };
             Ok(Json(response))
         }
@@ -232,7 +539,7 @@ async fn verify_receipt(
     match receipt_opt {
         Some(receipt) => {
             // Calculate hash of provided data for comparison
-            let provided_hash = blake3::hash(&data).to_hex().to_string();
+            let provided_hash = crate::hashing::algorithm_of(&receipt.hash).hash(&data);
 
             Ok(Json(VerificationResponse {
                 is_valid,
@@ -290,8 +597,10 @@ async fn search_by_identifier(
             identifiers: receipt
                 .identifiers
                 .into_iter()
-                .map(|id| IdentifierRequest::from_identifier(&id))
+                .map(|id| IdentifierDto::from(&id))
                 .collect(),
+            priority: receipt.priority,
+            occurred_at: receipt.occurred_at.map(|t| t.timestamp()),
         })
         .collect();
     Ok(Json(response))
@@ -331,8 +640,10 @@ async fn search_by_key(
             identifiers: receipt
                 .identifiers
                 .into_iter()
-                .map(|id| IdentifierRequest::from_identifier(&id))
+                .map(|id| IdentifierDto::from(&id))
                 .collect(),
+            priority: receipt.priority,
+            occurred_at: receipt.occurred_at.map(|t| t.timestamp()),
         })
         .collect();
     Ok(Json(response))
@@ -372,8 +683,10 @@ async fn search_by_value(
             identifiers: receipt
                 .identifiers
                 .into_iter()
-                .map(|id| IdentifierRequest::from_identifier(&id))
+                .map(|id| IdentifierDto::from(&id))
                 .collect(),
+            priority: receipt.priority,
+            occurred_at: receipt.occurred_at.map(|t| t.timestamp()),
         })
         .collect();
     Ok(Json(response))
@@ -412,8 +725,10 @@ async fn list_receipts(
             identifiers: receipt
                 .identifiers
                 .into_iter()
-                .map(|id| IdentifierRequest::from_identifier(&id))
+                .map(|id| IdentifierDto::from(&id))
                 .collect(),
+            priority: receipt.priority,
+            occurred_at: receipt.occurred_at.map(|t| t.timestamp()),
         })
         .collect();
     Ok(Json(response))
@@ -0,0 +1,248 @@
+//! Identifier mapping corrections and the re-verification they trigger, under
+//! the admin-guarded `/api/admin/reverification`. A correction queues a job;
+//! the job only proposes merges, which an admin then accepts or rejects.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post, put},
+    Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::items::IdentifierRequest;
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AdminUser;
+use crate::reverification_engine::{
+    MergeProposalFilter, ReverificationEngine, ReverificationError,
+};
+
+pub fn admin_reverification_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/mappings", put(correct_mapping))
+        .route("/jobs", get(list_jobs))
+        .route("/jobs/:job_id", get(get_job))
+        .route("/proposals", get(list_proposals))
+        .route("/proposals/:proposal_id", get(get_proposal))
+        .route("/proposals/:proposal_id/accept", post(accept_proposal))
+        .route("/proposals/:proposal_id/reject", post(reject_proposal))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CorrectMappingRequest {
+    pub identifier: IdentifierRequest,
+    /// Item the identifier should map to
+    pub dfid: String,
+}
+
+fn engine(app_state: &AppState) -> ReverificationEngine<SharedStorage> {
+    ReverificationEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn reverification_error_response(e: ReverificationError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        ReverificationError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        ReverificationError::NotFound(_) => StatusCode::NOT_FOUND,
+        ReverificationError::Conflict(_) => StatusCode::CONFLICT,
+        ReverificationError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// Repoint an identifier and queue re-verification of what it touched; poll
+/// `GET /jobs/:job_id` for the proposals it produced
+async fn correct_mapping(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Json(request): Json<CorrectMappingRequest>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    let identifier = request
+        .identifier
+        .into_identifier()
+        .map_err(|e| reverification_error_response(ReverificationError::ValidationError(e)))?;
+    let engine = engine(&app_state);
+    let job = engine
+        .correct_mapping(&admin_user_id, identifier, &request.dfid, Utc::now())
+        .map_err(reverification_error_response)?;
+    engine.spawn_job(job.job_id);
+
+    tracing::info!(
+        "🔁 {} mapped {}:{} to {}, re-verification job {}",
+        admin_user_id,
+        job.identifier.key,
+        job.identifier.value,
+        job.corrected_dfid,
+        job.job_id
+    );
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "success": true,
+            "job": job
+        })),
+    ))
+}
+
+async fn list_jobs(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let jobs = engine(&app_state)
+        .list_jobs()
+        .map_err(reverification_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": jobs.len(),
+        "jobs": jobs
+    })))
+}
+
+async fn get_job(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = engine(&app_state);
+    let job = engine
+        .get_job(&job_id)
+        .map_err(reverification_error_response)?;
+    let proposals = engine
+        .list_proposals(&MergeProposalFilter {
+            job_id: Some(job_id),
+            status: None,
+        })
+        .map_err(reverification_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "job": job,
+        "proposals": proposals
+    })))
+}
+
+async fn list_proposals(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Query(filter): Query<MergeProposalFilter>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let proposals = engine(&app_state)
+        .list_proposals(&filter)
+        .map_err(reverification_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": proposals.len(),
+        "proposals": proposals
+    })))
+}
+
+async fn get_proposal(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Path(proposal_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let proposal = engine(&app_state)
+        .get_proposal(&proposal_id)
+        .map_err(reverification_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "proposal": proposal
+    })))
+}
+
+/// Merge the secondary item into the primary, the same way
+/// `POST /api/items/:dfid/merge` does, then close the proposal
+async fn accept_proposal(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Path(proposal_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = engine(&app_state);
+    let proposal = engine
+        .open_proposal(&proposal_id)
+        .map_err(reverification_error_response)?;
+    let primary_dfid = proposal.primary_dfid.clone();
+    let secondary_dfid = proposal.secondary_dfid.clone();
+
+    let items_to_persist = {
+        let mut items_engine = app_state.items_engine.write().await;
+        let primary = items_engine
+            .merge_items(&primary_dfid, &secondary_dfid)
+            .map_err(|e| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": format!("Failed to merge items: {}", e)})),
+                )
+            })?;
+        let mut items = vec![primary];
+        if let Ok(Some(secondary)) = items_engine.get_item(&secondary_dfid) {
+            items.push(secondary);
+        }
+        items
+    };
+
+    if let Err(e) = app_state
+        .events_engine
+        .write()
+        .await
+        .create_item_merged_event(
+            primary_dfid.clone(),
+            secondary_dfid.clone(),
+            admin_user_id.clone(),
+        )
+    {
+        tracing::warn!(
+            "Failed to record merge of {} into {}: {}",
+            secondary_dfid,
+            primary_dfid,
+            e
+        );
+    }
+
+    let proposal = engine
+        .record_decision(proposal, true, &admin_user_id, Utc::now())
+        .map_err(reverification_error_response)?;
+
+    let postgres_persistence = Arc::clone(&app_state.postgres_persistence);
+    tokio::spawn(async move {
+        let pg_lock = postgres_persistence.read().await;
+        if let Some(pg) = &*pg_lock {
+            for item in items_to_persist {
+                if let Err(e) = pg.persist_item(&item).await {
+                    tracing::warn!("Failed to persist item {} to PostgreSQL: {}", item.dfid, e);
+                }
+            }
+        }
+    });
+
+    Ok(Json(json!({
+        "success": true,
+        "proposal": proposal
+    })))
+}
+
+async fn reject_proposal(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Path(proposal_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let engine = engine(&app_state);
+    let proposal = engine
+        .open_proposal(&proposal_id)
+        .map_err(reverification_error_response)?;
+    let proposal = engine
+        .record_decision(proposal, false, &admin_user_id, Utc::now())
+        .map_err(reverification_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "proposal": proposal
+    })))
+}
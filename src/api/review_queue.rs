@@ -0,0 +1,136 @@
+//! Manual review queue: operations staff claim pending items and approve them
+//! as new items, link them to an existing DFID or reject them.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::review_queue_engine::{
+    ReviewDecision, ReviewQueueEngine, ReviewQueueError, ReviewQueueFilter,
+};
+
+/// Mounted at `/api/review-queue`
+pub fn review_queue_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(list_queue))
+        .route("/:pending_id", get(get_pending))
+        .route("/:pending_id/claim", post(claim))
+        .route("/:pending_id/release", post(release))
+        .route("/:pending_id/resolve", post(resolve))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> ReviewQueueEngine<SharedStorage> {
+    ReviewQueueEngine::new(
+        Arc::clone(&app_state.shared_storage),
+        app_state.dfid_engine.clone(),
+    )
+}
+
+fn review_error_response(e: ReviewQueueError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        ReviewQueueError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        ReviewQueueError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        ReviewQueueError::NotFound(_) => StatusCode::NOT_FOUND,
+        ReviewQueueError::AlreadyClaimed(_) => StatusCode::CONFLICT,
+        ReviewQueueError::StorageError(_)
+        | ReviewQueueError::EventError(_)
+        | ReviewQueueError::AuditError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn list_queue(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Query(filter): Query<ReviewQueueFilter>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let items = engine(&app_state)
+        .list(&user_id, &filter, Utc::now())
+        .map_err(review_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": items.len(),
+        "pending_items": items
+    })))
+}
+
+async fn get_pending(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(pending_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let item = engine(&app_state)
+        .get(&user_id, &pending_id)
+        .map_err(review_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "pending_item": item
+    })))
+}
+
+async fn claim(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(pending_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let item = engine(&app_state)
+        .claim(&user_id, &pending_id, Utc::now())
+        .map_err(review_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "pending_item": item
+    })))
+}
+
+async fn release(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(pending_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let item = engine(&app_state)
+        .release(&user_id, &pending_id, Utc::now())
+        .map_err(review_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "pending_item": item
+    })))
+}
+
+/// Body: `{"action": "approve_as_new"}`, `{"action": "link_to_existing",
+/// "dfid": ...}` or `{"action": "reject", "reason": ...}`
+async fn resolve(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(pending_id): Path<Uuid>,
+    Json(decision): Json<ReviewDecision>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let outcome = engine(&app_state)
+        .resolve(&user_id, &pending_id, decision, Utc::now())
+        .map_err(review_error_response)?;
+
+    tracing::info!(
+        "🗂️  Pending item {} resolved as {} by {}",
+        pending_id,
+        outcome.decision,
+        user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "outcome": outcome
+    })))
+}
@@ -0,0 +1,145 @@
+//! Runbook automation hooks under the admin-guarded `/api/admin/runbooks`. The
+//! monitor evaluates hooks in the background; `POST /evaluate` runs a pass
+//! immediately. Actions taken by hooks are in the audit log as
+//! `runbook.*` system events.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AdminUser;
+use crate::runbook_engine::{registered_workers, HookInput, RunbookEngine, RunbookError};
+
+pub fn admin_runbook_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_hooks).post(create_hook))
+        .route(
+            "/:hook_id",
+            get(get_hook).put(update_hook).delete(delete_hook),
+        )
+        .route("/evaluate", post(evaluate_hooks))
+}
+
+fn engine(app_state: &AppState) -> RunbookEngine<SharedStorage> {
+    RunbookEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn runbook_error_response(e: RunbookError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        RunbookError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        RunbookError::NotFound(_) => StatusCode::NOT_FOUND,
+        RunbookError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// Hooks plus the worker names `restart_worker` can target in this process
+async fn list_hooks(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let hooks = engine(&app_state)
+        .list_hooks()
+        .map_err(runbook_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": hooks.len(),
+        "hooks": hooks,
+        "restartable_workers": registered_workers()
+    })))
+}
+
+async fn create_hook(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Json(input): Json<HookInput>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    let hook = engine(&app_state)
+        .create_hook(&admin_user_id, input, Utc::now())
+        .map_err(runbook_error_response)?;
+
+    tracing::info!(
+        "🛠️  {} created runbook hook {} ({} actions)",
+        admin_user_id,
+        hook.hook_id,
+        hook.actions.len()
+    );
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "success": true,
+            "hook": hook
+        })),
+    ))
+}
+
+async fn get_hook(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Path(hook_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let hook = engine(&app_state)
+        .get_hook(&hook_id)
+        .map_err(runbook_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "hook": hook
+    })))
+}
+
+async fn update_hook(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Path(hook_id): Path<Uuid>,
+    Json(input): Json<HookInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let hook = engine(&app_state)
+        .update_hook(&hook_id, input)
+        .map_err(runbook_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "hook": hook
+    })))
+}
+
+async fn delete_hook(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Path(hook_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    engine(&app_state)
+        .delete_hook(&hook_id)
+        .map_err(runbook_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "hook_id": hook_id
+    })))
+}
+
+/// Evaluate every enabled hook now; cooldowns apply as in the background pass
+async fn evaluate_hooks(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let report = engine(&app_state)
+        .evaluate(Utc::now())
+        .map_err(runbook_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "report": report
+    })))
+}
@@ -0,0 +1,142 @@
+//! Time series of sensor readings about items, under `/api/sensors/:dfid`.
+//! Gateways post batches of readings; clients read ranges raw or rolled up.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post, put},
+    Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::sensor_timeseries_engine::{
+    SensorError, SensorIngestInput, SensorThresholdInput, SensorTimeseriesEngine,
+};
+use crate::types::SensorResolution;
+
+/// Range read when the query gives no `from`
+const DEFAULT_RANGE_HOURS: i64 = 24;
+
+#[derive(Debug, Deserialize)]
+pub struct SensorRangeQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// Raw readings if omitted
+    pub resolution: Option<SensorResolution>,
+}
+
+/// Mounted at `/api/sensors`
+pub fn sensor_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:dfid/series", get(list_series))
+        .route("/:dfid/series/:series", get(query_range))
+        .route("/:dfid/series/:series/readings", post(ingest_readings))
+        .route("/:dfid/series/:series/thresholds", put(set_thresholds))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> SensorTimeseriesEngine<SharedStorage> {
+    SensorTimeseriesEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn sensor_error_response(e: SensorError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        SensorError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        SensorError::NotFound(_) => StatusCode::NOT_FOUND,
+        SensorError::StorageError(_) | SensorError::EventError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn list_series(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(_user_id): AuthenticatedUser,
+    Path(dfid): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let series = engine(&app_state)
+        .list_series(&dfid)
+        .map_err(sensor_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "dfid": dfid,
+        "series": series
+    })))
+}
+
+/// Readings are deduplicated by timestamp, so a failed upload can be resent whole
+async fn ingest_readings(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path((dfid, series)): Path<(String, String)>,
+    Json(input): Json<SensorIngestInput>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    let report = engine(&app_state)
+        .ingest(&user_id, &dfid, &series, input, Utc::now())
+        .map_err(sensor_error_response)?;
+
+    if !report.crossings.is_empty() {
+        tracing::info!(
+            "🌡️ {} threshold crossing(s) on {} of {}",
+            report.crossings.len(),
+            series,
+            dfid
+        );
+    }
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "success": true,
+            "report": report
+        })),
+    ))
+}
+
+async fn set_thresholds(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(_user_id): AuthenticatedUser,
+    Path((dfid, series)): Path<(String, String)>,
+    Json(input): Json<SensorThresholdInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let series = engine(&app_state)
+        .set_thresholds(&dfid, &series, input, Utc::now())
+        .map_err(sensor_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "series": series
+    })))
+}
+
+/// `from`/`to` are RFC 3339; the last day by default
+async fn query_range(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(_user_id): AuthenticatedUser,
+    Path((dfid, series)): Path<(String, String)>,
+    Query(query): Query<SensorRangeQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let to = query.to.unwrap_or_else(Utc::now);
+    let from = query
+        .from
+        .unwrap_or(to - Duration::hours(DEFAULT_RANGE_HOURS));
+    let range = engine(&app_state)
+        .query(&dfid, &series, from, to, query.resolution)
+        .map_err(sensor_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "dfid": dfid,
+        "series": series,
+        "from": from,
+        "to": to,
+        "range": range
+    })))
+}
@@ -1,5 +1,8 @@
+use crate::adapters::AdapterRegistry;
 use crate::api::notifications::NotificationMessage;
 use crate::api_key_engine::ApiKeyEngine;
+use crate::dfid_engine::DfidEngine;
+use crate::live_stream::LiveStream;
 use crate::logging::LoggingEngine;
 use crate::postgres_persistence::PostgresPersistence;
 use crate::postgres_storage_with_cache::PostgresStorageWithCache;
@@ -7,10 +10,12 @@ use crate::rate_limiter::RateLimiter;
 use crate::redis_cache::RedisCache;
 use crate::storage_helpers::{with_storage, StorageLockError};
 use crate::storage_history_reader::StorageHistoryReader;
+use crate::zk_proof_worker::ProofJobQueue;
 use crate::{
     ActivityEngine, AuditEngine, CircuitsEngine, EventsEngine, ItemsEngine, NotificationEngine,
     ReceiptEngine,
 };
+use std::net::IpAddr;
 use std::sync::{Arc, Mutex};
 use tokio::sync::{broadcast, RwLock as AsyncRwLock};
 
@@ -20,7 +25,7 @@ use tokio::sync::{broadcast, RwLock as AsyncRwLock};
 /// This ensures consistency between development, testing, and production.
 ///
 /// Storage is accessed via spawn_blocking to safely bridge sync storage with async runtime.
-type SharedStorage = Arc<Mutex<PostgresStorageWithCache>>;
+pub type SharedStorage = Arc<Mutex<PostgresStorageWithCache>>;
 
 pub struct AppState {
     pub circuits_engine: Arc<AsyncRwLock<CircuitsEngine<SharedStorage>>>,
@@ -35,13 +40,24 @@ pub struct AppState {
     pub api_key_engine: Arc<ApiKeyEngine>,
     pub api_key_storage: Arc<crate::api_key_storage::InMemoryApiKeyStorage>,
     pub rate_limiter: Arc<RateLimiter>,
+    /// Per-client-IP limits for unauthenticated public endpoints
+    pub public_rate_limiter: Arc<RateLimiter<IpAddr>>,
     pub notification_engine: Arc<AsyncRwLock<NotificationEngine<SharedStorage>>>,
     pub notification_tx: broadcast::Sender<NotificationMessage>,
+    /// Fan-out of stored events, activities and notifications for `/api/stream`
+    pub live_stream: LiveStream,
     pub jwt_secret: String,
     /// Optional PostgreSQL persistence layer - lazy initialized
     pub postgres_persistence: Arc<AsyncRwLock<Option<PostgresPersistence>>>,
     /// Optional Redis cache layer for horizontal scaling
     pub redis_cache: Arc<AsyncRwLock<Option<RedisCache>>>,
+    /// Adapters hot-registered from activated configurations
+    pub adapter_registry: Arc<AsyncRwLock<AdapterRegistry>>,
+    /// Background ZK proof generation, started with the state
+    pub proof_jobs: ProofJobQueue,
+    /// DFID generator leasing sequence ranges through the storage; clones
+    /// share its lease
+    pub dfid_engine: DfidEngine,
 }
 
 impl AppState {
@@ -56,22 +72,33 @@ impl AppState {
         let storage_for_notifications = Arc::clone(&storage);
         let storage_for_receipts = Arc::clone(&storage);
         let storage_for_history = Arc::clone(&storage);
+        let proof_jobs =
+            ProofJobQueue::start(Arc::clone(&storage), ProofJobQueue::workers_from_env());
 
-        let circuits_engine = Arc::new(AsyncRwLock::new(CircuitsEngine::<SharedStorage>::new(
-            storage_for_circuits,
-        )));
-        let items_engine = Arc::new(AsyncRwLock::new(ItemsEngine::<SharedStorage>::new(
-            storage_for_items,
-        )));
-        let events_engine = Arc::new(AsyncRwLock::new(EventsEngine::<SharedStorage>::new(
-            storage_for_events,
-        )));
+        let live_stream = LiveStream::default();
+
+        // Items and circuits lease DFID sequence ranges together, so replicas
+        // and regions never mint the same DFID
+        let dfid_engine = DfidEngine::coordinated(Arc::clone(&storage));
+        let mut circuits_engine = CircuitsEngine::<SharedStorage>::new(storage_for_circuits)
+            .with_dfid_engine(dfid_engine.clone());
+        circuits_engine.set_live_stream(live_stream.clone());
+        let circuits_engine = Arc::new(AsyncRwLock::new(circuits_engine));
+        let items_engine = Arc::new(AsyncRwLock::new(
+            ItemsEngine::<SharedStorage>::new(storage_for_items)
+                .with_dfid_engine(dfid_engine.clone()),
+        ));
+        let events_engine = Arc::new(AsyncRwLock::new(
+            EventsEngine::<SharedStorage>::new(storage_for_events)
+                .with_live_stream(live_stream.clone()),
+        ));
         let audit_engine = AuditEngine::<SharedStorage>::new(storage_for_audit);
         let activity_engine = Arc::new(AsyncRwLock::new(ActivityEngine::<SharedStorage>::new(
             storage_for_activity,
         )));
         let notification_engine = Arc::new(AsyncRwLock::new(
-            NotificationEngine::<SharedStorage>::new(storage_for_notifications),
+            NotificationEngine::<SharedStorage>::new(storage_for_notifications)
+                .with_live_stream(live_stream.clone()),
         ));
         let receipt_engine = Arc::new(Mutex::new(ReceiptEngine::new(storage_for_receipts)));
         let storage_history_reader =
@@ -107,11 +134,16 @@ impl AppState {
             api_key_engine,
             api_key_storage,
             rate_limiter,
+            public_rate_limiter: Arc::new(RateLimiter::new()),
             notification_engine,
             notification_tx,
+            live_stream,
             jwt_secret,
             postgres_persistence: Arc::new(AsyncRwLock::new(None)),
             redis_cache: Arc::new(AsyncRwLock::new(None)),
+            adapter_registry: Arc::new(AsyncRwLock::new(AdapterRegistry::new())),
+            proof_jobs,
+            dfid_engine,
         }
     }
 
@@ -119,7 +151,9 @@ impl AppState {
     pub async fn enable_event_persistence(&self) {
         let mut engine = self.events_engine.write().await;
         let new_engine = EventsEngine::new(self.shared_storage.clone())
-            .with_postgres(Arc::clone(&self.postgres_persistence));
+            .with_postgres(Arc::clone(&self.postgres_persistence))
+            .with_live_stream(self.live_stream.clone())
+            .with_dedup_window(engine.dedup_window());
         *engine = new_engine;
     }
 
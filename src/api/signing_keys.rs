@@ -0,0 +1,97 @@
+//! Ed25519 keys users and workspaces sign events with. Registering a key takes a
+//! signature over `registration_message` proving the caller holds it.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::event_signing_engine::{
+    registration_message, EventSigningEngine, EventSigningError, RegisterSigningKeyInput,
+};
+
+/// Mounted at `/api/signing-keys`
+pub fn signing_key_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(list_keys).post(register_key))
+        .route("/:key_id/revoke", post(revoke_key))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> EventSigningEngine<SharedStorage> {
+    EventSigningEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn signing_key_error_response(e: EventSigningError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        EventSigningError::ValidationError(_) | EventSigningError::InvalidSignature(_) => {
+            StatusCode::BAD_REQUEST
+        }
+        EventSigningError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        EventSigningError::NotFound(_) => StatusCode::NOT_FOUND,
+        EventSigningError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// The caller's own keys and their workspace's
+async fn list_keys(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let keys = engine(&app_state)
+        .list_keys(&user_id)
+        .map_err(signing_key_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "keys": keys,
+        "registration_message_format": registration_message(&user_id, "<public_key>")
+    })))
+}
+
+async fn register_key(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(input): Json<RegisterSigningKeyInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let key = engine(&app_state)
+        .register_key(&user_id, input, Utc::now())
+        .map_err(signing_key_error_response)?;
+
+    tracing::info!(
+        "🔏 Event signing key {} registered by {}",
+        key.key_id,
+        user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "key": key
+    })))
+}
+
+/// Events already signed with the key stay verifiable; new ones are refused
+async fn revoke_key(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(key_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let key = engine(&app_state)
+        .revoke_key(&user_id, &key_id, Utc::now())
+        .map_err(signing_key_error_response)?;
+
+    tracing::info!("🔏 Event signing key {} revoked by {}", key_id, user_id);
+    Ok(Json(json!({
+        "success": true,
+        "key": key
+    })))
+}
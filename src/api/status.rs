@@ -0,0 +1,114 @@
+//! Service status and SLA reporting. `/api/status` is public so it can back a
+//! status page; the monthly SLA report is admin-only.
+
+use axum::{
+    extract::{Query, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{Json, Response},
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AdminUser;
+use crate::sla_engine::{self, SlaEngine, SlaError};
+use crate::types::SlaComponent;
+
+const DEFAULT_HISTORY_DAYS: i64 = 30;
+
+#[derive(Debug, Deserialize)]
+pub struct StatusHistoryQuery {
+    pub days: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MonthlySlaQuery {
+    /// `YYYY-MM`; defaults to the current month
+    pub month: Option<String>,
+}
+
+/// Public routes, mounted at `/api/status`
+pub fn status_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(get_status))
+        .route("/history", get(get_status_history))
+        .merge(crate::api::maintenance::maintenance_status_routes())
+        .with_state(app_state)
+}
+
+/// Nested under the admin-guarded `/api/admin/sla`
+pub fn admin_sla_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/monthly", get(get_monthly_sla))
+}
+
+/// Counts every API response towards availability; 5xx responses are errors
+pub async fn sla_tracking_middleware(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+    sla_engine::record_outcome(SlaComponent::Api, !response.status().is_server_error());
+    response
+}
+
+fn engine(app_state: &AppState) -> SlaEngine<SharedStorage> {
+    SlaEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn sla_error_response(e: SlaError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        SlaError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        SlaError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn get_status(
+    State(app_state): State<Arc<AppState>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let status = engine(&app_state)
+        .status(Utc::now())
+        .map_err(sla_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "status": status
+    })))
+}
+
+async fn get_status_history(
+    State(app_state): State<Arc<AppState>>,
+    Query(query): Query<StatusHistoryQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let days = query.days.unwrap_or(DEFAULT_HISTORY_DAYS);
+    let history = engine(&app_state)
+        .daily_history(days, Utc::now())
+        .map_err(sla_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "days": days,
+        "history": history
+    })))
+}
+
+async fn get_monthly_sla(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Query(query): Query<MonthlySlaQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let now = Utc::now();
+    let month = query
+        .month
+        .unwrap_or_else(|| now.format("%Y-%m").to_string());
+    let report = engine(&app_state)
+        .monthly_report(&month, now)
+        .map_err(sla_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "report": report
+    })))
+}
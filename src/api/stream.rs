@@ -0,0 +1,207 @@
+use axum::{
+    extract::{
+        ws::{Message, WebSocket},
+        Query, State, WebSocketUpgrade,
+    },
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    response::{
+        sse::{Event as SseEvent, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::get,
+    Json, Router,
+};
+use futures::{sink::SinkExt, stream, stream::StreamExt};
+use jsonwebtoken::{decode, DecodingKey, Validation};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::api::auth::Claims;
+use crate::api::shared_state::AppState;
+use crate::live_stream::{LiveMessage, LiveRecordKind, LiveSubscription};
+use crate::postgres_storage_with_cache::PostgresStorageWithCache;
+
+type Subscription = LiveSubscription<Arc<std::sync::Mutex<PostgresStorageWithCache>>>;
+
+#[derive(Debug, Deserialize)]
+pub struct StreamQuery {
+    /// JWT for clients that cannot set headers (EventSource, browser WebSocket)
+    pub token: Option<String>,
+    /// Only records of this circuit (the caller must be a member)
+    pub circuit_id: Option<Uuid>,
+    /// Comma-separated subset of `event,activity,notification`
+    pub types: Option<String>,
+}
+
+// NOT behind the JWT middleware: EventSource and WebSocket clients pass the token
+// as a query parameter, so it is verified here
+pub fn stream_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(sse_handler))
+        .route("/ws", get(websocket_handler))
+}
+
+type StreamError = (StatusCode, Json<Value>);
+
+fn open_subscription(
+    state: &Arc<AppState>,
+    headers: &HeaderMap,
+    query: &StreamQuery,
+) -> Result<Subscription, StreamError> {
+    let bearer = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let token = query.token.as_deref().or(bearer).ok_or_else(|| {
+        (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({"error": "Missing authentication token"})),
+        )
+    })?;
+    let claims = decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(state.jwt_secret.as_ref()),
+        &Validation::default(),
+    )
+    .map_err(|_| {
+        (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({"error": "Invalid authentication token"})),
+        )
+    })?
+    .claims;
+
+    let kinds = query
+        .types
+        .as_deref()
+        .map(LiveRecordKind::parse_list)
+        .transpose()
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
+
+    let subscription = LiveSubscription::new(
+        Arc::clone(&state.shared_storage),
+        &state.live_stream,
+        &claims.user_id,
+        query.circuit_id,
+        kinds,
+    )
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("Failed to open stream: {e}")})),
+        )
+    })?;
+
+    if let Some(circuit_id) = &query.circuit_id {
+        if !subscription.is_member_of(circuit_id) {
+            return Err((
+                StatusCode::FORBIDDEN,
+                Json(json!({"error": "Not a member of this circuit"})),
+            ));
+        }
+    }
+    Ok(subscription)
+}
+
+fn message_json(message: &LiveMessage) -> Value {
+    match message {
+        LiveMessage::Record(record) => json!(record),
+        LiveMessage::Lagged(n) => json!({
+            "type": "lag",
+            "message": format!("Stream lagged by {n} records"),
+            "missed_count": n
+        }),
+    }
+}
+
+/// Server-sent events: one SSE event per record, named after its type
+async fn sse_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<StreamQuery>,
+) -> Response {
+    let subscription = match open_subscription(&state, &headers, &query) {
+        Ok(subscription) => subscription,
+        Err(e) => return e.into_response(),
+    };
+
+    let connected = stream::once(async {
+        Ok::<_, Infallible>(SseEvent::default().event("connected").data("{}"))
+    });
+    let records = stream::unfold(subscription, |mut subscription| async move {
+        let message = subscription.next().await?;
+        let name = match &message {
+            LiveMessage::Record(record) => record.kind().as_str(),
+            LiveMessage::Lagged(_) => "lag",
+        };
+        let event = SseEvent::default()
+            .event(name)
+            .data(message_json(&message).to_string());
+        Some((Ok(event), subscription))
+    });
+
+    Sse::new(connected.chain(records))
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+async fn websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<StreamQuery>,
+) -> Response {
+    match open_subscription(&state, &headers, &query) {
+        Ok(subscription) => ws.on_upgrade(move |socket| handle_socket(socket, subscription)),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn handle_socket(socket: WebSocket, mut subscription: Subscription) {
+    let (mut sender, mut receiver) = socket.split();
+    let mut ping_interval = interval(Duration::from_secs(30));
+
+    let welcome = json!({"type": "connected"});
+    if sender
+        .send(Message::Text(welcome.to_string()))
+        .await
+        .is_err()
+    {
+        return;
+    }
+    info!("Live stream WebSocket connected");
+
+    loop {
+        tokio::select! {
+            _ = ping_interval.tick() => {
+                if sender.send(Message::Ping(vec![])).await.is_err() {
+                    break;
+                }
+            }
+            incoming = receiver.next() => {
+                match incoming {
+                    Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                    Some(Ok(_)) => {}
+                }
+            }
+            message = subscription.next() => {
+                let Some(message) = message else {
+                    warn!("Live stream channel closed");
+                    break;
+                };
+                if sender.send(Message::Text(message_json(&message).to_string())).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    let _ = sender.send(Message::Close(None)).await;
+    info!("Live stream WebSocket closed");
+}
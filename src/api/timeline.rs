@@ -3,19 +3,21 @@
 /// Provides endpoints for querying item CID timeline from blockchain events.
 ///
 /// Endpoints:
-/// - GET /api/items/:dfid/timeline - Get complete timeline for an item
+/// - GET /api/items/:dfid/timeline - Get a page of an item's timeline, optionally
+///   limited to a sequence range, a date range or the latest N entries
 /// - GET /api/items/:dfid/timeline/:sequence - Get specific timeline entry
 /// - GET /api/timeline/indexing-progress/:network - Get blockchain indexing status
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Json},
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+use crate::pagination::page_size;
 use crate::postgres_persistence::PostgresPersistence;
-use crate::types::{IndexingProgress, TimelineEntry};
+use crate::types::{IndexingProgress, TimelineEntry, TimelinePage, TimelineQuery};
 
 /// Timeline API state
 #[derive(Clone)]
@@ -29,6 +31,35 @@ pub struct TimelineResponse {
     pub dfid: String,
     pub total_entries: usize,
     pub timeline: Vec<TimelineEntryResponse>,
+    /// Pass back as `after_sequence` for the next page; `None` on the last page
+    #[serde(default)]
+    pub next_after_sequence: Option<i32>,
+}
+
+/// Query parameters of `GET /api/items/:dfid/timeline`
+#[derive(Debug, Default, Deserialize)]
+pub struct TimelineParams {
+    pub from_sequence: Option<i32>,
+    pub to_sequence: Option<i32>,
+    /// RFC 3339; compared with the blockchain timestamp
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only the newest N entries within the other bounds
+    pub latest: Option<usize>,
+    pub after_sequence: Option<i32>,
+    pub limit: Option<usize>,
+}
+
+impl TimelineParams {
+    pub fn query(&self) -> TimelineQuery {
+        TimelineQuery {
+            from_sequence: self.from_sequence,
+            to_sequence: self.to_sequence,
+            from: self.from,
+            to: self.to,
+            latest: self.latest,
+        }
+    }
 }
 
 /// Timeline entry for API response
@@ -82,24 +113,43 @@ impl From<IndexingProgress> for IndexingProgressResponse {
 }
 
 /// GET /api/items/:dfid/timeline
-/// Get the complete CID timeline for an item
+/// Get a page of the CID timeline for an item
 ///
-/// Returns chronological list of all CIDs for this DFID from blockchain events.
+/// Returns CIDs for this DFID from blockchain events in sequence order, at most
+/// `limit` (default 50, max 200) per page, following `after_sequence`.
 pub async fn get_item_timeline(
     State(state): State<TimelineState>,
     Path(dfid): Path<String>,
+    Query(params): Query<TimelineParams>,
 ) -> impl IntoResponse {
     tracing::debug!("📋 Getting timeline for DFID: {}", dfid);
 
-    match state.persistence.get_item_timeline(&dfid).await {
+    let query = params.query();
+    if let Err(e) = query.validate() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": e })),
+        )
+            .into_response();
+    }
+    let limit = page_size(params.limit);
+
+    match state
+        .persistence
+        .get_item_timeline_page(&dfid, &query, params.after_sequence, limit + 1)
+        .await
+    {
         Ok(entries) => {
+            let page = TimelinePage::from_fetched(entries, limit);
             let response = TimelineResponse {
                 dfid: dfid.clone(),
-                total_entries: entries.len(),
-                timeline: entries
+                total_entries: page.entries.len(),
+                timeline: page
+                    .entries
                     .into_iter()
                     .map(TimelineEntryResponse::from)
                     .collect(),
+                next_after_sequence: page.next_after_sequence,
             };
 
             (StatusCode::OK, Json(response)).into_response()
@@ -234,4 +284,62 @@ mod tests {
         assert_eq!(response.sequence, 1);
         assert_eq!(response.cid, "QmTest123");
     }
+
+    #[test]
+    fn test_timeline_pages_within_range_and_latest() {
+        use crate::storage::{InMemoryStorage, StorageBackend};
+
+        let storage = InMemoryStorage::new();
+        let base = 1_704_067_200;
+        for n in 0..10 {
+            storage
+                .add_cid_to_timeline(
+                    "DFID-1",
+                    &format!("Qm{n}"),
+                    &format!("tx{n}"),
+                    base + n * 3600,
+                    "stellar-testnet",
+                )
+                .unwrap();
+        }
+        let read_all = |query: &TimelineQuery, limit: usize| {
+            let mut after = None;
+            let mut sequences = Vec::new();
+            loop {
+                let page = TimelinePage::from_fetched(
+                    storage
+                        .get_item_timeline_page("DFID-1", query, after, limit + 1)
+                        .unwrap(),
+                    limit,
+                );
+                sequences.extend(page.entries.iter().map(|e| e.event_sequence));
+                match page.next_after_sequence {
+                    Some(next) => after = Some(next),
+                    None => return sequences,
+                }
+            }
+        };
+
+        let range = TimelineQuery {
+            from_sequence: Some(3),
+            to_sequence: Some(8),
+            ..Default::default()
+        };
+        assert_eq!(read_all(&range, 4), vec![3, 4, 5, 6, 7, 8]);
+
+        // Sequences 1-4 were anchored in the first four hours; keep the newest two
+        let early_latest = TimelineQuery {
+            to: chrono::DateTime::from_timestamp(base + 3 * 3600, 0),
+            latest: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(read_all(&early_latest, 1), vec![3, 4]);
+
+        let backwards = TimelineQuery {
+            from_sequence: Some(5),
+            to_sequence: Some(2),
+            ..Default::default()
+        };
+        assert!(backwards.validate().is_err());
+    }
 }
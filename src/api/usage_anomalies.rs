@@ -0,0 +1,170 @@
+//! Per-workspace API usage anomalies. `usage_tracking_middleware` counts
+//! authenticated requests by workspace and route; admins review anomalies and
+//! tune each workspace's thresholds under `/api/admin/usage-anomalies`.
+
+use axum::{
+    extract::{MatchedPath, Path, Query, Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{Json, Response},
+    routing::get,
+    Router,
+};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::api::auth::Claims;
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::api_key_middleware::ApiKeyContext;
+use crate::auth_middleware::AdminUser;
+use crate::usage_anomaly_engine::{
+    self, UsageAnomalyEngine, UsageAnomalyError, UsageBaselineInput, UsageSubject,
+};
+
+const DEFAULT_ANOMALY_HOURS: i64 = 24;
+const DEFAULT_USAGE_HOURS: i64 = 48;
+const MAX_HOURS: i64 = 24 * 90;
+
+#[derive(Debug, Deserialize)]
+pub struct AnomalyQuery {
+    pub workspace_id: Option<String>,
+    pub hours: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UsageQuery {
+    pub hours: Option<i64>,
+}
+
+/// Nested under the admin-guarded `/api/admin/usage-anomalies`
+pub fn admin_usage_anomaly_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_anomalies))
+        .route(
+            "/workspaces/:workspace_id/baseline",
+            get(get_baseline).put(set_baseline),
+        )
+        .route("/workspaces/:workspace_id/usage", get(get_usage))
+}
+
+/// Counts authenticated requests towards their workspace's usage. Runs inside
+/// the auth middleware so the JWT claims or API key context are present;
+/// unauthenticated and unrouted requests are not counted.
+pub async fn usage_tracking_middleware(request: Request, next: Next) -> Response {
+    let subject = if let Some(claims) = request.extensions().get::<Claims>() {
+        Some(match &claims.workspace_id {
+            Some(workspace_id) => UsageSubject::Workspace(workspace_id.clone()),
+            None => UsageSubject::User(claims.user_id.clone()),
+        })
+    } else {
+        request
+            .extensions()
+            .get::<ApiKeyContext>()
+            .map(|context| UsageSubject::User(context.original_user_id.clone()))
+    };
+    let endpoint = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| format!("{} {}", request.method(), path.as_str()));
+    let is_delete = request.method() == Method::DELETE;
+
+    let response = next.run(request).await;
+    if let (Some(subject), Some(endpoint)) = (subject, endpoint) {
+        let deletion = is_delete && response.status().is_success();
+        usage_anomaly_engine::record_request(subject, &endpoint, deletion);
+    }
+    response
+}
+
+fn engine(app_state: &AppState) -> UsageAnomalyEngine<SharedStorage> {
+    UsageAnomalyEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn usage_anomaly_error_response(e: UsageAnomalyError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        UsageAnomalyError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        UsageAnomalyError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+fn hours_back(hours: Option<i64>, default: i64) -> Result<Duration, (StatusCode, Json<Value>)> {
+    let hours = hours.unwrap_or(default);
+    if !(1..=MAX_HOURS).contains(&hours) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("hours must be between 1 and {MAX_HOURS}")})),
+        ));
+    }
+    Ok(Duration::hours(hours))
+}
+
+async fn list_anomalies(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Query(query): Query<AnomalyQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let since = Utc::now() - hours_back(query.hours, DEFAULT_ANOMALY_HOURS)?;
+    let anomalies = engine(&app_state)
+        .anomalies(query.workspace_id.as_deref(), since)
+        .map_err(usage_anomaly_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "count": anomalies.len(),
+        "anomalies": anomalies
+    })))
+}
+
+async fn get_baseline(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Path(workspace_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let baseline = engine(&app_state)
+        .baseline(&workspace_id)
+        .map_err(usage_anomaly_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "baseline": baseline
+    })))
+}
+
+async fn set_baseline(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Path(workspace_id): Path<String>,
+    Json(input): Json<UsageBaselineInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let baseline = engine(&app_state)
+        .set_baseline(&admin_user_id, &workspace_id, input, Utc::now())
+        .map_err(usage_anomaly_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "baseline": baseline
+    })))
+}
+
+/// Hourly usage of a workspace, oldest first
+async fn get_usage(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+    Path(workspace_id): Path<String>,
+    Query(query): Query<UsageQuery>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let now = Utc::now();
+    let from = now - hours_back(query.hours, DEFAULT_USAGE_HOURS)?;
+    let windows = engine(&app_state)
+        .usage(&workspace_id, from, now)
+        .map_err(usage_anomaly_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "workspace_id": workspace_id,
+        "windows": windows
+    })))
+}
@@ -0,0 +1,77 @@
+//! Per-workspace rules verification applies to submissions: identifiers and
+//! payload fields they must carry, and sources held for manual review.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::verification_rules::{
+    VerificationRulesEngine, VerificationRulesError, VerificationRulesInput,
+};
+
+/// Mounted at `/api/verification-rules`
+pub fn verification_rule_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:workspace_id", get(get_rules).put(set_rules))
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> VerificationRulesEngine<SharedStorage> {
+    VerificationRulesEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn verification_rules_error_response(e: VerificationRulesError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        VerificationRulesError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        VerificationRulesError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        VerificationRulesError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+async fn get_rules(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let rules = engine(&app_state)
+        .get_rules(&user_id, &workspace_id)
+        .map_err(verification_rules_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "rules": rules
+    })))
+}
+
+/// Entries already waiting are judged by the new rules when verified
+async fn set_rules(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+    Json(input): Json<VerificationRulesInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let rules = engine(&app_state)
+        .set_rules(&user_id, &workspace_id, input, Utc::now())
+        .map_err(verification_rules_error_response)?;
+
+    tracing::info!(
+        "📐 {} verification rules set for workspace {} by {}",
+        rules.rules.len(),
+        workspace_id,
+        user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "rules": rules
+    })))
+}
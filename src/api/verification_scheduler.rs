@@ -0,0 +1,103 @@
+//! Background verification worker, under the admin-guarded
+//! `/api/admin/verification-scheduler`: progress, batch settings, and
+//! pause/resume.
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+    routing::{get, post, put},
+    Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::api::shared_state::AppState;
+use crate::auth_middleware::AdminUser;
+use crate::verification_scheduler::{
+    self, VerificationSchedulerConfig, VerificationSchedulerError,
+};
+
+pub fn admin_verification_scheduler_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(get_status))
+        .route("/config", put(update_config))
+        .route("/pause", post(pause))
+        .route("/resume", post(resume))
+}
+
+/// Settings to change; omitted fields keep their current value
+#[derive(Debug, Deserialize)]
+pub struct UpdateSchedulerConfigRequest {
+    pub interval_secs: Option<u64>,
+    pub batch_size: Option<usize>,
+    pub concurrency: Option<usize>,
+}
+
+fn scheduler_error_response(e: VerificationSchedulerError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        VerificationSchedulerError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        VerificationSchedulerError::StorageError(_)
+        | VerificationSchedulerError::VerificationError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+fn status_response(app_state: &AppState) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let status = verification_scheduler::status(&app_state.shared_storage)
+        .map_err(scheduler_error_response)?;
+    Ok(Json(json!({
+        "success": true,
+        "scheduler": status
+    })))
+}
+
+async fn get_status(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(_admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    status_response(&app_state)
+}
+
+async fn update_config(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Json(request): Json<UpdateSchedulerConfigRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let current = verification_scheduler::config();
+    let config = VerificationSchedulerConfig {
+        interval_secs: request.interval_secs.unwrap_or(current.interval_secs),
+        batch_size: request.batch_size.unwrap_or(current.batch_size),
+        concurrency: request.concurrency.unwrap_or(current.concurrency),
+    };
+    verification_scheduler::configure(config).map_err(scheduler_error_response)?;
+
+    tracing::info!(
+        "🔎 {} set verification to {} entries every {}s on {} thread(s)",
+        admin_user_id,
+        config.batch_size,
+        config.interval_secs,
+        config.concurrency
+    );
+    status_response(&app_state)
+}
+
+async fn pause(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    verification_scheduler::pause(&admin_user_id, Utc::now());
+    tracing::info!("⏸️  {} paused background verification", admin_user_id);
+    status_response(&app_state)
+}
+
+async fn resume(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    verification_scheduler::resume();
+    tracing::info!("▶️  {} resumed background verification", admin_user_id);
+    status_response(&app_state)
+}
@@ -0,0 +1,300 @@
+//! API versioning for the `/api` route tree.
+//!
+//! Routes are registered once; `/api/v1/...` and `/api/v2/...` are resolved before
+//! routing by [`api_version_middleware`], which strips the version segment and
+//! records the [`ApiVersion`] in the request extensions, so both trees share the
+//! same handlers. A handler whose response shape changes between versions takes
+//! `ApiVersion` as an extractor and branches on it.
+//!
+//! Unversioned `/api/...` paths remain an alias of v1 for existing clients and are
+//! answered with `Deprecation` and `Link: rel="successor-version"` headers.
+//! Versions listed in `API_DEPRECATED_VERSIONS` (e.g. `v1:2027-06-30`) get the
+//! same headers plus `Sunset` when a date is given.
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request, State},
+    http::{request::Parts, HeaderValue, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiVersion {
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    pub const ALL: [ApiVersion; 2] = [ApiVersion::V1, ApiVersion::V2];
+    pub const LATEST: ApiVersion = ApiVersion::V2;
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "v1",
+            ApiVersion::V2 => "v2",
+        }
+    }
+
+    pub fn parse(segment: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|version| version.as_str().eq_ignore_ascii_case(segment))
+    }
+}
+
+impl std::fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Version of the current request; requests that did not go through the
+/// versioning middleware are v1
+#[async_trait]
+impl<S> FromRequestParts<S> for ApiVersion
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<ApiVersion>()
+            .copied()
+            .unwrap_or(ApiVersion::V1))
+    }
+}
+
+/// Deprecated versions and their optional sunset dates
+#[derive(Debug, Clone, Default)]
+pub struct VersioningConfig {
+    deprecated: HashMap<ApiVersion, Option<DateTime<Utc>>>,
+}
+
+impl VersioningConfig {
+    /// Read `API_DEPRECATED_VERSIONS`, a comma-separated list of `version[:date]`
+    /// where date is `YYYY-MM-DD` or RFC 3339
+    pub fn from_env() -> Self {
+        std::env::var("API_DEPRECATED_VERSIONS")
+            .map(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(value: &str) -> Self {
+        let mut config = Self::default();
+        for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (version, sunset) = match entry.split_once(':') {
+                Some((version, date)) => (version, parse_sunset(date.trim())),
+                None => (entry, None),
+            };
+            match ApiVersion::parse(version.trim()) {
+                Some(version) => config = config.deprecate(version, sunset),
+                None => tracing::warn!(
+                    "Ignoring unknown API version '{}' in API_DEPRECATED_VERSIONS",
+                    version
+                ),
+            }
+        }
+        config
+    }
+
+    pub fn deprecate(mut self, version: ApiVersion, sunset: Option<DateTime<Utc>>) -> Self {
+        self.deprecated.insert(version, sunset);
+        self
+    }
+
+    /// `Some(sunset)` when the version is deprecated
+    pub fn deprecation(&self, version: ApiVersion) -> Option<Option<DateTime<Utc>>> {
+        self.deprecated.get(&version).copied()
+    }
+}
+
+fn parse_sunset(date: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(date)
+        .map(|d| d.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|d| d.and_utc())
+        })
+}
+
+/// Where a request path lands in the versioned tree
+#[derive(Debug, PartialEq)]
+pub enum VersionedPath {
+    /// `/api/v{n}/rest`, with the path the shared routes are registered under
+    Versioned(ApiVersion, String),
+    /// Unversioned `/api/rest`, served as v1
+    Legacy,
+    /// `/api/v{n}/...` for a version this server does not have
+    Unsupported(String),
+    /// Outside `/api`
+    Other,
+}
+
+pub fn resolve_path(path: &str) -> VersionedPath {
+    let Some(rest) = path.strip_prefix("/api") else {
+        return VersionedPath::Other;
+    };
+    if !rest.is_empty() && !rest.starts_with('/') {
+        return VersionedPath::Other;
+    }
+    let trimmed = rest.trim_start_matches('/');
+    let (segment, remainder) = match trimmed.find('/') {
+        Some(at) => trimmed.split_at(at),
+        None => (trimmed, ""),
+    };
+    let is_version_segment = segment.len() > 1
+        && segment.starts_with(['v', 'V'])
+        && segment[1..].chars().all(|c| c.is_ascii_digit());
+    if !is_version_segment {
+        return VersionedPath::Legacy;
+    }
+    match ApiVersion::parse(segment) {
+        Some(version) => VersionedPath::Versioned(version, format!("/api{remainder}")),
+        None => VersionedPath::Unsupported(segment.to_string()),
+    }
+}
+
+fn rewrite_path(uri: &Uri, path: &str) -> Option<Uri> {
+    let path_and_query = match uri.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_string(),
+    };
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(path_and_query.parse().ok()?);
+    Uri::from_parts(parts).ok()
+}
+
+fn header(value: &str) -> HeaderValue {
+    HeaderValue::from_str(value).unwrap_or_else(|_| HeaderValue::from_static(""))
+}
+
+/// Resolves `/api/v{n}` paths onto the shared routes and stamps version and
+/// deprecation headers. Must wrap the whole router (not `Router::layer`) so the
+/// rewrite happens before routing.
+pub async fn api_version_middleware(
+    State(config): State<Arc<VersioningConfig>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let (version, legacy, resource) = match resolve_path(request.uri().path()) {
+        VersionedPath::Versioned(version, path) => {
+            let Some(uri) = rewrite_path(request.uri(), &path) else {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": "Invalid request path"})),
+                )
+                    .into_response();
+            };
+            *request.uri_mut() = uri;
+            let resource = path["/api".len()..].to_string();
+            (version, false, resource)
+        }
+        VersionedPath::Legacy => {
+            let resource = request.uri().path()["/api".len()..].to_string();
+            (ApiVersion::V1, true, resource)
+        }
+        VersionedPath::Unsupported(segment) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": format!("Unsupported API version '{segment}'"),
+                    "supported_versions": ApiVersion::ALL,
+                })),
+            )
+                .into_response();
+        }
+        VersionedPath::Other => return next.run(request).await,
+    };
+
+    request.extensions_mut().insert(version);
+    let mut response = next.run(request).await;
+
+    let headers = response.headers_mut();
+    headers.insert("API-Version", HeaderValue::from_static(version.as_str()));
+    let sunset = config.deprecation(version);
+    let successor = if legacy {
+        Some(version)
+    } else if sunset.is_some() {
+        Some(ApiVersion::LATEST)
+    } else {
+        None
+    };
+    if let Some(successor) = successor {
+        headers.insert("Deprecation", HeaderValue::from_static("true"));
+        headers.insert(
+            "Link",
+            header(&format!(
+                "</api/{successor}{resource}>; rel=\"successor-version\""
+            )),
+        );
+    }
+    if let Some(Some(sunset)) = sunset {
+        headers.insert(
+            "Sunset",
+            header(&sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string()),
+        );
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_path_and_deprecation_config() {
+        assert_eq!(
+            resolve_path("/api/v2/items/DFID-1"),
+            VersionedPath::Versioned(ApiVersion::V2, "/api/items/DFID-1".to_string())
+        );
+        assert_eq!(
+            resolve_path("/api/V1"),
+            VersionedPath::Versioned(ApiVersion::V1, "/api".to_string())
+        );
+        assert_eq!(resolve_path("/api/items"), VersionedPath::Legacy);
+        assert_eq!(resolve_path("/api/verify"), VersionedPath::Legacy);
+        assert_eq!(
+            resolve_path("/api/v9/items"),
+            VersionedPath::Unsupported("v9".to_string())
+        );
+        assert_eq!(resolve_path("/apis/v1"), VersionedPath::Other);
+        assert_eq!(resolve_path("/health"), VersionedPath::Other);
+
+        let uri: Uri = "/api/v1/items/search?q=nelore".parse().unwrap();
+        assert_eq!(
+            rewrite_path(&uri, "/api/items/search").unwrap(),
+            "/api/items/search?q=nelore"
+        );
+
+        let config = VersioningConfig::parse("v1:2027-06-30, v7, v2");
+        assert_eq!(
+            config.deprecation(ApiVersion::V1),
+            Some(Some(
+                NaiveDate::from_ymd_opt(2027, 6, 30)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+                    .and_utc()
+            ))
+        );
+        assert_eq!(config.deprecation(ApiVersion::V2), Some(None));
+        assert_eq!(
+            VersioningConfig::default().deprecation(ApiVersion::V1),
+            None
+        );
+    }
+}
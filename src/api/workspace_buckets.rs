@@ -0,0 +1,116 @@
+//! Enterprise workspaces' own buckets for attachments and exports. The secret
+//! key is write-only.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::api::shared_state::{AppState, SharedStorage};
+use crate::auth_middleware::AuthenticatedUser;
+use crate::types::WorkspaceBucketConfig;
+use crate::workspace_bucket_engine::{
+    WorkspaceBucketEngine, WorkspaceBucketError, WorkspaceBucketInput,
+};
+
+/// Mounted at `/api/workspace-buckets`
+pub fn workspace_bucket_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/:workspace_id",
+            get(get_bucket).put(set_bucket).delete(delete_bucket),
+        )
+        .with_state(app_state)
+}
+
+fn engine(app_state: &AppState) -> WorkspaceBucketEngine<SharedStorage> {
+    WorkspaceBucketEngine::new(Arc::clone(&app_state.shared_storage))
+}
+
+fn bucket_error_response(e: WorkspaceBucketError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        WorkspaceBucketError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        WorkspaceBucketError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        WorkspaceBucketError::NotConfigured(_) => StatusCode::NOT_FOUND,
+        WorkspaceBucketError::TransferError(_) => StatusCode::BAD_GATEWAY,
+        WorkspaceBucketError::StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+fn config_json(config: &WorkspaceBucketConfig) -> Value {
+    let mut value = json!(config);
+    if let Some(object) = value.as_object_mut() {
+        object.remove("secret_access_key");
+    }
+    value
+}
+
+async fn get_bucket(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let config = engine(&app_state)
+        .get_config(&user_id, &workspace_id)
+        .map_err(bucket_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "configured": config.is_some(),
+        "bucket": config.as_ref().map(config_json)
+    })))
+}
+
+/// New attachments and exports of the workspace go to this bucket
+async fn set_bucket(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+    Json(input): Json<WorkspaceBucketInput>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let config = engine(&app_state)
+        .set_config(&user_id, &workspace_id, input, Utc::now())
+        .map_err(bucket_error_response)?;
+
+    tracing::info!(
+        "🪣 Workspace {} now stores files in bucket {} (set by {})",
+        workspace_id,
+        config.bucket,
+        user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "bucket": config_json(&config)
+    })))
+}
+
+/// Files already in the bucket keep their references but can no longer be
+/// downloaded through the API
+async fn delete_bucket(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(workspace_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let deleted = engine(&app_state)
+        .delete_config(&user_id, &workspace_id)
+        .map_err(bucket_error_response)?;
+    if !deleted {
+        return Err(bucket_error_response(WorkspaceBucketError::NotConfigured(
+            format!("workspace {workspace_id}"),
+        )));
+    }
+
+    tracing::info!(
+        "🪣 Workspace {} bucket removed by {}",
+        workspace_id,
+        user_id
+    );
+    Ok(Json(json!({"success": true})))
+}
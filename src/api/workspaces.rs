@@ -13,6 +13,7 @@ use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 use crate::storage_helpers::{with_lock, with_lock_mut, StorageLockError};
+use crate::types::TimeAxis;
 
 #[derive(Debug, Deserialize)]
 pub struct CreateWorkspaceRequest {
@@ -35,6 +36,7 @@ pub struct WorkspaceSettingsRequest {
     pub default_event_visibility: Option<String>,
     pub encryption_enabled: Option<bool>,
     pub retention_policy_days: Option<u32>,
+    pub retention_axis: Option<TimeAxis>,
     pub max_members: Option<u32>,
     pub allow_public_circuits: Option<bool>,
 }
@@ -71,6 +73,7 @@ pub struct WorkspaceSettingsResponse {
     pub default_event_visibility: String,
     pub encryption_enabled: bool,
     pub retention_policy_days: u32,
+    pub retention_axis: TimeAxis,
     pub max_members: u32,
     pub allow_public_circuits: bool,
 }
@@ -110,6 +113,9 @@ pub struct WorkspaceSettings {
     pub default_event_visibility: String,
     pub encryption_enabled: bool,
     pub retention_policy_days: u32,
+    /// Clock retention ages records by; `Occurred` so backfilled history expires by
+    /// when it happened, not when it was imported
+    pub retention_axis: TimeAxis,
     pub max_members: u32,
     pub allow_public_circuits: bool,
 }
@@ -133,6 +139,7 @@ impl Default for WorkspaceSettings {
             default_event_visibility: "Private".to_string(),
             encryption_enabled: true,
             retention_policy_days: 365,
+            retention_axis: TimeAxis::Occurred,
             max_members: 100,
             allow_public_circuits: false,
         }
@@ -188,6 +195,7 @@ fn workspace_to_response(workspace: Workspace) -> WorkspaceResponse {
             default_event_visibility: workspace.settings.default_event_visibility,
             encryption_enabled: workspace.settings.encryption_enabled,
             retention_policy_days: workspace.settings.retention_policy_days,
+            retention_axis: workspace.settings.retention_axis,
             max_members: workspace.settings.max_members,
             allow_public_circuits: workspace.settings.allow_public_circuits,
         },
@@ -234,6 +242,9 @@ async fn create_workspace(
         if let Some(retention) = settings_req.retention_policy_days {
             settings.retention_policy_days = retention;
         }
+        if let Some(axis) = settings_req.retention_axis {
+            settings.retention_axis = axis;
+        }
         if let Some(max_members) = settings_req.max_members {
             settings.max_members = max_members;
         }
@@ -359,6 +370,9 @@ async fn update_workspace(
                     if let Some(retention) = settings_req.retention_policy_days {
                         workspace.settings.retention_policy_days = retention;
                     }
+                    if let Some(axis) = settings_req.retention_axis {
+                        workspace.settings.retention_axis = axis;
+                    }
                     if let Some(max_members) = settings_req.max_members {
                         workspace.settings.max_members = max_members;
                     }
@@ -13,8 +13,14 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::api::shared_state::AppState;
+use crate::auth_middleware::{AdminUser, AuthenticatedUser};
 use crate::storage_helpers::{with_lock_mut, StorageLockError};
-use crate::zk_proof_engine::{CircuitType, ProofStatus, ZkProof, ZkProofEngine};
+use crate::zk_proof_engine::{
+    CircuitType, CustomCircuitInput, ProofAnchor, ProofStatus, ProofValidity, ZkProof,
+    ZkProofEngine, ZkProofError, ZkSetupArtifact,
+};
+use crate::zk_proof_worker::ProofJobTask;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 
 // API Request/Response types
 #[derive(Debug, Deserialize)]
@@ -23,6 +29,14 @@ pub struct SubmitProofRequest {
     pub circuit_input: HashMap<String, serde_json::Value>,
     pub private_inputs: HashMap<String, serde_json::Value>,
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Validity window; without `expires_at` the circuit's default applies
+    pub valid_from: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RevokeProofRequest {
+    pub reason: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -55,6 +69,11 @@ pub struct VerifyProofRequest {
     pub verification_key: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct VerifyBatchRequest {
+    pub proof_ids: Vec<Uuid>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ZkProofQueryParams {
     pub user_id: Option<String>,
@@ -72,8 +91,14 @@ pub struct ZkProofResponse {
     pub user_id: String,
     pub circuit_type: CircuitType,
     pub status: ProofStatus,
+    /// Compressed Groth16 proof, base64
     pub proof_data: Option<String>,
+    pub setup_artifact_id: Option<String>,
     pub verification_result: Option<bool>,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Stellar transaction publishing the proof's digest, once anchored
+    pub anchor: Option<ProofAnchor>,
     pub error_message: Option<String>,
     pub metadata: Option<HashMap<String, serde_json::Value>>,
     pub created_at: DateTime<Utc>,
@@ -82,13 +107,18 @@ pub struct ZkProofResponse {
 
 impl From<ZkProof> for ZkProofResponse {
     fn from(proof: ZkProof) -> Self {
+        let status = proof.status_at(Utc::now());
         Self {
             proof_id: proof.proof_id,
             user_id: proof.prover_id,
             circuit_type: proof.circuit_type,
-            status: proof.status,
-            proof_data: Some(String::from_utf8_lossy(&proof.proof_data).to_string()),
+            status,
+            proof_data: Some(BASE64.encode(&proof.proof_data)),
+            setup_artifact_id: proof.setup_artifact_id,
             verification_result: proof.verification_result.map(|vr| vr.is_valid),
+            valid_from: proof.valid_from,
+            expires_at: proof.expires_at,
+            anchor: proof.anchor,
             error_message: None, // ZkProof doesn't have error_message field
             metadata: None,      // ZkProof doesn't have metadata field
             created_at: proof.created_at,
@@ -100,26 +130,29 @@ impl From<ZkProof> for ZkProofResponse {
 // Handler functions
 async fn submit_proof(
     State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
     Json(request): Json<SubmitProofRequest>,
 ) -> Result<Json<Value>, StatusCode> {
     // Create ZK proof engine using shared storage
     let zk_engine = ZkProofEngine::new(Arc::clone(&app_state.shared_storage));
 
-    // Implementation pending
-    let user_id = "anonymous_user".to_string();
-
-    match zk_engine.submit_proof(
+    match zk_engine.submit_proof_with_validity(
         request.circuit_type,
         user_id,
         request.circuit_input,
         request.private_inputs,
         None,
+        ProofValidity {
+            valid_from: request.valid_from,
+            expires_at: request.expires_at,
+        },
     ) {
         Ok(proof_id) => Ok(Json(json!({
             "success": true,
             "proof_id": proof_id,
             "message": "Proof submitted successfully"
         }))),
+        Err(ZkProofError::InvalidInput(_)) => Err(StatusCode::BAD_REQUEST),
         Err(e) => {
             let log_result = with_lock_mut(
                 &app_state.logging,
@@ -141,19 +174,125 @@ async fn submit_proof(
     }
 }
 
+/// Queue a proof for background generation. Inputs are checked right away;
+/// poll `GET /jobs/:job_id` for the outcome.
+async fn submit_proof_job(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(request): Json<SubmitProofRequest>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    let zk_engine = ZkProofEngine::new(Arc::clone(&app_state.shared_storage));
+    let validity = ProofValidity {
+        valid_from: request.valid_from,
+        expires_at: request.expires_at,
+    };
+    let job = zk_engine
+        .create_proof_job(
+            request.circuit_type,
+            user_id,
+            &request.circuit_input,
+            &request.private_inputs,
+            &validity,
+        )
+        .map_err(zk_error_response)?;
+
+    let task = ProofJobTask {
+        job: job.clone(),
+        public_inputs: request.circuit_input,
+        private_inputs: request.private_inputs,
+        validity,
+    };
+    if let Err(e) = app_state.proof_jobs.enqueue(task) {
+        zk_engine
+            .fail_proof_job(job, &e.to_string())
+            .map_err(zk_error_response)?;
+        return Err(zk_error_response(e));
+    }
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "success": true,
+            "job": job,
+            "status_url": format!("/api/proofs/jobs/{}", job.job_id)
+        })),
+    ))
+}
+
+async fn get_proof_job(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let job = ZkProofEngine::new(Arc::clone(&app_state.shared_storage))
+        .get_proof_job(&job_id, &user_id)
+        .map_err(zk_error_response)?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": format!("Proof job {job_id} not found")})),
+            )
+        })?;
+    Ok(Json(json!({
+        "success": true,
+        "job": job
+    })))
+}
+
 async fn verify_proof(
     State(app_state): State<Arc<AppState>>,
     Json(request): Json<VerifyProofRequest>,
-) -> Result<Json<Value>, StatusCode> {
-    let zk_engine = ZkProofEngine::new(Arc::clone(&app_state.shared_storage));
+) -> Result<(StatusCode, Json<Value>), StatusCode> {
+    let zk_engine =
+        ZkProofEngine::new(Arc::clone(&app_state.shared_storage)).with_stellar_anchoring_from_env();
 
     let verifier_id = "anonymous_verifier".to_string();
 
     match zk_engine.verify_proof(request.proof_id, verifier_id) {
-        Ok(verification_result) => Ok(Json(json!({
-            "success": true,
-            "verification_result": verification_result
-        }))),
+        // Expired, revoked or not yet valid proofs are reported, not logged as errors
+        Err(
+            e @ (ZkProofError::ExpiredProof(_)
+            | ZkProofError::RevokedProof(_)
+            | ZkProofError::NotYetValid(_)),
+        ) => {
+            let error = e.to_string();
+            let (status, _) = zk_error_response(e);
+            let proof = zk_engine
+                .get_proof(&request.proof_id)
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            let revocation = zk_engine
+                .get_revocation(&request.proof_id)
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            Ok((
+                status,
+                Json(json!({
+                    "success": false,
+                    "error": error,
+                    "status": proof.map(|proof| proof.status_at(Utc::now())),
+                    "revocation": revocation
+                })),
+            ))
+        }
+        Ok(verification_result) => {
+            // Anchoring is best effort; the proof can be anchored again later
+            let mut anchor = None;
+            if verification_result.is_valid && zk_engine.anchoring_enabled() {
+                match zk_engine.anchor_proof(request.proof_id).await {
+                    Ok(recorded) => anchor = Some(recorded),
+                    Err(e) => {
+                        tracing::warn!("Failed to anchor ZK proof {}: {}", request.proof_id, e)
+                    }
+                }
+            }
+            Ok((
+                StatusCode::OK,
+                Json(json!({
+                    "success": true,
+                    "verification_result": verification_result,
+                    "anchor": anchor
+                })),
+            ))
+        }
         Err(e) => {
             let log_result = with_lock_mut(
                 &app_state.logging,
@@ -175,6 +314,124 @@ async fn verify_proof(
     }
 }
 
+async fn verify_proofs_batch(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(request): Json<VerifyBatchRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let zk_engine =
+        ZkProofEngine::new(Arc::clone(&app_state.shared_storage)).with_stellar_anchoring_from_env();
+    let report = zk_engine
+        .verify_proofs_batch(&request.proof_ids, user_id.clone())
+        .map_err(zk_error_response)?;
+
+    // One transaction per proof, so a batch is anchored in the background
+    if zk_engine.anchoring_enabled() {
+        let valid: Vec<Uuid> = report
+            .results
+            .iter()
+            .filter(|result| result.is_valid)
+            .map(|result| result.proof_id)
+            .collect();
+        tokio::spawn(async move {
+            for proof_id in valid {
+                if let Err(e) = zk_engine.anchor_proof(proof_id).await {
+                    tracing::warn!("Failed to anchor ZK proof {}: {}", proof_id, e);
+                }
+            }
+        });
+    }
+
+    tracing::info!(
+        "🔎 {} proofs verified by {} in {:.1} ms ({} valid, {} in combined checks)",
+        report.total,
+        user_id,
+        report.elapsed_ms,
+        report.valid,
+        report.batched_proofs
+    );
+    Ok(Json(json!({
+        "success": true,
+        "report": report
+    })))
+}
+
+/// Self-contained bundle for checking the proof offline with
+/// `zk_proof_engine::verify_bundle`
+async fn export_proof_bundle(
+    State(app_state): State<Arc<AppState>>,
+    Path(proof_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let zk_engine = ZkProofEngine::new(Arc::clone(&app_state.shared_storage));
+    if zk_engine
+        .get_proof(&proof_id)
+        .map_err(zk_error_response)?
+        .is_none()
+    {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": format!("Proof {proof_id} not found")})),
+        ));
+    }
+    let bundle = zk_engine
+        .export_bundle(&proof_id)
+        .map_err(zk_error_response)?;
+    Ok(Json(json!({
+        "success": true,
+        "bundle": bundle
+    })))
+}
+
+/// Withdraw a proof, e.g. when the certificate behind it is revoked. Open to
+/// the prover and members of the prover's workspace.
+async fn revoke_proof(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(proof_id): Path<Uuid>,
+    Json(request): Json<RevokeProofRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let revocation = ZkProofEngine::new(Arc::clone(&app_state.shared_storage))
+        .revoke_proof(&proof_id, &user_id, &request.reason)
+        .await
+        .map_err(zk_error_response)?;
+    Ok(Json(json!({
+        "success": true,
+        "revocation": revocation
+    })))
+}
+
+/// The revocation registry of the caller's workspace
+async fn list_revocations(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let revocations = ZkProofEngine::new(Arc::clone(&app_state.shared_storage))
+        .list_revocations(&user_id)
+        .map_err(zk_error_response)?;
+    Ok(Json(json!({
+        "success": true,
+        "count": revocations.len(),
+        "revocations": revocations
+    })))
+}
+
+/// Anchor a verified proof now, e.g. after anchoring failed during verification
+async fn anchor_proof(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(_user_id): AuthenticatedUser,
+    Path(proof_id): Path<Uuid>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let anchor = ZkProofEngine::new(Arc::clone(&app_state.shared_storage))
+        .with_stellar_anchoring_from_env()
+        .anchor_proof(proof_id)
+        .await
+        .map_err(zk_error_response)?;
+    Ok(Json(json!({
+        "success": true,
+        "anchor": anchor
+    })))
+}
+
 async fn get_proof(
     State(app_state): State<Arc<AppState>>,
     Path(proof_id): Path<Uuid>,
@@ -226,6 +483,8 @@ async fn list_proofs(
             "quality_grade" => CircuitType::QualityGrade,
             "ownership_proof" => CircuitType::OwnershipProof,
             "timestamp_freshness" => CircuitType::TimestampFreshness,
+            "cold_chain_compliance" => CircuitType::ColdChainCompliance,
+            "carbon_footprint_threshold" => CircuitType::CarbonFootprintThreshold,
             custom => CircuitType::Custom(custom.to_string()),
         };
         circuit_types = Some(vec![circuit_type]);
@@ -235,9 +494,11 @@ async fn list_proofs(
     if let Some(status_str) = params.status {
         let status = match status_str.as_str() {
             "pending" => ProofStatus::Pending,
+            "generating" => ProofStatus::Generating,
             "verified" => ProofStatus::Verified,
             "failed" => ProofStatus::Failed,
             "expired" => ProofStatus::Expired,
+            "revoked" => ProofStatus::Revoked,
             _ => return Err(StatusCode::BAD_REQUEST),
         };
         statuses = Some(vec![status]);
@@ -348,36 +609,11 @@ async fn delete_proof(
 }
 
 async fn get_circuit_templates(
-    State(_app_state): State<Arc<AppState>>,
+    State(app_state): State<Arc<AppState>>,
 ) -> Result<Json<Value>, StatusCode> {
-    // Return available circuit templates
-    let templates = json!({
-        "organic_certification": {
-            "description": "Proves organic certification status without revealing certificate details",
-            "required_inputs": ["certificate_id", "certification_body", "expiry_date"],
-            "private_inputs": ["certificate_hash", "verification_code"]
-        },
-        "pesticide_threshold": {
-            "description": "Proves pesticide levels are within acceptable thresholds",
-            "required_inputs": ["test_results", "threshold_limits", "testing_date"],
-            "private_inputs": ["raw_test_data", "lab_signature"]
-        },
-        "quality_grade": {
-            "description": "Proves product quality grade without revealing assessment details",
-            "required_inputs": ["grade_level", "assessment_date", "standards_version"],
-            "private_inputs": ["assessment_data", "inspector_signature"]
-        },
-        "ownership_proof": {
-            "description": "Proves ownership of agricultural assets",
-            "required_inputs": ["asset_id", "ownership_date", "jurisdiction"],
-            "private_inputs": ["title_deed_hash", "registration_number"]
-        },
-        "timestamp_freshness": {
-            "description": "Proves harvest/production timestamp for freshness verification",
-            "required_inputs": ["harvest_date", "location", "product_type"],
-            "private_inputs": ["timestamp_signature", "gps_coordinates"]
-        }
-    });
+    let zk_engine = ZkProofEngine::new(Arc::clone(&app_state.shared_storage));
+    let mut templates = zk_engine.get_circuit_templates();
+    templates.sort_by(|a, b| a.template_id.cmp(&b.template_id));
 
     Ok(Json(json!({
         "success": true,
@@ -385,15 +621,196 @@ async fn get_circuit_templates(
     })))
 }
 
+/// Register a circuit for the caller's workspace (Enterprise tier). Proofs
+/// then name it as `{"Custom": "<circuit_id>"}` in `circuit_type`.
+async fn register_custom_circuit(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Json(input): Json<CustomCircuitInput>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    let definition = ZkProofEngine::new(Arc::clone(&app_state.shared_storage))
+        .register_custom_circuit(&user_id, input)
+        .map_err(zk_error_response)?;
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "success": true,
+            "circuit": definition,
+            "template": definition.to_template()
+        })),
+    ))
+}
+
+async fn list_custom_circuits(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let circuits = ZkProofEngine::new(Arc::clone(&app_state.shared_storage))
+        .list_custom_circuits(&user_id)
+        .map_err(zk_error_response)?;
+    Ok(Json(json!({
+        "success": true,
+        "count": circuits.len(),
+        "circuits": circuits
+    })))
+}
+
+async fn get_custom_circuit(
+    State(app_state): State<Arc<AppState>>,
+    AuthenticatedUser(user_id): AuthenticatedUser,
+    Path(circuit_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let definition = ZkProofEngine::new(Arc::clone(&app_state.shared_storage))
+        .get_custom_circuit(&user_id, &circuit_id)
+        .map_err(zk_error_response)?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": format!("Custom circuit {circuit_id} not found")})),
+            )
+        })?;
+    Ok(Json(json!({
+        "success": true,
+        "circuit": definition,
+        "template": definition.to_template()
+    })))
+}
+
+fn zk_error_response(e: ZkProofError) -> (StatusCode, Json<Value>) {
+    let status = match &e {
+        ZkProofError::InvalidInput(_) | ZkProofError::InvalidCircuit(_) => StatusCode::BAD_REQUEST,
+        ZkProofError::ExpiredProof(_) | ZkProofError::RevokedProof(_) => StatusCode::GONE,
+        ZkProofError::NotYetValid(_) => StatusCode::CONFLICT,
+        ZkProofError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        ZkProofError::AnchoringError(_) => StatusCode::BAD_GATEWAY,
+        ZkProofError::QueueFull => StatusCode::SERVICE_UNAVAILABLE,
+        ZkProofError::StorageError(_)
+        | ZkProofError::ProofGenerationError(_)
+        | ZkProofError::VerificationError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({"error": e.to_string()})))
+}
+
+/// Setup artifacts without their proving key, which only the server needs
+fn setup_artifact_json(artifact: &ZkSetupArtifact) -> Value {
+    json!({
+        "artifact_id": artifact.artifact_id,
+        "circuit_type": artifact.circuit_type,
+        "circuit_version": artifact.circuit_version,
+        "source": artifact.source,
+        "active": artifact.active,
+        "created_at": artifact.created_at,
+        "verifying_key": BASE64.encode(&artifact.verifying_key),
+        "proving_key_bytes": artifact.proving_key.len(),
+    })
+}
+
+/// Verifying keys are public so proofs can be checked independently
+async fn list_setup_artifacts(
+    State(app_state): State<Arc<AppState>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let zk_engine = ZkProofEngine::new(Arc::clone(&app_state.shared_storage));
+    let artifacts = zk_engine
+        .list_setup_artifacts()
+        .map_err(zk_error_response)?;
+
+    Ok(Json(json!({
+        "success": true,
+        "artifacts": artifacts.iter().map(setup_artifact_json).collect::<Vec<_>>()
+    })))
+}
+
+async fn get_setup_artifact(
+    State(app_state): State<Arc<AppState>>,
+    Path(artifact_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let zk_engine = ZkProofEngine::new(Arc::clone(&app_state.shared_storage));
+    let artifact = zk_engine
+        .get_setup_artifact(&artifact_id)
+        .map_err(zk_error_response)?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": format!("Setup artifact {artifact_id} not found")})),
+            )
+        })?;
+
+    Ok(Json(json!({
+        "success": true,
+        "artifact": setup_artifact_json(&artifact)
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportSetupRequest {
+    pub circuit_type: CircuitType,
+    /// Compressed arkworks keys, base64
+    pub proving_key: String,
+    pub verifying_key: String,
+}
+
+async fn import_setup_artifact(
+    State(app_state): State<Arc<AppState>>,
+    AdminUser(admin_user_id): AdminUser,
+    Json(request): Json<ImportSetupRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let decode = |key: &str, name: &str| {
+        BASE64.decode(key).map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": format!("{name} must be base64")})),
+            )
+        })
+    };
+    let proving_key = decode(&request.proving_key, "proving_key")?;
+    let verifying_key = decode(&request.verifying_key, "verifying_key")?;
+
+    let zk_engine = ZkProofEngine::new(Arc::clone(&app_state.shared_storage));
+    let artifact = zk_engine
+        .import_setup_artifact(&request.circuit_type, proving_key, verifying_key)
+        .map_err(zk_error_response)?;
+
+    tracing::info!(
+        "🔐 Setup {} imported for {:?} by {}",
+        artifact.artifact_id,
+        artifact.circuit_type,
+        admin_user_id
+    );
+    Ok(Json(json!({
+        "success": true,
+        "artifact": setup_artifact_json(&artifact)
+    })))
+}
+
+/// Nested under the admin-guarded `/api/admin/zk-setup`
+pub fn admin_zk_setup_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/", post(import_setup_artifact))
+}
+
 // Router function
 pub fn zk_proof_routes(app_state: Arc<AppState>) -> Router {
     Router::new()
         .route("/submit", post(submit_proof))
+        .route("/jobs", post(submit_proof_job))
+        .route("/jobs/:job_id", get(get_proof_job))
         .route("/verify", post(verify_proof))
+        .route("/verify-batch", post(verify_proofs_batch))
         .route("/", get(list_proofs))
         .route("/statistics", get(get_proof_statistics))
         .route("/templates", get(get_circuit_templates))
+        .route(
+            "/circuits",
+            get(list_custom_circuits).post(register_custom_circuit),
+        )
+        .route("/circuits/:circuit_id", get(get_custom_circuit))
+        .route("/revocations", get(list_revocations))
+        .route("/setup", get(list_setup_artifacts))
+        .route("/setup/:artifact_id", get(get_setup_artifact))
         .route("/:proof_id", get(get_proof))
+        .route("/:proof_id/anchor", post(anchor_proof))
+        .route("/:proof_id/revoke", post(revoke_proof))
+        .route("/:proof_id/bundle", get(export_proof_bundle))
         .route("/:proof_id", delete(delete_proof))
+        .merge(crate::api::proof_shares::proof_share_routes())
         .with_state(app_state)
 }
@@ -92,7 +92,7 @@ fn extract_api_key(headers: &HeaderMap) -> Option<String> {
 }
 
 /// Extract client IP address from request
-fn extract_client_ip(headers: &HeaderMap) -> Option<IpAddr> {
+pub(crate) fn extract_client_ip(headers: &HeaderMap) -> Option<IpAddr> {
     // Try X-Forwarded-For header first (for proxied requests)
     if let Some(forwarded) = headers.get("x-forwarded-for") {
         if let Ok(forwarded_str) = forwarded.to_str() {
@@ -79,6 +79,9 @@ pub trait ApiKeyStorage: Send + Sync {
     /// Get all API keys for a user
     async fn get_user_api_keys(&self, user_id: Uuid) -> Result<Vec<ApiKey>, ApiKeyStorageError>;
 
+    /// Get every API key, e.g. for access reviews
+    async fn list_api_keys(&self) -> Result<Vec<ApiKey>, ApiKeyStorageError>;
+
     /// Update API key
     async fn update_api_key(&self, api_key: ApiKey) -> Result<ApiKey, ApiKeyStorageError>;
 
@@ -255,6 +258,14 @@ impl ApiKeyStorage for InMemoryApiKeyStorage {
         Ok(user_keys)
     }
 
+    async fn list_api_keys(&self) -> Result<Vec<ApiKey>, ApiKeyStorageError> {
+        let keys = self.api_keys.lock().map_err(|e| {
+            ApiKeyStorageError::LockError(format!("Failed to acquire read lock: {e}"))
+        })?;
+
+        Ok(keys.values().cloned().collect())
+    }
+
     async fn update_api_key(&self, api_key: ApiKey) -> Result<ApiKey, ApiKeyStorageError> {
         let mut keys = self.api_keys.lock().map_err(|e| {
             ApiKeyStorageError::LockError(format!("Failed to acquire write lock: {e}"))
@@ -0,0 +1,716 @@
+use crate::item_access::can_read_item;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{Attestation, Circuit, Permission, RequiredAttestation, SigningKeyOwner};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub enum AttestationError {
+    StorageError(StorageError),
+    ValidationError(String),
+    InvalidSignature(String),
+    PermissionDenied(String),
+    NotFound(String),
+}
+
+impl From<StorageError> for AttestationError {
+    fn from(err: StorageError) -> Self {
+        AttestationError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for AttestationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttestationError::StorageError(e) => write!(f, "Storage error: {e}"),
+            AttestationError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            AttestationError::InvalidSignature(e) => write!(f, "Invalid signature: {e}"),
+            AttestationError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            AttestationError::NotFound(e) => write!(f, "Not found: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AttestationError {}
+
+/// Unsigned attestation content. The attester signs the hash returned by
+/// `signing_hash` for this draft.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttestationDraft {
+    pub subject_id: String,
+    pub claim_type: String,
+    pub claim_value: Option<String>,
+    pub circuit_id: Option<Uuid>,
+    pub zk_proof_id: Option<Uuid>,
+    pub statement: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedAttestationRequest {
+    #[serde(flatten)]
+    pub draft: AttestationDraft,
+    /// Hex-encoded ed25519 public key; must be one the attester registered
+    /// as a signing key
+    pub public_key: String,
+    /// Hex-encoded ed25519 signature over the signing hash
+    pub signature: String,
+}
+
+/// Hash the attester must sign for the given draft
+pub fn signing_hash(attester_id: &str, draft: &AttestationDraft) -> String {
+    Attestation::calculate_content_hash(
+        attester_id,
+        &draft.subject_id,
+        &draft.claim_type,
+        draft.claim_value.as_deref(),
+        draft.circuit_id.as_ref(),
+        draft.zk_proof_id.as_ref(),
+        draft.statement.as_deref(),
+        draft.expires_at.as_ref(),
+    )
+}
+
+pub struct AttestationEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> AttestationEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Validate and store a signed attestation issued by `attester_id`
+    pub fn create_attestation(
+        &self,
+        attester_id: &str,
+        request: SignedAttestationRequest,
+    ) -> Result<Attestation, AttestationError> {
+        let draft = request.draft;
+
+        if draft.claim_type.trim().is_empty() {
+            return Err(AttestationError::ValidationError(
+                "claim_type cannot be empty".to_string(),
+            ));
+        }
+        if draft.subject_id == attester_id {
+            return Err(AttestationError::ValidationError(
+                "Members cannot attest to their own claims".to_string(),
+            ));
+        }
+        if draft.expires_at.is_some_and(|e| e <= Utc::now()) {
+            return Err(AttestationError::ValidationError(
+                "expires_at must be in the future".to_string(),
+            ));
+        }
+        if self.storage.get_user_account(&draft.subject_id)?.is_none() {
+            return Err(AttestationError::NotFound(format!(
+                "User {}",
+                draft.subject_id
+            )));
+        }
+
+        if let Some(circuit_id) = &draft.circuit_id {
+            let circuit = self
+                .storage
+                .get_circuit(circuit_id)?
+                .ok_or_else(|| AttestationError::NotFound(format!("Circuit {circuit_id}")))?;
+            if !circuit.is_member(attester_id) || !circuit.is_member(&draft.subject_id) {
+                return Err(AttestationError::PermissionDenied(
+                    "Attester and subject must both be members of the circuit".to_string(),
+                ));
+            }
+        }
+
+        if let Some(proof_id) = &draft.zk_proof_id {
+            let proof = self
+                .storage
+                .get_zk_proof(proof_id)?
+                .ok_or_else(|| AttestationError::NotFound(format!("ZK proof {proof_id}")))?;
+            if proof.prover_id != draft.subject_id {
+                return Err(AttestationError::ValidationError(
+                    "Linked ZK proof was not submitted by the subject".to_string(),
+                ));
+            }
+        }
+
+        let public_key = self.registered_key(attester_id, &request.public_key)?;
+        let content_hash = signing_hash(attester_id, &draft);
+        verify_signature(&public_key, &request.signature, &content_hash)?;
+
+        let attestation = Attestation {
+            attestation_id: Uuid::new_v4(),
+            attester_id: attester_id.to_string(),
+            subject_id: draft.subject_id,
+            claim_type: draft.claim_type.trim().to_string(),
+            claim_value: draft.claim_value,
+            circuit_id: draft.circuit_id,
+            zk_proof_id: draft.zk_proof_id,
+            statement: draft.statement,
+            content_hash,
+            public_key,
+            signature: request.signature.to_lowercase(),
+            issued_at: Utc::now(),
+            expires_at: draft.expires_at,
+            revoked_at: None,
+            revocation_reason: None,
+        };
+
+        self.storage.store_attestation(&attestation)?;
+        Ok(attestation)
+    }
+
+    /// The attester's active registered key named by `public_key`. Keys are
+    /// registered through event signing; only the attester's own keys count.
+    fn registered_key(
+        &self,
+        attester_id: &str,
+        public_key: &str,
+    ) -> Result<String, AttestationError> {
+        let public_key = public_key.to_lowercase();
+        let keys: Vec<String> = self
+            .storage
+            .list_event_signing_keys()?
+            .into_iter()
+            .filter(|key| {
+                key.revoked_at.is_none()
+                    && matches!(&key.owner, SigningKeyOwner::User { user_id } if user_id == attester_id)
+            })
+            .map(|key| key.public_key)
+            .collect();
+        if keys.is_empty() {
+            return Err(AttestationError::PermissionDenied(
+                "Attester has no registered signing key".to_string(),
+            ));
+        }
+        if !keys.contains(&public_key) {
+            return Err(AttestationError::PermissionDenied(
+                "public_key is not an active registered key of the attester".to_string(),
+            ));
+        }
+        Ok(public_key)
+    }
+
+    /// An attestation readable by `requester_id`, see `can_read`
+    pub fn get_attestation(
+        &self,
+        attestation_id: &Uuid,
+        requester_id: &str,
+    ) -> Result<Option<Attestation>, AttestationError> {
+        let Some(attestation) = self.storage.get_attestation(attestation_id)? else {
+            return Ok(None);
+        };
+        if !self.can_read(&attestation, requester_id)? {
+            return Err(AttestationError::PermissionDenied(
+                "No access to this attestation".to_string(),
+            ));
+        }
+        Ok(Some(attestation))
+    }
+
+    /// The attester, the subject and admins read every attestation. Others
+    /// must share the attestation's circuit with it, or any circuit with the
+    /// subject when it is not circuit-scoped, and be able to read the item a
+    /// linked proof is about.
+    fn can_read(
+        &self,
+        attestation: &Attestation,
+        requester_id: &str,
+    ) -> Result<bool, AttestationError> {
+        if attestation.attester_id == requester_id || attestation.subject_id == requester_id {
+            return Ok(true);
+        }
+        if self
+            .storage
+            .get_user_account(requester_id)?
+            .is_some_and(|account| account.is_admin)
+        {
+            return Ok(true);
+        }
+
+        if let Some(proof_id) = &attestation.zk_proof_id {
+            let dfid = self.storage.get_zk_proof(proof_id)?.and_then(|proof| {
+                proof
+                    .public_inputs
+                    .get("dfid")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            });
+            if let Some(dfid) = dfid {
+                if !can_read_item(&self.storage, &dfid, requester_id)? {
+                    return Ok(false);
+                }
+            }
+        }
+
+        Ok(match &attestation.circuit_id {
+            Some(circuit_id) => self
+                .storage
+                .get_circuit(circuit_id)?
+                .is_some_and(|circuit| circuit.is_member(requester_id)),
+            None => self.storage.list_circuits()?.iter().any(|circuit| {
+                circuit.is_member(requester_id) && circuit.is_member(&attestation.subject_id)
+            }),
+        })
+    }
+
+    fn readable(
+        &self,
+        attestations: Vec<Attestation>,
+        requester_id: &str,
+    ) -> Result<Vec<Attestation>, AttestationError> {
+        let mut readable = Vec::with_capacity(attestations.len());
+        for attestation in attestations {
+            if self.can_read(&attestation, requester_id)? {
+                readable.push(attestation);
+            }
+        }
+        Ok(readable)
+    }
+
+    /// Only the attester can revoke an attestation
+    pub fn revoke_attestation(
+        &self,
+        attestation_id: &Uuid,
+        requester_id: &str,
+        reason: Option<String>,
+    ) -> Result<Attestation, AttestationError> {
+        let mut attestation = self
+            .storage
+            .get_attestation(attestation_id)?
+            .ok_or_else(|| AttestationError::NotFound(format!("Attestation {attestation_id}")))?;
+
+        if attestation.attester_id != requester_id {
+            return Err(AttestationError::PermissionDenied(
+                "Only the attester can revoke an attestation".to_string(),
+            ));
+        }
+        if attestation.is_revoked() {
+            return Err(AttestationError::ValidationError(
+                "Attestation is already revoked".to_string(),
+            ));
+        }
+
+        attestation.revoked_at = Some(Utc::now());
+        attestation.revocation_reason = reason;
+        self.storage.store_attestation(&attestation)?;
+        Ok(attestation)
+    }
+
+    /// The subject's attestations `requester_id` can read
+    pub fn list_for_subject(
+        &self,
+        subject_id: &str,
+        requester_id: &str,
+        claim_type: Option<&str>,
+        active_only: bool,
+    ) -> Result<Vec<Attestation>, AttestationError> {
+        let attestations = self.storage.list_attestations_for_subject(subject_id)?;
+        let mut attestations = self.readable(attestations, requester_id)?;
+        filter_attestations(&mut attestations, claim_type, active_only);
+        Ok(attestations)
+    }
+
+    /// The attester's attestations `requester_id` can read
+    pub fn list_by_attester(
+        &self,
+        attester_id: &str,
+        requester_id: &str,
+        claim_type: Option<&str>,
+        active_only: bool,
+    ) -> Result<Vec<Attestation>, AttestationError> {
+        let attestations = self.storage.list_attestations_by_attester(attester_id)?;
+        let mut attestations = self.readable(attestations, requester_id)?;
+        filter_attestations(&mut attestations, claim_type, active_only);
+        Ok(attestations)
+    }
+
+    /// Required attestations of the circuit's push policy that `subject_id` does not hold
+    pub fn missing_required_attestations(
+        &self,
+        circuit: &Circuit,
+        subject_id: &str,
+    ) -> Result<Vec<RequiredAttestation>, AttestationError> {
+        Ok(missing_required_attestations(
+            &self.storage,
+            circuit,
+            subject_id,
+        )?)
+    }
+}
+
+fn filter_attestations(
+    attestations: &mut Vec<Attestation>,
+    claim_type: Option<&str>,
+    active_only: bool,
+) {
+    let now = Utc::now();
+    attestations.retain(|a| {
+        claim_type.is_none_or(|c| a.claim_type.eq_ignore_ascii_case(c))
+            && (!active_only || a.is_active_at(now))
+    });
+    attestations.sort_by_key(|a| std::cmp::Reverse(a.issued_at));
+}
+
+fn verify_signature(
+    public_key_hex: &str,
+    signature_hex: &str,
+    content_hash: &str,
+) -> Result<(), AttestationError> {
+    let key_bytes: [u8; 32] = hex::decode(public_key_hex)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| {
+            AttestationError::InvalidSignature(
+                "public_key must be a 32-byte hex-encoded ed25519 key".to_string(),
+            )
+        })?;
+    let signature_bytes: [u8; 64] = hex::decode(signature_hex)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| {
+            AttestationError::InvalidSignature(
+                "signature must be a 64-byte hex-encoded ed25519 signature".to_string(),
+            )
+        })?;
+
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| AttestationError::InvalidSignature(format!("Invalid public key: {e}")))?;
+    let signature = Signature::from_bytes(&signature_bytes);
+
+    verifying_key
+        .verify(content_hash.as_bytes(), &signature)
+        .map_err(|_| {
+            AttestationError::InvalidSignature(
+                "Signature does not match attestation content".to_string(),
+            )
+        })
+}
+
+/// Evaluate the circuit's attestation push policy for a member.
+/// Attestations scoped to another circuit, or issued by someone who is not a
+/// member of this one, do not count.
+pub fn missing_required_attestations<S: StorageBackend>(
+    storage: &S,
+    circuit: &Circuit,
+    subject_id: &str,
+) -> Result<Vec<RequiredAttestation>, StorageError> {
+    let requirements = &circuit.permissions.required_attestations;
+    if requirements.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let now = Utc::now();
+    let held: Vec<Attestation> = storage
+        .list_attestations_for_subject(subject_id)?
+        .into_iter()
+        .filter(|a| a.is_active_at(now))
+        .filter(|a| a.circuit_id.is_none_or(|c| c == circuit.circuit_id))
+        .filter(|a| circuit.is_member(&a.attester_id))
+        .collect();
+
+    Ok(requirements
+        .iter()
+        .filter(|req| {
+            !held.iter().any(|a| {
+                a.claim_type.eq_ignore_ascii_case(&req.claim_type)
+                    && (!req.require_certifier
+                        || circuit.has_permission(&a.attester_id, &Permission::Certify))
+            })
+        })
+        .cloned()
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{
+        AccountStatus, EventSigningKey, MemberRole, TierLimits, UserAccount, UserTier,
+    };
+    use ed25519_dalek::{Signer, SigningKey};
+    use std::sync::{Arc, Mutex};
+
+    fn store_user(storage: &Arc<Mutex<InMemoryStorage>>, user_id: &str) {
+        let user = UserAccount {
+            user_id: user_id.to_string(),
+            username: user_id.to_string(),
+            email: format!("{user_id}@example.com"),
+            password_hash: "hash".to_string(),
+            tier: UserTier::Basic,
+            status: AccountStatus::Active,
+            credits: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_login: None,
+            subscription: None,
+            limits: TierLimits::for_tier(&UserTier::Basic),
+            is_admin: false,
+            workspace_id: None,
+            available_adapters: None,
+            locale: None,
+        };
+        storage.store_user_account(&user).unwrap();
+    }
+
+    fn register_key(storage: &Arc<Mutex<InMemoryStorage>>, user_id: &str, key: &SigningKey) {
+        storage
+            .store_event_signing_key(&EventSigningKey {
+                key_id: Uuid::new_v4(),
+                owner: SigningKeyOwner::User {
+                    user_id: user_id.to_string(),
+                },
+                public_key: hex::encode(key.verifying_key().to_bytes()),
+                registered_by: user_id.to_string(),
+                registered_at: Utc::now(),
+                revoked_at: None,
+            })
+            .unwrap();
+    }
+
+    fn signed(
+        key: &SigningKey,
+        attester_id: &str,
+        draft: AttestationDraft,
+    ) -> SignedAttestationRequest {
+        let hash = signing_hash(attester_id, &draft);
+        SignedAttestationRequest {
+            draft,
+            public_key: hex::encode(key.verifying_key().to_bytes()),
+            signature: hex::encode(key.sign(hash.as_bytes()).to_bytes()),
+        }
+    }
+
+    fn organic_draft(subject_id: &str) -> AttestationDraft {
+        AttestationDraft {
+            subject_id: subject_id.to_string(),
+            claim_type: "organic".to_string(),
+            claim_value: None,
+            circuit_id: None,
+            zk_proof_id: None,
+            statement: Some("Inspected 2024 harvest".to_string()),
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_create_and_revoke_attestation() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        store_user(&storage, "farm-1");
+        let engine = AttestationEngine::new(Arc::clone(&storage));
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        register_key(&storage, "certifier-1", &key);
+
+        let attestation = engine
+            .create_attestation(
+                "certifier-1",
+                signed(&key, "certifier-1", organic_draft("farm-1")),
+            )
+            .unwrap();
+        assert!(attestation.is_active());
+
+        let held = engine
+            .list_for_subject("farm-1", "farm-1", Some("organic"), true)
+            .unwrap();
+        assert_eq!(held.len(), 1);
+
+        assert!(matches!(
+            engine.revoke_attestation(&attestation.attestation_id, "farm-1", None),
+            Err(AttestationError::PermissionDenied(_))
+        ));
+        engine
+            .revoke_attestation(&attestation.attestation_id, "certifier-1", None)
+            .unwrap();
+        assert!(engine
+            .list_for_subject("farm-1", "farm-1", None, true)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_rejects_tampered_signature() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        store_user(&storage, "farm-1");
+        let engine = AttestationEngine::new(Arc::clone(&storage));
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        register_key(&storage, "certifier-1", &key);
+
+        let mut request = signed(&key, "certifier-1", organic_draft("farm-1"));
+        request.draft.claim_type = "fair-trade".to_string();
+
+        assert!(matches!(
+            engine.create_attestation("certifier-1", request),
+            Err(AttestationError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_push_policy_requires_certifier_attestation() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        store_user(&storage, "farm-1");
+        let engine = AttestationEngine::new(Arc::clone(&storage));
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        register_key(&storage, "peer-1", &key);
+        register_key(&storage, "owner-1", &key);
+
+        let mut circuit = Circuit::new(
+            "Organic".to_string(),
+            "Organic supply".to_string(),
+            "owner-1".to_string(),
+        );
+        circuit.add_member("farm-1".to_string(), MemberRole::Member);
+        circuit.add_member("peer-1".to_string(), MemberRole::Member);
+        circuit.permissions.required_attestations = vec![RequiredAttestation {
+            claim_type: "organic".to_string(),
+            require_certifier: true,
+        }];
+        storage.store_circuit(&circuit).unwrap();
+
+        assert_eq!(
+            engine
+                .missing_required_attestations(&circuit, "farm-1")
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // A plain member's endorsement does not satisfy a certifier requirement
+        engine
+            .create_attestation("peer-1", signed(&key, "peer-1", organic_draft("farm-1")))
+            .unwrap();
+        assert_eq!(
+            engine
+                .missing_required_attestations(&circuit, "farm-1")
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // The owner holds the Certify permission
+        engine
+            .create_attestation("owner-1", signed(&key, "owner-1", organic_draft("farm-1")))
+            .unwrap();
+        assert!(engine
+            .missing_required_attestations(&circuit, "farm-1")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_rejects_unregistered_attester_key() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        store_user(&storage, "farm-1");
+        let engine = AttestationEngine::new(Arc::clone(&storage));
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+
+        // No key registered at all
+        assert!(matches!(
+            engine.create_attestation(
+                "certifier-1",
+                signed(&key, "certifier-1", organic_draft("farm-1"))
+            ),
+            Err(AttestationError::PermissionDenied(_))
+        ));
+
+        // A validly signed request with a key registered to someone else
+        register_key(&storage, "other-1", &key);
+        register_key(&storage, "certifier-1", &SigningKey::from_bytes(&[8u8; 32]));
+        assert!(matches!(
+            engine.create_attestation(
+                "certifier-1",
+                signed(&key, "certifier-1", organic_draft("farm-1"))
+            ),
+            Err(AttestationError::PermissionDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_non_member_attestation_does_not_satisfy_policy() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        store_user(&storage, "farm-1");
+        let engine = AttestationEngine::new(Arc::clone(&storage));
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        register_key(&storage, "outsider-1", &key);
+
+        let mut circuit = Circuit::new(
+            "Organic".to_string(),
+            "Organic supply".to_string(),
+            "owner-1".to_string(),
+        );
+        circuit.add_member("farm-1".to_string(), MemberRole::Member);
+        circuit.permissions.required_attestations = vec![RequiredAttestation {
+            claim_type: "organic".to_string(),
+            require_certifier: false,
+        }];
+        storage.store_circuit(&circuit).unwrap();
+
+        engine
+            .create_attestation(
+                "outsider-1",
+                signed(&key, "outsider-1", organic_draft("farm-1")),
+            )
+            .unwrap();
+        assert_eq!(
+            engine
+                .missing_required_attestations(&circuit, "farm-1")
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_attestation_reads_require_shared_circuit() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        store_user(&storage, "farm-1");
+        store_user(&storage, "stranger-1");
+        store_user(&storage, "peer-1");
+        let engine = AttestationEngine::new(Arc::clone(&storage));
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        register_key(&storage, "certifier-1", &key);
+
+        let mut circuit = Circuit::new(
+            "Organic".to_string(),
+            "Organic supply".to_string(),
+            "owner-1".to_string(),
+        );
+        circuit.add_member("farm-1".to_string(), MemberRole::Member);
+        circuit.add_member("peer-1".to_string(), MemberRole::Member);
+        storage.store_circuit(&circuit).unwrap();
+
+        let attestation = engine
+            .create_attestation(
+                "certifier-1",
+                signed(&key, "certifier-1", organic_draft("farm-1")),
+            )
+            .unwrap();
+
+        assert!(matches!(
+            engine.get_attestation(&attestation.attestation_id, "stranger-1"),
+            Err(AttestationError::PermissionDenied(_))
+        ));
+        assert!(engine
+            .list_for_subject("farm-1", "stranger-1", None, false)
+            .unwrap()
+            .is_empty());
+        assert!(engine
+            .list_by_attester("certifier-1", "stranger-1", None, false)
+            .unwrap()
+            .is_empty());
+
+        assert!(engine
+            .get_attestation(&attestation.attestation_id, "peer-1")
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            engine
+                .list_for_subject("farm-1", "peer-1", None, false)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
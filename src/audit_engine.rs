@@ -1,8 +1,11 @@
+use crate::anonymization_engine::anonymization_finding;
+use crate::audit_query_language::parse_audit_expression;
 use crate::storage::{StorageBackend, StorageError};
 use crate::types::{
-    AuditDashboardMetrics, AuditEvent, AuditEventMetadata, AuditEventType, AuditOutcome,
-    AuditQuery, AuditSeverity, ComplianceInfo, ComplianceReport, ComplianceReportType,
-    ComplianceScope, ExportFormat, IncidentCategory, SecurityIncident,
+    AuditDashboardMetrics, AuditEvent, AuditEventMetadata, AuditEventType, AuditFilterExpr,
+    AuditOutcome, AuditQuery, AuditSeverity, ComplianceInfo, ComplianceReport,
+    ComplianceReportType, ComplianceScope, ExportFormat, IncidentCategory, SavedAuditQuery,
+    SecurityIncident,
 };
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
@@ -13,6 +16,8 @@ pub enum AuditError {
     StorageError(StorageError),
     ValidationError(String),
     ProcessingError(String),
+    NotFound(String),
+    PermissionDenied(String),
 }
 
 impl From<StorageError> for AuditError {
@@ -27,6 +32,8 @@ impl std::fmt::Display for AuditError {
             AuditError::StorageError(e) => write!(f, "Storage error: {e}"),
             AuditError::ValidationError(e) => write!(f, "Validation error: {e}"),
             AuditError::ProcessingError(e) => write!(f, "Processing error: {e}"),
+            AuditError::NotFound(e) => write!(f, "Not found: {e}"),
+            AuditError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
         }
     }
 }
@@ -285,6 +292,105 @@ impl<S: StorageBackend + 'static> AuditEngine<S> {
         Ok(self.storage.query_audit_events(query)?)
     }
 
+    // Query audit events with a query-language expression layered on the fixed filters
+    pub fn query_events_with_expression(
+        &self,
+        query: &AuditQuery,
+        expression: &str,
+    ) -> Result<Vec<AuditEvent>, AuditError> {
+        let parsed = parse_audit_expression(expression)
+            .map_err(|e| AuditError::ValidationError(e.to_string()))?;
+
+        let mut query = query.clone();
+        query.filter = Some(match query.filter.take() {
+            Some(existing) => AuditFilterExpr::And(Box::new(existing), Box::new(parsed)),
+            None => parsed,
+        });
+        Ok(self.storage.query_audit_events(&query)?)
+    }
+
+    // Saved audit queries
+    pub fn create_saved_query(
+        &self,
+        owner_id: &str,
+        name: String,
+        description: Option<String>,
+        expression: String,
+    ) -> Result<SavedAuditQuery, AuditError> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(AuditError::ValidationError(
+                "Saved query name cannot be empty".to_string(),
+            ));
+        }
+        parse_audit_expression(&expression)
+            .map_err(|e| AuditError::ValidationError(e.to_string()))?;
+
+        let now = Utc::now();
+        let saved = SavedAuditQuery {
+            query_id: Uuid::new_v4(),
+            owner_id: owner_id.to_string(),
+            name,
+            description,
+            expression,
+            created_at: now,
+            updated_at: now,
+            last_run_at: None,
+            run_count: 0,
+        };
+        self.storage.store_saved_audit_query(&saved)?;
+        Ok(saved)
+    }
+
+    pub fn get_saved_query(
+        &self,
+        query_id: &Uuid,
+        requester_id: &str,
+    ) -> Result<SavedAuditQuery, AuditError> {
+        let saved = self
+            .storage
+            .get_saved_audit_query(query_id)?
+            .ok_or_else(|| AuditError::NotFound(format!("Saved query {query_id}")))?;
+        if saved.owner_id != requester_id {
+            return Err(AuditError::PermissionDenied(
+                "Saved queries are only visible to their owner".to_string(),
+            ));
+        }
+        Ok(saved)
+    }
+
+    pub fn list_saved_queries(&self, owner_id: &str) -> Result<Vec<SavedAuditQuery>, AuditError> {
+        let mut queries = self.storage.list_saved_audit_queries(owner_id)?;
+        queries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(queries)
+    }
+
+    pub fn delete_saved_query(
+        &self,
+        query_id: &Uuid,
+        requester_id: &str,
+    ) -> Result<(), AuditError> {
+        self.get_saved_query(query_id, requester_id)?;
+        Ok(self.storage.delete_saved_audit_query(query_id)?)
+    }
+
+    /// Run a saved query, optionally narrowed by fixed filters, and record the run
+    pub fn run_saved_query(
+        &self,
+        query_id: &Uuid,
+        requester_id: &str,
+        base_query: &AuditQuery,
+    ) -> Result<(SavedAuditQuery, Vec<AuditEvent>), AuditError> {
+        let mut saved = self.get_saved_query(query_id, requester_id)?;
+        let events = self.query_events_with_expression(base_query, &saved.expression)?;
+
+        saved.last_run_at = Some(Utc::now());
+        saved.run_count += 1;
+        self.storage.store_saved_audit_query(&saved)?;
+
+        Ok((saved, events))
+    }
+
     // Get events by user
     pub fn get_user_events(&self, user_id: &str) -> Result<Vec<AuditEvent>, AuditError> {
         Ok(self.storage.get_audit_events_by_user(user_id)?)
@@ -404,13 +510,17 @@ impl<S: StorageBackend + 'static> AuditEngine<S> {
             event_types: vec![AuditEventType::Data, AuditEventType::Access],
             regulations: vec!["GDPR".to_string()],
         };
-        let report = ComplianceReport::new(
+        let mut report = ComplianceReport::new(
             ComplianceReportType::GDPR,
             start_date,
             end_date,
             scope,
             ExportFormat::Json,
         );
+        // Records kept for traceability but stripped of personal data
+        if let Some(finding) = anonymization_finding(&self.storage, start_date, end_date)? {
+            report.add_finding(finding);
+        }
 
         self.storage.store_compliance_report(&report)?;
         Ok(report)
@@ -520,6 +630,76 @@ mod tests {
     use crate::storage::InMemoryStorage;
     use std::sync::Arc;
 
+    fn empty_query() -> AuditQuery {
+        AuditQuery {
+            user_id: None,
+            event_types: None,
+            actions: None,
+            resources: None,
+            outcomes: None,
+            severities: None,
+            start_date: None,
+            end_date: None,
+            compliance: None,
+            limit: None,
+            offset: None,
+            sort_by: None,
+            sort_order: None,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn test_saved_query_runs_expression() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let audit_engine = AuditEngine::new(storage.clone());
+
+        for (action, severity) in [
+            ("login_failed", AuditSeverity::High),
+            ("login_failed", AuditSeverity::Low),
+            ("export", AuditSeverity::Critical),
+        ] {
+            audit_engine
+                .log_event(
+                    "user123".to_string(),
+                    AuditEventType::Security,
+                    action.to_string(),
+                    "authentication_system".to_string(),
+                    AuditOutcome::Failure,
+                    severity,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+
+        assert!(audit_engine
+            .create_saved_query("auditor", "Bad".to_string(), None, "severity >".to_string())
+            .is_err());
+
+        let saved = audit_engine
+            .create_saved_query(
+                "auditor",
+                "Failed logins".to_string(),
+                None,
+                "action ~ login AND severity >= high".to_string(),
+            )
+            .unwrap();
+
+        let (saved, events) = audit_engine
+            .run_saved_query(&saved.query_id, "auditor", &empty_query())
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(saved.run_count, 1);
+        assert!(saved.last_run_at.is_some());
+
+        assert!(matches!(
+            audit_engine.run_saved_query(&saved.query_id, "someone-else", &empty_query()),
+            Err(AuditError::PermissionDenied(_))
+        ));
+    }
+
     #[test]
     fn test_log_event() {
         let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
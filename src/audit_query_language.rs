@@ -0,0 +1,421 @@
+//! Small expression language for audit event queries.
+//!
+//! ```text
+//! severity >= high AND (action ~ login OR outcome = blocked) NOT user = system
+//! details.ip_country = "BR" "export"
+//! ```
+//!
+//! * comparisons: `field op value` with `=`, `!=`, `~` (contains), `>`, `>=`, `<`, `<=`
+//! * boolean logic: `AND`, `OR`, `NOT` and parentheses; adjacent terms are ANDed
+//! * a bare word or quoted string on its own is free text matched against action and resource
+//!
+//! Fields: `user`, `type`, `action`, `resource`, `resource_id`, `outcome`, `severity`,
+//! `timestamp`, `ip` and `details.<key>`. Severity and timestamp compare by order;
+//! timestamps accept RFC 3339 or `YYYY-MM-DD`.
+
+use crate::types::{AuditCompareOp, AuditField, AuditFilterExpr};
+
+const MAX_EXPRESSION_LENGTH: usize = 2000;
+const MAX_NESTING_DEPTH: usize = 32;
+
+const EVENT_TYPES: &[&str] = &["security", "data", "access", "compliance", "system", "user"];
+const OUTCOMES: &[&str] = &["success", "failure", "warning", "blocked"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditQueryParseError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for AuditQueryParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at position {})", self.message, self.position)
+    }
+}
+
+impl std::error::Error for AuditQueryParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(AuditCompareOp),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+/// Parse an expression into a filter that storage backends evaluate per event
+pub fn parse_audit_expression(input: &str) -> Result<AuditFilterExpr, AuditQueryParseError> {
+    if input.len() > MAX_EXPRESSION_LENGTH {
+        return Err(error(
+            0,
+            format!("Expression cannot exceed {MAX_EXPRESSION_LENGTH} characters"),
+        ));
+    }
+
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Err(error(0, "Expression is empty"));
+    }
+
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        depth: 0,
+        input_len: input.len(),
+    };
+    let expr = parser.parse_or()?;
+    if let Some((position, token)) = parser.tokens.get(parser.pos) {
+        return Err(error(*position, format!("Unexpected token {token:?}")));
+    }
+    Ok(expr)
+}
+
+fn error(position: usize, message: impl Into<String>) -> AuditQueryParseError {
+    AuditQueryParseError {
+        position,
+        message: message.into(),
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, AuditQueryParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(pos, ch)) = chars.peek() {
+        match ch {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push((pos, Token::LParen));
+            }
+            ')' => {
+                chars.next();
+                tokens.push((pos, Token::RParen));
+            }
+            '=' | '~' => {
+                chars.next();
+                let op = if ch == '=' {
+                    AuditCompareOp::Eq
+                } else {
+                    AuditCompareOp::Contains
+                };
+                tokens.push((pos, Token::Op(op)));
+            }
+            '!' | '>' | '<' => {
+                chars.next();
+                let has_eq = chars.peek().is_some_and(|&(_, c)| c == '=');
+                if has_eq {
+                    chars.next();
+                }
+                let op = match (ch, has_eq) {
+                    ('!', true) => AuditCompareOp::NotEq,
+                    ('>', false) => AuditCompareOp::Gt,
+                    ('>', true) => AuditCompareOp::Gte,
+                    ('<', false) => AuditCompareOp::Lt,
+                    ('<', true) => AuditCompareOp::Lte,
+                    _ => return Err(error(pos, "Expected '!='")),
+                };
+                tokens.push((pos, Token::Op(op)));
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut value = String::new();
+                let mut closed = false;
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            if let Some((_, escaped)) = chars.next() {
+                                value.push(escaped);
+                            }
+                        }
+                        c if c == ch => {
+                            closed = true;
+                            break;
+                        }
+                        c => value.push(c),
+                    }
+                }
+                if !closed {
+                    return Err(error(pos, "Unterminated string"));
+                }
+                tokens.push((pos, Token::Quoted(value)));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if c.is_whitespace() || "()=~!<>\"'".contains(c) {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                let token = match word.to_uppercase().as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => Token::Word(word),
+                };
+                tokens.push((pos, token));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    depth: usize,
+    input_len: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, t)| t)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map(|(p, _)| *p)
+            .unwrap_or(self.input_len)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(_, t)| t.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<AuditFilterExpr, AuditQueryParseError> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            let right = self.parse_and()?;
+            left = AuditFilterExpr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<AuditFilterExpr, AuditQueryParseError> {
+        let mut left = self.parse_unary()?;
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.next();
+                }
+                // Adjacent terms are an implicit AND
+                Some(Token::Word(_) | Token::Quoted(_) | Token::Not | Token::LParen) => {}
+                _ => break,
+            }
+            let right = self.parse_unary()?;
+            left = AuditFilterExpr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<AuditFilterExpr, AuditQueryParseError> {
+        let position = self.position();
+        match self.next() {
+            Some(Token::Not) => {
+                let inner = self.nested(|p| p.parse_unary())?;
+                Ok(AuditFilterExpr::Not(Box::new(inner)))
+            }
+            Some(Token::LParen) => {
+                let inner = self.nested(|p| p.parse_or())?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => Err(error(position, "Unclosed parenthesis")),
+                }
+            }
+            Some(Token::Quoted(text)) => Ok(AuditFilterExpr::Text(text)),
+            Some(Token::Word(word)) => match self.peek() {
+                Some(Token::Op(op)) => {
+                    let op = *op;
+                    self.next();
+                    let value_position = self.position();
+                    let value = match self.next() {
+                        Some(Token::Word(v)) | Some(Token::Quoted(v)) => v,
+                        _ => return Err(error(value_position, "Expected a value")),
+                    };
+                    comparison(position, &word, op, value)
+                }
+                _ => Ok(AuditFilterExpr::Text(word)),
+            },
+            Some(token) => Err(error(position, format!("Unexpected token {token:?}"))),
+            None => Err(error(position, "Unexpected end of expression")),
+        }
+    }
+
+    fn nested(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<AuditFilterExpr, AuditQueryParseError>,
+    ) -> Result<AuditFilterExpr, AuditQueryParseError> {
+        self.depth += 1;
+        if self.depth > MAX_NESTING_DEPTH {
+            return Err(error(self.position(), "Expression is nested too deeply"));
+        }
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+}
+
+fn parse_field(name: &str) -> Option<AuditField> {
+    if let Some(key) = name.strip_prefix("details.") {
+        return (!key.is_empty()).then(|| AuditField::Detail(key.to_string()));
+    }
+    Some(match name.to_lowercase().as_str() {
+        "user" | "user_id" => AuditField::UserId,
+        "type" | "event_type" => AuditField::EventType,
+        "action" => AuditField::Action,
+        "resource" => AuditField::Resource,
+        "resource_id" => AuditField::ResourceId,
+        "outcome" => AuditField::Outcome,
+        "severity" => AuditField::Severity,
+        "timestamp" | "time" => AuditField::Timestamp,
+        "ip" | "ip_address" => AuditField::IpAddress,
+        _ => return None,
+    })
+}
+
+/// Build a comparison, rejecting values and operators the field cannot support
+fn comparison(
+    position: usize,
+    field_name: &str,
+    op: AuditCompareOp,
+    value: String,
+) -> Result<AuditFilterExpr, AuditQueryParseError> {
+    let field = parse_field(field_name)
+        .ok_or_else(|| error(position, format!("Unknown field '{field_name}'")))?;
+    let ordered = matches!(
+        op,
+        AuditCompareOp::Gt | AuditCompareOp::Gte | AuditCompareOp::Lt | AuditCompareOp::Lte
+    );
+
+    match &field {
+        AuditField::Severity => {
+            if op == AuditCompareOp::Contains {
+                return Err(error(position, "severity does not support '~'"));
+            }
+            if AuditFilterExpr::parse_severity(&value).is_none() {
+                return Err(error(position, format!("Unknown severity '{value}'")));
+            }
+        }
+        AuditField::Timestamp => {
+            if op == AuditCompareOp::Contains {
+                return Err(error(position, "timestamp does not support '~'"));
+            }
+            if AuditFilterExpr::parse_timestamp(&value).is_none() {
+                return Err(error(position, format!("Invalid timestamp '{value}'")));
+            }
+        }
+        AuditField::EventType | AuditField::Outcome => {
+            let (allowed, what) = if field == AuditField::EventType {
+                (EVENT_TYPES, "event type")
+            } else {
+                (OUTCOMES, "outcome")
+            };
+            if ordered || op == AuditCompareOp::Contains {
+                return Err(error(
+                    position,
+                    format!("{field_name} only supports '=' and '!='"),
+                ));
+            }
+            if !allowed.contains(&value.to_lowercase().as_str()) {
+                return Err(error(position, format!("Unknown {what} '{value}'")));
+            }
+        }
+        _ => {}
+    }
+
+    Ok(AuditFilterExpr::Compare { field, op, value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AuditEvent, AuditEventType, AuditOutcome, AuditSeverity};
+
+    fn event(action: &str, outcome: AuditOutcome, severity: AuditSeverity) -> AuditEvent {
+        AuditEvent::new(
+            "user-1".to_string(),
+            AuditEventType::Security,
+            action.to_string(),
+            "circuit/abc".to_string(),
+            outcome,
+            severity,
+        )
+    }
+
+    #[test]
+    fn test_and_binds_tighter_than_or() {
+        let expr =
+            parse_audit_expression("action = a OR action = b AND outcome = failure").unwrap();
+        assert!(matches!(expr, AuditFilterExpr::Or(_, _)));
+
+        let login = event("a", AuditOutcome::Success, AuditSeverity::Low);
+        assert!(expr.matches(&login));
+    }
+
+    #[test]
+    fn test_comparisons_and_free_text() {
+        let expr = parse_audit_expression(
+            "severity >= high AND (action ~ login OR outcome = blocked) NOT user = system",
+        )
+        .unwrap();
+
+        assert!(expr.matches(&event(
+            "user_login",
+            AuditOutcome::Failure,
+            AuditSeverity::Critical
+        )));
+        assert!(!expr.matches(&event(
+            "user_login",
+            AuditOutcome::Failure,
+            AuditSeverity::Medium
+        )));
+
+        let text = parse_audit_expression("\"circuit/\" type = security").unwrap();
+        assert!(text.matches(&event("push", AuditOutcome::Success, AuditSeverity::Low)));
+    }
+
+    #[test]
+    fn test_detail_and_missing_fields() {
+        let mut e = event("export", AuditOutcome::Success, AuditSeverity::Low);
+        e.add_detail("country".to_string(), serde_json::json!("BR"));
+
+        assert!(parse_audit_expression("details.country = br")
+            .unwrap()
+            .matches(&e));
+        assert!(parse_audit_expression("resource_id != x")
+            .unwrap()
+            .matches(&e));
+        assert!(!parse_audit_expression("resource_id = x")
+            .unwrap()
+            .matches(&e));
+    }
+
+    #[test]
+    fn test_rejects_invalid_expressions() {
+        for input in [
+            "",
+            "severity = extreme",
+            "colour = red",
+            "outcome > success",
+            "(action = a",
+            "timestamp >= yesterday",
+            "action = \"open",
+            "action =",
+        ] {
+            assert!(parse_audit_expression(input).is_err(), "{input}");
+        }
+    }
+}
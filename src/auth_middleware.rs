@@ -12,6 +12,9 @@ use std::sync::Arc;
 
 use crate::api::auth::Claims;
 use crate::api::shared_state::AppState;
+use crate::storage::StorageBackend;
+use crate::storage_helpers::{with_storage, StorageLockError};
+use crate::types::{AccountStatus, UserAccount};
 
 /// Extractor for authenticated user ID from JWT claims or API key
 /// Use this in handlers to get the authenticated user's ID automatically
@@ -82,6 +85,87 @@ pub async fn jwt_auth_middleware(
     Ok(next.run(request).await)
 }
 
+/// Authenticated user holding the admin role, inserted by [`admin_auth_middleware`].
+/// Handlers behind that middleware take this instead of re-checking the role.
+#[derive(Debug, Clone)]
+pub struct AdminUser(pub String);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AdminUser
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<serde_json::Value>);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<AdminUser>().cloned().ok_or((
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": "Admin privileges required"})),
+        ))
+    }
+}
+
+/// Admin role guard for `/api/admin`
+/// Runs after JWT / API key authentication; rejects callers that are not active
+/// admins and injects [`AdminUser`] on success
+pub async fn admin_auth_middleware(
+    State(state): State<Arc<AppState>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
+    let (mut parts, body) = request.into_parts();
+    let AuthenticatedUser(user_id) = AuthenticatedUser::from_request_parts(&mut parts, &()).await?;
+    request = Request::from_parts(parts, body);
+
+    let user = with_storage(
+        &state.shared_storage,
+        "auth::admin_auth_middleware",
+        |storage| Ok(storage.get_user_account(&user_id)?),
+    )
+    .map_err(|e| match e {
+        StorageLockError::Timeout => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"error": "Storage timeout, please retry"})),
+        ),
+        StorageLockError::Other(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("Database error: {}", err)})),
+        ),
+    })?;
+    authorize_admin(user.as_ref())?;
+
+    request.extensions_mut().insert(AdminUser(user_id));
+    Ok(next.run(request).await)
+}
+
+/// Role check behind [`admin_auth_middleware`]: unknown users are
+/// unauthenticated, non-admins and suspended or banned admins are forbidden
+fn authorize_admin(
+    user: Option<&UserAccount>,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    let user = user.ok_or((
+        StatusCode::UNAUTHORIZED,
+        Json(json!({"error": "User not found"})),
+    ))?;
+
+    if !user.is_admin {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": "Admin privileges required"})),
+        ));
+    }
+    if matches!(
+        user.status,
+        AccountStatus::Suspended | AccountStatus::Banned
+    ) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": "Admin account is not active"})),
+        ));
+    }
+    Ok(())
+}
+
 /// Extract JWT token from Authorization header (Bearer token)
 fn extract_jwt_token(request: &Request) -> Option<String> {
     let auth_header = request.headers().get("Authorization")?.to_str().ok()?;
@@ -89,3 +173,60 @@ fn extract_jwt_token(request: &Request) -> Option<String> {
     // Support "Bearer <token>" format
     auth_header.strip_prefix("Bearer ").map(|s| s.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{TierLimits, UserTier};
+    use chrono::Utc;
+
+    fn user(is_admin: bool, status: AccountStatus) -> UserAccount {
+        UserAccount {
+            user_id: "user-1".to_string(),
+            username: "user-1".to_string(),
+            email: "user-1@example.com".to_string(),
+            password_hash: "hash".to_string(),
+            tier: UserTier::Admin,
+            status,
+            credits: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_login: None,
+            subscription: None,
+            limits: TierLimits::for_tier(&UserTier::Admin),
+            is_admin,
+            workspace_id: None,
+            available_adapters: None,
+            locale: None,
+        }
+    }
+
+    fn status_of(user: Option<&UserAccount>) -> Option<StatusCode> {
+        authorize_admin(user).err().map(|(status, _)| status)
+    }
+
+    #[test]
+    fn test_admin_guard_rejects_non_admin() {
+        let user = user(false, AccountStatus::Active);
+        assert_eq!(status_of(Some(&user)), Some(StatusCode::FORBIDDEN));
+    }
+
+    #[test]
+    fn test_admin_guard_rejects_suspended_and_banned_admins() {
+        for status in [AccountStatus::Suspended, AccountStatus::Banned] {
+            let user = user(true, status);
+            assert_eq!(status_of(Some(&user)), Some(StatusCode::FORBIDDEN));
+        }
+    }
+
+    #[test]
+    fn test_admin_guard_rejects_unknown_user() {
+        assert_eq!(status_of(None), Some(StatusCode::UNAUTHORIZED));
+    }
+
+    #[test]
+    fn test_admin_guard_admits_active_admin() {
+        let user = user(true, AccountStatus::Active);
+        assert_eq!(status_of(Some(&user)), None);
+    }
+}
@@ -1,19 +1,30 @@
-use axum::{http::StatusCode, middleware, response::Json, routing::get, Router};
+use axum::{http::StatusCode, middleware, response::Json, routing::get, Router, ServiceExt};
 use serde_json::{json, Value};
 use std::net::SocketAddr;
+use tower::Layer;
 use tower_http::cors::CorsLayer;
 use tower_http::services::ServeDir;
 use tower_http::trace::TraceLayer;
 use tracing::{info, Level};
 
 use defarm_engine::api::{
-    activity_routes, adapter_routes, admin_routes, api_key_routes, audit_routes, auth_routes,
-    circuit_routes, create_public_snapshot_routes, create_snapshot_routes, event_routes,
-    get_indexing_progress, get_item_timeline, get_timeline_entry, item_routes, merkle_routes,
-    notifications_rest_routes, notifications_ws_route, public_merkle_routes,
-    public_storage_history_routes, receipt_routes, shared_state::AppState, storage_history_routes,
-    test_blockchain_routes, user_activity_routes, user_credits_routes, workspace_routes,
-    zk_proof_routes, TimelineState,
+    access_review_routes, activity_routes, adapter_routes, admin_routes, anchoring_routes,
+    announcement_routes, api_key_routes, api_version_middleware, attestation_routes, audit_routes,
+    auth_routes, change_feed_routes, circuit_directory_routes, circuit_routes, comment_routes,
+    connector_routes, create_public_snapshot_routes, create_snapshot_routes,
+    dashboard_metric_routes, data_export_routes, data_quality_routes, dedup_sharing_routes,
+    dedup_strategy_routes, device_backup_routes, disclosure_routes, engagement_routes,
+    enrichment_policy_routes, event_routes, federation_routes, get_indexing_progress,
+    get_item_timeline, get_timeline_entry, item_routes, lifecycle_routes,
+    maintenance_mode_middleware, merkle_routes, notarization_routes, notifications_rest_routes,
+    notifications_ws_route, organization_routes, partner_access_routes, partner_token_routes,
+    preview_routes, provenance_routes, public_disclosure_routes, public_item_routes,
+    public_merkle_routes, public_notarization_routes, public_proof_share_routes,
+    public_storage_history_routes, receipt_routes, review_queue_routes, sensor_routes,
+    shared_state::AppState, signing_key_routes, sla_tracking_middleware, status_routes,
+    storage_history_routes, stream_routes, test_blockchain_routes, usage_tracking_middleware,
+    user_activity_routes, user_credits_routes, verification_rule_routes, workspace_bucket_routes,
+    workspace_routes, zk_proof_routes, ApiVersion, TimelineState, VersioningConfig,
 };
 use defarm_engine::api_key_middleware::ApiKeyMiddlewareState;
 use defarm_engine::api_key_storage::InMemoryApiKeyStorage;
@@ -103,6 +114,22 @@ async fn async_main() {
         }
     };
 
+    // Device backups are the only copy of a lost device's data
+    match defarm_engine::device_backup_engine::configured_backup_dir() {
+        Ok(dir) => match std::fs::create_dir_all(&dir) {
+            Ok(()) => info!("💾 Device backups stored in {}", dir.display()),
+            Err(e) => {
+                tracing::error!("❌ Cannot create {}: {}", dir.display(), e);
+                std::process::exit(1);
+            }
+        },
+        Err(e) => {
+            tracing::error!("❌ {}", e);
+            tracing::error!("❌ Set DEVICE_BACKUP_DIR to durable storage shared by all replicas");
+            std::process::exit(1);
+        }
+    }
+
     info!("🗄️  Initializing PostgreSQL as primary storage backend...");
 
     // Create PostgreSQL persistence instance
@@ -263,6 +290,200 @@ async fn async_main() {
         });
     }
 
+    // Background scheduler for legacy system connectors
+    defarm_engine::connectors::ConnectorEngine::spawn_scheduler(
+        app_state.shared_storage.clone(),
+        std::time::Duration::from_secs(30),
+    );
+
+    // Background dispatcher for circuit change-feed subscriptions
+    defarm_engine::change_feed_engine::ChangeFeedEngine::spawn_dispatcher(
+        app_state.shared_storage.clone(),
+        std::time::Duration::from_secs(5),
+    );
+
+    // Background sync with federation peers (only with FEDERATION_NODE_ID/KEY set)
+    defarm_engine::federation_engine::FederationEngine::spawn_sync_worker(
+        app_state.shared_storage.clone(),
+        std::time::Duration::from_secs(
+            std::env::var("FEDERATION_SYNC_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+        ),
+    );
+
+    // Background scheduler for announcements with a future publish_at
+    {
+        let scheduler_state = app_state.clone();
+        defarm_engine::announcement_engine::AnnouncementEngine::new(
+            app_state.shared_storage.clone(),
+        )
+        .with_live_stream(app_state.live_stream.clone())
+        .spawn_scheduler(std::time::Duration::from_secs(30), move |notifications| {
+            defarm_engine::api::announcements::broadcast_notifications(
+                &scheduler_state,
+                notifications,
+            )
+        });
+    }
+
+    // HASH_ALGORITHM selects it; records made with another one still verify
+    tracing::info!(
+        "#️⃣  Hashing new records with {}",
+        defarm_engine::hashing::default_algorithm()
+    );
+
+    // Background job refreshing per-workspace engagement snapshots
+    defarm_engine::engagement_engine::EngagementEngine::spawn_scheduler(
+        app_state.shared_storage.clone(),
+        std::time::Duration::from_secs(3600),
+    );
+
+    // Closes a minute of availability measurements for SLA reporting
+    defarm_engine::sla_engine::SlaEngine::spawn_recorder(
+        app_state.shared_storage.clone(),
+        std::time::Duration::from_secs(60),
+    );
+
+    // Folds per-workspace API usage into hourly windows and raises anomalies
+    defarm_engine::usage_anomaly_engine::UsageAnomalyEngine::spawn_monitor(
+        app_state.shared_storage.clone(),
+        std::time::Duration::from_secs(60),
+    );
+    defarm_engine::sensor_summary_engine::SensorSummaryEngine::spawn_summarizer(
+        Arc::clone(&app_state.shared_storage),
+        std::time::Duration::from_secs(3600),
+    );
+
+    // Drains pending data lake entries (VERIFICATION_* settings, pausable by
+    // admins); registered so runbook hooks can restart it
+    {
+        let storage = Arc::clone(&app_state.shared_storage);
+        defarm_engine::runbook_engine::register_worker("verification", move || {
+            defarm_engine::verification_scheduler::VerificationScheduler::spawn_worker(Arc::clone(
+                &storage,
+            ))
+        });
+    }
+
+    // Identical events recorded within the window are retries of the first
+    if let Some(secs) = std::env::var("EVENT_DEDUP_WINDOW_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+    {
+        app_state
+            .events_engine
+            .write()
+            .await
+            .set_dedup_window(chrono::Duration::seconds(secs));
+    }
+
+    // Folds long event histories into snapshots written to the default adapter
+    defarm_engine::events_engine::EventsEngine::spawn_compactor(
+        app_state.shared_storage.clone(),
+        std::time::Duration::from_secs(600),
+        std::env::var("EVENT_COMPACTION_THRESHOLD")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defarm_engine::events_engine::DEFAULT_COMPACTION_THRESHOLD),
+    );
+
+    // Anchors pending document notarizations in Merkle batches
+    defarm_engine::notarization_engine::NotarizationEngine::spawn_anchorer(
+        app_state.shared_storage.clone(),
+        std::time::Duration::from_secs(300),
+    );
+
+    // Samples Stellar fees for the anchoring cost optimizer
+    defarm_engine::anchoring_cost_engine::AnchoringCostEngine::spawn_fee_sampler(
+        app_state.shared_storage.clone(),
+        std::time::Duration::from_secs(600),
+    );
+
+    // Generates access reviews for circuits whose review schedule is due
+    defarm_engine::access_review_engine::AccessReviewEngine::spawn_scheduler(
+        app_state.shared_storage.clone(),
+        app_state.api_key_storage.clone(),
+        std::time::Duration::from_secs(3600),
+    );
+
+    // Scores circuit data quality weekly and sends owners the digest
+    defarm_engine::data_quality_engine::DataQualityEngine::spawn_scheduler(
+        app_state.shared_storage.clone(),
+        app_state.live_stream.clone(),
+        std::time::Duration::from_secs(3600),
+    );
+
+    // Archives or deletes data lake entries and receipts no item refers to
+    defarm_engine::data_lake_gc_engine::DataLakeGcEngine::spawn_collector(
+        app_state.shared_storage.clone(),
+        std::time::Duration::from_secs(24 * 3600),
+    );
+
+    // Masks personal data in items and events past retention
+    defarm_engine::anonymization_engine::AnonymizationEngine::spawn_scheduler(
+        app_state.shared_storage.clone(),
+        std::time::Duration::from_secs(24 * 3600),
+    );
+
+    // Runs remediation actions of runbook hooks whose incident is detected
+    defarm_engine::runbook_engine::RunbookEngine::spawn_monitor(
+        app_state.shared_storage.clone(),
+        std::time::Duration::from_secs(60),
+    );
+
+    // Compares circuit items with push and removal events, repairing per policy
+    defarm_engine::membership_reconciliation_engine::MembershipReconciliationEngine::spawn_reconciler(
+        app_state.shared_storage.clone(),
+        std::time::Duration::from_secs(6 * 3600),
+    );
+
+    // Removes circuit items whose time in the circuit has run out
+    defarm_engine::circuits_engine::CircuitsEngine::spawn_item_expiry(
+        app_state.circuits_engine.clone(),
+        std::time::Duration::from_secs(300),
+    );
+
+    // Resume adapter writes a previous process started and never completed;
+    // nothing is in flight yet, so pending intents are resumed immediately
+    {
+        let circuits_engine = app_state.circuits_engine.clone();
+        tokio::spawn(async move {
+            let engine = circuits_engine.read().await;
+            match engine
+                .resume_adapter_intents(chrono::Duration::zero())
+                .await
+            {
+                Ok(resumed) if !resumed.is_empty() => info!(
+                    "🔁 Resumed {} incomplete adapter writes ({} completed)",
+                    resumed.len(),
+                    resumed.iter().filter(|intent| intent.is_complete()).count()
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("⚠️  Failed to resume adapter writes: {}", e),
+            }
+        });
+    }
+
+    // Coalesces receipt writes under sustained ingestion (RECEIPT_BATCH_SIZE)
+    if let Some(batching) = defarm_engine::receipt_engine::ReceiptBatchConfig::from_env() {
+        app_state
+            .receipt_engine
+            .lock()
+            .unwrap()
+            .set_batching(Some(batching));
+        defarm_engine::receipt_engine::ReceiptEngine::spawn_flusher(
+            app_state.receipt_engine.clone(),
+            batching.window,
+        );
+        info!(
+            "🧾 Batching receipt writes: up to {} per {:?}",
+            batching.max_receipts, batching.window
+        );
+    }
+    let receipt_engine = app_state.receipt_engine.clone();
+
     // Create API key middleware state from AppState components
     let api_key_middleware_state = Arc::new(ApiKeyMiddlewareState::new(
         app_state.api_key_engine.clone(),
@@ -280,13 +501,18 @@ async fn async_main() {
     let public_routes = Router::new()
         .route("/", get(root))
         .route("/health", get(health_check))
+        .route("/health/scaling", get(health_check_scaling))
         .merge(health_routes)
+        // Service status and uptime history (backs the public status page)
+        .nest("/api/status", status_routes(app_state.clone()))
         .nest("/api/auth", auth_routes(app_state.clone()))
         // WebSocket route does NOT use JWT middleware (verifies token from query param)
         .nest(
             "/api/notifications",
             notifications_ws_route(app_state.notification_tx.clone()).with_state(app_state.clone()),
         )
+        // Live event stream (SSE + WebSocket) also verifies its own token
+        .nest("/api/stream", stream_routes().with_state(app_state.clone()))
         // Public storage history endpoint (for items in public circuits - no auth required)
         .nest(
             "/api/public/storage-history",
@@ -301,7 +527,33 @@ async fn async_main() {
         .nest(
             "/api/public/merkle",
             public_merkle_routes().with_state(app_state.clone()),
-        );
+        )
+        // Public item traceability view (QR codes - no auth, rate limited per IP)
+        .nest("/api/public/items", public_item_routes(app_state.clone()))
+        // Public circuit directory (opt-in listings - no auth, rate limited per IP)
+        .nest(
+            "/api/public/circuits",
+            circuit_directory_routes(app_state.clone()),
+        )
+        // Public document verification (proof of existence - no auth, rate limited per IP)
+        .nest(
+            "/api/public/notarizations",
+            public_notarization_routes(app_state.clone()),
+        )
+        // Selectively disclosed item attributes (disclosure token, rate limited per IP)
+        .nest(
+            "/api/public/disclosures",
+            public_disclosure_routes(app_state.clone()),
+        )
+        // Shared ZK proofs (signed share link, rate limited per IP)
+        .nest(
+            "/api/public/proofs",
+            public_proof_share_routes(app_state.clone()),
+        )
+        // Read-only partner API (circuit-scoped partner token, rate limited per IP)
+        .nest("/api/partner", partner_access_routes(app_state.clone()))
+        // Circuit changes for federation peers (node-signed requests)
+        .nest("/api/federation", federation_routes(app_state.clone()));
 
     // Timeline routes (requires PostgreSQL - will return error if not available)
     // Note: timeline_state will be created even if PostgreSQL is None, but endpoints will fail gracefully
@@ -336,6 +588,7 @@ async fn async_main() {
     // Protected routes (require JWT authentication)
     let protected_routes = Router::new()
         .nest("/api/receipts", receipt_routes(app_state.clone()))
+        .nest("/api/notarizations", notarization_routes(app_state.clone()))
         .nest("/api/events", event_routes(app_state.clone()))
         .nest("/api/circuits", circuit_routes(app_state.clone()))
         .nest("/api/items", item_routes(app_state.clone()))
@@ -352,6 +605,8 @@ async fn async_main() {
         .nest("/audit", audit_routes(app_state.clone()))
         .nest("/api/proofs", zk_proof_routes(app_state.clone()))
         .nest("/api/adapters", adapter_routes(app_state.clone()))
+        .nest("/api/organizations", organization_routes(app_state.clone()))
+        .nest("/api/attestations", attestation_routes(app_state.clone()))
         .nest(
             "/api/storage-history",
             storage_history_routes(app_state.clone()),
@@ -360,6 +615,57 @@ async fn async_main() {
         .merge(create_snapshot_routes().with_state(app_state.clone()))
         // Merkle State Tree endpoints (Merkle proofs and sync verification)
         .nest("/api/merkle", merkle_routes().with_state(app_state.clone()))
+        .nest("/api/previews", preview_routes(app_state.clone()))
+        .nest("/api/connectors", connector_routes(app_state.clone()))
+        .nest("/api/change-feeds", change_feed_routes(app_state.clone()))
+        .nest("/api/announcements", announcement_routes(app_state.clone()))
+        .nest("/api/engagement", engagement_routes(app_state.clone()))
+        .nest("/api/exports", data_export_routes(app_state.clone()))
+        .nest(
+            "/api/device-backups",
+            device_backup_routes(app_state.clone()),
+        )
+        .nest("/api/disclosures", disclosure_routes(app_state.clone()))
+        .nest(
+            "/api/enrichment-policies",
+            enrichment_policy_routes(app_state.clone()),
+        )
+        .nest(
+            "/api/dedup-strategies",
+            dedup_strategy_routes(app_state.clone()),
+        )
+        .nest(
+            "/api/dedup-sharing",
+            dedup_sharing_routes(app_state.clone()),
+        )
+        .nest(
+            "/api/verification-rules",
+            verification_rule_routes(app_state.clone()),
+        )
+        .nest("/api/sensors", sensor_routes(app_state.clone()))
+        .nest("/api/review-queue", review_queue_routes(app_state.clone()))
+        .nest(
+            "/api/workspace-buckets",
+            workspace_bucket_routes(app_state.clone()),
+        )
+        .nest("/api/signing-keys", signing_key_routes(app_state.clone()))
+        .nest("/api/anchoring", anchoring_routes(app_state.clone()))
+        .nest("/api/lifecycle", lifecycle_routes(app_state.clone()))
+        .nest(
+            "/api/access-reviews",
+            access_review_routes(app_state.clone()),
+        )
+        .nest("/api/provenance", provenance_routes(app_state.clone()))
+        .nest(
+            "/api/partner-tokens",
+            partner_token_routes(app_state.clone()),
+        )
+        .nest("/api/comments", comment_routes(app_state.clone()))
+        .nest("/api/data-quality", data_quality_routes(app_state.clone()))
+        .nest(
+            "/api/dashboard-metrics",
+            dashboard_metric_routes(app_state.clone()),
+        )
         .nest("/api/test", test_blockchain_routes(app_state.clone()))
         // Notification REST API routes (protected by JWT middleware)
         .nest(
@@ -367,8 +673,10 @@ async fn async_main() {
             notifications_rest_routes().with_state(app_state.clone()),
         )
         .merge(user_credits_routes().with_state(app_state.clone()))
-        .nest("/api/admin", admin_routes().with_state(app_state.clone()))
+        .nest("/api/admin", admin_routes(app_state.clone()))
         .merge(timeline_routes) // Add timeline routes
+        // Inside authentication, which identifies the workspace
+        .layer(middleware::from_fn(usage_tracking_middleware))
         .layer(middleware::from_fn_with_state(
             app_state.clone(),
             jwt_auth_middleware,
@@ -383,9 +691,23 @@ async fn async_main() {
     let app = public_routes
         .merge(protected_routes)
         .nest_service("/docs", ServeDir::new("docs"))
+        .layer(middleware::from_fn(sla_tracking_middleware))
+        // Outside SLA tracking: planned maintenance is not downtime
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            maintenance_mode_middleware,
+        ))
         .layer(TraceLayer::new_for_http())
         .layer(CorsLayer::permissive());
 
+    // /api/v1 and /api/v2 resolve onto the same routes; this wraps the router so
+    // the version segment is stripped before routing
+    let app = middleware::from_fn_with_state(
+        Arc::new(VersioningConfig::from_env()),
+        api_version_middleware,
+    )
+    .layer(app);
+
     // Railway provides PORT environment variable, fallback to 3000 for local development
     let port = std::env::var("PORT")
         .ok()
@@ -416,7 +738,21 @@ async fn async_main() {
     };
 
     info!("🚀 Starting Axum server...");
-    match axum::serve(listener, app).await {
+    let served = axum::serve(
+        listener,
+        ServiceExt::<Request>::into_make_service_with_connect_info::<SocketAddr>(app),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await;
+
+    // Receipts accepted before shutdown must not stay in the write buffer
+    match receipt_engine.lock().unwrap().flush() {
+        Ok(count) if count > 0 => info!("🧾 Flushed {} buffered receipt(s)", count),
+        Ok(_) => {}
+        Err(e) => tracing::error!("❌ Failed to flush buffered receipts: {}", e),
+    }
+
+    match served {
         Ok(_) => info!("✅ Server stopped gracefully"),
         Err(e) => {
             tracing::error!("❌ Server error: {}", e);
@@ -425,10 +761,44 @@ async fn async_main() {
     }
 }
 
+/// Resolves on Ctrl-C or SIGTERM; the server then stops accepting connections
+/// and lets in-flight requests finish
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!("⚠️  Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("⚠️  Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    info!("🛑 Shutdown signal received, draining requests");
+}
+
 async fn root() -> Json<Value> {
     Json(json!({
         "name": "DeFarm Traceability API",
         "version": "0.1.0",
+        "api_versions": ApiVersion::ALL,
+        "latest_api_version": ApiVersion::LATEST,
         "description": "Privacy-first agricultural traceability system",
         "features": [
             "Receipt Engine - BLAKE3-based cryptographic receipts",
@@ -456,6 +826,11 @@ async fn health_check() -> (StatusCode, Json<Value>) {
     )
 }
 
+/// Queue depths and processing rates for orchestrators scaling worker replicas
+async fn health_check_scaling() -> Json<Value> {
+    Json(json!(defarm_engine::scaling_signals::scaling_signals()))
+}
+
 async fn health_check_db(
     axum::extract::State(state): axum::extract::State<Arc<AppState>>,
 ) -> (StatusCode, Json<Value>) {
@@ -1031,6 +1406,7 @@ async fn initialize_development_data_to_postgres(pg: &PostgresPersistence) -> Re
         is_admin: true,
         workspace_id: Some("hen-workspace".to_string()),
         available_adapters: None,
+        locale: None,
     };
 
     pg.persist_user(&hen_admin).await?;
@@ -1058,6 +1434,7 @@ async fn initialize_development_data_to_postgres(pg: &PostgresPersistence) -> Re
             is_admin: false,
             workspace_id: Some("pullet-workspace".to_string()),
             available_adapters: None,
+            locale: None,
         },
         UserAccount {
             user_id: "cock-user-001".to_string(),
@@ -1075,6 +1452,7 @@ async fn initialize_development_data_to_postgres(pg: &PostgresPersistence) -> Re
             is_admin: false,
             workspace_id: Some("cock-workspace".to_string()),
             available_adapters: None,
+            locale: None,
         },
         UserAccount {
             user_id: "basic-farmer-001".to_string(),
@@ -1092,6 +1470,7 @@ async fn initialize_development_data_to_postgres(pg: &PostgresPersistence) -> Re
             is_admin: false,
             workspace_id: Some("basic-workspace".to_string()),
             available_adapters: None,
+            locale: None,
         },
         UserAccount {
             user_id: "pro-farmer-001".to_string(),
@@ -1109,6 +1488,7 @@ async fn initialize_development_data_to_postgres(pg: &PostgresPersistence) -> Re
             is_admin: false,
             workspace_id: Some("pro-workspace".to_string()),
             available_adapters: None,
+            locale: None,
         },
         UserAccount {
             user_id: "enterprise-farmer-001".to_string(),
@@ -1126,6 +1506,7 @@ async fn initialize_development_data_to_postgres(pg: &PostgresPersistence) -> Re
             is_admin: false,
             workspace_id: Some("enterprise-workspace".to_string()),
             available_adapters: None,
+            locale: None,
         },
     ];
 
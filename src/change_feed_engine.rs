@@ -0,0 +1,784 @@
+//! Per-circuit change data feed.
+//!
+//! Every circuit with at least one feed subscription, or shared with a federation
+//! peer, keeps an ordered log of [`ChangeRecord`]s: items added or re-pushed,
+//! events on its items and membership changes. The first subscription seeds the
+//! log with a snapshot of the circuit's current items and members, so replaying
+//! from cursor 0 rebuilds a full mirror.
+//!
+//! Subscribers either pull batches (`read_changes`) or have them POSTed by the
+//! dispatcher, one batch in flight per subscription, advancing the cursor only on a
+//! 2xx answer. Failed deliveries are retried with exponential backoff from the same
+//! cursor, so an endpoint never sees records out of order.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    ChangeBatch, ChangeFeedStatus, ChangeFeedSubscription, ChangeKind, ChangeRecord, Circuit,
+    CircuitItem, Event, EventVisibility, Permission,
+};
+use crate::webhook_engine::WebhookEngine;
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+pub const DEFAULT_BATCH_SIZE: usize = 100;
+pub const MAX_BATCH_SIZE: usize = 1000;
+/// Header carrying the hex blake3 keyed hash of the body, keyed by `blake3(secret)`
+pub const SIGNATURE_HEADER: &str = "X-Change-Feed-Signature";
+/// Upper bound for the retry delay after consecutive failed deliveries
+pub const MAX_BACKOFF_SECS: i64 = 3600;
+const BASE_BACKOFF_SECS: i64 = 30;
+/// Batches pushed to one subscription per dispatcher tick before yielding to others
+const MAX_BATCHES_PER_TICK: usize = 10;
+const DELIVERY_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Debug)]
+pub enum ChangeFeedError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+    NotFound(String),
+    /// The requested cursor is older than the retained part of the feed
+    CursorExpired(String),
+    DeliveryError(String),
+}
+
+impl From<StorageError> for ChangeFeedError {
+    fn from(err: StorageError) -> Self {
+        ChangeFeedError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for ChangeFeedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeFeedError::StorageError(e) => write!(f, "Storage error: {e}"),
+            ChangeFeedError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            ChangeFeedError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            ChangeFeedError::NotFound(e) => write!(f, "Not found: {e}"),
+            ChangeFeedError::CursorExpired(e) => write!(f, "Cursor expired: {e}"),
+            ChangeFeedError::DeliveryError(e) => write!(f, "Delivery error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ChangeFeedError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChangeFeedInput {
+    pub url: String,
+    pub secret: Option<String>,
+    pub batch_size: Option<usize>,
+    /// Start after this sequence; 0 (the default) replays the feed from its snapshot
+    pub from_cursor: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ChangeFeedUpdate {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub batch_size: Option<usize>,
+    /// `false` pauses delivery; records keep accumulating
+    pub enabled: Option<bool>,
+}
+
+/// Whether changes of this circuit are being recorded: it has a subscription or
+/// is shared with a federation peer
+pub fn feed_enabled<S: StorageBackend>(storage: &S, circuit_id: &Uuid) -> bool {
+    let subscribed = storage
+        .list_change_feed_subscriptions(Some(circuit_id))
+        .map(|subscriptions| !subscriptions.is_empty())
+        .unwrap_or(false);
+    subscribed || federated_circuits(storage).contains(circuit_id)
+}
+
+fn federated_circuits<S: StorageBackend>(storage: &S) -> Vec<Uuid> {
+    storage
+        .list_federation_peers()
+        .map(|peers| {
+            peers
+                .iter()
+                .flat_map(|peer| peer.circuits.iter().map(|c| c.local_circuit_id))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Append a change to its circuit's feed if anyone subscribes to it. Never fails the
+/// caller: the change itself has already been stored.
+pub fn record_change<S: StorageBackend>(storage: &S, record: ChangeRecord) {
+    if !feed_enabled(storage, &record.circuit_id) {
+        return;
+    }
+    if let Err(e) = storage.append_change_record(&record) {
+        tracing::warn!(
+            "Failed to record {:?} change for circuit {}: {}",
+            record.kind,
+            record.circuit_id,
+            e
+        );
+    }
+}
+
+/// Feed an event into every subscribed circuit its item belongs to. Local and
+/// private events never leave their owner.
+pub fn record_event_change<S: StorageBackend>(storage: &S, event: &Event) {
+    if event.is_local || matches!(event.visibility, EventVisibility::Private) {
+        return;
+    }
+    let Ok(subscriptions) = storage.list_change_feed_subscriptions(None) else {
+        return;
+    };
+    let mut circuits: Vec<Uuid> = subscriptions.iter().map(|sub| sub.circuit_id).collect();
+    circuits.extend(federated_circuits(storage));
+    circuits.sort();
+    circuits.dedup();
+
+    for circuit_id in circuits {
+        let contains_item = match event.pushed_to_circuit {
+            Some(pushed_to) => pushed_to == circuit_id,
+            None => storage
+                .get_circuit_items(&circuit_id)
+                .map(|items| items.iter().any(|item| item.dfid == event.dfid))
+                .unwrap_or(false),
+        };
+        if contains_item {
+            let record = ChangeRecord::new(circuit_id, ChangeKind::Event, json!(event))
+                .with_dfid(&event.dfid);
+            if let Err(e) = storage.append_change_record(&record) {
+                tracing::warn!("Failed to record event change for {}: {}", circuit_id, e);
+            }
+        }
+    }
+}
+
+/// Item change payload: the circuit membership of the item plus the item itself
+pub fn item_change_record<S: StorageBackend>(
+    storage: &S,
+    circuit_item: &CircuitItem,
+    kind: ChangeKind,
+) -> ChangeRecord {
+    let item = storage.get_item_by_dfid(&circuit_item.dfid).ok().flatten();
+    ChangeRecord::new(
+        circuit_item.circuit_id,
+        kind,
+        json!({ "circuit_item": circuit_item, "item": item }),
+    )
+    .with_dfid(&circuit_item.dfid)
+}
+
+/// Member change payload, or `None` if `member_id` is not in the circuit
+pub fn member_change_record(
+    circuit: &Circuit,
+    member_id: &str,
+    kind: ChangeKind,
+) -> Option<ChangeRecord> {
+    let member = circuit.get_member(member_id)?;
+    Some(ChangeRecord::new(circuit.circuit_id, kind, json!(member)).with_member(member_id))
+}
+
+/// Seed an empty feed with the circuit's current members and items
+pub fn seed_snapshot<S: StorageBackend>(
+    storage: &S,
+    circuit: &Circuit,
+) -> Result<(), StorageError> {
+    if storage.get_latest_change_sequence(&circuit.circuit_id)? > 0 {
+        return Ok(());
+    }
+    for member in &circuit.members {
+        if let Some(record) =
+            member_change_record(circuit, &member.member_id, ChangeKind::MemberSnapshot)
+        {
+            storage.append_change_record(&record)?;
+        }
+    }
+    let mut items = storage.get_circuit_items(&circuit.circuit_id)?;
+    items.sort_by_key(|item| item.pushed_at);
+    for circuit_item in &items {
+        let record = item_change_record(storage, circuit_item, ChangeKind::ItemSnapshot);
+        storage.append_change_record(&record)?;
+    }
+    Ok(())
+}
+
+/// Up to `limit` records after `cursor`, failing if some of them are no longer
+/// retained
+pub fn read_change_batch<S: StorageBackend>(
+    storage: &S,
+    circuit_id: &Uuid,
+    cursor: u64,
+    limit: usize,
+) -> Result<ChangeBatch, ChangeFeedError> {
+    // One extra record tells whether more are waiting
+    let mut records = storage.list_change_records(circuit_id, cursor, limit + 1)?;
+    if let Some(first) = records.first() {
+        if first.sequence > cursor + 1 {
+            return Err(ChangeFeedError::CursorExpired(format!(
+                "Records after {cursor} are no longer retained; resume from {}",
+                first.sequence - 1
+            )));
+        }
+    }
+    let has_more = records.len() > limit;
+    records.truncate(limit);
+    let next_cursor = records.last().map(|r| r.sequence).unwrap_or(cursor);
+    Ok(ChangeBatch {
+        circuit_id: *circuit_id,
+        cursor,
+        next_cursor,
+        records,
+        has_more,
+    })
+}
+
+/// Sign a delivery body with the subscription secret
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let key = blake3::hash(secret.as_bytes());
+    blake3::keyed_hash(key.as_bytes(), body)
+        .to_hex()
+        .to_string()
+}
+
+fn backoff(consecutive_failures: u32) -> Duration {
+    let exponent = consecutive_failures.saturating_sub(1).min(16);
+    Duration::seconds((BASE_BACKOFF_SECS << exponent).min(MAX_BACKOFF_SECS))
+}
+
+pub struct ChangeFeedEngine<S: StorageBackend> {
+    storage: S,
+    client: reqwest::Client,
+}
+
+impl<S: StorageBackend + 'static> ChangeFeedEngine<S> {
+    pub fn new(storage: S) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(DELIVERY_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+        Self { storage, client }
+    }
+
+    fn readable_circuit(
+        &self,
+        user_id: &str,
+        circuit_id: &Uuid,
+    ) -> Result<Circuit, ChangeFeedError> {
+        let circuit = self
+            .storage
+            .get_circuit(circuit_id)?
+            .ok_or_else(|| ChangeFeedError::NotFound(format!("Circuit {circuit_id}")))?;
+        if !circuit.has_permission(user_id, &Permission::Pull) {
+            return Err(ChangeFeedError::PermissionDenied(
+                "Reading the change feed requires pull permission on the circuit".to_string(),
+            ));
+        }
+        Ok(circuit)
+    }
+
+    fn validate(url: &str, batch_size: usize) -> Result<(), ChangeFeedError> {
+        WebhookEngine::<S>::validate_webhook_url(url)
+            .map_err(|e| ChangeFeedError::ValidationError(e.to_string()))?;
+        if batch_size == 0 || batch_size > MAX_BATCH_SIZE {
+            return Err(ChangeFeedError::ValidationError(format!(
+                "batch_size must be between 1 and {MAX_BATCH_SIZE}"
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn create_subscription(
+        &self,
+        user_id: &str,
+        circuit_id: &Uuid,
+        input: ChangeFeedInput,
+    ) -> Result<ChangeFeedSubscription, ChangeFeedError> {
+        let circuit = self.readable_circuit(user_id, circuit_id)?;
+        let batch_size = input.batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
+        Self::validate(&input.url, batch_size)?;
+
+        seed_snapshot(&self.storage, &circuit)?;
+        let cursor = input.from_cursor.unwrap_or(0);
+        self.check_cursor(circuit_id, cursor)?;
+
+        let now = Utc::now();
+        let subscription = ChangeFeedSubscription {
+            subscription_id: Uuid::new_v4(),
+            circuit_id: *circuit_id,
+            owner_id: user_id.to_string(),
+            url: input.url,
+            secret: input.secret.filter(|secret| !secret.is_empty()),
+            batch_size,
+            cursor,
+            status: ChangeFeedStatus::Active,
+            consecutive_failures: 0,
+            last_error: None,
+            last_delivered_at: None,
+            next_attempt_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        self.storage.store_change_feed_subscription(&subscription)?;
+        Ok(subscription)
+    }
+
+    pub fn get_subscription(
+        &self,
+        user_id: &str,
+        subscription_id: &Uuid,
+    ) -> Result<ChangeFeedSubscription, ChangeFeedError> {
+        let subscription = self
+            .storage
+            .get_change_feed_subscription(subscription_id)?
+            .ok_or_else(|| {
+                ChangeFeedError::NotFound(format!("Change feed subscription {subscription_id}"))
+            })?;
+        if subscription.owner_id != user_id {
+            return Err(ChangeFeedError::PermissionDenied(
+                "Not the owner of this subscription".to_string(),
+            ));
+        }
+        Ok(subscription)
+    }
+
+    pub fn list_subscriptions(
+        &self,
+        user_id: &str,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<ChangeFeedSubscription>, ChangeFeedError> {
+        self.readable_circuit(user_id, circuit_id)?;
+        let mut subscriptions: Vec<_> = self
+            .storage
+            .list_change_feed_subscriptions(Some(circuit_id))?
+            .into_iter()
+            .filter(|sub| sub.owner_id == user_id)
+            .collect();
+        subscriptions.sort_by_key(|sub| sub.created_at);
+        Ok(subscriptions)
+    }
+
+    pub fn update_subscription(
+        &self,
+        user_id: &str,
+        subscription_id: &Uuid,
+        update: ChangeFeedUpdate,
+    ) -> Result<ChangeFeedSubscription, ChangeFeedError> {
+        let mut subscription = self.get_subscription(user_id, subscription_id)?;
+        if let Some(url) = update.url {
+            subscription.url = url;
+        }
+        if let Some(secret) = update.secret {
+            subscription.secret = Some(secret).filter(|secret| !secret.is_empty());
+        }
+        if let Some(batch_size) = update.batch_size {
+            subscription.batch_size = batch_size;
+        }
+        Self::validate(&subscription.url, subscription.batch_size)?;
+        match update.enabled {
+            Some(false) => subscription.status = ChangeFeedStatus::Paused,
+            Some(true) if subscription.status == ChangeFeedStatus::Paused => {
+                subscription.status = ChangeFeedStatus::Active;
+                subscription.next_attempt_at = None;
+            }
+            _ => {}
+        }
+        subscription.updated_at = Utc::now();
+        self.storage.store_change_feed_subscription(&subscription)?;
+        Ok(subscription)
+    }
+
+    /// Rewind (or fast-forward) delivery to resume after `cursor`
+    pub fn replay_from(
+        &self,
+        user_id: &str,
+        subscription_id: &Uuid,
+        cursor: u64,
+    ) -> Result<ChangeFeedSubscription, ChangeFeedError> {
+        let mut subscription = self.get_subscription(user_id, subscription_id)?;
+        self.check_cursor(&subscription.circuit_id, cursor)?;
+        subscription.cursor = cursor;
+        subscription.consecutive_failures = 0;
+        subscription.last_error = None;
+        subscription.next_attempt_at = None;
+        if subscription.status == ChangeFeedStatus::Failing {
+            subscription.status = ChangeFeedStatus::Active;
+        }
+        subscription.updated_at = Utc::now();
+        self.storage.store_change_feed_subscription(&subscription)?;
+        Ok(subscription)
+    }
+
+    pub fn delete_subscription(
+        &self,
+        user_id: &str,
+        subscription_id: &Uuid,
+    ) -> Result<(), ChangeFeedError> {
+        self.get_subscription(user_id, subscription_id)?;
+        self.storage
+            .delete_change_feed_subscription(subscription_id)?;
+        Ok(())
+    }
+
+    /// Pull the records after `cursor`, for consumers that poll instead of receiving
+    /// pushes
+    pub fn read_changes(
+        &self,
+        user_id: &str,
+        circuit_id: &Uuid,
+        cursor: u64,
+        limit: Option<usize>,
+    ) -> Result<ChangeBatch, ChangeFeedError> {
+        self.readable_circuit(user_id, circuit_id)?;
+        let limit = limit.unwrap_or(DEFAULT_BATCH_SIZE).clamp(1, MAX_BATCH_SIZE);
+        self.batch_after(circuit_id, cursor, limit)
+    }
+
+    fn check_cursor(&self, circuit_id: &Uuid, cursor: u64) -> Result<(), ChangeFeedError> {
+        let latest = self.storage.get_latest_change_sequence(circuit_id)?;
+        if cursor > latest {
+            return Err(ChangeFeedError::ValidationError(format!(
+                "Cursor {cursor} is ahead of the feed (latest {latest})"
+            )));
+        }
+        self.batch_after(circuit_id, cursor, 1).map(|_| ())
+    }
+
+    fn batch_after(
+        &self,
+        circuit_id: &Uuid,
+        cursor: u64,
+        limit: usize,
+    ) -> Result<ChangeBatch, ChangeFeedError> {
+        read_change_batch(&self.storage, circuit_id, cursor, limit)
+    }
+
+    /// Subscriptions with undelivered records whose retry delay has passed
+    pub fn due_subscriptions(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<ChangeFeedSubscription>, ChangeFeedError> {
+        let mut due = Vec::new();
+        for subscription in self.storage.list_change_feed_subscriptions(None)? {
+            if subscription.status == ChangeFeedStatus::Paused
+                || subscription.next_attempt_at.is_some_and(|at| at > now)
+            {
+                continue;
+            }
+            if self
+                .storage
+                .get_latest_change_sequence(&subscription.circuit_id)?
+                > subscription.cursor
+            {
+                due.push(subscription);
+            }
+        }
+        Ok(due)
+    }
+
+    /// POST batches until the subscriber is caught up, a delivery fails or the
+    /// per-tick limit is reached. Returns the number of records delivered.
+    pub async fn deliver(
+        &self,
+        mut subscription: ChangeFeedSubscription,
+    ) -> Result<usize, ChangeFeedError> {
+        let mut delivered = 0;
+        for _ in 0..MAX_BATCHES_PER_TICK {
+            let batch = match self.batch_after(
+                &subscription.circuit_id,
+                subscription.cursor,
+                subscription.batch_size,
+            ) {
+                Ok(batch) => batch,
+                Err(e @ ChangeFeedError::CursorExpired(_)) => {
+                    self.record_failure(&mut subscription, e.to_string())?;
+                    return Err(e);
+                }
+                Err(e) => return Err(e),
+            };
+            if batch.records.is_empty() {
+                break;
+            }
+
+            if let Err(e) = self.post_batch(&subscription, &batch).await {
+                self.record_failure(&mut subscription, e.to_string())?;
+                return Err(e);
+            }
+
+            delivered += batch.records.len();
+            subscription.cursor = batch.next_cursor;
+            subscription.status = ChangeFeedStatus::Active;
+            subscription.consecutive_failures = 0;
+            subscription.last_error = None;
+            subscription.next_attempt_at = None;
+            subscription.last_delivered_at = Some(Utc::now());
+            subscription.updated_at = Utc::now();
+            self.storage.store_change_feed_subscription(&subscription)?;
+            if !batch.has_more {
+                break;
+            }
+        }
+        Ok(delivered)
+    }
+
+    async fn post_batch(
+        &self,
+        subscription: &ChangeFeedSubscription,
+        batch: &ChangeBatch,
+    ) -> Result<(), ChangeFeedError> {
+        let body = serde_json::to_vec(batch).map_err(|e| {
+            ChangeFeedError::DeliveryError(format!("Failed to serialize batch: {e}"))
+        })?;
+        let mut request = self
+            .client
+            .post(&subscription.url)
+            .header("Content-Type", "application/json");
+        if let Some(secret) = &subscription.secret {
+            request = request.header(SIGNATURE_HEADER, sign_payload(secret, &body));
+        }
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| ChangeFeedError::DeliveryError(format!("Request failed: {e}")))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ChangeFeedError::DeliveryError(format!(
+                "{} responded with {status}",
+                subscription.url
+            )));
+        }
+        Ok(())
+    }
+
+    fn record_failure(
+        &self,
+        subscription: &mut ChangeFeedSubscription,
+        error: String,
+    ) -> Result<(), ChangeFeedError> {
+        subscription.consecutive_failures += 1;
+        subscription.status = ChangeFeedStatus::Failing;
+        subscription.last_error = Some(error);
+        subscription.next_attempt_at =
+            Some(Utc::now() + backoff(subscription.consecutive_failures));
+        subscription.updated_at = Utc::now();
+        self.storage.store_change_feed_subscription(subscription)?;
+        Ok(())
+    }
+
+    /// Background task pushing due subscriptions every `tick`. Subscriptions are
+    /// served one after another, which keeps each endpoint's deliveries ordered.
+    pub fn spawn_dispatcher(storage: S, tick: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let engine = ChangeFeedEngine::new(storage);
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                let due = match engine.due_subscriptions(Utc::now()) {
+                    Ok(due) => due,
+                    Err(e) => {
+                        tracing::warn!("⚠️  Failed to list due change feeds: {}", e);
+                        continue;
+                    }
+                };
+                for subscription in due {
+                    let subscription_id = subscription.subscription_id;
+                    match engine.deliver(subscription).await {
+                        Ok(count) => tracing::debug!(
+                            "📤 Change feed {} delivered {} records",
+                            subscription_id,
+                            count
+                        ),
+                        Err(e) => tracing::warn!(
+                            "⚠️  Change feed {} delivery failed: {}",
+                            subscription_id,
+                            e
+                        ),
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::MemberRole;
+    use crate::CircuitsEngine;
+    use std::sync::{Arc, Mutex};
+
+    type Storage = Arc<Mutex<InMemoryStorage>>;
+
+    /// Alice's circuit holding DFID-1, not yet subscribed to
+    async fn circuit_with_item() -> (Storage, CircuitsEngine<Storage>, Uuid) {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let mut circuits = CircuitsEngine::new(Arc::clone(&storage));
+        let circuit = circuits
+            .create_circuit(
+                "Mirror".to_string(),
+                "Synced herd".to_string(),
+                "alice".to_string(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        storage
+            .store_circuit_item(&CircuitItem::new(
+                "DFID-1".to_string(),
+                circuit.circuit_id,
+                "alice".to_string(),
+                vec![],
+            ))
+            .unwrap();
+        (storage, circuits, circuit.circuit_id)
+    }
+
+    fn input() -> ChangeFeedInput {
+        ChangeFeedInput {
+            url: "https://mirror.example.com/changes".to_string(),
+            secret: Some("s3cret".to_string()),
+            batch_size: Some(2),
+            from_cursor: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscription_requires_pull_permission() {
+        let (storage, _, circuit_id) = circuit_with_item().await;
+        let engine = ChangeFeedEngine::new(storage);
+
+        assert!(matches!(
+            engine.create_subscription("mallory", &circuit_id, input()),
+            Err(ChangeFeedError::PermissionDenied(_))
+        ));
+        assert!(matches!(
+            engine.read_changes("mallory", &circuit_id, 0, None),
+            Err(ChangeFeedError::PermissionDenied(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_subscription_input_is_refused() {
+        let (storage, _, circuit_id) = circuit_with_item().await;
+        let engine = ChangeFeedEngine::new(storage);
+
+        for batch_size in [0, MAX_BATCH_SIZE + 1] {
+            let input = ChangeFeedInput {
+                batch_size: Some(batch_size),
+                ..input()
+            };
+            assert!(matches!(
+                engine.create_subscription("alice", &circuit_id, input),
+                Err(ChangeFeedError::ValidationError(_))
+            ));
+        }
+        let input = ChangeFeedInput {
+            from_cursor: Some(9),
+            ..input()
+        };
+        assert!(matches!(
+            engine.create_subscription("alice", &circuit_id, input),
+            Err(ChangeFeedError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_first_subscription_seeds_snapshot() {
+        let (storage, _, circuit_id) = circuit_with_item().await;
+        let engine = ChangeFeedEngine::new(storage);
+
+        let subscription = engine
+            .create_subscription("alice", &circuit_id, input())
+            .unwrap();
+        assert_eq!(subscription.cursor, 0);
+
+        // Snapshot: owner + item
+        let snapshot = engine.read_changes("alice", &circuit_id, 0, None).unwrap();
+        let kinds: Vec<_> = snapshot.records.iter().map(|r| r.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![ChangeKind::MemberSnapshot, ChangeKind::ItemSnapshot]
+        );
+        assert!(!snapshot.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_changes_page_in_order_after_snapshot() {
+        let (storage, mut circuits, circuit_id) = circuit_with_item().await;
+        let engine = ChangeFeedEngine::new(storage);
+        engine
+            .create_subscription("alice", &circuit_id, input())
+            .unwrap();
+        circuits
+            .add_member_to_circuit(&circuit_id, "bob".to_string(), MemberRole::Member, "alice")
+            .await
+            .unwrap();
+
+        let first = engine
+            .read_changes("alice", &circuit_id, 0, Some(2))
+            .unwrap();
+        assert_eq!(first.records.len(), 2);
+        assert!(first.has_more);
+
+        let rest = engine
+            .read_changes("alice", &circuit_id, first.next_cursor, None)
+            .unwrap();
+        assert_eq!(rest.records.len(), 1);
+        assert_eq!(rest.records[0].kind, ChangeKind::MemberAdded);
+        assert_eq!(rest.records[0].member_id.as_deref(), Some("bob"));
+        assert_eq!(rest.next_cursor, 3);
+        assert!(!rest.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_replay_is_limited_to_the_recorded_feed() {
+        let (storage, _, circuit_id) = circuit_with_item().await;
+        let engine = ChangeFeedEngine::new(storage);
+        let subscription = engine
+            .create_subscription("alice", &circuit_id, input())
+            .unwrap();
+
+        assert!(matches!(
+            engine.replay_from("alice", &subscription.subscription_id, 9),
+            Err(ChangeFeedError::ValidationError(_))
+        ));
+        assert!(matches!(
+            engine.replay_from("bob", &subscription.subscription_id, 0),
+            Err(ChangeFeedError::PermissionDenied(_))
+        ));
+        let rewound = engine
+            .replay_from("alice", &subscription.subscription_id, 1)
+            .unwrap();
+        assert_eq!(rewound.cursor, 1);
+    }
+
+    #[tokio::test]
+    async fn test_paused_subscription_is_not_due() {
+        let (storage, _, circuit_id) = circuit_with_item().await;
+        let engine = ChangeFeedEngine::new(storage);
+        let subscription = engine
+            .create_subscription("alice", &circuit_id, input())
+            .unwrap();
+        assert_eq!(engine.due_subscriptions(Utc::now()).unwrap().len(), 1);
+
+        engine
+            .update_subscription(
+                "alice",
+                &subscription.subscription_id,
+                ChangeFeedUpdate {
+                    enabled: Some(false),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert!(engine.due_subscriptions(Utc::now()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_payload_signature_depends_on_secret() {
+        assert_eq!(sign_payload("s3cret", b"{}"), sign_payload("s3cret", b"{}"));
+        assert_ne!(sign_payload("s3cret", b"{}"), sign_payload("other", b"{}"));
+    }
+}
@@ -1,30 +1,44 @@
-use crate::adapters::{
-    base::StorageLocation, IpfsIpfsAdapter, StellarMainnetIpfsAdapter, StellarTestnetIpfsAdapter,
-    StorageAdapter,
+use crate::adapter_intent_log;
+use crate::adapter_manager::{AdapterManager, AdapterManagerError};
+use crate::adapters::base::StorageLocation;
+use crate::attestation_engine::missing_required_attestations;
+use crate::change_feed_engine::{
+    feed_enabled, item_change_record, member_change_record, record_change,
 };
+use crate::dedup_sharing_engine::active_agreement;
+use crate::dedup_strategy::identifier_fingerprint;
 use crate::dfid_engine::DfidEngine;
 use crate::events_engine::EventsEngine;
 use crate::identifier_types::{
     CircuitAliasConfig, EnhancedIdentifier, ExternalAlias, IdentifierType,
 };
+use crate::live_stream::{LiveRecord, LiveStream};
 use crate::logging::LoggingEngine;
 use crate::postgres_persistence::PostgresPersistence;
 use crate::storage::StorageBackend;
 use crate::types::{
-    Activity, ActivityDetails, ActivityStatus, ActivityType, AdapterType, BatchPushItemResult,
-    BatchPushResult, Circuit, CircuitAdapterConfig, CircuitItem, CircuitOperation,
-    CircuitPermissions, CircuitStatus, CustomRole, EventVisibility, Identifier, Item, ItemStatus,
-    MemberRole, Notification, NotificationType, OperationStatus, OperationType, Permission,
-    PostActionTrigger, PublicSettings, UserTier, WebhookItemData, WebhookPayload,
+    Activity, ActivityDetails, ActivityStatus, ActivityType, AdapterIntent, AdapterOperation,
+    AdapterType, AnchoringNetwork, AuditEvent, AuditEventType, AuditOutcome, AuditSeverity,
+    BatchPushItemResult, BatchPushResult, ChangeKind, Circuit, CircuitAdapterConfig,
+    CircuitDryRunReport, CircuitItem, CircuitOperation, CircuitPermissions, CircuitStatus,
+    CustomRole, EffectiveMember, Event, EventCausality, EventType, EventVisibility, Identifier,
+    IngestionPriority, Item, ItemForward, ItemStatus, MemberRole, Notification, NotificationType,
+    OperationStatus, OperationType, OwnershipTransfer, ParentAccess, Permission, PostActionTrigger,
+    PublicSettings, ReplicationPolicy, RolledUpItem, UserTier, WebhookItemData, WebhookPayload,
     WebhookStorageData,
 };
 use crate::webhook_engine::WebhookEngine;
-use chrono::Utc;
-use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+/// Circuit hierarchies (national body > region > co-op ...) are at most this deep
+pub const MAX_CIRCUIT_DEPTH: usize = 8;
+/// Days the proposed owner has to accept an ownership transfer
+pub const OWNERSHIP_TRANSFER_EXPIRY_DAYS: i64 = 7;
+
 #[derive(Debug)]
 pub enum CircuitsError {
     StorageError(String),
@@ -35,6 +49,7 @@ pub enum CircuitsError {
     ItemNotFound,
     CircuitNotFound,
     MemberNotFound,
+    CircuitArchived,
 }
 
 impl std::fmt::Display for CircuitsError {
@@ -50,6 +65,9 @@ impl std::fmt::Display for CircuitsError {
             CircuitsError::ItemNotFound => write!(f, "Item not found"),
             CircuitsError::CircuitNotFound => write!(f, "Circuit not found"),
             CircuitsError::MemberNotFound => write!(f, "Member not found"),
+            CircuitsError::CircuitArchived => {
+                write!(f, "Circuit is archived; restore it to push or pull")
+            }
         }
     }
 }
@@ -78,16 +96,16 @@ fn validate_adapter_tier_access(user_tier: &UserTier, adapter_type: &AdapterType
         // Basic tier adapter - all tiers have access
         AdapterType::IpfsIpfs => true,
 
-        // Professional tier adapter - Professional, Enterprise, Admin
-        AdapterType::StellarTestnetIpfs => {
+        // Professional tier adapters - Professional, Enterprise, Admin
+        AdapterType::StellarTestnetIpfs | AdapterType::StellarTestnetLocal => {
             matches!(
                 user_tier,
                 UserTier::Professional | UserTier::Enterprise | UserTier::Admin
             )
         }
 
-        // Enterprise tier adapter - Enterprise, Admin only
-        AdapterType::StellarMainnetIpfs => {
+        // Enterprise tier adapters - Enterprise, Admin only
+        AdapterType::StellarMainnetIpfs | AdapterType::StellarMainnetLocal => {
             matches!(user_tier, UserTier::Enterprise | UserTier::Admin)
         }
 
@@ -102,7 +120,9 @@ pub struct CircuitsEngine<S: StorageBackend> {
     events_engine: EventsEngine<S>,
     dfid_engine: DfidEngine,
     webhook_engine: Arc<tokio::sync::RwLock<WebhookEngine<S>>>,
+    adapter_manager: AdapterManager<S>,
     postgres: Option<Arc<RwLock<Option<PostgresPersistence>>>>,
+    live_stream: Option<LiveStream>,
 }
 
 impl<S: StorageBackend + 'static> CircuitsEngine<S> {
@@ -110,23 +130,82 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
     where
         S: Clone,
     {
-        let logger = LoggingEngine::new();
+        let logger = Arc::new(std::sync::Mutex::new(LoggingEngine::new()));
         let events_engine = EventsEngine::new(storage.clone());
         let webhook_engine = WebhookEngine::new(storage.clone());
+        let adapter_manager = AdapterManager::new(storage.clone(), Arc::clone(&logger));
         Self {
             storage,
-            logger: Arc::new(std::sync::Mutex::new(logger)),
+            logger,
             events_engine,
             dfid_engine: DfidEngine::new(),
             webhook_engine: Arc::new(tokio::sync::RwLock::new(webhook_engine)),
+            adapter_manager,
             postgres: None,
+            live_stream: None,
         }
     }
 
+    /// Generate DFIDs with `dfid_engine`, e.g. one coordinated across replicas
+    pub fn with_dfid_engine(mut self, dfid_engine: DfidEngine) -> Self {
+        self.dfid_engine = dfid_engine;
+        self
+    }
+
     pub fn set_postgres(&mut self, postgres: Arc<RwLock<Option<PostgresPersistence>>>) {
         self.postgres = Some(postgres);
     }
 
+    /// Publish stored activities, events and notifications to `/api/stream` subscribers
+    pub fn set_live_stream(&mut self, live_stream: LiveStream) {
+        self.events_engine.set_live_stream(live_stream.clone());
+        self.live_stream = Some(live_stream);
+    }
+
+    fn publish_live(&self, record: LiveRecord) {
+        if let Some(live_stream) = &self.live_stream {
+            live_stream.publish(record);
+        }
+    }
+
+    /// Store a circuit item and append it to the circuit's change feed, as an update
+    /// when the item was already in the circuit
+    fn store_circuit_item_with_change(
+        &self,
+        circuit_item: &CircuitItem,
+    ) -> Result<(), CircuitsError> {
+        let feed = feed_enabled(&self.storage, &circuit_item.circuit_id);
+        let already_in_circuit = feed
+            && self
+                .storage
+                .get_circuit_items(&circuit_item.circuit_id)
+                .map(|items| items.iter().any(|item| item.dfid == circuit_item.dfid))
+                .unwrap_or(false);
+
+        self.storage
+            .store_circuit_item(circuit_item)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+        if feed {
+            let kind = if already_in_circuit {
+                ChangeKind::ItemUpdated
+            } else {
+                ChangeKind::ItemAdded
+            };
+            record_change(
+                &self.storage,
+                item_change_record(&self.storage, circuit_item, kind),
+            );
+        }
+        Ok(())
+    }
+
+    fn record_member_change(&self, circuit: &Circuit, member_id: &str, kind: ChangeKind) {
+        if let Some(record) = member_change_record(circuit, member_id, kind) {
+            record_change(&self.storage, record);
+        }
+    }
+
     fn spawn_persist_activity(&self, activity: Activity) {
         if let Some(pg_ref) = &self.postgres {
             let pg = Arc::clone(pg_ref);
@@ -343,6 +422,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
         self.storage
             .update_circuit(&circuit)
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+        self.record_member_change(&circuit, &member_id, ChangeKind::MemberAdded);
 
         self.logger
             .lock()
@@ -362,6 +442,23 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
         dfid: &str,
         circuit_id: &Uuid,
         requester_id: &str,
+    ) -> Result<CircuitOperation, CircuitsError> {
+        self.push_item_to_circuit_with_priority(
+            dfid,
+            circuit_id,
+            requester_id,
+            IngestionPriority::Realtime,
+        )
+        .await
+    }
+
+    /// Push an item, fanning out webhooks in the given priority lane
+    pub async fn push_item_to_circuit_with_priority(
+        &mut self,
+        dfid: &str,
+        circuit_id: &Uuid,
+        requester_id: &str,
+        priority: IngestionPriority,
     ) -> Result<CircuitOperation, CircuitsError> {
         self.logger
             .lock()
@@ -431,6 +528,11 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                 "User does not have permission to push to this circuit".to_string(),
             ));
         }
+        if circuit.is_archived() {
+            return Err(CircuitsError::CircuitArchived);
+        }
+
+        self.check_attestation_policy(&circuit, requester_id)?;
 
         // Check adapter permissions if circuit has a configured adapter
         if let Some(adapter_config) = &circuit.adapter_config {
@@ -491,9 +593,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                 requester_id.to_string(),
                 vec!["read".to_string(), "verify".to_string()],
             );
-            self.storage
-                .store_circuit_item(&circuit_item)
-                .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+            self.store_circuit_item_with_change(&circuit_item)?;
 
             let activity = Activity::new(
                 ActivityType::Push,
@@ -511,6 +611,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             self.storage
                 .store_activity(&activity)
                 .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+            self.publish_live(LiveRecord::Activity(activity.clone()));
             self.spawn_persist_activity(activity.clone());
         }
 
@@ -529,6 +630,8 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             EventVisibility::CircuitOnly
         };
 
+        // The push carries the item as of its latest event
+        let cause = self.latest_item_event(dfid).map(|event| event.event_id);
         self.events_engine
             .create_circuit_operation_event(
                 dfid.to_string(),
@@ -536,6 +639,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                 "push".to_string(),
                 requester_id.to_string(),
                 visibility,
+                EventCausality::following(cause, operation.operation_id),
             )
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
 
@@ -566,6 +670,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             &operation.operation_id,
             PostActionTrigger::ItemPushed,
             None, // Implementation pending
+            priority,
         )
         .await;
 
@@ -573,14 +678,35 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
     }
 
     // NEW: Push with LID (tokenization in circuit)
-    #[allow(clippy::await_holding_lock)]
     pub async fn push_local_item_to_circuit(
+        &mut self,
+        local_id: &Uuid,
+        identifiers: Vec<EnhancedIdentifier>,
+        enriched_data: Option<HashMap<String, serde_json::Value>>,
+        circuit_id: &Uuid,
+        requester_id: &str,
+    ) -> Result<PushResult, CircuitsError> {
+        self.push_local_item_to_circuit_with_priority(
+            local_id,
+            identifiers,
+            enriched_data,
+            circuit_id,
+            requester_id,
+            IngestionPriority::Realtime,
+        )
+        .await
+    }
+
+    /// Push with LID, fanning out webhooks in the given priority lane
+    #[allow(clippy::await_holding_lock)]
+    pub async fn push_local_item_to_circuit_with_priority(
         &mut self,
         local_id: &Uuid,
         mut identifiers: Vec<EnhancedIdentifier>,
         enriched_data: Option<HashMap<String, serde_json::Value>>,
         circuit_id: &Uuid,
         requester_id: &str,
+        priority: IngestionPriority,
     ) -> Result<PushResult, CircuitsError> {
         // 1. Get circuit and validate permissions
         let circuit = self
@@ -594,6 +720,11 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                 "User does not have permission to push to this circuit".to_string(),
             ));
         }
+        if circuit.is_archived() {
+            return Err(CircuitsError::CircuitArchived);
+        }
+
+        self.check_attestation_policy(&circuit, requester_id)?;
 
         // 2. Auto-apply namespace if configured
         if circuit
@@ -635,9 +766,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             requester_id.to_string(),
             vec!["read".to_string(), "verify".to_string()],
         );
-        self.storage
-            .store_circuit_item(&circuit_item)
-            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+        self.store_circuit_item_with_change(&circuit_item)?;
 
         // 6.5. CALL ADAPTER TO ACTUALLY UPLOAD TO BLOCKCHAIN/IPFS
         // This is where the REAL blockchain integration happens!
@@ -646,13 +775,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             .get_circuit_adapter_config(circuit_id)
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?
         {
-            if let Some(adapter_type) = circuit_adapter_config.adapter_type {
-                // Get the full adapter configuration by type
-                let adapter_configs = self
-                    .storage
-                    .get_adapter_configs_by_type(&adapter_type)
-                    .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
-                let full_adapter_config = adapter_configs.into_iter().find(|c| c.is_active);
+            if circuit_adapter_config.adapter_type.is_some() {
                 // Get the item from storage to upload it
                 let item = self
                     .storage
@@ -663,72 +786,43 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                 // Determine if this is a new DFID (for NFT minting)
                 let is_new_dfid = matches!(status, PushStatus::NewItemCreated);
 
-                // Create adapter instance based on type and call store_new_item with mint flag
-                let upload_result = match adapter_type {
-                    AdapterType::None => {
-                        // No storage adapter - skip upload entirely
-                        return Err(CircuitsError::StorageError(
-                            "Circuit has no storage adapter configured (adapter type: None)"
-                                .to_string(),
-                        ));
-                    }
-                    AdapterType::IpfsIpfs => {
-                        let adapter = IpfsIpfsAdapter::new().map_err(|e| {
-                            CircuitsError::StorageError(format!(
-                                "Failed to create IPFS adapter: {e}"
-                            ))
-                        })?;
-                        adapter
-                            .store_new_item(&item, is_new_dfid, requester_id)
-                            .await
-                            .map_err(|e| {
-                                CircuitsError::StorageError(format!(
-                                    "Failed to upload to IPFS: {e}"
-                                ))
-                            })?
-                    }
-                    AdapterType::StellarTestnetIpfs => {
-                        let adapter = StellarTestnetIpfsAdapter::new_with_config(
-                            full_adapter_config.as_ref(),
-                        )
-                        .map_err(|e| {
-                            CircuitsError::StorageError(format!(
-                                "Failed to create Stellar Testnet adapter: {e}"
-                            ))
-                        })?;
-                        adapter
-                            .store_new_item(&item, is_new_dfid, requester_id)
-                            .await
-                            .map_err(|e| {
-                                CircuitsError::StorageError(format!(
-                                    "Failed to upload to Stellar Testnet: {e}"
-                                ))
-                            })?
-                    }
-                    AdapterType::StellarMainnetIpfs => {
-                        let adapter = StellarMainnetIpfsAdapter::new_with_config(
-                            full_adapter_config.as_ref(),
-                        )
-                        .map_err(|e| {
-                            CircuitsError::StorageError(format!(
-                                "Failed to create Stellar Mainnet adapter: {e}"
-                            ))
-                        })?;
-                        adapter
-                            .store_new_item(&item, is_new_dfid, requester_id)
-                            .await
-                            .map_err(|e| {
-                                CircuitsError::StorageError(format!(
-                                    "Failed to upload to Stellar Mainnet: {e}"
-                                ))
-                            })?
-                    }
-                    _ => {
-                        return Err(CircuitsError::StorageError(format!(
-                            "Unsupported adapter type: {adapter_type:?}"
-                        )));
+                // Recorded before the write so a crash before its storage record is
+                // added leaves an incomplete intent to resume
+                let mut intent = adapter_intent_log::record_intent(
+                    &self.storage,
+                    &dfid,
+                    Some(*circuit_id),
+                    circuit_adapter_config
+                        .adapter_type
+                        .clone()
+                        .unwrap_or(AdapterType::None),
+                    AdapterOperation::CircuitPush,
+                    is_new_dfid,
+                    requester_id,
+                )
+                .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+                // Write to the primary adapter (and any mirrors) according to the
+                // circuit's replication policy
+                let replication = match self
+                    .adapter_manager
+                    .replicate_new_item(
+                        &dfid,
+                        &circuit_adapter_config,
+                        &item,
+                        is_new_dfid,
+                        requester_id,
+                    )
+                    .await
+                {
+                    Ok(replication) => replication,
+                    Err(e) => {
+                        self.settle_adapter_intent(&mut intent, Err(&e.to_string()));
+                        return Err(CircuitsError::StorageError(e.to_string()));
                     }
                 };
+                let adapter_type = replication.primary_adapter.clone();
+                let upload_result = &replication.primary_result;
 
                 // Extract storage location from adapter result
                 let storage_location = upload_result.metadata.item_location.clone();
@@ -747,8 +841,10 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                 transaction_metadata.insert(
                     "network".to_string(),
                     serde_json::json!(match adapter_type {
-                        crate::types::AdapterType::StellarTestnetIpfs => "stellar-testnet",
-                        crate::types::AdapterType::StellarMainnetIpfs => "stellar-mainnet",
+                        crate::types::AdapterType::StellarTestnetIpfs
+                        | crate::types::AdapterType::StellarTestnetLocal => "stellar-testnet",
+                        crate::types::AdapterType::StellarMainnetIpfs
+                        | crate::types::AdapterType::StellarMainnetLocal => "stellar-mainnet",
                         _ => "unknown",
                     }),
                 );
@@ -801,10 +897,30 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                             transaction_metadata
                                 .insert("ipfs_pinned".to_string(), serde_json::json!(pinned));
                         }
+                        StorageLocation::Local { id } => {
+                            // Local adapters anchor a file hash in place of a CID
+                            transaction_metadata
+                                .insert("local_file_hash".to_string(), serde_json::json!(id));
+                        }
                         _ => {}
                     }
                 }
 
+                if replication.policy != ReplicationPolicy::Single {
+                    transaction_metadata.insert(
+                        "replication_policy".to_string(),
+                        serde_json::json!(replication.policy),
+                    );
+                    transaction_metadata.insert(
+                        "replica_results".to_string(),
+                        serde_json::json!(replication.replicas),
+                    );
+                    transaction_metadata.insert(
+                        "pending_mirrors".to_string(),
+                        serde_json::json!(replication.pending_mirrors),
+                    );
+                }
+
                 // ============================================================
                 // IMPORTANT: Storage History Recording
                 // ============================================================
@@ -836,9 +952,22 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                     metadata: transaction_metadata.clone(), // Contains: nft_mint_tx, ipcm_update_tx, ipfs_cid, etc.
                 };
 
-                self.storage
-                    .add_storage_record(&dfid, storage_record) // ← THIS is where history is recorded
-                    .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+                if let Some(network) = AnchoringNetwork::for_adapter(&adapter_type) {
+                    self.record_anchoring_cost(
+                        &dfid,
+                        *circuit_id,
+                        network,
+                        &storage_location,
+                        &upload_result.metadata.event_locations,
+                    );
+                }
+
+                if let Err(e) = self.storage.add_storage_record(&dfid, storage_record) {
+                    // ← THIS is where history is recorded
+                    self.settle_adapter_intent(&mut intent, Err(&e.to_string()));
+                    return Err(CircuitsError::StorageError(e.to_string()));
+                }
+                self.settle_adapter_intent(&mut intent, Ok(Some(&storage_location)));
 
                 // ============================================================
                 // DUAL-WRITE STRATEGY: CID Timeline Entry
@@ -960,6 +1089,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             self.storage
                 .store_activity(&activity)
                 .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+            self.publish_live(LiveRecord::Activity(activity.clone()));
             self.spawn_persist_activity(activity.clone());
         }
 
@@ -974,6 +1104,8 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             EventVisibility::CircuitOnly
         };
 
+        // The push carries the item as of its latest event
+        let cause = self.latest_item_event(&dfid).map(|event| event.event_id);
         self.events_engine
             .create_circuit_operation_event(
                 dfid.clone(),
@@ -981,6 +1113,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                 "push".to_string(),
                 requester_id.to_string(),
                 visibility,
+                EventCausality::following(cause, operation.operation_id),
             )
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
 
@@ -1012,6 +1145,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             &operation.operation_id,
             trigger_event,
             storage_details, // NOW INCLUDES REAL STORAGE DETAILS!
+            priority,
         )
         .await;
 
@@ -1023,6 +1157,29 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
         })
     }
 
+    /// Enforce the circuit's required attestations for the pushing member
+    fn check_attestation_policy(
+        &self,
+        circuit: &Circuit,
+        requester_id: &str,
+    ) -> Result<(), CircuitsError> {
+        let missing = missing_required_attestations(&self.storage, circuit, requester_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        let claims = missing
+            .iter()
+            .map(|r| r.claim_type.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        Err(CircuitsError::PermissionDenied(format!(
+            "This circuit requires attestations you do not hold: {claims}"
+        )))
+    }
+
     fn validate_circuit_requirements(
         &self,
         circuit: &Circuit,
@@ -1115,6 +1272,52 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             }
         }
 
+        // Circuits sharing a dedup namespace resolve the same identifier set
+        // to one item, whichever of them it was pushed to
+        let shared_scope = active_agreement(&self.storage, &circuit.circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .map(|agreement| agreement.agreement_id);
+        let shared_fingerprint = identifier_fingerprint(identifiers);
+        if let Some(scope) = shared_scope {
+            if let Some(dfid) = self
+                .storage
+                .get_dfid_by_fingerprint(&shared_fingerprint, &scope)
+                .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            {
+                self.enrich_existing_item_internal(
+                    &dfid,
+                    identifiers,
+                    enriched_data,
+                    requester_id,
+                )?;
+                return Ok((dfid, PushStatus::ExistingItemEnriched));
+            }
+        }
+
+        let (dfid, status) = self.resolve_within_circuit(
+            identifiers,
+            circuit,
+            requester_id,
+            local_id,
+            enriched_data,
+        )?;
+        if let Some(scope) = shared_scope {
+            self.storage
+                .store_fingerprint_mapping(&shared_fingerprint, &dfid, &scope)
+                .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+        }
+        Ok((dfid, status))
+    }
+
+    /// Fingerprint lookup (if configured) or a new item, within the circuit
+    fn resolve_within_circuit(
+        &self,
+        identifiers: &[EnhancedIdentifier],
+        circuit: &Circuit,
+        requester_id: &str,
+        local_id: &Uuid,
+        enriched_data: Option<HashMap<String, serde_json::Value>>,
+    ) -> Result<(String, PushStatus), CircuitsError> {
         // STEP 2: Look for fingerprint (if configured)
         if circuit
             .alias_config
@@ -1195,7 +1398,10 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
         local_id: &Uuid,
         fingerprint: Option<String>,
     ) -> Result<String, CircuitsError> {
-        let dfid = self.dfid_engine.generate_dfid();
+        let dfid = self
+            .dfid_engine
+            .generate_dfid()
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
 
         let mut item = Item {
             dfid: dfid.clone(),
@@ -1210,6 +1416,8 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             source_entries: vec![Uuid::new_v4()],
             confidence_score: 1.0,
             status: ItemStatus::Active,
+            first_occurred_at: None,
+            last_occurred_at: None,
         };
 
         // Add alias from requester
@@ -1278,12 +1486,218 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
         Ok(())
     }
 
+    /// Latest event describing the item itself, leaving out earlier pushes and pulls
+    fn latest_item_event(&self, dfid: &str) -> Option<Event> {
+        self.storage
+            .get_events_by_dfid(dfid)
+            .ok()?
+            .into_iter()
+            .filter(|event| {
+                !matches!(
+                    event.event_type,
+                    EventType::PushedToCircuit
+                        | EventType::PulledFromCircuit
+                        | EventType::ForwardedToCircuit
+                        | EventType::RemovedFromCircuit
+                )
+            })
+            .max_by_key(|event| event.timestamp)
+    }
+
+    /// Record what a push cost on chain: one transaction per Stellar location,
+    /// covering the item's events since its previous anchoring. Cost tracking
+    /// never fails the push.
+    fn record_anchoring_cost(
+        &self,
+        dfid: &str,
+        circuit_id: Uuid,
+        network: AnchoringNetwork,
+        item_location: &StorageLocation,
+        event_locations: &[StorageLocation],
+    ) {
+        let transactions = std::iter::once(item_location)
+            .chain(event_locations)
+            .filter(|location| matches!(location, StorageLocation::Stellar { .. }))
+            .count() as u32;
+        if transactions == 0 {
+            return;
+        }
+        let previous_anchor = self
+            .storage
+            .get_storage_history(dfid)
+            .ok()
+            .flatten()
+            .and_then(|history| {
+                history
+                    .storage_records
+                    .iter()
+                    .map(|record| record.stored_at)
+                    .max()
+            });
+        let anchored_events = self
+            .storage
+            .get_events_by_dfid(dfid)
+            .map(|events| {
+                events
+                    .iter()
+                    .filter(|event| previous_anchor.is_none_or(|at| event.timestamp > at))
+                    .count() as u64
+            })
+            .unwrap_or(0)
+            .max(1);
+        if let Err(e) = crate::anchoring_cost_engine::record_anchoring(
+            &self.storage,
+            Some(circuit_id),
+            network,
+            transactions,
+            anchored_events,
+            Utc::now(),
+        ) {
+            tracing::warn!("Failed to record anchoring cost for {}: {}", dfid, e);
+        }
+    }
+
     async fn handle_storage_migration(
         &self,
         dfid: &str,
         circuit: &Circuit,
         user_id: &str,
     ) -> Result<(), String> {
+        let adapter_type = circuit
+            .adapter_config
+            .as_ref()
+            .and_then(|config| config.adapter_type.clone())
+            .filter(|adapter_type| !matches!(adapter_type, AdapterType::None));
+        let mut intent = match adapter_type {
+            Some(adapter_type) => Some(
+                adapter_intent_log::record_intent(
+                    &self.storage,
+                    dfid,
+                    Some(circuit.circuit_id),
+                    adapter_type,
+                    AdapterOperation::StorageMigration,
+                    false,
+                    user_id,
+                )
+                .map_err(|e| format!("Failed to record adapter intent: {e}"))?,
+            ),
+            None => None,
+        };
+
+        let result = self
+            .store_with_circuit_adapter(dfid, circuit, user_id, false, "circuit_migration")
+            .await;
+        if let Some(intent) = intent.as_mut() {
+            self.settle_adapter_intent(
+                intent,
+                result.as_ref().map(Option::as_ref).map_err(String::as_str),
+            );
+        }
+        result.map(|_| ())
+    }
+
+    /// Complete or fail `intent`; a failure to update it only leaves it to be
+    /// resumed, so it is logged rather than returned
+    fn settle_adapter_intent(
+        &self,
+        intent: &mut AdapterIntent,
+        result: Result<Option<&StorageLocation>, &str>,
+    ) {
+        let updated = match result {
+            Ok(location) => adapter_intent_log::complete_intent(&self.storage, intent, location),
+            Err(error) => adapter_intent_log::fail_intent(&self.storage, intent, error),
+        };
+        if let Err(e) = updated {
+            tracing::warn!(
+                "⚠️  Failed to update adapter intent {} for {}: {}",
+                intent.intent_id,
+                intent.dfid,
+                e
+            );
+        }
+    }
+
+    /// Adapter writes that were started and never completed, oldest first
+    pub fn get_incomplete_adapter_intents(&self) -> Result<Vec<AdapterIntent>, CircuitsError> {
+        self.storage
+            .get_incomplete_adapter_intents()
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))
+    }
+
+    /// Retry the adapter writes whose intents are incomplete: failed ones, and
+    /// pending ones idle for `grace` (left behind by a crash). Writes that reached
+    /// their storage record before the crash are completed without writing again.
+    pub async fn resume_adapter_intents(
+        &self,
+        grace: chrono::Duration,
+    ) -> Result<Vec<AdapterIntent>, CircuitsError> {
+        let intents = adapter_intent_log::resumable_intents(&self.storage, Utc::now(), grace)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+        let mut resumed = Vec::with_capacity(intents.len());
+
+        for mut intent in intents {
+            let recorded = adapter_intent_log::recorded_location(&self.storage, &intent)
+                .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+            if let Some(location) = recorded {
+                self.settle_adapter_intent(&mut intent, Ok(Some(&location)));
+                resumed.push(intent);
+                continue;
+            }
+
+            let circuit = match intent.circuit_id {
+                Some(circuit_id) => self
+                    .storage
+                    .get_circuit(&circuit_id)
+                    .map_err(|e| CircuitsError::StorageError(e.to_string()))?,
+                None => None,
+            };
+            let Some(circuit) = circuit else {
+                self.settle_adapter_intent(&mut intent, Err("Circuit no longer exists"));
+                resumed.push(intent);
+                continue;
+            };
+
+            adapter_intent_log::begin_retry(&self.storage, &mut intent)
+                .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+            let triggered_by = match intent.operation {
+                AdapterOperation::CircuitPush => "circuit_push",
+                AdapterOperation::StorageMigration => "circuit_migration",
+            };
+            let result = self
+                .store_with_circuit_adapter(
+                    &intent.dfid,
+                    &circuit,
+                    &intent.requested_by,
+                    intent.is_new_dfid,
+                    triggered_by,
+                )
+                .await;
+            self.settle_adapter_intent(
+                &mut intent,
+                result.as_ref().map(Option::as_ref).map_err(String::as_str),
+            );
+            tracing::info!(
+                "🔁 Resumed adapter intent {} for {} ({})",
+                intent.intent_id,
+                intent.dfid,
+                intent.status.as_str()
+            );
+            resumed.push(intent);
+        }
+
+        Ok(resumed)
+    }
+
+    /// Store `dfid` on the circuit's adapter and add its storage record.
+    /// Returns where it was stored, or None when the circuit has no adapter.
+    async fn store_with_circuit_adapter(
+        &self,
+        dfid: &str,
+        circuit: &Circuit,
+        user_id: &str,
+        is_new_dfid: bool,
+        triggered_by: &str,
+    ) -> Result<Option<StorageLocation>, String> {
         // Check if circuit has adapter configuration
         let adapter_config = match &circuit.adapter_config {
             Some(config) => config,
@@ -1298,7 +1712,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                     )
                     .with_context("dfid", dfid.to_string())
                     .with_context("circuit_id", circuit.circuit_id.to_string());
-                return Ok(());
+                return Ok(None);
             }
         };
 
@@ -1314,13 +1728,13 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                         "No adapter type configured",
                     )
                     .with_context("dfid", dfid.to_string());
-                return Ok(());
+                return Ok(None);
             }
         };
 
         // Skip migration for None adapter type
         if matches!(adapter_type, AdapterType::None) {
-            return Ok(());
+            return Ok(None);
         }
 
         self.logger
@@ -1342,51 +1756,12 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             .map_err(|e| format!("Failed to get item: {e}"))?
             .ok_or_else(|| format!("Item {dfid} not found"))?;
 
-        // Get full adapter config
-        let adapter_configs = self
-            .storage
-            .get_adapter_configs_by_type(&adapter_type)
-            .map_err(|e| format!("Failed to get adapter configs: {e}"))?;
-        let full_adapter_config = adapter_configs.into_iter().find(|c| c.is_active);
-
-        // IMPORTANT: is_new_dfid = false for migration (no NFT minting, just IPCM update)
-        let is_new_dfid = false;
-
-        // Create adapter and upload item
-        let upload_result = match adapter_type {
-            AdapterType::None => return Ok(()),
-            AdapterType::IpfsIpfs => {
-                let adapter = IpfsIpfsAdapter::new()
-                    .map_err(|e| format!("Failed to create IPFS adapter: {e}"))?;
-                adapter
-                    .store_new_item(&item, is_new_dfid, user_id)
-                    .await
-                    .map_err(|e| format!("Failed to upload to IPFS: {e}"))?
-            }
-            AdapterType::StellarTestnetIpfs => {
-                let adapter =
-                    StellarTestnetIpfsAdapter::new_with_config(full_adapter_config.as_ref())
-                        .map_err(|e| format!("Failed to create Stellar Testnet adapter: {e}"))?;
-                adapter
-                    .store_new_item(&item, is_new_dfid, user_id)
-                    .await
-                    .map_err(|e| format!("Failed to upload to Stellar Testnet: {e}"))?
-            }
-            AdapterType::StellarMainnetIpfs => {
-                let adapter =
-                    StellarMainnetIpfsAdapter::new_with_config(full_adapter_config.as_ref())
-                        .map_err(|e| format!("Failed to create Stellar Mainnet adapter: {e}"))?;
-                adapter
-                    .store_new_item(&item, is_new_dfid, user_id)
-                    .await
-                    .map_err(|e| format!("Failed to upload to Stellar Mainnet: {e}"))?
-            }
-            _ => {
-                return Err(format!(
-                    "Unsupported adapter type for migration: {adapter_type:?}"
-                ));
-            }
-        };
+        // Upload through the active adapter config, under the write retry policy
+        let upload_result = self
+            .adapter_manager
+            .store_new_item_with_retry(&adapter_type, &item, is_new_dfid, user_id)
+            .await
+            .map_err(|e| e.to_string())?;
 
         // Extract storage location and metadata
         let storage_location = upload_result.metadata.item_location.clone();
@@ -1395,8 +1770,12 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
         transaction_metadata.insert(
             "network".to_string(),
             serde_json::json!(match adapter_type {
-                AdapterType::StellarTestnetIpfs => "stellar-testnet",
-                AdapterType::StellarMainnetIpfs => "stellar-mainnet",
+                AdapterType::StellarTestnetIpfs | AdapterType::StellarTestnetLocal => {
+                    "stellar-testnet"
+                }
+                AdapterType::StellarMainnetIpfs | AdapterType::StellarMainnetLocal => {
+                    "stellar-mainnet"
+                }
                 _ => "unknown",
             }),
         );
@@ -1434,7 +1813,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             adapter_type: adapter_type.clone(),
             storage_location: storage_location.clone(),
             stored_at: Utc::now(),
-            triggered_by: "circuit_migration".to_string(),
+            triggered_by: triggered_by.to_string(),
             triggered_by_id: Some(circuit.circuit_id.to_string()),
             events_range: None,
             is_active: true,
@@ -1492,7 +1871,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             .with_context("dfid", dfid.to_string())
             .with_context("circuit_id", circuit.circuit_id.to_string());
 
-        Ok(())
+        Ok(Some(storage_location))
     }
 
     pub async fn pull_item_from_circuit(
@@ -1512,6 +1891,9 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                 "User does not have permission to pull from this circuit".to_string(),
             ));
         }
+        if circuit.is_archived() {
+            return Err(CircuitsError::CircuitArchived);
+        }
 
         let item = self
             .storage
@@ -1549,6 +1931,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             self.storage
                 .store_activity(&activity)
                 .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+            self.publish_live(LiveRecord::Activity(activity.clone()));
             self.spawn_persist_activity(activity.clone());
         }
 
@@ -1562,6 +1945,8 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             EventVisibility::CircuitOnly
         };
 
+        // The pull takes the item as of its latest event
+        let cause = self.latest_item_event(dfid).map(|event| event.event_id);
         self.events_engine
             .create_circuit_operation_event(
                 dfid.to_string(),
@@ -1569,6 +1954,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                 "pull".to_string(),
                 requester_id.to_string(),
                 visibility,
+                EventCausality::following(cause, operation.operation_id),
             )
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
 
@@ -1590,31 +1976,410 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
         Ok((item, operation))
     }
 
-    pub async fn approve_operation(
+    /// Push an item of `source_circuit_id` into `target_circuit_id`, keeping it
+    /// in the source. The requester needs Pull in the source and Push in the
+    /// target, and the push follows the target's rules (approval, attestations,
+    /// adapter access) and writes to the target's adapter. The source records a
+    /// `ForwardedToCircuit` event that the target's push event follows from.
+    pub async fn forward_item_to_circuit(
         &mut self,
-        operation_id: &Uuid,
-        approver_id: &str,
-    ) -> Result<CircuitOperation, CircuitsError> {
-        let mut operation = self
-            .storage
-            .get_circuit_operation(operation_id)
-            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
-            .ok_or(CircuitsError::NotFound)?;
-
-        let circuit = self
+        dfid: &str,
+        source_circuit_id: &Uuid,
+        target_circuit_id: &Uuid,
+        requester_id: &str,
+    ) -> Result<ItemForward, CircuitsError> {
+        if source_circuit_id == target_circuit_id {
+            return Err(CircuitsError::ValidationError(
+                "Source and target circuits must differ".to_string(),
+            ));
+        }
+        let source = self
             .storage
-            .get_circuit(&operation.circuit_id)
+            .get_circuit(source_circuit_id)
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?
             .ok_or(CircuitsError::CircuitNotFound)?;
-
-        if !circuit.has_permission(approver_id, &Permission::ManageMembers) {
+        if !source.has_permission(requester_id, &Permission::Pull) {
             return Err(CircuitsError::PermissionDenied(
-                "User does not have permission to approve operations".to_string(),
+                "User does not have permission to pull from the source circuit".to_string(),
             ));
         }
+        if source.is_archived() {
+            return Err(CircuitsError::CircuitArchived);
+        }
+        let in_source = self
+            .storage
+            .get_circuit_items(source_circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .iter()
+            .any(|item| item.dfid == dfid);
+        if !in_source {
+            return Err(CircuitsError::ItemNotFound);
+        }
 
-        operation.approve();
-        operation.complete();
+        let operation = self
+            .push_item_to_circuit_with_priority(
+                dfid,
+                target_circuit_id,
+                requester_id,
+                IngestionPriority::Realtime,
+            )
+            .await?;
+
+        let visibility = if source.permissions.allow_public_visibility {
+            EventVisibility::Public
+        } else {
+            EventVisibility::CircuitOnly
+        };
+        let metadata = HashMap::from([
+            (
+                "circuit_id".to_string(),
+                serde_json::json!(source_circuit_id.to_string()),
+            ),
+            (
+                "target_circuit_id".to_string(),
+                serde_json::json!(target_circuit_id.to_string()),
+            ),
+            (
+                "operation_id".to_string(),
+                serde_json::json!(operation.operation_id.to_string()),
+            ),
+            ("requester_id".to_string(), serde_json::json!(requester_id)),
+        ]);
+        let cause = self.latest_item_event(dfid).map(|event| event.event_id);
+        let forward_event = self
+            .events_engine
+            .create_linked_event(
+                dfid.to_string(),
+                EventType::ForwardedToCircuit,
+                requester_id.to_string(),
+                visibility,
+                metadata,
+                EventCausality::following(cause, operation.operation_id),
+            )
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+        // The target's push event follows from the forward
+        let push_event = self
+            .storage
+            .get_events_by_dfid(dfid)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .into_iter()
+            .find(|event| {
+                event.event_type == EventType::PushedToCircuit
+                    && event.correlation_id == Some(operation.operation_id)
+            });
+        if let Some(push_event) = push_event {
+            self.events_engine
+                .add_event_metadata(
+                    &push_event.event_id,
+                    HashMap::from([(
+                        "forwarded_from".to_string(),
+                        serde_json::json!(source_circuit_id.to_string()),
+                    )]),
+                )
+                .and_then(|_| {
+                    self.events_engine.link_event(
+                        &push_event.event_id,
+                        EventCausality {
+                            caused_by: Some(forward_event.event_id),
+                            correlation_id: None,
+                        },
+                    )
+                })
+                .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+        }
+
+        self.logger
+            .lock()
+            .unwrap()
+            .info(
+                "circuits_engine",
+                "item_forwarded",
+                "Item forwarded between circuits",
+            )
+            .with_context("dfid", dfid.to_string())
+            .with_context("source_circuit_id", source_circuit_id.to_string())
+            .with_context("target_circuit_id", target_circuit_id.to_string())
+            .with_context("operation_id", operation.operation_id.to_string());
+
+        Ok(ItemForward {
+            dfid: dfid.to_string(),
+            source_circuit_id: *source_circuit_id,
+            target_circuit_id: *target_circuit_id,
+            operation,
+            forward_event_id: forward_event.event_id,
+        })
+    }
+
+    /// Limit how long `dfid` stays in the circuit; None keeps it indefinitely.
+    /// Only the member who pushed the item or one who manages permissions may
+    /// change its expiry.
+    pub async fn set_circuit_item_expiry(
+        &self,
+        circuit_id: &Uuid,
+        dfid: &str,
+        expires_at: Option<DateTime<Utc>>,
+        requester_id: &str,
+    ) -> Result<CircuitItem, CircuitsError> {
+        let circuit = self
+            .storage
+            .get_circuit(circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .ok_or(CircuitsError::CircuitNotFound)?;
+        if circuit.is_archived() {
+            return Err(CircuitsError::CircuitArchived);
+        }
+        let mut item = self
+            .storage
+            .get_circuit_items(circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .into_iter()
+            .find(|item| item.dfid == dfid)
+            .ok_or(CircuitsError::ItemNotFound)?;
+        if item.pushed_by != requester_id
+            && !circuit.has_permission(requester_id, &Permission::ManagePermissions)
+        {
+            return Err(CircuitsError::PermissionDenied(
+                "Only the member who pushed the item can change its expiry".to_string(),
+            ));
+        }
+        if expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+            return Err(CircuitsError::ValidationError(
+                "Expiry must be in the future".to_string(),
+            ));
+        }
+
+        item.expires_at = expires_at;
+        self.storage
+            .store_circuit_item(&item)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+        self.logger
+            .lock()
+            .unwrap()
+            .info(
+                "circuits_engine",
+                "item_expiry_set",
+                "Circuit item expiry updated",
+            )
+            .with_context("dfid", dfid.to_string())
+            .with_context("circuit_id", circuit_id.to_string())
+            .with_context("requester_id", requester_id.to_string())
+            .with_context(
+                "expires_at",
+                expires_at.map_or("never".to_string(), |ts| ts.to_rfc3339()),
+            );
+
+        Ok(item)
+    }
+
+    /// Remove every circuit item whose expiry has passed at `now`, recording a
+    /// `RemovedFromCircuit` event and notifying the member who pushed the item
+    /// and the circuit owner
+    pub async fn remove_expired_items(
+        &mut self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<CircuitItem>, CircuitsError> {
+        let circuits = self
+            .storage
+            .list_circuits()
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+        let mut removed = Vec::new();
+        for circuit in circuits {
+            let expired: Vec<CircuitItem> = self
+                .storage
+                .get_circuit_items(&circuit.circuit_id)
+                .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+                .into_iter()
+                .filter(|item| item.is_expired(now))
+                .collect();
+
+            for item in expired {
+                self.storage
+                    .remove_circuit_item(&circuit.circuit_id, &item.dfid)
+                    .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+                let visibility = if circuit.permissions.allow_public_visibility {
+                    EventVisibility::Public
+                } else {
+                    EventVisibility::CircuitOnly
+                };
+                let metadata = HashMap::from([
+                    (
+                        "circuit_id".to_string(),
+                        serde_json::json!(circuit.circuit_id.to_string()),
+                    ),
+                    ("reason".to_string(), serde_json::json!("expired")),
+                    ("expires_at".to_string(), serde_json::json!(item.expires_at)),
+                    ("pushed_by".to_string(), serde_json::json!(item.pushed_by)),
+                ]);
+                let cause = self
+                    .latest_item_event(&item.dfid)
+                    .map(|event| event.event_id);
+                self.events_engine
+                    .create_linked_event(
+                        item.dfid.clone(),
+                        EventType::RemovedFromCircuit,
+                        "system".to_string(),
+                        visibility,
+                        metadata,
+                        EventCausality {
+                            caused_by: cause,
+                            correlation_id: None,
+                        },
+                    )
+                    .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+                self.notify_item_expired(&circuit, &item)?;
+
+                self.logger
+                    .lock()
+                    .unwrap()
+                    .info(
+                        "circuits_engine",
+                        "item_expired",
+                        "Expired item removed from circuit",
+                    )
+                    .with_context("dfid", item.dfid.clone())
+                    .with_context("circuit_id", circuit.circuit_id.to_string())
+                    .with_context("pushed_by", item.pushed_by.clone());
+                removed.push(item);
+            }
+        }
+
+        Ok(removed)
+    }
+
+    fn notify_item_expired(
+        &self,
+        circuit: &Circuit,
+        item: &CircuitItem,
+    ) -> Result<(), CircuitsError> {
+        let args = [
+            ("circuit_name", circuit.name.as_str()),
+            ("dfid", item.dfid.as_str()),
+        ];
+        let data = serde_json::json!({
+            "circuit_id": circuit.circuit_id,
+            "circuit_name": circuit.name,
+            "dfid": item.dfid,
+            "pushed_by": item.pushed_by,
+            "expires_at": item.expires_at,
+        });
+        let mut recipients = vec![item.pushed_by.as_str()];
+        if circuit.owner_id != item.pushed_by {
+            recipients.push(circuit.owner_id.as_str());
+        }
+        for recipient in recipients {
+            self.notify_localized(
+                recipient,
+                NotificationType::CircuitItemExpired,
+                "circuit_item_expired",
+                &args,
+                data.clone(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Store and publish a notification titled from the
+    /// `notification.<key>.title`/`.message` translations in the recipient's locale
+    fn notify_localized(
+        &self,
+        recipient: &str,
+        notification_type: NotificationType,
+        key: &str,
+        args: &[(&str, &str)],
+        data: serde_json::Value,
+    ) -> Result<(), CircuitsError> {
+        let localizer = crate::i18n::Localizer::global();
+        let locale = self
+            .storage
+            .get_user_account(recipient)
+            .ok()
+            .flatten()
+            .and_then(|user| user.locale);
+        let notification = Notification::new(
+            recipient.to_string(),
+            notification_type,
+            localizer.translate(
+                locale.as_deref(),
+                &format!("notification.{key}.title"),
+                args,
+            ),
+            localizer.translate(
+                locale.as_deref(),
+                &format!("notification.{key}.message"),
+                args,
+            ),
+            data,
+        );
+
+        self.storage
+            .store_notification(&notification)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+        self.publish_live(LiveRecord::Notification(notification));
+        Ok(())
+    }
+
+    /// Periodically remove expired circuit items
+    pub fn spawn_item_expiry(
+        engine: Arc<RwLock<Self>>,
+        tick: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        S: Send + Sync + 'static,
+    {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                let mut engine = engine.write().await;
+                match engine.remove_expired_items(Utc::now()).await {
+                    Ok(removed) if !removed.is_empty() => {
+                        tracing::info!("⌛ Removed {} expired circuit items", removed.len())
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("⚠️  Failed to remove expired circuit items: {}", e),
+                }
+            }
+        })
+    }
+
+    pub async fn approve_operation(
+        &mut self,
+        operation_id: &Uuid,
+        approver_id: &str,
+    ) -> Result<CircuitOperation, CircuitsError> {
+        let mut operation = self
+            .storage
+            .get_circuit_operation(operation_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .ok_or(CircuitsError::NotFound)?;
+
+        let circuit = self
+            .storage
+            .get_circuit(&operation.circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .ok_or(CircuitsError::CircuitNotFound)?;
+
+        if !circuit.has_permission(approver_id, &Permission::ManageMembers) {
+            return Err(CircuitsError::PermissionDenied(
+                "User does not have permission to approve operations".to_string(),
+            ));
+        }
+
+        operation.approve();
+        operation.complete();
+        operation.metadata.insert(
+            "approved_by".to_string(),
+            serde_json::Value::String(approver_id.to_string()),
+        );
+        operation.metadata.insert(
+            "approved_at".to_string(),
+            serde_json::Value::String(Utc::now().to_rfc3339()),
+        );
 
         let op_type = operation.operation_type.clone();
         match op_type {
@@ -1625,9 +2390,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                     operation.requester_id.clone(),
                     vec!["read".to_string(), "verify".to_string()],
                 );
-                self.storage
-                    .store_circuit_item(&circuit_item)
-                    .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+                self.store_circuit_item_with_change(&circuit_item)?;
 
                 let activity = Activity::new(
                     ActivityType::Push,
@@ -1645,6 +2408,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                 self.storage
                     .store_activity(&activity)
                     .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+                self.publish_live(LiveRecord::Activity(activity.clone()));
                 self.spawn_persist_activity(activity.clone());
 
                 // Handle auto-publish if enabled
@@ -1678,114 +2442,634 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             .unwrap()
             .info(
                 "circuits_engine",
-                "operation_approved",
-                "Circuit operation approved",
+                "operation_approved",
+                "Circuit operation approved",
+            )
+            .with_context("operation_id", operation_id.to_string())
+            .with_context("approver_id", approver_id.to_string());
+
+        Ok(operation)
+    }
+
+    pub async fn reject_operation(
+        &mut self,
+        operation_id: &Uuid,
+        rejecter_id: &str,
+        reason: Option<String>,
+    ) -> Result<CircuitOperation, CircuitsError> {
+        let mut operation = self
+            .storage
+            .get_circuit_operation(operation_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .ok_or(CircuitsError::NotFound)?;
+
+        let circuit = self
+            .storage
+            .get_circuit(&operation.circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .ok_or(CircuitsError::CircuitNotFound)?;
+
+        if !circuit.has_permission(rejecter_id, &Permission::ManageMembers) {
+            return Err(CircuitsError::PermissionDenied(
+                "User does not have permission to reject operations".to_string(),
+            ));
+        }
+
+        operation.status = OperationStatus::Rejected;
+
+        // Store rejection reason in metadata
+        if let Some(reason_text) = reason {
+            operation.metadata.insert(
+                "rejection_reason".to_string(),
+                serde_json::Value::String(reason_text),
+            );
+        }
+        operation.metadata.insert(
+            "rejected_by".to_string(),
+            serde_json::Value::String(rejecter_id.to_string()),
+        );
+        operation.metadata.insert(
+            "rejected_at".to_string(),
+            serde_json::Value::String(Utc::now().to_rfc3339()),
+        );
+
+        self.storage
+            .update_circuit_operation(&operation)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+        self.logger
+            .lock()
+            .unwrap()
+            .info(
+                "circuits_engine",
+                "operation_rejected",
+                "Circuit operation rejected",
+            )
+            .with_context("operation_id", operation_id.to_string())
+            .with_context("rejecter_id", rejecter_id.to_string());
+
+        Ok(operation)
+    }
+
+    pub fn list_circuits(&self) -> Result<Vec<Circuit>, CircuitsError> {
+        self.storage
+            .list_circuits()
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))
+    }
+
+    pub fn get_circuit(&self, circuit_id: &Uuid) -> Result<Option<Circuit>, CircuitsError> {
+        self.storage
+            .get_circuit(circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))
+    }
+
+    pub fn get_circuits_for_member(&self, member_id: &str) -> Result<Vec<Circuit>, CircuitsError> {
+        self.storage
+            .get_circuits_for_member(member_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))
+    }
+
+    pub fn get_archived_circuits_for_member(
+        &self,
+        member_id: &str,
+    ) -> Result<Vec<Circuit>, CircuitsError> {
+        self.storage
+            .get_archived_circuits_for_member(member_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))
+    }
+
+    pub fn get_circuit_operations(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<CircuitOperation>, CircuitsError> {
+        self.storage
+            .get_circuit_operations(circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))
+    }
+
+    pub fn get_pending_operations(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<CircuitOperation>, CircuitsError> {
+        let operations = self.get_circuit_operations(circuit_id)?;
+        Ok(operations
+            .into_iter()
+            .filter(|op| matches!(op.status, OperationStatus::Pending))
+            .collect())
+    }
+
+    pub async fn deactivate_circuit(
+        &mut self,
+        circuit_id: &Uuid,
+        requester_id: &str,
+    ) -> Result<Circuit, CircuitsError> {
+        let mut circuit = self
+            .storage
+            .get_circuit(circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .ok_or(CircuitsError::CircuitNotFound)?;
+
+        if !circuit.has_permission(requester_id, &Permission::ManagePermissions) {
+            return Err(CircuitsError::PermissionDenied(
+                "User does not have permission to deactivate circuit".to_string(),
+            ));
+        }
+
+        circuit.status = CircuitStatus::Inactive;
+        circuit.last_modified = chrono::Utc::now();
+
+        self.storage
+            .update_circuit(&circuit)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+        self.logger
+            .lock()
+            .unwrap()
+            .info(
+                "circuits_engine",
+                "circuit_deactivated",
+                "Circuit deactivated",
+            )
+            .with_context("circuit_id", circuit_id.to_string())
+            .with_context("requester_id", requester_id.to_string());
+
+        Ok(circuit)
+    }
+
+    /// Ancestors of a circuit, nearest first. With `readable_only` the walk stops
+    /// at the first circuit that does not grant its parent's members read access.
+    fn ancestor_chain(
+        &self,
+        circuit: &Circuit,
+        readable_only: bool,
+    ) -> Result<Vec<Circuit>, CircuitsError> {
+        let mut ancestors: Vec<Circuit> = Vec::new();
+        let mut current = circuit.clone();
+        while let Some(parent_id) = current.parent_circuit_id {
+            if readable_only && current.parent_access == ParentAccess::None {
+                break;
+            }
+            // Stored chains are validated on write; this only guards against bad data
+            if parent_id == circuit.circuit_id
+                || ancestors.len() > MAX_CIRCUIT_DEPTH
+                || ancestors.iter().any(|a| a.circuit_id == parent_id)
+            {
+                break;
+            }
+            let Some(parent) = self
+                .storage
+                .get_circuit(&parent_id)
+                .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            else {
+                break;
+            };
+            ancestors.push(parent.clone());
+            current = parent;
+        }
+        Ok(ancestors)
+    }
+
+    /// Ancestors of a circuit, nearest first
+    pub fn get_ancestor_circuits(&self, circuit_id: &Uuid) -> Result<Vec<Circuit>, CircuitsError> {
+        let circuit = self
+            .get_circuit(circuit_id)?
+            .ok_or(CircuitsError::CircuitNotFound)?;
+        self.ancestor_chain(&circuit, false)
+    }
+
+    pub fn get_child_circuits(&self, circuit_id: &Uuid) -> Result<Vec<Circuit>, CircuitsError> {
+        self.storage
+            .get_child_circuits(circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))
+    }
+
+    /// Levels of circuits below `circuit_id`; 0 when it has no children
+    fn subtree_height(&self, circuit_id: &Uuid) -> Result<usize, CircuitsError> {
+        let mut height = 0;
+        let mut level = vec![*circuit_id];
+        let mut seen = HashSet::from([*circuit_id]);
+        while height <= MAX_CIRCUIT_DEPTH {
+            let mut next = Vec::new();
+            for id in &level {
+                for child in self.get_child_circuits(id)? {
+                    if seen.insert(child.circuit_id) {
+                        next.push(child.circuit_id);
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            height += 1;
+            level = next;
+        }
+        Ok(height)
+    }
+
+    /// Place a circuit under `parent_id`, or detach it with `None`. The requester
+    /// must manage permissions in both circuits, so a parent accepts its children.
+    pub async fn set_parent_circuit(
+        &mut self,
+        circuit_id: &Uuid,
+        parent_id: Option<Uuid>,
+        parent_access: ParentAccess,
+        requester_id: &str,
+    ) -> Result<Circuit, CircuitsError> {
+        let mut circuit = self
+            .get_circuit(circuit_id)?
+            .ok_or(CircuitsError::CircuitNotFound)?;
+        if !circuit.has_permission(requester_id, &Permission::ManagePermissions) {
+            return Err(CircuitsError::PermissionDenied(
+                "User does not have permission to change the circuit's parent".to_string(),
+            ));
+        }
+
+        if let Some(parent_id) = parent_id {
+            if parent_id == *circuit_id {
+                return Err(CircuitsError::ValidationError(
+                    "A circuit cannot be its own parent".to_string(),
+                ));
+            }
+            let parent = self
+                .get_circuit(&parent_id)?
+                .ok_or(CircuitsError::CircuitNotFound)?;
+            if !parent.has_permission(requester_id, &Permission::ManagePermissions) {
+                return Err(CircuitsError::PermissionDenied(
+                    "User does not have permission to add children to the parent circuit"
+                        .to_string(),
+                ));
+            }
+            if parent.is_archived() {
+                return Err(CircuitsError::CircuitArchived);
+            }
+
+            let parent_ancestors = self.ancestor_chain(&parent, false)?;
+            if parent_ancestors
+                .iter()
+                .any(|ancestor| ancestor.circuit_id == *circuit_id)
+            {
+                return Err(CircuitsError::ValidationError(
+                    "The parent circuit is a descendant of this circuit".to_string(),
+                ));
+            }
+            // Levels above the circuit, the circuit itself, and levels below it
+            let depth = parent_ancestors.len() + 2 + self.subtree_height(circuit_id)?;
+            if depth > MAX_CIRCUIT_DEPTH {
+                return Err(CircuitsError::ValidationError(format!(
+                    "Circuit hierarchies are limited to {MAX_CIRCUIT_DEPTH} levels"
+                )));
+            }
+        }
+
+        circuit.parent_circuit_id = parent_id;
+        circuit.parent_access = parent_access;
+        circuit.last_modified = Utc::now();
+        self.storage
+            .update_circuit(&circuit)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+        self.logger
+            .lock()
+            .unwrap()
+            .info(
+                "circuits_engine",
+                "circuit_parent_changed",
+                "Circuit parent changed",
+            )
+            .with_context("circuit_id", circuit_id.to_string())
+            .with_context(
+                "parent_circuit_id",
+                parent_id.map(|id| id.to_string()).unwrap_or_default(),
+            )
+            .with_context("requester_id", requester_id.to_string());
+
+        Ok(circuit)
+    }
+
+    /// Whether `user_id` can read a circuit: as a member, or as a member of an
+    /// ancestor reached through links that all grant `ParentAccess::Read`
+    pub fn can_read_circuit(
+        &self,
+        circuit: &Circuit,
+        user_id: &str,
+    ) -> Result<bool, CircuitsError> {
+        if circuit.is_member(user_id) {
+            return Ok(true);
+        }
+        Ok(self
+            .ancestor_chain(circuit, true)?
+            .iter()
+            .any(|ancestor| ancestor.is_member(user_id)))
+    }
+
+    /// Direct members plus the members of ancestors that have read access; a
+    /// user holding several memberships is listed once, with the nearest one
+    pub fn get_effective_members(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<EffectiveMember>, CircuitsError> {
+        let circuit = self
+            .get_circuit(circuit_id)?
+            .ok_or(CircuitsError::CircuitNotFound)?;
+        let ancestors = self.ancestor_chain(&circuit, true)?;
+
+        let mut seen = HashSet::new();
+        let mut members = Vec::new();
+        for source in std::iter::once(&circuit).chain(ancestors.iter()) {
+            for member in &source.members {
+                if seen.insert(member.member_id.clone()) {
+                    members.push(EffectiveMember {
+                        member_id: member.member_id.clone(),
+                        role: member.role,
+                        source_circuit_id: source.circuit_id,
+                        inherited: source.circuit_id != circuit.circuit_id,
+                    });
+                }
+            }
+        }
+        Ok(members)
+    }
+
+    /// Items of a circuit and of every descendant whose items roll up into it
+    /// (each link on the way grants its parent `ParentAccess::Read`)
+    pub fn get_rollup_items(
+        &self,
+        circuit_id: &Uuid,
+        requester_id: &str,
+    ) -> Result<Vec<RolledUpItem>, CircuitsError> {
+        let circuit = self
+            .get_circuit(circuit_id)?
+            .ok_or(CircuitsError::CircuitNotFound)?;
+        if !self.can_read_circuit(&circuit, requester_id)? {
+            return Err(CircuitsError::PermissionDenied(
+                "User cannot read this circuit".to_string(),
+            ));
+        }
+
+        let mut items = Vec::new();
+        let mut seen = HashSet::from([*circuit_id]);
+        let mut queue = VecDeque::from([(*circuit_id, 0)]);
+        while let Some((id, depth)) = queue.pop_front() {
+            items.extend(
+                self.get_circuit_items(&id)?
+                    .into_iter()
+                    .map(|item| RolledUpItem { item, depth }),
+            );
+            if depth >= MAX_CIRCUIT_DEPTH {
+                continue;
+            }
+            for child in self.get_child_circuits(&id)? {
+                if child.parent_access == ParentAccess::Read && seen.insert(child.circuit_id) {
+                    queue.push_back((child.circuit_id, depth + 1));
+                }
+            }
+        }
+        Ok(items)
+    }
+
+    /// Soft-delete a circuit: it keeps its items, operations and history, leaves
+    /// the default listings and rejects pushes and pulls until restored
+    pub async fn archive_circuit(
+        &mut self,
+        circuit_id: &Uuid,
+        requester_id: &str,
+    ) -> Result<Circuit, CircuitsError> {
+        let mut circuit = self
+            .storage
+            .get_circuit(circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .ok_or(CircuitsError::CircuitNotFound)?;
+
+        if !circuit.has_permission(requester_id, &Permission::Delete) {
+            return Err(CircuitsError::PermissionDenied(
+                "User does not have permission to archive circuit".to_string(),
+            ));
+        }
+        if circuit.is_archived() {
+            return Err(CircuitsError::ValidationError(
+                "Circuit is already archived".to_string(),
+            ));
+        }
+
+        let now = chrono::Utc::now();
+        circuit.status = CircuitStatus::Archived;
+        circuit.archived_at = Some(now);
+        circuit.archived_by = Some(requester_id.to_string());
+        circuit.last_modified = now;
+
+        self.storage
+            .update_circuit(&circuit)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+        self.logger
+            .lock()
+            .unwrap()
+            .info("circuits_engine", "circuit_archived", "Circuit archived")
+            .with_context("circuit_id", circuit_id.to_string())
+            .with_context("requester_id", requester_id.to_string());
+
+        Ok(circuit)
+    }
+
+    /// Bring an archived circuit back as active; only its owner can
+    pub async fn restore_circuit(
+        &mut self,
+        circuit_id: &Uuid,
+        requester_id: &str,
+    ) -> Result<Circuit, CircuitsError> {
+        let mut circuit = self
+            .storage
+            .get_circuit(circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .ok_or(CircuitsError::CircuitNotFound)?;
+
+        if circuit.owner_id != requester_id {
+            return Err(CircuitsError::PermissionDenied(
+                "Only the circuit owner can restore it".to_string(),
+            ));
+        }
+        if !circuit.is_archived() {
+            return Err(CircuitsError::ValidationError(
+                "Circuit is not archived".to_string(),
+            ));
+        }
+
+        circuit.status = CircuitStatus::Active;
+        circuit.archived_at = None;
+        circuit.archived_by = None;
+        circuit.last_modified = chrono::Utc::now();
+
+        self.storage
+            .update_circuit(&circuit)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+        self.logger
+            .lock()
+            .unwrap()
+            .info("circuits_engine", "circuit_restored", "Circuit restored")
+            .with_context("circuit_id", circuit_id.to_string())
+            .with_context("requester_id", requester_id.to_string());
+
+        Ok(circuit)
+    }
+
+    /// Offer ownership of the circuit to another member. Only the owner can,
+    /// and ownership changes hands only when that member accepts.
+    pub async fn initiate_ownership_transfer(
+        &mut self,
+        circuit_id: &Uuid,
+        new_owner_id: &str,
+        requester_id: &str,
+    ) -> Result<Circuit, CircuitsError> {
+        let mut circuit = self
+            .storage
+            .get_circuit(circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .ok_or(CircuitsError::CircuitNotFound)?;
+
+        if circuit.owner_id != requester_id {
+            return Err(CircuitsError::PermissionDenied(
+                "Only the circuit owner can transfer ownership".to_string(),
+            ));
+        }
+        if circuit.is_archived() {
+            return Err(CircuitsError::CircuitArchived);
+        }
+        if new_owner_id == requester_id {
+            return Err(CircuitsError::ValidationError(
+                "The new owner must be someone else".to_string(),
+            ));
+        }
+        if circuit.get_member(new_owner_id).is_none() {
+            return Err(CircuitsError::MemberNotFound);
+        }
+
+        let now = Utc::now();
+        let transfer = OwnershipTransfer {
+            new_owner_id: new_owner_id.to_string(),
+            initiated_by: requester_id.to_string(),
+            initiated_at: now,
+            expires_at: now + chrono::Duration::days(OWNERSHIP_TRANSFER_EXPIRY_DAYS),
+        };
+        circuit.pending_ownership_transfer = Some(transfer.clone());
+        circuit.last_modified = now;
+        self.storage
+            .update_circuit(&circuit)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+        self.audit_ownership_change(
+            &circuit,
+            requester_id,
+            "ownership_transfer_initiated",
+            new_owner_id,
+        )?;
+        self.notify_localized(
+            new_owner_id,
+            NotificationType::OwnershipTransferRequested,
+            "ownership_transfer_requested",
+            &[
+                ("circuit_name", circuit.name.as_str()),
+                ("owner", requester_id),
+            ],
+            serde_json::json!({
+                "circuit_id": circuit_id,
+                "circuit_name": circuit.name,
+                "initiated_by": requester_id,
+                "expires_at": transfer.expires_at,
+            }),
+        )?;
+
+        self.logger
+            .lock()
+            .unwrap()
+            .info(
+                "circuits_engine",
+                "ownership_transfer_initiated",
+                "Circuit ownership transfer initiated",
             )
-            .with_context("operation_id", operation_id.to_string())
-            .with_context("approver_id", approver_id.to_string());
+            .with_context("circuit_id", circuit_id.to_string())
+            .with_context("owner_id", requester_id.to_string())
+            .with_context("new_owner_id", new_owner_id.to_string());
 
-        Ok(operation)
+        Ok(circuit)
     }
 
-    pub async fn reject_operation(
+    /// Accept ownership offered to `requester_id`; the previous owner stays
+    /// on as an admin
+    pub async fn accept_ownership_transfer(
         &mut self,
-        operation_id: &Uuid,
-        rejecter_id: &str,
-        reason: Option<String>,
-    ) -> Result<CircuitOperation, CircuitsError> {
-        let mut operation = self
-            .storage
-            .get_circuit_operation(operation_id)
-            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
-            .ok_or(CircuitsError::NotFound)?;
-
-        let circuit = self
+        circuit_id: &Uuid,
+        requester_id: &str,
+    ) -> Result<Circuit, CircuitsError> {
+        let mut circuit = self
             .storage
-            .get_circuit(&operation.circuit_id)
+            .get_circuit(circuit_id)
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?
             .ok_or(CircuitsError::CircuitNotFound)?;
 
-        if !circuit.has_permission(rejecter_id, &Permission::ManageMembers) {
-            return Err(CircuitsError::PermissionDenied(
-                "User does not have permission to reject operations".to_string(),
+        let transfer = circuit
+            .pending_ownership_transfer
+            .clone()
+            .filter(|transfer| transfer.new_owner_id == requester_id)
+            .ok_or(CircuitsError::NotFound)?;
+        if transfer.expires_at <= Utc::now() {
+            return Err(CircuitsError::ValidationError(
+                "The ownership transfer has expired".to_string(),
             ));
         }
-
-        operation.status = OperationStatus::Rejected;
-
-        // Store rejection reason in metadata
-        if let Some(reason_text) = reason {
-            operation.metadata.insert(
-                "rejection_reason".to_string(),
-                serde_json::Value::String(reason_text),
-            );
+        if circuit.is_archived() {
+            return Err(CircuitsError::CircuitArchived);
         }
-        operation.metadata.insert(
-            "rejected_by".to_string(),
-            serde_json::Value::String(rejecter_id.to_string()),
-        );
-        operation.metadata.insert(
-            "rejected_at".to_string(),
-            serde_json::Value::String(Utc::now().to_rfc3339()),
-        );
 
+        let previous_owner = circuit.owner_id.clone();
+        circuit
+            .transfer_ownership(requester_id)
+            .map_err(CircuitsError::ValidationError)?;
         self.storage
-            .update_circuit_operation(&operation)
+            .update_circuit(&circuit)
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+        self.record_member_change(&circuit, requester_id, ChangeKind::MemberUpdated);
+        self.record_member_change(&circuit, &previous_owner, ChangeKind::MemberUpdated);
+
+        self.audit_ownership_change(
+            &circuit,
+            requester_id,
+            "ownership_transferred",
+            &previous_owner,
+        )?;
+        self.notify_localized(
+            &previous_owner,
+            NotificationType::OwnershipTransferred,
+            "ownership_transferred",
+            &[
+                ("circuit_name", circuit.name.as_str()),
+                ("new_owner", requester_id),
+            ],
+            serde_json::json!({
+                "circuit_id": circuit_id,
+                "circuit_name": circuit.name,
+                "previous_owner_id": previous_owner,
+                "new_owner_id": requester_id,
+            }),
+        )?;
 
         self.logger
             .lock()
             .unwrap()
             .info(
                 "circuits_engine",
-                "operation_rejected",
-                "Circuit operation rejected",
+                "ownership_transferred",
+                "Circuit ownership transferred",
             )
-            .with_context("operation_id", operation_id.to_string())
-            .with_context("rejecter_id", rejecter_id.to_string());
-
-        Ok(operation)
-    }
-
-    pub fn list_circuits(&self) -> Result<Vec<Circuit>, CircuitsError> {
-        self.storage
-            .list_circuits()
-            .map_err(|e| CircuitsError::StorageError(e.to_string()))
-    }
-
-    pub fn get_circuit(&self, circuit_id: &Uuid) -> Result<Option<Circuit>, CircuitsError> {
-        self.storage
-            .get_circuit(circuit_id)
-            .map_err(|e| CircuitsError::StorageError(e.to_string()))
-    }
-
-    pub fn get_circuits_for_member(&self, member_id: &str) -> Result<Vec<Circuit>, CircuitsError> {
-        self.storage
-            .get_circuits_for_member(member_id)
-            .map_err(|e| CircuitsError::StorageError(e.to_string()))
-    }
-
-    pub fn get_circuit_operations(
-        &self,
-        circuit_id: &Uuid,
-    ) -> Result<Vec<CircuitOperation>, CircuitsError> {
-        self.storage
-            .get_circuit_operations(circuit_id)
-            .map_err(|e| CircuitsError::StorageError(e.to_string()))
-    }
+            .with_context("circuit_id", circuit_id.to_string())
+            .with_context("previous_owner_id", previous_owner)
+            .with_context("new_owner_id", requester_id.to_string());
 
-    pub fn get_pending_operations(
-        &self,
-        circuit_id: &Uuid,
-    ) -> Result<Vec<CircuitOperation>, CircuitsError> {
-        let operations = self.get_circuit_operations(circuit_id)?;
-        Ok(operations
-            .into_iter()
-            .filter(|op| matches!(op.status, OperationStatus::Pending))
-            .collect())
+        Ok(circuit)
     }
 
-    pub async fn deactivate_circuit(
+    /// Withdraw a pending transfer (the owner) or decline it (the nominee)
+    pub async fn cancel_ownership_transfer(
         &mut self,
         circuit_id: &Uuid,
         requester_id: &str,
@@ -1796,33 +3080,87 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?
             .ok_or(CircuitsError::CircuitNotFound)?;
 
-        if !circuit.has_permission(requester_id, &Permission::ManagePermissions) {
+        let transfer = circuit
+            .pending_ownership_transfer
+            .take()
+            .ok_or(CircuitsError::NotFound)?;
+        if requester_id != circuit.owner_id && requester_id != transfer.new_owner_id {
             return Err(CircuitsError::PermissionDenied(
-                "User does not have permission to deactivate circuit".to_string(),
+                "Only the owner or the proposed owner can cancel the transfer".to_string(),
             ));
         }
-
-        circuit.status = CircuitStatus::Inactive;
-        circuit.last_modified = chrono::Utc::now();
-
+        circuit.last_modified = Utc::now();
         self.storage
             .update_circuit(&circuit)
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
 
+        self.audit_ownership_change(
+            &circuit,
+            requester_id,
+            "ownership_transfer_cancelled",
+            &transfer.new_owner_id,
+        )?;
+
         self.logger
             .lock()
             .unwrap()
             .info(
                 "circuits_engine",
-                "circuit_deactivated",
-                "Circuit deactivated",
+                "ownership_transfer_cancelled",
+                "Circuit ownership transfer cancelled",
             )
             .with_context("circuit_id", circuit_id.to_string())
-            .with_context("requester_id", requester_id.to_string());
+            .with_context("requester_id", requester_id.to_string())
+            .with_context("new_owner_id", transfer.new_owner_id);
 
         Ok(circuit)
     }
 
+    fn audit_ownership_change(
+        &self,
+        circuit: &Circuit,
+        user_id: &str,
+        action: &str,
+        counterpart_id: &str,
+    ) -> Result<(), CircuitsError> {
+        self.audit_membership(
+            circuit,
+            user_id,
+            action,
+            AuditSeverity::High,
+            [("counterpart_id", serde_json::json!(counterpart_id))],
+        )
+    }
+
+    /// Record a change to who belongs to the circuit in the audit trail
+    fn audit_membership<const N: usize>(
+        &self,
+        circuit: &Circuit,
+        user_id: &str,
+        action: &str,
+        severity: AuditSeverity,
+        details: [(&str, serde_json::Value); N],
+    ) -> Result<(), CircuitsError> {
+        let mut event_details = HashMap::from([
+            ("circuit_name".to_string(), serde_json::json!(circuit.name)),
+            ("owner_id".to_string(), serde_json::json!(circuit.owner_id)),
+        ]);
+        event_details.extend(details.map(|(key, value)| (key.to_string(), value)));
+        let event = AuditEvent::new(
+            user_id.to_string(),
+            AuditEventType::Access,
+            action.to_string(),
+            format!("circuit:{}", circuit.circuit_id),
+            AuditOutcome::Success,
+            severity,
+        )
+        .with_resource_id(circuit.circuit_id.to_string())
+        .with_details(event_details);
+        self.storage
+            .store_audit_event(&event)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))
+    }
+
     pub async fn get_logs(&self) -> Vec<crate::logging::LogEntry> {
         self.logger.lock().unwrap().get_logs().to_vec()
     }
@@ -1849,13 +3187,23 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?
             .ok_or(CircuitsError::CircuitNotFound)?;
 
+        if circuit.is_archived() {
+            return Err(CircuitsError::CircuitArchived);
+        }
         circuit
-            .add_join_request(requester_id.to_string(), message)
+            .add_join_request(requester_id.to_string(), message.clone())
             .map_err(CircuitsError::ValidationError)?;
 
         self.storage
             .update_circuit(&circuit)
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+        self.audit_membership(
+            &circuit,
+            requester_id,
+            "circuit_join_requested",
+            AuditSeverity::Low,
+            [("message", serde_json::json!(message))],
+        )?;
 
         self.logger
             .lock()
@@ -1890,14 +3238,30 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
                 "User does not have permission to approve join requests".to_string(),
             ));
         }
+        if role == MemberRole::Owner {
+            return Err(CircuitsError::ValidationError(
+                "Ownership changes hands through an ownership transfer".to_string(),
+            ));
+        }
 
         circuit
-            .approve_join_request(requester_id, role)
+            .approve_join_request(requester_id, role, approver_id)
             .map_err(CircuitsError::ValidationError)?;
 
         self.storage
             .update_circuit(&circuit)
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+        self.record_member_change(&circuit, requester_id, ChangeKind::MemberAdded);
+        self.audit_membership(
+            &circuit,
+            approver_id,
+            "circuit_join_approved",
+            AuditSeverity::Medium,
+            [
+                ("requester_id", serde_json::json!(requester_id)),
+                ("role", serde_json::json!(role)),
+            ],
+        )?;
 
         self.logger
             .lock()
@@ -1919,6 +3283,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
         circuit_id: &Uuid,
         requester_id: &str,
         rejector_id: &str,
+        reason: Option<String>,
     ) -> Result<Circuit, CircuitsError> {
         let mut circuit = self
             .storage
@@ -1934,12 +3299,22 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
         }
 
         circuit
-            .reject_join_request(requester_id)
+            .reject_join_request(requester_id, rejector_id, reason.clone())
             .map_err(CircuitsError::ValidationError)?;
 
         self.storage
             .update_circuit(&circuit)
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+        self.audit_membership(
+            &circuit,
+            rejector_id,
+            "circuit_join_rejected",
+            AuditSeverity::Medium,
+            [
+                ("requester_id", serde_json::json!(requester_id)),
+                ("reason", serde_json::json!(reason)),
+            ],
+        )?;
 
         self.logger
             .lock()
@@ -1956,15 +3331,22 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
         Ok(circuit)
     }
 
+    /// Requests waiting for a decision, oldest first; for member managers
     pub fn get_pending_join_requests(
         &self,
         circuit_id: &Uuid,
+        requester_id: &str,
     ) -> Result<Vec<crate::types::JoinRequest>, CircuitsError> {
         let circuit = self
             .storage
             .get_circuit(circuit_id)
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?
             .ok_or(CircuitsError::CircuitNotFound)?;
+        if !circuit.has_permission(requester_id, &crate::types::Permission::ManageMembers) {
+            return Err(CircuitsError::PermissionDenied(
+                "User does not have permission to review join requests".to_string(),
+            ));
+        }
 
         Ok(circuit
             .get_pending_requests()
@@ -2071,7 +3453,12 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             }
         }
 
-        // Create the adapter config
+        // Create the adapter config, keeping any replication settings already in place
+        let (replication_policy, mirror_adapters) = circuit
+            .adapter_config
+            .as_ref()
+            .map(|c| (c.replication_policy.clone(), c.mirror_adapters.clone()))
+            .unwrap_or_default();
         let adapter_config = CircuitAdapterConfig {
             circuit_id: *circuit_id,
             adapter_type,
@@ -2080,6 +3467,8 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             requires_approval,
             auto_migrate_existing,
             sponsor_adapter_access,
+            replication_policy,
+            mirror_adapters,
         };
 
         // Update the circuit
@@ -2102,14 +3491,30 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
         );
 
         // Send notifications to all circuit members
+        let localizer = crate::i18n::Localizer::global();
+        let args = [
+            ("circuit_name", circuit.name.as_str()),
+            ("configured_by", requester_id),
+        ];
         for member in &circuit.members {
+            let locale = self
+                .storage
+                .get_user_account(&member.member_id)
+                .ok()
+                .flatten()
+                .and_then(|user| user.locale);
             let notification = Notification::new(
                 member.member_id.clone(),
                 NotificationType::CircuitAdapterConfigUpdated,
-                "Circuit Adapter Configuration Updated".to_string(),
-                format!(
-                    "The adapter configuration for circuit '{}' has been updated by {}",
-                    circuit.name, requester_id
+                localizer.translate(
+                    locale.as_deref(),
+                    "notification.adapter_config_updated.title",
+                    &args,
+                ),
+                localizer.translate(
+                    locale.as_deref(),
+                    "notification.adapter_config_updated.message",
+                    &args,
                 ),
                 serde_json::json!({
                     "circuit_id": circuit_id,
@@ -2123,6 +3528,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             self.storage
                 .store_notification(&notification)
                 .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+            self.publish_live(LiveRecord::Notification(notification));
         }
 
         self.logger
@@ -2138,7 +3544,195 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             .with_context("adapter_type", format!("{:?}", adapter_config.adapter_type))
             .with_context("sponsor_adapter_access", sponsor_adapter_access.to_string());
 
-        Ok(adapter_config)
+        Ok(adapter_config)
+    }
+
+    /// Configure how pushed items are replicated across the circuit's adapters
+    pub async fn set_circuit_replication_policy(
+        &mut self,
+        circuit_id: &Uuid,
+        requester_id: &str,
+        replication_policy: ReplicationPolicy,
+        mirror_adapters: Vec<AdapterType>,
+    ) -> Result<CircuitAdapterConfig, CircuitsError> {
+        let mut circuit = self
+            .storage
+            .get_circuit(circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .ok_or(CircuitsError::CircuitNotFound)?;
+
+        if circuit.owner_id != requester_id
+            && !circuit.has_permission(requester_id, &Permission::ManagePermissions)
+        {
+            return Err(CircuitsError::PermissionDenied(
+                "Only circuit owner or admins can configure adapter settings".to_string(),
+            ));
+        }
+
+        let mut adapter_config = circuit.adapter_config.clone().ok_or_else(|| {
+            CircuitsError::ValidationError("Circuit has no adapter configuration".to_string())
+        })?;
+
+        if replication_policy != ReplicationPolicy::Single {
+            let primary = match &adapter_config.adapter_type {
+                None | Some(AdapterType::None) => {
+                    return Err(CircuitsError::ValidationError(
+                        "A primary adapter must be configured before enabling replication"
+                            .to_string(),
+                    ))
+                }
+                Some(primary) => primary.clone(),
+            };
+
+            if mirror_adapters.is_empty() {
+                return Err(CircuitsError::ValidationError(
+                    "Replication requires at least one mirror adapter".to_string(),
+                ));
+            }
+            if mirror_adapters.contains(&primary) {
+                return Err(CircuitsError::ValidationError(
+                    "Mirror adapters must differ from the primary adapter".to_string(),
+                ));
+            }
+            if let ReplicationPolicy::Quorum { min_successes } = replication_policy {
+                if min_successes == 0 || min_successes > mirror_adapters.len() + 1 {
+                    return Err(CircuitsError::ValidationError(format!(
+                        "Quorum must be between 1 and {}",
+                        mirror_adapters.len() + 1
+                    )));
+                }
+            }
+
+            let user = self
+                .storage
+                .get_user_account(requester_id)
+                .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+                .ok_or_else(|| CircuitsError::ValidationError("User not found".to_string()))?;
+            if let Some(adapter) = mirror_adapters
+                .iter()
+                .find(|a| !validate_adapter_tier_access(&user.tier, a))
+            {
+                return Err(CircuitsError::PermissionDenied(format!(
+                    "Your tier ({}) does not have access to the {:?} adapter",
+                    user.tier.as_str(),
+                    adapter
+                )));
+            }
+        }
+
+        adapter_config.replication_policy = replication_policy;
+        adapter_config.mirror_adapters = mirror_adapters;
+        adapter_config.configured_by = requester_id.to_string();
+        adapter_config.configured_at = Utc::now();
+
+        circuit.adapter_config = Some(adapter_config.clone());
+        circuit.last_modified = Utc::now();
+        self.storage
+            .update_circuit(&circuit)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+
+        self.logger
+            .lock()
+            .unwrap()
+            .info(
+                "circuits_engine",
+                "replication_policy_updated",
+                "Circuit replication policy updated",
+            )
+            .with_context("circuit_id", circuit_id.to_string())
+            .with_context("requester_id", requester_id.to_string())
+            .with_context(
+                "replication_policy",
+                format!("{:?}", adapter_config.replication_policy),
+            );
+
+        Ok(adapter_config)
+    }
+
+    /// Dry-run a candidate adapter configuration before switching the circuit to it.
+    /// Fields left as `None` keep the circuit's current values. The sample item is `dfid`
+    /// when given, otherwise a synthetic new item; nothing is written to any adapter.
+    pub async fn dry_run_circuit_adapter_config(
+        &self,
+        circuit_id: &Uuid,
+        requester_id: &str,
+        adapter_type: Option<AdapterType>,
+        replication_policy: Option<ReplicationPolicy>,
+        mirror_adapters: Option<Vec<AdapterType>>,
+        dfid: Option<&str>,
+    ) -> Result<CircuitDryRunReport, CircuitsError> {
+        let circuit = self
+            .storage
+            .get_circuit(circuit_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .ok_or(CircuitsError::CircuitNotFound)?;
+
+        if circuit.owner_id != requester_id
+            && !circuit.has_permission(requester_id, &Permission::ManagePermissions)
+        {
+            return Err(CircuitsError::PermissionDenied(
+                "Only circuit owner or admins can configure adapter settings".to_string(),
+            ));
+        }
+
+        let mut candidate = circuit.adapter_config.clone().ok_or_else(|| {
+            CircuitsError::ValidationError("Circuit has no adapter configuration".to_string())
+        })?;
+        if let Some(adapter_type) = adapter_type {
+            candidate.adapter_type = Some(adapter_type);
+        }
+        if let Some(replication_policy) = replication_policy {
+            candidate.replication_policy = replication_policy;
+        }
+        if let Some(mirror_adapters) = mirror_adapters {
+            candidate.mirror_adapters = mirror_adapters;
+        }
+
+        // Same tier rules as actually switching the configuration
+        let user = self
+            .storage
+            .get_user_account(requester_id)
+            .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+            .ok_or_else(|| CircuitsError::ValidationError("User not found".to_string()))?;
+        if let Some(adapter) = candidate
+            .adapter_type
+            .iter()
+            .chain(candidate.mirror_adapters.iter())
+            .find(|a| !validate_adapter_tier_access(&user.tier, a))
+        {
+            return Err(CircuitsError::PermissionDenied(format!(
+                "Your tier ({}) does not have access to the {:?} adapter",
+                user.tier.as_str(),
+                adapter
+            )));
+        }
+
+        let (item, is_new_dfid) = match dfid {
+            Some(dfid) => {
+                let item = self
+                    .storage
+                    .get_item_by_dfid(dfid)
+                    .map_err(|e| CircuitsError::StorageError(e.to_string()))?
+                    .ok_or(CircuitsError::ItemNotFound)?;
+                (item, false)
+            }
+            None => (
+                Item::new(
+                    format!("DFID-DRY-RUN-{}", Uuid::new_v4().simple()),
+                    Vec::new(),
+                    Uuid::nil(),
+                ),
+                true,
+            ),
+        };
+
+        self.adapter_manager
+            .dry_run_circuit_config(&candidate, &item, is_new_dfid)
+            .await
+            .map_err(|e| match e {
+                AdapterManagerError::ValidationError(msg) => CircuitsError::ValidationError(msg),
+                other => CircuitsError::StorageError(other.to_string()),
+            })
     }
 
     pub async fn create_custom_role(
@@ -2233,6 +3827,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
         self.storage
             .store_circuit(&circuit)
             .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+        self.record_member_change(&circuit, member_id, ChangeKind::MemberUpdated);
 
         self.logger
             .lock()
@@ -2512,6 +4107,14 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             self.storage
                 .store_circuit(&circuit)
                 .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+            self.record_member_change(&circuit, requester_id, ChangeKind::MemberAdded);
+            self.audit_membership(
+                &circuit,
+                requester_id,
+                "circuit_join_auto_approved",
+                AuditSeverity::Low,
+                [("role", serde_json::json!(crate::types::MemberRole::Member))],
+            )?;
 
             self.logger
                 .lock()
@@ -2528,12 +4131,19 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
         } else {
             // Create join request
             circuit
-                .add_join_request(requester_id.to_string(), message)
+                .add_join_request(requester_id.to_string(), message.clone())
                 .map_err(CircuitsError::ValidationError)?;
 
             self.storage
                 .store_circuit(&circuit)
                 .map_err(|e| CircuitsError::StorageError(e.to_string()))?;
+            self.audit_membership(
+                &circuit,
+                requester_id,
+                "circuit_join_requested",
+                AuditSeverity::Low,
+                [("message", serde_json::json!(message))],
+            )?;
 
             self.logger
                 .lock()
@@ -2640,6 +4250,7 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
         operation_id: &Uuid,
         trigger_event: PostActionTrigger,
         storage_details: Option<WebhookStorageData>,
+        priority: IngestionPriority,
     ) {
         // Check if post-action settings are enabled (optional)
         let post_settings = match &circuit.post_action_settings {
@@ -2689,13 +4300,22 @@ impl<S: StorageBackend + 'static> CircuitsEngine<S> {
             },
             operation_id: operation_id.to_string(),
             status: "completed".to_string(),
+            priority,
         };
 
+        // Webhook filters look at the item event the operation follows
+        let subject = self.latest_item_event(dfid);
+
         // Trigger webhooks asynchronously
         let webhook_result = {
             let mut webhook_guard = self.webhook_engine.write().await;
             webhook_guard
-                .trigger_webhooks(&circuit.circuit_id, trigger_event, payload)
+                .trigger_webhooks(
+                    &circuit.circuit_id,
+                    trigger_event,
+                    payload,
+                    subject.as_ref(),
+                )
                 .await
         };
 
@@ -2772,6 +4392,51 @@ mod tests {
         assert_eq!(circuit.members[0].member_id, "owner123");
     }
 
+    #[tokio::test]
+    async fn test_replication_policy_requires_primary_adapter() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let mut circuits_engine = CircuitsEngine::new(Arc::clone(&storage));
+
+        // Circuit::new initializes the default "none" adapter configuration
+        let circuit = Circuit::new(
+            "Replicated".to_string(),
+            "A replicated circuit".to_string(),
+            "owner123".to_string(),
+        );
+        storage.store_circuit(&circuit).unwrap();
+
+        let result = circuits_engine
+            .set_circuit_replication_policy(
+                &circuit.circuit_id,
+                "owner123",
+                ReplicationPolicy::WriteAll,
+                vec![AdapterType::IpfsIpfs],
+            )
+            .await;
+        assert!(matches!(result, Err(CircuitsError::ValidationError(_))));
+
+        let result = circuits_engine
+            .set_circuit_replication_policy(
+                &circuit.circuit_id,
+                "someone-else",
+                ReplicationPolicy::Single,
+                vec![],
+            )
+            .await;
+        assert!(matches!(result, Err(CircuitsError::PermissionDenied(_))));
+
+        let config = circuits_engine
+            .set_circuit_replication_policy(
+                &circuit.circuit_id,
+                "owner123",
+                ReplicationPolicy::Single,
+                vec![],
+            )
+            .await
+            .unwrap();
+        assert_eq!(config.replication_policy, ReplicationPolicy::Single);
+    }
+
     #[tokio::test]
     async fn test_add_member_to_circuit() {
         let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
@@ -2884,6 +4549,583 @@ mod tests {
             CircuitsError::PermissionDenied(_)
         ));
     }
+
+    #[tokio::test]
+    async fn test_parent_members_read_children_and_items_roll_up() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let mut engine = CircuitsEngine::new(Arc::clone(&storage));
+        let circuit = |name: &str| {
+            let circuit = Circuit::new(name.to_string(), String::new(), "federation".to_string());
+            storage.lock().unwrap().store_circuit(&circuit).unwrap();
+            storage
+                .lock()
+                .unwrap()
+                .store_circuit_item(&CircuitItem::new(
+                    format!("DFID-{name}"),
+                    circuit.circuit_id,
+                    "federation".to_string(),
+                    Vec::new(),
+                ))
+                .unwrap();
+            circuit.circuit_id
+        };
+        let national = circuit("national");
+        let region = circuit("region");
+        let coop = circuit("coop");
+        let private_coop = circuit("private-coop");
+
+        for (child, parent, access) in [
+            (region, national, ParentAccess::Read),
+            (coop, region, ParentAccess::Read),
+            (private_coop, region, ParentAccess::None),
+        ] {
+            engine
+                .set_parent_circuit(&child, Some(parent), access, "federation")
+                .await
+                .unwrap();
+        }
+        assert!(matches!(
+            engine
+                .set_parent_circuit(&national, Some(coop), ParentAccess::Read, "federation")
+                .await,
+            Err(CircuitsError::ValidationError(_))
+        ));
+
+        // A national member reads the co-op through the region
+        engine
+            .add_member_to_circuit(
+                &national,
+                "auditor".to_string(),
+                MemberRole::Viewer,
+                "federation",
+            )
+            .await
+            .unwrap();
+        let coop_circuit = engine.get_circuit(&coop).unwrap().unwrap();
+        assert!(engine.can_read_circuit(&coop_circuit, "auditor").unwrap());
+        let private = engine.get_circuit(&private_coop).unwrap().unwrap();
+        assert!(!engine.can_read_circuit(&private, "auditor").unwrap());
+
+        let members = engine.get_effective_members(&coop).unwrap();
+        let auditor = members.iter().find(|m| m.member_id == "auditor").unwrap();
+        assert!(auditor.inherited);
+        assert_eq!(auditor.source_circuit_id, national);
+
+        let mut rolled_up: Vec<(String, usize)> = engine
+            .get_rollup_items(&national, "auditor")
+            .unwrap()
+            .into_iter()
+            .map(|r| (r.item.dfid, r.depth))
+            .collect();
+        rolled_up.sort();
+        assert_eq!(
+            rolled_up,
+            [
+                ("DFID-coop".to_string(), 2),
+                ("DFID-national".to_string(), 0),
+                ("DFID-region".to_string(), 1),
+            ]
+        );
+        assert_eq!(engine.get_ancestor_circuits(&coop).unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_ownership_changes_hands_only_when_accepted() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let mut engine = CircuitsEngine::new(Arc::clone(&storage));
+        let circuit = engine
+            .create_circuit(
+                "Co-op".to_string(),
+                "Handover".to_string(),
+                "departing".to_string(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let circuit_id = circuit.circuit_id;
+        engine
+            .add_member_to_circuit(
+                &circuit_id,
+                "successor".to_string(),
+                MemberRole::Member,
+                "departing",
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            engine
+                .initiate_ownership_transfer(&circuit_id, "departing", "successor")
+                .await,
+            Err(CircuitsError::PermissionDenied(_))
+        ));
+        assert!(matches!(
+            engine
+                .initiate_ownership_transfer(&circuit_id, "stranger", "departing")
+                .await,
+            Err(CircuitsError::MemberNotFound)
+        ));
+        let pending = engine
+            .initiate_ownership_transfer(&circuit_id, "successor", "departing")
+            .await
+            .unwrap();
+        assert_eq!(pending.owner_id, "departing");
+        assert!(matches!(
+            engine
+                .accept_ownership_transfer(&circuit_id, "departing")
+                .await,
+            Err(CircuitsError::NotFound)
+        ));
+
+        let circuit = engine
+            .accept_ownership_transfer(&circuit_id, "successor")
+            .await
+            .unwrap();
+        assert_eq!(circuit.owner_id, "successor");
+        assert!(circuit.pending_ownership_transfer.is_none());
+        assert!(circuit.has_permission("successor", &Permission::Delete));
+        assert_eq!(
+            circuit.get_member("departing").unwrap().role,
+            MemberRole::Admin
+        );
+        assert!(!circuit.has_permission("departing", &Permission::Delete));
+
+        let audited: Vec<String> = storage
+            .list_audit_events()
+            .unwrap()
+            .into_iter()
+            .map(|event| event.action)
+            .collect();
+        assert!(audited.contains(&"ownership_transfer_initiated".to_string()));
+        assert!(audited.contains(&"ownership_transferred".to_string()));
+        let notified = |user: &str, expected: fn(&NotificationType) -> bool| {
+            storage
+                .get_user_notifications(user, None, None, false)
+                .unwrap()
+                .iter()
+                .any(|n| expected(&n.notification_type))
+        };
+        assert!(notified("successor", |t| matches!(
+            t,
+            NotificationType::OwnershipTransferRequested
+        )));
+        assert!(notified("departing", |t| matches!(
+            t,
+            NotificationType::OwnershipTransferred
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_join_requests_are_decided_by_member_managers_and_audited() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let mut engine = CircuitsEngine::new(Arc::clone(&storage));
+        let circuit = engine
+            .create_circuit(
+                "Co-op".to_string(),
+                "Open network".to_string(),
+                "owner".to_string(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let circuit_id = circuit.circuit_id;
+        for requester in ["grower", "spammer"] {
+            engine
+                .request_to_join_circuit(&circuit_id, requester, Some("Hi".to_string()))
+                .await
+                .unwrap();
+        }
+
+        assert!(matches!(
+            engine.get_pending_join_requests(&circuit_id, "grower"),
+            Err(CircuitsError::PermissionDenied(_))
+        ));
+        let queue = engine
+            .get_pending_join_requests(&circuit_id, "owner")
+            .unwrap();
+        assert_eq!(queue.len(), 2);
+        assert!(matches!(
+            engine
+                .approve_join_request(&circuit_id, "grower", "owner", MemberRole::Owner)
+                .await,
+            Err(CircuitsError::ValidationError(_))
+        ));
+
+        let circuit = engine
+            .approve_join_request(&circuit_id, "grower", "owner", MemberRole::Viewer)
+            .await
+            .unwrap();
+        assert_eq!(
+            circuit.get_member("grower").unwrap().role,
+            MemberRole::Viewer
+        );
+        let circuit = engine
+            .reject_join_request(
+                &circuit_id,
+                "spammer",
+                "owner",
+                Some("Not a producer".to_string()),
+            )
+            .await
+            .unwrap();
+        let rejected = &circuit.pending_requests[1];
+        assert_eq!(rejected.decided_by.as_deref(), Some("owner"));
+        assert_eq!(rejected.decision_reason.as_deref(), Some("Not a producer"));
+        assert!(engine
+            .get_pending_join_requests(&circuit_id, "owner")
+            .unwrap()
+            .is_empty());
+
+        let audited: Vec<String> = storage
+            .list_audit_events()
+            .unwrap()
+            .into_iter()
+            .map(|event| event.action)
+            .collect();
+        for action in [
+            "circuit_join_requested",
+            "circuit_join_approved",
+            "circuit_join_rejected",
+        ] {
+            assert!(audited.contains(&action.to_string()), "{action}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archived_circuit_rejects_push_and_pull_until_restored() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        create_test_item(&storage, "DFID-123");
+        let mut circuits_engine = CircuitsEngine::new(Arc::clone(&storage));
+
+        let circuit = circuits_engine
+            .create_circuit(
+                "Test Circuit".to_string(),
+                "A test circuit".to_string(),
+                "owner123".to_string(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let circuit_id = circuit.circuit_id;
+        circuits_engine
+            .push_item_to_circuit("DFID-123", &circuit_id, "owner123")
+            .await
+            .unwrap();
+
+        let archived = circuits_engine
+            .archive_circuit(&circuit_id, "owner123")
+            .await
+            .unwrap();
+        assert!(archived.is_archived());
+        assert_eq!(archived.archived_by.as_deref(), Some("owner123"));
+        assert!(matches!(
+            circuits_engine
+                .push_item_to_circuit("DFID-123", &circuit_id, "owner123")
+                .await,
+            Err(CircuitsError::CircuitArchived)
+        ));
+        assert!(matches!(
+            circuits_engine
+                .pull_item_from_circuit("DFID-123", &circuit_id, "owner123")
+                .await,
+            Err(CircuitsError::CircuitArchived)
+        ));
+
+        // History stays and the circuit is still reachable by id
+        assert_eq!(
+            circuits_engine
+                .get_circuit_operations(&circuit_id)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            circuits_engine
+                .get_archived_circuits_for_member("owner123")
+                .unwrap()
+                .len(),
+            1
+        );
+
+        circuits_engine
+            .add_member_to_circuit(
+                &circuit_id,
+                "member456".to_string(),
+                MemberRole::Admin,
+                "owner123",
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            circuits_engine
+                .restore_circuit(&circuit_id, "member456")
+                .await,
+            Err(CircuitsError::PermissionDenied(_))
+        ));
+        let restored = circuits_engine
+            .restore_circuit(&circuit_id, "owner123")
+            .await
+            .unwrap();
+        assert!(!restored.is_archived());
+        assert!(restored.archived_at.is_none());
+        circuits_engine
+            .pull_item_from_circuit("DFID-123", &circuit_id, "owner123")
+            .await
+            .unwrap();
+        assert!(circuits_engine
+            .get_archived_circuits_for_member("owner123")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_forwarded_item_stays_in_source_with_linked_provenance() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        create_test_item(&storage, "DFID-123");
+        let mut circuits_engine = CircuitsEngine::new(Arc::clone(&storage));
+        let mut ids = Vec::new();
+        for (name, owner) in [
+            ("Co-op", "owner123"),
+            ("Exporter", "owner123"),
+            ("Other", "someone_else"),
+        ] {
+            let circuit = circuits_engine
+                .create_circuit(
+                    name.to_string(),
+                    "Forwarding".to_string(),
+                    owner.to_string(),
+                    None,
+                    None,
+                )
+                .await
+                .unwrap();
+            ids.push(circuit.circuit_id);
+        }
+        let (source, target, foreign) = (ids[0], ids[1], ids[2]);
+        circuits_engine
+            .push_item_to_circuit("DFID-123", &source, "owner123")
+            .await
+            .unwrap();
+
+        // Rights are needed in both circuits
+        assert!(matches!(
+            circuits_engine
+                .forward_item_to_circuit("DFID-123", &source, &foreign, "owner123")
+                .await,
+            Err(CircuitsError::PermissionDenied(_))
+        ));
+        assert!(matches!(
+            circuits_engine
+                .forward_item_to_circuit("DFID-123", &source, &source, "owner123")
+                .await,
+            Err(CircuitsError::ValidationError(_))
+        ));
+
+        let forward = circuits_engine
+            .forward_item_to_circuit("DFID-123", &source, &target, "owner123")
+            .await
+            .unwrap();
+        for circuit_id in [source, target] {
+            assert!(storage
+                .get_circuit_items(&circuit_id)
+                .unwrap()
+                .iter()
+                .any(|item| item.dfid == "DFID-123"));
+        }
+
+        let events = storage.get_events_by_dfid("DFID-123").unwrap();
+        let forward_event = events
+            .iter()
+            .find(|e| e.event_id == forward.forward_event_id)
+            .unwrap();
+        assert_eq!(forward_event.event_type, EventType::ForwardedToCircuit);
+        assert_eq!(
+            forward_event.metadata["target_circuit_id"],
+            serde_json::json!(target.to_string())
+        );
+        let push_event = events
+            .iter()
+            .find(|e| {
+                e.correlation_id == Some(forward.operation.operation_id)
+                    && e.event_type == EventType::PushedToCircuit
+            })
+            .unwrap();
+        assert_eq!(push_event.caused_by, Some(forward.forward_event_id));
+        assert_eq!(
+            push_event.metadata["forwarded_from"],
+            serde_json::json!(source.to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_items_are_removed_and_both_parties_notified() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        create_test_item(&storage, "DFID-123");
+        let mut circuits_engine = CircuitsEngine::new(Arc::clone(&storage));
+        let circuit = circuits_engine
+            .create_circuit(
+                "Buyer".to_string(),
+                "Supplier data for 90 days".to_string(),
+                "buyer".to_string(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let circuit_id = circuit.circuit_id;
+        for (member, role) in [
+            ("supplier", MemberRole::Member),
+            ("viewer", MemberRole::Viewer),
+        ] {
+            circuits_engine
+                .add_member_to_circuit(&circuit_id, member.to_string(), role, "buyer")
+                .await
+                .unwrap();
+        }
+        circuits_engine
+            .push_item_to_circuit("DFID-123", &circuit_id, "supplier")
+            .await
+            .unwrap();
+
+        let expires_at = Utc::now() + chrono::Duration::days(90);
+        assert!(matches!(
+            circuits_engine
+                .set_circuit_item_expiry(&circuit_id, "DFID-123", Some(expires_at), "viewer")
+                .await,
+            Err(CircuitsError::PermissionDenied(_))
+        ));
+        assert!(matches!(
+            circuits_engine
+                .set_circuit_item_expiry(
+                    &circuit_id,
+                    "DFID-123",
+                    Some(Utc::now() - chrono::Duration::days(1)),
+                    "supplier"
+                )
+                .await,
+            Err(CircuitsError::ValidationError(_))
+        ));
+        let item = circuits_engine
+            .set_circuit_item_expiry(&circuit_id, "DFID-123", Some(expires_at), "supplier")
+            .await
+            .unwrap();
+        assert_eq!(item.expires_at, Some(expires_at));
+
+        // Nothing is removed before the expiry
+        assert!(circuits_engine
+            .remove_expired_items(Utc::now())
+            .await
+            .unwrap()
+            .is_empty());
+
+        let removed = circuits_engine
+            .remove_expired_items(expires_at)
+            .await
+            .unwrap();
+        assert_eq!(removed.len(), 1);
+        assert!(storage.get_circuit_items(&circuit_id).unwrap().is_empty());
+
+        let events = storage.get_events_by_dfid("DFID-123").unwrap();
+        let removal = events
+            .iter()
+            .find(|e| e.event_type == EventType::RemovedFromCircuit)
+            .unwrap();
+        assert_eq!(
+            removal.metadata["circuit_id"],
+            serde_json::json!(circuit_id.to_string())
+        );
+        for party in ["supplier", "buyer"] {
+            let notifications = storage
+                .get_user_notifications(party, None, None, false)
+                .unwrap();
+            assert!(notifications
+                .iter()
+                .any(|n| matches!(n.notification_type, NotificationType::CircuitItemExpired)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resume_completes_recorded_writes_and_fails_orphaned_ones() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let circuits_engine = CircuitsEngine::new(Arc::clone(&storage));
+
+        // The process stopped after DFID-1's storage record was added but before
+        // its intent was completed; DFID-2's circuit has since been deleted
+        let recorded = adapter_intent_log::record_intent(
+            &storage,
+            "DFID-1",
+            Some(Uuid::new_v4()),
+            AdapterType::IpfsIpfs,
+            AdapterOperation::CircuitPush,
+            true,
+            "owner123",
+        )
+        .unwrap();
+        let location = StorageLocation::IPFS {
+            cid: "bafy-dfid-1".to_string(),
+            pinned: true,
+        };
+        storage
+            .add_storage_record(
+                "DFID-1",
+                crate::types::StorageRecord {
+                    adapter_type: AdapterType::IpfsIpfs,
+                    storage_location: location.clone(),
+                    stored_at: Utc::now(),
+                    triggered_by: "circuit_push".to_string(),
+                    triggered_by_id: None,
+                    events_range: None,
+                    is_active: true,
+                    metadata: HashMap::new(),
+                },
+            )
+            .unwrap();
+        let orphaned = adapter_intent_log::record_intent(
+            &storage,
+            "DFID-2",
+            Some(Uuid::new_v4()),
+            AdapterType::IpfsIpfs,
+            AdapterOperation::StorageMigration,
+            false,
+            "owner123",
+        )
+        .unwrap();
+
+        // Still pending and recently updated: assumed in flight
+        assert!(circuits_engine
+            .resume_adapter_intents(chrono::Duration::seconds(600))
+            .await
+            .unwrap()
+            .is_empty());
+
+        let resumed = circuits_engine
+            .resume_adapter_intents(chrono::Duration::zero())
+            .await
+            .unwrap();
+        assert_eq!(resumed.len(), 2);
+        let intent = storage
+            .get_adapter_intent(&recorded.intent_id)
+            .unwrap()
+            .unwrap();
+        assert!(intent.is_complete());
+        assert_eq!(intent.attempts, 1);
+        assert!(matches!(
+            intent.storage_location,
+            Some(StorageLocation::IPFS { ref cid, .. }) if cid == "bafy-dfid-1"
+        ));
+        let intent = storage
+            .get_adapter_intent(&orphaned.intent_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(intent.status, crate::types::AdapterIntentStatus::Failed);
+
+        let incomplete = circuits_engine.get_incomplete_adapter_intents().unwrap();
+        assert_eq!(incomplete.len(), 1);
+        assert_eq!(incomplete[0].dfid, "DFID-2");
+    }
 }
 
 // New structures for push_local_item_to_circuit
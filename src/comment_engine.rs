@@ -0,0 +1,363 @@
+//! Comment threads on circuit events and pending operations.
+//!
+//! Members coordinate on data quality inside the circuit: a thread hangs off
+//! one event of an item in the circuit, or off a push or pull waiting for
+//! approval. Threads are visible to circuit members only. Writing
+//! `@member_id` in a comment notifies that member.
+
+use crate::live_stream::{LiveRecord, LiveStream};
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    Circuit, CircuitComment, CommentTarget, Notification, NotificationType, OperationStatus,
+    Permission,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+pub const MAX_COMMENT_LENGTH: usize = 4000;
+/// Characters of the comment quoted in a mention notification
+const MENTION_PREVIEW_CHARS: usize = 140;
+
+#[derive(Debug)]
+pub enum CommentError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+    NotFound(String),
+}
+
+impl From<StorageError> for CommentError {
+    fn from(err: StorageError) -> Self {
+        CommentError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for CommentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommentError::StorageError(e) => write!(f, "Storage error: {e}"),
+            CommentError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            CommentError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            CommentError::NotFound(e) => write!(f, "Not found: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for CommentError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AddCommentInput {
+    pub body: String,
+    pub reply_to: Option<Uuid>,
+}
+
+/// `@member_id` mentions in `body`, in order of first appearance
+pub fn parse_mentions(body: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    for word in body.split_whitespace() {
+        let Some(name) = word.strip_prefix('@') else {
+            continue;
+        };
+        let name: String = name
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+            .collect();
+        let name = name.trim_end_matches('.');
+        if !name.is_empty() && !mentions.iter().any(|m| m == name) {
+            mentions.push(name.to_string());
+        }
+    }
+    mentions
+}
+
+pub struct CommentEngine<S: StorageBackend> {
+    storage: S,
+    live_stream: Option<LiveStream>,
+}
+
+impl<S: StorageBackend> CommentEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            live_stream: None,
+        }
+    }
+
+    /// Publish mention notifications to `/api/stream` subscribers
+    pub fn with_live_stream(mut self, live_stream: LiveStream) -> Self {
+        self.live_stream = Some(live_stream);
+        self
+    }
+
+    fn member_circuit(&self, circuit_id: &Uuid, user_id: &str) -> Result<Circuit, CommentError> {
+        let circuit = self
+            .storage
+            .get_circuit(circuit_id)?
+            .ok_or_else(|| CommentError::NotFound(format!("Circuit {circuit_id}")))?;
+        if !circuit.is_member(user_id) {
+            return Err(CommentError::PermissionDenied(
+                "Only circuit members can see its comments".to_string(),
+            ));
+        }
+        Ok(circuit)
+    }
+
+    /// The target must belong to the circuit: an event recorded for it or on
+    /// one of its items, or an operation of the circuit that is still pending
+    fn check_target(&self, circuit: &Circuit, target: &CommentTarget) -> Result<(), CommentError> {
+        match target {
+            CommentTarget::Event { event_id } => {
+                let event = self
+                    .storage
+                    .get_event(event_id)?
+                    .ok_or_else(|| CommentError::NotFound(format!("Event {event_id}")))?;
+                let circuit_id = circuit.circuit_id.to_string();
+                let recorded_here = event
+                    .metadata
+                    .get("circuit_id")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|id| id == circuit_id);
+                let item_here = || -> Result<bool, CommentError> {
+                    Ok(self
+                        .storage
+                        .get_circuit_items(&circuit.circuit_id)?
+                        .iter()
+                        .any(|item| item.dfid == event.dfid))
+                };
+                if !recorded_here && !item_here()? {
+                    return Err(CommentError::NotFound(format!(
+                        "Event {event_id} in this circuit"
+                    )));
+                }
+            }
+            CommentTarget::PendingOperation { operation_id } => {
+                let operation = self
+                    .storage
+                    .get_circuit_operation(operation_id)?
+                    .filter(|operation| operation.circuit_id == circuit.circuit_id)
+                    .ok_or_else(|| {
+                        CommentError::NotFound(format!("Operation {operation_id} in this circuit"))
+                    })?;
+                if !matches!(operation.status, OperationStatus::Pending) {
+                    return Err(CommentError::ValidationError(
+                        "Only pending operations can be discussed".to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn add(
+        &self,
+        circuit_id: &Uuid,
+        target: CommentTarget,
+        input: AddCommentInput,
+        author_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<CircuitComment, CommentError> {
+        let circuit = self.member_circuit(circuit_id, author_id)?;
+        if circuit.is_archived() {
+            return Err(CommentError::ValidationError(
+                "Circuit is archived".to_string(),
+            ));
+        }
+        let body = input.body.trim();
+        if body.is_empty() {
+            return Err(CommentError::ValidationError(
+                "Comment body is required".to_string(),
+            ));
+        }
+        if body.chars().count() > MAX_COMMENT_LENGTH {
+            return Err(CommentError::ValidationError(format!(
+                "Comments are limited to {MAX_COMMENT_LENGTH} characters"
+            )));
+        }
+        self.check_target(&circuit, &target)?;
+        if let Some(reply_to) = input.reply_to {
+            self.storage
+                .get_circuit_comment(&reply_to)?
+                .filter(|parent| parent.circuit_id == *circuit_id && parent.target == target)
+                .ok_or_else(|| {
+                    CommentError::NotFound(format!("Comment {reply_to} in this thread"))
+                })?;
+        }
+
+        // Mentions of non-members are left as plain text
+        let mentions: Vec<String> = parse_mentions(body)
+            .into_iter()
+            .filter(|member_id| member_id != author_id && circuit.is_member(member_id))
+            .collect();
+        let comment = CircuitComment {
+            comment_id: Uuid::new_v4(),
+            circuit_id: *circuit_id,
+            target,
+            author_id: author_id.to_string(),
+            body: body.to_string(),
+            mentions,
+            reply_to: input.reply_to,
+            created_at: now,
+        };
+        self.storage.store_circuit_comment(&comment)?;
+
+        for member_id in &comment.mentions {
+            self.notify_mention(&circuit, &comment, member_id)?;
+        }
+        Ok(comment)
+    }
+
+    fn notify_mention(
+        &self,
+        circuit: &Circuit,
+        comment: &CircuitComment,
+        member_id: &str,
+    ) -> Result<(), CommentError> {
+        let mut preview: String = comment.body.chars().take(MENTION_PREVIEW_CHARS).collect();
+        if preview.len() < comment.body.len() {
+            preview.push('…');
+        }
+        let notification = Notification::new(
+            member_id.to_string(),
+            NotificationType::CommentMention,
+            format!("Mentioned in {}", circuit.name),
+            format!("{} mentioned you: {}", comment.author_id, preview),
+            serde_json::json!({
+                "circuit_id": circuit.circuit_id,
+                "circuit_name": circuit.name,
+                "comment_id": comment.comment_id,
+                "target": comment.target,
+                "author_id": comment.author_id,
+            }),
+        );
+        self.storage.store_notification(&notification)?;
+        if let Some(live_stream) = &self.live_stream {
+            live_stream.publish(LiveRecord::Notification(notification));
+        }
+        Ok(())
+    }
+
+    /// The thread on `target`, oldest comment first
+    pub fn list(
+        &self,
+        circuit_id: &Uuid,
+        target: &CommentTarget,
+        user_id: &str,
+    ) -> Result<Vec<CircuitComment>, CommentError> {
+        self.member_circuit(circuit_id, user_id)?;
+        Ok(self.storage.list_circuit_comments(circuit_id, target)?)
+    }
+
+    /// Authors can delete their own comments, member managers any comment
+    pub fn delete(
+        &self,
+        circuit_id: &Uuid,
+        comment_id: &Uuid,
+        user_id: &str,
+    ) -> Result<CircuitComment, CommentError> {
+        let circuit = self.member_circuit(circuit_id, user_id)?;
+        let comment = self
+            .storage
+            .get_circuit_comment(comment_id)?
+            .filter(|comment| comment.circuit_id == *circuit_id)
+            .ok_or_else(|| CommentError::NotFound(format!("Comment {comment_id}")))?;
+        if comment.author_id != user_id
+            && !circuit.has_permission(user_id, &Permission::ManageMembers)
+        {
+            return Err(CommentError::PermissionDenied(
+                "Only the author or a member manager can delete this comment".to_string(),
+            ));
+        }
+        self.storage.delete_circuit_comment(comment_id)?;
+        Ok(comment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{CircuitOperation, MemberRole, OperationType};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_pending_operation_thread_notifies_mentioned_members() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let mut circuit = Circuit::new(
+            "Co-op".to_string(),
+            "Quality review".to_string(),
+            "owner".to_string(),
+        );
+        circuit.add_member("farmer".to_string(), MemberRole::Member);
+        storage.store_circuit(&circuit).unwrap();
+        let operation = CircuitOperation::new(
+            circuit.circuit_id,
+            "DFID-1".to_string(),
+            OperationType::Push,
+            "farmer".to_string(),
+        );
+        storage.store_circuit_operation(&operation).unwrap();
+
+        let engine = CommentEngine::new(Arc::clone(&storage));
+        let target = CommentTarget::PendingOperation {
+            operation_id: operation.operation_id,
+        };
+        let question = engine
+            .add(
+                &circuit.circuit_id,
+                target.clone(),
+                AddCommentInput {
+                    body: "@farmer the weight looks off, @stranger please ignore".to_string(),
+                    reply_to: None,
+                },
+                "owner",
+                Utc::now(),
+            )
+            .unwrap();
+        assert_eq!(question.mentions, ["farmer"]);
+        let notifications = storage
+            .get_user_notifications("farmer", None, None, false)
+            .unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert!(matches!(
+            notifications[0].notification_type,
+            NotificationType::CommentMention
+        ));
+
+        engine
+            .add(
+                &circuit.circuit_id,
+                target.clone(),
+                AddCommentInput {
+                    body: "Fixed, scale was miscalibrated.".to_string(),
+                    reply_to: Some(question.comment_id),
+                },
+                "farmer",
+                Utc::now(),
+            )
+            .unwrap();
+        let thread = engine.list(&circuit.circuit_id, &target, "farmer").unwrap();
+        assert_eq!(thread.len(), 2);
+        assert_eq!(thread[1].reply_to, Some(question.comment_id));
+
+        // Threads stay inside the circuit
+        assert!(matches!(
+            engine.list(&circuit.circuit_id, &target, "outsider"),
+            Err(CommentError::PermissionDenied(_))
+        ));
+        assert!(matches!(
+            engine.delete(&circuit.circuit_id, &question.comment_id, "farmer"),
+            Err(CommentError::PermissionDenied(_))
+        ));
+        engine
+            .delete(&circuit.circuit_id, &question.comment_id, "owner")
+            .unwrap();
+        assert_eq!(
+            engine
+                .list(&circuit.circuit_id, &target, "owner")
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,748 @@
+//! Export and import of a deployment's configuration as a signed, encrypted
+//! bundle, so a staging or on-prem environment can be set up like production.
+//!
+//! A bundle carries adapter configs, tier limits, circuit policies, event
+//! schemas and webhooks - never items, events or users. Secrets, including
+//! every custom adapter header and webhook header value, are swapped for
+//! `secret-ref:` references on export; on import each reference resolves to a
+//! value supplied with the request, or to the secret the target already holds
+//! for the same config. The contents are sealed with AES-256-GCM under a key the
+//! environments share (`CONFIG_BUNDLE_KEY`) and signed with the exporter's
+//! Ed25519 key (`CONFIG_BUNDLE_SIGNING_KEY`); importers only accept signers
+//! listed in `CONFIG_BUNDLE_TRUSTED_SIGNERS`, or their own key when it is unset.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    AdapterConfig, CircuitPolicyConfig, CircuitWebhookConfig, ConfigBundle, ConfigBundleContents,
+    TierConfig, TierLimits, UserTier,
+};
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+const SECRET_REF_PREFIX: &str = "secret-ref:";
+const TIERS: [UserTier; 4] = [
+    UserTier::Basic,
+    UserTier::Professional,
+    UserTier::Enterprise,
+    UserTier::Admin,
+];
+
+#[derive(Debug)]
+pub enum ConfigBundleError {
+    StorageError(StorageError),
+    ValidationError(String),
+    /// Keys are missing or malformed
+    KeyError(String),
+    /// The bundle was tampered with, signed by an untrusted key or sealed with
+    /// another key
+    InvalidBundle(String),
+}
+
+impl From<StorageError> for ConfigBundleError {
+    fn from(err: StorageError) -> Self {
+        ConfigBundleError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for ConfigBundleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigBundleError::StorageError(e) => write!(f, "Storage error: {e}"),
+            ConfigBundleError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            ConfigBundleError::KeyError(e) => write!(f, "Bundle key error: {e}"),
+            ConfigBundleError::InvalidBundle(e) => write!(f, "Invalid bundle: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigBundleError {}
+
+/// Keys used to seal, sign and verify bundles
+pub struct BundleKeys {
+    pub encryption_key: [u8; 32],
+    pub signing_key: Option<SigningKey>,
+    pub trusted_signers: Vec<VerifyingKey>,
+}
+
+impl BundleKeys {
+    /// Read `CONFIG_BUNDLE_KEY` (base64, 32 bytes), `CONFIG_BUNDLE_SIGNING_KEY`
+    /// (hex Ed25519 seed) and `CONFIG_BUNDLE_TRUSTED_SIGNERS` (comma-separated hex
+    /// public keys)
+    pub fn from_env() -> Result<Self, ConfigBundleError> {
+        let encryption_key = std::env::var("CONFIG_BUNDLE_KEY")
+            .map_err(|_| ConfigBundleError::KeyError("CONFIG_BUNDLE_KEY is not set".to_string()))
+            .and_then(|key| {
+                STANDARD
+                    .decode(key.trim())
+                    .ok()
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or_else(|| {
+                        ConfigBundleError::KeyError(
+                            "CONFIG_BUNDLE_KEY must be 32 base64-encoded bytes".to_string(),
+                        )
+                    })
+            })?;
+        let signing_key = match std::env::var("CONFIG_BUNDLE_SIGNING_KEY") {
+            Ok(seed) => Some(SigningKey::from_bytes(&decode_hex_32(
+                "CONFIG_BUNDLE_SIGNING_KEY",
+                &seed,
+            )?)),
+            Err(_) => None,
+        };
+        let trusted_signers = match std::env::var("CONFIG_BUNDLE_TRUSTED_SIGNERS") {
+            Ok(keys) => keys
+                .split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(|key| verifying_key("CONFIG_BUNDLE_TRUSTED_SIGNERS", key))
+                .collect::<Result<Vec<_>, _>>()?,
+            Err(_) => Vec::new(),
+        };
+        Ok(Self {
+            encryption_key,
+            signing_key,
+            trusted_signers,
+        })
+    }
+
+    fn is_trusted(&self, signer: &VerifyingKey) -> bool {
+        if self.trusted_signers.is_empty() {
+            self.signing_key
+                .as_ref()
+                .is_some_and(|own| own.verifying_key() == *signer)
+        } else {
+            self.trusted_signers.contains(signer)
+        }
+    }
+}
+
+fn decode_hex_32(name: &str, value: &str) -> Result<[u8; 32], ConfigBundleError> {
+    hex::decode(value.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| ConfigBundleError::KeyError(format!("{name} must be 32 hex-encoded bytes")))
+}
+
+fn verifying_key(name: &str, value: &str) -> Result<VerifyingKey, ConfigBundleError> {
+    VerifyingKey::from_bytes(&decode_hex_32(name, value)?)
+        .map_err(|e| ConfigBundleError::KeyError(format!("{name}: invalid Ed25519 key: {e}")))
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfigImportRequest {
+    pub bundle: ConfigBundle,
+    /// Values for the bundle's secret references, keyed by reference
+    #[serde(default)]
+    pub secrets: HashMap<String, String>,
+    /// Report what would change without writing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// What an import changed, or would change on a dry run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConfigImportSummary {
+    pub dry_run: bool,
+    pub source_environment: Option<String>,
+    pub exported_at: Option<DateTime<Utc>>,
+    pub adapter_configs_created: usize,
+    pub adapter_configs_updated: usize,
+    pub circuit_policies_applied: usize,
+    pub event_schemas_imported: usize,
+    pub webhooks_applied: usize,
+    /// Tiers are defined in code; these differ from the exporting deployment
+    pub tier_differences: Vec<UserTier>,
+    /// Secret references left empty because no value was available
+    pub unresolved_secrets: Vec<String>,
+    /// Entries that could not be applied here, with the reason
+    pub skipped: Vec<String>,
+}
+
+fn secret_ref(path: String) -> String {
+    format!("{SECRET_REF_PREFIX}{path}")
+}
+
+/// Header values often carry credentials (the Stellar adapters keep their
+/// secret key in `custom_headers`), so none are exported verbatim
+fn redact_headers(headers: &mut HashMap<String, String>, path: &str) {
+    for (name, value) in headers.iter_mut() {
+        *value = secret_ref(format!("{path}/{name}"));
+    }
+}
+
+/// Resolve each header's reference; unresolved headers are dropped rather than
+/// sent with a reference as their value
+fn resolve_headers(
+    resolve: &mut impl FnMut(&mut Option<String>, Option<String>),
+    headers: &mut HashMap<String, String>,
+    local: Option<&HashMap<String, String>>,
+) {
+    *headers = std::mem::take(headers)
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let mut value = Some(value);
+            resolve(
+                &mut value,
+                local.and_then(|local| local.get(&name).cloned()),
+            );
+            value.map(|value| (name, value))
+        })
+        .collect();
+}
+
+pub struct ConfigBundleEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> ConfigBundleEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Snapshot this deployment's configuration with secrets replaced by references
+    pub fn collect(
+        &self,
+        exported_by: &str,
+        source_environment: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<ConfigBundleContents, ConfigBundleError> {
+        let adapter_configs = self
+            .storage
+            .list_adapter_configs()?
+            .into_iter()
+            .map(|mut config| {
+                let details = &mut config.connection_details;
+                if details.api_key.is_some() {
+                    details.api_key =
+                        Some(secret_ref(format!("adapter/{}/api_key", config.config_id)));
+                }
+                if details.secret_key.is_some() {
+                    details.secret_key = Some(secret_ref(format!(
+                        "adapter/{}/secret_key",
+                        config.config_id
+                    )));
+                }
+                redact_headers(
+                    &mut details.custom_headers,
+                    &format!("adapter/{}/custom_headers", config.config_id),
+                );
+                config
+            })
+            .collect();
+
+        let tiers = TIERS
+            .iter()
+            .map(|tier| TierConfig {
+                tier: tier.clone(),
+                limits: TierLimits::for_tier(tier),
+                sla_target_percent: tier.sla_target_percent(),
+            })
+            .collect();
+
+        let mut circuit_policies = Vec::new();
+        let mut webhooks = Vec::new();
+        for circuit in self.storage.list_circuits()? {
+            circuit_policies.push(CircuitPolicyConfig {
+                circuit_id: circuit.circuit_id,
+                circuit_name: circuit.name.clone(),
+                permissions: circuit.permissions.clone(),
+                adapter_config: circuit.adapter_config.clone(),
+            });
+            if let Some(mut settings) = circuit.post_action_settings {
+                for webhook in &mut settings.webhooks {
+                    if webhook.auth_credentials.is_some() {
+                        webhook.auth_credentials = Some(secret_ref(format!(
+                            "webhook/{}/auth_credentials",
+                            webhook.id
+                        )));
+                    }
+                    redact_headers(
+                        &mut webhook.headers,
+                        &format!("webhook/{}/headers", webhook.id),
+                    );
+                }
+                webhooks.push(CircuitWebhookConfig {
+                    circuit_id: circuit.circuit_id,
+                    circuit_name: circuit.name,
+                    post_action_settings: settings,
+                });
+            }
+        }
+
+        Ok(ConfigBundleContents {
+            format_version: BUNDLE_FORMAT_VERSION,
+            exported_at: now,
+            exported_by: exported_by.to_string(),
+            source_environment,
+            adapter_configs,
+            tiers,
+            circuit_policies,
+            event_schemas: self.storage.list_event_schemas()?,
+            webhooks,
+        })
+    }
+
+    /// Collect, encrypt and sign this deployment's configuration
+    pub fn export(
+        &self,
+        keys: &BundleKeys,
+        exported_by: &str,
+        source_environment: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<ConfigBundle, ConfigBundleError> {
+        let signing_key = keys.signing_key.as_ref().ok_or_else(|| {
+            ConfigBundleError::KeyError("CONFIG_BUNDLE_SIGNING_KEY is not set".to_string())
+        })?;
+        let contents = self.collect(exported_by, source_environment, now)?;
+        let plaintext = serde_json::to_vec(&contents)
+            .map_err(|e| ConfigBundleError::ValidationError(e.to_string()))?;
+
+        let mut nonce = [0u8; 12];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = Aes256Gcm::new((&keys.encryption_key).into())
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|_| ConfigBundleError::KeyError("Encryption failed".to_string()))?;
+
+        let mut bundle = ConfigBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            created_at: now,
+            signer_public_key: hex::encode(signing_key.verifying_key().to_bytes()),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+            signature: String::new(),
+        };
+        bundle.signature = hex::encode(
+            signing_key
+                .sign(bundle.signing_input().as_bytes())
+                .to_bytes(),
+        );
+        Ok(bundle)
+    }
+
+    /// Check the signature against trusted signers and decrypt the contents
+    pub fn open(
+        &self,
+        keys: &BundleKeys,
+        bundle: &ConfigBundle,
+    ) -> Result<ConfigBundleContents, ConfigBundleError> {
+        if bundle.format_version != BUNDLE_FORMAT_VERSION {
+            return Err(ConfigBundleError::InvalidBundle(format!(
+                "Unsupported bundle format version {}",
+                bundle.format_version
+            )));
+        }
+        let signer = verifying_key("signer_public_key", &bundle.signer_public_key)
+            .map_err(|e| ConfigBundleError::InvalidBundle(e.to_string()))?;
+        if !keys.is_trusted(&signer) {
+            return Err(ConfigBundleError::InvalidBundle(
+                "Bundle is signed by an untrusted key".to_string(),
+            ));
+        }
+        let signature: [u8; 64] = hex::decode(&bundle.signature)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                ConfigBundleError::InvalidBundle("Malformed bundle signature".to_string())
+            })?;
+        signer
+            .verify(
+                bundle.signing_input().as_bytes(),
+                &Signature::from_bytes(&signature),
+            )
+            .map_err(|_| {
+                ConfigBundleError::InvalidBundle(
+                    "Signature does not match bundle contents".to_string(),
+                )
+            })?;
+
+        let decode = |field: &str, value: &str| {
+            STANDARD.decode(value).map_err(|_| {
+                ConfigBundleError::InvalidBundle(format!("{field} is not valid base64"))
+            })
+        };
+        let nonce = decode("nonce", &bundle.nonce)?;
+        if nonce.len() != 12 {
+            return Err(ConfigBundleError::InvalidBundle(
+                "nonce must be 12 bytes".to_string(),
+            ));
+        }
+        let plaintext = Aes256Gcm::new((&keys.encryption_key).into())
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                decode("ciphertext", &bundle.ciphertext)?.as_slice(),
+            )
+            .map_err(|_| {
+                ConfigBundleError::InvalidBundle(
+                    "Bundle was encrypted with a different CONFIG_BUNDLE_KEY".to_string(),
+                )
+            })?;
+        serde_json::from_slice(&plaintext)
+            .map_err(|e| ConfigBundleError::InvalidBundle(format!("Unreadable contents: {e}")))
+    }
+
+    /// Apply a bundle's configuration to this deployment
+    pub fn import(
+        &self,
+        keys: &BundleKeys,
+        request: ConfigImportRequest,
+    ) -> Result<ConfigImportSummary, ConfigBundleError> {
+        let contents = self.open(keys, &request.bundle)?;
+        let mut summary = ConfigImportSummary {
+            dry_run: request.dry_run,
+            source_environment: contents.source_environment.clone(),
+            exported_at: Some(contents.exported_at),
+            ..Default::default()
+        };
+        let mut resolve = |value: &mut Option<String>, local: Option<String>| {
+            let Some(reference) = value
+                .as_deref()
+                .and_then(|v| v.starts_with(SECRET_REF_PREFIX).then(|| v.to_string()))
+            else {
+                return;
+            };
+            *value = request.secrets.get(&reference).cloned().or(local);
+            if value.is_none() {
+                summary.unresolved_secrets.push(reference);
+            }
+        };
+
+        let mut adapter_configs: Vec<(AdapterConfig, bool)> = Vec::new();
+        for mut config in contents.adapter_configs {
+            let existing = self.storage.get_adapter_config(&config.config_id)?;
+            let local = existing.as_ref().map(|c| c.connection_details.clone());
+            let details = &mut config.connection_details;
+            resolve(
+                &mut details.api_key,
+                local.as_ref().and_then(|d| d.api_key.clone()),
+            );
+            resolve(
+                &mut details.secret_key,
+                local.as_ref().and_then(|d| d.secret_key.clone()),
+            );
+            resolve_headers(
+                &mut resolve,
+                &mut details.custom_headers,
+                local.as_ref().map(|d| &d.custom_headers),
+            );
+            adapter_configs.push((config, existing.is_some()));
+        }
+
+        let mut circuits = HashMap::new();
+        for policy in contents.circuit_policies {
+            let Some(circuit) = self.storage.get_circuit(&policy.circuit_id)? else {
+                summary.skipped.push(format!(
+                    "Policy for circuit '{}' ({}): circuit does not exist here",
+                    policy.circuit_name, policy.circuit_id
+                ));
+                continue;
+            };
+            let circuit = circuits.entry(policy.circuit_id).or_insert(circuit);
+            circuit.permissions = policy.permissions;
+            circuit.adapter_config = policy.adapter_config;
+            summary.circuit_policies_applied += 1;
+        }
+        for config in contents.webhooks {
+            let circuit = match circuits.get_mut(&config.circuit_id) {
+                Some(circuit) => circuit,
+                None => match self.storage.get_circuit(&config.circuit_id)? {
+                    Some(circuit) => circuits.entry(config.circuit_id).or_insert(circuit),
+                    None => {
+                        summary.skipped.push(format!(
+                            "Webhooks for circuit '{}' ({}): circuit does not exist here",
+                            config.circuit_name, config.circuit_id
+                        ));
+                        continue;
+                    }
+                },
+            };
+            let local_webhooks = circuit
+                .post_action_settings
+                .as_ref()
+                .map(|s| s.webhooks.clone())
+                .unwrap_or_default();
+            let mut settings = config.post_action_settings;
+            for webhook in &mut settings.webhooks {
+                let local = local_webhooks.iter().find(|w| w.id == webhook.id);
+                resolve(
+                    &mut webhook.auth_credentials,
+                    local.and_then(|w| w.auth_credentials.clone()),
+                );
+                resolve_headers(
+                    &mut resolve,
+                    &mut webhook.headers,
+                    local.map(|w| &w.headers),
+                );
+            }
+            circuit.post_action_settings = Some(settings);
+            summary.webhooks_applied += 1;
+        }
+
+        let existing_schemas: Vec<_> = self
+            .storage
+            .list_event_schemas()?
+            .into_iter()
+            .map(|s| s.schema_id)
+            .collect();
+        let mut event_schemas = Vec::new();
+        for schema in contents.event_schemas {
+            if existing_schemas.contains(&schema.schema_id) {
+                continue;
+            }
+            if self.storage.get_circuit(&schema.circuit_id)?.is_none() {
+                summary.skipped.push(format!(
+                    "Event schema {} v{}: circuit {} does not exist here",
+                    schema.schema_id, schema.version, schema.circuit_id
+                ));
+                continue;
+            }
+            event_schemas.push(schema);
+        }
+        summary.event_schemas_imported = event_schemas.len();
+
+        for tier in contents.tiers {
+            let local = serde_json::to_value(TierLimits::for_tier(&tier.tier)).ok();
+            if local != serde_json::to_value(&tier.limits).ok()
+                || tier.tier.sla_target_percent() != tier.sla_target_percent
+            {
+                summary.tier_differences.push(tier.tier);
+            }
+        }
+
+        for (config, exists) in &adapter_configs {
+            if *exists {
+                summary.adapter_configs_updated += 1;
+            } else {
+                summary.adapter_configs_created += 1;
+            }
+            if !request.dry_run {
+                if *exists {
+                    self.storage.update_adapter_config(config)?;
+                } else {
+                    self.storage.store_adapter_config(config)?;
+                }
+            }
+        }
+        if !request.dry_run {
+            for circuit in circuits.values_mut() {
+                circuit.last_modified = Utc::now();
+                self.storage.update_circuit(circuit)?;
+            }
+            for schema in &event_schemas {
+                self.storage.store_event_schema(schema)?;
+            }
+        }
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{
+        AdapterConnectionDetails, AdapterType, Circuit, HttpMethod, PostActionSettings,
+        RetryConfig, WebhookAuthType, WebhookConfig, WebhookFilter,
+    };
+    use std::sync::{Arc, Mutex};
+
+    fn keys(seed: u8) -> BundleKeys {
+        BundleKeys {
+            encryption_key: [42u8; 32],
+            signing_key: Some(SigningKey::from_bytes(&[seed; 32])),
+            trusted_signers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let source = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let adapter = AdapterConfig::new(
+            "Pinata".to_string(),
+            "IPFS pinning".to_string(),
+            AdapterType::IpfsIpfs,
+            AdapterConnectionDetails {
+                api_key: Some("pinata-key".to_string()),
+                secret_key: Some("pinata-secret".to_string()),
+                ..Default::default()
+            },
+            "admin".to_string(),
+        );
+        source.store_adapter_config(&adapter).unwrap();
+        let mut circuit = Circuit::new("Coop".to_string(), String::new(), "owner".to_string());
+        circuit.permissions.require_approval_for_push = true;
+        circuit.post_action_settings = Some(PostActionSettings {
+            webhooks: vec![WebhookConfig {
+                id: uuid::Uuid::new_v4(),
+                name: "ERP".to_string(),
+                url: "https://erp.example.com/hook".to_string(),
+                method: HttpMethod::Post,
+                headers: HashMap::new(),
+                auth_type: WebhookAuthType::BearerToken,
+                auth_credentials: Some("erp-token".to_string()),
+                enabled: true,
+                retry_config: RetryConfig::default(),
+                encryption_key: None,
+                filter: WebhookFilter::default(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }],
+            ..Default::default()
+        });
+        source.store_circuit(&circuit).unwrap();
+
+        let engine = ConfigBundleEngine::new(Arc::clone(&source));
+        let bundle = engine
+            .export(
+                &keys(1),
+                "admin",
+                Some("production".to_string()),
+                Utc::now(),
+            )
+            .unwrap();
+        let contents = engine.open(&keys(1), &bundle).unwrap();
+        let exported_key = contents.adapter_configs[0]
+            .connection_details
+            .api_key
+            .clone()
+            .unwrap();
+        assert_eq!(
+            exported_key,
+            format!("secret-ref:adapter/{}/api_key", adapter.config_id)
+        );
+
+        // Another deployment that trusts the exporter and has the same circuit
+        let target = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let mut bare = circuit.clone();
+        bare.permissions.require_approval_for_push = false;
+        bare.post_action_settings = None;
+        target.store_circuit(&bare).unwrap();
+        let target_keys = BundleKeys {
+            trusted_signers: vec![SigningKey::from_bytes(&[1; 32]).verifying_key()],
+            ..keys(2)
+        };
+        let importer = ConfigBundleEngine::new(Arc::clone(&target));
+        let request = |dry_run| ConfigImportRequest {
+            bundle: bundle.clone(),
+            secrets: HashMap::from([(exported_key.clone(), "staging-key".to_string())]),
+            dry_run,
+        };
+
+        let preview = importer.import(&target_keys, request(true)).unwrap();
+        assert_eq!(preview.adapter_configs_created, 1);
+        assert!(target.list_adapter_configs().unwrap().is_empty());
+
+        let summary = importer.import(&target_keys, request(false)).unwrap();
+        assert_eq!(summary.circuit_policies_applied, 1);
+        assert_eq!(summary.webhooks_applied, 1);
+        assert!(summary.tier_differences.is_empty());
+        assert_eq!(summary.unresolved_secrets.len(), 2);
+        let imported = target
+            .get_adapter_config(&adapter.config_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            imported.connection_details.api_key.as_deref(),
+            Some("staging-key")
+        );
+        assert_eq!(imported.connection_details.secret_key, None);
+        let updated = target.get_circuit(&circuit.circuit_id).unwrap().unwrap();
+        assert!(updated.permissions.require_approval_for_push);
+        assert_eq!(updated.post_action_settings.unwrap().webhooks.len(), 1);
+
+        // Untrusted signer and tampering are both rejected
+        assert!(matches!(
+            importer.import(&keys(3), request(true)),
+            Err(ConfigBundleError::InvalidBundle(_))
+        ));
+        let mut tampered = bundle.clone();
+        tampered.nonce = STANDARD.encode([0u8; 12]);
+        assert!(matches!(
+            importer.open(&target_keys, &tampered),
+            Err(ConfigBundleError::InvalidBundle(_))
+        ));
+    }
+
+    #[test]
+    fn test_export_redacts_header_secrets() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let stellar_secret = "SBSECRETSEEDTHATMUSTNEVERLEAVE";
+        let adapter = AdapterConfig::new(
+            "Stellar".to_string(),
+            "Stellar mainnet".to_string(),
+            AdapterType::StellarMainnetIpfs,
+            AdapterConnectionDetails {
+                custom_headers: HashMap::from([(
+                    "stellar_secret".to_string(),
+                    stellar_secret.to_string(),
+                )]),
+                ..Default::default()
+            },
+            "admin".to_string(),
+        );
+        storage.store_adapter_config(&adapter).unwrap();
+        let mut circuit = Circuit::new("Coop".to_string(), String::new(), "owner".to_string());
+        circuit.post_action_settings = Some(PostActionSettings {
+            webhooks: vec![WebhookConfig {
+                id: uuid::Uuid::new_v4(),
+                name: "ERP".to_string(),
+                url: "https://erp.example.com/hook".to_string(),
+                method: HttpMethod::Post,
+                headers: HashMap::from([("X-Api-Key".to_string(), "erp-header-key".to_string())]),
+                auth_type: WebhookAuthType::None,
+                auth_credentials: None,
+                enabled: true,
+                retry_config: RetryConfig::default(),
+                encryption_key: None,
+                filter: WebhookFilter::default(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }],
+            ..Default::default()
+        });
+        storage.store_circuit(&circuit).unwrap();
+
+        let engine = ConfigBundleEngine::new(Arc::clone(&storage));
+        let contents = engine.collect("admin", None, Utc::now()).unwrap();
+        let serialized = serde_json::to_string(&contents).unwrap();
+        assert!(!serialized.contains(stellar_secret));
+        assert!(!serialized.contains("erp-header-key"));
+        assert_eq!(
+            contents.adapter_configs[0]
+                .connection_details
+                .custom_headers["stellar_secret"],
+            format!(
+                "secret-ref:adapter/{}/custom_headers/stellar_secret",
+                adapter.config_id
+            )
+        );
+
+        // Importing into the same deployment keeps the secret it already holds
+        let bundle = engine.export(&keys(1), "admin", None, Utc::now()).unwrap();
+        engine
+            .import(
+                &keys(1),
+                ConfigImportRequest {
+                    bundle,
+                    secrets: HashMap::new(),
+                    dry_run: false,
+                },
+            )
+            .unwrap();
+        let kept = storage
+            .get_adapter_config(&adapter.config_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            kept.connection_details.custom_headers["stellar_secret"],
+            stellar_secret
+        );
+    }
+}
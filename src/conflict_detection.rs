@@ -1,4 +1,4 @@
-use crate::storage::StorageBackend;
+use crate::storage::{StorageBackend, StorageError};
 use crate::types::{
     ConflictAnalysisResult, ConflictInfo, ConflictSeverity, ConflictType, Identifier,
     PendingReason, QualitySeverity, ResolutionStrategy, SuggestedAction,
@@ -8,10 +8,10 @@ use std::sync::Arc;
 
 pub struct ConflictDetectionEngine<S: StorageBackend> {
     storage: Arc<std::sync::Mutex<S>>,
-    #[allow(dead_code)]
     confidence_threshold: f64,
     similarity_threshold: f64,
     dfid_conflict_threshold: usize,
+    auto_merge: bool,
 }
 
 impl<S: StorageBackend> ConflictDetectionEngine<S> {
@@ -21,6 +21,7 @@ impl<S: StorageBackend> ConflictDetectionEngine<S> {
             confidence_threshold: 0.8,
             similarity_threshold: 0.85,
             dfid_conflict_threshold: 2,
+            auto_merge: true,
         }
     }
 
@@ -35,9 +36,26 @@ impl<S: StorageBackend> ConflictDetectionEngine<S> {
             confidence_threshold,
             similarity_threshold,
             dfid_conflict_threshold,
+            auto_merge: true,
         }
     }
 
+    /// Auto-resolve only what the workspace's auto-link policy would link;
+    /// workspaces without one keep the engine's thresholds
+    pub fn with_workspace_policy(mut self, workspace_id: &str) -> Result<Self, StorageError> {
+        let policy = self
+            .storage
+            .lock()
+            .unwrap()
+            .get_workspace_dedup_config(workspace_id)?
+            .and_then(|config| config.auto_link);
+        if let Some(policy) = policy {
+            self.confidence_threshold = policy.min_confidence;
+            self.auto_merge = policy.auto_merge_conflicts;
+        }
+        Ok(self)
+    }
+
     pub fn analyze_identifiers(&self, identifiers: &[Identifier]) -> ConflictAnalysisResult {
         if identifiers.is_empty() {
             return ConflictAnalysisResult {
@@ -363,12 +381,16 @@ impl<S: StorageBackend> ConflictDetectionEngine<S> {
             matches!(
                 conflict.severity,
                 ConflictSeverity::None | ConflictSeverity::Low
-            ) && conflict.suggested_resolution.as_ref().is_some_and(|res| {
-                matches!(
-                    res,
-                    ResolutionStrategy::AutoMerge | ResolutionStrategy::SkipProcessing
-                )
-            })
+            ) && conflict
+                .suggested_resolution
+                .as_ref()
+                .is_some_and(|res| match res {
+                    ResolutionStrategy::AutoMerge => {
+                        self.auto_merge && conflict.confidence >= self.confidence_threshold
+                    }
+                    ResolutionStrategy::SkipProcessing => true,
+                    _ => false,
+                })
         })
     }
 
@@ -0,0 +1,70 @@
+use crate::storage::StorageError;
+use async_trait::async_trait;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+#[derive(Debug)]
+pub enum ConnectorError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+    NotFound(String),
+    Conflict(String),
+    /// The legacy system could not be read
+    SourceError(String),
+}
+
+impl From<StorageError> for ConnectorError {
+    fn from(err: StorageError) -> Self {
+        ConnectorError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for ConnectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConnectorError::StorageError(e) => write!(f, "Storage error: {e}"),
+            ConnectorError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            ConnectorError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            ConnectorError::NotFound(e) => write!(f, "Not found: {e}"),
+            ConnectorError::Conflict(e) => write!(f, "Conflict: {e}"),
+            ConnectorError::SourceError(e) => write!(f, "Source error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ConnectorError {}
+
+/// One exported row/object as read from the legacy system
+#[derive(Debug, Clone)]
+pub struct SourceRecord {
+    /// Stable reference shown on the error dashboard, e.g. `export.csv#12`
+    pub record_ref: String,
+    pub fields: Map<String, Value>,
+}
+
+/// Everything a single poll picked up
+#[derive(Debug, Default)]
+pub struct PollBatch {
+    pub records: Vec<SourceRecord>,
+    /// Records that could not even be parsed: (record_ref, reason)
+    pub rejected: Vec<(String, String)>,
+    /// Resume point to persist once the batch has been ingested
+    pub watermark: Option<String>,
+}
+
+#[async_trait]
+pub trait LegacyConnector: Send + Sync {
+    /// Read everything new since `watermark`
+    async fn poll(&self, watermark: Option<&str>) -> Result<PollBatch, ConnectorError>;
+
+    /// Called after ingestion so the source can be marked consumed; `failed_refs`
+    /// holds the refs of records that were rejected or could not be ingested
+    async fn acknowledge(
+        &self,
+        _batch: &PollBatch,
+        _failed_refs: &HashSet<String>,
+    ) -> Result<(), ConnectorError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,781 @@
+use super::base::{ConnectorError, LegacyConnector, PollBatch};
+use super::mapping::{apply_template, preview_record, sample_records, validate_template};
+use super::rest_poller::RestPollerConnector;
+use super::sftp_csv::{csv_records, SftpCsvConnector};
+use crate::receipt_engine::ReceiptEngine;
+use crate::storage::StorageBackend;
+use crate::types::{
+    ConnectorConfig, ConnectorDashboard, ConnectorRunError, ConnectorRunStatus,
+    ConnectorRunSummary, ConnectorSource, ConnectorState, EnrichedFieldMapping,
+    IdentifierFieldMapping, IngestionPriority, MappingTemplate, ReceiptPreview,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+pub const MIN_POLL_INTERVAL_SECS: u64 = 60;
+pub const DEFAULT_POLL_INTERVAL_SECS: u64 = 3600;
+/// Upper bound for the retry delay after consecutive failed runs
+pub const MAX_BACKOFF_SECS: u64 = 6 * 3600;
+/// A run still marked `Running` after this long is assumed to have crashed
+const STALE_RUN_SECS: i64 = 3600;
+const DASHBOARD_ERROR_LIMIT: usize = 50;
+const OVERVIEW_ERROR_LIMIT: usize = 5;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MappingTemplateInput {
+    pub name: String,
+    pub identifier_fields: Vec<IdentifierFieldMapping>,
+    #[serde(default)]
+    pub enriched_fields: Vec<EnrichedFieldMapping>,
+    #[serde(default)]
+    pub field_renames: HashMap<String, String>,
+    #[serde(default)]
+    pub excluded_fields: Vec<String>,
+    pub occurred_at_field: Option<String>,
+    pub priority: Option<IngestionPriority>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConnectorInput {
+    pub name: String,
+    pub source: ConnectorSource,
+    pub mapping_template_id: Uuid,
+    pub poll_interval_secs: Option<u64>,
+    pub enabled: Option<bool>,
+}
+
+/// Sample data for a template preview: JSON record(s) or raw CSV text with a header row
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PreviewSample {
+    pub sample: Option<serde_json::Value>,
+    pub csv: Option<String>,
+    pub delimiter: Option<char>,
+}
+
+impl PreviewSample {
+    fn records(self) -> Result<PollBatch, ConnectorError> {
+        match (self.sample, self.csv) {
+            (Some(sample), None) => sample_records(sample)
+                .map(|records| PollBatch {
+                    records,
+                    ..PollBatch::default()
+                })
+                .map_err(ConnectorError::ValidationError),
+            (None, Some(csv)) => csv_records(&csv, self.delimiter.unwrap_or(','), "sample")
+                .map_err(ConnectorError::ValidationError),
+            _ => Err(ConnectorError::ValidationError(
+                "Provide either 'sample' or 'csv'".to_string(),
+            )),
+        }
+    }
+}
+
+/// Maximum sample records previewed per request
+pub const MAX_PREVIEW_RECORDS: usize = 100;
+
+pub struct ConnectorEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> ConnectorEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    // ------------------------------------------------------------------
+    // Mapping templates
+    // ------------------------------------------------------------------
+
+    pub fn create_template(
+        &self,
+        owner_id: &str,
+        input: MappingTemplateInput,
+    ) -> Result<MappingTemplate, ConnectorError> {
+        let template = draft_template(owner_id, input);
+        validate_template(&template).map_err(ConnectorError::ValidationError)?;
+        self.storage.store_mapping_template(&template)?;
+        Ok(template)
+    }
+
+    pub fn update_template(
+        &self,
+        owner_id: &str,
+        template_id: &Uuid,
+        input: MappingTemplateInput,
+    ) -> Result<MappingTemplate, ConnectorError> {
+        let mut template = self.get_template(owner_id, template_id)?;
+        template.name = input.name.trim().to_string();
+        template.identifier_fields = input.identifier_fields;
+        template.enriched_fields = input.enriched_fields;
+        template.field_renames = input.field_renames;
+        template.excluded_fields = input.excluded_fields;
+        template.occurred_at_field = input.occurred_at_field;
+        if let Some(priority) = input.priority {
+            template.priority = priority;
+        }
+        template.updated_at = Utc::now();
+        validate_template(&template).map_err(ConnectorError::ValidationError)?;
+        self.storage.store_mapping_template(&template)?;
+        Ok(template)
+    }
+
+    pub fn get_template(
+        &self,
+        owner_id: &str,
+        template_id: &Uuid,
+    ) -> Result<MappingTemplate, ConnectorError> {
+        let template = self
+            .storage
+            .get_mapping_template(template_id)?
+            .ok_or_else(|| ConnectorError::NotFound(format!("Mapping template {template_id}")))?;
+        if template.owner_id != owner_id {
+            return Err(ConnectorError::PermissionDenied(
+                "Mapping template belongs to another user".to_string(),
+            ));
+        }
+        Ok(template)
+    }
+
+    pub fn list_templates(&self, owner_id: &str) -> Result<Vec<MappingTemplate>, ConnectorError> {
+        let mut templates = self.storage.list_mapping_templates(owner_id)?;
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(templates)
+    }
+
+    /// Templates still referenced by a connector cannot be deleted
+    pub fn delete_template(
+        &self,
+        owner_id: &str,
+        template_id: &Uuid,
+    ) -> Result<(), ConnectorError> {
+        self.get_template(owner_id, template_id)?;
+        let users: Vec<String> = self
+            .storage
+            .list_connector_configs()?
+            .into_iter()
+            .filter(|c| c.mapping_template_id == *template_id)
+            .map(|c| c.name)
+            .collect();
+        if !users.is_empty() {
+            return Err(ConnectorError::Conflict(format!(
+                "Mapping template is used by connector(s): {}",
+                users.join(", ")
+            )));
+        }
+        self.storage.delete_mapping_template(template_id)?;
+        Ok(())
+    }
+
+    /// Run a saved template against sample data without creating receipts
+    pub fn preview_template(
+        &self,
+        owner_id: &str,
+        template_id: &Uuid,
+        sample: PreviewSample,
+    ) -> Result<Vec<ReceiptPreview>, ConnectorError> {
+        let template = self.get_template(owner_id, template_id)?;
+        preview_sample(&template, sample)
+    }
+
+    /// Preview an unsaved template; validation problems are returned as errors
+    pub fn preview_draft_template(
+        &self,
+        owner_id: &str,
+        input: MappingTemplateInput,
+        sample: PreviewSample,
+    ) -> Result<Vec<ReceiptPreview>, ConnectorError> {
+        let template = draft_template(owner_id, input);
+        validate_template(&template).map_err(ConnectorError::ValidationError)?;
+        preview_sample(&template, sample)
+    }
+
+    // ------------------------------------------------------------------
+    // Connectors
+    // ------------------------------------------------------------------
+
+    pub fn create_connector(
+        &self,
+        owner_id: &str,
+        input: ConnectorInput,
+    ) -> Result<ConnectorConfig, ConnectorError> {
+        self.get_template(owner_id, &input.mapping_template_id)?;
+        let now = Utc::now();
+        let config = ConnectorConfig {
+            connector_id: Uuid::new_v4(),
+            owner_id: owner_id.to_string(),
+            name: input.name.trim().to_string(),
+            source: input.source,
+            mapping_template_id: input.mapping_template_id,
+            poll_interval_secs: input
+                .poll_interval_secs
+                .unwrap_or(DEFAULT_POLL_INTERVAL_SECS),
+            enabled: input.enabled.unwrap_or(true),
+            created_at: now,
+            updated_at: now,
+        };
+        validate_connector(&config)?;
+
+        self.storage.store_connector_config(&config)?;
+        self.storage.store_connector_state(&ConnectorState {
+            connector_id: config.connector_id,
+            next_run_at: config.enabled.then_some(now),
+            ..ConnectorState::default()
+        })?;
+        Ok(config)
+    }
+
+    pub fn update_connector(
+        &self,
+        owner_id: &str,
+        connector_id: &Uuid,
+        input: ConnectorInput,
+    ) -> Result<ConnectorConfig, ConnectorError> {
+        let mut config = self.get_connector(owner_id, connector_id)?;
+        self.get_template(owner_id, &input.mapping_template_id)?;
+
+        let source_changed = config.source != input.source;
+        config.name = input.name.trim().to_string();
+        config.source = input.source;
+        config.mapping_template_id = input.mapping_template_id;
+        if let Some(interval) = input.poll_interval_secs {
+            config.poll_interval_secs = interval;
+        }
+        if let Some(enabled) = input.enabled {
+            config.enabled = enabled;
+        }
+        config.updated_at = Utc::now();
+        validate_connector(&config)?;
+        self.storage.store_connector_config(&config)?;
+
+        let mut state = self.load_state(connector_id)?;
+        if source_changed {
+            // A watermark from another endpoint is meaningless
+            state.watermark = None;
+        }
+        state.next_run_at = config
+            .enabled
+            .then(|| state.next_run_at.unwrap_or(Utc::now()));
+        self.storage.store_connector_state(&state)?;
+        Ok(config)
+    }
+
+    pub fn delete_connector(
+        &self,
+        owner_id: &str,
+        connector_id: &Uuid,
+    ) -> Result<(), ConnectorError> {
+        self.get_connector(owner_id, connector_id)?;
+        self.storage.delete_connector_config(connector_id)?;
+        Ok(())
+    }
+
+    pub fn get_connector(
+        &self,
+        owner_id: &str,
+        connector_id: &Uuid,
+    ) -> Result<ConnectorConfig, ConnectorError> {
+        let config = self
+            .storage
+            .get_connector_config(connector_id)?
+            .ok_or_else(|| ConnectorError::NotFound(format!("Connector {connector_id}")))?;
+        if config.owner_id != owner_id {
+            return Err(ConnectorError::PermissionDenied(
+                "Connector belongs to another user".to_string(),
+            ));
+        }
+        Ok(config)
+    }
+
+    pub fn list_connectors(&self, owner_id: &str) -> Result<Vec<ConnectorConfig>, ConnectorError> {
+        let mut connectors: Vec<ConnectorConfig> = self
+            .storage
+            .list_connector_configs()?
+            .into_iter()
+            .filter(|c| c.owner_id == owner_id)
+            .collect();
+        connectors.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(connectors)
+    }
+
+    // ------------------------------------------------------------------
+    // Dashboards
+    // ------------------------------------------------------------------
+
+    pub fn dashboard(
+        &self,
+        owner_id: &str,
+        connector_id: &Uuid,
+    ) -> Result<ConnectorDashboard, ConnectorError> {
+        let connector = self.get_connector(owner_id, connector_id)?;
+        self.build_dashboard(connector, DASHBOARD_ERROR_LIMIT)
+    }
+
+    /// State and latest errors of every connector the user owns
+    pub fn dashboards(&self, owner_id: &str) -> Result<Vec<ConnectorDashboard>, ConnectorError> {
+        self.list_connectors(owner_id)?
+            .into_iter()
+            .map(|connector| self.build_dashboard(connector, OVERVIEW_ERROR_LIMIT))
+            .collect()
+    }
+
+    fn build_dashboard(
+        &self,
+        connector: ConnectorConfig,
+        error_limit: usize,
+    ) -> Result<ConnectorDashboard, ConnectorError> {
+        let state = self.load_state(&connector.connector_id)?;
+        let recent_errors = self
+            .storage
+            .list_connector_errors(&connector.connector_id, error_limit)?;
+        Ok(ConnectorDashboard {
+            connector,
+            state,
+            recent_errors,
+        })
+    }
+
+    fn load_state(&self, connector_id: &Uuid) -> Result<ConnectorState, ConnectorError> {
+        Ok(self
+            .storage
+            .get_connector_state(connector_id)?
+            .unwrap_or_else(|| ConnectorState {
+                connector_id: *connector_id,
+                ..ConnectorState::default()
+            }))
+    }
+
+    // ------------------------------------------------------------------
+    // Scheduling and runs
+    // ------------------------------------------------------------------
+
+    /// Enabled connectors whose next run is due at `now`
+    pub fn due_connectors(&self, now: DateTime<Utc>) -> Result<Vec<Uuid>, ConnectorError> {
+        let mut due = Vec::new();
+        for config in self.storage.list_connector_configs()? {
+            if !config.enabled {
+                continue;
+            }
+            let state = self.load_state(&config.connector_id)?;
+            if is_running(&state, now) {
+                continue;
+            }
+            if state.next_run_at.is_none_or(|next| next <= now) {
+                due.push(config.connector_id);
+            }
+        }
+        Ok(due)
+    }
+
+    fn record_error(
+        &self,
+        connector_id: Uuid,
+        record_ref: Option<String>,
+        message: String,
+    ) -> Result<(), ConnectorError> {
+        self.storage.store_connector_error(&ConnectorRunError {
+            error_id: Uuid::new_v4(),
+            connector_id,
+            at: Utc::now(),
+            record_ref,
+            message,
+        })?;
+        Ok(())
+    }
+}
+
+impl<S: StorageBackend + Clone> ConnectorEngine<S> {
+    /// Poll the connector's source once and turn every mapped record into a receipt.
+    /// Source and record failures are recorded on the dashboard and reflected in the
+    /// returned summary; only configuration and storage problems are errors.
+    pub async fn run_connector(
+        &self,
+        connector_id: &Uuid,
+    ) -> Result<ConnectorRunSummary, ConnectorError> {
+        let config = self
+            .storage
+            .get_connector_config(connector_id)?
+            .ok_or_else(|| ConnectorError::NotFound(format!("Connector {connector_id}")))?;
+        let template = self
+            .storage
+            .get_mapping_template(&config.mapping_template_id)?
+            .ok_or_else(|| {
+                ConnectorError::NotFound(format!("Mapping template {}", config.mapping_template_id))
+            })?;
+
+        let started_at = Utc::now();
+        let mut state = self.load_state(connector_id)?;
+        if is_running(&state, started_at) {
+            return Err(ConnectorError::Conflict(
+                "Connector run already in progress".to_string(),
+            ));
+        }
+        state.status = ConnectorRunStatus::Running;
+        state.last_run_at = Some(started_at);
+        self.storage.store_connector_state(&state)?;
+
+        let source = connector_for(&config.source);
+        let mut summary = ConnectorRunSummary {
+            connector_id: *connector_id,
+            started_at,
+            finished_at: started_at,
+            records_seen: 0,
+            receipts_created: 0,
+            records_failed: 0,
+            status: ConnectorRunStatus::Failed,
+        };
+
+        let run_error = match source.poll(state.watermark.as_deref()).await {
+            Ok(batch) => {
+                let failed_refs = self.ingest_batch(&config, &template, &batch, &mut summary)?;
+                let ack = source.acknowledge(&batch, &failed_refs).await;
+                if batch.watermark.is_some() {
+                    state.watermark = batch.watermark;
+                }
+                ack.err().map(|e| e.to_string())
+            }
+            Err(e) => Some(e.to_string()),
+        };
+
+        let finished_at = Utc::now();
+        summary.finished_at = finished_at;
+        summary.status = if run_error.is_none() && summary.records_failed == 0 {
+            ConnectorRunStatus::Succeeded
+        } else if summary.receipts_created == 0 {
+            ConnectorRunStatus::Failed
+        } else {
+            ConnectorRunStatus::PartiallyFailed
+        };
+
+        if let Some(message) = &run_error {
+            self.record_error(*connector_id, None, message.clone())?;
+        }
+        state.status = summary.status;
+        state.records_ingested += summary.receipts_created as u64;
+        state.records_failed += summary.records_failed as u64;
+        state.last_error = run_error.or_else(|| {
+            (summary.records_failed > 0)
+                .then(|| format!("{} record(s) failed", summary.records_failed))
+        });
+        if summary.status == ConnectorRunStatus::Failed {
+            state.consecutive_failures += 1;
+        } else {
+            state.consecutive_failures = 0;
+            state.last_success_at = Some(finished_at);
+        }
+        let delay = retry_delay_secs(config.poll_interval_secs, state.consecutive_failures);
+        state.next_run_at = Some(finished_at + Duration::seconds(delay as i64));
+        self.storage.store_connector_state(&state)?;
+
+        Ok(summary)
+    }
+
+    fn ingest_batch(
+        &self,
+        config: &ConnectorConfig,
+        template: &MappingTemplate,
+        batch: &PollBatch,
+        summary: &mut ConnectorRunSummary,
+    ) -> Result<HashSet<String>, ConnectorError> {
+        let mut receipts = ReceiptEngine::new(self.storage.clone());
+        let mut failed_refs = HashSet::new();
+        summary.records_seen = batch.records.len() + batch.rejected.len();
+
+        for (record_ref, reason) in &batch.rejected {
+            self.record_error(
+                config.connector_id,
+                Some(record_ref.clone()),
+                reason.clone(),
+            )?;
+            failed_refs.insert(record_ref.clone());
+        }
+
+        for record in &batch.records {
+            let result = apply_template(template, record).and_then(|mapped| {
+                receipts
+                    .process_data_with_occurred_at(
+                        &mapped.payload,
+                        mapped.identifiers,
+                        template.priority,
+                        mapped.occurred_at,
+                    )
+                    .map_err(|e| e.to_string())
+            });
+            match result {
+                Ok(_) => summary.receipts_created += 1,
+                Err(message) => {
+                    self.record_error(
+                        config.connector_id,
+                        Some(record.record_ref.clone()),
+                        message,
+                    )?;
+                    failed_refs.insert(record.record_ref.clone());
+                }
+            }
+        }
+
+        summary.records_failed = failed_refs.len();
+        Ok(failed_refs)
+    }
+}
+
+impl<S: StorageBackend + Clone + 'static> ConnectorEngine<S> {
+    /// Run due connectors every `tick` until the runtime shuts down
+    pub fn spawn_scheduler(storage: S, tick: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let engine = ConnectorEngine::new(storage);
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                let due = match engine.due_connectors(Utc::now()) {
+                    Ok(due) => due,
+                    Err(e) => {
+                        tracing::warn!("⚠️  Failed to list due connectors: {}", e);
+                        continue;
+                    }
+                };
+                for connector_id in due {
+                    match engine.run_connector(&connector_id).await {
+                        Ok(summary) => tracing::debug!(
+                            "🔌 Connector {} run {:?}: {} receipts, {} failed",
+                            connector_id,
+                            summary.status,
+                            summary.receipts_created,
+                            summary.records_failed
+                        ),
+                        Err(e) => {
+                            tracing::warn!("⚠️  Connector {} run failed: {}", connector_id, e)
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Build an unsaved template from designer input
+pub fn draft_template(owner_id: &str, input: MappingTemplateInput) -> MappingTemplate {
+    let now = Utc::now();
+    MappingTemplate {
+        template_id: Uuid::new_v4(),
+        owner_id: owner_id.to_string(),
+        name: input.name.trim().to_string(),
+        identifier_fields: input.identifier_fields,
+        enriched_fields: input.enriched_fields,
+        field_renames: input.field_renames,
+        excluded_fields: input.excluded_fields,
+        occurred_at_field: input.occurred_at_field,
+        priority: input.priority.unwrap_or(IngestionPriority::Bulk),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn preview_sample(
+    template: &MappingTemplate,
+    sample: PreviewSample,
+) -> Result<Vec<ReceiptPreview>, ConnectorError> {
+    let PollBatch {
+        records, rejected, ..
+    } = sample.records()?;
+    if records.len() + rejected.len() > MAX_PREVIEW_RECORDS {
+        return Err(ConnectorError::ValidationError(format!(
+            "Previews are limited to {MAX_PREVIEW_RECORDS} sample records"
+        )));
+    }
+
+    let mut previews: Vec<ReceiptPreview> = records
+        .iter()
+        .map(|record| preview_record(template, record))
+        .collect();
+    previews.extend(
+        rejected
+            .into_iter()
+            .map(|(record_ref, reason)| ReceiptPreview {
+                record_ref,
+                identifiers: Vec::new(),
+                payload: serde_json::Value::Null,
+                hash: String::new(),
+                data_size: 0,
+                priority: template.priority,
+                occurred_at: None,
+                warnings: Vec::new(),
+                error: Some(reason),
+            }),
+    );
+    Ok(previews)
+}
+
+pub fn connector_for(source: &ConnectorSource) -> Box<dyn LegacyConnector> {
+    match source {
+        ConnectorSource::SftpCsv {
+            drop_folder,
+            delimiter,
+            file_suffix,
+        } => Box::new(SftpCsvConnector::new(drop_folder, *delimiter, file_suffix)),
+        ConnectorSource::RestPoller {
+            url,
+            authorization,
+            records_path,
+            watermark_field,
+            watermark_param,
+        } => Box::new(RestPollerConnector::new(
+            url,
+            authorization.clone(),
+            records_path.clone(),
+            watermark_field.clone(),
+            watermark_param.clone(),
+        )),
+    }
+}
+
+fn validate_connector(config: &ConnectorConfig) -> Result<(), ConnectorError> {
+    let invalid = |msg: &str| Err(ConnectorError::ValidationError(msg.to_string()));
+    if config.name.is_empty() {
+        return invalid("Connector name cannot be empty");
+    }
+    if config.poll_interval_secs < MIN_POLL_INTERVAL_SECS {
+        return Err(ConnectorError::ValidationError(format!(
+            "poll_interval_secs must be at least {MIN_POLL_INTERVAL_SECS}"
+        )));
+    }
+    match &config.source {
+        ConnectorSource::SftpCsv {
+            drop_folder,
+            delimiter,
+            ..
+        } => {
+            if drop_folder.trim().is_empty() {
+                return invalid("drop_folder cannot be empty");
+            }
+            if matches!(delimiter, '"' | '\n' | '\r') {
+                return invalid("delimiter cannot be a quote or line break");
+            }
+        }
+        ConnectorSource::RestPoller {
+            url,
+            watermark_field,
+            watermark_param,
+            ..
+        } => {
+            if !(url.starts_with("https://") || url.starts_with("http://")) {
+                return invalid("url must be an http(s) URL");
+            }
+            if watermark_param.is_some() && watermark_field.is_none() {
+                return invalid("watermark_param requires watermark_field");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_running(state: &ConnectorState, now: DateTime<Utc>) -> bool {
+    state.status == ConnectorRunStatus::Running
+        && state
+            .last_run_at
+            .is_some_and(|started| now - started < Duration::seconds(STALE_RUN_SECS))
+}
+
+/// Poll interval, doubled per consecutive failure up to `MAX_BACKOFF_SECS`
+fn retry_delay_secs(poll_interval_secs: u64, consecutive_failures: u32) -> u64 {
+    if consecutive_failures == 0 {
+        return poll_interval_secs;
+    }
+    poll_interval_secs
+        .saturating_mul(1u64 << consecutive_failures.min(16))
+        .min(MAX_BACKOFF_SECS.max(poll_interval_secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_sftp_csv_run_creates_receipts_and_tracks_failures() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let engine = ConnectorEngine::new(Arc::clone(&storage));
+
+        let drop_folder = std::env::temp_dir().join(format!("connector-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&drop_folder).unwrap();
+        std::fs::write(
+            drop_folder.join("export.csv"),
+            "EARTAG,WEIGHT,SHIPPED,INTERNAL\nBR-001,410,2019-03-02,x\n,380,2019-03-03,y\n",
+        )
+        .unwrap();
+
+        let template = engine
+            .create_template(
+                "owner-1",
+                MappingTemplateInput {
+                    name: "ERP cattle export".to_string(),
+                    identifier_fields: vec![IdentifierFieldMapping {
+                        source_field: "EARTAG".to_string(),
+                        key: "sisbov".to_string(),
+                        namespace: Some("bovino".to_string()),
+                        transforms: vec![],
+                        default: None,
+                    }],
+                    enriched_fields: vec![],
+                    field_renames: HashMap::from([("WEIGHT".to_string(), "weight_kg".to_string())]),
+                    excluded_fields: vec!["INTERNAL".to_string()],
+                    occurred_at_field: Some("SHIPPED".to_string()),
+                    priority: None,
+                },
+            )
+            .unwrap();
+        let connector = engine
+            .create_connector(
+                "owner-1",
+                ConnectorInput {
+                    name: "Nightly ERP drop".to_string(),
+                    source: ConnectorSource::SftpCsv {
+                        drop_folder: drop_folder.to_string_lossy().into_owned(),
+                        delimiter: ',',
+                        file_suffix: ".csv".to_string(),
+                    },
+                    mapping_template_id: template.template_id,
+                    poll_interval_secs: None,
+                    enabled: None,
+                },
+            )
+            .unwrap();
+        assert!(matches!(
+            engine.get_connector("owner-2", &connector.connector_id),
+            Err(ConnectorError::PermissionDenied(_))
+        ));
+        assert_eq!(
+            engine.due_connectors(Utc::now()).unwrap(),
+            vec![connector.connector_id]
+        );
+
+        let summary = engine.run_connector(&connector.connector_id).await.unwrap();
+        assert_eq!(summary.records_seen, 2);
+        assert_eq!(summary.receipts_created, 1);
+        assert_eq!(summary.status, ConnectorRunStatus::PartiallyFailed);
+
+        let receipts = storage.list_receipts().unwrap();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].identifiers[0].value, "BR-001");
+        assert_eq!(
+            receipts[0].occurred_at.map(|t| t.date_naive().to_string()),
+            Some("2019-03-02".to_string())
+        );
+
+        assert!(drop_folder.join("failed").join("export.csv").exists());
+        let dashboard = engine
+            .dashboard("owner-1", &connector.connector_id)
+            .unwrap();
+        assert_eq!(dashboard.state.records_ingested, 1);
+        assert_eq!(dashboard.recent_errors.len(), 1);
+        assert_eq!(
+            dashboard.recent_errors[0].record_ref.as_deref(),
+            Some("export.csv#2")
+        );
+        assert!(engine.due_connectors(Utc::now()).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&drop_folder).ok();
+    }
+}
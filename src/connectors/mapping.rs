@@ -0,0 +1,444 @@
+//! Applies saved mapping templates to legacy export records.
+
+use super::base::SourceRecord;
+use crate::identifier_types::{namespaces, registries};
+use crate::types::{FieldTransform, Identifier, MappingTemplate, ReceiptPreview};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde_json::{Number, Value};
+use std::collections::{BTreeMap, HashSet};
+
+/// Receipt input produced from one source record
+#[derive(Debug, Clone)]
+pub struct MappedRecord {
+    pub identifiers: Vec<Identifier>,
+    /// Canonical JSON (sorted keys) so identical rows hash identically
+    pub payload: Vec<u8>,
+    pub occurred_at: Option<DateTime<Utc>>,
+}
+
+/// Everything wrong with a template, so a designer can show all problems at once
+pub fn template_issues(template: &MappingTemplate) -> Vec<String> {
+    let mut issues = Vec::new();
+    if template.name.trim().is_empty() {
+        issues.push("Template name cannot be empty".to_string());
+    }
+    if template.identifier_fields.is_empty() {
+        issues.push("At least one identifier field mapping is required".to_string());
+    }
+
+    let mut identifier_keys = HashSet::new();
+    for mapping in &template.identifier_fields {
+        if mapping.source_field.trim().is_empty() || mapping.key.trim().is_empty() {
+            issues.push("Identifier mappings need a source_field and a key".to_string());
+            continue;
+        }
+        if let Some(namespace) = &mapping.namespace {
+            if !namespaces::is_valid(namespace) {
+                issues.push(format!(
+                    "Unknown namespace '{namespace}' for identifier '{}' (expected one of {})",
+                    mapping.key,
+                    namespaces::all().join(", ")
+                ));
+            }
+        }
+        let namespace = mapping.namespace.as_deref().unwrap_or(namespaces::GENERIC);
+        if !identifier_keys.insert((namespace, mapping.key.as_str())) {
+            issues.push(format!(
+                "Identifier '{namespace}:{}' is mapped twice",
+                mapping.key
+            ));
+        }
+        transform_issues(&mapping.source_field, &mapping.transforms, &mut issues);
+    }
+
+    let mut targets = HashSet::new();
+    for mapping in &template.enriched_fields {
+        if mapping.source_field.trim().is_empty() || mapping.target_field.trim().is_empty() {
+            issues
+                .push("Enriched field mappings need a source_field and a target_field".to_string());
+            continue;
+        }
+        if !targets.insert(mapping.target_field.as_str()) {
+            issues.push(format!(
+                "Payload field '{}' is produced by more than one mapping",
+                mapping.target_field
+            ));
+        }
+        transform_issues(&mapping.source_field, &mapping.transforms, &mut issues);
+    }
+
+    if template
+        .occurred_at_field
+        .as_deref()
+        .is_some_and(|field| field.trim().is_empty())
+    {
+        issues.push("occurred_at_field cannot be blank".to_string());
+    }
+    issues
+}
+
+fn transform_issues(source_field: &str, transforms: &[FieldTransform], issues: &mut Vec<String>) {
+    for transform in transforms {
+        match transform {
+            FieldTransform::Replace { from, .. } if from.is_empty() => issues.push(format!(
+                "Replace transform on '{source_field}' needs a non-empty 'from'"
+            )),
+            FieldTransform::Date { format }
+                if format.is_empty()
+                    || StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) =>
+            {
+                issues.push(format!(
+                    "Invalid date format '{format}' on '{source_field}'"
+                ))
+            }
+            FieldTransform::Lookup { values, .. } if values.is_empty() => issues.push(format!(
+                "Lookup transform on '{source_field}' has no values"
+            )),
+            _ => {}
+        }
+    }
+}
+
+pub fn validate_template(template: &MappingTemplate) -> Result<(), String> {
+    let issues = template_issues(template);
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues.join("; "))
+    }
+}
+
+pub fn apply_template(
+    template: &MappingTemplate,
+    record: &SourceRecord,
+) -> Result<MappedRecord, String> {
+    let mut identifiers = Vec::with_capacity(template.identifier_fields.len());
+    for mapping in &template.identifier_fields {
+        let value = match present(record.fields.get(&mapping.source_field)) {
+            Some(value) => apply_transforms(value.clone(), &mapping.transforms)
+                .map_err(|e| format!("Field '{}': {e}", mapping.source_field))?,
+            None => mapping
+                .default
+                .clone()
+                .map(Value::String)
+                .unwrap_or(Value::Null),
+        };
+        let value = field_as_string(&value)
+            .ok_or_else(|| format!("Missing identifier field '{}'", mapping.source_field))?;
+        identifiers.push(match &mapping.namespace {
+            Some(namespace) => Identifier::contextual(namespace, &mapping.key, value),
+            None => Identifier::new(&mapping.key, value),
+        });
+    }
+
+    let occurred_at = match &template.occurred_at_field {
+        Some(field) => match record.fields.get(field) {
+            Some(value) => parse_occurred_at(value)
+                .map_err(|e| format!("Invalid occurred_at field '{field}': {e}"))?,
+            None => None,
+        },
+        None => None,
+    };
+
+    let enriched_sources: HashSet<&str> = template
+        .enriched_fields
+        .iter()
+        .map(|mapping| mapping.source_field.as_str())
+        .collect();
+    let mut payload: BTreeMap<String, Value> = record
+        .fields
+        .iter()
+        .filter(|(field, _)| {
+            !template.excluded_fields.contains(field) && !enriched_sources.contains(field.as_str())
+        })
+        .map(|(field, value)| {
+            let name = template.field_renames.get(field).unwrap_or(field);
+            (name.clone(), value.clone())
+        })
+        .collect();
+
+    for mapping in &template.enriched_fields {
+        let value = match present(record.fields.get(&mapping.source_field)) {
+            Some(value) => Some(
+                apply_transforms(value.clone(), &mapping.transforms)
+                    .map_err(|e| format!("Field '{}': {e}", mapping.source_field))?,
+            ),
+            None => mapping.default.clone(),
+        };
+        match value {
+            Some(value) => {
+                payload.insert(mapping.target_field.clone(), value);
+            }
+            None if mapping.required => {
+                return Err(format!("Missing required field '{}'", mapping.source_field));
+            }
+            None => {}
+        }
+    }
+    let payload = serde_json::to_vec(&payload).map_err(|e| e.to_string())?;
+
+    Ok(MappedRecord {
+        identifiers,
+        payload,
+        occurred_at,
+    })
+}
+
+/// Run a template against a sample record and describe the receipt it would produce
+pub fn preview_record(template: &MappingTemplate, record: &SourceRecord) -> ReceiptPreview {
+    let mut preview = ReceiptPreview {
+        record_ref: record.record_ref.clone(),
+        identifiers: Vec::new(),
+        payload: Value::Null,
+        hash: String::new(),
+        data_size: 0,
+        priority: template.priority,
+        occurred_at: None,
+        warnings: Vec::new(),
+        error: None,
+    };
+
+    match apply_template(template, record) {
+        Ok(mapped) => {
+            preview.warnings = mapped
+                .identifiers
+                .iter()
+                .filter(|id| !registries::validate(&id.key, &id.value))
+                .map(|id| format!("'{}' is not a valid {} value", id.value, id.key))
+                .collect();
+            preview.hash = crate::hashing::hash(&mapped.payload);
+            preview.data_size = mapped.payload.len();
+            preview.payload = serde_json::from_slice(&mapped.payload).unwrap_or(Value::Null);
+            preview.identifiers = mapped.identifiers;
+            preview.occurred_at = mapped.occurred_at;
+        }
+        Err(e) => preview.error = Some(e),
+    }
+    preview
+}
+
+/// `None` for absent, null or blank values so defaults can kick in
+fn present(value: Option<&Value>) -> Option<&Value> {
+    value.filter(|value| match value {
+        Value::Null => false,
+        Value::String(s) => !s.trim().is_empty(),
+        _ => true,
+    })
+}
+
+fn apply_transforms(mut value: Value, transforms: &[FieldTransform]) -> Result<Value, String> {
+    for transform in transforms {
+        let text = match &value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        value = match transform {
+            FieldTransform::Trim => Value::String(text.trim().to_string()),
+            FieldTransform::Uppercase => Value::String(text.to_uppercase()),
+            FieldTransform::Lowercase => Value::String(text.to_lowercase()),
+            FieldTransform::Replace { from, to } => Value::String(text.replace(from, to)),
+            FieldTransform::Prefix { value } => Value::String(format!("{value}{text}")),
+            FieldTransform::Suffix { value } => Value::String(format!("{text}{value}")),
+            FieldTransform::Number => {
+                let normalized = text.trim().replace(',', ".");
+                let number = match normalized.parse::<i64>() {
+                    Ok(n) => Some(Number::from(n)),
+                    Err(_) => normalized.parse::<f64>().ok().and_then(Number::from_f64),
+                };
+                Value::Number(number.ok_or_else(|| format!("'{text}' is not a number"))?)
+            }
+            FieldTransform::Boolean => match text.trim().to_lowercase().as_str() {
+                "true" | "yes" | "y" | "sim" | "s" | "1" => Value::Bool(true),
+                "false" | "no" | "n" | "nao" | "não" | "0" => Value::Bool(false),
+                _ => return Err(format!("'{text}' is not a boolean")),
+            },
+            FieldTransform::Date { format } => {
+                let text = text.trim();
+                let date = NaiveDate::parse_from_str(text, format)
+                    .or_else(|_| NaiveDateTime::parse_from_str(text, format).map(|ts| ts.date()))
+                    .map_err(|_| format!("'{text}' does not match date format '{format}'"))?;
+                Value::String(date.format("%Y-%m-%d").to_string())
+            }
+            FieldTransform::Lookup { values, default } => {
+                match values.get(text.trim()).or(default.as_ref()) {
+                    Some(mapped) => Value::String(mapped.clone()),
+                    None => return Err(format!("No lookup value for '{text}'")),
+                }
+            }
+        };
+    }
+    Ok(value)
+}
+
+fn field_as_string(value: &Value) -> Option<String> {
+    let value = match value {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        _ => return None,
+    };
+    (!value.is_empty()).then_some(value)
+}
+
+/// Accepts RFC 3339, `YYYY-MM-DD[ HH:MM:SS]` (UTC) or unix seconds; blank means unknown
+fn parse_occurred_at(value: &Value) -> Result<Option<DateTime<Utc>>, String> {
+    match value {
+        Value::Null => Ok(None),
+        Value::Number(n) => n
+            .as_i64()
+            .and_then(|secs| DateTime::from_timestamp(secs, 0))
+            .map(Some)
+            .ok_or_else(|| format!("{n} is not a unix timestamp")),
+        Value::String(s) => {
+            let s = s.trim();
+            if s.is_empty() {
+                return Ok(None);
+            }
+            if let Ok(ts) = DateTime::parse_from_rfc3339(s) {
+                return Ok(Some(ts.with_timezone(&Utc)));
+            }
+            if let Ok(ts) = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
+                return Ok(Some(ts.and_utc()));
+            }
+            if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+                return Ok(date.and_hms_opt(0, 0, 0).map(|ts| ts.and_utc()));
+            }
+            if let Ok(secs) = s.parse::<i64>() {
+                return Ok(DateTime::from_timestamp(secs, 0));
+            }
+            Err(format!("unrecognised timestamp '{s}'"))
+        }
+        other => Err(format!("unsupported value {other}")),
+    }
+}
+
+/// Sample records for a preview: one JSON object, or an array of them
+pub fn sample_records(sample: Value) -> Result<Vec<SourceRecord>, String> {
+    let objects = match sample {
+        Value::Array(values) => values,
+        object @ Value::Object(_) => vec![object],
+        _ => return Err("Sample must be a JSON object or an array of objects".to_string()),
+    };
+    objects
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| match value {
+            Value::Object(fields) => Ok(SourceRecord {
+                record_ref: format!("sample#{}", index + 1),
+                fields,
+            }),
+            _ => Err(format!("Sample record {} is not a JSON object", index + 1)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EnrichedFieldMapping, IdentifierFieldMapping, IngestionPriority};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_transforms_defaults_and_preview() {
+        let template = MappingTemplate {
+            template_id: uuid::Uuid::new_v4(),
+            owner_id: "owner-1".to_string(),
+            name: "ERP export".to_string(),
+            identifier_fields: vec![IdentifierFieldMapping {
+                source_field: "tag".to_string(),
+                key: "sisbov".to_string(),
+                namespace: Some("bovino".to_string()),
+                transforms: vec![
+                    FieldTransform::Trim,
+                    FieldTransform::Uppercase,
+                    FieldTransform::Replace {
+                        from: " ".to_string(),
+                        to: String::new(),
+                    },
+                ],
+                default: None,
+            }],
+            enriched_fields: vec![
+                EnrichedFieldMapping {
+                    source_field: "peso".to_string(),
+                    target_field: "weight_kg".to_string(),
+                    transforms: vec![FieldTransform::Number],
+                    default: None,
+                    required: true,
+                },
+                EnrichedFieldMapping {
+                    source_field: "sexo".to_string(),
+                    target_field: "sex".to_string(),
+                    transforms: vec![FieldTransform::Lookup {
+                        values: HashMap::from([("M".to_string(), "male".to_string())]),
+                        default: None,
+                    }],
+                    default: Some(json!("unknown")),
+                    required: false,
+                },
+                EnrichedFieldMapping {
+                    source_field: "nasc".to_string(),
+                    target_field: "born_on".to_string(),
+                    transforms: vec![FieldTransform::Date {
+                        format: "%d/%m/%Y".to_string(),
+                    }],
+                    default: None,
+                    required: false,
+                },
+            ],
+            field_renames: HashMap::new(),
+            excluded_fields: vec![],
+            occurred_at_field: None,
+            priority: IngestionPriority::Bulk,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        assert!(template_issues(&template).is_empty());
+
+        let records = sample_records(json!([
+            {"tag": " br 123456789012 ", "peso": "410,5", "nasc": "02/03/2019", "lote": 7},
+            {"tag": "BR1", "sexo": "X", "peso": "380"},
+            {"tag": "BR123456789012"}
+        ]))
+        .unwrap();
+        let previews: Vec<ReceiptPreview> = records
+            .iter()
+            .map(|record| preview_record(&template, record))
+            .collect();
+
+        assert_eq!(previews[0].error, None);
+        assert_eq!(previews[0].identifiers[0].value, "BR123456789012");
+        assert_eq!(
+            previews[0].payload,
+            json!({
+                "born_on": "2019-03-02",
+                "lote": 7,
+                "sex": "unknown",
+                "tag": " br 123456789012 ",
+                "weight_kg": 410.5
+            })
+        );
+        assert!(previews[0].warnings.is_empty());
+        assert_eq!(previews[0].hash.len(), 64);
+        assert!(previews[1]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("No lookup value"));
+        assert!(previews[2]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Missing required field 'peso'"));
+
+        let mut broken = template.clone();
+        broken.identifier_fields[0].namespace = Some("cattle".to_string());
+        broken.enriched_fields[1].target_field = "weight_kg".to_string();
+        broken.enriched_fields[2].transforms = vec![FieldTransform::Date {
+            format: "%Q".to_string(),
+        }];
+        assert_eq!(template_issues(&broken).len(), 3);
+    }
+}
@@ -0,0 +1,24 @@
+//! Legacy system connectors.
+//!
+//! A connector polls a legacy source (an SFTP CSV drop folder or a REST endpoint) on
+//! its own schedule, maps every exported record to receipt input through a saved
+//! mapping template, and keeps per-connector state (watermark, counters, next run)
+//! plus a log of failed records for the connector dashboards.
+
+pub mod base;
+pub mod engine;
+pub mod mapping;
+pub mod rest_poller;
+pub mod sftp_csv;
+
+pub use base::{ConnectorError, LegacyConnector, PollBatch, SourceRecord};
+pub use engine::{
+    connector_for, draft_template, ConnectorEngine, ConnectorInput, MappingTemplateInput,
+    PreviewSample,
+};
+pub use mapping::{
+    apply_template, preview_record, sample_records, template_issues, validate_template,
+    MappedRecord,
+};
+pub use rest_poller::RestPollerConnector;
+pub use sftp_csv::{csv_records, SftpCsvConnector};
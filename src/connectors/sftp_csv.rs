@@ -0,0 +1,210 @@
+//! CSV drop-folder connector.
+//!
+//! Legacy ERPs typically push nightly exports to an SFTP landing directory. The
+//! connector reads that directory as mounted on this host, turns every data row into
+//! a record keyed by the header row, and afterwards moves each file into
+//! `processed/` or, if any of its rows failed, `failed/` so it can be fixed and
+//! dropped again.
+
+use super::base::{ConnectorError, LegacyConnector, PollBatch, SourceRecord};
+use async_trait::async_trait;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+const PROCESSED_DIR: &str = "processed";
+const FAILED_DIR: &str = "failed";
+
+pub struct SftpCsvConnector {
+    drop_folder: PathBuf,
+    delimiter: char,
+    file_suffix: String,
+}
+
+impl SftpCsvConnector {
+    pub fn new(drop_folder: impl Into<PathBuf>, delimiter: char, file_suffix: &str) -> Self {
+        Self {
+            drop_folder: drop_folder.into(),
+            delimiter,
+            file_suffix: file_suffix.to_string(),
+        }
+    }
+
+    fn pending_files(&self) -> Result<Vec<PathBuf>, ConnectorError> {
+        let entries = std::fs::read_dir(&self.drop_folder).map_err(|e| {
+            ConnectorError::SourceError(format!(
+                "Cannot read drop folder {}: {e}",
+                self.drop_folder.display()
+            ))
+        })?;
+
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.ends_with(&self.file_suffix))
+            })
+            .collect();
+        files.sort();
+        Ok(files)
+    }
+
+    fn read_file(&self, path: &Path, file_name: &str, batch: &mut PollBatch) {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) => {
+                batch.rejected.push((file_name.to_string(), e.to_string()));
+                return;
+            }
+        };
+        match csv_records(&text, self.delimiter, file_name) {
+            Ok(parsed) => {
+                batch.records.extend(parsed.records);
+                batch.rejected.extend(parsed.rejected);
+            }
+            Err(e) => batch.rejected.push((file_name.to_string(), e)),
+        }
+    }
+}
+
+/// Turn CSV text into records keyed by its header row. Rows with the wrong column
+/// count are rejected; record refs are `source#row`.
+pub fn csv_records(text: &str, delimiter: char, source: &str) -> Result<PollBatch, String> {
+    let mut rows = parse_csv(text.trim_start_matches('\u{feff}'), delimiter)?.into_iter();
+    let mut batch = PollBatch::default();
+    let Some(header) = rows.next() else {
+        return Ok(batch);
+    };
+    let header: Vec<String> = header.iter().map(|h| h.trim().to_string()).collect();
+
+    for (index, row) in rows.enumerate() {
+        let record_ref = format!("{source}#{}", index + 1);
+        if row.len() != header.len() {
+            batch.rejected.push((
+                record_ref,
+                format!("Expected {} columns, found {}", header.len(), row.len()),
+            ));
+            continue;
+        }
+        let fields: Map<String, Value> = header
+            .iter()
+            .cloned()
+            .zip(row.into_iter().map(Value::String))
+            .collect();
+        batch.records.push(SourceRecord { record_ref, fields });
+    }
+    Ok(batch)
+}
+
+#[async_trait]
+impl LegacyConnector for SftpCsvConnector {
+    async fn poll(&self, _watermark: Option<&str>) -> Result<PollBatch, ConnectorError> {
+        let mut batch = PollBatch::default();
+        for path in self.pending_files()? {
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            self.read_file(&path, &file_name, &mut batch);
+        }
+        Ok(batch)
+    }
+
+    async fn acknowledge(
+        &self,
+        _batch: &PollBatch,
+        failed_refs: &HashSet<String>,
+    ) -> Result<(), ConnectorError> {
+        let failed_files: HashSet<&str> = failed_refs
+            .iter()
+            .map(|record_ref| record_ref.split('#').next().unwrap_or(record_ref))
+            .collect();
+
+        // Re-list rather than trusting the batch: files dropped mid-run stay for next poll
+        for path in self.pending_files()? {
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let target_dir = if failed_files.contains(file_name.as_str()) {
+                FAILED_DIR
+            } else {
+                PROCESSED_DIR
+            };
+            let target_dir = self.drop_folder.join(target_dir);
+            std::fs::create_dir_all(&target_dir)
+                .and_then(|_| std::fs::rename(&path, target_dir.join(&file_name)))
+                .map_err(|e| {
+                    ConnectorError::SourceError(format!("Cannot move {file_name}: {e}"))
+                })?;
+        }
+        Ok(())
+    }
+}
+
+/// Minimal RFC 4180 reader: quoted fields may contain the delimiter, newlines and
+/// doubled quotes. Blank lines are skipped.
+pub fn parse_csv(text: &str, delimiter: char) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            '\r' => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                if !(row.len() == 1 && row[0].is_empty()) {
+                    rows.push(std::mem::take(&mut row));
+                }
+                row.clear();
+            }
+            c if c == delimiter => row.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(format!(
+            "Unterminated quoted field on row {}",
+            rows.len() + 1
+        ));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_handles_quotes_and_blank_lines() {
+        let text = "sku;name;note\r\n\r\nA-1;\"Steer; heavy\";\"said \"\"ok\"\"\"\nA-2;Heifer;\"two\nlines\"";
+        let rows = parse_csv(text, ';').unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1], vec!["A-1", "Steer; heavy", "said \"ok\""]);
+        assert_eq!(rows[2][2], "two\nlines");
+        assert!(parse_csv("a,\"b\n", ',').is_err());
+    }
+}
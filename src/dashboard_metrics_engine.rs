@@ -0,0 +1,586 @@
+//! Custom metrics for workspace dashboards.
+//!
+//! A workspace owner defines metrics over one circuit's events, such as the
+//! number of `Harvested` events per week or the average `weight_kg` recorded in
+//! metadata per month. Metrics are evaluated on request over a time range, one
+//! point per period, and any member of the workspace can query them. An event
+//! belongs to the circuit when it was pushed there or its item is in the
+//! circuit.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    Event, EventType, MetricDefinition, MetricMeasure, MetricPeriod, MetricPoint, MetricSeries,
+};
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, Utc};
+use serde::Deserialize;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub const MAX_METRICS_PER_WORKSPACE: usize = 50;
+/// Points a single query may return per metric
+pub const MAX_POINTS: usize = 366;
+/// Periods returned when the query gives no start
+pub const DEFAULT_POINTS: usize = 12;
+const MAX_NAME_LEN: usize = 100;
+
+#[derive(Debug)]
+pub enum MetricsError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+    NotFound(String),
+}
+
+impl From<StorageError> for MetricsError {
+    fn from(err: StorageError) -> Self {
+        MetricsError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for MetricsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetricsError::StorageError(e) => write!(f, "Storage error: {e}"),
+            MetricsError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            MetricsError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            MetricsError::NotFound(e) => write!(f, "Not found: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for MetricsError {}
+
+/// Event types are given by name, as in `EventType::from_name`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MetricMeasureInput {
+    EventCount {
+        event_type: String,
+    },
+    MetadataAverage {
+        field: String,
+        event_type: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetricDefinitionInput {
+    pub name: String,
+    pub description: Option<String>,
+    pub circuit_id: Uuid,
+    pub measure: MetricMeasureInput,
+    pub period: MetricPeriod,
+}
+
+fn event_type_named(name: &str) -> Result<EventType, MetricsError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(MetricsError::ValidationError(
+            "Event type names must not be empty".to_string(),
+        ));
+    }
+    Ok(EventType::from_name(name))
+}
+
+impl MetricMeasureInput {
+    fn into_measure(self) -> Result<MetricMeasure, MetricsError> {
+        match self {
+            MetricMeasureInput::EventCount { event_type } => Ok(MetricMeasure::EventCount {
+                event_type: event_type_named(&event_type)?,
+            }),
+            MetricMeasureInput::MetadataAverage { field, event_type } => {
+                let field = field.trim().to_string();
+                if field.is_empty() {
+                    return Err(MetricsError::ValidationError(
+                        "Averages need a metadata field".to_string(),
+                    ));
+                }
+                Ok(MetricMeasure::MetadataAverage {
+                    field,
+                    event_type: event_type.as_deref().map(event_type_named).transpose()?,
+                })
+            }
+        }
+    }
+}
+
+/// Start of the period `at` falls in
+pub fn period_start(period: MetricPeriod, at: DateTime<Utc>) -> DateTime<Utc> {
+    let date = at.date_naive();
+    let start = match period {
+        MetricPeriod::Day => date,
+        MetricPeriod::Week => {
+            date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+        }
+        MetricPeriod::Month => NaiveDate::from_ymd_opt(date.year(), date.month(), 1)
+            .expect("first of the month is a valid date"),
+    };
+    start.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+fn next_period(period: MetricPeriod, start: DateTime<Utc>) -> DateTime<Utc> {
+    match period {
+        MetricPeriod::Day => start + Duration::days(1),
+        MetricPeriod::Week => start + Duration::weeks(1),
+        MetricPeriod::Month => start + Months::new(1),
+    }
+}
+
+fn previous_period(period: MetricPeriod, start: DateTime<Utc>) -> DateTime<Utc> {
+    match period {
+        MetricPeriod::Day => start - Duration::days(1),
+        MetricPeriod::Week => start - Duration::weeks(1),
+        MetricPeriod::Month => start - Months::new(1),
+    }
+}
+
+/// Numeric value of a metadata field; numbers sent as strings count too
+fn metadata_number(event: &Event, field: &str) -> Option<f64> {
+    match event.metadata.get(field)? {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .filter(|v: &f64| v.is_finite())
+}
+
+/// Evaluate `definition` on `events` (already restricted to its circuit), one
+/// point per period from the one holding `from` to the one holding `to`
+pub fn evaluate(
+    definition: &MetricDefinition,
+    events: &[Event],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<MetricPoint> {
+    let mut starts = Vec::new();
+    let mut start = period_start(definition.period, from);
+    while start <= to && starts.len() < MAX_POINTS {
+        starts.push(start);
+        start = next_period(definition.period, start);
+    }
+
+    let mut sums = vec![0.0; starts.len()];
+    let mut counts = vec![0usize; starts.len()];
+    for event in events {
+        let bucket = period_start(definition.period, event.timestamp);
+        let Ok(index) = starts.binary_search(&bucket) else {
+            continue;
+        };
+        match &definition.measure {
+            MetricMeasure::EventCount { event_type } => {
+                if event.event_type == *event_type {
+                    counts[index] += 1;
+                }
+            }
+            MetricMeasure::MetadataAverage { field, event_type } => {
+                if event_type.as_ref().is_some_and(|t| event.event_type != *t) {
+                    continue;
+                }
+                if let Some(value) = metadata_number(event, field) {
+                    sums[index] += value;
+                    counts[index] += 1;
+                }
+            }
+        }
+    }
+
+    starts
+        .into_iter()
+        .enumerate()
+        .map(|(index, period_start)| {
+            let value = match definition.measure {
+                MetricMeasure::EventCount { .. } => Some(counts[index] as f64),
+                MetricMeasure::MetadataAverage { .. } => {
+                    (counts[index] > 0).then(|| sums[index] / counts[index] as f64)
+                }
+            };
+            MetricPoint {
+                period_start,
+                value,
+                sample_count: counts[index],
+            }
+        })
+        .collect()
+}
+
+pub struct DashboardMetricsEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> DashboardMetricsEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Members query their own workspace's metrics; admins any workspace's
+    fn check_member(&self, user_id: &str, workspace_id: &str) -> Result<(), MetricsError> {
+        let account = self
+            .storage
+            .get_user_account(user_id)?
+            .ok_or_else(|| MetricsError::PermissionDenied(format!("Unknown user {user_id}")))?;
+        if account.is_admin || account.workspace_id.as_deref() == Some(workspace_id) {
+            Ok(())
+        } else {
+            Err(MetricsError::PermissionDenied(
+                "Only workspace members can see its metrics".to_string(),
+            ))
+        }
+    }
+
+    /// Metrics are defined by the workspace owner (the account that opened
+    /// it) and admins
+    fn check_owner(&self, user_id: &str, workspace_id: &str) -> Result<(), MetricsError> {
+        let accounts = self.storage.list_user_accounts()?;
+        if accounts
+            .iter()
+            .any(|account| account.user_id == user_id && account.is_admin)
+        {
+            return Ok(());
+        }
+        let owner = accounts
+            .iter()
+            .filter(|account| account.workspace_id.as_deref() == Some(workspace_id))
+            .min_by_key(|account| account.created_at);
+        if owner.is_some_and(|account| account.user_id == user_id) {
+            Ok(())
+        } else {
+            Err(MetricsError::PermissionDenied(
+                "Only the workspace owner can define its metrics".to_string(),
+            ))
+        }
+    }
+
+    fn validated(
+        &self,
+        user_id: &str,
+        input: MetricDefinitionInput,
+    ) -> Result<(String, Option<String>, MetricMeasure), MetricsError> {
+        let name = input.name.trim().to_string();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+            return Err(MetricsError::ValidationError(format!(
+                "Metric names must be 1 to {MAX_NAME_LEN} characters"
+            )));
+        }
+        let circuit = self
+            .storage
+            .get_circuit(&input.circuit_id)?
+            .ok_or_else(|| MetricsError::NotFound(format!("Circuit {}", input.circuit_id)))?;
+        if !circuit.is_member(user_id) {
+            return Err(MetricsError::PermissionDenied(
+                "Metrics can only measure circuits you are a member of".to_string(),
+            ));
+        }
+        let description = input
+            .description
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty());
+        Ok((name, description, input.measure.into_measure()?))
+    }
+
+    pub fn define(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+        input: MetricDefinitionInput,
+        now: DateTime<Utc>,
+    ) -> Result<MetricDefinition, MetricsError> {
+        self.check_owner(user_id, workspace_id)?;
+        if self.storage.list_metric_definitions(workspace_id)?.len() >= MAX_METRICS_PER_WORKSPACE {
+            return Err(MetricsError::ValidationError(format!(
+                "A workspace can define at most {MAX_METRICS_PER_WORKSPACE} metrics"
+            )));
+        }
+        let circuit_id = input.circuit_id;
+        let period = input.period;
+        let (name, description, measure) = self.validated(user_id, input)?;
+
+        let definition = MetricDefinition {
+            metric_id: Uuid::new_v4(),
+            workspace_id: workspace_id.to_string(),
+            name,
+            description,
+            circuit_id,
+            measure,
+            period,
+            created_by: user_id.to_string(),
+            created_at: now,
+            updated_at: now,
+        };
+        self.storage.store_metric_definition(&definition)?;
+        Ok(definition)
+    }
+
+    fn workspace_metric(
+        &self,
+        workspace_id: &str,
+        metric_id: &Uuid,
+    ) -> Result<MetricDefinition, MetricsError> {
+        self.storage
+            .get_metric_definition(metric_id)?
+            .filter(|definition| definition.workspace_id == workspace_id)
+            .ok_or_else(|| MetricsError::NotFound(format!("Metric {metric_id}")))
+    }
+
+    pub fn update(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+        metric_id: &Uuid,
+        input: MetricDefinitionInput,
+        now: DateTime<Utc>,
+    ) -> Result<MetricDefinition, MetricsError> {
+        self.check_owner(user_id, workspace_id)?;
+        let mut definition = self.workspace_metric(workspace_id, metric_id)?;
+        definition.circuit_id = input.circuit_id;
+        definition.period = input.period;
+        let (name, description, measure) = self.validated(user_id, input)?;
+        definition.name = name;
+        definition.description = description;
+        definition.measure = measure;
+        definition.updated_at = now;
+        self.storage.store_metric_definition(&definition)?;
+        Ok(definition)
+    }
+
+    pub fn delete(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+        metric_id: &Uuid,
+    ) -> Result<(), MetricsError> {
+        self.check_owner(user_id, workspace_id)?;
+        self.workspace_metric(workspace_id, metric_id)?;
+        Ok(self.storage.delete_metric_definition(metric_id)?)
+    }
+
+    pub fn list(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+    ) -> Result<Vec<MetricDefinition>, MetricsError> {
+        self.check_member(user_id, workspace_id)?;
+        Ok(self.storage.list_metric_definitions(workspace_id)?)
+    }
+
+    /// Series of the given metrics, or of all the workspace's metrics when
+    /// `metric_ids` is empty. `from` defaults to the last `DEFAULT_POINTS`
+    /// periods of each metric.
+    pub fn query(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+        metric_ids: &[Uuid],
+        from: Option<DateTime<Utc>>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<MetricSeries>, MetricsError> {
+        self.check_member(user_id, workspace_id)?;
+        if from.is_some_and(|from| from > to) {
+            return Err(MetricsError::ValidationError(
+                "The range must start before it ends".to_string(),
+            ));
+        }
+        let definitions = if metric_ids.is_empty() {
+            self.storage.list_metric_definitions(workspace_id)?
+        } else {
+            metric_ids
+                .iter()
+                .map(|metric_id| self.workspace_metric(workspace_id, metric_id))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        let mut series = Vec::with_capacity(definitions.len());
+        for definition in definitions {
+            // Whole periods, so the first point counts its full period
+            let from = match from {
+                Some(from) => period_start(definition.period, from),
+                None => (1..DEFAULT_POINTS)
+                    .fold(period_start(definition.period, to), |start, _| {
+                        previous_period(definition.period, start)
+                    }),
+            };
+            let events = self.circuit_events(&definition.circuit_id, from, to)?;
+            series.push(MetricSeries {
+                metric_id: definition.metric_id,
+                name: definition.name.clone(),
+                period: definition.period,
+                points: evaluate(&definition, &events, from, to),
+            });
+        }
+        Ok(series)
+    }
+
+    fn circuit_events(
+        &self,
+        circuit_id: &Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Event>, MetricsError> {
+        let dfids: HashSet<String> = self
+            .storage
+            .get_circuit_items(circuit_id)?
+            .into_iter()
+            .map(|item| item.dfid)
+            .collect();
+        Ok(self
+            .storage
+            .get_events_in_time_range(from, to)?
+            .into_iter()
+            .filter(|event| {
+                event.pushed_to_circuit == Some(*circuit_id) || dfids.contains(&event.dfid)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{
+        AccountStatus, Circuit, CircuitItem, EventVisibility, TierLimits, UserAccount, UserTier,
+    };
+    use chrono::TimeZone;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    fn account(user_id: &str, workspace_id: &str, created_at: DateTime<Utc>) -> UserAccount {
+        UserAccount {
+            user_id: user_id.to_string(),
+            username: user_id.to_string(),
+            email: format!("{user_id}@coop.example"),
+            password_hash: "hash".to_string(),
+            limits: TierLimits::for_tier(&UserTier::Basic),
+            tier: UserTier::Basic,
+            status: AccountStatus::Active,
+            credits: 0,
+            created_at,
+            updated_at: created_at,
+            last_login: None,
+            subscription: None,
+            is_admin: false,
+            workspace_id: Some(workspace_id.to_string()),
+            available_adapters: None,
+            locale: None,
+        }
+    }
+
+    #[test]
+    fn test_weekly_metrics_count_events_and_average_metadata() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let opened = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        storage
+            .store_user_account(&account("owner", "coop", opened))
+            .unwrap();
+        storage
+            .store_user_account(&account("member", "coop", opened + Duration::days(1)))
+            .unwrap();
+        let circuit = Circuit::new(
+            "Harvest".to_string(),
+            "Co-op harvests".to_string(),
+            "owner".to_string(),
+        );
+        storage.store_circuit(&circuit).unwrap();
+        storage
+            .store_circuit_item(&CircuitItem::new(
+                "DFID-1".to_string(),
+                circuit.circuit_id,
+                "owner".to_string(),
+                vec![],
+            ))
+            .unwrap();
+
+        // Monday 2 March and Wednesday 11 March 2026
+        let first_week = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        let second_week = Utc.with_ymd_and_hms(2026, 3, 11, 9, 0, 0).unwrap();
+        for (at, dfid, weight) in [
+            (first_week, "DFID-1", "10"),
+            (first_week, "DFID-1", "20"),
+            (second_week, "DFID-1", "40"),
+            (second_week, "DFID-OTHER", "1000"),
+        ] {
+            let mut event = Event::new_with_metadata(
+                dfid.to_string(),
+                EventType::Custom("Harvested".to_string()),
+                "owner".to_string(),
+                EventVisibility::CircuitOnly,
+                HashMap::from([("weight_kg".to_string(), serde_json::json!(weight))]),
+            );
+            event.timestamp = at;
+            storage.store_event(&event).unwrap();
+        }
+
+        let engine = DashboardMetricsEngine::new(Arc::clone(&storage));
+        let input = |measure| MetricDefinitionInput {
+            name: "Harvests".to_string(),
+            description: None,
+            circuit_id: circuit.circuit_id,
+            measure,
+            period: MetricPeriod::Week,
+        };
+        let count = engine
+            .define(
+                "owner",
+                "coop",
+                input(MetricMeasureInput::EventCount {
+                    event_type: "Harvested".to_string(),
+                }),
+                opened,
+            )
+            .unwrap();
+        let average = engine
+            .define(
+                "owner",
+                "coop",
+                input(MetricMeasureInput::MetadataAverage {
+                    field: "weight_kg".to_string(),
+                    event_type: None,
+                }),
+                opened,
+            )
+            .unwrap();
+        assert!(matches!(
+            engine.define(
+                "member",
+                "coop",
+                input(MetricMeasureInput::EventCount {
+                    event_type: "Harvested".to_string(),
+                }),
+                opened,
+            ),
+            Err(MetricsError::PermissionDenied(_))
+        ));
+
+        let series = engine
+            .query(
+                "member",
+                "coop",
+                &[],
+                Some(first_week),
+                Utc.with_ymd_and_hms(2026, 3, 15, 0, 0, 0).unwrap(),
+            )
+            .unwrap();
+        let values = |metric_id: Uuid| -> Vec<Option<f64>> {
+            series
+                .iter()
+                .find(|s| s.metric_id == metric_id)
+                .unwrap()
+                .points
+                .iter()
+                .map(|point| point.value)
+                .collect()
+        };
+        assert_eq!(values(count.metric_id), [Some(2.0), Some(1.0)]);
+        assert_eq!(values(average.metric_id), [Some(15.0), Some(40.0)]);
+
+        // Without a start, the last twelve weeks are returned
+        let recent = engine
+            .query("member", "coop", &[count.metric_id], None, second_week)
+            .unwrap();
+        assert_eq!(recent[0].points.len(), DEFAULT_POINTS);
+        assert_eq!(recent[0].points[DEFAULT_POINTS - 1].value, Some(1.0));
+        assert!(matches!(
+            engine.list("outsider", "coop"),
+            Err(MetricsError::PermissionDenied(_))
+        ));
+    }
+}
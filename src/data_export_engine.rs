@@ -0,0 +1,582 @@
+//! Workspace data exports for BI tools.
+//!
+//! An export job takes one dataset (items, events, receipts or audit events)
+//! of a workspace, optionally bounded by a date range, and writes it as CSV or
+//! JSON Lines to `EXPORT_DIR` in the background, storing progress on the job
+//! as it goes. The API polls the job and streams the file once completed.
+//! Workspaces with their own bucket get the file written there instead, and
+//! the download redirects to a presigned URL.
+//!
+//! A workspace's data is what its members produced: events they sourced,
+//! items those events touch, receipts sharing an identifier with such items
+//! and audit events of the members themselves.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    AuditEvent, DataExportDataset, DataExportFormat, DataExportJob, DataExportStatus, Event,
+    Identifier, Item, Receipt,
+};
+use crate::workspace_bucket_engine;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Unfinished jobs a user may have at once
+pub const MAX_ACTIVE_JOBS_PER_USER: usize = 3;
+
+/// Rows written between progress updates
+const PROGRESS_INTERVAL: usize = 500;
+
+#[derive(Debug)]
+pub enum DataExportError {
+    StorageError(StorageError),
+    ValidationError(String),
+    NotFound(String),
+    PermissionDenied(String),
+    TooManyJobs(usize),
+    IoError(String),
+}
+
+impl From<StorageError> for DataExportError {
+    fn from(err: StorageError) -> Self {
+        DataExportError::StorageError(err)
+    }
+}
+
+impl From<std::io::Error> for DataExportError {
+    fn from(err: std::io::Error) -> Self {
+        DataExportError::IoError(err.to_string())
+    }
+}
+
+impl std::fmt::Display for DataExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataExportError::StorageError(e) => write!(f, "Storage error: {e}"),
+            DataExportError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            DataExportError::NotFound(e) => write!(f, "Not found: {e}"),
+            DataExportError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            DataExportError::TooManyJobs(max) => {
+                write!(
+                    f,
+                    "At most {max} exports can run at once; wait for one to finish"
+                )
+            }
+            DataExportError::IoError(e) => write!(f, "I/O error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for DataExportError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DataExportRequest {
+    pub dataset: DataExportDataset,
+    pub format: DataExportFormat,
+    /// Defaults to the caller's workspace; admins may export any workspace
+    pub workspace_id: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Directory export files are written to, from `EXPORT_DIR`
+pub fn default_output_dir() -> PathBuf {
+    std::env::var("EXPORT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::temp_dir().join("defarm-exports"))
+}
+
+pub struct DataExportEngine<S: StorageBackend> {
+    storage: S,
+    output_dir: PathBuf,
+}
+
+impl<S: StorageBackend> DataExportEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            output_dir: default_output_dir(),
+        }
+    }
+
+    pub fn with_output_dir(mut self, output_dir: PathBuf) -> Self {
+        self.output_dir = output_dir;
+        self
+    }
+
+    fn is_admin(&self, user_id: &str) -> Result<bool, DataExportError> {
+        Ok(self
+            .storage
+            .get_user_account(user_id)?
+            .is_some_and(|account| account.is_admin))
+    }
+
+    /// Members may export their own workspace; admins any workspace
+    pub fn create_job(
+        &self,
+        user_id: &str,
+        request: DataExportRequest,
+        now: DateTime<Utc>,
+    ) -> Result<DataExportJob, DataExportError> {
+        if request.format == DataExportFormat::Parquet {
+            return Err(DataExportError::ValidationError(
+                "Parquet export is not available on this server; use csv or jsonl".to_string(),
+            ));
+        }
+        if let (Some(from), Some(to)) = (request.from, request.to) {
+            if from > to {
+                return Err(DataExportError::ValidationError(
+                    "from must not be after to".to_string(),
+                ));
+            }
+        }
+
+        let account = self
+            .storage
+            .get_user_account(user_id)?
+            .ok_or_else(|| DataExportError::NotFound(format!("User {user_id}")))?;
+        let workspace_id = match (request.workspace_id, account.workspace_id) {
+            (None, Some(own)) => own,
+            (None, None) => {
+                return Err(DataExportError::ValidationError(
+                    "workspace_id is required for users without a workspace".to_string(),
+                ))
+            }
+            (Some(requested), own) => {
+                if own.as_deref() != Some(requested.as_str()) && !account.is_admin {
+                    return Err(DataExportError::PermissionDenied(
+                        "Only workspace members can export its data".to_string(),
+                    ));
+                }
+                requested
+            }
+        };
+
+        let active = self
+            .storage
+            .list_data_export_jobs(user_id)?
+            .iter()
+            .filter(|job| {
+                matches!(
+                    job.status,
+                    DataExportStatus::Queued | DataExportStatus::Running
+                )
+            })
+            .count();
+        if active >= MAX_ACTIVE_JOBS_PER_USER {
+            return Err(DataExportError::TooManyJobs(MAX_ACTIVE_JOBS_PER_USER));
+        }
+
+        let job = DataExportJob {
+            job_id: Uuid::new_v4(),
+            requested_by: user_id.to_string(),
+            workspace_id,
+            dataset: request.dataset,
+            format: request.format,
+            from: request.from,
+            to: request.to,
+            status: DataExportStatus::Queued,
+            total_rows: None,
+            rows_written: 0,
+            size_bytes: 0,
+            output_path: None,
+            bucket_object: None,
+            error: None,
+            created_at: now,
+            started_at: None,
+            completed_at: None,
+        };
+        self.storage.store_data_export_job(&job)?;
+        Ok(job)
+    }
+
+    /// A job as seen by `user_id`: its requester or an admin
+    pub fn get_job(&self, user_id: &str, job_id: &Uuid) -> Result<DataExportJob, DataExportError> {
+        let job = self
+            .storage
+            .get_data_export_job(job_id)?
+            .ok_or_else(|| DataExportError::NotFound(format!("Export {job_id}")))?;
+        if job.requested_by != user_id && !self.is_admin(user_id)? {
+            return Err(DataExportError::NotFound(format!("Export {job_id}")));
+        }
+        Ok(job)
+    }
+
+    pub fn list_jobs(&self, user_id: &str) -> Result<Vec<DataExportJob>, DataExportError> {
+        Ok(self.storage.list_data_export_jobs(user_id)?)
+    }
+
+    /// Write the job's file, recording progress and the final status on the job
+    pub fn run_job(&self, job_id: &Uuid) -> Result<DataExportJob, DataExportError> {
+        let mut job = self
+            .storage
+            .get_data_export_job(job_id)?
+            .ok_or_else(|| DataExportError::NotFound(format!("Export {job_id}")))?;
+        job.status = DataExportStatus::Running;
+        job.started_at = Some(Utc::now());
+        self.storage.store_data_export_job(&job)?;
+
+        match self
+            .write_export(&mut job)
+            .and_then(|()| self.deliver_to_bucket(&mut job))
+        {
+            Ok(()) => {
+                job.status = DataExportStatus::Completed;
+            }
+            Err(e) => {
+                tracing::warn!("⚠️  Export {} failed: {}", job.job_id, e);
+                job.status = DataExportStatus::Failed;
+                job.error = Some(e.to_string());
+            }
+        }
+        job.completed_at = Some(Utc::now());
+        self.storage.store_data_export_job(&job)?;
+        Ok(job)
+    }
+
+    /// Run the job on the blocking pool
+    pub fn spawn_job(self, job_id: Uuid) -> tokio::task::JoinHandle<()>
+    where
+        S: Send + 'static,
+    {
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = self.run_job(&job_id) {
+                tracing::warn!("⚠️  Could not run export {}: {}", job_id, e);
+            }
+        })
+    }
+
+    fn write_export(&self, job: &mut DataExportJob) -> Result<(), DataExportError> {
+        let rows = self.collect_rows(job)?;
+        job.total_rows = Some(rows.len());
+        self.storage.store_data_export_job(job)?;
+
+        std::fs::create_dir_all(&self.output_dir)?;
+        let path = self
+            .output_dir
+            .join(format!("{}.{}", job.job_id, job.format.extension()));
+        let mut writer = BufWriter::new(File::create(&path)?);
+        job.output_path = Some(path.to_string_lossy().into_owned());
+
+        let columns = columns(job.dataset);
+        if job.format == DataExportFormat::Csv {
+            writeln!(writer, "{}", columns.join(","))?;
+        }
+        for (index, row) in rows.iter().enumerate() {
+            match job.format {
+                DataExportFormat::Csv => writeln!(writer, "{}", csv_line(row, columns))?,
+                _ => writeln!(writer, "{row}")?,
+            }
+            job.rows_written = index + 1;
+            if job.rows_written.is_multiple_of(PROGRESS_INTERVAL) {
+                self.storage.store_data_export_job(job)?;
+            }
+        }
+        writer.flush()?;
+        drop(writer);
+        job.size_bytes = std::fs::metadata(&path)?.len();
+        Ok(())
+    }
+
+    /// Move the written file into the workspace's own bucket, if it has one.
+    /// Needs to run on the blocking pool of a Tokio runtime, as `spawn_job` does.
+    fn deliver_to_bucket(&self, job: &mut DataExportJob) -> Result<(), DataExportError> {
+        let (Some(config), Some(path)) = (
+            self.storage
+                .get_workspace_bucket_config(&job.workspace_id)?,
+            job.output_path.clone(),
+        ) else {
+            return Ok(());
+        };
+        let content = std::fs::read(&path)?;
+        // Whatever happens, the file does not stay on the API host
+        std::fs::remove_file(&path)?;
+        job.output_path = None;
+
+        let runtime = tokio::runtime::Handle::try_current()
+            .map_err(|e| DataExportError::IoError(format!("No runtime to upload with: {e}")))?;
+        let key = workspace_bucket_engine::export_key(&config, &job.job_id, &job.file_name());
+        let object = runtime
+            .block_on(workspace_bucket_engine::put_object(
+                &config,
+                &key,
+                content,
+                job.format.content_type(),
+            ))
+            .map_err(|e| {
+                DataExportError::IoError(format!("Could not write to the workspace bucket: {e}"))
+            })?;
+        job.bucket_object = Some(object);
+        Ok(())
+    }
+
+    /// Records in the job's workspace and date range, oldest first
+    fn collect_rows(&self, job: &DataExportJob) -> Result<Vec<Value>, DataExportError> {
+        let in_range = |at: &DateTime<Utc>| {
+            job.from.is_none_or(|from| *at >= from) && job.to.is_none_or(|to| *at <= to)
+        };
+        let members: HashSet<String> = self
+            .storage
+            .list_user_accounts()?
+            .into_iter()
+            .filter(|account| account.workspace_id.as_deref() == Some(job.workspace_id.as_str()))
+            .map(|account| account.user_id)
+            .collect();
+
+        let rows = match job.dataset {
+            DataExportDataset::Events => {
+                let mut events = self.workspace_events(&members)?;
+                events.retain(|event| in_range(&event.timestamp));
+                events.sort_by_key(|event| event.timestamp);
+                events.iter().map(to_row).collect()
+            }
+            DataExportDataset::Items => {
+                let mut items = self.workspace_items(&members)?;
+                items.retain(|item| in_range(&item.creation_timestamp));
+                items.sort_by_key(|item| item.creation_timestamp);
+                items.iter().map(to_row).collect()
+            }
+            DataExportDataset::Receipts => {
+                let identifiers: HashSet<(String, String)> = self
+                    .workspace_items(&members)?
+                    .iter()
+                    .flat_map(|item| item.identifiers.iter().map(identifier_key))
+                    .collect();
+                let mut receipts: Vec<Receipt> = self
+                    .storage
+                    .list_receipts()?
+                    .into_iter()
+                    .filter(|receipt| {
+                        in_range(&receipt.timestamp)
+                            && receipt
+                                .identifiers
+                                .iter()
+                                .any(|id| identifiers.contains(&identifier_key(id)))
+                    })
+                    .collect();
+                receipts.sort_by_key(|receipt| receipt.timestamp);
+                receipts.iter().map(to_row).collect()
+            }
+            DataExportDataset::AuditEvents => {
+                let mut events: Vec<AuditEvent> = self
+                    .storage
+                    .list_audit_events()?
+                    .into_iter()
+                    .filter(|event| members.contains(&event.user_id) && in_range(&event.timestamp))
+                    .collect();
+                events.sort_by_key(|event| event.timestamp);
+                events.iter().map(to_row).collect()
+            }
+        };
+        Ok(rows)
+    }
+
+    fn workspace_events(&self, members: &HashSet<String>) -> Result<Vec<Event>, DataExportError> {
+        Ok(self
+            .storage
+            .list_events()?
+            .into_iter()
+            .filter(|event| members.contains(&event.source))
+            .collect())
+    }
+
+    fn workspace_items(&self, members: &HashSet<String>) -> Result<Vec<Item>, DataExportError> {
+        let dfids: HashSet<String> = self
+            .workspace_events(members)?
+            .into_iter()
+            .map(|event| event.dfid)
+            .collect();
+        Ok(self
+            .storage
+            .list_items()?
+            .into_iter()
+            .filter(|item| dfids.contains(&item.dfid))
+            .collect())
+    }
+}
+
+fn identifier_key(identifier: &Identifier) -> (String, String) {
+    (identifier.key.clone(), identifier.value.clone())
+}
+
+fn to_row<T: serde::Serialize>(record: &T) -> Value {
+    serde_json::to_value(record).unwrap_or(Value::Null)
+}
+
+/// CSV columns per dataset; JSONL rows carry the full record
+fn columns(dataset: DataExportDataset) -> &'static [&'static str] {
+    match dataset {
+        DataExportDataset::Items => &[
+            "dfid",
+            "status",
+            "identifiers",
+            "creation_timestamp",
+            "last_modified",
+            "confidence_score",
+            "first_occurred_at",
+            "last_occurred_at",
+        ],
+        DataExportDataset::Events => &[
+            "event_id",
+            "dfid",
+            "event_type",
+            "timestamp",
+            "occurred_at",
+            "source",
+            "visibility",
+            "is_encrypted",
+            "content_hash",
+            "pushed_to_circuit",
+        ],
+        DataExportDataset::Receipts => &[
+            "id",
+            "hash",
+            "timestamp",
+            "occurred_at",
+            "data_size",
+            "priority",
+            "identifiers",
+        ],
+        DataExportDataset::AuditEvents => &[
+            "event_id",
+            "user_id",
+            "event_type",
+            "action",
+            "resource",
+            "resource_id",
+            "outcome",
+            "severity",
+            "timestamp",
+        ],
+    }
+}
+
+fn csv_field(value: Option<&Value>) -> String {
+    let text = match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        // Identifiers flatten to `key:value` pairs separated by `;`
+        Some(Value::Array(values)) if values.iter().all(|v| v.get("key").is_some()) => values
+            .iter()
+            .map(|v| {
+                format!(
+                    "{}:{}",
+                    v["key"].as_str().unwrap_or_default(),
+                    v["value"].as_str().unwrap_or_default()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(";"),
+        Some(other) => other.to_string(),
+    };
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+fn csv_line(row: &Value, columns: &[&str]) -> String {
+    columns
+        .iter()
+        .map(|column| csv_field(row.get(*column)))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{
+        AccountStatus, EventType, EventVisibility, TierLimits, UserAccount, UserTier,
+    };
+    use std::sync::{Arc, Mutex};
+
+    fn store_user(storage: &Arc<Mutex<InMemoryStorage>>, user_id: &str, workspace: &str) {
+        let user = UserAccount {
+            user_id: user_id.to_string(),
+            username: user_id.to_string(),
+            email: format!("{user_id}@example.com"),
+            password_hash: "hash".to_string(),
+            limits: TierLimits::for_tier(&UserTier::Professional),
+            tier: UserTier::Professional,
+            status: AccountStatus::Active,
+            credits: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_login: None,
+            subscription: None,
+            is_admin: false,
+            workspace_id: Some(workspace.to_string()),
+            available_adapters: None,
+            locale: None,
+        };
+        storage.store_user_account(&user).unwrap();
+    }
+
+    #[test]
+    fn test_csv_export_is_scoped_to_workspace_members() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        store_user(&storage, "ana", "coop-a");
+        store_user(&storage, "otto", "coop-b");
+        for (source, dfid) in [("ana", "DFID-A"), ("otto", "DFID-B")] {
+            let mut event = Event::new(
+                dfid.to_string(),
+                EventType::Created,
+                source.to_string(),
+                EventVisibility::Private,
+            );
+            event
+                .metadata
+                .insert("note".to_string(), serde_json::json!("weighed, \"ok\""));
+            storage.store_event(&event).unwrap();
+        }
+
+        let output_dir =
+            std::env::temp_dir().join(format!("defarm-export-test-{}", Uuid::new_v4()));
+        let engine =
+            DataExportEngine::new(Arc::clone(&storage)).with_output_dir(output_dir.clone());
+        let request = |format| DataExportRequest {
+            dataset: DataExportDataset::Events,
+            format,
+            workspace_id: None,
+            from: None,
+            to: None,
+        };
+
+        assert!(matches!(
+            engine.create_job("ana", request(DataExportFormat::Parquet), Utc::now()),
+            Err(DataExportError::ValidationError(_))
+        ));
+        let mut foreign = request(DataExportFormat::Csv);
+        foreign.workspace_id = Some("coop-b".to_string());
+        assert!(matches!(
+            engine.create_job("ana", foreign, Utc::now()),
+            Err(DataExportError::PermissionDenied(_))
+        ));
+
+        let job = engine
+            .create_job("ana", request(DataExportFormat::Csv), Utc::now())
+            .unwrap();
+        assert_eq!(job.workspace_id, "coop-a");
+        let job = engine.run_job(&job.job_id).unwrap();
+        assert_eq!(job.status, DataExportStatus::Completed);
+        assert_eq!(job.total_rows, Some(1));
+        assert_eq!(job.progress(), 100.0);
+
+        let contents = std::fs::read_to_string(job.output_path.as_ref().unwrap()).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("event_id,dfid,event_type"));
+        assert!(lines[1].contains(",DFID-A,Created,"));
+        assert!(lines[1].contains(",ana,Private,false,"));
+
+        let _ = std::fs::remove_dir_all(output_dir);
+    }
+}
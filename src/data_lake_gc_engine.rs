@@ -0,0 +1,428 @@
+//! Garbage collection of orphaned data lake entries and receipts.
+//!
+//! Data that never became part of an item — failed verifications, abandoned
+//! pending items, entries whose item was deleted — would otherwise stay in the
+//! data lake forever. An entry is an orphan once it is older than the retention
+//! window, has finished processing, and no item or pending item refers to it;
+//! a receipt is an orphan once it is older than the window and no remaining
+//! entry was created from it.
+//!
+//! Orphans are archived to IPFS as one JSON document and then removed, or just
+//! deleted, as the policy says. Every run is recorded in a report with the space
+//! it reclaimed. The scheduled run uses the policy from the environment
+//! (`DATA_LAKE_GC_RETENTION_DAYS`, `DATA_LAKE_GC_ACTION`); admins can run it on
+//! demand, or as a dry run to see what would go.
+
+use crate::ipfs_client::IpfsClient;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{DataLakeEntry, DataLakeGcAction, DataLakeGcReport, ProcessingStatus, Receipt};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub const DEFAULT_RETENTION_DAYS: u32 = 90;
+/// Entries of the last day are never collected, whatever the policy
+pub const MIN_RETENTION_DAYS: u32 = 1;
+
+#[derive(Debug)]
+pub enum DataLakeGcError {
+    StorageError(StorageError),
+    ValidationError(String),
+    ArchiveError(String),
+}
+
+impl From<StorageError> for DataLakeGcError {
+    fn from(err: StorageError) -> Self {
+        DataLakeGcError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for DataLakeGcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataLakeGcError::StorageError(e) => write!(f, "Storage error: {e}"),
+            DataLakeGcError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            DataLakeGcError::ArchiveError(e) => write!(f, "Archive failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for DataLakeGcError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataLakeGcPolicy {
+    pub retention_days: u32,
+    pub action: DataLakeGcAction,
+}
+
+impl Default for DataLakeGcPolicy {
+    fn default() -> Self {
+        Self {
+            retention_days: DEFAULT_RETENTION_DAYS,
+            action: DataLakeGcAction::Archive,
+        }
+    }
+}
+
+impl DataLakeGcPolicy {
+    /// `DATA_LAKE_GC_RETENTION_DAYS` and `DATA_LAKE_GC_ACTION` (`archive` or
+    /// `delete`); unset or invalid values fall back to the defaults
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            retention_days: std::env::var("DATA_LAKE_GC_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.retention_days),
+            action: match std::env::var("DATA_LAKE_GC_ACTION").as_deref() {
+                Ok("delete") => DataLakeGcAction::Delete,
+                _ => default.action,
+            },
+        }
+    }
+
+    fn validate(&self) -> Result<(), DataLakeGcError> {
+        if self.retention_days < MIN_RETENTION_DAYS {
+            return Err(DataLakeGcError::ValidationError(format!(
+                "Retention must be at least {MIN_RETENTION_DAYS} day(s)"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Entries and receipts a run would remove
+#[derive(Debug, Default)]
+pub struct Orphans {
+    pub entries: Vec<DataLakeEntry>,
+    pub receipts: Vec<Receipt>,
+    pub entries_scanned: usize,
+    pub receipts_scanned: usize,
+}
+
+/// Document written to IPFS when orphans are archived
+#[derive(Serialize)]
+struct Archive<'a> {
+    run_id: Uuid,
+    archived_at: DateTime<Utc>,
+    entries: &'a [DataLakeEntry],
+    receipts: &'a [Receipt],
+}
+
+pub struct DataLakeGcEngine<S: StorageBackend> {
+    storage: S,
+    ipfs: Option<IpfsClient>,
+}
+
+impl<S: StorageBackend> DataLakeGcEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            ipfs: None,
+        }
+    }
+
+    /// Where orphans go under the `Archive` action; without it such runs fail
+    /// before anything is removed
+    pub fn with_ipfs(mut self, ipfs: IpfsClient) -> Self {
+        self.ipfs = Some(ipfs);
+        self
+    }
+
+    pub fn find_orphans(
+        &self,
+        retention_days: u32,
+        now: DateTime<Utc>,
+    ) -> Result<Orphans, DataLakeGcError> {
+        let cutoff = now - Duration::days(i64::from(retention_days));
+
+        let items = self.storage.list_items()?;
+        let live_dfids: HashSet<&str> = items.iter().map(|item| item.dfid.as_str()).collect();
+        let mut referenced: HashSet<Uuid> = items
+            .iter()
+            .flat_map(|item| item.source_entries.iter().copied())
+            .collect();
+        referenced.extend(
+            self.storage
+                .list_pending_items()?
+                .iter()
+                .map(|pending| pending.source_entry),
+        );
+
+        let entries = self.storage.list_data_lake_entries()?;
+        let entries_scanned = entries.len();
+        let (orphan_entries, kept_entries): (Vec<DataLakeEntry>, Vec<DataLakeEntry>) =
+            entries.into_iter().partition(|entry| {
+                let in_flight = matches!(
+                    entry.status,
+                    ProcessingStatus::Pending | ProcessingStatus::Processing
+                );
+                let linked = entry
+                    .linked_dfid
+                    .as_deref()
+                    .is_some_and(|dfid| live_dfids.contains(dfid));
+                !in_flight
+                    && entry.timestamp < cutoff
+                    && !linked
+                    && !referenced.contains(&entry.entry_id)
+            });
+
+        let kept_receipts: HashSet<Uuid> = kept_entries.iter().map(|e| e.receipt_id).collect();
+        let receipts = self.storage.list_receipts()?;
+        let receipts_scanned = receipts.len();
+        let orphan_receipts = receipts
+            .into_iter()
+            .filter(|receipt| receipt.timestamp < cutoff && !kept_receipts.contains(&receipt.id))
+            .collect();
+
+        Ok(Orphans {
+            entries: orphan_entries,
+            receipts: orphan_receipts,
+            entries_scanned,
+            receipts_scanned,
+        })
+    }
+
+    /// Collect orphans under `policy`; a dry run removes nothing and is not recorded
+    pub async fn run(
+        &self,
+        policy: DataLakeGcPolicy,
+        dry_run: bool,
+        triggered_by: Option<String>,
+    ) -> Result<DataLakeGcReport, DataLakeGcError> {
+        policy.validate()?;
+        let started_at = Utc::now();
+        let run_id = Uuid::new_v4();
+        let orphans = self.find_orphans(policy.retention_days, started_at)?;
+
+        let reclaimed_bytes = orphans
+            .entries
+            .iter()
+            .map(serialized_size)
+            .chain(orphans.receipts.iter().map(serialized_size))
+            .sum::<Result<u64, _>>()?;
+        let source_data_bytes = orphans.entries.iter().map(|e| e.data_size as u64).sum();
+        let has_orphans = !orphans.entries.is_empty() || !orphans.receipts.is_empty();
+
+        let mut archive_cid = None;
+        if !dry_run && has_orphans {
+            if policy.action == DataLakeGcAction::Archive {
+                archive_cid = Some(self.archive(run_id, &orphans).await?);
+            }
+            let entry_ids: Vec<Uuid> = orphans.entries.iter().map(|e| e.entry_id).collect();
+            let receipt_ids: Vec<Uuid> = orphans.receipts.iter().map(|r| r.id).collect();
+            self.storage.delete_data_lake_entries(&entry_ids)?;
+            self.storage.delete_receipts(&receipt_ids)?;
+        }
+
+        let report = DataLakeGcReport {
+            run_id,
+            started_at,
+            completed_at: Utc::now(),
+            triggered_by,
+            action: policy.action,
+            retention_days: policy.retention_days,
+            dry_run,
+            entries_scanned: orphans.entries_scanned,
+            receipts_scanned: orphans.receipts_scanned,
+            entries_removed: orphans.entries.len(),
+            receipts_removed: orphans.receipts.len(),
+            reclaimed_bytes,
+            source_data_bytes,
+            archive_cid,
+        };
+        if !dry_run {
+            self.storage.store_data_lake_gc_report(&report)?;
+        }
+        Ok(report)
+    }
+
+    /// Reports of past runs, newest first
+    pub fn list_reports(&self) -> Result<Vec<DataLakeGcReport>, DataLakeGcError> {
+        let mut reports = self.storage.list_data_lake_gc_reports()?;
+        reports.sort_by_key(|report| std::cmp::Reverse(report.started_at));
+        Ok(reports)
+    }
+
+    async fn archive(&self, run_id: Uuid, orphans: &Orphans) -> Result<String, DataLakeGcError> {
+        let ipfs = self.ipfs.as_ref().ok_or_else(|| {
+            DataLakeGcError::ArchiveError("No IPFS client configured for archiving".to_string())
+        })?;
+        let archive = Archive {
+            run_id,
+            archived_at: Utc::now(),
+            entries: &orphans.entries,
+            receipts: &orphans.receipts,
+        };
+        let cid = ipfs
+            .upload_json(&archive)
+            .await
+            .map_err(|e| DataLakeGcError::ArchiveError(e.to_string()))?;
+        ipfs.pin(&cid)
+            .await
+            .map_err(|e| DataLakeGcError::ArchiveError(e.to_string()))?;
+        Ok(cid)
+    }
+}
+
+impl<S: StorageBackend + Send + Sync + 'static> DataLakeGcEngine<S> {
+    /// Collect orphans under the environment's policy every `tick`
+    pub fn spawn_collector(storage: S, tick: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut engine = DataLakeGcEngine::new(storage);
+            match IpfsClient::from_env() {
+                Ok(ipfs) => engine = engine.with_ipfs(ipfs),
+                Err(e) => tracing::warn!("⚠️  Data lake GC has no IPFS client: {}", e),
+            }
+            let policy = DataLakeGcPolicy::from_env();
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                match engine.run(policy, false, None).await {
+                    Ok(report) if report.entries_removed + report.receipts_removed > 0 => {
+                        tracing::info!(
+                            "🧹 Data lake GC removed {} entries and {} receipts ({} bytes)",
+                            report.entries_removed,
+                            report.receipts_removed,
+                            report.reclaimed_bytes
+                        )
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("⚠️  Data lake GC failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+fn serialized_size<T: Serialize>(value: &T) -> Result<u64, StorageError> {
+    Ok(serde_json::to_vec(value)?.len() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identifier_types::Identifier;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{Item, PendingItem, PendingReason};
+    use std::sync::{Arc, Mutex};
+
+    fn ingest(
+        storage: &Arc<Mutex<InMemoryStorage>>,
+        status: ProcessingStatus,
+        age_days: i64,
+    ) -> DataLakeEntry {
+        let identifiers = vec![Identifier::contextual("generic", "lot", "L-1")];
+        let receipt = Receipt {
+            id: Uuid::new_v4(),
+            hash: crate::hashing::hash(b"payload"),
+            timestamp: Utc::now() - Duration::days(age_days),
+            data_size: 7,
+            identifiers: identifiers.clone(),
+            priority: Default::default(),
+            occurred_at: None,
+        };
+        let mut entry = DataLakeEntry::new(receipt.id, identifiers, receipt.hash.clone(), 7);
+        entry.timestamp = receipt.timestamp;
+        entry.status = status;
+        storage.store_receipt(&receipt).unwrap();
+        storage.store_data_lake_entry(&entry).unwrap();
+        entry
+    }
+
+    #[tokio::test]
+    async fn test_collects_only_old_unreferenced_entries_and_their_receipts() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+
+        let failed = ingest(&storage, ProcessingStatus::Failed, 120);
+        let recent_failure = ingest(&storage, ProcessingStatus::Failed, 10);
+        let stuck = ingest(&storage, ProcessingStatus::Pending, 120);
+        let used = ingest(&storage, ProcessingStatus::Completed, 120);
+        let abandoned = ingest(&storage, ProcessingStatus::Conflicted, 120);
+        let mut deleted_item = ingest(&storage, ProcessingStatus::Completed, 120);
+        deleted_item.mark_completed("DFID-GONE".to_string());
+        storage.update_data_lake_entry(&deleted_item).unwrap();
+        let awaiting_review = ingest(&storage, ProcessingStatus::Conflicted, 120);
+
+        storage
+            .store_item(&Item::new(
+                "DFID-1".to_string(),
+                vec![Identifier::contextual("generic", "lot", "L-1")],
+                used.entry_id,
+            ))
+            .unwrap();
+        let pending = PendingItem::new(
+            vec![Identifier::contextual("generic", "lot", "L-1")],
+            None,
+            awaiting_review.entry_id,
+            PendingReason::NoIdentifiers,
+            None,
+            None,
+        );
+        storage.store_pending_item(&pending).unwrap();
+
+        let engine = DataLakeGcEngine::new(Arc::clone(&storage));
+        let policy = DataLakeGcPolicy {
+            retention_days: 90,
+            action: DataLakeGcAction::Delete,
+        };
+
+        let dry = engine.run(policy, true, None).await.unwrap();
+        assert_eq!(dry.entries_removed, 3);
+        assert_eq!(dry.receipts_removed, 3);
+        assert!(dry.reclaimed_bytes > 0);
+        assert_eq!(dry.source_data_bytes, 21);
+        assert_eq!(storage.list_data_lake_entries().unwrap().len(), 7);
+        assert!(engine.list_reports().unwrap().is_empty());
+
+        // Archiving without IPFS must fail before anything is removed
+        let archive = DataLakeGcPolicy {
+            action: DataLakeGcAction::Archive,
+            ..policy
+        };
+        assert!(matches!(
+            engine.run(archive, false, None).await,
+            Err(DataLakeGcError::ArchiveError(_))
+        ));
+        assert_eq!(storage.list_receipts().unwrap().len(), 7);
+
+        let report = engine
+            .run(policy, false, Some("admin".to_string()))
+            .await
+            .unwrap();
+        assert_eq!((report.entries_removed, report.receipts_removed), (3, 3));
+        assert_eq!(report.reclaimed_bytes, dry.reclaimed_bytes);
+
+        let remaining: HashSet<Uuid> = storage
+            .list_data_lake_entries()
+            .unwrap()
+            .iter()
+            .map(|e| e.entry_id)
+            .collect();
+        let expected: HashSet<Uuid> = [&recent_failure, &stuck, &used, &awaiting_review]
+            .iter()
+            .map(|e| e.entry_id)
+            .collect();
+        assert_eq!(remaining, expected);
+        for gone in [&failed, &abandoned, &deleted_item] {
+            assert!(storage.get_receipt(&gone.receipt_id).unwrap().is_none());
+        }
+        assert!(storage.get_receipt(&used.receipt_id).unwrap().is_some());
+        assert_eq!(engine.list_reports().unwrap(), vec![report]);
+
+        assert!(matches!(
+            engine
+                .run(
+                    DataLakeGcPolicy {
+                        retention_days: 0,
+                        ..policy
+                    },
+                    true,
+                    None
+                )
+                .await,
+            Err(DataLakeGcError::ValidationError(_))
+        ));
+    }
+}
@@ -0,0 +1,734 @@
+//! Data quality scores for circuits.
+//!
+//! A report measures the circuit's items on three things: identifier
+//! completeness (every identifier the circuit's alias config requires, or at
+//! least one canonical identifier when it requires none), event coverage of
+//! each lifecycle stage the items reached, and the share of incoming data about
+//! the items that failed verification. The measures combine into a 0–100
+//! score, and every gap becomes an issue listing the items to fix.
+//!
+//! Reports are kept so the score can be followed over time. Every circuit with
+//! items gets one a week, and its owner a digest notification; members can
+//! also ask for one on demand.
+
+use crate::i18n::Localizer;
+use crate::identifier_types::IdentifierType;
+use crate::live_stream::{LiveRecord, LiveStream};
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    Circuit, DataLakeEntry, DataQualityIssue, DataQualityIssueKind, DataQualityReport, Item,
+    Notification, NotificationType, Permission, ProcessingStatus, QualitySeverity, StageCoverage,
+};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Days between scheduled reports of a circuit
+pub const DIGEST_INTERVAL_DAYS: i64 = 7;
+/// Reports kept per circuit; older ones are dropped as new ones are stored
+pub const MAX_REPORTS_PER_CIRCUIT: usize = 104;
+/// Items named in each issue
+const MAX_SAMPLE_DFIDS: usize = 20;
+const IDENTIFIER_WEIGHT: f64 = 0.4;
+const EVENT_COVERAGE_WEIGHT: f64 = 0.3;
+const VERIFICATION_WEIGHT: f64 = 0.3;
+
+#[derive(Debug)]
+pub enum DataQualityError {
+    StorageError(StorageError),
+    PermissionDenied(String),
+    NotFound(String),
+}
+
+impl From<StorageError> for DataQualityError {
+    fn from(err: StorageError) -> Self {
+        DataQualityError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for DataQualityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataQualityError::StorageError(e) => write!(f, "Storage error: {e}"),
+            DataQualityError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            DataQualityError::NotFound(e) => write!(f, "Not found: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for DataQualityError {}
+
+/// Identifiers the circuit requires that `item` lacks
+fn missing_identifiers(circuit: &Circuit, item: &Item) -> Vec<String> {
+    let config = circuit.alias_config.as_ref();
+    let required_canonical = config
+        .map(|c| c.required_canonical.as_slice())
+        .unwrap_or(&[]);
+    let required_contextual = config
+        .map(|c| c.required_contextual.as_slice())
+        .unwrap_or(&[]);
+
+    if required_canonical.is_empty() && required_contextual.is_empty() {
+        let has_canonical = item
+            .identifiers
+            .iter()
+            .any(|id| matches!(id.id_type, IdentifierType::Canonical { .. }));
+        return if has_canonical {
+            Vec::new()
+        } else {
+            vec!["canonical".to_string()]
+        };
+    }
+
+    let mut missing = Vec::new();
+    for required in required_canonical {
+        let found = item.identifiers.iter().any(|id| {
+            matches!(&id.id_type, IdentifierType::Canonical { registry, .. } if registry == required)
+        });
+        if !found {
+            missing.push(required.clone());
+        }
+    }
+    for required in required_contextual {
+        let found = item.identifiers.iter().any(|id| {
+            matches!(id.id_type, IdentifierType::Contextual { .. }) && id.key == *required
+        });
+        if !found {
+            missing.push(required.clone());
+        }
+    }
+    missing
+}
+
+/// Severity of a gap affecting `share` of what was measured
+fn severity_for(share: f64) -> QualitySeverity {
+    if share >= 0.5 {
+        QualitySeverity::High
+    } else if share >= 0.2 {
+        QualitySeverity::Medium
+    } else {
+        QualitySeverity::Low
+    }
+}
+
+fn ratio(part: usize, whole: usize) -> Option<f64> {
+    (whole > 0).then(|| part as f64 / whole as f64)
+}
+
+fn sample(dfids: &[String]) -> Vec<String> {
+    dfids.iter().take(MAX_SAMPLE_DFIDS).cloned().collect()
+}
+
+/// Weighted mean of the measures available, on a 0–100 scale
+fn combined_score(
+    identifier_completeness: Option<f64>,
+    event_coverage: Option<f64>,
+    verification_failure_rate: Option<f64>,
+) -> Option<f64> {
+    let measures = [
+        (identifier_completeness, IDENTIFIER_WEIGHT),
+        (event_coverage, EVENT_COVERAGE_WEIGHT),
+        (
+            verification_failure_rate.map(|rate| 1.0 - rate),
+            VERIFICATION_WEIGHT,
+        ),
+    ];
+    let (total, weights) = measures
+        .iter()
+        .filter_map(|(value, weight)| value.map(|v| (v * weight, *weight)))
+        .fold((0.0, 0.0), |(total, weights), (v, w)| {
+            (total + v, weights + w)
+        });
+    (weights > 0.0).then(|| (total / weights * 1000.0).round() / 10.0)
+}
+
+/// Measure `circuit` and list its issues. `data_lake` is the incoming data to
+/// count verification outcomes in; `previous` the last report, for the trend.
+pub fn measure_circuit<S: StorageBackend + ?Sized>(
+    storage: &S,
+    circuit: &Circuit,
+    data_lake: &[DataLakeEntry],
+    previous: Option<&DataQualityReport>,
+    generated_by: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<DataQualityReport, StorageError> {
+    let mut dfids: Vec<String> = storage
+        .get_circuit_items(&circuit.circuit_id)?
+        .into_iter()
+        .map(|item| item.dfid)
+        .collect();
+    dfids.sort();
+    dfids.dedup();
+
+    let mut issues = Vec::new();
+
+    // Identifier completeness
+    let mut incomplete: Vec<String> = Vec::new();
+    let mut missing_keys: Vec<String> = Vec::new();
+    let mut identifier_owner = HashMap::new();
+    for dfid in &dfids {
+        let missing = match storage.get_item_by_dfid(dfid)? {
+            Some(item) => {
+                for identifier in &item.identifiers {
+                    identifier_owner.insert(identifier.clone(), dfid.clone());
+                }
+                missing_identifiers(circuit, &item)
+            }
+            None => vec!["item record".to_string()],
+        };
+        if !missing.is_empty() {
+            incomplete.push(dfid.clone());
+            for key in missing {
+                if !missing_keys.contains(&key) {
+                    missing_keys.push(key);
+                }
+            }
+        }
+    }
+    let identifier_completeness = ratio(dfids.len() - incomplete.len(), dfids.len());
+    if !incomplete.is_empty() {
+        issues.push(DataQualityIssue {
+            kind: DataQualityIssueKind::MissingIdentifiers,
+            severity: severity_for(incomplete.len() as f64 / dfids.len() as f64),
+            message: format!(
+                "{} of {} items lack required identifiers ({})",
+                incomplete.len(),
+                dfids.len(),
+                missing_keys.join(", ")
+            ),
+            affected_items: incomplete.len(),
+            sample_dfids: sample(&incomplete),
+        });
+    }
+
+    // Event coverage per lifecycle stage, across the workspaces items come from
+    let mut definitions = HashMap::new();
+    let mut stage_coverage: Vec<StageCoverage> = Vec::new();
+    let mut uncovered: Vec<(String, Vec<String>)> = Vec::new();
+    for dfid in &dfids {
+        let Some(lifecycle) = storage.get_item_lifecycle(dfid)? else {
+            continue;
+        };
+        let Some(current) = lifecycle.stage.as_deref() else {
+            continue;
+        };
+        if !definitions.contains_key(&lifecycle.workspace_id) {
+            let definition = storage.get_lifecycle_definition(&lifecycle.workspace_id)?;
+            definitions.insert(lifecycle.workspace_id.clone(), definition);
+        }
+        let Some(definition) = definitions[&lifecycle.workspace_id].as_ref() else {
+            continue;
+        };
+        let Some(reached) = definition.stages.iter().position(|s| s.name == current) else {
+            continue;
+        };
+        let recorded: Vec<_> = storage
+            .get_events_by_dfid(dfid)?
+            .into_iter()
+            .map(|event| event.event_type)
+            .collect();
+
+        for stage in &definition.stages[..=reached] {
+            let covered = stage.entered_by.iter().any(|t| recorded.contains(t));
+            let index = match stage_coverage.iter().position(|c| c.stage == stage.name) {
+                Some(index) => index,
+                None => {
+                    stage_coverage.push(StageCoverage {
+                        stage: stage.name.clone(),
+                        items_reached: 0,
+                        items_with_events: 0,
+                    });
+                    uncovered.push((stage.name.clone(), Vec::new()));
+                    stage_coverage.len() - 1
+                }
+            };
+            stage_coverage[index].items_reached += 1;
+            if covered {
+                stage_coverage[index].items_with_events += 1;
+            } else {
+                uncovered[index].1.push(dfid.clone());
+            }
+        }
+    }
+    let event_coverage = ratio(
+        stage_coverage.iter().map(|c| c.items_with_events).sum(),
+        stage_coverage.iter().map(|c| c.items_reached).sum(),
+    );
+    for (coverage, (stage, missing)) in stage_coverage.iter().zip(&uncovered) {
+        if missing.is_empty() {
+            continue;
+        }
+        issues.push(DataQualityIssue {
+            kind: DataQualityIssueKind::MissingStageEvents,
+            severity: severity_for(1.0 - coverage.coverage()),
+            message: format!(
+                "{} of {} items reached '{}' without an event entering it",
+                missing.len(),
+                coverage.items_reached,
+                stage
+            ),
+            affected_items: missing.len(),
+            sample_dfids: sample(missing),
+        });
+    }
+
+    // Verification outcomes of incoming data about the items
+    let circuit_dfids: HashSet<&String> = dfids.iter().collect();
+    let mut verification_attempts = 0;
+    let mut failed_items: Vec<String> = Vec::new();
+    let mut verification_failures = 0;
+    for entry in data_lake {
+        let dfid = entry
+            .linked_dfid
+            .as_ref()
+            .filter(|dfid| circuit_dfids.contains(dfid))
+            .or_else(|| {
+                entry
+                    .identifiers
+                    .iter()
+                    .find_map(|identifier| identifier_owner.get(identifier))
+            });
+        let Some(dfid) = dfid else {
+            continue;
+        };
+        match entry.status {
+            ProcessingStatus::Completed => verification_attempts += 1,
+            ProcessingStatus::Failed | ProcessingStatus::Conflicted => {
+                verification_attempts += 1;
+                verification_failures += 1;
+                if !failed_items.contains(dfid) {
+                    failed_items.push(dfid.clone());
+                }
+            }
+            ProcessingStatus::Pending | ProcessingStatus::Processing => {}
+        }
+    }
+    let verification_failure_rate = ratio(verification_failures, verification_attempts);
+    if let Some(rate) = verification_failure_rate.filter(|rate| *rate > 0.0) {
+        issues.push(DataQualityIssue {
+            kind: DataQualityIssueKind::VerificationFailures,
+            severity: severity_for(rate),
+            message: format!(
+                "{verification_failures} of {verification_attempts} incoming records about {} items failed verification",
+                failed_items.len()
+            ),
+            affected_items: failed_items.len(),
+            sample_dfids: sample(&failed_items),
+        });
+    }
+
+    issues.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then_with(|| b.affected_items.cmp(&a.affected_items))
+    });
+
+    let score = if dfids.is_empty() {
+        None
+    } else {
+        combined_score(
+            identifier_completeness,
+            event_coverage,
+            verification_failure_rate,
+        )
+    };
+    let score_change = score
+        .zip(previous.and_then(|report| report.score))
+        .map(|(score, before)| ((score - before) * 10.0).round() / 10.0);
+
+    Ok(DataQualityReport {
+        report_id: Uuid::new_v4(),
+        circuit_id: circuit.circuit_id,
+        generated_at: now,
+        generated_by: generated_by.map(str::to_string),
+        item_count: dfids.len(),
+        identifier_completeness,
+        stage_coverage,
+        event_coverage,
+        verification_attempts,
+        verification_failures,
+        verification_failure_rate,
+        score,
+        score_change,
+        issues,
+    })
+}
+
+pub struct DataQualityEngine<S: StorageBackend> {
+    storage: S,
+    live_stream: Option<LiveStream>,
+}
+
+impl<S: StorageBackend> DataQualityEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            live_stream: None,
+        }
+    }
+
+    /// Publish digest notifications to `/api/stream` subscribers
+    pub fn with_live_stream(mut self, live_stream: LiveStream) -> Self {
+        self.live_stream = Some(live_stream);
+        self
+    }
+
+    fn member_circuit(
+        &self,
+        circuit_id: &Uuid,
+        user_id: &str,
+    ) -> Result<Circuit, DataQualityError> {
+        let circuit = self
+            .storage
+            .get_circuit(circuit_id)?
+            .ok_or_else(|| DataQualityError::NotFound(format!("Circuit {circuit_id}")))?;
+        if !circuit.is_member(user_id) {
+            return Err(DataQualityError::PermissionDenied(
+                "Only circuit members can see its data quality".to_string(),
+            ));
+        }
+        Ok(circuit)
+    }
+
+    fn store_report(
+        &self,
+        circuit: &Circuit,
+        data_lake: &[DataLakeEntry],
+        generated_by: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<DataQualityReport, DataQualityError> {
+        let reports = self
+            .storage
+            .list_data_quality_reports(&circuit.circuit_id)?;
+        let report = measure_circuit(
+            &self.storage,
+            circuit,
+            data_lake,
+            reports.first(),
+            generated_by,
+            now,
+        )?;
+        self.storage.store_data_quality_report(&report)?;
+
+        // `reports` is newest first and does not hold the new one yet
+        if reports.len() >= MAX_REPORTS_PER_CIRCUIT {
+            for old in &reports[MAX_REPORTS_PER_CIRCUIT - 1..] {
+                self.storage.delete_data_quality_report(&old.report_id)?;
+            }
+        }
+        Ok(report)
+    }
+
+    /// Measure the circuit now; for the owner, auditors and member managers
+    pub fn generate_report(
+        &self,
+        circuit_id: &Uuid,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<DataQualityReport, DataQualityError> {
+        let circuit = self.member_circuit(circuit_id, user_id)?;
+        if circuit.owner_id != user_id
+            && !circuit.has_permission(user_id, &Permission::Audit)
+            && !circuit.has_permission(user_id, &Permission::ManageMembers)
+        {
+            return Err(DataQualityError::PermissionDenied(
+                "Only the owner, auditors and member managers can run a data quality report"
+                    .to_string(),
+            ));
+        }
+        let data_lake = self.storage.list_data_lake_entries()?;
+        self.store_report(&circuit, &data_lake, Some(user_id), now)
+    }
+
+    /// The circuit's reports, newest first
+    pub fn history(
+        &self,
+        circuit_id: &Uuid,
+        user_id: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<DataQualityReport>, DataQualityError> {
+        self.member_circuit(circuit_id, user_id)?;
+        let mut reports = self.storage.list_data_quality_reports(circuit_id)?;
+        if let Some(limit) = limit {
+            reports.truncate(limit);
+        }
+        Ok(reports)
+    }
+
+    pub fn latest(
+        &self,
+        circuit_id: &Uuid,
+        user_id: &str,
+    ) -> Result<DataQualityReport, DataQualityError> {
+        self.history(circuit_id, user_id, Some(1))?
+            .pop()
+            .ok_or_else(|| {
+                DataQualityError::NotFound(format!("Data quality report of circuit {circuit_id}"))
+            })
+    }
+
+    /// Report on every active circuit with items whose last report is a week
+    /// old, and send its owner the digest
+    pub fn run_due(&self, now: DateTime<Utc>) -> Result<Vec<DataQualityReport>, DataQualityError> {
+        let mut due = Vec::new();
+        for circuit in self.storage.list_circuits()? {
+            if circuit.is_archived() {
+                continue;
+            }
+            let last_run = self
+                .storage
+                .list_data_quality_reports(&circuit.circuit_id)?
+                .into_iter()
+                .find(|report| report.generated_by.is_none())
+                .map(|report| report.generated_at);
+            if last_run.is_some_and(|at| now - at < Duration::days(DIGEST_INTERVAL_DAYS)) {
+                continue;
+            }
+            if self
+                .storage
+                .get_circuit_items(&circuit.circuit_id)?
+                .is_empty()
+            {
+                continue;
+            }
+            due.push(circuit);
+        }
+        if due.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let data_lake = self.storage.list_data_lake_entries()?;
+        let mut reports = Vec::new();
+        for circuit in due {
+            let report = self.store_report(&circuit, &data_lake, None, now)?;
+            if let Err(e) = self.send_digest(&circuit, &report) {
+                tracing::warn!(
+                    "Failed to send data quality digest of {}: {}",
+                    circuit.name,
+                    e
+                );
+            }
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+
+    fn send_digest(
+        &self,
+        circuit: &Circuit,
+        report: &DataQualityReport,
+    ) -> Result<(), DataQualityError> {
+        let locale = self
+            .storage
+            .get_user_account(&circuit.owner_id)?
+            .and_then(|user| user.locale);
+        let score = report.score.map_or("-".to_string(), |s| format!("{s:.1}"));
+        let issue_count = report.issues.len().to_string();
+        let args = [
+            ("circuit_name", circuit.name.as_str()),
+            ("score", score.as_str()),
+            ("issue_count", issue_count.as_str()),
+        ];
+        let localizer = Localizer::global();
+        let notification = Notification::new(
+            circuit.owner_id.clone(),
+            NotificationType::DataQualityDigest,
+            localizer.translate(
+                locale.as_deref(),
+                "notification.data_quality_digest.title",
+                &args,
+            ),
+            localizer.translate(
+                locale.as_deref(),
+                "notification.data_quality_digest.message",
+                &args,
+            ),
+            serde_json::json!({
+                "circuit_id": circuit.circuit_id,
+                "report_id": report.report_id,
+                "score": report.score,
+                "score_change": report.score_change,
+                "issues": report.issues,
+            }),
+        );
+        self.storage.store_notification(&notification)?;
+        if let Some(live_stream) = &self.live_stream {
+            live_stream.publish(LiveRecord::Notification(notification));
+        }
+        Ok(())
+    }
+
+    pub fn spawn_scheduler(
+        storage: S,
+        live_stream: LiveStream,
+        tick: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        S: Send + Sync + 'static,
+    {
+        tokio::spawn(async move {
+            let engine = DataQualityEngine::new(storage).with_live_stream(live_stream);
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                match engine.run_due(Utc::now()) {
+                    Ok(reports) if !reports.is_empty() => {
+                        tracing::info!("📊 Sent {} data quality digests", reports.len())
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("⚠️  Failed to run data quality reports: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identifier_types::{CircuitAliasConfig, Identifier};
+    use crate::storage::InMemoryStorage;
+    use crate::types::{
+        CircuitItem, Event, EventType, EventVisibility, ItemLifecycle, LifecycleDefinition,
+        LifecycleStage, MemberRole,
+    };
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_weekly_report_scores_circuit_and_lists_issues() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let mut circuit = Circuit::new(
+            "Cattle".to_string(),
+            "Traceability".to_string(),
+            "owner".to_string(),
+        );
+        circuit.add_member("farmer".to_string(), MemberRole::Member);
+        circuit.alias_config = Some(CircuitAliasConfig {
+            required_canonical: vec!["sisbov".to_string()],
+            ..Default::default()
+        });
+        storage.store_circuit(&circuit).unwrap();
+        storage
+            .store_lifecycle_definition(&LifecycleDefinition {
+                workspace_id: "ws".to_string(),
+                stages: vec![
+                    LifecycleStage {
+                        name: "born".to_string(),
+                        entered_by: vec![EventType::Created],
+                    },
+                    LifecycleStage {
+                        name: "sold".to_string(),
+                        entered_by: vec![EventType::StatusChanged],
+                    },
+                ],
+                allow_skipping: true,
+                updated_by: "owner".to_string(),
+                updated_at: Utc::now(),
+            })
+            .unwrap();
+
+        let tagged = Identifier::canonical("bovino", "sisbov", "BR123");
+        for (dfid, identifiers) in [
+            ("DFID-1", vec![tagged.clone()]),
+            ("DFID-2", vec![Identifier::new("lote", "7")]),
+        ] {
+            storage
+                .store_item(&Item::new(dfid.to_string(), identifiers, Uuid::new_v4()))
+                .unwrap();
+            storage
+                .store_circuit_item(&CircuitItem::new(
+                    dfid.to_string(),
+                    circuit.circuit_id,
+                    "farmer".to_string(),
+                    vec![],
+                ))
+                .unwrap();
+            storage
+                .store_event(&Event::new(
+                    dfid.to_string(),
+                    EventType::Created,
+                    "farmer".to_string(),
+                    EventVisibility::CircuitOnly,
+                ))
+                .unwrap();
+            storage
+                .store_item_lifecycle(&ItemLifecycle {
+                    dfid: dfid.to_string(),
+                    workspace_id: "ws".to_string(),
+                    stage: Some("sold".to_string()),
+                    stage_entered_at: None,
+                    transitions: vec![],
+                })
+                .unwrap();
+        }
+        storage
+            .store_event(&Event::new(
+                "DFID-1".to_string(),
+                EventType::StatusChanged,
+                "farmer".to_string(),
+                EventVisibility::CircuitOnly,
+            ))
+            .unwrap();
+        let mut failed = DataLakeEntry::new(Uuid::new_v4(), vec![tagged], "h".to_string(), 1);
+        failed.mark_failed("checksum mismatch".to_string());
+        storage.store_data_lake_entry(&failed).unwrap();
+
+        let engine = DataQualityEngine::new(Arc::clone(&storage));
+        let now = Utc::now();
+        let reports = engine.run_due(now).unwrap();
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+        assert_eq!(report.identifier_completeness, Some(0.5));
+        assert_eq!(report.event_coverage, Some(0.75));
+        assert_eq!(report.verification_failure_rate, Some(1.0));
+        // 0.4 * 0.5 + 0.3 * 0.75 + 0.3 * 0.0
+        assert_eq!(report.score, Some(42.5));
+        assert_eq!(report.issues.len(), 3);
+        let failures = report
+            .issues
+            .iter()
+            .find(|issue| issue.kind == DataQualityIssueKind::VerificationFailures)
+            .unwrap();
+        assert_eq!(failures.sample_dfids, ["DFID-1"]);
+        let sold = &report.stage_coverage[1];
+        assert_eq!((sold.items_reached, sold.items_with_events), (2, 1));
+
+        // The owner gets the digest, and the next one is a week away
+        let digests = storage
+            .get_user_notifications("owner", None, None, false)
+            .unwrap();
+        assert_eq!(digests.len(), 1);
+        assert!(matches!(
+            digests[0].notification_type,
+            NotificationType::DataQualityDigest
+        ));
+        assert!(engine.run_due(now + Duration::days(1)).unwrap().is_empty());
+
+        // On demand reports follow the trend; outsiders see nothing
+        let fixed = Item::new(
+            "DFID-2".to_string(),
+            vec![Identifier::canonical("bovino", "sisbov", "BR456")],
+            Uuid::new_v4(),
+        );
+        storage.store_item(&fixed).unwrap();
+        let report = engine
+            .generate_report(&circuit.circuit_id, "owner", now)
+            .unwrap();
+        assert_eq!(report.score_change, Some(20.0));
+        assert!(matches!(
+            engine.generate_report(&circuit.circuit_id, "farmer", now),
+            Err(DataQualityError::PermissionDenied(_))
+        ));
+        assert_eq!(
+            engine
+                .history(&circuit.circuit_id, "farmer", None)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(matches!(
+            engine.latest(&circuit.circuit_id, "outsider"),
+            Err(DataQualityError::PermissionDenied(_))
+        ));
+    }
+}
@@ -44,6 +44,7 @@ pub fn initialize_default_admin(
         is_admin: true,
         workspace_id: Some("hen-workspace".to_string()),
         available_adapters: None, // Use tier defaults
+        locale: None,
     };
 
     // Store the admin user
@@ -101,6 +102,7 @@ pub fn initialize_sample_users(
             is_admin: false,
             workspace_id: Some("pullet-workspace".to_string()),
             available_adapters: None, // Use tier defaults
+            locale: None,
         },
         // Add cock user (matches auth.rs)
         UserAccount {
@@ -119,6 +121,7 @@ pub fn initialize_sample_users(
             is_admin: false,
             workspace_id: Some("cock-workspace".to_string()),
             available_adapters: None, // Use tier defaults
+            locale: None,
         },
         UserAccount {
             user_id: "basic-farmer-001".to_string(),
@@ -136,6 +139,7 @@ pub fn initialize_sample_users(
             is_admin: false,
             workspace_id: Some("basic-workspace".to_string()),
             available_adapters: None, // Use tier defaults
+            locale: None,
         },
         UserAccount {
             user_id: "pro-farmer-001".to_string(),
@@ -153,6 +157,7 @@ pub fn initialize_sample_users(
             is_admin: false,
             workspace_id: Some("pro-workspace".to_string()),
             available_adapters: None, // Use tier defaults
+            locale: None,
         },
         UserAccount {
             user_id: "enterprise-farmer-001".to_string(),
@@ -170,6 +175,7 @@ pub fn initialize_sample_users(
             is_admin: false,
             workspace_id: Some("enterprise-workspace".to_string()),
             available_adapters: None, // Use tier defaults
+            locale: None,
         },
     ];
 
@@ -269,9 +275,8 @@ pub fn initialize_production_adapters(
     {
         // Get testnet configuration from environment
         let testnet_secret = std::env::var("STELLAR_TESTNET_SECRET").ok();
-        let interface_address = std::env::var("DEFARM_OWNER_WALLET").unwrap_or_else(|_| {
-            "STELLAR_WALLET_PLACEHOLDER".to_string()
-        });
+        let interface_address = std::env::var("DEFARM_OWNER_WALLET")
+            .unwrap_or_else(|_| "STELLAR_WALLET_PLACEHOLDER".to_string());
 
         let mut custom_headers = HashMap::new();
         if let Some(secret_key) = testnet_secret {
@@ -328,9 +333,8 @@ pub fn initialize_production_adapters(
     if let (Some(api_key), Some(secret), Some(contract_addr), Some(mainnet_key)) =
         (pinata_api_key, pinata_secret, mainnet_ipcm, mainnet_secret)
     {
-        let interface_address = std::env::var("DEFARM_OWNER_WALLET").unwrap_or_else(|_| {
-            "STELLAR_WALLET_PLACEHOLDER".to_string()
-        });
+        let interface_address = std::env::var("DEFARM_OWNER_WALLET")
+            .unwrap_or_else(|_| "STELLAR_WALLET_PLACEHOLDER".to_string());
 
         let mut custom_headers = HashMap::new();
         custom_headers.insert("stellar_secret".to_string(), mainnet_key);
@@ -0,0 +1,401 @@
+//! Cross-circuit deduplication.
+//!
+//! Fingerprint mappings are kept per circuit, so the same physical batch
+//! pushed to two circuits becomes two items. Two circuits may opt in to
+//! sharing a dedup namespace: one proposes an agreement, the other consents,
+//! and while it is active fingerprints of both are looked up and stored under
+//! the agreement's id. Either side may revoke it; each circuit then goes back
+//! to its own namespace and items already resolved stay as they are.
+//!
+//! Consent for a circuit is given by its owner, a member who may manage its
+//! permissions, or a platform admin. Every step is in the audit log as a
+//! `dedup_sharing.*` event.
+
+use crate::audit_engine::{AuditEngine, AuditError};
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    AuditEventType, AuditOutcome, AuditSeverity, Circuit, DedupSharingAgreement,
+    DedupSharingConsent, DedupSharingStatus, Permission,
+};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub enum DedupSharingError {
+    StorageError(StorageError),
+    AuditError(AuditError),
+    ValidationError(String),
+    PermissionDenied(String),
+    NotFound(String),
+    Conflict(String),
+}
+
+impl From<StorageError> for DedupSharingError {
+    fn from(err: StorageError) -> Self {
+        DedupSharingError::StorageError(err)
+    }
+}
+
+impl From<AuditError> for DedupSharingError {
+    fn from(err: AuditError) -> Self {
+        DedupSharingError::AuditError(err)
+    }
+}
+
+impl std::fmt::Display for DedupSharingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DedupSharingError::StorageError(e) => write!(f, "Storage error: {e}"),
+            DedupSharingError::AuditError(e) => write!(f, "Audit log error: {e}"),
+            DedupSharingError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            DedupSharingError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            DedupSharingError::NotFound(e) => write!(f, "Not found: {e}"),
+            DedupSharingError::Conflict(e) => write!(f, "Conflict: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for DedupSharingError {}
+
+/// The circuit's active agreement, if it has one
+pub fn active_agreement<S: StorageBackend>(
+    storage: &S,
+    circuit_id: &Uuid,
+) -> Result<Option<DedupSharingAgreement>, StorageError> {
+    Ok(storage
+        .list_dedup_sharing_agreements(circuit_id)?
+        .into_iter()
+        .find(|agreement| agreement.status == DedupSharingStatus::Active))
+}
+
+/// Namespace the circuit's fingerprints live in: the shared one while an
+/// agreement is active, else the circuit's own
+pub fn shared_dedup_scope<S: StorageBackend>(
+    storage: &S,
+    circuit_id: &Uuid,
+) -> Result<Uuid, StorageError> {
+    Ok(active_agreement(storage, circuit_id)?
+        .map(|agreement| agreement.agreement_id)
+        .unwrap_or(*circuit_id))
+}
+
+pub struct DedupSharingEngine<S: StorageBackend> {
+    storage: S,
+    audit: AuditEngine<S>,
+}
+
+impl<S: StorageBackend + Clone + 'static> DedupSharingEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            audit: AuditEngine::new(storage.clone()),
+            storage,
+        }
+    }
+
+    /// Agreements the circuit is part of, newest first; members may look
+    pub fn list(
+        &self,
+        user_id: &str,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<DedupSharingAgreement>, DedupSharingError> {
+        let circuit = self.circuit(circuit_id)?;
+        if !circuit.is_member(user_id) && !self.is_admin(user_id)? {
+            return Err(DedupSharingError::PermissionDenied(
+                "Only circuit members can see its dedup sharing agreements".to_string(),
+            ));
+        }
+        Ok(self.storage.list_dedup_sharing_agreements(circuit_id)?)
+    }
+
+    /// Propose sharing `circuit_id`'s dedup namespace with `partner_circuit_id`.
+    /// Proposing counts as the proposing circuit's consent.
+    pub fn propose(
+        &self,
+        user_id: &str,
+        circuit_id: &Uuid,
+        partner_circuit_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<DedupSharingAgreement, DedupSharingError> {
+        if circuit_id == partner_circuit_id {
+            return Err(DedupSharingError::ValidationError(
+                "A circuit cannot share its dedup namespace with itself".to_string(),
+            ));
+        }
+        let circuit = self.circuit(circuit_id)?;
+        self.check_consent_authority(user_id, &circuit)?;
+        self.circuit(partner_circuit_id)?;
+        self.check_not_sharing(circuit_id)?;
+        self.check_not_sharing(partner_circuit_id)?;
+        let pending = self
+            .storage
+            .list_dedup_sharing_agreements(circuit_id)?
+            .into_iter()
+            .any(|a| a.status == DedupSharingStatus::Proposed && a.involves(partner_circuit_id));
+        if pending {
+            return Err(DedupSharingError::Conflict(
+                "An agreement between these circuits is already waiting for consent".to_string(),
+            ));
+        }
+
+        let agreement = DedupSharingAgreement {
+            agreement_id: Uuid::new_v4(),
+            circuits: [*circuit_id, *partner_circuit_id],
+            status: DedupSharingStatus::Proposed,
+            proposed_by: user_id.to_string(),
+            proposed_at: now,
+            consents: vec![DedupSharingConsent {
+                circuit_id: *circuit_id,
+                granted_by: user_id.to_string(),
+                granted_at: now,
+            }],
+            activated_at: None,
+            revoked_by: None,
+            revoked_at: None,
+        };
+        self.storage.store_dedup_sharing_agreement(&agreement)?;
+        self.audit_agreement(user_id, "dedup_sharing.propose", &agreement, circuit_id)?;
+        Ok(agreement)
+    }
+
+    /// Consent on behalf of the circuit still missing from the agreement; it
+    /// becomes active once both circuits have consented
+    pub fn accept(
+        &self,
+        user_id: &str,
+        agreement_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<DedupSharingAgreement, DedupSharingError> {
+        let mut agreement = self.agreement(agreement_id)?;
+        if agreement.status != DedupSharingStatus::Proposed {
+            return Err(DedupSharingError::Conflict(format!(
+                "Agreement {agreement_id} is not waiting for consent"
+            )));
+        }
+        let awaiting = agreement
+            .circuits
+            .into_iter()
+            .find(|circuit_id| !agreement.has_consent_of(circuit_id))
+            .ok_or_else(|| {
+                DedupSharingError::Conflict(format!(
+                    "Agreement {agreement_id} already has both consents"
+                ))
+            })?;
+        let circuit = self.circuit(&awaiting)?;
+        self.check_consent_authority(user_id, &circuit)?;
+        for circuit_id in agreement.circuits {
+            self.check_not_sharing(&circuit_id)?;
+        }
+
+        agreement.consents.push(DedupSharingConsent {
+            circuit_id: awaiting,
+            granted_by: user_id.to_string(),
+            granted_at: now,
+        });
+        agreement.status = DedupSharingStatus::Active;
+        agreement.activated_at = Some(now);
+        self.storage.store_dedup_sharing_agreement(&agreement)?;
+        self.audit_agreement(user_id, "dedup_sharing.accept", &agreement, &awaiting)?;
+        Ok(agreement)
+    }
+
+    /// End an active agreement, or decline a proposal, for either circuit
+    pub fn revoke(
+        &self,
+        user_id: &str,
+        agreement_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<DedupSharingAgreement, DedupSharingError> {
+        let mut agreement = self.agreement(agreement_id)?;
+        if agreement.status == DedupSharingStatus::Revoked {
+            return Err(DedupSharingError::Conflict(format!(
+                "Agreement {agreement_id} is already revoked"
+            )));
+        }
+        let mut revoking_for = None;
+        for circuit_id in agreement.circuits {
+            let circuit = self.circuit(&circuit_id)?;
+            if self.check_consent_authority(user_id, &circuit).is_ok() {
+                revoking_for = Some(circuit_id);
+                break;
+            }
+        }
+        let revoking_for = revoking_for.ok_or_else(|| {
+            DedupSharingError::PermissionDenied(
+                "Only managers of either circuit can revoke the agreement".to_string(),
+            )
+        })?;
+
+        agreement.status = DedupSharingStatus::Revoked;
+        agreement.revoked_by = Some(user_id.to_string());
+        agreement.revoked_at = Some(now);
+        self.storage.store_dedup_sharing_agreement(&agreement)?;
+        self.audit_agreement(user_id, "dedup_sharing.revoke", &agreement, &revoking_for)?;
+        Ok(agreement)
+    }
+
+    fn circuit(&self, circuit_id: &Uuid) -> Result<Circuit, DedupSharingError> {
+        self.storage
+            .get_circuit(circuit_id)?
+            .ok_or_else(|| DedupSharingError::NotFound(format!("Circuit {circuit_id}")))
+    }
+
+    fn agreement(&self, agreement_id: &Uuid) -> Result<DedupSharingAgreement, DedupSharingError> {
+        self.storage
+            .get_dedup_sharing_agreement(agreement_id)?
+            .ok_or_else(|| DedupSharingError::NotFound(format!("Agreement {agreement_id}")))
+    }
+
+    fn is_admin(&self, user_id: &str) -> Result<bool, DedupSharingError> {
+        Ok(self
+            .storage
+            .get_user_account(user_id)?
+            .is_some_and(|account| account.is_admin))
+    }
+
+    fn check_consent_authority(
+        &self,
+        user_id: &str,
+        circuit: &Circuit,
+    ) -> Result<(), DedupSharingError> {
+        if circuit.owner_id == user_id
+            || circuit.has_permission(user_id, &Permission::ManagePermissions)
+            || self.is_admin(user_id)?
+        {
+            Ok(())
+        } else {
+            Err(DedupSharingError::PermissionDenied(format!(
+                "Not allowed to consent for circuit {}",
+                circuit.circuit_id
+            )))
+        }
+    }
+
+    /// A circuit shares its namespace with at most one other circuit at a time
+    fn check_not_sharing(&self, circuit_id: &Uuid) -> Result<(), DedupSharingError> {
+        match active_agreement(&self.storage, circuit_id)? {
+            Some(agreement) => Err(DedupSharingError::Conflict(format!(
+                "Circuit {circuit_id} already shares its dedup namespace (agreement {})",
+                agreement.agreement_id
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn audit_agreement(
+        &self,
+        user_id: &str,
+        action: &str,
+        agreement: &DedupSharingAgreement,
+        circuit_id: &Uuid,
+    ) -> Result<Uuid, DedupSharingError> {
+        let details = HashMap::from([
+            ("circuit_id".to_string(), json!(circuit_id)),
+            ("circuits".to_string(), json!(agreement.circuits)),
+            ("status".to_string(), json!(agreement.status)),
+        ]);
+        Ok(self.audit.log_event(
+            user_id.to_string(),
+            AuditEventType::Data,
+            action.to_string(),
+            format!("dedup_sharing:{}", agreement.agreement_id),
+            AuditOutcome::Success,
+            AuditSeverity::Medium,
+            Some(details),
+            None,
+            None,
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dedup_strategy::{identifier_fingerprint, strategy_for};
+    use crate::identifier_types::DedupStrategyKind;
+    use crate::storage::InMemoryStorage;
+    use crate::types::Identifier;
+    use std::sync::{Arc, Mutex};
+
+    fn store_circuit(storage: &Arc<Mutex<InMemoryStorage>>, owner: &str) -> Uuid {
+        let circuit = Circuit::new(
+            format!("{owner}'s circuit"),
+            "Supplier".to_string(),
+            owner.to_string(),
+        );
+        storage.store_circuit(&circuit).unwrap();
+        circuit.circuit_id
+    }
+
+    #[test]
+    fn test_consenting_circuits_share_a_dedup_namespace() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let supplier_a = store_circuit(&storage, "alice");
+        let supplier_b = store_circuit(&storage, "bruno");
+        let engine = DedupSharingEngine::new(Arc::clone(&storage));
+        let now = Utc::now();
+
+        let agreement = engine
+            .propose("alice", &supplier_a, &supplier_b, now)
+            .unwrap();
+        assert_eq!(agreement.status, DedupSharingStatus::Proposed);
+        // Not shared until the partner consents, and only its managers may
+        assert_eq!(
+            shared_dedup_scope(&storage, &supplier_a).unwrap(),
+            supplier_a
+        );
+        assert!(matches!(
+            engine.accept("alice", &agreement.agreement_id, now),
+            Err(DedupSharingError::PermissionDenied(_))
+        ));
+
+        let agreement = engine
+            .accept("bruno", &agreement.agreement_id, now)
+            .unwrap();
+        assert_eq!(agreement.status, DedupSharingStatus::Active);
+        let scope = shared_dedup_scope(&storage, &supplier_a).unwrap();
+        assert_eq!(scope, agreement.agreement_id);
+        assert_eq!(shared_dedup_scope(&storage, &supplier_b).unwrap(), scope);
+
+        // A batch fingerprinted through one circuit is found through the other
+        let batch = vec![Identifier::contextual("cafe", "lote", "B-2024-117")];
+        storage
+            .store_fingerprint_mapping(&identifier_fingerprint(&batch), "DFID-BATCH", &scope)
+            .unwrap();
+        let candidates = strategy_for::<Arc<Mutex<InMemoryStorage>>>(
+            DedupStrategyKind::Fingerprint,
+            Some(shared_dedup_scope(&storage, &supplier_b).unwrap()),
+        )
+        .candidates(&storage, &batch)
+        .unwrap();
+        assert_eq!(candidates[0].dfid, "DFID-BATCH");
+
+        let third = store_circuit(&storage, "carla");
+        assert!(matches!(
+            engine.propose("carla", &third, &supplier_a, now),
+            Err(DedupSharingError::Conflict(_))
+        ));
+
+        engine
+            .revoke("bruno", &agreement.agreement_id, now)
+            .unwrap();
+        assert_eq!(
+            shared_dedup_scope(&storage, &supplier_b).unwrap(),
+            supplier_b
+        );
+        let actions: Vec<String> = storage
+            .list_audit_events()
+            .unwrap()
+            .into_iter()
+            .map(|event| event.action)
+            .collect();
+        for action in [
+            "dedup_sharing.propose",
+            "dedup_sharing.accept",
+            "dedup_sharing.revoke",
+        ] {
+            assert!(actions.iter().any(|a| a == action), "missing {action}");
+        }
+    }
+}
@@ -0,0 +1,496 @@
+//! Pluggable deduplication strategies for verification.
+//!
+//! A strategy decides which existing items the identifiers of an incoming
+//! entry may belong to. Verification creates an item when there is no
+//! candidate, enriches the one candidate, and opens a conflict when several
+//! remain. A circuit's alias config may pick the strategy for its items; other
+//! entries use their workspace's choice, and without one any identifier mapped
+//! to an item matches it, as before strategies existed.
+
+use crate::identifier_types::DedupStrategyKind;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    AutoLinkPolicy, FuzzyMatchConfig, Identifier, MappingStatus, WorkspaceDedupConfig,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Composite score of a matched canonical identifier
+pub const CANONICAL_MATCH_WEIGHT: f64 = 1.0;
+/// Composite score of a matched contextual identifier; one alone is too weak to match
+pub const CONTEXTUAL_MATCH_WEIGHT: f64 = 0.4;
+/// Composite score of an item with the entry's exact identifier set
+pub const FINGERPRINT_MATCH_WEIGHT: f64 = 1.0;
+/// Candidates scoring less are treated as unrelated
+pub const MIN_COMPOSITE_SCORE: f64 = 0.75;
+/// A candidate leading the runner-up by this much wins without a conflict
+pub const DECISIVE_SCORE_MARGIN: f64 = 1.0;
+
+#[derive(Debug)]
+pub enum DedupConfigError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+}
+
+impl From<StorageError> for DedupConfigError {
+    fn from(err: StorageError) -> Self {
+        DedupConfigError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for DedupConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DedupConfigError::StorageError(e) => write!(f, "Storage error: {e}"),
+            DedupConfigError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            DedupConfigError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for DedupConfigError {}
+
+/// An item an entry may belong to
+#[derive(Debug, Clone, PartialEq)]
+pub struct DedupCandidate {
+    pub dfid: String,
+    /// The entry's identifiers that led to the item
+    pub matched_identifiers: Vec<Identifier>,
+    pub score: f64,
+}
+
+/// Decides which existing items an entry's identifiers match
+pub trait DedupStrategy<S: StorageBackend>: Send + Sync {
+    fn kind(&self) -> DedupStrategyKind;
+
+    /// Items the identifiers may belong to, best first
+    fn candidates(
+        &self,
+        storage: &S,
+        identifiers: &[Identifier],
+    ) -> Result<Vec<DedupCandidate>, StorageError>;
+
+    /// Called once an entry is linked to an item, for strategies keeping an
+    /// index of their own
+    fn record_item(
+        &self,
+        _storage: &S,
+        _dfid: &str,
+        _identifiers: &[Identifier],
+    ) -> Result<(), StorageError> {
+        Ok(())
+    }
+}
+
+/// Any identifier with an active mapping matches its item
+pub struct ExactIdentifierStrategy;
+
+/// Canonical identifiers (mappings and the canonical index) decide; contextual
+/// ones are only consulted when no canonical identifier matches, so a reused
+/// lot number cannot pull an animal into another item
+pub struct CanonicalFirstStrategy;
+
+/// Matches the item last seen with the entry's exact identifier set
+pub struct FingerprintStrategy {
+    scope: Uuid,
+}
+
+/// Scores every candidate from its canonical, contextual and fingerprint
+/// matches, drops weak ones and settles clear winners without a conflict
+pub struct CompositeScoringStrategy {
+    scope: Uuid,
+}
+
+impl FingerprintStrategy {
+    /// Fingerprints are kept per circuit; entries outside one share the nil scope
+    pub fn new(circuit_id: Option<Uuid>) -> Self {
+        Self {
+            scope: circuit_id.unwrap_or_else(Uuid::nil),
+        }
+    }
+}
+
+impl CompositeScoringStrategy {
+    pub fn new(circuit_id: Option<Uuid>) -> Self {
+        Self {
+            scope: circuit_id.unwrap_or_else(Uuid::nil),
+        }
+    }
+}
+
+impl<S: StorageBackend> DedupStrategy<S> for ExactIdentifierStrategy {
+    fn kind(&self) -> DedupStrategyKind {
+        DedupStrategyKind::ExactIdentifier
+    }
+
+    fn candidates(
+        &self,
+        storage: &S,
+        identifiers: &[Identifier],
+    ) -> Result<Vec<DedupCandidate>, StorageError> {
+        let matches = active_matches(storage, identifiers.iter())?;
+        Ok(ranked(matches, |matched| matched.len() as f64))
+    }
+}
+
+impl<S: StorageBackend> DedupStrategy<S> for CanonicalFirstStrategy {
+    fn kind(&self) -> DedupStrategyKind {
+        DedupStrategyKind::CanonicalFirst
+    }
+
+    fn candidates(
+        &self,
+        storage: &S,
+        identifiers: &[Identifier],
+    ) -> Result<Vec<DedupCandidate>, StorageError> {
+        let (canonical, contextual): (Vec<&Identifier>, Vec<&Identifier>) =
+            identifiers.iter().partition(|id| id.is_canonical());
+
+        let mut matches = active_matches(storage, canonical.iter().copied())?;
+        for identifier in &canonical {
+            let Some(registry) = identifier.get_registry() else {
+                continue;
+            };
+            if let Some(dfid) = storage.get_dfid_by_canonical(
+                &identifier.namespace,
+                &registry,
+                &identifier.value,
+            )? {
+                let matched = matches.entry(dfid).or_default();
+                if !matched.contains(identifier) {
+                    matched.push((*identifier).clone());
+                }
+            }
+        }
+        if matches.is_empty() {
+            matches = active_matches(storage, contextual)?;
+        }
+        Ok(ranked(matches, |matched| matched.len() as f64))
+    }
+}
+
+impl<S: StorageBackend> DedupStrategy<S> for FingerprintStrategy {
+    fn kind(&self) -> DedupStrategyKind {
+        DedupStrategyKind::Fingerprint
+    }
+
+    fn candidates(
+        &self,
+        storage: &S,
+        identifiers: &[Identifier],
+    ) -> Result<Vec<DedupCandidate>, StorageError> {
+        let fingerprint = identifier_fingerprint(identifiers);
+        Ok(storage
+            .get_dfid_by_fingerprint(&fingerprint, &self.scope)?
+            .map(|dfid| DedupCandidate {
+                dfid,
+                matched_identifiers: identifiers.to_vec(),
+                score: FINGERPRINT_MATCH_WEIGHT,
+            })
+            .into_iter()
+            .collect())
+    }
+
+    fn record_item(
+        &self,
+        storage: &S,
+        dfid: &str,
+        identifiers: &[Identifier],
+    ) -> Result<(), StorageError> {
+        storage.store_fingerprint_mapping(&identifier_fingerprint(identifiers), dfid, &self.scope)
+    }
+}
+
+impl<S: StorageBackend> DedupStrategy<S> for CompositeScoringStrategy {
+    fn kind(&self) -> DedupStrategyKind {
+        DedupStrategyKind::CompositeScoring
+    }
+
+    fn candidates(
+        &self,
+        storage: &S,
+        identifiers: &[Identifier],
+    ) -> Result<Vec<DedupCandidate>, StorageError> {
+        let mut matches = active_matches(storage, identifiers.iter())?;
+        let fingerprint_dfid =
+            storage.get_dfid_by_fingerprint(&identifier_fingerprint(identifiers), &self.scope)?;
+        if let Some(dfid) = &fingerprint_dfid {
+            matches.entry(dfid.clone()).or_default();
+        }
+
+        let mut candidates: Vec<DedupCandidate> = ranked(matches, |_| 0.0)
+            .into_iter()
+            .map(|mut candidate| {
+                candidate.score = candidate
+                    .matched_identifiers
+                    .iter()
+                    .map(|id| {
+                        if id.is_canonical() {
+                            CANONICAL_MATCH_WEIGHT
+                        } else {
+                            CONTEXTUAL_MATCH_WEIGHT
+                        }
+                    })
+                    .sum();
+                if fingerprint_dfid.as_deref() == Some(candidate.dfid.as_str()) {
+                    candidate.score += FINGERPRINT_MATCH_WEIGHT;
+                }
+                candidate
+            })
+            .filter(|candidate| candidate.score >= MIN_COMPOSITE_SCORE)
+            .collect();
+        sort_candidates(&mut candidates);
+
+        if candidates.len() > 1
+            && candidates[0].score - candidates[1].score >= DECISIVE_SCORE_MARGIN
+        {
+            candidates.truncate(1);
+        }
+        Ok(candidates)
+    }
+
+    fn record_item(
+        &self,
+        storage: &S,
+        dfid: &str,
+        identifiers: &[Identifier],
+    ) -> Result<(), StorageError> {
+        storage.store_fingerprint_mapping(&identifier_fingerprint(identifiers), dfid, &self.scope)
+    }
+}
+
+/// The strategy of a kind; fingerprints are scoped to `circuit_id`
+pub fn strategy_for<S: StorageBackend>(
+    kind: DedupStrategyKind,
+    circuit_id: Option<Uuid>,
+) -> Box<dyn DedupStrategy<S>> {
+    match kind {
+        DedupStrategyKind::ExactIdentifier => Box::new(ExactIdentifierStrategy),
+        DedupStrategyKind::CanonicalFirst => Box::new(CanonicalFirstStrategy),
+        DedupStrategyKind::Fingerprint => Box::new(FingerprintStrategy::new(circuit_id)),
+        DedupStrategyKind::CompositeScoring => Box::new(CompositeScoringStrategy::new(circuit_id)),
+    }
+}
+
+/// The circuit's override if it has one, else the workspace's choice, else
+/// exact-identifier matching
+pub fn resolve_strategy_kind<S: StorageBackend>(
+    storage: &S,
+    workspace_id: Option<&str>,
+    circuit_id: Option<&Uuid>,
+) -> Result<DedupStrategyKind, StorageError> {
+    if let Some(circuit_id) = circuit_id {
+        let circuit_kind = storage
+            .get_circuit(circuit_id)?
+            .and_then(|circuit| circuit.alias_config)
+            .and_then(|config| config.dedup_strategy);
+        if let Some(kind) = circuit_kind {
+            return Ok(kind);
+        }
+    }
+    Ok(match workspace_id {
+        Some(workspace_id) => storage
+            .get_workspace_dedup_config(workspace_id)?
+            .map(|config| config.strategy)
+            .unwrap_or_default(),
+        None => DedupStrategyKind::default(),
+    })
+}
+
+/// Order-independent hash of an identifier set
+pub fn identifier_fingerprint(identifiers: &[Identifier]) -> String {
+    let mut keys: Vec<String> = identifiers.iter().map(|id| id.unique_key()).collect();
+    keys.sort();
+    keys.dedup();
+    blake3::hash(format!("ids:{}", keys.join("|")).as_bytes())
+        .to_hex()
+        .to_string()
+}
+
+/// Items holding an active mapping of any of the identifiers, with the
+/// identifiers mapped to each
+fn active_matches<'a, S: StorageBackend>(
+    storage: &S,
+    identifiers: impl IntoIterator<Item = &'a Identifier>,
+) -> Result<HashMap<String, Vec<Identifier>>, StorageError> {
+    let mut matches: HashMap<String, Vec<Identifier>> = HashMap::new();
+    for identifier in identifiers {
+        for mapping in storage.get_identifier_mappings(identifier)? {
+            if matches!(mapping.status, MappingStatus::Active) {
+                matches
+                    .entry(mapping.dfid)
+                    .or_default()
+                    .push(identifier.clone());
+            }
+        }
+    }
+    Ok(matches)
+}
+
+fn ranked(
+    matches: HashMap<String, Vec<Identifier>>,
+    score: impl Fn(&[Identifier]) -> f64,
+) -> Vec<DedupCandidate> {
+    let mut candidates: Vec<DedupCandidate> = matches
+        .into_iter()
+        .map(|(dfid, matched_identifiers)| DedupCandidate {
+            score: score(&matched_identifiers),
+            dfid,
+            matched_identifiers,
+        })
+        .collect();
+    sort_candidates(&mut candidates);
+    candidates
+}
+
+/// Best score first, ties by DFID so results are stable
+fn sort_candidates(candidates: &mut [DedupCandidate]) {
+    candidates.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.dfid.cmp(&b.dfid))
+    });
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DedupConfigInput {
+    pub strategy: DedupStrategyKind,
+    /// Near-duplicate detection for entries no item matches exactly; off if omitted
+    #[serde(default)]
+    pub fuzzy_matching: Option<FuzzyMatchConfig>,
+    /// Confidence needed to link without review; every match links if omitted
+    #[serde(default)]
+    pub auto_link: Option<AutoLinkPolicy>,
+}
+
+/// Reads and sets workspaces' dedup strategies
+pub struct DedupConfigEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> DedupConfigEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// The workspace's config, or exact-identifier matching if it never set one
+    pub fn get_config(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+    ) -> Result<WorkspaceDedupConfig, DedupConfigError> {
+        self.check_member(user_id, workspace_id)?;
+        Ok(self
+            .storage
+            .get_workspace_dedup_config(workspace_id)?
+            .unwrap_or_else(|| WorkspaceDedupConfig {
+                workspace_id: workspace_id.to_string(),
+                strategy: DedupStrategyKind::default(),
+                fuzzy_matching: None,
+                auto_link: None,
+                updated_by: "system".to_string(),
+                updated_at: DateTime::<Utc>::UNIX_EPOCH,
+            }))
+    }
+
+    pub fn set_config(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+        input: DedupConfigInput,
+        now: DateTime<Utc>,
+    ) -> Result<WorkspaceDedupConfig, DedupConfigError> {
+        self.check_member(user_id, workspace_id)?;
+        if let Some(fuzzy) = &input.fuzzy_matching {
+            if !(fuzzy.min_confidence > 0.0 && fuzzy.min_confidence <= 1.0) {
+                return Err(DedupConfigError::ValidationError(
+                    "fuzzy_matching.min_confidence must be in (0, 1]".to_string(),
+                ));
+            }
+        }
+        if let Some(policy) = &input.auto_link {
+            if !(policy.min_confidence > 0.0 && policy.min_confidence <= 1.0) {
+                return Err(DedupConfigError::ValidationError(
+                    "auto_link.min_confidence must be in (0, 1]".to_string(),
+                ));
+            }
+        }
+        let config = WorkspaceDedupConfig {
+            workspace_id: workspace_id.to_string(),
+            strategy: input.strategy,
+            fuzzy_matching: input.fuzzy_matching,
+            auto_link: input.auto_link,
+            updated_by: user_id.to_string(),
+            updated_at: now,
+        };
+        self.storage.store_workspace_dedup_config(&config)?;
+        Ok(config)
+    }
+
+    /// Members manage their own workspace's strategy; admins any workspace's
+    fn check_member(&self, user_id: &str, workspace_id: &str) -> Result<(), DedupConfigError> {
+        let account = self
+            .storage
+            .get_user_account(user_id)?
+            .ok_or_else(|| DedupConfigError::PermissionDenied(format!("Unknown user {user_id}")))?;
+        if account.is_admin || account.workspace_id.as_deref() == Some(workspace_id) {
+            Ok(())
+        } else {
+            Err(DedupConfigError::PermissionDenied(
+                "Only workspace members can manage its dedup strategy".to_string(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::IdentifierMapping;
+
+    #[test]
+    fn test_composite_scoring_settles_clear_winner() {
+        let storage = InMemoryStorage::new();
+        let sisbov = Identifier::canonical("bovino", "sisbov", "BR123");
+        let lot = Identifier::contextual("bovino", "lote", "L-7");
+        for (identifier, dfid) in [(&sisbov, "DFID-A"), (&lot, "DFID-A"), (&lot, "DFID-B")] {
+            storage
+                .store_identifier_mapping(&IdentifierMapping::new(
+                    identifier.clone(),
+                    dfid.to_string(),
+                    "primary".to_string(),
+                ))
+                .unwrap();
+        }
+        let identifiers = vec![sisbov, lot.clone()];
+
+        // Exact matching sees two items; composite scoring sees one clear winner
+        let exact = ExactIdentifierStrategy
+            .candidates(&storage, &identifiers)
+            .unwrap();
+        assert_eq!(exact.len(), 2);
+
+        let composite = CompositeScoringStrategy::new(None);
+        let candidates = composite.candidates(&storage, &identifiers).unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].dfid, "DFID-A");
+        assert!((candidates[0].score - 1.4).abs() < f64::EPSILON);
+
+        // A lone contextual match is too weak until the fingerprint backs it
+        let lot_only = vec![lot];
+        assert!(composite
+            .candidates(&storage, &lot_only)
+            .unwrap()
+            .is_empty());
+        composite
+            .record_item(&storage, "DFID-B", &lot_only)
+            .unwrap();
+        let candidates = composite.candidates(&storage, &lot_only).unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].dfid, "DFID-B");
+    }
+}
@@ -0,0 +1,526 @@
+//! Encrypted backups of field devices' local databases.
+//!
+//! Mobile and edge clients work offline and keep unsynced data in a local
+//! database. So that a lost or broken device does not lose that data, a client
+//! encrypts its database with a key only it (and its user) holds and uploads
+//! the result here. The server treats the blob as opaque: it streams it to
+//! `DEVICE_BACKUP_DIR`, numbers it as the device's next version, and hands it
+//! back on restore. Each device keeps its last `DEVICE_BACKUP_KEEP_VERSIONS`
+//! backups; older ones are pruned on upload. A user's backups together may not
+//! exceed `DEVICE_BACKUP_QUOTA_BYTES`.
+//!
+//! Backups belong to the user who uploaded them; a device is identified by the
+//! id the client generated for it at install time.
+
+use crate::hashing::HashAlgorithm;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::DeviceBackup;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+/// Largest blob accepted, 256 MiB
+pub const MAX_BACKUP_BYTES: usize = 256 * 1024 * 1024;
+pub const DEFAULT_KEEP_VERSIONS: usize = 5;
+/// Default total size of one user's backups, 2 GiB
+pub const DEFAULT_QUOTA_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+const MAX_DEVICE_ID_LEN: usize = 128;
+const MAX_ENCRYPTION_LEN: usize = 64;
+/// Header of an unencrypted SQLite database, which must not be uploaded as is
+const SQLITE_HEADER: &[u8] = b"SQLite format 3\0";
+
+#[derive(Debug)]
+pub enum DeviceBackupError {
+    StorageError(StorageError),
+    ValidationError(String),
+    NotFound(String),
+    /// The upload would take the user past their backup quota
+    QuotaExceeded(String),
+    /// `DEVICE_BACKUP_DIR` is missing or unsuitable
+    NotConfigured(String),
+    IoError(String),
+}
+
+impl From<StorageError> for DeviceBackupError {
+    fn from(err: StorageError) -> Self {
+        DeviceBackupError::StorageError(err)
+    }
+}
+
+impl From<std::io::Error> for DeviceBackupError {
+    fn from(err: std::io::Error) -> Self {
+        DeviceBackupError::IoError(err.to_string())
+    }
+}
+
+impl std::fmt::Display for DeviceBackupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeviceBackupError::StorageError(e) => write!(f, "Storage error: {e}"),
+            DeviceBackupError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            DeviceBackupError::NotFound(e) => write!(f, "Not found: {e}"),
+            DeviceBackupError::QuotaExceeded(e) => write!(f, "Quota exceeded: {e}"),
+            DeviceBackupError::NotConfigured(e) => write!(f, "Not configured: {e}"),
+            DeviceBackupError::IoError(e) => write!(f, "I/O error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for DeviceBackupError {}
+
+/// Directory backup blobs are written to, from `DEVICE_BACKUP_DIR`. Backups
+/// are the only copy of a lost device's data, so it must be durable storage
+/// every replica mounts; there is no default and the server does not start
+/// without it.
+pub fn configured_backup_dir() -> Result<PathBuf, DeviceBackupError> {
+    backup_dir_from(std::env::var("DEVICE_BACKUP_DIR").ok())
+}
+
+fn backup_dir_from(value: Option<String>) -> Result<PathBuf, DeviceBackupError> {
+    let dir = value
+        .filter(|dir| !dir.trim().is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| {
+            DeviceBackupError::NotConfigured("DEVICE_BACKUP_DIR is not set".to_string())
+        })?;
+    if !dir.is_absolute() || dir.starts_with(std::env::temp_dir()) {
+        return Err(DeviceBackupError::NotConfigured(format!(
+            "DEVICE_BACKUP_DIR must be an absolute path outside the temp directory, got {}",
+            dir.display()
+        )));
+    }
+    Ok(dir)
+}
+
+/// Total bytes of backups a user may hold, from `DEVICE_BACKUP_QUOTA_BYTES`
+pub fn default_quota_bytes() -> u64 {
+    std::env::var("DEVICE_BACKUP_QUOTA_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|quota| *quota > 0)
+        .unwrap_or(DEFAULT_QUOTA_BYTES)
+}
+
+/// Versions kept per device, from `DEVICE_BACKUP_KEEP_VERSIONS`
+pub fn default_keep_versions() -> usize {
+    std::env::var("DEVICE_BACKUP_KEEP_VERSIONS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|keep| *keep > 0)
+        .unwrap_or(DEFAULT_KEEP_VERSIONS)
+}
+
+fn validate_device_id(device_id: &str) -> Result<(), DeviceBackupError> {
+    let valid = !device_id.is_empty()
+        && device_id.len() <= MAX_DEVICE_ID_LEN
+        && device_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'));
+    if valid {
+        Ok(())
+    } else {
+        Err(DeviceBackupError::ValidationError(format!(
+            "Device ids are 1-{MAX_DEVICE_ID_LEN} letters, digits, '-', '_', '.' or ':'"
+        )))
+    }
+}
+
+pub struct DeviceBackupEngine<S: StorageBackend> {
+    storage: S,
+    backup_dir: PathBuf,
+    keep_versions: usize,
+    quota_bytes: u64,
+}
+
+impl<S: StorageBackend> DeviceBackupEngine<S> {
+    pub fn new(storage: S, backup_dir: PathBuf) -> Self {
+        Self {
+            storage,
+            backup_dir,
+            keep_versions: default_keep_versions(),
+            quota_bytes: default_quota_bytes(),
+        }
+    }
+
+    pub fn with_quota_bytes(mut self, quota_bytes: u64) -> Self {
+        self.quota_bytes = quota_bytes;
+        self
+    }
+
+    pub fn with_keep_versions(mut self, keep_versions: usize) -> Self {
+        self.keep_versions = keep_versions.max(1);
+        self
+    }
+
+    /// Stream `body` to disk as the device's next backup version.
+    /// `expected_hash`, the BLAKE3 hex the client computed, catches uploads
+    /// truncated on the way.
+    pub async fn upload<B, C, E>(
+        &self,
+        user_id: &str,
+        device_id: &str,
+        encryption: &str,
+        expected_hash: Option<&str>,
+        body: B,
+        now: DateTime<Utc>,
+    ) -> Result<DeviceBackup, DeviceBackupError>
+    where
+        B: Stream<Item = Result<C, E>> + Unpin,
+        C: AsRef<[u8]>,
+        E: std::fmt::Display,
+    {
+        validate_device_id(device_id)?;
+        let encryption = encryption.trim().to_ascii_lowercase();
+        if encryption.is_empty() || encryption.len() > MAX_ENCRYPTION_LEN {
+            return Err(DeviceBackupError::ValidationError(
+                "Name the cipher the backup is encrypted with".to_string(),
+            ));
+        }
+
+        // Versions this upload prunes do not count against the quota
+        let existing = self.device_backups(user_id, device_id)?;
+        let pruned: Vec<&DeviceBackup> = existing
+            .iter()
+            .skip(self.keep_versions.saturating_sub(1))
+            .collect();
+        let kept_bytes: u64 = self
+            .storage
+            .list_device_backups(user_id)?
+            .iter()
+            .filter(|backup| !pruned.iter().any(|p| p.backup_id == backup.backup_id))
+            .map(|backup| backup.size_bytes)
+            .sum();
+        let quota_left = self.quota_bytes.saturating_sub(kept_bytes);
+
+        let version = existing.first().map_or(1, |latest| latest.version + 1);
+        let backup_id = Uuid::new_v4();
+        tokio::fs::create_dir_all(&self.backup_dir).await?;
+        let path = self.backup_dir.join(format!("{backup_id}.bin"));
+        let partial = self.backup_dir.join(format!("{backup_id}.part"));
+        let written = match write_blob(&partial, body, quota_left).await {
+            Ok((size_bytes, content_hash)) => match expected_hash {
+                Some(expected) if !expected.eq_ignore_ascii_case(&content_hash) => {
+                    Err(DeviceBackupError::ValidationError(format!(
+                        "Content hash mismatch: received {content_hash}, expected {expected}"
+                    )))
+                }
+                _ => Ok((size_bytes, content_hash)),
+            },
+            Err(e) => Err(e),
+        };
+        let (size_bytes, content_hash) = match written {
+            Ok(written) => written,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&partial).await;
+                return Err(e);
+            }
+        };
+        tokio::fs::rename(&partial, &path).await?;
+
+        let backup = DeviceBackup {
+            backup_id,
+            user_id: user_id.to_string(),
+            device_id: device_id.to_string(),
+            version,
+            size_bytes,
+            content_hash,
+            encryption,
+            blob_path: path.to_string_lossy().into_owned(),
+            created_at: now,
+        };
+        if let Err(e) = self.storage.store_device_backup(&backup) {
+            let _ = tokio::fs::remove_file(&path).await;
+            return Err(e.into());
+        }
+
+        for old in pruned {
+            if let Err(e) = self.remove(old) {
+                tracing::warn!("⚠️  Could not prune device backup {}: {}", old.backup_id, e);
+            }
+        }
+        Ok(backup)
+    }
+
+    /// The user's backups, newest version first per device
+    pub fn list(
+        &self,
+        user_id: &str,
+        device_id: Option<&str>,
+    ) -> Result<Vec<DeviceBackup>, DeviceBackupError> {
+        let mut backups = self.storage.list_device_backups(user_id)?;
+        if let Some(device_id) = device_id {
+            backups.retain(|backup| backup.device_id == device_id);
+        }
+        backups.sort_by(|a, b| {
+            a.device_id
+                .cmp(&b.device_id)
+                .then(b.version.cmp(&a.version))
+        });
+        Ok(backups)
+    }
+
+    /// One version of a device's backup, or its latest with `None`
+    pub fn get(
+        &self,
+        user_id: &str,
+        device_id: &str,
+        version: Option<u32>,
+    ) -> Result<DeviceBackup, DeviceBackupError> {
+        self.device_backups(user_id, device_id)?
+            .into_iter()
+            .find(|backup| version.is_none_or(|version| backup.version == version))
+            .ok_or_else(|| {
+                DeviceBackupError::NotFound(match version {
+                    Some(version) => format!("Backup version {version} of device {device_id}"),
+                    None => format!("Backups of device {device_id}"),
+                })
+            })
+    }
+
+    pub fn delete(
+        &self,
+        user_id: &str,
+        device_id: &str,
+        version: u32,
+    ) -> Result<DeviceBackup, DeviceBackupError> {
+        let backup = self.get(user_id, device_id, Some(version))?;
+        self.remove(&backup)?;
+        Ok(backup)
+    }
+
+    fn device_backups(
+        &self,
+        user_id: &str,
+        device_id: &str,
+    ) -> Result<Vec<DeviceBackup>, DeviceBackupError> {
+        self.list(user_id, Some(device_id))
+    }
+
+    /// Drops the record first, so a failed file removal leaves no dangling entry
+    fn remove(&self, backup: &DeviceBackup) -> Result<(), DeviceBackupError> {
+        self.storage.delete_device_backup(&backup.backup_id)?;
+        match std::fs::remove_file(&backup.blob_path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Write the body to `path` a chunk at a time, returning its size and BLAKE3
+/// hash. Stops once it passes `MAX_BACKUP_BYTES` or `quota_left`.
+async fn write_blob<B, C, E>(
+    path: &Path,
+    mut body: B,
+    quota_left: u64,
+) -> Result<(u64, String), DeviceBackupError>
+where
+    B: Stream<Item = Result<C, E>> + Unpin,
+    C: AsRef<[u8]>,
+    E: std::fmt::Display,
+{
+    let mut file = tokio::fs::File::create(path).await?;
+    let mut hasher = HashAlgorithm::Blake3.hasher();
+    let mut size: u64 = 0;
+    let mut head = Vec::with_capacity(SQLITE_HEADER.len());
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(|e| {
+            DeviceBackupError::ValidationError(format!("Upload was interrupted: {e}"))
+        })?;
+        let chunk = chunk.as_ref();
+        size += chunk.len() as u64;
+        if size > MAX_BACKUP_BYTES as u64 {
+            return Err(DeviceBackupError::ValidationError(format!(
+                "Backups are 1 to {MAX_BACKUP_BYTES} bytes"
+            )));
+        }
+        if size > quota_left {
+            return Err(DeviceBackupError::QuotaExceeded(format!(
+                "Only {quota_left} bytes of backup space are left"
+            )));
+        }
+        if head.len() < SQLITE_HEADER.len() {
+            let take = (SQLITE_HEADER.len() - head.len()).min(chunk.len());
+            head.extend_from_slice(&chunk[..take]);
+            if head == SQLITE_HEADER {
+                return Err(DeviceBackupError::ValidationError(
+                    "This is an unencrypted SQLite database; encrypt it before uploading"
+                        .to_string(),
+                ));
+            }
+        }
+        hasher.update(chunk);
+        file.write_all(chunk).await?;
+    }
+    if size == 0 {
+        return Err(DeviceBackupError::ValidationError(format!(
+            "Backups are 1 to {MAX_BACKUP_BYTES} bytes"
+        )));
+    }
+    file.sync_all().await?;
+    Ok((size, hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use std::sync::{Arc, Mutex};
+
+    fn body(data: &[u8]) -> impl Stream<Item = Result<Vec<u8>, std::io::Error>> + Unpin {
+        // Two chunks, so limits and header checks see a split body
+        let (first, rest) = data.split_at(data.len() / 2);
+        futures::stream::iter(vec![Ok(first.to_vec()), Ok(rest.to_vec())])
+    }
+
+    fn temp_backup_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("device-backups-{}", Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn test_backups_are_versioned_pruned_and_restored() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let backup_dir = temp_backup_dir();
+        let engine =
+            DeviceBackupEngine::new(Arc::clone(&storage), backup_dir.clone()).with_keep_versions(2);
+        let now = Utc::now();
+
+        for round in 1..=3u8 {
+            let data = vec![round; 64];
+            let backup = engine
+                .upload("farmer", "phone-1", "AES-256-GCM", None, body(&data), now)
+                .await
+                .unwrap();
+            assert_eq!(backup.version, u32::from(round));
+            assert_eq!(backup.encryption, "aes-256-gcm");
+        }
+
+        // Only the last two versions are kept, and their blobs come back intact
+        let backups = engine.list("farmer", Some("phone-1")).unwrap();
+        assert_eq!(
+            backups.iter().map(|b| b.version).collect::<Vec<_>>(),
+            [3, 2]
+        );
+        let latest = engine.get("farmer", "phone-1", None).unwrap();
+        assert_eq!(std::fs::read(&latest.blob_path).unwrap(), vec![3u8; 64]);
+        assert!(matches!(
+            engine.get("farmer", "phone-1", Some(1)),
+            Err(DeviceBackupError::NotFound(_))
+        ));
+        assert!(engine.list("someone-else", None).unwrap().is_empty());
+
+        // Integrity and encryption checks
+        assert!(matches!(
+            engine
+                .upload(
+                    "farmer",
+                    "phone-1",
+                    "aes-256-gcm",
+                    Some("00"),
+                    body(b"blob"),
+                    now
+                )
+                .await,
+            Err(DeviceBackupError::ValidationError(_))
+        ));
+        assert!(matches!(
+            engine
+                .upload(
+                    "farmer",
+                    "phone-1",
+                    "none",
+                    None,
+                    body(b"SQLite format 3\0..."),
+                    now
+                )
+                .await,
+            Err(DeviceBackupError::ValidationError(_))
+        ));
+        assert!(matches!(
+            engine
+                .upload("farmer", "../etc", "aes-256-gcm", None, body(b"blob"), now)
+                .await,
+            Err(DeviceBackupError::ValidationError(_))
+        ));
+
+        let deleted = engine.delete("farmer", "phone-1", 2).unwrap();
+        assert!(!std::path::Path::new(&deleted.blob_path).exists());
+        assert_eq!(engine.list("farmer", None).unwrap().len(), 1);
+
+        let _ = std::fs::remove_dir_all(backup_dir);
+    }
+
+    #[tokio::test]
+    async fn test_uploads_stop_at_the_user_quota() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let backup_dir = temp_backup_dir();
+        let engine = DeviceBackupEngine::new(Arc::clone(&storage), backup_dir.clone())
+            .with_keep_versions(1)
+            .with_quota_bytes(100);
+        let now = Utc::now();
+
+        engine
+            .upload(
+                "farmer",
+                "phone-1",
+                "aes-256-gcm",
+                None,
+                body(&[1; 60]),
+                now,
+            )
+            .await
+            .unwrap();
+        // A second device's backup counts against the same user's quota
+        assert!(matches!(
+            engine
+                .upload(
+                    "farmer",
+                    "tablet-1",
+                    "aes-256-gcm",
+                    None,
+                    body(&[2; 60]),
+                    now
+                )
+                .await,
+            Err(DeviceBackupError::QuotaExceeded(_))
+        ));
+        // Replacing the device's only kept version frees its space first
+        engine
+            .upload(
+                "farmer",
+                "phone-1",
+                "aes-256-gcm",
+                None,
+                body(&[3; 90]),
+                now,
+            )
+            .await
+            .unwrap();
+        // Rejected uploads leave nothing behind on disk
+        assert_eq!(std::fs::read_dir(&backup_dir).unwrap().count(), 1);
+
+        let _ = std::fs::remove_dir_all(backup_dir);
+    }
+
+    #[test]
+    fn test_backup_dir_must_be_configured_and_durable() {
+        assert!(matches!(
+            backup_dir_from(None),
+            Err(DeviceBackupError::NotConfigured(_))
+        ));
+        let temp = std::env::temp_dir().join("defarm-device-backups");
+        assert!(matches!(
+            backup_dir_from(Some(temp.to_string_lossy().into_owned())),
+            Err(DeviceBackupError::NotConfigured(_))
+        ));
+        assert!(matches!(
+            backup_dir_from(Some("backups".to_string())),
+            Err(DeviceBackupError::NotConfigured(_))
+        ));
+        assert_eq!(
+            backup_dir_from(Some("/var/lib/defarm/backups".to_string())).unwrap(),
+            PathBuf::from("/var/lib/defarm/backups")
+        );
+    }
+}
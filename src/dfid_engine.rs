@@ -1,27 +1,121 @@
+//! DFIDs are `DFID-<YYYYMMDD>-<daily sequence>-<checksum>`.
+//!
+//! A standalone engine counts on its own, which is only safe for a single
+//! generator. Replicas and regions use coordinated engines: each leases
+//! ranges of the day's sequence from storage, the primary database every
+//! region writes to, and hands out numbers from its range, so no two
+//! generators ever issue the same DFID. Clones share their lease.
+
+use crate::storage::{StorageBackend, StorageError};
 use chrono::{DateTime, Utc};
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+
+/// Sequence numbers a coordinated engine leases at a time; unused ones are
+/// skipped when the process stops
+pub const DEFAULT_SEQUENCE_LEASE_SIZE: u64 = 50;
+/// Largest daily sequence the six-digit format holds
+pub const MAX_DAILY_SEQUENCE: u64 = 999_999;
+
+/// Hands out disjoint ranges of a day's DFID sequence
+pub trait DfidSequenceCoordinator: Send + Sync {
+    /// Reserves `count` numbers of `day`'s sequence and returns the first
+    fn lease_sequence_range(&self, day: &str, count: u64) -> Result<u64, StorageError>;
+}
+
+impl<S: StorageBackend> DfidSequenceCoordinator for S {
+    fn lease_sequence_range(&self, day: &str, count: u64) -> Result<u64, StorageError> {
+        self.lease_dfid_sequence_range(day, count)
+    }
+}
+
+/// The part of a leased range not yet handed out
+struct SequenceLease {
+    day: String,
+    next: u64,
+    end: u64,
+}
 
+#[derive(Clone)]
 pub struct DfidEngine {
     sequence_counter: Arc<AtomicU64>,
+    coordinator: Option<Arc<dyn DfidSequenceCoordinator>>,
+    lease: Arc<Mutex<Option<SequenceLease>>>,
+    lease_size: u64,
 }
 
 impl DfidEngine {
     pub fn new() -> Self {
         Self {
             sequence_counter: Arc::new(AtomicU64::new(1)),
+            coordinator: None,
+            lease: Arc::new(Mutex::new(None)),
+            lease_size: DEFAULT_SEQUENCE_LEASE_SIZE,
         }
     }
 
-    pub fn generate_dfid(&self) -> String {
-        let timestamp = Utc::now();
-        let sequence = self.sequence_counter.fetch_add(1, Ordering::SeqCst);
+    /// Numbers come from ranges leased through `storage`, per day
+    pub fn coordinated<S: StorageBackend + 'static>(storage: S) -> Self {
+        Self {
+            coordinator: Some(Arc::new(storage)),
+            ..Self::new()
+        }
+    }
+
+    pub fn with_lease_size(mut self, lease_size: u64) -> Self {
+        self.lease_size = lease_size.max(1);
+        self
+    }
+
+    pub fn is_coordinated(&self) -> bool {
+        self.coordinator.is_some()
+    }
+
+    pub fn generate_dfid(&self) -> Result<String, StorageError> {
+        let timestamp_str = Utc::now().format("%Y%m%d").to_string();
+        let sequence = match &self.coordinator {
+            Some(coordinator) => self.next_leased(coordinator.as_ref(), &timestamp_str)?,
+            None => self.sequence_counter.fetch_add(1, Ordering::SeqCst),
+        };
 
-        let timestamp_str = timestamp.format("%Y%m%d").to_string();
         let sequence_str = format!("{sequence:06}");
         let checksum = self.calculate_checksum(&timestamp_str, &sequence_str);
 
-        format!("DFID-{timestamp_str}-{sequence_str}-{checksum}")
+        Ok(format!("DFID-{timestamp_str}-{sequence_str}-{checksum}"))
+    }
+
+    /// The next number of the current lease, leasing a new range when it runs
+    /// out or the day changes
+    fn next_leased(
+        &self,
+        coordinator: &dyn DfidSequenceCoordinator,
+        day: &str,
+    ) -> Result<u64, StorageError> {
+        let mut lease = self.lease.lock().unwrap();
+        let current = lease
+            .as_mut()
+            .filter(|lease| lease.day == day && lease.next < lease.end);
+        let sequence = match current {
+            Some(lease) => {
+                lease.next += 1;
+                lease.next - 1
+            }
+            None => {
+                let first = coordinator.lease_sequence_range(day, self.lease_size)?;
+                *lease = Some(SequenceLease {
+                    day: day.to_string(),
+                    next: first + 1,
+                    end: first + self.lease_size,
+                });
+                first
+            }
+        };
+        if sequence > MAX_DAILY_SEQUENCE {
+            return Err(StorageError::WriteError(format!(
+                "DFID sequence for {day} exhausted"
+            )));
+        }
+        Ok(sequence)
     }
 
     pub fn validate_dfid(&self, dfid: &str) -> bool {
@@ -142,7 +236,7 @@ mod tests {
     #[test]
     fn test_dfid_generation() {
         let engine = DfidEngine::new();
-        let dfid = engine.generate_dfid();
+        let dfid = engine.generate_dfid().unwrap();
 
         assert!(dfid.starts_with("DFID-"));
         assert!(engine.validate_dfid(&dfid));
@@ -151,7 +245,7 @@ mod tests {
     #[test]
     fn test_dfid_format() {
         let engine = DfidEngine::new();
-        let dfid = engine.generate_dfid();
+        let dfid = engine.generate_dfid().unwrap();
 
         let parts: Vec<&str> = dfid.split('-').collect();
         assert_eq!(parts.len(), 4);
@@ -177,8 +271,8 @@ mod tests {
     fn test_sequential_dfids() {
         let engine = DfidEngine::new();
 
-        let dfid1 = engine.generate_dfid();
-        let dfid2 = engine.generate_dfid();
+        let dfid1 = engine.generate_dfid().unwrap();
+        let dfid2 = engine.generate_dfid().unwrap();
 
         assert_ne!(dfid1, dfid2);
         assert!(engine.validate_dfid(&dfid1));
@@ -193,7 +287,7 @@ mod tests {
     #[test]
     fn test_metadata_extraction() {
         let engine = DfidEngine::new();
-        let dfid = engine.generate_dfid();
+        let dfid = engine.generate_dfid().unwrap();
 
         let metadata = engine.extract_metadata(&dfid).unwrap();
         assert_eq!(metadata.full_dfid, dfid);
@@ -206,7 +300,7 @@ mod tests {
     #[test]
     fn test_metadata_creation_date() {
         let engine = DfidEngine::new();
-        let dfid = engine.generate_dfid();
+        let dfid = engine.generate_dfid().unwrap();
 
         let metadata = engine.extract_metadata(&dfid).unwrap();
         let creation_date = metadata.creation_date().unwrap();
@@ -219,14 +313,47 @@ mod tests {
     fn test_sequence_reset() {
         let engine = DfidEngine::new();
 
-        engine.generate_dfid();
-        engine.generate_dfid();
+        engine.generate_dfid().unwrap();
+        engine.generate_dfid().unwrap();
         assert!(engine.get_current_sequence() > 2);
 
         engine.reset_sequence();
         assert_eq!(engine.get_current_sequence(), 1);
     }
 
+    #[test]
+    fn test_coordinated_generators_never_collide() {
+        use crate::storage::InMemoryStorage;
+        use std::collections::HashSet;
+
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+
+        // Replicas each with their own engine, and threads sharing one
+        let replicas: Vec<DfidEngine> = (0..4)
+            .map(|_| DfidEngine::coordinated(Arc::clone(&storage)).with_lease_size(7))
+            .collect();
+        let handles: Vec<_> = replicas
+            .iter()
+            .flat_map(|engine| [engine.clone(), engine.clone()])
+            .map(|engine| {
+                std::thread::spawn(move || {
+                    (0..250)
+                        .map(|_| engine.generate_dfid().unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let mut seen = HashSet::new();
+        for handle in handles {
+            for dfid in handle.join().unwrap() {
+                assert!(replicas[0].validate_dfid(&dfid));
+                assert!(seen.insert(dfid.clone()), "duplicate DFID {dfid}");
+            }
+        }
+        assert_eq!(seen.len(), 8 * 250);
+    }
+
     #[test]
     fn test_checksum_consistency() {
         let engine = DfidEngine::new();
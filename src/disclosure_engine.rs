@@ -0,0 +1,544 @@
+//! Selective disclosure of item attributes.
+//!
+//! An item's owner reveals some of its enriched data to a recipient (a buyer,
+//! an auditor) without handing over the rest. The item's attributes are
+//! committed to with salted BLAKE3 commitments under one Merkle root (see
+//! `zk_proof_engine::AttributeCommitments`), and the root is notarized for the
+//! item, which anchors it with the next notarization batch. A disclosure holds
+//! the chosen attributes opened from their commitments; the recipient gets a
+//! token to fetch it and can check every value against the anchored root
+//! without learning anything about the undisclosed attributes.
+//!
+//! Commitments are made again, under new salts, once the item's data changes;
+//! disclosures keep the root they were made from. Only a token's BLAKE3 hash
+//! is stored.
+
+use crate::events_engine::{EventsEngine, EventsError};
+use crate::notarization_engine::{
+    hash_document, NotarizationEngine, NotarizationError, NotarizeInput,
+};
+use crate::partner_token_engine::hash_token;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{ItemAttributeCommitments, SelectiveDisclosure};
+use crate::zk_proof_engine::{AttributeCommitments, DisclosedAttribute};
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+pub const TOKEN_PREFIX: &str = "dfd_";
+const TOKEN_RANDOM_CHARS: usize = 40;
+pub const DEFAULT_EXPIRY_DAYS: i64 = 30;
+pub const MAX_EXPIRY_DAYS: i64 = 365;
+/// File name the commitment root is notarized under
+const COMMITMENT_FILE_NAME: &str = "attribute-commitments";
+
+#[derive(Debug)]
+pub enum DisclosureError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+    NotFound(String),
+    NotarizationError(NotarizationError),
+    /// Unknown, expired or revoked token; deliberately not more specific
+    InvalidToken,
+}
+
+impl From<StorageError> for DisclosureError {
+    fn from(err: StorageError) -> Self {
+        DisclosureError::StorageError(err)
+    }
+}
+
+impl From<NotarizationError> for DisclosureError {
+    fn from(err: NotarizationError) -> Self {
+        DisclosureError::NotarizationError(err)
+    }
+}
+
+impl From<EventsError> for DisclosureError {
+    fn from(err: EventsError) -> Self {
+        DisclosureError::NotarizationError(NotarizationError::EventsError(err))
+    }
+}
+
+impl std::fmt::Display for DisclosureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DisclosureError::StorageError(e) => write!(f, "Storage error: {e}"),
+            DisclosureError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            DisclosureError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            DisclosureError::NotFound(e) => write!(f, "Not found: {e}"),
+            DisclosureError::NotarizationError(e) => write!(f, "Notarization error: {e}"),
+            DisclosureError::InvalidToken => {
+                write!(f, "Disclosure token is invalid, expired or revoked")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DisclosureError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateDisclosureInput {
+    pub dfid: String,
+    /// Keys of the item's enriched data to reveal
+    pub fields: Vec<String>,
+    pub recipient: Option<String>,
+    /// Defaults to `DEFAULT_EXPIRY_DAYS`
+    pub expires_in_days: Option<i64>,
+}
+
+/// What the recipient receives: the disclosed attributes and the root they
+/// open against. Self-contained, so it can be verified again later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisclosurePackage {
+    pub disclosure_id: Uuid,
+    pub dfid: String,
+    pub root: String,
+    pub notarization_id: Uuid,
+    pub attributes: Vec<DisclosedAttribute>,
+    pub recipient: Option<String>,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<&SelectiveDisclosure> for DisclosurePackage {
+    fn from(disclosure: &SelectiveDisclosure) -> Self {
+        Self {
+            disclosure_id: disclosure.disclosure_id,
+            dfid: disclosure.dfid.clone(),
+            root: disclosure.root.clone(),
+            notarization_id: disclosure.notarization_id,
+            attributes: disclosure.attributes.clone(),
+            recipient: disclosure.recipient.clone(),
+            issued_at: disclosure.created_at,
+            expires_at: disclosure.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DisclosureVerification {
+    /// Every attribute opens its commitment and the root is notarized for the item
+    pub valid: bool,
+    /// Attributes whose value, salt or proof does not lead to the root
+    pub invalid_fields: Vec<String>,
+    pub root_notarized: bool,
+    /// The root's notarization is in an anchored batch
+    pub anchored: bool,
+    pub anchored_at: Option<DateTime<Utc>>,
+}
+
+fn generate_token() -> String {
+    const CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+    let mut rng = rand::thread_rng();
+    let random: String = (0..TOKEN_RANDOM_CHARS)
+        .map(|_| CHARS[rng.gen_range(0..CHARS.len())] as char)
+        .collect();
+    format!("{TOKEN_PREFIX}{random}")
+}
+
+pub struct DisclosureEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend + Clone + 'static> DisclosureEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// The item's owner is whoever recorded its first event, usually by
+    /// creating it or pushing it into a circuit
+    fn check_owner(&self, user_id: &str, dfid: &str) -> Result<(), DisclosureError> {
+        let owner = self
+            .storage
+            .get_events_by_dfid(dfid)?
+            .into_iter()
+            .min_by_key(|event| event.timestamp)
+            .map(|event| event.source);
+        if owner.as_deref() == Some(user_id) {
+            Ok(())
+        } else {
+            Err(DisclosureError::PermissionDenied(
+                "Only the item's owner can disclose its attributes".to_string(),
+            ))
+        }
+    }
+
+    /// Disclose `input.fields` of the item. Returns the token, shown only
+    /// this once, with the stored disclosure.
+    pub fn create(
+        &self,
+        events: &mut EventsEngine<S>,
+        user_id: &str,
+        input: CreateDisclosureInput,
+        now: DateTime<Utc>,
+    ) -> Result<(String, SelectiveDisclosure), DisclosureError> {
+        let item = self
+            .storage
+            .get_item_by_dfid(&input.dfid)?
+            .ok_or_else(|| DisclosureError::NotFound(format!("Item {}", input.dfid)))?;
+        self.check_owner(user_id, &input.dfid)?;
+
+        let fields: BTreeSet<&str> = input.fields.iter().map(|f| f.trim()).collect();
+        if fields.is_empty() || fields.contains("") {
+            return Err(DisclosureError::ValidationError(
+                "Name at least one attribute to disclose".to_string(),
+            ));
+        }
+        if let Some(missing) = fields
+            .iter()
+            .find(|field| !item.enriched_data.contains_key(**field))
+        {
+            return Err(DisclosureError::ValidationError(format!(
+                "Item {} has no attribute '{missing}'",
+                input.dfid
+            )));
+        }
+        let days = input.expires_in_days.unwrap_or(DEFAULT_EXPIRY_DAYS);
+        if !(1..=MAX_EXPIRY_DAYS).contains(&days) {
+            return Err(DisclosureError::ValidationError(format!(
+                "Disclosures expire after 1 to {MAX_EXPIRY_DAYS} days"
+            )));
+        }
+
+        let current = self.current_commitments(events, user_id, &item, now)?;
+        let attributes = fields
+            .iter()
+            .map(|field| {
+                current
+                    .commitments
+                    .disclose(field, &item.enriched_data[*field])
+                    .ok_or_else(|| {
+                        DisclosureError::ValidationError(format!("Cannot open '{field}'"))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let token = generate_token();
+        let disclosure = SelectiveDisclosure {
+            disclosure_id: Uuid::new_v4(),
+            token_hash: hash_token(&token),
+            dfid: item.dfid,
+            root: current.commitments.root,
+            notarization_id: current.notarization_id,
+            attributes,
+            recipient: input.recipient.filter(|r| !r.trim().is_empty()),
+            created_by: user_id.to_string(),
+            created_at: now,
+            expires_at: now + Duration::days(days),
+            revoked_at: None,
+        };
+        self.storage.store_selective_disclosure(&disclosure)?;
+        Ok((token, disclosure))
+    }
+
+    /// The disclosures the user made, newest first
+    pub fn list(&self, user_id: &str) -> Result<Vec<SelectiveDisclosure>, DisclosureError> {
+        let mut disclosures = self.storage.list_selective_disclosures(user_id)?;
+        disclosures.sort_by_key(|disclosure| std::cmp::Reverse(disclosure.created_at));
+        Ok(disclosures)
+    }
+
+    pub fn revoke(
+        &self,
+        user_id: &str,
+        disclosure_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<SelectiveDisclosure, DisclosureError> {
+        let mut disclosure = self
+            .storage
+            .get_selective_disclosure(disclosure_id)?
+            .filter(|disclosure| disclosure.created_by == user_id)
+            .ok_or_else(|| DisclosureError::NotFound(format!("Disclosure {disclosure_id}")))?;
+        if disclosure.revoked_at.is_none() {
+            disclosure.revoked_at = Some(now);
+            self.storage.store_selective_disclosure(&disclosure)?;
+        }
+        Ok(disclosure)
+    }
+
+    /// The package behind a live token
+    pub fn open(
+        &self,
+        token: &str,
+        now: DateTime<Utc>,
+    ) -> Result<DisclosurePackage, DisclosureError> {
+        self.storage
+            .get_selective_disclosure_by_token(&hash_token(token))?
+            .filter(|disclosure| disclosure.revoked_at.is_none() && now < disclosure.expires_at)
+            .map(|disclosure| DisclosurePackage::from(&disclosure))
+            .ok_or(DisclosureError::InvalidToken)
+    }
+
+    /// Check each attribute against the package's root, and the root against
+    /// its notarization for the item
+    pub fn verify(
+        &self,
+        package: &DisclosurePackage,
+    ) -> Result<DisclosureVerification, DisclosureError> {
+        let invalid_fields: Vec<String> = package
+            .attributes
+            .iter()
+            .filter(|attribute| !attribute.verify(&package.root))
+            .map(|attribute| attribute.field.clone())
+            .collect();
+
+        let notarizations = NotarizationEngine::new(self.storage.clone())
+            .verify_hash(&hash_document(package.root.as_bytes()))?;
+        let for_item: Vec<_> = notarizations
+            .matches
+            .iter()
+            .filter(|m| m.notarization.dfid.as_deref() == Some(package.dfid.as_str()))
+            .collect();
+        let root_notarized = !for_item.is_empty();
+        let anchored_at = for_item
+            .iter()
+            .filter(|m| m.proof_valid)
+            .filter_map(|m| m.notarization.anchored_at)
+            .min();
+
+        Ok(DisclosureVerification {
+            valid: invalid_fields.is_empty() && !package.attributes.is_empty() && root_notarized,
+            invalid_fields,
+            root_notarized,
+            anchored: anchored_at.is_some(),
+            anchored_at,
+        })
+    }
+
+    /// The stored commitments while they still match the item's data; new
+    /// ones, with a notarized root, once it changed
+    fn current_commitments(
+        &self,
+        events: &mut EventsEngine<S>,
+        user_id: &str,
+        item: &crate::types::Item,
+        now: DateTime<Utc>,
+    ) -> Result<ItemAttributeCommitments, DisclosureError> {
+        if let Some(stored) = self.storage.get_item_attribute_commitments(&item.dfid)? {
+            let unchanged = AttributeCommitments::with_salts(
+                &item.enriched_data,
+                stored.commitments.salts.clone(),
+            )
+            .is_some_and(|recomputed| recomputed.root == stored.commitments.root);
+            if unchanged {
+                return Ok(stored);
+            }
+        }
+
+        let commitments = AttributeCommitments::commit(&item.enriched_data);
+        let notarization = NotarizationEngine::new(self.storage.clone()).notarize(
+            events,
+            commitments.root.as_bytes(),
+            NotarizeInput {
+                file_name: Some(COMMITMENT_FILE_NAME.to_string()),
+                content_type: Some("text/plain".to_string()),
+                dfid: Some(item.dfid.clone()),
+            },
+            user_id,
+            now,
+        )?;
+        let current = ItemAttributeCommitments {
+            dfid: item.dfid.clone(),
+            commitments,
+            notarization_id: notarization.notarization_id,
+            created_by: user_id.to_string(),
+            created_at: now,
+        };
+        self.storage.store_item_attribute_commitments(&current)?;
+        Ok(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{EventType, EventVisibility, Identifier, Item};
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    type Storage = Arc<Mutex<InMemoryStorage>>;
+
+    const DFID: &str = "DFID-2026-0001";
+
+    /// A farmer's item with three attributes
+    fn farm() -> (Storage, EventsEngine<Storage>, DisclosureEngine<Storage>) {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let mut item = Item::new(
+            DFID.to_string(),
+            vec![Identifier::new("lot", "L-1")],
+            Uuid::new_v4(),
+        );
+        item.enriched_data
+            .insert("origin".to_string(), json!("Mato Grosso"));
+        item.enriched_data
+            .insert("weight_kg".to_string(), json!(480));
+        item.enriched_data
+            .insert("price".to_string(), json!(1200.5));
+        storage.store_item(&item).unwrap();
+        let mut events = EventsEngine::new(Arc::clone(&storage));
+        events
+            .create_event(
+                item.dfid.clone(),
+                EventType::Created,
+                "farmer".to_string(),
+                EventVisibility::Private,
+            )
+            .unwrap();
+        let engine = DisclosureEngine::new(Arc::clone(&storage));
+        (storage, events, engine)
+    }
+
+    fn input(fields: &[&str]) -> CreateDisclosureInput {
+        CreateDisclosureInput {
+            dfid: DFID.to_string(),
+            fields: fields.iter().map(|f| f.to_string()).collect(),
+            recipient: Some("buyer".to_string()),
+            expires_in_days: None,
+        }
+    }
+
+    #[test]
+    fn test_only_the_owner_can_disclose() {
+        let (_, mut events, engine) = farm();
+
+        assert!(matches!(
+            engine.create(&mut events, "buyer", input(&["origin"]), Utc::now()),
+            Err(DisclosureError::PermissionDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_disclosure_requests_are_refused() {
+        let (_, mut events, engine) = farm();
+        let now = Utc::now();
+
+        for input in [
+            input(&["colour"]),
+            input(&[]),
+            input(&[" "]),
+            CreateDisclosureInput {
+                expires_in_days: Some(0),
+                ..input(&["origin"])
+            },
+            CreateDisclosureInput {
+                expires_in_days: Some(MAX_EXPIRY_DAYS + 1),
+                ..input(&["origin"])
+            },
+        ] {
+            assert!(matches!(
+                engine.create(&mut events, "farmer", input, now),
+                Err(DisclosureError::ValidationError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_disclosed_attributes_verify_against_notarized_root() {
+        let (_, mut events, engine) = farm();
+        let now = Utc::now();
+
+        let (token, _) = engine
+            .create(&mut events, "farmer", input(&["origin", "weight_kg"]), now)
+            .unwrap();
+        let package = engine.open(&token, now).unwrap();
+        assert_eq!(package.attributes.len(), 2);
+        assert!(!serde_json::to_string(&package).unwrap().contains("1200.5"));
+
+        let verification = engine.verify(&package).unwrap();
+        assert!(verification.valid);
+        assert!(verification.root_notarized);
+        assert!(!verification.anchored);
+    }
+
+    #[test]
+    fn test_unchanged_data_reuses_the_notarized_root() {
+        let (storage, mut events, engine) = farm();
+        let now = Utc::now();
+
+        let (_, first) = engine
+            .create(&mut events, "farmer", input(&["origin"]), now)
+            .unwrap();
+        let (_, again) = engine
+            .create(&mut events, "farmer", input(&["price"]), now)
+            .unwrap();
+        assert_eq!(again.root, first.root);
+        assert_eq!(again.notarization_id, first.notarization_id);
+
+        let mut item = storage.get_item_by_dfid(DFID).unwrap().unwrap();
+        item.enriched_data
+            .insert("weight_kg".to_string(), json!(495));
+        storage.update_item(&item).unwrap();
+        let (_, changed) = engine
+            .create(&mut events, "farmer", input(&["origin"]), now)
+            .unwrap();
+        assert_ne!(changed.root, first.root);
+    }
+
+    #[test]
+    fn test_altered_value_fails_verification() {
+        let (_, mut events, engine) = farm();
+        let now = Utc::now();
+        let (token, _) = engine
+            .create(&mut events, "farmer", input(&["origin", "weight_kg"]), now)
+            .unwrap();
+
+        let mut forged = engine.open(&token, now).unwrap();
+        forged.attributes[1].value = json!(520);
+        let verification = engine.verify(&forged).unwrap();
+        assert!(!verification.valid);
+        assert_eq!(verification.invalid_fields, ["weight_kg"]);
+    }
+
+    #[test]
+    fn test_root_moved_to_another_item_is_not_notarized() {
+        let (_, mut events, engine) = farm();
+        let now = Utc::now();
+        let (token, _) = engine
+            .create(&mut events, "farmer", input(&["origin"]), now)
+            .unwrap();
+
+        let mut forged = engine.open(&token, now).unwrap();
+        forged.dfid = "DFID-2026-0002".to_string();
+        let verification = engine.verify(&forged).unwrap();
+        assert!(!verification.root_notarized);
+        assert!(!verification.valid);
+    }
+
+    #[test]
+    fn test_revoked_or_expired_disclosure_cannot_be_opened() {
+        let (_, mut events, engine) = farm();
+        let now = Utc::now();
+
+        let expiring = CreateDisclosureInput {
+            expires_in_days: Some(1),
+            ..input(&["origin"])
+        };
+        let (token, _) = engine.create(&mut events, "farmer", expiring, now).unwrap();
+        assert!(engine.open(&token, now).is_ok());
+        assert!(matches!(
+            engine.open(&token, now + Duration::days(2)),
+            Err(DisclosureError::InvalidToken)
+        ));
+
+        let (token, disclosure) = engine
+            .create(&mut events, "farmer", input(&["origin"]), now)
+            .unwrap();
+        assert!(matches!(
+            engine.revoke("buyer", &disclosure.disclosure_id, now),
+            Err(DisclosureError::NotFound(_))
+        ));
+        engine
+            .revoke("farmer", &disclosure.disclosure_id, now)
+            .unwrap();
+        assert!(matches!(
+            engine.open(&token, now),
+            Err(DisclosureError::InvalidToken)
+        ));
+        assert_eq!(engine.list("farmer").unwrap().len(), 2);
+    }
+}
@@ -5,9 +5,14 @@
 /// - SendGrid (fallback - limited free trial)
 ///
 /// It supports password reset emails and can be extended for other use cases.
+use crate::i18n::Localizer;
+use chrono::{Datelike, Utc};
 use serde_json::json;
 use std::env;
 
+/// Lifetime of password reset links, stated in the email
+const RESET_LINK_TTL_MINUTES: i64 = 30;
+
 /// Email provider selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EmailProvider {
@@ -63,85 +68,128 @@ impl EmailConfig {
     }
 }
 
-/// Send a password reset email via SendGrid
+/// Escape text interpolated into HTML email bodies
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Send a password reset email via SendGrid, in the user's locale
 pub async fn send_password_reset_email(
     to_email: &str,
     username: &str,
     token: &str,
+    locale: Option<&str>,
 ) -> Result<(), String> {
     let config = EmailConfig::from_env()?;
+    let localizer = Localizer::global();
+    let t = |key: &str, args: &[(&str, &str)]| localizer.translate(locale, key, args);
 
     // Build the reset link
     let reset_link = format!("{}/reset-password?token={}", config.frontend_url, token);
 
+    let subject = t("email.password_reset.subject", &[]);
+    let year = Utc::now().year().to_string();
+    let expires = localizer.translate_count(
+        locale,
+        "email.password_reset.expires",
+        RESET_LINK_TTL_MINUTES,
+        &[],
+    );
+    let rights = t("email.footer.rights", &[("year", year.as_str())]);
+    let automated = t("email.footer.automated", &[]);
+    let intro = t("email.password_reset.intro", &[]);
+    let ignore = t("email.password_reset.ignore", &[]);
+
     // Create HTML email body
+    let html_greeting = t(
+        "email.password_reset.greeting",
+        &[(
+            "username",
+            format!("<strong>{}</strong>", escape_html(username)).as_str(),
+        )],
+    );
     let html_body = format!(
         r#"
 <!DOCTYPE html>
-<html>
+<html lang="{}">
 <head>
     <meta charset="utf-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
 </head>
 <body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
     <div style="background-color: #f8f9fa; border-radius: 10px; padding: 30px; margin-bottom: 20px;">
-        <h1 style="color: #2c3e50; margin-top: 0;">Password Reset Request</h1>
-        <p>Hello <strong>{}</strong>,</p>
-        <p>We received a request to reset your password for your DeFarm Connect account.</p>
-        <p>Click the button below to reset your password:</p>
+        <h1 style="color: #2c3e50; margin-top: 0;">{}</h1>
+        <p>{}</p>
+        <p>{}</p>
+        <p>{}</p>
         <div style="text-align: center; margin: 30px 0;">
-            <a href="{}" style="background-color: #3498db; color: white; padding: 12px 30px; text-decoration: none; border-radius: 5px; display: inline-block; font-weight: bold;">Reset Password</a>
+            <a href="{}" style="background-color: #3498db; color: white; padding: 12px 30px; text-decoration: none; border-radius: 5px; display: inline-block; font-weight: bold;">{}</a>
         </div>
-        <p><small style="color: #7f8c8d;">Or copy and paste this link into your browser:</small></p>
+        <p><small style="color: #7f8c8d;">{}</small></p>
         <p style="background-color: #ecf0f1; padding: 10px; border-radius: 5px; word-break: break-all;"><small>{}</small></p>
         <hr style="border: none; border-top: 1px solid #ddd; margin: 30px 0;">
-        <p style="color: #e74c3c; font-weight: bold;">⏰ This link expires in 30 minutes.</p>
-        <p><small style="color: #7f8c8d;">If you didn't request a password reset, you can safely ignore this email. Your password will remain unchanged.</small></p>
+        <p style="color: #e74c3c; font-weight: bold;">⏰ {}</p>
+        <p><small style="color: #7f8c8d;">{}</small></p>
     </div>
     <div style="text-align: center; color: #95a5a6; font-size: 12px;">
-        <p>© 2024 DeFarm Connect. All rights reserved.</p>
-        <p>This is an automated message, please do not reply to this email.</p>
+        <p>{}</p>
+        <p>{}</p>
     </div>
 </body>
 </html>
         "#,
-        username, reset_link, reset_link
+        localizer.fallback_chain(locale)[0],
+        escape_html(&t("email.password_reset.heading", &[])),
+        html_greeting,
+        escape_html(&intro),
+        escape_html(&t("email.password_reset.action", &[])),
+        reset_link,
+        escape_html(&t("email.password_reset.button", &[])),
+        escape_html(&t("email.password_reset.copy_link", &[])),
+        reset_link,
+        escape_html(&expires),
+        escape_html(&ignore),
+        escape_html(&rights),
+        escape_html(&automated)
     );
 
     // Create plain text fallback
     let text_body = format!(
-        r#"Hello {},
+        r#"{}
 
-We received a request to reset your password for your DeFarm Connect account.
+{}
 
-To reset your password, click the following link or copy it into your browser:
+{}
 
 {}
 
-This link expires in 30 minutes.
+{}
 
-If you didn't request a password reset, you can safely ignore this email. Your password will remain unchanged.
+{}
 
 ---
-© 2024 DeFarm Connect
-This is an automated message, please do not reply to this email.
+{}
+{}
         "#,
-        username, reset_link
+        t("email.password_reset.greeting", &[("username", username)]),
+        intro,
+        t("email.password_reset.text_action", &[]),
+        reset_link,
+        expires,
+        ignore,
+        rights,
+        automated
     );
 
     // Send email via configured provider with automatic SMTP fallback
     match config.provider {
         EmailProvider::MailerSend => {
             // Try MailerSend API first
-            match send_via_mailersend(
-                &config,
-                to_email,
-                "Reset Your Password",
-                &html_body,
-                &text_body,
-            )
-            .await
-            {
+            match send_via_mailersend(&config, to_email, &subject, &html_body, &text_body).await {
                 Ok(()) => Ok(()),
                 Err(api_error) => {
                     // Fallback to SMTP if API fails
@@ -149,26 +197,12 @@ This is an automated message, please do not reply to this email.
                         "MailerSend API failed ({}), falling back to SMTP",
                         api_error
                     );
-                    send_via_smtp(
-                        &config,
-                        to_email,
-                        "Reset Your Password",
-                        &html_body,
-                        &text_body,
-                    )
-                    .await
+                    send_via_smtp(&config, to_email, &subject, &html_body, &text_body).await
                 }
             }
         }
         EmailProvider::SendGrid => {
-            send_via_sendgrid(
-                &config,
-                to_email,
-                "Reset Your Password",
-                &html_body,
-                &text_body,
-            )
-            .await
+            send_via_sendgrid(&config, to_email, &subject, &html_body, &text_body).await
         }
     }
 }
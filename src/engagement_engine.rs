@@ -0,0 +1,443 @@
+//! Per-workspace engagement analytics for co-op owners.
+//!
+//! A scheduled job snapshots, for each workspace, how many members were active
+//! each day, how much they push into circuits and how long those pushes wait
+//! for approval or rejection. Members who are inactive, never submit or see
+//! most of their submissions rejected are flagged so owners know who needs
+//! training. Workspace membership is the `workspace_id` on user accounts, and
+//! the owner is the account that opened the workspace.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    CircuitOperation, DailyActiveMembers, EngagementFlag, MemberEngagement, OperationType,
+    UserAccount, WorkspaceEngagement,
+};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Timelike, Utc};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+pub const DEFAULT_WINDOW_DAYS: i64 = 30;
+
+/// Reviewed submissions needed before the rejection rate counts
+const MIN_REVIEWED_FOR_REJECTION_FLAG: usize = 2;
+
+#[derive(Debug)]
+pub enum EngagementError {
+    StorageError(StorageError),
+    NotFound(String),
+}
+
+impl From<StorageError> for EngagementError {
+    fn from(err: StorageError) -> Self {
+        EngagementError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for EngagementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EngagementError::StorageError(e) => write!(f, "Storage error: {e}"),
+            EngagementError::NotFound(e) => write!(f, "Not found: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for EngagementError {}
+
+pub struct EngagementEngine<S: StorageBackend> {
+    storage: S,
+    window_days: i64,
+}
+
+impl<S: StorageBackend> EngagementEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            window_days: DEFAULT_WINDOW_DAYS,
+        }
+    }
+
+    pub fn with_window_days(mut self, window_days: i64) -> Self {
+        self.window_days = window_days.max(1);
+        self
+    }
+
+    fn members(&self, workspace_id: &str) -> Result<Vec<UserAccount>, EngagementError> {
+        Ok(self
+            .storage
+            .list_user_accounts()?
+            .into_iter()
+            .filter(|account| account.workspace_id.as_deref() == Some(workspace_id))
+            .collect())
+    }
+
+    pub fn user_workspace(&self, user_id: &str) -> Result<Option<String>, EngagementError> {
+        Ok(self
+            .storage
+            .get_user_account(user_id)?
+            .and_then(|account| account.workspace_id))
+    }
+
+    /// The account that opened the workspace, i.e. its earliest member
+    pub fn workspace_owner(&self, workspace_id: &str) -> Result<Option<String>, EngagementError> {
+        Ok(self
+            .members(workspace_id)?
+            .into_iter()
+            .min_by_key(|account| account.created_at)
+            .map(|account| account.user_id))
+    }
+
+    /// Owners see their own workspace; admins see every workspace
+    pub fn can_view(&self, user_id: &str, workspace_id: &str) -> Result<bool, EngagementError> {
+        if self
+            .storage
+            .get_user_account(user_id)?
+            .is_some_and(|account| account.is_admin)
+        {
+            return Ok(true);
+        }
+        Ok(self.workspace_owner(workspace_id)?.as_deref() == Some(user_id))
+    }
+
+    pub fn compute(
+        &self,
+        workspace_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<WorkspaceEngagement, EngagementError> {
+        let members = self.members(workspace_id)?;
+        if members.is_empty() {
+            return Err(EngagementError::NotFound(format!(
+                "Workspace {workspace_id} has no members"
+            )));
+        }
+        let member_ids: HashSet<&str> = members.iter().map(|m| m.user_id.as_str()).collect();
+
+        let mut activity: Vec<(String, DateTime<Utc>)> = self
+            .storage
+            .list_user_activities()?
+            .into_iter()
+            .filter(|a| member_ids.contains(a.user_id.as_str()))
+            .map(|a| (a.user_id, a.timestamp))
+            .collect();
+        activity.extend(
+            self.storage
+                .get_all_activities()?
+                .into_iter()
+                .filter(|a| member_ids.contains(a.user_id.as_str()))
+                .map(|a| (a.user_id, a.timestamp)),
+        );
+
+        let mut operations = Vec::new();
+        for circuit in self.storage.list_circuits()? {
+            operations.extend(
+                self.storage
+                    .get_circuit_operations(&circuit.circuit_id)?
+                    .into_iter()
+                    .filter(|op| member_ids.contains(op.requester_id.as_str())),
+            );
+        }
+
+        Ok(summarize(
+            workspace_id,
+            &members,
+            &activity,
+            &operations,
+            now,
+            self.window_days,
+        ))
+    }
+
+    /// Recompute and store the snapshot of every workspace with members
+    pub fn refresh_all(&self, now: DateTime<Utc>) -> Result<usize, EngagementError> {
+        let workspaces: BTreeSet<String> = self
+            .storage
+            .list_user_accounts()?
+            .into_iter()
+            .filter_map(|account| account.workspace_id)
+            .collect();
+        for workspace_id in &workspaces {
+            let engagement = self.compute(workspace_id, now)?;
+            self.storage.store_workspace_engagement(&engagement)?;
+        }
+        Ok(workspaces.len())
+    }
+
+    /// Latest stored snapshot, computed on the spot if the job has not reached
+    /// this workspace yet
+    pub fn latest(
+        &self,
+        workspace_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<WorkspaceEngagement, EngagementError> {
+        if let Some(engagement) = self.storage.get_workspace_engagement(workspace_id)? {
+            return Ok(engagement);
+        }
+        let engagement = self.compute(workspace_id, now)?;
+        self.storage.store_workspace_engagement(&engagement)?;
+        Ok(engagement)
+    }
+
+    pub fn spawn_scheduler(storage: S, tick: std::time::Duration) -> tokio::task::JoinHandle<()>
+    where
+        S: Send + Sync + 'static,
+    {
+        tokio::spawn(async move {
+            let engine = EngagementEngine::new(storage);
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                match engine.refresh_all(Utc::now()) {
+                    Ok(count) => {
+                        tracing::debug!("📈 Refreshed engagement for {} workspaces", count)
+                    }
+                    Err(e) => tracing::warn!("⚠️  Failed to refresh workspace engagement: {}", e),
+                }
+            }
+        })
+    }
+}
+
+fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    })
+}
+
+/// Minutes from push to approval or rejection, if the operation was reviewed
+fn minutes_to_verification(operation: &CircuitOperation) -> Option<f64> {
+    let reviewed_at = ["approved_at", "rejected_at"].iter().find_map(|key| {
+        operation
+            .metadata
+            .get(*key)
+            .and_then(|v| v.as_str())
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+    })?;
+    let elapsed = reviewed_at.with_timezone(&Utc) - operation.timestamp;
+    Some(elapsed.num_seconds().max(0) as f64 / 60.0)
+}
+
+#[derive(Default)]
+struct MemberTally {
+    active_days: HashSet<NaiveDate>,
+    last_active_at: Option<DateTime<Utc>>,
+    submissions: usize,
+    approved: usize,
+    rejected: usize,
+    verification_minutes: Vec<f64>,
+}
+
+fn summarize(
+    workspace_id: &str,
+    members: &[UserAccount],
+    activity: &[(String, DateTime<Utc>)],
+    operations: &[CircuitOperation],
+    now: DateTime<Utc>,
+    window_days: i64,
+) -> WorkspaceEngagement {
+    let first_day = now.date_naive() - Duration::days(window_days - 1);
+    let in_window = |at: &DateTime<Utc>| at.date_naive() >= first_day && *at <= now;
+
+    let mut tallies: HashMap<&str, MemberTally> = members
+        .iter()
+        .map(|m| (m.user_id.as_str(), MemberTally::default()))
+        .collect();
+    let mut active_by_day: HashMap<NaiveDate, HashSet<&str>> = HashMap::new();
+    let mut heatmap = vec![vec![0usize; 24]; 7];
+
+    for (user_id, at) in activity.iter().filter(|(_, at)| in_window(at)) {
+        let Some(tally) = tallies.get_mut(user_id.as_str()) else {
+            continue;
+        };
+        tally.active_days.insert(at.date_naive());
+        tally.last_active_at = tally.last_active_at.max(Some(*at));
+        active_by_day
+            .entry(at.date_naive())
+            .or_default()
+            .insert(user_id.as_str());
+        heatmap[at.weekday().num_days_from_monday() as usize][at.hour() as usize] += 1;
+    }
+
+    let mut all_verification_minutes = Vec::new();
+    for operation in operations
+        .iter()
+        .filter(|op| matches!(op.operation_type, OperationType::Push) && in_window(&op.timestamp))
+    {
+        let Some(tally) = tallies.get_mut(operation.requester_id.as_str()) else {
+            continue;
+        };
+        tally.submissions += 1;
+        if operation.metadata.contains_key("approved_at") {
+            tally.approved += 1;
+        } else if operation.metadata.contains_key("rejected_at") {
+            tally.rejected += 1;
+        }
+        if let Some(minutes) = minutes_to_verification(operation) {
+            tally.verification_minutes.push(minutes);
+            all_verification_minutes.push(minutes);
+        }
+    }
+
+    let daily_active_members: Vec<DailyActiveMembers> = (0..window_days)
+        .map(|offset| {
+            let date = first_day + Duration::days(offset);
+            DailyActiveMembers {
+                date,
+                active_members: active_by_day.get(&date).map_or(0, HashSet::len),
+            }
+        })
+        .collect();
+    let total_active: usize = daily_active_members.iter().map(|d| d.active_members).sum();
+
+    let mut member_engagement: Vec<MemberEngagement> = members
+        .iter()
+        .map(|member| {
+            let mut tally = tallies.remove(member.user_id.as_str()).unwrap_or_default();
+            let mut flags = Vec::new();
+            if tally.active_days.is_empty() {
+                flags.push(EngagementFlag::Inactive);
+            } else if tally.submissions == 0 {
+                flags.push(EngagementFlag::NoSubmissions);
+            }
+            let reviewed = tally.approved + tally.rejected;
+            if reviewed >= MIN_REVIEWED_FOR_REJECTION_FLAG && tally.rejected * 2 >= reviewed {
+                flags.push(EngagementFlag::HighRejectionRate);
+            }
+            MemberEngagement {
+                user_id: member.user_id.clone(),
+                username: member.username.clone(),
+                active_days: tally.active_days.len(),
+                last_active_at: tally.last_active_at,
+                submissions: tally.submissions,
+                approved_submissions: tally.approved,
+                rejected_submissions: tally.rejected,
+                median_minutes_to_verification: median(&mut tally.verification_minutes),
+                flags,
+            }
+        })
+        .collect();
+    // Members needing attention first, least active first
+    member_engagement.sort_by(|a, b| {
+        b.flags
+            .len()
+            .cmp(&a.flags.len())
+            .then(a.active_days.cmp(&b.active_days))
+            .then(a.username.cmp(&b.username))
+    });
+
+    let submissions: usize = member_engagement.iter().map(|m| m.submissions).sum();
+    WorkspaceEngagement {
+        workspace_id: workspace_id.to_string(),
+        computed_at: now,
+        window_days,
+        member_count: members.len(),
+        average_daily_active_members: total_active as f64 / window_days as f64,
+        daily_active_members,
+        submissions_per_member: submissions as f64 / members.len() as f64,
+        median_minutes_to_verification: median(&mut all_verification_minutes),
+        activity_heatmap: heatmap,
+        members: member_engagement,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AccountStatus, OperationStatus, TierLimits, UserTier};
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn member(user_id: &str) -> UserAccount {
+        UserAccount {
+            user_id: user_id.to_string(),
+            username: user_id.to_string(),
+            email: format!("{user_id}@coop.example"),
+            password_hash: "hash".to_string(),
+            limits: TierLimits::for_tier(&UserTier::Basic),
+            tier: UserTier::Basic,
+            status: AccountStatus::Active,
+            credits: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_login: None,
+            subscription: None,
+            is_admin: false,
+            workspace_id: Some("coop-workspace".to_string()),
+            available_adapters: None,
+            locale: None,
+        }
+    }
+
+    fn push(requester: &str, at: DateTime<Utc>, review: Option<(&str, i64)>) -> CircuitOperation {
+        let mut operation = CircuitOperation {
+            operation_id: Uuid::new_v4(),
+            circuit_id: Uuid::new_v4(),
+            dfid: "DFID-1".to_string(),
+            operation_type: OperationType::Push,
+            requester_id: requester.to_string(),
+            timestamp: at,
+            status: OperationStatus::Pending,
+            metadata: HashMap::new(),
+        };
+        if let Some((key, minutes)) = review {
+            operation.metadata.insert(
+                key.to_string(),
+                serde_json::json!((at + Duration::minutes(minutes)).to_rfc3339()),
+            );
+        }
+        operation
+    }
+
+    #[test]
+    fn test_summarize_counts_activity_and_flags_members() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 18, 0, 0).unwrap();
+        let monday_9am = Utc.with_ymd_and_hms(2026, 3, 9, 9, 0, 0).unwrap();
+        let members = vec![member("ana"), member("bruno"), member("carla")];
+        let activity = vec![
+            ("ana".to_string(), monday_9am),
+            ("ana".to_string(), now - Duration::hours(1)),
+            ("bruno".to_string(), monday_9am),
+            ("ana".to_string(), now - Duration::days(60)),
+        ];
+        let operations = vec![
+            push("ana", monday_9am, Some(("approved_at", 30))),
+            push("ana", monday_9am, Some(("approved_at", 90))),
+            push("carla", monday_9am, Some(("rejected_at", 10))),
+            push("carla", monday_9am, Some(("rejected_at", 20))),
+        ];
+
+        let engagement = summarize("coop-workspace", &members, &activity, &operations, now, 7);
+
+        assert_eq!(engagement.daily_active_members.len(), 7);
+        assert_eq!(
+            engagement.daily_active_members[5],
+            DailyActiveMembers {
+                date: monday_9am.date_naive(),
+                active_members: 2
+            }
+        );
+        assert_eq!(engagement.activity_heatmap[0][9], 2);
+        assert!((engagement.submissions_per_member - 4.0 / 3.0).abs() < 1e-9);
+        assert_eq!(engagement.median_minutes_to_verification, Some(25.0));
+
+        let by_id: HashMap<&str, &MemberEngagement> = engagement
+            .members
+            .iter()
+            .map(|m| (m.user_id.as_str(), m))
+            .collect();
+        assert_eq!(by_id["ana"].active_days, 2);
+        assert_eq!(by_id["ana"].median_minutes_to_verification, Some(60.0));
+        assert!(by_id["ana"].flags.is_empty());
+        assert_eq!(by_id["bruno"].flags, vec![EngagementFlag::NoSubmissions]);
+        assert_eq!(
+            by_id["carla"].flags,
+            vec![EngagementFlag::Inactive, EngagementFlag::HighRejectionRate]
+        );
+        assert_eq!(engagement.members[0].user_id, "carla");
+    }
+}
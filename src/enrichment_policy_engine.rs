@@ -0,0 +1,336 @@
+//! Per-workspace merge policies for enriching items.
+//!
+//! When data for an existing item arrives, each field is merged under the
+//! policy of the workspace doing the enrichment: the enriching user's, or for
+//! anonymous data (receipts) the workspace of whoever sourced the item's first
+//! event. Without a policy every incoming value wins. Each enrichment records an
+//! Enriched event carrying the merge report, so what happened to every field,
+//! including rejected conflicts, stays visible in the item's history.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    EnrichmentPolicy, Event, EventType, EventVisibility, FieldMergeOutcome, Item, MergePolicy,
+    MergeReport,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Field names a policy may hold overrides for
+const MAX_FIELD_POLICIES: usize = 500;
+
+#[derive(Debug)]
+pub enum EnrichmentPolicyError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+}
+
+impl From<StorageError> for EnrichmentPolicyError {
+    fn from(err: StorageError) -> Self {
+        EnrichmentPolicyError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for EnrichmentPolicyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnrichmentPolicyError::StorageError(e) => write!(f, "Storage error: {e}"),
+            EnrichmentPolicyError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            EnrichmentPolicyError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for EnrichmentPolicyError {}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EnrichmentPolicyInput {
+    /// Defaults to last-wins
+    #[serde(default)]
+    pub default_policy: MergePolicy,
+    #[serde(default)]
+    pub field_policies: HashMap<String, MergePolicy>,
+}
+
+/// An applied enrichment: the updated item, what happened to each field and the
+/// Enriched event recording it
+#[derive(Debug, Clone)]
+pub struct Enrichment {
+    pub item: Item,
+    pub report: MergeReport,
+    pub event: Event,
+}
+
+pub struct EnrichmentPolicyEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> EnrichmentPolicyEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// The workspace's policy, or the last-wins default if it never set one
+    pub fn get_policy(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+    ) -> Result<EnrichmentPolicy, EnrichmentPolicyError> {
+        self.check_member(user_id, workspace_id)?;
+        Ok(self
+            .storage
+            .get_enrichment_policy(workspace_id)?
+            .unwrap_or_else(|| EnrichmentPolicy {
+                workspace_id: workspace_id.to_string(),
+                default_policy: MergePolicy::default(),
+                field_policies: HashMap::new(),
+                updated_by: "system".to_string(),
+                updated_at: DateTime::<Utc>::UNIX_EPOCH,
+            }))
+    }
+
+    pub fn set_policy(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+        input: EnrichmentPolicyInput,
+        now: DateTime<Utc>,
+    ) -> Result<EnrichmentPolicy, EnrichmentPolicyError> {
+        self.check_member(user_id, workspace_id)?;
+        if input.field_policies.len() > MAX_FIELD_POLICIES {
+            return Err(EnrichmentPolicyError::ValidationError(format!(
+                "At most {MAX_FIELD_POLICIES} field policies are allowed"
+            )));
+        }
+        if input
+            .field_policies
+            .keys()
+            .any(|field| field.trim().is_empty())
+        {
+            return Err(EnrichmentPolicyError::ValidationError(
+                "Field names must not be empty".to_string(),
+            ));
+        }
+
+        let policy = EnrichmentPolicy {
+            workspace_id: workspace_id.to_string(),
+            default_policy: input.default_policy,
+            field_policies: input.field_policies,
+            updated_by: user_id.to_string(),
+            updated_at: now,
+        };
+        self.storage.store_enrichment_policy(&policy)?;
+        Ok(policy)
+    }
+
+    /// Members manage their own workspace's policy; admins any workspace's
+    fn check_member(&self, user_id: &str, workspace_id: &str) -> Result<(), EnrichmentPolicyError> {
+        let account = self.storage.get_user_account(user_id)?.ok_or_else(|| {
+            EnrichmentPolicyError::PermissionDenied(format!("Unknown user {user_id}"))
+        })?;
+        if account.is_admin || account.workspace_id.as_deref() == Some(workspace_id) {
+            Ok(())
+        } else {
+            Err(EnrichmentPolicyError::PermissionDenied(
+                "Only workspace members can manage its enrichment policy".to_string(),
+            ))
+        }
+    }
+}
+
+/// The policy enriching `dfid` falls under: that of `actor`'s workspace, else
+/// that of the workspace whose member sourced the item's first event
+pub fn policy_for_enrichment<S: StorageBackend>(
+    storage: &S,
+    dfid: &str,
+    actor: Option<&str>,
+) -> Result<Option<EnrichmentPolicy>, StorageError> {
+    let workspace_of = |user_id: &str| -> Result<Option<String>, StorageError> {
+        Ok(storage
+            .get_user_account(user_id)?
+            .and_then(|account| account.workspace_id))
+    };
+
+    let mut workspace_id = match actor {
+        Some(actor) => workspace_of(actor)?,
+        None => None,
+    };
+    if workspace_id.is_none() {
+        let first_source = storage
+            .get_events_by_dfid(dfid)?
+            .into_iter()
+            .min_by_key(|event| event.timestamp)
+            .map(|event| event.source);
+        if let Some(source) = first_source {
+            workspace_id = workspace_of(&source)?;
+        }
+    }
+    match workspace_id {
+        Some(workspace_id) => storage.get_enrichment_policy(&workspace_id),
+        None => Ok(None),
+    }
+}
+
+/// Merge `data` into `item` under the applicable policy, save the item and
+/// record the Enriched event with the merge report
+pub fn enrich_with_policy<S: StorageBackend>(
+    storage: &S,
+    mut item: Item,
+    data: HashMap<String, serde_json::Value>,
+    source_entry: Uuid,
+    actor: Option<&str>,
+) -> Result<Enrichment, StorageError> {
+    let policy = policy_for_enrichment(storage, &item.dfid, actor)?;
+    let report = item.enrich_with_policy(data, source_entry, policy.as_ref());
+    storage.update_item(&item)?;
+
+    // Changed fields carry their merged values so replaying the event rebuilds them
+    let mut metadata = HashMap::new();
+    let mut changed_keys = Vec::new();
+    for field in &report.fields {
+        if matches!(
+            field.outcome,
+            FieldMergeOutcome::Kept | FieldMergeOutcome::Rejected
+        ) {
+            continue;
+        }
+        if let Some(value) = item.enriched_data.get(&field.field) {
+            metadata.insert(field.field.clone(), value.clone());
+        }
+        changed_keys.push(serde_json::Value::String(field.field.clone()));
+    }
+    metadata.insert(
+        "enriched_keys".to_string(),
+        serde_json::Value::Array(changed_keys),
+    );
+    metadata.insert(
+        "source_entry".to_string(),
+        serde_json::Value::String(source_entry.to_string()),
+    );
+    metadata.insert(
+        "merge_report".to_string(),
+        serde_json::to_value(&report).map_err(StorageError::SerializationError)?,
+    );
+    // Private: rejected conflicts carry the dropped values
+    let event = Event::new_with_metadata(
+        item.dfid.clone(),
+        EventType::Enriched,
+        actor.unwrap_or("system").to_string(),
+        EventVisibility::Private,
+        metadata,
+    );
+    storage.store_event(&event)?;
+
+    Ok(Enrichment {
+        item,
+        report,
+        event,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{AccountStatus, TierLimits, UserAccount, UserTier};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_enrichment_follows_workspace_field_policies() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let account = UserAccount {
+            user_id: "user-1".to_string(),
+            username: "farmer".to_string(),
+            email: "farmer@example.com".to_string(),
+            password_hash: "hash".to_string(),
+            limits: TierLimits::for_tier(&UserTier::Professional),
+            tier: UserTier::Professional,
+            status: AccountStatus::Active,
+            credits: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_login: None,
+            subscription: None,
+            is_admin: false,
+            workspace_id: Some("ws-1".to_string()),
+            available_adapters: None,
+            locale: None,
+        };
+        storage.store_user_account(&account).unwrap();
+
+        let engine = EnrichmentPolicyEngine::new(Arc::clone(&storage));
+        engine
+            .set_policy(
+                "user-1",
+                "ws-1",
+                EnrichmentPolicyInput {
+                    default_policy: MergePolicy::LastWins,
+                    field_policies: HashMap::from([
+                        ("origin".to_string(), MergePolicy::FirstWins),
+                        ("certifications".to_string(), MergePolicy::Append),
+                        ("weight_kg".to_string(), MergePolicy::RejectConflict),
+                    ]),
+                },
+                Utc::now(),
+            )
+            .unwrap();
+        assert!(engine
+            .set_policy(
+                "user-2",
+                "ws-1",
+                EnrichmentPolicyInput::default(),
+                Utc::now()
+            )
+            .is_err());
+
+        let mut item = Item::new("DFID-ENRICH".to_string(), vec![], Uuid::new_v4());
+        item.enriched_data = HashMap::from([
+            ("origin".to_string(), "MT".into()),
+            ("certifications".to_string(), "organic".into()),
+            ("weight_kg".to_string(), 400.into()),
+            ("grade".to_string(), "B".into()),
+        ]);
+        storage.store_item(&item).unwrap();
+
+        let incoming = HashMap::from([
+            ("origin".to_string(), "GO".into()),
+            ("certifications".to_string(), "rainforest".into()),
+            ("weight_kg".to_string(), 420.into()),
+            ("grade".to_string(), "A".into()),
+            ("breed".to_string(), "nelore".into()),
+        ]);
+        let enrichment =
+            enrich_with_policy(&storage, item, incoming, Uuid::new_v4(), Some("user-1")).unwrap();
+
+        let data = &enrichment.item.enriched_data;
+        assert_eq!(data["origin"], "MT");
+        assert_eq!(
+            data["certifications"],
+            serde_json::json!(["organic", "rainforest"])
+        );
+        assert_eq!(data["weight_kg"], 400);
+        assert_eq!(data["grade"], "A");
+        assert_eq!(data["breed"], "nelore");
+
+        let report = &enrichment.report;
+        assert_eq!(report.workspace_id.as_deref(), Some("ws-1"));
+        let conflicts: Vec<_> = report.conflicts().collect();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].field, "weight_kg");
+        assert_eq!(conflicts[0].rejected_value, Some(420.into()));
+
+        assert_eq!(enrichment.event.event_type, EventType::Enriched);
+        assert_eq!(
+            enrichment.event.metadata["enriched_keys"],
+            serde_json::json!(["breed", "certifications", "grade"])
+        );
+        assert_eq!(
+            enrichment.event.metadata["certifications"],
+            serde_json::json!(["organic", "rainforest"])
+        );
+        assert!(!enrichment.event.metadata.contains_key("weight_kg"));
+        assert!(enrichment.event.metadata.contains_key("merge_report"));
+    }
+}
@@ -0,0 +1,320 @@
+//! JSON Schema validation of event metadata.
+//!
+//! Covers the subset of JSON Schema circuits need to describe metadata:
+//! `type`, `properties`, `required`, `additionalProperties`, `items`, `enum`,
+//! `const`, numeric and length bounds and the `date-time`, `date` and `uuid`
+//! formats. Schemas using any other keyword are rejected when registered, so a
+//! schema never silently validates less than its author expects.
+
+use crate::types::SchemaViolation;
+use serde_json::{Map, Value};
+
+const TYPES: [&str; 7] = [
+    "object", "array", "string", "number", "integer", "boolean", "null",
+];
+const FORMATS: [&str; 3] = ["date-time", "date", "uuid"];
+/// Keywords accepted for documentation only
+const ANNOTATIONS: [&str; 6] = [
+    "$schema",
+    "$id",
+    "title",
+    "description",
+    "default",
+    "examples",
+];
+
+/// Check that `schema` only uses supported keywords with well-formed values
+pub fn check_schema(schema: &Value) -> Result<(), String> {
+    check_at(schema, "$")
+}
+
+fn check_at(schema: &Value, path: &str) -> Result<(), String> {
+    let object = match schema {
+        Value::Bool(_) => return Ok(()),
+        Value::Object(object) => object,
+        _ => return Err(format!("{path}: a schema must be an object or a boolean")),
+    };
+    for (keyword, value) in object {
+        let at = format!("{path}.{keyword}");
+        match keyword.as_str() {
+            "type" => {
+                let names: Vec<&Value> = match value {
+                    Value::Array(names) if !names.is_empty() => names.iter().collect(),
+                    single => vec![single],
+                };
+                for name in names {
+                    if !name.as_str().is_some_and(|n| TYPES.contains(&n)) {
+                        return Err(format!("{at}: unknown type {name}"));
+                    }
+                }
+            }
+            "properties" => {
+                let properties = value
+                    .as_object()
+                    .ok_or_else(|| format!("{at}: must be an object"))?;
+                for (name, property) in properties {
+                    check_at(property, &format!("{path}.properties.{name}"))?;
+                }
+            }
+            "additionalProperties" | "items" => check_at(value, &at)?,
+            "required" => {
+                if !value
+                    .as_array()
+                    .is_some_and(|names| names.iter().all(Value::is_string))
+                {
+                    return Err(format!("{at}: must be an array of property names"));
+                }
+            }
+            "enum" => {
+                if value.as_array().is_none_or(|values| values.is_empty()) {
+                    return Err(format!("{at}: must be a non-empty array"));
+                }
+            }
+            "const" => {}
+            "minimum" | "maximum" | "exclusiveMinimum" | "exclusiveMaximum" => {
+                if !value.is_number() {
+                    return Err(format!("{at}: must be a number"));
+                }
+            }
+            "minLength" | "maxLength" | "minItems" | "maxItems" => {
+                if value.as_u64().is_none() {
+                    return Err(format!("{at}: must be a non-negative integer"));
+                }
+            }
+            "format" => {
+                if !value.as_str().is_some_and(|f| FORMATS.contains(&f)) {
+                    return Err(format!(
+                        "{at}: unsupported format {value}, expected one of {}",
+                        FORMATS.join(", ")
+                    ));
+                }
+            }
+            other if ANNOTATIONS.contains(&other) => {}
+            other => return Err(format!("{at}: unsupported keyword '{other}'")),
+        }
+    }
+    Ok(())
+}
+
+/// Every way `instance` fails `schema`; empty when it is valid. The schema
+/// must have passed `check_schema`.
+pub fn validate(schema: &Value, instance: &Value) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    validate_at(schema, instance, "$", &mut violations);
+    violations
+}
+
+fn type_matches(name: &str, instance: &Value) -> bool {
+    match name {
+        "object" => instance.is_object(),
+        "array" => instance.is_array(),
+        "string" => instance.is_string(),
+        "number" => instance.is_number(),
+        "integer" => {
+            instance.is_i64()
+                || instance.is_u64()
+                || instance.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        "boolean" => instance.is_boolean(),
+        "null" => instance.is_null(),
+        _ => false,
+    }
+}
+
+fn format_matches(format: &str, value: &str) -> bool {
+    match format {
+        "date-time" => chrono::DateTime::parse_from_rfc3339(value).is_ok(),
+        "date" => chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+        "uuid" => uuid::Uuid::parse_str(value).is_ok(),
+        _ => true,
+    }
+}
+
+fn validate_at(schema: &Value, instance: &Value, path: &str, out: &mut Vec<SchemaViolation>) {
+    let mut violation = |message: String| {
+        out.push(SchemaViolation {
+            path: path.to_string(),
+            message,
+        })
+    };
+    let object = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => return violation("no value is allowed here".to_string()),
+        Value::Object(object) => object,
+        _ => return,
+    };
+
+    if let Some(expected) = object.get("type") {
+        let names: Vec<&str> = match expected {
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            single => single.as_str().into_iter().collect(),
+        };
+        if !names.iter().any(|name| type_matches(name, instance)) {
+            // Nested keywords would only repeat the type mismatch
+            return violation(format!("expected {}", names.join(" or ")));
+        }
+    }
+    if let Some(allowed) = object.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(instance) {
+            violation(format!("must be one of {}", Value::Array(allowed.clone())));
+        }
+    }
+    if let Some(expected) = object.get("const") {
+        if expected != instance {
+            violation(format!("must equal {expected}"));
+        }
+    }
+
+    if let Some(n) = instance.as_f64() {
+        let bound = |keyword: &str| object.get(keyword).and_then(Value::as_f64);
+        if let Some(min) = bound("minimum").filter(|min| n < *min) {
+            violation(format!("must be at least {min}"));
+        }
+        if let Some(max) = bound("maximum").filter(|max| n > *max) {
+            violation(format!("must be at most {max}"));
+        }
+        if let Some(min) = bound("exclusiveMinimum").filter(|min| n <= *min) {
+            violation(format!("must be greater than {min}"));
+        }
+        if let Some(max) = bound("exclusiveMaximum").filter(|max| n >= *max) {
+            violation(format!("must be less than {max}"));
+        }
+    }
+
+    if let Some(s) = instance.as_str() {
+        let length = s.chars().count() as u64;
+        if let Some(min) = object.get("minLength").and_then(Value::as_u64) {
+            if length < min {
+                violation(format!("must be at least {min} characters"));
+            }
+        }
+        if let Some(max) = object.get("maxLength").and_then(Value::as_u64) {
+            if length > max {
+                violation(format!("must be at most {max} characters"));
+            }
+        }
+        if let Some(format) = object.get("format").and_then(Value::as_str) {
+            if !format_matches(format, s) {
+                violation(format!("must be a valid {format}"));
+            }
+        }
+    }
+
+    if let Some(items) = instance.as_array() {
+        let count = items.len() as u64;
+        if let Some(min) = object.get("minItems").and_then(Value::as_u64) {
+            if count < min {
+                violation(format!("must have at least {min} items"));
+            }
+        }
+        if let Some(max) = object.get("maxItems").and_then(Value::as_u64) {
+            if count > max {
+                violation(format!("must have at most {max} items"));
+            }
+        }
+        if let Some(item_schema) = object.get("items") {
+            for (index, item) in items.iter().enumerate() {
+                validate_at(item_schema, item, &format!("{path}[{index}]"), out);
+            }
+        }
+    }
+
+    if let Some(fields) = instance.as_object() {
+        validate_object(object, fields, path, out);
+    }
+}
+
+fn validate_object(
+    schema: &Map<String, Value>,
+    fields: &Map<String, Value>,
+    path: &str,
+    out: &mut Vec<SchemaViolation>,
+) {
+    for name in schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+    {
+        if !fields.contains_key(name) {
+            out.push(SchemaViolation {
+                path: format!("{path}.{name}"),
+                message: "is required".to_string(),
+            });
+        }
+    }
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (name, value) in fields {
+        let field_path = format!("{path}.{name}");
+        match properties.and_then(|p| p.get(name)) {
+            Some(property) => validate_at(property, value, &field_path, out),
+            None => {
+                if let Some(additional) = schema.get("additionalProperties") {
+                    validate_at(additional, value, &field_path, out);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_weighing_schema() {
+        let schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "required": ["weight_kg", "scale_id"],
+            "properties": {
+                "weight_kg": {"type": "number", "exclusiveMinimum": 0, "maximum": 2000},
+                "scale_id": {"type": "string", "minLength": 3},
+                "weighed_on": {"type": "string", "format": "date"},
+                "method": {"enum": ["electronic", "tape"]},
+                "tags": {"type": "array", "items": {"type": "string"}, "maxItems": 3}
+            },
+            "additionalProperties": false
+        });
+        assert!(check_schema(&schema).is_ok());
+        assert!(check_schema(&json!({"type": "object", "pattern": "^a"}))
+            .unwrap_err()
+            .contains("unsupported keyword 'pattern'"));
+        assert!(check_schema(&json!({"type": "decimal"})).is_err());
+
+        let valid = json!({
+            "weight_kg": 412.5,
+            "scale_id": "SC-01",
+            "weighed_on": "2026-10-01",
+            "method": "electronic",
+            "tags": ["lot-7"]
+        });
+        assert!(validate(&schema, &valid).is_empty());
+
+        let invalid = json!({
+            "weight_kg": 0,
+            "weighed_on": "01/10/2026",
+            "method": "guess",
+            "tags": ["a", 2],
+            "operator": "x"
+        });
+        let mut paths: Vec<String> = validate(&schema, &invalid)
+            .into_iter()
+            .map(|v| format!("{} {}", v.path, v.message))
+            .collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "$.method must be one of [\"electronic\",\"tape\"]",
+                "$.operator no value is allowed here",
+                "$.scale_id is required",
+                "$.tags[1] expected string",
+                "$.weighed_on must be a valid date",
+                "$.weight_kg must be greater than 0",
+            ]
+        );
+    }
+}
@@ -0,0 +1,451 @@
+//! Ed25519 signing of events by the users asserting them.
+//!
+//! Users register public keys for themselves or their workspace, proving
+//! possession by signing `registration_message`. An event created with a
+//! signature over its `Event::signing_hash` keeps the signature and public key,
+//! provided the key is registered, unrevoked and belongs to the event's source
+//! or the source's workspace. Anyone holding the event and the signer's public
+//! key can check the signature with `verify_event_signature`, without the
+//! database.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{Event, EventSigningKey, SigningKeyOwner};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub enum EventSigningError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+    NotFound(String),
+    InvalidSignature(String),
+}
+
+impl From<StorageError> for EventSigningError {
+    fn from(err: StorageError) -> Self {
+        EventSigningError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for EventSigningError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EventSigningError::StorageError(e) => write!(f, "Storage error: {e}"),
+            EventSigningError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            EventSigningError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            EventSigningError::NotFound(e) => write!(f, "Not found: {e}"),
+            EventSigningError::InvalidSignature(e) => write!(f, "Invalid signature: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for EventSigningError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegisterSigningKeyInput {
+    /// Hex-encoded ed25519 public key
+    pub public_key: String,
+    /// Register for the caller's workspace instead of the caller alone
+    #[serde(default)]
+    pub for_workspace: bool,
+    /// Hex-encoded signature over `registration_message(user_id, public_key)`
+    pub proof: String,
+}
+
+/// Signature sent along with an event being created
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventSignatureInput {
+    /// Hex-encoded ed25519 public key
+    pub signer_public_key: String,
+    /// Hex-encoded ed25519 signature over the event's signing hash
+    pub signature: String,
+}
+
+/// Result of checking a stored event's signature
+#[derive(Debug, Clone, Serialize)]
+pub struct EventSignatureVerification {
+    pub event_id: Uuid,
+    pub signing_hash: String,
+    pub signed: bool,
+    pub signature_valid: bool,
+    pub signature: Option<String>,
+    pub signer_public_key: Option<String>,
+    /// Registration of the signer's key, if it is still on record
+    pub key: Option<EventSigningKey>,
+}
+
+/// Message a user signs to prove they hold the key they register
+pub fn registration_message(user_id: &str, public_key: &str) -> String {
+    format!(
+        "defarm event signing key registration:{user_id}:{}",
+        public_key.to_lowercase()
+    )
+}
+
+/// Whether the event carries a signature that verifies against the public key it
+/// names. Says nothing about who owns that key.
+pub fn verify_event_signature(event: &Event) -> bool {
+    match (&event.signer_public_key, &event.signature) {
+        (Some(public_key), Some(signature)) => {
+            verify_signature(public_key, signature, &event.signing_hash()).is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Check that `source` may sign with the key in `input` and that it signed
+/// `signing_hash`. Returns the registered key.
+pub fn authorize_event_signature<S: StorageBackend>(
+    storage: &S,
+    source: &str,
+    signing_hash: &str,
+    input: &EventSignatureInput,
+) -> Result<EventSigningKey, EventSigningError> {
+    let public_key = input.signer_public_key.to_lowercase();
+    let workspace_id = storage
+        .get_user_account(source)?
+        .and_then(|account| account.workspace_id);
+    let key = storage
+        .list_event_signing_keys()?
+        .into_iter()
+        .find(|key| {
+            key.public_key == public_key
+                && key.revoked_at.is_none()
+                && match &key.owner {
+                    SigningKeyOwner::User { user_id } => user_id == source,
+                    SigningKeyOwner::Workspace {
+                        workspace_id: owner,
+                    } => workspace_id.as_deref() == Some(owner.as_str()),
+                }
+        })
+        .ok_or_else(|| {
+            EventSigningError::PermissionDenied(
+                "Signer key is not an active key of the event source or its workspace".to_string(),
+            )
+        })?;
+    verify_signature(&public_key, &input.signature, signing_hash)?;
+    Ok(key)
+}
+
+pub struct EventSigningEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> EventSigningEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    pub fn register_key(
+        &self,
+        user_id: &str,
+        input: RegisterSigningKeyInput,
+        now: DateTime<Utc>,
+    ) -> Result<EventSigningKey, EventSigningError> {
+        let public_key = input.public_key.to_lowercase();
+        verify_signature(
+            &public_key,
+            &input.proof,
+            &registration_message(user_id, &public_key),
+        )?;
+
+        let owner = if input.for_workspace {
+            let workspace_id = self
+                .storage
+                .get_user_account(user_id)?
+                .and_then(|account| account.workspace_id)
+                .ok_or_else(|| {
+                    EventSigningError::ValidationError(
+                        "Workspace keys need a user with a workspace".to_string(),
+                    )
+                })?;
+            SigningKeyOwner::Workspace { workspace_id }
+        } else {
+            SigningKeyOwner::User {
+                user_id: user_id.to_string(),
+            }
+        };
+        if self
+            .storage
+            .list_event_signing_keys()?
+            .iter()
+            .any(|key| key.public_key == public_key && key.revoked_at.is_none())
+        {
+            return Err(EventSigningError::ValidationError(
+                "Public key is already registered".to_string(),
+            ));
+        }
+
+        let key = EventSigningKey {
+            key_id: Uuid::new_v4(),
+            owner,
+            public_key,
+            registered_by: user_id.to_string(),
+            registered_at: now,
+            revoked_at: None,
+        };
+        self.storage.store_event_signing_key(&key)?;
+        Ok(key)
+    }
+
+    /// Keys the user can sign with: their own and their workspace's
+    pub fn list_keys(&self, user_id: &str) -> Result<Vec<EventSigningKey>, EventSigningError> {
+        let workspace_id = self.workspace_of(user_id)?;
+        Ok(self
+            .storage
+            .list_event_signing_keys()?
+            .into_iter()
+            .filter(|key| Self::usable_by(key, user_id, workspace_id.as_deref()))
+            .collect())
+    }
+
+    /// Any user who can sign with a key can revoke it
+    pub fn revoke_key(
+        &self,
+        user_id: &str,
+        key_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<EventSigningKey, EventSigningError> {
+        let mut key = self
+            .storage
+            .get_event_signing_key(key_id)?
+            .ok_or_else(|| EventSigningError::NotFound(format!("Signing key {key_id}")))?;
+        let workspace_id = self.workspace_of(user_id)?;
+        if !Self::usable_by(&key, user_id, workspace_id.as_deref()) {
+            return Err(EventSigningError::PermissionDenied(
+                "Only the key's owner can revoke it".to_string(),
+            ));
+        }
+        if key.revoked_at.is_none() {
+            key.revoked_at = Some(now);
+            self.storage.store_event_signing_key(&key)?;
+        }
+        Ok(key)
+    }
+
+    pub fn verify_event(
+        &self,
+        event: &Event,
+    ) -> Result<EventSignatureVerification, EventSigningError> {
+        let key = match &event.signer_public_key {
+            Some(public_key) => {
+                let public_key = public_key.to_lowercase();
+                self.storage
+                    .list_event_signing_keys()?
+                    .into_iter()
+                    .find(|key| key.public_key == public_key)
+            }
+            None => None,
+        };
+        Ok(EventSignatureVerification {
+            event_id: event.event_id,
+            signing_hash: event.signing_hash(),
+            signed: event.signature.is_some(),
+            signature_valid: verify_event_signature(event),
+            signature: event.signature.clone(),
+            signer_public_key: event.signer_public_key.clone(),
+            key,
+        })
+    }
+
+    fn workspace_of(&self, user_id: &str) -> Result<Option<String>, EventSigningError> {
+        Ok(self
+            .storage
+            .get_user_account(user_id)?
+            .and_then(|account| account.workspace_id))
+    }
+
+    fn usable_by(key: &EventSigningKey, user_id: &str, workspace_id: Option<&str>) -> bool {
+        match &key.owner {
+            SigningKeyOwner::User { user_id: owner } => owner == user_id,
+            SigningKeyOwner::Workspace {
+                workspace_id: owner,
+            } => workspace_id == Some(owner.as_str()),
+        }
+    }
+}
+
+fn verify_signature(
+    public_key_hex: &str,
+    signature_hex: &str,
+    message: &str,
+) -> Result<(), EventSigningError> {
+    let key_bytes: [u8; 32] = hex::decode(public_key_hex)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| {
+            EventSigningError::InvalidSignature(
+                "public key must be a 32-byte hex-encoded ed25519 key".to_string(),
+            )
+        })?;
+    let signature_bytes: [u8; 64] = hex::decode(signature_hex)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| {
+            EventSigningError::InvalidSignature(
+                "signature must be a 64-byte hex-encoded ed25519 signature".to_string(),
+            )
+        })?;
+
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| EventSigningError::InvalidSignature(format!("Invalid public key: {e}")))?;
+    verifying_key
+        .verify(message.as_bytes(), &Signature::from_bytes(&signature_bytes))
+        .map_err(|_| EventSigningError::InvalidSignature("Signature does not match".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events_engine::EventsEngine;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{
+        AccountStatus, EventType, EventVisibility, TierLimits, UserAccount, UserTier,
+    };
+    use ed25519_dalek::{Signer, SigningKey};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    fn account(user_id: &str, workspace_id: &str) -> UserAccount {
+        UserAccount {
+            user_id: user_id.to_string(),
+            username: user_id.to_string(),
+            email: format!("{user_id}@example.com"),
+            password_hash: "hash".to_string(),
+            limits: TierLimits::for_tier(&UserTier::Professional),
+            tier: UserTier::Professional,
+            status: AccountStatus::Active,
+            credits: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_login: None,
+            subscription: None,
+            is_admin: false,
+            workspace_id: Some(workspace_id.to_string()),
+            available_adapters: None,
+            locale: None,
+        }
+    }
+
+    #[test]
+    fn test_signed_events_need_a_registered_key_of_the_source() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        storage
+            .store_user_account(&account("user-1", "ws-1"))
+            .unwrap();
+        storage
+            .store_user_account(&account("user-2", "ws-1"))
+            .unwrap();
+        storage
+            .store_user_account(&account("user-3", "ws-2"))
+            .unwrap();
+
+        let signing_key = SigningKey::from_bytes(&[11u8; 32]);
+        let public_key = hex::encode(signing_key.verifying_key().to_bytes());
+        let proof = |user_id: &str| {
+            hex::encode(
+                signing_key
+                    .sign(registration_message(user_id, &public_key).as_bytes())
+                    .to_bytes(),
+            )
+        };
+
+        let engine = EventSigningEngine::new(Arc::clone(&storage));
+        // Proof made for another user does not register the key
+        assert!(engine
+            .register_key(
+                "user-1",
+                RegisterSigningKeyInput {
+                    public_key: public_key.clone(),
+                    for_workspace: true,
+                    proof: proof("user-2"),
+                },
+                Utc::now(),
+            )
+            .is_err());
+        let key = engine
+            .register_key(
+                "user-1",
+                RegisterSigningKeyInput {
+                    public_key: public_key.clone(),
+                    for_workspace: true,
+                    proof: proof("user-1"),
+                },
+                Utc::now(),
+            )
+            .unwrap();
+        assert_eq!(engine.list_keys("user-2").unwrap().len(), 1);
+        assert!(engine.list_keys("user-3").unwrap().is_empty());
+
+        let metadata = HashMap::from([("weight_kg".to_string(), serde_json::json!(410))]);
+        let sign = |source: &str| {
+            let hash = Event::calculate_signing_hash(
+                "DFID-SIGNED",
+                &EventType::Updated,
+                source,
+                &metadata,
+                None,
+            );
+            EventSignatureInput {
+                signer_public_key: public_key.clone(),
+                signature: hex::encode(signing_key.sign(hash.as_bytes()).to_bytes()),
+            }
+        };
+
+        let mut events = EventsEngine::new(Arc::clone(&storage));
+        let created = events
+            .create_signed_event(
+                "DFID-SIGNED".to_string(),
+                EventType::Updated,
+                "user-2".to_string(),
+                EventVisibility::Public,
+                metadata.clone(),
+                None,
+                sign("user-2"),
+            )
+            .unwrap();
+        assert!(verify_event_signature(&created.event));
+        let verification = engine.verify_event(&created.event).unwrap();
+        assert!(verification.signature_valid);
+        assert_eq!(verification.key.unwrap().key_id, key.key_id);
+        assert!(events
+            .add_event_metadata(&created.event.event_id, HashMap::new())
+            .is_err());
+
+        // Outside the key's workspace, or with a tampered event, the signature fails
+        assert!(events
+            .create_signed_event(
+                "DFID-SIGNED".to_string(),
+                EventType::Updated,
+                "user-3".to_string(),
+                EventVisibility::Public,
+                metadata.clone(),
+                None,
+                sign("user-3"),
+            )
+            .is_err());
+        let mut tampered = created.event.clone();
+        tampered
+            .metadata
+            .insert("weight_kg".to_string(), serde_json::json!(500));
+        assert!(!verify_event_signature(&tampered));
+
+        engine
+            .revoke_key("user-2", &key.key_id, Utc::now())
+            .unwrap();
+        assert!(events
+            .create_signed_event(
+                "DFID-SIGNED".to_string(),
+                EventType::Updated,
+                "user-1".to_string(),
+                EventVisibility::Public,
+                metadata.clone(),
+                None,
+                sign("user-1"),
+            )
+            .is_err());
+    }
+}
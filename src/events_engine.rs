@@ -1,19 +1,141 @@
+use crate::adapter_manager::AdapterManager;
+use crate::change_feed_engine::record_event_change;
+use crate::event_schema;
+use crate::event_signing_engine::{authorize_event_signature, EventSignatureInput};
+use crate::hashing;
+use crate::ipfs_client::IpfsClient;
+use crate::lifecycle_engine::record_lifecycle_transition;
+use crate::live_stream::{LiveRecord, LiveStream};
 use crate::logging::LoggingEngine;
+use crate::merkle_engine::hash_event;
+use crate::merkle_tree::MerkleTree;
+use crate::pagination::{collect_page, Page, PageCursor};
+use crate::payload_limits::{self, PayloadLimitError};
 use crate::postgres_persistence::PostgresPersistence;
+use crate::snapshot_types::{SnapshotEntityType, SnapshotOperation, StateSnapshot};
 use crate::storage::StorageBackend;
-use crate::types::{Event, EventCreationResult, EventType, EventVisibility};
-use chrono::{DateTime, Utc};
+use crate::types::{
+    validate_occurred_at, CompactedItemState, CustomEventType, Event, EventAttachment,
+    EventCausality, EventChain, EventCreationResult, EventSchema, EventType, EventVisibility, Item,
+    ItemReplay, ItemStatus, Permission, ReplaySkip, SchemaViolation, TimeAxis,
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Deserialize;
 use serde_json;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+/// Identical events recorded within this many seconds of each other are one
+/// event: client retries and re-ingested data return the original
+pub const DEFAULT_DEDUP_WINDOW_SECS: i64 = 24 * 60 * 60;
+
+/// Largest file accepted as an event attachment
+pub const MAX_ATTACHMENT_BYTES: usize = 25 * 1024 * 1024;
+
+/// Metadata keys system events use to describe themselves rather than item data
+const BOOKKEEPING_KEYS: [&str; 9] = [
+    "identifiers",
+    "enriched_keys",
+    "merge_report",
+    "source_entry",
+    "conflict_id",
+    "merged_from",
+    "circuit_id",
+    "requester_id",
+    "operation",
+];
+/// How deep a replay follows merges into the merged items' own histories
+const MAX_REPLAY_DEPTH: usize = 8;
+/// Causal links followed from an event in either direction
+pub const MAX_CHAIN_DEPTH: usize = 64;
+/// Unsnapshotted events an item accumulates before the compactor folds them
+pub const DEFAULT_COMPACTION_THRESHOLD: usize = 500;
+const MAX_CUSTOM_TYPE_NAME_LEN: usize = 64;
+/// Events accepted in one bulk upload
+pub const MAX_BULK_EVENTS: usize = 1000;
+
+/// One event of a bulk upload; the source is shared by the whole batch
+#[derive(Debug, Clone)]
+pub struct BulkEventInput {
+    pub dfid: String,
+    pub event_type: EventType,
+    pub visibility: EventVisibility,
+    pub metadata: HashMap<String, serde_json::Value>,
+    pub occurred_at: Option<DateTime<Utc>>,
+    pub signature: Option<EventSignatureInput>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BulkEventResult {
+    /// In the order the events were given
+    pub results: Vec<EventCreationResult>,
+    /// Root over the events newly stored; None when every event was a duplicate
+    pub merkle_root: Option<String>,
+}
+
+/// A validated event ready to store, or the stored event it repeats
+enum PreparedEvent {
+    New(Event),
+    Duplicate(Event),
+}
+
+fn created(event: Event) -> EventCreationResult {
+    EventCreationResult {
+        event,
+        was_deduplicated: false,
+        original_event_id: None,
+        was_merged: false,
+        merged_keys: vec![],
+    }
+}
+
+fn deduplicated(existing: Event) -> EventCreationResult {
+    EventCreationResult {
+        original_event_id: Some(existing.event_id),
+        event: existing,
+        was_deduplicated: true,
+        was_merged: false,
+        merged_keys: vec![],
+    }
+}
+
+/// Point a validation failure at the event of the batch that caused it
+fn bulk_error(index: usize, error: EventsError) -> EventsError {
+    match error {
+        EventsError::ValidationError(e) => {
+            EventsError::ValidationError(format!("Event {index}: {e}"))
+        }
+        EventsError::PermissionDenied(e) => {
+            EventsError::PermissionDenied(format!("Event {index}: {e}"))
+        }
+        EventsError::SchemaViolation(violations) => EventsError::SchemaViolation(
+            violations
+                .into_iter()
+                .map(|violation| SchemaViolation {
+                    path: format!(
+                        "$[{index}].metadata{}",
+                        violation.path.trim_start_matches('$')
+                    ),
+                    message: violation.message,
+                })
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
 #[derive(Debug)]
 pub enum EventsError {
     StorageError(String),
     EncryptionError(String),
     ValidationError(String),
+    /// Metadata does not match a circuit's schema for the event type
+    SchemaViolation(Vec<SchemaViolation>),
+    /// Payload exceeds a limit of the source's tier
+    PayloadTooLarge(PayloadLimitError),
+    PermissionDenied(String),
     NotFound,
 }
 
@@ -23,6 +145,19 @@ impl std::fmt::Display for EventsError {
             EventsError::StorageError(e) => write!(f, "Storage error: {e}"),
             EventsError::EncryptionError(e) => write!(f, "Encryption error: {e}"),
             EventsError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            EventsError::SchemaViolation(violations) => {
+                let details: Vec<String> = violations
+                    .iter()
+                    .map(|v| format!("{} {}", v.path, v.message))
+                    .collect();
+                write!(
+                    f,
+                    "Metadata does not match event schema: {}",
+                    details.join("; ")
+                )
+            }
+            EventsError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            EventsError::PayloadTooLarge(e) => write!(f, "Payload limit exceeded: {e}"),
             EventsError::NotFound => write!(f, "Event not found"),
         }
     }
@@ -30,10 +165,31 @@ impl std::fmt::Display for EventsError {
 
 impl std::error::Error for EventsError {}
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomEventTypeInput {
+    pub name: String,
+    pub display_name: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+}
+
+/// Circuits whose event schemas and custom event types apply to an event
+enum SchemaScope<'a> {
+    /// Every circuit holding the item
+    Item(&'a str),
+    /// The circuit the event is being pushed to
+    Circuit(Uuid),
+}
+
 pub struct EventsEngine<S: StorageBackend> {
     storage: S,
     logger: Arc<std::sync::Mutex<LoggingEngine>>,
     postgres: Option<Arc<RwLock<Option<PostgresPersistence>>>>,
+    live_stream: Option<LiveStream>,
+    dedup_window: Duration,
+    /// Time new events are stamped with and deduplicated against
+    clock: Arc<dyn Fn() -> DateTime<Utc> + Send + Sync>,
 }
 
 impl<S: StorageBackend + 'static> EventsEngine<S> {
@@ -43,14 +199,48 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
             storage,
             logger: Arc::new(std::sync::Mutex::new(logger)),
             postgres: None,
+            live_stream: None,
+            dedup_window: Duration::seconds(DEFAULT_DEDUP_WINDOW_SECS),
+            clock: Arc::new(Utc::now),
         }
     }
 
+    /// Read the current time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: impl Fn() -> DateTime<Utc> + Send + Sync + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// How long a resubmitted identical event counts as a retry of the first
+    /// rather than a new occurrence
+    pub fn with_dedup_window(mut self, dedup_window: Duration) -> Self {
+        self.dedup_window = dedup_window;
+        self
+    }
+
+    pub fn set_dedup_window(&mut self, dedup_window: Duration) {
+        self.dedup_window = dedup_window;
+    }
+
+    pub fn dedup_window(&self) -> Duration {
+        self.dedup_window
+    }
+
     pub fn with_postgres(mut self, postgres: Arc<RwLock<Option<PostgresPersistence>>>) -> Self {
         self.postgres = Some(postgres);
         self
     }
 
+    pub fn with_live_stream(mut self, live_stream: LiveStream) -> Self {
+        self.live_stream = Some(live_stream);
+        self
+    }
+
+    /// Publish newly stored events to `/api/stream` subscribers
+    pub fn set_live_stream(&mut self, live_stream: LiveStream) {
+        self.live_stream = Some(live_stream);
+    }
+
     /// Create event without metadata (backward compatible)
     pub fn create_event(
         &mut self,
@@ -59,9 +249,18 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
         source: String,
         visibility: EventVisibility,
     ) -> Result<Event, EventsError> {
-        // Delegate to create_event_with_metadata with empty metadata
-        let result =
-            self.create_event_with_metadata(dfid, event_type, source, visibility, HashMap::new())?;
+        // System-generated events carry no producer metadata, so circuit
+        // schemas do not apply to them
+        let result = self.store_new_event(
+            dfid,
+            event_type,
+            source,
+            visibility,
+            HashMap::new(),
+            None,
+            false,
+            None,
+        )?;
         Ok(result.event)
     }
 
@@ -75,8 +274,259 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
         visibility: EventVisibility,
         metadata: HashMap<String, serde_json::Value>,
     ) -> Result<EventCreationResult, EventsError> {
+        self.create_event_with_occurred_at(dfid, event_type, source, visibility, metadata, None)
+    }
+
+    /// Create event that may describe a past fact. `occurred_at` is validated for
+    /// plausibility, kept next to the recording `timestamp`, and widens the item's
+    /// occurrence window.
+    pub fn create_event_with_occurred_at(
+        &mut self,
+        dfid: String,
+        event_type: EventType,
+        source: String,
+        visibility: EventVisibility,
+        metadata: HashMap<String, serde_json::Value>,
+        occurred_at: Option<DateTime<Utc>>,
+    ) -> Result<EventCreationResult, EventsError> {
+        self.store_new_event(
+            dfid,
+            event_type,
+            source,
+            visibility,
+            metadata,
+            occurred_at,
+            true,
+            None,
+        )
+    }
+
+    /// Create an event signed by its source. The signature must be over
+    /// `Event::calculate_signing_hash` of these fields, made with a registered,
+    /// unrevoked key of the source or the source's workspace.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_signed_event(
+        &mut self,
+        dfid: String,
+        event_type: EventType,
+        source: String,
+        visibility: EventVisibility,
+        metadata: HashMap<String, serde_json::Value>,
+        occurred_at: Option<DateTime<Utc>>,
+        signature: EventSignatureInput,
+    ) -> Result<EventCreationResult, EventsError> {
+        self.store_new_event(
+            dfid,
+            event_type,
+            source,
+            visibility,
+            metadata,
+            occurred_at,
+            true,
+            Some(signature),
+        )
+    }
+
+    /// Create a set of events from one source. Every event is validated before
+    /// any is stored, so one bad event rejects the whole batch. The events
+    /// stored are hashed into a Merkle tree whose root the caller anchors.
+    pub fn create_events_bulk(
+        &mut self,
+        source: String,
+        inputs: Vec<BulkEventInput>,
+    ) -> Result<BulkEventResult, EventsError> {
+        if inputs.is_empty() || inputs.len() > MAX_BULK_EVENTS {
+            return Err(EventsError::ValidationError(format!(
+                "A batch holds between 1 and {MAX_BULK_EVENTS} events"
+            )));
+        }
+        for (index, input) in inputs.iter().enumerate() {
+            self.validate_new_event(
+                &input.dfid,
+                &input.event_type,
+                &source,
+                &input.metadata,
+                input.occurred_at,
+                true,
+                input.signature.as_ref(),
+            )
+            .map_err(|e| bulk_error(index, e))?;
+        }
+
+        // Stage the whole batch, then store it in one write so a storage
+        // failure leaves none of it behind
+        let mut prepared = Vec::with_capacity(inputs.len());
+        let mut staged: Vec<Event> = Vec::new();
+        for input in inputs {
+            let event = self.prepare_event(
+                input.dfid,
+                input.event_type,
+                source.clone(),
+                input.visibility,
+                input.metadata,
+                input.occurred_at,
+                input.signature,
+                &staged,
+            );
+            if let PreparedEvent::New(event) = &event {
+                staged.push(event.clone());
+            }
+            prepared.push(event);
+        }
+        self.storage
+            .store_events(&staged)
+            .map_err(|e| EventsError::StorageError(format!("Stored none of the events: {e}")))?;
+
+        let mut results = Vec::with_capacity(prepared.len());
+        for event in prepared {
+            results.push(match event {
+                PreparedEvent::Duplicate(existing) => deduplicated(existing),
+                PreparedEvent::New(event) => {
+                    self.finish_event(&event)?;
+                    created(event)
+                }
+            });
+        }
+
+        let leaves = results
+            .iter()
+            .filter(|result| !result.was_deduplicated)
+            .map(|result| {
+                (
+                    hash_event(&result.event),
+                    Some(result.event.event_id.to_string()),
+                )
+            })
+            .collect();
+        let merkle_root = MerkleTree::from_leaves_with_ids(leaves)
+            .root()
+            .map(str::to_string);
+
+        Ok(BulkEventResult {
+            results,
+            merkle_root,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn store_new_event(
+        &mut self,
+        dfid: String,
+        event_type: EventType,
+        source: String,
+        visibility: EventVisibility,
+        metadata: HashMap<String, serde_json::Value>,
+        occurred_at: Option<DateTime<Utc>>,
+        check_schemas: bool,
+        signature: Option<EventSignatureInput>,
+    ) -> Result<EventCreationResult, EventsError> {
+        self.validate_new_event(
+            &dfid,
+            &event_type,
+            &source,
+            &metadata,
+            occurred_at,
+            check_schemas,
+            signature.as_ref(),
+        )?;
+        self.insert_event(
+            dfid,
+            event_type,
+            source,
+            visibility,
+            metadata,
+            occurred_at,
+            signature,
+        )
+    }
+
+    /// Everything that can reject an event before it is stored
+    #[allow(clippy::too_many_arguments)]
+    fn validate_new_event(
+        &self,
+        dfid: &str,
+        event_type: &EventType,
+        source: &str,
+        metadata: &HashMap<String, serde_json::Value>,
+        occurred_at: Option<DateTime<Utc>>,
+        check_schemas: bool,
+        signature: Option<&EventSignatureInput>,
+    ) -> Result<(), EventsError> {
+        if let Some(occurred_at) = occurred_at {
+            validate_occurred_at(occurred_at, Utc::now()).map_err(EventsError::ValidationError)?;
+        }
+        let (tier, limits) = payload_limits::limits_for_source(&self.storage, source);
+        if let Err(e) = payload_limits::check_metadata(&limits, metadata) {
+            payload_limits::record_rejection(&tier, source, &e);
+            return Err(EventsError::PayloadTooLarge(e));
+        }
+        self.check_custom_event_type(event_type, SchemaScope::Item(dfid))?;
+        if check_schemas {
+            self.validate_event_metadata(event_type, metadata, SchemaScope::Item(dfid))?;
+        }
+        if let Some(signature) = signature {
+            let signing_hash =
+                Event::calculate_signing_hash(dfid, event_type, source, metadata, occurred_at);
+            authorize_event_signature(&self.storage, source, &signing_hash, signature)
+                .map_err(|e| EventsError::PermissionDenied(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Store a validated event, or return the duplicate it repeats
+    #[allow(clippy::too_many_arguments)]
+    fn insert_event(
+        &mut self,
+        dfid: String,
+        event_type: EventType,
+        source: String,
+        visibility: EventVisibility,
+        metadata: HashMap<String, serde_json::Value>,
+        occurred_at: Option<DateTime<Utc>>,
+        signature: Option<EventSignatureInput>,
+    ) -> Result<EventCreationResult, EventsError> {
+        let event = match self.prepare_event(
+            dfid,
+            event_type,
+            source,
+            visibility,
+            metadata,
+            occurred_at,
+            signature,
+            &[],
+        ) {
+            PreparedEvent::Duplicate(existing) => return Ok(deduplicated(existing)),
+            PreparedEvent::New(event) => event,
+        };
+
+        // Store in storage first
+        self.storage
+            .store_event(&event)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?;
+        self.finish_event(&event)?;
+
+        Ok(created(event))
+    }
+
+    /// Build the event a validated input creates, or find the duplicate it
+    /// repeats in storage or among `staged` events not yet stored
+    #[allow(clippy::too_many_arguments)]
+    fn prepare_event(
+        &self,
+        dfid: String,
+        event_type: EventType,
+        source: String,
+        visibility: EventVisibility,
+        metadata: HashMap<String, serde_json::Value>,
+        occurred_at: Option<DateTime<Utc>>,
+        signature: Option<EventSignatureInput>,
+        staged: &[Event],
+    ) -> PreparedEvent {
         // Calculate dedup hash BEFORE creating the event
-        let dedup_hash = Event::calculate_dedup_hash(&dfid, &event_type, &source, &metadata);
+        let dedup_hash = Event::occurrence_dedup_hash(
+            &Event::calculate_dedup_hash(&dfid, &event_type, &source, &metadata),
+            occurred_at,
+        );
 
         self.logger
             .lock()
@@ -92,7 +542,18 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
             .with_context("dedup_hash", dedup_hash.clone());
 
         // Check for existing event with same content hash (deduplication)
-        if let Ok(Some(existing_event)) = self.storage.get_event_by_content_hash(&dedup_hash) {
+        let now = (self.clock)();
+        let event_id = self.idempotent_event_id(&dedup_hash, occurred_at, now);
+        let existing = staged
+            .iter()
+            .find(|event| event.content_hash == dedup_hash)
+            .cloned()
+            .or_else(|| self.find_duplicate(&dedup_hash, occurred_at, now))
+            .or_else(|| {
+                // Recorded elsewhere (another node, a re-ingest) under the same id
+                self.storage.get_event(&event_id).ok().flatten()
+            });
+        if let Some(existing_event) = existing {
             self.logger
                 .lock()
                 .unwrap()
@@ -104,13 +565,7 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
                 .with_context("existing_event_id", existing_event.event_id.to_string())
                 .with_context("content_hash", dedup_hash);
 
-            return Ok(EventCreationResult {
-                event: existing_event.clone(),
-                was_deduplicated: true,
-                original_event_id: Some(existing_event.event_id),
-                was_merged: false,
-                merged_keys: vec![],
-            });
+            return PreparedEvent::Duplicate(existing_event);
         }
 
         // No duplicate found, create new event
@@ -121,6 +576,15 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
             visibility.clone(),
             metadata,
         );
+        if let Some(occurred_at) = occurred_at {
+            event = event.with_occurred_at(occurred_at);
+        }
+        event.event_id = event_id;
+        event.timestamp = now;
+        if let Some(signature) = signature {
+            event.signature = Some(signature.signature.to_lowercase());
+            event.signer_public_key = Some(signature.signer_public_key.to_lowercase());
+        }
 
         if matches!(visibility, EventVisibility::Private) {
             event.encrypt();
@@ -135,10 +599,21 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
                 .with_context("event_id", event.event_id.to_string());
         }
 
-        // Store in storage first
-        self.storage
-            .store_event(&event)
-            .map_err(|e| EventsError::StorageError(e.to_string()))?;
+        PreparedEvent::New(event)
+    }
+
+    /// Follow-up of a stored event: stream, change feed, lifecycle, item
+    /// occurrence and the PostgreSQL write-through
+    fn finish_event(&self, event: &Event) -> Result<(), EventsError> {
+        if let Some(live_stream) = &self.live_stream {
+            live_stream.publish(LiveRecord::Event(event.clone()));
+        }
+        record_event_change(&self.storage, event);
+        record_lifecycle_transition(&self.storage, event);
+
+        if let Some(occurred_at) = event.occurred_at {
+            self.record_item_occurrence(&event.dfid, occurred_at)?;
+        }
 
         self.logger
             .lock()
@@ -149,7 +624,7 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
                 "Event created successfully",
             )
             .with_context("event_id", event.event_id.to_string())
-            .with_context("dfid", dfid.clone())
+            .with_context("dfid", event.dfid.clone())
             .with_context("was_deduplicated", "false".to_string());
 
         // Write-through cache: Persist to PostgreSQL asynchronously (non-blocking)
@@ -167,13 +642,7 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
             });
         }
 
-        Ok(EventCreationResult {
-            event,
-            was_deduplicated: false,
-            original_event_id: None,
-            was_merged: false,
-            merged_keys: vec![],
-        })
+        Ok(())
     }
 
     /// Create a local event (without DFID yet)
@@ -203,6 +672,9 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
         self.storage
             .store_event(&event)
             .map_err(|e| EventsError::StorageError(e.to_string()))?;
+        if let Some(live_stream) = &self.live_stream {
+            live_stream.publish(LiveRecord::Event(event.clone()));
+        }
 
         // Write-through cache: Persist to PostgreSQL asynchronously
         if let Some(pg_ref) = &self.postgres {
@@ -261,6 +733,12 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
                 "Event is not a local event".to_string(),
             ));
         }
+        self.check_custom_event_type(&event.event_type, SchemaScope::Circuit(circuit_id))?;
+        self.validate_event_metadata(
+            &event.event_type,
+            &event.metadata,
+            SchemaScope::Circuit(circuit_id),
+        )?;
 
         // Calculate dedup hash with the new DFID to check for duplicates
         let dedup_hash = Event::calculate_dedup_hash(
@@ -271,7 +749,9 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
         );
 
         // Check for existing event with same content (deduplication)
-        if let Ok(Some(mut existing_event)) = self.storage.get_event_by_content_hash(&dedup_hash) {
+        if let Some(mut existing_event) =
+            self.find_duplicate(&dedup_hash, event.occurred_at, (self.clock)())
+        {
             // Auto-merge: merge new metadata into existing event
             let merged_keys = existing_event.merge_metadata(event.metadata.clone());
 
@@ -395,6 +875,12 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
             .get_event(event_id)
             .map_err(|e| EventsError::StorageError(e.to_string()))?
             .ok_or(EventsError::NotFound)?;
+        // The signature covers the metadata as signed
+        if event.signature.is_some() {
+            return Err(EventsError::ValidationError(
+                "Signed events cannot take additional metadata".to_string(),
+            ));
+        }
 
         for (key, value) in metadata {
             event.add_metadata(key.clone(), value.clone());
@@ -413,69 +899,932 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
         Ok(event)
     }
 
-    pub fn get_events_for_item(&self, dfid: &str) -> Result<Vec<Event>, EventsError> {
-        self.storage
-            .get_events_by_dfid(dfid)
-            .map_err(|e| EventsError::StorageError(e.to_string()))
+    /// Record an event the system derives from an operation and place it in the
+    /// causal graph. Its metadata is bookkeeping, so circuit schemas do not apply.
+    pub fn create_linked_event(
+        &mut self,
+        dfid: String,
+        event_type: EventType,
+        source: String,
+        visibility: EventVisibility,
+        metadata: HashMap<String, serde_json::Value>,
+        causality: EventCausality,
+    ) -> Result<Event, EventsError> {
+        let result = self.store_new_event(
+            dfid, event_type, source, visibility, metadata, None, false, None,
+        )?;
+        if causality == EventCausality::default() {
+            return Ok(result.event);
+        }
+        self.link_event(&result.event.event_id, causality)
     }
 
-    pub fn get_events_by_type(&self, event_type: EventType) -> Result<Vec<Event>, EventsError> {
-        self.storage
-            .get_events_by_type(event_type)
-            .map_err(|e| EventsError::StorageError(e.to_string()))
-    }
+    /// Place an event in the causal graph. The cause must exist and must not
+    /// itself follow from the event; unset fields of `causality` are kept.
+    pub fn link_event(
+        &mut self,
+        event_id: &Uuid,
+        causality: EventCausality,
+    ) -> Result<Event, EventsError> {
+        let mut event = self
+            .storage
+            .get_event(event_id)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?
+            .ok_or(EventsError::NotFound)?;
 
-    pub fn get_events_by_visibility(
-        &self,
-        visibility: EventVisibility,
-    ) -> Result<Vec<Event>, EventsError> {
-        self.storage
-            .get_events_by_visibility(visibility)
-            .map_err(|e| EventsError::StorageError(e.to_string()))
-    }
+        if let Some(cause_id) = causality.caused_by {
+            let get = |id: &Uuid| {
+                self.storage
+                    .get_event(id)
+                    .map_err(|e| EventsError::StorageError(e.to_string()))
+            };
+            let mut cursor = Some(cause_id);
+            for _ in 0..MAX_CHAIN_DEPTH {
+                let Some(id) = cursor else { break };
+                if id == *event_id {
+                    return Err(EventsError::ValidationError(
+                        "An event cannot follow from itself".to_string(),
+                    ));
+                }
+                cursor = match get(&id)? {
+                    Some(ancestor) => ancestor.caused_by,
+                    None if id == cause_id => {
+                        return Err(EventsError::ValidationError(format!(
+                            "Cause event {cause_id} not found"
+                        )))
+                    }
+                    None => None,
+                };
+            }
+            event.caused_by = Some(cause_id);
+        }
+        if causality.correlation_id.is_some() {
+            event.correlation_id = causality.correlation_id;
+        }
 
-    pub fn get_events_in_time_range(
-        &self,
-        start: DateTime<Utc>,
-        end: DateTime<Utc>,
-    ) -> Result<Vec<Event>, EventsError> {
         self.storage
-            .get_events_in_time_range(start, end)
-            .map_err(|e| EventsError::StorageError(e.to_string()))
+            .update_event(&event)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?;
+        Ok(event)
     }
 
-    pub fn get_public_events(&self) -> Result<Vec<Event>, EventsError> {
-        self.get_events_by_visibility(EventVisibility::Public)
-    }
+    /// Walk the causal graph around an event: its causes up to the root, the
+    /// events that followed from it, and the rest of its operation
+    pub fn event_chain(&self, event_id: &Uuid) -> Result<EventChain, EventsError> {
+        let get = |id: &Uuid| {
+            self.storage
+                .get_event(id)
+                .map_err(|e| EventsError::StorageError(e.to_string()))
+        };
+        let event = get(event_id)?.ok_or(EventsError::NotFound)?;
+        let mut truncated = false;
+        let mut seen = HashSet::from([event.event_id]);
+
+        let mut ancestors = Vec::new();
+        let mut cursor = event.caused_by;
+        while let Some(cause_id) = cursor {
+            if ancestors.len() == MAX_CHAIN_DEPTH || !seen.insert(cause_id) {
+                truncated = true;
+                break;
+            }
+            match get(&cause_id)? {
+                Some(cause) => {
+                    cursor = cause.caused_by;
+                    ancestors.push(cause);
+                }
+                None => {
+                    truncated = true;
+                    break;
+                }
+            }
+        }
+        ancestors.reverse();
 
-    pub fn get_private_events(&self) -> Result<Vec<Event>, EventsError> {
-        self.get_events_by_visibility(EventVisibility::Private)
+        let mut descendants = Vec::new();
+        let mut frontier = vec![event.event_id];
+        for depth in 0.. {
+            if frontier.is_empty() {
+                break;
+            }
+            if depth == MAX_CHAIN_DEPTH {
+                truncated = true;
+                break;
+            }
+            let mut next = Vec::new();
+            for id in &frontier {
+                let children = self
+                    .storage
+                    .get_events_caused_by(id)
+                    .map_err(|e| EventsError::StorageError(e.to_string()))?;
+                for child in children {
+                    if seen.insert(child.event_id) {
+                        next.push(child.event_id);
+                        descendants.push(child);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        descendants.sort_by_key(|e| (e.timestamp, e.event_id));
+
+        let mut correlated = match event.correlation_id {
+            Some(correlation_id) => self
+                .storage
+                .get_events_by_correlation_id(&correlation_id)
+                .map_err(|e| EventsError::StorageError(e.to_string()))?
+                .into_iter()
+                .filter(|e| e.event_id != event.event_id)
+                .collect(),
+            None => Vec::new(),
+        };
+        correlated.sort_by_key(|e: &Event| (e.timestamp, e.event_id));
+
+        Ok(EventChain {
+            event,
+            ancestors,
+            descendants,
+            correlated,
+            truncated,
+        })
     }
 
-    pub fn list_all_events(&self) -> Result<Vec<Event>, EventsError> {
-        self.storage
-            .list_events()
-            .map_err(|e| EventsError::StorageError(e.to_string()))
+    /// The latest event with this content hash, if it still counts as the same
+    /// occurrence: recorded within the dedup window, or declaring when it
+    /// happened (a declared occurrence is the same fact however late it is resent)
+    fn find_duplicate(
+        &self,
+        dedup_hash: &str,
+        occurred_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Option<Event> {
+        let existing = self
+            .storage
+            .get_event_by_content_hash(dedup_hash)
+            .ok()
+            .flatten()?;
+        (occurred_at.is_some() || now - existing.timestamp <= self.dedup_window).then_some(existing)
     }
 
-    pub fn get_event(&self, event_id: &Uuid) -> Result<Option<Event>, EventsError> {
-        self.storage
-            .get_event(event_id)
-            .map_err(|e| EventsError::StorageError(e.to_string()))
+    /// Event id derived from the content hash and the dedup window the event
+    /// falls in, so nodes and re-ingests creating the same event agree on its id
+    fn idempotent_event_id(
+        &self,
+        dedup_hash: &str,
+        occurred_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Uuid {
+        let window = match occurred_at {
+            Some(_) => "occurred".to_string(),
+            None => now
+                .timestamp()
+                .div_euclid(self.dedup_window.num_seconds().max(1))
+                .to_string(),
+        };
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(dedup_hash.as_bytes());
+        hasher.update(b":");
+        hasher.update(window.as_bytes());
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&hasher.finalize().as_bytes()[..16]);
+        uuid::Builder::from_custom_bytes(bytes).into_uuid()
     }
 
-    pub fn create_item_created_event(
+    /// Attach an uploaded file (see `upload_attachment`) to an event. Only the
+    /// event's source may attach; attaching the same content twice is a no-op.
+    pub fn add_attachment(
         &mut self,
-        dfid: String,
-        source: String,
-        identifiers: Vec<String>,
+        event_id: &Uuid,
+        attachment: EventAttachment,
     ) -> Result<Event, EventsError> {
-        let event = self.create_event(
-            dfid.clone(),
-            EventType::Created,
-            source,
-            EventVisibility::Public,
-        )?;
+        let mut event = self
+            .storage
+            .get_event(event_id)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?
+            .ok_or(EventsError::NotFound)?;
+        if event.source != attachment.uploaded_by {
+            return Err(EventsError::PermissionDenied(
+                "Only the event's source can attach files to it".to_string(),
+            ));
+        }
+        if event
+            .attachments
+            .iter()
+            .any(|existing| existing.content_hash == attachment.content_hash)
+        {
+            return Ok(event);
+        }
+        let (tier, limits) = payload_limits::limits_for_source(&self.storage, &event.source);
+        if let Err(e) = payload_limits::check_attachments(&limits, event.attachments.len() + 1) {
+            payload_limits::record_rejection(&tier, &event.source, &e);
+            return Err(EventsError::PayloadTooLarge(e));
+        }
+
+        self.logger
+            .lock()
+            .unwrap()
+            .info(
+                "events_engine",
+                "attachment_added",
+                "Attachment added to event",
+            )
+            .with_context("event_id", event_id.to_string())
+            .with_context("cid", attachment.cid.clone());
+        event.attachments.push(attachment);
+        self.storage
+            .update_event(&event)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?;
+
+        if let Some(pg_ref) = &self.postgres {
+            let pg = Arc::clone(pg_ref);
+            let event_clone = event.clone();
+            tokio::spawn(async move {
+                let pg_guard = pg.read().await;
+                if let Some(pg_persistence) = &*pg_guard {
+                    if let Err(e) = pg_persistence.persist_event(&event_clone).await {
+                        tracing::warn!("Failed to persist event attachment to PostgreSQL: {}", e);
+                    }
+                }
+            });
+        }
+
+        Ok(event)
+    }
+
+    /// Register a new version of a circuit's schema for `event_type`
+    pub fn register_event_schema(
+        &self,
+        circuit_id: Uuid,
+        event_type: EventType,
+        schema: serde_json::Value,
+        created_by: &str,
+    ) -> Result<EventSchema, EventsError> {
+        let circuit = self
+            .storage
+            .get_circuit(&circuit_id)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?
+            .ok_or(EventsError::NotFound)?;
+        if !circuit.has_permission(created_by, &Permission::ManagePermissions) {
+            return Err(EventsError::PermissionDenied(
+                "Only circuit managers can register event schemas".to_string(),
+            ));
+        }
+        event_schema::check_schema(&schema)
+            .map_err(|e| EventsError::ValidationError(format!("Invalid schema: {e}")))?;
+
+        let version = self
+            .list_event_schemas(&circuit_id)?
+            .iter()
+            .filter(|s| s.event_type == event_type)
+            .map(|s| s.version)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let event_schema = EventSchema {
+            schema_id: Uuid::new_v4(),
+            circuit_id,
+            event_type,
+            version,
+            schema,
+            created_by: created_by.to_string(),
+            created_at: Utc::now(),
+        };
+        self.storage
+            .store_event_schema(&event_schema)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?;
+        Ok(event_schema)
+    }
+
+    /// Every schema version registered for a circuit, oldest first
+    pub fn list_event_schemas(&self, circuit_id: &Uuid) -> Result<Vec<EventSchema>, EventsError> {
+        Ok(self
+            .storage
+            .list_event_schemas()
+            .map_err(|e| EventsError::StorageError(e.to_string()))?
+            .into_iter()
+            .filter(|s| s.circuit_id == *circuit_id)
+            .collect())
+    }
+
+    /// Register a domain event type for a circuit, or update its display
+    /// metadata if it is already registered
+    pub fn register_custom_event_type(
+        &self,
+        circuit_id: Uuid,
+        input: CustomEventTypeInput,
+        user_id: &str,
+    ) -> Result<CustomEventType, EventsError> {
+        self.require_circuit_manager(&circuit_id, user_id)?;
+        let name = input.name.trim();
+        validate_custom_type_name(name)?;
+        let display_name = input.display_name.trim();
+        if display_name.is_empty() {
+            return Err(EventsError::ValidationError(
+                "display_name is required".to_string(),
+            ));
+        }
+        if let Some(color) = &input.color {
+            let hex = color.strip_prefix('#').unwrap_or_default();
+            if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(EventsError::ValidationError(
+                    "color must be a hex color like #1e88e5".to_string(),
+                ));
+            }
+        }
+
+        let now = Utc::now();
+        let existing = self
+            .list_custom_event_types(&circuit_id)?
+            .into_iter()
+            .find(|t| t.name == name);
+        let event_type = CustomEventType {
+            circuit_id,
+            name: name.to_string(),
+            display_name: display_name.to_string(),
+            description: input.description,
+            icon: input.icon,
+            color: input.color,
+            created_by: existing
+                .as_ref()
+                .map_or_else(|| user_id.to_string(), |t| t.created_by.clone()),
+            created_at: existing.as_ref().map_or(now, |t| t.created_at),
+            updated_at: now,
+        };
+        self.storage
+            .store_custom_event_type(&event_type)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?;
+        Ok(event_type)
+    }
+
+    /// Custom event types a circuit accepts, by name
+    pub fn list_custom_event_types(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<CustomEventType>, EventsError> {
+        Ok(self
+            .storage
+            .list_custom_event_types()
+            .map_err(|e| EventsError::StorageError(e.to_string()))?
+            .into_iter()
+            .filter(|t| t.circuit_id == *circuit_id)
+            .collect())
+    }
+
+    /// Stop accepting a custom type. Events already recorded keep it.
+    pub fn remove_custom_event_type(
+        &self,
+        circuit_id: Uuid,
+        name: &str,
+        user_id: &str,
+    ) -> Result<(), EventsError> {
+        self.require_circuit_manager(&circuit_id, user_id)?;
+        if self
+            .storage
+            .delete_custom_event_type(&circuit_id, name)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?
+        {
+            Ok(())
+        } else {
+            Err(EventsError::NotFound)
+        }
+    }
+
+    fn require_circuit_manager(&self, circuit_id: &Uuid, user_id: &str) -> Result<(), EventsError> {
+        let circuit = self
+            .storage
+            .get_circuit(circuit_id)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?
+            .ok_or(EventsError::NotFound)?;
+        if circuit.has_permission(user_id, &Permission::ManagePermissions) {
+            Ok(())
+        } else {
+            Err(EventsError::PermissionDenied(
+                "Only circuit managers can change custom event types".to_string(),
+            ))
+        }
+    }
+
+    /// A custom type must be registered by a circuit the event belongs to
+    fn check_custom_event_type(
+        &self,
+        event_type: &EventType,
+        scope: SchemaScope,
+    ) -> Result<(), EventsError> {
+        let EventType::Custom(name) = event_type else {
+            return Ok(());
+        };
+        for registered in self
+            .storage
+            .list_custom_event_types()
+            .map_err(|e| EventsError::StorageError(e.to_string()))?
+            .iter()
+            .filter(|t| t.name == *name)
+        {
+            if self.scope_includes(&scope, &registered.circuit_id)? {
+                return Ok(());
+            }
+        }
+        Err(EventsError::ValidationError(format!(
+            "Event type {name} is not registered for this circuit"
+        )))
+    }
+
+    fn scope_includes(&self, scope: &SchemaScope, circuit_id: &Uuid) -> Result<bool, EventsError> {
+        Ok(match scope {
+            SchemaScope::Circuit(target) => target == circuit_id,
+            SchemaScope::Item(dfid) => self
+                .storage
+                .get_circuit_items(circuit_id)
+                .map_err(|e| EventsError::StorageError(e.to_string()))?
+                .iter()
+                .any(|item| item.dfid == *dfid),
+        })
+    }
+
+    fn validate_event_metadata(
+        &self,
+        event_type: &EventType,
+        metadata: &HashMap<String, serde_json::Value>,
+        scope: SchemaScope,
+    ) -> Result<(), EventsError> {
+        let mut latest: HashMap<Uuid, EventSchema> = HashMap::new();
+        for schema in self
+            .storage
+            .list_event_schemas()
+            .map_err(|e| EventsError::StorageError(e.to_string()))?
+            .into_iter()
+            .filter(|s| s.event_type == *event_type)
+        {
+            if latest
+                .get(&schema.circuit_id)
+                .is_none_or(|current| current.version < schema.version)
+            {
+                latest.insert(schema.circuit_id, schema);
+            }
+        }
+        if latest.is_empty() {
+            return Ok(());
+        }
+
+        let instance = serde_json::Value::Object(metadata.clone().into_iter().collect());
+        let mut violations = Vec::new();
+        for (circuit_id, schema) in &latest {
+            if self.scope_includes(&scope, circuit_id)? {
+                for violation in event_schema::validate(&schema.schema, &instance) {
+                    if !violations.contains(&violation) {
+                        violations.push(violation);
+                    }
+                }
+            }
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(EventsError::SchemaViolation(violations))
+        }
+    }
+
+    pub fn get_events_for_item(&self, dfid: &str) -> Result<Vec<Event>, EventsError> {
+        self.storage
+            .get_events_by_dfid(dfid)
+            .map_err(|e| EventsError::StorageError(e.to_string()))
+    }
+
+    /// Rebuild an item's enriched state from its event history without writing
+    /// anything. Identity (identifiers, aliases, source entries) is kept; the
+    /// enriched data, status and occurrence window come from the events alone.
+    pub fn replay_item(&self, item: &Item) -> Result<ItemReplay, EventsError> {
+        let (rebuilt, events_applied, skipped) = self.replay_until(item, None, 0)?;
+
+        let mut added_fields = Vec::new();
+        let mut changed_fields = Vec::new();
+        for (key, value) in &rebuilt.enriched_data {
+            match item.enriched_data.get(key) {
+                None => added_fields.push(key.clone()),
+                Some(current) if current != value => changed_fields.push(key.clone()),
+                Some(_) => {}
+            }
+        }
+        let mut removed_fields: Vec<String> = item
+            .enriched_data
+            .keys()
+            .filter(|key| !rebuilt.enriched_data.contains_key(*key))
+            .cloned()
+            .collect();
+        added_fields.sort();
+        changed_fields.sort();
+        removed_fields.sort();
+
+        Ok(ItemReplay {
+            status_changed: rebuilt.status != item.status,
+            item: rebuilt,
+            events_applied,
+            skipped,
+            added_fields,
+            removed_fields,
+            changed_fields,
+        })
+    }
+
+    /// Replay `dfid`'s events and store the rebuilt item unless `dry_run`
+    pub fn rebuild_item(&self, dfid: &str, dry_run: bool) -> Result<ItemReplay, EventsError> {
+        let item = self
+            .storage
+            .get_item_by_dfid(dfid)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?
+            .ok_or(EventsError::NotFound)?;
+        let mut replay = self.replay_item(&item)?;
+        if !dry_run {
+            replay.item.last_modified = Utc::now();
+            self.storage
+                .update_item(&replay.item)
+                .map_err(|e| EventsError::StorageError(e.to_string()))?;
+            self.logger
+                .lock()
+                .unwrap()
+                .info(
+                    "events_engine",
+                    "item_rebuilt",
+                    format!("Rebuilt item from event history: {dfid}"),
+                )
+                .with_context("events_applied", replay.events_applied.to_string())
+                .with_context("events_skipped", replay.skipped.len().to_string());
+        }
+        Ok(replay)
+    }
+
+    /// Fold the events recorded up to `until` onto a copy of `item` with no
+    /// enrichment, in recorded order with event id as tie-break
+    fn replay_until(
+        &self,
+        item: &Item,
+        until: Option<DateTime<Utc>>,
+        depth: usize,
+    ) -> Result<(Item, usize, Vec<ReplaySkip>), EventsError> {
+        let mut events: Vec<Event> = self
+            .get_events_for_item(&item.dfid)?
+            .into_iter()
+            .filter(|event| until.is_none_or(|until| event.timestamp < until))
+            .collect();
+        events.sort_by_key(|event| (event.timestamp, event.event_id));
+
+        let mut rebuilt = without_event_state(item);
+        let (applied, skipped) = self.apply_events(&mut rebuilt, &events, depth)?;
+        Ok((rebuilt, applied, skipped))
+    }
+
+    /// Fold `events`, already in replay order, onto `rebuilt`
+    fn apply_events(
+        &self,
+        rebuilt: &mut Item,
+        events: &[Event],
+        depth: usize,
+    ) -> Result<(usize, Vec<ReplaySkip>), EventsError> {
+        let mut applied = 0;
+        let mut skipped = Vec::new();
+        let mut skip = |event: &Event, reason: String| {
+            skipped.push(ReplaySkip {
+                event_id: event.event_id,
+                reason,
+            })
+        };
+
+        for event in events {
+            match event.event_type {
+                EventType::Enriched | EventType::Updated => {
+                    for (key, value) in &event.metadata {
+                        if !BOOKKEEPING_KEYS.contains(&key.as_str()) {
+                            rebuilt.enriched_data.insert(key.clone(), value.clone());
+                        }
+                    }
+                }
+                EventType::StatusChanged => {
+                    let status = match event.metadata.get("status").and_then(|s| s.as_str()) {
+                        Some("Active") => ItemStatus::Active,
+                        Some("Deprecated") => ItemStatus::Deprecated,
+                        Some("Merged") => ItemStatus::Merged,
+                        Some("Split") => ItemStatus::Split,
+                        other => {
+                            skip(event, format!("Unrecognized status {other:?}"));
+                            continue;
+                        }
+                    };
+                    rebuilt.status = status;
+                }
+                EventType::Merged => {
+                    let Some(source_dfid) =
+                        event.metadata.get("merged_from").and_then(|v| v.as_str())
+                    else {
+                        skip(event, "Merge event has no merged_from".to_string());
+                        continue;
+                    };
+                    if depth >= MAX_REPLAY_DEPTH {
+                        skip(event, format!("Merge chain deeper than {MAX_REPLAY_DEPTH}"));
+                        continue;
+                    }
+                    let Some(source) = self
+                        .storage
+                        .get_item_by_dfid(source_dfid)
+                        .map_err(|e| EventsError::StorageError(e.to_string()))?
+                    else {
+                        skip(event, format!("Merged item {source_dfid} no longer exists"));
+                        continue;
+                    };
+                    // The merged item contributes what it held at merge time
+                    let (source, _, _) =
+                        self.replay_until(&source, Some(event.timestamp), depth + 1)?;
+                    rebuilt.enriched_data.extend(source.enriched_data);
+                }
+                EventType::Split => rebuilt.status = ItemStatus::Split,
+                EventType::Created
+                | EventType::PushedToCircuit
+                | EventType::PulledFromCircuit
+                | EventType::ForwardedToCircuit
+                | EventType::RemovedFromCircuit
+                | EventType::DocumentAnchored
+                // The events it anonymized were masked in place
+                | EventType::Anonymized
+                | EventType::Custom(_) => {}
+            }
+            if let Some(occurred_at) = event.occurred_at {
+                rebuilt.record_occurrence(occurred_at);
+            }
+            applied += 1;
+        }
+        Ok((applied, skipped))
+    }
+
+    /// Read an item from its latest compaction snapshot plus the events recorded
+    /// after it, so long histories are not folded from the start on every load.
+    /// Identity is kept from the stored item, as in `replay_item`.
+    pub fn load_compacted_item(&self, dfid: &str) -> Result<CompactedItemState, EventsError> {
+        let item = self
+            .storage
+            .get_item_by_dfid(dfid)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?
+            .ok_or(EventsError::NotFound)?;
+        let snapshot = self
+            .storage
+            .get_latest_snapshot(SnapshotEntityType::ItemCompaction, dfid)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?;
+
+        let (mut state, watermark) = match &snapshot {
+            Some(snapshot) => {
+                let compacted: Item =
+                    serde_json::from_value(snapshot.state.clone()).map_err(|e| {
+                        EventsError::StorageError(format!(
+                            "Unreadable compaction snapshot {}: {e}",
+                            snapshot.snapshot_id
+                        ))
+                    })?;
+                let mut state = item;
+                state.enriched_data = compacted.enriched_data;
+                state.status = compacted.status;
+                state.first_occurred_at = compacted.first_occurred_at;
+                state.last_occurred_at = compacted.last_occurred_at;
+                (state, Some(compaction_watermark(snapshot)?))
+            }
+            None => (without_event_state(&item), None),
+        };
+
+        let mut tail_events: Vec<Event> = self
+            .get_events_for_item(dfid)?
+            .into_iter()
+            .filter(|event| watermark.is_none_or(|mark| (event.timestamp, event.event_id) > mark))
+            .collect();
+        tail_events.sort_by_key(|event| (event.timestamp, event.event_id));
+        let (_, skipped) = self.apply_events(&mut state, &tail_events, 0)?;
+
+        Ok(CompactedItemState {
+            item: state,
+            snapshot,
+            tail_events,
+            skipped,
+        })
+    }
+
+    /// Fold the events recorded since the last compaction into a new snapshot
+    /// and mark them with its id. Returns None when there is nothing to fold.
+    pub fn compact_item(
+        &self,
+        dfid: &str,
+        created_by: &str,
+    ) -> Result<Option<StateSnapshot>, EventsError> {
+        let state = self.load_compacted_item(dfid)?;
+        let Some(last) = state.tail_events.last() else {
+            return Ok(None);
+        };
+        let (version, parent_hash) = match &state.snapshot {
+            Some(previous) => (previous.version + 1, Some(previous.snapshot_id.clone())),
+            None => (1, None),
+        };
+        let item_state = serde_json::to_value(&state.item)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?;
+        let snapshot = StateSnapshot::new(
+            SnapshotEntityType::ItemCompaction,
+            dfid.to_string(),
+            version,
+            parent_hash,
+            item_state,
+            SnapshotOperation::ItemCompacted {
+                through_event_id: last.event_id.to_string(),
+                through_timestamp: last.timestamp,
+                events_compacted: state.tail_events.len(),
+            },
+            created_by.to_string(),
+        )
+        .with_computed_hash();
+        self.storage
+            .store_snapshot(&snapshot)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?;
+
+        let events_compacted = state.tail_events.len();
+        for mut event in state.tail_events {
+            event.snapshot_id = Some(snapshot.snapshot_id.clone());
+            event.snapshot_cid = None;
+            self.storage
+                .update_event(&event)
+                .map_err(|e| EventsError::StorageError(e.to_string()))?;
+        }
+
+        self.logger
+            .lock()
+            .unwrap()
+            .info(
+                "events_engine",
+                "item_compacted",
+                format!("Compacted event history of item: {dfid}"),
+            )
+            .with_context("snapshot_id", snapshot.snapshot_id.clone())
+            .with_context("events_compacted", events_compacted.to_string());
+        Ok(Some(snapshot))
+    }
+
+    /// Record where an adapter stored a compaction snapshot, on the snapshot and
+    /// on every event folded into it
+    pub fn record_compaction_cid(&self, snapshot_id: &str, cid: &str) -> Result<(), EventsError> {
+        let mut snapshot = self
+            .storage
+            .get_snapshot(snapshot_id)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?
+            .ok_or(EventsError::NotFound)?;
+        snapshot.ipfs_cid = Some(cid.to_string());
+        self.storage
+            .update_snapshot(&snapshot)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?;
+
+        for mut event in self.get_events_for_item(&snapshot.entity_id)? {
+            if event.snapshot_id.as_deref() == Some(snapshot_id) {
+                event.snapshot_cid = Some(cid.to_string());
+                self.storage
+                    .update_event(&event)
+                    .map_err(|e| EventsError::StorageError(e.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Items with at least `threshold` events not yet folded into a snapshot
+    pub fn items_due_for_compaction(&self, threshold: usize) -> Result<Vec<String>, EventsError> {
+        let mut pending: HashMap<String, usize> = HashMap::new();
+        for event in self.list_all_events()? {
+            if event.snapshot_id.is_none() {
+                *pending.entry(event.dfid).or_default() += 1;
+            }
+        }
+        let mut due: Vec<String> = pending
+            .into_iter()
+            .filter(|(_, count)| *count >= threshold.max(1))
+            .map(|(dfid, _)| dfid)
+            .collect();
+        due.sort();
+        Ok(due)
+    }
+
+    pub fn get_events_by_type(&self, event_type: EventType) -> Result<Vec<Event>, EventsError> {
+        self.storage
+            .get_events_by_type(event_type)
+            .map_err(|e| EventsError::StorageError(e.to_string()))
+    }
+
+    pub fn get_events_by_visibility(
+        &self,
+        visibility: EventVisibility,
+    ) -> Result<Vec<Event>, EventsError> {
+        self.storage
+            .get_events_by_visibility(visibility)
+            .map_err(|e| EventsError::StorageError(e.to_string()))
+    }
+
+    pub fn get_events_in_time_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Event>, EventsError> {
+        self.storage
+            .get_events_in_time_range(start, end)
+            .map_err(|e| EventsError::StorageError(e.to_string()))
+    }
+
+    /// Events whose time on `axis` falls within `[start, end]`, oldest first
+    pub fn get_events_in_time_range_on(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        axis: TimeAxis,
+    ) -> Result<Vec<Event>, EventsError> {
+        let mut events = match axis {
+            TimeAxis::Recorded => self.get_events_in_time_range(start, end)?,
+            TimeAxis::Occurred => self
+                .list_all_events()?
+                .into_iter()
+                .filter(|event| {
+                    let at = event.time_on(axis);
+                    at >= start && at <= end
+                })
+                .collect(),
+        };
+        events.sort_by_key(|event| event.time_on(axis));
+        Ok(events)
+    }
+
+    /// Number of events per UTC day within `[start, end]` on the given axis
+    pub fn daily_event_counts(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        axis: TimeAxis,
+    ) -> Result<BTreeMap<NaiveDate, u64>, EventsError> {
+        let mut counts = BTreeMap::new();
+        for event in self.get_events_in_time_range_on(start, end, axis)? {
+            *counts.entry(event.time_on(axis).date_naive()).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
+    /// Events older than `retention_days` on the given axis, i.e. due for expiry
+    pub fn events_past_retention(
+        &self,
+        retention_days: u32,
+        axis: TimeAxis,
+    ) -> Result<Vec<Event>, EventsError> {
+        let cutoff = Utc::now() - Duration::days(i64::from(retention_days));
+        Ok(self
+            .list_all_events()?
+            .into_iter()
+            .filter(|event| event.time_on(axis) < cutoff)
+            .collect())
+    }
+
+    pub fn get_public_events(&self) -> Result<Vec<Event>, EventsError> {
+        self.get_events_by_visibility(EventVisibility::Public)
+    }
+
+    pub fn get_private_events(&self) -> Result<Vec<Event>, EventsError> {
+        self.get_events_by_visibility(EventVisibility::Private)
+    }
+
+    pub fn list_all_events(&self) -> Result<Vec<Event>, EventsError> {
+        self.storage
+            .list_events()
+            .map_err(|e| EventsError::StorageError(e.to_string()))
+    }
+
+    /// One page of events matching `keep` in recording order, optionally for one item
+    pub fn list_events_page(
+        &self,
+        dfid: Option<&str>,
+        after: Option<PageCursor>,
+        limit: usize,
+        keep: impl Fn(&Event) -> bool,
+    ) -> Result<Page<Event>, EventsError> {
+        collect_page(
+            after,
+            limit,
+            |after, limit| self.storage.list_events_page(dfid, after, limit),
+            |event| PageCursor::new(event.timestamp, event.event_id.to_string()),
+            keep,
+        )
+        .map_err(|e| EventsError::StorageError(e.to_string()))
+    }
+
+    pub fn get_event(&self, event_id: &Uuid) -> Result<Option<Event>, EventsError> {
+        self.storage
+            .get_event(event_id)
+            .map_err(|e| EventsError::StorageError(e.to_string()))
+    }
+
+    pub fn create_item_created_event(
+        &mut self,
+        dfid: String,
+        source: String,
+        identifiers: Vec<String>,
+    ) -> Result<Event, EventsError> {
+        let event = self.create_event(
+            dfid.clone(),
+            EventType::Created,
+            source,
+            EventVisibility::Public,
+        )?;
 
         let identifiers_json: Vec<serde_json::Value> = identifiers
             .into_iter()
@@ -556,6 +1905,7 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
         operation: String,
         requester_id: String,
         visibility: EventVisibility,
+        causality: EventCausality,
     ) -> Result<Event, EventsError> {
         let event_type = match operation.as_str() {
             "push" => EventType::PushedToCircuit,
@@ -588,7 +1938,26 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
         .cloned()
         .collect();
 
-        self.add_event_metadata(&event.event_id, metadata)
+        let event = self.add_event_metadata(&event.event_id, metadata)?;
+        self.link_event(&event.event_id, causality)
+    }
+
+    fn record_item_occurrence(
+        &self,
+        dfid: &str,
+        occurred_at: DateTime<Utc>,
+    ) -> Result<(), EventsError> {
+        let item = self
+            .storage
+            .get_item_by_dfid(dfid)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?;
+        if let Some(mut item) = item {
+            item.record_occurrence(occurred_at);
+            self.storage
+                .update_item(&item)
+                .map_err(|e| EventsError::StorageError(e.to_string()))?;
+        }
+        Ok(())
     }
 
     pub fn get_logs(&self) -> Vec<crate::logging::LogEntry> {
@@ -606,6 +1975,201 @@ impl<S: StorageBackend + 'static> EventsEngine<S> {
     }
 }
 
+impl<S: StorageBackend + Clone + Send + Sync + 'static> EventsEngine<S> {
+    /// Write a compaction snapshot's item state to the default adapter and record
+    /// the location it returns. None when no adapter is configured, in which case
+    /// the snapshot is only kept in storage.
+    pub async fn store_compaction_on_adapter(
+        &self,
+        snapshot: &StateSnapshot,
+    ) -> Result<Option<String>, EventsError> {
+        let Some(config) = self
+            .storage
+            .get_default_adapter_config()
+            .map_err(|e| EventsError::StorageError(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        let item: Item = serde_json::from_value(snapshot.state.clone())
+            .map_err(|e| EventsError::StorageError(e.to_string()))?;
+        let manager = AdapterManager::new(self.storage.clone(), Arc::clone(&self.logger));
+        let adapter = manager
+            .create_adapter_instance_for_config(&config)
+            .map_err(|e| EventsError::StorageError(e.to_string()))?;
+        let result = manager
+            .store_item_with_retry(&adapter, &item)
+            .await
+            .map_err(|e| EventsError::StorageError(e.to_string()))?;
+        self.record_compaction_cid(&snapshot.snapshot_id, &result.data)?;
+        Ok(Some(result.data))
+    }
+
+    /// Compact every item with at least `threshold` unsnapshotted events and
+    /// write the snapshots to the default adapter
+    pub async fn compact_due_items(
+        &self,
+        threshold: usize,
+    ) -> Result<Vec<StateSnapshot>, EventsError> {
+        let mut compacted = Vec::new();
+        for dfid in self.items_due_for_compaction(threshold)? {
+            let mut snapshot = match self.compact_item(&dfid, "system") {
+                Ok(Some(snapshot)) => snapshot,
+                // Events of a deleted item have nothing to fold onto
+                Ok(None) | Err(EventsError::NotFound) => continue,
+                Err(e) => return Err(e),
+            };
+            match self.store_compaction_on_adapter(&snapshot).await {
+                Ok(cid) => snapshot.ipfs_cid = cid,
+                Err(e) => tracing::warn!(
+                    "⚠️  Compaction snapshot {} of {} not written to adapter: {}",
+                    snapshot.snapshot_id,
+                    dfid,
+                    e
+                ),
+            }
+            compacted.push(snapshot);
+        }
+        Ok(compacted)
+    }
+
+    /// Compact items whose unsnapshotted events reach `threshold`, every `tick`
+    pub fn spawn_compactor(
+        storage: S,
+        tick: std::time::Duration,
+        threshold: usize,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let engine = EventsEngine::new(storage);
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                match engine.compact_due_items(threshold).await {
+                    Ok(compacted) if !compacted.is_empty() => {
+                        tracing::info!("🗜️  Compacted event history of {} item(s)", compacted.len())
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("⚠️  Event compaction failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+/// Custom type names are identifiers so they survive URLs and storage columns
+/// Upload a file to IPFS for attaching to an event with `add_attachment`
+pub async fn upload_attachment(
+    ipfs: &IpfsClient,
+    content: Vec<u8>,
+    mime_type: Option<String>,
+    file_name: Option<String>,
+    uploaded_by: &str,
+) -> Result<EventAttachment, EventsError> {
+    if content.is_empty() {
+        return Err(EventsError::ValidationError(
+            "Attachment is empty".to_string(),
+        ));
+    }
+    if content.len() > MAX_ATTACHMENT_BYTES {
+        return Err(EventsError::ValidationError(format!(
+            "Attachments are limited to {MAX_ATTACHMENT_BYTES} bytes"
+        )));
+    }
+    let mime_type = mime_type.unwrap_or_else(|| "application/octet-stream".to_string());
+    if !is_valid_mime_type(&mime_type) {
+        return Err(EventsError::ValidationError(format!(
+            "Invalid mime type: {mime_type}"
+        )));
+    }
+    let content_hash = hashing::hash(&content);
+    let size = content.len() as u64;
+
+    let cid = ipfs
+        .upload_bytes(
+            content,
+            file_name.as_deref().unwrap_or(&content_hash),
+            &mime_type,
+        )
+        .await
+        .map_err(|e| {
+            EventsError::StorageError(format!("Failed to upload attachment to IPFS: {e}"))
+        })?;
+
+    Ok(EventAttachment {
+        attachment_id: Uuid::new_v4(),
+        cid,
+        content_hash,
+        mime_type,
+        file_name,
+        size,
+        uploaded_by: uploaded_by.to_string(),
+        uploaded_at: Utc::now(),
+        bucket_object: None,
+    })
+}
+
+/// `type/subtype`, optionally with parameters, e.g. `application/pdf`
+pub(crate) fn is_valid_mime_type(mime_type: &str) -> bool {
+    let essence = mime_type.split(';').next().unwrap_or_default().trim();
+    match essence.split_once('/') {
+        Some((kind, subtype)) => [kind, subtype].iter().all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+        }),
+        None => false,
+    }
+}
+
+fn validate_custom_type_name(name: &str) -> Result<(), EventsError> {
+    let valid = (1..=MAX_CUSTOM_TYPE_NAME_LEN).contains(&name.len())
+        && name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(EventsError::ValidationError(format!(
+            "Custom event type names start with a letter and use letters, digits and \
+             underscores, up to {MAX_CUSTOM_TYPE_NAME_LEN} characters"
+        )));
+    }
+    if EventType::BUILT_IN
+        .iter()
+        .any(|built_in| built_in.to_string().eq_ignore_ascii_case(name))
+    {
+        return Err(EventsError::ValidationError(format!(
+            "{name} is a built-in event type"
+        )));
+    }
+    Ok(())
+}
+
+/// `item` with everything events contribute cleared, ready to fold them onto
+fn without_event_state(item: &Item) -> Item {
+    let mut cleared = item.clone();
+    cleared.enriched_data.clear();
+    cleared.status = ItemStatus::Active;
+    cleared.first_occurred_at = None;
+    cleared.last_occurred_at = None;
+    cleared
+}
+
+/// Position of the last event folded into a compaction snapshot
+fn compaction_watermark(snapshot: &StateSnapshot) -> Result<(DateTime<Utc>, Uuid), EventsError> {
+    match &snapshot.operation {
+        SnapshotOperation::ItemCompacted {
+            through_event_id,
+            through_timestamp,
+            ..
+        } => Uuid::parse_str(through_event_id)
+            .map(|event_id| (*through_timestamp, event_id))
+            .map_err(|e| EventsError::StorageError(format!("Invalid compaction watermark: {e}"))),
+        other => Err(EventsError::StorageError(format!(
+            "Snapshot {} is not a compaction: {}",
+            snapshot.snapshot_id,
+            other.description()
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -681,6 +2245,314 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_event_chain_follows_causes_and_correlation() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let mut events_engine = EventsEngine::new(Arc::clone(&storage));
+        let receipt_id = Uuid::new_v4();
+        let operation_id = Uuid::new_v4();
+        let record = |engine: &mut EventsEngine<_>, event_type, causality| {
+            engine
+                .create_linked_event(
+                    "DFID-CHAIN".to_string(),
+                    event_type,
+                    "system".to_string(),
+                    EventVisibility::Public,
+                    HashMap::new(),
+                    causality,
+                )
+                .unwrap()
+        };
+
+        let created = record(
+            &mut events_engine,
+            EventType::Created,
+            EventCausality::root(receipt_id),
+        );
+        let pushed = record(
+            &mut events_engine,
+            EventType::PushedToCircuit,
+            EventCausality::following(Some(created.event_id), operation_id),
+        );
+        let updated = record(
+            &mut events_engine,
+            EventType::Updated,
+            EventCausality::following(Some(pushed.event_id), operation_id),
+        );
+
+        let chain = events_engine.event_chain(&pushed.event_id).unwrap();
+        assert_eq!(chain.ancestors.len(), 1);
+        assert_eq!(chain.ancestors[0].event_id, created.event_id);
+        assert_eq!(chain.descendants.len(), 1);
+        assert_eq!(chain.descendants[0].event_id, updated.event_id);
+        assert_eq!(chain.correlated.len(), 1);
+        assert_eq!(chain.correlated[0].correlation_id, Some(operation_id));
+        assert!(!chain.truncated);
+
+        // A cause cannot follow from its own effect
+        assert!(matches!(
+            events_engine.link_event(
+                &created.event_id,
+                EventCausality::following(Some(updated.event_id), operation_id),
+            ),
+            Err(EventsError::ValidationError(_))
+        ));
+        assert_eq!(
+            storage
+                .get_events_caused_by(&created.event_id)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_deduplication_window() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let clock = Arc::new(std::sync::Mutex::new(Utc::now()));
+        let mut events_engine = EventsEngine::new(Arc::clone(&storage))
+            .with_dedup_window(Duration::seconds(1))
+            .with_clock({
+                let clock = Arc::clone(&clock);
+                move || *clock.lock().unwrap()
+            });
+        let metadata = HashMap::from([("weight_kg".to_string(), serde_json::json!(410))]);
+        let create = |engine: &mut EventsEngine<_>, occurred_at| {
+            engine
+                .create_event_with_occurred_at(
+                    "DFID-123".to_string(),
+                    EventType::Updated,
+                    "scale".to_string(),
+                    EventVisibility::Public,
+                    metadata.clone(),
+                    occurred_at,
+                )
+                .unwrap()
+        };
+
+        let first = create(&mut events_engine, None);
+        let retry = create(&mut events_engine, None);
+        assert!(retry.was_deduplicated);
+        assert_eq!(retry.event.event_id, first.event.event_id);
+
+        // The same reading after the window is a new occurrence
+        *clock.lock().unwrap() += Duration::milliseconds(1100);
+        let later = create(&mut events_engine, None);
+        assert!(!later.was_deduplicated);
+        assert_ne!(later.event.event_id, first.event.event_id);
+
+        // Declared occurrences stay deduplicated past the window
+        let occurred_at = Utc::now() - Duration::days(30);
+        let backfilled = create(&mut events_engine, Some(occurred_at));
+        let mut aged = backfilled.event.clone();
+        aged.timestamp -= Duration::days(2);
+        storage.update_event(&aged).unwrap();
+        assert!(create(&mut events_engine, Some(occurred_at)).was_deduplicated);
+
+        // Independent nodes derive the same id for the same event and window
+        let mut node_a = EventsEngine::new(Arc::new(std::sync::Mutex::new(InMemoryStorage::new())))
+            .with_dedup_window(Duration::days(36_500));
+        let mut node_b = EventsEngine::new(Arc::new(std::sync::Mutex::new(InMemoryStorage::new())))
+            .with_dedup_window(Duration::days(36_500));
+        assert_eq!(
+            create(&mut node_a, None).event.event_id,
+            create(&mut node_b, None).event.event_id
+        );
+    }
+
+    #[test]
+    fn test_add_attachment() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let mut events_engine = EventsEngine::new(storage);
+
+        let event = events_engine
+            .create_event(
+                "DFID-123".to_string(),
+                EventType::Updated,
+                "lab".to_string(),
+                EventVisibility::Public,
+            )
+            .unwrap();
+        let attachment = |uploaded_by: &str| EventAttachment {
+            attachment_id: Uuid::new_v4(),
+            cid: "bafy-certificate".to_string(),
+            content_hash: blake3::hash(b"certificate").to_hex().to_string(),
+            mime_type: "application/pdf".to_string(),
+            file_name: Some("certificate.pdf".to_string()),
+            size: 11,
+            uploaded_by: uploaded_by.to_string(),
+            uploaded_at: Utc::now(),
+            bucket_object: None,
+        };
+
+        assert!(matches!(
+            events_engine.add_attachment(&event.event_id, attachment("someone-else")),
+            Err(EventsError::PermissionDenied(_))
+        ));
+        events_engine
+            .add_attachment(&event.event_id, attachment("lab"))
+            .unwrap();
+        // Same content again is not attached twice
+        let updated = events_engine
+            .add_attachment(&event.event_id, attachment("lab"))
+            .unwrap();
+        assert_eq!(updated.attachments.len(), 1);
+        assert_eq!(updated.attachments[0].cid, "bafy-certificate");
+
+        let stored = events_engine.get_event(&event.event_id).unwrap().unwrap();
+        assert_eq!(stored.attachments, updated.attachments);
+        let locations = crate::adapters::base::attachment_locations(&stored, true);
+        assert!(matches!(
+            &locations[..],
+            [crate::adapters::base::StorageLocation::IPFS { cid, pinned: true }] if cid == "bafy-certificate"
+        ));
+        assert!(!is_valid_mime_type("pdf"));
+        assert!(is_valid_mime_type("image/jpeg"));
+    }
+
+    #[test]
+    fn test_event_schema_validation() {
+        use crate::types::{Circuit, CircuitItem};
+        use serde_json::json;
+
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let circuit = Circuit::new("Weighing".to_string(), String::new(), "owner".to_string());
+        let circuit_id = circuit.circuit_id;
+        {
+            let storage = storage.lock().unwrap();
+            storage.store_circuit(&circuit).unwrap();
+            storage
+                .store_circuit_item(&CircuitItem::new(
+                    "DFID-IN".to_string(),
+                    circuit_id,
+                    "owner".to_string(),
+                    vec![],
+                ))
+                .unwrap();
+        }
+        let mut events_engine = EventsEngine::new(storage);
+
+        let schema = json!({
+            "type": "object",
+            "required": ["weight_kg"],
+            "properties": {"weight_kg": {"type": "number"}}
+        });
+        assert!(matches!(
+            events_engine.register_event_schema(
+                circuit_id,
+                EventType::Updated,
+                schema.clone(),
+                "stranger"
+            ),
+            Err(EventsError::PermissionDenied(_))
+        ));
+        let registered = events_engine
+            .register_event_schema(circuit_id, EventType::Updated, schema, "owner")
+            .unwrap();
+        assert_eq!(registered.version, 1);
+
+        let create = |engine: &mut EventsEngine<_>, dfid: &str, weight: serde_json::Value| {
+            engine.create_event_with_metadata(
+                dfid.to_string(),
+                EventType::Updated,
+                "scale".to_string(),
+                EventVisibility::Public,
+                HashMap::from([("weight_kg".to_string(), weight)]),
+            )
+        };
+        match create(&mut events_engine, "DFID-IN", json!("heavy")) {
+            Err(EventsError::SchemaViolation(violations)) => {
+                assert_eq!(violations[0].path, "$.weight_kg")
+            }
+            other => panic!("expected schema violation, got {other:?}"),
+        }
+        assert!(create(&mut events_engine, "DFID-IN", json!(410.5)).is_ok());
+        // Items outside the circuit and other event types are unaffected
+        assert!(create(&mut events_engine, "DFID-OUT", json!("heavy")).is_ok());
+        assert!(events_engine
+            .create_event(
+                "DFID-IN".to_string(),
+                EventType::Created,
+                "system".to_string(),
+                EventVisibility::Public,
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_custom_event_types_are_registered_per_circuit() {
+        use crate::types::{Circuit, CircuitItem};
+
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let circuit = Circuit::new("Harvest".to_string(), String::new(), "owner".to_string());
+        let circuit_id = circuit.circuit_id;
+        {
+            let storage = storage.lock().unwrap();
+            storage.store_circuit(&circuit).unwrap();
+            storage
+                .store_circuit_item(&CircuitItem::new(
+                    "DFID-IN".to_string(),
+                    circuit_id,
+                    "owner".to_string(),
+                    vec![],
+                ))
+                .unwrap();
+        }
+        let mut events_engine = EventsEngine::new(storage);
+        let input = |name: &str| CustomEventTypeInput {
+            name: name.to_string(),
+            display_name: "Harvested".to_string(),
+            description: None,
+            icon: Some("wheat".to_string()),
+            color: Some("#8d6e63".to_string()),
+        };
+        assert!(matches!(
+            events_engine.register_custom_event_type(circuit_id, input("Created"), "owner"),
+            Err(EventsError::ValidationError(_))
+        ));
+        assert!(matches!(
+            events_engine.register_custom_event_type(circuit_id, input("Harvested"), "stranger"),
+            Err(EventsError::PermissionDenied(_))
+        ));
+        events_engine
+            .register_custom_event_type(circuit_id, input("Harvested"), "owner")
+            .unwrap();
+
+        let harvested = EventType::from_name("Harvested");
+        assert_eq!(harvested, EventType::Custom("Harvested".to_string()));
+        assert_eq!(harvested.to_string(), "Harvested");
+        assert_eq!(EventType::from_name("Merged"), EventType::Merged);
+        let create = |engine: &mut EventsEngine<_>, dfid: &str, event_type: EventType| {
+            engine.create_event_with_metadata(
+                dfid.to_string(),
+                event_type,
+                "field-app".to_string(),
+                EventVisibility::Public,
+                HashMap::new(),
+            )
+        };
+        assert!(create(&mut events_engine, "DFID-IN", harvested.clone()).is_ok());
+        assert!(create(&mut events_engine, "DFID-OUT", harvested.clone()).is_err());
+        assert!(create(
+            &mut events_engine,
+            "DFID-IN",
+            EventType::Custom("Sprayed".to_string())
+        )
+        .is_err());
+        assert_eq!(
+            events_engine.get_events_by_type(harvested).unwrap().len(),
+            1
+        );
+
+        events_engine
+            .remove_custom_event_type(circuit_id, "Harvested", "owner")
+            .unwrap();
+        assert!(events_engine
+            .list_custom_event_types(&circuit_id)
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn test_get_events_for_item() {
         let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
@@ -707,4 +2579,377 @@ mod tests {
         let events = events_engine.get_events_for_item("DFID-123").unwrap();
         assert_eq!(events.len(), 2);
     }
+
+    #[test]
+    fn test_list_events_page_walks_item_events() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let mut events_engine = EventsEngine::new(storage);
+
+        for n in 0..5 {
+            let mut metadata = HashMap::new();
+            metadata.insert("reading".to_string(), serde_json::json!(n));
+            events_engine
+                .create_event_with_metadata(
+                    "DFID-PAGED".to_string(),
+                    EventType::Enriched,
+                    "sensor".to_string(),
+                    EventVisibility::Public,
+                    metadata,
+                )
+                .unwrap();
+        }
+        events_engine
+            .create_event(
+                "DFID-OTHER".to_string(),
+                EventType::Created,
+                "sensor".to_string(),
+                EventVisibility::Public,
+            )
+            .unwrap();
+
+        let first = events_engine
+            .list_events_page(Some("DFID-PAGED"), None, 3, |_| true)
+            .unwrap();
+        assert_eq!(first.items.len(), 3);
+        let cursor = PageCursor::decode(first.next_cursor.as_deref().unwrap()).unwrap();
+
+        let second = events_engine
+            .list_events_page(Some("DFID-PAGED"), Some(cursor), 3, |_| true)
+            .unwrap();
+        assert_eq!(second.items.len(), 2);
+        assert!(second.next_cursor.is_none());
+        assert!(second
+            .items
+            .iter()
+            .all(|event| first.items.iter().all(|e| e.event_id != event.event_id)));
+    }
+
+    #[test]
+    fn test_replay_rebuilds_enriched_state() {
+        use serde_json::json;
+
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let base = Utc::now() - Duration::hours(1);
+        let event = |dfid: &str, event_type, minutes, metadata: serde_json::Value| {
+            let mut event = Event::new_with_metadata(
+                dfid.to_string(),
+                event_type,
+                "test".to_string(),
+                EventVisibility::Public,
+                serde_json::from_value(metadata).unwrap(),
+            );
+            event.timestamp = base + Duration::minutes(minutes);
+            storage.lock().unwrap().store_event(&event).unwrap();
+        };
+        event("DFID-A", EventType::Updated, 1, json!({"weight_kg": 400}));
+        event(
+            "DFID-A",
+            EventType::Enriched,
+            2,
+            json!({"enriched_keys": ["x"]}),
+        );
+        event("DFID-B", EventType::Updated, 3, json!({"breed": "angus"}));
+        event(
+            "DFID-A",
+            EventType::Merged,
+            4,
+            json!({"merged_from": "DFID-B"}),
+        );
+        // Recorded after the merge, so not part of what B contributed
+        event("DFID-B", EventType::Updated, 5, json!({"vaccinated": true}));
+        event("DFID-A", EventType::Updated, 6, json!({"weight_kg": 410}));
+        event(
+            "DFID-A",
+            EventType::StatusChanged,
+            7,
+            json!({"status": "Retired"}),
+        );
+
+        let mut corrupted = Item::new("DFID-A".to_string(), vec![], Uuid::new_v4());
+        corrupted
+            .enriched_data
+            .insert("weight_kg".to_string(), json!("4100"));
+        corrupted
+            .enriched_data
+            .insert("bogus".to_string(), json!(true));
+        corrupted.status = ItemStatus::Deprecated;
+        for dfid in ["DFID-A", "DFID-B"] {
+            let mut item = corrupted.clone();
+            item.dfid = dfid.to_string();
+            storage.lock().unwrap().store_item(&item).unwrap();
+        }
+
+        let events_engine = EventsEngine::new(Arc::clone(&storage));
+        let preview = events_engine.rebuild_item("DFID-A", true).unwrap();
+        assert_eq!(
+            preview.item.enriched_data,
+            HashMap::from([
+                ("weight_kg".to_string(), json!(410)),
+                ("breed".to_string(), json!("angus")),
+            ])
+        );
+        assert_eq!(preview.events_applied, 4);
+        assert_eq!(preview.skipped.len(), 1);
+        assert_eq!(preview.added_fields, vec!["breed"]);
+        assert_eq!(preview.removed_fields, vec!["bogus"]);
+        assert_eq!(preview.changed_fields, vec!["weight_kg"]);
+        assert!(preview.status_changed);
+        let stored = storage.lock().unwrap().get_item_by_dfid("DFID-A").unwrap();
+        assert_eq!(stored.unwrap().enriched_data, corrupted.enriched_data);
+
+        let rebuilt = events_engine.rebuild_item("DFID-A", false).unwrap();
+        let stored = storage.lock().unwrap().get_item_by_dfid("DFID-A").unwrap();
+        assert_eq!(stored.unwrap().enriched_data, rebuilt.item.enriched_data);
+        assert!(matches!(
+            events_engine.rebuild_item("DFID-MISSING", true),
+            Err(EventsError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn test_compaction_reads_snapshot_plus_tail() {
+        use serde_json::json;
+
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let base = Utc::now() - Duration::hours(1);
+        let event = |minutes, metadata: serde_json::Value| {
+            let mut event = Event::new_with_metadata(
+                "DFID-C".to_string(),
+                EventType::Updated,
+                "test".to_string(),
+                EventVisibility::Public,
+                serde_json::from_value(metadata).unwrap(),
+            );
+            event.timestamp = base + Duration::minutes(minutes);
+            storage.lock().unwrap().store_event(&event).unwrap();
+        };
+        let item = Item::new("DFID-C".to_string(), vec![], Uuid::new_v4());
+        storage.lock().unwrap().store_item(&item).unwrap();
+        event(1, json!({"weight_kg": 400}));
+        event(2, json!({"breed": "angus"}));
+        event(3, json!({"weight_kg": 405}));
+
+        let events_engine = EventsEngine::new(Arc::clone(&storage));
+        assert!(events_engine
+            .items_due_for_compaction(4)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            events_engine.items_due_for_compaction(3).unwrap(),
+            vec!["DFID-C"]
+        );
+
+        let first = events_engine
+            .compact_item("DFID-C", "admin")
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.version, 1);
+        assert!(events_engine
+            .compact_item("DFID-C", "admin")
+            .unwrap()
+            .is_none());
+        assert!(events_engine
+            .items_due_for_compaction(1)
+            .unwrap()
+            .is_empty());
+        let loaded = events_engine.load_compacted_item("DFID-C").unwrap();
+        assert!(loaded.tail_events.is_empty());
+        assert_eq!(loaded.item.enriched_data["weight_kg"], json!(405));
+
+        event(4, json!({"weight_kg": 410}));
+        let loaded = events_engine.load_compacted_item("DFID-C").unwrap();
+        assert_eq!(loaded.tail_events.len(), 1);
+        assert_eq!(
+            loaded.item.enriched_data,
+            events_engine.replay_item(&item).unwrap().item.enriched_data
+        );
+
+        let second = events_engine
+            .compact_item("DFID-C", "admin")
+            .unwrap()
+            .unwrap();
+        assert_eq!(second.version, 2);
+        assert_eq!(
+            second.parent_hash.as_deref(),
+            Some(first.snapshot_id.as_str())
+        );
+        events_engine
+            .record_compaction_cid(&second.snapshot_id, "bafy-compacted")
+            .unwrap();
+        let events = events_engine.get_events_for_item("DFID-C").unwrap();
+        assert_eq!(
+            events
+                .iter()
+                .filter(|e| e.snapshot_cid.as_deref() == Some("bafy-compacted"))
+                .count(),
+            1
+        );
+        assert!(events.iter().all(|e| e.snapshot_id.is_some()));
+    }
+
+    #[test]
+    fn test_backfilled_events_use_occurred_axis() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        storage
+            .lock()
+            .unwrap()
+            .store_item(&crate::types::Item::new(
+                "DFID-HIST".to_string(),
+                vec![],
+                Uuid::new_v4(),
+            ))
+            .unwrap();
+        let mut events_engine = EventsEngine::new(Arc::clone(&storage));
+        let occurred_at = Utc::now() - Duration::days(800);
+
+        let first = events_engine
+            .create_event_with_occurred_at(
+                "DFID-HIST".to_string(),
+                EventType::Enriched,
+                "migration".to_string(),
+                EventVisibility::Public,
+                HashMap::new(),
+                Some(occurred_at),
+            )
+            .unwrap();
+        // Same payload at a different occurrence is a distinct historical reading
+        let second = events_engine
+            .create_event_with_occurred_at(
+                "DFID-HIST".to_string(),
+                EventType::Enriched,
+                "migration".to_string(),
+                EventVisibility::Public,
+                HashMap::new(),
+                Some(occurred_at + Duration::days(1)),
+            )
+            .unwrap();
+        assert!(!second.was_deduplicated);
+        assert_eq!(first.event.occurred_at, Some(occurred_at));
+
+        let window_start = occurred_at - Duration::days(1);
+        let window_end = occurred_at + Duration::days(2);
+        let occurred = events_engine
+            .get_events_in_time_range_on(window_start, window_end, TimeAxis::Occurred)
+            .unwrap();
+        assert_eq!(occurred.len(), 2);
+        let recorded = events_engine
+            .get_events_in_time_range_on(window_start, window_end, TimeAxis::Recorded)
+            .unwrap();
+        assert!(recorded.is_empty());
+
+        let counts = events_engine
+            .daily_event_counts(window_start, window_end, TimeAxis::Occurred)
+            .unwrap();
+        assert_eq!(counts.values().sum::<u64>(), 2);
+
+        assert_eq!(
+            events_engine
+                .events_past_retention(365, TimeAxis::Occurred)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(events_engine
+            .events_past_retention(365, TimeAxis::Recorded)
+            .unwrap()
+            .is_empty());
+
+        let item = storage
+            .lock()
+            .unwrap()
+            .get_item_by_dfid("DFID-HIST")
+            .unwrap()
+            .unwrap();
+        assert_eq!(item.first_occurred_at, Some(occurred_at));
+        assert_eq!(item.last_occurred_at, Some(occurred_at + Duration::days(1)));
+
+        let future = events_engine.create_event_with_occurred_at(
+            "DFID-HIST".to_string(),
+            EventType::Enriched,
+            "migration".to_string(),
+            EventVisibility::Public,
+            HashMap::new(),
+            Some(Utc::now() + Duration::days(2)),
+        );
+        assert!(matches!(future, Err(EventsError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_bulk_events_are_validated_as_a_whole() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let mut events_engine = EventsEngine::new(Arc::clone(&storage));
+        let reading = |dfid: &str, event_type: EventType, celsius: f64| BulkEventInput {
+            dfid: dfid.to_string(),
+            event_type,
+            visibility: EventVisibility::Public,
+            metadata: HashMap::from([("celsius".to_string(), serde_json::json!(celsius))]),
+            occurred_at: None,
+            signature: None,
+        };
+
+        // An unregistered custom type rejects the batch before anything is stored
+        let err = events_engine
+            .create_events_bulk(
+                "gateway".to_string(),
+                vec![
+                    reading("DFID-SENSOR", EventType::Updated, 4.0),
+                    reading("DFID-SENSOR", EventType::Custom("Reading".to_string()), 4.5),
+                ],
+            )
+            .unwrap_err();
+        assert!(matches!(err, EventsError::ValidationError(ref e) if e.starts_with("Event 1:")));
+        assert!(storage
+            .get_events_by_dfid("DFID-SENSOR")
+            .unwrap()
+            .is_empty());
+
+        let bulk = events_engine
+            .create_events_bulk(
+                "gateway".to_string(),
+                vec![
+                    reading("DFID-SENSOR", EventType::Updated, 4.0),
+                    reading("DFID-SENSOR", EventType::Updated, 4.5),
+                    reading("DFID-SENSOR", EventType::Updated, 4.0),
+                ],
+            )
+            .unwrap();
+        assert_eq!(bulk.results.len(), 3);
+        assert!(bulk.results[2].was_deduplicated);
+        assert_eq!(storage.get_events_by_dfid("DFID-SENSOR").unwrap().len(), 2);
+        assert!(bulk.merkle_root.is_some());
+    }
+
+    #[test]
+    fn test_bulk_events_failing_mid_batch_store_none() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let mut events_engine = EventsEngine::new(Arc::clone(&storage));
+        let reading = |celsius: f64| BulkEventInput {
+            dfid: "DFID-SENSOR".to_string(),
+            event_type: EventType::Updated,
+            visibility: EventVisibility::Public,
+            metadata: HashMap::from([("celsius".to_string(), serde_json::json!(celsius))]),
+            occurred_at: None,
+            signature: None,
+        };
+
+        // Storage accepts two more events, so the third of the batch fails
+        storage.lock().unwrap().fail_event_writes_after(2);
+        let err = events_engine
+            .create_events_bulk(
+                "gateway".to_string(),
+                vec![reading(4.0), reading(4.5), reading(5.0)],
+            )
+            .unwrap_err();
+        assert!(matches!(err, EventsError::StorageError(_)));
+        assert!(storage
+            .get_events_by_dfid("DFID-SENSOR")
+            .unwrap()
+            .is_empty());
+
+        let bulk = events_engine
+            .create_events_bulk("gateway".to_string(), vec![reading(4.0), reading(4.5)])
+            .unwrap();
+        assert!(bulk.results.iter().all(|result| !result.was_deduplicated));
+        assert_eq!(storage.get_events_by_dfid("DFID-SENSOR").unwrap().len(), 2);
+    }
 }
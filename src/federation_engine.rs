@@ -0,0 +1,2233 @@
+//! Federation between independent deployments.
+//!
+//! Two deployments, say a co-op's on-prem instance and the hosted platform, each
+//! register the other as a peer and name which of their circuits map onto which
+//! of the peer's. A node pulls the peer's change feed of every mapped circuit,
+//! resuming from a per-circuit cursor, and imports the items and events in it.
+//! Membership stays local to each deployment.
+//!
+//! Every record carries the id of the node that produced that version and the
+//! node's ed25519 signature over it, so a record relayed through a third node
+//! still proves where it came from, and a node never gets its own records echoed
+//! back. The node identity is `FEDERATION_NODE_ID` with the hex 32-byte ed25519
+//! seed in `FEDERATION_NODE_KEY`, and `FEDERATION_NODE_URL` the base URL peers
+//! reach it at; requests between nodes are signed with the same key.
+//!
+//! A remote version replaces a local record only if the local record is unchanged
+//! since it was last imported. Otherwise the remote version is held back as a
+//! [`FederationConflict`] until an admin resolves it.
+//!
+//! Deployments meet through a handshake: a node posts its id, key and URL,
+//! signed with that key, and gets the other node's identity back signed over its
+//! nonce. Both sides then hold the other as a pending peer until an admin
+//! compares the key fingerprints out of band and trusts it; only trusted peers
+//! are served or synced. Each mapped circuit carries an agreement naming the
+//! [`FederationDataClass`]es that may cross, enforced when serving and when
+//! importing. Peer, trust and agreement changes, handshakes and refused records
+//! all go to the audit log.
+
+use crate::audit_engine::{AuditEngine, AuditError};
+use crate::change_feed_engine::{
+    item_change_record, read_change_batch, record_change, record_event_change, seed_snapshot,
+    ChangeFeedError, DEFAULT_BATCH_SIZE, MAX_BATCH_SIZE,
+};
+use crate::hashing::HashAlgorithm;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    AuditEventType, AuditOutcome, AuditSeverity, ChangeKind, ChangeRecord, CircuitItem, Event,
+    EventVisibility, FederatedCircuit, FederatedRecord, FederatedRecordKind, FederationBatch,
+    FederationConflict, FederationConflictStatus, FederationDataClass, FederationHandshake,
+    FederationHandshakeReply, FederationPeer, FederationProvenance, FederationTrust, Item,
+};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Header naming the node a request comes from
+pub const NODE_HEADER: &str = "X-Federation-Node";
+/// Unix seconds the request was signed at
+pub const TIMESTAMP_HEADER: &str = "X-Federation-Timestamp";
+/// Hex ed25519 signature over `request_message`
+pub const SIGNATURE_HEADER: &str = "X-Federation-Signature";
+/// Signed requests older or newer than this are refused
+pub const MAX_CLOCK_SKEW_SECS: i64 = 300;
+/// Batches pulled per circuit and sync, so one busy circuit cannot starve the rest
+const MAX_BATCHES_PER_SYNC: usize = 20;
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Debug)]
+pub enum FederationError {
+    StorageError(StorageError),
+    ValidationError(String),
+    NotFound(String),
+    /// This deployment has no node identity
+    NotConfigured(String),
+    Unauthorized(String),
+    CursorExpired(String),
+    SyncError(String),
+    AuditError(AuditError),
+}
+
+impl From<StorageError> for FederationError {
+    fn from(err: StorageError) -> Self {
+        FederationError::StorageError(err)
+    }
+}
+
+impl From<AuditError> for FederationError {
+    fn from(err: AuditError) -> Self {
+        FederationError::AuditError(err)
+    }
+}
+
+impl From<ChangeFeedError> for FederationError {
+    fn from(err: ChangeFeedError) -> Self {
+        match err {
+            ChangeFeedError::StorageError(e) => FederationError::StorageError(e),
+            ChangeFeedError::CursorExpired(e) => FederationError::CursorExpired(e),
+            other => FederationError::ValidationError(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for FederationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FederationError::StorageError(e) => write!(f, "Storage error: {e}"),
+            FederationError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            FederationError::NotFound(e) => write!(f, "Not found: {e}"),
+            FederationError::NotConfigured(e) => write!(f, "Federation not configured: {e}"),
+            FederationError::Unauthorized(e) => write!(f, "Unauthorized: {e}"),
+            FederationError::CursorExpired(e) => write!(f, "Cursor expired: {e}"),
+            FederationError::SyncError(e) => write!(f, "Sync error: {e}"),
+            FederationError::AuditError(e) => write!(f, "Audit log error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for FederationError {}
+
+/// This deployment's identity towards its peers
+#[derive(Clone)]
+pub struct FederationNode {
+    pub node_id: String,
+    /// Base URL peers reach this node at; needed to start handshakes
+    pub url: Option<String>,
+    signing_key: SigningKey,
+}
+
+impl FederationNode {
+    pub fn new(node_id: impl Into<String>, seed: [u8; 32]) -> Self {
+        Self {
+            node_id: node_id.into(),
+            url: None,
+            signing_key: SigningKey::from_bytes(&seed),
+        }
+    }
+
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// From `FEDERATION_NODE_ID` and `FEDERATION_NODE_KEY`; `None` leaves
+    /// federation off
+    pub fn from_env() -> Option<Self> {
+        let node_id = std::env::var("FEDERATION_NODE_ID").ok()?;
+        let key = std::env::var("FEDERATION_NODE_KEY").ok()?;
+        let seed: Option<[u8; 32]> = hex::decode(key.trim())
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok());
+        match seed {
+            Some(seed) if !node_id.trim().is_empty() => {
+                let node = Self::new(node_id.trim(), seed);
+                Some(match std::env::var("FEDERATION_NODE_URL") {
+                    Ok(url) if !url.trim().is_empty() => node.with_url(url.trim()),
+                    _ => node,
+                })
+            }
+            _ => {
+                tracing::warn!(
+                    "⚠️  FEDERATION_NODE_KEY must be a 32-byte hex seed and FEDERATION_NODE_ID non-empty; federation is off"
+                );
+                None
+            }
+        }
+    }
+
+    /// Hex-encoded ed25519 public key peers register for this node
+    pub fn public_key(&self) -> String {
+        hex::encode(self.signing_key.verifying_key().to_bytes())
+    }
+
+    pub fn fingerprint(&self) -> String {
+        key_fingerprint(&self.public_key())
+    }
+
+    pub fn sign(&self, message: &str) -> String {
+        hex::encode(self.signing_key.sign(message.as_bytes()).to_bytes())
+    }
+
+    /// A signed introduction of this node, with a fresh nonce
+    pub fn handshake(&self, now: DateTime<Utc>) -> Result<FederationHandshake, FederationError> {
+        let url = self.url.clone().ok_or_else(|| {
+            FederationError::NotConfigured(
+                "set FEDERATION_NODE_URL to start handshakes".to_string(),
+            )
+        })?;
+        let mut nonce = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let nonce = hex::encode(nonce);
+        let public_key = self.public_key();
+        let timestamp = now.timestamp();
+        let signature = self.sign(&handshake_message(
+            &self.node_id,
+            &public_key,
+            &url,
+            timestamp,
+            &nonce,
+        ));
+        Ok(FederationHandshake {
+            node_id: self.node_id.clone(),
+            public_key,
+            url,
+            timestamp,
+            nonce,
+            signature,
+        })
+    }
+}
+
+/// Short BLAKE3 digest of a hex node key, for admins to compare out of band
+pub fn key_fingerprint(public_key_hex: &str) -> String {
+    let bytes = hex::decode(public_key_hex).unwrap_or_default();
+    HashAlgorithm::Blake3
+        .hash(&bytes)
+        .chars()
+        .take(16)
+        .collect()
+}
+
+/// What a node signs to introduce itself
+pub fn handshake_message(
+    node_id: &str,
+    public_key: &str,
+    url: &str,
+    timestamp: i64,
+    nonce: &str,
+) -> String {
+    format!("handshake\n{node_id}\n{public_key}\n{url}\n{timestamp}\n{nonce}")
+}
+
+/// What the receiving node signs to answer a handshake
+pub fn handshake_reply_message(
+    node_id: &str,
+    public_key: &str,
+    requester_node: &str,
+    nonce: &str,
+) -> String {
+    format!("handshake-reply\n{node_id}\n{public_key}\n{requester_node}\n{nonce}")
+}
+
+/// What the origin node signs for each record
+pub fn record_message(origin_node: &str, record_key: &str, content_hash: &str) -> String {
+    format!("{origin_node}\n{record_key}\n{content_hash}")
+}
+
+/// What a node signs to pull a page of a peer's circuit
+pub fn request_message(node_id: &str, timestamp: i64, circuit_id: &Uuid, cursor: u64) -> String {
+    format!("{node_id}\n{timestamp}\n{circuit_id}\n{cursor}")
+}
+
+fn verify_signature(public_key_hex: &str, signature_hex: &str, message: &str) -> bool {
+    let key: Option<[u8; 32]> = hex::decode(public_key_hex)
+        .ok()
+        .and_then(|b| b.try_into().ok());
+    let signature: Option<[u8; 64]> = hex::decode(signature_hex)
+        .ok()
+        .and_then(|b| b.try_into().ok());
+    let (Some(key), Some(signature)) = (key, signature) else {
+        return false;
+    };
+    VerifyingKey::from_bytes(&key)
+        .map(|key| {
+            key.verify(message.as_bytes(), &Signature::from_bytes(&signature))
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+fn item_hash(item: &Item) -> String {
+    HashAlgorithm::Blake3.hash(&serde_json::to_vec(item).unwrap_or_default())
+}
+
+/// Events are hashed without the circuit they were pushed to, which differs per
+/// deployment
+fn event_hash(event: &Event) -> String {
+    let mut event = event.clone();
+    event.pushed_to_circuit = None;
+    HashAlgorithm::Blake3.hash(&serde_json::to_vec(&event).unwrap_or_default())
+}
+
+/// Data class an event falls in by its visibility
+fn event_class(event: &Event) -> FederationDataClass {
+    match event.visibility {
+        EventVisibility::Public | EventVisibility::CircuitOnly => FederationDataClass::Events,
+        EventVisibility::Private | EventVisibility::Direct => FederationDataClass::PrivateEvents,
+    }
+}
+
+/// Why a record falls outside a circuit's agreement, if it does
+fn outside_agreement(
+    circuit: &FederatedCircuit,
+    kind: FederatedRecordKind,
+    data: &serde_json::Value,
+) -> Option<String> {
+    let class = match kind {
+        FederatedRecordKind::Item => {
+            let has_enriched_data = data["enriched_data"]
+                .as_object()
+                .is_some_and(|fields| !fields.is_empty());
+            if circuit.allows(FederationDataClass::Items)
+                && has_enriched_data
+                && !circuit.allows(FederationDataClass::EnrichedData)
+            {
+                return Some("enriched data is not in the circuit's agreement".to_string());
+            }
+            FederationDataClass::Items
+        }
+        FederatedRecordKind::Event => serde_json::from_value::<Event>(data.clone())
+            .map(|event| event_class(&event))
+            .unwrap_or(FederationDataClass::PrivateEvents),
+    };
+    (!circuit.allows(class)).then(|| {
+        format!(
+            "{} are not in the circuit's agreement",
+            class.as_str().replace('_', " ")
+        )
+    })
+}
+
+/// Content hash of a record's data, or `None` if the data is not a valid item
+/// or event
+fn content_hash(kind: FederatedRecordKind, data: &serde_json::Value) -> Option<String> {
+    match kind {
+        FederatedRecordKind::Item => serde_json::from_value::<Item>(data.clone())
+            .ok()
+            .map(|item| item_hash(&item)),
+        FederatedRecordKind::Event => serde_json::from_value::<Event>(data.clone())
+            .ok()
+            .map(|event| event_hash(&event)),
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FederatedCircuitInput {
+    pub local_circuit_id: Uuid,
+    pub remote_circuit_id: Uuid,
+    /// Agreement of the circuit; a kept mapping keeps its agreement and a new
+    /// one gets [`FederationDataClass::default_agreement`]
+    pub data_classes: Option<Vec<FederationDataClass>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FederationPeerInput {
+    pub name: String,
+    pub node_id: String,
+    pub public_key: String,
+    pub url: String,
+    #[serde(default)]
+    pub circuits: Vec<FederatedCircuitInput>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FederationPeerUpdate {
+    pub name: Option<String>,
+    pub public_key: Option<String>,
+    pub url: Option<String>,
+    /// Replaces the mapping; circuits kept from the old one keep their cursor
+    pub circuits: Option<Vec<FederatedCircuitInput>>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    /// Apply the held-back remote version over the local record
+    AcceptRemote,
+    KeepLocal,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct FederationImportSummary {
+    pub imported: usize,
+    pub unchanged: usize,
+    pub conflicts: usize,
+    /// Records with a bad signature or hash, or from an unknown node
+    pub rejected: usize,
+    /// Records of a data class the circuit's agreement does not allow
+    pub denied: usize,
+    /// Records at or before the circuit's cursor, already imported once
+    pub replayed: usize,
+}
+
+/// Outcome of pulling one circuit from a peer
+#[derive(Debug, Clone, Serialize)]
+pub struct FederationCircuitSync {
+    pub local_circuit_id: Uuid,
+    pub remote_circuit_id: Uuid,
+    pub cursor: u64,
+    #[serde(flatten)]
+    pub summary: FederationImportSummary,
+    pub error: Option<String>,
+}
+
+enum ImportOutcome {
+    Imported,
+    Unchanged,
+    Conflict,
+    Rejected(String),
+}
+
+pub struct FederationEngine<S: StorageBackend> {
+    storage: S,
+    audit: AuditEngine<S>,
+    node: Option<FederationNode>,
+    client: reqwest::Client,
+}
+
+impl<S: StorageBackend + Clone + 'static> FederationEngine<S> {
+    pub fn new(storage: S) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+        Self {
+            audit: AuditEngine::new(storage.clone()),
+            storage,
+            node: FederationNode::from_env(),
+            client,
+        }
+    }
+
+    pub fn with_node(mut self, node: FederationNode) -> Self {
+        self.node = Some(node);
+        self
+    }
+
+    pub fn node(&self) -> Result<&FederationNode, FederationError> {
+        self.node.as_ref().ok_or_else(|| {
+            FederationError::NotConfigured(
+                "set FEDERATION_NODE_ID and FEDERATION_NODE_KEY".to_string(),
+            )
+        })
+    }
+
+    fn validate_peer(
+        &self,
+        name: &str,
+        node_id: &str,
+        public_key: &str,
+        url: &str,
+    ) -> Result<(), FederationError> {
+        if name.trim().is_empty() || node_id.trim().is_empty() {
+            return Err(FederationError::ValidationError(
+                "Peers need a name and a node id".to_string(),
+            ));
+        }
+        if node_id == self.node()?.node_id {
+            return Err(FederationError::ValidationError(
+                "A node cannot peer with itself".to_string(),
+            ));
+        }
+        let key: Option<[u8; 32]> = hex::decode(public_key).ok().and_then(|b| b.try_into().ok());
+        if key.is_none_or(|key| VerifyingKey::from_bytes(&key).is_err()) {
+            return Err(FederationError::ValidationError(
+                "public_key must be a 32-byte hex-encoded ed25519 key".to_string(),
+            ));
+        }
+        let scheme_ok = url::Url::parse(url)
+            .map(|parsed| matches!(parsed.scheme(), "http" | "https"))
+            .unwrap_or(false);
+        if !scheme_ok {
+            return Err(FederationError::ValidationError(format!(
+                "Invalid peer URL: {url}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check the mapped circuits exist, keep the cursors of mappings that stay,
+    /// and start the change feed of each shared circuit
+    fn map_circuits(
+        &self,
+        inputs: Vec<FederatedCircuitInput>,
+        existing: &[FederatedCircuit],
+    ) -> Result<Vec<FederatedCircuit>, FederationError> {
+        let mut circuits: Vec<FederatedCircuit> = Vec::new();
+        for input in inputs {
+            if circuits
+                .iter()
+                .any(|c| c.local_circuit_id == input.local_circuit_id)
+            {
+                return Err(FederationError::ValidationError(format!(
+                    "Circuit {} is mapped twice",
+                    input.local_circuit_id
+                )));
+            }
+            let circuit = self
+                .storage
+                .get_circuit(&input.local_circuit_id)?
+                .ok_or_else(|| {
+                    FederationError::NotFound(format!("Circuit {}", input.local_circuit_id))
+                })?;
+            seed_snapshot(&self.storage, &circuit)?;
+            let kept = existing.iter().find(|c| {
+                c.local_circuit_id == input.local_circuit_id
+                    && c.remote_circuit_id == input.remote_circuit_id
+            });
+            let mut data_classes = match (input.data_classes, kept) {
+                (Some(data_classes), _) => data_classes,
+                (None, Some(kept)) => kept.data_classes.clone(),
+                (None, None) => FederationDataClass::default_agreement(),
+            };
+            data_classes.sort_by_key(|class| class.as_str());
+            data_classes.dedup();
+            if data_classes.is_empty() {
+                return Err(FederationError::ValidationError(format!(
+                    "The agreement for circuit {} names no data classes",
+                    input.local_circuit_id
+                )));
+            }
+            if data_classes.contains(&FederationDataClass::EnrichedData)
+                && !data_classes.contains(&FederationDataClass::Items)
+            {
+                return Err(FederationError::ValidationError(
+                    "Enriched data can only cross with items".to_string(),
+                ));
+            }
+            circuits.push(FederatedCircuit {
+                local_circuit_id: input.local_circuit_id,
+                remote_circuit_id: input.remote_circuit_id,
+                cursor: kept.map_or(0, |c| c.cursor),
+                data_classes,
+            });
+        }
+        Ok(circuits)
+    }
+
+    /// Register a peer whose key an admin already has; it is trusted right away
+    pub fn register_peer(
+        &self,
+        input: FederationPeerInput,
+        actor: &str,
+    ) -> Result<FederationPeer, FederationError> {
+        self.validate_peer(&input.name, &input.node_id, &input.public_key, &input.url)?;
+        if self.peer_by_node(&input.node_id)?.is_some() {
+            return Err(FederationError::ValidationError(format!(
+                "Node {} is already a peer",
+                input.node_id
+            )));
+        }
+        let circuits = self.map_circuits(input.circuits, &[])?;
+
+        let now = Utc::now();
+        let peer = FederationPeer {
+            peer_id: Uuid::new_v4(),
+            name: input.name.trim().to_string(),
+            node_id: input.node_id,
+            public_key: input.public_key.to_ascii_lowercase(),
+            url: input.url.trim_end_matches('/').to_string(),
+            circuits,
+            enabled: true,
+            trust: FederationTrust::Trusted,
+            last_synced_at: None,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        self.storage.store_federation_peer(&peer)?;
+        self.audit_peer(
+            actor,
+            "federation_peer_registered",
+            &peer,
+            AuditOutcome::Success,
+            AuditSeverity::High,
+        )?;
+        Ok(peer)
+    }
+
+    pub fn get_peer(&self, peer_id: &Uuid) -> Result<FederationPeer, FederationError> {
+        self.storage
+            .get_federation_peer(peer_id)?
+            .ok_or_else(|| FederationError::NotFound(format!("Federation peer {peer_id}")))
+    }
+
+    pub fn list_peers(&self) -> Result<Vec<FederationPeer>, FederationError> {
+        let mut peers = self.storage.list_federation_peers()?;
+        peers.sort_by_key(|peer| peer.created_at);
+        Ok(peers)
+    }
+
+    pub fn update_peer(
+        &self,
+        peer_id: &Uuid,
+        update: FederationPeerUpdate,
+        actor: &str,
+    ) -> Result<FederationPeer, FederationError> {
+        let mut peer = self.get_peer(peer_id)?;
+        let key_changed = update
+            .public_key
+            .as_ref()
+            .is_some_and(|key| !key.eq_ignore_ascii_case(&peer.public_key));
+        if let Some(name) = update.name {
+            peer.name = name.trim().to_string();
+        }
+        if let Some(public_key) = update.public_key {
+            peer.public_key = public_key.to_ascii_lowercase();
+        }
+        if let Some(url) = update.url {
+            peer.url = url.trim_end_matches('/').to_string();
+        }
+        self.validate_peer(&peer.name, &peer.node_id, &peer.public_key, &peer.url)?;
+        if let Some(circuits) = update.circuits {
+            peer.circuits = self.map_circuits(circuits, &peer.circuits)?;
+        }
+        if let Some(enabled) = update.enabled {
+            peer.enabled = enabled;
+        }
+        peer.updated_at = Utc::now();
+        self.storage.store_federation_peer(&peer)?;
+        self.audit_peer(
+            actor,
+            if key_changed {
+                "federation_peer_key_changed"
+            } else {
+                "federation_peer_updated"
+            },
+            &peer,
+            AuditOutcome::Success,
+            if key_changed {
+                AuditSeverity::High
+            } else {
+                AuditSeverity::Medium
+            },
+        )?;
+        Ok(peer)
+    }
+
+    pub fn delete_peer(&self, peer_id: &Uuid, actor: &str) -> Result<(), FederationError> {
+        let peer = self.get_peer(peer_id)?;
+        self.storage.delete_federation_peer(peer_id)?;
+        self.audit_peer(
+            actor,
+            "federation_peer_deleted",
+            &peer,
+            AuditOutcome::Success,
+            AuditSeverity::High,
+        )?;
+        Ok(())
+    }
+
+    /// Confirm a peer's key after comparing its fingerprint out of band
+    pub fn trust_peer(
+        &self,
+        peer_id: &Uuid,
+        actor: &str,
+    ) -> Result<FederationPeer, FederationError> {
+        self.set_trust(
+            peer_id,
+            FederationTrust::Trusted,
+            "federation_peer_trusted",
+            actor,
+        )
+    }
+
+    /// Stop serving and syncing a peer and refuse its handshakes
+    pub fn revoke_peer(
+        &self,
+        peer_id: &Uuid,
+        actor: &str,
+    ) -> Result<FederationPeer, FederationError> {
+        self.set_trust(
+            peer_id,
+            FederationTrust::Revoked,
+            "federation_peer_revoked",
+            actor,
+        )
+    }
+
+    fn set_trust(
+        &self,
+        peer_id: &Uuid,
+        trust: FederationTrust,
+        action: &str,
+        actor: &str,
+    ) -> Result<FederationPeer, FederationError> {
+        let mut peer = self.get_peer(peer_id)?;
+        if peer.trust == trust {
+            return Err(FederationError::ValidationError(format!(
+                "Peer {} is already {}",
+                peer.name,
+                trust.as_str()
+            )));
+        }
+        peer.trust = trust;
+        peer.updated_at = Utc::now();
+        self.storage.store_federation_peer(&peer)?;
+        self.audit_peer(
+            actor,
+            action,
+            &peer,
+            AuditOutcome::Success,
+            AuditSeverity::High,
+        )?;
+        Ok(peer)
+    }
+
+    /// Answer another node's handshake. A new node becomes a pending peer; a
+    /// known one must present the key it is known by unless it is still pending.
+    pub fn accept_handshake(
+        &self,
+        handshake: &FederationHandshake,
+        now: DateTime<Utc>,
+    ) -> Result<FederationHandshakeReply, FederationError> {
+        let node = self.node()?;
+        if (now.timestamp() - handshake.timestamp).abs() > MAX_CLOCK_SKEW_SECS {
+            return Err(FederationError::Unauthorized(
+                "Handshake timestamp is too far from the server clock".to_string(),
+            ));
+        }
+        if handshake.nonce.len() < 16 {
+            return Err(FederationError::ValidationError(
+                "Handshake nonce is too short".to_string(),
+            ));
+        }
+        let public_key = handshake.public_key.to_ascii_lowercase();
+        let url = handshake.url.trim_end_matches('/');
+        self.validate_peer(&handshake.node_id, &handshake.node_id, &public_key, url)?;
+        let message = handshake_message(
+            &handshake.node_id,
+            &handshake.public_key,
+            &handshake.url,
+            handshake.timestamp,
+            &handshake.nonce,
+        );
+        if !verify_signature(&public_key, &handshake.signature, &message) {
+            return Err(FederationError::Unauthorized(
+                "Handshake signature does not match the presented key".to_string(),
+            ));
+        }
+
+        self.record_handshake(
+            &handshake.node_id,
+            &public_key,
+            url,
+            &format!("federation:{}", handshake.node_id),
+            "federation_handshake_received",
+        )?;
+        let own_key = node.public_key();
+        let signature = node.sign(&handshake_reply_message(
+            &node.node_id,
+            &own_key,
+            &handshake.node_id,
+            &handshake.nonce,
+        ));
+        Ok(FederationHandshakeReply {
+            node_id: node.node_id.clone(),
+            public_key: own_key,
+            nonce: handshake.nonce.clone(),
+            signature,
+        })
+    }
+
+    /// Introduce this node to the deployment at `url` and keep it as a pending
+    /// peer if its reply checks out
+    pub async fn initiate_handshake(
+        &self,
+        url: &str,
+        actor: &str,
+    ) -> Result<FederationPeer, FederationError> {
+        let node = self.node()?;
+        let url = url.trim().trim_end_matches('/');
+        let handshake = node.handshake(Utc::now())?;
+        let response = self
+            .client
+            .post(format!("{url}/api/federation/handshake"))
+            .json(&handshake)
+            .send()
+            .await
+            .map_err(|e| FederationError::SyncError(format!("Handshake failed: {e}")))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(FederationError::SyncError(format!(
+                "{url} refused the handshake with {status}: {}",
+                body.chars().take(200).collect::<String>()
+            )));
+        }
+        let reply: FederationHandshakeReply = response
+            .json()
+            .await
+            .map_err(|e| FederationError::SyncError(format!("Invalid handshake reply: {e}")))?;
+        self.complete_handshake(&handshake, &reply, url, actor)
+    }
+
+    /// Check the reply to a handshake this node sent and record its sender
+    pub fn complete_handshake(
+        &self,
+        handshake: &FederationHandshake,
+        reply: &FederationHandshakeReply,
+        url: &str,
+        actor: &str,
+    ) -> Result<FederationPeer, FederationError> {
+        let public_key = reply.public_key.to_ascii_lowercase();
+        self.validate_peer(&reply.node_id, &reply.node_id, &public_key, url)?;
+        let message = handshake_reply_message(
+            &reply.node_id,
+            &reply.public_key,
+            &handshake.node_id,
+            &handshake.nonce,
+        );
+        if reply.nonce != handshake.nonce
+            || !verify_signature(&public_key, &reply.signature, &message)
+        {
+            return Err(FederationError::Unauthorized(
+                "Handshake reply is not signed over our nonce by the presented key".to_string(),
+            ));
+        }
+        self.record_handshake(
+            &reply.node_id,
+            &public_key,
+            url,
+            actor,
+            "federation_handshake_completed",
+        )
+    }
+
+    fn record_handshake(
+        &self,
+        node_id: &str,
+        public_key: &str,
+        url: &str,
+        actor: &str,
+        action: &str,
+    ) -> Result<FederationPeer, FederationError> {
+        let Some(mut peer) = self.peer_by_node(node_id)? else {
+            let now = Utc::now();
+            let peer = FederationPeer {
+                peer_id: Uuid::new_v4(),
+                name: node_id.to_string(),
+                node_id: node_id.to_string(),
+                public_key: public_key.to_string(),
+                url: url.to_string(),
+                circuits: vec![],
+                enabled: true,
+                trust: FederationTrust::Pending,
+                last_synced_at: None,
+                last_error: None,
+                created_at: now,
+                updated_at: now,
+            };
+            self.storage.store_federation_peer(&peer)?;
+            self.audit_peer(
+                actor,
+                action,
+                &peer,
+                AuditOutcome::Success,
+                AuditSeverity::Medium,
+            )?;
+            return Ok(peer);
+        };
+
+        let refusal = match peer.trust {
+            FederationTrust::Revoked => Some(format!("Node {node_id} is revoked")),
+            FederationTrust::Trusted if peer.public_key != public_key => Some(format!(
+                "Node {node_id} is trusted with another key; an admin must change it"
+            )),
+            _ => None,
+        };
+        if let Some(refusal) = refusal {
+            self.audit_peer(
+                actor,
+                action,
+                &peer,
+                AuditOutcome::Blocked,
+                AuditSeverity::High,
+            )?;
+            return Err(FederationError::Unauthorized(refusal));
+        }
+        if peer.trust == FederationTrust::Pending {
+            peer.public_key = public_key.to_string();
+            peer.url = url.to_string();
+            peer.updated_at = Utc::now();
+            self.storage.store_federation_peer(&peer)?;
+        }
+        self.audit_peer(
+            actor,
+            action,
+            &peer,
+            AuditOutcome::Success,
+            AuditSeverity::Low,
+        )?;
+        Ok(peer)
+    }
+
+    fn audit_peer(
+        &self,
+        actor: &str,
+        action: &str,
+        peer: &FederationPeer,
+        outcome: AuditOutcome,
+        severity: AuditSeverity,
+    ) -> Result<(), FederationError> {
+        self.audit_peer_with(actor, action, peer, outcome, severity, HashMap::new())
+    }
+
+    fn audit_peer_with(
+        &self,
+        actor: &str,
+        action: &str,
+        peer: &FederationPeer,
+        outcome: AuditOutcome,
+        severity: AuditSeverity,
+        mut details: HashMap<String, serde_json::Value>,
+    ) -> Result<(), FederationError> {
+        details.extend([
+            ("node_id".to_string(), json!(peer.node_id)),
+            (
+                "fingerprint".to_string(),
+                json!(key_fingerprint(&peer.public_key)),
+            ),
+            ("trust".to_string(), json!(peer.trust)),
+            ("enabled".to_string(), json!(peer.enabled)),
+            (
+                "agreements".to_string(),
+                json!(peer
+                    .circuits
+                    .iter()
+                    .map(|circuit| json!({
+                        "local_circuit_id": circuit.local_circuit_id,
+                        "remote_circuit_id": circuit.remote_circuit_id,
+                        "data_classes": circuit.data_classes
+                    }))
+                    .collect::<Vec<_>>()),
+            ),
+        ]);
+        self.audit.log_event(
+            actor.to_string(),
+            AuditEventType::Security,
+            action.to_string(),
+            format!("federation_peer:{}", peer.peer_id),
+            outcome,
+            severity,
+            Some(details),
+            None,
+            None,
+        )?;
+        Ok(())
+    }
+
+    fn peer_by_node(&self, node_id: &str) -> Result<Option<FederationPeer>, FederationError> {
+        Ok(self
+            .storage
+            .list_federation_peers()?
+            .into_iter()
+            .find(|peer| peer.node_id == node_id))
+    }
+
+    /// The enabled peer that signed a pull request for `circuit_id`
+    pub fn authenticate(
+        &self,
+        node_id: &str,
+        timestamp: i64,
+        signature: &str,
+        circuit_id: &Uuid,
+        cursor: u64,
+        now: DateTime<Utc>,
+    ) -> Result<FederationPeer, FederationError> {
+        self.node()?;
+        if (now.timestamp() - timestamp).abs() > MAX_CLOCK_SKEW_SECS {
+            return Err(FederationError::Unauthorized(
+                "Request timestamp is too far from the server clock".to_string(),
+            ));
+        }
+        let peer = self
+            .peer_by_node(node_id)?
+            .filter(|peer| peer.enabled && peer.trust == FederationTrust::Trusted)
+            .ok_or_else(|| {
+                FederationError::Unauthorized(format!("Node {node_id} is not a trusted peer"))
+            })?;
+        let message = request_message(node_id, timestamp, circuit_id, cursor);
+        if !verify_signature(&peer.public_key, signature, &message) {
+            return Err(FederationError::Unauthorized(
+                "Request signature does not match the peer's key".to_string(),
+            ));
+        }
+        Ok(peer)
+    }
+
+    /// Page of a shared circuit's changes for `peer`. Records the peer itself
+    /// produced and data classes outside the circuit's agreement are left out.
+    pub fn serve_changes(
+        &self,
+        peer: &FederationPeer,
+        circuit_id: &Uuid,
+        cursor: u64,
+        limit: Option<usize>,
+    ) -> Result<FederationBatch, FederationError> {
+        let node = self.node()?;
+        let Some(circuit) = peer.circuit_by_local(circuit_id) else {
+            return Err(FederationError::Unauthorized(format!(
+                "Circuit {circuit_id} is not shared with {}",
+                peer.name
+            )));
+        };
+        let limit = limit.unwrap_or(DEFAULT_BATCH_SIZE).clamp(1, MAX_BATCH_SIZE);
+        let batch = read_change_batch(&self.storage, circuit_id, cursor, limit)?;
+
+        let mut records = Vec::new();
+        for change in &batch.records {
+            if let Some(record) = self.federated_record(node, peer, change, circuit)? {
+                records.push(record);
+            }
+        }
+        Ok(FederationBatch {
+            node_id: node.node_id.clone(),
+            circuit_id: *circuit_id,
+            cursor: batch.cursor,
+            next_cursor: batch.next_cursor,
+            records,
+            has_more: batch.has_more,
+        })
+    }
+
+    /// A change as exchanged with a peer: relayed with its original signature if
+    /// it is the version imported from elsewhere, otherwise signed by this node.
+    /// Items go without their enriched data when the agreement leaves it out,
+    /// and versions the peer itself produced are not echoed back.
+    fn federated_record(
+        &self,
+        node: &FederationNode,
+        peer: &FederationPeer,
+        change: &ChangeRecord,
+        circuit: &FederatedCircuit,
+    ) -> Result<Option<FederatedRecord>, FederationError> {
+        let (kind, data) = match change.kind {
+            ChangeKind::ItemSnapshot | ChangeKind::ItemAdded | ChangeKind::ItemUpdated => {
+                (FederatedRecordKind::Item, change.data["item"].clone())
+            }
+            ChangeKind::Event => (FederatedRecordKind::Event, change.data.clone()),
+            ChangeKind::MemberSnapshot | ChangeKind::MemberAdded | ChangeKind::MemberUpdated => {
+                return Ok(None)
+            }
+        };
+        let (Some(hash), Some(id)) = (
+            content_hash(kind, &data),
+            match kind {
+                FederatedRecordKind::Item => change.dfid.clone(),
+                FederatedRecordKind::Event => data["event_id"].as_str().map(str::to_string),
+            },
+        ) else {
+            return Ok(None);
+        };
+
+        let mut record = FederatedRecord {
+            kind,
+            id,
+            sequence: change.sequence,
+            data,
+            content_hash: hash,
+            origin_node: node.node_id.clone(),
+            signature: String::new(),
+        };
+        let record_key = record.record_key();
+        let provenance = self
+            .storage
+            .get_federation_provenance(&record_key)?
+            .filter(|provenance| provenance.content_hash == record.content_hash);
+        if provenance
+            .as_ref()
+            .is_some_and(|provenance| provenance.origin_node == peer.node_id)
+        {
+            return Ok(None);
+        }
+
+        let strip_enriched_data = kind == FederatedRecordKind::Item
+            && !circuit.allows(FederationDataClass::EnrichedData)
+            && record.data["enriched_data"]
+                .as_object()
+                .is_some_and(|fields| !fields.is_empty());
+        if strip_enriched_data {
+            record.data["enriched_data"] = json!({});
+            record.content_hash = content_hash(kind, &record.data).unwrap_or_default();
+        }
+        if outside_agreement(circuit, kind, &record.data).is_some() {
+            return Ok(None);
+        }
+        match provenance {
+            Some(provenance) if !strip_enriched_data => {
+                record.origin_node = provenance.origin_node;
+                record.signature = provenance.signature;
+            }
+            _ => {
+                record.signature = node.sign(&record_message(
+                    &node.node_id,
+                    &record_key,
+                    &record.content_hash,
+                ));
+            }
+        }
+        Ok(Some(record))
+    }
+
+    /// Import a batch pulled from `peer` into the local circuit it maps to.
+    /// Records up to the circuit's stored cursor were imported before and are
+    /// skipped, so a replayed batch cannot roll back newer versions; the cursor
+    /// then moves to the end of the batch.
+    pub fn import_batch(
+        &self,
+        peer: &FederationPeer,
+        local_circuit_id: &Uuid,
+        batch: &FederationBatch,
+    ) -> Result<FederationImportSummary, FederationError> {
+        let node = self.node()?;
+        let mut stored = self.get_peer(&peer.peer_id)?;
+        let index = stored
+            .circuits
+            .iter()
+            .position(|circuit| circuit.local_circuit_id == *local_circuit_id)
+            .ok_or_else(|| {
+                FederationError::ValidationError(format!(
+                    "Circuit {local_circuit_id} is not shared with {}",
+                    peer.name
+                ))
+            })?;
+        let circuit = stored.circuits[index].clone();
+        if batch.node_id != peer.node_id || batch.circuit_id != circuit.remote_circuit_id {
+            return Err(FederationError::ValidationError(format!(
+                "Batch is not {}'s feed of circuit {}",
+                peer.name, circuit.remote_circuit_id
+            )));
+        }
+        let mut summary = FederationImportSummary::default();
+        let mut denied = Vec::new();
+        for record in &batch.records {
+            if record.sequence <= circuit.cursor {
+                summary.replayed += 1;
+                continue;
+            }
+            if let Some(reason) = outside_agreement(&circuit, record.kind, &record.data) {
+                denied.push(json!({"record_key": record.record_key(), "reason": reason}));
+                summary.denied += 1;
+                continue;
+            }
+            match self.import_record(node, peer, local_circuit_id, record)? {
+                ImportOutcome::Imported => summary.imported += 1,
+                ImportOutcome::Unchanged => summary.unchanged += 1,
+                ImportOutcome::Conflict => summary.conflicts += 1,
+                ImportOutcome::Rejected(reason) => {
+                    tracing::warn!(
+                        "⚠️  Rejected {} from federation peer {}: {}",
+                        record.record_key(),
+                        peer.name,
+                        reason
+                    );
+                    summary.rejected += 1;
+                }
+            }
+        }
+        if !denied.is_empty() {
+            self.audit_peer_with(
+                &format!("federation:{}", peer.node_id),
+                "federation_records_denied",
+                peer,
+                AuditOutcome::Blocked,
+                AuditSeverity::Medium,
+                HashMap::from([
+                    ("local_circuit_id".to_string(), json!(local_circuit_id)),
+                    ("records".to_string(), json!(denied)),
+                ]),
+            )?;
+        }
+        if batch.next_cursor > circuit.cursor {
+            stored.circuits[index].cursor = batch.next_cursor;
+            stored.updated_at = Utc::now();
+            self.storage.store_federation_peer(&stored)?;
+        }
+        Ok(summary)
+    }
+
+    fn import_record(
+        &self,
+        node: &FederationNode,
+        peer: &FederationPeer,
+        local_circuit_id: &Uuid,
+        record: &FederatedRecord,
+    ) -> Result<ImportOutcome, FederationError> {
+        if record.origin_node == node.node_id {
+            return Ok(ImportOutcome::Unchanged);
+        }
+        let public_key = if record.origin_node == peer.node_id {
+            Some(peer.public_key.clone())
+        } else {
+            self.peer_by_node(&record.origin_node)?
+                .filter(|origin| origin.trust == FederationTrust::Trusted)
+                .map(|origin| origin.public_key)
+        };
+        let Some(public_key) = public_key else {
+            return Ok(ImportOutcome::Rejected(format!(
+                "origin node {} is not a known peer",
+                record.origin_node
+            )));
+        };
+        if content_hash(record.kind, &record.data).as_deref() != Some(&record.content_hash) {
+            return Ok(ImportOutcome::Rejected(
+                "content does not match its hash".to_string(),
+            ));
+        }
+        let record_key = record.record_key();
+        let message = record_message(&record.origin_node, &record_key, &record.content_hash);
+        if !verify_signature(&public_key, &record.signature, &message) {
+            return Ok(ImportOutcome::Rejected(
+                "signature does not match the origin node's key".to_string(),
+            ));
+        }
+
+        let local_hash = match record.kind {
+            FederatedRecordKind::Item => self
+                .storage
+                .get_item_by_dfid(&record.id)?
+                .map(|item| item_hash(&item)),
+            FederatedRecordKind::Event => match Uuid::parse_str(&record.id) {
+                Ok(event_id) => self
+                    .storage
+                    .get_event(&event_id)?
+                    .map(|event| event_hash(&event)),
+                Err(_) => return Ok(ImportOutcome::Rejected("invalid event id".to_string())),
+            },
+        };
+        match local_hash {
+            Some(hash) if hash == record.content_hash => {
+                if record.kind == FederatedRecordKind::Item {
+                    self.ensure_in_circuit(&record.id, local_circuit_id, &record.origin_node)?;
+                }
+                Ok(ImportOutcome::Unchanged)
+            }
+            Some(hash) => {
+                // Fast-forward only over a local copy nobody touched since its import
+                let untouched = self
+                    .storage
+                    .get_federation_provenance(&record_key)?
+                    .is_some_and(|provenance| provenance.content_hash == hash);
+                if untouched {
+                    self.apply(peer.peer_id, local_circuit_id, record)?;
+                    Ok(ImportOutcome::Imported)
+                } else {
+                    self.hold_back(peer.peer_id, local_circuit_id, record, hash)?;
+                    Ok(ImportOutcome::Conflict)
+                }
+            }
+            None => {
+                self.apply(peer.peer_id, local_circuit_id, record)?;
+                Ok(ImportOutcome::Imported)
+            }
+        }
+    }
+
+    /// Store a verified record and remember where it came from
+    fn apply(
+        &self,
+        peer_id: Uuid,
+        local_circuit_id: &Uuid,
+        record: &FederatedRecord,
+    ) -> Result<(), FederationError> {
+        let invalid =
+            |e: serde_json::Error| FederationError::ValidationError(format!("Invalid record: {e}"));
+        match record.kind {
+            FederatedRecordKind::Item => {
+                let item: Item = serde_json::from_value(record.data.clone()).map_err(invalid)?;
+                let exists = self.storage.get_item_by_dfid(&item.dfid)?.is_some();
+                if exists {
+                    self.storage.update_item(&item)?;
+                } else {
+                    self.storage.store_item(&item)?;
+                }
+                let added =
+                    self.ensure_in_circuit(&item.dfid, local_circuit_id, &record.origin_node)?;
+                if !added {
+                    let circuit_item = self
+                        .storage
+                        .get_circuit_items(local_circuit_id)?
+                        .into_iter()
+                        .find(|ci| ci.dfid == item.dfid);
+                    if let Some(circuit_item) = circuit_item {
+                        record_change(
+                            &self.storage,
+                            item_change_record(
+                                &self.storage,
+                                &circuit_item,
+                                ChangeKind::ItemUpdated,
+                            ),
+                        );
+                    }
+                }
+            }
+            FederatedRecordKind::Event => {
+                let mut event: Event =
+                    serde_json::from_value(record.data.clone()).map_err(invalid)?;
+                event.pushed_to_circuit = Some(*local_circuit_id);
+                self.storage.store_event(&event)?;
+                record_event_change(&self.storage, &event);
+            }
+        }
+
+        self.storage
+            .store_federation_provenance(&FederationProvenance {
+                record_key: record.record_key(),
+                origin_node: record.origin_node.clone(),
+                content_hash: record.content_hash.clone(),
+                signature: record.signature.clone(),
+                peer_id,
+                imported_at: Utc::now(),
+            })?;
+        Ok(())
+    }
+
+    /// Add an imported item to the local circuit; `false` if it was already there
+    fn ensure_in_circuit(
+        &self,
+        dfid: &str,
+        local_circuit_id: &Uuid,
+        origin_node: &str,
+    ) -> Result<bool, FederationError> {
+        let present = self
+            .storage
+            .get_circuit_items(local_circuit_id)?
+            .iter()
+            .any(|circuit_item| circuit_item.dfid == dfid);
+        if present {
+            return Ok(false);
+        }
+        let circuit_item = CircuitItem::new(
+            dfid.to_string(),
+            *local_circuit_id,
+            format!("federation:{origin_node}"),
+            vec![],
+        );
+        self.storage.store_circuit_item(&circuit_item)?;
+        record_change(
+            &self.storage,
+            item_change_record(&self.storage, &circuit_item, ChangeKind::ItemAdded),
+        );
+        Ok(true)
+    }
+
+    /// Keep a diverging remote version for review; the same version is held once
+    fn hold_back(
+        &self,
+        peer_id: Uuid,
+        local_circuit_id: &Uuid,
+        record: &FederatedRecord,
+        local_hash: String,
+    ) -> Result<(), FederationError> {
+        let record_key = record.record_key();
+        let already_held = self.storage.list_federation_conflicts()?.iter().any(|c| {
+            c.status == FederationConflictStatus::Open
+                && c.record_key == record_key
+                && c.remote.content_hash == record.content_hash
+        });
+        if already_held {
+            return Ok(());
+        }
+        self.storage
+            .store_federation_conflict(&FederationConflict {
+                conflict_id: Uuid::new_v4(),
+                peer_id,
+                local_circuit_id: *local_circuit_id,
+                record_key,
+                local_hash,
+                remote: record.clone(),
+                status: FederationConflictStatus::Open,
+                detected_at: Utc::now(),
+                resolved_by: None,
+                resolved_at: None,
+            })?;
+        Ok(())
+    }
+
+    /// Conflicts, newest first
+    pub fn list_conflicts(
+        &self,
+        status: Option<FederationConflictStatus>,
+    ) -> Result<Vec<FederationConflict>, FederationError> {
+        let mut conflicts: Vec<_> = self
+            .storage
+            .list_federation_conflicts()?
+            .into_iter()
+            .filter(|c| status.is_none_or(|status| c.status == status))
+            .collect();
+        conflicts.sort_by_key(|c| std::cmp::Reverse(c.detected_at));
+        Ok(conflicts)
+    }
+
+    pub fn resolve_conflict(
+        &self,
+        conflict_id: &Uuid,
+        resolution: ConflictResolution,
+        resolved_by: &str,
+    ) -> Result<FederationConflict, FederationError> {
+        let mut conflict = self
+            .storage
+            .get_federation_conflict(conflict_id)?
+            .ok_or_else(|| {
+                FederationError::NotFound(format!("Federation conflict {conflict_id}"))
+            })?;
+        if conflict.status != FederationConflictStatus::Open {
+            return Err(FederationError::ValidationError(
+                "Conflict is already resolved".to_string(),
+            ));
+        }
+        conflict.status = match resolution {
+            ConflictResolution::AcceptRemote => {
+                self.apply(
+                    conflict.peer_id,
+                    &conflict.local_circuit_id,
+                    &conflict.remote,
+                )?;
+                FederationConflictStatus::AcceptedRemote
+            }
+            ConflictResolution::KeepLocal => FederationConflictStatus::KeptLocal,
+        };
+        conflict.resolved_by = Some(resolved_by.to_string());
+        conflict.resolved_at = Some(Utc::now());
+        self.storage.store_federation_conflict(&conflict)?;
+        if let Some(peer) = self.storage.get_federation_peer(&conflict.peer_id)? {
+            self.audit_peer_with(
+                resolved_by,
+                "federation_conflict_resolved",
+                &peer,
+                AuditOutcome::Success,
+                AuditSeverity::Medium,
+                HashMap::from([
+                    ("conflict_id".to_string(), json!(conflict.conflict_id)),
+                    ("record_key".to_string(), json!(conflict.record_key)),
+                    ("status".to_string(), json!(conflict.status)),
+                ]),
+            )?;
+        }
+        Ok(conflict)
+    }
+
+    async fn fetch_batch(
+        &self,
+        node: &FederationNode,
+        peer: &FederationPeer,
+        circuit: &FederatedCircuit,
+    ) -> Result<FederationBatch, FederationError> {
+        let timestamp = Utc::now().timestamp();
+        let signature = node.sign(&request_message(
+            &node.node_id,
+            timestamp,
+            &circuit.remote_circuit_id,
+            circuit.cursor,
+        ));
+        let response = self
+            .client
+            .get(format!(
+                "{}/api/federation/circuits/{}/changes",
+                peer.url, circuit.remote_circuit_id
+            ))
+            .query(&[("cursor", circuit.cursor)])
+            .header(NODE_HEADER, &node.node_id)
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, signature)
+            .send()
+            .await
+            .map_err(|e| FederationError::SyncError(format!("Request failed: {e}")))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(FederationError::SyncError(format!(
+                "{} answered {}: {}",
+                peer.name,
+                status,
+                body.chars().take(200).collect::<String>()
+            )));
+        }
+        let batch: FederationBatch = response
+            .json()
+            .await
+            .map_err(|e| FederationError::SyncError(format!("Invalid batch: {e}")))?;
+        if batch.node_id != peer.node_id
+            || batch.circuit_id != circuit.remote_circuit_id
+            || batch.cursor != circuit.cursor
+        {
+            return Err(FederationError::SyncError(format!(
+                "{} answered with a batch for another node, circuit or cursor",
+                peer.name
+            )));
+        }
+        Ok(batch)
+    }
+
+    /// Pull every mapped circuit of a peer until caught up. Cursors are stored
+    /// after each imported batch, so an interrupted sync resumes where it stopped.
+    pub async fn sync_peer(
+        &self,
+        peer_id: &Uuid,
+    ) -> Result<Vec<FederationCircuitSync>, FederationError> {
+        let node = self.node()?;
+        let mut peer = self.get_peer(peer_id)?;
+        if !peer.enabled {
+            return Err(FederationError::ValidationError(format!(
+                "Peer {} is disabled",
+                peer.name
+            )));
+        }
+        if peer.trust != FederationTrust::Trusted {
+            return Err(FederationError::Unauthorized(format!(
+                "Peer {} is not trusted",
+                peer.name
+            )));
+        }
+
+        let mut results = Vec::new();
+        for index in 0..peer.circuits.len() {
+            let mut summary = FederationImportSummary::default();
+            let mut error = None;
+            for _ in 0..MAX_BATCHES_PER_SYNC {
+                let circuit = peer.circuits[index].clone();
+                let batch = match self.fetch_batch(node, &peer, &circuit).await {
+                    Ok(batch) => batch,
+                    Err(e) => {
+                        error = Some(e.to_string());
+                        break;
+                    }
+                };
+                let imported = self.import_batch(&peer, &circuit.local_circuit_id, &batch)?;
+                summary.imported += imported.imported;
+                summary.unchanged += imported.unchanged;
+                summary.conflicts += imported.conflicts;
+                summary.rejected += imported.rejected;
+                summary.denied += imported.denied;
+                summary.replayed += imported.replayed;
+
+                peer.circuits[index].cursor = batch.next_cursor;
+                peer.updated_at = Utc::now();
+                self.storage.store_federation_peer(&peer)?;
+                if !batch.has_more {
+                    break;
+                }
+            }
+            let circuit = &peer.circuits[index];
+            results.push(FederationCircuitSync {
+                local_circuit_id: circuit.local_circuit_id,
+                remote_circuit_id: circuit.remote_circuit_id,
+                cursor: circuit.cursor,
+                summary,
+                error,
+            });
+        }
+
+        peer.last_synced_at = Some(Utc::now());
+        peer.last_error = results.iter().find_map(|result| result.error.clone());
+        peer.updated_at = Utc::now();
+        self.storage.store_federation_peer(&peer)?;
+        Ok(results)
+    }
+
+    /// Background task syncing every enabled peer each `tick`. Does nothing
+    /// without a node identity.
+    pub fn spawn_sync_worker(storage: S, tick: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let engine = FederationEngine::new(storage);
+            if engine.node.is_none() {
+                return;
+            }
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                let peers = match engine.list_peers() {
+                    Ok(peers) => peers,
+                    Err(e) => {
+                        tracing::warn!("⚠️  Failed to list federation peers: {}", e);
+                        continue;
+                    }
+                };
+                for peer in peers
+                    .into_iter()
+                    .filter(|peer| peer.enabled && peer.trust == FederationTrust::Trusted)
+                {
+                    match engine.sync_peer(&peer.peer_id).await {
+                        Ok(results) => {
+                            let imported: usize = results.iter().map(|r| r.summary.imported).sum();
+                            let conflicts: usize =
+                                results.iter().map(|r| r.summary.conflicts).sum();
+                            if imported + conflicts > 0 {
+                                tracing::info!(
+                                    "🔄 Synced {} from {}: {} imported, {} conflicts",
+                                    results.len(),
+                                    peer.name,
+                                    imported,
+                                    conflicts
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            tracing::warn!("⚠️  Federation sync with {} failed: {}", peer.name, e)
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::CircuitsEngine;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    type Storage = Arc<Mutex<InMemoryStorage>>;
+
+    async fn deployment(circuit_name: &str) -> (Storage, Uuid) {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let circuit = CircuitsEngine::new(Arc::clone(&storage))
+            .create_circuit(
+                circuit_name.to_string(),
+                "Shared herd".to_string(),
+                "alice".to_string(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        (storage, circuit.circuit_id)
+    }
+
+    fn peering(
+        name: &str,
+        node: &FederationNode,
+        local: Uuid,
+        remote: Uuid,
+    ) -> FederationPeerInput {
+        FederationPeerInput {
+            name: name.to_string(),
+            node_id: node.node_id.clone(),
+            public_key: node.public_key(),
+            url: format!("https://{name}.example.com"),
+            circuits: vec![FederatedCircuitInput {
+                local_circuit_id: local,
+                remote_circuit_id: remote,
+                data_classes: None,
+            }],
+        }
+    }
+
+    /// A co-op and a hosted deployment peered over one circuit each, with
+    /// DFID-1 already in the co-op's circuit
+    struct Federation {
+        coop_storage: Storage,
+        coop_circuit: Uuid,
+        coop_node: FederationNode,
+        coop: FederationEngine<Storage>,
+        hosted_storage: Storage,
+        hosted_circuit: Uuid,
+        hosted_node: FederationNode,
+        hosted: FederationEngine<Storage>,
+        /// The hosted node as registered on the co-op side
+        hosted_peer: FederationPeer,
+        /// The co-op node as registered on the hosted side
+        coop_peer: FederationPeer,
+        circuit_item: CircuitItem,
+    }
+
+    async fn federation() -> Federation {
+        let (coop_storage, coop_circuit) = deployment("Co-op herd").await;
+        let (hosted_storage, hosted_circuit) = deployment("Hosted herd").await;
+        let coop_node = FederationNode::new("coop", [1u8; 32]);
+        let hosted_node = FederationNode::new("hosted", [2u8; 32]);
+        let coop = FederationEngine::new(Arc::clone(&coop_storage)).with_node(coop_node.clone());
+        let hosted =
+            FederationEngine::new(Arc::clone(&hosted_storage)).with_node(hosted_node.clone());
+
+        coop_storage
+            .store_item(&Item::new("DFID-1".to_string(), vec![], Uuid::new_v4()))
+            .unwrap();
+        let circuit_item = CircuitItem::new(
+            "DFID-1".to_string(),
+            coop_circuit,
+            "alice".to_string(),
+            vec![],
+        );
+        coop_storage.store_circuit_item(&circuit_item).unwrap();
+        let hosted_peer = coop
+            .register_peer(
+                peering("hosted", &hosted_node, coop_circuit, hosted_circuit),
+                "admin",
+            )
+            .unwrap();
+        let coop_peer = hosted
+            .register_peer(
+                peering("coop", &coop_node, hosted_circuit, coop_circuit),
+                "admin",
+            )
+            .unwrap();
+
+        Federation {
+            coop_storage,
+            coop_circuit,
+            coop_node,
+            coop,
+            hosted_storage,
+            hosted_circuit,
+            hosted_node,
+            hosted,
+            hosted_peer,
+            coop_peer,
+            circuit_item,
+        }
+    }
+
+    impl Federation {
+        /// The co-op's first batch, imported on the hosted side
+        fn import_snapshot(&self) -> FederationBatch {
+            let batch = self
+                .coop
+                .serve_changes(&self.hosted_peer, &self.coop_circuit, 0, None)
+                .unwrap();
+            let summary = self
+                .hosted
+                .import_batch(&self.coop_peer, &self.hosted_circuit, &batch)
+                .unwrap();
+            assert_eq!(summary.imported, 1);
+            batch
+        }
+
+        /// The co-op sets DFID-1's confidence score and records the change
+        fn update_on_coop(&self, confidence_score: f64) {
+            let mut item = self
+                .coop_storage
+                .get_item_by_dfid("DFID-1")
+                .unwrap()
+                .unwrap();
+            item.confidence_score = confidence_score;
+            self.coop_storage.update_item(&item).unwrap();
+            record_change(
+                &self.coop_storage,
+                item_change_record(
+                    &self.coop_storage,
+                    &self.circuit_item,
+                    ChangeKind::ItemUpdated,
+                ),
+            );
+        }
+
+        fn hosted_score(&self) -> f64 {
+            self.hosted_storage
+                .get_item_by_dfid("DFID-1")
+                .unwrap()
+                .unwrap()
+                .confidence_score
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signed_pull_request_is_authenticated() {
+        let f = federation().await;
+        let now = Utc::now();
+        let signature = f.hosted_node.sign(&request_message(
+            "hosted",
+            now.timestamp(),
+            &f.coop_circuit,
+            0,
+        ));
+
+        let peer = f
+            .coop
+            .authenticate(
+                "hosted",
+                now.timestamp(),
+                &signature,
+                &f.coop_circuit,
+                0,
+                now,
+            )
+            .unwrap();
+        assert_eq!(peer.peer_id, f.hosted_peer.peer_id);
+    }
+
+    #[tokio::test]
+    async fn test_pull_request_with_bad_signature_is_refused() {
+        let f = federation().await;
+        let now = Utc::now();
+        let signature = f.hosted_node.sign(&request_message(
+            "hosted",
+            now.timestamp(),
+            &f.coop_circuit,
+            0,
+        ));
+
+        // Signed for another cursor
+        assert!(matches!(
+            f.coop.authenticate(
+                "hosted",
+                now.timestamp(),
+                &signature,
+                &f.coop_circuit,
+                5,
+                now
+            ),
+            Err(FederationError::Unauthorized(_))
+        ));
+
+        // Signed by another node's key
+        let impostor = f.coop_node.sign(&request_message(
+            "hosted",
+            now.timestamp(),
+            &f.coop_circuit,
+            0,
+        ));
+        assert!(matches!(
+            f.coop.authenticate(
+                "hosted",
+                now.timestamp(),
+                &impostor,
+                &f.coop_circuit,
+                0,
+                now
+            ),
+            Err(FederationError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            f.coop
+                .authenticate("hosted", now.timestamp(), "zz", &f.coop_circuit, 0, now),
+            Err(FederationError::Unauthorized(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_pull_request_outside_clock_skew_is_refused() {
+        let f = federation().await;
+        let signed_at = Utc::now();
+        let signature = f.hosted_node.sign(&request_message(
+            "hosted",
+            signed_at.timestamp(),
+            &f.coop_circuit,
+            0,
+        ));
+        let authenticate_at = |now| {
+            f.coop.authenticate(
+                "hosted",
+                signed_at.timestamp(),
+                &signature,
+                &f.coop_circuit,
+                0,
+                now,
+            )
+        };
+
+        let skew = chrono::Duration::seconds(MAX_CLOCK_SKEW_SECS);
+        assert!(authenticate_at(signed_at + skew).is_ok());
+        for now in [
+            signed_at + skew + chrono::Duration::seconds(1),
+            signed_at - skew - chrono::Duration::seconds(1),
+        ] {
+            assert!(matches!(
+                authenticate_at(now),
+                Err(FederationError::Unauthorized(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pull_request_from_untrusted_peer_is_refused() {
+        let f = federation().await;
+        let now = Utc::now();
+
+        // A node that was never registered
+        let stranger = FederationNode::new("stranger", [3u8; 32]);
+        let signature = stranger.sign(&request_message(
+            "stranger",
+            now.timestamp(),
+            &f.coop_circuit,
+            0,
+        ));
+        assert!(matches!(
+            f.coop.authenticate(
+                "stranger",
+                now.timestamp(),
+                &signature,
+                &f.coop_circuit,
+                0,
+                now
+            ),
+            Err(FederationError::Unauthorized(_))
+        ));
+
+        // A peer that was revoked
+        f.coop.revoke_peer(&f.hosted_peer.peer_id, "admin").unwrap();
+        let signature = f.hosted_node.sign(&request_message(
+            "hosted",
+            now.timestamp(),
+            &f.coop_circuit,
+            0,
+        ));
+        assert!(matches!(
+            f.coop.authenticate(
+                "hosted",
+                now.timestamp(),
+                &signature,
+                &f.coop_circuit,
+                0,
+                now
+            ),
+            Err(FederationError::Unauthorized(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_imported_records_keep_provenance_and_are_not_echoed() {
+        let f = federation().await;
+        let batch = f.import_snapshot();
+        assert_eq!(batch.records.len(), 1);
+        assert_eq!(batch.records[0].origin_node, "coop");
+
+        assert!(f
+            .hosted_storage
+            .get_circuit_items(&f.hosted_circuit)
+            .unwrap()
+            .iter()
+            .any(|ci| ci.dfid == "DFID-1" && ci.pushed_by == "federation:coop"));
+        let provenance = f
+            .hosted_storage
+            .get_federation_provenance("item:DFID-1")
+            .unwrap()
+            .unwrap();
+        assert_eq!(provenance.origin_node, "coop");
+
+        // The imported item is in the hosted feed but never echoed back to its origin
+        let echo = f
+            .hosted
+            .serve_changes(&f.coop_peer, &f.hosted_circuit, 0, None)
+            .unwrap();
+        assert!(echo.records.is_empty());
+        assert!(echo.next_cursor > 0);
+    }
+
+    #[tokio::test]
+    async fn test_tampered_record_is_rejected() {
+        let f = federation().await;
+        let mut batch = f
+            .coop
+            .serve_changes(&f.hosted_peer, &f.coop_circuit, 0, None)
+            .unwrap();
+        batch.records[0].data["confidence_score"] = json!(0.1);
+
+        let summary = f
+            .hosted
+            .import_batch(&f.coop_peer, &f.hosted_circuit, &batch)
+            .unwrap();
+        assert_eq!(summary.rejected, 1);
+        assert!(f
+            .hosted_storage
+            .get_item_by_dfid("DFID-1")
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_record_with_bad_signature_is_rejected() {
+        let f = federation().await;
+        let mut batch = f
+            .coop
+            .serve_changes(&f.hosted_peer, &f.coop_circuit, 0, None)
+            .unwrap();
+        let record = &mut batch.records[0];
+        record.signature = f.hosted_node.sign(&record_message(
+            &record.origin_node,
+            &record.record_key(),
+            &record.content_hash,
+        ));
+
+        let summary = f
+            .hosted
+            .import_batch(&f.coop_peer, &f.hosted_circuit, &batch)
+            .unwrap();
+        assert_eq!(summary.rejected, 1);
+        assert!(f
+            .hosted_storage
+            .get_item_by_dfid("DFID-1")
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_batch_of_another_circuit_is_refused() {
+        let f = federation().await;
+        let mut batch = f
+            .coop
+            .serve_changes(&f.hosted_peer, &f.coop_circuit, 0, None)
+            .unwrap();
+        batch.circuit_id = Uuid::new_v4();
+
+        assert!(matches!(
+            f.hosted
+                .import_batch(&f.coop_peer, &f.hosted_circuit, &batch),
+            Err(FederationError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_replayed_batch_is_skipped() {
+        let f = federation().await;
+        let snapshot = f.import_snapshot();
+
+        let summary = f
+            .hosted
+            .import_batch(&f.coop_peer, &f.hosted_circuit, &snapshot)
+            .unwrap();
+        assert_eq!(summary.replayed, 1);
+        assert_eq!(summary.imported, 0);
+
+        // Replaying the old batch after a newer version arrived does not roll it back
+        f.update_on_coop(0.5);
+        let update = f
+            .coop
+            .serve_changes(&f.hosted_peer, &f.coop_circuit, snapshot.next_cursor, None)
+            .unwrap();
+        let summary = f
+            .hosted
+            .import_batch(&f.coop_peer, &f.hosted_circuit, &update)
+            .unwrap();
+        assert_eq!(summary.imported, 1);
+
+        let summary = f
+            .hosted
+            .import_batch(&f.coop_peer, &f.hosted_circuit, &snapshot)
+            .unwrap();
+        assert_eq!(summary.replayed, 1);
+        assert_eq!(f.hosted_score(), 0.5);
+        assert!(f.hosted.list_conflicts(None).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_diverging_edits_are_held_as_conflicts_until_resolved() {
+        let f = federation().await;
+        let snapshot = f.import_snapshot();
+
+        // Both sides edit the item: the co-op's update is held back on the hosted side
+        f.update_on_coop(0.5);
+        let mut hosted_item = f
+            .hosted_storage
+            .get_item_by_dfid("DFID-1")
+            .unwrap()
+            .unwrap();
+        hosted_item.confidence_score = 0.7;
+        f.hosted_storage.update_item(&hosted_item).unwrap();
+
+        let update = f
+            .coop
+            .serve_changes(&f.hosted_peer, &f.coop_circuit, snapshot.next_cursor, None)
+            .unwrap();
+        let summary = f
+            .hosted
+            .import_batch(&f.coop_peer, &f.hosted_circuit, &update)
+            .unwrap();
+        assert_eq!(summary.conflicts, 1);
+        assert_eq!(f.hosted_score(), 0.7);
+
+        let conflicts = f
+            .hosted
+            .list_conflicts(Some(FederationConflictStatus::Open))
+            .unwrap();
+        assert_eq!(conflicts.len(), 1);
+        let resolved = f
+            .hosted
+            .resolve_conflict(
+                &conflicts[0].conflict_id,
+                ConflictResolution::AcceptRemote,
+                "admin",
+            )
+            .unwrap();
+        assert_eq!(resolved.status, FederationConflictStatus::AcceptedRemote);
+        assert_eq!(f.hosted_score(), 0.5);
+        assert!(f
+            .hosted
+            .resolve_conflict(
+                &conflicts[0].conflict_id,
+                ConflictResolution::KeepLocal,
+                "admin"
+            )
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_handshake_trust_and_circuit_agreements() {
+        let (coop_storage, coop_circuit) = deployment("Co-op herd").await;
+        let (hosted_storage, hosted_circuit) = deployment("Hosted herd").await;
+        let coop_node = FederationNode::new("coop", [1u8; 32]).with_url("https://coop.example.com");
+        let hosted_node =
+            FederationNode::new("hosted", [2u8; 32]).with_url("https://hosted.example.com/");
+        let coop = FederationEngine::new(Arc::clone(&coop_storage)).with_node(coop_node.clone());
+        let hosted =
+            FederationEngine::new(Arc::clone(&hosted_storage)).with_node(hosted_node.clone());
+
+        // The co-op introduces itself and each side keeps the other as pending
+        let handshake = coop_node.handshake(Utc::now()).unwrap();
+        let reply = hosted.accept_handshake(&handshake, Utc::now()).unwrap();
+        let mut forged = reply.clone();
+        forged.nonce = "00".repeat(16);
+        assert!(matches!(
+            coop.complete_handshake(&handshake, &forged, "https://hosted.example.com", "admin"),
+            Err(FederationError::Unauthorized(_))
+        ));
+        let hosted_peer = coop
+            .complete_handshake(&handshake, &reply, "https://hosted.example.com", "admin")
+            .unwrap();
+        let coop_peer = hosted.peer_by_node("coop").unwrap().unwrap();
+        assert_eq!(hosted_peer.trust, FederationTrust::Pending);
+        assert_eq!(coop_peer.trust, FederationTrust::Pending);
+        assert_eq!(coop_peer.url, "https://coop.example.com");
+        assert_eq!(coop_peer.public_key, coop_node.public_key());
+
+        // Pending peers cannot pull
+        let now = Utc::now();
+        let signature = coop_node.sign(&request_message(
+            "coop",
+            now.timestamp(),
+            &hosted_circuit,
+            0,
+        ));
+        let pull = |engine: &FederationEngine<Storage>| {
+            engine.authenticate("coop", now.timestamp(), &signature, &hosted_circuit, 0, now)
+        };
+        assert!(matches!(
+            pull(&hosted),
+            Err(FederationError::Unauthorized(_))
+        ));
+
+        // The hosted side shares items without their enriched data, and events;
+        // the co-op only accepts items
+        let mut item = Item::new("DFID-7".to_string(), vec![], Uuid::new_v4());
+        item.enriched_data
+            .insert("weight_kg".to_string(), json!(420));
+        hosted_storage.store_item(&item).unwrap();
+        hosted_storage
+            .store_circuit_item(&CircuitItem::new(
+                "DFID-7".to_string(),
+                hosted_circuit,
+                "alice".to_string(),
+                vec![],
+            ))
+            .unwrap();
+        let agreement = |local, remote, data_classes| FederationPeerUpdate {
+            circuits: Some(vec![FederatedCircuitInput {
+                local_circuit_id: local,
+                remote_circuit_id: remote,
+                data_classes: Some(data_classes),
+            }]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            hosted.update_peer(
+                &coop_peer.peer_id,
+                agreement(
+                    hosted_circuit,
+                    coop_circuit,
+                    vec![FederationDataClass::EnrichedData]
+                ),
+                "admin"
+            ),
+            Err(FederationError::ValidationError(_))
+        ));
+        hosted
+            .update_peer(
+                &coop_peer.peer_id,
+                agreement(
+                    hosted_circuit,
+                    coop_circuit,
+                    vec![FederationDataClass::Items, FederationDataClass::Events],
+                ),
+                "admin",
+            )
+            .unwrap();
+        coop.update_peer(
+            &hosted_peer.peer_id,
+            agreement(
+                coop_circuit,
+                hosted_circuit,
+                vec![FederationDataClass::Items],
+            ),
+            "admin",
+        )
+        .unwrap();
+        let mut event = Event::new(
+            "DFID-7".to_string(),
+            crate::types::EventType::Enriched,
+            "alice".to_string(),
+            EventVisibility::Public,
+        );
+        event.pushed_to_circuit = Some(hosted_circuit);
+        hosted_storage.store_event(&event).unwrap();
+        record_event_change(&hosted_storage, &event);
+
+        let coop_peer = hosted.trust_peer(&coop_peer.peer_id, "admin").unwrap();
+        let hosted_peer = coop.trust_peer(&hosted_peer.peer_id, "admin").unwrap();
+        assert!(pull(&hosted).is_ok());
+
+        let batch = hosted
+            .serve_changes(&coop_peer, &hosted_circuit, 0, None)
+            .unwrap();
+        assert_eq!(batch.records.len(), 2);
+        let served_item = &batch.records[0];
+        assert_eq!(served_item.kind, FederatedRecordKind::Item);
+        assert_eq!(served_item.data["enriched_data"], json!({}));
+
+        let summary = coop
+            .import_batch(&hosted_peer, &coop_circuit, &batch)
+            .unwrap();
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.denied, 1);
+        let imported = coop_storage.get_item_by_dfid("DFID-7").unwrap().unwrap();
+        assert!(imported.enriched_data.is_empty());
+        assert!(coop_storage.get_event(&event.event_id).unwrap().is_none());
+
+        // Once revoked, the hosted node's handshakes are refused
+        coop.revoke_peer(&hosted_peer.peer_id, "admin").unwrap();
+        let handshake = hosted_node.handshake(Utc::now()).unwrap();
+        assert!(matches!(
+            coop.accept_handshake(&handshake, Utc::now()),
+            Err(FederationError::Unauthorized(_))
+        ));
+
+        let audited: Vec<(String, AuditOutcome)> = coop_storage
+            .list_audit_events()
+            .unwrap()
+            .into_iter()
+            .map(|event| (event.action, event.outcome))
+            .collect();
+        for action in [
+            "federation_handshake_completed",
+            "federation_peer_updated",
+            "federation_peer_trusted",
+            "federation_records_denied",
+            "federation_peer_revoked",
+        ] {
+            assert!(audited.iter().any(|(audited, _)| audited == action));
+        }
+        assert!(audited.contains(&(
+            "federation_handshake_received".to_string(),
+            AuditOutcome::Blocked
+        )));
+    }
+}
@@ -0,0 +1,157 @@
+//! Fuzzy matching of identifier values.
+//!
+//! Catches near duplicates such as "LOT-00123" and "LOT 123" that exact
+//! identifier lookups miss. Only identifiers of the same namespace, key and
+//! kind (canonical registry or contextual) are compared, so a lot number is
+//! never matched against an animal's SISBOV.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    FuzzyAlgorithm, FuzzyIdentifierMatch, FuzzyMatchConfig, Identifier, MappingStatus,
+};
+
+/// Similarity of two values in [0, 1]; 1 means equal once normalized
+pub fn similarity(algorithm: FuzzyAlgorithm, a: &str, b: &str) -> f64 {
+    match algorithm {
+        FuzzyAlgorithm::NormalizedLevenshtein => normalized_levenshtein(&squash(a), &squash(b)),
+        FuzzyAlgorithm::TokenSort => normalized_levenshtein(&sorted_tokens(a), &sorted_tokens(b)),
+    }
+}
+
+/// Existing identifiers whose values come close enough to the incoming ones
+/// without being equal, most confident first and at most one per item and
+/// incoming identifier
+pub fn find_fuzzy_matches<S: StorageBackend>(
+    storage: &S,
+    identifiers: &[Identifier],
+    config: &FuzzyMatchConfig,
+) -> Result<Vec<FuzzyIdentifierMatch>, StorageError> {
+    let mappings = storage.list_identifier_mappings()?;
+    let mut matches: Vec<FuzzyIdentifierMatch> = Vec::new();
+
+    for identifier in identifiers {
+        for mapping in &mappings {
+            let existing = &mapping.identifier;
+            if !matches!(mapping.status, MappingStatus::Active)
+                || existing.namespace != identifier.namespace
+                || existing.key != identifier.key
+                || existing.get_registry() != identifier.get_registry()
+                || existing.value == identifier.value
+            {
+                continue;
+            }
+            let confidence = similarity(config.algorithm, &identifier.value, &existing.value);
+            if confidence < config.min_confidence {
+                continue;
+            }
+            match matches
+                .iter_mut()
+                .find(|m| m.identifier == *identifier && m.dfid == mapping.dfid)
+            {
+                Some(best) if best.confidence >= confidence => {}
+                Some(best) => {
+                    best.matched_identifier = existing.clone();
+                    best.confidence = confidence;
+                }
+                None => matches.push(FuzzyIdentifierMatch {
+                    identifier: identifier.clone(),
+                    matched_identifier: existing.clone(),
+                    dfid: mapping.dfid.clone(),
+                    confidence,
+                    algorithm: config.algorithm,
+                }),
+            }
+        }
+    }
+
+    matches.sort_by(|a, b| {
+        b.confidence
+            .total_cmp(&a.confidence)
+            .then_with(|| a.dfid.cmp(&b.dfid))
+    });
+    Ok(matches)
+}
+
+/// Uppercase letters and digits only
+fn squash(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// Letter and digit runs as separate tokens, numbers without leading zeros,
+/// sorted and space-joined
+fn sorted_tokens(value: &str) -> String {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut current_is_digit = false;
+    for c in value.chars().flat_map(char::to_uppercase) {
+        if !c.is_alphanumeric() {
+            push_token(&mut tokens, &mut current);
+            continue;
+        }
+        if !current.is_empty() && c.is_ascii_digit() != current_is_digit {
+            push_token(&mut tokens, &mut current);
+        }
+        current_is_digit = c.is_ascii_digit();
+        current.push(c);
+    }
+    push_token(&mut tokens, &mut current);
+    tokens.sort();
+    tokens.join(" ")
+}
+
+fn push_token(tokens: &mut Vec<String>, current: &mut String) {
+    if current.is_empty() {
+        return;
+    }
+    let token = std::mem::take(current);
+    if token.chars().all(|c| c.is_ascii_digit()) {
+        let trimmed = token.trim_start_matches('0');
+        tokens.push(if trimmed.is_empty() { "0" } else { trimmed }.to_string());
+    } else {
+        tokens.push(token);
+    }
+}
+
+/// 1 - edit distance / length of the longer value
+fn normalized_levenshtein(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similarity_of_lot_numbers() {
+        let lev = FuzzyAlgorithm::NormalizedLevenshtein;
+        let token = FuzzyAlgorithm::TokenSort;
+
+        assert_eq!(similarity(lev, "lot-123", "LOT 123"), 1.0);
+        assert_eq!(similarity(lev, "LOT-00123", "LOT 123"), 0.75);
+        assert_eq!(similarity(token, "LOT-00123", "LOT 123"), 1.0);
+        assert_eq!(similarity(token, "123 LOT", "LOT-0123"), 1.0);
+        assert!(similarity(token, "LOT-123", "LOT-124") < 1.0);
+        assert!(similarity(lev, "LOT-123", "SAFRA-2024") < 0.5);
+    }
+}
@@ -0,0 +1,351 @@
+//! Content hash algorithms.
+//!
+//! Records that are verified later (event content hashes, Merkle trees,
+//! snapshots, receipts, notarized documents, provenance manifests) hash through
+//! this module instead of calling an algorithm directly, so the algorithm can
+//! change without invalidating what was written before.
+//!
+//! A hash carries its algorithm: BLAKE3 digests are bare hex, the format every
+//! record had before algorithms were selectable, and any other algorithm's
+//! digest is prefixed with its identifier (`sha256:<hex>`). Verification always
+//! recomputes with the algorithm of the stored hash, whatever the current
+//! default is.
+//!
+//! The default for new hashes is BLAKE3; `HASH_ALGORITHM=sha256` switches a
+//! deployment to SHA-256 for everything it writes from then on.
+//!
+//! Large content is hashed from a reader or a stream of chunks, one buffer at a
+//! time, so uploads never have to fit in memory.
+//!
+//! Keyed hashes and key derivation (API keys, session tokens, change feed
+//! signatures, key ceremonies) are not content records and keep using BLAKE3.
+
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use std::sync::{OnceLock, RwLock};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+pub const HASH_ALGORITHM_ENV: &str = "HASH_ALGORITHM";
+/// Read buffer when hashing from a reader
+pub const STREAM_CHUNK_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Blake3,
+    Sha256,
+}
+
+impl HashAlgorithm {
+    pub const ALL: [HashAlgorithm; 2] = [HashAlgorithm::Blake3, HashAlgorithm::Sha256];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Blake3 => "blake3",
+            HashAlgorithm::Sha256 => "sha256",
+        }
+    }
+
+    pub fn hasher(self) -> ContentHasher {
+        match self {
+            HashAlgorithm::Blake3 => ContentHasher::Blake3(Box::new(blake3::Hasher::new())),
+            HashAlgorithm::Sha256 => ContentHasher::Sha256(sha2::Sha256::new()),
+        }
+    }
+
+    /// Hash of `data` in stored form
+    pub fn hash(self, data: &[u8]) -> String {
+        let mut hasher = self.hasher();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Stored form of a hex digest made with this algorithm
+    fn tag(self, hex: String) -> String {
+        match self {
+            HashAlgorithm::Blake3 => hex,
+            other => format!("{}:{hex}", other.as_str()),
+        }
+    }
+}
+
+/// Hash and length of content that was hashed as it streamed past
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StreamDigest {
+    pub hash: String,
+    pub size: usize,
+}
+
+impl StreamDigest {
+    pub fn of(algorithm: HashAlgorithm, data: &[u8]) -> Self {
+        StreamDigest {
+            hash: algorithm.hash(data),
+            size: data.len(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum StreamHashError {
+    Read(String),
+    TooLarge { max_bytes: usize },
+}
+
+impl std::fmt::Display for StreamHashError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamHashError::Read(e) => write!(f, "Failed to read content: {e}"),
+            StreamHashError::TooLarge { max_bytes } => {
+                write!(f, "Content exceeds {max_bytes} bytes")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StreamHashError {}
+
+/// Hasher that counts what it is fed and stops past `max_bytes`
+struct SizedHasher {
+    hasher: ContentHasher,
+    size: usize,
+    max_bytes: Option<usize>,
+}
+
+impl SizedHasher {
+    fn update(&mut self, chunk: &[u8]) -> Result<(), StreamHashError> {
+        self.size += chunk.len();
+        if let Some(max_bytes) = self.max_bytes.filter(|max| self.size > *max) {
+            return Err(StreamHashError::TooLarge { max_bytes });
+        }
+        self.hasher.update(chunk);
+        Ok(())
+    }
+
+    fn finalize(self) -> StreamDigest {
+        StreamDigest {
+            hash: self.hasher.finalize(),
+            size: self.size,
+        }
+    }
+}
+
+impl HashAlgorithm {
+    fn sized_hasher(self, max_bytes: Option<usize>) -> SizedHasher {
+        SizedHasher {
+            hasher: self.hasher(),
+            size: 0,
+            max_bytes,
+        }
+    }
+
+    /// Hash everything `reader` yields, holding one buffer of it at a time
+    pub async fn hash_reader<R: AsyncRead + Unpin>(
+        self,
+        reader: &mut R,
+        max_bytes: Option<usize>,
+    ) -> Result<StreamDigest, StreamHashError> {
+        let mut hasher = self.sized_hasher(max_bytes);
+        let mut buffer = vec![0u8; STREAM_CHUNK_BYTES];
+        loop {
+            let read = reader
+                .read(&mut buffer)
+                .await
+                .map_err(|e| StreamHashError::Read(e.to_string()))?;
+            if read == 0 {
+                return Ok(hasher.finalize());
+            }
+            hasher.update(&buffer[..read])?;
+        }
+    }
+
+    /// Hash a stream of chunks, e.g. a request body, in place as they arrive
+    pub async fn hash_stream<S, B, E>(
+        self,
+        mut stream: S,
+        max_bytes: Option<usize>,
+    ) -> Result<StreamDigest, StreamHashError>
+    where
+        S: Stream<Item = Result<B, E>> + Unpin,
+        B: AsRef<[u8]>,
+        E: std::fmt::Display,
+    {
+        let mut hasher = self.sized_hasher(max_bytes);
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| StreamHashError::Read(e.to_string()))?;
+            hasher.update(chunk.as_ref())?;
+        }
+        Ok(hasher.finalize())
+    }
+}
+
+impl std::fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for HashAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "").as_str() {
+            "blake3" => Ok(HashAlgorithm::Blake3),
+            "sha256" => Ok(HashAlgorithm::Sha256),
+            _ => Err(format!("Unknown hash algorithm: {s}")),
+        }
+    }
+}
+
+/// Incremental hash, for content fed in pieces
+#[derive(Clone)]
+pub enum ContentHasher {
+    Blake3(Box<blake3::Hasher>),
+    Sha256(sha2::Sha256),
+}
+
+impl ContentHasher {
+    pub fn algorithm(&self) -> HashAlgorithm {
+        match self {
+            ContentHasher::Blake3(_) => HashAlgorithm::Blake3,
+            ContentHasher::Sha256(_) => HashAlgorithm::Sha256,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        match self {
+            ContentHasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+            ContentHasher::Sha256(hasher) => hasher.update(data),
+        }
+        self
+    }
+
+    /// The hash in stored form
+    pub fn finalize(self) -> String {
+        let algorithm = self.algorithm();
+        let hex = match self {
+            ContentHasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+            ContentHasher::Sha256(hasher) => hex::encode(hasher.finalize()),
+        };
+        algorithm.tag(hex)
+    }
+}
+
+fn default_cell() -> &'static RwLock<HashAlgorithm> {
+    static DEFAULT: OnceLock<RwLock<HashAlgorithm>> = OnceLock::new();
+    DEFAULT.get_or_init(|| {
+        let algorithm = match std::env::var(HASH_ALGORITHM_ENV) {
+            Ok(value) => value.parse().unwrap_or_else(|e| {
+                tracing::warn!("⚠️  {}; hashing with BLAKE3", e);
+                HashAlgorithm::Blake3
+            }),
+            Err(_) => HashAlgorithm::Blake3,
+        };
+        RwLock::new(algorithm)
+    })
+}
+
+/// Algorithm new records are hashed with
+pub fn default_algorithm() -> HashAlgorithm {
+    *default_cell().read().unwrap()
+}
+
+pub fn set_default_algorithm(algorithm: HashAlgorithm) {
+    *default_cell().write().unwrap() = algorithm;
+}
+
+/// Hash `data` with the default algorithm
+pub fn hash(data: &[u8]) -> String {
+    default_algorithm().hash(data)
+}
+
+/// Incremental hash with the default algorithm
+pub fn hasher() -> ContentHasher {
+    default_algorithm().hasher()
+}
+
+/// Algorithm a stored hash was made with; bare digests are BLAKE3
+pub fn algorithm_of(stored: &str) -> HashAlgorithm {
+    stored
+        .split_once(':')
+        .and_then(|(prefix, _)| prefix.parse().ok())
+        .unwrap_or(HashAlgorithm::Blake3)
+}
+
+/// Hex digest of a stored hash, without its algorithm prefix
+pub fn digest_hex(stored: &str) -> &str {
+    match stored.split_once(':') {
+        Some((prefix, hex)) if prefix.parse::<HashAlgorithm>().is_ok() => hex,
+        _ => stored,
+    }
+}
+
+/// Whether `data` hashes to `stored` under the algorithm `stored` was made with
+pub fn verify(stored: &str, data: &[u8]) -> bool {
+    algorithm_of(stored).hash(data) == stored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashes_carry_their_algorithm() {
+        let blake3 = HashAlgorithm::Blake3.hash(b"lot 42");
+        assert_eq!(blake3, blake3::hash(b"lot 42").to_hex().to_string());
+        assert_eq!(algorithm_of(&blake3), HashAlgorithm::Blake3);
+
+        let sha256 = HashAlgorithm::Sha256.hash(b"lot 42");
+        assert!(sha256.starts_with("sha256:"));
+        assert_eq!(digest_hex(&sha256).len(), 64);
+        assert_eq!(algorithm_of(&sha256), HashAlgorithm::Sha256);
+
+        // Records verify under the algorithm they were written with
+        assert!(verify(&blake3, b"lot 42"));
+        assert!(verify(&sha256, b"lot 42"));
+        assert!(!verify(&sha256, b"lot 43"));
+
+        let mut hasher = HashAlgorithm::Sha256.hasher();
+        hasher.update(b"lot ").update(b"42");
+        assert_eq!(hasher.finalize(), sha256);
+
+        assert_eq!("SHA-256".parse(), Ok(HashAlgorithm::Sha256));
+        assert!("md5".parse::<HashAlgorithm>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_streamed_hash_matches_in_memory_hash() {
+        let payload: Vec<u8> = (0..3 * STREAM_CHUNK_BYTES + 17)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let expected = StreamDigest::of(HashAlgorithm::Sha256, &payload);
+
+        let mut reader = payload.as_slice();
+        let digest = HashAlgorithm::Sha256
+            .hash_reader(&mut reader, None)
+            .await
+            .unwrap();
+        assert_eq!(digest, expected);
+
+        let chunks = payload
+            .chunks(1000)
+            .map(Ok::<_, std::io::Error>)
+            .collect::<Vec<_>>();
+        let digest = HashAlgorithm::Sha256
+            .hash_stream(futures::stream::iter(chunks), None)
+            .await
+            .unwrap();
+        assert_eq!(digest, expected);
+
+        let mut reader = payload.as_slice();
+        assert!(matches!(
+            HashAlgorithm::Blake3
+                .hash_reader(&mut reader, Some(STREAM_CHUNK_BYTES))
+                .await,
+            Err(StreamHashError::TooLarge { .. })
+        ));
+    }
+}
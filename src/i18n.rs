@@ -0,0 +1,455 @@
+//! Localization of notification and email texts.
+//!
+//! Texts come from locale packs: JSON documents mapping message keys either to a
+//! template or, for counted messages, to CLDR plural forms (`zero`, `one`, `two`,
+//! `few`, `many`, `other`). Templates substitute `{name}` arguments. The en,
+//! pt-BR and es packs are built in (config/locales); packs found in `LOCALES_DIR`
+//! are loaded at startup and admins can install more at runtime.
+//!
+//! A lookup walks the locale's fallback chain, e.g. `pt-BR` -> `pt` -> the
+//! pack's declared `fallback` -> `DEFAULT_LOCALE` (en), so a partial pack only
+//! needs the messages it changes.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+pub const DEFAULT_LOCALE: &str = "en";
+
+const BUILTIN_PACKS: [&str; 3] = [
+    include_str!("../config/locales/en.json"),
+    include_str!("../config/locales/pt-BR.json"),
+    include_str!("../config/locales/es.json"),
+];
+
+#[derive(Debug)]
+pub enum LocaleError {
+    InvalidLocale(String),
+    InvalidPack(String),
+    NotFound(String),
+    IoError(String),
+}
+
+impl std::fmt::Display for LocaleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocaleError::InvalidLocale(tag) => write!(f, "Invalid locale: {tag}"),
+            LocaleError::InvalidPack(msg) => write!(f, "Invalid locale pack: {msg}"),
+            LocaleError::NotFound(tag) => write!(f, "Locale not found: {tag}"),
+            LocaleError::IoError(msg) => write!(f, "I/O error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for LocaleError {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum MessageTemplate {
+    Text(String),
+    /// Forms by CLDR plural category; `other` is required
+    Plural(BTreeMap<String, String>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalePack {
+    pub locale: String,
+    /// Locale consulted for messages missing here, before the language and default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<String>,
+    pub messages: HashMap<String, MessageTemplate>,
+}
+
+/// Canonical BCP 47 casing (`pt_br` -> `pt-BR`, `zh-hant-tw` -> `zh-Hant-TW`)
+pub fn normalize_locale(tag: &str) -> Option<String> {
+    let mut subtags = tag.trim().split(['-', '_']);
+    let language = subtags.next()?;
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let mut normalized = language.to_ascii_lowercase();
+    for subtag in subtags {
+        let canonical = match subtag.len() {
+            2 if subtag.chars().all(|c| c.is_ascii_alphabetic()) => subtag.to_ascii_uppercase(),
+            3 if subtag.chars().all(|c| c.is_ascii_digit()) => subtag.to_string(),
+            4 if subtag.chars().all(|c| c.is_ascii_alphabetic()) => {
+                let lower = subtag.to_ascii_lowercase();
+                lower[..1].to_ascii_uppercase() + &lower[1..]
+            }
+            _ => return None,
+        };
+        normalized.push('-');
+        normalized.push_str(&canonical);
+    }
+    Some(normalized)
+}
+
+/// CLDR plural category of an integer count in a locale
+pub fn plural_category(locale: &str, count: i64) -> &'static str {
+    let n = count.unsigned_abs();
+    let language = locale.split('-').next().unwrap_or(locale);
+    match language {
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" => "other",
+        // pt-PT follows the English rule; Brazilian Portuguese and French treat 0 as singular
+        "pt" if locale != "pt-PT" => {
+            if n <= 1 {
+                "one"
+            } else {
+                "other"
+            }
+        }
+        "fr" => {
+            if n <= 1 {
+                "one"
+            } else {
+                "other"
+            }
+        }
+        "ru" | "uk" => {
+            if n % 10 == 1 && n % 100 != 11 {
+                "one"
+            } else if (2..=4).contains(&(n % 10)) && !(12..=14).contains(&(n % 100)) {
+                "few"
+            } else {
+                "many"
+            }
+        }
+        _ => {
+            if n == 1 {
+                "one"
+            } else {
+                "other"
+            }
+        }
+    }
+}
+
+/// Substitute `{name}` placeholders; unknown placeholders are left as written
+pub fn render(template: &str, args: &[(&str, &str)]) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) => {
+                let name = &after[..end];
+                match args.iter().find(|(key, _)| *key == name) {
+                    Some((_, value)) => rendered.push_str(value),
+                    None => {
+                        rendered.push('{');
+                        rendered.push_str(name);
+                        rendered.push('}');
+                    }
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                rendered.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+#[derive(Debug)]
+pub struct Localizer {
+    packs: RwLock<HashMap<String, LocalePack>>,
+    default_locale: String,
+}
+
+impl Default for Localizer {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOCALE)
+    }
+}
+
+impl Localizer {
+    /// Localizer holding only the built-in packs
+    pub fn new(default_locale: &str) -> Self {
+        let localizer = Self {
+            packs: RwLock::new(HashMap::new()),
+            default_locale: normalize_locale(default_locale)
+                .unwrap_or_else(|| DEFAULT_LOCALE.to_string()),
+        };
+        for pack in BUILTIN_PACKS {
+            let pack: LocalePack =
+                serde_json::from_str(pack).expect("built-in locale packs are valid JSON");
+            localizer
+                .install(pack)
+                .expect("built-in locale packs are valid");
+        }
+        localizer
+    }
+
+    /// Process-wide localizer: built-in packs, `DEFAULT_LOCALE` and the packs in
+    /// `LOCALES_DIR`
+    pub fn global() -> &'static Localizer {
+        static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+        LOCALIZER.get_or_init(|| {
+            let default_locale =
+                std::env::var("DEFAULT_LOCALE").unwrap_or_else(|_| DEFAULT_LOCALE.to_string());
+            let localizer = Localizer::new(&default_locale);
+            if let Ok(dir) = std::env::var("LOCALES_DIR") {
+                match localizer.load_dir(Path::new(&dir)) {
+                    Ok(count) => tracing::info!("Loaded {} locale packs from {}", count, dir),
+                    Err(e) => tracing::warn!("Failed to load locale packs from {}: {}", dir, e),
+                }
+            }
+            localizer
+        })
+    }
+
+    pub fn default_locale(&self) -> &str {
+        &self.default_locale
+    }
+
+    /// Add or replace a pack
+    pub fn install(&self, mut pack: LocalePack) -> Result<(), LocaleError> {
+        pack.locale = normalize_locale(&pack.locale)
+            .ok_or_else(|| LocaleError::InvalidLocale(pack.locale.clone()))?;
+        if let Some(fallback) = &pack.fallback {
+            pack.fallback = Some(
+                normalize_locale(fallback)
+                    .ok_or_else(|| LocaleError::InvalidLocale(fallback.clone()))?,
+            );
+        }
+        for (key, message) in &pack.messages {
+            if let MessageTemplate::Plural(forms) = message {
+                if !forms.contains_key("other") {
+                    return Err(LocaleError::InvalidPack(format!(
+                        "plural message '{key}' has no 'other' form"
+                    )));
+                }
+            }
+        }
+        self.packs
+            .write()
+            .unwrap()
+            .insert(pack.locale.clone(), pack);
+        Ok(())
+    }
+
+    /// Install every `*.json` pack in a directory
+    pub fn load_dir(&self, dir: &Path) -> Result<usize, LocaleError> {
+        let entries = std::fs::read_dir(dir).map_err(|e| LocaleError::IoError(e.to_string()))?;
+        let mut loaded = 0;
+        for entry in entries {
+            let path = entry
+                .map_err(|e| LocaleError::IoError(e.to_string()))?
+                .path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let contents =
+                std::fs::read_to_string(&path).map_err(|e| LocaleError::IoError(e.to_string()))?;
+            let pack: LocalePack = serde_json::from_str(&contents)
+                .map_err(|e| LocaleError::InvalidPack(format!("{}: {e}", path.display())))?;
+            self.install(pack)?;
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+
+    /// Remove a pack; the default locale and the built-in English pack stay
+    pub fn remove(&self, locale: &str) -> Result<(), LocaleError> {
+        let locale =
+            normalize_locale(locale).ok_or_else(|| LocaleError::InvalidLocale(locale.into()))?;
+        if locale == self.default_locale || locale == DEFAULT_LOCALE {
+            return Err(LocaleError::InvalidLocale(format!(
+                "{locale} is a default locale"
+            )));
+        }
+        self.packs
+            .write()
+            .unwrap()
+            .remove(&locale)
+            .map(|_| ())
+            .ok_or(LocaleError::NotFound(locale))
+    }
+
+    pub fn pack(&self, locale: &str) -> Option<LocalePack> {
+        let locale = normalize_locale(locale)?;
+        self.packs.read().unwrap().get(&locale).cloned()
+    }
+
+    pub fn locales(&self) -> Vec<String> {
+        let mut locales: Vec<String> = self.packs.read().unwrap().keys().cloned().collect();
+        locales.sort();
+        locales
+    }
+
+    /// Whether a user locale resolves to a pack other than the default's
+    pub fn supports(&self, locale: &str) -> bool {
+        let Some(locale) = normalize_locale(locale) else {
+            return false;
+        };
+        let packs = self.packs.read().unwrap();
+        let language = locale.split('-').next().unwrap_or(&locale);
+        packs.contains_key(&locale) || packs.contains_key(language)
+    }
+
+    /// Locales consulted for `locale`, most specific first, ending in the default
+    pub fn fallback_chain(&self, locale: Option<&str>) -> Vec<String> {
+        let packs = self.packs.read().unwrap();
+        let mut chain: Vec<String> = Vec::new();
+        let mut next = locale.and_then(normalize_locale);
+        while let Some(tag) = next.take() {
+            if chain.contains(&tag) {
+                break;
+            }
+            next = match packs.get(&tag).and_then(|pack| pack.fallback.clone()) {
+                Some(fallback) => Some(fallback),
+                None => tag.rfind('-').map(|at| tag[..at].to_string()),
+            };
+            chain.push(tag);
+        }
+        // The built-in English pack backs every chain, even with another default
+        for last_resort in [self.default_locale.as_str(), DEFAULT_LOCALE] {
+            if !chain.iter().any(|tag| tag == last_resort) {
+                chain.push(last_resort.to_string());
+            }
+        }
+        chain
+    }
+
+    fn lookup(&self, locale: Option<&str>, key: &str) -> Option<(String, MessageTemplate)> {
+        let chain = self.fallback_chain(locale);
+        let packs = self.packs.read().unwrap();
+        chain.into_iter().find_map(|tag| {
+            let message = packs.get(&tag)?.messages.get(key)?.clone();
+            Some((tag, message))
+        })
+    }
+
+    /// Render a message; a key no pack defines renders as the key itself
+    pub fn translate(&self, locale: Option<&str>, key: &str, args: &[(&str, &str)]) -> String {
+        match self.lookup(locale, key) {
+            Some((_, MessageTemplate::Text(template))) => render(&template, args),
+            Some((_, MessageTemplate::Plural(forms))) => render(&forms["other"], args),
+            None => {
+                tracing::debug!("Missing translation for '{}'", key);
+                key.to_string()
+            }
+        }
+    }
+
+    /// Render a counted message, choosing the plural form by the rules of the
+    /// locale that supplied it. `{count}` is available to the template.
+    pub fn translate_count(
+        &self,
+        locale: Option<&str>,
+        key: &str,
+        count: i64,
+        args: &[(&str, &str)],
+    ) -> String {
+        let count_arg = count.to_string();
+        let mut all_args = vec![("count", count_arg.as_str())];
+        all_args.extend_from_slice(args);
+        match self.lookup(locale, key) {
+            Some((tag, MessageTemplate::Plural(forms))) => {
+                let category = match (count, forms.get("zero")) {
+                    (0, Some(_)) => "zero",
+                    _ => plural_category(&tag, count),
+                };
+                let template = forms.get(category).unwrap_or(&forms["other"]);
+                render(template, &all_args)
+            }
+            Some((_, MessageTemplate::Text(template))) => render(&template, &all_args),
+            None => {
+                tracing::debug!("Missing translation for '{}'", key);
+                key.to_string()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_chain_pluralization_and_runtime_packs() {
+        let localizer = Localizer::default();
+        assert_eq!(
+            localizer.fallback_chain(Some("pt_br")),
+            vec!["pt-BR", "pt", "en"]
+        );
+        assert_eq!(normalize_locale("zh-hant-tw").unwrap(), "zh-Hant-TW");
+        assert!(normalize_locale("português").is_none());
+
+        let args = [("circuit_name", "Fazenda Boa Vista")];
+        assert_eq!(
+            localizer.translate(Some("pt-BR"), "notification.item_shared.title", &args),
+            "Novo item em Fazenda Boa Vista"
+        );
+        // es-AR has no pack of its own and resolves through es
+        assert_eq!(
+            localizer.translate(Some("es-AR"), "notification.item_shared.title", &args),
+            "Nuevo ítem en Fazenda Boa Vista"
+        );
+        assert_eq!(
+            localizer.translate(None, "notification.item_shared.title", &args),
+            "New item in Fazenda Boa Vista"
+        );
+
+        // Brazilian Portuguese treats 0 as singular, English does not
+        let key = "email.password_reset.expires";
+        assert_eq!(
+            localizer.translate_count(Some("pt-BR"), key, 0, &[]),
+            "Este link expira em 0 minuto."
+        );
+        assert_eq!(
+            localizer.translate_count(Some("en"), key, 0, &[]),
+            "This link expires in 0 minutes."
+        );
+        assert_eq!(
+            localizer.translate_count(Some("en"), key, 1, &[]),
+            "This link expires in 1 minute."
+        );
+        assert_eq!(plural_category("ru", 22), "few");
+        assert_eq!(plural_category("ru", 11), "many");
+
+        // A partial runtime pack falls back through its declared fallback
+        let pack: LocalePack = serde_json::from_value(serde_json::json!({
+            "locale": "gn-py",
+            "fallback": "es",
+            "messages": {"notification.account_frozen.title": "Cuenta jokoha"}
+        }))
+        .unwrap();
+        localizer.install(pack).unwrap();
+        assert!(localizer.supports("gn-PY"));
+        assert_eq!(
+            localizer.fallback_chain(Some("gn-PY")),
+            vec!["gn-PY", "es", "en"]
+        );
+        assert_eq!(
+            localizer.translate(Some("gn-PY"), "notification.account_frozen.title", &[]),
+            "Cuenta jokoha"
+        );
+        assert_eq!(
+            localizer.translate(Some("gn-PY"), "notification.account_unfrozen.title", &[]),
+            "Cuenta reactivada"
+        );
+        assert_eq!(
+            localizer.translate(Some("gn-PY"), "no.such.key", &[]),
+            "no.such.key"
+        );
+
+        let bad: LocalePack = serde_json::from_value(serde_json::json!({
+            "locale": "de",
+            "messages": {"x": {"one": "ein"}}
+        }))
+        .unwrap();
+        assert!(matches!(
+            localizer.install(bad),
+            Err(LocaleError::InvalidPack(_))
+        ));
+        assert!(localizer.remove("en").is_err());
+        localizer.remove("gn-PY").unwrap();
+        assert!(!localizer.supports("gn"));
+    }
+}
@@ -264,6 +264,32 @@ impl ExternalAlias {
     }
 }
 
+/// How verification decides which existing item incoming identifiers belong to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupStrategyKind {
+    /// Any identifier mapped to an item matches it (the behavior before strategies existed)
+    #[default]
+    ExactIdentifier,
+    /// Canonical identifiers decide; contextual ones only when no canonical one matches
+    CanonicalFirst,
+    /// Only an item with the same full set of identifiers matches
+    Fingerprint,
+    /// Weighs canonical, contextual and fingerprint matches and keeps confident ones
+    CompositeScoring,
+}
+
+impl DedupStrategyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DedupStrategyKind::ExactIdentifier => "exact_identifier",
+            DedupStrategyKind::CanonicalFirst => "canonical_first",
+            DedupStrategyKind::Fingerprint => "fingerprint",
+            DedupStrategyKind::CompositeScoring => "composite_scoring",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CircuitAliasConfig {
     pub required_canonical: Vec<String>,         // ["sisbov", "cpf"]
@@ -271,6 +297,9 @@ pub struct CircuitAliasConfig {
     pub use_fingerprint: bool,                   // use fingerprint for dedup
     pub allowed_namespaces: Option<Vec<String>>, // None = all allowed
     pub auto_apply_namespace: bool,              // apply default_namespace if missing
+    /// Overrides the workspace's dedup strategy for this circuit's items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedup_strategy: Option<DedupStrategyKind>,
 }
 
 impl Default for CircuitAliasConfig {
@@ -281,6 +310,7 @@ impl Default for CircuitAliasConfig {
             use_fingerprint: true,
             auto_apply_namespace: true,
             allowed_namespaces: None,
+            dedup_strategy: None,
         }
     }
 }
@@ -294,6 +324,7 @@ impl CircuitAliasConfig {
             use_fingerprint: false,
             allowed_namespaces: Some(vec!["bovino".to_string()]),
             auto_apply_namespace: true,
+            dedup_strategy: Some(DedupStrategyKind::CanonicalFirst),
         }
     }
 
@@ -305,6 +336,7 @@ impl CircuitAliasConfig {
             use_fingerprint: true,
             allowed_namespaces: Some(vec!["soja".to_string(), "milho".to_string()]),
             auto_apply_namespace: true,
+            dedup_strategy: None,
         }
     }
 
@@ -316,6 +348,7 @@ impl CircuitAliasConfig {
             use_fingerprint: true,
             allowed_namespaces: Some(vec!["aves".to_string()]),
             auto_apply_namespace: true,
+            dedup_strategy: None,
         }
     }
 
@@ -327,6 +360,7 @@ impl CircuitAliasConfig {
             use_fingerprint: true,
             allowed_namespaces: None,
             auto_apply_namespace: false,
+            dedup_strategy: None,
         }
     }
 }
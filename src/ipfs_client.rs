@@ -112,6 +112,74 @@ impl IpfsClient {
         })
     }
 
+    /// Pinata if `PINATA_API_KEY`/`PINATA_SECRET_KEY` are set, else the Kubo node
+    /// at `IPFS_ENDPOINT` (default `http://localhost:5001`)
+    pub fn from_env() -> Result<Self, IpfsError> {
+        match (
+            std::env::var("PINATA_API_KEY").ok(),
+            std::env::var("PINATA_SECRET_KEY").ok(),
+        ) {
+            (Some(api_key), Some(secret)) => Self::with_pinata(api_key, secret),
+            _ => Self::with_endpoint(
+                &std::env::var("IPFS_ENDPOINT")
+                    .unwrap_or_else(|_| "http://localhost:5001".to_string()),
+            ),
+        }
+    }
+
+    /// Upload a file to IPFS and return its CID
+    pub async fn upload_bytes(
+        &self,
+        content: Vec<u8>,
+        file_name: &str,
+        mime_type: &str,
+    ) -> Result<String, IpfsError> {
+        let part = reqwest::multipart::Part::bytes(content)
+            .file_name(file_name.to_string())
+            .mime_str(mime_type)
+            .map_err(|e| IpfsError::UploadError(format!("Invalid mime type: {e}")))?;
+        let form = reqwest::multipart::Form::new().part("file", part);
+
+        match &self.client_type {
+            IpfsClientType::Kubo { endpoint } => {
+                let url = format!("{endpoint}/api/v0/add");
+                let response = self.http_client.post(&url).multipart(form).send().await?;
+
+                if !response.status().is_success() {
+                    let error_text = response.text().await.unwrap_or_default();
+                    return Err(IpfsError::UploadError(format!(
+                        "Kubo upload failed: {error_text}"
+                    )));
+                }
+
+                let result: KuboAddResponse = response.json().await?;
+                Ok(result.hash)
+            }
+            IpfsClientType::Pinata { api_key, secret } => {
+                let url = "https://api.pinata.cloud/pinning/pinFileToIPFS";
+                let response = self
+                    .http_client
+                    .post(url)
+                    .header("pinata_api_key", api_key)
+                    .header("pinata_secret_api_key", secret)
+                    .multipart(form)
+                    .send()
+                    .await?;
+
+                if !response.status().is_success() {
+                    let status = response.status();
+                    let error_text = response.text().await.unwrap_or_default();
+                    return Err(IpfsError::UploadError(format!(
+                        "Pinata upload failed ({status}): {error_text}"
+                    )));
+                }
+
+                let result: PinataResponse = response.json().await?;
+                Ok(result.ipfs_hash)
+            }
+        }
+    }
+
     /// Upload JSON data to IPFS and return CID
     pub async fn upload_json<T: Serialize>(&self, data: &T) -> Result<String, IpfsError> {
         let json_data = serde_json::to_string(data)?;
@@ -0,0 +1,41 @@
+//! Who may read an item and the records about it
+
+use crate::lifecycle_engine::item_workspace;
+use crate::storage::{StorageBackend, StorageError};
+
+/// Admins, the item's creator and their workspace, and members of a circuit
+/// holding the item can read it
+pub fn can_read_item<S: StorageBackend + ?Sized>(
+    storage: &S,
+    dfid: &str,
+    user_id: &str,
+) -> Result<bool, StorageError> {
+    let creator = storage
+        .get_events_by_dfid(dfid)?
+        .into_iter()
+        .min_by_key(|event| event.timestamp)
+        .map(|event| event.source);
+    if creator.as_deref() == Some(user_id) {
+        return Ok(true);
+    }
+    if let Some(account) = storage.get_user_account(user_id)? {
+        if account.is_admin {
+            return Ok(true);
+        }
+        if account.workspace_id.is_some() && account.workspace_id == item_workspace(storage, dfid)?
+        {
+            return Ok(true);
+        }
+    }
+    for circuit in storage.list_circuits()? {
+        if circuit.is_member(user_id)
+            && storage
+                .get_circuit_items(&circuit.circuit_id)?
+                .iter()
+                .any(|circuit_item| circuit_item.dfid == dfid)
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
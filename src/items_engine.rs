@@ -1,9 +1,11 @@
 use crate::dfid_engine::DfidEngine;
+use crate::enrichment_policy_engine::{enrich_with_policy, Enrichment};
 use crate::logging::{LogEntry, LoggingEngine};
+use crate::pagination::{collect_page, Page, PageCursor};
 use crate::storage::{StorageBackend, StorageError};
 use crate::types::{
-    Identifier, Item, ItemShare, ItemStatus, MergeStrategy, PendingItem, PendingReason,
-    SharedItemResponse,
+    Identifier, Item, ItemSearchQuery, ItemShare, ItemStatus, ItemSummary, MergeStrategy,
+    PendingItem, PendingReason, SharedItemResponse,
 };
 use chrono::Utc;
 use std::collections::HashMap;
@@ -78,6 +80,12 @@ impl<S: StorageBackend + 'static> ItemsEngine<S> {
         }
     }
 
+    /// Generate DFIDs with `dfid_engine`, e.g. one coordinated across replicas
+    pub fn with_dfid_engine(mut self, dfid_engine: DfidEngine) -> Self {
+        self.dfid_engine = dfid_engine;
+        self
+    }
+
     pub fn create_item(
         &mut self,
         dfid: String,
@@ -112,6 +120,18 @@ impl<S: StorageBackend + 'static> ItemsEngine<S> {
         identifiers: Vec<Identifier>,
         source_entry: Uuid,
         enriched_data: Option<HashMap<String, serde_json::Value>>,
+    ) -> Result<Item, ItemsError> {
+        self.create_item_with_generated_dfid_as(identifiers, source_entry, enriched_data, None)
+    }
+
+    /// As `create_item_with_generated_dfid`; when the identifiers match an
+    /// existing item, its enrichment follows the merge policy of `actor`'s workspace
+    pub fn create_item_with_generated_dfid_as(
+        &mut self,
+        identifiers: Vec<Identifier>,
+        source_entry: Uuid,
+        enriched_data: Option<HashMap<String, serde_json::Value>>,
+        actor: Option<&str>,
     ) -> Result<Item, ItemsError> {
         // Step 0: Check for conflicts and handle them
         if let Some(pending_reason) =
@@ -177,7 +197,9 @@ impl<S: StorageBackend + 'static> ItemsEngine<S> {
 
                     // Enrich existing item with new data
                     if let Some(data) = enriched_data {
-                        return self.enrich_item(&dfid, data, source_entry);
+                        return self
+                            .enrich_item_as(&dfid, data, source_entry, actor)
+                            .map(|enrichment| enrichment.item);
                     }
 
                     // Return the existing item (potentially with new identifiers)
@@ -187,7 +209,7 @@ impl<S: StorageBackend + 'static> ItemsEngine<S> {
         }
 
         // Step 2: No duplicate found - generate DFID and create new item
-        let dfid = self.dfid_engine.generate_dfid();
+        let dfid = self.dfid_engine.generate_dfid()?;
 
         self.logger
             .info(
@@ -243,6 +265,8 @@ impl<S: StorageBackend + 'static> ItemsEngine<S> {
             source_entries: vec![source_entry],
             confidence_score: 1.0,
             status: ItemStatus::Active, // Status will indicate "LocalOnly" through dfid format
+            first_occurred_at: None,
+            last_occurred_at: None,
         };
 
         self.storage.store_item(&item)?;
@@ -567,7 +591,20 @@ impl<S: StorageBackend + 'static> ItemsEngine<S> {
         data: HashMap<String, serde_json::Value>,
         source_entry: Uuid,
     ) -> Result<Item, ItemsError> {
-        let mut item = self
+        self.enrich_item_as(dfid, data, source_entry, None)
+            .map(|enrichment| enrichment.item)
+    }
+
+    /// Enrich under the merge policy of `actor`'s workspace (or the item's, for
+    /// anonymous data) and record the Enriched event with the merge report
+    pub fn enrich_item_as(
+        &mut self,
+        dfid: &str,
+        data: HashMap<String, serde_json::Value>,
+        source_entry: Uuid,
+        actor: Option<&str>,
+    ) -> Result<Enrichment, ItemsError> {
+        let item = self
             .storage
             .get_item_by_dfid(dfid)?
             .ok_or_else(|| ItemsError::ItemNotFound(dfid.to_string()))?;
@@ -584,14 +621,17 @@ impl<S: StorageBackend + 'static> ItemsEngine<S> {
                     .join(","),
             );
 
-        item.enrich(data, source_entry);
-        self.storage.update_item(&item)?;
+        let enrichment = enrich_with_policy(&self.storage, item, data, source_entry, actor)?;
 
         self.logger
             .info("ItemsEngine", "item_enriched", "Item enriched successfully")
-            .with_context("dfid", dfid.to_string());
+            .with_context("dfid", dfid.to_string())
+            .with_context(
+                "conflicts",
+                enrichment.report.conflicts().count().to_string(),
+            );
 
-        Ok(item)
+        Ok(enrichment)
     }
 
     pub fn add_identifiers(
@@ -738,7 +778,7 @@ impl<S: StorageBackend + 'static> ItemsEngine<S> {
         identifiers_for_new_item: Vec<Identifier>,
     ) -> Result<(Item, Item), ItemsError> {
         // Generate a unique DFID for the new item
-        let new_dfid = self.dfid_engine.generate_dfid();
+        let new_dfid = self.dfid_engine.generate_dfid()?;
 
         // Use the existing split_item method
         self.split_item(dfid, identifiers_for_new_item, new_dfid)
@@ -772,6 +812,57 @@ impl<S: StorageBackend + 'static> ItemsEngine<S> {
         self.storage.list_items().map_err(ItemsError::from)
     }
 
+    /// One page of items matching `keep`, in creation order
+    pub fn list_items_page(
+        &self,
+        after: Option<PageCursor>,
+        limit: usize,
+        keep: impl Fn(&Item) -> bool,
+    ) -> Result<Page<Item>, ItemsError> {
+        collect_page(
+            after,
+            limit,
+            |after, limit| self.storage.list_items_page(after, limit),
+            |item| PageCursor::new(item.creation_timestamp, item.dfid.clone()),
+            keep,
+        )
+        .map_err(ItemsError::from)
+    }
+
+    /// One page of items matching a structured search, in creation order
+    pub fn search_items(
+        &self,
+        query: &ItemSearchQuery,
+        after: Option<PageCursor>,
+        limit: usize,
+    ) -> Result<Page<Item>, ItemsError> {
+        collect_page(
+            after,
+            limit,
+            |after, limit| self.storage.search_items(query, after, limit),
+            |item| PageCursor::new(item.creation_timestamp, item.dfid.clone()),
+            |_| true,
+        )
+        .map_err(ItemsError::from)
+    }
+
+    /// Like [`Self::search_items`], reading only the summary of each item
+    pub fn search_item_summaries(
+        &self,
+        query: &ItemSearchQuery,
+        after: Option<PageCursor>,
+        limit: usize,
+    ) -> Result<Page<ItemSummary>, ItemsError> {
+        collect_page(
+            after,
+            limit,
+            |after, limit| self.storage.search_item_summaries(query, after, limit),
+            |summary| PageCursor::new(summary.created_at, summary.dfid.clone()),
+            |_| true,
+        )
+        .map_err(ItemsError::from)
+    }
+
     pub fn find_items_by_identifier(
         &self,
         identifier: &Identifier,
@@ -0,0 +1,655 @@
+//! Key ceremonies for the Stellar anchoring keys and the audit signing key.
+//!
+//! A ceremony generates a key, hands its secret out once as Shamir shares (and
+//! optionally a passphrase-sealed backup) and keeps only the public half. The
+//! key stays pending until enough admins have confirmed its fingerprint; only
+//! then can it be activated, which retires the previous key for the purpose.
+//! Once a purpose has an active key, its signers (provenance manifests, Stellar
+//! adapters and proof anchoring) refuse to sign with any other key; see
+//! [`check_signer`]. Every step is written to the audit log with the key's
+//! fingerprint.
+
+use crate::audit_engine::{AuditEngine, AuditError};
+use crate::shamir::{self, Share};
+use crate::stellar_client::{StellarClient, StellarNetwork};
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    AuditEventType, AuditOutcome, AuditSeverity, KeyBackup, KeyConfirmation, KeyPurpose,
+    ManagedKey, ManagedKeyStatus,
+};
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::SigningKey;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const BACKUP_FORMAT_VERSION: u32 = 1;
+const BACKUP_KEY_CONTEXT: &str = "defarm key ceremony backup v1";
+/// bcrypt cost of the passphrase key derivation
+const BACKUP_KDF_COST: u32 = 12;
+const MIN_PASSPHRASE_LEN: usize = 12;
+const DEFAULT_REQUIRED_CONFIRMATIONS: u32 = 2;
+/// Fingerprint characters prefixed to each share so shares of different keys
+/// are not mixed up
+const SHARE_TAG_LEN: usize = 8;
+
+#[derive(Debug)]
+pub enum KeyCeremonyError {
+    StorageError(StorageError),
+    ValidationError(String),
+    NotFound(String),
+    AuditError(AuditError),
+}
+
+impl From<StorageError> for KeyCeremonyError {
+    fn from(err: StorageError) -> Self {
+        KeyCeremonyError::StorageError(err)
+    }
+}
+
+impl From<AuditError> for KeyCeremonyError {
+    fn from(err: AuditError) -> Self {
+        KeyCeremonyError::AuditError(err)
+    }
+}
+
+impl std::fmt::Display for KeyCeremonyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyCeremonyError::StorageError(e) => write!(f, "Storage error: {e}"),
+            KeyCeremonyError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            KeyCeremonyError::NotFound(e) => write!(f, "Not found: {e}"),
+            KeyCeremonyError::AuditError(e) => write!(f, "Audit log error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for KeyCeremonyError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GenerateKeyInput {
+    pub purpose: KeyPurpose,
+    pub share_threshold: u8,
+    pub share_count: u8,
+    /// Admins who must confirm the fingerprint before activation, default 2
+    pub required_confirmations: Option<u32>,
+    /// Also seal the secret under this passphrase
+    pub backup_passphrase: Option<String>,
+}
+
+/// The outcome of a ceremony. `shares` and `backup` are returned once and never
+/// stored, so they must be handed to custodians straight away.
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedKey {
+    pub key: ManagedKey,
+    pub shares: Vec<String>,
+    pub backup: Option<KeyBackup>,
+}
+
+/// Where a restore takes the secret from: shares, or a backup and its passphrase
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RestoreKeyInput {
+    #[serde(default)]
+    pub shares: Vec<String>,
+    pub backup: Option<KeyBackup>,
+    pub passphrase: Option<String>,
+}
+
+pub struct KeyCeremonyEngine<S: StorageBackend> {
+    storage: S,
+    audit: AuditEngine<S>,
+}
+
+impl<S: StorageBackend + Clone + 'static> KeyCeremonyEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            audit: AuditEngine::new(storage.clone()),
+            storage,
+        }
+    }
+
+    pub fn list_keys(&self) -> Result<Vec<ManagedKey>, KeyCeremonyError> {
+        Ok(self.storage.list_managed_keys()?)
+    }
+
+    pub fn get_key(&self, key_id: &Uuid) -> Result<ManagedKey, KeyCeremonyError> {
+        self.storage
+            .get_managed_key(key_id)?
+            .ok_or_else(|| KeyCeremonyError::NotFound(format!("Key {key_id}")))
+    }
+
+    /// The key currently active for `purpose`, if any
+    pub fn active_key(&self, purpose: KeyPurpose) -> Result<Option<ManagedKey>, KeyCeremonyError> {
+        Ok(self
+            .list_keys()?
+            .into_iter()
+            .find(|key| key.purpose == purpose && key.status == ManagedKeyStatus::Active))
+    }
+
+    /// Generate a key and split its secret into shares
+    pub fn generate_key(
+        &self,
+        admin_id: &str,
+        input: GenerateKeyInput,
+        now: DateTime<Utc>,
+    ) -> Result<GeneratedKey, KeyCeremonyError> {
+        let required_confirmations = input
+            .required_confirmations
+            .unwrap_or(DEFAULT_REQUIRED_CONFIRMATIONS);
+        if required_confirmations == 0 {
+            return Err(KeyCeremonyError::ValidationError(
+                "At least one confirmation is required".to_string(),
+            ));
+        }
+
+        let mut seed = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut seed);
+        let public_key = SigningKey::from_bytes(&seed).verifying_key().to_bytes();
+        let fingerprint = blake3::hash(&public_key).to_hex().to_string();
+        let shares = shamir::split(&seed, input.share_threshold, input.share_count)
+            .map_err(KeyCeremonyError::ValidationError)?
+            .iter()
+            .map(|share| encode_share(&fingerprint, share))
+            .collect();
+
+        let key = ManagedKey {
+            key_id: Uuid::new_v4(),
+            purpose: input.purpose,
+            public_key: render_public_key(input.purpose, &public_key),
+            fingerprint,
+            status: ManagedKeyStatus::Pending,
+            share_threshold: input.share_threshold,
+            share_count: input.share_count,
+            required_confirmations,
+            confirmations: Vec::new(),
+            created_by: admin_id.to_string(),
+            created_at: now,
+            activated_at: None,
+            retired_at: None,
+        };
+        let backup = match &input.backup_passphrase {
+            Some(passphrase) => Some(seal_backup(&key, &seed, passphrase)?),
+            None => None,
+        };
+
+        self.storage.store_managed_key(&key)?;
+        self.log(admin_id, "key_generated", &key, AuditSeverity::High)?;
+        Ok(GeneratedKey {
+            key,
+            shares,
+            backup,
+        })
+    }
+
+    /// Seal the secret recovered from `shares` under a new passphrase, e.g. for
+    /// an additional custodian
+    pub fn backup_key(
+        &self,
+        admin_id: &str,
+        key_id: &Uuid,
+        shares: &[String],
+        passphrase: &str,
+    ) -> Result<KeyBackup, KeyCeremonyError> {
+        let key = self.get_key(key_id)?;
+        let seed = recover_from_shares(&key, shares)?;
+        let backup = seal_backup(&key, &seed, passphrase)?;
+        self.log(admin_id, "key_backed_up", &key, AuditSeverity::Medium)?;
+        Ok(backup)
+    }
+
+    /// Recover a key's secret, rendered the way its consumer expects it: an
+    /// `S...` seed for Stellar keys, a hex seed for the audit signing key
+    pub fn restore_key(
+        &self,
+        admin_id: &str,
+        key_id: &Uuid,
+        input: RestoreKeyInput,
+    ) -> Result<String, KeyCeremonyError> {
+        let key = self.get_key(key_id)?;
+        let seed = match (input.backup, input.passphrase) {
+            (Some(backup), Some(passphrase)) if input.shares.is_empty() => {
+                open_backup(&key, &backup, &passphrase)?
+            }
+            (None, None) if !input.shares.is_empty() => recover_from_shares(&key, &input.shares)?,
+            _ => {
+                return Err(KeyCeremonyError::ValidationError(
+                    "Provide either shares or a backup with its passphrase".to_string(),
+                ))
+            }
+        };
+        self.log(admin_id, "key_restored", &key, AuditSeverity::High)?;
+        Ok(render_secret(key.purpose, &seed))
+    }
+
+    /// Record that `admin_id` checked the key's fingerprint out of band
+    pub fn confirm_key(
+        &self,
+        admin_id: &str,
+        key_id: &Uuid,
+        fingerprint: &str,
+        now: DateTime<Utc>,
+    ) -> Result<ManagedKey, KeyCeremonyError> {
+        let mut key = self.get_key(key_id)?;
+        if key.status != ManagedKeyStatus::Pending {
+            return Err(KeyCeremonyError::ValidationError(
+                "Only pending keys can be confirmed".to_string(),
+            ));
+        }
+        if !fingerprint.trim().eq_ignore_ascii_case(&key.fingerprint) {
+            self.log_outcome(
+                admin_id,
+                "key_confirmation_rejected",
+                &key,
+                AuditOutcome::Failure,
+                AuditSeverity::High,
+            )?;
+            return Err(KeyCeremonyError::ValidationError(
+                "Fingerprint does not match the key".to_string(),
+            ));
+        }
+        if key.confirmations.iter().any(|c| c.admin_id == admin_id) {
+            return Err(KeyCeremonyError::ValidationError(
+                "You have already confirmed this key".to_string(),
+            ));
+        }
+
+        key.confirmations.push(KeyConfirmation {
+            admin_id: admin_id.to_string(),
+            confirmed_at: now,
+        });
+        self.storage.store_managed_key(&key)?;
+        self.log(admin_id, "key_confirmed", &key, AuditSeverity::Medium)?;
+        Ok(key)
+    }
+
+    /// Activate a confirmed key, retiring the one it replaces
+    pub fn activate_key(
+        &self,
+        admin_id: &str,
+        key_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<ManagedKey, KeyCeremonyError> {
+        let mut key = self.get_key(key_id)?;
+        if key.status != ManagedKeyStatus::Pending {
+            return Err(KeyCeremonyError::ValidationError(
+                "Only pending keys can be activated".to_string(),
+            ));
+        }
+        let confirmed = key.confirmations.len() as u32;
+        if confirmed < key.required_confirmations {
+            return Err(KeyCeremonyError::ValidationError(format!(
+                "Key has {confirmed} of {} required confirmations",
+                key.required_confirmations
+            )));
+        }
+
+        if let Some(mut previous) = self.active_key(key.purpose)? {
+            previous.status = ManagedKeyStatus::Retired;
+            previous.retired_at = Some(now);
+            self.storage.store_managed_key(&previous)?;
+            self.log(admin_id, "key_retired", &previous, AuditSeverity::High)?;
+        }
+        key.status = ManagedKeyStatus::Active;
+        key.activated_at = Some(now);
+        self.storage.store_managed_key(&key)?;
+        self.log(admin_id, "key_activated", &key, AuditSeverity::High)?;
+        Ok(key)
+    }
+
+    fn log(
+        &self,
+        admin_id: &str,
+        action: &str,
+        key: &ManagedKey,
+        severity: AuditSeverity,
+    ) -> Result<(), KeyCeremonyError> {
+        self.log_outcome(admin_id, action, key, AuditOutcome::Success, severity)
+    }
+
+    fn log_outcome(
+        &self,
+        admin_id: &str,
+        action: &str,
+        key: &ManagedKey,
+        outcome: AuditOutcome,
+        severity: AuditSeverity,
+    ) -> Result<(), KeyCeremonyError> {
+        let details = HashMap::from([
+            ("key_id".to_string(), serde_json::json!(key.key_id)),
+            ("purpose".to_string(), serde_json::json!(key.purpose)),
+            (
+                "fingerprint".to_string(),
+                serde_json::json!(key.fingerprint),
+            ),
+            ("status".to_string(), serde_json::json!(key.status)),
+        ]);
+        self.audit.log_event(
+            admin_id.to_string(),
+            AuditEventType::Security,
+            action.to_string(),
+            format!("managed_key:{}", key.key_id),
+            outcome,
+            severity,
+            Some(details),
+            None,
+            None,
+        )?;
+        Ok(())
+    }
+}
+
+/// Refuse to sign with `public_key` when another key is active for `purpose`.
+/// Until a ceremony key is activated for a purpose, its signers keep using
+/// the key the deployment configured.
+pub fn check_signer<S: StorageBackend + ?Sized>(
+    storage: &S,
+    purpose: KeyPurpose,
+    public_key: &str,
+) -> Result<(), KeyCeremonyError> {
+    let active = storage
+        .list_managed_keys()?
+        .into_iter()
+        .find(|key| key.purpose == purpose && key.status == ManagedKeyStatus::Active);
+    match active {
+        Some(key) if !key.public_key.eq_ignore_ascii_case(public_key) => {
+            Err(KeyCeremonyError::ValidationError(format!(
+                "Signing key {public_key} is not the active {purpose:?} key {}",
+                key.public_key
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// [`check_signer`] for the keypair a Stellar client anchors with
+pub fn check_stellar_signer<S: StorageBackend + ?Sized>(
+    storage: &S,
+    client: &StellarClient,
+) -> Result<(), KeyCeremonyError> {
+    let Some(public_key) = client.signer_public_key() else {
+        return Ok(());
+    };
+    let purpose = match client.network() {
+        StellarNetwork::Testnet => KeyPurpose::StellarTestnetAnchoring,
+        StellarNetwork::Mainnet => KeyPurpose::StellarMainnetAnchoring,
+    };
+    check_signer(storage, purpose, &public_key)
+}
+
+fn render_public_key(purpose: KeyPurpose, public_key: &[u8; 32]) -> String {
+    match purpose {
+        KeyPurpose::StellarTestnetAnchoring | KeyPurpose::StellarMainnetAnchoring => {
+            stellar_strkey::ed25519::PublicKey(*public_key).to_string()
+        }
+        KeyPurpose::AuditSigning => hex::encode(public_key),
+    }
+}
+
+fn render_secret(purpose: KeyPurpose, seed: &[u8; 32]) -> String {
+    match purpose {
+        KeyPurpose::StellarTestnetAnchoring | KeyPurpose::StellarMainnetAnchoring => {
+            stellar_strkey::ed25519::PrivateKey(*seed).to_string()
+        }
+        KeyPurpose::AuditSigning => hex::encode(seed),
+    }
+}
+
+/// Shares look like `<fingerprint prefix>-<index>-<hex bytes>`
+fn encode_share(fingerprint: &str, share: &Share) -> String {
+    format!(
+        "{}-{}-{}",
+        &fingerprint[..SHARE_TAG_LEN],
+        share.index,
+        hex::encode(&share.bytes)
+    )
+}
+
+fn decode_share(key: &ManagedKey, encoded: &str) -> Result<Share, KeyCeremonyError> {
+    let invalid = || KeyCeremonyError::ValidationError("Malformed key share".to_string());
+    let mut parts = encoded.trim().splitn(3, '-');
+    let (Some(tag), Some(index), Some(bytes)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid());
+    };
+    if !key.fingerprint.starts_with(&tag.to_ascii_lowercase()) {
+        return Err(KeyCeremonyError::ValidationError(
+            "Share belongs to a different key".to_string(),
+        ));
+    }
+    Ok(Share {
+        index: index.parse().map_err(|_| invalid())?,
+        bytes: hex::decode(bytes).map_err(|_| invalid())?,
+    })
+}
+
+fn recover_from_shares(key: &ManagedKey, encoded: &[String]) -> Result<[u8; 32], KeyCeremonyError> {
+    if encoded.len() < key.share_threshold as usize {
+        return Err(KeyCeremonyError::ValidationError(format!(
+            "{} shares are needed to restore this key",
+            key.share_threshold
+        )));
+    }
+    let shares = encoded
+        .iter()
+        .map(|share| decode_share(key, share))
+        .collect::<Result<Vec<_>, _>>()?;
+    let seed = shamir::combine(&shares).map_err(KeyCeremonyError::ValidationError)?;
+    verified_seed(key, &seed)
+}
+
+/// Check recovered bytes against the key's fingerprint
+fn verified_seed(key: &ManagedKey, seed: &[u8]) -> Result<[u8; 32], KeyCeremonyError> {
+    let seed: [u8; 32] = seed.try_into().map_err(|_| {
+        KeyCeremonyError::ValidationError("Recovered secret has the wrong length".to_string())
+    })?;
+    let public_key = SigningKey::from_bytes(&seed).verifying_key().to_bytes();
+    if blake3::hash(&public_key).to_hex().as_str() != key.fingerprint {
+        return Err(KeyCeremonyError::ValidationError(
+            "Recovered secret does not match the key fingerprint".to_string(),
+        ));
+    }
+    Ok(seed)
+}
+
+fn passphrase_key(passphrase: &str, salt: [u8; 16]) -> Result<[u8; 32], KeyCeremonyError> {
+    let hash = bcrypt::hash_with_salt(passphrase, BACKUP_KDF_COST, salt)
+        .map_err(|e| KeyCeremonyError::ValidationError(format!("Invalid passphrase: {e}")))?;
+    Ok(blake3::derive_key(
+        BACKUP_KEY_CONTEXT,
+        hash.to_string().as_bytes(),
+    ))
+}
+
+fn seal_backup(
+    key: &ManagedKey,
+    seed: &[u8; 32],
+    passphrase: &str,
+) -> Result<KeyBackup, KeyCeremonyError> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(KeyCeremonyError::ValidationError(format!(
+            "Backup passphrase must be at least {MIN_PASSPHRASE_LEN} characters"
+        )));
+    }
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let cipher_key = passphrase_key(passphrase, salt)?;
+    let ciphertext = Aes256Gcm::new((&cipher_key).into())
+        .encrypt(Nonce::from_slice(&nonce), seed.as_slice())
+        .map_err(|_| KeyCeremonyError::ValidationError("Could not seal backup".to_string()))?;
+    Ok(KeyBackup {
+        format_version: BACKUP_FORMAT_VERSION,
+        key_id: key.key_id,
+        purpose: key.purpose,
+        fingerprint: key.fingerprint.clone(),
+        salt: STANDARD.encode(salt),
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(ciphertext),
+    })
+}
+
+fn open_backup(
+    key: &ManagedKey,
+    backup: &KeyBackup,
+    passphrase: &str,
+) -> Result<[u8; 32], KeyCeremonyError> {
+    if backup.format_version != BACKUP_FORMAT_VERSION {
+        return Err(KeyCeremonyError::ValidationError(format!(
+            "Unsupported backup format version {}",
+            backup.format_version
+        )));
+    }
+    if backup.key_id != key.key_id || backup.fingerprint != key.fingerprint {
+        return Err(KeyCeremonyError::ValidationError(
+            "Backup belongs to a different key".to_string(),
+        ));
+    }
+    let invalid = || KeyCeremonyError::ValidationError("Malformed backup".to_string());
+    let salt: [u8; 16] = STANDARD
+        .decode(&backup.salt)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(invalid)?;
+    let nonce: [u8; 12] = STANDARD
+        .decode(&backup.nonce)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(invalid)?;
+    let ciphertext = STANDARD.decode(&backup.ciphertext).map_err(|_| invalid())?;
+    let cipher_key = passphrase_key(passphrase, salt)?;
+    let seed = Aes256Gcm::new((&cipher_key).into())
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| {
+            KeyCeremonyError::ValidationError("Wrong passphrase or corrupted backup".to_string())
+        })?;
+    verified_seed(key, &seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_ceremony_restores_secret_and_gates_activation() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let engine = KeyCeremonyEngine::new(Arc::clone(&storage));
+        let now = Utc::now();
+        let generated = engine
+            .generate_key(
+                "admin-1",
+                GenerateKeyInput {
+                    purpose: KeyPurpose::StellarTestnetAnchoring,
+                    share_threshold: 2,
+                    share_count: 3,
+                    required_confirmations: None,
+                    backup_passphrase: Some("correct horse battery".to_string()),
+                },
+                now,
+            )
+            .unwrap();
+        let key = &generated.key;
+        assert!(key.public_key.starts_with('G'));
+
+        let from_shares = engine
+            .restore_key(
+                "admin-1",
+                &key.key_id,
+                RestoreKeyInput {
+                    shares: generated.shares[1..].to_vec(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert!(from_shares.starts_with('S'));
+        let from_backup = engine
+            .restore_key(
+                "admin-2",
+                &key.key_id,
+                RestoreKeyInput {
+                    shares: Vec::new(),
+                    backup: generated.backup.clone(),
+                    passphrase: Some("correct horse battery".to_string()),
+                },
+            )
+            .unwrap();
+        assert_eq!(from_shares, from_backup);
+        assert!(engine
+            .restore_key(
+                "admin-2",
+                &key.key_id,
+                RestoreKeyInput {
+                    shares: Vec::new(),
+                    backup: generated.backup.clone(),
+                    passphrase: Some("wrong horse battery".to_string()),
+                },
+            )
+            .is_err());
+
+        assert!(engine.activate_key("admin-1", &key.key_id, now).is_err());
+        assert!(engine
+            .confirm_key("admin-2", &key.key_id, "00ff", now)
+            .is_err());
+        engine
+            .confirm_key("admin-1", &key.key_id, &key.fingerprint, now)
+            .unwrap();
+        assert!(engine
+            .confirm_key("admin-1", &key.key_id, &key.fingerprint, now)
+            .is_err());
+        engine
+            .confirm_key("admin-2", &key.key_id, &key.fingerprint, now)
+            .unwrap();
+        let active = engine.activate_key("admin-1", &key.key_id, now).unwrap();
+        assert_eq!(active.status, ManagedKeyStatus::Active);
+
+        let events = storage.lock().unwrap().list_audit_events().unwrap();
+        assert!(events.iter().any(|e| e.action == "key_activated"
+            && e.details.get("fingerprint") == Some(&serde_json::json!(key.fingerprint))));
+    }
+
+    #[test]
+    fn test_signers_must_use_the_active_key() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let engine = KeyCeremonyEngine::new(Arc::clone(&storage));
+        let now = Utc::now();
+        let configured = hex::encode([7u8; 32]);
+
+        // Before any ceremony the configured key signs
+        check_signer(&storage, KeyPurpose::AuditSigning, &configured).unwrap();
+
+        let key = engine
+            .generate_key(
+                "admin-1",
+                GenerateKeyInput {
+                    purpose: KeyPurpose::AuditSigning,
+                    share_threshold: 2,
+                    share_count: 2,
+                    required_confirmations: Some(1),
+                    backup_passphrase: None,
+                },
+                now,
+            )
+            .unwrap()
+            .key;
+        engine
+            .confirm_key("admin-1", &key.key_id, &key.fingerprint, now)
+            .unwrap();
+        // A pending key does not displace the configured one yet
+        check_signer(&storage, KeyPurpose::AuditSigning, &configured).unwrap();
+
+        engine.activate_key("admin-1", &key.key_id, now).unwrap();
+        assert!(matches!(
+            check_signer(&storage, KeyPurpose::AuditSigning, &configured),
+            Err(KeyCeremonyError::ValidationError(_))
+        ));
+        check_signer(&storage, KeyPurpose::AuditSigning, &key.public_key).unwrap();
+        // Other purposes are unaffected
+        check_signer(&storage, KeyPurpose::StellarMainnetAnchoring, &configured).unwrap();
+    }
+}
@@ -1,29 +1,84 @@
+pub mod access_review_engine;
 pub mod activity_engine;
+pub mod adapter_intent_log;
 pub mod adapters;
+pub mod anchoring_cost_engine;
+pub mod announcement_engine;
+pub mod anonymization_engine;
+pub mod attestation_engine;
 pub mod audit_engine;
+pub mod audit_query_language;
 pub mod blockchain_event_listener;
 pub mod cattle_robot;
+pub mod change_feed_engine;
 pub mod circuits_engine;
+pub mod comment_engine;
+pub mod config_bundle_engine;
 pub mod conflict_detection;
+pub mod connectors;
+pub mod dashboard_metrics_engine;
+pub mod data_export_engine;
+pub mod data_lake_gc_engine;
+pub mod data_quality_engine;
+pub mod dedup_sharing_engine;
+pub mod dedup_strategy;
+pub mod device_backup_engine;
 pub mod dfid_engine;
+pub mod disclosure_engine;
 pub mod email_service;
+pub mod engagement_engine;
+pub mod enrichment_policy_engine;
 pub mod error_tracking;
+pub mod event_schema;
+pub mod event_signing_engine;
 pub mod events_engine;
+pub mod federation_engine;
+pub mod fuzzy_identifier_matching;
+pub mod hashing;
+pub mod i18n;
 pub mod identifier_types;
 pub mod ipfs_client;
+pub mod item_access;
 pub mod items_engine;
+pub mod key_ceremony_engine;
+pub mod lifecycle_engine;
+pub mod live_stream;
 pub mod logging;
+pub mod maintenance_engine;
+pub mod membership_reconciliation_engine;
+pub mod merge_lineage;
 pub mod merkle_engine;
 pub mod merkle_tree;
+pub mod notarization_engine;
+pub mod pagination;
+pub mod partner_token_engine;
+pub mod payload_limits;
+pub mod preview_engine;
+pub mod proof_share_engine;
+pub mod provenance_engine;
 pub mod receipt_engine;
+pub mod reverification_engine;
+pub mod review_queue_engine;
+pub mod runbook_engine;
+pub mod scaling_signals;
+pub mod search_index;
+pub mod sensor_summary_engine;
+pub mod sensor_timeseries_engine;
+pub mod shamir;
+pub mod sla_engine;
 pub mod snapshot_engine;
 pub mod snapshot_types;
 pub mod stellar_client;
 pub mod storage;
 pub mod storage_helpers;
 pub mod types;
+pub mod usage_anomaly_engine;
 pub mod verification_engine;
+pub mod verification_rules;
+pub mod verification_scheduler;
+pub mod zk_circuits;
 pub mod zk_proof_engine;
+pub mod zk_proof_worker;
 // Stellar health check disabled - using SDK not CLI
 // pub mod stellar_health_check;
 // pub mod postgres_storage; // Disabled - has type incompatibilities. Use PostgresPersistence instead.
@@ -43,6 +98,7 @@ pub mod db_init;
 pub mod error_handling;
 pub mod http_utils;
 pub mod notification_engine;
+pub mod organization_engine;
 pub mod postgres_persistence;
 pub mod rate_limiter;
 pub mod safe_json_numbers;
@@ -51,7 +107,9 @@ pub mod storage_history_manager; // Deprecated - use storage_history_reader
 pub mod storage_history_reader;
 pub mod tier_permission_system;
 pub mod webhook_delivery_worker;
+pub mod webhook_encryption;
 pub mod webhook_engine;
+pub mod workspace_bucket_engine;
 
 #[cfg(test)]
 mod test_safe_json_numbers;
@@ -85,6 +143,7 @@ pub use types::*;
 pub use verification_engine::{VerificationEngine, VerificationError, VerificationResult};
 pub use webhook_engine::*;
 pub use zk_proof_engine::{
-    AgriculturalContext, CircuitInput, CircuitTemplate, CircuitType, ProofStatus,
+    AgriculturalContext, CircuitInput, CircuitTemplate, CircuitType, ProofStatus, SetupSource,
     VerificationResult as ZkVerificationResult, ZkProof, ZkProofEngine, ZkProofError,
+    ZkSetupArtifact,
 };
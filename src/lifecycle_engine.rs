@@ -0,0 +1,472 @@
+//! Per-workspace item lifecycles.
+//!
+//! A workspace declares its lifecycle as ordered stages (planted → harvested →
+//! processed → shipped → sold), each entered by recording events of certain
+//! types. A move that keeps the order is applied as the event is recorded. A
+//! move back, or past stages the item never reached unless the lifecycle allows
+//! skipping, is held and flagged into the pending-item review queue; reviewers
+//! settle it by setting the item's stage by hand.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    Event, EventType, ItemLifecycle, LifecycleDefinition, LifecycleStage, LifecycleTransition,
+    PendingItem, PendingReason, QualitySeverity,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashSet;
+
+pub const MAX_STAGES: usize = 32;
+const MAX_STAGE_NAME_LEN: usize = 64;
+/// Issue type of the review entries held transitions create
+pub const OUT_OF_ORDER_ISSUE: &str = "lifecycle_out_of_order";
+
+#[derive(Debug)]
+pub enum LifecycleError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+    NotFound(String),
+}
+
+impl From<StorageError> for LifecycleError {
+    fn from(err: StorageError) -> Self {
+        LifecycleError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for LifecycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LifecycleError::StorageError(e) => write!(f, "Storage error: {e}"),
+            LifecycleError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            LifecycleError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            LifecycleError::NotFound(e) => write!(f, "Not found: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for LifecycleError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LifecycleStageInput {
+    pub name: String,
+    /// Event type names, built-in or custom
+    pub entered_by: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LifecycleDefinitionInput {
+    pub stages: Vec<LifecycleStageInput>,
+    #[serde(default)]
+    pub allow_skipping: bool,
+}
+
+/// Workspace an item belongs to: that of the member who sourced its first event
+pub fn item_workspace<S: StorageBackend + ?Sized>(
+    storage: &S,
+    dfid: &str,
+) -> Result<Option<String>, StorageError> {
+    let first_source = storage
+        .get_events_by_dfid(dfid)?
+        .into_iter()
+        .min_by_key(|event| event.timestamp)
+        .map(|event| event.source);
+    match first_source {
+        Some(source) => Ok(storage
+            .get_user_account(&source)?
+            .and_then(|account| account.workspace_id)),
+        None => Ok(None),
+    }
+}
+
+/// Move the event's item through its workspace's lifecycle. Lifecycle tracking
+/// never fails the event that triggers it.
+pub fn record_lifecycle_transition<S: StorageBackend + ?Sized>(storage: &S, event: &Event) {
+    if event.is_local {
+        return;
+    }
+    if let Err(e) = apply_event(storage, event) {
+        tracing::warn!("Failed to track lifecycle of {}: {}", event.dfid, e);
+    }
+}
+
+/// The transition the event caused, if its type enters a stage of the item's lifecycle
+pub fn apply_event<S: StorageBackend + ?Sized>(
+    storage: &S,
+    event: &Event,
+) -> Result<Option<LifecycleTransition>, StorageError> {
+    let existing = storage.get_item_lifecycle(&event.dfid)?;
+    let workspace_id = match &existing {
+        Some(lifecycle) => lifecycle.workspace_id.clone(),
+        None => match item_workspace(storage, &event.dfid)? {
+            Some(workspace_id) => workspace_id,
+            None => return Ok(None),
+        },
+    };
+    let Some(definition) = storage.get_lifecycle_definition(&workspace_id)? else {
+        return Ok(None);
+    };
+    let Some(to) = definition.stage_for(&event.event_type) else {
+        return Ok(None);
+    };
+
+    let mut lifecycle = existing.unwrap_or_else(|| ItemLifecycle {
+        dfid: event.dfid.clone(),
+        workspace_id: workspace_id.clone(),
+        stage: None,
+        stage_entered_at: None,
+        transitions: Vec::new(),
+    });
+    let from = lifecycle
+        .stage
+        .as_ref()
+        .and_then(|name| definition.stages.iter().position(|s| &s.name == name));
+    if from == Some(to) {
+        return Ok(None);
+    }
+
+    let to_stage = definition.stages[to].name.clone();
+    let mut transition = LifecycleTransition {
+        from_stage: lifecycle.stage.clone(),
+        to_stage: to_stage.clone(),
+        event_id: Some(event.event_id),
+        at: event.timestamp,
+        applied: true,
+        review_id: None,
+        set_by: None,
+    };
+    if definition.is_in_order(from, to) {
+        lifecycle.stage = Some(to_stage);
+        lifecycle.stage_entered_at = Some(event.timestamp);
+    } else {
+        let review = flag_for_review(storage, event, &workspace_id, &transition)?;
+        transition.applied = false;
+        transition.review_id = Some(review.pending_id);
+    }
+    lifecycle.transitions.push(transition.clone());
+    storage.store_item_lifecycle(&lifecycle)?;
+    Ok(Some(transition))
+}
+
+fn flag_for_review<S: StorageBackend + ?Sized>(
+    storage: &S,
+    event: &Event,
+    workspace_id: &str,
+    transition: &LifecycleTransition,
+) -> Result<PendingItem, StorageError> {
+    let identifiers = storage
+        .get_item_by_dfid(&event.dfid)?
+        .map(|item| item.identifiers)
+        .unwrap_or_default();
+    let from = transition.from_stage.as_deref().unwrap_or("(none)");
+    let mut review = PendingItem::new(
+        identifiers,
+        None,
+        event.event_id,
+        PendingReason::DataQualityIssue {
+            issue_type: OUT_OF_ORDER_ISSUE.to_string(),
+            severity: QualitySeverity::High,
+            details: format!(
+                "{} event on {} would move it from {} to {}",
+                event.event_type, event.dfid, from, transition.to_stage
+            ),
+        },
+        Some(event.source.clone()),
+        Some(workspace_id.to_string()),
+    );
+    review.add_metadata("dfid".to_string(), event.dfid.clone().into());
+    review.add_metadata("event_id".to_string(), event.event_id.to_string().into());
+    review.add_metadata(
+        "from_stage".to_string(),
+        transition.from_stage.clone().into(),
+    );
+    review.add_metadata("to_stage".to_string(), transition.to_stage.clone().into());
+    storage.store_pending_item(&review)?;
+    Ok(review)
+}
+
+pub struct LifecycleEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> LifecycleEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Members manage their own workspace's lifecycle; admins any workspace's
+    fn check_member(&self, user_id: &str, workspace_id: &str) -> Result<(), LifecycleError> {
+        let account = self
+            .storage
+            .get_user_account(user_id)?
+            .ok_or_else(|| LifecycleError::PermissionDenied(format!("Unknown user {user_id}")))?;
+        if account.is_admin || account.workspace_id.as_deref() == Some(workspace_id) {
+            Ok(())
+        } else {
+            Err(LifecycleError::PermissionDenied(
+                "Only workspace members can manage its lifecycle".to_string(),
+            ))
+        }
+    }
+
+    pub fn get_definition(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+    ) -> Result<LifecycleDefinition, LifecycleError> {
+        self.check_member(user_id, workspace_id)?;
+        self.storage
+            .get_lifecycle_definition(workspace_id)?
+            .ok_or_else(|| {
+                LifecycleError::NotFound(format!("Workspace {workspace_id} has no lifecycle"))
+            })
+    }
+
+    pub fn set_definition(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+        input: LifecycleDefinitionInput,
+        now: DateTime<Utc>,
+    ) -> Result<LifecycleDefinition, LifecycleError> {
+        self.check_member(user_id, workspace_id)?;
+        if input.stages.is_empty() || input.stages.len() > MAX_STAGES {
+            return Err(LifecycleError::ValidationError(format!(
+                "A lifecycle has between 1 and {MAX_STAGES} stages"
+            )));
+        }
+
+        let mut names = HashSet::new();
+        let mut event_types: Vec<EventType> = Vec::new();
+        let mut stages = Vec::with_capacity(input.stages.len());
+        for stage in input.stages {
+            let name = stage.name.trim().to_string();
+            if name.is_empty() || name.len() > MAX_STAGE_NAME_LEN {
+                return Err(LifecycleError::ValidationError(format!(
+                    "Stage names must be 1 to {MAX_STAGE_NAME_LEN} characters"
+                )));
+            }
+            if !names.insert(name.to_lowercase()) {
+                return Err(LifecycleError::ValidationError(format!(
+                    "Stage {name} is listed twice"
+                )));
+            }
+            if stage.entered_by.is_empty() {
+                return Err(LifecycleError::ValidationError(format!(
+                    "Stage {name} needs at least one event type entering it"
+                )));
+            }
+            let mut entered_by = Vec::with_capacity(stage.entered_by.len());
+            for type_name in &stage.entered_by {
+                let type_name = type_name.trim();
+                if type_name.is_empty() {
+                    return Err(LifecycleError::ValidationError(
+                        "Event type names must not be empty".to_string(),
+                    ));
+                }
+                let event_type = EventType::from_name(type_name);
+                if event_types.contains(&event_type) {
+                    return Err(LifecycleError::ValidationError(format!(
+                        "Event type {type_name} enters more than one stage"
+                    )));
+                }
+                event_types.push(event_type.clone());
+                entered_by.push(event_type);
+            }
+            stages.push(LifecycleStage { name, entered_by });
+        }
+
+        let definition = LifecycleDefinition {
+            workspace_id: workspace_id.to_string(),
+            stages,
+            allow_skipping: input.allow_skipping,
+            updated_by: user_id.to_string(),
+            updated_at: now,
+        };
+        self.storage.store_lifecycle_definition(&definition)?;
+        Ok(definition)
+    }
+
+    pub fn get_item_lifecycle(&self, dfid: &str) -> Result<ItemLifecycle, LifecycleError> {
+        self.storage
+            .get_item_lifecycle(dfid)?
+            .ok_or_else(|| LifecycleError::NotFound(format!("Item {dfid} has no lifecycle stage")))
+    }
+
+    /// The workspace's tracked items, optionally only those currently in `stage`
+    pub fn list_items(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+        stage: Option<&str>,
+    ) -> Result<Vec<ItemLifecycle>, LifecycleError> {
+        self.check_member(user_id, workspace_id)?;
+        let mut items: Vec<ItemLifecycle> = self
+            .storage
+            .list_item_lifecycles(workspace_id)?
+            .into_iter()
+            .filter(|lifecycle| stage.is_none_or(|stage| lifecycle.stage.as_deref() == Some(stage)))
+            .collect();
+        items.sort_by(|a, b| a.dfid.cmp(&b.dfid));
+        Ok(items)
+    }
+
+    /// Set an item's stage by hand, settling the moves held for review
+    pub fn set_stage(
+        &self,
+        user_id: &str,
+        dfid: &str,
+        stage: &str,
+        now: DateTime<Utc>,
+    ) -> Result<ItemLifecycle, LifecycleError> {
+        let existing = self.storage.get_item_lifecycle(dfid)?;
+        let workspace_id = match &existing {
+            Some(lifecycle) => lifecycle.workspace_id.clone(),
+            None => item_workspace(&self.storage, dfid)?.ok_or_else(|| {
+                LifecycleError::NotFound(format!("Item {dfid} belongs to no workspace"))
+            })?,
+        };
+        self.check_member(user_id, &workspace_id)?;
+        let definition = self
+            .storage
+            .get_lifecycle_definition(&workspace_id)?
+            .ok_or_else(|| {
+                LifecycleError::NotFound(format!("Workspace {workspace_id} has no lifecycle"))
+            })?;
+        if !definition.stages.iter().any(|s| s.name == stage) {
+            return Err(LifecycleError::ValidationError(format!(
+                "Stage {stage} is not part of the lifecycle"
+            )));
+        }
+
+        let mut lifecycle = existing.unwrap_or_else(|| ItemLifecycle {
+            dfid: dfid.to_string(),
+            workspace_id,
+            stage: None,
+            stage_entered_at: None,
+            transitions: Vec::new(),
+        });
+        for held in lifecycle.transitions.iter_mut().filter(|t| !t.applied) {
+            if let Some(review_id) = held.review_id.take() {
+                self.storage.delete_pending_item(&review_id)?;
+            }
+        }
+        lifecycle.transitions.push(LifecycleTransition {
+            from_stage: lifecycle.stage.clone(),
+            to_stage: stage.to_string(),
+            event_id: None,
+            at: now,
+            applied: true,
+            review_id: None,
+            set_by: Some(user_id.to_string()),
+        });
+        lifecycle.stage = Some(stage.to_string());
+        lifecycle.stage_entered_at = Some(now);
+        self.storage.store_item_lifecycle(&lifecycle)?;
+        Ok(lifecycle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{AccountStatus, EventVisibility, TierLimits, UserAccount, UserTier};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_events_move_items_through_stages_in_order() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let account = UserAccount {
+            user_id: "farmer".to_string(),
+            username: "farmer".to_string(),
+            email: "farmer@example.com".to_string(),
+            password_hash: "hash".to_string(),
+            limits: TierLimits::for_tier(&UserTier::Professional),
+            tier: UserTier::Professional,
+            status: AccountStatus::Active,
+            credits: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_login: None,
+            subscription: None,
+            is_admin: false,
+            workspace_id: Some("coop".to_string()),
+            available_adapters: None,
+            locale: None,
+        };
+        storage.store_user_account(&account).unwrap();
+
+        let engine = LifecycleEngine::new(Arc::clone(&storage));
+        let stages = ["Planted", "Harvested", "Processed", "Shipped", "Sold"];
+        engine
+            .set_definition(
+                "farmer",
+                "coop",
+                LifecycleDefinitionInput {
+                    stages: stages
+                        .iter()
+                        .map(|name| LifecycleStageInput {
+                            name: name.to_lowercase(),
+                            entered_by: vec![name.to_string()],
+                        })
+                        .collect(),
+                    allow_skipping: false,
+                },
+                Utc::now(),
+            )
+            .unwrap();
+
+        // Custom domain types, as circuits register them
+        let record = |event_type: &str| {
+            let event = Event::new(
+                "DFID-LOT-1".to_string(),
+                EventType::from_name(event_type),
+                "farmer".to_string(),
+                EventVisibility::Public,
+            );
+            storage.store_event(&event).unwrap();
+            record_lifecycle_transition(&storage, &event);
+        };
+        record("Planted");
+        record("Harvested");
+        assert_eq!(
+            engine
+                .get_item_lifecycle("DFID-LOT-1")
+                .unwrap()
+                .stage
+                .as_deref(),
+            Some("harvested")
+        );
+
+        // Selling before processing and shipping is held for review
+        record("Sold");
+        let lifecycle = engine.get_item_lifecycle("DFID-LOT-1").unwrap();
+        assert_eq!(lifecycle.stage.as_deref(), Some("harvested"));
+        let held = lifecycle.transitions.last().unwrap();
+        assert!(!held.applied);
+        let review_id = held.review_id.unwrap();
+        assert!(
+            storage
+                .get_pending_item(&review_id)
+                .unwrap()
+                .unwrap()
+                .manual_review_required
+        );
+
+        // A reviewer settles it
+        let lifecycle = engine
+            .set_stage("farmer", "DFID-LOT-1", "sold", Utc::now())
+            .unwrap();
+        assert_eq!(lifecycle.stage.as_deref(), Some("sold"));
+        assert!(storage.get_pending_item(&review_id).unwrap().is_none());
+        assert_eq!(
+            engine
+                .list_items("farmer", "coop", Some("sold"))
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//! In-process fan-out of newly stored events, circuit activities and notifications
+//! to `/api/stream` subscribers.
+//!
+//! Engines publish through an optional [`LiveStream`] handle right after a record is
+//! stored. Each connection wraps its receiver in a [`LiveSubscription`], which only
+//! lets through records the user may see: notifications addressed to them, and
+//! events/activities of circuits they are a member of.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{Activity, Event, EventVisibility, Notification};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+pub const LIVE_STREAM_CAPACITY: usize = 1024;
+/// How long a subscription trusts its cached circuit membership
+const MEMBERSHIP_REFRESH: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum LiveRecord {
+    Event(Event),
+    Activity(Activity),
+    Notification(Notification),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LiveRecordKind {
+    Event,
+    Activity,
+    Notification,
+}
+
+impl LiveRecordKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LiveRecordKind::Event => "event",
+            LiveRecordKind::Activity => "activity",
+            LiveRecordKind::Notification => "notification",
+        }
+    }
+
+    /// Parse a comma-separated `types=` filter such as `event,notification`
+    pub fn parse_list(list: &str) -> Result<HashSet<Self>, String> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|kind| !kind.is_empty())
+            .map(|kind| match kind {
+                "event" | "events" => Ok(LiveRecordKind::Event),
+                "activity" | "activities" => Ok(LiveRecordKind::Activity),
+                "notification" | "notifications" => Ok(LiveRecordKind::Notification),
+                other => Err(format!("Unknown stream record type '{other}'")),
+            })
+            .collect()
+    }
+}
+
+impl LiveRecord {
+    pub fn kind(&self) -> LiveRecordKind {
+        match self {
+            LiveRecord::Event(_) => LiveRecordKind::Event,
+            LiveRecord::Activity(_) => LiveRecordKind::Activity,
+            LiveRecord::Notification(_) => LiveRecordKind::Notification,
+        }
+    }
+}
+
+/// Cloneable publishing handle shared by the engines and the stream endpoint
+#[derive(Debug, Clone)]
+pub struct LiveStream {
+    tx: broadcast::Sender<LiveRecord>,
+}
+
+impl Default for LiveStream {
+    fn default() -> Self {
+        Self::new(LIVE_STREAM_CAPACITY)
+    }
+}
+
+impl LiveStream {
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _rx) = broadcast::channel(capacity);
+        Self { tx }
+    }
+
+    /// Fire-and-forget: having no subscribers is not an error
+    pub fn publish(&self, record: LiveRecord) {
+        let _ = self.tx.send(record);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<LiveRecord> {
+        self.tx.subscribe()
+    }
+}
+
+/// What a subscription delivers next
+#[derive(Debug)]
+pub enum LiveMessage {
+    Record(Box<LiveRecord>),
+    /// The client fell behind and this many records were dropped
+    Lagged(u64),
+}
+
+pub struct LiveSubscription<S: StorageBackend> {
+    storage: S,
+    rx: broadcast::Receiver<LiveRecord>,
+    user_id: String,
+    circuit_filter: Option<Uuid>,
+    kinds: Option<HashSet<LiveRecordKind>>,
+    member_circuits: HashSet<Uuid>,
+    dfid_visibility: HashMap<String, bool>,
+    refreshed_at: Instant,
+}
+
+impl<S: StorageBackend> LiveSubscription<S> {
+    /// Subscribe `user_id`, optionally narrowed to one circuit (which they must belong
+    /// to) and to some record kinds
+    pub fn new(
+        storage: S,
+        stream: &LiveStream,
+        user_id: &str,
+        circuit_filter: Option<Uuid>,
+        kinds: Option<HashSet<LiveRecordKind>>,
+    ) -> Result<Self, StorageError> {
+        let mut subscription = Self {
+            storage,
+            rx: stream.subscribe(),
+            user_id: user_id.to_string(),
+            circuit_filter,
+            kinds,
+            member_circuits: HashSet::new(),
+            dfid_visibility: HashMap::new(),
+            refreshed_at: Instant::now(),
+        };
+        subscription.refresh_membership()?;
+        Ok(subscription)
+    }
+
+    pub fn is_member_of(&self, circuit_id: &Uuid) -> bool {
+        self.member_circuits.contains(circuit_id)
+    }
+
+    fn refresh_membership(&mut self) -> Result<(), StorageError> {
+        self.member_circuits = self
+            .storage
+            .get_circuits_for_member(&self.user_id)?
+            .into_iter()
+            .map(|circuit| circuit.circuit_id)
+            .collect();
+        self.dfid_visibility.clear();
+        self.refreshed_at = Instant::now();
+        Ok(())
+    }
+
+    /// Wait for the next record this user may see
+    pub async fn next(&mut self) -> Option<LiveMessage> {
+        loop {
+            match self.rx.recv().await {
+                Ok(record) => {
+                    if self.allows(&record) {
+                        return Some(LiveMessage::Record(Box::new(record)));
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    return Some(LiveMessage::Lagged(n));
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    pub fn allows(&mut self, record: &LiveRecord) -> bool {
+        if let Some(kinds) = &self.kinds {
+            if !kinds.contains(&record.kind()) {
+                return false;
+            }
+        }
+        if self.refreshed_at.elapsed() > MEMBERSHIP_REFRESH {
+            if let Err(e) = self.refresh_membership() {
+                tracing::warn!("Failed to refresh stream membership: {}", e);
+            }
+        }
+
+        match record {
+            LiveRecord::Notification(notification) => notification.user_id == self.user_id,
+            LiveRecord::Activity(activity) => self.in_scope(&activity.circuit_id),
+            LiveRecord::Event(event) => self.allows_event(event),
+        }
+    }
+
+    fn in_scope(&self, circuit_id: &Uuid) -> bool {
+        self.circuit_filter
+            .is_none_or(|filter| filter == *circuit_id)
+            && self.member_circuits.contains(circuit_id)
+    }
+
+    fn allows_event(&mut self, event: &Event) -> bool {
+        let own = event.source == self.user_id;
+        if event.is_local || matches!(event.visibility, EventVisibility::Private) {
+            return own && self.circuit_filter.is_none();
+        }
+        if let Some(circuit_id) = event.pushed_to_circuit {
+            return self.in_scope(&circuit_id);
+        }
+        if own && self.circuit_filter.is_none() {
+            return true;
+        }
+        if let Some(visible) = self.dfid_visibility.get(&event.dfid) {
+            return *visible;
+        }
+
+        let scope: Vec<Uuid> = match self.circuit_filter {
+            Some(filter) if self.member_circuits.contains(&filter) => vec![filter],
+            Some(_) => Vec::new(),
+            None => self.member_circuits.iter().copied().collect(),
+        };
+        let visible = scope.iter().any(|circuit_id| {
+            self.storage
+                .get_circuit_items(circuit_id)
+                .map(|items| items.iter().any(|item| item.dfid == event.dfid))
+                .unwrap_or(false)
+        });
+        self.dfid_visibility.insert(event.dfid.clone(), visible);
+        visible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{CircuitItem, EventType, NotificationType};
+    use crate::CircuitsEngine;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_subscription_only_delivers_member_records() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let mut circuits = CircuitsEngine::new(Arc::clone(&storage));
+        let circuit = circuits
+            .create_circuit(
+                "Cooperative".to_string(),
+                "Shared herd".to_string(),
+                "alice".to_string(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        storage
+            .store_circuit_item(&CircuitItem::new(
+                "DFID-shared".to_string(),
+                circuit.circuit_id,
+                "alice".to_string(),
+                vec![],
+            ))
+            .unwrap();
+
+        let stream = LiveStream::default();
+        let mut alice =
+            LiveSubscription::new(Arc::clone(&storage), &stream, "alice", None, None).unwrap();
+        let mut bob =
+            LiveSubscription::new(Arc::clone(&storage), &stream, "bob", None, None).unwrap();
+
+        let shared = Event::new(
+            "DFID-shared".to_string(),
+            EventType::Created,
+            "carol".to_string(),
+            EventVisibility::Public,
+        );
+        let unrelated = Event::new(
+            "DFID-other".to_string(),
+            EventType::Created,
+            "carol".to_string(),
+            EventVisibility::Public,
+        );
+        let to_bob = Notification::new(
+            "bob".to_string(),
+            NotificationType::JoinRequestReceived,
+            "Hello".to_string(),
+            "Hi bob".to_string(),
+            serde_json::Value::Null,
+        );
+        stream.publish(LiveRecord::Event(unrelated));
+        stream.publish(LiveRecord::Event(shared));
+        stream.publish(LiveRecord::Notification(to_bob));
+
+        match alice.next().await {
+            Some(LiveMessage::Record(record)) => match *record {
+                LiveRecord::Event(event) => assert_eq!(event.dfid, "DFID-shared"),
+                other => panic!("unexpected {other:?}"),
+            },
+            other => panic!("unexpected {other:?}"),
+        }
+        match bob.next().await {
+            Some(LiveMessage::Record(record)) => {
+                assert_eq!(record.kind(), LiveRecordKind::Notification)
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert_eq!(
+            LiveRecordKind::parse_list("event, notifications")
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(LiveRecordKind::parse_list("items").is_err());
+    }
+}
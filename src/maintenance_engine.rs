@@ -0,0 +1,319 @@
+//! Maintenance mode: an operator-controlled freeze on writes.
+//!
+//! While a window is active the API rejects mutating requests with a 503 that
+//! carries the window, so clients can back off until `ends_at`. Reads, sign-in
+//! and the verification endpoints that store nothing stay available (ZK proof
+//! verification records its result, so it is frozen too), and background
+//! work such as verification of in-flight anchors is unaffected. Setting a window
+//! announces it to every user through an `Announcement` notification.
+
+use crate::announcement_engine::{AnnouncementEngine, AnnouncementError, AnnouncementInput};
+use crate::live_stream::LiveStream;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{AnnouncementAudience, AnnouncementCategory, MaintenanceMode, Notification};
+use axum::http::Method;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+const DEFAULT_MESSAGE: &str = "Scheduled platform maintenance.";
+
+/// Non-safe requests that still pass during maintenance, as (method, route);
+/// `:name` matches one path segment. Only signing in and lifting the freeze
+/// write anything; the verify endpoints listed store nothing.
+const ALLOWED_DURING_MAINTENANCE: [(Method, &str); 10] = [
+    (Method::PUT, "/api/admin/maintenance"),
+    (Method::POST, "/api/auth/login"),
+    (Method::POST, "/api/auth/refresh"),
+    (Method::POST, "/api/receipts/:id/verify"),
+    (Method::POST, "/api/receipts/:id/verify/stream"),
+    (Method::POST, "/api/merkle/verify-proof"),
+    (Method::POST, "/api/public/merkle/verify-proof"),
+    (Method::POST, "/api/public/notarizations/verify"),
+    (Method::POST, "/api/public/disclosures/verify"),
+    (Method::POST, "/api/provenance/verify"),
+];
+
+#[derive(Debug)]
+pub enum MaintenanceError {
+    StorageError(StorageError),
+    ValidationError(String),
+    AnnouncementError(AnnouncementError),
+}
+
+impl From<StorageError> for MaintenanceError {
+    fn from(err: StorageError) -> Self {
+        MaintenanceError::StorageError(err)
+    }
+}
+
+impl From<AnnouncementError> for MaintenanceError {
+    fn from(err: AnnouncementError) -> Self {
+        MaintenanceError::AnnouncementError(err)
+    }
+}
+
+impl std::fmt::Display for MaintenanceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MaintenanceError::StorageError(e) => write!(f, "Storage error: {e}"),
+            MaintenanceError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            MaintenanceError::AnnouncementError(e) => write!(f, "Announcement failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for MaintenanceError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MaintenanceInput {
+    pub enabled: bool,
+    pub message: Option<String>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    /// Notify all users of the window; defaults to true when enabling
+    pub announce: Option<bool>,
+}
+
+/// Whether a request may pass while writes are frozen
+pub fn is_exempt(method: &Method, path: &str) -> bool {
+    method.is_safe()
+        || ALLOWED_DURING_MAINTENANCE
+            .iter()
+            .any(|(allowed, route)| allowed == method && route_matches(route, path))
+}
+
+fn route_matches(route: &str, path: &str) -> bool {
+    let mut route = route.split('/');
+    let mut path = path.trim_end_matches('/').split('/');
+    loop {
+        match (route.next(), path.next()) {
+            (None, None) => return true,
+            (Some(r), Some(p)) if r == p || (r.starts_with(':') && !p.is_empty()) => {}
+            _ => return false,
+        }
+    }
+}
+
+pub struct MaintenanceEngine<S: StorageBackend> {
+    storage: S,
+    announcements: AnnouncementEngine<S>,
+}
+
+impl<S: StorageBackend + Clone> MaintenanceEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            announcements: AnnouncementEngine::new(storage.clone()),
+            storage,
+        }
+    }
+
+    /// Publish the window's announcement to `/api/stream` subscribers
+    pub fn with_live_stream(mut self, live_stream: LiveStream) -> Self {
+        self.announcements = self.announcements.with_live_stream(live_stream);
+        self
+    }
+
+    pub fn current(&self) -> Result<MaintenanceMode, MaintenanceError> {
+        Ok(self.storage.get_maintenance_mode()?.unwrap_or_default())
+    }
+
+    /// The current window if writes are frozen at `now`
+    pub fn active_window(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Option<MaintenanceMode>, MaintenanceError> {
+        let mode = self.current()?;
+        Ok(mode.is_active(now).then_some(mode))
+    }
+
+    /// Replace the maintenance window, returning it together with any
+    /// announcement notifications delivered
+    pub fn update(
+        &self,
+        admin_user_id: &str,
+        input: MaintenanceInput,
+        now: DateTime<Utc>,
+    ) -> Result<(MaintenanceMode, Vec<Notification>), MaintenanceError> {
+        if let (Some(starts_at), Some(ends_at)) = (input.starts_at, input.ends_at) {
+            if ends_at <= starts_at {
+                return Err(MaintenanceError::ValidationError(
+                    "ends_at must be after starts_at".to_string(),
+                ));
+            }
+        }
+        if input.enabled && input.ends_at.is_some_and(|ends_at| ends_at <= now) {
+            return Err(MaintenanceError::ValidationError(
+                "ends_at is already in the past".to_string(),
+            ));
+        }
+
+        let previous = self.current()?;
+        // A window that is lifted or replaced should no longer be advertised
+        if let Some(announcement_id) = previous.announcement_id {
+            let _ = self
+                .announcements
+                .cancel_announcement(&announcement_id, now);
+        }
+
+        let message = input
+            .message
+            .map(|m| m.trim().to_string())
+            .filter(|m| !m.is_empty())
+            .unwrap_or_else(|| DEFAULT_MESSAGE.to_string());
+        let mut mode = MaintenanceMode {
+            enabled: input.enabled,
+            message,
+            starts_at: input.starts_at,
+            ends_at: input.ends_at,
+            announcement_id: None,
+            updated_by: Some(admin_user_id.to_string()),
+            updated_at: Some(now),
+        };
+
+        let mut notifications = Vec::new();
+        if mode.enabled && input.announce.unwrap_or(true) {
+            let announcement = self.announcements.create_announcement(
+                admin_user_id,
+                AnnouncementInput {
+                    title: "Scheduled maintenance".to_string(),
+                    message: announcement_message(&mode),
+                    category: Some(AnnouncementCategory::Maintenance),
+                    audience: AnnouncementAudience::All,
+                    publish_at: Some(now),
+                    expires_at: mode.ends_at,
+                },
+                now,
+            )?;
+            notifications = self
+                .announcements
+                .publish(&announcement.announcement_id, now)?;
+            mode.announcement_id = Some(announcement.announcement_id);
+        }
+
+        self.storage.store_maintenance_mode(&mode)?;
+        Ok((mode, notifications))
+    }
+}
+
+fn announcement_message(mode: &MaintenanceMode) -> String {
+    let format = |at: DateTime<Utc>| at.format("%Y-%m-%d %H:%M UTC").to_string();
+    let window = match (mode.starts_at, mode.ends_at) {
+        (Some(starts_at), Some(ends_at)) => {
+            format!("from {} until {}", format(starts_at), format(ends_at))
+        }
+        (Some(starts_at), None) => format!("from {}", format(starts_at)),
+        (None, Some(ends_at)) => format!("now until {}", format(ends_at)),
+        (None, None) => "now".to_string(),
+    };
+    format!(
+        "{} The platform is read-only {window}; reads and verification remain available.",
+        mode.message
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{AccountStatus, TierLimits, UserAccount, UserTier};
+    use chrono::Duration;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_scheduled_window_freezes_writes_and_announces() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let tier = UserTier::Basic;
+        storage
+            .store_user_account(&UserAccount {
+                user_id: "alice".to_string(),
+                username: "alice".to_string(),
+                email: "alice@example.com".to_string(),
+                password_hash: "hash".to_string(),
+                limits: TierLimits::for_tier(&tier),
+                tier,
+                status: AccountStatus::Active,
+                credits: 0,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                last_login: None,
+                subscription: None,
+                is_admin: false,
+                workspace_id: None,
+                available_adapters: None,
+                locale: None,
+            })
+            .unwrap();
+        let engine = MaintenanceEngine::new(Arc::clone(&storage));
+        let now = Utc::now();
+        assert!(engine.active_window(now).unwrap().is_none());
+
+        let input = MaintenanceInput {
+            enabled: true,
+            message: None,
+            starts_at: Some(now + Duration::hours(1)),
+            ends_at: Some(now + Duration::hours(3)),
+            announce: None,
+        };
+        let (mode, notifications) = engine.update("admin", input.clone(), now).unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].user_id, "alice");
+        assert!(engine.active_window(now).unwrap().is_none());
+        assert!(engine
+            .active_window(now + Duration::hours(2))
+            .unwrap()
+            .is_some());
+        assert!(engine
+            .active_window(now + Duration::hours(3))
+            .unwrap()
+            .is_none());
+
+        let backwards = MaintenanceInput {
+            ends_at: input.starts_at,
+            starts_at: input.ends_at,
+            ..input
+        };
+        assert!(matches!(
+            engine.update("admin", backwards, now),
+            Err(MaintenanceError::ValidationError(_))
+        ));
+
+        let lifted = MaintenanceInput {
+            enabled: false,
+            message: None,
+            starts_at: None,
+            ends_at: None,
+            announce: None,
+        };
+        let (off, notifications) = engine.update("admin", lifted, now).unwrap();
+        assert!(notifications.is_empty());
+        assert!(!off.is_active(now + Duration::hours(2)));
+        let announcements = AnnouncementEngine::new(Arc::clone(&storage));
+        assert!(mode.announcement_id.is_some());
+        assert!(announcements
+            .list_for_user("alice", false, now)
+            .unwrap()
+            .is_empty());
+
+        assert!(is_exempt(&Method::GET, "/api/items"));
+        assert!(is_exempt(&Method::PUT, "/api/admin/maintenance"));
+        assert!(is_exempt(&Method::POST, "/api/receipts/abc/verify"));
+        assert!(!is_exempt(&Method::POST, "/api/items"));
+        assert!(!is_exempt(&Method::POST, "/api/administrators"));
+    }
+
+    #[test]
+    fn test_only_listed_writes_pass_during_maintenance() {
+        assert!(is_exempt(&Method::PUT, "/api/admin/maintenance/"));
+        assert!(is_exempt(&Method::POST, "/api/auth/login"));
+        assert!(is_exempt(&Method::POST, "/api/receipts/abc/verify/stream"));
+
+        assert!(!is_exempt(&Method::POST, "/api/auth/register"));
+        assert!(!is_exempt(&Method::POST, "/api/auth/forgot-password"));
+        assert!(!is_exempt(&Method::POST, "/api/auth/reset-password"));
+        assert!(!is_exempt(&Method::PUT, "/api/auth/profile"));
+        assert!(!is_exempt(&Method::POST, "/api/admin/users"));
+        // Verifying a ZK proof records the verification
+        assert!(!is_exempt(&Method::POST, "/api/proofs/verify"));
+        assert!(!is_exempt(&Method::POST, "/api/receipts/verify"));
+    }
+}
@@ -0,0 +1,479 @@
+//! Consistency between circuit membership and the event log.
+//!
+//! A push stores a circuit item and records a `PushedToCircuit` event; pulls
+//! and expiry remove the item and record `PulledFromCircuit` or
+//! `RemovedFromCircuit`. A push that fails half way leaves one without the
+//! other. The reconciler replays each item's circuit events, ignoring those of
+//! operations still pending or rejected, and compares the membership they
+//! describe with the stored circuit items.
+//!
+//! What happens to a mismatch depends on the policy: `report_only` only lists
+//! it, `trust_events` makes circuit items follow the event log, and
+//! `trust_membership` records the events missing from the log. Every run is
+//! kept as a report. The scheduled run uses `MEMBERSHIP_RECONCILIATION_POLICY`;
+//! admins can run it on demand, or as a dry run.
+
+use crate::events_engine::EventsEngine;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    CircuitItem, Event, EventCausality, EventType, EventVisibility, MembershipDiscrepancy,
+    MembershipDiscrepancyKind, MembershipReconciliationReport, MembershipRepairPolicy,
+    OperationStatus,
+};
+use chrono::Utc;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub enum MembershipReconciliationError {
+    StorageError(StorageError),
+    EventError(String),
+}
+
+impl From<StorageError> for MembershipReconciliationError {
+    fn from(err: StorageError) -> Self {
+        MembershipReconciliationError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for MembershipReconciliationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MembershipReconciliationError::StorageError(e) => write!(f, "Storage error: {e}"),
+            MembershipReconciliationError::EventError(e) => write!(f, "Event error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for MembershipReconciliationError {}
+
+impl MembershipRepairPolicy {
+    /// `MEMBERSHIP_RECONCILIATION_POLICY` (`report_only`, `trust_events` or
+    /// `trust_membership`); report only when unset or invalid
+    pub fn from_env() -> Self {
+        match std::env::var("MEMBERSHIP_RECONCILIATION_POLICY").as_deref() {
+            Ok("trust_events") => MembershipRepairPolicy::TrustEvents,
+            Ok("trust_membership") => MembershipRepairPolicy::TrustMembership,
+            _ => MembershipRepairPolicy::ReportOnly,
+        }
+    }
+}
+
+/// Circuit the event moved the item in or out of
+fn event_circuit(event: &Event) -> Option<Uuid> {
+    event
+        .metadata
+        .get("circuit_id")
+        .and_then(|id| id.as_str())
+        .and_then(|id| Uuid::parse_str(id).ok())
+}
+
+/// What the stored state and the event log say about memberships
+pub struct MembershipComparison {
+    pub discrepancies: Vec<MembershipDiscrepancy>,
+    pub circuits_scanned: usize,
+    pub memberships_scanned: usize,
+    pub events_scanned: usize,
+}
+
+pub struct MembershipReconciliationEngine<S: StorageBackend> {
+    storage: S,
+    events: EventsEngine<S>,
+}
+
+impl<S: StorageBackend + Clone + 'static> MembershipReconciliationEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            events: EventsEngine::new(storage.clone()),
+            storage,
+        }
+    }
+
+    pub fn compare(&self) -> Result<MembershipComparison, MembershipReconciliationError> {
+        let circuits = self.storage.list_circuits()?;
+        let mut members: HashMap<(Uuid, String), CircuitItem> = HashMap::new();
+        for circuit in &circuits {
+            for item in self.storage.get_circuit_items(&circuit.circuit_id)? {
+                members.insert((item.circuit_id, item.dfid.clone()), item);
+            }
+        }
+
+        let mut events: Vec<Event> = self
+            .storage
+            .list_events()?
+            .into_iter()
+            .filter(|event| {
+                matches!(
+                    event.event_type,
+                    EventType::PushedToCircuit
+                        | EventType::PulledFromCircuit
+                        | EventType::RemovedFromCircuit
+                )
+            })
+            .collect();
+        let events_scanned = events.len();
+        events.sort_by_key(|event| event.timestamp);
+
+        // The latest event that took effect, per circuit and item
+        let mut operation_status: HashMap<Uuid, Option<OperationStatus>> = HashMap::new();
+        let mut latest: HashMap<(Uuid, String), Event> = HashMap::new();
+        for event in events {
+            let Some(circuit_id) = event_circuit(&event) else {
+                continue;
+            };
+            if let Some(operation_id) = event.correlation_id {
+                let status = match operation_status.entry(operation_id) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(
+                        self.storage
+                            .get_circuit_operation(&operation_id)?
+                            .map(|operation| operation.status),
+                    ),
+                };
+                if matches!(
+                    status,
+                    Some(
+                        OperationStatus::Pending
+                            | OperationStatus::Rejected
+                            | OperationStatus::Failed
+                    )
+                ) {
+                    continue;
+                }
+            }
+            latest.insert((circuit_id, event.dfid.clone()), event);
+        }
+
+        let mut discrepancies = Vec::new();
+        let mut seen = HashSet::new();
+        for ((circuit_id, dfid), event) in &latest {
+            seen.insert((*circuit_id, dfid.clone()));
+            let member = members.contains_key(&(*circuit_id, dfid.clone()));
+            let pushed = event.event_type == EventType::PushedToCircuit;
+            if pushed && !member {
+                discrepancies.push(MembershipDiscrepancy {
+                    circuit_id: *circuit_id,
+                    dfid: dfid.clone(),
+                    kind: MembershipDiscrepancyKind::PushEventWithoutMember,
+                    event_id: Some(event.event_id),
+                    repaired: false,
+                });
+            } else if !pushed && member {
+                discrepancies.push(MembershipDiscrepancy {
+                    circuit_id: *circuit_id,
+                    dfid: dfid.clone(),
+                    kind: MembershipDiscrepancyKind::MemberWithoutPushEvent,
+                    event_id: Some(event.event_id),
+                    repaired: false,
+                });
+            }
+        }
+        for (circuit_id, dfid) in members.keys() {
+            if !seen.contains(&(*circuit_id, dfid.clone())) {
+                discrepancies.push(MembershipDiscrepancy {
+                    circuit_id: *circuit_id,
+                    dfid: dfid.clone(),
+                    kind: MembershipDiscrepancyKind::MemberWithoutPushEvent,
+                    event_id: None,
+                    repaired: false,
+                });
+            }
+        }
+        discrepancies.sort_by(|a, b| (a.circuit_id, &a.dfid).cmp(&(b.circuit_id, &b.dfid)));
+
+        Ok(MembershipComparison {
+            discrepancies,
+            circuits_scanned: circuits.len(),
+            memberships_scanned: members.len(),
+            events_scanned,
+        })
+    }
+
+    /// Find mismatches and repair them under `policy`; a dry run changes
+    /// nothing and is not recorded
+    pub fn run(
+        &mut self,
+        policy: MembershipRepairPolicy,
+        dry_run: bool,
+        triggered_by: Option<String>,
+    ) -> Result<MembershipReconciliationReport, MembershipReconciliationError> {
+        let started_at = Utc::now();
+        let mut comparison = self.compare()?;
+
+        if !dry_run && policy != MembershipRepairPolicy::ReportOnly {
+            for discrepancy in &mut comparison.discrepancies {
+                self.repair(policy, discrepancy)?;
+                discrepancy.repaired = true;
+            }
+        }
+
+        let report = MembershipReconciliationReport {
+            run_id: Uuid::new_v4(),
+            started_at,
+            completed_at: Utc::now(),
+            triggered_by,
+            policy,
+            dry_run,
+            circuits_scanned: comparison.circuits_scanned,
+            memberships_scanned: comparison.memberships_scanned,
+            events_scanned: comparison.events_scanned,
+            repaired: comparison
+                .discrepancies
+                .iter()
+                .filter(|d| d.repaired)
+                .count(),
+            discrepancies: comparison.discrepancies,
+        };
+        if !dry_run {
+            self.storage
+                .store_membership_reconciliation_report(&report)?;
+        }
+        Ok(report)
+    }
+
+    /// Reports of past runs, newest first
+    pub fn list_reports(
+        &self,
+    ) -> Result<Vec<MembershipReconciliationReport>, MembershipReconciliationError> {
+        let mut reports = self.storage.list_membership_reconciliation_reports()?;
+        reports.sort_by_key(|report| std::cmp::Reverse(report.started_at));
+        Ok(reports)
+    }
+
+    fn repair(
+        &mut self,
+        policy: MembershipRepairPolicy,
+        discrepancy: &MembershipDiscrepancy,
+    ) -> Result<(), MembershipReconciliationError> {
+        let event = discrepancy
+            .event_id
+            .map(|event_id| self.storage.get_event(&event_id))
+            .transpose()?
+            .flatten();
+        match (policy, discrepancy.kind) {
+            (MembershipRepairPolicy::ReportOnly, _) => {}
+            (
+                MembershipRepairPolicy::TrustEvents,
+                MembershipDiscrepancyKind::PushEventWithoutMember,
+            ) => {
+                let pushed_by = event
+                    .as_ref()
+                    .and_then(|event| event.metadata.get("requester_id"))
+                    .and_then(|id| id.as_str())
+                    .map(str::to_string)
+                    .or_else(|| event.as_ref().map(|event| event.source.clone()))
+                    .unwrap_or_else(|| "system".to_string());
+                let mut item = CircuitItem::new(
+                    discrepancy.dfid.clone(),
+                    discrepancy.circuit_id,
+                    pushed_by,
+                    vec!["read".to_string(), "verify".to_string()],
+                );
+                if let Some(event) = &event {
+                    item.pushed_at = event.timestamp;
+                }
+                self.storage.store_circuit_item(&item)?;
+            }
+            (
+                MembershipRepairPolicy::TrustEvents,
+                MembershipDiscrepancyKind::MemberWithoutPushEvent,
+            ) => {
+                self.storage
+                    .remove_circuit_item(&discrepancy.circuit_id, &discrepancy.dfid)?;
+            }
+            (
+                MembershipRepairPolicy::TrustMembership,
+                MembershipDiscrepancyKind::PushEventWithoutMember,
+            ) => {
+                self.record_event(discrepancy, EventType::RemovedFromCircuit, None)?;
+            }
+            (
+                MembershipRepairPolicy::TrustMembership,
+                MembershipDiscrepancyKind::MemberWithoutPushEvent,
+            ) => {
+                let pushed_by = self
+                    .storage
+                    .get_circuit_items(&discrepancy.circuit_id)?
+                    .into_iter()
+                    .find(|item| item.dfid == discrepancy.dfid)
+                    .map(|item| item.pushed_by);
+                self.record_event(discrepancy, EventType::PushedToCircuit, pushed_by)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Record the membership change the log is missing, marked as a repair
+    fn record_event(
+        &mut self,
+        discrepancy: &MembershipDiscrepancy,
+        event_type: EventType,
+        requester_id: Option<String>,
+    ) -> Result<(), MembershipReconciliationError> {
+        let public = self
+            .storage
+            .get_circuit(&discrepancy.circuit_id)?
+            .is_some_and(|circuit| circuit.permissions.allow_public_visibility);
+        let visibility = if public {
+            EventVisibility::Public
+        } else {
+            EventVisibility::CircuitOnly
+        };
+        let mut metadata = HashMap::from([
+            (
+                "circuit_id".to_string(),
+                serde_json::json!(discrepancy.circuit_id.to_string()),
+            ),
+            ("reason".to_string(), serde_json::json!("reconciliation")),
+        ]);
+        if let Some(requester_id) = requester_id {
+            metadata.insert("requester_id".to_string(), serde_json::json!(requester_id));
+        }
+        self.events
+            .create_linked_event(
+                discrepancy.dfid.clone(),
+                event_type,
+                "system".to_string(),
+                visibility,
+                metadata,
+                EventCausality {
+                    caused_by: discrepancy.event_id,
+                    correlation_id: None,
+                },
+            )
+            .map_err(|e| MembershipReconciliationError::EventError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+impl<S: StorageBackend + Clone + Send + Sync + 'static> MembershipReconciliationEngine<S> {
+    /// Reconcile under the environment's policy every `tick`
+    pub fn spawn_reconciler(storage: S, tick: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut engine = MembershipReconciliationEngine::new(storage);
+            let policy = MembershipRepairPolicy::from_env();
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                match engine.run(policy, false, None) {
+                    Ok(report) if !report.discrepancies.is_empty() => tracing::warn!(
+                        "🔍 Membership reconciliation found {} discrepancies, repaired {}",
+                        report.discrepancies.len(),
+                        report.repaired
+                    ),
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("⚠️  Membership reconciliation failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{Circuit, CircuitOperation, OperationType};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_reconciler_reports_and_repairs_from_event_log() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let circuit = Circuit::new(
+            "Grain".to_string(),
+            "Export lots".to_string(),
+            "owner".to_string(),
+        );
+        let circuit_id = circuit.circuit_id;
+        storage.store_circuit(&circuit).unwrap();
+        let mut events = EventsEngine::new(Arc::clone(&storage));
+        let push = |events: &mut EventsEngine<_>, dfid: &str, causality: EventCausality| {
+            events
+                .create_circuit_operation_event(
+                    dfid.to_string(),
+                    circuit_id.to_string(),
+                    "push".to_string(),
+                    "grower".to_string(),
+                    EventVisibility::CircuitOnly,
+                    causality,
+                )
+                .unwrap()
+        };
+
+        // Consistent: pushed and a member
+        push(&mut events, "DFID-OK", EventCausality::default());
+        storage
+            .store_circuit_item(&CircuitItem::new(
+                "DFID-OK".to_string(),
+                circuit_id,
+                "grower".to_string(),
+                vec!["read".to_string()],
+            ))
+            .unwrap();
+        // The push event was recorded but storing the circuit item failed
+        push(&mut events, "DFID-LOST", EventCausality::default());
+        // A member whose push event never made it to the log
+        storage
+            .store_circuit_item(&CircuitItem::new(
+                "DFID-SILENT".to_string(),
+                circuit_id,
+                "grower".to_string(),
+                vec!["read".to_string()],
+            ))
+            .unwrap();
+        // Waiting for approval: no membership expected yet
+        let pending = CircuitOperation::new(
+            circuit_id,
+            "DFID-PENDING".to_string(),
+            OperationType::Push,
+            "grower".to_string(),
+        );
+        storage.store_circuit_operation(&pending).unwrap();
+        push(
+            &mut events,
+            "DFID-PENDING",
+            EventCausality::root(pending.operation_id),
+        );
+
+        let mut engine = MembershipReconciliationEngine::new(Arc::clone(&storage));
+        let report = engine
+            .run(MembershipRepairPolicy::TrustEvents, true, None)
+            .unwrap();
+        let found: Vec<(&str, MembershipDiscrepancyKind)> = report
+            .discrepancies
+            .iter()
+            .map(|d| (d.dfid.as_str(), d.kind))
+            .collect();
+        assert_eq!(found.len(), 2);
+        assert!(found.contains(&(
+            "DFID-LOST",
+            MembershipDiscrepancyKind::PushEventWithoutMember
+        )));
+        assert!(found.contains(&(
+            "DFID-SILENT",
+            MembershipDiscrepancyKind::MemberWithoutPushEvent
+        )));
+        assert_eq!(report.repaired, 0);
+        assert!(engine.list_reports().unwrap().is_empty());
+
+        let report = engine
+            .run(
+                MembershipRepairPolicy::TrustEvents,
+                false,
+                Some("admin".to_string()),
+            )
+            .unwrap();
+        assert_eq!(report.repaired, 2);
+        let members: Vec<String> = storage
+            .get_circuit_items(&circuit_id)
+            .unwrap()
+            .into_iter()
+            .map(|item| item.dfid)
+            .collect();
+        assert!(members.contains(&"DFID-LOST".to_string()));
+        assert!(!members.contains(&"DFID-SILENT".to_string()));
+        assert!(engine.compare().unwrap().discrepancies.is_empty());
+        assert_eq!(engine.list_reports().unwrap().len(), 1);
+    }
+}
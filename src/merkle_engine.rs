@@ -61,7 +61,8 @@ pub struct CircuitMerkleRootResponse {
 
 /// Compute event hash for Merkle leaf node
 ///
-/// Hash formula: BLAKE3(event_id|event_type|timestamp_nanos|source|metadata_json)
+/// Hash formula: H(event_id|event_type|timestamp_nanos|source|metadata_json), with H
+/// the algorithm the event was created with so its leaf never changes
 pub fn hash_event(event: &Event) -> String {
     let data = format!(
         "{}|{:?}|{}|{}|{}",
@@ -71,7 +72,7 @@ pub fn hash_event(event: &Event) -> String {
         event.source,
         serde_json::to_string(&event.metadata).unwrap_or_default()
     );
-    blake3::hash(data.as_bytes()).to_hex().to_string()
+    event.hash_algorithm().hash(data.as_bytes())
 }
 
 impl<S: StorageBackend + Clone + 'static> MerkleEngine<S> {
@@ -370,6 +371,8 @@ mod tests {
             source_entries: vec![],
             confidence_score: 1.0,
             status: ItemStatus::Active,
+            first_occurred_at: None,
+            last_occurred_at: None,
         }
     }
 
@@ -19,6 +19,7 @@
 //!   Ev1   Ev2   Ev3  Ev1   Ev2   Ev3       Ev1
 //! ```
 
+use crate::hashing::{self, HashAlgorithm};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -243,7 +244,7 @@ impl MerkleTree {
                 } else {
                     // Odd node - pair with empty node
                     let left = chunk[0].clone();
-                    let empty_hash = Self::empty_hash();
+                    let empty_hash = Self::empty_hash_for(&left.hash);
                     let combined_hash = Self::hash_pair(&left.hash, &empty_hash);
 
                     let empty_node = MerkleNode {
@@ -270,7 +271,7 @@ impl MerkleTree {
         // Handle single leaf case: wrap it in a parent node with empty sibling
         let root = if nodes.len() == 1 && nodes[0].node_type == MerkleNodeType::Leaf {
             let leaf = nodes.into_iter().next().unwrap();
-            let empty_hash = Self::empty_hash();
+            let empty_hash = Self::empty_hash_for(&leaf.hash);
             let combined_hash = Self::hash_pair(&leaf.hash, &empty_hash);
             let empty_node = MerkleNode {
                 hash: empty_hash,
@@ -387,20 +388,26 @@ impl MerkleTree {
         }
     }
 
-    /// Hash two values together using BLAKE3
+    /// Hash two values together with the algorithm of the left one, so a tree
+    /// (and every proof from it) stays on the algorithm of its leaves
     pub fn hash_pair(left: &str, right: &str) -> String {
         let combined = format!("{}|{}", left, right);
-        blake3::hash(combined.as_bytes()).to_hex().to_string()
+        hashing::algorithm_of(left).hash(combined.as_bytes())
     }
 
-    /// Generate a hash for a single value using BLAKE3
+    /// Generate a hash for a single value with the default algorithm
     pub fn hash_single(data: &str) -> String {
-        blake3::hash(data.as_bytes()).to_hex().to_string()
+        hashing::hash(data.as_bytes())
     }
 
-    /// Generate an empty hash (for padding unbalanced trees)
+    /// Generate an empty BLAKE3 hash (for padding unbalanced trees)
     pub fn empty_hash() -> String {
-        blake3::hash(b"EMPTY_NODE").to_hex().to_string()
+        HashAlgorithm::Blake3.hash(b"EMPTY_NODE")
+    }
+
+    /// Empty hash for padding next to `sibling`, in its algorithm
+    pub fn empty_hash_for(sibling: &str) -> String {
+        hashing::algorithm_of(sibling).hash(b"EMPTY_NODE")
     }
 
     /// Collect proof siblings using flattened index computation
@@ -437,7 +444,7 @@ impl MerkleTree {
             } else {
                 // Sibling doesn't exist (unbalanced tree), use empty hash
                 siblings.push(ProofSibling {
-                    hash: Self::empty_hash(),
+                    hash: Self::empty_hash_for(&level[idx]),
                     position: SiblingPosition::Right,
                 });
             }
@@ -458,7 +465,6 @@ impl MerkleTree {
 
         // Build each subsequent level
         let mut current_level = self.leaves.clone();
-        let empty = Self::empty_hash();
 
         while current_level.len() > 1 {
             let mut next_level = Vec::new();
@@ -466,12 +472,12 @@ impl MerkleTree {
             for i in (0..current_level.len()).step_by(2) {
                 let left = &current_level[i];
                 let right = if i + 1 < current_level.len() {
-                    &current_level[i + 1]
+                    current_level[i + 1].clone()
                 } else {
                     // Pad with empty hash for odd number of nodes
-                    &empty
+                    Self::empty_hash_for(left)
                 };
-                next_level.push(Self::hash_pair(left, right));
+                next_level.push(Self::hash_pair(left, &right));
             }
 
             levels.push(next_level.clone());
@@ -721,4 +727,20 @@ mod tests {
         let hash2 = MerkleTree::hash_pair("b", "a");
         assert_ne!(hash1, hash2);
     }
+
+    #[test]
+    fn test_tree_keeps_the_algorithm_of_its_leaves() {
+        let leaves: Vec<String> = ["a", "b", "c"]
+            .iter()
+            .map(|d| HashAlgorithm::Sha256.hash(d.as_bytes()))
+            .collect();
+        let tree = MerkleTree::from_leaves(leaves);
+        let root = tree.root().unwrap();
+        assert_eq!(hashing::algorithm_of(root), HashAlgorithm::Sha256);
+
+        for index in 0..3 {
+            let proof = tree.generate_proof(index).unwrap();
+            assert!(MerkleTree::verify_proof(&proof, root));
+        }
+    }
 }
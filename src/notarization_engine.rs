@@ -0,0 +1,478 @@
+//! Proof of existence for arbitrary documents.
+//!
+//! A notarization keeps only the document's hash, with a receipt for it
+//! and, when the document belongs to an item, a `DocumentAnchored` event on that
+//! item. Pending notarizations are anchored in batches: their hashes become the
+//! leaves of a Merkle tree whose root is written through the default adapter,
+//! and each notarization keeps its inclusion proof. Verifying a document later
+//! needs only the document (or its hash).
+
+use crate::adapter_manager::AdapterManager;
+use crate::adapters::base::StorageLocation;
+use crate::anchoring_cost_engine::record_anchoring;
+use crate::events_engine::{EventsEngine, EventsError};
+use crate::hashing::{self, HashAlgorithm};
+use crate::logging::LoggingEngine;
+use crate::merkle_tree::MerkleTree;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    AnchoringNetwork, DocumentNotarization, Event, EventType, EventVisibility, Identifier,
+    IngestionPriority, NotarizationBatch, NotarizationStatus, Receipt,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Notarizations anchored under one root at most; the rest wait for the next batch
+pub const MAX_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug)]
+pub enum NotarizationError {
+    StorageError(StorageError),
+    ValidationError(String),
+    NotFound(String),
+    EventsError(EventsError),
+    AnchoringError(String),
+}
+
+impl From<StorageError> for NotarizationError {
+    fn from(err: StorageError) -> Self {
+        NotarizationError::StorageError(err)
+    }
+}
+
+impl From<EventsError> for NotarizationError {
+    fn from(err: EventsError) -> Self {
+        NotarizationError::EventsError(err)
+    }
+}
+
+impl std::fmt::Display for NotarizationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotarizationError::StorageError(e) => write!(f, "Storage error: {e}"),
+            NotarizationError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            NotarizationError::NotFound(e) => write!(f, "Not found: {e}"),
+            NotarizationError::EventsError(e) => write!(f, "Event error: {e}"),
+            NotarizationError::AnchoringError(e) => write!(f, "Anchoring error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for NotarizationError {}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NotarizeInput {
+    pub file_name: Option<String>,
+    pub content_type: Option<String>,
+    /// Item to record the DocumentAnchored event on
+    pub dfid: Option<String>,
+}
+
+/// A notarization matching a verified document, with the batch anchoring it
+#[derive(Debug, Clone, Serialize)]
+pub struct NotarizationMatch {
+    pub notarization: DocumentNotarization,
+    pub batch: Option<NotarizationBatch>,
+    /// The stored proof leads from the document hash to the batch's root
+    pub proof_valid: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotarizationVerification {
+    pub document_hash: String,
+    /// True once at least one matching notarization is anchored with a valid proof
+    pub anchored: bool,
+    pub matches: Vec<NotarizationMatch>,
+}
+
+pub struct NotarizationEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend + 'static> NotarizationEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Hash `content`, record its receipt and, for an item, the DocumentAnchored
+    /// event. The notarization is pending until the next anchoring batch.
+    pub fn notarize(
+        &self,
+        events: &mut EventsEngine<S>,
+        content: &[u8],
+        input: NotarizeInput,
+        submitted_by: &str,
+        now: DateTime<Utc>,
+    ) -> Result<DocumentNotarization, NotarizationError> {
+        if content.is_empty() {
+            return Err(NotarizationError::ValidationError(
+                "Document is empty".to_string(),
+            ));
+        }
+        if let Some(dfid) = &input.dfid {
+            if self.storage.get_item_by_dfid(dfid)?.is_none() {
+                return Err(NotarizationError::NotFound(format!("Item {dfid}")));
+            }
+        }
+
+        let notarization_id = Uuid::new_v4();
+        let document_hash = hash_document(content);
+
+        // Stored directly rather than through the receipt engine, which would
+        // also queue the document for verification into an item
+        let mut identifiers = vec![Identifier::new(
+            "notarization_id",
+            notarization_id.to_string(),
+        )];
+        if let Some(dfid) = &input.dfid {
+            identifiers.push(Identifier::new("dfid", dfid.clone()));
+        }
+        let receipt = Receipt {
+            id: Uuid::new_v4(),
+            hash: document_hash.clone(),
+            timestamp: now,
+            data_size: content.len(),
+            identifiers,
+            priority: IngestionPriority::Realtime,
+            occurred_at: None,
+        };
+        self.storage.store_receipt(&receipt)?;
+
+        let event_id = match &input.dfid {
+            Some(dfid) => {
+                let mut metadata = HashMap::new();
+                metadata.insert(
+                    "notarization_id".to_string(),
+                    notarization_id.to_string().into(),
+                );
+                metadata.insert("document_hash".to_string(), document_hash.clone().into());
+                if let Some(file_name) = &input.file_name {
+                    metadata.insert("file_name".to_string(), file_name.clone().into());
+                }
+                let result = events.create_event_with_metadata(
+                    dfid.clone(),
+                    EventType::DocumentAnchored,
+                    submitted_by.to_string(),
+                    EventVisibility::Private,
+                    metadata,
+                )?;
+                Some(result.event.event_id)
+            }
+            None => None,
+        };
+
+        let notarization = DocumentNotarization {
+            notarization_id,
+            document_hash,
+            file_name: input.file_name,
+            content_type: input.content_type,
+            size: content.len(),
+            dfid: input.dfid,
+            event_id,
+            receipt_id: receipt.id,
+            status: NotarizationStatus::Pending,
+            batch_id: None,
+            proof: None,
+            submitted_by: submitted_by.to_string(),
+            submitted_at: now,
+            anchored_at: None,
+        };
+        self.storage.store_notarization(&notarization)?;
+        Ok(notarization)
+    }
+
+    pub fn get_notarization(
+        &self,
+        notarization_id: &Uuid,
+    ) -> Result<DocumentNotarization, NotarizationError> {
+        self.storage
+            .get_notarization(notarization_id)?
+            .ok_or_else(|| NotarizationError::NotFound(format!("Notarization {notarization_id}")))
+    }
+
+    /// Look up every notarization of a document by its hash
+    pub fn verify_hash(
+        &self,
+        document_hash: &str,
+    ) -> Result<NotarizationVerification, NotarizationError> {
+        let document_hash = document_hash.to_lowercase();
+        let digest = hashing::digest_hex(&document_hash);
+        if digest.len() != 64 || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(NotarizationError::ValidationError(
+                "Document hash must be 64 hex characters, with an algorithm prefix unless BLAKE3"
+                    .to_string(),
+            ));
+        }
+
+        let mut matches = Vec::new();
+        for notarization in self.storage.list_notarizations()? {
+            if notarization.document_hash != document_hash {
+                continue;
+            }
+            let batch = match notarization.batch_id {
+                Some(batch_id) => self.storage.get_notarization_batch(&batch_id)?,
+                None => None,
+            };
+            let proof_valid = match (&notarization.proof, &batch) {
+                (Some(proof), Some(batch)) => {
+                    proof.leaf_hash == document_hash
+                        && MerkleTree::verify_proof(proof, &batch.merkle_root)
+                }
+                _ => false,
+            };
+            matches.push(NotarizationMatch {
+                notarization,
+                batch,
+                proof_valid,
+            });
+        }
+        Ok(NotarizationVerification {
+            anchored: matches.iter().any(|m| m.proof_valid),
+            document_hash,
+            matches,
+        })
+    }
+
+    pub fn verify_document(
+        &self,
+        content: &[u8],
+    ) -> Result<NotarizationVerification, NotarizationError> {
+        // Documents notarized before a change of algorithm are found under the old one
+        let mut fallback = None;
+        for algorithm in HashAlgorithm::ALL {
+            let verification = self.verify_hash(&algorithm.hash(content))?;
+            if !verification.matches.is_empty() {
+                return Ok(verification);
+            }
+            if algorithm == hashing::default_algorithm() {
+                fallback = Some(verification);
+            }
+        }
+        Ok(fallback.expect("the default algorithm is one of ALL"))
+    }
+
+    /// Oldest pending notarizations, up to one batch
+    pub fn pending_notarizations(&self) -> Result<Vec<DocumentNotarization>, NotarizationError> {
+        Ok(self
+            .storage
+            .list_notarizations()?
+            .into_iter()
+            .filter(|n| n.status == NotarizationStatus::Pending)
+            .take(MAX_BATCH_SIZE)
+            .collect())
+    }
+
+    /// Record `batch` as anchored and give each of its notarizations its proof
+    pub fn complete_batch(
+        &self,
+        batch: &NotarizationBatch,
+        tree: &MerkleTree,
+        pending: Vec<DocumentNotarization>,
+    ) -> Result<(), NotarizationError> {
+        self.storage.store_notarization_batch(batch)?;
+        for mut notarization in pending {
+            let proof = tree
+                .generate_proof_by_hash(&notarization.document_hash)
+                .map_err(|e| NotarizationError::AnchoringError(e.to_string()))?;
+            notarization.status = NotarizationStatus::Anchored;
+            notarization.batch_id = Some(batch.batch_id);
+            notarization.proof = Some(proof);
+            notarization.anchored_at = Some(batch.created_at);
+            self.storage.store_notarization(&notarization)?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: StorageBackend + Clone + Send + Sync + 'static> NotarizationEngine<S> {
+    /// Anchor pending notarizations under one Merkle root. The root is written
+    /// through the default adapter when one is configured; if writing fails the
+    /// notarizations stay pending for the next run.
+    pub async fn anchor_pending(&self) -> Result<Option<NotarizationBatch>, NotarizationError> {
+        let pending = self.pending_notarizations()?;
+        if pending.is_empty() {
+            return Ok(None);
+        }
+        let tree = MerkleTree::from_leaves_with_ids(
+            pending
+                .iter()
+                .map(|n| (n.document_hash.clone(), Some(n.notarization_id.to_string())))
+                .collect(),
+        );
+        let merkle_root = tree
+            .root()
+            .ok_or_else(|| NotarizationError::AnchoringError("Empty Merkle tree".to_string()))?
+            .to_string();
+
+        let mut batch = NotarizationBatch {
+            batch_id: Uuid::new_v4(),
+            merkle_root,
+            notarization_ids: pending.iter().map(|n| n.notarization_id).collect(),
+            locations: Vec::new(),
+            created_at: Utc::now(),
+        };
+        batch.locations = self.write_root_to_adapter(&batch).await?;
+        self.complete_batch(&batch, &tree, pending)?;
+        Ok(Some(batch))
+    }
+
+    /// The root goes out as an event of a pseudo-item named after the batch, so
+    /// any adapter that can anchor events can anchor it
+    async fn write_root_to_adapter(
+        &self,
+        batch: &NotarizationBatch,
+    ) -> Result<Vec<StorageLocation>, NotarizationError> {
+        let Some(config) = self.storage.get_default_adapter_config()? else {
+            return Ok(Vec::new());
+        };
+        let batch_item_id = format!("notarization-batch-{}", batch.batch_id);
+        let mut metadata = HashMap::new();
+        metadata.insert("batch_id".to_string(), batch.batch_id.to_string().into());
+        metadata.insert("merkle_root".to_string(), batch.merkle_root.clone().into());
+        metadata.insert(
+            "notarization_count".to_string(),
+            batch.notarization_ids.len().into(),
+        );
+        let event = Event::new_with_metadata(
+            batch_item_id.clone(),
+            EventType::DocumentAnchored,
+            "notarization_engine".to_string(),
+            EventVisibility::Public,
+            metadata,
+        );
+
+        let logger = Arc::new(Mutex::new(LoggingEngine::new()));
+        let manager = AdapterManager::new(self.storage.clone(), logger);
+        let adapter = manager
+            .create_adapter_instance_for_config(&config)
+            .map_err(|e| NotarizationError::AnchoringError(e.to_string()))?;
+        let result = manager
+            .store_event_with_retry(&adapter, &event, &batch_item_id)
+            .await
+            .map_err(|e| NotarizationError::AnchoringError(e.to_string()))?;
+
+        if let Some(network) = AnchoringNetwork::for_adapter(&config.adapter_type) {
+            let transactions = std::iter::once(&result.metadata.item_location)
+                .chain(&result.metadata.event_locations)
+                .filter(|location| matches!(location, StorageLocation::Stellar { .. }))
+                .count() as u32;
+            if transactions > 0 {
+                if let Err(e) = record_anchoring(
+                    &self.storage,
+                    None,
+                    network,
+                    transactions,
+                    batch.notarization_ids.len() as u64,
+                    Utc::now(),
+                ) {
+                    tracing::warn!("Failed to record notarization anchoring cost: {}", e);
+                }
+            }
+        }
+        Ok(result.metadata.event_locations)
+    }
+
+    /// Anchor pending notarizations every `tick`
+    pub fn spawn_anchorer(storage: S, tick: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let engine = NotarizationEngine::new(storage);
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                match engine.anchor_pending().await {
+                    Ok(Some(batch)) => tracing::info!(
+                        "📜 Anchored {} notarization(s) under root {}",
+                        batch.notarization_ids.len(),
+                        batch.merkle_root
+                    ),
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!("⚠️  Notarization anchoring failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+/// Documents are identified by their hash under the default algorithm, like receipts
+pub fn hash_document(content: &[u8]) -> String {
+    hashing::hash(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::Item;
+
+    #[tokio::test]
+    async fn test_notarized_document_verifies_after_anchoring() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let item = Item::new("DFID-NOTARY-1".to_string(), vec![], Uuid::new_v4());
+        storage.lock().unwrap().store_item(&item).unwrap();
+        let mut events = EventsEngine::new(Arc::clone(&storage));
+        let engine = NotarizationEngine::new(Arc::clone(&storage));
+
+        let certificate = b"organic certificate 2026";
+        let notarization = engine
+            .notarize(
+                &mut events,
+                certificate,
+                NotarizeInput {
+                    file_name: Some("certificate.pdf".to_string()),
+                    content_type: None,
+                    dfid: Some(item.dfid.clone()),
+                },
+                "user-1",
+                Utc::now(),
+            )
+            .unwrap();
+        engine
+            .notarize(
+                &mut events,
+                b"lab report",
+                NotarizeInput::default(),
+                "user-1",
+                Utc::now(),
+            )
+            .unwrap();
+
+        let item_events = events.get_events_for_item(&item.dfid).unwrap();
+        assert!(item_events.iter().any(|e| {
+            e.event_type == EventType::DocumentAnchored && Some(e.event_id) == notarization.event_id
+        }));
+        assert!(!engine.verify_document(certificate).unwrap().anchored);
+
+        let batch = engine.anchor_pending().await.unwrap().unwrap();
+        assert_eq!(batch.notarization_ids.len(), 2);
+        assert!(engine.anchor_pending().await.unwrap().is_none());
+
+        let verification = engine.verify_document(certificate).unwrap();
+        assert!(verification.anchored);
+        assert_eq!(verification.matches.len(), 1);
+        assert_eq!(
+            verification.matches[0].notarization.batch_id,
+            Some(batch.batch_id)
+        );
+        assert!(
+            !engine
+                .verify_document(b"forged certificate")
+                .unwrap()
+                .anchored
+        );
+        assert!(engine
+            .notarize(
+                &mut events,
+                certificate,
+                NotarizeInput {
+                    dfid: Some("DFID-MISSING".to_string()),
+                    ..NotarizeInput::default()
+                },
+                "user-1",
+                Utc::now(),
+            )
+            .is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::i18n::Localizer;
+use crate::live_stream::{LiveRecord, LiveStream};
 use crate::storage::StorageBackend;
 use crate::types::{Notification, NotificationType};
 use chrono::{DateTime, Utc};
@@ -24,11 +26,21 @@ impl std::error::Error for NotificationError {}
 
 pub struct NotificationEngine<S: StorageBackend> {
     storage: S,
+    live_stream: Option<LiveStream>,
 }
 
 impl<S: StorageBackend + 'static> NotificationEngine<S> {
     pub fn new(storage: S) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            live_stream: None,
+        }
+    }
+
+    /// Publish stored notifications to `/api/stream` subscribers
+    pub fn with_live_stream(mut self, live_stream: LiveStream) -> Self {
+        self.live_stream = Some(live_stream);
+        self
     }
 
     /// Create a notification for when a user requests to join a circuit
@@ -40,11 +52,16 @@ impl<S: StorageBackend + 'static> NotificationEngine<S> {
         circuit_name: &str,
         message: Option<&str>,
     ) -> Result<Notification, NotificationError> {
+        let locale = self.recipient_locale(admin_user_id);
+        let args = [
+            ("circuit_name", circuit_name),
+            ("requester_id", requester_id),
+        ];
         let notification = Notification::new(
             admin_user_id.to_string(),
             NotificationType::JoinRequestReceived,
-            format!("New join request for {circuit_name}"),
-            format!("User {requester_id} requested to join your circuit"),
+            t(&locale, "notification.join_request_received.title", &args),
+            t(&locale, "notification.join_request_received.message", &args),
             json!({
                 "requester_id": requester_id,
                 "circuit_id": circuit_id,
@@ -67,13 +84,13 @@ impl<S: StorageBackend + 'static> NotificationEngine<S> {
         approved_by: &str,
         assigned_role: &str,
     ) -> Result<Notification, NotificationError> {
+        let locale = self.recipient_locale(requester_id);
+        let args = [("circuit_name", circuit_name), ("role", assigned_role)];
         let notification = Notification::new(
             requester_id.to_string(),
             NotificationType::JoinRequestApproved,
-            format!("Join request approved for {circuit_name}"),
-            format!(
-                "Your request to join {circuit_name} has been approved. You are now a {assigned_role}."
-            ),
+            t(&locale, "notification.join_request_approved.title", &args),
+            t(&locale, "notification.join_request_approved.message", &args),
             json!({
                 "circuit_id": circuit_id,
                 "circuit_name": circuit_name,
@@ -94,16 +111,20 @@ impl<S: StorageBackend + 'static> NotificationEngine<S> {
         circuit_id: &str,
         circuit_name: &str,
         rejected_by: &str,
+        reason: Option<&str>,
     ) -> Result<Notification, NotificationError> {
+        let locale = self.recipient_locale(requester_id);
+        let args = [("circuit_name", circuit_name)];
         let notification = Notification::new(
             requester_id.to_string(),
             NotificationType::JoinRequestRejected,
-            format!("Join request rejected for {circuit_name}"),
-            format!("Your request to join {circuit_name} has been rejected."),
+            t(&locale, "notification.join_request_rejected.title", &args),
+            t(&locale, "notification.join_request_rejected.message", &args),
             json!({
                 "circuit_id": circuit_id,
                 "circuit_name": circuit_name,
                 "rejected_by": rejected_by,
+                "reason": reason,
                 "timestamp": Utc::now().timestamp(),
             }),
         );
@@ -121,11 +142,13 @@ impl<S: StorageBackend + 'static> NotificationEngine<S> {
         invited_by: &str,
         role: &str,
     ) -> Result<Notification, NotificationError> {
+        let locale = self.recipient_locale(invited_user_id);
+        let args = [("circuit_name", circuit_name), ("role", role)];
         let notification = Notification::new(
             invited_user_id.to_string(),
             NotificationType::CircuitInvite,
-            format!("Invited to {circuit_name}"),
-            format!("You have been invited to join {circuit_name} as a {role}."),
+            t(&locale, "notification.circuit_invite.title", &args),
+            t(&locale, "notification.circuit_invite.message", &args),
             json!({
                 "circuit_id": circuit_id,
                 "circuit_name": circuit_name,
@@ -148,11 +171,13 @@ impl<S: StorageBackend + 'static> NotificationEngine<S> {
         circuit_name: &str,
         shared_by: &str,
     ) -> Result<Notification, NotificationError> {
+        let locale = self.recipient_locale(member_user_id);
+        let args = [("circuit_name", circuit_name), ("shared_by", shared_by)];
         let notification = Notification::new(
             member_user_id.to_string(),
             NotificationType::ItemShared,
-            format!("New item in {circuit_name}"),
-            format!("User {shared_by} shared a new item to {circuit_name}."),
+            t(&locale, "notification.item_shared.title", &args),
+            t(&locale, "notification.item_shared.message", &args),
             json!({
                 "item_id": item_id,
                 "circuit_id": circuit_id,
@@ -173,11 +198,13 @@ impl<S: StorageBackend + 'static> NotificationEngine<S> {
         admin_username: &str,
         changes: &str,
     ) -> Result<Notification, NotificationError> {
+        let locale = self.recipient_locale(user_id);
+        let args = [("admin", admin_username), ("changes", changes)];
         let notification = Notification::new(
             user_id.to_string(),
             NotificationType::AccountUpdated,
-            "Account Updated".to_string(),
-            format!("Your account has been updated by admin {admin_username}. Changes: {changes}"),
+            t(&locale, "notification.account_updated.title", &args),
+            t(&locale, "notification.account_updated.message", &args),
             json!({
                 "admin_username": admin_username,
                 "changes": changes,
@@ -198,18 +225,27 @@ impl<S: StorageBackend + 'static> NotificationEngine<S> {
         reason: &str,
         new_balance: i64,
     ) -> Result<Notification, NotificationError> {
-        let action = if amount > 0 { "added" } else { "deducted" };
+        let message_key = if amount > 0 {
+            "notification.credits_added.message"
+        } else {
+            "notification.credits_deducted.message"
+        };
+        let locale = self.recipient_locale(user_id);
+        let balance = new_balance.to_string();
+        let args = [
+            ("admin", admin_username),
+            ("reason", reason),
+            ("balance", balance.as_str()),
+        ];
         let notification = Notification::new(
             user_id.to_string(),
             NotificationType::CreditsAdjusted,
-            "Credits Adjusted".to_string(),
-            format!(
-                "Admin {} {} {} credits. Reason: {}. New balance: {}",
-                admin_username,
-                action,
+            t(&locale, "notification.credits_adjusted.title", &args),
+            Localizer::global().translate_count(
+                locale.as_deref(),
+                message_key,
                 amount.abs(),
-                reason,
-                new_balance
+                &args,
             ),
             json!({
                 "admin_username": admin_username,
@@ -231,11 +267,13 @@ impl<S: StorageBackend + 'static> NotificationEngine<S> {
         admin_username: &str,
         reason: &str,
     ) -> Result<Notification, NotificationError> {
+        let locale = self.recipient_locale(user_id);
+        let args = [("admin", admin_username), ("reason", reason)];
         let notification = Notification::new(
             user_id.to_string(),
             NotificationType::AccountFrozen,
-            "Account Frozen".to_string(),
-            format!("Your account has been frozen by admin {admin_username}. Reason: {reason}"),
+            t(&locale, "notification.account_frozen.title", &args),
+            t(&locale, "notification.account_frozen.message", &args),
             json!({
                 "admin_username": admin_username,
                 "reason": reason,
@@ -253,13 +291,13 @@ impl<S: StorageBackend + 'static> NotificationEngine<S> {
         user_id: &str,
         admin_username: &str,
     ) -> Result<Notification, NotificationError> {
+        let locale = self.recipient_locale(user_id);
+        let args = [("admin", admin_username)];
         let notification = Notification::new(
             user_id.to_string(),
             NotificationType::AccountUnfrozen,
-            "Account Unfrozen".to_string(),
-            format!(
-                "Your account has been reactivated by admin {admin_username}. You can now access all features."
-            ),
+            t(&locale, "notification.account_unfrozen.title", &args),
+            t(&locale, "notification.account_unfrozen.message", &args),
             json!({
                 "admin_username": admin_username,
                 "timestamp": Utc::now().timestamp(),
@@ -360,10 +398,27 @@ impl<S: StorageBackend + 'static> NotificationEngine<S> {
             .map_err(|e| NotificationError::StorageError(e.to_string()))
     }
 
+    /// Locale of the notified user, None when unset or the user is unknown
+    fn recipient_locale(&self, user_id: &str) -> Option<String> {
+        self.storage
+            .get_user_account(user_id)
+            .ok()
+            .flatten()
+            .and_then(|user| user.locale)
+    }
+
     // Internal helper to store a notification
     fn store_notification(&self, notification: &Notification) -> Result<(), NotificationError> {
         self.storage
             .store_notification(notification)
-            .map_err(|e| NotificationError::StorageError(e.to_string()))
+            .map_err(|e| NotificationError::StorageError(e.to_string()))?;
+        if let Some(live_stream) = &self.live_stream {
+            live_stream.publish(LiveRecord::Notification(notification.clone()));
+        }
+        Ok(())
     }
 }
+
+fn t(locale: &Option<String>, key: &str, args: &[(&str, &str)]) -> String {
+    Localizer::global().translate(locale.as_deref(), key, args)
+}
@@ -0,0 +1,356 @@
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    CircuitMemberProfile, HeldCertification, OrganizationKind, OrganizationProfile,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const MAX_DISPLAY_NAME_LENGTH: usize = 120;
+const MAX_CERTIFICATIONS: usize = 50;
+
+#[derive(Debug)]
+pub enum OrganizationError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+    NotFound(String),
+}
+
+impl From<StorageError> for OrganizationError {
+    fn from(err: StorageError) -> Self {
+        OrganizationError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for OrganizationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrganizationError::StorageError(e) => write!(f, "Storage error: {e}"),
+            OrganizationError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            OrganizationError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            OrganizationError::NotFound(e) => write!(f, "Not found: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for OrganizationError {}
+
+/// Fields a member can set on their own directory profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationProfileUpdate {
+    pub display_name: String,
+    pub org_type: OrganizationKind,
+    pub region: Option<String>,
+    #[serde(default)]
+    pub certifications: Vec<HeldCertification>,
+}
+
+/// Directory search filters. Searches are always scoped to a single workspace.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrganizationSearchQuery {
+    pub workspace_id: Option<String>,
+    pub text: Option<String>,
+    pub org_type: Option<OrganizationKind>,
+    pub region: Option<String>,
+    pub certification: Option<String>,
+    pub limit: Option<usize>,
+}
+
+pub struct OrganizationEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> OrganizationEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Create or replace the directory profile of a user
+    pub fn upsert_profile(
+        &self,
+        user_id: &str,
+        workspace_id: Option<String>,
+        update: OrganizationProfileUpdate,
+    ) -> Result<OrganizationProfile, OrganizationError> {
+        Self::validate_update(&update)?;
+
+        let mut profile = match self.storage.get_organization_profile(user_id)? {
+            Some(existing) => existing,
+            None => OrganizationProfile::new(
+                user_id.to_string(),
+                update.display_name.clone(),
+                update.org_type,
+            ),
+        };
+
+        profile.display_name = update.display_name.trim().to_string();
+        profile.org_type = update.org_type;
+        profile.region = update
+            .region
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty());
+        profile.certifications = update.certifications;
+        if workspace_id.is_some() {
+            profile.workspace_id = workspace_id;
+        }
+        profile.updated_at = Utc::now();
+
+        self.storage.store_organization_profile(&profile)?;
+        Ok(profile)
+    }
+
+    pub fn get_profile(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<OrganizationProfile>, OrganizationError> {
+        Ok(self.storage.get_organization_profile(user_id)?)
+    }
+
+    pub fn delete_profile(&self, user_id: &str) -> Result<(), OrganizationError> {
+        if self.storage.get_organization_profile(user_id)?.is_none() {
+            return Err(OrganizationError::NotFound(format!(
+                "No directory profile for user {user_id}"
+            )));
+        }
+        self.storage.delete_organization_profile(user_id)?;
+        Ok(())
+    }
+
+    /// Search the directory within a workspace
+    pub fn search(
+        &self,
+        query: &OrganizationSearchQuery,
+    ) -> Result<Vec<OrganizationProfile>, OrganizationError> {
+        let workspace_id = query.workspace_id.as_deref().ok_or_else(|| {
+            OrganizationError::ValidationError(
+                "Directory searches must be scoped to a workspace".to_string(),
+            )
+        })?;
+
+        let mut profiles: Vec<OrganizationProfile> = self
+            .storage
+            .list_organization_profiles()?
+            .into_iter()
+            .filter(|p| p.workspace_id.as_deref() == Some(workspace_id))
+            .filter(|p| query.org_type.is_none_or(|t| p.org_type == t))
+            .filter(|p| {
+                query.region.as_ref().is_none_or(|region| {
+                    p.region
+                        .as_ref()
+                        .is_some_and(|r| r.eq_ignore_ascii_case(region))
+                })
+            })
+            .filter(|p| {
+                query
+                    .certification
+                    .as_ref()
+                    .is_none_or(|c| p.holds_certification(c))
+            })
+            .filter(|p| query.text.as_ref().is_none_or(|t| p.matches_text(t)))
+            .collect();
+
+        profiles.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+
+        if let Some(limit) = query.limit {
+            profiles.truncate(limit);
+        }
+
+        Ok(profiles)
+    }
+
+    /// Members of a circuit joined with their directory profiles.
+    /// Only members of the circuit may list its member profiles.
+    pub fn get_circuit_member_profiles(
+        &self,
+        circuit_id: &Uuid,
+        requester_id: &str,
+    ) -> Result<Vec<CircuitMemberProfile>, OrganizationError> {
+        let circuit = self
+            .storage
+            .get_circuit(circuit_id)?
+            .ok_or_else(|| OrganizationError::NotFound(format!("Circuit {circuit_id}")))?;
+
+        if !circuit.is_member(requester_id) {
+            return Err(OrganizationError::PermissionDenied(
+                "Only circuit members can view member profiles".to_string(),
+            ));
+        }
+
+        let mut result = Vec::with_capacity(circuit.members.len());
+        for member in circuit.members {
+            let profile = self.storage.get_organization_profile(&member.member_id)?;
+            let summary = profile.as_ref().map(|p| p.summary());
+            result.push(CircuitMemberProfile {
+                member,
+                profile,
+                summary,
+            });
+        }
+
+        Ok(result)
+    }
+
+    fn validate_update(update: &OrganizationProfileUpdate) -> Result<(), OrganizationError> {
+        let name = update.display_name.trim();
+        if name.is_empty() {
+            return Err(OrganizationError::ValidationError(
+                "display_name cannot be empty".to_string(),
+            ));
+        }
+        if name.len() > MAX_DISPLAY_NAME_LENGTH {
+            return Err(OrganizationError::ValidationError(format!(
+                "display_name cannot exceed {MAX_DISPLAY_NAME_LENGTH} characters"
+            )));
+        }
+        if update.certifications.len() > MAX_CERTIFICATIONS {
+            return Err(OrganizationError::ValidationError(format!(
+                "A profile cannot list more than {MAX_CERTIFICATIONS} certifications"
+            )));
+        }
+        if update
+            .certifications
+            .iter()
+            .any(|c| c.name.trim().is_empty())
+        {
+            return Err(OrganizationError::ValidationError(
+                "Certification name cannot be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{Circuit, MemberRole};
+    use std::sync::{Arc, Mutex};
+
+    fn organic() -> HeldCertification {
+        HeldCertification {
+            name: "Organic".to_string(),
+            issuer: Some("IBD".to_string()),
+            certificate_id: None,
+            valid_until: None,
+        }
+    }
+
+    fn update(name: &str, org_type: OrganizationKind, region: &str) -> OrganizationProfileUpdate {
+        OrganizationProfileUpdate {
+            display_name: name.to_string(),
+            org_type,
+            region: Some(region.to_string()),
+            certifications: vec![],
+        }
+    }
+
+    #[test]
+    fn test_profile_summary() {
+        let mut profile = OrganizationProfile::new(
+            "user-1".to_string(),
+            "Green Valley".to_string(),
+            OrganizationKind::Processor,
+        );
+        profile.region = Some("Region X".to_string());
+        profile.certifications.push(organic());
+
+        assert_eq!(profile.summary(), "certified organic processor in Region X");
+
+        profile.certifications[0].valid_until = Some(Utc::now() - chrono::Duration::days(1));
+        assert_eq!(profile.summary(), "processor in Region X");
+    }
+
+    #[test]
+    fn test_search_is_scoped_to_workspace() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let engine = OrganizationEngine::new(Arc::clone(&storage));
+
+        let mut farm = update("Sunny Farm", OrganizationKind::Farm, "Goiás");
+        farm.certifications.push(organic());
+        engine
+            .upsert_profile("farm-1", Some("ws-1".to_string()), farm)
+            .unwrap();
+        engine
+            .upsert_profile(
+                "proc-1",
+                Some("ws-1".to_string()),
+                update("Mill Co", OrganizationKind::Processor, "Goiás"),
+            )
+            .unwrap();
+        engine
+            .upsert_profile(
+                "farm-2",
+                Some("ws-2".to_string()),
+                update("Other Farm", OrganizationKind::Farm, "Goiás"),
+            )
+            .unwrap();
+
+        let all_farms = engine
+            .search(&OrganizationSearchQuery {
+                workspace_id: Some("ws-1".to_string()),
+                org_type: Some(OrganizationKind::Farm),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(all_farms.len(), 1);
+        assert_eq!(all_farms[0].user_id, "farm-1");
+
+        let certified = engine
+            .search(&OrganizationSearchQuery {
+                workspace_id: Some("ws-1".to_string()),
+                certification: Some("organic".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(certified.len(), 1);
+
+        assert!(engine.search(&OrganizationSearchQuery::default()).is_err());
+    }
+
+    #[test]
+    fn test_circuit_member_profiles() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let engine = OrganizationEngine::new(Arc::clone(&storage));
+
+        let mut circuit = Circuit::new(
+            "Coffee".to_string(),
+            "Coffee chain".to_string(),
+            "owner-1".to_string(),
+        );
+        circuit.add_member("farm-1".to_string(), MemberRole::Member);
+        storage.store_circuit(&circuit).unwrap();
+
+        engine
+            .upsert_profile(
+                "farm-1",
+                None,
+                update("Sunny Farm", OrganizationKind::Farm, "Minas"),
+            )
+            .unwrap();
+
+        let members = engine
+            .get_circuit_member_profiles(&circuit.circuit_id, "owner-1")
+            .unwrap();
+        assert_eq!(members.len(), 2);
+        let farm = members
+            .iter()
+            .find(|m| m.member.member_id == "farm-1")
+            .unwrap();
+        assert_eq!(farm.summary.as_deref(), Some("farm in Minas"));
+
+        assert!(matches!(
+            engine.get_circuit_member_profiles(&circuit.circuit_id, "stranger"),
+            Err(OrganizationError::PermissionDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_empty_display_name() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let engine = OrganizationEngine::new(storage);
+        let result = engine.upsert_profile("u", None, update("  ", OrganizationKind::Farm, "X"));
+        assert!(matches!(result, Err(OrganizationError::ValidationError(_))));
+    }
+}
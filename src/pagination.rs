@@ -0,0 +1,178 @@
+//! Keyset (cursor) pagination for list endpoints.
+//!
+//! Pages are ordered by creation timestamp and then id, so a cursor stays valid
+//! while new records are appended. Cursors are opaque to clients.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+pub const MAX_PAGE_SIZE: usize = 200;
+
+/// Position after the last record of a page: (created timestamp, id)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PageCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: String,
+}
+
+impl PageCursor {
+    pub fn new(created_at: DateTime<Utc>, id: impl Into<String>) -> Self {
+        Self {
+            created_at,
+            id: id.into(),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!(
+            "{}.{:09}:{}",
+            self.created_at.timestamp(),
+            self.created_at.timestamp_subsec_nanos(),
+            self.id
+        ))
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, String> {
+        let invalid = || "Invalid pagination cursor".to_string();
+        let raw = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+        let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+        let (timestamp, id) = raw.split_once(':').ok_or_else(invalid)?;
+        let (secs, nanos) = timestamp.split_once('.').ok_or_else(invalid)?;
+        let secs: i64 = secs.parse().map_err(|_| invalid())?;
+        let nanos: u32 = nanos.parse().map_err(|_| invalid())?;
+        let created_at = DateTime::from_timestamp(secs, nanos).ok_or_else(invalid)?;
+        Ok(Self::new(created_at, id))
+    }
+
+    /// True if a record at `(created_at, id)` sorts after this cursor
+    pub fn precedes(&self, created_at: DateTime<Utc>, id: &str) -> bool {
+        (created_at, id) > (self.created_at, self.id.as_str())
+    }
+}
+
+/// `?cursor=&limit=` query parameters accepted by paginated list routes
+#[derive(Debug, Default, Deserialize)]
+pub struct PageParams {
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl PageParams {
+    pub fn after(&self) -> Result<Option<PageCursor>, String> {
+        self.cursor.as_deref().map(PageCursor::decode).transpose()
+    }
+
+    pub fn page_size(&self) -> usize {
+        page_size(self.limit)
+    }
+}
+
+/// Requested page size, defaulted and capped at `MAX_PAGE_SIZE`
+pub fn page_size(requested: Option<usize>) -> usize {
+    requested
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Pass back as `cursor` to fetch the next page; `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+/// Build one page of records matching `keep`, pulling ordered batches from
+/// `fetch(after, limit)` until the page is full or the source is exhausted.
+pub fn collect_page<T, E>(
+    after: Option<PageCursor>,
+    limit: usize,
+    mut fetch: impl FnMut(Option<&PageCursor>, usize) -> Result<Vec<T>, E>,
+    cursor_of: impl Fn(&T) -> PageCursor,
+    keep: impl Fn(&T) -> bool,
+) -> Result<Page<T>, E> {
+    let batch_size = limit + 1;
+    let mut scan_cursor = after;
+    let mut items = Vec::with_capacity(batch_size);
+
+    while items.len() <= limit {
+        let batch = fetch(scan_cursor.as_ref(), batch_size)?;
+        let exhausted = batch.len() < batch_size;
+        for record in batch {
+            scan_cursor = Some(cursor_of(&record));
+            if keep(&record) {
+                items.push(record);
+                if items.len() > limit {
+                    break;
+                }
+            }
+        }
+        if exhausted {
+            break;
+        }
+    }
+
+    let next_cursor = if items.len() > limit {
+        items.truncate(limit);
+        items.last().map(|record| cursor_of(record).encode())
+    } else {
+        None
+    };
+    Ok(Page { items, next_cursor })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_page_walks_filtered_records_in_order() {
+        let base = Utc::now();
+        let records: Vec<(PageCursor, u32)> = (0..25)
+            .map(|n| {
+                (
+                    PageCursor::new(
+                        base + chrono::Duration::seconds(n / 2),
+                        format!("id-{n:02}"),
+                    ),
+                    n as u32,
+                )
+            })
+            .collect();
+        let fetch = |after: Option<&PageCursor>, limit: usize| -> Result<_, String> {
+            Ok(records
+                .iter()
+                .filter(|(c, _)| after.is_none_or(|a| a.precedes(c.created_at, &c.id)))
+                .take(limit)
+                .cloned()
+                .collect::<Vec<_>>())
+        };
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page =
+                collect_page(cursor, 4, fetch, |(c, _)| c.clone(), |(_, n)| n % 3 != 0).unwrap();
+            seen.extend(page.items.iter().map(|(_, n)| *n));
+            match page.next_cursor {
+                Some(next) => cursor = Some(PageCursor::decode(&next).unwrap()),
+                None => break,
+            }
+        }
+
+        let expected: Vec<u32> = (0..25).filter(|n| n % 3 != 0).collect();
+        assert_eq!(seen, expected);
+        assert!(PageCursor::decode("not a cursor").is_err());
+        assert_eq!(page_size(Some(10_000)), MAX_PAGE_SIZE);
+    }
+}
@@ -0,0 +1,399 @@
+//! Circuit-scoped partner tokens.
+//!
+//! A circuit owner mints a token for an external partner (a buyer, a
+//! certifier) that can read the circuit's items and their shared events
+//! through the partner API and nothing else: no user account is created, and
+//! the token cannot push, manage or reach any other circuit. Tokens expire,
+//! can be revoked, and every request made with one is logged for the owner.
+//!
+//! Only the token's BLAKE3 hash is stored; the token itself is returned once,
+//! when it is minted.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    Circuit, CircuitItem, Event, EventVisibility, Item, PartnerToken, PartnerTokenUsage,
+};
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use serde::Deserialize;
+use uuid::Uuid;
+
+pub const TOKEN_PREFIX: &str = "dfp_";
+const TOKEN_RANDOM_CHARS: usize = 40;
+pub const DEFAULT_EXPIRY_DAYS: i64 = 90;
+pub const MAX_EXPIRY_DAYS: i64 = 365;
+pub const DEFAULT_USAGE_LIMIT: usize = 100;
+pub const MAX_USAGE_LIMIT: usize = 1000;
+
+#[derive(Debug)]
+pub enum PartnerTokenError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+    NotFound(String),
+    /// Unknown, expired or revoked token; deliberately not more specific
+    InvalidToken,
+}
+
+impl From<StorageError> for PartnerTokenError {
+    fn from(err: StorageError) -> Self {
+        PartnerTokenError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for PartnerTokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PartnerTokenError::StorageError(e) => write!(f, "Storage error: {e}"),
+            PartnerTokenError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            PartnerTokenError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            PartnerTokenError::NotFound(e) => write!(f, "Not found: {e}"),
+            PartnerTokenError::InvalidToken => {
+                write!(f, "Partner token is invalid, expired or revoked")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PartnerTokenError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MintPartnerTokenInput {
+    pub partner_name: String,
+    /// Defaults to `DEFAULT_EXPIRY_DAYS`
+    pub expires_in_days: Option<i64>,
+}
+
+pub fn hash_token(token: &str) -> String {
+    blake3::hash(token.as_bytes()).to_hex().to_string()
+}
+
+fn generate_token() -> String {
+    const CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+    let mut rng = rand::thread_rng();
+    let random: String = (0..TOKEN_RANDOM_CHARS)
+        .map(|_| CHARS[rng.gen_range(0..CHARS.len())] as char)
+        .collect();
+    format!("{TOKEN_PREFIX}{random}")
+}
+
+/// Events a partner may see: shared with the circuit or public, never local
+/// ones; encrypted events are listed without their metadata
+pub fn partner_visible_events(events: Vec<Event>) -> Vec<Event> {
+    events
+        .into_iter()
+        .filter(|event| {
+            matches!(
+                event.visibility,
+                EventVisibility::Public | EventVisibility::CircuitOnly
+            ) && !event.is_local
+        })
+        .map(|mut event| {
+            if event.is_encrypted {
+                event.metadata.clear();
+            }
+            event
+        })
+        .collect()
+}
+
+pub struct PartnerTokenEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> PartnerTokenEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    fn owned_circuit(
+        &self,
+        circuit_id: &Uuid,
+        user_id: &str,
+    ) -> Result<Circuit, PartnerTokenError> {
+        let circuit = self
+            .storage
+            .get_circuit(circuit_id)?
+            .ok_or_else(|| PartnerTokenError::NotFound(format!("Circuit {circuit_id}")))?;
+        if circuit.owner_id != user_id {
+            return Err(PartnerTokenError::PermissionDenied(
+                "Only the circuit owner can manage partner tokens".to_string(),
+            ));
+        }
+        Ok(circuit)
+    }
+
+    fn circuit_token(
+        &self,
+        circuit_id: &Uuid,
+        token_id: &Uuid,
+    ) -> Result<PartnerToken, PartnerTokenError> {
+        self.storage
+            .get_partner_token(token_id)?
+            .filter(|token| token.circuit_id == *circuit_id)
+            .ok_or_else(|| PartnerTokenError::NotFound(format!("Partner token {token_id}")))
+    }
+
+    /// Returns the stored token and the token itself, which is not kept
+    pub fn mint(
+        &self,
+        circuit_id: &Uuid,
+        input: MintPartnerTokenInput,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(PartnerToken, String), PartnerTokenError> {
+        let circuit = self.owned_circuit(circuit_id, user_id)?;
+        if circuit.is_archived() {
+            return Err(PartnerTokenError::ValidationError(
+                "Circuit is archived".to_string(),
+            ));
+        }
+        let partner_name = input.partner_name.trim();
+        if partner_name.is_empty() {
+            return Err(PartnerTokenError::ValidationError(
+                "partner_name is required".to_string(),
+            ));
+        }
+        let days = input.expires_in_days.unwrap_or(DEFAULT_EXPIRY_DAYS);
+        if !(1..=MAX_EXPIRY_DAYS).contains(&days) {
+            return Err(PartnerTokenError::ValidationError(format!(
+                "expires_in_days must be between 1 and {MAX_EXPIRY_DAYS}"
+            )));
+        }
+
+        let secret = generate_token();
+        let token = PartnerToken {
+            token_id: Uuid::new_v4(),
+            circuit_id: *circuit_id,
+            partner_name: partner_name.to_string(),
+            token_hash: hash_token(&secret),
+            token_prefix: secret.chars().take(TOKEN_PREFIX.len() + 6).collect(),
+            created_by: user_id.to_string(),
+            created_at: now,
+            expires_at: now + Duration::days(days),
+            revoked_at: None,
+            revoked_by: None,
+            last_used_at: None,
+            use_count: 0,
+        };
+        self.storage.store_partner_token(&token)?;
+        Ok((token, secret))
+    }
+
+    pub fn list(
+        &self,
+        circuit_id: &Uuid,
+        user_id: &str,
+    ) -> Result<Vec<PartnerToken>, PartnerTokenError> {
+        self.owned_circuit(circuit_id, user_id)?;
+        Ok(self.storage.list_partner_tokens(circuit_id)?)
+    }
+
+    pub fn revoke(
+        &self,
+        circuit_id: &Uuid,
+        token_id: &Uuid,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<PartnerToken, PartnerTokenError> {
+        self.owned_circuit(circuit_id, user_id)?;
+        let mut token = self.circuit_token(circuit_id, token_id)?;
+        if token.revoked_at.is_none() {
+            token.revoked_at = Some(now);
+            token.revoked_by = Some(user_id.to_string());
+            self.storage.store_partner_token(&token)?;
+        }
+        Ok(token)
+    }
+
+    /// Most recent requests first
+    pub fn usage(
+        &self,
+        circuit_id: &Uuid,
+        token_id: &Uuid,
+        user_id: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<PartnerTokenUsage>, PartnerTokenError> {
+        self.owned_circuit(circuit_id, user_id)?;
+        self.circuit_token(circuit_id, token_id)?;
+        let limit = limit.unwrap_or(DEFAULT_USAGE_LIMIT).min(MAX_USAGE_LIMIT);
+        Ok(self.storage.list_partner_token_usage(token_id, limit)?)
+    }
+
+    /// Token for a request, if it is active and its circuit still exists and
+    /// is not archived
+    pub fn authenticate(
+        &self,
+        secret: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(PartnerToken, Circuit), PartnerTokenError> {
+        if !secret.starts_with(TOKEN_PREFIX) {
+            return Err(PartnerTokenError::InvalidToken);
+        }
+        let token = self
+            .storage
+            .get_partner_token_by_hash(&hash_token(secret))?
+            .filter(|token| token.is_active(now))
+            .ok_or(PartnerTokenError::InvalidToken)?;
+        let circuit = self
+            .storage
+            .get_circuit(&token.circuit_id)?
+            .filter(|circuit| !circuit.is_archived())
+            .ok_or(PartnerTokenError::InvalidToken)?;
+        Ok((token, circuit))
+    }
+
+    /// Log a request made with `token` and bump its usage counters
+    pub fn record_use(
+        &self,
+        token: &PartnerToken,
+        action: &str,
+        dfid: Option<&str>,
+        client_ip: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<(), PartnerTokenError> {
+        self.storage
+            .record_partner_token_usage(&PartnerTokenUsage {
+                usage_id: Uuid::new_v4(),
+                token_id: token.token_id,
+                circuit_id: token.circuit_id,
+                action: action.to_string(),
+                dfid: dfid.map(str::to_string),
+                client_ip,
+                used_at: now,
+            })?;
+
+        // Re-read so concurrent requests don't lose each other's counts
+        // (or a revocation made since authentication)
+        if let Some(mut stored) = self.storage.get_partner_token(&token.token_id)? {
+            stored.last_used_at = Some(now);
+            stored.use_count += 1;
+            self.storage.store_partner_token(&stored)?;
+        }
+        Ok(())
+    }
+
+    pub fn items(&self, token: &PartnerToken) -> Result<Vec<CircuitItem>, PartnerTokenError> {
+        Ok(self.storage.get_circuit_items(&token.circuit_id)?)
+    }
+
+    /// An item of the token's circuit with the events the partner may see
+    pub fn item(
+        &self,
+        token: &PartnerToken,
+        dfid: &str,
+    ) -> Result<(Item, Vec<Event>), PartnerTokenError> {
+        let in_circuit = self
+            .storage
+            .get_circuit_items(&token.circuit_id)?
+            .iter()
+            .any(|item| item.dfid == dfid);
+        let item = in_circuit
+            .then(|| self.storage.get_item_by_dfid(dfid))
+            .transpose()?
+            .flatten()
+            .ok_or_else(|| PartnerTokenError::NotFound(format!("Item {dfid}")))?;
+        let events = partner_visible_events(self.storage.get_events_by_dfid(dfid)?);
+        Ok((item, events))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuits_engine::CircuitsEngine;
+    use crate::storage::InMemoryStorage;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_partner_token_reads_only_its_circuit_until_revoked() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let mut circuits = CircuitsEngine::new(Arc::clone(&storage));
+        let circuit = circuits
+            .create_circuit(
+                "Exports".to_string(),
+                "Export lots".to_string(),
+                "owner".to_string(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let other = circuits
+            .create_circuit(
+                "Other".to_string(),
+                "Other lots".to_string(),
+                "owner".to_string(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        for (dfid, circuit_id) in [("DFID-1", circuit.circuit_id), ("DFID-2", other.circuit_id)] {
+            storage
+                .store_item(&Item::new(dfid.to_string(), Vec::new(), Uuid::new_v4()))
+                .unwrap();
+            storage
+                .store_circuit_item(&CircuitItem::new(
+                    dfid.to_string(),
+                    circuit_id,
+                    "owner".to_string(),
+                    vec!["read".to_string()],
+                ))
+                .unwrap();
+        }
+
+        let engine = PartnerTokenEngine::new(Arc::clone(&storage));
+        let now = Utc::now();
+        let input = MintPartnerTokenInput {
+            partner_name: "Buyer Co".to_string(),
+            expires_in_days: Some(30),
+        };
+        assert!(matches!(
+            engine.mint(&circuit.circuit_id, input.clone(), "member", now),
+            Err(PartnerTokenError::PermissionDenied(_))
+        ));
+        let (token, secret) = engine
+            .mint(&circuit.circuit_id, input, "owner", now)
+            .unwrap();
+        assert!(secret.starts_with(TOKEN_PREFIX));
+        assert_ne!(token.token_hash, secret);
+
+        let (authenticated, _) = engine.authenticate(&secret, now).unwrap();
+        assert_eq!(authenticated.token_id, token.token_id);
+        assert_eq!(engine.items(&authenticated).unwrap().len(), 1);
+        engine.item(&authenticated, "DFID-1").unwrap();
+        assert!(matches!(
+            engine.item(&authenticated, "DFID-2"),
+            Err(PartnerTokenError::NotFound(_))
+        ));
+
+        engine
+            .record_use(&authenticated, "get_item", Some("DFID-1"), None, now)
+            .unwrap();
+        let usage = engine
+            .usage(&circuit.circuit_id, &token.token_id, "owner", None)
+            .unwrap();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].dfid.as_deref(), Some("DFID-1"));
+        let listed = engine.list(&circuit.circuit_id, "owner").unwrap();
+        assert_eq!(listed[0].use_count, 1);
+
+        // Expired, then revoked tokens no longer authenticate
+        assert!(matches!(
+            engine.authenticate(&secret, now + Duration::days(31)),
+            Err(PartnerTokenError::InvalidToken)
+        ));
+        engine
+            .revoke(&circuit.circuit_id, &token.token_id, "owner", now)
+            .unwrap();
+        assert!(matches!(
+            engine.authenticate(&secret, now),
+            Err(PartnerTokenError::InvalidToken)
+        ));
+        assert!(matches!(
+            engine.authenticate("dfp_guess", now),
+            Err(PartnerTokenError::InvalidToken)
+        ));
+    }
+}
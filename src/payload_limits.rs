@@ -0,0 +1,241 @@
+//! Per-tier limits on event payload size and complexity, and process-wide
+//! counts of the events rejected for exceeding them.
+//!
+//! Limits come from the tier of the event's source (see
+//! [`EventPayloadLimits::for_tier`]); sources without an account get the basic
+//! tier's limits.
+
+use crate::storage::StorageBackend;
+use crate::types::{EventPayloadLimits, PayloadRejectionCount, UserTier};
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Metadata key whose entries count against `max_identifiers`
+pub const IDENTIFIERS_KEY: &str = "identifiers";
+
+/// Which limit an event exceeded, with its measured value
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "limit", rename_all = "snake_case")]
+pub enum PayloadLimitError {
+    MetadataBytes { actual: usize, max: usize },
+    MetadataDepth { actual: usize, max: usize },
+    MetadataFields { actual: usize, max: usize },
+    Attachments { actual: usize, max: usize },
+    Identifiers { actual: usize, max: usize },
+}
+
+impl PayloadLimitError {
+    pub fn limit(&self) -> &'static str {
+        match self {
+            PayloadLimitError::MetadataBytes { .. } => "metadata_bytes",
+            PayloadLimitError::MetadataDepth { .. } => "metadata_depth",
+            PayloadLimitError::MetadataFields { .. } => "metadata_fields",
+            PayloadLimitError::Attachments { .. } => "attachments",
+            PayloadLimitError::Identifiers { .. } => "identifiers",
+        }
+    }
+}
+
+impl std::fmt::Display for PayloadLimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PayloadLimitError::MetadataBytes { actual, max } => {
+                write!(f, "metadata is {actual} bytes, the limit is {max}")
+            }
+            PayloadLimitError::MetadataDepth { actual, max } => {
+                write!(
+                    f,
+                    "metadata is nested {actual} levels deep, the limit is {max}"
+                )
+            }
+            PayloadLimitError::MetadataFields { actual, max } => {
+                write!(f, "metadata has {actual} fields, the limit is {max}")
+            }
+            PayloadLimitError::Attachments { actual, max } => {
+                write!(
+                    f,
+                    "event would have {actual} attachments, the limit is {max}"
+                )
+            }
+            PayloadLimitError::Identifiers { actual, max } => {
+                write!(f, "event has {actual} identifiers, the limit is {max}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PayloadLimitError {}
+
+/// Tier of `source` and its limits
+pub fn limits_for_source<S: StorageBackend + ?Sized>(
+    storage: &S,
+    source: &str,
+) -> (UserTier, EventPayloadLimits) {
+    let tier = storage
+        .get_user_account(source)
+        .ok()
+        .flatten()
+        .map(|account| account.tier)
+        .unwrap_or(UserTier::Basic);
+    let limits = EventPayloadLimits::for_tier(&tier);
+    (tier, limits)
+}
+
+/// Depth below `value` and the number of keys and elements it contains
+fn measure(value: &Value) -> (usize, usize) {
+    let children: Box<dyn Iterator<Item = &Value>> = match value {
+        Value::Object(map) => Box::new(map.values()),
+        Value::Array(items) => Box::new(items.iter()),
+        _ => return (0, 0),
+    };
+    children.fold((1, 0), |(depth, fields), child| {
+        let (child_depth, child_fields) = measure(child);
+        (depth.max(child_depth + 1), fields + 1 + child_fields)
+    })
+}
+
+pub fn check_metadata(
+    limits: &EventPayloadLimits,
+    metadata: &HashMap<String, Value>,
+) -> Result<(), PayloadLimitError> {
+    let bytes = serde_json::to_vec(metadata).map(|v| v.len()).unwrap_or(0);
+    if bytes > limits.max_metadata_bytes {
+        return Err(PayloadLimitError::MetadataBytes {
+            actual: bytes,
+            max: limits.max_metadata_bytes,
+        });
+    }
+
+    let (depth, fields) = metadata
+        .values()
+        .map(measure)
+        .fold((0, metadata.len()), |(depth, fields), (d, f)| {
+            (depth.max(d), fields + f)
+        });
+    if depth > limits.max_metadata_depth {
+        return Err(PayloadLimitError::MetadataDepth {
+            actual: depth,
+            max: limits.max_metadata_depth,
+        });
+    }
+    if fields > limits.max_metadata_fields {
+        return Err(PayloadLimitError::MetadataFields {
+            actual: fields,
+            max: limits.max_metadata_fields,
+        });
+    }
+
+    let identifiers = match metadata.get(IDENTIFIERS_KEY) {
+        Some(Value::Array(items)) => items.len(),
+        Some(Value::Object(map)) => map.len(),
+        Some(Value::Null) | None => 0,
+        Some(_) => 1,
+    };
+    if identifiers > limits.max_identifiers {
+        return Err(PayloadLimitError::Identifiers {
+            actual: identifiers,
+            max: limits.max_identifiers,
+        });
+    }
+    Ok(())
+}
+
+/// `attachments` is the count the event would have after attaching
+pub fn check_attachments(
+    limits: &EventPayloadLimits,
+    attachments: usize,
+) -> Result<(), PayloadLimitError> {
+    if attachments > limits.max_attachments {
+        return Err(PayloadLimitError::Attachments {
+            actual: attachments,
+            max: limits.max_attachments,
+        });
+    }
+    Ok(())
+}
+
+fn registry() -> &'static Mutex<HashMap<(UserTier, &'static str), PayloadRejectionCount>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<(UserTier, &'static str), PayloadRejectionCount>>> =
+        OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub fn record_rejection(tier: &UserTier, source: &str, error: &PayloadLimitError) {
+    tracing::warn!(
+        "🚫 Event from {} ({}) rejected: {}",
+        source,
+        tier.as_str(),
+        error
+    );
+    let now = Utc::now();
+    let mut counts = registry().lock().unwrap();
+    let count = counts
+        .entry((tier.clone(), error.limit()))
+        .or_insert_with(|| PayloadRejectionCount {
+            tier: tier.clone(),
+            limit: error.limit().to_string(),
+            rejections: 0,
+            last_rejected_at: now,
+        });
+    count.rejections += 1;
+    count.last_rejected_at = now;
+}
+
+/// Rejections since the process started, by tier and limit
+pub fn rejection_counts() -> Vec<PayloadRejectionCount> {
+    let mut counts: Vec<PayloadRejectionCount> =
+        registry().lock().unwrap().values().cloned().collect();
+    counts.sort_by(|a, b| (a.tier.as_str(), &a.limit).cmp(&(b.tier.as_str(), &b.limit)));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_metadata_limits_follow_the_tier() {
+        let basic = EventPayloadLimits::for_tier(&UserTier::Basic);
+        let enterprise = EventPayloadLimits::for_tier(&UserTier::Enterprise);
+
+        let mut metadata = HashMap::from([
+            ("weight_kg".to_string(), json!(412.5)),
+            ("lot".to_string(), json!({"farm": {"plot": ["a", "b"]}})),
+        ]);
+        check_metadata(&basic, &metadata).unwrap();
+        assert_eq!(measure(&metadata["lot"]), (3, 4));
+
+        let identifiers: Vec<String> = (0..50).map(|i| format!("tag:{i}")).collect();
+        metadata.insert(IDENTIFIERS_KEY.to_string(), json!(identifiers));
+        assert_eq!(
+            check_metadata(&basic, &metadata),
+            Err(PayloadLimitError::Identifiers {
+                actual: 50,
+                max: 20
+            })
+        );
+        check_metadata(&enterprise, &metadata).unwrap();
+
+        let mut nested = json!("leaf");
+        for _ in 0..10 {
+            nested = json!([nested]);
+        }
+        let deep = HashMap::from([("deep".to_string(), nested)]);
+        assert!(matches!(
+            check_metadata(&basic, &deep),
+            Err(PayloadLimitError::MetadataDepth { actual: 10, .. })
+        ));
+
+        let large = HashMap::from([("notes".to_string(), json!("x".repeat(20_000)))]);
+        let error = check_metadata(&basic, &large).unwrap_err();
+        assert_eq!(error.limit(), "metadata_bytes");
+        check_metadata(&enterprise, &large).unwrap();
+
+        assert!(check_attachments(&basic, 5).is_ok());
+        assert!(check_attachments(&basic, 6).is_err());
+    }
+}
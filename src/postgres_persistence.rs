@@ -15,7 +15,8 @@ use tokio::sync::{mpsc, oneshot};
 use tokio::time::{sleep, timeout};
 
 use chrono::{DateTime, NaiveDateTime, Utc};
-use deadpool_postgres::{Manager, ManagerConfig, Pool, RecyclingMethod, Runtime};
+use deadpool_postgres::{GenericClient, Manager, ManagerConfig, Pool, RecyclingMethod, Runtime};
+use tokio_postgres::types::ToSql;
 use tokio_postgres::{NoTls, Row};
 use uuid::Uuid;
 
@@ -23,6 +24,27 @@ use crate::identifier_types::{ExternalAlias, IdentifierType};
 use crate::types::*;
 use serde_json::json;
 
+/// Owned query parameter of a dynamically built statement
+type SqlParam = Box<dyn ToSql + Sync + Send>;
+
+/// Append a parameter and return its `$n` placeholder
+fn bind(params: &mut Vec<SqlParam>, value: SqlParam) -> String {
+    params.push(value);
+    format!("${}", params.len())
+}
+
+/// Which rows `load_items_matching` reads
+#[derive(Clone, Copy)]
+enum ItemSelection<'a> {
+    All,
+    Page(Option<&'a crate::pagination::PageCursor>, usize),
+    Search(
+        &'a ItemSearchQuery,
+        Option<&'a crate::pagination::PageCursor>,
+        usize,
+    ),
+}
+
 /// PostgreSQL persistence manager with circuit breaker
 #[derive(Clone)]
 pub struct PostgresPersistence {
@@ -73,7 +95,7 @@ pub struct PersistMetricsSnapshot {
 
 #[derive(Clone)]
 enum PersistCommand {
-    Circuit(Circuit),
+    Circuit(Box<Circuit>),
     User(UserAccount),
     Item(Item),
     Event(Event),
@@ -262,6 +284,244 @@ impl PostgresPersistence {
                 "V9__create_audit_events",
                 include_str!("../config/migrations/V9__create_audit_events.sql"),
             ),
+            (
+                "V10__add_occurred_at",
+                include_str!("../config/migrations/V10__add_occurred_at.sql"),
+            ),
+            (
+                "V11__item_search_index",
+                include_str!("../config/migrations/V11__item_search_index.sql"),
+            ),
+            (
+                "V12__add_user_locale",
+                include_str!("../config/migrations/V12__add_user_locale.sql"),
+            ),
+            (
+                "V13__add_event_signatures",
+                include_str!("../config/migrations/V13__add_event_signatures.sql"),
+            ),
+            (
+                "V14__add_event_attachments",
+                include_str!("../config/migrations/V14__add_event_attachments.sql"),
+            ),
+            (
+                "V15__add_event_causality",
+                include_str!("../config/migrations/V15__add_event_causality.sql"),
+            ),
+            (
+                "V16__add_circuit_archival",
+                include_str!("../config/migrations/V16__add_circuit_archival.sql"),
+            ),
+            (
+                "V17__add_circuit_hierarchy",
+                include_str!("../config/migrations/V17__add_circuit_hierarchy.sql"),
+            ),
+            (
+                "V18__create_adapter_intents",
+                include_str!("../config/migrations/V18__create_adapter_intents.sql"),
+            ),
+            (
+                "V19__add_circuit_item_expiry",
+                include_str!("../config/migrations/V19__add_circuit_item_expiry.sql"),
+            ),
+            (
+                "V20__add_circuit_ownership_transfer",
+                include_str!("../config/migrations/V20__add_circuit_ownership_transfer.sql"),
+            ),
+            (
+                "V21__add_circuit_join_requests",
+                include_str!("../config/migrations/V21__add_circuit_join_requests.sql"),
+            ),
+            (
+                "V22__create_zk_setup_artifacts",
+                include_str!("../config/migrations/V22__create_zk_setup_artifacts.sql"),
+            ),
+            (
+                "V23__add_zk_proof_anchor",
+                include_str!("../config/migrations/V23__add_zk_proof_anchor.sql"),
+            ),
+            (
+                "V24__create_device_backups",
+                include_str!("../config/migrations/V24__create_device_backups.sql"),
+            ),
+            (
+                "V25__create_federation",
+                include_str!("../config/migrations/V25__create_federation.sql"),
+            ),
+            (
+                "V26__add_zk_proof_revocations",
+                include_str!("../config/migrations/V26__add_zk_proof_revocations.sql"),
+            ),
+            (
+                "V27__create_selective_disclosures",
+                include_str!("../config/migrations/V27__create_selective_disclosures.sql"),
+            ),
+            (
+                "V28__add_federation_peer_trust",
+                include_str!("../config/migrations/V28__add_federation_peer_trust.sql"),
+            ),
+            (
+                "V29__create_custom_zk_circuits",
+                include_str!("../config/migrations/V29__create_custom_zk_circuits.sql"),
+            ),
+            (
+                "V30__create_zk_proof_jobs",
+                include_str!("../config/migrations/V30__create_zk_proof_jobs.sql"),
+            ),
+            (
+                "V31__create_usage_anomalies",
+                include_str!("../config/migrations/V31__create_usage_anomalies.sql"),
+            ),
+            (
+                "V32__create_proof_share_links",
+                include_str!("../config/migrations/V32__create_proof_share_links.sql"),
+            ),
+            (
+                "V33__create_workspace_dedup_configs",
+                include_str!("../config/migrations/V33__create_workspace_dedup_configs.sql"),
+            ),
+            (
+                "V34__create_sensor_timeseries",
+                include_str!("../config/migrations/V34__create_sensor_timeseries.sql"),
+            ),
+            (
+                "V35__create_workspace_bucket_configs",
+                include_str!("../config/migrations/V35__create_workspace_bucket_configs.sql"),
+            ),
+            (
+                "V36__create_dfid_sequence_leases",
+                include_str!("../config/migrations/V36__create_dfid_sequence_leases.sql"),
+            ),
+            (
+                "V37__create_pending_items",
+                include_str!("../config/migrations/V37__create_pending_items.sql"),
+            ),
+            (
+                "V38__create_reverification_jobs",
+                include_str!("../config/migrations/V38__create_reverification_jobs.sql"),
+            ),
+            (
+                "V39__create_runbook_hooks",
+                include_str!("../config/migrations/V39__create_runbook_hooks.sql"),
+            ),
+            (
+                "V40__create_workspace_verification_rules",
+                include_str!("../config/migrations/V40__create_workspace_verification_rules.sql"),
+            ),
+            (
+                "V41__create_dedup_sharing_agreements",
+                include_str!("../config/migrations/V41__create_dedup_sharing_agreements.sql"),
+            ),
+            (
+                "V42__create_membership_reconciliation_reports",
+                include_str!(
+                    "../config/migrations/V42__create_membership_reconciliation_reports.sql"
+                ),
+            ),
+            (
+                "V43__create_organization_profiles",
+                include_str!("../config/migrations/V43__create_organization_profiles.sql"),
+            ),
+            (
+                "V44__create_attestations",
+                include_str!("../config/migrations/V44__create_attestations.sql"),
+            ),
+            (
+                "V45__create_saved_audit_queries",
+                include_str!("../config/migrations/V45__create_saved_audit_queries.sql"),
+            ),
+            (
+                "V46__create_preview_environments",
+                include_str!("../config/migrations/V46__create_preview_environments.sql"),
+            ),
+            (
+                "V47__create_legacy_connectors",
+                include_str!("../config/migrations/V47__create_legacy_connectors.sql"),
+            ),
+            (
+                "V48__create_change_feed",
+                include_str!("../config/migrations/V48__create_change_feed.sql"),
+            ),
+            (
+                "V49__create_announcements",
+                include_str!("../config/migrations/V49__create_announcements.sql"),
+            ),
+            (
+                "V50__create_workspace_engagement",
+                include_str!("../config/migrations/V50__create_workspace_engagement.sql"),
+            ),
+            (
+                "V51__create_data_export_jobs",
+                include_str!("../config/migrations/V51__create_data_export_jobs.sql"),
+            ),
+            (
+                "V52__create_sla_windows",
+                include_str!("../config/migrations/V52__create_sla_windows.sql"),
+            ),
+            (
+                "V53__create_event_schemas",
+                include_str!("../config/migrations/V53__create_event_schemas.sql"),
+            ),
+            (
+                "V54__create_maintenance_mode",
+                include_str!("../config/migrations/V54__create_maintenance_mode.sql"),
+            ),
+            (
+                "V55__create_managed_keys",
+                include_str!("../config/migrations/V55__create_managed_keys.sql"),
+            ),
+            (
+                "V56__create_custom_event_types",
+                include_str!("../config/migrations/V56__create_custom_event_types.sql"),
+            ),
+            (
+                "V57__create_document_notarizations",
+                include_str!("../config/migrations/V57__create_document_notarizations.sql"),
+            ),
+            (
+                "V58__create_enrichment_policies",
+                include_str!("../config/migrations/V58__create_enrichment_policies.sql"),
+            ),
+            (
+                "V59__create_event_signing_keys",
+                include_str!("../config/migrations/V59__create_event_signing_keys.sql"),
+            ),
+            (
+                "V60__create_anchoring_costs",
+                include_str!("../config/migrations/V60__create_anchoring_costs.sql"),
+            ),
+            (
+                "V61__create_item_lifecycles",
+                include_str!("../config/migrations/V61__create_item_lifecycles.sql"),
+            ),
+            (
+                "V62__create_access_reviews",
+                include_str!("../config/migrations/V62__create_access_reviews.sql"),
+            ),
+            (
+                "V63__create_partner_tokens",
+                include_str!("../config/migrations/V63__create_partner_tokens.sql"),
+            ),
+            (
+                "V64__create_circuit_comments",
+                include_str!("../config/migrations/V64__create_circuit_comments.sql"),
+            ),
+            (
+                "V65__create_data_quality_reports",
+                include_str!("../config/migrations/V65__create_data_quality_reports.sql"),
+            ),
+            (
+                "V66__create_metric_definitions",
+                include_str!("../config/migrations/V66__create_metric_definitions.sql"),
+            ),
+            (
+                "V67__add_zk_proof_item_dfid",
+                include_str!("../config/migrations/V67__add_zk_proof_item_dfid.sql"),
+            ),
+            (
+                "V68__create_data_lake_gc_reports",
+                include_str!("../config/migrations/V68__create_data_lake_gc_reports.sql"),
+            ),
         ];
 
         for (name, migration_sql) in migrations {
@@ -599,6 +859,16 @@ impl PostgresPersistence {
             .transpose()
             .map_err(|e| format!("Failed to serialize post_action_settings: {e}"))?;
 
+        let ownership_transfer_json = circuit
+            .pending_ownership_transfer
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| format!("Failed to serialize pending_ownership_transfer: {e}"))?;
+
+        let join_requests_json = serde_json::to_value(&circuit.pending_requests)
+            .map_err(|e| format!("Failed to serialize join_requests: {e}"))?;
+
         let status_str = match circuit.status {
             CircuitStatus::Active => "Active",
             CircuitStatus::Inactive => "Inactive",
@@ -611,11 +881,14 @@ impl PostgresPersistence {
                 "INSERT INTO circuits (
                 circuit_id, name, description, owner_id, status,
                 created_at_ts, last_modified_ts, permissions, default_namespace,
-                alias_config, adapter_config, public_settings, post_action_settings
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                alias_config, adapter_config, public_settings, post_action_settings,
+                archived_at_ts, archived_by, parent_circuit_id, parent_access,
+                pending_ownership_transfer, join_requests
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)
             ON CONFLICT (circuit_id) DO UPDATE SET
                 name = EXCLUDED.name,
                 description = EXCLUDED.description,
+                owner_id = EXCLUDED.owner_id,
                 status = EXCLUDED.status,
                 last_modified_ts = EXCLUDED.last_modified_ts,
                 permissions = EXCLUDED.permissions,
@@ -623,7 +896,13 @@ impl PostgresPersistence {
                 alias_config = EXCLUDED.alias_config,
                 adapter_config = EXCLUDED.adapter_config,
                 public_settings = EXCLUDED.public_settings,
-                post_action_settings = EXCLUDED.post_action_settings",
+                post_action_settings = EXCLUDED.post_action_settings,
+                archived_at_ts = EXCLUDED.archived_at_ts,
+                archived_by = EXCLUDED.archived_by,
+                parent_circuit_id = EXCLUDED.parent_circuit_id,
+                parent_access = EXCLUDED.parent_access,
+                pending_ownership_transfer = EXCLUDED.pending_ownership_transfer,
+                join_requests = EXCLUDED.join_requests",
                 &[
                     &circuit.circuit_id,
                     &circuit.name,
@@ -638,6 +917,12 @@ impl PostgresPersistence {
                     &adapter_config_json,
                     &public_settings_json,
                     &post_action_json,
+                    &circuit.archived_at.map(|t| t.timestamp()),
+                    &circuit.archived_by,
+                    &circuit.parent_circuit_id,
+                    &circuit.parent_access.as_str(),
+                    &ownership_transfer_json,
+                    &join_requests_json,
                 ],
             )
             .await
@@ -721,7 +1006,7 @@ impl PostgresPersistence {
         Ok(())
     }
 
-    /// Load all circuits from PostgreSQL on startup
+    /// Load all circuits from PostgreSQL on startup, archived ones included
     /// OPTIMIZED: Uses a single JOIN query instead of N+1 queries
     pub async fn load_circuits(&self) -> Result<Vec<Circuit>, String> {
         if !self.is_connected().await {
@@ -738,6 +1023,8 @@ impl PostgresPersistence {
                     c.circuit_id, c.name, c.description, c.owner_id, c.status,
                     c.created_at_ts, c.last_modified_ts, c.permissions, c.default_namespace,
                     c.alias_config, c.adapter_config, c.public_settings, c.post_action_settings,
+                    c.archived_at_ts, c.archived_by, c.parent_circuit_id, c.parent_access,
+                    c.pending_ownership_transfer, c.join_requests,
                     COALESCE(
                         json_agg(
                             DISTINCT jsonb_build_object(
@@ -763,7 +1050,6 @@ impl PostgresPersistence {
                 FROM circuits c
                 LEFT JOIN circuit_members cm ON c.circuit_id = cm.circuit_id
                 LEFT JOIN circuit_custom_roles ccr ON c.circuit_id = ccr.circuit_id
-                WHERE c.status != 'Archived'
                 GROUP BY c.circuit_id
                 ORDER BY c.created_at_ts DESC",
                 &[],
@@ -825,8 +1111,20 @@ impl PostgresPersistence {
             .transpose()
             .map_err(|e| format!("Failed to parse post_action_settings: {e}"))?;
 
+        let pending_ownership_transfer: Option<serde_json::Value> =
+            row.get("pending_ownership_transfer");
+        let pending_ownership_transfer = pending_ownership_transfer
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| format!("Failed to parse pending_ownership_transfer: {e}"))?;
+
+        let join_requests: serde_json::Value = row.get("join_requests");
+        let pending_requests = serde_json::from_value(join_requests)
+            .map_err(|e| format!("Failed to parse join_requests: {e}"))?;
+
         let created_at_ts: i64 = row.get("created_at_ts");
         let last_modified_ts: i64 = row.get("last_modified_ts");
+        let archived_at_ts: Option<i64> = row.get("archived_at_ts");
 
         Ok(Circuit {
             circuit_id: row.get("circuit_id"),
@@ -840,11 +1138,19 @@ impl PostgresPersistence {
             members: Vec::new(), // Load separately if needed
             permissions,
             status,
-            pending_requests: Vec::new(), // Load separately if needed
-            custom_roles: Vec::new(),     // Load separately if needed
+            pending_requests,
+            custom_roles: Vec::new(), // Load separately if needed
             public_settings,
             adapter_config,
             post_action_settings,
+            archived_at: archived_at_ts.and_then(|ts| DateTime::from_timestamp(ts, 0)),
+            archived_by: row.get("archived_by"),
+            parent_circuit_id: row.get("parent_circuit_id"),
+            parent_access: match row.get::<_, &str>("parent_access") {
+                "none" => ParentAccess::None,
+                _ => ParentAccess::Read,
+            },
+            pending_ownership_transfer,
         })
     }
 
@@ -1174,8 +1480,8 @@ impl PostgresPersistence {
             .execute(
                 "INSERT INTO user_accounts (
                 user_id, username, email, password_hash, tier, status,
-                is_admin, workspace_id, created_at_ts, last_login_ts, available_adapters
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                is_admin, workspace_id, created_at_ts, last_login_ts, available_adapters, locale
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             ON CONFLICT (user_id) DO UPDATE SET
                 username = EXCLUDED.username,
                 email = EXCLUDED.email,
@@ -1186,6 +1492,7 @@ impl PostgresPersistence {
                 workspace_id = EXCLUDED.workspace_id,
                 last_login_ts = EXCLUDED.last_login_ts,
                 available_adapters = EXCLUDED.available_adapters,
+                locale = EXCLUDED.locale,
                 updated_at = NOW()",
                 &[
                     &user.user_id,
@@ -1199,6 +1506,7 @@ impl PostgresPersistence {
                     &user.created_at.timestamp(),
                     &user.last_login.map(|t| t.timestamp()),
                     &adapters_array,
+                    &user.locale,
                 ],
             )
             .await
@@ -1232,6 +1540,7 @@ impl PostgresPersistence {
         let rows = client.query(
             "SELECT u.user_id, u.username, u.email, u.password_hash, u.tier, u.status,
                     u.is_admin, u.workspace_id, u.created_at_ts, u.last_login_ts, u.available_adapters,
+                    u.locale,
                     COALESCE(c.credits, 0) as credits
              FROM user_accounts u
              LEFT JOIN credit_balances c ON u.user_id = c.user_id
@@ -1535,8 +1844,8 @@ impl PostgresPersistence {
         let aliases_json = serde_json::to_value(&item.aliases).unwrap_or(serde_json::Value::Null);
 
         client.execute(
-            "INSERT INTO items (dfid, item_hash, status, created_at_ts, last_updated_ts, enriched_data, legacy_mode, fingerprint, aliases, confidence_score)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "INSERT INTO items (dfid, item_hash, status, created_at_ts, last_updated_ts, enriched_data, legacy_mode, fingerprint, aliases, confidence_score, first_occurred_at_ts, last_occurred_at_ts)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
              ON CONFLICT (dfid) DO UPDATE SET
                 status = EXCLUDED.status,
                 last_updated_ts = EXCLUDED.last_updated_ts,
@@ -1545,6 +1854,8 @@ impl PostgresPersistence {
                 fingerprint = EXCLUDED.fingerprint,
                 aliases = EXCLUDED.aliases,
                 confidence_score = EXCLUDED.confidence_score,
+                first_occurred_at_ts = EXCLUDED.first_occurred_at_ts,
+                last_occurred_at_ts = EXCLUDED.last_occurred_at_ts,
                 updated_at = NOW()",
             &[
                 &item.dfid,
@@ -1557,6 +1868,8 @@ impl PostgresPersistence {
                 &item.fingerprint,
                 &aliases_json,
                 &item.confidence_score,
+                &item.first_occurred_at.map(|t| t.timestamp()),
+                &item.last_occurred_at.map(|t| t.timestamp()),
             ],
         ).await
         .map_err(|e| format!("Failed to persist item: {e}"))?;
@@ -1630,18 +1943,205 @@ impl PostgresPersistence {
             .await
     }
 
+    /// Build the item search statement. Text words become `word:*` prefix
+    /// terms; `tokenize` only yields alphanumerics, so they cannot alter the
+    /// tsquery. A summary projection selects only the list view columns.
+    fn item_search_sql(
+        query: &ItemSearchQuery,
+        after: Option<&crate::pagination::PageCursor>,
+        limit: usize,
+        projection: ItemProjection,
+    ) -> (String, Vec<SqlParam>) {
+        let mut clauses: Vec<String> = Vec::new();
+        let mut params: Vec<SqlParam> = Vec::new();
+
+        if let Some(text) = &query.text {
+            let terms: Vec<String> = crate::search_index::tokenize(text)
+                .into_iter()
+                .map(|word| format!("{word}:*"))
+                .collect();
+            if !terms.is_empty() {
+                let p = bind(&mut params, Box::new(terms.join(" & ")));
+                clauses.push(format!("search_vector @@ to_tsquery('simple', {p})"));
+            }
+        }
+        for filter in &query.identifiers {
+            let key = bind(&mut params, Box::new(filter.key.clone()));
+            let value_clause = match &filter.value {
+                Some(value) => format!(
+                    " AND ii.value = {}",
+                    bind(&mut params, Box::new(value.clone()))
+                ),
+                None => String::new(),
+            };
+            clauses.push(format!(
+                "EXISTS (SELECT 1 FROM item_identifiers ii WHERE ii.dfid = items.dfid AND ii.key = {key}{value_clause})"
+            ));
+        }
+        if let Some(status) = &query.status {
+            let p = bind(&mut params, Box::new(Self::item_status_to_code(status)));
+            clauses.push(format!("status = {p}"));
+        }
+        if let Some(from) = query.created_from {
+            let p = bind(&mut params, Box::new(from.timestamp()));
+            clauses.push(format!("created_at_ts >= {p}"));
+        }
+        if let Some(to) = query.created_to {
+            let p = bind(&mut params, Box::new(to.timestamp()));
+            clauses.push(format!("created_at_ts <= {p}"));
+        }
+        if let Some(from) = query.occurred_from {
+            let p = bind(&mut params, Box::new(from.timestamp()));
+            clauses.push(format!(
+                "COALESCE(last_occurred_at_ts, created_at_ts) >= {p}"
+            ));
+        }
+        if let Some(to) = query.occurred_to {
+            let p = bind(&mut params, Box::new(to.timestamp()));
+            clauses.push(format!(
+                "COALESCE(first_occurred_at_ts, created_at_ts) <= {p}"
+            ));
+        }
+        if let Some(circuit_id) = query.circuit_id {
+            let p = bind(&mut params, Box::new(circuit_id));
+            clauses.push(format!(
+                "EXISTS (SELECT 1 FROM circuit_items ci WHERE ci.dfid = items.dfid AND ci.circuit_id = {p})"
+            ));
+        }
+        if let Some(cursor) = after {
+            let ts = bind(&mut params, Box::new(cursor.created_at.timestamp()));
+            let dfid = bind(&mut params, Box::new(cursor.id.clone()));
+            clauses.push(format!("(created_at_ts, dfid) > ({ts}, {dfid})"));
+        }
+        let limit = bind(&mut params, Box::new(limit as i64));
+
+        let filter = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+        let columns = match projection {
+            ItemProjection::Full => {
+                "dfid, status, created_at_ts, last_updated_ts, enriched_data, legacy_mode,
+                    fingerprint, aliases, confidence_score, first_occurred_at_ts, last_occurred_at_ts"
+            }
+            ItemProjection::Summary => "dfid, status, created_at_ts, last_updated_ts",
+        };
+        let sql = format!(
+            "SELECT {columns}
+             FROM items
+             {filter}
+             ORDER BY created_at_ts, dfid
+             LIMIT {limit}"
+        );
+        (sql, params)
+    }
+
     pub async fn load_items(&self) -> Result<Vec<Item>, String> {
-        let client = self.get_client().await?;
+        let items = self.load_items_matching(ItemSelection::All).await?;
+        tracing::info!("✅ Loaded {} items from PostgreSQL", items.len());
+        Ok(items)
+    }
 
-        let item_rows = client
-            .query(
-                "SELECT dfid, status, created_at_ts, last_updated_ts, enriched_data, legacy_mode,
-                        fingerprint, aliases, confidence_score
-                 FROM items",
-                &[],
-            )
+    /// Load up to `limit` items created after `after`, ordered by (created_at_ts, dfid)
+    pub async fn load_items_page(
+        &self,
+        after: Option<&crate::pagination::PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Item>, String> {
+        self.load_items_matching(ItemSelection::Page(after, limit))
+            .await
+    }
+
+    /// One page of items matching `query`, served by the `search_vector` GIN index
+    /// and the identifier/circuit indexes of migration V11
+    pub async fn search_items_page(
+        &self,
+        query: &ItemSearchQuery,
+        after: Option<&crate::pagination::PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Item>, String> {
+        self.load_items_matching(ItemSelection::Search(query, after, limit))
+            .await
+    }
+
+    /// One page of item summaries matching `query`. Reads only the summary
+    /// columns of `items`, skipping the identifier and source entry tables.
+    pub async fn search_item_summaries_page(
+        &self,
+        query: &ItemSearchQuery,
+        after: Option<&crate::pagination::PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<ItemSummary>, String> {
+        let client = self.get_client().await?;
+        let (sql, params) = Self::item_search_sql(query, after, limit, ItemProjection::Summary);
+        let params: Vec<&(dyn ToSql + Sync)> = params
+            .iter()
+            .map(|param| param.as_ref() as &(dyn ToSql + Sync))
+            .collect();
+        let rows = client
+            .query(&sql, &params)
             .await
-            .map_err(|e| format!("Failed to load items: {e}"))?;
+            .map_err(|e| format!("Failed to load item summaries: {e}"))?;
+
+        rows.iter()
+            .map(|row| {
+                let dfid: String = row.get("dfid");
+                let status_code: String = row.get("status");
+                let status = Self::item_status_from_code(&status_code)
+                    .map_err(|e| format!("Failed to parse status for item {dfid}: {e}"))?;
+                let timestamp = |column: &str| {
+                    DateTime::<Utc>::from_timestamp(row.get::<_, i64>(column), 0)
+                        .ok_or_else(|| format!("Invalid {column} for item {dfid}"))
+                };
+                Ok(ItemSummary {
+                    created_at: timestamp("created_at_ts")?,
+                    updated_at: timestamp("last_updated_ts")?,
+                    status,
+                    dfid,
+                })
+            })
+            .collect()
+    }
+
+    async fn load_items_matching(&self, selection: ItemSelection<'_>) -> Result<Vec<Item>, String> {
+        let client = self.get_client().await?;
+
+        let item_rows = match selection {
+            ItemSelection::All => client
+                .query(
+                    "SELECT dfid, status, created_at_ts, last_updated_ts, enriched_data, legacy_mode,
+                            fingerprint, aliases, confidence_score, first_occurred_at_ts, last_occurred_at_ts
+                     FROM items",
+                    &[],
+                )
+                .await,
+            ItemSelection::Page(after, limit) => {
+                let after_ts = after.map(|cursor| cursor.created_at.timestamp());
+                let after_dfid = after.map(|cursor| cursor.id.clone());
+                client
+                    .query(
+                        "SELECT dfid, status, created_at_ts, last_updated_ts, enriched_data, legacy_mode,
+                                fingerprint, aliases, confidence_score, first_occurred_at_ts, last_occurred_at_ts
+                         FROM items
+                         WHERE $1::BIGINT IS NULL OR (created_at_ts, dfid) > ($1, $2::TEXT)
+                         ORDER BY created_at_ts, dfid
+                         LIMIT $3",
+                        &[&after_ts, &after_dfid, &(limit as i64)],
+                    )
+                    .await
+            }
+            ItemSelection::Search(query, after, limit) => {
+                let (sql, params) =
+                    Self::item_search_sql(query, after, limit, ItemProjection::Full);
+                let params: Vec<&(dyn ToSql + Sync)> = params
+                    .iter()
+                    .map(|param| param.as_ref() as &(dyn ToSql + Sync))
+                    .collect();
+                client.query(&sql, &params).await
+            }
+        }
+        .map_err(|e| format!("Failed to load items: {e}"))?;
 
         let mut items_map: HashMap<String, Item> = HashMap::new();
 
@@ -1687,6 +2187,8 @@ impl PostgresPersistence {
             };
 
             let confidence_score: f64 = row.get("confidence_score");
+            let first_occurred_at_ts: Option<i64> = row.get("first_occurred_at_ts");
+            let last_occurred_at_ts: Option<i64> = row.get("last_occurred_at_ts");
 
             let item = Item {
                 dfid: dfid.clone(),
@@ -1701,6 +2203,10 @@ impl PostgresPersistence {
                 source_entries: Vec::new(),
                 confidence_score,
                 status,
+                first_occurred_at: first_occurred_at_ts
+                    .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+                last_occurred_at: last_occurred_at_ts
+                    .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
             };
 
             items_map.insert(dfid, item);
@@ -1710,14 +2216,34 @@ impl PostgresPersistence {
             return Ok(Vec::new());
         }
 
-        let identifier_rows = client
-            .query(
-                "SELECT dfid, namespace, key, value, id_type, type_metadata
-                 FROM item_identifiers",
-                &[],
-            )
-            .await
-            .map_err(|e| format!("Failed to load item identifiers: {e}"))?;
+        // A page only needs the child rows of its own items
+        let dfids: Option<Vec<String>> = match selection {
+            ItemSelection::All => None,
+            _ => Some(items_map.keys().cloned().collect()),
+        };
+
+        let identifier_rows = match &dfids {
+            None => {
+                client
+                    .query(
+                        "SELECT dfid, namespace, key, value, id_type, type_metadata
+                     FROM item_identifiers",
+                        &[],
+                    )
+                    .await
+            }
+            Some(dfids) => {
+                client
+                    .query(
+                        "SELECT dfid, namespace, key, value, id_type, type_metadata
+                     FROM item_identifiers
+                     WHERE dfid = ANY($1)",
+                        &[dfids],
+                    )
+                    .await
+            }
+        }
+        .map_err(|e| format!("Failed to load item identifiers: {e}"))?;
 
         for row in identifier_rows {
             let dfid: String = row.get("dfid");
@@ -1739,14 +2265,28 @@ impl PostgresPersistence {
             }
         }
 
-        let source_rows = client
-            .query(
-                "SELECT dfid, entry_id
-                 FROM item_source_entries",
-                &[],
-            )
-            .await
-            .map_err(|e| format!("Failed to load item source entries: {e}"))?;
+        let source_rows = match &dfids {
+            None => {
+                client
+                    .query(
+                        "SELECT dfid, entry_id
+                     FROM item_source_entries",
+                        &[],
+                    )
+                    .await
+            }
+            Some(dfids) => {
+                client
+                    .query(
+                        "SELECT dfid, entry_id
+                     FROM item_source_entries
+                     WHERE dfid = ANY($1)",
+                        &[dfids],
+                    )
+                    .await
+            }
+        }
+        .map_err(|e| format!("Failed to load item source entries: {e}"))?;
 
         for row in source_rows {
             let dfid: String = row.get("dfid");
@@ -1756,14 +2296,28 @@ impl PostgresPersistence {
             }
         }
 
-        let lid_rows = client
-            .query(
-                "SELECT local_id, dfid
-                 FROM lid_dfid_mappings",
-                &[],
-            )
-            .await
-            .map_err(|e| format!("Failed to load LID mappings: {e}"))?;
+        let lid_rows = match &dfids {
+            None => {
+                client
+                    .query(
+                        "SELECT local_id, dfid
+                     FROM lid_dfid_mappings",
+                        &[],
+                    )
+                    .await
+            }
+            Some(dfids) => {
+                client
+                    .query(
+                        "SELECT local_id, dfid
+                     FROM lid_dfid_mappings
+                     WHERE dfid = ANY($1)",
+                        &[dfids],
+                    )
+                    .await
+            }
+        }
+        .map_err(|e| format!("Failed to load LID mappings: {e}"))?;
 
         for row in lid_rows {
             let dfid: String = row.get("dfid");
@@ -1774,9 +2328,7 @@ impl PostgresPersistence {
         }
 
         let mut items: Vec<Item> = items_map.into_values().collect();
-        items.sort_by_key(|item| item.creation_timestamp);
-
-        tracing::info!("✅ Loaded {} items from PostgreSQL", items.len());
+        items.sort_by(|a, b| (a.creation_timestamp, &a.dfid).cmp(&(b.creation_timestamp, &b.dfid)));
         Ok(items)
     }
 
@@ -1791,7 +2343,38 @@ impl PostgresPersistence {
         }
 
         let client = self.get_client().await?;
+        Self::upsert_event(&client, event).await
+    }
+
+    /// Store a batch of events in one transaction: either every event is
+    /// written or none is
+    pub async fn persist_events(&self, events: &[crate::types::Event]) -> Result<(), String> {
+        if let Err(e) = self.wait_for_connection(10).await {
+            tracing::debug!(
+                "⏳ Waiting for PostgreSQL connection before persisting {} events...",
+                events.len()
+            );
+            return Err(e);
+        }
+
+        let mut client = self.get_client().await?;
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+        for event in events {
+            Self::upsert_event(&transaction, event).await?;
+        }
+        transaction
+            .commit()
+            .await
+            .map_err(|e| format!("Failed to commit events: {e}"))
+    }
 
+    async fn upsert_event(
+        client: &impl GenericClient,
+        event: &crate::types::Event,
+    ) -> Result<(), String> {
         // Serialize encrypted_data if encrypted
         let encrypted_data: Option<Vec<u8>> = if event.is_encrypted {
             // For now, we'll store the content_hash as encrypted_data
@@ -1801,8 +2384,8 @@ impl PostgresPersistence {
         };
 
         client.execute(
-            "INSERT INTO events (event_id, event_type, dfid, timestamp, visibility, encrypted_data, metadata, content_hash, source)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "INSERT INTO events (event_id, event_type, dfid, timestamp, visibility, encrypted_data, metadata, content_hash, source, occurred_at_ts, signature, signer_public_key, attachments, caused_by, correlation_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
              ON CONFLICT (event_id) DO UPDATE SET
                 event_type = EXCLUDED.event_type,
                 dfid = EXCLUDED.dfid,
@@ -1811,10 +2394,16 @@ impl PostgresPersistence {
                 encrypted_data = EXCLUDED.encrypted_data,
                 metadata = EXCLUDED.metadata,
                 content_hash = EXCLUDED.content_hash,
-                source = EXCLUDED.source",
+                source = EXCLUDED.source,
+                occurred_at_ts = EXCLUDED.occurred_at_ts,
+                signature = EXCLUDED.signature,
+                signer_public_key = EXCLUDED.signer_public_key,
+                attachments = EXCLUDED.attachments,
+                caused_by = EXCLUDED.caused_by,
+                correlation_id = EXCLUDED.correlation_id",
             &[
                 &event.event_id,
-                &format!("{:?}", event.event_type),
+                &event.event_type.to_string(),
                 &event.dfid,
                 &event.timestamp.timestamp(),
                 &format!("{:?}", event.visibility),
@@ -1822,6 +2411,12 @@ impl PostgresPersistence {
                 &serde_json::to_value(&event.metadata).unwrap_or(serde_json::Value::Null),
                 &event.content_hash,
                 &event.source,
+                &event.occurred_at.map(|t| t.timestamp()),
+                &event.signature,
+                &event.signer_public_key,
+                &serde_json::to_value(&event.attachments).unwrap_or(serde_json::Value::Null),
+                &event.caused_by,
+                &event.correlation_id,
             ],
         ).await
         .map_err(|e| format!("Failed to persist event: {e}"))?;
@@ -1894,6 +2489,7 @@ impl PostgresPersistence {
             is_admin: row.get("is_admin"),
             workspace_id: row.get("workspace_id"),
             available_adapters, // Now properly parsed from PostgreSQL
+            locale: row.get("locale"),
         })
     }
 
@@ -1929,6 +2525,9 @@ impl PostgresPersistence {
             pushed_by: row.get("added_by"),
             pushed_at,
             permissions: Vec::new(),
+            expires_at: row
+                .get::<_, Option<i64>>("expires_at_ts")
+                .and_then(|ts| DateTime::from_timestamp(ts, 0)),
         })
     }
 
@@ -2128,17 +2727,19 @@ impl PostgresPersistence {
 
         client
             .execute(
-                "INSERT INTO circuit_items (circuit_id, dfid, added_at_ts, added_by, created_at)
-                 VALUES ($1, $2, $3, $4, NOW())
+                "INSERT INTO circuit_items (circuit_id, dfid, added_at_ts, added_by, expires_at_ts, created_at)
+                 VALUES ($1, $2, $3, $4, $5, NOW())
                  ON CONFLICT (circuit_id, dfid) DO UPDATE
                  SET added_at_ts = EXCLUDED.added_at_ts,
                      added_by = EXCLUDED.added_by,
+                     expires_at_ts = EXCLUDED.expires_at_ts,
                      created_at = NOW()",
                 &[
                     &item.circuit_id,
                     &item.dfid,
                     &item.pushed_at.timestamp(),
                     &item.pushed_by,
+                    &item.expires_at.map(|ts| ts.timestamp()),
                 ],
             )
             .await
@@ -2157,7 +2758,7 @@ impl PostgresPersistence {
 
         let rows = client
             .query(
-                "SELECT circuit_id, dfid, added_at_ts, added_by
+                "SELECT circuit_id, dfid, added_at_ts, added_by, expires_at_ts
                  FROM circuit_items
                  WHERE circuit_id = $1
                  ORDER BY added_at_ts DESC",
@@ -2511,23 +3112,149 @@ impl PostgresPersistence {
     }
 
     // ========================================================================
-    // ADAPTER CONFIGS PERSISTENCE
+    // ADAPTER INTENTS PERSISTENCE
     // ========================================================================
 
-    /// Persist adapter config to PostgreSQL
-    pub async fn persist_adapter_config(&self, config: &AdapterConfig) -> Result<(), String> {
-        self.enqueue_persist(
-            "persist_adapter_config",
-            PersistCommand::AdapterConfig(config.clone()),
-        )
-        .await
-    }
-
-    async fn persist_adapter_config_once(&self, config: &AdapterConfig) -> Result<(), String> {
+    /// Persist an adapter intent directly, bypassing the write queue: the intent
+    /// has to be durable before the adapter write it guards is started
+    pub async fn persist_adapter_intent(&self, intent: &AdapterIntent) -> Result<(), String> {
+        self.wait_for_connection(10).await?;
         let client = self.get_client().await?;
 
-        let connection_details_json = serde_json::to_value(&config.connection_details)
-            .map_err(|e| format!("Failed to serialize connection details: {e}"))?;
+        let storage_location_json = intent
+            .storage_location
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| format!("Failed to serialize storage location: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO adapter_intents
+             (intent_id, dfid, circuit_id, adapter_type, operation, is_new_dfid,
+              requested_by, status, attempts, last_error, storage_location,
+              created_at_ts, updated_at_ts, completed_at_ts)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+             ON CONFLICT (intent_id) DO UPDATE
+             SET status = EXCLUDED.status,
+                 attempts = EXCLUDED.attempts,
+                 last_error = EXCLUDED.last_error,
+                 storage_location = EXCLUDED.storage_location,
+                 updated_at_ts = EXCLUDED.updated_at_ts,
+                 completed_at_ts = EXCLUDED.completed_at_ts",
+                &[
+                    &intent.intent_id,
+                    &intent.dfid,
+                    &intent.circuit_id,
+                    &intent.adapter_type.to_string(),
+                    &intent.operation.as_str(),
+                    &intent.is_new_dfid,
+                    &intent.requested_by,
+                    &intent.status.as_str(),
+                    &(intent.attempts as i32),
+                    &intent.last_error,
+                    &storage_location_json,
+                    &intent.created_at.timestamp(),
+                    &intent.updated_at.timestamp(),
+                    &intent.completed_at.map(|t| t.timestamp()),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist adapter intent: {e}"))?;
+
+        Ok(())
+    }
+
+    pub async fn load_adapter_intent(
+        &self,
+        intent_id: &Uuid,
+    ) -> Result<Option<AdapterIntent>, String> {
+        let client = self.get_client().await?;
+
+        let row = client
+            .query_opt(
+                "SELECT * FROM adapter_intents WHERE intent_id = $1",
+                &[intent_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load adapter intent: {e}"))?;
+
+        Ok(row.as_ref().and_then(Self::row_to_adapter_intent))
+    }
+
+    /// Intents that are pending or failed, oldest first
+    pub async fn load_incomplete_adapter_intents(&self) -> Result<Vec<AdapterIntent>, String> {
+        let client = self.get_client().await?;
+
+        let rows = client
+            .query(
+                "SELECT * FROM adapter_intents
+                 WHERE status <> 'completed'
+                 ORDER BY created_at_ts ASC",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load adapter intents: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(Self::row_to_adapter_intent)
+            .collect())
+    }
+
+    fn row_to_adapter_intent(row: &Row) -> Option<AdapterIntent> {
+        let adapter_type_str: String = row.get("adapter_type");
+        let operation = match row.get::<_, String>("operation").as_str() {
+            "circuit_push" => AdapterOperation::CircuitPush,
+            "storage_migration" => AdapterOperation::StorageMigration,
+            _ => return None,
+        };
+        let status = match row.get::<_, String>("status").as_str() {
+            "completed" => AdapterIntentStatus::Completed,
+            "failed" => AdapterIntentStatus::Failed,
+            _ => AdapterIntentStatus::Pending,
+        };
+        let storage_location = row
+            .get::<_, Option<serde_json::Value>>("storage_location")
+            .and_then(|v| serde_json::from_value(v).ok());
+        let completed_at_ts: Option<i64> = row.get("completed_at_ts");
+
+        Some(AdapterIntent {
+            intent_id: row.get("intent_id"),
+            dfid: row.get("dfid"),
+            circuit_id: row.get("circuit_id"),
+            adapter_type: AdapterType::from_string(&adapter_type_str).ok()?,
+            operation,
+            is_new_dfid: row.get("is_new_dfid"),
+            requested_by: row.get("requested_by"),
+            status,
+            attempts: row.get::<_, i32>("attempts").max(0) as u32,
+            last_error: row.get("last_error"),
+            storage_location,
+            created_at: DateTime::from_timestamp(row.get("created_at_ts"), 0)?,
+            updated_at: DateTime::from_timestamp(row.get("updated_at_ts"), 0)?,
+            completed_at: completed_at_ts.and_then(|ts| DateTime::from_timestamp(ts, 0)),
+        })
+    }
+
+    // ========================================================================
+    // ADAPTER CONFIGS PERSISTENCE
+    // ========================================================================
+
+    /// Persist adapter config to PostgreSQL
+    pub async fn persist_adapter_config(&self, config: &AdapterConfig) -> Result<(), String> {
+        self.enqueue_persist(
+            "persist_adapter_config",
+            PersistCommand::AdapterConfig(config.clone()),
+        )
+        .await
+    }
+
+    async fn persist_adapter_config_once(&self, config: &AdapterConfig) -> Result<(), String> {
+        let client = self.get_client().await?;
+
+        let connection_details_json = serde_json::to_value(&config.connection_details)
+            .map_err(|e| format!("Failed to serialize connection details: {e}"))?;
 
         let contract_configs_json = config
             .contract_configs
@@ -2733,6 +3460,8 @@ impl PostgresPersistence {
                     auth_credentials: None,
                     enabled: row.get("enabled"),
                     retry_config,
+                    encryption_key: None,
+                    filter: WebhookFilter::default(),
                     created_at: DateTime::from_timestamp(created_at_ts, 0).unwrap_or_else(Utc::now),
                     updated_at: DateTime::from_timestamp(updated_at_ts, 0).unwrap_or_else(Utc::now),
                 })
@@ -2819,6 +3548,72 @@ impl PostgresPersistence {
         Ok(entries)
     }
 
+    /// Up to `limit` timeline entries after `after_sequence` within the
+    /// query's bounds, in sequence order
+    pub async fn get_item_timeline_page(
+        &self,
+        dfid: &str,
+        query: &crate::types::TimelineQuery,
+        after_sequence: Option<i32>,
+        limit: usize,
+    ) -> Result<Vec<TimelineEntry>, String> {
+        let client = self.get_client().await?;
+
+        let from_timestamp = query.from.map(|from| from.timestamp());
+        let to_timestamp = query.to.map(|to| to.timestamp());
+        let latest = query.latest.map(|latest| latest as i64);
+        // `latest` moves the lower bound up to the Nth newest matching entry
+        let rows = client
+            .query(
+                "WITH matching AS (
+                    SELECT id, dfid, cid, event_sequence, blockchain_timestamp,
+                           ipcm_transaction_hash, network, created_at
+                    FROM item_cid_timeline
+                    WHERE dfid = $1
+                      AND ($2::INT IS NULL OR event_sequence >= $2)
+                      AND ($3::INT IS NULL OR event_sequence <= $3)
+                      AND ($4::BIGINT IS NULL OR blockchain_timestamp >= $4)
+                      AND ($5::BIGINT IS NULL OR blockchain_timestamp <= $5)
+                 ),
+                 newest AS (
+                    SELECT event_sequence FROM matching
+                    ORDER BY event_sequence DESC
+                    LIMIT $6
+                 )
+                 SELECT * FROM matching
+                 WHERE event_sequence >= (SELECT COALESCE(MIN(event_sequence), 0) FROM newest)
+                   AND ($7::INT IS NULL OR event_sequence > $7)
+                 ORDER BY event_sequence ASC
+                 LIMIT $8",
+                &[
+                    &dfid,
+                    &query.from_sequence,
+                    &query.to_sequence,
+                    &from_timestamp,
+                    &to_timestamp,
+                    &latest,
+                    &after_sequence,
+                    &(limit as i64),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to get item timeline page: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| TimelineEntry {
+                id: row.get("id"),
+                dfid: row.get("dfid"),
+                cid: row.get("cid"),
+                event_sequence: row.get("event_sequence"),
+                blockchain_timestamp: row.get("blockchain_timestamp"),
+                ipcm_transaction_hash: row.get("ipcm_transaction_hash"),
+                network: row.get("network"),
+                created_at: row.get("created_at"),
+            })
+            .collect())
+    }
+
     /// Get a specific timeline entry by sequence number
     pub async fn get_timeline_by_sequence(
         &self,
@@ -3090,7 +3885,8 @@ impl PostgresPersistence {
         // Query uses actual database columns (no source, is_encrypted, content_hash columns)
         let rows = client
             .query(
-                "SELECT event_id, dfid, event_type, timestamp, visibility, encrypted_data, metadata
+                "SELECT event_id, dfid, event_type, timestamp, visibility, encrypted_data, metadata, occurred_at_ts,
+                        signature, signer_public_key, attachments, caused_by, correlation_id
                  FROM events
                  ORDER BY timestamp DESC",
                 &[],
@@ -3098,55 +3894,100 @@ impl PostgresPersistence {
             .await
             .map_err(|e| format!("Failed to load events: {e}"))?;
 
-        let mut events = Vec::new();
-        for row in rows {
-            let event_type_str: String = row.get(2);
-            let timestamp_secs: i64 = row.get(3);
-            let visibility_str: String = row.get(4);
-            let encrypted_data: Option<Vec<u8>> = row.get(5);
-            let metadata_json: serde_json::Value = row.get(6);
-
-            // Derive is_encrypted from presence of encrypted_data
-            let is_encrypted = encrypted_data.is_some();
-
-            // Derive content_hash from encrypted_data if present, else empty
-            let content_hash = encrypted_data
-                .as_ref()
-                .map(|d| String::from_utf8_lossy(d).to_string())
-                .unwrap_or_default();
-
-            // Extract source from metadata if present, else use default
-            let source = metadata_json
-                .get("source")
-                .and_then(|v| v.as_str())
-                .unwrap_or("system")
-                .to_string();
-
-            events.push(Event {
-                event_id: row.get(0),
-                dfid: row.get(1),
-                event_type: serde_json::from_str(&format!("\"{}\"", event_type_str))
-                    .unwrap_or(EventType::Created),
-                timestamp: DateTime::from_timestamp(timestamp_secs, 0)
-                    .unwrap_or_else(|| Utc::now()),
-                source,
-                metadata: serde_json::from_value(metadata_json).unwrap_or_default(),
-                is_encrypted,
-                visibility: serde_json::from_str(&format!("\"{}\"", visibility_str))
-                    .unwrap_or(EventVisibility::Private),
-                content_hash,
-                local_event_id: None,
-                is_local: false,
-                pushed_to_circuit: None,
-                snapshot_id: None,
-                snapshot_cid: None,
-            });
-        }
+        let events: Vec<Event> = rows.iter().map(Self::row_to_event).collect();
 
         tracing::debug!("✅ Loaded {} events from PostgreSQL", events.len());
         Ok(events)
     }
 
+    /// Map a row of `event_id, dfid, event_type, timestamp, visibility, encrypted_data,
+    /// metadata, occurred_at_ts, signature, signer_public_key, attachments, caused_by,
+    /// correlation_id` to an event
+    fn row_to_event(row: &Row) -> Event {
+        let event_type_str: String = row.get(2);
+        let timestamp_secs: i64 = row.get(3);
+        let visibility_str: String = row.get(4);
+        let encrypted_data: Option<Vec<u8>> = row.get(5);
+        let metadata_json: serde_json::Value = row.get(6);
+        let occurred_at_ts: Option<i64> = row.get(7);
+
+        // Derive is_encrypted from presence of encrypted_data
+        let is_encrypted = encrypted_data.is_some();
+
+        // Derive content_hash from encrypted_data if present, else empty
+        let content_hash = encrypted_data
+            .as_ref()
+            .map(|d| String::from_utf8_lossy(d).to_string())
+            .unwrap_or_default();
+
+        // Extract source from metadata if present, else use default
+        let source = metadata_json
+            .get("source")
+            .and_then(|v| v.as_str())
+            .unwrap_or("system")
+            .to_string();
+
+        Event {
+            event_id: row.get(0),
+            dfid: row.get(1),
+            event_type: EventType::from_name(&event_type_str),
+            timestamp: DateTime::from_timestamp(timestamp_secs, 0).unwrap_or_else(|| Utc::now()),
+            source,
+            metadata: serde_json::from_value(metadata_json).unwrap_or_default(),
+            is_encrypted,
+            visibility: serde_json::from_str(&format!("\"{}\"", visibility_str))
+                .unwrap_or(EventVisibility::Private),
+            content_hash,
+            local_event_id: None,
+            is_local: false,
+            pushed_to_circuit: None,
+            snapshot_id: None,
+            snapshot_cid: None,
+            occurred_at: occurred_at_ts.and_then(|ts| DateTime::from_timestamp(ts, 0)),
+            signature: row.get(8),
+            signer_public_key: row.get(9),
+            attachments: serde_json::from_value(row.get(10)).unwrap_or_default(),
+            caused_by: row.get(11),
+            correlation_id: row.get(12),
+        }
+    }
+
+    /// Load up to `limit` events recorded after `after`, ordered by (timestamp, event_id),
+    /// optionally restricted to one item
+    pub async fn load_events_page(
+        &self,
+        dfid: Option<&str>,
+        after: Option<&crate::pagination::PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Event>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let after_ts = after.map(|cursor| cursor.created_at.timestamp());
+        let after_id = after
+            .map(|cursor| Uuid::parse_str(&cursor.id))
+            .transpose()
+            .map_err(|_| "Invalid pagination cursor".to_string())?;
+
+        let rows = client
+            .query(
+                "SELECT event_id, dfid, event_type, timestamp, visibility, encrypted_data, metadata, occurred_at_ts,
+                        signature, signer_public_key, attachments, caused_by, correlation_id
+                 FROM events
+                 WHERE ($1::TEXT IS NULL OR dfid = $1)
+                   AND ($2::BIGINT IS NULL OR (timestamp, event_id) > ($2, $3::UUID))
+                 ORDER BY timestamp, event_id
+                 LIMIT $4",
+                &[&dfid, &after_ts, &after_id, &(limit as i64)],
+            )
+            .await
+            .map_err(|e| format!("Failed to load events page: {e}"))?;
+
+        Ok(rows.iter().map(Self::row_to_event).collect())
+    }
+
     /// Load event by content hash for deduplication
     pub async fn load_event_by_content_hash(
         &self,
@@ -3159,9 +4000,11 @@ impl PostgresPersistence {
 
         let row = client
             .query_opt(
-                "SELECT event_id, dfid, event_type, timestamp, visibility, encrypted_data, metadata, content_hash, source
+                "SELECT event_id, dfid, event_type, timestamp, visibility, encrypted_data, metadata, content_hash, source, occurred_at_ts,
+                        signature, signer_public_key, attachments, caused_by, correlation_id
                  FROM events
                  WHERE content_hash = $1
+                 ORDER BY timestamp DESC
                  LIMIT 1",
                 &[&content_hash],
             )
@@ -3177,6 +4020,7 @@ impl PostgresPersistence {
                 let metadata_json: serde_json::Value = row.get(6);
                 let db_content_hash: Option<String> = row.get(7);
                 let db_source: Option<String> = row.get(8);
+                let occurred_at_ts: Option<i64> = row.get(9);
 
                 let is_encrypted = encrypted_data.is_some();
                 let final_content_hash =
@@ -3192,8 +4036,7 @@ impl PostgresPersistence {
                 Ok(Some(Event {
                     event_id: row.get(0),
                     dfid: row.get(1),
-                    event_type: serde_json::from_str(&format!("\"{}\"", event_type_str))
-                        .unwrap_or(EventType::Created),
+                    event_type: EventType::from_name(&event_type_str),
                     timestamp: DateTime::from_timestamp(timestamp_secs, 0)
                         .unwrap_or_else(|| Utc::now()),
                     source,
@@ -3207,6 +4050,12 @@ impl PostgresPersistence {
                     pushed_to_circuit: None,
                     snapshot_id: None,
                     snapshot_cid: None,
+                    occurred_at: occurred_at_ts.and_then(|ts| DateTime::from_timestamp(ts, 0)),
+                    signature: row.get(10),
+                    signer_public_key: row.get(11),
+                    attachments: serde_json::from_value(row.get(12)).unwrap_or_default(),
+                    caused_by: row.get(13),
+                    correlation_id: row.get(14),
                 }))
             }
             None => Ok(None),
@@ -3307,12 +4156,13 @@ impl PostgresPersistence {
 
         client
             .execute(
-                "INSERT INTO zk_proofs (proof_id, circuit_type, item_id, prover_id, proof_data, public_inputs, private_inputs_hash, status, created_at, verified_at, expires_at, verification_result)
-                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                "INSERT INTO zk_proofs (proof_id, circuit_type, item_id, prover_id, proof_data, public_inputs, private_inputs_hash, status, created_at, verified_at, expires_at, verification_result, setup_artifact_id, anchor, valid_from, item_dfid)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
                  ON CONFLICT (proof_id) DO UPDATE SET
                     status = EXCLUDED.status,
                     verified_at = EXCLUDED.verified_at,
-                    verification_result = EXCLUDED.verification_result",
+                    verification_result = EXCLUDED.verification_result,
+                    anchor = EXCLUDED.anchor",
                 &[
                     &proof.proof_id,
                     &serde_json::to_string(&proof.circuit_type).unwrap_or_default(),
@@ -3326,6 +4176,13 @@ impl PostgresPersistence {
                     &proof.verified_at,
                     &proof.expires_at,
                     &serde_json::to_value(&proof.verification_result).unwrap_or(json!(null)),
+                    &proof.setup_artifact_id,
+                    &proof
+                        .anchor
+                        .as_ref()
+                        .and_then(|anchor| serde_json::to_value(anchor).ok()),
+                    &proof.valid_from,
+                    &proof.item_dfid,
                 ],
             )
             .await
@@ -3344,7 +4201,7 @@ impl PostgresPersistence {
 
         let rows = client
             .query(
-                "SELECT proof_id, circuit_type, item_id, prover_id, proof_data, public_inputs, private_inputs_hash, status, created_at, verified_at, expires_at, verification_result
+                "SELECT proof_id, circuit_type, item_id, prover_id, proof_data, public_inputs, private_inputs_hash, status, created_at, verified_at, expires_at, verification_result, setup_artifact_id, anchor, valid_from, item_dfid
                  FROM zk_proofs
                  ORDER BY created_at DESC",
                 &[],
@@ -3358,12 +4215,14 @@ impl PostgresPersistence {
             let public_inputs: serde_json::Value = row.get(5);
             let status: String = row.get(7);
             let verification_result: serde_json::Value = row.get(11);
+            let anchor: Option<serde_json::Value> = row.get(13);
 
             proofs.push(crate::zk_proof_engine::ZkProof {
                 proof_id: row.get(0),
                 circuit_type: serde_json::from_str(&circuit_type)
                     .unwrap_or(crate::zk_proof_engine::CircuitType::OwnershipProof),
                 item_id: row.get(2),
+                item_dfid: row.get(15),
                 prover_id: row.get(3),
                 proof_data: row.get(4),
                 public_inputs: serde_json::from_value(public_inputs).unwrap_or_default(),
@@ -3373,11 +4232,4509 @@ impl PostgresPersistence {
                 created_at: row.get(8),
                 verified_at: row.get(9),
                 expires_at: row.get(10),
+                valid_from: row.get(14),
                 verification_result: serde_json::from_value(verification_result).ok(),
+                setup_artifact_id: row.get(12),
+                anchor: anchor.and_then(|anchor| serde_json::from_value(anchor).ok()),
             });
         }
 
         tracing::debug!("✅ Loaded {} ZK proofs from PostgreSQL", proofs.len());
         Ok(proofs)
     }
+
+    /// Persist a device backup's metadata; the blob itself lives on disk
+    pub async fn persist_device_backup(
+        &self,
+        backup: &crate::types::DeviceBackup,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO device_backups (backup_id, user_id, device_id, version, size_bytes, content_hash, encryption, blob_path, created_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                 ON CONFLICT (backup_id) DO NOTHING",
+                &[
+                    &backup.backup_id,
+                    &backup.user_id,
+                    &backup.device_id,
+                    &(backup.version as i32),
+                    &(backup.size_bytes as i64),
+                    &backup.content_hash,
+                    &backup.encryption,
+                    &backup.blob_path,
+                    &backup.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist device backup: {e}"))?;
+
+        tracing::debug!("✅ Device backup persisted: {}", backup.backup_id);
+        Ok(())
+    }
+
+    fn row_to_device_backup(row: &Row) -> crate::types::DeviceBackup {
+        let version: i32 = row.get(3);
+        let size_bytes: i64 = row.get(4);
+        crate::types::DeviceBackup {
+            backup_id: row.get(0),
+            user_id: row.get(1),
+            device_id: row.get(2),
+            version: version as u32,
+            size_bytes: size_bytes as u64,
+            content_hash: row.get(5),
+            encryption: row.get(6),
+            blob_path: row.get(7),
+            created_at: row.get(8),
+        }
+    }
+
+    pub async fn load_device_backup(
+        &self,
+        backup_id: &Uuid,
+    ) -> Result<Option<crate::types::DeviceBackup>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT backup_id, user_id, device_id, version, size_bytes, content_hash, encryption, blob_path, created_at
+                 FROM device_backups
+                 WHERE backup_id = $1",
+                &[backup_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load device backup: {e}"))?;
+
+        Ok(row.as_ref().map(Self::row_to_device_backup))
+    }
+
+    /// All of a user's backups, across devices
+    pub async fn load_device_backups(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<crate::types::DeviceBackup>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT backup_id, user_id, device_id, version, size_bytes, content_hash, encryption, blob_path, created_at
+                 FROM device_backups
+                 WHERE user_id = $1
+                 ORDER BY device_id, version",
+                &[&user_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load device backups: {e}"))?;
+
+        Ok(rows.iter().map(Self::row_to_device_backup).collect())
+    }
+
+    pub async fn delete_device_backup(&self, backup_id: &Uuid) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "DELETE FROM device_backups WHERE backup_id = $1",
+                &[backup_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete device backup: {e}"))?;
+        Ok(())
+    }
+
+    /// Persist a federation peer (upsert; cursors advance on every sync)
+    pub async fn persist_federation_peer(
+        &self,
+        peer: &crate::types::FederationPeer,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO federation_peers (peer_id, name, node_id, public_key, url, circuits, enabled, last_synced_at, last_error, created_at, updated_at, trust)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                 ON CONFLICT (peer_id) DO UPDATE SET
+                    name = EXCLUDED.name,
+                    node_id = EXCLUDED.node_id,
+                    public_key = EXCLUDED.public_key,
+                    url = EXCLUDED.url,
+                    circuits = EXCLUDED.circuits,
+                    enabled = EXCLUDED.enabled,
+                    last_synced_at = EXCLUDED.last_synced_at,
+                    last_error = EXCLUDED.last_error,
+                    updated_at = EXCLUDED.updated_at,
+                    trust = EXCLUDED.trust",
+                &[
+                    &peer.peer_id,
+                    &peer.name,
+                    &peer.node_id,
+                    &peer.public_key,
+                    &peer.url,
+                    &serde_json::to_value(&peer.circuits).unwrap_or_default(),
+                    &peer.enabled,
+                    &peer.last_synced_at,
+                    &peer.last_error,
+                    &peer.created_at,
+                    &peer.updated_at,
+                    &serde_json::to_string(&peer.trust).unwrap_or_default(),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist federation peer: {e}"))?;
+
+        tracing::debug!("✅ Federation peer persisted: {}", peer.peer_id);
+        Ok(())
+    }
+
+    fn row_to_federation_peer(row: &Row) -> crate::types::FederationPeer {
+        let circuits: serde_json::Value = row.get(5);
+        let trust: String = row.get(11);
+        crate::types::FederationPeer {
+            peer_id: row.get(0),
+            name: row.get(1),
+            node_id: row.get(2),
+            public_key: row.get(3),
+            url: row.get(4),
+            circuits: serde_json::from_value(circuits).unwrap_or_default(),
+            enabled: row.get(6),
+            trust: serde_json::from_str(&trust).unwrap_or(crate::types::FederationTrust::Pending),
+            last_synced_at: row.get(7),
+            last_error: row.get(8),
+            created_at: row.get(9),
+            updated_at: row.get(10),
+        }
+    }
+
+    pub async fn load_federation_peer(
+        &self,
+        peer_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationPeer>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT peer_id, name, node_id, public_key, url, circuits, enabled, last_synced_at, last_error, created_at, updated_at, trust
+                 FROM federation_peers
+                 WHERE peer_id = $1",
+                &[peer_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load federation peer: {e}"))?;
+
+        Ok(row.as_ref().map(Self::row_to_federation_peer))
+    }
+
+    pub async fn load_federation_peers(&self) -> Result<Vec<crate::types::FederationPeer>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT peer_id, name, node_id, public_key, url, circuits, enabled, last_synced_at, last_error, created_at, updated_at, trust
+                 FROM federation_peers
+                 ORDER BY created_at",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load federation peers: {e}"))?;
+
+        Ok(rows.iter().map(Self::row_to_federation_peer).collect())
+    }
+
+    pub async fn delete_federation_peer(&self, peer_id: &Uuid) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "DELETE FROM federation_peers WHERE peer_id = $1",
+                &[peer_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete federation peer: {e}"))?;
+        Ok(())
+    }
+
+    /// Persist the origin of an imported record (upsert; the latest import wins)
+    pub async fn persist_federation_provenance(
+        &self,
+        provenance: &crate::types::FederationProvenance,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO federation_provenance (record_key, origin_node, content_hash, signature, peer_id, imported_at)
+                 VALUES ($1, $2, $3, $4, $5, $6)
+                 ON CONFLICT (record_key) DO UPDATE SET
+                    origin_node = EXCLUDED.origin_node,
+                    content_hash = EXCLUDED.content_hash,
+                    signature = EXCLUDED.signature,
+                    peer_id = EXCLUDED.peer_id,
+                    imported_at = EXCLUDED.imported_at",
+                &[
+                    &provenance.record_key,
+                    &provenance.origin_node,
+                    &provenance.content_hash,
+                    &provenance.signature,
+                    &provenance.peer_id,
+                    &provenance.imported_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist federation provenance: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_federation_provenance(
+        &self,
+        record_key: &str,
+    ) -> Result<Option<crate::types::FederationProvenance>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT record_key, origin_node, content_hash, signature, peer_id, imported_at
+                 FROM federation_provenance
+                 WHERE record_key = $1",
+                &[&record_key],
+            )
+            .await
+            .map_err(|e| format!("Failed to load federation provenance: {e}"))?;
+
+        Ok(row.map(|row| crate::types::FederationProvenance {
+            record_key: row.get(0),
+            origin_node: row.get(1),
+            content_hash: row.get(2),
+            signature: row.get(3),
+            peer_id: row.get(4),
+            imported_at: row.get(5),
+        }))
+    }
+
+    /// Persist a federation conflict (upsert; resolution updates it)
+    pub async fn persist_federation_conflict(
+        &self,
+        conflict: &crate::types::FederationConflict,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO federation_conflicts (conflict_id, peer_id, local_circuit_id, record_key, local_hash, remote, status, detected_at, resolved_by, resolved_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                 ON CONFLICT (conflict_id) DO UPDATE SET
+                    status = EXCLUDED.status,
+                    resolved_by = EXCLUDED.resolved_by,
+                    resolved_at = EXCLUDED.resolved_at",
+                &[
+                    &conflict.conflict_id,
+                    &conflict.peer_id,
+                    &conflict.local_circuit_id,
+                    &conflict.record_key,
+                    &conflict.local_hash,
+                    &serde_json::to_value(&conflict.remote).unwrap_or_default(),
+                    &serde_json::to_string(&conflict.status).unwrap_or_default(),
+                    &conflict.detected_at,
+                    &conflict.resolved_by,
+                    &conflict.resolved_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist federation conflict: {e}"))?;
+        Ok(())
+    }
+
+    fn row_to_federation_conflict(row: &Row) -> Option<crate::types::FederationConflict> {
+        let remote: serde_json::Value = row.get(5);
+        let status: String = row.get(6);
+        Some(crate::types::FederationConflict {
+            conflict_id: row.get(0),
+            peer_id: row.get(1),
+            local_circuit_id: row.get(2),
+            record_key: row.get(3),
+            local_hash: row.get(4),
+            remote: serde_json::from_value(remote).ok()?,
+            status: serde_json::from_str(&status)
+                .unwrap_or(crate::types::FederationConflictStatus::Open),
+            detected_at: row.get(7),
+            resolved_by: row.get(8),
+            resolved_at: row.get(9),
+        })
+    }
+
+    pub async fn load_federation_conflict(
+        &self,
+        conflict_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationConflict>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT conflict_id, peer_id, local_circuit_id, record_key, local_hash, remote, status, detected_at, resolved_by, resolved_at
+                 FROM federation_conflicts
+                 WHERE conflict_id = $1",
+                &[conflict_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load federation conflict: {e}"))?;
+
+        Ok(row.as_ref().and_then(Self::row_to_federation_conflict))
+    }
+
+    pub async fn load_federation_conflicts(
+        &self,
+    ) -> Result<Vec<crate::types::FederationConflict>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT conflict_id, peer_id, local_circuit_id, record_key, local_hash, remote, status, detected_at, resolved_by, resolved_at
+                 FROM federation_conflicts
+                 ORDER BY detected_at",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load federation conflicts: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(Self::row_to_federation_conflict)
+            .collect())
+    }
+
+    /// Persist a ZK proof revocation; revocations are final
+    pub async fn persist_zk_proof_revocation(
+        &self,
+        revocation: &crate::zk_proof_engine::ProofRevocation,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO zk_proof_revocations (proof_id, prover_id, workspace_id, revoked_by, reason, revoked_at)
+                 VALUES ($1, $2, $3, $4, $5, $6)
+                 ON CONFLICT (proof_id) DO NOTHING",
+                &[
+                    &revocation.proof_id,
+                    &revocation.prover_id,
+                    &revocation.workspace_id,
+                    &revocation.revoked_by,
+                    &revocation.reason,
+                    &revocation.revoked_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist ZK proof revocation: {e}"))?;
+        Ok(())
+    }
+
+    fn row_to_zk_proof_revocation(row: &Row) -> crate::zk_proof_engine::ProofRevocation {
+        crate::zk_proof_engine::ProofRevocation {
+            proof_id: row.get(0),
+            prover_id: row.get(1),
+            workspace_id: row.get(2),
+            revoked_by: row.get(3),
+            reason: row.get(4),
+            revoked_at: row.get(5),
+        }
+    }
+
+    pub async fn load_zk_proof_revocation(
+        &self,
+        proof_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofRevocation>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT proof_id, prover_id, workspace_id, revoked_by, reason, revoked_at
+                 FROM zk_proof_revocations
+                 WHERE proof_id = $1",
+                &[proof_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load ZK proof revocation: {e}"))?;
+
+        Ok(row.as_ref().map(Self::row_to_zk_proof_revocation))
+    }
+
+    pub async fn load_zk_proof_revocations(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ProofRevocation>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT proof_id, prover_id, workspace_id, revoked_by, reason, revoked_at
+                 FROM zk_proof_revocations
+                 ORDER BY revoked_at DESC",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load ZK proof revocations: {e}"))?;
+
+        Ok(rows.iter().map(Self::row_to_zk_proof_revocation).collect())
+    }
+
+    /// Persist an item's current attribute commitments (upsert; one set per item)
+    pub async fn persist_item_attribute_commitments(
+        &self,
+        commitments: &crate::types::ItemAttributeCommitments,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO item_attribute_commitments (dfid, commitments, notarization_id, created_by, created_at)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (dfid) DO UPDATE SET
+                    commitments = EXCLUDED.commitments,
+                    notarization_id = EXCLUDED.notarization_id,
+                    created_by = EXCLUDED.created_by,
+                    created_at = EXCLUDED.created_at",
+                &[
+                    &commitments.dfid,
+                    &serde_json::to_value(&commitments.commitments).unwrap_or_default(),
+                    &commitments.notarization_id,
+                    &commitments.created_by,
+                    &commitments.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist attribute commitments: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_item_attribute_commitments(
+        &self,
+        dfid: &str,
+    ) -> Result<Option<crate::types::ItemAttributeCommitments>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT dfid, commitments, notarization_id, created_by, created_at
+                 FROM item_attribute_commitments
+                 WHERE dfid = $1",
+                &[&dfid],
+            )
+            .await
+            .map_err(|e| format!("Failed to load attribute commitments: {e}"))?;
+
+        Ok(row.and_then(|row| {
+            let commitments: serde_json::Value = row.get(1);
+            Some(crate::types::ItemAttributeCommitments {
+                dfid: row.get(0),
+                commitments: serde_json::from_value(commitments).ok()?,
+                notarization_id: row.get(2),
+                created_by: row.get(3),
+                created_at: row.get(4),
+            })
+        }))
+    }
+
+    /// Persist a selective disclosure (upsert; revocation updates it)
+    pub async fn persist_selective_disclosure(
+        &self,
+        disclosure: &crate::types::SelectiveDisclosure,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO selective_disclosures (disclosure_id, token_hash, dfid, root, notarization_id, attributes, recipient, created_by, created_at, expires_at, revoked_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                 ON CONFLICT (disclosure_id) DO UPDATE SET
+                    revoked_at = EXCLUDED.revoked_at",
+                &[
+                    &disclosure.disclosure_id,
+                    &disclosure.token_hash,
+                    &disclosure.dfid,
+                    &disclosure.root,
+                    &disclosure.notarization_id,
+                    &serde_json::to_value(&disclosure.attributes).unwrap_or_default(),
+                    &disclosure.recipient,
+                    &disclosure.created_by,
+                    &disclosure.created_at,
+                    &disclosure.expires_at,
+                    &disclosure.revoked_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist selective disclosure: {e}"))?;
+        Ok(())
+    }
+
+    fn row_to_selective_disclosure(row: &Row) -> Option<crate::types::SelectiveDisclosure> {
+        let attributes: serde_json::Value = row.get(5);
+        Some(crate::types::SelectiveDisclosure {
+            disclosure_id: row.get(0),
+            token_hash: row.get(1),
+            dfid: row.get(2),
+            root: row.get(3),
+            notarization_id: row.get(4),
+            attributes: serde_json::from_value(attributes).ok()?,
+            recipient: row.get(6),
+            created_by: row.get(7),
+            created_at: row.get(8),
+            expires_at: row.get(9),
+            revoked_at: row.get(10),
+        })
+    }
+
+    pub async fn load_selective_disclosure(
+        &self,
+        disclosure_id: &Uuid,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT disclosure_id, token_hash, dfid, root, notarization_id, attributes, recipient, created_by, created_at, expires_at, revoked_at
+                 FROM selective_disclosures
+                 WHERE disclosure_id = $1",
+                &[disclosure_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load selective disclosure: {e}"))?;
+
+        Ok(row.as_ref().and_then(Self::row_to_selective_disclosure))
+    }
+
+    pub async fn load_selective_disclosure_by_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT disclosure_id, token_hash, dfid, root, notarization_id, attributes, recipient, created_by, created_at, expires_at, revoked_at
+                 FROM selective_disclosures
+                 WHERE token_hash = $1",
+                &[&token_hash],
+            )
+            .await
+            .map_err(|e| format!("Failed to load selective disclosure: {e}"))?;
+
+        Ok(row.as_ref().and_then(Self::row_to_selective_disclosure))
+    }
+
+    pub async fn load_selective_disclosures(
+        &self,
+        created_by: &str,
+    ) -> Result<Vec<crate::types::SelectiveDisclosure>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT disclosure_id, token_hash, dfid, root, notarization_id, attributes, recipient, created_by, created_at, expires_at, revoked_at
+                 FROM selective_disclosures
+                 WHERE created_by = $1
+                 ORDER BY created_at DESC",
+                &[&created_by],
+            )
+            .await
+            .map_err(|e| format!("Failed to load selective disclosures: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(Self::row_to_selective_disclosure)
+            .collect())
+    }
+
+    /// Persist a custom circuit definition; definitions never change once
+    /// registered, as proofs depend on them
+    pub async fn persist_custom_circuit_definition(
+        &self,
+        definition: &crate::zk_proof_engine::CustomCircuitDefinition,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO custom_zk_circuits (circuit_id, workspace_id, definition, created_by, created_at)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (circuit_id) DO NOTHING",
+                &[
+                    &definition.circuit_id,
+                    &definition.workspace_id,
+                    &serde_json::to_value(definition).unwrap_or_default(),
+                    &definition.created_by,
+                    &definition.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist custom circuit definition: {e}"))?;
+
+        tracing::debug!(
+            "✅ Custom circuit definition persisted: {}",
+            definition.circuit_id
+        );
+        Ok(())
+    }
+
+    pub async fn load_custom_circuit_definition(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::CustomCircuitDefinition>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT definition FROM custom_zk_circuits WHERE circuit_id = $1",
+                &[&circuit_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load custom circuit definition: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_custom_circuit_definitions(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::CustomCircuitDefinition>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT definition FROM custom_zk_circuits ORDER BY created_at",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load custom circuit definitions: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    /// Persist a proof generation job (upsert; status and results change)
+    pub async fn persist_zk_proof_job(
+        &self,
+        job: &crate::zk_proof_engine::ProofJob,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO zk_proof_jobs (job_id, prover_id, status, job, created_at, updated_at)
+                 VALUES ($1, $2, $3, $4, $5, NOW())
+                 ON CONFLICT (job_id) DO UPDATE SET
+                    status = EXCLUDED.status,
+                    job = EXCLUDED.job,
+                    updated_at = NOW()",
+                &[
+                    &job.job_id,
+                    &job.prover_id,
+                    &serde_json::to_string(&job.status).unwrap_or_default(),
+                    &serde_json::to_value(job).unwrap_or_default(),
+                    &job.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist ZK proof job: {e}"))?;
+
+        tracing::debug!("✅ ZK proof job persisted: {}", job.job_id);
+        Ok(())
+    }
+
+    pub async fn load_zk_proof_job(
+        &self,
+        job_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofJob>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt("SELECT job FROM zk_proof_jobs WHERE job_id = $1", &[job_id])
+            .await
+            .map_err(|e| format!("Failed to load ZK proof job: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_zk_proof_jobs(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ProofJob>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query("SELECT job FROM zk_proof_jobs ORDER BY created_at", &[])
+            .await
+            .map_err(|e| format!("Failed to load ZK proof jobs: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    /// Persist an hour of a workspace's API usage (upsert; counts grow)
+    pub async fn persist_workspace_usage_window(
+        &self,
+        window: &crate::types::WorkspaceUsageWindow,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO workspace_usage_windows (workspace_id, hour_start, usage)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (workspace_id, hour_start) DO UPDATE SET usage = EXCLUDED.usage",
+                &[
+                    &window.workspace_id,
+                    &window.hour_start,
+                    &serde_json::to_value(window).unwrap_or_default(),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist workspace usage window: {e}"))?;
+
+        Ok(())
+    }
+
+    pub async fn load_workspace_usage_window(
+        &self,
+        workspace_id: &str,
+        hour_start: DateTime<Utc>,
+    ) -> Result<Option<crate::types::WorkspaceUsageWindow>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT usage FROM workspace_usage_windows
+                 WHERE workspace_id = $1 AND hour_start = $2",
+                &[&workspace_id, &hour_start],
+            )
+            .await
+            .map_err(|e| format!("Failed to load workspace usage window: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_workspace_usage_windows(
+        &self,
+        workspace_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::WorkspaceUsageWindow>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT usage FROM workspace_usage_windows
+                 WHERE workspace_id = $1 AND hour_start >= $2 AND hour_start < $3
+                 ORDER BY hour_start",
+                &[&workspace_id, &from, &to],
+            )
+            .await
+            .map_err(|e| format!("Failed to load workspace usage windows: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    /// Persist a workspace's usage anomaly thresholds (upsert)
+    pub async fn persist_usage_anomaly_baseline(
+        &self,
+        baseline: &crate::types::UsageAnomalyBaseline,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO usage_anomaly_baselines (workspace_id, baseline, updated_at)
+                 VALUES ($1, $2, NOW())
+                 ON CONFLICT (workspace_id) DO UPDATE SET
+                    baseline = EXCLUDED.baseline,
+                    updated_at = NOW()",
+                &[
+                    &baseline.workspace_id,
+                    &serde_json::to_value(baseline).unwrap_or_default(),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist usage anomaly baseline: {e}"))?;
+
+        tracing::debug!(
+            "✅ Usage anomaly baseline persisted: {}",
+            baseline.workspace_id
+        );
+        Ok(())
+    }
+
+    pub async fn load_usage_anomaly_baseline(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::UsageAnomalyBaseline>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT baseline FROM usage_anomaly_baselines WHERE workspace_id = $1",
+                &[&workspace_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load usage anomaly baseline: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    /// Persist a detected usage anomaly (insert only)
+    pub async fn persist_usage_anomaly(
+        &self,
+        anomaly: &crate::types::UsageAnomaly,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO usage_anomalies (anomaly_id, workspace_id, kind, anomaly, detected_at)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (anomaly_id) DO NOTHING",
+                &[
+                    &anomaly.anomaly_id,
+                    &anomaly.workspace_id,
+                    &anomaly.kind.as_str(),
+                    &serde_json::to_value(anomaly).unwrap_or_default(),
+                    &anomaly.detected_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist usage anomaly: {e}"))?;
+
+        tracing::debug!("✅ Usage anomaly persisted: {}", anomaly.anomaly_id);
+        Ok(())
+    }
+
+    pub async fn load_usage_anomalies(
+        &self,
+        workspace_id: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::UsageAnomaly>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT anomaly FROM usage_anomalies
+                 WHERE ($1::TEXT IS NULL OR workspace_id = $1) AND detected_at >= $2
+                 ORDER BY detected_at DESC",
+                &[&workspace_id, &since],
+            )
+            .await
+            .map_err(|e| format!("Failed to load usage anomalies: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    /// Persist a proof share link (upsert; views and revocation change)
+    pub async fn persist_proof_share_link(
+        &self,
+        link: &crate::types::ProofShareLink,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO proof_share_links (share_id, proof_id, created_by, link, expires_at)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (share_id) DO UPDATE SET link = EXCLUDED.link",
+                &[
+                    &link.share_id,
+                    &link.proof_id,
+                    &link.created_by,
+                    &serde_json::to_value(link).unwrap_or_default(),
+                    &link.expires_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist proof share link: {e}"))?;
+
+        tracing::debug!("✅ Proof share link persisted: {}", link.share_id);
+        Ok(())
+    }
+
+    pub async fn load_proof_share_link(
+        &self,
+        share_id: &Uuid,
+    ) -> Result<Option<crate::types::ProofShareLink>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT link FROM proof_share_links WHERE share_id = $1",
+                &[share_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load proof share link: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    /// Count a view in one conditional update, so concurrent opens cannot
+    /// exceed the view limit
+    pub async fn record_proof_share_view(
+        &self,
+        share_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<crate::types::ProofShareLink>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+        let viewed_at = serde_json::to_value(now).unwrap_or_default();
+
+        let row = client
+            .query_opt(
+                "UPDATE proof_share_links SET link = link
+                    || jsonb_build_object('views', (link->>'views')::BIGINT + 1, 'last_viewed_at', $2::JSONB)
+                 WHERE share_id = $1
+                   AND expires_at > $3
+                   AND link->>'revoked_at' IS NULL
+                   AND (link->>'max_views' IS NULL
+                        OR (link->>'views')::BIGINT < (link->>'max_views')::BIGINT)
+                 RETURNING link",
+                &[share_id, &viewed_at, &now],
+            )
+            .await
+            .map_err(|e| format!("Failed to record proof share view: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_proof_share_links(
+        &self,
+        proof_id: &Uuid,
+    ) -> Result<Vec<crate::types::ProofShareLink>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT link FROM proof_share_links WHERE proof_id = $1",
+                &[proof_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load proof share links: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_workspace_dedup_config(
+        &self,
+        config: &crate::types::WorkspaceDedupConfig,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO workspace_dedup_configs (workspace_id, config, updated_at)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (workspace_id) DO UPDATE SET
+                    config = EXCLUDED.config,
+                    updated_at = EXCLUDED.updated_at",
+                &[
+                    &config.workspace_id,
+                    &serde_json::to_value(config).unwrap_or_default(),
+                    &config.updated_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist workspace dedup config: {e}"))?;
+
+        tracing::debug!(
+            "✅ Dedup config persisted for workspace {}",
+            config.workspace_id
+        );
+        Ok(())
+    }
+
+    pub async fn load_workspace_dedup_config(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceDedupConfig>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT config FROM workspace_dedup_configs WHERE workspace_id = $1",
+                &[&workspace_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load workspace dedup config: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn persist_sensor_series(
+        &self,
+        series: &crate::types::SensorSeries,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO sensor_series (dfid, series, data, updated_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (dfid, series) DO UPDATE SET
+                    data = EXCLUDED.data,
+                    updated_at = EXCLUDED.updated_at",
+                &[
+                    &series.dfid,
+                    &series.series,
+                    &serde_json::to_value(series).unwrap_or_default(),
+                    &series.updated_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist sensor series: {e}"))?;
+
+        Ok(())
+    }
+
+    pub async fn load_sensor_series(
+        &self,
+        dfid: &str,
+        series: &str,
+    ) -> Result<Option<crate::types::SensorSeries>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT data FROM sensor_series WHERE dfid = $1 AND series = $2",
+                &[&dfid, &series],
+            )
+            .await
+            .map_err(|e| format!("Failed to load sensor series: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_sensor_series_for_item(
+        &self,
+        dfid: &str,
+    ) -> Result<Vec<crate::types::SensorSeries>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT data FROM sensor_series WHERE dfid = $1 ORDER BY series",
+                &[&dfid],
+            )
+            .await
+            .map_err(|e| format!("Failed to load sensor series: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn load_all_sensor_series(&self) -> Result<Vec<crate::types::SensorSeries>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query("SELECT data FROM sensor_series", &[])
+            .await
+            .map_err(|e| format!("Failed to load sensor series: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_sensor_chunk(
+        &self,
+        chunk: &crate::types::SensorChunk,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO sensor_chunks (dfid, series, chunk_start, readings)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (dfid, series, chunk_start) DO UPDATE SET
+                    readings = EXCLUDED.readings",
+                &[
+                    &chunk.dfid,
+                    &chunk.series,
+                    &chunk.chunk_start,
+                    &serde_json::to_value(&chunk.readings).unwrap_or_default(),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist sensor chunk: {e}"))?;
+
+        Ok(())
+    }
+
+    pub async fn load_sensor_chunks(
+        &self,
+        dfid: &str,
+        series: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorChunk>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT chunk_start, readings FROM sensor_chunks
+                 WHERE dfid = $1 AND series = $2 AND chunk_start >= $3 AND chunk_start <= $4
+                 ORDER BY chunk_start",
+                &[&dfid, &series, &from, &to],
+            )
+            .await
+            .map_err(|e| format!("Failed to load sensor chunks: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                Some(crate::types::SensorChunk {
+                    dfid: dfid.to_string(),
+                    series: series.to_string(),
+                    chunk_start: row.get(0),
+                    readings: serde_json::from_value(row.get(1)).ok()?,
+                })
+            })
+            .collect())
+    }
+
+    pub async fn persist_sensor_rollup(
+        &self,
+        rollup: &crate::types::SensorRollup,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO sensor_rollups (dfid, series, resolution, bucket_start, rollup)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (dfid, series, resolution, bucket_start) DO UPDATE SET
+                    rollup = EXCLUDED.rollup",
+                &[
+                    &rollup.dfid,
+                    &rollup.series,
+                    &rollup.resolution.as_str(),
+                    &rollup.bucket_start,
+                    &serde_json::to_value(rollup).unwrap_or_default(),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist sensor rollup: {e}"))?;
+
+        Ok(())
+    }
+
+    pub async fn load_sensor_rollups(
+        &self,
+        dfid: &str,
+        series: &str,
+        resolution: crate::types::SensorResolution,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorRollup>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT rollup FROM sensor_rollups
+                 WHERE dfid = $1 AND series = $2 AND resolution = $3
+                   AND bucket_start >= $4 AND bucket_start <= $5
+                 ORDER BY bucket_start",
+                &[&dfid, &series, &resolution.as_str(), &from, &to],
+            )
+            .await
+            .map_err(|e| format!("Failed to load sensor rollups: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    /// The secret access key is stored sealed with the credential key
+    pub async fn persist_workspace_bucket_config(
+        &self,
+        config: &crate::types::WorkspaceBucketConfig,
+    ) -> Result<(), String> {
+        let sealed = crate::workspace_bucket_engine::credential_key()
+            .and_then(|key| crate::workspace_bucket_engine::seal_secret(config, &key))
+            .map_err(|e| format!("Failed to seal bucket secret: {e}"))?;
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO workspace_bucket_configs (workspace_id, config, updated_at)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (workspace_id) DO UPDATE SET
+                    config = EXCLUDED.config,
+                    updated_at = EXCLUDED.updated_at",
+                &[
+                    &config.workspace_id,
+                    &serde_json::to_value(&sealed).unwrap_or_default(),
+                    &config.updated_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist workspace bucket config: {e}"))?;
+
+        tracing::debug!(
+            "✅ Bucket config persisted for workspace {}",
+            config.workspace_id
+        );
+        Ok(())
+    }
+
+    pub async fn load_workspace_bucket_config(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceBucketConfig>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT config FROM workspace_bucket_configs WHERE workspace_id = $1",
+                &[&workspace_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load workspace bucket config: {e}"))?;
+
+        let Some(config) = row.and_then(|row| {
+            serde_json::from_value::<crate::types::WorkspaceBucketConfig>(row.get(0)).ok()
+        }) else {
+            return Ok(None);
+        };
+        crate::workspace_bucket_engine::credential_key()
+            .and_then(|key| crate::workspace_bucket_engine::open_secret(config, &key))
+            .map(Some)
+            .map_err(|e| format!("Failed to open bucket secret: {e}"))
+    }
+
+    pub async fn delete_workspace_bucket_config(&self, workspace_id: &str) -> Result<bool, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let deleted = client
+            .execute(
+                "DELETE FROM workspace_bucket_configs WHERE workspace_id = $1",
+                &[&workspace_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete workspace bucket config: {e}"))?;
+        Ok(deleted > 0)
+    }
+
+    /// Reserve `count` numbers of the day's DFID sequence in one atomic
+    /// upsert; returns the first
+    pub async fn lease_dfid_sequence_range(&self, day: &str, count: u64) -> Result<u64, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+        let count = i64::try_from(count).map_err(|_| "Lease size out of range".to_string())?;
+
+        let row = client
+            .query_one(
+                "INSERT INTO dfid_sequence_leases (day, next_sequence, updated_at)
+                 VALUES ($1, 1 + $2, NOW())
+                 ON CONFLICT (day) DO UPDATE SET
+                    next_sequence = dfid_sequence_leases.next_sequence + $2,
+                    updated_at = NOW()
+                 RETURNING next_sequence - $2",
+                &[&day, &count],
+            )
+            .await
+            .map_err(|e| format!("Failed to lease DFID sequence range: {e}"))?;
+
+        let first: i64 = row.get(0);
+        Ok(first as u64)
+    }
+
+    pub async fn persist_pending_item(
+        &self,
+        item: &crate::types::PendingItem,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO pending_items (pending_id, workspace_id, item, created_at, updated_at)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (pending_id) DO UPDATE SET
+                    workspace_id = EXCLUDED.workspace_id,
+                    item = EXCLUDED.item,
+                    updated_at = EXCLUDED.updated_at",
+                &[
+                    &item.pending_id,
+                    &item.workspace_id,
+                    &serde_json::to_value(item).unwrap_or_default(),
+                    &item.created_at,
+                    &item.last_updated,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist pending item: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_pending_item(
+        &self,
+        pending_id: &Uuid,
+    ) -> Result<Option<crate::types::PendingItem>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT item FROM pending_items WHERE pending_id = $1",
+                &[pending_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load pending item: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_pending_items(&self) -> Result<Vec<crate::types::PendingItem>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query("SELECT item FROM pending_items ORDER BY created_at", &[])
+            .await
+            .map_err(|e| format!("Failed to load pending items: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn delete_pending_item(&self, pending_id: &Uuid) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "DELETE FROM pending_items WHERE pending_id = $1",
+                &[pending_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete pending item: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn persist_reverification_job(
+        &self,
+        job: &crate::types::ReverificationJob,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO reverification_jobs (job_id, job, created_at)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (job_id) DO UPDATE SET job = EXCLUDED.job",
+                &[
+                    &job.job_id,
+                    &serde_json::to_value(job).unwrap_or_default(),
+                    &job.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist re-verification job: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_reverification_job(
+        &self,
+        job_id: &Uuid,
+    ) -> Result<Option<crate::types::ReverificationJob>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT job FROM reverification_jobs WHERE job_id = $1",
+                &[job_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load re-verification job: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_reverification_jobs(
+        &self,
+    ) -> Result<Vec<crate::types::ReverificationJob>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT job FROM reverification_jobs ORDER BY created_at",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load re-verification jobs: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_merge_proposal(
+        &self,
+        proposal: &crate::types::MergeProposal,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let status = serde_json::to_value(proposal.status)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        client
+            .execute(
+                "INSERT INTO merge_proposals (proposal_id, job_id, status, proposal, created_at)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (proposal_id) DO UPDATE SET
+                    status = EXCLUDED.status,
+                    proposal = EXCLUDED.proposal",
+                &[
+                    &proposal.proposal_id,
+                    &proposal.job_id,
+                    &status,
+                    &serde_json::to_value(proposal).unwrap_or_default(),
+                    &proposal.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist merge proposal: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_merge_proposal(
+        &self,
+        proposal_id: &Uuid,
+    ) -> Result<Option<crate::types::MergeProposal>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT proposal FROM merge_proposals WHERE proposal_id = $1",
+                &[proposal_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load merge proposal: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_merge_proposals(&self) -> Result<Vec<crate::types::MergeProposal>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT proposal FROM merge_proposals ORDER BY created_at",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load merge proposals: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_runbook_hook(
+        &self,
+        hook: &crate::types::RunbookHook,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let trigger = serde_json::to_value(hook.trigger)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        client
+            .execute(
+                "INSERT INTO runbook_hooks (hook_id, trigger, hook, created_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (hook_id) DO UPDATE SET
+                    trigger = EXCLUDED.trigger,
+                    hook = EXCLUDED.hook",
+                &[
+                    &hook.hook_id,
+                    &trigger,
+                    &serde_json::to_value(hook).unwrap_or_default(),
+                    &hook.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist runbook hook: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_runbook_hook(
+        &self,
+        hook_id: &Uuid,
+    ) -> Result<Option<crate::types::RunbookHook>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT hook FROM runbook_hooks WHERE hook_id = $1",
+                &[hook_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load runbook hook: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_runbook_hooks(&self) -> Result<Vec<crate::types::RunbookHook>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query("SELECT hook FROM runbook_hooks ORDER BY created_at", &[])
+            .await
+            .map_err(|e| format!("Failed to load runbook hooks: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn delete_runbook_hook(&self, hook_id: &Uuid) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute("DELETE FROM runbook_hooks WHERE hook_id = $1", &[hook_id])
+            .await
+            .map_err(|e| format!("Failed to delete runbook hook: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn persist_workspace_verification_rules(
+        &self,
+        rules: &crate::types::WorkspaceVerificationRules,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO workspace_verification_rules (workspace_id, rules, updated_at)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (workspace_id) DO UPDATE SET
+                    rules = EXCLUDED.rules,
+                    updated_at = EXCLUDED.updated_at",
+                &[
+                    &rules.workspace_id,
+                    &serde_json::to_value(rules).unwrap_or_default(),
+                    &rules.updated_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist workspace verification rules: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_workspace_verification_rules(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceVerificationRules>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT rules FROM workspace_verification_rules WHERE workspace_id = $1",
+                &[&workspace_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load workspace verification rules: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn persist_dedup_sharing_agreement(
+        &self,
+        agreement: &crate::types::DedupSharingAgreement,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let status = serde_json::to_value(agreement.status)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        client
+            .execute(
+                "INSERT INTO dedup_sharing_agreements (agreement_id, circuit_a, circuit_b, status, agreement, proposed_at)
+                 VALUES ($1, $2, $3, $4, $5, $6)
+                 ON CONFLICT (agreement_id) DO UPDATE SET
+                    status = EXCLUDED.status,
+                    agreement = EXCLUDED.agreement",
+                &[
+                    &agreement.agreement_id,
+                    &agreement.circuits[0],
+                    &agreement.circuits[1],
+                    &status,
+                    &serde_json::to_value(agreement).unwrap_or_default(),
+                    &agreement.proposed_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist dedup sharing agreement: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_dedup_sharing_agreement(
+        &self,
+        agreement_id: &Uuid,
+    ) -> Result<Option<crate::types::DedupSharingAgreement>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT agreement FROM dedup_sharing_agreements WHERE agreement_id = $1",
+                &[agreement_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load dedup sharing agreement: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    /// Agreements involving a circuit, newest first
+    pub async fn load_dedup_sharing_agreements(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<crate::types::DedupSharingAgreement>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT agreement FROM dedup_sharing_agreements
+                 WHERE circuit_a = $1 OR circuit_b = $1
+                 ORDER BY proposed_at DESC",
+                &[circuit_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load dedup sharing agreements: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_membership_reconciliation_report(
+        &self,
+        report: &crate::types::MembershipReconciliationReport,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let policy = serde_json::to_value(report.policy)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        client
+            .execute(
+                "INSERT INTO membership_reconciliation_reports (run_id, policy, report, started_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (run_id) DO NOTHING",
+                &[
+                    &report.run_id,
+                    &policy,
+                    &serde_json::to_value(report).unwrap_or_default(),
+                    &report.started_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist membership reconciliation report: {e}"))?;
+        Ok(())
+    }
+
+    /// Reports oldest first
+    pub async fn load_membership_reconciliation_reports(
+        &self,
+    ) -> Result<Vec<crate::types::MembershipReconciliationReport>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT report FROM membership_reconciliation_reports ORDER BY started_at ASC",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load membership reconciliation reports: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    /// Persist Groth16 setup keys (upsert; only the active flag changes)
+    pub async fn persist_zk_setup_artifact(
+        &self,
+        artifact: &crate::zk_proof_engine::ZkSetupArtifact,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO zk_setup_artifacts (artifact_id, circuit_type, circuit_version, proving_key, verifying_key, source, active, created_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                 ON CONFLICT (artifact_id) DO UPDATE SET
+                    active = EXCLUDED.active",
+                &[
+                    &artifact.artifact_id,
+                    &serde_json::to_string(&artifact.circuit_type).unwrap_or_default(),
+                    &artifact.circuit_version,
+                    &artifact.proving_key,
+                    &artifact.verifying_key,
+                    &serde_json::to_string(&artifact.source).unwrap_or_default(),
+                    &artifact.active,
+                    &artifact.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist ZK setup artifact: {e}"))?;
+
+        tracing::debug!("✅ ZK setup artifact persisted: {}", artifact.artifact_id);
+        Ok(())
+    }
+
+    /// Load all Groth16 setup keys, oldest first
+    pub async fn load_zk_setup_artifacts(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkSetupArtifact>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT artifact_id, circuit_type, circuit_version, proving_key, verifying_key, source, active, created_at
+                 FROM zk_setup_artifacts
+                 ORDER BY created_at ASC",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load ZK setup artifacts: {e}"))?;
+
+        let mut artifacts = Vec::new();
+        for row in rows {
+            let circuit_type: String = row.get(1);
+            let source: String = row.get(5);
+            let Ok(circuit_type) = serde_json::from_str(&circuit_type) else {
+                continue;
+            };
+
+            artifacts.push(crate::zk_proof_engine::ZkSetupArtifact {
+                artifact_id: row.get(0),
+                circuit_type,
+                circuit_version: row.get(2),
+                proving_key: row.get(3),
+                verifying_key: row.get(4),
+                source: serde_json::from_str(&source)
+                    .unwrap_or(crate::zk_proof_engine::SetupSource::Imported),
+                active: row.get(6),
+                created_at: row.get(7),
+            });
+        }
+
+        Ok(artifacts)
+    }
+
+    pub async fn persist_organization_profile(
+        &self,
+        profile: &crate::types::OrganizationProfile,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO organization_profiles (user_id, workspace_id, profile, updated_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (user_id) DO UPDATE SET
+                    workspace_id = EXCLUDED.workspace_id,
+                    profile = EXCLUDED.profile,
+                    updated_at = EXCLUDED.updated_at",
+                &[
+                    &profile.user_id,
+                    &profile.workspace_id,
+                    &serde_json::to_value(profile).unwrap_or_default(),
+                    &profile.updated_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist organization profile: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_organization_profile(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<crate::types::OrganizationProfile>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT profile FROM organization_profiles WHERE user_id = $1",
+                &[&user_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load organization profile: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_organization_profiles(
+        &self,
+    ) -> Result<Vec<crate::types::OrganizationProfile>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT profile FROM organization_profiles ORDER BY user_id ASC",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load organization profiles: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn delete_organization_profile(&self, user_id: &str) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "DELETE FROM organization_profiles WHERE user_id = $1",
+                &[&user_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete organization profile: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn persist_attestation(
+        &self,
+        attestation: &crate::types::Attestation,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO attestations (attestation_id, attester_id, subject_id, attestation, issued_at)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (attestation_id) DO UPDATE SET
+                    attestation = EXCLUDED.attestation",
+                &[
+                    &attestation.attestation_id,
+                    &attestation.attester_id,
+                    &attestation.subject_id,
+                    &serde_json::to_value(attestation).unwrap_or_default(),
+                    &attestation.issued_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist attestation: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_attestation(
+        &self,
+        attestation_id: &Uuid,
+    ) -> Result<Option<crate::types::Attestation>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT attestation FROM attestations WHERE attestation_id = $1",
+                &[attestation_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load attestation: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_attestations_for_subject(
+        &self,
+        subject_id: &str,
+    ) -> Result<Vec<crate::types::Attestation>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT attestation FROM attestations
+                 WHERE subject_id = $1
+                 ORDER BY issued_at ASC",
+                &[&subject_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load attestations: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn load_attestations_by_attester(
+        &self,
+        attester_id: &str,
+    ) -> Result<Vec<crate::types::Attestation>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT attestation FROM attestations
+                 WHERE attester_id = $1
+                 ORDER BY issued_at ASC",
+                &[&attester_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load attestations: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_saved_audit_query(
+        &self,
+        query: &crate::types::SavedAuditQuery,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO saved_audit_queries (query_id, owner_id, query, created_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (query_id) DO UPDATE SET
+                    query = EXCLUDED.query",
+                &[
+                    &query.query_id,
+                    &query.owner_id,
+                    &serde_json::to_value(query).unwrap_or_default(),
+                    &query.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist saved audit query: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_saved_audit_query(
+        &self,
+        query_id: &Uuid,
+    ) -> Result<Option<crate::types::SavedAuditQuery>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT query FROM saved_audit_queries WHERE query_id = $1",
+                &[query_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load saved audit query: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_saved_audit_queries(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<crate::types::SavedAuditQuery>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT query FROM saved_audit_queries
+                 WHERE owner_id = $1
+                 ORDER BY created_at ASC",
+                &[&owner_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load saved audit queries: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn delete_saved_audit_query(&self, query_id: &Uuid) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "DELETE FROM saved_audit_queries WHERE query_id = $1",
+                &[query_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete saved audit query: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn persist_preview_environment(
+        &self,
+        preview: &crate::types::PreviewEnvironment,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO preview_environments (preview_id, owner_id, preview, created_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (preview_id) DO UPDATE SET
+                    preview = EXCLUDED.preview",
+                &[
+                    &preview.preview_id,
+                    &preview.owner_id,
+                    &serde_json::to_value(preview).unwrap_or_default(),
+                    &preview.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist preview environment: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_preview_environment(
+        &self,
+        preview_id: &Uuid,
+    ) -> Result<Option<crate::types::PreviewEnvironment>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT preview FROM preview_environments WHERE preview_id = $1",
+                &[preview_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load preview environment: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_preview_environments(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<crate::types::PreviewEnvironment>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT preview FROM preview_environments
+                 WHERE owner_id = $1
+                 ORDER BY created_at ASC",
+                &[&owner_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load preview environments: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_connector_config(
+        &self,
+        config: &crate::types::ConnectorConfig,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO connector_configs (connector_id, owner_id, config, created_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (connector_id) DO UPDATE SET
+                    owner_id = EXCLUDED.owner_id,
+                    config = EXCLUDED.config",
+                &[
+                    &config.connector_id,
+                    &config.owner_id,
+                    &serde_json::to_value(config).unwrap_or_default(),
+                    &config.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist connector config: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_connector_config(
+        &self,
+        connector_id: &Uuid,
+    ) -> Result<Option<crate::types::ConnectorConfig>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT config FROM connector_configs WHERE connector_id = $1",
+                &[connector_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load connector config: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_connector_configs(
+        &self,
+    ) -> Result<Vec<crate::types::ConnectorConfig>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT config FROM connector_configs
+                 ORDER BY created_at ASC",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load connector configs: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    /// Delete a connector together with its run state and error history
+    pub async fn delete_connector_config(&self, connector_id: &Uuid) -> Result<(), String> {
+        let mut client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+        for table in ["connector_errors", "connector_states", "connector_configs"] {
+            tx.execute(
+                &format!("DELETE FROM {table} WHERE connector_id = $1"),
+                &[connector_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete connector config: {e}"))?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit connector deletion: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn persist_mapping_template(
+        &self,
+        template: &crate::types::MappingTemplate,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO mapping_templates (template_id, owner_id, template, created_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (template_id) DO UPDATE SET
+                    owner_id = EXCLUDED.owner_id,
+                    template = EXCLUDED.template",
+                &[
+                    &template.template_id,
+                    &template.owner_id,
+                    &serde_json::to_value(template).unwrap_or_default(),
+                    &template.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist mapping template: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_mapping_template(
+        &self,
+        template_id: &Uuid,
+    ) -> Result<Option<crate::types::MappingTemplate>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT template FROM mapping_templates WHERE template_id = $1",
+                &[template_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load mapping template: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_mapping_templates(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<crate::types::MappingTemplate>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT template FROM mapping_templates
+                 WHERE owner_id = $1
+                 ORDER BY created_at ASC",
+                &[&owner_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load mapping templates: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_connector_state(
+        &self,
+        state: &crate::types::ConnectorState,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO connector_states (connector_id, state)
+                 VALUES ($1, $2)
+                 ON CONFLICT (connector_id) DO UPDATE SET
+                    state = EXCLUDED.state",
+                &[
+                    &state.connector_id,
+                    &serde_json::to_value(state).unwrap_or_default(),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist connector state: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_connector_state(
+        &self,
+        connector_id: &Uuid,
+    ) -> Result<Option<crate::types::ConnectorState>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT state FROM connector_states WHERE connector_id = $1",
+                &[connector_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load connector state: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn persist_connector_error(
+        &self,
+        error: &crate::types::ConnectorRunError,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO connector_errors (error_id, connector_id, error, occurred_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (error_id) DO NOTHING",
+                &[
+                    &error.error_id,
+                    &error.connector_id,
+                    &serde_json::to_value(error).unwrap_or_default(),
+                    &error.at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist connector error: {e}"))?;
+        Ok(())
+    }
+
+    /// Most recent errors first
+    pub async fn load_connector_errors(
+        &self,
+        connector_id: &Uuid,
+        limit: usize,
+    ) -> Result<Vec<crate::types::ConnectorRunError>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT error FROM connector_errors
+                 WHERE connector_id = $1
+                 ORDER BY occurred_at DESC
+                 LIMIT $2",
+                &[connector_id, &(limit as i64)],
+            )
+            .await
+            .map_err(|e| format!("Failed to load connector errors: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn delete_mapping_template(&self, template_id: &Uuid) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "DELETE FROM mapping_templates WHERE template_id = $1",
+                &[template_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete mapping template: {e}"))?;
+        Ok(())
+    }
+
+    /// Append a change record under the next sequence of its circuit
+    pub async fn append_change_record(
+        &self,
+        record: &crate::types::ChangeRecord,
+    ) -> Result<crate::types::ChangeRecord, String> {
+        let mut client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+
+        let row = tx
+            .query_one(
+                "INSERT INTO change_feed_sequences (circuit_id, last_sequence)
+                 VALUES ($1, 1)
+                 ON CONFLICT (circuit_id) DO UPDATE SET
+                    last_sequence = change_feed_sequences.last_sequence + 1
+                 RETURNING last_sequence",
+                &[&record.circuit_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to allocate change sequence: {e}"))?;
+        let sequence: i64 = row.get(0);
+
+        let mut record = record.clone();
+        record.sequence = sequence as u64;
+        tx.execute(
+            "INSERT INTO change_records (circuit_id, sequence, record, recorded_at)
+             VALUES ($1, $2, $3, $4)",
+            &[
+                &record.circuit_id,
+                &sequence,
+                &serde_json::to_value(&record).unwrap_or_default(),
+                &record.recorded_at,
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to append change record: {e}"))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit change record: {e}"))?;
+        Ok(record)
+    }
+
+    /// Records after `after_sequence`, in sequence order
+    pub async fn load_change_records(
+        &self,
+        circuit_id: &Uuid,
+        after_sequence: u64,
+        limit: usize,
+    ) -> Result<Vec<crate::types::ChangeRecord>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT record FROM change_records
+                 WHERE circuit_id = $1 AND sequence > $2
+                 ORDER BY sequence ASC
+                 LIMIT $3",
+                &[circuit_id, &(after_sequence as i64), &(limit as i64)],
+            )
+            .await
+            .map_err(|e| format!("Failed to load change records: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    /// Sequence of the newest change record of a circuit, 0 when it has none
+    pub async fn load_latest_change_sequence(&self, circuit_id: &Uuid) -> Result<u64, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT last_sequence FROM change_feed_sequences WHERE circuit_id = $1",
+                &[circuit_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load latest change sequence: {e}"))?;
+
+        Ok(row.map(|row| row.get::<_, i64>(0) as u64).unwrap_or(0))
+    }
+
+    pub async fn persist_change_feed_subscription(
+        &self,
+        subscription: &crate::types::ChangeFeedSubscription,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO change_feed_subscriptions (subscription_id, circuit_id, subscription, created_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (subscription_id) DO UPDATE SET
+                    subscription = EXCLUDED.subscription",
+                &[
+                    &subscription.subscription_id,
+                    &subscription.circuit_id,
+                    &serde_json::to_value(subscription).unwrap_or_default(),
+                    &subscription.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist change feed subscription: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_change_feed_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<Option<crate::types::ChangeFeedSubscription>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT subscription FROM change_feed_subscriptions WHERE subscription_id = $1",
+                &[subscription_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load change feed subscription: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    /// Subscriptions of one circuit, or of every circuit when `circuit_id` is `None`
+    pub async fn load_change_feed_subscriptions(
+        &self,
+        circuit_id: Option<&Uuid>,
+    ) -> Result<Vec<crate::types::ChangeFeedSubscription>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT subscription FROM change_feed_subscriptions
+                 WHERE $1::UUID IS NULL OR circuit_id = $1
+                 ORDER BY created_at ASC",
+                &[&circuit_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load change feed subscriptions: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn delete_change_feed_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "DELETE FROM change_feed_subscriptions WHERE subscription_id = $1",
+                &[subscription_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete change feed subscription: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn persist_announcement(
+        &self,
+        announcement: &crate::types::Announcement,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO announcements (announcement_id, announcement, publish_at)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (announcement_id) DO UPDATE SET
+                    announcement = EXCLUDED.announcement,
+                    publish_at = EXCLUDED.publish_at",
+                &[
+                    &announcement.announcement_id,
+                    &serde_json::to_value(announcement).unwrap_or_default(),
+                    &announcement.publish_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist announcement: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_announcement(
+        &self,
+        announcement_id: &Uuid,
+    ) -> Result<Option<crate::types::Announcement>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT announcement FROM announcements WHERE announcement_id = $1",
+                &[announcement_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load announcement: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    /// Announcements, latest publish time first
+    pub async fn load_announcements(&self) -> Result<Vec<crate::types::Announcement>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT announcement FROM announcements
+                 ORDER BY publish_at DESC",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load announcements: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_workspace_engagement(
+        &self,
+        engagement: &crate::types::WorkspaceEngagement,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO workspace_engagement (workspace_id, engagement, computed_at)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (workspace_id) DO UPDATE SET
+                    engagement = EXCLUDED.engagement,
+                    computed_at = EXCLUDED.computed_at",
+                &[
+                    &engagement.workspace_id,
+                    &serde_json::to_value(engagement).unwrap_or_default(),
+                    &engagement.computed_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist workspace engagement: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_workspace_engagement(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceEngagement>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT engagement FROM workspace_engagement WHERE workspace_id = $1",
+                &[&workspace_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load workspace engagement: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn persist_data_export_job(
+        &self,
+        job: &crate::types::DataExportJob,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO data_export_jobs (job_id, requested_by, job, created_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (job_id) DO UPDATE SET
+                    job = EXCLUDED.job",
+                &[
+                    &job.job_id,
+                    &job.requested_by,
+                    &serde_json::to_value(job).unwrap_or_default(),
+                    &job.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist data export job: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_data_export_job(
+        &self,
+        job_id: &Uuid,
+    ) -> Result<Option<crate::types::DataExportJob>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT job FROM data_export_jobs WHERE job_id = $1",
+                &[job_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load data export job: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    /// Jobs requested by a user, newest first
+    pub async fn load_data_export_jobs(
+        &self,
+        requested_by: &str,
+    ) -> Result<Vec<crate::types::DataExportJob>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT job FROM data_export_jobs
+                 WHERE requested_by = $1
+                 ORDER BY created_at DESC",
+                &[&requested_by],
+            )
+            .await
+            .map_err(|e| format!("Failed to load data export jobs: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_sla_window(&self, window: &crate::types::SlaWindow) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let component = serde_json::to_value(window.component)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        client
+            .execute(
+                "INSERT INTO sla_windows (component, hour_start, sla_window)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (component, hour_start) DO UPDATE SET
+                    sla_window = EXCLUDED.sla_window",
+                &[
+                    &component,
+                    &window.hour_start,
+                    &serde_json::to_value(window).unwrap_or_default(),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist SLA window: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_sla_window(
+        &self,
+        component: crate::types::SlaComponent,
+        hour_start: DateTime<Utc>,
+    ) -> Result<Option<crate::types::SlaWindow>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let component = serde_json::to_value(component)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let row = client
+            .query_opt(
+                "SELECT sla_window FROM sla_windows WHERE component = $1 AND hour_start = $2",
+                &[&component, &hour_start],
+            )
+            .await
+            .map_err(|e| format!("Failed to load SLA window: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    /// Windows starting in `[from, to)`, ordered by hour then component
+    pub async fn load_sla_windows(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SlaWindow>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT sla_window FROM sla_windows
+                 WHERE hour_start >= $1 AND hour_start < $2",
+                &[&from, &to],
+            )
+            .await
+            .map_err(|e| format!("Failed to load SLA windows: {e}"))?;
+
+        let mut windows: Vec<crate::types::SlaWindow> = rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect();
+        windows.sort_by_key(|w| (w.hour_start, w.component));
+        Ok(windows)
+    }
+
+    pub async fn persist_event_schema(
+        &self,
+        schema: &crate::types::EventSchema,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO event_schemas (schema_id, circuit_id, version, schema)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (schema_id) DO UPDATE SET
+                    schema = EXCLUDED.schema",
+                &[
+                    &schema.schema_id,
+                    &schema.circuit_id,
+                    &(schema.version as i32),
+                    &serde_json::to_value(schema).unwrap_or_default(),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist event schema: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_event_schemas(&self) -> Result<Vec<crate::types::EventSchema>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT schema FROM event_schemas
+                 ORDER BY circuit_id ASC, version ASC",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load event schemas: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_maintenance_mode(
+        &self,
+        mode: &crate::types::MaintenanceMode,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO maintenance_mode (singleton, mode, updated_at)
+                 VALUES (TRUE, $1, NOW())
+                 ON CONFLICT (singleton) DO UPDATE SET
+                    mode = EXCLUDED.mode,
+                    updated_at = EXCLUDED.updated_at",
+                &[&serde_json::to_value(mode).unwrap_or_default()],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist maintenance mode: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_maintenance_mode(
+        &self,
+    ) -> Result<Option<crate::types::MaintenanceMode>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt("SELECT mode FROM maintenance_mode WHERE singleton", &[])
+            .await
+            .map_err(|e| format!("Failed to load maintenance mode: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn persist_managed_key(&self, key: &crate::types::ManagedKey) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO managed_keys (key_id, fingerprint, managed_key, created_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (key_id) DO UPDATE SET
+                    managed_key = EXCLUDED.managed_key",
+                &[
+                    &key.key_id,
+                    &key.fingerprint,
+                    &serde_json::to_value(key).unwrap_or_default(),
+                    &key.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist managed key: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_managed_key(
+        &self,
+        key_id: &Uuid,
+    ) -> Result<Option<crate::types::ManagedKey>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT managed_key FROM managed_keys WHERE key_id = $1",
+                &[key_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load managed key: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_managed_keys(&self) -> Result<Vec<crate::types::ManagedKey>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT managed_key FROM managed_keys
+                 ORDER BY created_at ASC",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load managed keys: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_custom_event_type(
+        &self,
+        event_type: &crate::types::CustomEventType,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO custom_event_types (circuit_id, name, event_type)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (circuit_id, name) DO UPDATE SET
+                    event_type = EXCLUDED.event_type",
+                &[
+                    &event_type.circuit_id,
+                    &event_type.name,
+                    &serde_json::to_value(event_type).unwrap_or_default(),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist custom event type: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_custom_event_types(
+        &self,
+    ) -> Result<Vec<crate::types::CustomEventType>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT event_type FROM custom_event_types
+                 ORDER BY circuit_id ASC, name ASC",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load custom event types: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    /// Returns whether a type was registered under that name
+    pub async fn delete_custom_event_type(
+        &self,
+        circuit_id: &Uuid,
+        name: &str,
+    ) -> Result<bool, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let deleted = client
+            .execute(
+                "DELETE FROM custom_event_types WHERE circuit_id = $1 AND name = $2",
+                &[circuit_id, &name],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete custom event type: {e}"))?;
+        Ok(deleted > 0)
+    }
+
+    pub async fn persist_notarization(
+        &self,
+        notarization: &crate::types::DocumentNotarization,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO document_notarizations (notarization_id, document_hash, batch_id, notarization, submitted_at)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (notarization_id) DO UPDATE SET
+                    batch_id = EXCLUDED.batch_id,
+                    notarization = EXCLUDED.notarization",
+                &[
+                    &notarization.notarization_id,
+                    &notarization.document_hash,
+                    &notarization.batch_id,
+                    &serde_json::to_value(notarization).unwrap_or_default(),
+                    &notarization.submitted_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist notarization: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_notarization(
+        &self,
+        notarization_id: &Uuid,
+    ) -> Result<Option<crate::types::DocumentNotarization>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT notarization FROM document_notarizations WHERE notarization_id = $1",
+                &[notarization_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load notarization: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_notarizations(
+        &self,
+    ) -> Result<Vec<crate::types::DocumentNotarization>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT notarization FROM document_notarizations
+                 ORDER BY submitted_at ASC",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load notarizations: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_notarization_batch(
+        &self,
+        batch: &crate::types::NotarizationBatch,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO notarization_batches (batch_id, merkle_root, batch, created_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (batch_id) DO UPDATE SET
+                    batch = EXCLUDED.batch",
+                &[
+                    &batch.batch_id,
+                    &batch.merkle_root,
+                    &serde_json::to_value(batch).unwrap_or_default(),
+                    &batch.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist notarization batch: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_notarization_batch(
+        &self,
+        batch_id: &Uuid,
+    ) -> Result<Option<crate::types::NotarizationBatch>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT batch FROM notarization_batches WHERE batch_id = $1",
+                &[batch_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load notarization batch: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn persist_enrichment_policy(
+        &self,
+        policy: &crate::types::EnrichmentPolicy,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO enrichment_policies (workspace_id, policy, updated_at)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (workspace_id) DO UPDATE SET
+                    policy = EXCLUDED.policy,
+                    updated_at = EXCLUDED.updated_at",
+                &[
+                    &policy.workspace_id,
+                    &serde_json::to_value(policy).unwrap_or_default(),
+                    &policy.updated_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist enrichment policy: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_enrichment_policy(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::EnrichmentPolicy>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT policy FROM enrichment_policies WHERE workspace_id = $1",
+                &[&workspace_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load enrichment policy: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn persist_event_signing_key(
+        &self,
+        key: &crate::types::EventSigningKey,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO event_signing_keys (key_id, public_key, signing_key, registered_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (key_id) DO UPDATE SET
+                    signing_key = EXCLUDED.signing_key",
+                &[
+                    &key.key_id,
+                    &key.public_key,
+                    &serde_json::to_value(key).unwrap_or_default(),
+                    &key.registered_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist event signing key: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_event_signing_key(
+        &self,
+        key_id: &Uuid,
+    ) -> Result<Option<crate::types::EventSigningKey>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT signing_key FROM event_signing_keys WHERE key_id = $1",
+                &[key_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load event signing key: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_event_signing_keys(
+        &self,
+    ) -> Result<Vec<crate::types::EventSigningKey>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT signing_key FROM event_signing_keys
+                 ORDER BY registered_at ASC",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load event signing keys: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_anchoring_sla(
+        &self,
+        sla: &crate::types::AnchoringSla,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO anchoring_slas (circuit_id, sla, updated_at)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (circuit_id) DO UPDATE SET
+                    sla = EXCLUDED.sla,
+                    updated_at = EXCLUDED.updated_at",
+                &[
+                    &sla.circuit_id,
+                    &serde_json::to_value(sla).unwrap_or_default(),
+                    &sla.updated_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist anchoring SLA: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_anchoring_sla(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Option<crate::types::AnchoringSla>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT sla FROM anchoring_slas WHERE circuit_id = $1",
+                &[circuit_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load anchoring SLA: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    /// Replace the latest fee sample of the sample's network
+    pub async fn persist_fee_sample(&self, sample: &crate::types::FeeSample) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let network = serde_json::to_value(sample.network)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        client
+            .execute(
+                "INSERT INTO anchoring_fee_samples (network, sample, sampled_at)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (network) DO UPDATE SET
+                    sample = EXCLUDED.sample,
+                    sampled_at = EXCLUDED.sampled_at",
+                &[
+                    &network,
+                    &serde_json::to_value(sample).unwrap_or_default(),
+                    &sample.sampled_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist fee sample: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_latest_fee_sample(
+        &self,
+        network: &crate::types::AnchoringNetwork,
+    ) -> Result<Option<crate::types::FeeSample>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let network = serde_json::to_value(network)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let row = client
+            .query_opt(
+                "SELECT sample FROM anchoring_fee_samples WHERE network = $1",
+                &[&network],
+            )
+            .await
+            .map_err(|e| format!("Failed to load fee sample: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn persist_anchoring_cost_record(
+        &self,
+        record: &crate::types::AnchoringCostRecord,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO anchoring_cost_records (record_id, circuit_id, record, anchored_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (record_id) DO NOTHING",
+                &[
+                    &record.record_id,
+                    &record.circuit_id,
+                    &serde_json::to_value(record).unwrap_or_default(),
+                    &record.anchored_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist anchoring cost record: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_anchoring_cost_records(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::AnchoringCostRecord>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT record FROM anchoring_cost_records
+                 WHERE anchored_at >= $1
+                 ORDER BY anchored_at ASC",
+                &[&since],
+            )
+            .await
+            .map_err(|e| format!("Failed to load anchoring cost records: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_lifecycle_definition(
+        &self,
+        definition: &crate::types::LifecycleDefinition,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO lifecycle_definitions (workspace_id, definition, updated_at)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (workspace_id) DO UPDATE SET
+                    definition = EXCLUDED.definition,
+                    updated_at = EXCLUDED.updated_at",
+                &[
+                    &definition.workspace_id,
+                    &serde_json::to_value(definition).unwrap_or_default(),
+                    &definition.updated_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist lifecycle definition: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_lifecycle_definition(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::LifecycleDefinition>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT definition FROM lifecycle_definitions WHERE workspace_id = $1",
+                &[&workspace_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load lifecycle definition: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn persist_item_lifecycle(
+        &self,
+        lifecycle: &crate::types::ItemLifecycle,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO item_lifecycles (dfid, workspace_id, lifecycle)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (dfid) DO UPDATE SET
+                    workspace_id = EXCLUDED.workspace_id,
+                    lifecycle = EXCLUDED.lifecycle",
+                &[
+                    &lifecycle.dfid,
+                    &lifecycle.workspace_id,
+                    &serde_json::to_value(lifecycle).unwrap_or_default(),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist item lifecycle: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_item_lifecycle(
+        &self,
+        dfid: &str,
+    ) -> Result<Option<crate::types::ItemLifecycle>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT lifecycle FROM item_lifecycles WHERE dfid = $1",
+                &[&dfid],
+            )
+            .await
+            .map_err(|e| format!("Failed to load item lifecycle: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_item_lifecycles(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Vec<crate::types::ItemLifecycle>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT lifecycle FROM item_lifecycles
+                 WHERE workspace_id = $1
+                 ORDER BY dfid ASC",
+                &[&workspace_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load item lifecycles: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_access_report(
+        &self,
+        report: &crate::types::CircuitAccessReport,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO circuit_access_reports (report_id, circuit_id, report, generated_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (report_id) DO UPDATE SET
+                    report = EXCLUDED.report",
+                &[
+                    &report.report_id,
+                    &report.circuit_id,
+                    &serde_json::to_value(report).unwrap_or_default(),
+                    &report.generated_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist access report: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_access_report(
+        &self,
+        report_id: &Uuid,
+    ) -> Result<Option<crate::types::CircuitAccessReport>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT report FROM circuit_access_reports WHERE report_id = $1",
+                &[report_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load access report: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_access_reports(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<crate::types::CircuitAccessReport>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT report FROM circuit_access_reports
+                 WHERE circuit_id = $1
+                 ORDER BY generated_at ASC",
+                &[circuit_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load access reports: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn delete_access_report(&self, report_id: &Uuid) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "DELETE FROM circuit_access_reports WHERE report_id = $1",
+                &[report_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete access report: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn persist_access_review_schedule(
+        &self,
+        schedule: &crate::types::AccessReviewSchedule,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO access_review_schedules (circuit_id, schedule, next_run_at)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (circuit_id) DO UPDATE SET
+                    schedule = EXCLUDED.schedule,
+                    next_run_at = EXCLUDED.next_run_at",
+                &[
+                    &schedule.circuit_id,
+                    &serde_json::to_value(schedule).unwrap_or_default(),
+                    &schedule.next_run_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist access review schedule: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_access_review_schedule(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Option<crate::types::AccessReviewSchedule>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT schedule FROM access_review_schedules WHERE circuit_id = $1",
+                &[circuit_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load access review schedule: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn delete_access_review_schedule(&self, circuit_id: &Uuid) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "DELETE FROM access_review_schedules WHERE circuit_id = $1",
+                &[circuit_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete access review schedule: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_access_review_schedules(
+        &self,
+    ) -> Result<Vec<crate::types::AccessReviewSchedule>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT schedule FROM access_review_schedules
+                 ORDER BY next_run_at ASC",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load access review schedules: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    /// Persist a partner token (upsert; identity and hash never change). The
+    /// hash is kept in its own column since the type never serializes it.
+    pub async fn persist_partner_token(
+        &self,
+        token: &crate::types::PartnerToken,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO partner_tokens (token_id, circuit_id, partner_name, token_hash, token_prefix, created_by, created_at, expires_at, revoked_at, revoked_by, last_used_at, use_count)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                 ON CONFLICT (token_id) DO UPDATE SET
+                    partner_name = EXCLUDED.partner_name,
+                    expires_at = EXCLUDED.expires_at,
+                    revoked_at = EXCLUDED.revoked_at,
+                    revoked_by = EXCLUDED.revoked_by,
+                    last_used_at = EXCLUDED.last_used_at,
+                    use_count = EXCLUDED.use_count",
+                &[
+                    &token.token_id,
+                    &token.circuit_id,
+                    &token.partner_name,
+                    &token.token_hash,
+                    &token.token_prefix,
+                    &token.created_by,
+                    &token.created_at,
+                    &token.expires_at,
+                    &token.revoked_at,
+                    &token.revoked_by,
+                    &token.last_used_at,
+                    &(token.use_count as i64),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist partner token: {e}"))?;
+        Ok(())
+    }
+
+    fn row_to_partner_token(row: &Row) -> crate::types::PartnerToken {
+        crate::types::PartnerToken {
+            token_id: row.get("token_id"),
+            circuit_id: row.get("circuit_id"),
+            partner_name: row.get("partner_name"),
+            token_hash: row.get("token_hash"),
+            token_prefix: row.get("token_prefix"),
+            created_by: row.get("created_by"),
+            created_at: row.get("created_at"),
+            expires_at: row.get("expires_at"),
+            revoked_at: row.get("revoked_at"),
+            revoked_by: row.get("revoked_by"),
+            last_used_at: row.get("last_used_at"),
+            use_count: row.get::<_, i64>("use_count") as u64,
+        }
+    }
+
+    pub async fn load_partner_token(
+        &self,
+        token_id: &Uuid,
+    ) -> Result<Option<crate::types::PartnerToken>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT * FROM partner_tokens WHERE token_id = $1",
+                &[token_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load partner token: {e}"))?;
+
+        Ok(row.as_ref().map(Self::row_to_partner_token))
+    }
+
+    pub async fn load_partner_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<crate::types::PartnerToken>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT * FROM partner_tokens WHERE token_hash = $1",
+                &[&token_hash],
+            )
+            .await
+            .map_err(|e| format!("Failed to load partner token: {e}"))?;
+
+        Ok(row.as_ref().map(Self::row_to_partner_token))
+    }
+
+    /// Tokens of a circuit, oldest first
+    pub async fn load_partner_tokens(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<crate::types::PartnerToken>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT * FROM partner_tokens
+                 WHERE circuit_id = $1
+                 ORDER BY created_at ASC",
+                &[circuit_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load partner tokens: {e}"))?;
+
+        Ok(rows.iter().map(Self::row_to_partner_token).collect())
+    }
+
+    pub async fn persist_partner_token_usage(
+        &self,
+        usage: &crate::types::PartnerTokenUsage,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO partner_token_usage (usage_id, token_id, usage, used_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (usage_id) DO NOTHING",
+                &[
+                    &usage.usage_id,
+                    &usage.token_id,
+                    &serde_json::to_value(usage).unwrap_or_default(),
+                    &usage.used_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist partner token usage: {e}"))?;
+        Ok(())
+    }
+
+    /// Most recent calls first
+    pub async fn load_partner_token_usage(
+        &self,
+        token_id: &Uuid,
+        limit: usize,
+    ) -> Result<Vec<crate::types::PartnerTokenUsage>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT usage FROM partner_token_usage
+                 WHERE token_id = $1
+                 ORDER BY used_at DESC
+                 LIMIT $2",
+                &[token_id, &(limit as i64)],
+            )
+            .await
+            .map_err(|e| format!("Failed to load partner token usage: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_circuit_comment(
+        &self,
+        comment: &crate::types::CircuitComment,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO circuit_comments (comment_id, circuit_id, target, comment, created_at)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (comment_id) DO UPDATE SET
+                    comment = EXCLUDED.comment",
+                &[
+                    &comment.comment_id,
+                    &comment.circuit_id,
+                    &serde_json::to_value(&comment.target).unwrap_or_default(),
+                    &serde_json::to_value(comment).unwrap_or_default(),
+                    &comment.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist circuit comment: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_circuit_comment(
+        &self,
+        comment_id: &Uuid,
+    ) -> Result<Option<crate::types::CircuitComment>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT comment FROM circuit_comments WHERE comment_id = $1",
+                &[comment_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load circuit comment: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    /// Thread on one target, oldest first
+    pub async fn load_circuit_comments(
+        &self,
+        circuit_id: &Uuid,
+        target: &crate::types::CommentTarget,
+    ) -> Result<Vec<crate::types::CircuitComment>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT comment FROM circuit_comments
+                 WHERE circuit_id = $1 AND target = $2
+                 ORDER BY created_at ASC",
+                &[
+                    circuit_id,
+                    &serde_json::to_value(target).unwrap_or_default(),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to load circuit comments: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn delete_circuit_comment(&self, comment_id: &Uuid) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "DELETE FROM circuit_comments WHERE comment_id = $1",
+                &[comment_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete circuit comment: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn persist_data_lake_gc_report(
+        &self,
+        report: &crate::types::DataLakeGcReport,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO data_lake_gc_reports (run_id, report, started_at)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (run_id) DO UPDATE SET
+                    report = EXCLUDED.report",
+                &[
+                    &report.run_id,
+                    &serde_json::to_value(report).unwrap_or_default(),
+                    &report.started_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist data lake GC report: {e}"))?;
+        Ok(())
+    }
+
+    /// Reports of every run, newest first
+    pub async fn load_data_lake_gc_reports(
+        &self,
+    ) -> Result<Vec<crate::types::DataLakeGcReport>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT report FROM data_lake_gc_reports ORDER BY started_at DESC",
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to load data lake GC reports: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn persist_data_quality_report(
+        &self,
+        report: &crate::types::DataQualityReport,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO data_quality_reports (report_id, circuit_id, report, generated_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (report_id) DO UPDATE SET
+                    report = EXCLUDED.report",
+                &[
+                    &report.report_id,
+                    &report.circuit_id,
+                    &serde_json::to_value(report).unwrap_or_default(),
+                    &report.generated_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist data quality report: {e}"))?;
+        Ok(())
+    }
+
+    /// Reports of a circuit, newest first
+    pub async fn load_data_quality_reports(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<crate::types::DataQualityReport>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT report FROM data_quality_reports
+                 WHERE circuit_id = $1
+                 ORDER BY generated_at DESC",
+                &[circuit_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load data quality reports: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn delete_data_quality_report(&self, report_id: &Uuid) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "DELETE FROM data_quality_reports WHERE report_id = $1",
+                &[report_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete data quality report: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn persist_metric_definition(
+        &self,
+        definition: &crate::types::MetricDefinition,
+    ) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "INSERT INTO metric_definitions (metric_id, workspace_id, definition, created_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (metric_id) DO UPDATE SET
+                    definition = EXCLUDED.definition",
+                &[
+                    &definition.metric_id,
+                    &definition.workspace_id,
+                    &serde_json::to_value(definition).unwrap_or_default(),
+                    &definition.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to persist metric definition: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn load_metric_definition(
+        &self,
+        metric_id: &Uuid,
+    ) -> Result<Option<crate::types::MetricDefinition>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let row = client
+            .query_opt(
+                "SELECT definition FROM metric_definitions WHERE metric_id = $1",
+                &[metric_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load metric definition: {e}"))?;
+
+        Ok(row.and_then(|row| serde_json::from_value(row.get(0)).ok()))
+    }
+
+    pub async fn load_metric_definitions(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Vec<crate::types::MetricDefinition>, String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        let rows = client
+            .query(
+                "SELECT definition FROM metric_definitions
+                 WHERE workspace_id = $1
+                 ORDER BY created_at ASC",
+                &[&workspace_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to load metric definitions: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.get(0)).ok())
+            .collect())
+    }
+
+    pub async fn delete_metric_definition(&self, metric_id: &Uuid) -> Result<(), String> {
+        let client = self
+            .get_client()
+            .await
+            .map_err(|e| format!("Failed to get database client: {e}"))?;
+
+        client
+            .execute(
+                "DELETE FROM metric_definitions WHERE metric_id = $1",
+                &[metric_id],
+            )
+            .await
+            .map_err(|e| format!("Failed to delete metric definition: {e}"))?;
+        Ok(())
+    }
 }
@@ -62,6 +62,8 @@ impl PostgresStorage {
             timestamp: row.get("timestamp"),
             data_size: row.get::<_, i64>("data_size") as usize,
             identifiers: Vec::new(), // Loaded separately
+            priority: IngestionPriority::default(),
+            occurred_at: None,
         })
     }
 
@@ -133,6 +135,8 @@ impl PostgresStorage {
             source_entries: Vec::new(), // Loaded separately
             confidence_score: 1.0, // Not in DB schema yet
             status,
+            first_occurred_at: None,
+            last_occurred_at: None,
         })
     }
 }
@@ -971,6 +975,12 @@ impl StorageBackend for PostgresStorage {
                 pushed_to_circuit: None,
                 snapshot_id: None,
                 snapshot_cid: None,
+                occurred_at: None,
+                signature: None,
+                signer_public_key: None,
+                attachments: Vec::new(),
+                caused_by: None,
+                correlation_id: None,
             }))
         })
     }
@@ -1031,6 +1041,12 @@ impl StorageBackend for PostgresStorage {
                     pushed_to_circuit: None,
                     snapshot_id: None,
                     snapshot_cid: None,
+                    occurred_at: None,
+                    signature: None,
+                    signer_public_key: None,
+                    attachments: Vec::new(),
+                    caused_by: None,
+                    correlation_id: None,
                 });
             }
 
@@ -1090,6 +1106,12 @@ impl StorageBackend for PostgresStorage {
                     pushed_to_circuit: None,
                     snapshot_id: None,
                     snapshot_cid: None,
+                    occurred_at: None,
+                    signature: None,
+                    signer_public_key: None,
+                    attachments: Vec::new(),
+                    caused_by: None,
+                    correlation_id: None,
                 });
             }
 
@@ -1142,6 +1164,12 @@ impl StorageBackend for PostgresStorage {
                     pushed_to_circuit: None,
                     snapshot_id: None,
                     snapshot_cid: None,
+                    occurred_at: None,
+                    signature: None,
+                    signer_public_key: None,
+                    attachments: Vec::new(),
+                    caused_by: None,
+                    correlation_id: None,
                 });
             }
 
@@ -1194,6 +1222,12 @@ impl StorageBackend for PostgresStorage {
                     pushed_to_circuit: None,
                     snapshot_id: None,
                     snapshot_cid: None,
+                    occurred_at: None,
+                    signature: None,
+                    signer_public_key: None,
+                    attachments: Vec::new(),
+                    caused_by: None,
+                    correlation_id: None,
                 });
             }
 
@@ -1256,6 +1290,12 @@ impl StorageBackend for PostgresStorage {
                     pushed_to_circuit: None,
                     snapshot_id: None,
                     snapshot_cid: None,
+                    occurred_at: None,
+                    signature: None,
+                    signer_public_key: None,
+                    attachments: Vec::new(),
+                    caused_by: None,
+                    correlation_id: None,
                 });
             }
 
@@ -1698,6 +1738,7 @@ impl StorageBackend for PostgresStorage {
                 created_at: row.get("created_at_ts"),
                 last_login: row.get("last_login_ts"),
                 available_adapters,
+                locale: None,
             }))
         })
     }
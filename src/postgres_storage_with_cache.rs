@@ -12,6 +12,7 @@ use crate::logging::LogEntry;
 /// 3. ZERO data loss: Return success only if PostgreSQL confirms
 /// 4. ACID guarantees: PostgreSQL transactions ensure consistency
 /// 5. Performance: Redis cache provides speed, PostgreSQL ensures durability
+use crate::pagination::PageCursor;
 use crate::postgres_persistence::PostgresPersistence;
 use crate::redis_cache::RedisCache;
 use crate::storage::{StorageBackend, StorageError};
@@ -392,6 +393,25 @@ impl StorageBackend for PostgresStorageWithCache {
             .collect())
     }
 
+    fn get_archived_circuits_for_member(
+        &self,
+        member_id: &str,
+    ) -> Result<Vec<Circuit>, StorageError> {
+        Ok(self
+            .get_circuits_for_member(member_id)?
+            .into_iter()
+            .filter(|c| c.is_archived())
+            .collect())
+    }
+
+    fn get_child_circuits(&self, parent_id: &Uuid) -> Result<Vec<Circuit>, StorageError> {
+        Ok(self
+            .list_circuits()?
+            .into_iter()
+            .filter(|c| c.parent_circuit_id == Some(*parent_id))
+            .collect())
+    }
+
     // ============================================================================
     // CIRCUIT OPERATIONS - Push/Pull operation tracking
     // ============================================================================
@@ -696,6 +716,18 @@ impl StorageBackend for PostgresStorageWithCache {
         })
     }
 
+    fn store_events(&self, events: &[Event]) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_events(events)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
     fn get_event(&self, event_id: &Uuid) -> Result<Option<Event>, StorageError> {
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current().block_on(async {
@@ -1349,6 +1381,10 @@ impl StorageBackend for PostgresStorageWithCache {
             events.retain(|e| e.timestamp <= end);
         }
 
+        if let Some(filter) = &query.filter {
+            events.retain(|e| filter.matches(e));
+        }
+
         Ok(events)
     }
 
@@ -1561,6 +1597,10 @@ impl StorageBackend for PostgresStorageWithCache {
         Ok(Vec::new())
     }
 
+    fn store_receipts(&self, _receipts: &[Receipt]) -> Result<(), StorageError> {
+        Ok(())
+    }
+
     // ============================================================================
     // LOGS - System logging (separate from events)
     // ============================================================================
@@ -1584,6 +1624,10 @@ impl StorageBackend for PostgresStorageWithCache {
         Ok(())
     }
 
+    fn store_data_lake_entries(&self, _entries: &[DataLakeEntry]) -> Result<(), StorageError> {
+        Ok(())
+    }
+
     fn get_data_lake_entry(&self, entry_id: &Uuid) -> Result<Option<DataLakeEntry>, StorageError> {
         Ok(None)
     }
@@ -1674,44 +1718,80 @@ impl StorageBackend for PostgresStorageWithCache {
     // ============================================================================
 
     fn store_pending_item(&self, item: &PendingItem) -> Result<(), StorageError> {
-        Ok(())
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_pending_item(item)
+                    .await
+                    .map_err(|e| StorageError::WriteError(e.to_string()))
+            })
+        })
     }
 
     fn get_pending_item(&self, pending_id: &Uuid) -> Result<Option<PendingItem>, StorageError> {
-        Ok(None)
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_pending_item(pending_id)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
     }
 
     fn list_pending_items(&self) -> Result<Vec<PendingItem>, StorageError> {
-        Ok(Vec::new())
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_pending_items()
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
     }
 
     fn get_pending_items_by_priority(
         &self,
         priority: PendingPriority,
     ) -> Result<Vec<PendingItem>, StorageError> {
-        Ok(Vec::new())
+        let mut items = self.list_pending_items()?;
+        items.retain(|item| item.priority == priority);
+        Ok(items)
     }
 
     fn get_pending_items_by_reason(
         &self,
         reason_type: &str,
     ) -> Result<Vec<PendingItem>, StorageError> {
-        Ok(Vec::new())
+        let mut items = self.list_pending_items()?;
+        items.retain(|item| {
+            format!("{:?}", item.reason).split(['(', ' ', '{']).next() == Some(reason_type)
+        });
+        Ok(items)
     }
 
     fn get_pending_items_by_user(&self, user_id: &str) -> Result<Vec<PendingItem>, StorageError> {
-        Ok(Vec::new())
+        let mut items = self.list_pending_items()?;
+        items.retain(|item| item.user_id.as_deref() == Some(user_id));
+        Ok(items)
     }
 
     fn get_pending_items_by_workspace(
         &self,
         workspace_id: &str,
     ) -> Result<Vec<PendingItem>, StorageError> {
-        Ok(Vec::new())
+        let mut items = self.list_pending_items()?;
+        items.retain(|item| item.workspace_id.as_deref() == Some(workspace_id));
+        Ok(items)
     }
 
     fn get_pending_items_requiring_manual_review(&self) -> Result<Vec<PendingItem>, StorageError> {
-        Ok(Vec::new())
+        let mut items = self.list_pending_items()?;
+        items.retain(|item| item.manual_review_required);
+        Ok(items)
     }
 
     fn update_pending_item(&self, item: &PendingItem) -> Result<(), StorageError> {
@@ -1719,7 +1799,15 @@ impl StorageBackend for PostgresStorageWithCache {
     }
 
     fn delete_pending_item(&self, pending_id: &Uuid) -> Result<(), StorageError> {
-        Ok(())
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_pending_item(pending_id)
+                    .await
+                    .map_err(|e| StorageError::WriteError(e.to_string()))
+            })
+        })
     }
 
     // ============================================================================
@@ -2008,18 +2096,15 @@ impl StorageBackend for PostgresStorageWithCache {
     // ============================================================================
 
     fn get_system_statistics(&self) -> Result<SystemStatistics, StorageError> {
-        Ok(SystemStatistics {
-            total_users: self.list_user_accounts()?.len() as i64,
-            active_users_24h: 0, // Implementation pending
-            active_users_30d: 0, // Implementation pending
-            total_items: self.list_items()?.len() as i64,
-            total_circuits: self.list_circuits()?.len() as i64,
-            total_storage_operations: 0, // Implementation pending
-            credits_consumed_24h: 0,     // Implementation pending
-            tier_distribution: HashMap::new(),
-            adapter_usage_stats: HashMap::new(),
-            generated_at: Utc::now(),
-        })
+        // Credit and storage-operation totals are not loaded from PostgreSQL yet
+        Ok(SystemStatistics::tally(
+            &self.list_user_accounts()?,
+            std::iter::empty(),
+            std::iter::empty(),
+            self.list_items()?.len() as i64,
+            self.list_circuits()?.len() as i64,
+            Utc::now(),
+        ))
     }
 
     fn update_system_statistics(&self, _stats: &SystemStatistics) -> Result<(), StorageError> {
@@ -2083,6 +2168,2659 @@ impl StorageBackend for PostgresStorageWithCache {
         Ok(0)
     }
 
+    // Organization directory operations
+    fn store_organization_profile(
+        &self,
+        profile: &OrganizationProfile,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_organization_profile(profile)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_organization_profile(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<OrganizationProfile>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_organization_profile(user_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_organization_profiles(&self) -> Result<Vec<OrganizationProfile>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_organization_profiles()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_organization_profile(&self, user_id: &str) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_organization_profile(user_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Attestation operations
+    fn store_attestation(&self, attestation: &Attestation) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_attestation(attestation)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_attestation(&self, attestation_id: &Uuid) -> Result<Option<Attestation>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_attestation(attestation_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_attestations_for_subject(
+        &self,
+        subject_id: &str,
+    ) -> Result<Vec<Attestation>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_attestations_for_subject(subject_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_attestations_by_attester(
+        &self,
+        attester_id: &str,
+    ) -> Result<Vec<Attestation>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_attestations_by_attester(attester_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Saved audit query operations
+    fn store_saved_audit_query(&self, query: &SavedAuditQuery) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_saved_audit_query(query)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_saved_audit_query(
+        &self,
+        query_id: &Uuid,
+    ) -> Result<Option<SavedAuditQuery>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_saved_audit_query(query_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_saved_audit_queries(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<SavedAuditQuery>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_saved_audit_queries(owner_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_saved_audit_query(&self, query_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_saved_audit_query(query_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Preview environment operations
+    fn store_preview_environment(&self, preview: &PreviewEnvironment) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_preview_environment(preview)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_preview_environment(
+        &self,
+        preview_id: &Uuid,
+    ) -> Result<Option<PreviewEnvironment>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_preview_environment(preview_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_preview_environments(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<PreviewEnvironment>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_preview_environments(owner_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Paginated listings (ordered by creation timestamp, then id)
+    fn list_items_page(
+        &self,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Item>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_items_page(after, limit)
+                    .await
+                    .map_err(|e| StorageError::ReadError(format!("PostgreSQL read failed: {e}")))
+            })
+        })
+    }
+
+    fn list_events_page(
+        &self,
+        dfid: Option<&str>,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Event>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_events_page(dfid, after, limit)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    // Legacy connector operations
+    fn store_connector_config(&self, config: &ConnectorConfig) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_connector_config(config)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_connector_config(
+        &self,
+        connector_id: &Uuid,
+    ) -> Result<Option<ConnectorConfig>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_connector_config(connector_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_connector_configs(&self) -> Result<Vec<ConnectorConfig>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_connector_configs()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_connector_config(&self, connector_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_connector_config(connector_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn store_mapping_template(&self, template: &MappingTemplate) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_mapping_template(template)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_mapping_template(
+        &self,
+        template_id: &Uuid,
+    ) -> Result<Option<MappingTemplate>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_mapping_template(template_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_mapping_templates(&self, owner_id: &str) -> Result<Vec<MappingTemplate>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_mapping_templates(owner_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_connector_state(&self, state: &ConnectorState) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_connector_state(state)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_connector_state(
+        &self,
+        connector_id: &Uuid,
+    ) -> Result<Option<ConnectorState>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_connector_state(connector_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_connector_error(&self, error: &ConnectorRunError) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_connector_error(error)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_connector_errors(
+        &self,
+        connector_id: &Uuid,
+        limit: usize,
+    ) -> Result<Vec<ConnectorRunError>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_connector_errors(connector_id, limit)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_mapping_template(&self, template_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_mapping_template(template_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Circuit change feed operations
+    fn append_change_record(&self, record: &ChangeRecord) -> Result<ChangeRecord, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.append_change_record(record)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_change_records(
+        &self,
+        circuit_id: &Uuid,
+        after_sequence: u64,
+        limit: usize,
+    ) -> Result<Vec<ChangeRecord>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_change_records(circuit_id, after_sequence, limit)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn get_latest_change_sequence(&self, circuit_id: &Uuid) -> Result<u64, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_latest_change_sequence(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_change_feed_subscription(
+        &self,
+        subscription: &ChangeFeedSubscription,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_change_feed_subscription(subscription)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_change_feed_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<Option<ChangeFeedSubscription>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_change_feed_subscription(subscription_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_change_feed_subscriptions(
+        &self,
+        circuit_id: Option<&Uuid>,
+    ) -> Result<Vec<ChangeFeedSubscription>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_change_feed_subscriptions(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_change_feed_subscription(&self, subscription_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_change_feed_subscription(subscription_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Item search
+    fn search_items(
+        &self,
+        query: &ItemSearchQuery,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Item>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.search_items_page(query, after, limit)
+                    .await
+                    .map_err(|e| StorageError::ReadError(format!("PostgreSQL read failed: {e}")))
+            })
+        })
+    }
+
+    // Announcements
+    fn store_announcement(&self, announcement: &Announcement) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_announcement(announcement)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_announcement(
+        &self,
+        announcement_id: &Uuid,
+    ) -> Result<Option<Announcement>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_announcement(announcement_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_announcements(&self) -> Result<Vec<Announcement>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_announcements()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Workspace engagement snapshots
+    fn store_workspace_engagement(
+        &self,
+        engagement: &WorkspaceEngagement,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_workspace_engagement(engagement)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_workspace_engagement(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<WorkspaceEngagement>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_workspace_engagement(workspace_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Data export jobs
+    fn store_data_export_job(&self, job: &DataExportJob) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_data_export_job(job)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_data_export_job(&self, job_id: &Uuid) -> Result<Option<DataExportJob>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_data_export_job(job_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_data_export_jobs(
+        &self,
+        requested_by: &str,
+    ) -> Result<Vec<DataExportJob>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_data_export_jobs(requested_by)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // SLA windows
+    fn store_sla_window(&self, window: &SlaWindow) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_sla_window(window)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_sla_window(
+        &self,
+        component: SlaComponent,
+        hour_start: DateTime<Utc>,
+    ) -> Result<Option<SlaWindow>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_sla_window(component, hour_start)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_sla_windows(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<SlaWindow>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_sla_windows(from, to)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Event schemas
+    fn store_event_schema(&self, schema: &EventSchema) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_event_schema(schema)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_event_schemas(&self) -> Result<Vec<EventSchema>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_event_schemas()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Maintenance mode
+    fn store_maintenance_mode(&self, mode: &MaintenanceMode) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_maintenance_mode(mode)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_maintenance_mode(&self) -> Result<Option<MaintenanceMode>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_maintenance_mode()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Managed keys
+    fn store_managed_key(&self, key: &ManagedKey) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_managed_key(key)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_managed_key(&self, key_id: &Uuid) -> Result<Option<ManagedKey>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_managed_key(key_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_managed_keys(&self) -> Result<Vec<ManagedKey>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_managed_keys()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Custom event types
+    fn store_custom_event_type(&self, event_type: &CustomEventType) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_custom_event_type(event_type)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_custom_event_types(&self) -> Result<Vec<CustomEventType>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_custom_event_types()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_custom_event_type(
+        &self,
+        circuit_id: &Uuid,
+        name: &str,
+    ) -> Result<bool, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_custom_event_type(circuit_id, name)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Document notarizations
+    fn store_notarization(&self, notarization: &DocumentNotarization) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_notarization(notarization)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_notarization(
+        &self,
+        notarization_id: &Uuid,
+    ) -> Result<Option<DocumentNotarization>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_notarization(notarization_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_notarizations(&self) -> Result<Vec<DocumentNotarization>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_notarizations()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_notarization_batch(&self, batch: &NotarizationBatch) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_notarization_batch(batch)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_notarization_batch(
+        &self,
+        batch_id: &Uuid,
+    ) -> Result<Option<NotarizationBatch>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_notarization_batch(batch_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Enrichment policies
+    fn store_enrichment_policy(&self, policy: &EnrichmentPolicy) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_enrichment_policy(policy)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_enrichment_policy(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<EnrichmentPolicy>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_enrichment_policy(workspace_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Event signing keys
+    fn store_event_signing_key(&self, key: &EventSigningKey) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_event_signing_key(key)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_event_signing_key(
+        &self,
+        key_id: &Uuid,
+    ) -> Result<Option<EventSigningKey>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_event_signing_key(key_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_event_signing_keys(&self) -> Result<Vec<EventSigningKey>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_event_signing_keys()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Anchoring SLAs, fee samples and anchoring spend
+    fn store_anchoring_sla(&self, sla: &AnchoringSla) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_anchoring_sla(sla)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_anchoring_sla(&self, circuit_id: &Uuid) -> Result<Option<AnchoringSla>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_anchoring_sla(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_fee_sample(&self, sample: &FeeSample) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_fee_sample(sample)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_latest_fee_sample(
+        &self,
+        network: &AnchoringNetwork,
+    ) -> Result<Option<FeeSample>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_latest_fee_sample(network)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_anchoring_cost_record(
+        &self,
+        record: &AnchoringCostRecord,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_anchoring_cost_record(record)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_anchoring_cost_records(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AnchoringCostRecord>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_anchoring_cost_records(since)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Event causality
+    fn get_events_caused_by(&self, event_id: &Uuid) -> Result<Vec<Event>, StorageError> {
+        let events = self.list_events()?;
+
+        Ok(events
+            .into_iter()
+            .filter(|e| e.caused_by == Some(*event_id))
+            .collect())
+    }
+
+    fn get_events_by_correlation_id(
+        &self,
+        correlation_id: &Uuid,
+    ) -> Result<Vec<Event>, StorageError> {
+        let events = self.list_events()?;
+
+        Ok(events
+            .into_iter()
+            .filter(|e| e.correlation_id == Some(*correlation_id))
+            .collect())
+    }
+
+    // Item lifecycles
+    fn store_lifecycle_definition(
+        &self,
+        definition: &LifecycleDefinition,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_lifecycle_definition(definition)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_lifecycle_definition(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<LifecycleDefinition>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_lifecycle_definition(workspace_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_item_lifecycle(&self, lifecycle: &ItemLifecycle) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_item_lifecycle(lifecycle)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_item_lifecycle(&self, dfid: &str) -> Result<Option<ItemLifecycle>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_item_lifecycle(dfid)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_item_lifecycles(&self, workspace_id: &str) -> Result<Vec<ItemLifecycle>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_item_lifecycles(workspace_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Circuit access reviews
+    fn store_access_report(&self, report: &CircuitAccessReport) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_access_report(report)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_access_report(
+        &self,
+        report_id: &Uuid,
+    ) -> Result<Option<CircuitAccessReport>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_access_report(report_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_access_reports(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<CircuitAccessReport>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_access_reports(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_access_report(&self, report_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_access_report(report_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn store_access_review_schedule(
+        &self,
+        schedule: &AccessReviewSchedule,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_access_review_schedule(schedule)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_access_review_schedule(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Option<AccessReviewSchedule>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_access_review_schedule(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_access_review_schedule(&self, circuit_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_access_review_schedule(circuit_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_access_review_schedules(&self) -> Result<Vec<AccessReviewSchedule>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_access_review_schedules()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Adapter write intents
+    fn store_adapter_intent(&self, intent: &AdapterIntent) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_adapter_intent(intent)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_adapter_intent(&self, intent_id: &Uuid) -> Result<Option<AdapterIntent>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_adapter_intent(intent_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn get_incomplete_adapter_intents(&self) -> Result<Vec<AdapterIntent>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_incomplete_adapter_intents()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Circuit-scoped partner tokens
+    fn store_partner_token(&self, token: &PartnerToken) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_partner_token(token)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_partner_token(&self, token_id: &Uuid) -> Result<Option<PartnerToken>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_partner_token(token_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn get_partner_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PartnerToken>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_partner_token_by_hash(token_hash)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_partner_tokens(&self, circuit_id: &Uuid) -> Result<Vec<PartnerToken>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_partner_tokens(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn record_partner_token_usage(&self, usage: &PartnerTokenUsage) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_partner_token_usage(usage)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_partner_token_usage(
+        &self,
+        token_id: &Uuid,
+        limit: usize,
+    ) -> Result<Vec<PartnerTokenUsage>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_partner_token_usage(token_id, limit)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Circuit comment threads
+    fn store_circuit_comment(&self, comment: &CircuitComment) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_circuit_comment(comment)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_circuit_comment(
+        &self,
+        comment_id: &Uuid,
+    ) -> Result<Option<CircuitComment>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_circuit_comment(comment_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_circuit_comments(
+        &self,
+        circuit_id: &Uuid,
+        target: &CommentTarget,
+    ) -> Result<Vec<CircuitComment>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_circuit_comments(circuit_id, target)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_circuit_comment(&self, comment_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_circuit_comment(comment_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Circuit data quality reports
+    fn store_data_quality_report(&self, report: &DataQualityReport) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_data_quality_report(report)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_data_quality_reports(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<DataQualityReport>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_data_quality_reports(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_data_quality_report(&self, report_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_data_quality_report(report_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Dashboard metric definitions
+    fn store_metric_definition(&self, definition: &MetricDefinition) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_metric_definition(definition)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_metric_definition(
+        &self,
+        metric_id: &Uuid,
+    ) -> Result<Option<MetricDefinition>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_metric_definition(metric_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_metric_definitions(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Vec<MetricDefinition>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_metric_definitions(workspace_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_metric_definition(&self, metric_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_metric_definition(metric_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // ZK setup artifacts
+    fn store_zk_setup_artifact(
+        &self,
+        artifact: &crate::zk_proof_engine::ZkSetupArtifact,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_zk_setup_artifact(artifact).await.map_err(|e| {
+                    StorageError::WriteError(format!("Failed to persist ZK setup artifact: {e}"))
+                })
+            })
+        })
+    }
+
+    fn get_zk_setup_artifact(
+        &self,
+        artifact_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::ZkSetupArtifact>, StorageError> {
+        Ok(self
+            .list_zk_setup_artifacts()?
+            .into_iter()
+            .find(|artifact| artifact.artifact_id == artifact_id))
+    }
+
+    fn list_zk_setup_artifacts(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkSetupArtifact>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_zk_setup_artifacts()
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    // Data lake garbage collection - the data lake and receipts are not kept in
+    // PostgreSQL yet, so there is nothing to collect; run reports are persisted
+    fn delete_data_lake_entries(&self, _entry_ids: &[Uuid]) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn delete_receipts(&self, _receipt_ids: &[Uuid]) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn store_data_lake_gc_report(
+        &self,
+        report: &crate::types::DataLakeGcReport,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_data_lake_gc_report(report)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_data_lake_gc_reports(
+        &self,
+    ) -> Result<Vec<crate::types::DataLakeGcReport>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_data_lake_gc_reports()
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    // Device backups
+    fn store_device_backup(&self, backup: &crate::types::DeviceBackup) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_device_backup(backup).await.map_err(|e| {
+                    StorageError::WriteError(format!("Failed to persist device backup: {e}"))
+                })
+            })
+        })
+    }
+
+    fn get_device_backup(
+        &self,
+        backup_id: &Uuid,
+    ) -> Result<Option<crate::types::DeviceBackup>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_device_backup(backup_id)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn list_device_backups(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<crate::types::DeviceBackup>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_device_backups(user_id)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn delete_device_backup(&self, backup_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_device_backup(backup_id).await.map_err(|e| {
+                    StorageError::WriteError(format!("Failed to delete device backup: {e}"))
+                })
+            })
+        })
+    }
+
+    // Federation
+    fn store_federation_peer(
+        &self,
+        peer: &crate::types::FederationPeer,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_federation_peer(peer).await.map_err(|e| {
+                    StorageError::WriteError(format!("Failed to persist federation peer: {e}"))
+                })
+            })
+        })
+    }
+
+    fn get_federation_peer(
+        &self,
+        peer_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationPeer>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_federation_peer(peer_id)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn list_federation_peers(&self) -> Result<Vec<crate::types::FederationPeer>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_federation_peers()
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn delete_federation_peer(&self, peer_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_federation_peer(peer_id).await.map_err(|e| {
+                    StorageError::WriteError(format!("Failed to delete federation peer: {e}"))
+                })
+            })
+        })
+    }
+
+    fn store_federation_provenance(
+        &self,
+        provenance: &crate::types::FederationProvenance,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_federation_provenance(provenance)
+                    .await
+                    .map_err(|e| {
+                        StorageError::WriteError(format!(
+                            "Failed to persist federation provenance: {e}"
+                        ))
+                    })
+            })
+        })
+    }
+
+    fn get_federation_provenance(
+        &self,
+        record_key: &str,
+    ) -> Result<Option<crate::types::FederationProvenance>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_federation_provenance(record_key)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn store_federation_conflict(
+        &self,
+        conflict: &crate::types::FederationConflict,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_federation_conflict(conflict).await.map_err(|e| {
+                    StorageError::WriteError(format!("Failed to persist federation conflict: {e}"))
+                })
+            })
+        })
+    }
+
+    fn get_federation_conflict(
+        &self,
+        conflict_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationConflict>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_federation_conflict(conflict_id)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn list_federation_conflicts(
+        &self,
+    ) -> Result<Vec<crate::types::FederationConflict>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_federation_conflicts()
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn store_zk_proof_revocation(
+        &self,
+        revocation: &crate::zk_proof_engine::ProofRevocation,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_zk_proof_revocation(revocation)
+                    .await
+                    .map_err(|e| {
+                        StorageError::WriteError(format!(
+                            "Failed to persist ZK proof revocation: {e}"
+                        ))
+                    })
+            })
+        })
+    }
+
+    fn get_zk_proof_revocation(
+        &self,
+        proof_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofRevocation>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_zk_proof_revocation(proof_id)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn list_zk_proof_revocations(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ProofRevocation>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_zk_proof_revocations()
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn store_item_attribute_commitments(
+        &self,
+        commitments: &crate::types::ItemAttributeCommitments,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_item_attribute_commitments(commitments)
+                    .await
+                    .map_err(|e| {
+                        StorageError::WriteError(format!(
+                            "Failed to persist attribute commitments: {e}"
+                        ))
+                    })
+            })
+        })
+    }
+
+    fn get_item_attribute_commitments(
+        &self,
+        dfid: &str,
+    ) -> Result<Option<crate::types::ItemAttributeCommitments>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_item_attribute_commitments(dfid)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn store_selective_disclosure(
+        &self,
+        disclosure: &crate::types::SelectiveDisclosure,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_selective_disclosure(disclosure)
+                    .await
+                    .map_err(|e| {
+                        StorageError::WriteError(format!(
+                            "Failed to persist selective disclosure: {e}"
+                        ))
+                    })
+            })
+        })
+    }
+
+    fn get_selective_disclosure(
+        &self,
+        disclosure_id: &Uuid,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_selective_disclosure(disclosure_id)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn get_selective_disclosure_by_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_selective_disclosure_by_token(token_hash)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn list_selective_disclosures(
+        &self,
+        created_by: &str,
+    ) -> Result<Vec<crate::types::SelectiveDisclosure>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_selective_disclosures(created_by)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    // Custom ZK circuit definitions
+    fn store_custom_circuit_definition(
+        &self,
+        definition: &crate::zk_proof_engine::CustomCircuitDefinition,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_custom_circuit_definition(definition)
+                    .await
+                    .map_err(|e| {
+                        StorageError::WriteError(format!(
+                            "Failed to persist custom circuit definition: {e}"
+                        ))
+                    })
+            })
+        })
+    }
+
+    fn get_custom_circuit_definition(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::CustomCircuitDefinition>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_custom_circuit_definition(circuit_id)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn list_custom_circuit_definitions(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::CustomCircuitDefinition>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_custom_circuit_definitions()
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    // ZK proof generation jobs
+    fn store_zk_proof_job(
+        &self,
+        job: &crate::zk_proof_engine::ProofJob,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_zk_proof_job(job).await.map_err(|e| {
+                    StorageError::WriteError(format!("Failed to persist ZK proof job: {e}"))
+                })
+            })
+        })
+    }
+
+    fn get_zk_proof_job(
+        &self,
+        job_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofJob>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_zk_proof_job(job_id)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn list_zk_proof_jobs(&self) -> Result<Vec<crate::zk_proof_engine::ProofJob>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_zk_proof_jobs()
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    // Item search projected to summaries
+    fn search_item_summaries(
+        &self,
+        query: &ItemSearchQuery,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<ItemSummary>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.search_item_summaries_page(query, after, limit)
+                    .await
+                    .map_err(|e| StorageError::ReadError(format!("PostgreSQL read failed: {e}")))
+            })
+        })
+    }
+
+    // Per-workspace API usage and anomalies
+    fn store_workspace_usage_window(
+        &self,
+        window: &crate::types::WorkspaceUsageWindow,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_workspace_usage_window(window)
+                    .await
+                    .map_err(|e| {
+                        StorageError::WriteError(format!("Failed to persist usage window: {e}"))
+                    })
+            })
+        })
+    }
+
+    fn get_workspace_usage_window(
+        &self,
+        workspace_id: &str,
+        hour_start: DateTime<Utc>,
+    ) -> Result<Option<crate::types::WorkspaceUsageWindow>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_workspace_usage_window(workspace_id, hour_start)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn list_workspace_usage_windows(
+        &self,
+        workspace_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::WorkspaceUsageWindow>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_workspace_usage_windows(workspace_id, from, to)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn store_usage_anomaly_baseline(
+        &self,
+        baseline: &crate::types::UsageAnomalyBaseline,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_usage_anomaly_baseline(baseline)
+                    .await
+                    .map_err(|e| {
+                        StorageError::WriteError(format!("Failed to persist usage baseline: {e}"))
+                    })
+            })
+        })
+    }
+
+    fn get_usage_anomaly_baseline(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::UsageAnomalyBaseline>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_usage_anomaly_baseline(workspace_id)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn store_usage_anomaly(
+        &self,
+        anomaly: &crate::types::UsageAnomaly,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_usage_anomaly(anomaly).await.map_err(|e| {
+                    StorageError::WriteError(format!("Failed to persist usage anomaly: {e}"))
+                })
+            })
+        })
+    }
+
+    fn list_usage_anomalies(
+        &self,
+        workspace_id: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::UsageAnomaly>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_usage_anomalies(workspace_id, since)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    // Proof share links
+    fn store_proof_share_link(
+        &self,
+        link: &crate::types::ProofShareLink,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_proof_share_link(link).await.map_err(|e| {
+                    StorageError::WriteError(format!("Failed to persist proof share link: {e}"))
+                })
+            })
+        })
+    }
+
+    fn get_proof_share_link(
+        &self,
+        share_id: &Uuid,
+    ) -> Result<Option<crate::types::ProofShareLink>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_proof_share_link(share_id)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn list_proof_share_links(
+        &self,
+        proof_id: &Uuid,
+    ) -> Result<Vec<crate::types::ProofShareLink>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_proof_share_links(proof_id)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn record_proof_share_view(
+        &self,
+        share_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<crate::types::ProofShareLink>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.record_proof_share_view(share_id, now)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Workspace dedup configs
+    fn store_workspace_dedup_config(
+        &self,
+        config: &crate::types::WorkspaceDedupConfig,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_workspace_dedup_config(config)
+                    .await
+                    .map_err(|e| {
+                        StorageError::WriteError(format!(
+                            "Failed to persist workspace dedup config: {e}"
+                        ))
+                    })
+            })
+        })
+    }
+
+    fn get_workspace_dedup_config(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceDedupConfig>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_workspace_dedup_config(workspace_id)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    // Sensor time series
+    fn store_sensor_series(&self, series: &crate::types::SensorSeries) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_sensor_series(series).await.map_err(|e| {
+                    StorageError::WriteError(format!("Failed to persist sensor series: {e}"))
+                })
+            })
+        })
+    }
+
+    fn get_sensor_series(
+        &self,
+        dfid: &str,
+        series: &str,
+    ) -> Result<Option<crate::types::SensorSeries>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_sensor_series(dfid, series)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn list_sensor_series(
+        &self,
+        dfid: &str,
+    ) -> Result<Vec<crate::types::SensorSeries>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_sensor_series_for_item(dfid)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn store_sensor_chunk(&self, chunk: &crate::types::SensorChunk) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_sensor_chunk(chunk).await.map_err(|e| {
+                    StorageError::WriteError(format!("Failed to persist sensor chunk: {e}"))
+                })
+            })
+        })
+    }
+
+    fn get_sensor_chunks(
+        &self,
+        dfid: &str,
+        series: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorChunk>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_sensor_chunks(dfid, series, from, to)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn store_sensor_rollup(&self, rollup: &crate::types::SensorRollup) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_sensor_rollup(rollup).await.map_err(|e| {
+                    StorageError::WriteError(format!("Failed to persist sensor rollup: {e}"))
+                })
+            })
+        })
+    }
+
+    fn get_sensor_rollups(
+        &self,
+        dfid: &str,
+        series: &str,
+        resolution: crate::types::SensorResolution,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorRollup>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_sensor_rollups(dfid, series, resolution, from, to)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn list_all_sensor_series(&self) -> Result<Vec<crate::types::SensorSeries>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_all_sensor_series()
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    // Workspace buckets
+    fn store_workspace_bucket_config(
+        &self,
+        config: &crate::types::WorkspaceBucketConfig,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_workspace_bucket_config(config)
+                    .await
+                    .map_err(|e| {
+                        StorageError::WriteError(format!(
+                            "Failed to persist workspace bucket config: {e}"
+                        ))
+                    })
+            })
+        })
+    }
+
+    fn get_workspace_bucket_config(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceBucketConfig>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_workspace_bucket_config(workspace_id)
+                    .await
+                    .map_err(|e| StorageError::ReadError(e.to_string()))
+            })
+        })
+    }
+
+    fn delete_workspace_bucket_config(&self, workspace_id: &str) -> Result<bool, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_workspace_bucket_config(workspace_id)
+                    .await
+                    .map_err(|e| {
+                        StorageError::WriteError(format!(
+                            "Failed to delete workspace bucket config: {e}"
+                        ))
+                    })
+            })
+        })
+    }
+
+    // DFID sequence leases
+    fn lease_dfid_sequence_range(&self, day: &str, count: u64) -> Result<u64, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.lease_dfid_sequence_range(day, count)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Re-verification jobs and merge proposals
+    fn store_reverification_job(&self, job: &ReverificationJob) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_reverification_job(job)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_reverification_job(
+        &self,
+        job_id: &Uuid,
+    ) -> Result<Option<ReverificationJob>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_reverification_job(job_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_reverification_jobs(&self) -> Result<Vec<ReverificationJob>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_reverification_jobs()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_merge_proposal(&self, proposal: &MergeProposal) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_merge_proposal(proposal)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_merge_proposal(
+        &self,
+        proposal_id: &Uuid,
+    ) -> Result<Option<MergeProposal>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_merge_proposal(proposal_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_merge_proposals(&self) -> Result<Vec<MergeProposal>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_merge_proposals()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Runbook hooks
+    fn store_runbook_hook(&self, hook: &RunbookHook) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_runbook_hook(hook)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_runbook_hook(&self, hook_id: &Uuid) -> Result<Option<RunbookHook>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_runbook_hook(hook_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_runbook_hooks(&self) -> Result<Vec<RunbookHook>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_runbook_hooks()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_runbook_hook(&self, hook_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.delete_runbook_hook(hook_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Workspace verification rules
+    fn store_workspace_verification_rules(
+        &self,
+        rules: &WorkspaceVerificationRules,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_workspace_verification_rules(rules)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_workspace_verification_rules(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<WorkspaceVerificationRules>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_workspace_verification_rules(workspace_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Dedup sharing agreements
+    fn store_dedup_sharing_agreement(
+        &self,
+        agreement: &DedupSharingAgreement,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_dedup_sharing_agreement(agreement)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_dedup_sharing_agreement(
+        &self,
+        agreement_id: &Uuid,
+    ) -> Result<Option<DedupSharingAgreement>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_dedup_sharing_agreement(agreement_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_dedup_sharing_agreements(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<DedupSharingAgreement>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_dedup_sharing_agreements(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Paginated CID timeline
+    fn get_item_timeline_page(
+        &self,
+        dfid: &str,
+        query: &TimelineQuery,
+        after_sequence: Option<i32>,
+        limit: usize,
+    ) -> Result<Vec<TimelineEntry>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.get_item_timeline_page(dfid, query, after_sequence, limit)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Circuit membership reconciliation
+    fn store_membership_reconciliation_report(
+        &self,
+        report: &MembershipReconciliationReport,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.persist_membership_reconciliation_report(report)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_membership_reconciliation_reports(
+        &self,
+    ) -> Result<Vec<MembershipReconciliationReport>, StorageError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let pg = self.get_postgres().await?;
+
+                pg.load_membership_reconciliation_reports()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
     // ============================================================================
     // ✅ ALL 168 METHODS IMPLEMENTED!
     // PostgreSQL as single source of truth + Optional Redis cache
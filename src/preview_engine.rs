@@ -0,0 +1,558 @@
+//! Preview environments: configuration-only clones of production circuits.
+//!
+//! A preview copies a circuit's schema (alias config, namespace) and policies
+//! (permissions, required attestations, custom roles) into a staging namespace.
+//! Items, events, members, adapters and webhooks are never copied, so pushes to a
+//! preview circuit cannot reach production storage or external endpoints. Edits made
+//! through the regular circuit APIs can then be diffed and promoted back.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    Circuit, CircuitStatus, CustomRole, Permission, PreviewCircuit, PreviewConfigChange,
+    PreviewDiff, PreviewEnvironment, PreviewStatus,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const MAX_NAMESPACE_LENGTH: usize = 48;
+
+/// Circuit configuration fields that are cloned, diffed and promoted
+const PROMOTABLE_FIELDS: [&str; 5] = [
+    "description",
+    "default_namespace",
+    "alias_config",
+    "permissions",
+    "custom_roles",
+];
+
+#[derive(Debug)]
+pub enum PreviewError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+    NotFound(String),
+    Conflict(String),
+}
+
+impl From<StorageError> for PreviewError {
+    fn from(err: StorageError) -> Self {
+        PreviewError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for PreviewError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PreviewError::StorageError(e) => write!(f, "Storage error: {e}"),
+            PreviewError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            PreviewError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            PreviewError::NotFound(e) => write!(f, "Not found: {e}"),
+            PreviewError::Conflict(e) => write!(f, "Conflict: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PreviewError {}
+
+pub struct PreviewEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> PreviewEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Clone the configuration of `circuit_ids` (or every circuit the requester owns)
+    /// into a new staging namespace
+    pub fn create_preview(
+        &self,
+        requester_id: &str,
+        namespace: Option<String>,
+        circuit_ids: Option<Vec<Uuid>>,
+    ) -> Result<PreviewEnvironment, PreviewError> {
+        let preview_id = Uuid::new_v4();
+        let namespace = match namespace {
+            Some(namespace) => validate_namespace(namespace.trim())?,
+            None => format!("staging-{}", &preview_id.simple().to_string()[..8]),
+        };
+
+        let sources = match circuit_ids {
+            Some(ids) => {
+                let mut sources = Vec::with_capacity(ids.len());
+                for id in ids {
+                    let circuit = self
+                        .storage
+                        .get_circuit(&id)?
+                        .ok_or_else(|| PreviewError::NotFound(format!("Circuit {id}")))?;
+                    ensure_can_configure(&circuit, requester_id)?;
+                    sources.push(circuit);
+                }
+                sources
+            }
+            None => {
+                let previews = self.preview_circuit_ids(requester_id)?;
+                self.storage
+                    .get_circuits_for_member(requester_id)?
+                    .into_iter()
+                    .filter(|c| c.owner_id == requester_id)
+                    .filter(|c| !c.is_archived())
+                    .filter(|c| !previews.contains(&c.circuit_id))
+                    .collect()
+            }
+        };
+        if sources.is_empty() {
+            return Err(PreviewError::ValidationError(
+                "No circuits to clone into the preview".to_string(),
+            ));
+        }
+
+        let workspace_id = self
+            .storage
+            .get_user_account(requester_id)?
+            .and_then(|u| u.workspace_id);
+
+        let mut circuits = Vec::with_capacity(sources.len());
+        for source in &sources {
+            let clone = clone_configuration(source, &namespace, requester_id);
+            self.storage.store_circuit(&clone)?;
+            circuits.push(PreviewCircuit {
+                source_circuit_id: source.circuit_id,
+                preview_circuit_id: clone.circuit_id,
+                source_fingerprint: config_fingerprint(source),
+            });
+        }
+
+        let now = Utc::now();
+        let preview = PreviewEnvironment {
+            preview_id,
+            namespace,
+            workspace_id,
+            owner_id: requester_id.to_string(),
+            circuits,
+            status: PreviewStatus::Active,
+            created_at: now,
+            updated_at: now,
+            promoted_at: None,
+        };
+        self.storage.store_preview_environment(&preview)?;
+        Ok(preview)
+    }
+
+    pub fn get_preview(
+        &self,
+        preview_id: &Uuid,
+        requester_id: &str,
+    ) -> Result<PreviewEnvironment, PreviewError> {
+        let preview = self
+            .storage
+            .get_preview_environment(preview_id)?
+            .ok_or_else(|| PreviewError::NotFound(format!("Preview {preview_id}")))?;
+        if preview.owner_id != requester_id {
+            return Err(PreviewError::PermissionDenied(
+                "Only the preview owner can access it".to_string(),
+            ));
+        }
+        Ok(preview)
+    }
+
+    pub fn list_previews(&self, owner_id: &str) -> Result<Vec<PreviewEnvironment>, PreviewError> {
+        let mut previews = self.storage.list_preview_environments(owner_id)?;
+        previews.sort_by_key(|p| std::cmp::Reverse(p.created_at));
+        Ok(previews)
+    }
+
+    /// Configuration differences between the preview circuits and production
+    pub fn diff_preview(
+        &self,
+        preview_id: &Uuid,
+        requester_id: &str,
+    ) -> Result<PreviewDiff, PreviewError> {
+        let preview = self.get_preview(preview_id, requester_id)?;
+        let mut changes = Vec::new();
+        let mut drifted_circuits = Vec::new();
+
+        for (mapping, production, preview_circuit) in self.load_pairs(&preview)? {
+            if config_fingerprint(&production) != mapping.source_fingerprint {
+                drifted_circuits.push(mapping.source_circuit_id);
+            }
+            let production_config = config_view(&production);
+            let preview_config = config_view(&preview_circuit);
+            for field in PROMOTABLE_FIELDS {
+                if production_config[field] != preview_config[field] {
+                    changes.push(PreviewConfigChange {
+                        source_circuit_id: mapping.source_circuit_id,
+                        preview_circuit_id: mapping.preview_circuit_id,
+                        field: field.to_string(),
+                        production: production_config[field].clone(),
+                        preview: preview_config[field].clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(PreviewDiff {
+            preview_id: preview.preview_id,
+            changes,
+            drifted_circuits,
+            generated_at: Utc::now(),
+        })
+    }
+
+    /// Apply the preview configuration to the production circuits and close the preview.
+    /// Refuses when production changed after cloning unless `force` is set.
+    /// Returns the applied diff and the updated production circuits.
+    pub fn promote_preview(
+        &self,
+        preview_id: &Uuid,
+        requester_id: &str,
+        force: bool,
+    ) -> Result<(PreviewDiff, Vec<Circuit>), PreviewError> {
+        let mut preview = self.get_preview(preview_id, requester_id)?;
+        ensure_active(&preview)?;
+
+        let diff = self.diff_preview(preview_id, requester_id)?;
+        if !diff.drifted_circuits.is_empty() && !force {
+            return Err(PreviewError::Conflict(format!(
+                "{} production circuit(s) changed since the preview was created; review the diff and promote with force",
+                diff.drifted_circuits.len()
+            )));
+        }
+
+        let pairs = self.load_pairs(&preview)?;
+        for (_, production, _) in &pairs {
+            ensure_can_configure(production, requester_id)?;
+        }
+
+        let mut updated = Vec::new();
+        for (mapping, mut production, preview_circuit) in pairs {
+            if !diff
+                .changes
+                .iter()
+                .any(|c| c.source_circuit_id == mapping.source_circuit_id)
+            {
+                continue;
+            }
+            apply_configuration(&preview_circuit, &mut production, requester_id);
+            self.storage.update_circuit(&production)?;
+            updated.push(production);
+        }
+
+        self.archive_preview_circuits(&preview)?;
+        let now = Utc::now();
+        preview.status = PreviewStatus::Promoted;
+        preview.promoted_at = Some(now);
+        preview.updated_at = now;
+        self.storage.store_preview_environment(&preview)?;
+
+        Ok((diff, updated))
+    }
+
+    /// Close the preview without touching production
+    pub fn discard_preview(
+        &self,
+        preview_id: &Uuid,
+        requester_id: &str,
+    ) -> Result<PreviewEnvironment, PreviewError> {
+        let mut preview = self.get_preview(preview_id, requester_id)?;
+        ensure_active(&preview)?;
+
+        self.archive_preview_circuits(&preview)?;
+        preview.status = PreviewStatus::Discarded;
+        preview.updated_at = Utc::now();
+        self.storage.store_preview_environment(&preview)?;
+        Ok(preview)
+    }
+
+    fn load_pairs(
+        &self,
+        preview: &PreviewEnvironment,
+    ) -> Result<Vec<(PreviewCircuit, Circuit, Circuit)>, PreviewError> {
+        preview
+            .circuits
+            .iter()
+            .map(|mapping| {
+                let production = self
+                    .storage
+                    .get_circuit(&mapping.source_circuit_id)?
+                    .ok_or_else(|| {
+                        PreviewError::NotFound(format!("Circuit {}", mapping.source_circuit_id))
+                    })?;
+                let preview_circuit = self
+                    .storage
+                    .get_circuit(&mapping.preview_circuit_id)?
+                    .ok_or_else(|| {
+                        PreviewError::NotFound(format!(
+                            "Preview circuit {}",
+                            mapping.preview_circuit_id
+                        ))
+                    })?;
+                Ok((mapping.clone(), production, preview_circuit))
+            })
+            .collect()
+    }
+
+    fn archive_preview_circuits(&self, preview: &PreviewEnvironment) -> Result<(), PreviewError> {
+        for mapping in &preview.circuits {
+            if let Some(mut circuit) = self.storage.get_circuit(&mapping.preview_circuit_id)? {
+                let now = Utc::now();
+                circuit.status = CircuitStatus::Archived;
+                circuit.archived_at = Some(now);
+                circuit.archived_by = Some(preview.owner_id.clone());
+                circuit.last_modified = now;
+                self.storage.update_circuit(&circuit)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Circuits created as clones by this user's previews, so they are not cloned again
+    fn preview_circuit_ids(&self, owner_id: &str) -> Result<Vec<Uuid>, PreviewError> {
+        Ok(self
+            .storage
+            .list_preview_environments(owner_id)?
+            .iter()
+            .flat_map(|p| p.circuits.iter().map(|c| c.preview_circuit_id))
+            .collect())
+    }
+}
+
+fn validate_namespace(namespace: &str) -> Result<String, PreviewError> {
+    if namespace.is_empty() || namespace.len() > MAX_NAMESPACE_LENGTH {
+        return Err(PreviewError::ValidationError(format!(
+            "Namespace must be 1-{MAX_NAMESPACE_LENGTH} characters"
+        )));
+    }
+    if !namespace
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(PreviewError::ValidationError(
+            "Namespace may only contain lowercase letters, digits and '-'".to_string(),
+        ));
+    }
+    Ok(namespace.to_string())
+}
+
+fn ensure_can_configure(circuit: &Circuit, requester_id: &str) -> Result<(), PreviewError> {
+    if circuit.owner_id != requester_id
+        && !circuit.has_permission(requester_id, &Permission::ManagePermissions)
+    {
+        return Err(PreviewError::PermissionDenied(format!(
+            "Only circuit owner or admins can preview configuration of circuit {}",
+            circuit.circuit_id
+        )));
+    }
+    Ok(())
+}
+
+fn ensure_active(preview: &PreviewEnvironment) -> Result<(), PreviewError> {
+    if preview.status != PreviewStatus::Active {
+        return Err(PreviewError::Conflict(format!(
+            "Preview is already {:?}",
+            preview.status
+        )));
+    }
+    Ok(())
+}
+
+/// Comparable view of the promotable configuration. Custom roles are keyed by name
+/// because role ids and circuit ids differ between a circuit and its clone.
+fn config_view(circuit: &Circuit) -> Value {
+    let mut roles: Vec<Value> = circuit
+        .custom_roles
+        .iter()
+        .map(|r| {
+            json!({
+                "role_name": r.role_name,
+                "permissions": r.permissions,
+                "description": r.description,
+                "color": r.color,
+                "is_default": r.is_default
+            })
+        })
+        .collect();
+    roles.sort_by_key(|r| r["role_name"].as_str().unwrap_or_default().to_string());
+
+    json!({
+        "description": circuit.description,
+        "default_namespace": circuit.default_namespace,
+        "alias_config": circuit.alias_config,
+        "permissions": circuit.permissions,
+        "custom_roles": roles
+    })
+}
+
+fn config_fingerprint(circuit: &Circuit) -> String {
+    blake3::hash(config_view(circuit).to_string().as_bytes())
+        .to_hex()
+        .to_string()
+}
+
+fn clone_configuration(source: &Circuit, namespace: &str, requester_id: &str) -> Circuit {
+    let mut clone = Circuit::new(
+        format!("[{namespace}] {}", source.name),
+        source.description.clone(),
+        requester_id.to_string(),
+    );
+    clone.default_namespace = source.default_namespace.clone();
+    clone.alias_config = source.alias_config.clone();
+    clone.permissions = source.permissions.clone();
+    clone.custom_roles = source
+        .custom_roles
+        .iter()
+        .map(|role| CustomRole {
+            role_id: Uuid::new_v4(),
+            circuit_id: clone.circuit_id,
+            ..role.clone()
+        })
+        .collect();
+    clone
+}
+
+/// Copy promotable fields onto the production circuit, keeping production role ids
+/// for roles that already exist there
+fn apply_configuration(preview: &Circuit, production: &mut Circuit, requester_id: &str) {
+    production.description = preview.description.clone();
+    production.default_namespace = preview.default_namespace.clone();
+    production.alias_config = preview.alias_config.clone();
+    production.permissions = preview.permissions.clone();
+    production.custom_roles = preview
+        .custom_roles
+        .iter()
+        .map(|role| {
+            let existing = production
+                .custom_roles
+                .iter()
+                .find(|r| r.role_name == role.role_name);
+            CustomRole {
+                role_id: existing.map_or_else(Uuid::new_v4, |r| r.role_id),
+                circuit_id: production.circuit_id,
+                created_timestamp: existing.map_or_else(Utc::now, |r| r.created_timestamp),
+                created_by: existing
+                    .map_or_else(|| requester_id.to_string(), |r| r.created_by.clone()),
+                ..role.clone()
+            }
+        })
+        .collect();
+    production.last_modified = Utc::now();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identifier_types::CircuitAliasConfig;
+    use crate::storage::InMemoryStorage;
+    use crate::types::RequiredAttestation;
+    use std::sync::{Arc, Mutex};
+
+    fn setup() -> (PreviewEngine<Arc<Mutex<InMemoryStorage>>>, Circuit) {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let circuit = Circuit::new(
+            "Beef supply".to_string(),
+            "Production circuit".to_string(),
+            "owner-1".to_string(),
+        );
+        storage.store_circuit(&circuit).unwrap();
+        (PreviewEngine::new(storage), circuit)
+    }
+
+    #[test]
+    fn test_clone_diff_and_promote() {
+        let (engine, production) = setup();
+        let preview = engine
+            .create_preview("owner-1", Some("staging-policy".to_string()), None)
+            .unwrap();
+        assert_eq!(preview.circuits.len(), 1);
+        assert!(engine
+            .diff_preview(&preview.preview_id, "owner-1")
+            .unwrap()
+            .changes
+            .is_empty());
+
+        // Tighten the push policy and schema in the preview only
+        let preview_id = preview.circuits[0].preview_circuit_id;
+        let mut staged = engine.storage.get_circuit(&preview_id).unwrap().unwrap();
+        assert!(staged.name.starts_with("[staging-policy]"));
+        staged
+            .permissions
+            .required_attestations
+            .push(RequiredAttestation {
+                claim_type: "organic".to_string(),
+                require_certifier: true,
+            });
+        staged.alias_config = Some(CircuitAliasConfig {
+            required_canonical: vec!["sisbov".to_string()],
+            ..CircuitAliasConfig::default()
+        });
+        engine.storage.update_circuit(&staged).unwrap();
+
+        let diff = engine.diff_preview(&preview.preview_id, "owner-1").unwrap();
+        let fields: Vec<&str> = diff.changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["alias_config", "permissions"]);
+        assert!(diff.drifted_circuits.is_empty());
+
+        let (_, updated) = engine
+            .promote_preview(&preview.preview_id, "owner-1", false)
+            .unwrap();
+        assert_eq!(updated.len(), 1);
+
+        let promoted = engine
+            .storage
+            .get_circuit(&production.circuit_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(promoted.permissions.required_attestations.len(), 1);
+        assert_eq!(promoted.name, production.name);
+        assert_eq!(
+            promoted.custom_roles[0].role_id,
+            production.custom_roles[0].role_id
+        );
+        assert!(matches!(
+            engine
+                .storage
+                .get_circuit(&preview_id)
+                .unwrap()
+                .unwrap()
+                .status,
+            CircuitStatus::Archived
+        ));
+        assert!(matches!(
+            engine.discard_preview(&preview.preview_id, "owner-1"),
+            Err(PreviewError::Conflict(_))
+        ));
+    }
+
+    #[test]
+    fn test_promote_refuses_drifted_production() {
+        let (engine, production) = setup();
+        let preview = engine.create_preview("owner-1", None, None).unwrap();
+        assert!(preview.namespace.starts_with("staging-"));
+
+        let mut changed = production.clone();
+        changed.description = "Edited directly in production".to_string();
+        engine.storage.update_circuit(&changed).unwrap();
+
+        assert!(matches!(
+            engine.promote_preview(&preview.preview_id, "owner-1", false),
+            Err(PreviewError::Conflict(_))
+        ));
+        assert!(matches!(
+            engine.get_preview(&preview.preview_id, "someone-else"),
+            Err(PreviewError::PermissionDenied(_))
+        ));
+        assert!(engine
+            .promote_preview(&preview.preview_id, "owner-1", true)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_rejects_invalid_namespace() {
+        let (engine, _) = setup();
+        assert!(matches!(
+            engine.create_preview("owner-1", Some("Prod Copy!".to_string()), None),
+            Err(PreviewError::ValidationError(_))
+        ));
+    }
+}
@@ -0,0 +1,510 @@
+//! Shareable links to ZK proofs.
+//!
+//! A prover hands a buyer a URL that opens one proof, its public inputs and
+//! the public timeline of the item it is about, without the buyer needing an
+//! account. The link's token is the share id and expiry signed with a key
+//! derived from the server secret, so tampered or expired links are refused
+//! before storage is touched; the stored `ProofShareLink` carries what a
+//! signature cannot: revocation, the view limit and whether the timeline is
+//! in scope.
+
+use crate::merge_lineage::aggregated_timeline;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{EventVisibility, ProofShareLink};
+use crate::zk_proof_engine::{ProofBundle, ProofStatus, ZkProofEngine, ZkProofError};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub const TOKEN_PREFIX: &str = "dfs_";
+pub const DEFAULT_EXPIRY_HOURS: i64 = 24 * 7;
+pub const MAX_EXPIRY_HOURS: i64 = 24 * 90;
+const SHARE_KEY_CONTEXT: &str = "defarm proof share links v1";
+
+#[derive(Debug)]
+pub enum ProofShareError {
+    StorageError(StorageError),
+    ValidationError(String),
+    NotFound(String),
+    ZkProofError(ZkProofError),
+    /// Malformed, tampered, expired, revoked or used-up link; deliberately
+    /// not more specific
+    InvalidToken,
+}
+
+impl From<StorageError> for ProofShareError {
+    fn from(err: StorageError) -> Self {
+        ProofShareError::StorageError(err)
+    }
+}
+
+impl From<ZkProofError> for ProofShareError {
+    fn from(err: ZkProofError) -> Self {
+        ProofShareError::ZkProofError(err)
+    }
+}
+
+impl std::fmt::Display for ProofShareError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProofShareError::StorageError(e) => write!(f, "Storage error: {e}"),
+            ProofShareError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            ProofShareError::NotFound(e) => write!(f, "Not found: {e}"),
+            ProofShareError::ZkProofError(e) => write!(f, "ZK proof error: {e}"),
+            ProofShareError::InvalidToken => {
+                write!(f, "Share link is invalid, expired or revoked")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProofShareError {}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CreateProofShareInput {
+    pub recipient: Option<String>,
+    /// Defaults to `DEFAULT_EXPIRY_HOURS`
+    pub expires_in_hours: Option<i64>,
+    /// Defaults to true
+    pub include_timeline: Option<bool>,
+    pub max_views: Option<u32>,
+}
+
+/// A public event of the proof's item, as shown to link holders
+#[derive(Debug, Clone, Serialize)]
+pub struct SharedTimelineEvent {
+    pub event_id: Uuid,
+    /// DFID the event was recorded on, which differs for merged items
+    pub source_dfid: String,
+    pub event_type: String,
+    pub timestamp: DateTime<Utc>,
+    pub occurred_at: Option<DateTime<Utc>>,
+    pub metadata: HashMap<String, serde_json::Value>,
+    pub content_hash: String,
+}
+
+/// What a link opens to. The bundle holds the proof, its public inputs and
+/// the verifying key, so the holder can verify it offline.
+#[derive(Debug, Clone, Serialize)]
+pub struct SharedProof {
+    pub share_id: Uuid,
+    pub recipient: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    /// Status on this server now; a revoked proof still opens so the holder
+    /// learns it was revoked
+    pub status: ProofStatus,
+    pub item_dfid: Option<String>,
+    pub bundle: ProofBundle,
+    pub timeline: Option<Vec<SharedTimelineEvent>>,
+}
+
+/// Key share links are signed with, derived from the server secret
+pub fn share_link_key(server_secret: &str) -> [u8; 32] {
+    blake3::derive_key(SHARE_KEY_CONTEXT, server_secret.as_bytes())
+}
+
+fn signature(key: &[u8; 32], share_id: &Uuid, expires: i64) -> blake3::Hash {
+    blake3::keyed_hash(key, format!("{}.{expires}", share_id.simple()).as_bytes())
+}
+
+pub struct ProofShareEngine<S: StorageBackend> {
+    storage: S,
+    key: [u8; 32],
+}
+
+impl<S: StorageBackend + Clone + 'static> ProofShareEngine<S> {
+    pub fn new(storage: S, key: [u8; 32]) -> Self {
+        Self { storage, key }
+    }
+
+    /// `dfs_<share id>.<expiry>.<signature>`
+    pub fn token(&self, link: &ProofShareLink) -> String {
+        let expires = link.expires_at.timestamp();
+        format!(
+            "{TOKEN_PREFIX}{}.{expires}.{}",
+            link.share_id.simple(),
+            signature(&self.key, &link.share_id, expires).to_hex()
+        )
+    }
+
+    /// The share id of a correctly signed, unexpired token
+    fn check_token(&self, token: &str, now: DateTime<Utc>) -> Option<Uuid> {
+        let mut parts = token.strip_prefix(TOKEN_PREFIX)?.split('.');
+        let share_id = Uuid::parse_str(parts.next()?).ok()?;
+        let expires: i64 = parts.next()?.parse().ok()?;
+        let signed = blake3::Hash::from_hex(parts.next()?).ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        // blake3::Hash compares in constant time
+        (signed == signature(&self.key, &share_id, expires) && now.timestamp() < expires)
+            .then_some(share_id)
+    }
+
+    fn own_proof(
+        &self,
+        user_id: &str,
+        proof_id: &Uuid,
+    ) -> Result<crate::zk_proof_engine::ZkProof, ProofShareError> {
+        self.storage
+            .get_zk_proof(proof_id)?
+            .filter(|proof| proof.prover_id == user_id)
+            .ok_or_else(|| ProofShareError::NotFound(format!("Proof {proof_id}")))
+    }
+
+    /// Create a link to one of the user's proofs. Returns the link token with
+    /// the stored link; the token can be derived again with [`Self::token`].
+    pub fn create(
+        &self,
+        user_id: &str,
+        proof_id: &Uuid,
+        input: CreateProofShareInput,
+        now: DateTime<Utc>,
+    ) -> Result<(String, ProofShareLink), ProofShareError> {
+        let proof = self.own_proof(user_id, proof_id)?;
+        if proof.status_at(now) == ProofStatus::Revoked {
+            return Err(ProofShareError::ValidationError(format!(
+                "Proof {proof_id} is revoked"
+            )));
+        }
+        // Proofs without a bundle cannot be checked by the link holder
+        ZkProofEngine::new(self.storage.clone()).export_bundle(proof_id)?;
+
+        let hours = input.expires_in_hours.unwrap_or(DEFAULT_EXPIRY_HOURS);
+        if !(1..=MAX_EXPIRY_HOURS).contains(&hours) {
+            return Err(ProofShareError::ValidationError(format!(
+                "Share links expire after 1 to {MAX_EXPIRY_HOURS} hours"
+            )));
+        }
+        if input.max_views == Some(0) {
+            return Err(ProofShareError::ValidationError(
+                "max_views must be at least 1".to_string(),
+            ));
+        }
+        // Whole seconds, as the token carries the expiry
+        let expires_at = Utc
+            .timestamp_opt((now + Duration::hours(hours)).timestamp(), 0)
+            .single()
+            .ok_or_else(|| ProofShareError::ValidationError("Invalid expiry".to_string()))?;
+
+        let link = ProofShareLink {
+            share_id: Uuid::new_v4(),
+            proof_id: *proof_id,
+            created_by: user_id.to_string(),
+            recipient: input.recipient.filter(|r| !r.trim().is_empty()),
+            include_timeline: input.include_timeline.unwrap_or(true),
+            max_views: input.max_views,
+            views: 0,
+            created_at: now,
+            expires_at,
+            revoked_at: None,
+            last_viewed_at: None,
+        };
+        self.storage.store_proof_share_link(&link)?;
+        Ok((self.token(&link), link))
+    }
+
+    /// Links to one of the user's proofs, newest first
+    pub fn list(
+        &self,
+        user_id: &str,
+        proof_id: &Uuid,
+    ) -> Result<Vec<ProofShareLink>, ProofShareError> {
+        self.own_proof(user_id, proof_id)?;
+        let mut links = self.storage.list_proof_share_links(proof_id)?;
+        links.sort_by_key(|link| std::cmp::Reverse(link.created_at));
+        Ok(links)
+    }
+
+    pub fn revoke(
+        &self,
+        user_id: &str,
+        share_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<ProofShareLink, ProofShareError> {
+        let mut link = self
+            .storage
+            .get_proof_share_link(share_id)?
+            .filter(|link| link.created_by == user_id)
+            .ok_or_else(|| ProofShareError::NotFound(format!("Share link {share_id}")))?;
+        if link.revoked_at.is_none() {
+            link.revoked_at = Some(now);
+            self.storage.store_proof_share_link(&link)?;
+        }
+        Ok(link)
+    }
+
+    /// The proof behind a live link; counts as one view
+    pub fn open(&self, token: &str, now: DateTime<Utc>) -> Result<SharedProof, ProofShareError> {
+        let share_id = self
+            .check_token(token, now)
+            .ok_or(ProofShareError::InvalidToken)?;
+        // Checked and counted in one step, so parallel opens cannot pass the limit
+        let link = self
+            .storage
+            .record_proof_share_view(&share_id, now)?
+            .ok_or(ProofShareError::InvalidToken)?;
+
+        let zk = ZkProofEngine::new(self.storage.clone());
+        let proof = zk
+            .get_proof(&link.proof_id)?
+            .ok_or(ProofShareError::InvalidToken)?;
+        let bundle = zk.export_bundle(&link.proof_id)?;
+        // The item recorded with the proof, not the prover's public inputs
+        let item_dfid = proof.item_dfid.clone();
+        let timeline = match (&item_dfid, link.include_timeline) {
+            (Some(dfid), true) => Some(self.public_timeline(dfid)?),
+            _ => None,
+        };
+
+        Ok(SharedProof {
+            share_id: link.share_id,
+            recipient: link.recipient,
+            expires_at: link.expires_at,
+            status: proof.status_at(now),
+            item_dfid,
+            bundle,
+            timeline,
+        })
+    }
+
+    /// Public, non-local, unencrypted events of the item and the items merged
+    /// into it
+    fn public_timeline(&self, dfid: &str) -> Result<Vec<SharedTimelineEvent>, ProofShareError> {
+        Ok(aggregated_timeline(&self.storage, dfid)?
+            .events
+            .into_iter()
+            .map(|entry| entry.event)
+            .filter(|event| {
+                matches!(event.visibility, EventVisibility::Public)
+                    && !event.is_local
+                    && !event.is_encrypted
+            })
+            .map(|event| SharedTimelineEvent {
+                event_id: event.event_id,
+                source_dfid: event.dfid,
+                event_type: event.event_type.to_string(),
+                timestamp: event.timestamp,
+                occurred_at: event.occurred_at,
+                metadata: event.metadata,
+                content_hash: event.content_hash,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{Event, EventType};
+    use crate::zk_proof_engine::CircuitType;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    type Storage = Arc<Mutex<InMemoryStorage>>;
+
+    const DFID: &str = "DFID-2026-0042";
+
+    fn submit(storage: &Storage, prover: &str) -> Uuid {
+        let inputs = |pairs: &[(&str, serde_json::Value)]| -> HashMap<_, _> {
+            pairs
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect()
+        };
+        ZkProofEngine::new(Arc::clone(storage))
+            .submit_proof(
+                CircuitType::PesticideThreshold,
+                prover.to_string(),
+                inputs(&[
+                    ("item_dfid", json!(DFID)),
+                    ("threshold_standard", json!("Codex")),
+                    ("threshold_ppm", json!(0.4)),
+                ]),
+                inputs(&[("pesticide_levels", json!([0.1, 0.2]))]),
+                None,
+            )
+            .unwrap()
+    }
+
+    /// The producer's item with a public and a private event, and a proof
+    /// about it. Returns the public event.
+    fn setup() -> (Storage, ProofShareEngine<Storage>, Uuid, Event) {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let mut public = Event::new(
+            DFID.to_string(),
+            EventType::Created,
+            "producer".to_string(),
+            EventVisibility::Public,
+        );
+        public.is_local = false;
+        let mut private = Event::new(
+            DFID.to_string(),
+            EventType::Updated,
+            "producer".to_string(),
+            EventVisibility::Private,
+        );
+        private.is_local = false;
+        storage.store_event(&public).unwrap();
+        storage.store_event(&private).unwrap();
+
+        let proof_id = submit(&storage, "producer");
+        let engine = ProofShareEngine::new(Arc::clone(&storage), share_link_key("secret"));
+        (storage, engine, proof_id, public)
+    }
+
+    #[test]
+    fn test_share_link_opens_proof_with_public_timeline() {
+        let (_, engine, proof_id, public) = setup();
+        let now = Utc::now();
+        let (token, _) = engine
+            .create("producer", &proof_id, Default::default(), now)
+            .unwrap();
+        assert!(token.starts_with("dfs_"));
+        assert_ne!(TOKEN_PREFIX, crate::partner_token_engine::TOKEN_PREFIX);
+
+        let shared = engine.open(&token, now).unwrap();
+        assert_eq!(shared.bundle.proof_id, proof_id);
+        assert_eq!(shared.item_dfid.as_deref(), Some(DFID));
+        assert!(crate::zk_proof_engine::verify_bundle(&shared.bundle).unwrap());
+        let timeline = shared.timeline.unwrap();
+        assert_eq!(timeline.len(), 1);
+        assert_eq!(timeline[0].event_id, public.event_id);
+    }
+
+    #[test]
+    fn test_only_the_prover_can_share() {
+        let (_, engine, proof_id, _) = setup();
+        assert!(matches!(
+            engine.create("buyer", &proof_id, Default::default(), Utc::now()),
+            Err(ProofShareError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_item_is_taken_from_the_proof_record() {
+        // A prover naming someone else's item in its public inputs does not
+        // get that item's timeline on its links
+        let (storage, engine, _, _) = setup();
+        let proof_id = submit(&storage, "mallory");
+        let now = Utc::now();
+        let (token, _) = engine
+            .create("mallory", &proof_id, Default::default(), now)
+            .unwrap();
+
+        let shared = engine.open(&token, now).unwrap();
+        assert_eq!(shared.bundle.public_inputs["item_dfid"], json!(DFID));
+        assert_eq!(shared.item_dfid, None);
+        assert!(shared.timeline.is_none());
+    }
+
+    #[test]
+    fn test_tampered_and_expired_links_are_refused() {
+        let (storage, engine, proof_id, _) = setup();
+        let now = Utc::now();
+        let (token, link) = engine
+            .create("producer", &proof_id, Default::default(), now)
+            .unwrap();
+
+        let tampered = token.replacen(".", ".9", 1);
+        assert!(matches!(
+            engine.open(&tampered, now),
+            Err(ProofShareError::InvalidToken)
+        ));
+        let other_server = ProofShareEngine::new(Arc::clone(&storage), share_link_key("other"));
+        assert!(other_server.open(&token, now).is_err());
+        assert!(engine
+            .open(&token, link.expires_at + Duration::seconds(1))
+            .is_err());
+        // None of these counted as a view
+        assert_eq!(
+            storage
+                .get_proof_share_link(&link.share_id)
+                .unwrap()
+                .unwrap()
+                .views,
+            0
+        );
+    }
+
+    #[test]
+    fn test_view_limit_ends_the_link() {
+        let (_, engine, proof_id, _) = setup();
+        let now = Utc::now();
+        let (token, _) = engine
+            .create(
+                "producer",
+                &proof_id,
+                CreateProofShareInput {
+                    recipient: Some("buyer@example.com".to_string()),
+                    max_views: Some(2),
+                    ..Default::default()
+                },
+                now,
+            )
+            .unwrap();
+
+        engine.open(&token, now).unwrap();
+        engine.open(&token, now).unwrap();
+        assert!(matches!(
+            engine.open(&token, now),
+            Err(ProofShareError::InvalidToken)
+        ));
+    }
+
+    #[test]
+    fn test_parallel_opens_respect_the_view_limit() {
+        let (_, engine, proof_id, _) = setup();
+        let now = Utc::now();
+        let (token, link) = engine
+            .create(
+                "producer",
+                &proof_id,
+                CreateProofShareInput {
+                    max_views: Some(3),
+                    ..Default::default()
+                },
+                now,
+            )
+            .unwrap();
+
+        let opened = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| engine.open(&token, now).is_ok()))
+                .collect();
+            handles
+                .into_iter()
+                .filter_map(|handle| handle.join().unwrap().then_some(()))
+                .count()
+        });
+        assert_eq!(opened, 3);
+        let links = engine.list("producer", &proof_id).unwrap();
+        assert_eq!(links[0].share_id, link.share_id);
+        assert_eq!(links[0].views, 3);
+    }
+
+    #[test]
+    fn test_revoked_link_is_refused() {
+        let (_, engine, proof_id, _) = setup();
+        let now = Utc::now();
+        let (token, link) = engine
+            .create("producer", &proof_id, Default::default(), now)
+            .unwrap();
+        engine
+            .create("producer", &proof_id, Default::default(), now)
+            .unwrap();
+        assert_eq!(engine.list("producer", &proof_id).unwrap().len(), 2);
+
+        assert!(matches!(
+            engine.revoke("buyer", &link.share_id, now),
+            Err(ProofShareError::NotFound(_))
+        ));
+        engine.revoke("producer", &link.share_id, now).unwrap();
+        assert!(matches!(
+            engine.open(&token, now),
+            Err(ProofShareError::InvalidToken)
+        ));
+    }
+}
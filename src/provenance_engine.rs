@@ -0,0 +1,609 @@
+//! Provenance manifests - one signed document listing every content hash in a DFID's history
+//!
+//! A manifest collects the item state hash, event content hashes and their Merkle root,
+//! snapshot hashes and CIDs, storage CIDs, blockchain anchor transactions and ZK proof
+//! commitments, then signs the result with the server's ed25519 key so auditors can
+//! archive a single verifiable artifact.
+
+use crate::adapters::base::StorageLocation;
+use crate::hashing::{self, HashAlgorithm};
+use crate::item_access::can_read_item;
+use crate::key_ceremony_engine::check_signer;
+use crate::merge_lineage::{aggregated_timeline, AggregatedTimeline};
+use crate::merkle_engine::hash_event;
+use crate::merkle_tree::MerkleTree;
+use crate::snapshot_types::SnapshotEntityType;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{KeyPurpose, StorageRecord};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+const MANIFEST_VERSION: u32 = 1;
+const SIGNING_KEY_CONTEXT: &str = "defarm provenance manifest signing key v1";
+
+#[derive(Debug)]
+pub enum ProvenanceError {
+    StorageError(StorageError),
+    NotFound(String),
+    InvalidManifest(String),
+    PermissionDenied(String),
+    /// The configured signing key is not the active audit signing key
+    SigningKeyError(String),
+}
+
+impl From<StorageError> for ProvenanceError {
+    fn from(err: StorageError) -> Self {
+        ProvenanceError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for ProvenanceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProvenanceError::StorageError(e) => write!(f, "Storage error: {e}"),
+            ProvenanceError::NotFound(e) => write!(f, "Not found: {e}"),
+            ProvenanceError::InvalidManifest(e) => write!(f, "Invalid manifest: {e}"),
+            ProvenanceError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            ProvenanceError::SigningKeyError(e) => write!(f, "Signing key error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ProvenanceError {}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ProvenanceHashKind {
+    /// Hash of the current item record
+    ItemState,
+    /// Content hash of a raw event payload
+    EventContent,
+    /// Snapshot ID
+    Snapshot,
+    /// IPFS CID of stored content (snapshots or adapter uploads)
+    ContentCid,
+    /// Blockchain transaction that anchors the content
+    AnchorTransaction,
+    /// Hash committing to a ZK proof's private inputs or proof bytes
+    ProofCommitment,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProvenanceHashEntry {
+    pub kind: ProvenanceHashKind,
+    pub hash: String,
+    /// Where the hash came from, e.g. `event:<uuid>` or `storage:stellar_testnet-ipfs`
+    pub source: String,
+    pub recorded_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvenanceManifest {
+    pub manifest_id: Uuid,
+    pub version: u32,
+    pub dfid: String,
+    pub generated_at: DateTime<Utc>,
+    pub generated_by: String,
+    /// Merkle root over the item's events, as served by the Merkle API
+    pub events_merkle_root: Option<String>,
+    pub entries: Vec<ProvenanceHashEntry>,
+    /// Hash over every field above, in the algorithm it names (see `hashing`)
+    pub manifest_hash: String,
+    /// Hex-encoded ed25519 public key of the signer
+    pub signer_public_key: String,
+    /// Hex-encoded ed25519 signature over `manifest_hash`
+    pub signature: String,
+}
+
+/// The fields covered by the manifest hash, in a fixed order
+#[derive(Serialize)]
+struct SignedFields<'a> {
+    manifest_id: &'a Uuid,
+    version: u32,
+    dfid: &'a str,
+    generated_at: &'a DateTime<Utc>,
+    generated_by: &'a str,
+    events_merkle_root: &'a Option<String>,
+    entries: &'a [ProvenanceHashEntry],
+}
+
+impl ProvenanceManifest {
+    pub fn calculate_manifest_hash(&self) -> String {
+        self.manifest_hash_with(hashing::default_algorithm())
+    }
+
+    fn manifest_hash_with(&self, algorithm: HashAlgorithm) -> String {
+        let fields = SignedFields {
+            manifest_id: &self.manifest_id,
+            version: self.version,
+            dfid: &self.dfid,
+            generated_at: &self.generated_at,
+            generated_by: &self.generated_by,
+            events_merkle_root: &self.events_merkle_root,
+            entries: &self.entries,
+        };
+        let canonical = serde_json::to_vec(&fields).unwrap_or_default();
+        algorithm.hash(&canonical)
+    }
+}
+
+/// Derive the manifest signing key. `PROVENANCE_SIGNING_KEY` (32-byte hex seed) takes
+/// precedence; otherwise the key is derived from the server secret so it stays stable
+/// across restarts.
+pub fn manifest_signing_key(server_secret: &str) -> SigningKey {
+    let seed: [u8; 32] = std::env::var("PROVENANCE_SIGNING_KEY")
+        .ok()
+        .and_then(|hex_seed| hex::decode(hex_seed.trim()).ok())
+        .and_then(|bytes| bytes.try_into().ok())
+        .unwrap_or_else(|| blake3::derive_key(SIGNING_KEY_CONTEXT, server_secret.as_bytes()));
+    SigningKey::from_bytes(&seed)
+}
+
+/// Check that a manifest is unmodified and signed by the key it names.
+/// Pass `expected_public_key` to also pin the signer.
+pub fn verify_manifest(
+    manifest: &ProvenanceManifest,
+    expected_public_key: Option<&str>,
+) -> Result<(), ProvenanceError> {
+    let algorithm = hashing::algorithm_of(&manifest.manifest_hash);
+    if manifest.manifest_hash_with(algorithm) != manifest.manifest_hash {
+        return Err(ProvenanceError::InvalidManifest(
+            "manifest_hash does not match manifest contents".to_string(),
+        ));
+    }
+    if let Some(expected) = expected_public_key {
+        if !expected.eq_ignore_ascii_case(&manifest.signer_public_key) {
+            return Err(ProvenanceError::InvalidManifest(
+                "Manifest was signed by an unexpected key".to_string(),
+            ));
+        }
+    }
+
+    let key_bytes: [u8; 32] = hex::decode(&manifest.signer_public_key)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| {
+            ProvenanceError::InvalidManifest("signer_public_key is not a valid key".to_string())
+        })?;
+    let signature_bytes: [u8; 64] = hex::decode(&manifest.signature)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| {
+            ProvenanceError::InvalidManifest("signature is not a valid signature".to_string())
+        })?;
+
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| ProvenanceError::InvalidManifest(format!("Invalid public key: {e}")))?;
+    verifying_key
+        .verify(
+            manifest.manifest_hash.as_bytes(),
+            &Signature::from_bytes(&signature_bytes),
+        )
+        .map_err(|_| {
+            ProvenanceError::InvalidManifest("Signature does not match manifest".to_string())
+        })
+}
+
+pub struct ProvenanceEngine<S: StorageBackend> {
+    storage: S,
+    signing_key: SigningKey,
+}
+
+impl<S: StorageBackend> ProvenanceEngine<S> {
+    pub fn new(storage: S, signing_key: SigningKey) -> Self {
+        Self {
+            storage,
+            signing_key,
+        }
+    }
+
+    pub fn signer_public_key(&self) -> String {
+        hex::encode(self.signing_key.verifying_key().to_bytes())
+    }
+
+    /// History of a DFID including the items merged into it
+    pub fn timeline(
+        &self,
+        dfid: &str,
+        requester_id: &str,
+    ) -> Result<AggregatedTimeline, ProvenanceError> {
+        if self.storage.get_item_by_dfid(dfid)?.is_none() {
+            return Err(ProvenanceError::NotFound(format!("Item {dfid}")));
+        }
+        if !can_read_item(&self.storage, dfid, requester_id)? {
+            return Err(ProvenanceError::PermissionDenied(format!(
+                "No access to item {dfid}"
+            )));
+        }
+        Ok(aggregated_timeline(&self.storage, dfid)?)
+    }
+
+    /// Build and sign the provenance manifest for a DFID
+    pub fn build_manifest(
+        &self,
+        dfid: &str,
+        requester_id: &str,
+    ) -> Result<ProvenanceManifest, ProvenanceError> {
+        let item = self
+            .storage
+            .get_item_by_dfid(dfid)?
+            .ok_or_else(|| ProvenanceError::NotFound(format!("Item {dfid}")))?;
+        if !can_read_item(&self.storage, dfid, requester_id)? {
+            return Err(ProvenanceError::PermissionDenied(format!(
+                "No access to item {dfid}"
+            )));
+        }
+
+        let mut collector = EntryCollector::default();
+
+        // Going through Value sorts map keys, so the hash is stable across runs
+        let item_state = serde_json::to_value(&item)
+            .and_then(|v| serde_json::to_vec(&v))
+            .unwrap_or_default();
+        collector.push(
+            ProvenanceHashKind::ItemState,
+            hashing::hash(&item_state),
+            "item".to_string(),
+            Some(item.last_modified),
+        );
+
+        let events = self.storage.get_events_by_dfid(dfid)?;
+        for event in &events {
+            let source = format!("event:{}", event.event_id);
+            collector.push(
+                ProvenanceHashKind::EventContent,
+                event.content_hash.clone(),
+                source.clone(),
+                Some(event.timestamp),
+            );
+            if let Some(cid) = &event.snapshot_cid {
+                collector.push(
+                    ProvenanceHashKind::ContentCid,
+                    cid.clone(),
+                    source,
+                    Some(event.timestamp),
+                );
+            }
+        }
+        let events_merkle_root = if events.is_empty() {
+            None
+        } else {
+            let leaves = events
+                .iter()
+                .map(|e| (hash_event(e), Some(e.event_id.to_string())))
+                .collect();
+            MerkleTree::from_leaves_with_ids(leaves)
+                .root()
+                .map(str::to_string)
+        };
+
+        for snapshot in self
+            .storage
+            .get_snapshots_for_entity(SnapshotEntityType::Item, dfid)?
+        {
+            let source = format!("snapshot:v{}", snapshot.version);
+            let at = Some(snapshot.timestamp);
+            collector.push(
+                ProvenanceHashKind::Snapshot,
+                snapshot.snapshot_id.clone(),
+                source.clone(),
+                at,
+            );
+            if let Some(cid) = snapshot.ipfs_cid {
+                collector.push(ProvenanceHashKind::ContentCid, cid, source.clone(), at);
+            }
+            if let Some(tx) = snapshot.blockchain_tx {
+                collector.push(ProvenanceHashKind::AnchorTransaction, tx, source, at);
+            }
+        }
+
+        if let Some(history) = self.storage.get_storage_history(dfid)? {
+            for record in &history.storage_records {
+                collect_storage_record(&mut collector, record);
+            }
+        }
+
+        for proof in self.storage.list_zk_proofs()? {
+            let linked = (proof.item_id.is_some() && proof.item_id == item.local_id)
+                || proof
+                    .public_inputs
+                    .get("dfid")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|d| d == dfid);
+            if !linked {
+                continue;
+            }
+            let source = format!("zk_proof:{}", proof.proof_id);
+            let at = Some(proof.created_at);
+            collector.push(
+                ProvenanceHashKind::ProofCommitment,
+                proof.private_inputs_hash.clone(),
+                source.clone(),
+                at,
+            );
+            collector.push(
+                ProvenanceHashKind::ProofCommitment,
+                hashing::hash(&proof.proof_data),
+                source,
+                at,
+            );
+        }
+
+        let mut manifest = ProvenanceManifest {
+            manifest_id: Uuid::new_v4(),
+            version: MANIFEST_VERSION,
+            dfid: dfid.to_string(),
+            generated_at: Utc::now(),
+            generated_by: requester_id.to_string(),
+            events_merkle_root,
+            entries: collector.finish(),
+            manifest_hash: String::new(),
+            signer_public_key: self.signer_public_key(),
+            signature: String::new(),
+        };
+        manifest.manifest_hash = manifest.calculate_manifest_hash();
+        check_signer(
+            &self.storage,
+            KeyPurpose::AuditSigning,
+            &manifest.signer_public_key,
+        )
+        .map_err(|e| ProvenanceError::SigningKeyError(e.to_string()))?;
+        manifest.signature = hex::encode(
+            self.signing_key
+                .sign(manifest.manifest_hash.as_bytes())
+                .to_bytes(),
+        );
+
+        Ok(manifest)
+    }
+}
+
+fn collect_storage_record(collector: &mut EntryCollector, record: &StorageRecord) {
+    let source = format!("storage:{}", record.adapter_type);
+    let at = Some(record.stored_at);
+
+    match &record.storage_location {
+        StorageLocation::IPFS { cid, .. } => collector.push(
+            ProvenanceHashKind::ContentCid,
+            cid.clone(),
+            source.clone(),
+            at,
+        ),
+        StorageLocation::Stellar { transaction_id, .. }
+        | StorageLocation::Arweave { transaction_id } => collector.push(
+            ProvenanceHashKind::AnchorTransaction,
+            transaction_id.clone(),
+            source.clone(),
+            at,
+        ),
+        StorageLocation::Ethereum {
+            transaction_hash, ..
+        } => collector.push(
+            ProvenanceHashKind::AnchorTransaction,
+            transaction_hash.clone(),
+            source.clone(),
+            at,
+        ),
+        StorageLocation::Local { .. } => {}
+    }
+
+    // Adapters record secondary transactions and CIDs in the record metadata
+    for (key, kind) in [
+        ("ipfs_cid", ProvenanceHashKind::ContentCid),
+        ("nft_mint_tx", ProvenanceHashKind::AnchorTransaction),
+        ("ipcm_update_tx", ProvenanceHashKind::AnchorTransaction),
+    ] {
+        if let Some(value) = record.metadata.get(key).and_then(|v| v.as_str()) {
+            collector.push(kind, value.to_string(), source.clone(), at);
+        }
+    }
+}
+
+#[derive(Default)]
+struct EntryCollector {
+    entries: Vec<ProvenanceHashEntry>,
+    seen: HashSet<(ProvenanceHashKind, String)>,
+}
+
+impl EntryCollector {
+    /// Add an entry unless the same hash of the same kind was already listed
+    fn push(
+        &mut self,
+        kind: ProvenanceHashKind,
+        hash: String,
+        source: String,
+        recorded_at: Option<DateTime<Utc>>,
+    ) {
+        if hash.is_empty() || !self.seen.insert((kind, hash.clone())) {
+            return;
+        }
+        self.entries.push(ProvenanceHashEntry {
+            kind,
+            hash,
+            source,
+            recorded_at,
+        });
+    }
+
+    fn finish(mut self) -> Vec<ProvenanceHashEntry> {
+        self.entries.sort_by(|a, b| {
+            a.recorded_at
+                .cmp(&b.recorded_at)
+                .then_with(|| a.source.cmp(&b.source))
+                .then_with(|| a.hash.cmp(&b.hash))
+        });
+        self.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{Circuit, CircuitItem, Event, EventType, EventVisibility, Item};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    fn setup() -> (Arc<Mutex<InMemoryStorage>>, String) {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let dfid = "DFID-20240101-000001-ABCD".to_string();
+        let item = Item::new(dfid.clone(), vec![], Uuid::new_v4());
+        storage.store_item(&item).unwrap();
+
+        let event = Event::new(
+            dfid.clone(),
+            EventType::Created,
+            "test".to_string(),
+            EventVisibility::Public,
+        );
+        storage.store_event(&event).unwrap();
+
+        let mut metadata = HashMap::new();
+        metadata.insert("nft_mint_tx".to_string(), serde_json::json!("tx-mint"));
+        metadata.insert("ipfs_cid".to_string(), serde_json::json!("bafy-item"));
+        storage
+            .add_storage_record(
+                &dfid,
+                StorageRecord {
+                    adapter_type: crate::types::AdapterType::StellarTestnetIpfs,
+                    storage_location: StorageLocation::Stellar {
+                        transaction_id: "tx-ipcm".to_string(),
+                        contract_address: "C123".to_string(),
+                        asset_id: Some("bafy-item".to_string()),
+                    },
+                    stored_at: Utc::now(),
+                    triggered_by: "circuit_push".to_string(),
+                    triggered_by_id: None,
+                    events_range: None,
+                    is_active: true,
+                    metadata,
+                },
+            )
+            .unwrap();
+
+        (storage, dfid)
+    }
+
+    #[test]
+    fn test_manifest_lists_hashes_and_verifies() {
+        let (storage, dfid) = setup();
+        let engine = ProvenanceEngine::new(storage, manifest_signing_key("test-secret"));
+
+        let manifest = engine.build_manifest(&dfid, "test").unwrap();
+
+        let hashes: Vec<&str> = manifest.entries.iter().map(|e| e.hash.as_str()).collect();
+        assert!(hashes.contains(&"tx-mint"));
+        assert!(hashes.contains(&"tx-ipcm"));
+        assert_eq!(hashes.iter().filter(|h| **h == "bafy-item").count(), 1);
+        assert!(manifest
+            .entries
+            .iter()
+            .any(|e| e.kind == ProvenanceHashKind::EventContent));
+        assert!(manifest.events_merkle_root.is_some());
+
+        verify_manifest(&manifest, Some(&engine.signer_public_key())).unwrap();
+    }
+
+    #[test]
+    fn test_tampered_manifest_fails_verification() {
+        let (storage, dfid) = setup();
+        let engine = ProvenanceEngine::new(storage, manifest_signing_key("test-secret"));
+
+        let mut manifest = engine.build_manifest(&dfid, "test").unwrap();
+        manifest.entries.pop();
+        assert!(verify_manifest(&manifest, None).is_err());
+
+        let other_key = hex::encode(
+            manifest_signing_key("other-secret")
+                .verifying_key()
+                .to_bytes(),
+        );
+        let manifest = engine.build_manifest(&dfid, "test").unwrap();
+        assert!(verify_manifest(&manifest, Some(&other_key)).is_err());
+    }
+
+    #[test]
+    fn test_timeline_requires_item_access() {
+        let (storage, dfid) = setup();
+        let engine =
+            ProvenanceEngine::new(Arc::clone(&storage), manifest_signing_key("test-secret"));
+
+        // The source of the item's first event created it
+        assert!(engine.timeline(&dfid, "test").is_ok());
+        assert!(matches!(
+            engine.timeline(&dfid, "stranger"),
+            Err(ProvenanceError::PermissionDenied(_))
+        ));
+
+        let circuit = Circuit::new(
+            "Buyers".to_string(),
+            "Buyers network".to_string(),
+            "stranger".to_string(),
+        );
+        storage.store_circuit(&circuit).unwrap();
+        storage
+            .store_circuit_item(&CircuitItem {
+                dfid: dfid.clone(),
+                circuit_id: circuit.circuit_id,
+                pushed_by: "test".to_string(),
+                pushed_at: Utc::now(),
+                permissions: vec![],
+                expires_at: None,
+            })
+            .unwrap();
+        assert!(engine.timeline(&dfid, "stranger").is_ok());
+    }
+
+    #[test]
+    fn test_manifest_denied_to_non_member() {
+        let (storage, dfid) = setup();
+        let engine = ProvenanceEngine::new(storage, manifest_signing_key("test-secret"));
+
+        assert!(matches!(
+            engine.build_manifest(&dfid, "stranger"),
+            Err(ProvenanceError::PermissionDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_unknown_dfid() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let engine = ProvenanceEngine::new(storage, manifest_signing_key("test-secret"));
+        assert!(matches!(
+            engine.build_manifest("DFID-missing", "auditor"),
+            Err(ProvenanceError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_manifest_requires_the_active_signing_key() {
+        let (storage, dfid) = setup();
+        let engine =
+            ProvenanceEngine::new(Arc::clone(&storage), manifest_signing_key("test-secret"));
+        storage
+            .store_managed_key(&crate::types::ManagedKey {
+                key_id: Uuid::new_v4(),
+                purpose: KeyPurpose::AuditSigning,
+                public_key: hex::encode([9u8; 32]),
+                fingerprint: String::new(),
+                status: crate::types::ManagedKeyStatus::Active,
+                share_threshold: 2,
+                share_count: 3,
+                required_confirmations: 1,
+                confirmations: Vec::new(),
+                created_by: "admin".to_string(),
+                created_at: Utc::now(),
+                activated_at: Some(Utc::now()),
+                retired_at: None,
+            })
+            .unwrap();
+
+        assert!(matches!(
+            engine.build_manifest(&dfid, "test"),
+            Err(ProvenanceError::SigningKeyError(_))
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
 use uuid::Uuid;
@@ -280,17 +281,19 @@ impl ApiKeyLimits {
     }
 }
 
-pub struct RateLimiter {
-    limits: Arc<Mutex<HashMap<Uuid, ApiKeyLimits>>>,
+/// Sliding-window limiter keyed by API key id, or by any other caller key such as
+/// the client IP for anonymous endpoints
+pub struct RateLimiter<K = Uuid> {
+    limits: Arc<Mutex<HashMap<K, ApiKeyLimits>>>,
 }
 
-impl Default for RateLimiter {
+impl<K: Hash + Eq> Default for RateLimiter<K> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl RateLimiter {
+impl<K: Hash + Eq> RateLimiter<K> {
     pub fn new() -> Self {
         Self {
             limits: Arc::new(Mutex::new(HashMap::new())),
@@ -300,7 +303,7 @@ impl RateLimiter {
     /// Check if request is allowed for given API key
     pub fn check_rate_limit(
         &self,
-        api_key_id: Uuid,
+        api_key_id: K,
         config: &RateLimitConfig,
     ) -> Result<RateLimitResult, RateLimitError> {
         let mut limits = self
@@ -324,7 +327,7 @@ impl RateLimiter {
     }
 
     /// Record a successful request
-    pub fn record_request(&self, api_key_id: Uuid) -> Result<(), RateLimitError> {
+    pub fn record_request(&self, api_key_id: K) -> Result<(), RateLimitError> {
         let mut limits = self
             .limits
             .lock()
@@ -340,7 +343,7 @@ impl RateLimiter {
     /// Get current rate limit status without recording a request
     pub fn get_rate_limit_status(
         &self,
-        api_key_id: Uuid,
+        api_key_id: K,
         config: &RateLimitConfig,
     ) -> Result<RateLimitResult, RateLimitError> {
         let mut limits = self
@@ -360,7 +363,7 @@ impl RateLimiter {
     }
 
     /// Reset rate limits for a specific API key
-    pub fn reset_limits(&self, api_key_id: Uuid) -> Result<(), RateLimitError> {
+    pub fn reset_limits(&self, api_key_id: K) -> Result<(), RateLimitError> {
         let mut limits = self
             .limits
             .lock()
@@ -1,13 +1,26 @@
+use crate::hashing::{self, StreamDigest, StreamHashError};
 use crate::logging::{LogEntry, LoggingEngine};
+use crate::scaling_signals;
 use crate::storage::{InMemoryStorage, StorageBackend, StorageError};
-use crate::types::{DataLakeEntry, Identifier, Receipt};
-use blake3;
-use chrono::Utc;
+use crate::types::{
+    validate_occurred_at, DataLakeEntry, Identifier, IngestionPriority, Receipt, SubmissionOrigin,
+    WorkQueue,
+};
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncRead;
 use uuid::Uuid;
 
+pub const RECEIPT_BATCH_SIZE_ENV: &str = "RECEIPT_BATCH_SIZE";
+pub const RECEIPT_BATCH_WINDOW_MS_ENV: &str = "RECEIPT_BATCH_WINDOW_MS";
+
 #[derive(Debug)]
 pub enum ReceiptError {
     NoIdentifiers,
+    ImplausibleOccurredAt(String),
+    /// Streamed content could not be read or was too large
+    ContentError(StreamHashError),
     StorageError(StorageError),
 }
 
@@ -15,6 +28,8 @@ impl std::fmt::Display for ReceiptError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ReceiptError::NoIdentifiers => write!(f, "At least one identifier is required"),
+            ReceiptError::ImplausibleOccurredAt(e) => write!(f, "Implausible occurred_at: {e}"),
+            ReceiptError::ContentError(e) => write!(f, "Content error: {e}"),
             ReceiptError::StorageError(e) => write!(f, "Storage error: {e}"),
         }
     }
@@ -22,9 +37,42 @@ impl std::fmt::Display for ReceiptError {
 
 impl std::error::Error for ReceiptError {}
 
+/// Micro-batching of receipt writes. Receipts and their data lake entries are
+/// buffered and written with one storage call per kind once `max_receipts` are
+/// waiting or the oldest has waited `window`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReceiptBatchConfig {
+    pub max_receipts: usize,
+    pub window: Duration,
+}
+
+impl ReceiptBatchConfig {
+    /// Batching is on when `RECEIPT_BATCH_SIZE` is above 1; the window defaults
+    /// to 50ms
+    pub fn from_env() -> Option<Self> {
+        let max_receipts = std::env::var(RECEIPT_BATCH_SIZE_ENV)
+            .ok()?
+            .parse::<usize>()
+            .ok()
+            .filter(|size| *size > 1)?;
+        let window_ms = std::env::var(RECEIPT_BATCH_WINDOW_MS_ENV)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(50);
+        Some(ReceiptBatchConfig {
+            max_receipts,
+            window: Duration::from_millis(window_ms),
+        })
+    }
+}
+
 pub struct ReceiptEngine<S: StorageBackend> {
     storage: S,
     logger: LoggingEngine,
+    batching: Option<ReceiptBatchConfig>,
+    /// Accepted but not yet written; reads see these too
+    pending: Vec<(Receipt, DataLakeEntry)>,
+    pending_since: Option<Instant>,
 }
 
 impl<S: StorageBackend> ReceiptEngine<S> {
@@ -36,13 +84,205 @@ impl<S: StorageBackend> ReceiptEngine<S> {
             "Receipt engine initialized",
         );
 
-        Self { storage, logger }
+        Self {
+            storage,
+            logger,
+            batching: None,
+            pending: Vec::new(),
+            pending_since: None,
+        }
+    }
+
+    /// Turn micro-batching on or off; turning it off writes what is buffered
+    pub fn set_batching(&mut self, batching: Option<ReceiptBatchConfig>) {
+        self.batching = batching;
+        if batching.is_none() {
+            let _ = self.flush();
+        }
+    }
+
+    pub fn pending_writes(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Write buffered receipts and data lake entries. On failure the receipts
+    /// stay buffered for the next flush.
+    pub fn flush(&mut self) -> Result<usize, StorageError> {
+        if self.pending.is_empty() {
+            return Ok(0);
+        }
+        let pending = std::mem::take(&mut self.pending);
+        let (receipts, entries): (Vec<Receipt>, Vec<DataLakeEntry>) =
+            pending.iter().cloned().unzip();
+
+        if let Err(e) = self.write(&receipts, &entries) {
+            self.pending = pending;
+            return Err(e);
+        }
+        self.pending_since = None;
+        self.logger
+            .info(
+                "ReceiptEngine",
+                "receipt_batch_flushed",
+                "Receipt batch written",
+            )
+            .with_context("receipts", receipts.len().to_string());
+        Ok(receipts.len())
+    }
+
+    /// Flush if the oldest buffered receipt has waited out the batching window
+    pub fn flush_if_due(&mut self) -> Result<usize, StorageError> {
+        let due = match (self.batching, self.pending_since) {
+            (Some(batching), Some(since)) => since.elapsed() >= batching.window,
+            (None, Some(_)) => true,
+            _ => false,
+        };
+        if due {
+            self.flush()
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Receipts are stored first; a failed data lake write only delays
+    /// verification, so it is logged rather than failing the receipts
+    fn write(
+        &mut self,
+        receipts: &[Receipt],
+        entries: &[DataLakeEntry],
+    ) -> Result<(), StorageError> {
+        if let Err(e) = self.storage.store_receipts(receipts) {
+            self.logger
+                .error(
+                    "ReceiptEngine",
+                    "storage_failure",
+                    "Failed to store receipt",
+                )
+                .with_context("receipts", receipts.len().to_string())
+                .with_context("error", e.to_string());
+            return Err(e);
+        }
+
+        if let Err(e) = self.storage.store_data_lake_entries(entries) {
+            self.logger
+                .error(
+                    "ReceiptEngine",
+                    "data_lake_storage_failure",
+                    "Failed to store data lake entry",
+                )
+                .with_context("entries", entries.len().to_string())
+                .with_context("error", e.to_string());
+        } else {
+            for entry in entries {
+                scaling_signals::record_enqueued(WorkQueue::verification(entry.priority), 1);
+                self.logger
+                    .info(
+                        "ReceiptEngine",
+                        "data_lake_entry_created",
+                        "Data lake entry created",
+                    )
+                    .with_context("entry_id", entry.entry_id.to_string())
+                    .with_context("receipt_id", entry.receipt_id.to_string());
+            }
+        }
+        Ok(())
     }
 
     pub fn process_data(
         &mut self,
         data: &[u8],
         identifiers: Vec<Identifier>,
+    ) -> Result<Receipt, ReceiptError> {
+        self.process_data_with_priority(data, identifiers, IngestionPriority::Realtime)
+    }
+
+    /// Accept data in the given priority lane; bulk data is verified only when no
+    /// realtime entries are waiting
+    pub fn process_data_with_priority(
+        &mut self,
+        data: &[u8],
+        identifiers: Vec<Identifier>,
+        priority: IngestionPriority,
+    ) -> Result<Receipt, ReceiptError> {
+        self.process_data_with_occurred_at(data, identifiers, priority, None)
+    }
+
+    /// Accept data that may describe a past fact (historical migration). The receipt
+    /// keeps the recording time in `timestamp` and the declared `occurred_at` alongside.
+    pub fn process_data_with_occurred_at(
+        &mut self,
+        data: &[u8],
+        identifiers: Vec<Identifier>,
+        priority: IngestionPriority,
+        occurred_at: Option<DateTime<Utc>>,
+    ) -> Result<Receipt, ReceiptError> {
+        self.process_data_from(
+            data,
+            identifiers,
+            priority,
+            occurred_at,
+            SubmissionOrigin::default(),
+        )
+    }
+
+    /// Accept data submitted from a declared source on behalf of a workspace,
+    /// whose verification rules then apply to its entry
+    pub fn process_data_from(
+        &mut self,
+        data: &[u8],
+        identifiers: Vec<Identifier>,
+        priority: IngestionPriority,
+        occurred_at: Option<DateTime<Utc>>,
+        origin: SubmissionOrigin,
+    ) -> Result<Receipt, ReceiptError> {
+        let digest = StreamDigest::of(hashing::default_algorithm(), data);
+        self.process_digest_from(digest, identifiers, priority, occurred_at, origin)
+    }
+
+    /// Accept data read from `reader` without holding it in memory
+    pub async fn process_reader<R: AsyncRead + Unpin>(
+        &mut self,
+        reader: &mut R,
+        identifiers: Vec<Identifier>,
+        priority: IngestionPriority,
+        occurred_at: Option<DateTime<Utc>>,
+    ) -> Result<Receipt, ReceiptError> {
+        // Checked before reading so a bad request does not cost a full read
+        if identifiers.is_empty() {
+            return Err(ReceiptError::NoIdentifiers);
+        }
+        let digest = hashing::default_algorithm()
+            .hash_reader(reader, None)
+            .await
+            .map_err(ReceiptError::ContentError)?;
+        self.process_digest(digest, identifiers, priority, occurred_at)
+    }
+
+    /// Accept data that was hashed as it streamed in (see `HashAlgorithm::hash_stream`);
+    /// receipts and data lake entries only keep the hash and size
+    pub fn process_digest(
+        &mut self,
+        digest: StreamDigest,
+        identifiers: Vec<Identifier>,
+        priority: IngestionPriority,
+        occurred_at: Option<DateTime<Utc>>,
+    ) -> Result<Receipt, ReceiptError> {
+        self.process_digest_from(
+            digest,
+            identifiers,
+            priority,
+            occurred_at,
+            SubmissionOrigin::default(),
+        )
+    }
+
+    pub fn process_digest_from(
+        &mut self,
+        digest: StreamDigest,
+        identifiers: Vec<Identifier>,
+        priority: IngestionPriority,
+        occurred_at: Option<DateTime<Utc>>,
+        origin: SubmissionOrigin,
     ) -> Result<Receipt, ReceiptError> {
         self.logger
             .info(
@@ -50,8 +290,9 @@ impl<S: StorageBackend> ReceiptEngine<S> {
                 "data_reception_attempt",
                 "Processing data reception",
             )
-            .with_context("data_size", data.len().to_string())
-            .with_context("identifiers_count", identifiers.len().to_string());
+            .with_context("data_size", digest.size.to_string())
+            .with_context("identifiers_count", identifiers.len().to_string())
+            .with_context("priority", format!("{priority:?}"));
 
         if identifiers.is_empty() {
             self.logger
@@ -60,57 +301,62 @@ impl<S: StorageBackend> ReceiptEngine<S> {
                     "validation_failure",
                     "Data rejected: no identifiers provided",
                 )
-                .with_context("data_size", data.len().to_string());
+                .with_context("data_size", digest.size.to_string());
             return Err(ReceiptError::NoIdentifiers);
         }
 
-        let hash = blake3::hash(data);
+        let recorded_at = Utc::now();
+        if let Some(occurred_at) = occurred_at {
+            if let Err(e) = validate_occurred_at(occurred_at, recorded_at) {
+                self.logger
+                    .error(
+                        "ReceiptEngine",
+                        "validation_failure",
+                        "Data rejected: implausible occurred_at",
+                    )
+                    .with_context("error", e.clone());
+                return Err(ReceiptError::ImplausibleOccurredAt(e));
+            }
+        }
+
         let receipt = Receipt {
             id: Uuid::new_v4(),
-            hash: hash.to_hex().to_string(),
-            timestamp: Utc::now(),
-            data_size: data.len(),
+            hash: digest.hash,
+            timestamp: recorded_at,
+            data_size: digest.size,
             identifiers: identifiers.clone(),
+            priority,
+            occurred_at,
         };
 
-        if let Err(e) = self.storage.store_receipt(&receipt) {
-            self.logger
-                .error(
-                    "ReceiptEngine",
-                    "storage_failure",
-                    "Failed to store receipt",
-                )
-                .with_context("receipt_id", receipt.id.to_string())
-                .with_context("error", e.to_string());
-            return Err(ReceiptError::StorageError(e));
-        }
-
         // Create data lake entry for verification processing
         let data_lake_entry = DataLakeEntry::new(
             receipt.id,
             identifiers.clone(),
             receipt.hash.clone(),
-            data.len(),
-        );
-
-        if let Err(e) = self.storage.store_data_lake_entry(&data_lake_entry) {
-            self.logger
-                .error(
-                    "ReceiptEngine",
-                    "data_lake_storage_failure",
-                    "Failed to store data lake entry",
-                )
-                .with_context("receipt_id", receipt.id.to_string())
-                .with_context("error", e.to_string());
-        } else {
-            self.logger
-                .info(
-                    "ReceiptEngine",
-                    "data_lake_entry_created",
-                    "Data lake entry created",
-                )
-                .with_context("entry_id", data_lake_entry.entry_id.to_string())
-                .with_context("receipt_id", receipt.id.to_string());
+            receipt.data_size,
+        )
+        .with_priority(priority)
+        .with_occurred_at(occurred_at)
+        .with_origin(origin);
+
+        match self.batching {
+            None => self
+                .write(std::slice::from_ref(&receipt), &[data_lake_entry])
+                .map_err(ReceiptError::StorageError)?,
+            Some(batching) => {
+                self.pending.push((receipt.clone(), data_lake_entry));
+                self.pending_since.get_or_insert_with(Instant::now);
+                // A failed flush keeps the batch buffered for the next attempt
+                let flushed = if self.pending.len() >= batching.max_receipts {
+                    self.flush()
+                } else {
+                    self.flush_if_due()
+                };
+                if let Err(e) = flushed {
+                    tracing::warn!("⚠️  Receipt batch write failed, will retry: {}", e);
+                }
+            }
         }
 
         self.logger
@@ -121,33 +367,50 @@ impl<S: StorageBackend> ReceiptEngine<S> {
             )
             .with_context("receipt_id", receipt.id.to_string())
             .with_context("hash", receipt.hash.clone())
-            .with_context("data_size", data.len().to_string());
+            .with_context("data_size", receipt.data_size.to_string());
 
         Ok(receipt)
     }
 
     pub fn get_receipt(&self, id: &Uuid) -> Result<Option<Receipt>, StorageError> {
+        if let Some((receipt, _)) = self.pending.iter().find(|(r, _)| r.id == *id) {
+            return Ok(Some(receipt.clone()));
+        }
         self.storage.get_receipt(id)
     }
 
     pub fn verify_data(&self, id: &Uuid, data: &[u8]) -> Result<bool, StorageError> {
-        if let Some(receipt) = self.storage.get_receipt(id)? {
-            let hash = blake3::hash(data);
-            Ok(receipt.hash == hash.to_hex().to_string())
+        if let Some(receipt) = self.get_receipt(id)? {
+            Ok(hashing::verify(&receipt.hash, data))
         } else {
             Ok(false)
         }
     }
 
+    /// Whether streamed content matches a receipt; the digest must be made with
+    /// the algorithm of the receipt's hash
+    pub fn verify_digest(&self, id: &Uuid, digest: &StreamDigest) -> Result<bool, StorageError> {
+        Ok(self
+            .get_receipt(id)?
+            .is_some_and(|receipt| receipt.hash == digest.hash && receipt.data_size == digest.size))
+    }
+
     pub fn find_receipts_by_identifier(
         &self,
         identifier: &Identifier,
     ) -> Result<Vec<Receipt>, StorageError> {
-        self.storage.find_receipts_by_identifier(identifier)
+        let mut receipts = self.storage.find_receipts_by_identifier(identifier)?;
+        receipts.extend(
+            self.pending
+                .iter()
+                .filter(|(r, _)| r.identifiers.contains(identifier))
+                .map(|(r, _)| r.clone()),
+        );
+        Ok(receipts)
     }
 
     pub fn find_receipts_by_key(&self, key: &str) -> Result<Vec<Receipt>, StorageError> {
-        let receipts = self.storage.list_receipts()?;
+        let receipts = self.list_receipts()?;
         Ok(receipts
             .into_iter()
             .filter(|receipt| receipt.identifiers.iter().any(|id| id.key == key))
@@ -155,7 +418,7 @@ impl<S: StorageBackend> ReceiptEngine<S> {
     }
 
     pub fn find_receipts_by_value(&self, value: &str) -> Result<Vec<Receipt>, StorageError> {
-        let receipts = self.storage.list_receipts()?;
+        let receipts = self.list_receipts()?;
         Ok(receipts
             .into_iter()
             .filter(|receipt| receipt.identifiers.iter().any(|id| id.value == value))
@@ -163,11 +426,13 @@ impl<S: StorageBackend> ReceiptEngine<S> {
     }
 
     pub fn list_receipts(&self) -> Result<Vec<Receipt>, StorageError> {
-        self.storage.list_receipts()
+        let mut receipts = self.storage.list_receipts()?;
+        receipts.extend(self.pending.iter().map(|(r, _)| r.clone()));
+        Ok(receipts)
     }
 
     pub fn list_identifiers(&self) -> Result<Vec<Identifier>, StorageError> {
-        let receipts = self.storage.list_receipts()?;
+        let receipts = self.list_receipts()?;
         let mut identifiers = Vec::new();
         for receipt in receipts {
             for identifier in receipt.identifiers {
@@ -188,6 +453,40 @@ impl<S: StorageBackend> ReceiptEngine<S> {
     }
 }
 
+impl<S: StorageBackend + 'static> ReceiptEngine<S> {
+    /// Flush batches whose window has passed even when no new receipts arrive
+    pub fn spawn_flusher(
+        engine: Arc<Mutex<ReceiptEngine<S>>>,
+        tick: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                let flushed = match engine.lock() {
+                    Ok(mut engine) => engine.flush_if_due(),
+                    Err(_) => return,
+                };
+                if let Err(e) = flushed {
+                    tracing::warn!("⚠️  Receipt batch write failed, will retry: {}", e);
+                }
+            }
+        })
+    }
+}
+
+impl<S: StorageBackend> Drop for ReceiptEngine<S> {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            tracing::error!(
+                "❌ Lost {} buffered receipt(s) on shutdown: {}",
+                self.pending.len(),
+                e
+            );
+        }
+    }
+}
+
 impl Default for ReceiptEngine<InMemoryStorage> {
     fn default() -> Self {
         Self::new(InMemoryStorage::new())
@@ -226,6 +525,136 @@ mod tests {
         assert!(matches!(result.unwrap_err(), ReceiptError::NoIdentifiers));
     }
 
+    #[tokio::test]
+    async fn test_streamed_receipt_matches_in_memory_receipt() {
+        let mut engine = ReceiptEngine::new(InMemoryStorage::new());
+        let data = vec![7u8; 200_000];
+        let identifiers = vec![Identifier::new("lot", "L-7")];
+
+        let in_memory = engine.process_data(&data, identifiers.clone()).unwrap();
+        let streamed = engine
+            .process_reader(
+                &mut data.as_slice(),
+                identifiers,
+                IngestionPriority::Bulk,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(streamed.hash, in_memory.hash);
+        assert_eq!(streamed.data_size, data.len());
+
+        let digest = hashing::algorithm_of(&streamed.hash)
+            .hash_reader(&mut data.as_slice(), None)
+            .await
+            .unwrap();
+        assert!(engine.verify_digest(&streamed.id, &digest).unwrap());
+        assert!(!engine
+            .verify_digest(
+                &streamed.id,
+                &StreamDigest::of(hashing::default_algorithm(), b"other")
+            )
+            .unwrap());
+    }
+
+    #[test]
+    fn test_batched_receipts_are_readable_before_and_written_after_flush() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let mut engine = ReceiptEngine::new(Arc::clone(&storage));
+        engine.set_batching(Some(ReceiptBatchConfig {
+            max_receipts: 3,
+            window: Duration::from_secs(3600),
+        }));
+        let lot = Identifier::new("lot", "42");
+
+        let first = engine
+            .process_data(b"weighing 1", vec![lot.clone()])
+            .unwrap();
+        engine
+            .process_data(b"weighing 2", vec![lot.clone()])
+            .unwrap();
+        assert_eq!(engine.pending_writes(), 2);
+        assert!(storage.list_receipts().unwrap().is_empty());
+        // Buffered receipts are already visible through the engine
+        assert!(engine.verify_data(&first.id, b"weighing 1").unwrap());
+        assert_eq!(engine.find_receipts_by_identifier(&lot).unwrap().len(), 2);
+
+        // The third receipt fills the batch
+        engine
+            .process_data(b"weighing 3", vec![lot.clone()])
+            .unwrap();
+        assert_eq!(engine.pending_writes(), 0);
+        assert_eq!(storage.list_receipts().unwrap().len(), 3);
+        assert_eq!(storage.list_data_lake_entries().unwrap().len(), 3);
+        assert_eq!(
+            engine.get_logs_by_event_type("receipt_batch_flushed").len(),
+            1
+        );
+
+        // Whatever is still buffered is written when the engine goes away
+        engine
+            .process_data(b"weighing 4", vec![lot.clone()])
+            .unwrap();
+        assert_eq!(storage.list_receipts().unwrap().len(), 3);
+        drop(engine);
+        assert_eq!(storage.find_receipts_by_identifier(&lot).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_bulk_data_is_queued_in_the_bulk_lane() {
+        let mut engine = ReceiptEngine::new(InMemoryStorage::new());
+
+        let receipt = engine
+            .process_data_with_priority(
+                b"2021 harvest",
+                vec![Identifier::new("lot", "L-2021")],
+                IngestionPriority::Bulk,
+            )
+            .unwrap();
+        assert_eq!(receipt.priority, IngestionPriority::Bulk);
+
+        let pending = engine
+            .storage
+            .get_data_lake_entries_by_status(crate::types::ProcessingStatus::Pending)
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].priority, IngestionPriority::Bulk);
+    }
+
+    #[test]
+    fn test_process_historical_data_keeps_both_timestamps() {
+        let mut engine = ReceiptEngine::new(InMemoryStorage::new());
+        let occurred_at = Utc::now() - chrono::Duration::days(3 * 365);
+
+        let receipt = engine
+            .process_data_with_occurred_at(
+                b"2022 harvest",
+                vec![Identifier::new("lot", "L-2022")],
+                IngestionPriority::Bulk,
+                Some(occurred_at),
+            )
+            .unwrap();
+
+        assert_eq!(receipt.occurred_at, Some(occurred_at));
+        assert!(receipt.timestamp > occurred_at);
+        assert_eq!(
+            receipt.time_on(crate::types::TimeAxis::Occurred),
+            occurred_at
+        );
+
+        let future = Utc::now() + chrono::Duration::days(1);
+        let result = engine.process_data_with_occurred_at(
+            b"from the future",
+            vec![Identifier::new("lot", "L-2099")],
+            IngestionPriority::Bulk,
+            Some(future),
+        );
+        assert!(matches!(
+            result,
+            Err(ReceiptError::ImplausibleOccurredAt(_))
+        ));
+    }
+
     #[test]
     fn test_verify_data() {
         let mut engine = ReceiptEngine::new(InMemoryStorage::new());
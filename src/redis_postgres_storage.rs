@@ -16,6 +16,12 @@ use chrono::{DateTime, Utc};
 /// - Fast reads (1-5ms from Redis, 10-20ms from PostgreSQL)
 /// - No bulk loading required (< 2s startup)
 /// - Fixed RAM footprint (< 100MB per API)
+///
+/// Consistency checks: with `REDIS_CONSISTENCY_SAMPLE_RATE` set, that share of
+/// cache hits is also read from PostgreSQL and compared. Divergences are
+/// logged and counted; with `REDIS_CONSISTENCY_REPAIR=true` the cache entry is
+/// overwritten and PostgreSQL's value served.
+use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
@@ -23,6 +29,7 @@ use uuid::Uuid;
 
 use crate::identifier_types::EnhancedIdentifier;
 use crate::logging::LogEntry;
+use crate::pagination::PageCursor;
 use crate::postgres_persistence::PostgresPersistence;
 use crate::redis_cache::RedisCache;
 use crate::storage::{StorageBackend, StorageError};
@@ -35,6 +42,12 @@ pub struct CacheMetrics {
     pub cache_hits: AtomicU64,
     pub cache_misses: AtomicU64,
     pub cache_errors: AtomicU64,
+    /// Cache hits compared against PostgreSQL
+    pub consistency_checks: AtomicU64,
+    /// Compared hits whose cached value differed from PostgreSQL
+    pub consistency_divergences: AtomicU64,
+    /// Divergent cache entries overwritten with PostgreSQL's value
+    pub consistency_repairs: AtomicU64,
 }
 
 impl CacheMetrics {
@@ -43,6 +56,9 @@ impl CacheMetrics {
             cache_hits: self.cache_hits.load(Ordering::Relaxed),
             cache_misses: self.cache_misses.load(Ordering::Relaxed),
             cache_errors: self.cache_errors.load(Ordering::Relaxed),
+            consistency_checks: self.consistency_checks.load(Ordering::Relaxed),
+            consistency_divergences: self.consistency_divergences.load(Ordering::Relaxed),
+            consistency_repairs: self.consistency_repairs.load(Ordering::Relaxed),
         }
     }
 }
@@ -52,6 +68,46 @@ pub struct CacheMetricsSnapshot {
     pub cache_hits: u64,
     pub cache_misses: u64,
     pub cache_errors: u64,
+    pub consistency_checks: u64,
+    pub consistency_divergences: u64,
+    pub consistency_repairs: u64,
+}
+
+/// Read-through verification of cache hits against PostgreSQL
+#[derive(Debug, Clone, Default)]
+pub struct ConsistencyCheckConfig {
+    /// Share of cache hits to verify, from 0.0 (off) to 1.0 (every hit)
+    pub sample_rate: f64,
+    /// Overwrite divergent cache entries and serve PostgreSQL's value
+    pub repair: bool,
+}
+
+impl ConsistencyCheckConfig {
+    /// From `REDIS_CONSISTENCY_SAMPLE_RATE` and `REDIS_CONSISTENCY_REPAIR`;
+    /// off unless a rate is set
+    pub fn from_env() -> Self {
+        let sample_rate = std::env::var("REDIS_CONSISTENCY_SAMPLE_RATE")
+            .ok()
+            .and_then(|rate| rate.parse::<f64>().ok())
+            .filter(|rate| rate.is_finite())
+            .map_or(0.0, |rate| rate.clamp(0.0, 1.0));
+        let repair = std::env::var("REDIS_CONSISTENCY_REPAIR")
+            .map(|repair| repair == "true" || repair == "1")
+            .unwrap_or(false);
+        Self {
+            sample_rate,
+            repair,
+        }
+    }
+}
+
+/// Whether a cached value differs from PostgreSQL's, compared in their
+/// serialized form. A cached value PostgreSQL no longer has diverges too.
+fn diverges<T: Serialize>(cached: &T, fresh: Option<&T>) -> bool {
+    match fresh {
+        Some(fresh) => serde_json::to_value(cached).ok() != serde_json::to_value(fresh).ok(),
+        None => true,
+    }
 }
 
 /// Redis + PostgreSQL storage backend
@@ -62,6 +118,7 @@ pub struct RedisPostgresStorage {
     cache: Arc<RedisCache>,
     /// Cache performance metrics
     metrics: Arc<CacheMetrics>,
+    consistency: ConsistencyCheckConfig,
 }
 
 impl RedisPostgresStorage {
@@ -72,7 +129,125 @@ impl RedisPostgresStorage {
             pg,
             cache,
             metrics: Arc::new(CacheMetrics::default()),
+            consistency: ConsistencyCheckConfig::from_env(),
+        }
+        .log_consistency_check()
+    }
+
+    /// Replace the consistency check settings read from the environment
+    pub fn with_consistency_check(mut self, consistency: ConsistencyCheckConfig) -> Self {
+        self.consistency = consistency;
+        self.log_consistency_check()
+    }
+
+    fn log_consistency_check(self) -> Self {
+        if self.consistency.sample_rate > 0.0 {
+            tracing::info!(
+                "🔍 Checking {:.1}% of Redis cache hits against PostgreSQL (repair: {})",
+                self.consistency.sample_rate * 100.0,
+                self.consistency.repair
+            );
+        }
+        self
+    }
+
+    /// Whether to verify this cache hit against PostgreSQL
+    fn sample_consistency_check(&self) -> bool {
+        let rate = self.consistency.sample_rate;
+        let sampled = rate > 0.0 && rand::random::<f64>() < rate;
+        if sampled {
+            self.metrics
+                .consistency_checks
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        sampled
+    }
+
+    /// Count and log a cached value that differs from PostgreSQL's. Returns
+    /// whether the cache entry should be repaired.
+    fn record_divergence(&self, kind: &str, key: &str, missing: bool) -> bool {
+        self.metrics
+            .consistency_divergences
+            .fetch_add(1, Ordering::Relaxed);
+        tracing::warn!(
+            "⚠️ Redis cache diverges from PostgreSQL for {} {}{}",
+            kind,
+            key,
+            if missing {
+                " (deleted in PostgreSQL)"
+            } else {
+                ""
+            }
+        );
+        if self.consistency.repair {
+            self.metrics
+                .consistency_repairs
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        self.consistency.repair
+    }
+
+    async fn load_item(&self, dfid: &str) -> Result<Option<Item>, StorageError> {
+        let pg = self.get_pg()?;
+        let items = pg
+            .load_items()
+            .await
+            .map_err(|e| StorageError::ReadError(format!("Failed to load items: {e}")))?;
+        Ok(items.into_iter().find(|i| i.dfid == dfid))
+    }
+
+    async fn load_circuit(&self, circuit_id: &Uuid) -> Result<Option<Circuit>, StorageError> {
+        let pg = self.get_pg()?;
+        let circuits = pg
+            .load_circuits()
+            .await
+            .map_err(|e| StorageError::ReadError(format!("Failed to load circuits: {e}")))?;
+        Ok(circuits.into_iter().find(|c| c.circuit_id == *circuit_id))
+    }
+
+    /// Verify a sampled cached item, repairing the cache if configured
+    async fn check_cached_item(
+        &self,
+        dfid: &str,
+        cached: Item,
+    ) -> Result<Option<Item>, StorageError> {
+        let fresh = self.load_item(dfid).await?;
+        if !diverges(&cached, fresh.as_ref())
+            || !self.record_divergence("item", dfid, fresh.is_none())
+        {
+            return Ok(Some(cached));
+        }
+        let repaired = match &fresh {
+            Some(item) => self.cache.set_item(item).await,
+            None => self.cache.delete_item(dfid).await,
+        };
+        if let Err(e) = repaired {
+            tracing::warn!("Failed to repair cached item {}: {}", dfid, e);
+        }
+        Ok(fresh)
+    }
+
+    /// Verify a sampled cached circuit, repairing the cache if configured
+    async fn check_cached_circuit(
+        &self,
+        circuit_id: &Uuid,
+        cached: Circuit,
+    ) -> Result<Option<Circuit>, StorageError> {
+        let fresh = self.load_circuit(circuit_id).await?;
+        let key = circuit_id.to_string();
+        if !diverges(&cached, fresh.as_ref())
+            || !self.record_divergence("circuit", &key, fresh.is_none())
+        {
+            return Ok(Some(cached));
+        }
+        let repaired = match &fresh {
+            Some(circuit) => self.cache.set_circuit(circuit).await,
+            None => self.cache.delete_circuit(&key).await,
+        };
+        if let Err(e) = repaired {
+            tracing::warn!("Failed to repair cached circuit {}: {}", key, e);
         }
+        Ok(fresh)
     }
 
     /// Get cache metrics snapshot
@@ -120,6 +295,12 @@ impl StorageBackend for RedisPostgresStorage {
         Ok(Vec::new())
     }
 
+    fn store_receipts(&self, _receipts: &[Receipt]) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Receipts are not persisted in Redis+PostgreSQL backend".to_string(),
+        ))
+    }
+
     // ============================================================================
     // LOG OPERATIONS (In-memory only, not persisted)
     // ============================================================================
@@ -143,6 +324,12 @@ impl StorageBackend for RedisPostgresStorage {
         ))
     }
 
+    fn store_data_lake_entries(&self, _entries: &[DataLakeEntry]) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Data Lake not implemented in Redis+PostgreSQL backend".to_string(),
+        ))
+    }
+
     fn get_data_lake_entry(&self, _entry_id: &Uuid) -> Result<Option<DataLakeEntry>, StorageError> {
         Ok(None)
     }
@@ -194,6 +381,9 @@ impl StorageBackend for RedisPostgresStorage {
             match self.cache.get_item(dfid).await {
                 Ok(Some(item)) => {
                     self.metrics.cache_hits.fetch_add(1, Ordering::Relaxed);
+                    if self.sample_consistency_check() {
+                        return self.check_cached_item(dfid, item).await;
+                    }
                     return Ok(Some(item));
                 }
                 Ok(None) => {} // Not in cache, try PostgreSQL
@@ -206,13 +396,7 @@ impl StorageBackend for RedisPostgresStorage {
             // Cache miss - get from PostgreSQL
             self.metrics.cache_misses.fetch_add(1, Ordering::Relaxed);
 
-            let pg = self.get_pg()?;
-            let items = pg
-                .load_items()
-                .await
-                .map_err(|e| StorageError::ReadError(format!("Failed to load items: {e}")))?;
-
-            if let Some(item) = items.iter().find(|i| i.dfid == dfid) {
+            if let Some(item) = self.load_item(dfid).await? {
                 // Populate cache for next time (fire and forget)
                 let cache = self.cache.clone();
                 let item_clone = item.clone();
@@ -222,7 +406,7 @@ impl StorageBackend for RedisPostgresStorage {
                     }
                 });
 
-                Ok(Some(item.clone()))
+                Ok(Some(item))
             } else {
                 Ok(None)
             }
@@ -346,6 +530,16 @@ impl StorageBackend for RedisPostgresStorage {
         })
     }
 
+    fn store_events(&self, events: &[Event]) -> Result<(), StorageError> {
+        let pg = self.get_pg()?;
+
+        tokio::runtime::Handle::current().block_on(async {
+            pg.persist_events(events)
+                .await
+                .map_err(|e| StorageError::WriteError(format!("Failed to persist events: {e}")))
+        })
+    }
+
     fn get_event(&self, _event_id: &Uuid) -> Result<Option<Event>, StorageError> {
         // Events not cached individually - would need to implement if needed
         Err(StorageError::NotImplemented(
@@ -450,6 +644,9 @@ impl StorageBackend for RedisPostgresStorage {
             match self.cache.get_circuit(&circuit_id_str).await {
                 Ok(Some(circuit)) => {
                     self.metrics.cache_hits.fetch_add(1, Ordering::Relaxed);
+                    if self.sample_consistency_check() {
+                        return self.check_cached_circuit(circuit_id, circuit).await;
+                    }
                     return Ok(Some(circuit));
                 }
                 Ok(None) => {}
@@ -462,13 +659,7 @@ impl StorageBackend for RedisPostgresStorage {
             // Cache miss - get from PostgreSQL
             self.metrics.cache_misses.fetch_add(1, Ordering::Relaxed);
 
-            let pg = self.get_pg()?;
-            let circuits = pg
-                .load_circuits()
-                .await
-                .map_err(|e| StorageError::ReadError(format!("Failed to load circuits: {e}")))?;
-
-            if let Some(circuit) = circuits.iter().find(|c| c.circuit_id == *circuit_id) {
+            if let Some(circuit) = self.load_circuit(circuit_id).await? {
                 // Populate cache
                 let cache = self.cache.clone();
                 let circuit_clone = circuit.clone();
@@ -482,7 +673,7 @@ impl StorageBackend for RedisPostgresStorage {
                     }
                 });
 
-                Ok(Some(circuit.clone()))
+                Ok(Some(circuit))
             } else {
                 Ok(None)
             }
@@ -511,6 +702,25 @@ impl StorageBackend for RedisPostgresStorage {
             .collect())
     }
 
+    fn get_archived_circuits_for_member(
+        &self,
+        member_id: &str,
+    ) -> Result<Vec<Circuit>, StorageError> {
+        Ok(self
+            .get_circuits_for_member(member_id)?
+            .into_iter()
+            .filter(|c| c.is_archived())
+            .collect())
+    }
+
+    fn get_child_circuits(&self, parent_id: &Uuid) -> Result<Vec<Circuit>, StorageError> {
+        Ok(self
+            .list_circuits()?
+            .into_iter()
+            .filter(|c| c.parent_circuit_id == Some(*parent_id))
+            .collect())
+    }
+
     // ============================================================================
     // CIRCUIT OPERATION OPERATIONS (Direct PostgreSQL)
     // ============================================================================
@@ -1659,4 +1869,1994 @@ impl StorageBackend for RedisPostgresStorage {
         // Implementation pending
         Ok(0)
     }
+
+    // Organization directory operations
+    fn store_organization_profile(
+        &self,
+        profile: &OrganizationProfile,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_organization_profile(profile)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_organization_profile(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<OrganizationProfile>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_organization_profile(user_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_organization_profiles(&self) -> Result<Vec<OrganizationProfile>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_organization_profiles()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_organization_profile(&self, user_id: &str) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.delete_organization_profile(user_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Attestation operations
+    fn store_attestation(&self, attestation: &Attestation) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_attestation(attestation)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_attestation(&self, attestation_id: &Uuid) -> Result<Option<Attestation>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_attestation(attestation_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_attestations_for_subject(
+        &self,
+        subject_id: &str,
+    ) -> Result<Vec<Attestation>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_attestations_for_subject(subject_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_attestations_by_attester(
+        &self,
+        attester_id: &str,
+    ) -> Result<Vec<Attestation>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_attestations_by_attester(attester_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Saved audit query operations
+    fn store_saved_audit_query(&self, query: &SavedAuditQuery) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_saved_audit_query(query)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_saved_audit_query(
+        &self,
+        query_id: &Uuid,
+    ) -> Result<Option<SavedAuditQuery>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_saved_audit_query(query_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_saved_audit_queries(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<SavedAuditQuery>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_saved_audit_queries(owner_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_saved_audit_query(&self, query_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.delete_saved_audit_query(query_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Preview environment operations
+    fn store_preview_environment(&self, preview: &PreviewEnvironment) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_preview_environment(preview)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_preview_environment(
+        &self,
+        preview_id: &Uuid,
+    ) -> Result<Option<PreviewEnvironment>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_preview_environment(preview_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_preview_environments(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<PreviewEnvironment>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_preview_environments(owner_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Paginated listings (ordered by creation timestamp, then id)
+    fn list_items_page(
+        &self,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Item>, StorageError> {
+        let pg = self.get_pg()?;
+
+        tokio::runtime::Handle::current().block_on(async {
+            pg.load_items_page(after, limit)
+                .await
+                .map_err(|e| StorageError::ReadError(format!("Failed to list items: {e}")))
+        })
+    }
+
+    fn list_events_page(
+        &self,
+        dfid: Option<&str>,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Event>, StorageError> {
+        // Paged reads are bounded, unlike the unfiltered list_events
+        let pg = self.get_pg()?;
+
+        tokio::runtime::Handle::current().block_on(async {
+            pg.load_events_page(dfid, after, limit)
+                .await
+                .map_err(|e| StorageError::ReadError(format!("Failed to list events: {e}")))
+        })
+    }
+
+    // Legacy connector operations
+    fn store_connector_config(&self, config: &ConnectorConfig) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_connector_config(config)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_connector_config(
+        &self,
+        connector_id: &Uuid,
+    ) -> Result<Option<ConnectorConfig>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_connector_config(connector_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_connector_configs(&self) -> Result<Vec<ConnectorConfig>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_connector_configs()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_connector_config(&self, connector_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.delete_connector_config(connector_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn store_mapping_template(&self, template: &MappingTemplate) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_mapping_template(template)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_mapping_template(
+        &self,
+        template_id: &Uuid,
+    ) -> Result<Option<MappingTemplate>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_mapping_template(template_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_mapping_templates(&self, owner_id: &str) -> Result<Vec<MappingTemplate>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_mapping_templates(owner_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_connector_state(&self, state: &ConnectorState) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_connector_state(state)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_connector_state(
+        &self,
+        connector_id: &Uuid,
+    ) -> Result<Option<ConnectorState>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_connector_state(connector_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_connector_error(&self, error: &ConnectorRunError) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_connector_error(error)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_connector_errors(
+        &self,
+        connector_id: &Uuid,
+        limit: usize,
+    ) -> Result<Vec<ConnectorRunError>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_connector_errors(connector_id, limit)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_mapping_template(&self, template_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.delete_mapping_template(template_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Circuit change feed operations
+    fn append_change_record(&self, record: &ChangeRecord) -> Result<ChangeRecord, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.append_change_record(record)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_change_records(
+        &self,
+        circuit_id: &Uuid,
+        after_sequence: u64,
+        limit: usize,
+    ) -> Result<Vec<ChangeRecord>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_change_records(circuit_id, after_sequence, limit)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn get_latest_change_sequence(&self, circuit_id: &Uuid) -> Result<u64, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_latest_change_sequence(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_change_feed_subscription(
+        &self,
+        subscription: &ChangeFeedSubscription,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_change_feed_subscription(subscription)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_change_feed_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<Option<ChangeFeedSubscription>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_change_feed_subscription(subscription_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_change_feed_subscriptions(
+        &self,
+        circuit_id: Option<&Uuid>,
+    ) -> Result<Vec<ChangeFeedSubscription>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_change_feed_subscriptions(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_change_feed_subscription(&self, subscription_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.delete_change_feed_subscription(subscription_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Item search
+    fn search_items(
+        &self,
+        query: &ItemSearchQuery,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Item>, StorageError> {
+        let pg = self.get_pg()?;
+
+        tokio::runtime::Handle::current().block_on(async {
+            pg.search_items_page(query, after, limit)
+                .await
+                .map_err(|e| StorageError::ReadError(format!("Failed to search items: {e}")))
+        })
+    }
+
+    // Announcements
+    fn store_announcement(&self, announcement: &Announcement) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_announcement(announcement)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_announcement(
+        &self,
+        announcement_id: &Uuid,
+    ) -> Result<Option<Announcement>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_announcement(announcement_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_announcements(&self) -> Result<Vec<Announcement>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_announcements()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Workspace engagement snapshots
+    fn store_workspace_engagement(
+        &self,
+        engagement: &WorkspaceEngagement,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_workspace_engagement(engagement)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_workspace_engagement(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<WorkspaceEngagement>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_workspace_engagement(workspace_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Data export jobs
+    fn store_data_export_job(&self, job: &DataExportJob) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_data_export_job(job)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_data_export_job(&self, job_id: &Uuid) -> Result<Option<DataExportJob>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_data_export_job(job_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_data_export_jobs(
+        &self,
+        requested_by: &str,
+    ) -> Result<Vec<DataExportJob>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_data_export_jobs(requested_by)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // SLA windows
+    fn store_sla_window(&self, window: &SlaWindow) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_sla_window(window)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_sla_window(
+        &self,
+        component: SlaComponent,
+        hour_start: DateTime<Utc>,
+    ) -> Result<Option<SlaWindow>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_sla_window(component, hour_start)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_sla_windows(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<SlaWindow>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_sla_windows(from, to)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Event schemas
+    fn store_event_schema(&self, schema: &EventSchema) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_event_schema(schema)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_event_schemas(&self) -> Result<Vec<EventSchema>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_event_schemas()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Maintenance mode
+    fn store_maintenance_mode(&self, mode: &MaintenanceMode) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_maintenance_mode(mode)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_maintenance_mode(&self) -> Result<Option<MaintenanceMode>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_maintenance_mode()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Managed keys
+    fn store_managed_key(&self, key: &ManagedKey) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_managed_key(key)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_managed_key(&self, key_id: &Uuid) -> Result<Option<ManagedKey>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_managed_key(key_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_managed_keys(&self) -> Result<Vec<ManagedKey>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_managed_keys()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Custom event types
+    fn store_custom_event_type(&self, event_type: &CustomEventType) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_custom_event_type(event_type)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_custom_event_types(&self) -> Result<Vec<CustomEventType>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_custom_event_types()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_custom_event_type(
+        &self,
+        circuit_id: &Uuid,
+        name: &str,
+    ) -> Result<bool, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.delete_custom_event_type(circuit_id, name)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Document notarizations
+    fn store_notarization(&self, notarization: &DocumentNotarization) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_notarization(notarization)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_notarization(
+        &self,
+        notarization_id: &Uuid,
+    ) -> Result<Option<DocumentNotarization>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_notarization(notarization_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_notarizations(&self) -> Result<Vec<DocumentNotarization>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_notarizations()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_notarization_batch(&self, batch: &NotarizationBatch) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_notarization_batch(batch)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_notarization_batch(
+        &self,
+        batch_id: &Uuid,
+    ) -> Result<Option<NotarizationBatch>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_notarization_batch(batch_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Enrichment policies
+    fn store_enrichment_policy(&self, policy: &EnrichmentPolicy) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_enrichment_policy(policy)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_enrichment_policy(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<EnrichmentPolicy>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_enrichment_policy(workspace_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Event signing keys
+    fn store_event_signing_key(&self, key: &EventSigningKey) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_event_signing_key(key)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_event_signing_key(
+        &self,
+        key_id: &Uuid,
+    ) -> Result<Option<EventSigningKey>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_event_signing_key(key_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_event_signing_keys(&self) -> Result<Vec<EventSigningKey>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_event_signing_keys()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Anchoring SLAs, fee samples and anchoring spend
+    fn store_anchoring_sla(&self, sla: &AnchoringSla) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_anchoring_sla(sla)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_anchoring_sla(&self, circuit_id: &Uuid) -> Result<Option<AnchoringSla>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_anchoring_sla(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_fee_sample(&self, sample: &FeeSample) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_fee_sample(sample)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_latest_fee_sample(
+        &self,
+        network: &AnchoringNetwork,
+    ) -> Result<Option<FeeSample>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_latest_fee_sample(network)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_anchoring_cost_record(
+        &self,
+        record: &AnchoringCostRecord,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_anchoring_cost_record(record)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_anchoring_cost_records(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AnchoringCostRecord>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_anchoring_cost_records(since)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Event causality
+    fn get_events_caused_by(&self, _event_id: &Uuid) -> Result<Vec<Event>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    fn get_events_by_correlation_id(
+        &self,
+        _correlation_id: &Uuid,
+    ) -> Result<Vec<Event>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    // Item lifecycles
+    fn store_lifecycle_definition(
+        &self,
+        definition: &LifecycleDefinition,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_lifecycle_definition(definition)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_lifecycle_definition(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<LifecycleDefinition>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_lifecycle_definition(workspace_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn store_item_lifecycle(&self, lifecycle: &ItemLifecycle) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_item_lifecycle(lifecycle)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_item_lifecycle(&self, dfid: &str) -> Result<Option<ItemLifecycle>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_item_lifecycle(dfid)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_item_lifecycles(&self, workspace_id: &str) -> Result<Vec<ItemLifecycle>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_item_lifecycles(workspace_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Circuit access reviews
+    fn store_access_report(&self, report: &CircuitAccessReport) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_access_report(report)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_access_report(
+        &self,
+        report_id: &Uuid,
+    ) -> Result<Option<CircuitAccessReport>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_access_report(report_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_access_reports(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<CircuitAccessReport>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_access_reports(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_access_report(&self, report_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.delete_access_report(report_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn store_access_review_schedule(
+        &self,
+        schedule: &AccessReviewSchedule,
+    ) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_access_review_schedule(schedule)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_access_review_schedule(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Option<AccessReviewSchedule>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_access_review_schedule(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_access_review_schedule(&self, circuit_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.delete_access_review_schedule(circuit_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_access_review_schedules(&self) -> Result<Vec<AccessReviewSchedule>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_access_review_schedules()
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Adapter write intents
+    fn store_adapter_intent(&self, _intent: &AdapterIntent) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_adapter_intent(&self, _intent_id: &Uuid) -> Result<Option<AdapterIntent>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn get_incomplete_adapter_intents(&self) -> Result<Vec<AdapterIntent>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    // Circuit-scoped partner tokens
+    fn store_partner_token(&self, token: &PartnerToken) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_partner_token(token)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_partner_token(&self, token_id: &Uuid) -> Result<Option<PartnerToken>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_partner_token(token_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn get_partner_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PartnerToken>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_partner_token_by_hash(token_hash)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_partner_tokens(&self, circuit_id: &Uuid) -> Result<Vec<PartnerToken>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_partner_tokens(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn record_partner_token_usage(&self, usage: &PartnerTokenUsage) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_partner_token_usage(usage)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_partner_token_usage(
+        &self,
+        token_id: &Uuid,
+        limit: usize,
+    ) -> Result<Vec<PartnerTokenUsage>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_partner_token_usage(token_id, limit)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    // Circuit comment threads
+    fn store_circuit_comment(&self, comment: &CircuitComment) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_circuit_comment(comment)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_circuit_comment(
+        &self,
+        comment_id: &Uuid,
+    ) -> Result<Option<CircuitComment>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_circuit_comment(comment_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_circuit_comments(
+        &self,
+        circuit_id: &Uuid,
+        target: &CommentTarget,
+    ) -> Result<Vec<CircuitComment>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_circuit_comments(circuit_id, target)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_circuit_comment(&self, comment_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.delete_circuit_comment(comment_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Circuit data quality reports
+    fn store_data_quality_report(&self, report: &DataQualityReport) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_data_quality_report(report)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn list_data_quality_reports(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<DataQualityReport>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_data_quality_reports(circuit_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_data_quality_report(&self, report_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.delete_data_quality_report(report_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // Dashboard metric definitions
+    fn store_metric_definition(&self, definition: &MetricDefinition) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.persist_metric_definition(definition)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    fn get_metric_definition(
+        &self,
+        metric_id: &Uuid,
+    ) -> Result<Option<MetricDefinition>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_metric_definition(metric_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn list_metric_definitions(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Vec<MetricDefinition>, StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.load_metric_definitions(workspace_id)
+                    .await
+                    .map_err(StorageError::ReadError)
+            })
+        })
+    }
+
+    fn delete_metric_definition(&self, metric_id: &Uuid) -> Result<(), StorageError> {
+        tokio::task::block_in_place(|| {
+            let pg = self.get_pg()?;
+            tokio::runtime::Handle::current().block_on(async {
+                pg.delete_metric_definition(metric_id)
+                    .await
+                    .map_err(StorageError::WriteError)
+            })
+        })
+    }
+
+    // ZK setup artifacts
+    fn store_zk_setup_artifact(
+        &self,
+        _artifact: &crate::zk_proof_engine::ZkSetupArtifact,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_zk_setup_artifact(
+        &self,
+        _artifact_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::ZkSetupArtifact>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_zk_setup_artifacts(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkSetupArtifact>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    // Data lake garbage collection
+    fn delete_data_lake_entries(&self, _entry_ids: &[Uuid]) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn delete_receipts(&self, _receipt_ids: &[Uuid]) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn store_data_lake_gc_report(
+        &self,
+        _report: &crate::types::DataLakeGcReport,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn list_data_lake_gc_reports(
+        &self,
+    ) -> Result<Vec<crate::types::DataLakeGcReport>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    // Device backups
+    fn store_device_backup(
+        &self,
+        _backup: &crate::types::DeviceBackup,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_device_backup(
+        &self,
+        _backup_id: &Uuid,
+    ) -> Result<Option<crate::types::DeviceBackup>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_device_backups(
+        &self,
+        _user_id: &str,
+    ) -> Result<Vec<crate::types::DeviceBackup>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    fn delete_device_backup(&self, _backup_id: &Uuid) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    // Federation
+    fn store_federation_peer(
+        &self,
+        _peer: &crate::types::FederationPeer,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_federation_peer(
+        &self,
+        _peer_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationPeer>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_federation_peers(&self) -> Result<Vec<crate::types::FederationPeer>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    fn delete_federation_peer(&self, _peer_id: &Uuid) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn store_federation_provenance(
+        &self,
+        _provenance: &crate::types::FederationProvenance,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_federation_provenance(
+        &self,
+        _record_key: &str,
+    ) -> Result<Option<crate::types::FederationProvenance>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn store_federation_conflict(
+        &self,
+        _conflict: &crate::types::FederationConflict,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_federation_conflict(
+        &self,
+        _conflict_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationConflict>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_federation_conflicts(
+        &self,
+    ) -> Result<Vec<crate::types::FederationConflict>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    // ZK proof revocations
+    fn store_zk_proof_revocation(
+        &self,
+        _revocation: &crate::zk_proof_engine::ProofRevocation,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_zk_proof_revocation(
+        &self,
+        _proof_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofRevocation>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_zk_proof_revocations(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ProofRevocation>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    // Selective disclosures
+    fn store_item_attribute_commitments(
+        &self,
+        _commitments: &crate::types::ItemAttributeCommitments,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_item_attribute_commitments(
+        &self,
+        _dfid: &str,
+    ) -> Result<Option<crate::types::ItemAttributeCommitments>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn store_selective_disclosure(
+        &self,
+        _disclosure: &crate::types::SelectiveDisclosure,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_selective_disclosure(
+        &self,
+        _disclosure_id: &Uuid,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn get_selective_disclosure_by_token(
+        &self,
+        _token_hash: &str,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_selective_disclosures(
+        &self,
+        _created_by: &str,
+    ) -> Result<Vec<crate::types::SelectiveDisclosure>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    // Custom ZK circuit definitions
+    fn store_custom_circuit_definition(
+        &self,
+        _definition: &crate::zk_proof_engine::CustomCircuitDefinition,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_custom_circuit_definition(
+        &self,
+        _circuit_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::CustomCircuitDefinition>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_custom_circuit_definitions(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::CustomCircuitDefinition>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    // ZK proof generation jobs
+    fn store_zk_proof_job(
+        &self,
+        _job: &crate::zk_proof_engine::ProofJob,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_zk_proof_job(
+        &self,
+        _job_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofJob>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_zk_proof_jobs(&self) -> Result<Vec<crate::zk_proof_engine::ProofJob>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    // Item search projected to summaries
+    fn search_item_summaries(
+        &self,
+        query: &ItemSearchQuery,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<ItemSummary>, StorageError> {
+        let pg = self.get_pg()?;
+
+        tokio::runtime::Handle::current().block_on(async {
+            pg.search_item_summaries_page(query, after, limit)
+                .await
+                .map_err(|e| StorageError::ReadError(format!("Failed to search items: {e}")))
+        })
+    }
+
+    // Per-workspace API usage and anomalies
+    fn store_workspace_usage_window(
+        &self,
+        _window: &crate::types::WorkspaceUsageWindow,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_workspace_usage_window(
+        &self,
+        _workspace_id: &str,
+        _hour_start: DateTime<Utc>,
+    ) -> Result<Option<crate::types::WorkspaceUsageWindow>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_workspace_usage_windows(
+        &self,
+        _workspace_id: &str,
+        _from: DateTime<Utc>,
+        _to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::WorkspaceUsageWindow>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    fn store_usage_anomaly_baseline(
+        &self,
+        _baseline: &crate::types::UsageAnomalyBaseline,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_usage_anomaly_baseline(
+        &self,
+        _workspace_id: &str,
+    ) -> Result<Option<crate::types::UsageAnomalyBaseline>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn store_usage_anomaly(
+        &self,
+        _anomaly: &crate::types::UsageAnomaly,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn list_usage_anomalies(
+        &self,
+        _workspace_id: Option<&str>,
+        _since: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::UsageAnomaly>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    // Proof share links
+    fn store_proof_share_link(
+        &self,
+        _link: &crate::types::ProofShareLink,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_proof_share_link(
+        &self,
+        _share_id: &Uuid,
+    ) -> Result<Option<crate::types::ProofShareLink>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_proof_share_links(
+        &self,
+        _proof_id: &Uuid,
+    ) -> Result<Vec<crate::types::ProofShareLink>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    fn record_proof_share_view(
+        &self,
+        _share_id: &Uuid,
+        _now: DateTime<Utc>,
+    ) -> Result<Option<crate::types::ProofShareLink>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    // Workspace dedup configs
+    fn store_workspace_dedup_config(
+        &self,
+        _config: &crate::types::WorkspaceDedupConfig,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_workspace_dedup_config(
+        &self,
+        _workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceDedupConfig>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    // Sensor time series
+    fn store_sensor_series(
+        &self,
+        _series: &crate::types::SensorSeries,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_sensor_series(
+        &self,
+        _dfid: &str,
+        _series: &str,
+    ) -> Result<Option<crate::types::SensorSeries>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_sensor_series(
+        &self,
+        _dfid: &str,
+    ) -> Result<Vec<crate::types::SensorSeries>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    fn store_sensor_chunk(&self, _chunk: &crate::types::SensorChunk) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_sensor_chunks(
+        &self,
+        _dfid: &str,
+        _series: &str,
+        _from: DateTime<Utc>,
+        _to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorChunk>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    fn store_sensor_rollup(
+        &self,
+        _rollup: &crate::types::SensorRollup,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_sensor_rollups(
+        &self,
+        _dfid: &str,
+        _series: &str,
+        _resolution: crate::types::SensorResolution,
+        _from: DateTime<Utc>,
+        _to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorRollup>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    fn list_all_sensor_series(&self) -> Result<Vec<crate::types::SensorSeries>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    // Workspace buckets
+    fn store_workspace_bucket_config(
+        &self,
+        _config: &crate::types::WorkspaceBucketConfig,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_workspace_bucket_config(
+        &self,
+        _workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceBucketConfig>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn delete_workspace_bucket_config(&self, _workspace_id: &str) -> Result<bool, StorageError> {
+        // Implementation pending
+        Ok(false)
+    }
+
+    // DFID sequence leases
+    fn lease_dfid_sequence_range(&self, _day: &str, _count: u64) -> Result<u64, StorageError> {
+        // A made-up range could repeat DFIDs, so refuse until implemented
+        Err(StorageError::NotImplemented(
+            "DFID sequence leases not yet implemented for Redis storage".to_string(),
+        ))
+    }
+
+    // Re-verification jobs and merge proposals
+    fn store_reverification_job(&self, _job: &ReverificationJob) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_reverification_job(
+        &self,
+        _job_id: &Uuid,
+    ) -> Result<Option<ReverificationJob>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_reverification_jobs(&self) -> Result<Vec<ReverificationJob>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    fn store_merge_proposal(&self, _proposal: &MergeProposal) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_merge_proposal(
+        &self,
+        _proposal_id: &Uuid,
+    ) -> Result<Option<MergeProposal>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_merge_proposals(&self) -> Result<Vec<MergeProposal>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    // Runbook hooks
+    fn store_runbook_hook(&self, _hook: &RunbookHook) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_runbook_hook(&self, _hook_id: &Uuid) -> Result<Option<RunbookHook>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_runbook_hooks(&self) -> Result<Vec<RunbookHook>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    fn delete_runbook_hook(&self, _hook_id: &Uuid) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    // Workspace verification rules
+    fn store_workspace_verification_rules(
+        &self,
+        _rules: &WorkspaceVerificationRules,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_workspace_verification_rules(
+        &self,
+        _workspace_id: &str,
+    ) -> Result<Option<WorkspaceVerificationRules>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    // Dedup sharing agreements
+    fn store_dedup_sharing_agreement(
+        &self,
+        _agreement: &DedupSharingAgreement,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn get_dedup_sharing_agreement(
+        &self,
+        _agreement_id: &Uuid,
+    ) -> Result<Option<DedupSharingAgreement>, StorageError> {
+        // Implementation pending
+        Ok(None)
+    }
+
+    fn list_dedup_sharing_agreements(
+        &self,
+        _circuit_id: &Uuid,
+    ) -> Result<Vec<DedupSharingAgreement>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+
+    // Paginated CID timeline
+    fn get_item_timeline_page(
+        &self,
+        dfid: &str,
+        query: &TimelineQuery,
+        after_sequence: Option<i32>,
+        limit: usize,
+    ) -> Result<Vec<TimelineEntry>, StorageError> {
+        let pg = self.get_pg()?;
+
+        tokio::runtime::Handle::current().block_on(async {
+            pg.get_item_timeline_page(dfid, query, after_sequence, limit)
+                .await
+                .map_err(|e| StorageError::ReadError(format!("Failed to get timeline page: {e}")))
+        })
+    }
+
+    // Circuit membership reconciliation
+    fn store_membership_reconciliation_report(
+        &self,
+        _report: &MembershipReconciliationReport,
+    ) -> Result<(), StorageError> {
+        // Implementation pending
+        Ok(())
+    }
+
+    fn list_membership_reconciliation_reports(
+        &self,
+    ) -> Result<Vec<MembershipReconciliationReport>, StorageError> {
+        // Implementation pending
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_divergence_compares_serialized_values() {
+        let cached = json_item("DFID-1", "v1");
+        assert!(!diverges(&cached, Some(&cached.clone())));
+        assert!(diverges(&cached, Some(&json_item("DFID-1", "v2"))));
+        // Deleted in PostgreSQL but still cached
+        assert!(diverges(&cached, None));
+    }
+
+    fn json_item(dfid: &str, version: &str) -> serde_json::Value {
+        serde_json::json!({"dfid": dfid, "version": version})
+    }
 }
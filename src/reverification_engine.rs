@@ -0,0 +1,645 @@
+//! Re-verification after identifier mapping corrections.
+//!
+//! Correcting a mapping only repoints the identifier; items and receipts that
+//! were verified against the old mapping stay where they are. The correction
+//! queues a job that re-runs matching for everything carrying the identifier
+//! (plus the items it used to point at) and records a `MergeProposal` wherever
+//! an item's identifiers now resolve to a different item. The job never
+//! mutates items: a proposal only takes effect once someone accepts it.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    DataLakeEntry, Identifier, IdentifierMapping, Item, ItemStatus, MappingStatus, MergeProposal,
+    MergeProposalStatus, ReverificationJob, ReverificationStatus,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
+
+/// Source recorded on mappings written by a correction
+const CORRECTION_SOURCE: &str = "mapping_correction";
+
+#[derive(Debug)]
+pub enum ReverificationError {
+    StorageError(StorageError),
+    ValidationError(String),
+    NotFound(String),
+    /// The proposal was already decided or one of its items changed since
+    Conflict(String),
+}
+
+impl From<StorageError> for ReverificationError {
+    fn from(err: StorageError) -> Self {
+        ReverificationError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for ReverificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReverificationError::StorageError(e) => write!(f, "Storage error: {e}"),
+            ReverificationError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            ReverificationError::NotFound(e) => write!(f, "Not found: {e}"),
+            ReverificationError::Conflict(e) => write!(f, "Conflict: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ReverificationError {}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MergeProposalFilter {
+    pub job_id: Option<Uuid>,
+    pub status: Option<MergeProposalStatus>,
+}
+
+/// Proposal under construction, keyed by (primary, secondary)
+#[derive(Default)]
+struct ProposalDraft {
+    shared_identifiers: Vec<Identifier>,
+    receipt_ids: Vec<Uuid>,
+}
+
+pub struct ReverificationEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> ReverificationEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Point `identifier` at `corrected_dfid`, deprecate its other active
+    /// mappings and queue a job to re-verify what they affected
+    pub fn correct_mapping(
+        &self,
+        user_id: &str,
+        identifier: Identifier,
+        corrected_dfid: &str,
+        now: DateTime<Utc>,
+    ) -> Result<ReverificationJob, ReverificationError> {
+        if identifier.key.trim().is_empty() || identifier.value.trim().is_empty() {
+            return Err(ReverificationError::ValidationError(
+                "Identifier key and value are required".to_string(),
+            ));
+        }
+        let target = self
+            .storage
+            .get_item_by_dfid(corrected_dfid)?
+            .ok_or_else(|| ReverificationError::NotFound(format!("Item {corrected_dfid}")))?;
+        if target.status != ItemStatus::Active {
+            return Err(ReverificationError::ValidationError(format!(
+                "Item {corrected_dfid} is not active"
+            )));
+        }
+
+        let mut previous_dfids = Vec::new();
+        let mut already_mapped = false;
+        for mut mapping in self.storage.get_identifier_mappings(&identifier)? {
+            if !matches!(mapping.status, MappingStatus::Active) {
+                continue;
+            }
+            if mapping.dfid == corrected_dfid {
+                already_mapped = true;
+            } else {
+                previous_dfids.push(mapping.dfid.clone());
+                mapping.deprecate();
+                self.storage.update_identifier_mapping(&mapping)?;
+            }
+        }
+        if !already_mapped {
+            self.storage
+                .update_identifier_mapping(&IdentifierMapping::new(
+                    identifier.clone(),
+                    corrected_dfid.to_string(),
+                    CORRECTION_SOURCE.to_string(),
+                ))?;
+        }
+
+        let job = ReverificationJob {
+            job_id: Uuid::new_v4(),
+            identifier,
+            corrected_dfid: corrected_dfid.to_string(),
+            previous_dfids,
+            requested_by: user_id.to_string(),
+            status: ReverificationStatus::Queued,
+            items_checked: 0,
+            receipts_checked: 0,
+            proposals_created: 0,
+            created_at: now,
+            completed_at: None,
+            error: None,
+        };
+        self.storage.store_reverification_job(&job)?;
+        Ok(job)
+    }
+
+    /// Re-run matching for a queued job; a failure is recorded on the job
+    pub fn run_job(
+        &self,
+        job_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<ReverificationJob, ReverificationError> {
+        let mut job = self.get_job(job_id)?;
+        if job.status != ReverificationStatus::Queued {
+            return Err(ReverificationError::Conflict(format!(
+                "Job {job_id} has already run"
+            )));
+        }
+        job.status = ReverificationStatus::Running;
+        self.storage.store_reverification_job(&job)?;
+
+        match self.reverify(&mut job, now) {
+            Ok(()) => job.status = ReverificationStatus::Completed,
+            Err(e) => {
+                job.status = ReverificationStatus::Failed;
+                job.error = Some(e.to_string());
+            }
+        }
+        job.completed_at = Some(now);
+        self.storage.store_reverification_job(&job)?;
+        Ok(job)
+    }
+
+    /// Run the job on the blocking pool
+    pub fn spawn_job(self, job_id: Uuid) -> tokio::task::JoinHandle<()>
+    where
+        S: Send + 'static,
+    {
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = self.run_job(&job_id, Utc::now()) {
+                tracing::warn!("⚠️  Could not run re-verification {}: {}", job_id, e);
+            }
+        })
+    }
+
+    pub fn get_job(&self, job_id: &Uuid) -> Result<ReverificationJob, ReverificationError> {
+        self.storage
+            .get_reverification_job(job_id)?
+            .ok_or_else(|| ReverificationError::NotFound(format!("Job {job_id}")))
+    }
+
+    /// Newest first
+    pub fn list_jobs(&self) -> Result<Vec<ReverificationJob>, ReverificationError> {
+        let mut jobs = self.storage.list_reverification_jobs()?;
+        jobs.sort_by_key(|job| std::cmp::Reverse(job.created_at));
+        Ok(jobs)
+    }
+
+    /// Newest first
+    pub fn list_proposals(
+        &self,
+        filter: &MergeProposalFilter,
+    ) -> Result<Vec<MergeProposal>, ReverificationError> {
+        let mut proposals: Vec<_> = self
+            .storage
+            .list_merge_proposals()?
+            .into_iter()
+            .filter(|p| filter.job_id.is_none_or(|job_id| p.job_id == job_id))
+            .filter(|p| filter.status.is_none_or(|status| p.status == status))
+            .collect();
+        proposals.sort_by_key(|proposal| std::cmp::Reverse(proposal.created_at));
+        Ok(proposals)
+    }
+
+    pub fn get_proposal(&self, proposal_id: &Uuid) -> Result<MergeProposal, ReverificationError> {
+        self.storage
+            .get_merge_proposal(proposal_id)?
+            .ok_or_else(|| ReverificationError::NotFound(format!("Merge proposal {proposal_id}")))
+    }
+
+    /// A proposal that can still be decided: open, with both items active
+    pub fn open_proposal(&self, proposal_id: &Uuid) -> Result<MergeProposal, ReverificationError> {
+        let proposal = self.get_proposal(proposal_id)?;
+        if proposal.status != MergeProposalStatus::Open {
+            return Err(ReverificationError::Conflict(format!(
+                "Merge proposal {proposal_id} is {:?}",
+                proposal.status
+            )));
+        }
+        for dfid in [&proposal.primary_dfid, &proposal.secondary_dfid] {
+            if self.active_item(dfid)?.is_none() {
+                return Err(ReverificationError::Conflict(format!(
+                    "Item {dfid} is no longer active"
+                )));
+            }
+        }
+        Ok(proposal)
+    }
+
+    /// Record the decision on an open proposal. Accepting assumes the caller
+    /// merged the items; the proposal's receipts are relinked to the primary
+    /// and other open proposals for the merged-away item are superseded.
+    pub fn record_decision(
+        &self,
+        mut proposal: MergeProposal,
+        accepted: bool,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<MergeProposal, ReverificationError> {
+        proposal.status = if accepted {
+            MergeProposalStatus::Accepted
+        } else {
+            MergeProposalStatus::Rejected
+        };
+        proposal.decided_by = Some(user_id.to_string());
+        proposal.decided_at = Some(now);
+        self.storage.store_merge_proposal(&proposal)?;
+
+        if accepted {
+            let receipt_ids: HashSet<_> = proposal.receipt_ids.iter().collect();
+            for mut entry in self.storage.list_data_lake_entries()? {
+                if receipt_ids.contains(&entry.receipt_id)
+                    && entry.linked_dfid.as_deref() == Some(&proposal.secondary_dfid)
+                {
+                    entry.linked_dfid = Some(proposal.primary_dfid.clone());
+                    self.storage.update_data_lake_entry(&entry)?;
+                }
+            }
+            for mut other in self.storage.list_merge_proposals()? {
+                if other.status == MergeProposalStatus::Open
+                    && other.proposal_id != proposal.proposal_id
+                    && involves(&other, &proposal.secondary_dfid)
+                {
+                    other.status = MergeProposalStatus::Superseded;
+                    other.decided_at = Some(now);
+                    self.storage.store_merge_proposal(&other)?;
+                }
+            }
+        }
+        Ok(proposal)
+    }
+
+    fn reverify(
+        &self,
+        job: &mut ReverificationJob,
+        now: DateTime<Utc>,
+    ) -> Result<(), ReverificationError> {
+        let mut targets: HashMap<Identifier, Option<String>> = HashMap::new();
+        let mut drafts: BTreeMap<(String, String), ProposalDraft> = BTreeMap::new();
+
+        // Receipts carrying the identifier, and the items they were verified into
+        let receipts = self.storage.find_receipts_by_identifier(&job.identifier)?;
+        let receipt_ids: HashSet<Uuid> = receipts.iter().map(|r| r.id).collect();
+        let entries: Vec<DataLakeEntry> = if receipt_ids.is_empty() {
+            Vec::new()
+        } else {
+            self.storage
+                .list_data_lake_entries()?
+                .into_iter()
+                .filter(|e| receipt_ids.contains(&e.receipt_id))
+                .collect()
+        };
+        job.receipts_checked = receipts.len();
+
+        let mut affected: Vec<String> = job.previous_dfids.clone();
+        affected.extend(
+            self.storage
+                .find_items_by_identifier(&job.identifier)?
+                .into_iter()
+                .map(|item| item.dfid),
+        );
+        affected.extend(entries.iter().filter_map(|e| e.linked_dfid.clone()));
+        let mut seen = HashSet::new();
+        affected.retain(|dfid| dfid != &job.corrected_dfid && seen.insert(dfid.clone()));
+
+        let mut items_checked = 0;
+        for dfid in &affected {
+            let Some(item) = self.active_item(dfid)? else {
+                continue;
+            };
+            items_checked += 1;
+            for identifier in &item.identifiers {
+                if let Some(target) = self.active_target(&mut targets, identifier)? {
+                    if target != item.dfid {
+                        let draft = drafts.entry((target, item.dfid.clone())).or_default();
+                        draft.shared_identifiers.push(identifier.clone());
+                    }
+                }
+            }
+        }
+        job.items_checked = items_checked;
+
+        for receipt in &receipts {
+            let Some(linked) = entries
+                .iter()
+                .find(|e| e.receipt_id == receipt.id)
+                .and_then(|e| e.linked_dfid.clone())
+            else {
+                continue;
+            };
+            for identifier in &receipt.identifiers {
+                if let Some(target) = self.active_target(&mut targets, identifier)? {
+                    if target != linked {
+                        let draft = drafts.entry((target, linked.clone())).or_default();
+                        if !draft.receipt_ids.contains(&receipt.id) {
+                            draft.receipt_ids.push(receipt.id);
+                        }
+                    }
+                }
+            }
+        }
+
+        let open: HashSet<(String, String)> = self
+            .storage
+            .list_merge_proposals()?
+            .into_iter()
+            .filter(|p| p.status == MergeProposalStatus::Open)
+            .flat_map(|p| {
+                [
+                    (p.primary_dfid.clone(), p.secondary_dfid.clone()),
+                    (p.secondary_dfid, p.primary_dfid),
+                ]
+            })
+            .collect();
+
+        for ((primary, secondary), draft) in drafts {
+            if open.contains(&(primary.clone(), secondary.clone())) {
+                continue;
+            }
+            let (Some(_), Some(secondary_item)) =
+                (self.active_item(&primary)?, self.active_item(&secondary)?)
+            else {
+                continue;
+            };
+            let confidence = if secondary_item.identifiers.is_empty() {
+                0.0
+            } else {
+                (draft.shared_identifiers.len() as f64 / secondary_item.identifiers.len() as f64)
+                    .min(1.0)
+            };
+            self.storage.store_merge_proposal(&MergeProposal {
+                proposal_id: Uuid::new_v4(),
+                job_id: job.job_id,
+                primary_dfid: primary,
+                secondary_dfid: secondary,
+                shared_identifiers: draft.shared_identifiers,
+                receipt_ids: draft.receipt_ids,
+                confidence,
+                status: MergeProposalStatus::Open,
+                created_at: now,
+                decided_by: None,
+                decided_at: None,
+            })?;
+            job.proposals_created += 1;
+        }
+        Ok(())
+    }
+
+    fn active_item(&self, dfid: &str) -> Result<Option<Item>, StorageError> {
+        Ok(self
+            .storage
+            .get_item_by_dfid(dfid)?
+            .filter(|item| item.status == ItemStatus::Active))
+    }
+
+    /// The DFID an identifier's active mapping points at, cached per job
+    fn active_target(
+        &self,
+        cache: &mut HashMap<Identifier, Option<String>>,
+        identifier: &Identifier,
+    ) -> Result<Option<String>, StorageError> {
+        if let Some(target) = cache.get(identifier) {
+            return Ok(target.clone());
+        }
+        let target = self
+            .storage
+            .get_identifier_mappings(identifier)?
+            .into_iter()
+            .find(|m| matches!(m.status, MappingStatus::Active))
+            .map(|m| m.dfid);
+        cache.insert(identifier.clone(), target.clone());
+        Ok(target)
+    }
+}
+
+fn involves(proposal: &MergeProposal, dfid: &str) -> bool {
+    proposal.primary_dfid == dfid || proposal.secondary_dfid == dfid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{IngestionPriority, Receipt};
+    use std::sync::{Arc, Mutex};
+
+    fn mapped_item(storage: &Arc<Mutex<InMemoryStorage>>, dfid: &str, identifier: &Identifier) {
+        storage
+            .store_item(&Item::new(
+                dfid.to_string(),
+                vec![identifier.clone()],
+                Uuid::new_v4(),
+            ))
+            .unwrap();
+        storage
+            .store_identifier_mapping(&IdentifierMapping::new(
+                identifier.clone(),
+                dfid.to_string(),
+                "test".to_string(),
+            ))
+            .unwrap();
+    }
+
+    type Storage = Arc<Mutex<InMemoryStorage>>;
+
+    /// Lot L-9 was mapped to DFID-B, with a receipt linked there, but belongs
+    /// to DFID-A
+    struct Mislinked {
+        storage: Storage,
+        engine: ReverificationEngine<Storage>,
+        lot: Identifier,
+        receipt: Receipt,
+        entry: DataLakeEntry,
+    }
+
+    fn mislinked() -> Mislinked {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let tag = Identifier::new("tag", "T-1");
+        let lot = Identifier::new("lot", "L-9");
+        mapped_item(&storage, "DFID-A", &tag);
+        mapped_item(&storage, "DFID-B", &lot);
+
+        let receipt = Receipt {
+            id: Uuid::new_v4(),
+            hash: "hash".to_string(),
+            timestamp: Utc::now(),
+            data_size: 10,
+            identifiers: vec![lot.clone()],
+            priority: IngestionPriority::default(),
+            occurred_at: None,
+        };
+        storage.store_receipt(&receipt).unwrap();
+        let mut entry = DataLakeEntry::new(receipt.id, vec![lot.clone()], "hash".to_string(), 10);
+        entry.linked_dfid = Some("DFID-B".to_string());
+        storage.store_data_lake_entry(&entry).unwrap();
+
+        let engine = ReverificationEngine::new(Arc::clone(&storage));
+        Mislinked {
+            storage,
+            engine,
+            lot,
+            receipt,
+            entry,
+        }
+    }
+
+    impl Mislinked {
+        /// Correct the lot to DFID-A and run the job
+        fn correct(&self) -> ReverificationJob {
+            let now = Utc::now();
+            let job = self
+                .engine
+                .correct_mapping("admin", self.lot.clone(), "DFID-A", now)
+                .unwrap();
+            self.engine.run_job(&job.job_id, now).unwrap()
+        }
+
+        fn proposal(&self) -> MergeProposal {
+            self.engine
+                .list_proposals(&MergeProposalFilter::default())
+                .unwrap()
+                .remove(0)
+        }
+
+        fn entry_dfid(&self) -> Option<String> {
+            self.storage
+                .get_data_lake_entry(&self.entry.entry_id)
+                .unwrap()
+                .unwrap()
+                .linked_dfid
+        }
+    }
+
+    #[test]
+    fn test_correction_repoints_mapping_and_queues_job() {
+        let m = mislinked();
+
+        let job = m
+            .engine
+            .correct_mapping("admin", m.lot.clone(), "DFID-A", Utc::now())
+            .unwrap();
+        assert_eq!(job.status, ReverificationStatus::Queued);
+        assert_eq!(job.previous_dfids, vec!["DFID-B".to_string()]);
+
+        let active: Vec<_> = m
+            .storage
+            .get_identifier_mappings(&m.lot)
+            .unwrap()
+            .into_iter()
+            .filter(|mapping| matches!(mapping.status, MappingStatus::Active))
+            .map(|mapping| mapping.dfid)
+            .collect();
+        assert_eq!(active, vec!["DFID-A".to_string()]);
+    }
+
+    #[test]
+    fn test_invalid_correction_is_refused() {
+        let m = mislinked();
+        let now = Utc::now();
+
+        assert!(matches!(
+            m.engine
+                .correct_mapping("admin", Identifier::new("lot", " "), "DFID-A", now),
+            Err(ReverificationError::ValidationError(_))
+        ));
+        assert!(matches!(
+            m.engine
+                .correct_mapping("admin", m.lot.clone(), "DFID-MISSING", now),
+            Err(ReverificationError::NotFound(_))
+        ));
+
+        let mut deprecated = m.storage.get_item_by_dfid("DFID-A").unwrap().unwrap();
+        deprecated.status = ItemStatus::Deprecated;
+        m.storage.update_item(&deprecated).unwrap();
+        assert!(matches!(
+            m.engine
+                .correct_mapping("admin", m.lot.clone(), "DFID-A", now),
+            Err(ReverificationError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_correction_proposes_merges_without_mutating_items() {
+        let m = mislinked();
+
+        let job = m.correct();
+        assert_eq!(job.status, ReverificationStatus::Completed);
+        assert_eq!((job.items_checked, job.receipts_checked), (1, 1));
+        assert_eq!(job.proposals_created, 1);
+
+        let proposal = m.proposal();
+        assert_eq!(proposal.primary_dfid, "DFID-A");
+        assert_eq!(proposal.secondary_dfid, "DFID-B");
+        assert_eq!(proposal.receipt_ids, vec![m.receipt.id]);
+        assert_eq!(proposal.confidence, 1.0);
+
+        // Nothing merged or relinked yet
+        let secondary = m.storage.get_item_by_dfid("DFID-B").unwrap().unwrap();
+        assert_eq!(secondary.status, ItemStatus::Active);
+        assert_eq!(m.entry_dfid(), Some("DFID-B".to_string()));
+    }
+
+    #[test]
+    fn test_job_runs_only_once() {
+        let m = mislinked();
+        let job = m.correct();
+
+        assert!(matches!(
+            m.engine.run_job(&job.job_id, Utc::now()),
+            Err(ReverificationError::Conflict(_))
+        ));
+    }
+
+    #[test]
+    fn test_rerun_does_not_duplicate_open_proposal() {
+        let m = mislinked();
+        m.correct();
+
+        assert_eq!(m.correct().proposals_created, 0);
+        assert_eq!(
+            m.engine
+                .list_proposals(&MergeProposalFilter::default())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_accepted_proposal_relinks_receipts() {
+        let m = mislinked();
+        m.correct();
+
+        let proposal = m.engine.open_proposal(&m.proposal().proposal_id).unwrap();
+        let accepted = m
+            .engine
+            .record_decision(proposal, true, "admin", Utc::now())
+            .unwrap();
+        assert_eq!(accepted.status, MergeProposalStatus::Accepted);
+        assert_eq!(m.entry_dfid(), Some("DFID-A".to_string()));
+        assert!(matches!(
+            m.engine.open_proposal(&accepted.proposal_id),
+            Err(ReverificationError::Conflict(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejected_proposal_leaves_receipts_in_place() {
+        let m = mislinked();
+        m.correct();
+
+        let proposal = m.engine.open_proposal(&m.proposal().proposal_id).unwrap();
+        let rejected = m
+            .engine
+            .record_decision(proposal, false, "admin", Utc::now())
+            .unwrap();
+        assert_eq!(rejected.status, MergeProposalStatus::Rejected);
+        assert_eq!(m.entry_dfid(), Some("DFID-B".to_string()));
+        assert!(matches!(
+            m.engine.open_proposal(&rejected.proposal_id),
+            Err(ReverificationError::Conflict(_))
+        ));
+    }
+}
@@ -0,0 +1,600 @@
+//! Manual review queue for pending items.
+//!
+//! Items that ingestion, verification or lifecycle checks could not settle on
+//! their own wait as `PendingItem`s. A reviewer claims one so nobody else works
+//! it at the same time, then approves it as a new item, links it to an
+//! existing DFID or rejects it. Approvals and links emit the item's Created or
+//! Enriched event; every claim and decision is written to the audit log.
+//! Admins see the whole queue, other reviewers their workspace's items.
+
+use crate::audit_engine::{AuditEngine, AuditError};
+use crate::dfid_engine::DfidEngine;
+use crate::enrichment_policy_engine::enrich_with_policy;
+use crate::events_engine::EventsEngine;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    AuditEventType, AuditOutcome, AuditSeverity, EventType, EventVisibility, IdentifierMapping,
+    Item, PendingItem,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A claim nobody acted on for this long may be taken over
+pub const CLAIM_TIMEOUT_MINUTES: i64 = 30;
+
+/// Source recorded on the events and mappings a review produces
+const REVIEW_SOURCE: &str = "review_queue";
+
+#[derive(Debug)]
+pub enum ReviewQueueError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+    NotFound(String),
+    /// Another reviewer holds a live claim on the item
+    AlreadyClaimed(String),
+    EventError(String),
+    AuditError(AuditError),
+}
+
+impl From<StorageError> for ReviewQueueError {
+    fn from(err: StorageError) -> Self {
+        ReviewQueueError::StorageError(err)
+    }
+}
+
+impl From<AuditError> for ReviewQueueError {
+    fn from(err: AuditError) -> Self {
+        ReviewQueueError::AuditError(err)
+    }
+}
+
+impl std::fmt::Display for ReviewQueueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReviewQueueError::StorageError(e) => write!(f, "Storage error: {e}"),
+            ReviewQueueError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            ReviewQueueError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            ReviewQueueError::NotFound(e) => write!(f, "Not found: {e}"),
+            ReviewQueueError::AlreadyClaimed(e) => write!(f, "Already claimed: {e}"),
+            ReviewQueueError::EventError(e) => write!(f, "Event error: {e}"),
+            ReviewQueueError::AuditError(e) => write!(f, "Audit log error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ReviewQueueError {}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReviewQueueFilter {
+    /// Pending reason, e.g. `ConflictingDFIDs`
+    pub reason: Option<String>,
+    /// Only items nobody holds a live claim on
+    #[serde(default)]
+    pub unclaimed: bool,
+    /// Only items the caller has claimed
+    #[serde(default)]
+    pub mine: bool,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ReviewDecision {
+    /// Create an item with a fresh DFID from the pending identifiers and data
+    ApproveAsNew,
+    /// Add the pending identifiers and data to an existing item
+    LinkToExisting {
+        dfid: String,
+    },
+    Reject {
+        reason: Option<String>,
+    },
+}
+
+impl ReviewDecision {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ReviewDecision::ApproveAsNew => "approve_as_new",
+            ReviewDecision::LinkToExisting { .. } => "link_to_existing",
+            ReviewDecision::Reject { .. } => "reject",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewOutcome {
+    pub pending_id: Uuid,
+    pub decision: String,
+    /// The item created or linked to; `None` when rejected
+    pub dfid: Option<String>,
+    pub event_id: Option<Uuid>,
+    pub audit_event_id: Uuid,
+}
+
+pub struct ReviewQueueEngine<S: StorageBackend> {
+    storage: S,
+    audit: AuditEngine<S>,
+    events: EventsEngine<S>,
+    dfid_engine: DfidEngine,
+}
+
+impl<S: StorageBackend + Clone + 'static> ReviewQueueEngine<S> {
+    /// `dfid_engine` mints the DFIDs of items approved as new; pass the
+    /// deployment's shared engine so its leased sequence ranges are reused
+    pub fn new(storage: S, dfid_engine: DfidEngine) -> Self {
+        Self {
+            audit: AuditEngine::new(storage.clone()),
+            events: EventsEngine::new(storage.clone()),
+            dfid_engine,
+            storage,
+        }
+    }
+
+    /// Highest priority first, oldest first within a priority
+    pub fn list(
+        &self,
+        user_id: &str,
+        filter: &ReviewQueueFilter,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<PendingItem>, ReviewQueueError> {
+        let scope = self.reviewer_scope(user_id)?;
+        let mut items: Vec<PendingItem> = self
+            .storage
+            .list_pending_items()?
+            .into_iter()
+            .filter(|item| scope.covers(item))
+            .filter(|item| {
+                filter
+                    .reason
+                    .as_deref()
+                    .is_none_or(|reason| reason_name(item) == reason)
+            })
+            .filter(|item| !filter.unclaimed || live_claimant(item, now).is_none())
+            .filter(|item| !filter.mine || live_claimant(item, now) == Some(user_id))
+            .collect();
+        items.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then_with(|| a.created_at.cmp(&b.created_at))
+        });
+        if let Some(limit) = filter.limit {
+            items.truncate(limit);
+        }
+        Ok(items)
+    }
+
+    pub fn get(&self, user_id: &str, pending_id: &Uuid) -> Result<PendingItem, ReviewQueueError> {
+        let scope = self.reviewer_scope(user_id)?;
+        let item = self
+            .storage
+            .get_pending_item(pending_id)?
+            .ok_or_else(|| ReviewQueueError::NotFound(format!("Pending item {pending_id}")))?;
+        if !scope.covers(&item) {
+            return Err(ReviewQueueError::PermissionDenied(
+                "Pending item belongs to another workspace".to_string(),
+            ));
+        }
+        Ok(item)
+    }
+
+    /// Claims are renewed by claiming again; a stale claim may be taken over
+    pub fn claim(
+        &self,
+        user_id: &str,
+        pending_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<PendingItem, ReviewQueueError> {
+        let mut item = self.get(user_id, pending_id)?;
+        check_not_claimed_by_other(&item, user_id, now)?;
+
+        item.claimed_by = Some(user_id.to_string());
+        item.claimed_at = Some(now);
+        item.last_updated = now;
+        self.storage.update_pending_item(&item)?;
+
+        self.audit_review(user_id, "review.claim", &item, HashMap::new())?;
+        Ok(item)
+    }
+
+    /// The claimant, or an admin, hands the item back to the queue
+    pub fn release(
+        &self,
+        user_id: &str,
+        pending_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<PendingItem, ReviewQueueError> {
+        let scope = self.reviewer_scope(user_id)?;
+        let mut item = self.get(user_id, pending_id)?;
+        if !matches!(scope, ReviewerScope::All) {
+            check_not_claimed_by_other(&item, user_id, now)?;
+        }
+
+        let released = item.claimed_by.take();
+        item.claimed_at = None;
+        item.last_updated = now;
+        self.storage.update_pending_item(&item)?;
+
+        self.audit_review(
+            user_id,
+            "review.release",
+            &item,
+            HashMap::from([("released_claim".to_string(), serde_json::json!(released))]),
+        )?;
+        Ok(item)
+    }
+
+    /// Settle the item and take it off the queue. Unclaimed items may be
+    /// resolved directly; items claimed by someone else may not.
+    pub fn resolve(
+        &mut self,
+        user_id: &str,
+        pending_id: &Uuid,
+        decision: ReviewDecision,
+        now: DateTime<Utc>,
+    ) -> Result<ReviewOutcome, ReviewQueueError> {
+        let item = self.get(user_id, pending_id)?;
+        check_not_claimed_by_other(&item, user_id, now)?;
+
+        let (dfid, event_id) = match &decision {
+            ReviewDecision::ApproveAsNew => {
+                let (dfid, event_id) = self.approve_as_new(user_id, &item)?;
+                (Some(dfid), Some(event_id))
+            }
+            ReviewDecision::LinkToExisting { dfid } => {
+                let event_id = self.link_to_existing(user_id, &item, dfid)?;
+                (Some(dfid.clone()), Some(event_id))
+            }
+            ReviewDecision::Reject { .. } => (None, None),
+        };
+        self.storage.delete_pending_item(pending_id)?;
+
+        let mut details = HashMap::from([
+            ("decision".to_string(), serde_json::json!(decision.as_str())),
+            ("dfid".to_string(), serde_json::json!(dfid)),
+            ("event_id".to_string(), serde_json::json!(event_id)),
+        ]);
+        if let ReviewDecision::Reject {
+            reason: Some(reason),
+        } = &decision
+        {
+            details.insert("rejection_reason".to_string(), serde_json::json!(reason));
+        }
+        let audit_event_id = self.audit_review(user_id, "review.resolve", &item, details)?;
+
+        Ok(ReviewOutcome {
+            pending_id: *pending_id,
+            decision: decision.as_str().to_string(),
+            dfid,
+            event_id,
+            audit_event_id,
+        })
+    }
+
+    fn approve_as_new(
+        &mut self,
+        user_id: &str,
+        pending: &PendingItem,
+    ) -> Result<(String, Uuid), ReviewQueueError> {
+        if pending.identifiers.is_empty() {
+            return Err(ReviewQueueError::ValidationError(
+                "A pending item without identifiers cannot become an item".to_string(),
+            ));
+        }
+        let dfid = self.dfid_engine.generate_dfid()?;
+
+        let mut item = Item::new(
+            dfid.clone(),
+            pending.identifiers.clone(),
+            pending.source_entry,
+        );
+        if let Some(data) = &pending.enriched_data {
+            item.enriched_data.extend(data.clone());
+        }
+        self.storage.store_item(&item)?;
+        for identifier in &pending.identifiers {
+            self.storage
+                .store_identifier_mapping(&IdentifierMapping::new(
+                    identifier.clone(),
+                    dfid.clone(),
+                    REVIEW_SOURCE.to_string(),
+                ))?;
+        }
+
+        let mut metadata = review_metadata(user_id, pending);
+        metadata.insert(
+            "identifiers".to_string(),
+            serde_json::to_value(&pending.identifiers).unwrap_or_default(),
+        );
+        let event = self
+            .events
+            .create_event_with_metadata(
+                dfid.clone(),
+                EventType::Created,
+                REVIEW_SOURCE.to_string(),
+                EventVisibility::Public,
+                metadata,
+            )
+            .map_err(|e| ReviewQueueError::EventError(e.to_string()))?
+            .event;
+        Ok((dfid, event.event_id))
+    }
+
+    fn link_to_existing(
+        &mut self,
+        user_id: &str,
+        pending: &PendingItem,
+        dfid: &str,
+    ) -> Result<Uuid, ReviewQueueError> {
+        let mut item = self
+            .storage
+            .get_item_by_dfid(dfid)?
+            .ok_or_else(|| ReviewQueueError::NotFound(format!("Item {dfid}")))?;
+        item.add_identifiers(pending.identifiers.clone());
+
+        // The submitter's workspace policy decides how the data merges
+        let enrichment = enrich_with_policy(
+            &self.storage,
+            item,
+            pending.enriched_data.clone().unwrap_or_default(),
+            pending.source_entry,
+            pending.user_id.as_deref(),
+        )?;
+        for identifier in &pending.identifiers {
+            if self.storage.get_identifier_mappings(identifier)?.is_empty() {
+                self.storage
+                    .store_identifier_mapping(&IdentifierMapping::new(
+                        identifier.clone(),
+                        dfid.to_string(),
+                        REVIEW_SOURCE.to_string(),
+                    ))?;
+            }
+        }
+
+        let event_id = enrichment.event.event_id;
+        self.events
+            .add_event_metadata(&event_id, review_metadata(user_id, pending))
+            .map_err(|e| ReviewQueueError::EventError(e.to_string()))?;
+        Ok(event_id)
+    }
+
+    fn reviewer_scope(&self, user_id: &str) -> Result<ReviewerScope, ReviewQueueError> {
+        let account = self
+            .storage
+            .get_user_account(user_id)?
+            .ok_or_else(|| ReviewQueueError::PermissionDenied(format!("Unknown user {user_id}")))?;
+        if account.is_admin {
+            return Ok(ReviewerScope::All);
+        }
+        account
+            .workspace_id
+            .map(ReviewerScope::Workspace)
+            .ok_or_else(|| {
+                ReviewQueueError::PermissionDenied(
+                    "Only workspace members can work the review queue".to_string(),
+                )
+            })
+    }
+
+    fn audit_review(
+        &self,
+        user_id: &str,
+        action: &str,
+        item: &PendingItem,
+        mut details: HashMap<String, serde_json::Value>,
+    ) -> Result<Uuid, ReviewQueueError> {
+        details.insert(
+            "pending_reason".to_string(),
+            serde_json::json!(reason_name(item)),
+        );
+        details.insert(
+            "workspace_id".to_string(),
+            serde_json::json!(item.workspace_id),
+        );
+        Ok(self.audit.log_event(
+            user_id.to_string(),
+            AuditEventType::Data,
+            action.to_string(),
+            format!("pending_item:{}", item.pending_id),
+            AuditOutcome::Success,
+            AuditSeverity::Low,
+            Some(details),
+            None,
+            None,
+        )?)
+    }
+}
+
+enum ReviewerScope {
+    All,
+    Workspace(String),
+}
+
+impl ReviewerScope {
+    fn covers(&self, item: &PendingItem) -> bool {
+        match self {
+            ReviewerScope::All => true,
+            ReviewerScope::Workspace(workspace_id) => {
+                item.workspace_id.as_deref() == Some(workspace_id)
+            }
+        }
+    }
+}
+
+/// Who holds a claim that has not timed out
+fn live_claimant(item: &PendingItem, now: DateTime<Utc>) -> Option<&str> {
+    let claimed_at = item.claimed_at?;
+    (now - claimed_at < Duration::minutes(CLAIM_TIMEOUT_MINUTES))
+        .then_some(item.claimed_by.as_deref())
+        .flatten()
+}
+
+fn check_not_claimed_by_other(
+    item: &PendingItem,
+    user_id: &str,
+    now: DateTime<Utc>,
+) -> Result<(), ReviewQueueError> {
+    match live_claimant(item, now) {
+        Some(claimant) if claimant != user_id => Err(ReviewQueueError::AlreadyClaimed(format!(
+            "Pending item {} is claimed by {claimant}",
+            item.pending_id
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// The variant of the pending reason, e.g. `ConflictingDFIDs`
+fn reason_name(item: &PendingItem) -> String {
+    format!("{:?}", item.reason)
+        .split(['(', ' ', '{'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn review_metadata(user_id: &str, pending: &PendingItem) -> HashMap<String, serde_json::Value> {
+    HashMap::from([
+        (
+            "pending_id".to_string(),
+            serde_json::Value::String(pending.pending_id.to_string()),
+        ),
+        (
+            "reviewed_by".to_string(),
+            serde_json::Value::String(user_id.to_string()),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{
+        AccountStatus, Identifier, PendingReason, TierLimits, UserAccount, UserTier,
+    };
+    use std::sync::{Arc, Mutex};
+
+    fn reviewer(user_id: &str, workspace_id: &str) -> UserAccount {
+        UserAccount {
+            user_id: user_id.to_string(),
+            username: user_id.to_string(),
+            email: format!("{user_id}@coop.example"),
+            password_hash: "hash".to_string(),
+            limits: TierLimits::for_tier(&UserTier::Basic),
+            tier: UserTier::Basic,
+            status: AccountStatus::Active,
+            credits: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_login: None,
+            subscription: None,
+            is_admin: false,
+            workspace_id: Some(workspace_id.to_string()),
+            available_adapters: None,
+            locale: None,
+        }
+    }
+
+    fn pending(lot: &str, workspace_id: &str) -> PendingItem {
+        PendingItem::new(
+            vec![Identifier::new("lot", lot)],
+            Some(HashMap::from([(
+                "weight_kg".to_string(),
+                serde_json::json!(1200),
+            )])),
+            Uuid::new_v4(),
+            PendingReason::DuplicateDetectionAmbiguous {
+                potential_matches: vec![],
+                similarity_scores: vec![],
+            },
+            Some("submitter".to_string()),
+            Some(workspace_id.to_string()),
+        )
+    }
+
+    #[test]
+    fn test_claimed_items_resolve_once_and_leave_the_queue() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        storage
+            .store_user_account(&reviewer("ana", "coop"))
+            .unwrap();
+        storage
+            .store_user_account(&reviewer("bruno", "coop"))
+            .unwrap();
+        storage
+            .store_user_account(&reviewer("outsider", "other"))
+            .unwrap();
+        let fresh = pending("L-1", "coop");
+        let linked = pending("L-2", "coop");
+        storage.store_pending_item(&fresh).unwrap();
+        storage.store_pending_item(&linked).unwrap();
+        let mut engine = ReviewQueueEngine::new(Arc::clone(&storage), DfidEngine::new());
+        let now = Utc::now();
+
+        assert!(matches!(
+            engine.get("outsider", &fresh.pending_id),
+            Err(ReviewQueueError::PermissionDenied(_))
+        ));
+        engine.claim("ana", &fresh.pending_id, now).unwrap();
+        assert!(matches!(
+            engine.resolve(
+                "bruno",
+                &fresh.pending_id,
+                ReviewDecision::ApproveAsNew,
+                now
+            ),
+            Err(ReviewQueueError::AlreadyClaimed(_))
+        ));
+        let unclaimed = ReviewQueueFilter {
+            unclaimed: true,
+            ..Default::default()
+        };
+        assert_eq!(engine.list("bruno", &unclaimed, now).unwrap().len(), 1);
+
+        // A stale claim can be taken over
+        let later = now + Duration::minutes(CLAIM_TIMEOUT_MINUTES + 1);
+        engine.claim("bruno", &fresh.pending_id, later).unwrap();
+        let created = engine
+            .resolve(
+                "bruno",
+                &fresh.pending_id,
+                ReviewDecision::ApproveAsNew,
+                later,
+            )
+            .unwrap();
+        let dfid = created.dfid.clone().unwrap();
+        let item = storage.get_item_by_dfid(&dfid).unwrap().unwrap();
+        assert_eq!(item.enriched_data["weight_kg"], serde_json::json!(1200));
+        assert_eq!(storage.get_events_by_dfid(&dfid).unwrap().len(), 1);
+
+        let link = engine
+            .resolve(
+                "ana",
+                &linked.pending_id,
+                ReviewDecision::LinkToExisting { dfid: dfid.clone() },
+                later,
+            )
+            .unwrap();
+        assert_eq!(link.dfid.as_deref(), Some(dfid.as_str()));
+        let item = storage.get_item_by_dfid(&dfid).unwrap().unwrap();
+        assert_eq!(item.identifiers.len(), 2);
+
+        assert!(storage
+            .get_pending_item(&fresh.pending_id)
+            .unwrap()
+            .is_none());
+        assert!(engine
+            .list("ana", &Default::default(), later)
+            .unwrap()
+            .is_empty());
+        let audited: Vec<_> = storage
+            .list_audit_events()
+            .unwrap()
+            .into_iter()
+            .filter(|e| e.action == "review.resolve")
+            .collect();
+        assert_eq!(audited.len(), 2);
+    }
+}
@@ -0,0 +1,662 @@
+//! Runbook automation hooks.
+//!
+//! A hook pairs an operational incident (stalled indexer, growing webhook
+//! dead-letter queue, unhealthy adapter) with the remediation an operator
+//! would otherwise run by hand: restart a worker, move circuits off the
+//! failing adapter, open an incident. The monitor evaluates every enabled hook
+//! each tick; a hook that fired stays quiet for its cooldown even if the
+//! incident persists, so a remediation that does not help is not repeated in a
+//! loop. Every automated action is written to the audit log as a `System`
+//! event acted by `runbook:<hook_id>`, whether it succeeded or not.
+//!
+//! Workers can only be restarted if they were started through
+//! [`register_worker`] in this process; the indexer runs as its own binary, so
+//! a stalled indexer is usually paired with an incident rather than a restart.
+
+use crate::adapters::metrics::{all_adapter_metrics, reset_adapter_metrics};
+use crate::audit_engine::{AuditEngine, AuditError};
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    AdapterType, AuditEventType, AuditOutcome, AuditSeverity, DeliveryStatus, IncidentCategory,
+    RemediationAction, RunbookHook, RunbookTrigger,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use uuid::Uuid;
+
+/// Networks whose indexing progress is watched
+pub const INDEXED_NETWORKS: [&str; 2] = ["stellar-testnet", "stellar-mainnet"];
+/// Minutes without indexing progress before the indexer counts as stalled
+pub const DEFAULT_STALL_MINUTES: f64 = 15.0;
+/// New dead-lettered webhook deliveries since the baseline
+pub const DEFAULT_DLQ_GROWTH: f64 = 10.0;
+pub const DEFAULT_ADAPTER_ERROR_RATE: f64 = 0.5;
+/// Calls an adapter must have made before its error rate is judged
+pub const MIN_ADAPTER_CALLS: u64 = 20;
+pub const DEFAULT_COOLDOWN_MINUTES: u32 = 30;
+
+type WorkerSpawner = Box<dyn Fn() -> tokio::task::JoinHandle<()> + Send + Sync>;
+
+struct RegisteredWorker {
+    spawn: WorkerSpawner,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+fn workers() -> &'static Mutex<HashMap<String, RegisteredWorker>> {
+    static WORKERS: OnceLock<Mutex<HashMap<String, RegisteredWorker>>> = OnceLock::new();
+    WORKERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Start a background worker and make it restartable by name
+pub fn register_worker<F>(name: &str, spawn: F)
+where
+    F: Fn() -> tokio::task::JoinHandle<()> + Send + Sync + 'static,
+{
+    let handle = spawn();
+    let previous = workers().lock().unwrap().insert(
+        name.to_string(),
+        RegisteredWorker {
+            spawn: Box::new(spawn),
+            handle,
+        },
+    );
+    if let Some(previous) = previous {
+        previous.handle.abort();
+    }
+}
+
+/// Names of the workers registered in this process
+pub fn registered_workers() -> Vec<String> {
+    let mut names: Vec<String> = workers().lock().unwrap().keys().cloned().collect();
+    names.sort();
+    names
+}
+
+/// Abort the worker's task and spawn a fresh one; false if no worker of that
+/// name is registered in this process or there is no runtime to spawn on
+pub fn restart_worker(name: &str) -> bool {
+    if tokio::runtime::Handle::try_current().is_err() {
+        return false;
+    }
+    let mut workers = workers().lock().unwrap();
+    let Some(worker) = workers.get_mut(name) else {
+        return false;
+    };
+    worker.handle.abort();
+    worker.handle = (worker.spawn)();
+    true
+}
+
+#[derive(Debug)]
+pub enum RunbookError {
+    StorageError(StorageError),
+    ValidationError(String),
+    NotFound(String),
+}
+
+impl From<StorageError> for RunbookError {
+    fn from(err: StorageError) -> Self {
+        RunbookError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for RunbookError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunbookError::StorageError(e) => write!(f, "Storage error: {e}"),
+            RunbookError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            RunbookError::NotFound(e) => write!(f, "Not found: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for RunbookError {}
+
+/// Fields of a hook an admin sets on create or update
+#[derive(Debug, Clone, Deserialize)]
+pub struct HookInput {
+    pub trigger: RunbookTrigger,
+    pub actions: Vec<RemediationAction>,
+    pub threshold: Option<f64>,
+    pub cooldown_minutes: Option<u32>,
+    pub enabled: Option<bool>,
+}
+
+/// An incident found while evaluating a trigger
+#[derive(Debug, Clone, Serialize)]
+pub struct Detection {
+    pub trigger: RunbookTrigger,
+    /// What the incident is about, e.g. `indexer:stellar-testnet`
+    pub subject: String,
+    /// Measured value compared against the threshold
+    pub observed: f64,
+    pub threshold: f64,
+    /// The unhealthy adapter, for `adapter_unhealthy`
+    pub adapter: Option<AdapterType>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ActionOutcome {
+    pub action: RemediationAction,
+    pub success: bool,
+    pub detail: String,
+    pub audit_event_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HookFiring {
+    pub hook_id: Uuid,
+    pub detection: Detection,
+    pub actions: Vec<ActionOutcome>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EvaluationReport {
+    pub evaluated_at: DateTime<Utc>,
+    pub hooks_evaluated: usize,
+    pub firings: Vec<HookFiring>,
+    /// Hooks whose incident was detected while they were cooling down
+    pub suppressed: Vec<Uuid>,
+}
+
+fn default_threshold(trigger: RunbookTrigger) -> f64 {
+    match trigger {
+        RunbookTrigger::IndexerStalled => DEFAULT_STALL_MINUTES,
+        RunbookTrigger::WebhookDlqGrowing => DEFAULT_DLQ_GROWTH,
+        RunbookTrigger::AdapterUnhealthy => DEFAULT_ADAPTER_ERROR_RATE,
+    }
+}
+
+fn action_name(action: &RemediationAction) -> &'static str {
+    match action {
+        RemediationAction::RestartWorker { .. } => "restart_worker",
+        RemediationAction::SwitchAdapter { .. } => "switch_adapter",
+        RemediationAction::OpenIncident { .. } => "open_incident",
+    }
+}
+
+fn trigger_name(trigger: RunbookTrigger) -> String {
+    serde_json::to_value(trigger)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+pub struct RunbookEngine<S: StorageBackend> {
+    storage: S,
+    audit: AuditEngine<S>,
+}
+
+impl<S: StorageBackend + Clone + 'static> RunbookEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            audit: AuditEngine::new(storage.clone()),
+            storage,
+        }
+    }
+
+    fn validate(input: &HookInput) -> Result<(), RunbookError> {
+        if input.actions.is_empty() {
+            return Err(RunbookError::ValidationError(
+                "At least one remediation action is required".to_string(),
+            ));
+        }
+        if input.threshold.is_some_and(|t| !t.is_finite() || t <= 0.0) {
+            return Err(RunbookError::ValidationError(
+                "Threshold must be a positive number".to_string(),
+            ));
+        }
+        if input.trigger == RunbookTrigger::AdapterUnhealthy
+            && input.threshold.is_some_and(|t| t > 1.0)
+        {
+            return Err(RunbookError::ValidationError(
+                "Adapter error rate threshold must be at most 1".to_string(),
+            ));
+        }
+        if input.cooldown_minutes == Some(0) {
+            return Err(RunbookError::ValidationError(
+                "Cooldown must be at least one minute".to_string(),
+            ));
+        }
+        for action in &input.actions {
+            match action {
+                RemediationAction::RestartWorker { worker } if worker.trim().is_empty() => {
+                    return Err(RunbookError::ValidationError(
+                        "restart_worker needs a worker name".to_string(),
+                    ));
+                }
+                RemediationAction::SwitchAdapter { .. }
+                    if input.trigger != RunbookTrigger::AdapterUnhealthy =>
+                {
+                    return Err(RunbookError::ValidationError(
+                        "switch_adapter only applies to adapter_unhealthy hooks".to_string(),
+                    ));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    pub fn create_hook(
+        &self,
+        user_id: &str,
+        input: HookInput,
+        now: DateTime<Utc>,
+    ) -> Result<RunbookHook, RunbookError> {
+        Self::validate(&input)?;
+        let hook = RunbookHook {
+            hook_id: Uuid::new_v4(),
+            trigger: input.trigger,
+            actions: input.actions,
+            threshold: input.threshold,
+            cooldown_minutes: input.cooldown_minutes.unwrap_or(DEFAULT_COOLDOWN_MINUTES),
+            enabled: input.enabled.unwrap_or(true),
+            created_by: user_id.to_string(),
+            created_at: now,
+            last_fired_at: None,
+            dlq_baseline: None,
+        };
+        self.storage.store_runbook_hook(&hook)?;
+        Ok(hook)
+    }
+
+    /// Replace a hook's settings; its firing history is kept unless the
+    /// trigger changes
+    pub fn update_hook(
+        &self,
+        hook_id: &Uuid,
+        input: HookInput,
+    ) -> Result<RunbookHook, RunbookError> {
+        Self::validate(&input)?;
+        let mut hook = self.get_hook(hook_id)?;
+        if hook.trigger != input.trigger {
+            hook.last_fired_at = None;
+            hook.dlq_baseline = None;
+        }
+        hook.trigger = input.trigger;
+        hook.actions = input.actions;
+        hook.threshold = input.threshold;
+        hook.cooldown_minutes = input.cooldown_minutes.unwrap_or(hook.cooldown_minutes);
+        hook.enabled = input.enabled.unwrap_or(hook.enabled);
+        self.storage.store_runbook_hook(&hook)?;
+        Ok(hook)
+    }
+
+    pub fn get_hook(&self, hook_id: &Uuid) -> Result<RunbookHook, RunbookError> {
+        self.storage
+            .get_runbook_hook(hook_id)?
+            .ok_or_else(|| RunbookError::NotFound(format!("Runbook hook {hook_id}")))
+    }
+
+    pub fn list_hooks(&self) -> Result<Vec<RunbookHook>, RunbookError> {
+        let mut hooks = self.storage.list_runbook_hooks()?;
+        hooks.sort_by_key(|h| h.created_at);
+        Ok(hooks)
+    }
+
+    pub fn delete_hook(&self, hook_id: &Uuid) -> Result<(), RunbookError> {
+        self.get_hook(hook_id)?;
+        self.storage.delete_runbook_hook(hook_id)?;
+        Ok(())
+    }
+
+    /// Evaluate every enabled hook and run the actions of those whose
+    /// incident is detected and whose cooldown has passed
+    pub fn evaluate(&self, now: DateTime<Utc>) -> Result<EvaluationReport, RunbookError> {
+        let hooks: Vec<RunbookHook> = self
+            .list_hooks()?
+            .into_iter()
+            .filter(|h| h.enabled)
+            .collect();
+        let mut report = EvaluationReport {
+            evaluated_at: now,
+            hooks_evaluated: hooks.len(),
+            firings: Vec::new(),
+            suppressed: Vec::new(),
+        };
+
+        for mut hook in hooks {
+            let threshold = hook.threshold.unwrap_or(default_threshold(hook.trigger));
+            let detections = match hook.trigger {
+                RunbookTrigger::IndexerStalled => self.detect_stalled_indexers(threshold, now)?,
+                RunbookTrigger::WebhookDlqGrowing => {
+                    self.detect_dlq_growth(&mut hook, threshold)?
+                }
+                RunbookTrigger::AdapterUnhealthy => detect_unhealthy_adapters(threshold),
+            };
+            if detections.is_empty() {
+                if hook.trigger == RunbookTrigger::WebhookDlqGrowing {
+                    self.storage.store_runbook_hook(&hook)?;
+                }
+                continue;
+            }
+
+            let cooling_down = hook.last_fired_at.is_some_and(|fired| {
+                now < fired + Duration::minutes(i64::from(hook.cooldown_minutes))
+            });
+            if cooling_down {
+                report.suppressed.push(hook.hook_id);
+                continue;
+            }
+
+            for detection in detections {
+                let actions = hook
+                    .actions
+                    .iter()
+                    .map(|action| self.run_action(&hook, action, &detection, now))
+                    .collect();
+                report.firings.push(HookFiring {
+                    hook_id: hook.hook_id,
+                    detection,
+                    actions,
+                });
+            }
+            hook.last_fired_at = Some(now);
+            if hook.trigger == RunbookTrigger::WebhookDlqGrowing {
+                hook.dlq_baseline = Some(self.dead_lettered_deliveries()?);
+            }
+            self.storage.store_runbook_hook(&hook)?;
+        }
+        Ok(report)
+    }
+
+    fn detect_stalled_indexers(
+        &self,
+        threshold: f64,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Detection>, RunbookError> {
+        let mut detections = Vec::new();
+        for network in INDEXED_NETWORKS {
+            let Some(progress) = self.storage.get_indexing_progress(network)? else {
+                continue;
+            };
+            let stalled_minutes = (now - progress.last_indexed_at).num_seconds() as f64 / 60.0;
+            if stalled_minutes >= threshold {
+                detections.push(Detection {
+                    trigger: RunbookTrigger::IndexerStalled,
+                    subject: format!("indexer:{network}"),
+                    observed: stalled_minutes,
+                    threshold,
+                    adapter: None,
+                });
+            }
+        }
+        Ok(detections)
+    }
+
+    /// Webhook deliveries that exhausted their retries
+    fn dead_lettered_deliveries(&self) -> Result<usize, RunbookError> {
+        let mut count = 0;
+        for circuit in self.storage.list_circuits()? {
+            count += self
+                .storage
+                .get_webhook_deliveries_by_circuit(&circuit.circuit_id, None)?
+                .iter()
+                .filter(|d| d.status == DeliveryStatus::Failed)
+                .count();
+        }
+        Ok(count)
+    }
+
+    /// Growth is measured against a baseline kept on the hook: taken on first
+    /// evaluation, moved down when the queue is drained and reset on firing
+    fn detect_dlq_growth(
+        &self,
+        hook: &mut RunbookHook,
+        threshold: f64,
+    ) -> Result<Vec<Detection>, RunbookError> {
+        let current = self.dead_lettered_deliveries()?;
+        let baseline = match hook.dlq_baseline {
+            Some(baseline) if baseline <= current => baseline,
+            _ => current,
+        };
+        hook.dlq_baseline = Some(baseline);
+        let growth = (current - baseline) as f64;
+        if growth < threshold {
+            return Ok(Vec::new());
+        }
+        Ok(vec![Detection {
+            trigger: RunbookTrigger::WebhookDlqGrowing,
+            subject: "webhooks:dead_letter".to_string(),
+            observed: growth,
+            threshold,
+            adapter: None,
+        }])
+    }
+
+    fn run_action(
+        &self,
+        hook: &RunbookHook,
+        action: &RemediationAction,
+        detection: &Detection,
+        now: DateTime<Utc>,
+    ) -> ActionOutcome {
+        let actor = format!("runbook:{}", hook.hook_id);
+        let result = match action {
+            RemediationAction::RestartWorker { worker } => {
+                if restart_worker(worker) {
+                    Ok(format!("Restarted worker {worker}"))
+                } else {
+                    Err(format!("Worker {worker} is not running in this process"))
+                }
+            }
+            RemediationAction::SwitchAdapter { to } => {
+                self.switch_adapter(&actor, detection, to, now)
+            }
+            RemediationAction::OpenIncident { severity } => self
+                .audit
+                .create_security_incident(
+                    format!(
+                        "Runbook: {} on {}",
+                        trigger_name(detection.trigger),
+                        detection.subject
+                    ),
+                    format!(
+                        "Runbook hook {} detected {} on {} (observed {:.2}, threshold {:.2})",
+                        hook.hook_id,
+                        trigger_name(detection.trigger),
+                        detection.subject,
+                        detection.observed,
+                        detection.threshold
+                    ),
+                    severity.clone(),
+                    IncidentCategory::ServiceDegradation,
+                    Vec::new(),
+                    vec![detection.subject.clone()],
+                    Vec::new(),
+                )
+                .map(|incident_id| format!("Opened incident {incident_id}"))
+                .map_err(|e| e.to_string()),
+        };
+
+        let (success, detail) = match result {
+            Ok(detail) => (true, detail),
+            Err(detail) => (false, detail),
+        };
+        let details: HashMap<String, Value> = [
+            ("hook_id".to_string(), json!(hook.hook_id)),
+            ("trigger".to_string(), json!(detection.trigger)),
+            ("observed".to_string(), json!(detection.observed)),
+            ("threshold".to_string(), json!(detection.threshold)),
+            ("remediation".to_string(), json!(action)),
+            ("detail".to_string(), json!(detail)),
+        ]
+        .into_iter()
+        .collect();
+        let audit_event_id = self
+            .audit
+            .log_event(
+                actor,
+                AuditEventType::System,
+                format!("runbook.{}", action_name(action)),
+                detection.subject.clone(),
+                if success {
+                    AuditOutcome::Success
+                } else {
+                    AuditOutcome::Failure
+                },
+                if success {
+                    AuditSeverity::Medium
+                } else {
+                    AuditSeverity::High
+                },
+                Some(details),
+                None,
+                None,
+            )
+            .map_err(|e: AuditError| {
+                tracing::warn!("⚠️  Failed to audit runbook action: {}", e);
+            })
+            .ok();
+
+        ActionOutcome {
+            action: action.clone(),
+            success,
+            detail,
+            audit_event_id,
+        }
+    }
+
+    /// Point circuits writing through the unhealthy adapter at `to` and
+    /// start its metrics afresh, so it is judged on traffic after the switch
+    fn switch_adapter(
+        &self,
+        actor: &str,
+        detection: &Detection,
+        to: &AdapterType,
+        now: DateTime<Utc>,
+    ) -> Result<String, String> {
+        let Some(from) = &detection.adapter else {
+            return Err("No adapter to switch away from".to_string());
+        };
+        if from == to {
+            return Err(format!("{to} is the unhealthy adapter"));
+        }
+        let configs = self
+            .storage
+            .list_circuit_adapter_configs()
+            .map_err(|e| e.to_string())?;
+        let mut switched = 0;
+        for mut config in configs {
+            if config.adapter_type.as_ref() != Some(from) {
+                continue;
+            }
+            config.adapter_type = Some(to.clone());
+            config.configured_by = actor.to_string();
+            config.configured_at = now;
+            self.storage
+                .update_circuit_adapter_config(&config)
+                .map_err(|e| e.to_string())?;
+            switched += 1;
+        }
+        reset_adapter_metrics(from);
+        Ok(format!("Switched {switched} circuits from {from} to {to}"))
+    }
+
+    /// Evaluate hooks every `tick` on the blocking pool until the process exits
+    pub fn spawn_monitor(storage: S, tick: std::time::Duration) -> tokio::task::JoinHandle<()>
+    where
+        S: Send + Sync,
+    {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                let storage = storage.clone();
+                let run = tokio::task::spawn_blocking(move || {
+                    RunbookEngine::new(storage).evaluate(Utc::now())
+                })
+                .await;
+                match run {
+                    Ok(Ok(report)) => {
+                        for firing in &report.firings {
+                            tracing::warn!(
+                                "🛠️  Runbook hook {} fired on {} ({} actions)",
+                                firing.hook_id,
+                                firing.detection.subject,
+                                firing.actions.len()
+                            );
+                        }
+                    }
+                    Ok(Err(e)) => tracing::warn!("⚠️  Runbook evaluation failed: {}", e),
+                    Err(e) => tracing::warn!("⚠️  Runbook task failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+fn detect_unhealthy_adapters(threshold: f64) -> Vec<Detection> {
+    all_adapter_metrics()
+        .into_iter()
+        .filter(|m| m.total_calls() >= MIN_ADAPTER_CALLS && m.error_rate() >= threshold)
+        .map(|m| Detection {
+            trigger: RunbookTrigger::AdapterUnhealthy,
+            subject: format!("adapter:{}", m.adapter_type),
+            observed: m.error_rate(),
+            threshold,
+            adapter: Some(m.adapter_type),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_stalled_indexer_opens_incident_once_per_cooldown() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        storage
+            .update_indexing_progress("stellar-testnet", 100, 100)
+            .unwrap();
+        let engine = RunbookEngine::new(Arc::clone(&storage));
+        let hook = engine
+            .create_hook(
+                "admin",
+                HookInput {
+                    trigger: RunbookTrigger::IndexerStalled,
+                    actions: vec![RemediationAction::OpenIncident {
+                        severity: AuditSeverity::High,
+                    }],
+                    threshold: None,
+                    cooldown_minutes: Some(60),
+                    enabled: None,
+                },
+                Utc::now(),
+            )
+            .unwrap();
+
+        // Fresh progress is not a stall
+        let report = engine.evaluate(Utc::now()).unwrap();
+        assert!(report.firings.is_empty());
+
+        let stalled_at = Utc::now() + Duration::minutes(20);
+        let report = engine.evaluate(stalled_at).unwrap();
+        assert_eq!(report.firings.len(), 1);
+        let firing = &report.firings[0];
+        assert_eq!(firing.detection.subject, "indexer:stellar-testnet");
+        assert!(firing.actions[0].success);
+        assert_eq!(storage.list_security_incidents().unwrap().len(), 1);
+
+        let audit_event_id = firing.actions[0].audit_event_id.unwrap();
+        let event = storage.get_audit_event(&audit_event_id).unwrap().unwrap();
+        assert_eq!(event.action, "runbook.open_incident");
+        assert_eq!(event.user_id, format!("runbook:{}", hook.hook_id));
+
+        // Still stalled, but the hook is cooling down
+        let report = engine.evaluate(stalled_at + Duration::minutes(30)).unwrap();
+        assert!(report.firings.is_empty());
+        assert_eq!(report.suppressed, vec![hook.hook_id]);
+        assert_eq!(storage.list_security_incidents().unwrap().len(), 1);
+
+        let report = engine.evaluate(stalled_at + Duration::minutes(61)).unwrap();
+        assert_eq!(report.firings.len(), 1);
+    }
+}
@@ -0,0 +1,165 @@
+//! Process-wide queue depth and throughput counters used as autoscaling signals.
+//!
+//! Work is enqueued and processed by engines and workers that do not share an
+//! owner, so counters live in a registry keyed by queue, in the same way as
+//! adapter metrics.
+
+use crate::types::{QueueSignal, ScalingSignals, WorkQueue};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// Rates are computed over this trailing window
+pub const SIGNAL_WINDOW_SECS: u64 = 60;
+
+#[derive(Debug, Default)]
+struct QueueCounters {
+    enqueued: u64,
+    completed: u64,
+    failed: u64,
+    arrivals: VecDeque<DateTime<Utc>>,
+    /// Completion time and latency of recently finished jobs
+    completions: VecDeque<(DateTime<Utc>, u64)>,
+}
+
+impl QueueCounters {
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - chrono::Duration::seconds(SIGNAL_WINDOW_SECS as i64);
+        while self.arrivals.front().is_some_and(|at| *at < cutoff) {
+            self.arrivals.pop_front();
+        }
+        while self.completions.front().is_some_and(|(at, _)| *at < cutoff) {
+            self.completions.pop_front();
+        }
+    }
+
+    fn signal(&self, queue: WorkQueue) -> QueueSignal {
+        let depth = self.enqueued.saturating_sub(self.completed);
+        let window = SIGNAL_WINDOW_SECS as f64;
+        let processing_rate = self.completions.len() as f64 / window;
+        let average_latency_ms = if self.completions.is_empty() {
+            0.0
+        } else {
+            self.completions
+                .iter()
+                .map(|(_, ms)| *ms as f64)
+                .sum::<f64>()
+                / self.completions.len() as f64
+        };
+
+        QueueSignal {
+            queue,
+            depth,
+            enqueued_total: self.enqueued,
+            completed_total: self.completed,
+            failed_total: self.failed,
+            processing_rate,
+            arrival_rate: self.arrivals.len() as f64 / window,
+            average_latency_ms,
+            estimated_drain_seconds: if depth == 0 {
+                Some(0.0)
+            } else if processing_rate > 0.0 {
+                Some(depth as f64 / processing_rate)
+            } else {
+                None
+            },
+        }
+    }
+}
+
+fn registry() -> &'static Mutex<HashMap<WorkQueue, QueueCounters>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<WorkQueue, QueueCounters>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Record jobs added to a queue
+pub fn record_enqueued(queue: WorkQueue, count: u64) {
+    let now = Utc::now();
+    let mut queues = registry().lock().unwrap();
+    let counters = queues.entry(queue).or_default();
+    counters.enqueued += count;
+    counters
+        .arrivals
+        .extend(std::iter::repeat_n(now, count as usize));
+    counters.prune(now);
+}
+
+/// Record one job leaving a queue, whether it succeeded or failed
+pub fn record_completed(queue: WorkQueue, latency: Duration, success: bool) {
+    let now = Utc::now();
+    let mut queues = registry().lock().unwrap();
+    let counters = queues.entry(queue).or_default();
+    counters.completed += 1;
+    if !success {
+        counters.failed += 1;
+    }
+    counters
+        .completions
+        .push_back((now, latency.as_millis() as u64));
+    counters.prune(now);
+}
+
+pub fn queue_signal(queue: WorkQueue) -> QueueSignal {
+    let now = Utc::now();
+    let mut queues = registry().lock().unwrap();
+    let counters = queues.entry(queue).or_default();
+    counters.prune(now);
+    counters.signal(queue)
+}
+
+/// Current pressure on every work queue
+pub fn scaling_signals() -> ScalingSignals {
+    let queues: Vec<QueueSignal> = WorkQueue::ALL.into_iter().map(queue_signal).collect();
+    let total_depth = queues.iter().map(|q| q.depth).sum();
+    let max_drain_seconds = queues
+        .iter()
+        .map(|q| q.estimated_drain_seconds)
+        .try_fold(0.0_f64, |max, drain| drain.map(|d| max.max(d)));
+
+    ScalingSignals {
+        generated_at: Utc::now(),
+        window_seconds: SIGNAL_WINDOW_SECS,
+        queues,
+        total_depth,
+        max_drain_seconds,
+    }
+}
+
+pub fn reset_queue(queue: WorkQueue) {
+    registry().lock().unwrap().remove(&queue);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depth_and_rates() {
+        // Registry is process-wide; this is the only test touching this queue
+        let queue = WorkQueue::WebhookDelivery;
+        reset_queue(queue);
+
+        record_enqueued(queue, 3);
+        record_completed(queue, Duration::from_millis(40), true);
+        record_completed(queue, Duration::from_millis(20), false);
+
+        let signal = queue_signal(queue);
+        assert_eq!(signal.depth, 1);
+        assert_eq!(signal.enqueued_total, 3);
+        assert_eq!(signal.failed_total, 1);
+        assert_eq!(signal.average_latency_ms, 30.0);
+        assert_eq!(signal.processing_rate, 2.0 / SIGNAL_WINDOW_SECS as f64);
+        assert_eq!(
+            signal.estimated_drain_seconds,
+            Some(SIGNAL_WINDOW_SECS as f64 / 2.0)
+        );
+
+        let signals = scaling_signals();
+        assert_eq!(signals.queues.len(), WorkQueue::ALL.len());
+
+        reset_queue(queue);
+        assert_eq!(queue_signal(queue).depth, 0);
+        assert_eq!(queue_signal(queue).estimated_drain_seconds, Some(0.0));
+    }
+}
@@ -0,0 +1,396 @@
+//! Inverted index behind `GET /api/items/search` for the in-memory backend.
+//!
+//! Postings are kept per enriched_data token, identifier key/value and status, plus
+//! a creation-ordered set, so a search intersects the postings of its criteria
+//! instead of scanning every item. Postgres-backed storage answers the same
+//! [`ItemSearchQuery`] from the `search_vector` tsvector column (migration V11).
+
+use crate::pagination::PageCursor;
+use crate::types::{Item, ItemSearchQuery, ItemStatus};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::Bound;
+
+/// Lowercased alphanumeric words, the same split Postgres' `simple` parser makes
+/// for ordinary text
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Words of enriched_data keys and scalar values, nested ones included
+fn value_tokens(value: &serde_json::Value, tokens: &mut HashSet<String>) {
+    match value {
+        serde_json::Value::String(s) => tokens.extend(tokenize(s)),
+        serde_json::Value::Number(n) => tokens.extend(tokenize(&n.to_string())),
+        serde_json::Value::Bool(b) => {
+            tokens.insert(b.to_string());
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                value_tokens(value, tokens);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                tokens.extend(tokenize(key));
+                value_tokens(value, tokens);
+            }
+        }
+        serde_json::Value::Null => {}
+    }
+}
+
+pub fn status_key(status: &ItemStatus) -> &'static str {
+    match status {
+        ItemStatus::Active => "active",
+        ItemStatus::Deprecated => "deprecated",
+        ItemStatus::Merged => "merged",
+        ItemStatus::Split => "split",
+        ItemStatus::MergedInto(_) => "merged_into",
+    }
+}
+
+/// What was indexed for an item, so it can be unindexed on update
+#[derive(Debug, Clone)]
+struct IndexedItem {
+    tokens: HashSet<String>,
+    identifiers: Vec<(String, String)>,
+    status: &'static str,
+    created_at: DateTime<Utc>,
+    occurred_span: (DateTime<Utc>, DateTime<Utc>),
+}
+
+#[derive(Debug, Default)]
+pub struct ItemSearchIndex {
+    tokens: BTreeMap<String, HashSet<String>>,
+    identifier_values: HashMap<(String, String), HashSet<String>>,
+    identifier_keys: HashMap<String, HashSet<String>>,
+    statuses: HashMap<&'static str, HashSet<String>>,
+    by_created: BTreeSet<(DateTime<Utc>, String)>,
+    indexed: HashMap<String, IndexedItem>,
+}
+
+fn unlink<K: std::hash::Hash + Eq>(postings: &mut HashMap<K, HashSet<String>>, key: K, dfid: &str) {
+    if let Some(dfids) = postings.get_mut(&key) {
+        dfids.remove(dfid);
+        if dfids.is_empty() {
+            postings.remove(&key);
+        }
+    }
+}
+
+impl ItemSearchIndex {
+    pub fn len(&self) -> usize {
+        self.indexed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indexed.is_empty()
+    }
+
+    /// Index an item, replacing what was indexed for its DFID before
+    pub fn index(&mut self, item: &Item) {
+        self.remove(&item.dfid);
+
+        let mut tokens = HashSet::new();
+        for (key, value) in &item.enriched_data {
+            tokens.extend(tokenize(key));
+            value_tokens(value, &mut tokens);
+        }
+        let identifiers: Vec<(String, String)> = item
+            .identifiers
+            .iter()
+            .map(|id| (id.key.clone(), id.value.clone()))
+            .collect();
+        let entry = IndexedItem {
+            status: status_key(&item.status),
+            created_at: item.creation_timestamp,
+            occurred_span: (
+                item.first_occurred_at.unwrap_or(item.creation_timestamp),
+                item.last_occurred_at.unwrap_or(item.creation_timestamp),
+            ),
+            tokens,
+            identifiers,
+        };
+
+        let dfid = &item.dfid;
+        for token in &entry.tokens {
+            self.tokens
+                .entry(token.clone())
+                .or_default()
+                .insert(dfid.clone());
+        }
+        for (key, value) in &entry.identifiers {
+            self.identifier_values
+                .entry((key.clone(), value.clone()))
+                .or_default()
+                .insert(dfid.clone());
+            self.identifier_keys
+                .entry(key.clone())
+                .or_default()
+                .insert(dfid.clone());
+        }
+        self.statuses
+            .entry(entry.status)
+            .or_default()
+            .insert(dfid.clone());
+        self.by_created.insert((entry.created_at, dfid.clone()));
+        self.indexed.insert(dfid.clone(), entry);
+    }
+
+    pub fn remove(&mut self, dfid: &str) {
+        let Some(entry) = self.indexed.remove(dfid) else {
+            return;
+        };
+        for token in entry.tokens {
+            if let Some(dfids) = self.tokens.get_mut(&token) {
+                dfids.remove(dfid);
+                if dfids.is_empty() {
+                    self.tokens.remove(&token);
+                }
+            }
+        }
+        for (key, value) in entry.identifiers {
+            unlink(&mut self.identifier_values, (key.clone(), value), dfid);
+            unlink(&mut self.identifier_keys, key, dfid);
+        }
+        unlink(&mut self.statuses, entry.status, dfid);
+        self.by_created
+            .remove(&(entry.created_at, dfid.to_string()));
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// DFIDs having a token starting with `prefix`
+    fn prefix_postings(&self, prefix: &str) -> HashSet<String> {
+        self.tokens
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(|(token, _)| token.starts_with(prefix))
+            .flat_map(|(_, dfids)| dfids.iter().cloned())
+            .collect()
+    }
+
+    /// Matching DFIDs after `after` in (created, dfid) order. `circuit_dfids` scopes
+    /// the search to a circuit's items.
+    pub fn search(
+        &self,
+        query: &ItemSearchQuery,
+        circuit_dfids: Option<HashSet<String>>,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Vec<String> {
+        let mut candidates = circuit_dfids;
+        let mut narrow = |postings: HashSet<String>| {
+            candidates = Some(match candidates.take() {
+                Some(current) => current.intersection(&postings).cloned().collect(),
+                None => postings,
+            });
+        };
+
+        if let Some(text) = &query.text {
+            for word in tokenize(text) {
+                narrow(self.prefix_postings(&word));
+            }
+        }
+        for filter in &query.identifiers {
+            let postings = match &filter.value {
+                Some(value) => self
+                    .identifier_values
+                    .get(&(filter.key.clone(), value.clone())),
+                None => self.identifier_keys.get(&filter.key),
+            };
+            narrow(postings.cloned().unwrap_or_default());
+        }
+        if let Some(status) = &query.status {
+            narrow(
+                self.statuses
+                    .get(status_key(status))
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+        }
+
+        let in_range = |created_at: &DateTime<Utc>, dfid: &str| {
+            after.is_none_or(|cursor| cursor.precedes(*created_at, dfid))
+                && query.created_from.is_none_or(|from| *created_at >= from)
+                && query.created_to.is_none_or(|to| *created_at <= to)
+        };
+        let occurred = |dfid: &str| {
+            let Some(entry) = self.indexed.get(dfid) else {
+                return false;
+            };
+            let (start, end) = entry.occurred_span;
+            query.occurred_from.is_none_or(|from| end >= from)
+                && query.occurred_to.is_none_or(|to| start <= to)
+        };
+
+        match candidates {
+            Some(dfids) => {
+                let mut matches: Vec<(DateTime<Utc>, String)> = dfids
+                    .into_iter()
+                    .filter_map(|dfid| {
+                        let created_at = self.indexed.get(&dfid)?.created_at;
+                        (in_range(&created_at, &dfid) && occurred(&dfid))
+                            .then_some((created_at, dfid))
+                    })
+                    .collect();
+                matches.sort();
+                matches
+                    .into_iter()
+                    .take(limit)
+                    .map(|(_, dfid)| dfid)
+                    .collect()
+            }
+            None => self
+                .by_created
+                .iter()
+                .filter(|(created_at, dfid)| in_range(created_at, dfid) && occurred(dfid))
+                .take(limit)
+                .map(|(_, dfid)| dfid.clone())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Identifier, IdentifierFilter, ItemSummary};
+    use chrono::Duration;
+
+    fn item(dfid: &str, minutes: i64, data: serde_json::Value, id: (&str, &str)) -> Item {
+        let mut item = Item::new(
+            dfid.to_string(),
+            vec![Identifier::contextual("bovino", id.0, id.1)],
+            uuid::Uuid::new_v4(),
+        );
+        item.creation_timestamp = Utc::now() - Duration::minutes(60 - minutes);
+        item.enriched_data = serde_json::from_value(data).unwrap();
+        item
+    }
+
+    #[test]
+    fn test_search_intersects_text_identifier_and_status() {
+        let mut index = ItemSearchIndex::default();
+        index.index(&item(
+            "DFID-1",
+            1,
+            serde_json::json!({"breed": "Nelore", "farm": {"name": "Santa Rita"}}),
+            ("sisbov", "BR001"),
+        ));
+        index.index(&item(
+            "DFID-2",
+            2,
+            serde_json::json!({"breed": "Angus", "farm": {"name": "Santa Fe"}}),
+            ("sisbov", "BR002"),
+        ));
+        let mut deprecated = item(
+            "DFID-3",
+            3,
+            serde_json::json!({"breed": "Nelore"}),
+            ("ear_tag", "77"),
+        );
+        deprecated.status = ItemStatus::Deprecated;
+        index.index(&deprecated);
+
+        let text = |text: &str| ItemSearchQuery {
+            text: Some(text.to_string()),
+            ..ItemSearchQuery::default()
+        };
+        assert_eq!(
+            index.search(&text("nel"), None, None, 10),
+            vec!["DFID-1", "DFID-3"]
+        );
+        assert_eq!(
+            index.search(&text("santa RITA"), None, None, 10),
+            vec!["DFID-1"]
+        );
+
+        let query = ItemSearchQuery {
+            text: Some("nelore".to_string()),
+            status: Some(ItemStatus::Active),
+            ..ItemSearchQuery::default()
+        };
+        assert_eq!(index.search(&query, None, None, 10), vec!["DFID-1"]);
+
+        let query = ItemSearchQuery {
+            identifiers: vec![IdentifierFilter {
+                key: "sisbov".to_string(),
+                value: None,
+            }],
+            ..ItemSearchQuery::default()
+        };
+        let first = index.search(&query, None, None, 1);
+        assert_eq!(first, vec!["DFID-1"]);
+        let cursor = PageCursor::new(index.indexed["DFID-1"].created_at, "DFID-1");
+        assert_eq!(
+            index.search(&query, None, Some(&cursor), 10),
+            vec!["DFID-2"]
+        );
+
+        // Re-indexing drops stale postings
+        let mut renamed = item(
+            "DFID-1",
+            1,
+            serde_json::json!({"breed": "Brahman"}),
+            ("sisbov", "BR001"),
+        );
+        renamed.status = ItemStatus::Active;
+        index.index(&renamed);
+        assert_eq!(
+            index.search(&text("nelore"), None, None, 10),
+            vec!["DFID-3"]
+        );
+        let scoped: HashSet<String> = ["DFID-2".to_string()].into_iter().collect();
+        assert_eq!(
+            index.search(&text("santa"), Some(scoped), None, 10),
+            vec!["DFID-2"]
+        );
+        assert_eq!(index.len(), 3);
+    }
+
+    #[test]
+    fn test_summary_pages_match_full_pages() {
+        use crate::items_engine::ItemsEngine;
+        use crate::storage::InMemoryStorage;
+        use std::sync::{Arc, Mutex};
+
+        let storage = InMemoryStorage::new();
+        for n in 1..=3 {
+            storage.seed_item(item(
+                &format!("DFID-{n}"),
+                n,
+                serde_json::json!({"breed": "Nelore"}),
+                ("sisbov", &format!("BR00{n}")),
+            ));
+        }
+        let engine = ItemsEngine::new(Arc::new(Mutex::new(storage)));
+        let query = ItemSearchQuery {
+            text: Some("nelore".to_string()),
+            ..ItemSearchQuery::default()
+        };
+
+        let full = engine.search_items(&query, None, 2).unwrap();
+        let summaries = engine.search_item_summaries(&query, None, 2).unwrap();
+        assert_eq!(
+            summaries.items,
+            full.items.iter().map(ItemSummary::from).collect::<Vec<_>>()
+        );
+        assert_eq!(summaries.next_cursor, full.next_cursor);
+        let rest = engine
+            .search_item_summaries(
+                &query,
+                Some(PageCursor::decode(summaries.next_cursor.as_deref().unwrap()).unwrap()),
+                2,
+            )
+            .unwrap();
+        assert_eq!(rest.items.len(), 1);
+        assert_eq!(rest.items[0].dfid, "DFID-3");
+        assert!(rest.next_cursor.is_none());
+    }
+}
@@ -0,0 +1,252 @@
+//! Daily summary events from sensor time series.
+//!
+//! Raw readings stay in the time-series store; what enters an item's event
+//! history is one Updated event per series and day carrying the day's reading
+//! count, min, max and average. A day is summarized once it has ended and
+//! `SUMMARY_GRACE` has passed for late readings; readings arriving after that
+//! are kept and rolled up but do not change the published summary.
+
+use crate::events_engine::EventsEngine;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    Event, EventType, EventVisibility, SensorResolution, SensorSeries, MIN_OCCURRED_AT_YEAR,
+};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Wait after a day ends before summarizing it
+pub const SUMMARY_GRACE: Duration = Duration::hours(1);
+const SUMMARY_RESOLUTION: SensorResolution = SensorResolution::Day;
+/// Source recorded on summary events
+const SUMMARY_SOURCE: &str = "sensor_summary";
+
+#[derive(Debug)]
+pub enum SensorSummaryError {
+    StorageError(StorageError),
+    EventError(String),
+}
+
+impl From<StorageError> for SensorSummaryError {
+    fn from(err: StorageError) -> Self {
+        SensorSummaryError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for SensorSummaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SensorSummaryError::StorageError(e) => write!(f, "Storage error: {e}"),
+            SensorSummaryError::EventError(e) => write!(f, "Event error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SensorSummaryError {}
+
+/// Outcome of one pass over every series
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SensorSummaryRun {
+    pub series_checked: usize,
+    pub summaries_created: usize,
+    /// Series whose summaries failed, as `dfid/series: error`
+    pub failures: Vec<String>,
+}
+
+pub struct SensorSummaryEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend + Clone + 'static> SensorSummaryEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Summarize every closed day of every series not summarized yet
+    pub fn summarize_due(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<SensorSummaryRun, SensorSummaryError> {
+        let mut run = SensorSummaryRun::default();
+        for series in self.storage.list_all_sensor_series()? {
+            run.series_checked += 1;
+            match self.summarize_series(&series.dfid, &series.series, now) {
+                Ok(events) => run.summaries_created += events.len(),
+                Err(e) => run
+                    .failures
+                    .push(format!("{}/{}: {e}", series.dfid, series.series)),
+            }
+        }
+        Ok(run)
+    }
+
+    /// Record a summary event for each closed, unsummarized day of one series
+    /// that had readings, oldest first
+    pub fn summarize_series(
+        &self,
+        dfid: &str,
+        series_name: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Event>, SensorSummaryError> {
+        let Some(series) = self.storage.get_sensor_series(dfid, series_name)? else {
+            return Ok(Vec::new());
+        };
+        let period = SUMMARY_RESOLUTION.duration();
+        // Start of the latest day whose grace period is over
+        let last_closed = SUMMARY_RESOLUTION.bucket_start(now - SUMMARY_GRACE) - period;
+        // Readings cannot predate MIN_OCCURRED_AT_YEAR
+        let first = series.summarized_through.unwrap_or_else(|| {
+            Utc.with_ymd_and_hms(MIN_OCCURRED_AT_YEAR, 1, 1, 0, 0, 0)
+                .single()
+                .unwrap_or(DateTime::<Utc>::UNIX_EPOCH)
+        });
+        if first > last_closed {
+            return Ok(Vec::new());
+        }
+
+        let rollups = self.storage.get_sensor_rollups(
+            dfid,
+            series_name,
+            SUMMARY_RESOLUTION,
+            first,
+            last_closed,
+        )?;
+        let mut events_engine = EventsEngine::new(self.storage.clone());
+        let mut events = Vec::new();
+        for rollup in rollups.iter().filter(|rollup| rollup.count > 0) {
+            let period_end = rollup.bucket_start + period;
+            let mut metadata = HashMap::new();
+            metadata.insert(
+                "sensor_series".to_string(),
+                serde_json::json!(series.series),
+            );
+            metadata.insert("summary_period".to_string(), serde_json::json!("day"));
+            metadata.insert(
+                "period_start".to_string(),
+                serde_json::json!(rollup.bucket_start.to_rfc3339()),
+            );
+            metadata.insert(
+                "period_end".to_string(),
+                serde_json::json!(period_end.to_rfc3339()),
+            );
+            metadata.insert("count".to_string(), serde_json::json!(rollup.count));
+            metadata.insert("min".to_string(), serde_json::json!(rollup.min));
+            metadata.insert("max".to_string(), serde_json::json!(rollup.max));
+            metadata.insert("avg".to_string(), serde_json::json!(rollup.avg()));
+            metadata.insert("unit".to_string(), serde_json::json!(series.unit));
+
+            let created = events_engine
+                .create_event_with_occurred_at(
+                    dfid.to_string(),
+                    EventType::Updated,
+                    SUMMARY_SOURCE.to_string(),
+                    EventVisibility::Private,
+                    metadata,
+                    Some(rollup.bucket_start),
+                )
+                .map_err(|e| SensorSummaryError::EventError(e.to_string()))?;
+            events.push(created.event);
+        }
+
+        // Re-read so readings ingested meanwhile are not overwritten
+        let mut series: SensorSeries = self
+            .storage
+            .get_sensor_series(dfid, series_name)?
+            .unwrap_or(series);
+        series.summarized_through = Some(last_closed + period);
+        self.storage.store_sensor_series(&series)?;
+        Ok(events)
+    }
+
+    /// Summarize closed days every `tick` (normally an hour)
+    pub fn spawn_summarizer(storage: S, tick: std::time::Duration) -> tokio::task::JoinHandle<()>
+    where
+        S: Send + Sync,
+    {
+        tokio::spawn(async move {
+            let engine = SensorSummaryEngine::new(storage);
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                match engine.summarize_due(Utc::now()) {
+                    Ok(run) => {
+                        if run.summaries_created > 0 {
+                            tracing::info!(
+                                "📊 Created {} sensor summary event(s)",
+                                run.summaries_created
+                            );
+                        }
+                        for failure in run.failures {
+                            tracing::warn!("⚠️  Sensor summary failed for {}", failure);
+                        }
+                    }
+                    Err(e) => tracing::warn!("⚠️  Sensor summary pass failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sensor_timeseries_engine::{SensorIngestInput, SensorTimeseriesEngine};
+    use crate::storage::InMemoryStorage;
+    use crate::types::{Item, SensorReading};
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_closed_days_are_summarized_once() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        storage
+            .store_item(&Item::new("DFID-SILO".to_string(), vec![], Uuid::new_v4()))
+            .unwrap();
+        let day_one = Utc.with_ymd_and_hms(2026, 5, 1, 0, 0, 0).unwrap();
+        let now = day_one + Duration::days(2) + Duration::minutes(30);
+
+        // Readings every ten minutes over two and a half days
+        let readings = (0..360)
+            .map(|i| SensorReading {
+                timestamp: day_one + Duration::minutes(10 * i),
+                value: if i < 144 { 20.0 } else { 24.0 },
+            })
+            .collect();
+        SensorTimeseriesEngine::new(Arc::clone(&storage))
+            .ingest(
+                "gateway-1",
+                "DFID-SILO",
+                "temperature",
+                SensorIngestInput {
+                    unit: Some("celsius".to_string()),
+                    readings,
+                },
+                day_one + Duration::days(3),
+            )
+            .unwrap();
+
+        // The second day is still within its grace period
+        let engine = SensorSummaryEngine::new(Arc::clone(&storage));
+        let run = engine.summarize_due(now).unwrap();
+        assert_eq!((run.series_checked, run.summaries_created), (1, 1));
+
+        let run = engine
+            .summarize_due(now + SUMMARY_GRACE + Duration::hours(12))
+            .unwrap();
+        assert_eq!(run.summaries_created, 1);
+        assert_eq!(
+            engine
+                .summarize_due(now + Duration::hours(20))
+                .unwrap()
+                .summaries_created,
+            0
+        );
+
+        let mut events = storage.get_events_by_dfid("DFID-SILO").unwrap();
+        events.sort_by_key(|event| event.occurred_at);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].metadata["avg"], serde_json::json!(20.0));
+        assert_eq!(events[1].metadata["count"], serde_json::json!(144));
+        assert_eq!(events[1].metadata["max"], serde_json::json!(24.0));
+    }
+}
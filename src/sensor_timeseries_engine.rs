@@ -0,0 +1,651 @@
+//! Time-series store for high-frequency sensor readings.
+//!
+//! Readings do not become events: they are kept per item and series in hourly
+//! chunks, and every accepted reading is folded into per-minute, per-hour and
+//! per-day rollups so long ranges are read without touching raw data. Only a
+//! reading that moves a series across one of its thresholds (or back within
+//! them) is recorded as a StatusChanged event on the item.
+
+use crate::events_engine::EventsEngine;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    validate_occurred_at, EventType, EventVisibility, SensorChunk, SensorReading, SensorResolution,
+    SensorRollup, SensorSeries, SensorThresholdState,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Readings accepted in one ingestion call
+pub const MAX_READINGS_PER_BATCH: usize = 10_000;
+/// Raw readings one range query may return; longer ranges ask for a rollup
+pub const MAX_RAW_QUERY_READINGS: usize = 10_000;
+pub const MAX_SERIES_NAME_LEN: usize = 64;
+/// Raw readings are stored in blocks of this resolution
+const CHUNK_RESOLUTION: SensorResolution = SensorResolution::Hour;
+/// Source recorded on threshold crossing events
+const SENSOR_SOURCE: &str = "sensor_timeseries";
+
+#[derive(Debug)]
+pub enum SensorError {
+    StorageError(StorageError),
+    ValidationError(String),
+    NotFound(String),
+    EventError(String),
+}
+
+impl From<StorageError> for SensorError {
+    fn from(err: StorageError) -> Self {
+        SensorError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for SensorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SensorError::StorageError(e) => write!(f, "Storage error: {e}"),
+            SensorError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            SensorError::NotFound(e) => write!(f, "Not found: {e}"),
+            SensorError::EventError(e) => write!(f, "Event error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SensorError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SensorIngestInput {
+    /// Sets the series' unit if given
+    pub unit: Option<String>,
+    pub readings: Vec<SensorReading>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SensorThresholdInput {
+    pub upper_threshold: Option<f64>,
+    pub lower_threshold: Option<f64>,
+    pub unit: Option<String>,
+}
+
+/// A reading that moved its series across a threshold
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ThresholdCrossing {
+    pub reading: SensorReading,
+    pub from: SensorThresholdState,
+    pub to: SensorThresholdState,
+    pub event_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SensorIngestReport {
+    pub series: SensorSeries,
+    pub accepted: usize,
+    /// Readings whose timestamp the series already had
+    pub duplicates: usize,
+    pub crossings: Vec<ThresholdCrossing>,
+}
+
+/// Readings of a range, raw or rolled up
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "resolution", content = "points", rename_all = "snake_case")]
+pub enum SensorRange {
+    Raw(Vec<SensorReading>),
+    Rollup(Vec<SensorRollup>),
+}
+
+pub struct SensorTimeseriesEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend + Clone + 'static> SensorTimeseriesEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Store a batch of readings of one series. Readings at a timestamp the
+    /// series already has are skipped, so gateways can safely resend. Readings
+    /// older than the series' latest are stored and rolled up but do not move
+    /// its threshold state.
+    pub fn ingest(
+        &self,
+        source: &str,
+        dfid: &str,
+        series_name: &str,
+        input: SensorIngestInput,
+        now: DateTime<Utc>,
+    ) -> Result<SensorIngestReport, SensorError> {
+        validate_series_name(series_name)?;
+        if input.readings.is_empty() {
+            return Err(SensorError::ValidationError(
+                "At least one reading is required".to_string(),
+            ));
+        }
+        if input.readings.len() > MAX_READINGS_PER_BATCH {
+            return Err(SensorError::ValidationError(format!(
+                "At most {MAX_READINGS_PER_BATCH} readings are accepted per batch"
+            )));
+        }
+        for reading in &input.readings {
+            if !reading.value.is_finite() {
+                return Err(SensorError::ValidationError(format!(
+                    "Reading at {} is not a finite number",
+                    reading.timestamp.to_rfc3339()
+                )));
+            }
+            validate_occurred_at(reading.timestamp, now).map_err(SensorError::ValidationError)?;
+        }
+
+        let mut series = match self.storage.get_sensor_series(dfid, series_name)? {
+            Some(series) => series,
+            None => self.new_series(dfid, series_name, now)?,
+        };
+        if input.unit.is_some() {
+            series.unit = input.unit;
+        }
+
+        let mut readings = input.readings;
+        readings.sort_by_key(|reading| reading.timestamp);
+        let total = readings.len();
+        let accepted = self.store_readings(dfid, &series.series, readings)?;
+        self.roll_up(dfid, &series.series, &accepted)?;
+
+        let crossings = self.track_thresholds(source, &mut series, &accepted)?;
+        series.reading_count += accepted.len() as u64;
+        series.updated_at = now;
+        self.storage.store_sensor_series(&series)?;
+
+        Ok(SensorIngestReport {
+            accepted: accepted.len(),
+            duplicates: total - accepted.len(),
+            series,
+            crossings,
+        })
+    }
+
+    /// Set the thresholds whose crossings become events; `None` clears one
+    pub fn set_thresholds(
+        &self,
+        dfid: &str,
+        series_name: &str,
+        input: SensorThresholdInput,
+        now: DateTime<Utc>,
+    ) -> Result<SensorSeries, SensorError> {
+        validate_series_name(series_name)?;
+        if let (Some(lower), Some(upper)) = (input.lower_threshold, input.upper_threshold) {
+            if lower >= upper {
+                return Err(SensorError::ValidationError(
+                    "lower_threshold must be below upper_threshold".to_string(),
+                ));
+            }
+        }
+        if [input.lower_threshold, input.upper_threshold]
+            .iter()
+            .flatten()
+            .any(|threshold| !threshold.is_finite())
+        {
+            return Err(SensorError::ValidationError(
+                "Thresholds must be finite numbers".to_string(),
+            ));
+        }
+
+        let mut series = match self.storage.get_sensor_series(dfid, series_name)? {
+            Some(series) => series,
+            None => self.new_series(dfid, series_name, now)?,
+        };
+        series.upper_threshold = input.upper_threshold;
+        series.lower_threshold = input.lower_threshold;
+        if input.unit.is_some() {
+            series.unit = input.unit;
+        }
+        // The next reading is judged against the new thresholds from scratch
+        series.state = series
+            .last_reading
+            .map(|reading| classify(&series, reading.value))
+            .unwrap_or_default();
+        series.updated_at = now;
+        self.storage.store_sensor_series(&series)?;
+        Ok(series)
+    }
+
+    pub fn list_series(&self, dfid: &str) -> Result<Vec<SensorSeries>, SensorError> {
+        Ok(self.storage.list_sensor_series(dfid)?)
+    }
+
+    /// Readings of `[from, to]`: raw if no resolution is asked for, else the
+    /// rollups whose buckets start in the range
+    pub fn query(
+        &self,
+        dfid: &str,
+        series_name: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        resolution: Option<SensorResolution>,
+    ) -> Result<SensorRange, SensorError> {
+        if from > to {
+            return Err(SensorError::ValidationError(
+                "from must not be after to".to_string(),
+            ));
+        }
+        if self.storage.get_sensor_series(dfid, series_name)?.is_none() {
+            return Err(SensorError::NotFound(format!(
+                "Series {series_name} of {dfid}"
+            )));
+        }
+
+        if let Some(resolution) = resolution {
+            let rollups = self.storage.get_sensor_rollups(
+                dfid,
+                series_name,
+                resolution,
+                resolution.bucket_start(from),
+                to,
+            )?;
+            return Ok(SensorRange::Rollup(rollups));
+        }
+
+        let mut readings = Vec::new();
+        for chunk in self.storage.get_sensor_chunks(
+            dfid,
+            series_name,
+            CHUNK_RESOLUTION.bucket_start(from),
+            to,
+        )? {
+            readings.extend(
+                chunk
+                    .readings
+                    .into_iter()
+                    .filter(|reading| reading.timestamp >= from && reading.timestamp <= to),
+            );
+            if readings.len() > MAX_RAW_QUERY_READINGS {
+                return Err(SensorError::ValidationError(format!(
+                    "More than {MAX_RAW_QUERY_READINGS} readings in range; ask for a resolution"
+                )));
+            }
+        }
+        Ok(SensorRange::Raw(readings))
+    }
+
+    fn new_series(
+        &self,
+        dfid: &str,
+        series_name: &str,
+        now: DateTime<Utc>,
+    ) -> Result<SensorSeries, SensorError> {
+        if self.storage.get_item_by_dfid(dfid)?.is_none() {
+            return Err(SensorError::NotFound(format!("Item {dfid}")));
+        }
+        Ok(SensorSeries {
+            dfid: dfid.to_string(),
+            series: series_name.to_string(),
+            unit: None,
+            upper_threshold: None,
+            lower_threshold: None,
+            state: SensorThresholdState::Normal,
+            last_reading: None,
+            reading_count: 0,
+            summarized_through: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Merge sorted readings into their chunks; returns the ones not already stored
+    fn store_readings(
+        &self,
+        dfid: &str,
+        series_name: &str,
+        readings: Vec<SensorReading>,
+    ) -> Result<Vec<SensorReading>, SensorError> {
+        let mut by_chunk: BTreeMap<DateTime<Utc>, Vec<SensorReading>> = BTreeMap::new();
+        for reading in readings {
+            by_chunk
+                .entry(CHUNK_RESOLUTION.bucket_start(reading.timestamp))
+                .or_default()
+                .push(reading);
+        }
+
+        let mut accepted = Vec::new();
+        for (chunk_start, incoming) in by_chunk {
+            let mut chunk = self
+                .storage
+                .get_sensor_chunks(dfid, series_name, chunk_start, chunk_start)?
+                .into_iter()
+                .next()
+                .unwrap_or_else(|| SensorChunk {
+                    dfid: dfid.to_string(),
+                    series: series_name.to_string(),
+                    chunk_start,
+                    readings: Vec::new(),
+                });
+
+            let before = accepted.len();
+            for reading in incoming {
+                if let Err(at) = chunk
+                    .readings
+                    .binary_search_by_key(&reading.timestamp, |r| r.timestamp)
+                {
+                    chunk.readings.insert(at, reading);
+                    accepted.push(reading);
+                }
+            }
+            if accepted.len() > before {
+                self.storage.store_sensor_chunk(&chunk)?;
+            }
+        }
+        accepted.sort_by_key(|reading| reading.timestamp);
+        Ok(accepted)
+    }
+
+    fn roll_up(
+        &self,
+        dfid: &str,
+        series_name: &str,
+        readings: &[SensorReading],
+    ) -> Result<(), SensorError> {
+        for resolution in SensorResolution::ALL {
+            let mut buckets: HashMap<DateTime<Utc>, Vec<f64>> = HashMap::new();
+            for reading in readings {
+                buckets
+                    .entry(resolution.bucket_start(reading.timestamp))
+                    .or_default()
+                    .push(reading.value);
+            }
+
+            for (bucket_start, values) in buckets {
+                let mut rollup = self
+                    .storage
+                    .get_sensor_rollups(dfid, series_name, resolution, bucket_start, bucket_start)?
+                    .into_iter()
+                    .next()
+                    .unwrap_or_else(|| SensorRollup {
+                        dfid: dfid.to_string(),
+                        series: series_name.to_string(),
+                        resolution,
+                        bucket_start,
+                        count: 0,
+                        min: f64::INFINITY,
+                        max: f64::NEG_INFINITY,
+                        sum: 0.0,
+                    });
+                for value in values {
+                    rollup.count += 1;
+                    rollup.min = rollup.min.min(value);
+                    rollup.max = rollup.max.max(value);
+                    rollup.sum += value;
+                }
+                self.storage.store_sensor_rollup(&rollup)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Move the series' threshold state through the readings newer than its
+    /// latest, recording an event for each crossing
+    fn track_thresholds(
+        &self,
+        source: &str,
+        series: &mut SensorSeries,
+        readings: &[SensorReading],
+    ) -> Result<Vec<ThresholdCrossing>, SensorError> {
+        let mut crossings = Vec::new();
+        let mut events = EventsEngine::new(self.storage.clone());
+
+        for reading in readings {
+            if series
+                .last_reading
+                .is_some_and(|last| reading.timestamp <= last.timestamp)
+            {
+                continue;
+            }
+            series.last_reading = Some(*reading);
+
+            let state = classify(series, reading.value);
+            if state == series.state {
+                continue;
+            }
+
+            let threshold = match state {
+                SensorThresholdState::Above => series.upper_threshold,
+                SensorThresholdState::Below => series.lower_threshold,
+                SensorThresholdState::Normal => None,
+            };
+            let mut metadata = HashMap::new();
+            metadata.insert(
+                "sensor_series".to_string(),
+                serde_json::json!(series.series),
+            );
+            metadata.insert("value".to_string(), serde_json::json!(reading.value));
+            metadata.insert("unit".to_string(), serde_json::json!(series.unit));
+            metadata.insert(
+                "threshold_state".to_string(),
+                serde_json::json!(state.as_str()),
+            );
+            metadata.insert(
+                "previous_state".to_string(),
+                serde_json::json!(series.state.as_str()),
+            );
+            metadata.insert("threshold".to_string(), serde_json::json!(threshold));
+            metadata.insert("reported_by".to_string(), serde_json::json!(source));
+
+            let created = events
+                .create_event_with_occurred_at(
+                    series.dfid.clone(),
+                    EventType::StatusChanged,
+                    SENSOR_SOURCE.to_string(),
+                    EventVisibility::Private,
+                    metadata,
+                    Some(reading.timestamp),
+                )
+                .map_err(|e| SensorError::EventError(e.to_string()))?;
+
+            crossings.push(ThresholdCrossing {
+                reading: *reading,
+                from: series.state,
+                to: state,
+                event_id: created.event.event_id,
+            });
+            series.state = state;
+        }
+        Ok(crossings)
+    }
+}
+
+fn classify(series: &SensorSeries, value: f64) -> SensorThresholdState {
+    if series.upper_threshold.is_some_and(|upper| value > upper) {
+        SensorThresholdState::Above
+    } else if series.lower_threshold.is_some_and(|lower| value < lower) {
+        SensorThresholdState::Below
+    } else {
+        SensorThresholdState::Normal
+    }
+}
+
+fn validate_series_name(name: &str) -> Result<(), SensorError> {
+    if name.is_empty()
+        || name.len() > MAX_SERIES_NAME_LEN
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+    {
+        return Err(SensorError::ValidationError(format!(
+            "Series names are 1-{MAX_SERIES_NAME_LEN} letters, digits, '_', '-' or '.'"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::Item;
+    use chrono::{Duration, TimeZone};
+    use std::sync::{Arc, Mutex};
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, 10, 0, 0).unwrap()
+    }
+
+    /// A reefer item whose temperature should stay within 2-8 °C
+    fn reefer() -> (
+        Arc<Mutex<InMemoryStorage>>,
+        SensorTimeseriesEngine<Arc<Mutex<InMemoryStorage>>>,
+    ) {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        storage
+            .store_item(&Item::new(
+                "DFID-REEFER".to_string(),
+                vec![],
+                Uuid::new_v4(),
+            ))
+            .unwrap();
+        let engine = SensorTimeseriesEngine::new(Arc::clone(&storage));
+        engine
+            .set_thresholds(
+                "DFID-REEFER",
+                "temperature",
+                SensorThresholdInput {
+                    upper_threshold: Some(8.0),
+                    lower_threshold: Some(2.0),
+                    unit: Some("celsius".to_string()),
+                },
+                start() + Duration::days(1),
+            )
+            .unwrap();
+        (storage, engine)
+    }
+
+    /// Two hours of readings every 30s; the cargo warms up for ten minutes
+    fn two_hours() -> SensorIngestInput {
+        SensorIngestInput {
+            unit: None,
+            readings: (0..240)
+                .map(|i| SensorReading {
+                    timestamp: start() + Duration::seconds(30 * i),
+                    value: if (100..120).contains(&i) { 9.5 } else { 4.0 },
+                })
+                .collect(),
+        }
+    }
+
+    fn ingest(
+        engine: &SensorTimeseriesEngine<Arc<Mutex<InMemoryStorage>>>,
+        input: SensorIngestInput,
+    ) -> Result<SensorIngestReport, SensorError> {
+        engine.ingest(
+            "gateway-1",
+            "DFID-REEFER",
+            "temperature",
+            input,
+            start() + Duration::days(1),
+        )
+    }
+
+    #[test]
+    fn test_ingest_records_only_threshold_crossings() {
+        let (storage, engine) = reefer();
+
+        let report = ingest(&engine, two_hours()).unwrap();
+        assert_eq!(report.accepted, 240);
+        assert_eq!(report.crossings.len(), 2);
+        assert_eq!(report.crossings[0].to, SensorThresholdState::Above);
+        assert_eq!(report.crossings[1].to, SensorThresholdState::Normal);
+        assert_eq!(storage.get_events_by_dfid("DFID-REEFER").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_resent_batch_is_deduplicated() {
+        let (storage, engine) = reefer();
+        ingest(&engine, two_hours()).unwrap();
+
+        let resent = ingest(&engine, two_hours()).unwrap();
+        assert_eq!((resent.accepted, resent.duplicates), (0, 240));
+        assert!(resent.crossings.is_empty());
+        assert_eq!(storage.get_events_by_dfid("DFID-REEFER").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_query_with_resolution_returns_rollups() {
+        let (_, engine) = reefer();
+        ingest(&engine, two_hours()).unwrap();
+
+        let SensorRange::Rollup(hours) = engine
+            .query(
+                "DFID-REEFER",
+                "temperature",
+                start(),
+                start() + Duration::days(1),
+                Some(SensorResolution::Hour),
+            )
+            .unwrap()
+        else {
+            panic!("expected hourly rollups");
+        };
+        assert_eq!(hours.len(), 2);
+        assert_eq!(hours[0].count, 120);
+        assert_eq!(hours[0].max, 9.5);
+        assert_eq!(hours[1].min, 4.0);
+    }
+
+    #[test]
+    fn test_query_without_resolution_returns_raw_readings_in_range() {
+        let (_, engine) = reefer();
+        let input = two_hours();
+        ingest(&engine, input.clone()).unwrap();
+
+        let SensorRange::Raw(raw) = engine
+            .query(
+                "DFID-REEFER",
+                "temperature",
+                start() + Duration::minutes(50),
+                start() + Duration::minutes(70),
+                None,
+            )
+            .unwrap()
+        else {
+            panic!("expected raw readings");
+        };
+        assert_eq!(raw.len(), 41);
+        assert_eq!(raw.first(), input.readings.get(100));
+    }
+
+    #[test]
+    fn test_invalid_readings_are_refused() {
+        let (storage, engine) = reefer();
+
+        let reading = |value| SensorIngestInput {
+            unit: None,
+            readings: vec![SensorReading {
+                timestamp: start(),
+                value,
+            }],
+        };
+        for input in [
+            SensorIngestInput {
+                unit: None,
+                readings: vec![],
+            },
+            reading(f64::NAN),
+        ] {
+            assert!(matches!(
+                ingest(&engine, input),
+                Err(SensorError::ValidationError(_))
+            ));
+        }
+        assert!(matches!(
+            engine.ingest(
+                "gateway-1",
+                "DFID-MISSING",
+                "temperature",
+                reading(4.0),
+                start() + Duration::days(1),
+            ),
+            Err(SensorError::NotFound(_))
+        ));
+        assert!(storage
+            .get_events_by_dfid("DFID-REEFER")
+            .unwrap()
+            .is_empty());
+    }
+}
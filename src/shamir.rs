@@ -0,0 +1,150 @@
+//! Shamir secret sharing over GF(256).
+//!
+//! Each byte of the secret is the constant term of its own random polynomial of
+//! degree `threshold - 1`; share `x` holds every polynomial evaluated at `x`.
+//! Any `threshold` shares recover the secret by Lagrange interpolation at zero,
+//! fewer reveal nothing about it.
+
+use rand::RngCore;
+
+/// One share: the evaluation point (never zero) and the evaluated bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    pub index: u8,
+    pub bytes: Vec<u8>,
+}
+
+/// Split `secret` into `count` shares, any `threshold` of which recover it
+pub fn split(secret: &[u8], threshold: u8, count: u8) -> Result<Vec<Share>, String> {
+    if threshold < 2 {
+        return Err("threshold must be at least 2".to_string());
+    }
+    if count < threshold {
+        return Err("share count must not be below the threshold".to_string());
+    }
+    if secret.is_empty() {
+        return Err("secret is empty".to_string());
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut shares: Vec<Share> = (1..=count)
+        .map(|index| Share {
+            index,
+            bytes: Vec::with_capacity(secret.len()),
+        })
+        .collect();
+    let mut coefficients = vec![0u8; threshold as usize];
+    for &byte in secret {
+        coefficients[0] = byte;
+        rng.fill_bytes(&mut coefficients[1..]);
+        for share in &mut shares {
+            // Horner's rule, highest coefficient first
+            let value = coefficients
+                .iter()
+                .rev()
+                .fold(0, |acc, &c| gf_mul(acc, share.index) ^ c);
+            share.bytes.push(value);
+        }
+    }
+    Ok(shares)
+}
+
+/// Recover the secret from at least `threshold` distinct shares. With fewer the
+/// result is garbage, so callers check it against something known, such as a
+/// public key fingerprint.
+pub fn combine(shares: &[Share]) -> Result<Vec<u8>, String> {
+    let Some(first) = shares.first() else {
+        return Err("no shares given".to_string());
+    };
+    for (i, share) in shares.iter().enumerate() {
+        if share.index == 0 {
+            return Err("share index 0 is invalid".to_string());
+        }
+        if share.bytes.len() != first.bytes.len() {
+            return Err("shares have different lengths".to_string());
+        }
+        if shares[..i].iter().any(|other| other.index == share.index) {
+            return Err(format!("share {} given twice", share.index));
+        }
+    }
+
+    // Lagrange basis polynomials evaluated at zero
+    let weights: Vec<u8> = shares
+        .iter()
+        .map(|share| {
+            shares
+                .iter()
+                .filter(|other| other.index != share.index)
+                .fold(1, |acc, other| {
+                    gf_mul(acc, gf_div(other.index, other.index ^ share.index))
+                })
+        })
+        .collect();
+    Ok((0..first.bytes.len())
+        .map(|position| {
+            shares
+                .iter()
+                .zip(&weights)
+                .fold(0, |acc, (share, &weight)| {
+                    acc ^ gf_mul(share.bytes[position], weight)
+                })
+        })
+        .collect())
+}
+
+/// Multiplication modulo the AES polynomial x^8 + x^4 + x^3 + x + 1. Runs the
+/// same eight rounds without branches or table lookups whatever the operands,
+/// so timing does not depend on secret bytes.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    for _ in 0..8 {
+        // All ones when the low bit of b is set, zero otherwise
+        product ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+/// `a / b` for non-zero `b`, using b^254 = b^-1
+fn gf_div(a: u8, b: u8) -> u8 {
+    let mut inverse = 1;
+    for _ in 0..254 {
+        inverse = gf_mul(inverse, b);
+    }
+    gf_mul(a, inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_any_threshold_subset_recovers_secret() {
+        let secret: Vec<u8> = (0..32).map(|i| i * 7 + 3).collect();
+        let shares = split(&secret, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let picked: Vec<Share> = subset.iter().map(|&i| shares[i].clone()).collect();
+            assert_eq!(combine(&picked).unwrap(), secret);
+        }
+        assert_ne!(combine(&shares[..2]).unwrap(), secret);
+        assert!(combine(&[shares[0].clone(), shares[0].clone()]).is_err());
+        assert!(split(&secret, 1, 3).is_err());
+        assert!(split(&secret, 4, 3).is_err());
+    }
+
+    #[test]
+    fn test_field_arithmetic() {
+        // FIPS-197 section 4.2
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        assert_eq!(gf_mul(0x57, 0x13), 0xfe);
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, 0), 0);
+            assert_eq!(gf_mul(a, 1), a);
+            assert_eq!(gf_div(a, a), 1);
+        }
+    }
+}
@@ -0,0 +1,508 @@
+//! Availability tracking and SLA reporting for the API, adapters and the
+//! anchoring pipeline.
+//!
+//! Outcomes are counted in a process-wide registry, like adapter metrics and
+//! scaling signals. Once a minute the recorder turns each component's counts
+//! into one observed minute, which is down when too many calls failed, and
+//! adds it to that hour's `SlaWindow` in storage. Minutes the recorder missed
+//! after it first ran (e.g. the process was down) count as downtime.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    ComponentAvailability, ComponentStatus, ComponentStatusSummary, DailyUptime, ServiceStatus,
+    SlaComponent, SlaMonthlyReport, SlaWindow, TierSlaAttainment, UserTier,
+};
+use chrono::{DateTime, Datelike, Duration, DurationRound, NaiveDate, TimeZone, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+
+/// A minute with at least this share of failed calls is down
+pub const UNAVAILABLE_ERROR_RATE: f64 = 0.5;
+/// Recent error rate above which a component is reported degraded
+pub const DEGRADED_ERROR_RATE: f64 = 0.05;
+/// Minutes the current status is based on
+pub const STATUS_WINDOW_MINUTES: usize = 15;
+/// Longest status history served
+pub const MAX_HISTORY_DAYS: i64 = 90;
+
+#[derive(Debug)]
+pub enum SlaError {
+    StorageError(StorageError),
+    ValidationError(String),
+}
+
+impl From<StorageError> for SlaError {
+    fn from(err: StorageError) -> Self {
+        SlaError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for SlaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlaError::StorageError(e) => write!(f, "Storage error: {e}"),
+            SlaError::ValidationError(e) => write!(f, "Validation error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SlaError {}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MinuteSample {
+    pub requests: u64,
+    pub errors: u64,
+}
+
+impl MinuteSample {
+    pub fn is_down(&self) -> bool {
+        self.requests > 0 && self.errors as f64 / self.requests as f64 >= UNAVAILABLE_ERROR_RATE
+    }
+}
+
+#[derive(Debug, Default)]
+struct ComponentCounters {
+    current: MinuteSample,
+    /// Most recent closed minutes, oldest first
+    recent: VecDeque<MinuteSample>,
+}
+
+fn registry() -> &'static Mutex<HashMap<SlaComponent, ComponentCounters>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<SlaComponent, ComponentCounters>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Record the outcome of one call to a component
+pub fn record_outcome(component: SlaComponent, success: bool) {
+    let mut components = registry().lock().unwrap();
+    let counters = components.entry(component).or_default();
+    counters.current.requests += 1;
+    if !success {
+        counters.current.errors += 1;
+    }
+}
+
+/// Close the current minute of every component and return its counts
+pub fn close_minute() -> Vec<(SlaComponent, MinuteSample)> {
+    let mut components = registry().lock().unwrap();
+    SlaComponent::ALL
+        .into_iter()
+        .map(|component| {
+            let counters = components.entry(component).or_default();
+            let sample = std::mem::take(&mut counters.current);
+            counters.recent.push_back(sample);
+            while counters.recent.len() > STATUS_WINDOW_MINUTES {
+                counters.recent.pop_front();
+            }
+            (component, sample)
+        })
+        .collect()
+}
+
+fn recent_samples(component: SlaComponent) -> Vec<MinuteSample> {
+    registry()
+        .lock()
+        .unwrap()
+        .get(&component)
+        .map(|counters| counters.recent.iter().copied().collect())
+        .unwrap_or_default()
+}
+
+/// Outage if the last minute was down, degraded if recent errors are elevated
+fn component_status(recent: &[MinuteSample]) -> (ComponentStatus, f64) {
+    let requests: u64 = recent.iter().map(|s| s.requests).sum();
+    let errors: u64 = recent.iter().map(|s| s.errors).sum();
+    let error_rate = if requests == 0 {
+        0.0
+    } else {
+        errors as f64 / requests as f64
+    };
+    let status = if recent.last().is_some_and(MinuteSample::is_down) {
+        ComponentStatus::Outage
+    } else if error_rate > DEGRADED_ERROR_RATE {
+        ComponentStatus::Degraded
+    } else {
+        ComponentStatus::Operational
+    };
+    (status, error_rate)
+}
+
+/// Availability of one component over a period
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Availability {
+    expected_minutes: u64,
+    downtime_minutes: u64,
+    requests: u64,
+    errors: u64,
+}
+
+impl Availability {
+    fn uptime_percent(&self) -> f64 {
+        if self.expected_minutes == 0 {
+            return 100.0;
+        }
+        let down = self.downtime_minutes.min(self.expected_minutes);
+        (self.expected_minutes - down) as f64 / self.expected_minutes as f64 * 100.0
+    }
+
+    fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.requests as f64
+        }
+    }
+}
+
+/// `windows` are one component's, all starting in `[from, to)`. Tracking
+/// starts with the first observed minute; later gaps count as downtime.
+fn availability(windows: &[&SlaWindow], from: DateTime<Utc>, to: DateTime<Utc>) -> Availability {
+    let Some(first) = windows.iter().min_by_key(|w| w.hour_start) else {
+        return Availability::default();
+    };
+    let tracked_from = from
+        .max(first.hour_start + Duration::minutes(60 - i64::from(first.observed_minutes.min(60))));
+    let expected_minutes = (to - tracked_from).num_minutes().max(0) as u64;
+    let observed: u64 = windows.iter().map(|w| u64::from(w.observed_minutes)).sum();
+    let down: u64 = windows.iter().map(|w| u64::from(w.down_minutes)).sum();
+    Availability {
+        expected_minutes,
+        downtime_minutes: down + expected_minutes.saturating_sub(observed),
+        requests: windows.iter().map(|w| w.requests).sum(),
+        errors: windows.iter().map(|w| w.errors).sum(),
+    }
+}
+
+fn month_bounds(month: &str) -> Result<(DateTime<Utc>, DateTime<Utc>), SlaError> {
+    let invalid =
+        || SlaError::ValidationError(format!("Invalid month '{month}', expected YYYY-MM"));
+    let start =
+        NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d").map_err(|_| invalid())?;
+    let end = if start.month() == 12 {
+        NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+    }
+    .ok_or_else(invalid)?;
+    let to_utc = |date: NaiveDate| Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap());
+    Ok((to_utc(start), to_utc(end)))
+}
+
+pub struct SlaEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> SlaEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Add one observed minute per component to the hour containing `now`
+    pub fn record_minute(
+        &self,
+        samples: &[(SlaComponent, MinuteSample)],
+        now: DateTime<Utc>,
+    ) -> Result<(), SlaError> {
+        let hour_start = now
+            .duration_trunc(Duration::hours(1))
+            .map_err(|e| SlaError::ValidationError(e.to_string()))?;
+        for (component, sample) in samples {
+            let mut window = self
+                .storage
+                .get_sla_window(*component, hour_start)?
+                .unwrap_or(SlaWindow {
+                    component: *component,
+                    hour_start,
+                    requests: 0,
+                    errors: 0,
+                    observed_minutes: 0,
+                    down_minutes: 0,
+                });
+            window.requests += sample.requests;
+            window.errors += sample.errors;
+            window.observed_minutes = (window.observed_minutes + 1).min(60);
+            if sample.is_down() {
+                window.down_minutes = (window.down_minutes + 1).min(60);
+            }
+            self.storage.store_sla_window(&window)?;
+        }
+        Ok(())
+    }
+
+    fn component_availability(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<HashMap<SlaComponent, Availability>, SlaError> {
+        let windows = self.storage.list_sla_windows(from, to)?;
+        Ok(SlaComponent::ALL
+            .into_iter()
+            .map(|component| {
+                let own: Vec<&SlaWindow> = windows
+                    .iter()
+                    .filter(|w| w.component == component)
+                    .collect();
+                (component, availability(&own, from, to))
+            })
+            .collect())
+    }
+
+    /// Current status per component, for a public status page
+    pub fn status(&self, now: DateTime<Utc>) -> Result<ServiceStatus, SlaError> {
+        let day = self.component_availability(now - Duration::days(1), now)?;
+        let month = self.component_availability(now - Duration::days(30), now)?;
+        let components: Vec<ComponentStatusSummary> = SlaComponent::ALL
+            .into_iter()
+            .map(|component| {
+                let (status, recent_error_rate) = component_status(&recent_samples(component));
+                ComponentStatusSummary {
+                    component,
+                    status,
+                    recent_error_rate,
+                    uptime_24h_percent: day[&component].uptime_percent(),
+                    uptime_30d_percent: month[&component].uptime_percent(),
+                }
+            })
+            .collect();
+        let status = if components
+            .iter()
+            .any(|c| c.status == ComponentStatus::Outage)
+        {
+            ComponentStatus::Outage
+        } else if components
+            .iter()
+            .any(|c| c.status == ComponentStatus::Degraded)
+        {
+            ComponentStatus::Degraded
+        } else {
+            ComponentStatus::Operational
+        };
+        Ok(ServiceStatus {
+            status,
+            generated_at: now,
+            components,
+        })
+    }
+
+    /// Daily uptime per component for the last `days` days, oldest first
+    pub fn daily_history(
+        &self,
+        days: i64,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<DailyUptime>, SlaError> {
+        if !(1..=MAX_HISTORY_DAYS).contains(&days) {
+            return Err(SlaError::ValidationError(format!(
+                "days must be between 1 and {MAX_HISTORY_DAYS}"
+            )));
+        }
+        let today = now
+            .duration_trunc(Duration::days(1))
+            .map_err(|e| SlaError::ValidationError(e.to_string()))?;
+        let first_day = today - Duration::days(days - 1);
+        let windows = self.storage.list_sla_windows(first_day, now)?;
+        // Gaps before the first recorded minute overall are not downtime
+        let tracking_start = windows.iter().map(|w| w.hour_start).min();
+
+        let mut history = Vec::new();
+        for offset in 0..days {
+            let day_start = first_day + Duration::days(offset);
+            let day_end = (day_start + Duration::days(1)).min(now);
+            for component in SlaComponent::ALL {
+                let own: Vec<&SlaWindow> = windows
+                    .iter()
+                    .filter(|w| {
+                        w.component == component
+                            && w.hour_start >= day_start
+                            && w.hour_start < day_end
+                    })
+                    .collect();
+                let mut day = availability(&own, day_start, day_end);
+                if own.is_empty() && tracking_start.is_some_and(|start| start < day_start) {
+                    day.expected_minutes = (day_end - day_start).num_minutes().max(0) as u64;
+                    day.downtime_minutes = day.expected_minutes;
+                }
+                history.push(DailyUptime {
+                    date: day_start.date_naive(),
+                    component,
+                    uptime_percent: day.uptime_percent(),
+                    requests: day.requests,
+                    errors: day.errors,
+                });
+            }
+        }
+        Ok(history)
+    }
+
+    /// Attainment of each tier's target for a calendar month (`YYYY-MM`). The
+    /// service is as available as its least available component.
+    pub fn monthly_report(
+        &self,
+        month: &str,
+        now: DateTime<Utc>,
+    ) -> Result<SlaMonthlyReport, SlaError> {
+        let (period_start, period_end) = month_bounds(month)?;
+        if period_start > now {
+            return Err(SlaError::ValidationError(format!(
+                "Month {month} has not started yet"
+            )));
+        }
+        let availability = self.component_availability(period_start, period_end.min(now))?;
+        let components: Vec<ComponentAvailability> = SlaComponent::ALL
+            .into_iter()
+            .map(|component| {
+                let a = availability[&component];
+                ComponentAvailability {
+                    component,
+                    uptime_percent: a.uptime_percent(),
+                    error_rate: a.error_rate(),
+                    downtime_minutes: a.downtime_minutes,
+                }
+            })
+            .collect();
+        let attained_percent = components
+            .iter()
+            .map(|c| c.uptime_percent)
+            .fold(100.0, f64::min);
+        let downtime_minutes = components
+            .iter()
+            .map(|c| c.downtime_minutes)
+            .max()
+            .unwrap_or(0);
+        let month_minutes = (period_end - period_start).num_minutes() as f64;
+
+        let tiers = [
+            UserTier::Basic,
+            UserTier::Professional,
+            UserTier::Enterprise,
+        ]
+        .into_iter()
+        .filter_map(|tier| {
+            let target_percent = tier.sla_target_percent()?;
+            Some(TierSlaAttainment {
+                tier,
+                target_percent,
+                attained_percent,
+                met: attained_percent >= target_percent,
+                allowed_downtime_minutes: (month_minutes * (100.0 - target_percent) / 100.0).floor()
+                    as u64,
+                downtime_minutes,
+            })
+        })
+        .collect();
+
+        Ok(SlaMonthlyReport {
+            month: month.to_string(),
+            period_start,
+            period_end,
+            components,
+            tiers,
+        })
+    }
+
+    /// Close a minute of measurements every `tick` (normally 60s)
+    pub fn spawn_recorder(storage: S, tick: std::time::Duration) -> tokio::task::JoinHandle<()>
+    where
+        S: Send + Sync + 'static,
+    {
+        tokio::spawn(async move {
+            let engine = SlaEngine::new(storage);
+            let mut interval = tokio::time::interval(tick);
+            // The first tick fires immediately; start measuring from it
+            interval.tick().await;
+            close_minute();
+            loop {
+                interval.tick().await;
+                let samples = close_minute();
+                if let Err(e) = engine.record_minute(&samples, Utc::now()) {
+                    tracing::warn!("⚠️  Failed to record SLA minute: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_monthly_report_counts_down_and_missed_minutes() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let engine = SlaEngine::new(Arc::clone(&storage));
+        let start = Utc.with_ymd_and_hms(2026, 9, 1, 0, 0, 0).unwrap();
+        let healthy = MinuteSample {
+            requests: 10,
+            errors: 0,
+        };
+        let failing = MinuteSample {
+            requests: 10,
+            errors: 6,
+        };
+
+        // The first 10 hours: one down API minute, adapters and anchoring healthy
+        for minute in 0..600 {
+            let api = if minute == 30 { failing } else { healthy };
+            let samples = [
+                (SlaComponent::Api, api),
+                (SlaComponent::Adapters, healthy),
+                (SlaComponent::Anchoring, MinuteSample::default()),
+            ];
+            engine
+                .record_minute(&samples, start + Duration::minutes(minute))
+                .unwrap();
+        }
+        let now = start + Duration::minutes(600);
+
+        let report = engine.monthly_report("2026-09", now).unwrap();
+        let api = &report.components[0];
+        assert_eq!(api.component, SlaComponent::Api);
+        assert_eq!(api.downtime_minutes, 1);
+        assert!((api.uptime_percent - 599.0 / 600.0 * 100.0).abs() < 1e-9);
+        assert_eq!(report.components[1].downtime_minutes, 0);
+
+        // 99.83% so far: within the professional target, short of enterprise
+        let tier = |tier: UserTier| report.tiers.iter().find(|t| t.tier == tier).unwrap();
+        assert!(tier(UserTier::Professional).met);
+        assert!(!tier(UserTier::Enterprise).met);
+        assert_eq!(tier(UserTier::Enterprise).allowed_downtime_minutes, 43);
+
+        // Two hours without the recorder running count against every component
+        let later = now + Duration::minutes(120);
+        let report = engine.monthly_report("2026-09", later).unwrap();
+        assert_eq!(report.components[1].downtime_minutes, 120);
+        assert!(!report.tiers.iter().any(|t| t.met));
+
+        assert!(matches!(
+            engine.monthly_report("2026-13", now),
+            Err(SlaError::ValidationError(_))
+        ));
+        let history = engine.daily_history(2, later).unwrap();
+        assert_eq!(history.len(), 6);
+        assert_eq!(history[3].date, start.date_naive());
+    }
+
+    #[test]
+    fn test_component_status_thresholds() {
+        let ok = MinuteSample {
+            requests: 100,
+            errors: 1,
+        };
+        let elevated = MinuteSample {
+            requests: 100,
+            errors: 20,
+        };
+        let down = MinuteSample {
+            requests: 4,
+            errors: 2,
+        };
+        assert_eq!(component_status(&[ok, ok]).0, ComponentStatus::Operational);
+        assert_eq!(
+            component_status(&[ok, elevated]).0,
+            ComponentStatus::Degraded
+        );
+        assert_eq!(component_status(&[ok, down]).0, ComponentStatus::Outage);
+        assert_eq!(component_status(&[]).0, ComponentStatus::Operational);
+    }
+}
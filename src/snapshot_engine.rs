@@ -444,7 +444,7 @@ impl SnapshotEngine {
 
         for (i, snapshot) in snapshots.iter().enumerate() {
             // Verify hash
-            let computed_hash = snapshot.compute_hash();
+            let computed_hash = snapshot.recompute_hash();
             if computed_hash != snapshot.snapshot_id {
                 tampered_hashes.push(snapshot.snapshot_id.clone());
                 continue;
@@ -583,7 +583,7 @@ impl SnapshotEngine {
                 .iter()
                 .map(|e| EventSummary {
                     event_id: e.event_id.to_string(),
-                    event_type: format!("{:?}", e.event_type),
+                    event_type: e.event_type.to_string(),
                     timestamp: e.timestamp,
                     source: e.source.clone(),
                     metadata: e.metadata.clone(),
@@ -4,6 +4,7 @@
 //! Each state change (item or circuit) creates a new snapshot with a reference to the
 //! previous snapshot, forming a hash chain that can be verified and audited.
 
+use crate::hashing::{self, HashAlgorithm};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -14,6 +15,9 @@ use uuid::Uuid;
 pub enum SnapshotEntityType {
     Item,
     Circuit,
+    /// Consolidated item state written by event compaction. Kept apart from
+    /// the per-change `Item` chain so each has its own versions.
+    ItemCompaction,
 }
 
 impl std::fmt::Display for SnapshotEntityType {
@@ -21,6 +25,7 @@ impl std::fmt::Display for SnapshotEntityType {
         match self {
             SnapshotEntityType::Item => write!(f, "item"),
             SnapshotEntityType::Circuit => write!(f, "circuit"),
+            SnapshotEntityType::ItemCompaction => write!(f, "item_compaction"),
         }
     }
 }
@@ -55,6 +60,12 @@ pub enum SnapshotOperation {
     ItemPulledFromCircuit {
         circuit_id: String,
     },
+    /// Events up to and including `through_event_id` folded into one state
+    ItemCompacted {
+        through_event_id: String,
+        through_timestamp: DateTime<Utc>,
+        events_compacted: usize,
+    },
 
     // Circuit operations
     CircuitCreated,
@@ -121,6 +132,11 @@ impl SnapshotOperation {
             SnapshotOperation::ItemPulledFromCircuit { circuit_id } => {
                 format!("Pulled from circuit {}", circuit_id)
             }
+            SnapshotOperation::ItemCompacted {
+                events_compacted, ..
+            } => {
+                format!("{} event(s) compacted", events_compacted)
+            }
             SnapshotOperation::CircuitCreated => "Circuit created".to_string(),
             SnapshotOperation::CircuitMemberAdded { member_id, role } => {
                 format!("Member {} added as {}", member_id, role)
@@ -154,7 +170,7 @@ impl SnapshotOperation {
 /// A state snapshot representing the complete state at a point in time
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StateSnapshot {
-    /// Unique snapshot ID (BLAKE3 hash of content)
+    /// Unique snapshot ID (hash of content, see `hashing`)
     pub snapshot_id: String,
 
     /// Type of entity (Item or Circuit)
@@ -226,11 +242,18 @@ impl StateSnapshot {
         }
     }
 
-    /// Compute the snapshot ID using BLAKE3
+    /// Compute the snapshot ID with the default algorithm
     pub fn compute_hash(&self) -> String {
-        use blake3::Hasher;
+        self.compute_hash_with(hashing::default_algorithm())
+    }
+
+    /// Recompute the snapshot ID under the algorithm it was made with
+    pub fn recompute_hash(&self) -> String {
+        self.compute_hash_with(hashing::algorithm_of(&self.snapshot_id))
+    }
 
-        let mut hasher = Hasher::new();
+    fn compute_hash_with(&self, algorithm: HashAlgorithm) -> String {
+        let mut hasher = algorithm.hasher();
 
         // Hash the essential fields
         hasher.update(self.entity_type.to_string().as_bytes());
@@ -254,8 +277,7 @@ impl StateSnapshot {
         // Hash timestamp
         hasher.update(self.timestamp.to_rfc3339().as_bytes());
 
-        let hash = hasher.finalize();
-        hash.to_hex().to_string()
+        hasher.finalize()
     }
 
     /// Set the snapshot ID after computing the hash
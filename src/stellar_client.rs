@@ -20,6 +20,19 @@ use soroban_client::{
 pub const TESTNET_IPCM_CONTRACT: &str = "CCDJV6VAFC2MSSDSL4AEJB5BAMGDA5PMCUIZ3UF6AYIJL467PQTBZ7BS";
 pub const MAINNET_IPCM_CONTRACT: &str = "CBHYQKSG2ZADD7NXZPLFZIH7ZK766VA3YWRLISKJ6PH6KXJ4JZ52OLNZ";
 
+/// Base fee set on every contract invocation before prepare_transaction adjusts it
+pub const BASE_FEE_STROOPS: u64 = 1000;
+/// Conservative per-invocation allowance for Soroban resource fees, used for dry-run estimates
+pub const SOROBAN_RESOURCE_FEE_ESTIMATE_STROOPS: u64 = 100_000;
+
+/// Inclusion fee percentiles in stroops, from Horizon's `fee_stats`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeStats {
+    pub p10: u64,
+    pub p50: u64,
+    pub p90: u64,
+}
+
 #[derive(Debug, Clone)]
 pub enum StellarNetwork {
     Testnet,
@@ -149,6 +162,55 @@ impl StellarClient {
         self.nft_contract_address.as_deref()
     }
 
+    /// Whether a signing keypair is configured (required for every write)
+    pub fn has_keypair(&self) -> bool {
+        self.keypair.is_some()
+    }
+
+    pub fn network(&self) -> &StellarNetwork {
+        &self.network
+    }
+
+    /// `G...` address of the signing keypair, if one is configured
+    pub fn signer_public_key(&self) -> Option<String> {
+        self.keypair.as_ref().map(|keypair| keypair.public_key())
+    }
+
+    /// Estimate the total fee in stroops for `invocations` contract calls.
+    /// Uses the network's recent p90 inclusion fee plus a conservative Soroban resource
+    /// allowance; the real fee is settled by prepare_transaction at submission time.
+    pub async fn estimate_fee_stroops(&self, invocations: u32) -> Result<u64, StellarError> {
+        let inclusion_fee = self.fee_stats().await?.p90;
+        Ok(u64::from(invocations) * (inclusion_fee + SOROBAN_RESOURCE_FEE_ESTIMATE_STROOPS))
+    }
+
+    /// Inclusion fees charged over the network's recent ledgers, never below the base fee
+    pub async fn fee_stats(&self) -> Result<FeeStats, StellarError> {
+        let url = format!("{}/fee_stats", self.network.horizon_url());
+        let stats: serde_json::Value = self
+            .http_client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| StellarError::NetworkError(format!("Fee stats request failed: {e}")))?
+            .json()
+            .await
+            .map_err(|e| StellarError::SerializationError(format!("Invalid fee stats: {e}")))?;
+
+        let percentile = |name: &str| {
+            stats["fee_charged"][name]
+                .as_str()
+                .and_then(|fee| fee.parse::<u64>().ok())
+                .unwrap_or(BASE_FEE_STROOPS)
+                .max(BASE_FEE_STROOPS)
+        };
+        Ok(FeeStats {
+            p10: percentile("p10"),
+            p50: percentile("p50"),
+            p90: percentile("p90"),
+        })
+    }
+
     /// Update IPCM contract with new CID for a DFID using soroban-client
     /// This writes to storage AND emits an event (costs ~0.0001+ XLM)
     pub async fn update_ipcm(&self, dfid: &str, cid: &str) -> Result<String, StellarError> {
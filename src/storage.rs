@@ -1,28 +1,45 @@
 use crate::identifier_types::EnhancedIdentifier;
 use crate::logging::LogEntry;
+use crate::pagination::PageCursor;
 use crate::postgres_storage_with_cache::PostgresStorageWithCache;
+use crate::search_index::ItemSearchIndex;
 use crate::types::{
-    Activity, AdapterConfig, AdapterTestResult, AdapterType, AdminAction, AuditDashboardMetrics,
-    AuditEvent, AuditEventType, AuditQuery, AuditSeverity, Circuit, CircuitAdapterConfig,
-    CircuitItem, CircuitOperation, CircuitType, ComplianceReport, ComplianceStatus,
-    ConflictResolution, CreditTransaction, DataLakeEntry, Event, EventCidMapping, EventType,
-    EventVisibility, Identifier, IdentifierMapping, IndexingProgress, Item, ItemShare, ItemStatus,
-    ItemStorageHistory, Notification, PasswordResetToken, PendingItem, PendingPriority,
-    PendingReason, ProcessingStatus, Receipt, SecurityIncident, SecurityIncidentSummary,
-    StorageRecord, SystemStatistics, TimelineEntry, UserAccount, UserActivity, WebhookDelivery,
+    AccessReviewSchedule, Activity, AdapterConfig, AdapterIntent, AdapterTestResult, AdapterType,
+    AdminAction, AnchoringCostRecord, AnchoringNetwork, AnchoringSla, Announcement, Attestation,
+    AuditDashboardMetrics, AuditEvent, AuditEventType, AuditQuery, AuditSeverity,
+    ChangeFeedSubscription, ChangeRecord, Circuit, CircuitAccessReport, CircuitAdapterConfig,
+    CircuitComment, CircuitItem, CircuitOperation, CircuitType, CommentTarget, ComplianceReport,
+    ComplianceStatus, ConflictResolution, ConnectorConfig, ConnectorRunError, ConnectorState,
+    CreditTransaction, CustomEventType, DataExportJob, DataLakeEntry, DataQualityReport,
+    DedupSharingAgreement, DocumentNotarization, EnrichmentPolicy, Event, EventCidMapping,
+    EventSchema, EventSigningKey, EventType, EventVisibility, FeeSample, Identifier,
+    IdentifierMapping, IndexingProgress, Item, ItemLifecycle, ItemSearchQuery, ItemShare,
+    ItemStatus, ItemStorageHistory, ItemSummary, LifecycleDefinition, MaintenanceMode, ManagedKey,
+    MappingTemplate, MembershipReconciliationReport, MergeProposal, MetricDefinition,
+    NotarizationBatch, Notification, OrganizationProfile, PartnerToken, PartnerTokenUsage,
+    PasswordResetToken, PendingItem, PendingPriority, PendingReason, PreviewEnvironment,
+    ProcessingStatus, Receipt, ReverificationJob, RunbookHook, SavedAuditQuery, SecurityIncident,
+    SecurityIncidentSummary, SlaComponent, SlaWindow, StorageRecord, SystemStatistics,
+    TimelineEntry, TimelineQuery, UserAccount, UserActivity, WebhookDelivery, WorkspaceEngagement,
+    WorkspaceVerificationRules,
 };
 use aes_gcm::aead::{Aead, KeyInit, OsRng};
 use aes_gcm::{Aes256Gcm, Key, Nonce};
 use chrono::{DateTime, Utc};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fs;
 use std::path::Path;
 use std::sync::Arc;
 use std::sync::Mutex;
 use uuid::Uuid;
 
+/// Run errors retained per connector by the in-memory backend
+const MAX_CONNECTOR_ERRORS: usize = 500;
+/// Change records retained per circuit by the in-memory backend; older cursors expire
+const MAX_CHANGE_RECORDS: usize = 50_000;
+
 #[derive(Debug)]
 pub enum StorageError {
     IoError(String),
@@ -88,6 +105,42 @@ impl EncryptionKey {
         Self(bytes)
     }
 
+    /// Parse a 32-byte key from its 64-character hex encoding
+    pub fn from_hex(hex_key: &str) -> Result<Self, StorageError> {
+        let bytes = hex::decode(hex_key.trim()).map_err(|e| {
+            StorageError::ConfigurationError(format!("Invalid encryption key hex: {e}"))
+        })?;
+        let key: [u8; 32] = bytes.try_into().map_err(|_| {
+            StorageError::ConfigurationError("Encryption key must be 32 bytes".to_string())
+        })?;
+        Ok(Self(key))
+    }
+
+    pub fn encrypt(&self, data: &[u8]) -> Result<EncryptedData, StorageError> {
+        let cipher = Aes256Gcm::new(self.as_aes_key());
+        let mut nonce_bytes = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let nonce = Nonce::from_slice(&nonce_bytes);
+
+        let ciphertext = cipher
+            .encrypt(nonce, data)
+            .map_err(|e| StorageError::EncryptionError(format!("Encryption failed: {e}")))?;
+
+        Ok(EncryptedData {
+            data: ciphertext,
+            nonce: nonce_bytes,
+        })
+    }
+
+    pub fn decrypt(&self, encrypted: &EncryptedData) -> Result<Vec<u8>, StorageError> {
+        let cipher = Aes256Gcm::new(self.as_aes_key());
+        let nonce = Nonce::from_slice(&encrypted.nonce);
+
+        cipher
+            .decrypt(nonce, encrypted.data.as_ref())
+            .map_err(|e| StorageError::EncryptionError(format!("Decryption failed: {e}")))
+    }
+
     fn as_aes_key(&self) -> &Key<Aes256Gcm> {
         Key::<Aes256Gcm>::from_slice(&self.0)
     }
@@ -101,12 +154,15 @@ pub trait StorageBackend: Send + Sync {
         identifier: &Identifier,
     ) -> Result<Vec<Receipt>, StorageError>;
     fn list_receipts(&self) -> Result<Vec<Receipt>, StorageError>;
+    /// Store several receipts and their identifier index entries in one operation
+    fn store_receipts(&self, receipts: &[Receipt]) -> Result<(), StorageError>;
 
     fn store_log(&self, log: &LogEntry) -> Result<(), StorageError>;
     fn get_logs(&self) -> Result<Vec<LogEntry>, StorageError>;
 
     // Data Lake operations
     fn store_data_lake_entry(&self, entry: &DataLakeEntry) -> Result<(), StorageError>;
+    fn store_data_lake_entries(&self, entries: &[DataLakeEntry]) -> Result<(), StorageError>;
     fn get_data_lake_entry(&self, entry_id: &Uuid) -> Result<Option<DataLakeEntry>, StorageError>;
     fn update_data_lake_entry(&self, entry: &DataLakeEntry) -> Result<(), StorageError>;
     fn get_data_lake_entries_by_status(
@@ -143,6 +199,8 @@ pub trait StorageBackend: Send + Sync {
 
     // Event operations
     fn store_event(&self, event: &Event) -> Result<(), StorageError>;
+    /// Store several events in one operation; on failure none of them is stored
+    fn store_events(&self, events: &[Event]) -> Result<(), StorageError>;
     fn get_event(&self, event_id: &Uuid) -> Result<Option<Event>, StorageError>;
     fn update_event(&self, event: &Event) -> Result<(), StorageError>;
     fn list_events(&self) -> Result<Vec<Event>, StorageError>;
@@ -158,7 +216,7 @@ pub trait StorageBackend: Send + Sync {
         end: DateTime<Utc>,
     ) -> Result<Vec<Event>, StorageError>;
 
-    /// Get event by content hash for deduplication
+    /// Most recently recorded event with this content hash, for deduplication
     fn get_event_by_content_hash(&self, content_hash: &str) -> Result<Option<Event>, StorageError>;
 
     // Circuit operations
@@ -167,6 +225,14 @@ pub trait StorageBackend: Send + Sync {
     fn update_circuit(&self, circuit: &Circuit) -> Result<(), StorageError>;
     fn list_circuits(&self) -> Result<Vec<Circuit>, StorageError>;
     fn get_circuits_for_member(&self, member_id: &str) -> Result<Vec<Circuit>, StorageError>;
+    /// Archived circuits the member belongs to. `list_circuits` and
+    /// `get_circuits_for_member` return archived circuits too.
+    fn get_archived_circuits_for_member(
+        &self,
+        member_id: &str,
+    ) -> Result<Vec<Circuit>, StorageError>;
+    /// Circuits whose `parent_circuit_id` is `parent_id`
+    fn get_child_circuits(&self, parent_id: &Uuid) -> Result<Vec<Circuit>, StorageError>;
 
     // Circuit Operation operations
     fn store_circuit_operation(&self, operation: &CircuitOperation) -> Result<(), StorageError>;
@@ -542,6 +608,619 @@ pub trait StorageBackend: Send + Sync {
         entity_type: crate::snapshot_types::SnapshotEntityType,
         entity_id: &str,
     ) -> Result<u64, StorageError>;
+
+    // Organization directory operations
+    fn store_organization_profile(&self, profile: &OrganizationProfile)
+        -> Result<(), StorageError>;
+    fn get_organization_profile(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<OrganizationProfile>, StorageError>;
+    fn list_organization_profiles(&self) -> Result<Vec<OrganizationProfile>, StorageError>;
+    fn delete_organization_profile(&self, user_id: &str) -> Result<(), StorageError>;
+
+    // Attestation operations
+    fn store_attestation(&self, attestation: &Attestation) -> Result<(), StorageError>;
+    fn get_attestation(&self, attestation_id: &Uuid) -> Result<Option<Attestation>, StorageError>;
+    fn list_attestations_for_subject(
+        &self,
+        subject_id: &str,
+    ) -> Result<Vec<Attestation>, StorageError>;
+    fn list_attestations_by_attester(
+        &self,
+        attester_id: &str,
+    ) -> Result<Vec<Attestation>, StorageError>;
+
+    // Saved audit query operations
+    fn store_saved_audit_query(&self, query: &SavedAuditQuery) -> Result<(), StorageError>;
+    fn get_saved_audit_query(
+        &self,
+        query_id: &Uuid,
+    ) -> Result<Option<SavedAuditQuery>, StorageError>;
+    fn list_saved_audit_queries(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<SavedAuditQuery>, StorageError>;
+    fn delete_saved_audit_query(&self, query_id: &Uuid) -> Result<(), StorageError>;
+
+    // Preview environment operations
+    fn store_preview_environment(&self, preview: &PreviewEnvironment) -> Result<(), StorageError>;
+    fn get_preview_environment(
+        &self,
+        preview_id: &Uuid,
+    ) -> Result<Option<PreviewEnvironment>, StorageError>;
+    fn list_preview_environments(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<PreviewEnvironment>, StorageError>;
+
+    // Paginated listings (ordered by creation timestamp, then id)
+    fn list_items_page(
+        &self,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Item>, StorageError>;
+    fn list_events_page(
+        &self,
+        dfid: Option<&str>,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Event>, StorageError>;
+
+    // Legacy connector operations
+    fn store_connector_config(&self, config: &ConnectorConfig) -> Result<(), StorageError>;
+    fn get_connector_config(
+        &self,
+        connector_id: &Uuid,
+    ) -> Result<Option<ConnectorConfig>, StorageError>;
+    fn list_connector_configs(&self) -> Result<Vec<ConnectorConfig>, StorageError>;
+    fn delete_connector_config(&self, connector_id: &Uuid) -> Result<(), StorageError>;
+    fn store_mapping_template(&self, template: &MappingTemplate) -> Result<(), StorageError>;
+    fn get_mapping_template(
+        &self,
+        template_id: &Uuid,
+    ) -> Result<Option<MappingTemplate>, StorageError>;
+    fn list_mapping_templates(&self, owner_id: &str) -> Result<Vec<MappingTemplate>, StorageError>;
+    fn store_connector_state(&self, state: &ConnectorState) -> Result<(), StorageError>;
+    fn get_connector_state(
+        &self,
+        connector_id: &Uuid,
+    ) -> Result<Option<ConnectorState>, StorageError>;
+    fn store_connector_error(&self, error: &ConnectorRunError) -> Result<(), StorageError>;
+    fn list_connector_errors(
+        &self,
+        connector_id: &Uuid,
+        limit: usize,
+    ) -> Result<Vec<ConnectorRunError>, StorageError>;
+    fn delete_mapping_template(&self, template_id: &Uuid) -> Result<(), StorageError>;
+
+    // Circuit change feed operations
+    fn append_change_record(&self, record: &ChangeRecord) -> Result<ChangeRecord, StorageError>;
+    fn list_change_records(
+        &self,
+        circuit_id: &Uuid,
+        after_sequence: u64,
+        limit: usize,
+    ) -> Result<Vec<ChangeRecord>, StorageError>;
+    fn get_latest_change_sequence(&self, circuit_id: &Uuid) -> Result<u64, StorageError>;
+    fn store_change_feed_subscription(
+        &self,
+        subscription: &ChangeFeedSubscription,
+    ) -> Result<(), StorageError>;
+    fn get_change_feed_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<Option<ChangeFeedSubscription>, StorageError>;
+    fn list_change_feed_subscriptions(
+        &self,
+        circuit_id: Option<&Uuid>,
+    ) -> Result<Vec<ChangeFeedSubscription>, StorageError>;
+    fn delete_change_feed_subscription(&self, subscription_id: &Uuid) -> Result<(), StorageError>;
+
+    // Item search
+    fn search_items(
+        &self,
+        query: &ItemSearchQuery,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Item>, StorageError>;
+
+    // Announcements
+    fn store_announcement(&self, announcement: &Announcement) -> Result<(), StorageError>;
+    fn get_announcement(
+        &self,
+        announcement_id: &Uuid,
+    ) -> Result<Option<Announcement>, StorageError>;
+    fn list_announcements(&self) -> Result<Vec<Announcement>, StorageError>;
+
+    // Workspace engagement snapshots
+    fn store_workspace_engagement(
+        &self,
+        engagement: &WorkspaceEngagement,
+    ) -> Result<(), StorageError>;
+    fn get_workspace_engagement(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<WorkspaceEngagement>, StorageError>;
+
+    // Data export jobs
+    fn store_data_export_job(&self, job: &DataExportJob) -> Result<(), StorageError>;
+    fn get_data_export_job(&self, job_id: &Uuid) -> Result<Option<DataExportJob>, StorageError>;
+    fn list_data_export_jobs(&self, requested_by: &str)
+        -> Result<Vec<DataExportJob>, StorageError>;
+
+    // SLA windows
+    fn store_sla_window(&self, window: &SlaWindow) -> Result<(), StorageError>;
+    fn get_sla_window(
+        &self,
+        component: SlaComponent,
+        hour_start: DateTime<Utc>,
+    ) -> Result<Option<SlaWindow>, StorageError>;
+    fn list_sla_windows(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<SlaWindow>, StorageError>;
+
+    // Event schemas
+    fn store_event_schema(&self, schema: &EventSchema) -> Result<(), StorageError>;
+    fn list_event_schemas(&self) -> Result<Vec<EventSchema>, StorageError>;
+
+    // Maintenance mode
+    fn store_maintenance_mode(&self, mode: &MaintenanceMode) -> Result<(), StorageError>;
+    fn get_maintenance_mode(&self) -> Result<Option<MaintenanceMode>, StorageError>;
+
+    // Managed keys
+    fn store_managed_key(&self, key: &ManagedKey) -> Result<(), StorageError>;
+    fn get_managed_key(&self, key_id: &Uuid) -> Result<Option<ManagedKey>, StorageError>;
+    fn list_managed_keys(&self) -> Result<Vec<ManagedKey>, StorageError>;
+
+    // Custom event types
+    fn store_custom_event_type(&self, event_type: &CustomEventType) -> Result<(), StorageError>;
+    fn list_custom_event_types(&self) -> Result<Vec<CustomEventType>, StorageError>;
+    fn delete_custom_event_type(&self, circuit_id: &Uuid, name: &str)
+        -> Result<bool, StorageError>;
+
+    // Document notarizations
+    fn store_notarization(&self, notarization: &DocumentNotarization) -> Result<(), StorageError>;
+    fn get_notarization(
+        &self,
+        notarization_id: &Uuid,
+    ) -> Result<Option<DocumentNotarization>, StorageError>;
+    fn list_notarizations(&self) -> Result<Vec<DocumentNotarization>, StorageError>;
+    fn store_notarization_batch(&self, batch: &NotarizationBatch) -> Result<(), StorageError>;
+    fn get_notarization_batch(
+        &self,
+        batch_id: &Uuid,
+    ) -> Result<Option<NotarizationBatch>, StorageError>;
+
+    // Enrichment policies
+    fn store_enrichment_policy(&self, policy: &EnrichmentPolicy) -> Result<(), StorageError>;
+    fn get_enrichment_policy(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<EnrichmentPolicy>, StorageError>;
+
+    // Event signing keys
+    fn store_event_signing_key(&self, key: &EventSigningKey) -> Result<(), StorageError>;
+    fn get_event_signing_key(&self, key_id: &Uuid)
+        -> Result<Option<EventSigningKey>, StorageError>;
+    fn list_event_signing_keys(&self) -> Result<Vec<EventSigningKey>, StorageError>;
+
+    // Anchoring SLAs, fee samples and anchoring spend
+    fn store_anchoring_sla(&self, sla: &AnchoringSla) -> Result<(), StorageError>;
+    fn get_anchoring_sla(&self, circuit_id: &Uuid) -> Result<Option<AnchoringSla>, StorageError>;
+    fn store_fee_sample(&self, sample: &FeeSample) -> Result<(), StorageError>;
+    fn get_latest_fee_sample(
+        &self,
+        network: &AnchoringNetwork,
+    ) -> Result<Option<FeeSample>, StorageError>;
+    fn store_anchoring_cost_record(&self, record: &AnchoringCostRecord)
+        -> Result<(), StorageError>;
+    fn list_anchoring_cost_records(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AnchoringCostRecord>, StorageError>;
+
+    // Event causality
+    fn get_events_caused_by(&self, event_id: &Uuid) -> Result<Vec<Event>, StorageError>;
+    fn get_events_by_correlation_id(
+        &self,
+        correlation_id: &Uuid,
+    ) -> Result<Vec<Event>, StorageError>;
+
+    // Item lifecycles
+    fn store_lifecycle_definition(
+        &self,
+        definition: &LifecycleDefinition,
+    ) -> Result<(), StorageError>;
+    fn get_lifecycle_definition(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<LifecycleDefinition>, StorageError>;
+    fn store_item_lifecycle(&self, lifecycle: &ItemLifecycle) -> Result<(), StorageError>;
+    fn get_item_lifecycle(&self, dfid: &str) -> Result<Option<ItemLifecycle>, StorageError>;
+    fn list_item_lifecycles(&self, workspace_id: &str) -> Result<Vec<ItemLifecycle>, StorageError>;
+
+    // Circuit access reviews
+    fn store_access_report(&self, report: &CircuitAccessReport) -> Result<(), StorageError>;
+    fn get_access_report(
+        &self,
+        report_id: &Uuid,
+    ) -> Result<Option<CircuitAccessReport>, StorageError>;
+    fn list_access_reports(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<CircuitAccessReport>, StorageError>;
+    fn delete_access_report(&self, report_id: &Uuid) -> Result<(), StorageError>;
+    fn store_access_review_schedule(
+        &self,
+        schedule: &AccessReviewSchedule,
+    ) -> Result<(), StorageError>;
+    fn get_access_review_schedule(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Option<AccessReviewSchedule>, StorageError>;
+    fn delete_access_review_schedule(&self, circuit_id: &Uuid) -> Result<(), StorageError>;
+    fn list_access_review_schedules(&self) -> Result<Vec<AccessReviewSchedule>, StorageError>;
+
+    // Adapter write intents
+    fn store_adapter_intent(&self, intent: &AdapterIntent) -> Result<(), StorageError>;
+    fn get_adapter_intent(&self, intent_id: &Uuid) -> Result<Option<AdapterIntent>, StorageError>;
+    fn get_incomplete_adapter_intents(&self) -> Result<Vec<AdapterIntent>, StorageError>;
+
+    // Circuit-scoped partner tokens
+    fn store_partner_token(&self, token: &PartnerToken) -> Result<(), StorageError>;
+    fn get_partner_token(&self, token_id: &Uuid) -> Result<Option<PartnerToken>, StorageError>;
+    fn get_partner_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PartnerToken>, StorageError>;
+    fn list_partner_tokens(&self, circuit_id: &Uuid) -> Result<Vec<PartnerToken>, StorageError>;
+    fn record_partner_token_usage(&self, usage: &PartnerTokenUsage) -> Result<(), StorageError>;
+    fn list_partner_token_usage(
+        &self,
+        token_id: &Uuid,
+        limit: usize,
+    ) -> Result<Vec<PartnerTokenUsage>, StorageError>;
+
+    // Circuit comment threads
+    fn store_circuit_comment(&self, comment: &CircuitComment) -> Result<(), StorageError>;
+    fn get_circuit_comment(
+        &self,
+        comment_id: &Uuid,
+    ) -> Result<Option<CircuitComment>, StorageError>;
+    fn list_circuit_comments(
+        &self,
+        circuit_id: &Uuid,
+        target: &CommentTarget,
+    ) -> Result<Vec<CircuitComment>, StorageError>;
+    fn delete_circuit_comment(&self, comment_id: &Uuid) -> Result<(), StorageError>;
+
+    // Circuit data quality reports
+    fn store_data_quality_report(&self, report: &DataQualityReport) -> Result<(), StorageError>;
+    fn list_data_quality_reports(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<DataQualityReport>, StorageError>;
+    fn delete_data_quality_report(&self, report_id: &Uuid) -> Result<(), StorageError>;
+
+    // Dashboard metric definitions
+    fn store_metric_definition(&self, definition: &MetricDefinition) -> Result<(), StorageError>;
+    fn get_metric_definition(
+        &self,
+        metric_id: &Uuid,
+    ) -> Result<Option<MetricDefinition>, StorageError>;
+    fn list_metric_definitions(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Vec<MetricDefinition>, StorageError>;
+    fn delete_metric_definition(&self, metric_id: &Uuid) -> Result<(), StorageError>;
+
+    // ZK setup artifacts
+    fn store_zk_setup_artifact(
+        &self,
+        artifact: &crate::zk_proof_engine::ZkSetupArtifact,
+    ) -> Result<(), StorageError>;
+    fn get_zk_setup_artifact(
+        &self,
+        artifact_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::ZkSetupArtifact>, StorageError>;
+    fn list_zk_setup_artifacts(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkSetupArtifact>, StorageError>;
+
+    // Data lake garbage collection
+    fn delete_data_lake_entries(&self, entry_ids: &[Uuid]) -> Result<(), StorageError>;
+    fn delete_receipts(&self, receipt_ids: &[Uuid]) -> Result<(), StorageError>;
+    fn store_data_lake_gc_report(
+        &self,
+        report: &crate::types::DataLakeGcReport,
+    ) -> Result<(), StorageError>;
+    fn list_data_lake_gc_reports(
+        &self,
+    ) -> Result<Vec<crate::types::DataLakeGcReport>, StorageError>;
+
+    // Device backups
+    fn store_device_backup(&self, backup: &crate::types::DeviceBackup) -> Result<(), StorageError>;
+    fn get_device_backup(
+        &self,
+        backup_id: &Uuid,
+    ) -> Result<Option<crate::types::DeviceBackup>, StorageError>;
+    fn list_device_backups(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<crate::types::DeviceBackup>, StorageError>;
+    fn delete_device_backup(&self, backup_id: &Uuid) -> Result<(), StorageError>;
+
+    // Federation
+    fn store_federation_peer(
+        &self,
+        peer: &crate::types::FederationPeer,
+    ) -> Result<(), StorageError>;
+    fn get_federation_peer(
+        &self,
+        peer_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationPeer>, StorageError>;
+    fn list_federation_peers(&self) -> Result<Vec<crate::types::FederationPeer>, StorageError>;
+    fn delete_federation_peer(&self, peer_id: &Uuid) -> Result<(), StorageError>;
+    fn store_federation_provenance(
+        &self,
+        provenance: &crate::types::FederationProvenance,
+    ) -> Result<(), StorageError>;
+    fn get_federation_provenance(
+        &self,
+        record_key: &str,
+    ) -> Result<Option<crate::types::FederationProvenance>, StorageError>;
+    fn store_federation_conflict(
+        &self,
+        conflict: &crate::types::FederationConflict,
+    ) -> Result<(), StorageError>;
+    fn get_federation_conflict(
+        &self,
+        conflict_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationConflict>, StorageError>;
+    fn list_federation_conflicts(
+        &self,
+    ) -> Result<Vec<crate::types::FederationConflict>, StorageError>;
+
+    // ZK proof revocations
+    fn store_zk_proof_revocation(
+        &self,
+        revocation: &crate::zk_proof_engine::ProofRevocation,
+    ) -> Result<(), StorageError>;
+    fn get_zk_proof_revocation(
+        &self,
+        proof_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofRevocation>, StorageError>;
+    fn list_zk_proof_revocations(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ProofRevocation>, StorageError>;
+
+    // Selective disclosures
+    fn store_item_attribute_commitments(
+        &self,
+        commitments: &crate::types::ItemAttributeCommitments,
+    ) -> Result<(), StorageError>;
+    fn get_item_attribute_commitments(
+        &self,
+        dfid: &str,
+    ) -> Result<Option<crate::types::ItemAttributeCommitments>, StorageError>;
+    fn store_selective_disclosure(
+        &self,
+        disclosure: &crate::types::SelectiveDisclosure,
+    ) -> Result<(), StorageError>;
+    fn get_selective_disclosure(
+        &self,
+        disclosure_id: &Uuid,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, StorageError>;
+    fn get_selective_disclosure_by_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, StorageError>;
+    fn list_selective_disclosures(
+        &self,
+        created_by: &str,
+    ) -> Result<Vec<crate::types::SelectiveDisclosure>, StorageError>;
+
+    // Custom ZK circuit definitions
+    fn store_custom_circuit_definition(
+        &self,
+        definition: &crate::zk_proof_engine::CustomCircuitDefinition,
+    ) -> Result<(), StorageError>;
+    fn get_custom_circuit_definition(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::CustomCircuitDefinition>, StorageError>;
+    fn list_custom_circuit_definitions(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::CustomCircuitDefinition>, StorageError>;
+
+    // ZK proof generation jobs
+    fn store_zk_proof_job(
+        &self,
+        job: &crate::zk_proof_engine::ProofJob,
+    ) -> Result<(), StorageError>;
+    fn get_zk_proof_job(
+        &self,
+        job_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofJob>, StorageError>;
+    fn list_zk_proof_jobs(&self) -> Result<Vec<crate::zk_proof_engine::ProofJob>, StorageError>;
+
+    // Item search projected to summaries
+    fn search_item_summaries(
+        &self,
+        query: &ItemSearchQuery,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<ItemSummary>, StorageError>;
+
+    // Per-workspace API usage and anomalies
+    fn store_workspace_usage_window(
+        &self,
+        window: &crate::types::WorkspaceUsageWindow,
+    ) -> Result<(), StorageError>;
+    fn get_workspace_usage_window(
+        &self,
+        workspace_id: &str,
+        hour_start: DateTime<Utc>,
+    ) -> Result<Option<crate::types::WorkspaceUsageWindow>, StorageError>;
+    fn list_workspace_usage_windows(
+        &self,
+        workspace_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::WorkspaceUsageWindow>, StorageError>;
+    fn store_usage_anomaly_baseline(
+        &self,
+        baseline: &crate::types::UsageAnomalyBaseline,
+    ) -> Result<(), StorageError>;
+    fn get_usage_anomaly_baseline(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::UsageAnomalyBaseline>, StorageError>;
+    fn store_usage_anomaly(&self, anomaly: &crate::types::UsageAnomaly)
+        -> Result<(), StorageError>;
+    fn list_usage_anomalies(
+        &self,
+        workspace_id: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::UsageAnomaly>, StorageError>;
+
+    // Proof share links
+    fn store_proof_share_link(
+        &self,
+        link: &crate::types::ProofShareLink,
+    ) -> Result<(), StorageError>;
+    fn get_proof_share_link(
+        &self,
+        share_id: &Uuid,
+    ) -> Result<Option<crate::types::ProofShareLink>, StorageError>;
+    fn list_proof_share_links(
+        &self,
+        proof_id: &Uuid,
+    ) -> Result<Vec<crate::types::ProofShareLink>, StorageError>;
+    /// Count one view of a link that is unrevoked, unexpired at `now` and
+    /// under its view limit, as a single atomic step; `None` for any other link
+    fn record_proof_share_view(
+        &self,
+        share_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<crate::types::ProofShareLink>, StorageError>;
+
+    // Workspace dedup configs
+    fn store_workspace_dedup_config(
+        &self,
+        config: &crate::types::WorkspaceDedupConfig,
+    ) -> Result<(), StorageError>;
+    fn get_workspace_dedup_config(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceDedupConfig>, StorageError>;
+
+    // Sensor time series
+    fn store_sensor_series(&self, series: &crate::types::SensorSeries) -> Result<(), StorageError>;
+    fn get_sensor_series(
+        &self,
+        dfid: &str,
+        series: &str,
+    ) -> Result<Option<crate::types::SensorSeries>, StorageError>;
+    fn list_sensor_series(
+        &self,
+        dfid: &str,
+    ) -> Result<Vec<crate::types::SensorSeries>, StorageError>;
+    fn store_sensor_chunk(&self, chunk: &crate::types::SensorChunk) -> Result<(), StorageError>;
+    fn get_sensor_chunks(
+        &self,
+        dfid: &str,
+        series: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorChunk>, StorageError>;
+    fn store_sensor_rollup(&self, rollup: &crate::types::SensorRollup) -> Result<(), StorageError>;
+    fn get_sensor_rollups(
+        &self,
+        dfid: &str,
+        series: &str,
+        resolution: crate::types::SensorResolution,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorRollup>, StorageError>;
+    fn list_all_sensor_series(&self) -> Result<Vec<crate::types::SensorSeries>, StorageError>;
+
+    // Workspace buckets
+    fn store_workspace_bucket_config(
+        &self,
+        config: &crate::types::WorkspaceBucketConfig,
+    ) -> Result<(), StorageError>;
+    fn get_workspace_bucket_config(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceBucketConfig>, StorageError>;
+    fn delete_workspace_bucket_config(&self, workspace_id: &str) -> Result<bool, StorageError>;
+
+    // DFID sequence leases
+    fn lease_dfid_sequence_range(&self, day: &str, count: u64) -> Result<u64, StorageError>;
+
+    // Re-verification jobs and merge proposals
+    fn store_reverification_job(&self, job: &ReverificationJob) -> Result<(), StorageError>;
+    fn get_reverification_job(
+        &self,
+        job_id: &Uuid,
+    ) -> Result<Option<ReverificationJob>, StorageError>;
+    fn list_reverification_jobs(&self) -> Result<Vec<ReverificationJob>, StorageError>;
+    fn store_merge_proposal(&self, proposal: &MergeProposal) -> Result<(), StorageError>;
+    fn get_merge_proposal(&self, proposal_id: &Uuid)
+        -> Result<Option<MergeProposal>, StorageError>;
+    fn list_merge_proposals(&self) -> Result<Vec<MergeProposal>, StorageError>;
+
+    // Runbook hooks
+    fn store_runbook_hook(&self, hook: &RunbookHook) -> Result<(), StorageError>;
+    fn get_runbook_hook(&self, hook_id: &Uuid) -> Result<Option<RunbookHook>, StorageError>;
+    fn list_runbook_hooks(&self) -> Result<Vec<RunbookHook>, StorageError>;
+    fn delete_runbook_hook(&self, hook_id: &Uuid) -> Result<(), StorageError>;
+
+    // Workspace verification rules
+    fn store_workspace_verification_rules(
+        &self,
+        rules: &WorkspaceVerificationRules,
+    ) -> Result<(), StorageError>;
+    fn get_workspace_verification_rules(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<WorkspaceVerificationRules>, StorageError>;
+
+    // Dedup sharing agreements
+    fn store_dedup_sharing_agreement(
+        &self,
+        agreement: &DedupSharingAgreement,
+    ) -> Result<(), StorageError>;
+    fn get_dedup_sharing_agreement(
+        &self,
+        agreement_id: &Uuid,
+    ) -> Result<Option<DedupSharingAgreement>, StorageError>;
+    fn list_dedup_sharing_agreements(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<DedupSharingAgreement>, StorageError>;
+
+    // Paginated CID timeline
+    fn get_item_timeline_page(
+        &self,
+        dfid: &str,
+        query: &TimelineQuery,
+        after_sequence: Option<i32>,
+        limit: usize,
+    ) -> Result<Vec<TimelineEntry>, StorageError>;
+
+    // Circuit membership reconciliation
+    fn store_membership_reconciliation_report(
+        &self,
+        report: &MembershipReconciliationReport,
+    ) -> Result<(), StorageError>;
+    fn list_membership_reconciliation_reports(
+        &self,
+    ) -> Result<Vec<MembershipReconciliationReport>, StorageError>;
 }
 
 #[derive(Default)]
@@ -554,6 +1233,10 @@ struct InMemoryState {
     identifier_mappings: HashMap<Identifier, Vec<IdentifierMapping>>,
     conflicts: HashMap<Uuid, ConflictResolution>,
     events: HashMap<Uuid, Event>,
+    /// Events that may still be written before writes fail, see
+    /// `fail_event_writes_after`
+    #[cfg(test)]
+    event_write_budget: Option<usize>,
     circuits: HashMap<Uuid, Circuit>,
     circuit_operations: HashMap<Uuid, CircuitOperation>,
     item_shares: HashMap<String, ItemShare>,
@@ -597,6 +1280,99 @@ struct InMemoryState {
     // State Snapshots
     snapshots: HashMap<String, crate::snapshot_types::StateSnapshot>, // snapshot_id -> snapshot
     snapshots_by_entity: HashMap<(String, String), Vec<String>>, // (entity_type, entity_id) -> snapshot_ids
+    // Organization directory
+    organization_profiles: HashMap<String, OrganizationProfile>, // user_id -> profile
+    // Member attestations
+    attestations: HashMap<Uuid, Attestation>, // attestation_id -> attestation
+    // Saved audit queries
+    saved_audit_queries: HashMap<Uuid, SavedAuditQuery>, // query_id -> query
+    preview_environments: HashMap<Uuid, PreviewEnvironment>, // preview_id -> environment
+    // Legacy connectors
+    connector_configs: HashMap<Uuid, ConnectorConfig>, // connector_id -> config
+    mapping_templates: HashMap<Uuid, MappingTemplate>, // template_id -> template
+    connector_states: HashMap<Uuid, ConnectorState>,   // connector_id -> state
+    connector_errors: HashMap<Uuid, Vec<ConnectorRunError>>, // connector_id -> errors, oldest first
+    // Circuit change feeds
+    change_records: HashMap<Uuid, VecDeque<ChangeRecord>>, // circuit_id -> records, oldest first
+    change_feed_subscriptions: HashMap<Uuid, ChangeFeedSubscription>, // subscription_id -> subscription
+    // Postings for item search, kept in step with `items`
+    item_search_index: ItemSearchIndex,
+    // Admin announcements
+    announcements: HashMap<Uuid, Announcement>, // announcement_id -> announcement
+    // Latest engagement snapshot per workspace
+    workspace_engagement: HashMap<String, WorkspaceEngagement>, // workspace_id -> snapshot
+    // Data export jobs
+    data_export_jobs: HashMap<Uuid, DataExportJob>, // job_id -> job
+    // Hourly availability per component
+    sla_windows: HashMap<(SlaComponent, DateTime<Utc>), SlaWindow>,
+    // Per-circuit event metadata schemas, every version
+    event_schemas: HashMap<Uuid, EventSchema>, // schema_id -> schema
+    // Operator write freeze, if one was ever set
+    maintenance_mode: Option<MaintenanceMode>,
+    // Anchoring and audit signing keys from key ceremonies (public parts only)
+    managed_keys: HashMap<Uuid, ManagedKey>, // key_id -> key
+    // Domain event types each circuit accepts
+    custom_event_types: HashMap<(Uuid, String), CustomEventType>, // (circuit_id, name) -> type
+    // Document proofs of existence and the batches anchoring them
+    notarizations: HashMap<Uuid, DocumentNotarization>, // notarization_id -> notarization
+    notarization_batches: HashMap<Uuid, NotarizationBatch>, // batch_id -> anchoring batch
+    // Per-workspace merge policies for enriching items
+    enrichment_policies: HashMap<String, EnrichmentPolicy>, // workspace_id -> policy
+    // Public keys users and workspaces sign events with
+    event_signing_keys: HashMap<Uuid, EventSigningKey>, // key_id -> key
+    // Per-circuit anchoring SLAs, latest fees per network and anchoring spend
+    anchoring_slas: HashMap<Uuid, AnchoringSla>, // circuit_id -> SLA
+    fee_samples: HashMap<AnchoringNetwork, FeeSample>, // network -> latest sample
+    anchoring_costs: Vec<AnchoringCostRecord>,
+    // Per-workspace lifecycle stages and where each item stands in them
+    lifecycle_definitions: HashMap<String, LifecycleDefinition>, // workspace_id -> definition
+    item_lifecycles: HashMap<String, ItemLifecycle>,             // dfid -> lifecycle
+    // Circuit access reports and their review schedules
+    access_reports: HashMap<Uuid, CircuitAccessReport>, // report_id -> report
+    access_review_schedules: HashMap<Uuid, AccessReviewSchedule>, // circuit_id -> schedule
+    // Write-ahead log of adapter writes
+    adapter_intents: HashMap<Uuid, AdapterIntent>, // intent_id -> intent
+    // Circuit-scoped partner tokens and their usage logs
+    partner_tokens: HashMap<Uuid, PartnerToken>, // token_id -> token
+    partner_token_usage: HashMap<Uuid, Vec<PartnerTokenUsage>>, // token_id -> usage, oldest first
+    circuit_comments: HashMap<Uuid, CircuitComment>,
+    data_quality_reports: HashMap<Uuid, DataQualityReport>, // report_id -> report
+    metric_definitions: HashMap<Uuid, MetricDefinition>,    // metric_id -> definition
+    zk_setup_artifacts: HashMap<String, crate::zk_proof_engine::ZkSetupArtifact>, // artifact_id -> keys
+    zk_proof_revocations: HashMap<Uuid, crate::zk_proof_engine::ProofRevocation>, // proof_id -> revocation
+    data_lake_gc_reports: Vec<crate::types::DataLakeGcReport>,                    // oldest first
+    device_backups: HashMap<Uuid, crate::types::DeviceBackup>, // backup_id -> backup
+    federation_peers: HashMap<Uuid, crate::types::FederationPeer>, // peer_id -> peer
+    federation_provenance: HashMap<String, crate::types::FederationProvenance>, // record key -> provenance
+    federation_conflicts: HashMap<Uuid, crate::types::FederationConflict>, // conflict_id -> conflict
+    item_attribute_commitments: HashMap<String, crate::types::ItemAttributeCommitments>, // dfid -> current commitments
+    selective_disclosures: HashMap<Uuid, crate::types::SelectiveDisclosure>, // disclosure_id -> disclosure
+    custom_circuit_definitions: HashMap<String, crate::zk_proof_engine::CustomCircuitDefinition>, // circuit_id -> definition
+    zk_proof_jobs: HashMap<Uuid, crate::zk_proof_engine::ProofJob>, // job_id -> job
+    workspace_usage_windows: HashMap<(String, DateTime<Utc>), crate::types::WorkspaceUsageWindow>, // (workspace_id, hour) -> usage
+    usage_anomaly_baselines: HashMap<String, crate::types::UsageAnomalyBaseline>, // workspace_id -> baseline
+    usage_anomalies: HashMap<Uuid, crate::types::UsageAnomaly>, // anomaly_id -> anomaly
+    proof_share_links: HashMap<Uuid, crate::types::ProofShareLink>, // share_id -> link
+    workspace_dedup_configs: HashMap<String, crate::types::WorkspaceDedupConfig>, // workspace_id -> config
+    sensor_series: HashMap<(String, String), crate::types::SensorSeries>, // (dfid, series) -> series
+    sensor_chunks: HashMap<(String, String, DateTime<Utc>), crate::types::SensorChunk>, // (dfid, series, hour) -> readings
+    sensor_rollups: HashMap<
+        (
+            String,
+            String,
+            crate::types::SensorResolution,
+            DateTime<Utc>,
+        ),
+        crate::types::SensorRollup,
+    >, // (dfid, series, resolution, bucket) -> rollup
+    workspace_bucket_configs: HashMap<String, crate::types::WorkspaceBucketConfig>, // workspace_id -> bucket
+    dfid_sequence_leases: HashMap<String, u64>, // YYYYMMDD -> next unleased sequence
+    reverification_jobs: HashMap<Uuid, ReverificationJob>,
+    merge_proposals: HashMap<Uuid, MergeProposal>,
+    runbook_hooks: HashMap<Uuid, RunbookHook>,
+    workspace_verification_rules: HashMap<String, WorkspaceVerificationRules>, // workspace_id -> rules
+    dedup_sharing_agreements: HashMap<Uuid, DedupSharingAgreement>,
+    membership_reconciliation_reports: Vec<MembershipReconciliationReport>, // oldest first
 }
 
 pub struct InMemoryStorage {
@@ -619,14 +1395,20 @@ impl InMemoryStorage {
 
 impl StorageBackend for InMemoryStorage {
     fn store_receipt(&self, receipt: &Receipt) -> Result<(), StorageError> {
+        self.store_receipts(std::slice::from_ref(receipt))
+    }
+
+    fn store_receipts(&self, receipts: &[Receipt]) -> Result<(), StorageError> {
         self.with_state(|s| {
-            for identifier in &receipt.identifiers {
-                s.identifier_index
-                    .entry(identifier.clone())
-                    .or_default()
-                    .push(receipt.id);
+            for receipt in receipts {
+                for identifier in &receipt.identifiers {
+                    s.identifier_index
+                        .entry(identifier.clone())
+                        .or_default()
+                        .push(receipt.id);
+                }
+                s.receipts.insert(receipt.id, receipt.clone());
             }
-            s.receipts.insert(receipt.id, receipt.clone());
         });
         Ok(())
     }
@@ -670,6 +1452,15 @@ impl StorageBackend for InMemoryStorage {
         Ok(())
     }
 
+    fn store_data_lake_entries(&self, entries: &[DataLakeEntry]) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            for entry in entries {
+                s.data_lake_entries.insert(entry.entry_id, entry.clone());
+            }
+        });
+        Ok(())
+    }
+
     fn get_data_lake_entry(&self, entry_id: &Uuid) -> Result<Option<DataLakeEntry>, StorageError> {
         Ok(self.with_state(|s| s.data_lake_entries.get(entry_id).cloned()))
     }
@@ -700,7 +1491,10 @@ impl StorageBackend for InMemoryStorage {
 
     // Items operations
     fn store_item(&self, item: &Item) -> Result<(), StorageError> {
-        self.with_state(|s| s.items.insert(item.dfid.clone(), item.clone()));
+        self.with_state(|s| {
+            s.item_search_index.index(item);
+            s.items.insert(item.dfid.clone(), item.clone())
+        });
         Ok(())
     }
 
@@ -709,7 +1503,10 @@ impl StorageBackend for InMemoryStorage {
     }
 
     fn update_item(&self, item: &Item) -> Result<(), StorageError> {
-        self.with_state(|s| s.items.insert(item.dfid.clone(), item.clone()));
+        self.with_state(|s| {
+            s.item_search_index.index(item);
+            s.items.insert(item.dfid.clone(), item.clone())
+        });
         Ok(())
     }
 
@@ -740,7 +1537,10 @@ impl StorageBackend for InMemoryStorage {
     }
 
     fn delete_item(&self, dfid: &str) -> Result<(), StorageError> {
-        self.with_state(|s| s.items.remove(dfid));
+        self.with_state(|s| {
+            s.item_search_index.remove(dfid);
+            s.items.remove(dfid)
+        });
         Ok(())
     }
 
@@ -822,8 +1622,25 @@ impl StorageBackend for InMemoryStorage {
 
     // Event operations
     fn store_event(&self, event: &Event) -> Result<(), StorageError> {
-        self.with_state(|s| s.events.insert(event.event_id, event.clone()));
-        Ok(())
+        self.store_events(std::slice::from_ref(event))
+    }
+
+    fn store_events(&self, events: &[Event]) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            #[cfg(test)]
+            if let Some(budget) = s.event_write_budget.as_mut() {
+                if events.len() > *budget {
+                    return Err(StorageError::WriteError(
+                        "Injected event write failure".to_string(),
+                    ));
+                }
+                *budget -= events.len();
+            }
+            for event in events {
+                s.events.insert(event.event_id, event.clone());
+            }
+            Ok(())
+        })
     }
 
     fn get_event(&self, event_id: &Uuid) -> Result<Option<Event>, StorageError> {
@@ -894,7 +1711,8 @@ impl StorageBackend for InMemoryStorage {
         Ok(self.with_state(|s| {
             s.events
                 .values()
-                .find(|event| event.content_hash == content_hash)
+                .filter(|event| event.content_hash == content_hash)
+                .max_by_key(|event| event.timestamp)
                 .cloned()
         }))
     }
@@ -944,6 +1762,29 @@ impl StorageBackend for InMemoryStorage {
         }))
     }
 
+    fn get_archived_circuits_for_member(
+        &self,
+        member_id: &str,
+    ) -> Result<Vec<Circuit>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.circuits
+                .values()
+                .filter(|circuit| circuit.is_archived() && circuit.get_member(member_id).is_some())
+                .cloned()
+                .collect()
+        }))
+    }
+
+    fn get_child_circuits(&self, parent_id: &Uuid) -> Result<Vec<Circuit>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.circuits
+                .values()
+                .filter(|circuit| circuit.parent_circuit_id == Some(*parent_id))
+                .cloned()
+                .collect()
+        }))
+    }
+
     // Circuit Operation operations
     fn store_circuit_operation(&self, operation: &CircuitOperation) -> Result<(), StorageError> {
         self.with_state(|s| {
@@ -1119,6 +1960,7 @@ impl StorageBackend for InMemoryStorage {
                     PendingReason::CrossSystemConflict { .. } => {
                         reason_type == "CrossSystemConflict"
                     }
+                    PendingReason::RoutedByRule { .. } => reason_type == "RoutedByRule",
                 })
                 .cloned()
                 .collect()
@@ -1246,6 +2088,10 @@ impl StorageBackend for InMemoryStorage {
             events.retain(|e| e.timestamp <= end_date);
         }
 
+        if let Some(filter) = &query.filter {
+            events.retain(|e| filter.matches(e));
+        }
+
         // Apply sorting
         if let Some(sort_by) = &query.sort_by {
             match sort_by {
@@ -1775,6 +2621,8 @@ impl StorageBackend for InMemoryStorage {
                 CircuitType::QualityGrade => "quality_grade",
                 CircuitType::OwnershipProof => "ownership_proof",
                 CircuitType::TimestampFreshness => "timestamp_freshness",
+                CircuitType::ColdChainCompliance => "cold_chain_compliance",
+                CircuitType::CarbonFootprintThreshold => "carbon_footprint_threshold",
                 CircuitType::Custom(name) => name,
             };
             *proof_types.entry(type_name.to_string()).or_insert(0u64) += 1;
@@ -2306,26 +3154,26 @@ impl StorageBackend for InMemoryStorage {
         }))
     }
 
-    // System Statistics operations (stub implementations for now)
+    // System Statistics operations
     fn get_system_statistics(&self) -> Result<SystemStatistics, StorageError> {
-        Ok(SystemStatistics {
-            total_users: 0,
-            active_users_24h: 0,
-            active_users_30d: 0,
-            total_items: 0,
-            total_circuits: 0,
-            total_storage_operations: 0,
-            credits_consumed_24h: 0,
-            tier_distribution: HashMap::new(),
-            adapter_usage_stats: HashMap::new(),
-            generated_at: Utc::now(),
-        })
+        Ok(self.with_state(|s| {
+            let users: Vec<UserAccount> = s.user_accounts.values().cloned().collect();
+            SystemStatistics::tally(
+                &users,
+                s.credit_transactions.values(),
+                s.storage_histories
+                    .values()
+                    .flat_map(|history| &history.storage_records),
+                s.items.len() as i64,
+                s.circuits.len() as i64,
+                Utc::now(),
+            )
+        }))
     }
 
-    fn update_system_statistics(&self, _stats: &SystemStatistics) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "System statistics operations not yet implemented".to_string(),
-        ))
+    fn update_system_statistics(&self, stats: &SystemStatistics) -> Result<(), StorageError> {
+        self.with_state(|s| s.system_statistics = Some(stats.clone()));
+        Ok(())
     }
 
     // Notification operations
@@ -2726,18 +3574,13 @@ impl StorageBackend for InMemoryStorage {
         &self,
         snapshot: &crate::snapshot_types::StateSnapshot,
     ) -> Result<(), StorageError> {
-        use crate::snapshot_types::SnapshotEntityType;
-
         self.with_state(|s| {
             // Store snapshot by its ID
             s.snapshots
                 .insert(snapshot.snapshot_id.clone(), snapshot.clone());
 
             // Index by entity
-            let entity_type_str = match snapshot.entity_type {
-                SnapshotEntityType::Item => "item".to_string(),
-                SnapshotEntityType::Circuit => "circuit".to_string(),
-            };
+            let entity_type_str = snapshot.entity_type.to_string();
             let key = (entity_type_str, snapshot.entity_id.clone());
             s.snapshots_by_entity
                 .entry(key)
@@ -2759,13 +3602,8 @@ impl StorageBackend for InMemoryStorage {
         entity_type: crate::snapshot_types::SnapshotEntityType,
         entity_id: &str,
     ) -> Result<Vec<crate::snapshot_types::StateSnapshot>, StorageError> {
-        use crate::snapshot_types::SnapshotEntityType;
-
         Ok(self.with_state(|s| {
-            let entity_type_str = match entity_type {
-                SnapshotEntityType::Item => "item".to_string(),
-                SnapshotEntityType::Circuit => "circuit".to_string(),
-            };
+            let entity_type_str = entity_type.to_string();
             let key = (entity_type_str, entity_id.to_string());
 
             if let Some(snapshot_ids) = s.snapshots_by_entity.get(&key) {
@@ -2810,13 +3648,8 @@ impl StorageBackend for InMemoryStorage {
         entity_type: crate::snapshot_types::SnapshotEntityType,
         entity_id: &str,
     ) -> Result<u64, StorageError> {
-        use crate::snapshot_types::SnapshotEntityType;
-
         Ok(self.with_state(|s| {
-            let entity_type_str = match entity_type {
-                SnapshotEntityType::Item => "item".to_string(),
-                SnapshotEntityType::Circuit => "circuit".to_string(),
-            };
+            let entity_type_str = entity_type.to_string();
             let key = (entity_type_str, entity_id.to_string());
 
             s.snapshots_by_entity
@@ -2825,3472 +3658,9532 @@ impl StorageBackend for InMemoryStorage {
                 .unwrap_or(0)
         }))
     }
-}
-impl StorageBackend for Arc<Mutex<InMemoryStorage>> {
-    fn store_receipt(&self, receipt: &Receipt) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_receipt(receipt)
-    }
 
-    fn get_receipt(&self, id: &Uuid) -> Result<Option<Receipt>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_receipt(id)
+    // Organization directory operations
+    fn store_organization_profile(
+        &self,
+        profile: &OrganizationProfile,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.organization_profiles
+                .insert(profile.user_id.clone(), profile.clone());
+        });
+        Ok(())
     }
 
-    fn find_receipts_by_identifier(
+    fn get_organization_profile(
         &self,
-        identifier: &Identifier,
-    ) -> Result<Vec<Receipt>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.find_receipts_by_identifier(identifier)
+        user_id: &str,
+    ) -> Result<Option<OrganizationProfile>, StorageError> {
+        Ok(self.with_state(|s| s.organization_profiles.get(user_id).cloned()))
     }
 
-    fn list_receipts(&self) -> Result<Vec<Receipt>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_receipts()
+    fn list_organization_profiles(&self) -> Result<Vec<OrganizationProfile>, StorageError> {
+        Ok(self.with_state(|s| s.organization_profiles.values().cloned().collect()))
     }
 
-    fn store_log(&self, log: &LogEntry) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_log(log)
+    fn delete_organization_profile(&self, user_id: &str) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.organization_profiles.remove(user_id);
+        });
+        Ok(())
     }
 
-    fn get_logs(&self) -> Result<Vec<LogEntry>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_logs()
+    // Attestation operations
+    fn store_attestation(&self, attestation: &Attestation) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.attestations
+                .insert(attestation.attestation_id, attestation.clone());
+        });
+        Ok(())
     }
 
-    fn store_data_lake_entry(&self, entry: &DataLakeEntry) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_data_lake_entry(entry)
+    fn get_attestation(&self, attestation_id: &Uuid) -> Result<Option<Attestation>, StorageError> {
+        Ok(self.with_state(|s| s.attestations.get(attestation_id).cloned()))
     }
 
-    fn get_data_lake_entry(&self, entry_id: &Uuid) -> Result<Option<DataLakeEntry>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_data_lake_entry(entry_id)
+    fn list_attestations_for_subject(
+        &self,
+        subject_id: &str,
+    ) -> Result<Vec<Attestation>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.attestations
+                .values()
+                .filter(|a| a.subject_id == subject_id)
+                .cloned()
+                .collect()
+        }))
     }
 
-    fn update_data_lake_entry(&self, entry: &DataLakeEntry) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_data_lake_entry(entry)
+    fn list_attestations_by_attester(
+        &self,
+        attester_id: &str,
+    ) -> Result<Vec<Attestation>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.attestations
+                .values()
+                .filter(|a| a.attester_id == attester_id)
+                .cloned()
+                .collect()
+        }))
     }
 
-    fn get_data_lake_entries_by_status(
-        &self,
-        status: ProcessingStatus,
-    ) -> Result<Vec<DataLakeEntry>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_data_lake_entries_by_status(status)
+    // Saved audit query operations
+    fn store_saved_audit_query(&self, query: &SavedAuditQuery) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.saved_audit_queries.insert(query.query_id, query.clone());
+        });
+        Ok(())
     }
 
-    fn list_data_lake_entries(&self) -> Result<Vec<DataLakeEntry>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_data_lake_entries()
+    fn get_saved_audit_query(
+        &self,
+        query_id: &Uuid,
+    ) -> Result<Option<SavedAuditQuery>, StorageError> {
+        Ok(self.with_state(|s| s.saved_audit_queries.get(query_id).cloned()))
     }
 
-    fn store_item(&self, item: &Item) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_item(item)
+    fn list_saved_audit_queries(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<SavedAuditQuery>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.saved_audit_queries
+                .values()
+                .filter(|q| q.owner_id == owner_id)
+                .cloned()
+                .collect()
+        }))
     }
 
-    fn get_item_by_dfid(&self, dfid: &str) -> Result<Option<Item>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_item_by_dfid(dfid)
+    fn delete_saved_audit_query(&self, query_id: &Uuid) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.saved_audit_queries.remove(query_id);
+        });
+        Ok(())
     }
 
-    fn update_item(&self, item: &Item) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_item(item)
+    // Preview environment operations
+    fn store_preview_environment(&self, preview: &PreviewEnvironment) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.preview_environments
+                .insert(preview.preview_id, preview.clone());
+        });
+        Ok(())
     }
 
-    fn list_items(&self) -> Result<Vec<Item>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_items()
+    fn get_preview_environment(
+        &self,
+        preview_id: &Uuid,
+    ) -> Result<Option<PreviewEnvironment>, StorageError> {
+        Ok(self.with_state(|s| s.preview_environments.get(preview_id).cloned()))
     }
 
-    fn find_items_by_identifier(&self, identifier: &Identifier) -> Result<Vec<Item>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.find_items_by_identifier(identifier)
+    fn list_preview_environments(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<PreviewEnvironment>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.preview_environments
+                .values()
+                .filter(|p| p.owner_id == owner_id)
+                .cloned()
+                .collect()
+        }))
     }
 
-    fn find_items_by_status(&self, status: ItemStatus) -> Result<Vec<Item>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.find_items_by_status(status)
+    // Paginated listings (ordered by creation timestamp, then id)
+    fn list_items_page(
+        &self,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Item>, StorageError> {
+        Ok(self.with_state(|s| {
+            let mut items: Vec<Item> = s
+                .items
+                .values()
+                .filter(|item| {
+                    after.is_none_or(|cursor| cursor.precedes(item.creation_timestamp, &item.dfid))
+                })
+                .cloned()
+                .collect();
+            items.sort_by(|a, b| {
+                (a.creation_timestamp, &a.dfid).cmp(&(b.creation_timestamp, &b.dfid))
+            });
+            items.truncate(limit);
+            items
+        }))
     }
 
-    fn delete_item(&self, dfid: &str) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.delete_item(dfid)
+    fn list_events_page(
+        &self,
+        dfid: Option<&str>,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Event>, StorageError> {
+        Ok(self.with_state(|s| {
+            let mut events: Vec<(String, Event)> = s
+                .events
+                .values()
+                .filter(|event| dfid.is_none_or(|dfid| event.dfid == dfid))
+                .map(|event| (event.event_id.to_string(), event.clone()))
+                .filter(|(id, event)| {
+                    after.is_none_or(|cursor| cursor.precedes(event.timestamp, id))
+                })
+                .collect();
+            events.sort_by(|(a_id, a), (b_id, b)| (a.timestamp, a_id).cmp(&(b.timestamp, b_id)));
+            events.truncate(limit);
+            events.into_iter().map(|(_, event)| event).collect()
+        }))
     }
 
-    fn store_identifier_mapping(&self, mapping: &IdentifierMapping) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_identifier_mapping(mapping)
+    // Legacy connector operations
+    fn store_connector_config(&self, config: &ConnectorConfig) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.connector_configs
+                .insert(config.connector_id, config.clone());
+        });
+        Ok(())
     }
 
-    fn get_identifier_mappings(
+    fn get_connector_config(
         &self,
-        identifier: &Identifier,
-    ) -> Result<Vec<IdentifierMapping>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_identifier_mappings(identifier)
+        connector_id: &Uuid,
+    ) -> Result<Option<ConnectorConfig>, StorageError> {
+        Ok(self.with_state(|s| s.connector_configs.get(connector_id).cloned()))
     }
 
-    fn update_identifier_mapping(&self, mapping: &IdentifierMapping) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_identifier_mapping(mapping)
+    fn list_connector_configs(&self) -> Result<Vec<ConnectorConfig>, StorageError> {
+        Ok(self.with_state(|s| s.connector_configs.values().cloned().collect()))
     }
 
-    fn list_identifier_mappings(&self) -> Result<Vec<IdentifierMapping>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_identifier_mappings()
+    fn delete_connector_config(&self, connector_id: &Uuid) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.connector_configs.remove(connector_id);
+            s.connector_states.remove(connector_id);
+            s.connector_errors.remove(connector_id);
+        });
+        Ok(())
     }
 
-    fn store_conflict_resolution(&self, conflict: &ConflictResolution) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_conflict_resolution(conflict)
+    fn store_mapping_template(&self, template: &MappingTemplate) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.mapping_templates
+                .insert(template.template_id, template.clone());
+        });
+        Ok(())
     }
 
-    fn get_conflict_resolution(
+    fn get_mapping_template(
         &self,
-        conflict_id: &Uuid,
-    ) -> Result<Option<ConflictResolution>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_conflict_resolution(conflict_id)
+        template_id: &Uuid,
+    ) -> Result<Option<MappingTemplate>, StorageError> {
+        Ok(self.with_state(|s| s.mapping_templates.get(template_id).cloned()))
     }
 
-    fn get_pending_conflicts(&self) -> Result<Vec<ConflictResolution>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_pending_conflicts()
+    fn list_mapping_templates(&self, owner_id: &str) -> Result<Vec<MappingTemplate>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.mapping_templates
+                .values()
+                .filter(|t| t.owner_id == owner_id)
+                .cloned()
+                .collect()
+        }))
     }
 
-    fn store_event(&self, event: &Event) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_event(event)
+    fn store_connector_state(&self, state: &ConnectorState) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.connector_states.insert(state.connector_id, state.clone());
+        });
+        Ok(())
     }
 
-    fn get_event(&self, event_id: &Uuid) -> Result<Option<Event>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_event(event_id)
+    fn get_connector_state(
+        &self,
+        connector_id: &Uuid,
+    ) -> Result<Option<ConnectorState>, StorageError> {
+        Ok(self.with_state(|s| s.connector_states.get(connector_id).cloned()))
     }
 
-    fn update_event(&self, event: &Event) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_event(event)
+    fn store_connector_error(&self, error: &ConnectorRunError) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            let errors = s.connector_errors.entry(error.connector_id).or_default();
+            errors.push(error.clone());
+            if errors.len() > MAX_CONNECTOR_ERRORS {
+                let overflow = errors.len() - MAX_CONNECTOR_ERRORS;
+                errors.drain(..overflow);
+            }
+        });
+        Ok(())
     }
 
-    fn list_events(&self) -> Result<Vec<Event>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_events()
+    fn list_connector_errors(
+        &self,
+        connector_id: &Uuid,
+        limit: usize,
+    ) -> Result<Vec<ConnectorRunError>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.connector_errors
+                .get(connector_id)
+                .map(|errors| errors.iter().rev().take(limit).cloned().collect())
+                .unwrap_or_default()
+        }))
     }
 
-    fn get_events_by_dfid(&self, dfid: &str) -> Result<Vec<Event>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_events_by_dfid(dfid)
+    fn delete_mapping_template(&self, template_id: &Uuid) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.mapping_templates.remove(template_id);
+        });
+        Ok(())
     }
 
-    fn get_events_by_type(&self, event_type: EventType) -> Result<Vec<Event>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_events_by_type(event_type)
+    // Circuit change feed operations
+    fn append_change_record(&self, record: &ChangeRecord) -> Result<ChangeRecord, StorageError> {
+        Ok(self.with_state(|s| {
+            let feed = s.change_records.entry(record.circuit_id).or_default();
+            let mut record = record.clone();
+            record.sequence = feed.back().map(|last| last.sequence).unwrap_or(0) + 1;
+            feed.push_back(record.clone());
+            if feed.len() > MAX_CHANGE_RECORDS {
+                feed.pop_front();
+            }
+            record
+        }))
     }
 
-    fn get_events_by_visibility(
+    fn list_change_records(
         &self,
-        visibility: EventVisibility,
-    ) -> Result<Vec<Event>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_events_by_visibility(visibility)
+        circuit_id: &Uuid,
+        after_sequence: u64,
+        limit: usize,
+    ) -> Result<Vec<ChangeRecord>, StorageError> {
+        Ok(self.with_state(|s| {
+            let Some(feed) = s.change_records.get(circuit_id) else {
+                return Vec::new();
+            };
+            let first = feed.front().map(|r| r.sequence).unwrap_or(1);
+            let skip = (after_sequence + 1).saturating_sub(first) as usize;
+            feed.iter().skip(skip).take(limit).cloned().collect()
+        }))
     }
 
-    fn get_events_in_time_range(
-        &self,
-        start: DateTime<Utc>,
-        end: DateTime<Utc>,
-    ) -> Result<Vec<Event>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_events_in_time_range(start, end)
+    fn get_latest_change_sequence(&self, circuit_id: &Uuid) -> Result<u64, StorageError> {
+        Ok(self.with_state(|s| {
+            s.change_records
+                .get(circuit_id)
+                .and_then(|feed| feed.back())
+                .map(|r| r.sequence)
+                .unwrap_or(0)
+        }))
     }
 
-    fn get_event_by_content_hash(&self, content_hash: &str) -> Result<Option<Event>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_event_by_content_hash(content_hash)
+    fn store_change_feed_subscription(
+        &self,
+        subscription: &ChangeFeedSubscription,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.change_feed_subscriptions
+                .insert(subscription.subscription_id, subscription.clone());
+        });
+        Ok(())
     }
 
-    fn store_circuit(&self, circuit: &Circuit) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_circuit(circuit)
+    fn get_change_feed_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<Option<ChangeFeedSubscription>, StorageError> {
+        Ok(self.with_state(|s| s.change_feed_subscriptions.get(subscription_id).cloned()))
     }
 
-    fn get_circuit(&self, circuit_id: &Uuid) -> Result<Option<Circuit>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_circuit(circuit_id)
+    fn list_change_feed_subscriptions(
+        &self,
+        circuit_id: Option<&Uuid>,
+    ) -> Result<Vec<ChangeFeedSubscription>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.change_feed_subscriptions
+                .values()
+                .filter(|sub| circuit_id.is_none_or(|id| sub.circuit_id == *id))
+                .cloned()
+                .collect()
+        }))
     }
 
-    fn update_circuit(&self, circuit: &Circuit) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_circuit(circuit)
+    fn delete_change_feed_subscription(&self, subscription_id: &Uuid) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.change_feed_subscriptions.remove(subscription_id);
+        });
+        Ok(())
     }
 
-    fn list_circuits(&self) -> Result<Vec<Circuit>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_circuits()
-    }
-
-    fn get_circuits_for_member(&self, member_id: &str) -> Result<Vec<Circuit>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_circuits_for_member(member_id)
+    // Item search
+    fn search_items(
+        &self,
+        query: &ItemSearchQuery,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Item>, StorageError> {
+        Ok(self.with_state(|s| {
+            let circuit_dfids = query.circuit_id.map(|circuit_id| {
+                s.circuit_items
+                    .keys()
+                    .filter(|(id, _)| *id == circuit_id)
+                    .map(|(_, dfid)| dfid.clone())
+                    .collect()
+            });
+            s.item_search_index
+                .search(query, circuit_dfids, after, limit)
+                .into_iter()
+                .filter_map(|dfid| s.items.get(&dfid).cloned())
+                .collect()
+        }))
     }
 
-    fn store_circuit_operation(&self, operation: &CircuitOperation) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_circuit_operation(operation)
+    // Announcements
+    fn store_announcement(&self, announcement: &Announcement) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.announcements
+                .insert(announcement.announcement_id, announcement.clone());
+        });
+        Ok(())
     }
 
-    fn get_circuit_operation(
+    fn get_announcement(
         &self,
-        operation_id: &Uuid,
-    ) -> Result<Option<CircuitOperation>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_circuit_operation(operation_id)
+        announcement_id: &Uuid,
+    ) -> Result<Option<Announcement>, StorageError> {
+        Ok(self.with_state(|s| s.announcements.get(announcement_id).cloned()))
     }
 
-    fn update_circuit_operation(&self, operation: &CircuitOperation) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_circuit_operation(operation)
+    fn list_announcements(&self) -> Result<Vec<Announcement>, StorageError> {
+        let mut announcements: Vec<Announcement> =
+            self.with_state(|s| s.announcements.values().cloned().collect());
+        announcements.sort_by_key(|a| std::cmp::Reverse(a.publish_at));
+        Ok(announcements)
     }
 
-    fn get_circuit_operations(
+    // Workspace engagement snapshots
+    fn store_workspace_engagement(
         &self,
-        circuit_id: &Uuid,
-    ) -> Result<Vec<CircuitOperation>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_circuit_operations(circuit_id)
+        engagement: &WorkspaceEngagement,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.workspace_engagement
+                .insert(engagement.workspace_id.clone(), engagement.clone());
+        });
+        Ok(())
     }
 
-    fn store_item_share(&self, share: &ItemShare) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_item_share(share)
+    fn get_workspace_engagement(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<WorkspaceEngagement>, StorageError> {
+        Ok(self.with_state(|s| s.workspace_engagement.get(workspace_id).cloned()))
     }
 
-    fn get_item_share(&self, share_id: &str) -> Result<Option<ItemShare>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_item_share(share_id)
+    // Data export jobs
+    fn store_data_export_job(&self, job: &DataExportJob) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.data_export_jobs.insert(job.job_id, job.clone());
+        });
+        Ok(())
     }
 
-    fn get_shares_for_user(&self, user_id: &str) -> Result<Vec<ItemShare>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_shares_for_user(user_id)
+    fn get_data_export_job(&self, job_id: &Uuid) -> Result<Option<DataExportJob>, StorageError> {
+        Ok(self.with_state(|s| s.data_export_jobs.get(job_id).cloned()))
     }
 
-    fn get_shares_for_item(&self, dfid: &str) -> Result<Vec<ItemShare>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_shares_for_item(dfid)
+    fn list_data_export_jobs(
+        &self,
+        requested_by: &str,
+    ) -> Result<Vec<DataExportJob>, StorageError> {
+        let mut jobs: Vec<DataExportJob> = self.with_state(|s| {
+            s.data_export_jobs
+                .values()
+                .filter(|job| job.requested_by == requested_by)
+                .cloned()
+                .collect()
+        });
+        jobs.sort_by_key(|job| std::cmp::Reverse(job.created_at));
+        Ok(jobs)
     }
 
-    fn is_item_shared_with_user(&self, dfid: &str, user_id: &str) -> Result<bool, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.is_item_shared_with_user(dfid, user_id)
+    // SLA windows
+    fn store_sla_window(&self, window: &SlaWindow) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.sla_windows
+                .insert((window.component, window.hour_start), window.clone());
+        });
+        Ok(())
     }
 
-    fn delete_item_share(&self, share_id: &str) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.delete_item_share(share_id)
+    fn get_sla_window(
+        &self,
+        component: SlaComponent,
+        hour_start: DateTime<Utc>,
+    ) -> Result<Option<SlaWindow>, StorageError> {
+        Ok(self.with_state(|s| s.sla_windows.get(&(component, hour_start)).cloned()))
     }
 
-    fn store_activity(&self, activity: &Activity) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_activity(activity)
+    fn list_sla_windows(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<SlaWindow>, StorageError> {
+        let mut windows: Vec<SlaWindow> = self.with_state(|s| {
+            s.sla_windows
+                .values()
+                .filter(|w| w.hour_start >= from && w.hour_start < to)
+                .cloned()
+                .collect()
+        });
+        windows.sort_by_key(|w| (w.hour_start, w.component));
+        Ok(windows)
     }
 
-    fn get_activities_for_user(&self, user_id: &str) -> Result<Vec<Activity>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_activities_for_user(user_id)
+    // Event schemas
+    fn store_event_schema(&self, schema: &EventSchema) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.event_schemas.insert(schema.schema_id, schema.clone());
+        });
+        Ok(())
     }
 
-    fn get_activities_for_circuit(&self, circuit_id: &Uuid) -> Result<Vec<Activity>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_activities_for_circuit(circuit_id)
+    fn list_event_schemas(&self) -> Result<Vec<EventSchema>, StorageError> {
+        let mut schemas: Vec<EventSchema> =
+            self.with_state(|s| s.event_schemas.values().cloned().collect());
+        schemas.sort_by_key(|schema| (schema.circuit_id, schema.version));
+        Ok(schemas)
     }
 
-    fn get_all_activities(&self) -> Result<Vec<Activity>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_all_activities()
+    // Maintenance mode
+    fn store_maintenance_mode(&self, mode: &MaintenanceMode) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.maintenance_mode = Some(mode.clone());
+        });
+        Ok(())
     }
 
-    fn store_circuit_item(&self, circuit_item: &CircuitItem) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_circuit_item(circuit_item)
+    fn get_maintenance_mode(&self) -> Result<Option<MaintenanceMode>, StorageError> {
+        Ok(self.with_state(|s| s.maintenance_mode.clone()))
     }
 
-    fn get_circuit_items(&self, circuit_id: &Uuid) -> Result<Vec<CircuitItem>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_circuit_items(circuit_id)
+    // Managed keys
+    fn store_managed_key(&self, key: &ManagedKey) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.managed_keys.insert(key.key_id, key.clone());
+        });
+        Ok(())
     }
 
-    fn remove_circuit_item(&self, circuit_id: &Uuid, dfid: &str) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.remove_circuit_item(circuit_id, dfid)
+    fn get_managed_key(&self, key_id: &Uuid) -> Result<Option<ManagedKey>, StorageError> {
+        Ok(self.with_state(|s| s.managed_keys.get(key_id).cloned()))
     }
 
-    fn store_audit_event(&self, event: &AuditEvent) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_audit_event(event)
+    fn list_managed_keys(&self) -> Result<Vec<ManagedKey>, StorageError> {
+        let mut keys: Vec<ManagedKey> =
+            self.with_state(|s| s.managed_keys.values().cloned().collect());
+        keys.sort_by_key(|key| key.created_at);
+        Ok(keys)
     }
 
-    fn get_audit_event(&self, event_id: &Uuid) -> Result<Option<AuditEvent>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_audit_event(event_id)
+    // Custom event types
+    fn store_custom_event_type(&self, event_type: &CustomEventType) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.custom_event_types.insert(
+                (event_type.circuit_id, event_type.name.clone()),
+                event_type.clone(),
+            );
+        });
+        Ok(())
     }
 
-    fn query_audit_events(&self, query: &AuditQuery) -> Result<Vec<AuditEvent>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.query_audit_events(query)
+    fn list_custom_event_types(&self) -> Result<Vec<CustomEventType>, StorageError> {
+        let mut event_types: Vec<CustomEventType> =
+            self.with_state(|s| s.custom_event_types.values().cloned().collect());
+        event_types.sort_by(|a, b| (a.circuit_id, &a.name).cmp(&(b.circuit_id, &b.name)));
+        Ok(event_types)
     }
 
-    fn list_audit_events(&self) -> Result<Vec<AuditEvent>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_audit_events()
+    fn delete_custom_event_type(
+        &self,
+        circuit_id: &Uuid,
+        name: &str,
+    ) -> Result<bool, StorageError> {
+        Ok(self.with_state(|s| {
+            s.custom_event_types
+                .remove(&(*circuit_id, name.to_string()))
+                .is_some()
+        }))
     }
 
-    fn get_audit_events_by_user(&self, user_id: &str) -> Result<Vec<AuditEvent>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_audit_events_by_user(user_id)
+    // Document notarizations
+    fn store_notarization(&self, notarization: &DocumentNotarization) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.notarizations
+                .insert(notarization.notarization_id, notarization.clone());
+        });
+        Ok(())
     }
 
-    fn get_audit_events_by_type(
+    fn get_notarization(
         &self,
-        event_type: AuditEventType,
-    ) -> Result<Vec<AuditEvent>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_audit_events_by_type(event_type)
+        notarization_id: &Uuid,
+    ) -> Result<Option<DocumentNotarization>, StorageError> {
+        Ok(self.with_state(|s| s.notarizations.get(notarization_id).cloned()))
     }
 
-    fn get_audit_events_by_severity(
-        &self,
-        severity: AuditSeverity,
-    ) -> Result<Vec<AuditEvent>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_audit_events_by_severity(severity)
+    fn list_notarizations(&self) -> Result<Vec<DocumentNotarization>, StorageError> {
+        let mut notarizations: Vec<DocumentNotarization> =
+            self.with_state(|s| s.notarizations.values().cloned().collect());
+        notarizations.sort_by_key(|notarization| notarization.submitted_at);
+        Ok(notarizations)
     }
 
-    fn get_audit_events_in_time_range(
+    fn store_notarization_batch(&self, batch: &NotarizationBatch) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.notarization_batches.insert(batch.batch_id, batch.clone());
+        });
+        Ok(())
+    }
+
+    fn get_notarization_batch(
         &self,
-        start: DateTime<Utc>,
-        end: DateTime<Utc>,
-    ) -> Result<Vec<AuditEvent>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_audit_events_in_time_range(start, end)
+        batch_id: &Uuid,
+    ) -> Result<Option<NotarizationBatch>, StorageError> {
+        Ok(self.with_state(|s| s.notarization_batches.get(batch_id).cloned()))
     }
 
-    fn sync_audit_events(&self, events: Vec<AuditEvent>) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.sync_audit_events(events)
+    // Enrichment policies
+    fn store_enrichment_policy(&self, policy: &EnrichmentPolicy) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.enrichment_policies
+                .insert(policy.workspace_id.clone(), policy.clone());
+        });
+        Ok(())
     }
 
-    fn store_security_incident(&self, incident: &SecurityIncident) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_security_incident(incident)
+    fn get_enrichment_policy(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<EnrichmentPolicy>, StorageError> {
+        Ok(self.with_state(|s| s.enrichment_policies.get(workspace_id).cloned()))
     }
 
-    fn get_security_incident(
+    // Event signing keys
+    fn store_event_signing_key(&self, key: &EventSigningKey) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.event_signing_keys.insert(key.key_id, key.clone());
+        });
+        Ok(())
+    }
+
+    fn get_event_signing_key(
         &self,
-        incident_id: &Uuid,
-    ) -> Result<Option<SecurityIncident>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_security_incident(incident_id)
+        key_id: &Uuid,
+    ) -> Result<Option<EventSigningKey>, StorageError> {
+        Ok(self.with_state(|s| s.event_signing_keys.get(key_id).cloned()))
     }
 
-    fn update_security_incident(&self, incident: &SecurityIncident) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_security_incident(incident)
+    fn list_event_signing_keys(&self) -> Result<Vec<EventSigningKey>, StorageError> {
+        let mut keys: Vec<EventSigningKey> =
+            self.with_state(|s| s.event_signing_keys.values().cloned().collect());
+        keys.sort_by_key(|key| key.registered_at);
+        Ok(keys)
     }
 
-    fn list_security_incidents(&self) -> Result<Vec<SecurityIncident>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_security_incidents()
+    // Anchoring SLAs, fee samples and anchoring spend
+    fn store_anchoring_sla(&self, sla: &AnchoringSla) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.anchoring_slas.insert(sla.circuit_id, sla.clone());
+        });
+        Ok(())
     }
 
-    fn get_incidents_by_severity(
-        &self,
-        severity: AuditSeverity,
-    ) -> Result<Vec<SecurityIncident>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_incidents_by_severity(severity)
+    fn get_anchoring_sla(&self, circuit_id: &Uuid) -> Result<Option<AnchoringSla>, StorageError> {
+        Ok(self.with_state(|s| s.anchoring_slas.get(circuit_id).cloned()))
     }
 
-    fn get_open_incidents(&self) -> Result<Vec<SecurityIncident>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_open_incidents()
+    fn store_fee_sample(&self, sample: &FeeSample) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.fee_samples.insert(sample.network, sample.clone());
+        });
+        Ok(())
     }
 
-    fn get_incidents_by_assignee(
+    fn get_latest_fee_sample(
         &self,
-        assignee: &str,
-    ) -> Result<Vec<SecurityIncident>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_incidents_by_assignee(assignee)
+        network: &AnchoringNetwork,
+    ) -> Result<Option<FeeSample>, StorageError> {
+        Ok(self.with_state(|s| s.fee_samples.get(network).cloned()))
     }
 
-    fn store_compliance_report(&self, report: &ComplianceReport) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_compliance_report(report)
+    fn store_anchoring_cost_record(
+        &self,
+        record: &AnchoringCostRecord,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| s.anchoring_costs.push(record.clone()));
+        Ok(())
     }
 
-    fn get_compliance_report(
+    fn list_anchoring_cost_records(
         &self,
-        report_id: &Uuid,
-    ) -> Result<Option<ComplianceReport>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_compliance_report(report_id)
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AnchoringCostRecord>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.anchoring_costs
+                .iter()
+                .filter(|record| record.anchored_at >= since)
+                .cloned()
+                .collect()
+        }))
     }
 
-    fn update_compliance_report(&self, report: &ComplianceReport) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_compliance_report(report)
+    // Event causality
+    fn get_events_caused_by(&self, event_id: &Uuid) -> Result<Vec<Event>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.events
+                .values()
+                .filter(|event| event.caused_by == Some(*event_id))
+                .cloned()
+                .collect()
+        }))
     }
 
-    fn list_compliance_reports(&self) -> Result<Vec<ComplianceReport>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_compliance_reports()
+    fn get_events_by_correlation_id(
+        &self,
+        correlation_id: &Uuid,
+    ) -> Result<Vec<Event>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.events
+                .values()
+                .filter(|event| event.correlation_id == Some(*correlation_id))
+                .cloned()
+                .collect()
+        }))
     }
 
-    fn get_reports_by_type(
+    // Item lifecycles
+    fn store_lifecycle_definition(
         &self,
-        report_type: &str,
-    ) -> Result<Vec<ComplianceReport>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_reports_by_type(report_type)
+        definition: &LifecycleDefinition,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.lifecycle_definitions
+                .insert(definition.workspace_id.clone(), definition.clone());
+        });
+        Ok(())
     }
 
-    fn get_pending_reports(&self) -> Result<Vec<ComplianceReport>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_pending_reports()
+    fn get_lifecycle_definition(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<LifecycleDefinition>, StorageError> {
+        Ok(self.with_state(|s| s.lifecycle_definitions.get(workspace_id).cloned()))
     }
 
-    fn get_audit_dashboard_metrics(&self) -> Result<AuditDashboardMetrics, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_audit_dashboard_metrics()
+    fn store_item_lifecycle(&self, lifecycle: &ItemLifecycle) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.item_lifecycles
+                .insert(lifecycle.dfid.clone(), lifecycle.clone());
+        });
+        Ok(())
     }
 
-    fn get_event_count_by_time_range(
-        &self,
-        start: DateTime<Utc>,
-        end: DateTime<Utc>,
-    ) -> Result<u64, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_event_count_by_time_range(start, end)
+    fn get_item_lifecycle(&self, dfid: &str) -> Result<Option<ItemLifecycle>, StorageError> {
+        Ok(self.with_state(|s| s.item_lifecycles.get(dfid).cloned()))
     }
 
-    fn store_pending_item(&self, item: &PendingItem) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_pending_item(item)
+    fn list_item_lifecycles(&self, workspace_id: &str) -> Result<Vec<ItemLifecycle>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.item_lifecycles
+                .values()
+                .filter(|lifecycle| lifecycle.workspace_id == workspace_id)
+                .cloned()
+                .collect()
+        }))
     }
 
-    fn get_pending_item(&self, pending_id: &Uuid) -> Result<Option<PendingItem>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_pending_item(pending_id)
+    // Circuit access reviews
+    fn store_access_report(&self, report: &CircuitAccessReport) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.access_reports.insert(report.report_id, report.clone());
+        });
+        Ok(())
     }
 
-    fn list_pending_items(&self) -> Result<Vec<PendingItem>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_pending_items()
+    fn get_access_report(
+        &self,
+        report_id: &Uuid,
+    ) -> Result<Option<CircuitAccessReport>, StorageError> {
+        Ok(self.with_state(|s| s.access_reports.get(report_id).cloned()))
     }
 
-    fn get_pending_items_by_reason(
+    fn list_access_reports(
         &self,
-        reason_type: &str,
-    ) -> Result<Vec<PendingItem>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_pending_items_by_reason(reason_type)
+        circuit_id: &Uuid,
+    ) -> Result<Vec<CircuitAccessReport>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.access_reports
+                .values()
+                .filter(|report| report.circuit_id == *circuit_id)
+                .cloned()
+                .collect()
+        }))
     }
 
-    fn get_pending_items_by_user(&self, user_id: &str) -> Result<Vec<PendingItem>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_pending_items_by_user(user_id)
+    fn delete_access_report(&self, report_id: &Uuid) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.access_reports.remove(report_id);
+        });
+        Ok(())
     }
 
-    fn get_pending_items_by_workspace(
+    fn store_access_review_schedule(
         &self,
-        workspace_id: &str,
-    ) -> Result<Vec<PendingItem>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_pending_items_by_workspace(workspace_id)
+        schedule: &AccessReviewSchedule,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.access_review_schedules
+                .insert(schedule.circuit_id, schedule.clone());
+        });
+        Ok(())
     }
 
-    fn get_pending_items_by_priority(
+    fn get_access_review_schedule(
         &self,
-        priority: PendingPriority,
-    ) -> Result<Vec<PendingItem>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_pending_items_by_priority(priority)
+        circuit_id: &Uuid,
+    ) -> Result<Option<AccessReviewSchedule>, StorageError> {
+        Ok(self.with_state(|s| s.access_review_schedules.get(circuit_id).cloned()))
     }
 
-    fn update_pending_item(&self, item: &PendingItem) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_pending_item(item)
+    fn delete_access_review_schedule(&self, circuit_id: &Uuid) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.access_review_schedules.remove(circuit_id);
+        });
+        Ok(())
     }
 
-    fn delete_pending_item(&self, pending_id: &Uuid) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.delete_pending_item(pending_id)
+    fn list_access_review_schedules(&self) -> Result<Vec<AccessReviewSchedule>, StorageError> {
+        Ok(self.with_state(|s| s.access_review_schedules.values().cloned().collect()))
     }
 
-    fn get_pending_items_requiring_manual_review(&self) -> Result<Vec<PendingItem>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_pending_items_requiring_manual_review()
+    // Adapter write intents
+    fn store_adapter_intent(&self, intent: &AdapterIntent) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.adapter_intents.insert(intent.intent_id, intent.clone());
+        });
+        Ok(())
     }
 
-    fn store_zk_proof(&self, proof: &crate::zk_proof_engine::ZkProof) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_zk_proof(proof)
+    fn get_adapter_intent(&self, intent_id: &Uuid) -> Result<Option<AdapterIntent>, StorageError> {
+        Ok(self.with_state(|s| s.adapter_intents.get(intent_id).cloned()))
     }
 
-    fn get_zk_proof(
-        &self,
-        proof_id: &Uuid,
-    ) -> Result<Option<crate::zk_proof_engine::ZkProof>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_zk_proof(proof_id)
+    fn get_incomplete_adapter_intents(&self) -> Result<Vec<AdapterIntent>, StorageError> {
+        let mut intents: Vec<AdapterIntent> = self.with_state(|s| {
+            s.adapter_intents
+                .values()
+                .filter(|intent| !intent.is_complete())
+                .cloned()
+                .collect()
+        });
+        intents.sort_by_key(|intent| intent.created_at);
+        Ok(intents)
     }
 
-    fn update_zk_proof(&self, proof: &crate::zk_proof_engine::ZkProof) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_zk_proof(proof)
+    // Circuit-scoped partner tokens
+    fn store_partner_token(&self, token: &PartnerToken) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.partner_tokens.insert(token.token_id, token.clone());
+        });
+        Ok(())
     }
 
-    fn query_zk_proofs(
-        &self,
-        query: &crate::api::zk_proofs::ZkProofQuery,
-    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.query_zk_proofs(query)
+    fn get_partner_token(&self, token_id: &Uuid) -> Result<Option<PartnerToken>, StorageError> {
+        Ok(self.with_state(|s| s.partner_tokens.get(token_id).cloned()))
     }
 
-    fn list_zk_proofs(&self) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_zk_proofs()
+    fn get_partner_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PartnerToken>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.partner_tokens
+                .values()
+                .find(|token| token.token_hash == token_hash)
+                .cloned()
+        }))
     }
 
-    fn get_zk_proofs_by_user(
-        &self,
-        user_id: &str,
-    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_zk_proofs_by_user(user_id)
+    fn list_partner_tokens(&self, circuit_id: &Uuid) -> Result<Vec<PartnerToken>, StorageError> {
+        let mut tokens: Vec<PartnerToken> = self.with_state(|s| {
+            s.partner_tokens
+                .values()
+                .filter(|token| token.circuit_id == *circuit_id)
+                .cloned()
+                .collect()
+        });
+        tokens.sort_by_key(|token| token.created_at);
+        Ok(tokens)
     }
 
-    fn get_zk_proofs_by_circuit_type(
-        &self,
-        circuit_type: CircuitType,
-    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_zk_proofs_by_circuit_type(circuit_type)
+    fn record_partner_token_usage(&self, usage: &PartnerTokenUsage) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.partner_token_usage
+                .entry(usage.token_id)
+                .or_default()
+                .push(usage.clone());
+        });
+        Ok(())
     }
 
-    fn get_zk_proofs_by_status(
+    fn list_partner_token_usage(
         &self,
-        status: crate::zk_proof_engine::ProofStatus,
-    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_zk_proofs_by_status(status)
+        token_id: &Uuid,
+        limit: usize,
+    ) -> Result<Vec<PartnerTokenUsage>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.partner_token_usage
+                .get(token_id)
+                .map(|usage| usage.iter().rev().take(limit).cloned().collect())
+                .unwrap_or_default()
+        }))
     }
 
-    fn get_zk_proof_statistics(
-        &self,
-    ) -> Result<crate::api::zk_proofs::ZkProofStatistics, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_zk_proof_statistics()
+    // Circuit comment threads
+    fn store_circuit_comment(&self, comment: &CircuitComment) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.circuit_comments
+                .insert(comment.comment_id, comment.clone());
+        });
+        Ok(())
     }
 
-    fn delete_zk_proof(&self, proof_id: &Uuid) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.delete_zk_proof(proof_id)
+    fn get_circuit_comment(
+        &self,
+        comment_id: &Uuid,
+    ) -> Result<Option<CircuitComment>, StorageError> {
+        Ok(self.with_state(|s| s.circuit_comments.get(comment_id).cloned()))
     }
 
-    fn store_storage_history(&self, history: &ItemStorageHistory) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_storage_history(history)
+    fn list_circuit_comments(
+        &self,
+        circuit_id: &Uuid,
+        target: &CommentTarget,
+    ) -> Result<Vec<CircuitComment>, StorageError> {
+        let mut comments: Vec<CircuitComment> = self.with_state(|s| {
+            s.circuit_comments
+                .values()
+                .filter(|comment| comment.circuit_id == *circuit_id && comment.target == *target)
+                .cloned()
+                .collect()
+        });
+        comments.sort_by_key(|comment| comment.created_at);
+        Ok(comments)
     }
 
-    fn get_storage_history(&self, dfid: &str) -> Result<Option<ItemStorageHistory>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_storage_history(dfid)
+    fn delete_circuit_comment(&self, comment_id: &Uuid) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.circuit_comments.remove(comment_id);
+        });
+        Ok(())
     }
 
-    fn add_storage_record(&self, dfid: &str, record: StorageRecord) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.add_storage_record(dfid, record)
+    // Circuit data quality reports
+    fn store_data_quality_report(&self, report: &DataQualityReport) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.data_quality_reports
+                .insert(report.report_id, report.clone());
+        });
+        Ok(())
     }
 
-    fn add_cid_to_timeline(
+    fn list_data_quality_reports(
         &self,
-        dfid: &str,
-        cid: &str,
-        ipcm_tx: &str,
-        timestamp: i64,
-        network: &str,
-    ) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.add_cid_to_timeline(dfid, cid, ipcm_tx, timestamp, network)
+        circuit_id: &Uuid,
+    ) -> Result<Vec<DataQualityReport>, StorageError> {
+        let mut reports: Vec<DataQualityReport> = self.with_state(|s| {
+            s.data_quality_reports
+                .values()
+                .filter(|report| report.circuit_id == *circuit_id)
+                .cloned()
+                .collect()
+        });
+        reports.sort_by_key(|report| std::cmp::Reverse(report.generated_at));
+        Ok(reports)
     }
 
-    fn get_item_timeline(&self, dfid: &str) -> Result<Vec<TimelineEntry>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_item_timeline(dfid)
+    fn delete_data_quality_report(&self, report_id: &Uuid) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.data_quality_reports.remove(report_id);
+        });
+        Ok(())
     }
 
-    fn get_timeline_by_sequence(
-        &self,
-        dfid: &str,
-        sequence: i32,
-    ) -> Result<Option<TimelineEntry>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_timeline_by_sequence(dfid, sequence)
+    // Dashboard metric definitions
+    fn store_metric_definition(&self, definition: &MetricDefinition) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.metric_definitions
+                .insert(definition.metric_id, definition.clone());
+        });
+        Ok(())
     }
 
-    fn map_event_to_cid(
+    fn get_metric_definition(
         &self,
-        event_id: &Uuid,
-        dfid: &str,
-        cid: &str,
-        sequence: i32,
-    ) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.map_event_to_cid(event_id, dfid, cid, sequence)
+        metric_id: &Uuid,
+    ) -> Result<Option<MetricDefinition>, StorageError> {
+        Ok(self.with_state(|s| s.metric_definitions.get(metric_id).cloned()))
     }
 
-    fn get_event_first_cid(
+    fn list_metric_definitions(
         &self,
-        event_id: &Uuid,
-    ) -> Result<Option<EventCidMapping>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_event_first_cid(event_id)
+        workspace_id: &str,
+    ) -> Result<Vec<MetricDefinition>, StorageError> {
+        let mut definitions: Vec<MetricDefinition> = self.with_state(|s| {
+            s.metric_definitions
+                .values()
+                .filter(|definition| definition.workspace_id == workspace_id)
+                .cloned()
+                .collect()
+        });
+        definitions.sort_by_key(|definition| definition.created_at);
+        Ok(definitions)
     }
 
-    fn get_events_in_cid(&self, cid: &str) -> Result<Vec<EventCidMapping>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_events_in_cid(cid)
+    fn delete_metric_definition(&self, metric_id: &Uuid) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.metric_definitions.remove(metric_id);
+        });
+        Ok(())
     }
 
-    fn update_indexing_progress(
+    // ZK setup artifacts
+    fn store_zk_setup_artifact(
         &self,
-        network: &str,
-        last_ledger: i64,
-        confirmed_ledger: i64,
+        artifact: &crate::zk_proof_engine::ZkSetupArtifact,
     ) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_indexing_progress(network, last_ledger, confirmed_ledger)
+        self.with_state(|s| {
+            s.zk_setup_artifacts
+                .insert(artifact.artifact_id.clone(), artifact.clone());
+        });
+        Ok(())
     }
 
-    fn get_indexing_progress(
+    fn get_zk_setup_artifact(
         &self,
-        network: &str,
-    ) -> Result<Option<IndexingProgress>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_indexing_progress(network)
-    }
-
-    fn increment_events_indexed(&self, network: &str, count: i64) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.increment_events_indexed(network, count)
+        artifact_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::ZkSetupArtifact>, StorageError> {
+        Ok(self.with_state(|s| s.zk_setup_artifacts.get(artifact_id).cloned()))
     }
 
-    fn store_circuit_adapter_config(
+    fn list_zk_setup_artifacts(
         &self,
-        config: &CircuitAdapterConfig,
-    ) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_circuit_adapter_config(config)
+    ) -> Result<Vec<crate::zk_proof_engine::ZkSetupArtifact>, StorageError> {
+        let mut artifacts: Vec<crate::zk_proof_engine::ZkSetupArtifact> =
+            self.with_state(|s| s.zk_setup_artifacts.values().cloned().collect());
+        artifacts.sort_by_key(|artifact| artifact.created_at);
+        Ok(artifacts)
     }
 
-    fn get_circuit_adapter_config(
-        &self,
-        circuit_id: &Uuid,
-    ) -> Result<Option<CircuitAdapterConfig>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_circuit_adapter_config(circuit_id)
+    // Data lake garbage collection
+    fn delete_data_lake_entries(&self, entry_ids: &[Uuid]) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            for entry_id in entry_ids {
+                s.data_lake_entries.remove(entry_id);
+            }
+        });
+        Ok(())
     }
 
-    fn update_circuit_adapter_config(
+    fn delete_receipts(&self, receipt_ids: &[Uuid]) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            for receipt_id in receipt_ids {
+                if let Some(receipt) = s.receipts.remove(receipt_id) {
+                    for identifier in &receipt.identifiers {
+                        if let Some(ids) = s.identifier_index.get_mut(identifier) {
+                            ids.retain(|id| id != receipt_id);
+                            if ids.is_empty() {
+                                s.identifier_index.remove(identifier);
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        Ok(())
+    }
+
+    fn store_data_lake_gc_report(
         &self,
-        config: &CircuitAdapterConfig,
+        report: &crate::types::DataLakeGcReport,
     ) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_circuit_adapter_config(config)
+        self.with_state(|s| s.data_lake_gc_reports.push(report.clone()));
+        Ok(())
     }
 
-    fn list_circuit_adapter_configs(&self) -> Result<Vec<CircuitAdapterConfig>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_circuit_adapter_configs()
+    fn list_data_lake_gc_reports(
+        &self,
+    ) -> Result<Vec<crate::types::DataLakeGcReport>, StorageError> {
+        Ok(self.with_state(|s| s.data_lake_gc_reports.clone()))
     }
 
-    fn store_user_account(&self, user: &UserAccount) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_user_account(user)
+    // Device backups
+    fn store_device_backup(&self, backup: &crate::types::DeviceBackup) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.device_backups.insert(backup.backup_id, backup.clone());
+        });
+        Ok(())
     }
 
-    fn get_user_account(&self, user_id: &str) -> Result<Option<UserAccount>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_user_account(user_id)
+    fn get_device_backup(
+        &self,
+        backup_id: &Uuid,
+    ) -> Result<Option<crate::types::DeviceBackup>, StorageError> {
+        Ok(self.with_state(|s| s.device_backups.get(backup_id).cloned()))
     }
 
-    fn get_user_by_username(&self, username: &str) -> Result<Option<UserAccount>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_user_by_username(username)
+    fn list_device_backups(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<crate::types::DeviceBackup>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.device_backups
+                .values()
+                .filter(|backup| backup.user_id == user_id)
+                .cloned()
+                .collect()
+        }))
     }
 
-    fn get_user_by_email(&self, email: &str) -> Result<Option<UserAccount>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_user_by_email(email)
+    fn delete_device_backup(&self, backup_id: &Uuid) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.device_backups.remove(backup_id);
+        });
+        Ok(())
     }
 
-    fn update_user_account(&self, user: &UserAccount) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_user_account(user)
+    // Federation
+    fn store_federation_peer(
+        &self,
+        peer: &crate::types::FederationPeer,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.federation_peers.insert(peer.peer_id, peer.clone());
+        });
+        Ok(())
     }
 
-    fn list_user_accounts(&self) -> Result<Vec<UserAccount>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_user_accounts()
+    fn get_federation_peer(
+        &self,
+        peer_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationPeer>, StorageError> {
+        Ok(self.with_state(|s| s.federation_peers.get(peer_id).cloned()))
     }
 
-    fn store_password_reset_token(&self, _token: &PasswordResetToken) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Password reset tokens not implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn list_federation_peers(&self) -> Result<Vec<crate::types::FederationPeer>, StorageError> {
+        Ok(self.with_state(|s| s.federation_peers.values().cloned().collect()))
     }
 
-    fn get_password_reset_token_by_hash(
-        &self,
-        _token_hash: &str,
-    ) -> Result<Option<PasswordResetToken>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Password reset tokens not implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn delete_federation_peer(&self, peer_id: &Uuid) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.federation_peers.remove(peer_id);
+        });
+        Ok(())
     }
 
-    fn mark_token_as_used(&self, _token_id: &str) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Password reset tokens not implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn store_federation_provenance(
+        &self,
+        provenance: &crate::types::FederationProvenance,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.federation_provenance
+                .insert(provenance.record_key.clone(), provenance.clone());
+        });
+        Ok(())
     }
 
-    fn count_recent_reset_requests(
+    fn get_federation_provenance(
         &self,
-        _user_id: &str,
-        _since: DateTime<Utc>,
-    ) -> Result<usize, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Password reset tokens not implemented for EncryptedFileStorage".to_string(),
-        ))
+        record_key: &str,
+    ) -> Result<Option<crate::types::FederationProvenance>, StorageError> {
+        Ok(self.with_state(|s| s.federation_provenance.get(record_key).cloned()))
     }
 
-    fn cleanup_expired_tokens(&self) -> Result<usize, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Password reset tokens not implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn store_federation_conflict(
+        &self,
+        conflict: &crate::types::FederationConflict,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.federation_conflicts
+                .insert(conflict.conflict_id, conflict.clone());
+        });
+        Ok(())
     }
 
-    fn delete_user_account(&self, user_id: &str) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.delete_user_account(user_id)
+    fn get_federation_conflict(
+        &self,
+        conflict_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationConflict>, StorageError> {
+        Ok(self.with_state(|s| s.federation_conflicts.get(conflict_id).cloned()))
     }
 
-    fn record_credit_transaction(
+    fn list_federation_conflicts(
         &self,
-        transaction: &CreditTransaction,
-    ) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.record_credit_transaction(transaction)
+    ) -> Result<Vec<crate::types::FederationConflict>, StorageError> {
+        Ok(self.with_state(|s| s.federation_conflicts.values().cloned().collect()))
     }
 
-    fn get_credit_transaction(
+    // ZK proof revocations
+    fn store_zk_proof_revocation(
         &self,
-        transaction_id: &str,
-    ) -> Result<Option<CreditTransaction>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_credit_transaction(transaction_id)
+        revocation: &crate::zk_proof_engine::ProofRevocation,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.zk_proof_revocations
+                .insert(revocation.proof_id, revocation.clone());
+        });
+        Ok(())
     }
 
-    fn get_credit_transactions(
+    fn get_zk_proof_revocation(
         &self,
-        user_id: &str,
-        limit: Option<usize>,
-    ) -> Result<Vec<CreditTransaction>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_credit_transactions(user_id, limit)
+        proof_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofRevocation>, StorageError> {
+        Ok(self.with_state(|s| s.zk_proof_revocations.get(proof_id).cloned()))
     }
 
-    fn get_credit_transactions_by_operation(
+    fn list_zk_proof_revocations(
         &self,
-        operation_type: &str,
-    ) -> Result<Vec<CreditTransaction>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_credit_transactions_by_operation(operation_type)
+    ) -> Result<Vec<crate::zk_proof_engine::ProofRevocation>, StorageError> {
+        Ok(self.with_state(|s| s.zk_proof_revocations.values().cloned().collect()))
     }
 
-    fn record_admin_action(&self, action: &AdminAction) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.record_admin_action(action)
+    // Selective disclosures
+    fn store_item_attribute_commitments(
+        &self,
+        commitments: &crate::types::ItemAttributeCommitments,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.item_attribute_commitments
+                .insert(commitments.dfid.clone(), commitments.clone());
+        });
+        Ok(())
     }
 
-    fn get_admin_actions(
+    fn get_item_attribute_commitments(
         &self,
-        admin_id: Option<&str>,
-        limit: Option<usize>,
-    ) -> Result<Vec<AdminAction>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_admin_actions(admin_id, limit)
+        dfid: &str,
+    ) -> Result<Option<crate::types::ItemAttributeCommitments>, StorageError> {
+        Ok(self.with_state(|s| s.item_attribute_commitments.get(dfid).cloned()))
     }
 
-    fn get_admin_actions_by_type(
+    fn store_selective_disclosure(
         &self,
-        action_type: &str,
-    ) -> Result<Vec<AdminAction>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_admin_actions_by_type(action_type)
+        disclosure: &crate::types::SelectiveDisclosure,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.selective_disclosures
+                .insert(disclosure.disclosure_id, disclosure.clone());
+        });
+        Ok(())
     }
 
-    fn get_system_statistics(&self) -> Result<SystemStatistics, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_system_statistics()
+    fn get_selective_disclosure(
+        &self,
+        disclosure_id: &Uuid,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, StorageError> {
+        Ok(self.with_state(|s| s.selective_disclosures.get(disclosure_id).cloned()))
     }
 
-    fn update_system_statistics(&self, stats: &SystemStatistics) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_system_statistics(stats)
+    fn get_selective_disclosure_by_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.selective_disclosures
+                .values()
+                .find(|disclosure| disclosure.token_hash == token_hash)
+                .cloned()
+        }))
     }
 
-    fn store_notification(&self, notification: &Notification) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_notification(notification)
+    fn list_selective_disclosures(
+        &self,
+        created_by: &str,
+    ) -> Result<Vec<crate::types::SelectiveDisclosure>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.selective_disclosures
+                .values()
+                .filter(|disclosure| disclosure.created_by == created_by)
+                .cloned()
+                .collect()
+        }))
     }
 
-    fn get_notification(
+    // Custom ZK circuit definitions
+    fn store_custom_circuit_definition(
         &self,
-        notification_id: &str,
-    ) -> Result<Option<Notification>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_notification(notification_id)
+        definition: &crate::zk_proof_engine::CustomCircuitDefinition,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.custom_circuit_definitions
+                .insert(definition.circuit_id.clone(), definition.clone());
+        });
+        Ok(())
     }
 
-    fn get_user_notifications(
+    fn get_custom_circuit_definition(
         &self,
-        user_id: &str,
-        since: Option<DateTime<Utc>>,
-        limit: Option<usize>,
-        unread_only: bool,
-    ) -> Result<Vec<Notification>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_user_notifications(user_id, since, limit, unread_only)
+        circuit_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::CustomCircuitDefinition>, StorageError> {
+        Ok(self.with_state(|s| s.custom_circuit_definitions.get(circuit_id).cloned()))
     }
 
-    fn update_notification(&self, notification: &Notification) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_notification(notification)
+    fn list_custom_circuit_definitions(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::CustomCircuitDefinition>, StorageError> {
+        Ok(self.with_state(|s| s.custom_circuit_definitions.values().cloned().collect()))
     }
 
-    fn delete_notification(&self, notification_id: &str) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.delete_notification(notification_id)
+    // ZK proof generation jobs
+    fn store_zk_proof_job(
+        &self,
+        job: &crate::zk_proof_engine::ProofJob,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.zk_proof_jobs.insert(job.job_id, job.clone());
+        });
+        Ok(())
     }
 
-    fn mark_all_notifications_read(&self, user_id: &str) -> Result<usize, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.mark_all_notifications_read(user_id)
+    fn get_zk_proof_job(
+        &self,
+        job_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofJob>, StorageError> {
+        Ok(self.with_state(|s| s.zk_proof_jobs.get(job_id).cloned()))
     }
 
-    fn get_unread_notification_count(&self, user_id: &str) -> Result<usize, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_unread_notification_count(user_id)
+    fn list_zk_proof_jobs(&self) -> Result<Vec<crate::zk_proof_engine::ProofJob>, StorageError> {
+        Ok(self.with_state(|s| s.zk_proof_jobs.values().cloned().collect()))
     }
 
-    fn store_adapter_config(&self, config: &AdapterConfig) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_adapter_config(config)
+    // Item search projected to summaries
+    fn search_item_summaries(
+        &self,
+        query: &ItemSearchQuery,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<ItemSummary>, StorageError> {
+        Ok(self.with_state(|s| {
+            let circuit_dfids = query.circuit_id.map(|circuit_id| {
+                s.circuit_items
+                    .keys()
+                    .filter(|(id, _)| *id == circuit_id)
+                    .map(|(_, dfid)| dfid.clone())
+                    .collect()
+            });
+            s.item_search_index
+                .search(query, circuit_dfids, after, limit)
+                .into_iter()
+                .filter_map(|dfid| s.items.get(&dfid).map(ItemSummary::from))
+                .collect()
+        }))
     }
 
-    fn get_adapter_config(&self, config_id: &Uuid) -> Result<Option<AdapterConfig>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_adapter_config(config_id)
+    // Per-workspace API usage and anomalies
+    fn store_workspace_usage_window(
+        &self,
+        window: &crate::types::WorkspaceUsageWindow,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.workspace_usage_windows.insert(
+                (window.workspace_id.clone(), window.hour_start),
+                window.clone(),
+            );
+        });
+        Ok(())
     }
 
-    fn update_adapter_config(&self, config: &AdapterConfig) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_adapter_config(config)
+    fn get_workspace_usage_window(
+        &self,
+        workspace_id: &str,
+        hour_start: DateTime<Utc>,
+    ) -> Result<Option<crate::types::WorkspaceUsageWindow>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.workspace_usage_windows
+                .get(&(workspace_id.to_string(), hour_start))
+                .cloned()
+        }))
     }
 
-    fn delete_adapter_config(&self, config_id: &Uuid) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.delete_adapter_config(config_id)
+    fn list_workspace_usage_windows(
+        &self,
+        workspace_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::WorkspaceUsageWindow>, StorageError> {
+        let mut windows: Vec<crate::types::WorkspaceUsageWindow> = self.with_state(|s| {
+            s.workspace_usage_windows
+                .values()
+                .filter(|w| {
+                    w.workspace_id == workspace_id && w.hour_start >= from && w.hour_start < to
+                })
+                .cloned()
+                .collect()
+        });
+        windows.sort_by_key(|w| w.hour_start);
+        Ok(windows)
     }
 
-    fn list_adapter_configs(&self) -> Result<Vec<AdapterConfig>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_adapter_configs()
+    fn store_usage_anomaly_baseline(
+        &self,
+        baseline: &crate::types::UsageAnomalyBaseline,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.usage_anomaly_baselines
+                .insert(baseline.workspace_id.clone(), baseline.clone());
+        });
+        Ok(())
     }
 
-    fn list_active_adapter_configs(&self) -> Result<Vec<AdapterConfig>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_active_adapter_configs()
+    fn get_usage_anomaly_baseline(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::UsageAnomalyBaseline>, StorageError> {
+        Ok(self.with_state(|s| s.usage_anomaly_baselines.get(workspace_id).cloned()))
     }
 
-    fn get_adapter_configs_by_type(
+    fn store_usage_anomaly(
         &self,
-        adapter_type: &AdapterType,
-    ) -> Result<Vec<AdapterConfig>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_adapter_configs_by_type(adapter_type)
+        anomaly: &crate::types::UsageAnomaly,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.usage_anomalies
+                .insert(anomaly.anomaly_id, anomaly.clone());
+        });
+        Ok(())
     }
 
-    fn get_default_adapter_config(&self) -> Result<Option<AdapterConfig>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_default_adapter_config()
+    fn list_usage_anomalies(
+        &self,
+        workspace_id: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::UsageAnomaly>, StorageError> {
+        let mut anomalies: Vec<crate::types::UsageAnomaly> = self.with_state(|s| {
+            s.usage_anomalies
+                .values()
+                .filter(|a| {
+                    workspace_id.is_none_or(|id| a.workspace_id == id) && a.detected_at >= since
+                })
+                .cloned()
+                .collect()
+        });
+        anomalies.sort_by_key(|a| std::cmp::Reverse(a.detected_at));
+        Ok(anomalies)
     }
 
-    fn set_default_adapter(&self, config_id: &Uuid) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.set_default_adapter(config_id)
+    // Proof share links
+    fn store_proof_share_link(
+        &self,
+        link: &crate::types::ProofShareLink,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.proof_share_links.insert(link.share_id, link.clone());
+        });
+        Ok(())
     }
 
-    fn store_adapter_test_result(&self, result: &AdapterTestResult) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_adapter_test_result(result)
+    fn get_proof_share_link(
+        &self,
+        share_id: &Uuid,
+    ) -> Result<Option<crate::types::ProofShareLink>, StorageError> {
+        Ok(self.with_state(|s| s.proof_share_links.get(share_id).cloned()))
     }
 
-    fn get_adapter_test_result(
+    fn record_proof_share_view(
         &self,
-        config_id: &Uuid,
-    ) -> Result<Option<AdapterTestResult>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_adapter_test_result(config_id)
+        share_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<crate::types::ProofShareLink>, StorageError> {
+        Ok(self.with_state(|s| {
+            let link = s.proof_share_links.get_mut(share_id).filter(|link| {
+                link.revoked_at.is_none()
+                    && now < link.expires_at
+                    && link.max_views.is_none_or(|max| link.views < max)
+            })?;
+            link.views += 1;
+            link.last_viewed_at = Some(now);
+            Some(link.clone())
+        }))
     }
 
-    fn store_lid_dfid_mapping(&self, lid: &Uuid, dfid: &str) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_lid_dfid_mapping(lid, dfid)
+    fn list_proof_share_links(
+        &self,
+        proof_id: &Uuid,
+    ) -> Result<Vec<crate::types::ProofShareLink>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.proof_share_links
+                .values()
+                .filter(|link| link.proof_id == *proof_id)
+                .cloned()
+                .collect()
+        }))
     }
 
-    fn get_dfid_by_lid(&self, lid: &Uuid) -> Result<Option<String>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_dfid_by_lid(lid)
+    // Workspace dedup configs
+    fn store_workspace_dedup_config(
+        &self,
+        config: &crate::types::WorkspaceDedupConfig,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.workspace_dedup_configs
+                .insert(config.workspace_id.clone(), config.clone());
+        });
+        Ok(())
     }
 
-    fn get_dfid_by_canonical(
+    fn get_workspace_dedup_config(
         &self,
-        namespace: &str,
-        registry: &str,
-        value: &str,
-    ) -> Result<Option<String>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_dfid_by_canonical(namespace, registry, value)
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceDedupConfig>, StorageError> {
+        Ok(self.with_state(|s| s.workspace_dedup_configs.get(workspace_id).cloned()))
     }
 
-    fn store_fingerprint_mapping(
+    // Sensor time series
+    fn store_sensor_series(&self, series: &crate::types::SensorSeries) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.sensor_series
+                .insert((series.dfid.clone(), series.series.clone()), series.clone());
+        });
+        Ok(())
+    }
+
+    fn get_sensor_series(
         &self,
-        fingerprint: &str,
         dfid: &str,
-        circuit_id: &Uuid,
-    ) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_fingerprint_mapping(fingerprint, dfid, circuit_id)
+        series: &str,
+    ) -> Result<Option<crate::types::SensorSeries>, StorageError> {
+        Ok(self.with_state(|s| {
+            s.sensor_series
+                .get(&(dfid.to_string(), series.to_string()))
+                .cloned()
+        }))
     }
 
-    fn get_dfid_by_fingerprint(
+    fn list_sensor_series(
         &self,
-        fingerprint: &str,
-        circuit_id: &Uuid,
-    ) -> Result<Option<String>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_dfid_by_fingerprint(fingerprint, circuit_id)
+        dfid: &str,
+    ) -> Result<Vec<crate::types::SensorSeries>, StorageError> {
+        Ok(self.with_state(|s| {
+            let mut series: Vec<_> = s
+                .sensor_series
+                .values()
+                .filter(|series| series.dfid == dfid)
+                .cloned()
+                .collect();
+            series.sort_by(|a, b| a.series.cmp(&b.series));
+            series
+        }))
     }
 
-    fn store_enhanced_identifier_mapping(
+    fn store_sensor_chunk(&self, chunk: &crate::types::SensorChunk) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.sensor_chunks.insert(
+                (chunk.dfid.clone(), chunk.series.clone(), chunk.chunk_start),
+                chunk.clone(),
+            );
+        });
+        Ok(())
+    }
+
+    fn get_sensor_chunks(
         &self,
-        identifier: &EnhancedIdentifier,
         dfid: &str,
-    ) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_enhanced_identifier_mapping(identifier, dfid)
+        series: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorChunk>, StorageError> {
+        Ok(self.with_state(|s| {
+            let mut chunks: Vec<_> = s
+                .sensor_chunks
+                .values()
+                .filter(|chunk| {
+                    chunk.dfid == dfid
+                        && chunk.series == series
+                        && chunk.chunk_start >= from
+                        && chunk.chunk_start <= to
+                })
+                .cloned()
+                .collect();
+            chunks.sort_by_key(|chunk| chunk.chunk_start);
+            chunks
+        }))
     }
 
-    fn store_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_webhook_delivery(delivery)
+    fn store_sensor_rollup(&self, rollup: &crate::types::SensorRollup) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.sensor_rollups.insert(
+                (
+                    rollup.dfid.clone(),
+                    rollup.series.clone(),
+                    rollup.resolution,
+                    rollup.bucket_start,
+                ),
+                rollup.clone(),
+            );
+        });
+        Ok(())
     }
 
-    fn get_webhook_delivery(
+    fn get_sensor_rollups(
         &self,
-        delivery_id: &Uuid,
-    ) -> Result<Option<WebhookDelivery>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_webhook_delivery(delivery_id)
+        dfid: &str,
+        series: &str,
+        resolution: crate::types::SensorResolution,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorRollup>, StorageError> {
+        Ok(self.with_state(|s| {
+            let mut rollups: Vec<_> = s
+                .sensor_rollups
+                .values()
+                .filter(|rollup| {
+                    rollup.dfid == dfid
+                        && rollup.series == series
+                        && rollup.resolution == resolution
+                        && rollup.bucket_start >= from
+                        && rollup.bucket_start <= to
+                })
+                .cloned()
+                .collect();
+            rollups.sort_by_key(|rollup| rollup.bucket_start);
+            rollups
+        }))
     }
 
-    fn get_webhook_deliveries_by_circuit(
-        &self,
-        circuit_id: &Uuid,
-        limit: Option<usize>,
-    ) -> Result<Vec<WebhookDelivery>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_webhook_deliveries_by_circuit(circuit_id, limit)
+    fn list_all_sensor_series(&self) -> Result<Vec<crate::types::SensorSeries>, StorageError> {
+        Ok(self.with_state(|s| s.sensor_series.values().cloned().collect()))
     }
 
-    fn get_webhook_deliveries_by_webhook(
+    // Workspace buckets
+    fn store_workspace_bucket_config(
         &self,
-        webhook_id: &Uuid,
-        limit: Option<usize>,
-    ) -> Result<Vec<WebhookDelivery>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_webhook_deliveries_by_webhook(webhook_id, limit)
+        config: &crate::types::WorkspaceBucketConfig,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.workspace_bucket_configs
+                .insert(config.workspace_id.clone(), config.clone());
+        });
+        Ok(())
     }
 
-    fn store_user_activity(&self, activity: &UserActivity) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_user_activity(activity)
+    fn get_workspace_bucket_config(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceBucketConfig>, StorageError> {
+        Ok(self.with_state(|s| s.workspace_bucket_configs.get(workspace_id).cloned()))
     }
 
-    fn list_user_activities(&self) -> Result<Vec<UserActivity>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.list_user_activities()
+    fn delete_workspace_bucket_config(&self, workspace_id: &str) -> Result<bool, StorageError> {
+        Ok(self.with_state(|s| s.workspace_bucket_configs.remove(workspace_id).is_some()))
     }
 
-    fn clear_user_activities(&self) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.clear_user_activities()
+    // DFID sequence leases
+    fn lease_dfid_sequence_range(&self, day: &str, count: u64) -> Result<u64, StorageError> {
+        Ok(self.with_state(|s| {
+            let next = s.dfid_sequence_leases.entry(day.to_string()).or_insert(1);
+            let first = *next;
+            *next += count;
+            first
+        }))
     }
 
-    // State Snapshot operations
-    fn store_snapshot(
-        &self,
-        snapshot: &crate::snapshot_types::StateSnapshot,
-    ) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.store_snapshot(snapshot)
+    // Re-verification jobs and merge proposals
+    fn store_reverification_job(&self, job: &ReverificationJob) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.reverification_jobs.insert(job.job_id, job.clone());
+        });
+        Ok(())
     }
 
-    fn get_snapshot(
+    fn get_reverification_job(
         &self,
-        snapshot_id: &str,
-    ) -> Result<Option<crate::snapshot_types::StateSnapshot>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_snapshot(snapshot_id)
+        job_id: &Uuid,
+    ) -> Result<Option<ReverificationJob>, StorageError> {
+        Ok(self.with_state(|s| s.reverification_jobs.get(job_id).cloned()))
     }
 
-    fn get_snapshots_for_entity(
-        &self,
-        entity_type: crate::snapshot_types::SnapshotEntityType,
-        entity_id: &str,
-    ) -> Result<Vec<crate::snapshot_types::StateSnapshot>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_snapshots_for_entity(entity_type, entity_id)
+    fn list_reverification_jobs(&self) -> Result<Vec<ReverificationJob>, StorageError> {
+        Ok(self.with_state(|s| s.reverification_jobs.values().cloned().collect()))
     }
 
-    fn get_latest_snapshot(
-        &self,
-        entity_type: crate::snapshot_types::SnapshotEntityType,
-        entity_id: &str,
-    ) -> Result<Option<crate::snapshot_types::StateSnapshot>, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_latest_snapshot(entity_type, entity_id)
+    fn store_merge_proposal(&self, proposal: &MergeProposal) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.merge_proposals
+                .insert(proposal.proposal_id, proposal.clone());
+        });
+        Ok(())
     }
 
-    fn update_snapshot(
+    fn get_merge_proposal(
         &self,
-        snapshot: &crate::snapshot_types::StateSnapshot,
-    ) -> Result<(), StorageError> {
-        let guard = self.lock().unwrap();
-        guard.update_snapshot(snapshot)
+        proposal_id: &Uuid,
+    ) -> Result<Option<MergeProposal>, StorageError> {
+        Ok(self.with_state(|s| s.merge_proposals.get(proposal_id).cloned()))
     }
 
-    fn get_snapshot_count(
-        &self,
-        entity_type: crate::snapshot_types::SnapshotEntityType,
-        entity_id: &str,
-    ) -> Result<u64, StorageError> {
-        let guard = self.lock().unwrap();
-        guard.get_snapshot_count(entity_type, entity_id)
+    fn list_merge_proposals(&self) -> Result<Vec<MergeProposal>, StorageError> {
+        Ok(self.with_state(|s| s.merge_proposals.values().cloned().collect()))
     }
-}
 
-impl Default for InMemoryStorage {
-    fn default() -> Self {
-        Self::new()
+    // Runbook hooks
+    fn store_runbook_hook(&self, hook: &RunbookHook) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.runbook_hooks.insert(hook.hook_id, hook.clone());
+        });
+        Ok(())
     }
-}
-
-pub struct EncryptedFileStorage {
-    base_path: String,
-    encryption_key: Option<EncryptionKey>,
-}
 
-impl EncryptedFileStorage {
-    pub fn new(base_path: impl Into<String>) -> Self {
-        Self {
-            base_path: base_path.into(),
-            encryption_key: None,
-        }
+    fn get_runbook_hook(&self, hook_id: &Uuid) -> Result<Option<RunbookHook>, StorageError> {
+        Ok(self.with_state(|s| s.runbook_hooks.get(hook_id).cloned()))
     }
 
-    pub fn with_encryption(mut self, key: EncryptionKey) -> Self {
-        self.encryption_key = Some(key);
-        self
+    fn list_runbook_hooks(&self) -> Result<Vec<RunbookHook>, StorageError> {
+        Ok(self.with_state(|s| s.runbook_hooks.values().cloned().collect()))
     }
 
-    fn encrypt_data(&self, data: &[u8]) -> Result<EncryptedData, StorageError> {
-        if let Some(key) = &self.encryption_key {
-            let cipher = Aes256Gcm::new(key.as_aes_key());
-            let mut nonce_bytes = [0u8; 12];
-            OsRng.fill_bytes(&mut nonce_bytes);
-            let nonce = Nonce::from_slice(&nonce_bytes);
-
-            let ciphertext = cipher
-                .encrypt(nonce, data)
-                .map_err(|e| StorageError::EncryptionError(format!("Encryption failed: {e}")))?;
-
-            Ok(EncryptedData {
-                data: ciphertext,
-                nonce: nonce_bytes,
-            })
-        } else {
-            Ok(EncryptedData {
-                data: data.to_vec(),
-                nonce: [0u8; 12],
-            })
-        }
+    fn delete_runbook_hook(&self, hook_id: &Uuid) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.runbook_hooks.remove(hook_id);
+        });
+        Ok(())
     }
 
-    fn decrypt_data(&self, encrypted: &EncryptedData) -> Result<Vec<u8>, StorageError> {
-        if let Some(key) = &self.encryption_key {
-            let cipher = Aes256Gcm::new(key.as_aes_key());
-            let nonce = Nonce::from_slice(&encrypted.nonce);
+    // Workspace verification rules
+    fn store_workspace_verification_rules(
+        &self,
+        rules: &WorkspaceVerificationRules,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.workspace_verification_rules
+                .insert(rules.workspace_id.clone(), rules.clone());
+        });
+        Ok(())
+    }
 
-            cipher
-                .decrypt(nonce, encrypted.data.as_ref())
-                .map_err(|e| StorageError::EncryptionError(format!("Decryption failed: {e}")))
-        } else {
-            Ok(encrypted.data.clone())
-        }
+    fn get_workspace_verification_rules(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<WorkspaceVerificationRules>, StorageError> {
+        Ok(self.with_state(|s| s.workspace_verification_rules.get(workspace_id).cloned()))
     }
 
-    fn ensure_directory(&self, path: &str) -> Result<(), StorageError> {
-        let dir_path = Path::new(&self.base_path).join(path);
-        fs::create_dir_all(dir_path)?;
+    // Dedup sharing agreements
+    fn store_dedup_sharing_agreement(
+        &self,
+        agreement: &DedupSharingAgreement,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| {
+            s.dedup_sharing_agreements
+                .insert(agreement.agreement_id, agreement.clone());
+        });
         Ok(())
     }
-}
 
-impl StorageBackend for EncryptedFileStorage {
+    fn get_dedup_sharing_agreement(
+        &self,
+        agreement_id: &Uuid,
+    ) -> Result<Option<DedupSharingAgreement>, StorageError> {
+        Ok(self.with_state(|s| s.dedup_sharing_agreements.get(agreement_id).cloned()))
+    }
+
+    fn list_dedup_sharing_agreements(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<DedupSharingAgreement>, StorageError> {
+        let mut agreements: Vec<DedupSharingAgreement> = self.with_state(|s| {
+            s.dedup_sharing_agreements
+                .values()
+                .filter(|a| a.involves(circuit_id))
+                .cloned()
+                .collect()
+        });
+        agreements.sort_by_key(|a| std::cmp::Reverse(a.proposed_at));
+        Ok(agreements)
+    }
+
+    // Paginated CID timeline
+    fn get_item_timeline_page(
+        &self,
+        dfid: &str,
+        query: &TimelineQuery,
+        after_sequence: Option<i32>,
+        limit: usize,
+    ) -> Result<Vec<TimelineEntry>, StorageError> {
+        Ok(self.with_state(|s| {
+            let matching: Vec<&TimelineEntry> = s
+                .cid_timeline
+                .get(dfid)
+                .map(|timeline| {
+                    timeline
+                        .iter()
+                        .filter(|entry| query.matches(entry))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let skip = query
+                .latest
+                .map_or(0, |latest| matching.len().saturating_sub(latest));
+            matching
+                .into_iter()
+                .skip(skip)
+                .filter(|entry| after_sequence.is_none_or(|after| entry.event_sequence > after))
+                .take(limit)
+                .cloned()
+                .collect()
+        }))
+    }
+
+    // Circuit membership reconciliation
+    fn store_membership_reconciliation_report(
+        &self,
+        report: &MembershipReconciliationReport,
+    ) -> Result<(), StorageError> {
+        self.with_state(|s| s.membership_reconciliation_reports.push(report.clone()));
+        Ok(())
+    }
+
+    fn list_membership_reconciliation_reports(
+        &self,
+    ) -> Result<Vec<MembershipReconciliationReport>, StorageError> {
+        Ok(self.with_state(|s| s.membership_reconciliation_reports.clone()))
+    }
+}
+impl StorageBackend for Arc<Mutex<InMemoryStorage>> {
     fn store_receipt(&self, receipt: &Receipt) -> Result<(), StorageError> {
-        self.ensure_directory("receipts")?;
+        let guard = self.lock().unwrap();
+        guard.store_receipt(receipt)
+    }
 
-        let serialized = serde_json::to_vec(receipt)?;
-        let encrypted = self.encrypt_data(&serialized)?;
-        let encrypted_json = serde_json::to_vec(&encrypted)?;
+    fn get_receipt(&self, id: &Uuid) -> Result<Option<Receipt>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_receipt(id)
+    }
+
+    fn find_receipts_by_identifier(
+        &self,
+        identifier: &Identifier,
+    ) -> Result<Vec<Receipt>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.find_receipts_by_identifier(identifier)
+    }
+
+    fn list_receipts(&self) -> Result<Vec<Receipt>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_receipts()
+    }
+
+    fn store_receipts(&self, receipts: &[Receipt]) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_receipts(receipts)
+    }
+
+    fn store_log(&self, log: &LogEntry) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_log(log)
+    }
+
+    fn get_logs(&self) -> Result<Vec<LogEntry>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_logs()
+    }
+
+    fn store_data_lake_entry(&self, entry: &DataLakeEntry) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_data_lake_entry(entry)
+    }
+
+    fn store_data_lake_entries(&self, entries: &[DataLakeEntry]) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_data_lake_entries(entries)
+    }
+
+    fn get_data_lake_entry(&self, entry_id: &Uuid) -> Result<Option<DataLakeEntry>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_data_lake_entry(entry_id)
+    }
+
+    fn update_data_lake_entry(&self, entry: &DataLakeEntry) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_data_lake_entry(entry)
+    }
+
+    fn get_data_lake_entries_by_status(
+        &self,
+        status: ProcessingStatus,
+    ) -> Result<Vec<DataLakeEntry>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_data_lake_entries_by_status(status)
+    }
+
+    fn list_data_lake_entries(&self) -> Result<Vec<DataLakeEntry>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_data_lake_entries()
+    }
+
+    fn store_item(&self, item: &Item) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_item(item)
+    }
+
+    fn get_item_by_dfid(&self, dfid: &str) -> Result<Option<Item>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_item_by_dfid(dfid)
+    }
+
+    fn update_item(&self, item: &Item) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_item(item)
+    }
+
+    fn list_items(&self) -> Result<Vec<Item>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_items()
+    }
+
+    fn find_items_by_identifier(&self, identifier: &Identifier) -> Result<Vec<Item>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.find_items_by_identifier(identifier)
+    }
+
+    fn find_items_by_status(&self, status: ItemStatus) -> Result<Vec<Item>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.find_items_by_status(status)
+    }
+
+    fn delete_item(&self, dfid: &str) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_item(dfid)
+    }
+
+    fn store_identifier_mapping(&self, mapping: &IdentifierMapping) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_identifier_mapping(mapping)
+    }
+
+    fn get_identifier_mappings(
+        &self,
+        identifier: &Identifier,
+    ) -> Result<Vec<IdentifierMapping>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_identifier_mappings(identifier)
+    }
+
+    fn update_identifier_mapping(&self, mapping: &IdentifierMapping) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_identifier_mapping(mapping)
+    }
+
+    fn list_identifier_mappings(&self) -> Result<Vec<IdentifierMapping>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_identifier_mappings()
+    }
+
+    fn store_conflict_resolution(&self, conflict: &ConflictResolution) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_conflict_resolution(conflict)
+    }
+
+    fn get_conflict_resolution(
+        &self,
+        conflict_id: &Uuid,
+    ) -> Result<Option<ConflictResolution>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_conflict_resolution(conflict_id)
+    }
+
+    fn get_pending_conflicts(&self) -> Result<Vec<ConflictResolution>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_pending_conflicts()
+    }
+
+    fn store_event(&self, event: &Event) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_event(event)
+    }
+
+    fn store_events(&self, events: &[Event]) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_events(events)
+    }
+
+    fn get_event(&self, event_id: &Uuid) -> Result<Option<Event>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_event(event_id)
+    }
+
+    fn update_event(&self, event: &Event) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_event(event)
+    }
+
+    fn list_events(&self) -> Result<Vec<Event>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_events()
+    }
+
+    fn get_events_by_dfid(&self, dfid: &str) -> Result<Vec<Event>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_events_by_dfid(dfid)
+    }
+
+    fn get_events_by_type(&self, event_type: EventType) -> Result<Vec<Event>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_events_by_type(event_type)
+    }
+
+    fn get_events_by_visibility(
+        &self,
+        visibility: EventVisibility,
+    ) -> Result<Vec<Event>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_events_by_visibility(visibility)
+    }
+
+    fn get_events_in_time_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Event>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_events_in_time_range(start, end)
+    }
+
+    fn get_event_by_content_hash(&self, content_hash: &str) -> Result<Option<Event>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_event_by_content_hash(content_hash)
+    }
+
+    fn store_circuit(&self, circuit: &Circuit) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_circuit(circuit)
+    }
+
+    fn get_circuit(&self, circuit_id: &Uuid) -> Result<Option<Circuit>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_circuit(circuit_id)
+    }
+
+    fn update_circuit(&self, circuit: &Circuit) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_circuit(circuit)
+    }
+
+    fn list_circuits(&self) -> Result<Vec<Circuit>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_circuits()
+    }
+
+    fn get_circuits_for_member(&self, member_id: &str) -> Result<Vec<Circuit>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_circuits_for_member(member_id)
+    }
+
+    fn get_archived_circuits_for_member(
+        &self,
+        member_id: &str,
+    ) -> Result<Vec<Circuit>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_archived_circuits_for_member(member_id)
+    }
+
+    fn get_child_circuits(&self, parent_id: &Uuid) -> Result<Vec<Circuit>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_child_circuits(parent_id)
+    }
+
+    fn store_circuit_operation(&self, operation: &CircuitOperation) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_circuit_operation(operation)
+    }
+
+    fn get_circuit_operation(
+        &self,
+        operation_id: &Uuid,
+    ) -> Result<Option<CircuitOperation>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_circuit_operation(operation_id)
+    }
+
+    fn update_circuit_operation(&self, operation: &CircuitOperation) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_circuit_operation(operation)
+    }
+
+    fn get_circuit_operations(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<CircuitOperation>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_circuit_operations(circuit_id)
+    }
+
+    fn store_item_share(&self, share: &ItemShare) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_item_share(share)
+    }
+
+    fn get_item_share(&self, share_id: &str) -> Result<Option<ItemShare>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_item_share(share_id)
+    }
+
+    fn get_shares_for_user(&self, user_id: &str) -> Result<Vec<ItemShare>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_shares_for_user(user_id)
+    }
+
+    fn get_shares_for_item(&self, dfid: &str) -> Result<Vec<ItemShare>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_shares_for_item(dfid)
+    }
+
+    fn is_item_shared_with_user(&self, dfid: &str, user_id: &str) -> Result<bool, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.is_item_shared_with_user(dfid, user_id)
+    }
+
+    fn delete_item_share(&self, share_id: &str) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_item_share(share_id)
+    }
+
+    fn store_activity(&self, activity: &Activity) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_activity(activity)
+    }
+
+    fn get_activities_for_user(&self, user_id: &str) -> Result<Vec<Activity>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_activities_for_user(user_id)
+    }
+
+    fn get_activities_for_circuit(&self, circuit_id: &Uuid) -> Result<Vec<Activity>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_activities_for_circuit(circuit_id)
+    }
+
+    fn get_all_activities(&self) -> Result<Vec<Activity>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_all_activities()
+    }
+
+    fn store_circuit_item(&self, circuit_item: &CircuitItem) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_circuit_item(circuit_item)
+    }
+
+    fn get_circuit_items(&self, circuit_id: &Uuid) -> Result<Vec<CircuitItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_circuit_items(circuit_id)
+    }
+
+    fn remove_circuit_item(&self, circuit_id: &Uuid, dfid: &str) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.remove_circuit_item(circuit_id, dfid)
+    }
+
+    fn store_audit_event(&self, event: &AuditEvent) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_audit_event(event)
+    }
+
+    fn get_audit_event(&self, event_id: &Uuid) -> Result<Option<AuditEvent>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_audit_event(event_id)
+    }
+
+    fn query_audit_events(&self, query: &AuditQuery) -> Result<Vec<AuditEvent>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.query_audit_events(query)
+    }
+
+    fn list_audit_events(&self) -> Result<Vec<AuditEvent>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_audit_events()
+    }
+
+    fn get_audit_events_by_user(&self, user_id: &str) -> Result<Vec<AuditEvent>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_audit_events_by_user(user_id)
+    }
+
+    fn get_audit_events_by_type(
+        &self,
+        event_type: AuditEventType,
+    ) -> Result<Vec<AuditEvent>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_audit_events_by_type(event_type)
+    }
+
+    fn get_audit_events_by_severity(
+        &self,
+        severity: AuditSeverity,
+    ) -> Result<Vec<AuditEvent>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_audit_events_by_severity(severity)
+    }
+
+    fn get_audit_events_in_time_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<AuditEvent>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_audit_events_in_time_range(start, end)
+    }
+
+    fn sync_audit_events(&self, events: Vec<AuditEvent>) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.sync_audit_events(events)
+    }
+
+    fn store_security_incident(&self, incident: &SecurityIncident) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_security_incident(incident)
+    }
+
+    fn get_security_incident(
+        &self,
+        incident_id: &Uuid,
+    ) -> Result<Option<SecurityIncident>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_security_incident(incident_id)
+    }
+
+    fn update_security_incident(&self, incident: &SecurityIncident) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_security_incident(incident)
+    }
+
+    fn list_security_incidents(&self) -> Result<Vec<SecurityIncident>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_security_incidents()
+    }
+
+    fn get_incidents_by_severity(
+        &self,
+        severity: AuditSeverity,
+    ) -> Result<Vec<SecurityIncident>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_incidents_by_severity(severity)
+    }
+
+    fn get_open_incidents(&self) -> Result<Vec<SecurityIncident>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_open_incidents()
+    }
+
+    fn get_incidents_by_assignee(
+        &self,
+        assignee: &str,
+    ) -> Result<Vec<SecurityIncident>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_incidents_by_assignee(assignee)
+    }
+
+    fn store_compliance_report(&self, report: &ComplianceReport) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_compliance_report(report)
+    }
+
+    fn get_compliance_report(
+        &self,
+        report_id: &Uuid,
+    ) -> Result<Option<ComplianceReport>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_compliance_report(report_id)
+    }
+
+    fn update_compliance_report(&self, report: &ComplianceReport) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_compliance_report(report)
+    }
+
+    fn list_compliance_reports(&self) -> Result<Vec<ComplianceReport>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_compliance_reports()
+    }
+
+    fn get_reports_by_type(
+        &self,
+        report_type: &str,
+    ) -> Result<Vec<ComplianceReport>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_reports_by_type(report_type)
+    }
+
+    fn get_pending_reports(&self) -> Result<Vec<ComplianceReport>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_pending_reports()
+    }
+
+    fn get_audit_dashboard_metrics(&self) -> Result<AuditDashboardMetrics, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_audit_dashboard_metrics()
+    }
+
+    fn get_event_count_by_time_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<u64, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_event_count_by_time_range(start, end)
+    }
+
+    fn store_pending_item(&self, item: &PendingItem) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_pending_item(item)
+    }
+
+    fn get_pending_item(&self, pending_id: &Uuid) -> Result<Option<PendingItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_pending_item(pending_id)
+    }
+
+    fn list_pending_items(&self) -> Result<Vec<PendingItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_pending_items()
+    }
+
+    fn get_pending_items_by_reason(
+        &self,
+        reason_type: &str,
+    ) -> Result<Vec<PendingItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_pending_items_by_reason(reason_type)
+    }
+
+    fn get_pending_items_by_user(&self, user_id: &str) -> Result<Vec<PendingItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_pending_items_by_user(user_id)
+    }
+
+    fn get_pending_items_by_workspace(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Vec<PendingItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_pending_items_by_workspace(workspace_id)
+    }
+
+    fn get_pending_items_by_priority(
+        &self,
+        priority: PendingPriority,
+    ) -> Result<Vec<PendingItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_pending_items_by_priority(priority)
+    }
+
+    fn update_pending_item(&self, item: &PendingItem) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_pending_item(item)
+    }
+
+    fn delete_pending_item(&self, pending_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_pending_item(pending_id)
+    }
+
+    fn get_pending_items_requiring_manual_review(&self) -> Result<Vec<PendingItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_pending_items_requiring_manual_review()
+    }
+
+    fn store_zk_proof(&self, proof: &crate::zk_proof_engine::ZkProof) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_zk_proof(proof)
+    }
+
+    fn get_zk_proof(
+        &self,
+        proof_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ZkProof>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_zk_proof(proof_id)
+    }
+
+    fn update_zk_proof(&self, proof: &crate::zk_proof_engine::ZkProof) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_zk_proof(proof)
+    }
+
+    fn query_zk_proofs(
+        &self,
+        query: &crate::api::zk_proofs::ZkProofQuery,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.query_zk_proofs(query)
+    }
+
+    fn list_zk_proofs(&self) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_zk_proofs()
+    }
+
+    fn get_zk_proofs_by_user(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_zk_proofs_by_user(user_id)
+    }
+
+    fn get_zk_proofs_by_circuit_type(
+        &self,
+        circuit_type: CircuitType,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_zk_proofs_by_circuit_type(circuit_type)
+    }
+
+    fn get_zk_proofs_by_status(
+        &self,
+        status: crate::zk_proof_engine::ProofStatus,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_zk_proofs_by_status(status)
+    }
+
+    fn get_zk_proof_statistics(
+        &self,
+    ) -> Result<crate::api::zk_proofs::ZkProofStatistics, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_zk_proof_statistics()
+    }
+
+    fn delete_zk_proof(&self, proof_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_zk_proof(proof_id)
+    }
+
+    fn store_storage_history(&self, history: &ItemStorageHistory) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_storage_history(history)
+    }
+
+    fn get_storage_history(&self, dfid: &str) -> Result<Option<ItemStorageHistory>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_storage_history(dfid)
+    }
+
+    fn add_storage_record(&self, dfid: &str, record: StorageRecord) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.add_storage_record(dfid, record)
+    }
+
+    fn add_cid_to_timeline(
+        &self,
+        dfid: &str,
+        cid: &str,
+        ipcm_tx: &str,
+        timestamp: i64,
+        network: &str,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.add_cid_to_timeline(dfid, cid, ipcm_tx, timestamp, network)
+    }
+
+    fn get_item_timeline(&self, dfid: &str) -> Result<Vec<TimelineEntry>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_item_timeline(dfid)
+    }
+
+    fn get_timeline_by_sequence(
+        &self,
+        dfid: &str,
+        sequence: i32,
+    ) -> Result<Option<TimelineEntry>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_timeline_by_sequence(dfid, sequence)
+    }
+
+    fn map_event_to_cid(
+        &self,
+        event_id: &Uuid,
+        dfid: &str,
+        cid: &str,
+        sequence: i32,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.map_event_to_cid(event_id, dfid, cid, sequence)
+    }
+
+    fn get_event_first_cid(
+        &self,
+        event_id: &Uuid,
+    ) -> Result<Option<EventCidMapping>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_event_first_cid(event_id)
+    }
+
+    fn get_events_in_cid(&self, cid: &str) -> Result<Vec<EventCidMapping>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_events_in_cid(cid)
+    }
+
+    fn update_indexing_progress(
+        &self,
+        network: &str,
+        last_ledger: i64,
+        confirmed_ledger: i64,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_indexing_progress(network, last_ledger, confirmed_ledger)
+    }
+
+    fn get_indexing_progress(
+        &self,
+        network: &str,
+    ) -> Result<Option<IndexingProgress>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_indexing_progress(network)
+    }
+
+    fn increment_events_indexed(&self, network: &str, count: i64) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.increment_events_indexed(network, count)
+    }
+
+    fn store_circuit_adapter_config(
+        &self,
+        config: &CircuitAdapterConfig,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_circuit_adapter_config(config)
+    }
+
+    fn get_circuit_adapter_config(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Option<CircuitAdapterConfig>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_circuit_adapter_config(circuit_id)
+    }
+
+    fn update_circuit_adapter_config(
+        &self,
+        config: &CircuitAdapterConfig,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_circuit_adapter_config(config)
+    }
+
+    fn list_circuit_adapter_configs(&self) -> Result<Vec<CircuitAdapterConfig>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_circuit_adapter_configs()
+    }
+
+    fn store_user_account(&self, user: &UserAccount) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_user_account(user)
+    }
+
+    fn get_user_account(&self, user_id: &str) -> Result<Option<UserAccount>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_user_account(user_id)
+    }
+
+    fn get_user_by_username(&self, username: &str) -> Result<Option<UserAccount>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_user_by_username(username)
+    }
+
+    fn get_user_by_email(&self, email: &str) -> Result<Option<UserAccount>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_user_by_email(email)
+    }
+
+    fn update_user_account(&self, user: &UserAccount) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_user_account(user)
+    }
+
+    fn list_user_accounts(&self) -> Result<Vec<UserAccount>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_user_accounts()
+    }
+
+    fn store_password_reset_token(&self, _token: &PasswordResetToken) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Password reset tokens not implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_password_reset_token_by_hash(
+        &self,
+        _token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Password reset tokens not implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn mark_token_as_used(&self, _token_id: &str) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Password reset tokens not implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn count_recent_reset_requests(
+        &self,
+        _user_id: &str,
+        _since: DateTime<Utc>,
+    ) -> Result<usize, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Password reset tokens not implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn cleanup_expired_tokens(&self) -> Result<usize, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Password reset tokens not implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn delete_user_account(&self, user_id: &str) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_user_account(user_id)
+    }
+
+    fn record_credit_transaction(
+        &self,
+        transaction: &CreditTransaction,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.record_credit_transaction(transaction)
+    }
+
+    fn get_credit_transaction(
+        &self,
+        transaction_id: &str,
+    ) -> Result<Option<CreditTransaction>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_credit_transaction(transaction_id)
+    }
+
+    fn get_credit_transactions(
+        &self,
+        user_id: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<CreditTransaction>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_credit_transactions(user_id, limit)
+    }
+
+    fn get_credit_transactions_by_operation(
+        &self,
+        operation_type: &str,
+    ) -> Result<Vec<CreditTransaction>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_credit_transactions_by_operation(operation_type)
+    }
+
+    fn record_admin_action(&self, action: &AdminAction) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.record_admin_action(action)
+    }
+
+    fn get_admin_actions(
+        &self,
+        admin_id: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<AdminAction>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_admin_actions(admin_id, limit)
+    }
+
+    fn get_admin_actions_by_type(
+        &self,
+        action_type: &str,
+    ) -> Result<Vec<AdminAction>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_admin_actions_by_type(action_type)
+    }
+
+    fn get_system_statistics(&self) -> Result<SystemStatistics, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_system_statistics()
+    }
+
+    fn update_system_statistics(&self, stats: &SystemStatistics) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_system_statistics(stats)
+    }
+
+    fn store_notification(&self, notification: &Notification) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_notification(notification)
+    }
+
+    fn get_notification(
+        &self,
+        notification_id: &str,
+    ) -> Result<Option<Notification>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_notification(notification_id)
+    }
+
+    fn get_user_notifications(
+        &self,
+        user_id: &str,
+        since: Option<DateTime<Utc>>,
+        limit: Option<usize>,
+        unread_only: bool,
+    ) -> Result<Vec<Notification>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_user_notifications(user_id, since, limit, unread_only)
+    }
+
+    fn update_notification(&self, notification: &Notification) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_notification(notification)
+    }
+
+    fn delete_notification(&self, notification_id: &str) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_notification(notification_id)
+    }
+
+    fn mark_all_notifications_read(&self, user_id: &str) -> Result<usize, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.mark_all_notifications_read(user_id)
+    }
+
+    fn get_unread_notification_count(&self, user_id: &str) -> Result<usize, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_unread_notification_count(user_id)
+    }
+
+    fn store_adapter_config(&self, config: &AdapterConfig) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_adapter_config(config)
+    }
+
+    fn get_adapter_config(&self, config_id: &Uuid) -> Result<Option<AdapterConfig>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_adapter_config(config_id)
+    }
+
+    fn update_adapter_config(&self, config: &AdapterConfig) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_adapter_config(config)
+    }
+
+    fn delete_adapter_config(&self, config_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_adapter_config(config_id)
+    }
+
+    fn list_adapter_configs(&self) -> Result<Vec<AdapterConfig>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_adapter_configs()
+    }
+
+    fn list_active_adapter_configs(&self) -> Result<Vec<AdapterConfig>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_active_adapter_configs()
+    }
+
+    fn get_adapter_configs_by_type(
+        &self,
+        adapter_type: &AdapterType,
+    ) -> Result<Vec<AdapterConfig>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_adapter_configs_by_type(adapter_type)
+    }
+
+    fn get_default_adapter_config(&self) -> Result<Option<AdapterConfig>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_default_adapter_config()
+    }
+
+    fn set_default_adapter(&self, config_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.set_default_adapter(config_id)
+    }
+
+    fn store_adapter_test_result(&self, result: &AdapterTestResult) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_adapter_test_result(result)
+    }
+
+    fn get_adapter_test_result(
+        &self,
+        config_id: &Uuid,
+    ) -> Result<Option<AdapterTestResult>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_adapter_test_result(config_id)
+    }
+
+    fn store_lid_dfid_mapping(&self, lid: &Uuid, dfid: &str) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_lid_dfid_mapping(lid, dfid)
+    }
+
+    fn get_dfid_by_lid(&self, lid: &Uuid) -> Result<Option<String>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_dfid_by_lid(lid)
+    }
+
+    fn get_dfid_by_canonical(
+        &self,
+        namespace: &str,
+        registry: &str,
+        value: &str,
+    ) -> Result<Option<String>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_dfid_by_canonical(namespace, registry, value)
+    }
+
+    fn store_fingerprint_mapping(
+        &self,
+        fingerprint: &str,
+        dfid: &str,
+        circuit_id: &Uuid,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_fingerprint_mapping(fingerprint, dfid, circuit_id)
+    }
+
+    fn get_dfid_by_fingerprint(
+        &self,
+        fingerprint: &str,
+        circuit_id: &Uuid,
+    ) -> Result<Option<String>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_dfid_by_fingerprint(fingerprint, circuit_id)
+    }
+
+    fn store_enhanced_identifier_mapping(
+        &self,
+        identifier: &EnhancedIdentifier,
+        dfid: &str,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_enhanced_identifier_mapping(identifier, dfid)
+    }
+
+    fn store_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_webhook_delivery(delivery)
+    }
+
+    fn get_webhook_delivery(
+        &self,
+        delivery_id: &Uuid,
+    ) -> Result<Option<WebhookDelivery>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_webhook_delivery(delivery_id)
+    }
+
+    fn get_webhook_deliveries_by_circuit(
+        &self,
+        circuit_id: &Uuid,
+        limit: Option<usize>,
+    ) -> Result<Vec<WebhookDelivery>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_webhook_deliveries_by_circuit(circuit_id, limit)
+    }
+
+    fn get_webhook_deliveries_by_webhook(
+        &self,
+        webhook_id: &Uuid,
+        limit: Option<usize>,
+    ) -> Result<Vec<WebhookDelivery>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_webhook_deliveries_by_webhook(webhook_id, limit)
+    }
+
+    fn store_user_activity(&self, activity: &UserActivity) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_user_activity(activity)
+    }
+
+    fn list_user_activities(&self) -> Result<Vec<UserActivity>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_user_activities()
+    }
+
+    fn clear_user_activities(&self) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.clear_user_activities()
+    }
+
+    // State Snapshot operations
+    fn store_snapshot(
+        &self,
+        snapshot: &crate::snapshot_types::StateSnapshot,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_snapshot(snapshot)
+    }
+
+    fn get_snapshot(
+        &self,
+        snapshot_id: &str,
+    ) -> Result<Option<crate::snapshot_types::StateSnapshot>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_snapshot(snapshot_id)
+    }
+
+    fn get_snapshots_for_entity(
+        &self,
+        entity_type: crate::snapshot_types::SnapshotEntityType,
+        entity_id: &str,
+    ) -> Result<Vec<crate::snapshot_types::StateSnapshot>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_snapshots_for_entity(entity_type, entity_id)
+    }
+
+    fn get_latest_snapshot(
+        &self,
+        entity_type: crate::snapshot_types::SnapshotEntityType,
+        entity_id: &str,
+    ) -> Result<Option<crate::snapshot_types::StateSnapshot>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_latest_snapshot(entity_type, entity_id)
+    }
+
+    fn update_snapshot(
+        &self,
+        snapshot: &crate::snapshot_types::StateSnapshot,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_snapshot(snapshot)
+    }
+
+    fn get_snapshot_count(
+        &self,
+        entity_type: crate::snapshot_types::SnapshotEntityType,
+        entity_id: &str,
+    ) -> Result<u64, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_snapshot_count(entity_type, entity_id)
+    }
+
+    // Organization directory operations
+    fn store_organization_profile(
+        &self,
+        profile: &OrganizationProfile,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_organization_profile(profile)
+    }
+
+    fn get_organization_profile(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<OrganizationProfile>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_organization_profile(user_id)
+    }
+
+    fn list_organization_profiles(&self) -> Result<Vec<OrganizationProfile>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_organization_profiles()
+    }
+
+    fn delete_organization_profile(&self, user_id: &str) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_organization_profile(user_id)
+    }
+
+    // Attestation operations
+    fn store_attestation(&self, attestation: &Attestation) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_attestation(attestation)
+    }
+
+    fn get_attestation(&self, attestation_id: &Uuid) -> Result<Option<Attestation>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_attestation(attestation_id)
+    }
+
+    fn list_attestations_for_subject(
+        &self,
+        subject_id: &str,
+    ) -> Result<Vec<Attestation>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_attestations_for_subject(subject_id)
+    }
+
+    fn list_attestations_by_attester(
+        &self,
+        attester_id: &str,
+    ) -> Result<Vec<Attestation>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_attestations_by_attester(attester_id)
+    }
+
+    // Saved audit query operations
+    fn store_saved_audit_query(&self, query: &SavedAuditQuery) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_saved_audit_query(query)
+    }
+
+    fn get_saved_audit_query(
+        &self,
+        query_id: &Uuid,
+    ) -> Result<Option<SavedAuditQuery>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_saved_audit_query(query_id)
+    }
+
+    fn list_saved_audit_queries(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<SavedAuditQuery>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_saved_audit_queries(owner_id)
+    }
+
+    fn delete_saved_audit_query(&self, query_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_saved_audit_query(query_id)
+    }
+
+    // Preview environment operations
+    fn store_preview_environment(&self, preview: &PreviewEnvironment) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_preview_environment(preview)
+    }
+
+    fn get_preview_environment(
+        &self,
+        preview_id: &Uuid,
+    ) -> Result<Option<PreviewEnvironment>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_preview_environment(preview_id)
+    }
+
+    fn list_preview_environments(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<PreviewEnvironment>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_preview_environments(owner_id)
+    }
+
+    // Paginated listings (ordered by creation timestamp, then id)
+    fn list_items_page(
+        &self,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Item>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_items_page(after, limit)
+    }
+
+    fn list_events_page(
+        &self,
+        dfid: Option<&str>,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Event>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_events_page(dfid, after, limit)
+    }
+
+    // Legacy connector operations
+    fn store_connector_config(&self, config: &ConnectorConfig) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_connector_config(config)
+    }
+
+    fn get_connector_config(
+        &self,
+        connector_id: &Uuid,
+    ) -> Result<Option<ConnectorConfig>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_connector_config(connector_id)
+    }
+
+    fn list_connector_configs(&self) -> Result<Vec<ConnectorConfig>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_connector_configs()
+    }
+
+    fn delete_connector_config(&self, connector_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_connector_config(connector_id)
+    }
+
+    fn store_mapping_template(&self, template: &MappingTemplate) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_mapping_template(template)
+    }
+
+    fn get_mapping_template(
+        &self,
+        template_id: &Uuid,
+    ) -> Result<Option<MappingTemplate>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_mapping_template(template_id)
+    }
+
+    fn list_mapping_templates(&self, owner_id: &str) -> Result<Vec<MappingTemplate>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_mapping_templates(owner_id)
+    }
+
+    fn store_connector_state(&self, state: &ConnectorState) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_connector_state(state)
+    }
+
+    fn get_connector_state(
+        &self,
+        connector_id: &Uuid,
+    ) -> Result<Option<ConnectorState>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_connector_state(connector_id)
+    }
+
+    fn store_connector_error(&self, error: &ConnectorRunError) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_connector_error(error)
+    }
+
+    fn list_connector_errors(
+        &self,
+        connector_id: &Uuid,
+        limit: usize,
+    ) -> Result<Vec<ConnectorRunError>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_connector_errors(connector_id, limit)
+    }
+
+    fn delete_mapping_template(&self, template_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_mapping_template(template_id)
+    }
+
+    // Circuit change feed operations
+    fn append_change_record(&self, record: &ChangeRecord) -> Result<ChangeRecord, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.append_change_record(record)
+    }
+
+    fn list_change_records(
+        &self,
+        circuit_id: &Uuid,
+        after_sequence: u64,
+        limit: usize,
+    ) -> Result<Vec<ChangeRecord>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_change_records(circuit_id, after_sequence, limit)
+    }
+
+    fn get_latest_change_sequence(&self, circuit_id: &Uuid) -> Result<u64, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_latest_change_sequence(circuit_id)
+    }
+
+    fn store_change_feed_subscription(
+        &self,
+        subscription: &ChangeFeedSubscription,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_change_feed_subscription(subscription)
+    }
+
+    fn get_change_feed_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<Option<ChangeFeedSubscription>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_change_feed_subscription(subscription_id)
+    }
+
+    fn list_change_feed_subscriptions(
+        &self,
+        circuit_id: Option<&Uuid>,
+    ) -> Result<Vec<ChangeFeedSubscription>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_change_feed_subscriptions(circuit_id)
+    }
+
+    fn delete_change_feed_subscription(&self, subscription_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_change_feed_subscription(subscription_id)
+    }
+
+    // Item search
+    fn search_items(
+        &self,
+        query: &ItemSearchQuery,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Item>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.search_items(query, after, limit)
+    }
+
+    // Announcements
+    fn store_announcement(&self, announcement: &Announcement) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_announcement(announcement)
+    }
+
+    fn get_announcement(
+        &self,
+        announcement_id: &Uuid,
+    ) -> Result<Option<Announcement>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_announcement(announcement_id)
+    }
+
+    fn list_announcements(&self) -> Result<Vec<Announcement>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_announcements()
+    }
+
+    // Workspace engagement snapshots
+    fn store_workspace_engagement(
+        &self,
+        engagement: &WorkspaceEngagement,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_workspace_engagement(engagement)
+    }
+
+    fn get_workspace_engagement(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<WorkspaceEngagement>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_workspace_engagement(workspace_id)
+    }
+
+    // Data export jobs
+    fn store_data_export_job(&self, job: &DataExportJob) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_data_export_job(job)
+    }
+
+    fn get_data_export_job(&self, job_id: &Uuid) -> Result<Option<DataExportJob>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_data_export_job(job_id)
+    }
+
+    fn list_data_export_jobs(
+        &self,
+        requested_by: &str,
+    ) -> Result<Vec<DataExportJob>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_data_export_jobs(requested_by)
+    }
+
+    // SLA windows
+    fn store_sla_window(&self, window: &SlaWindow) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_sla_window(window)
+    }
+
+    fn get_sla_window(
+        &self,
+        component: SlaComponent,
+        hour_start: DateTime<Utc>,
+    ) -> Result<Option<SlaWindow>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_sla_window(component, hour_start)
+    }
+
+    fn list_sla_windows(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<SlaWindow>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_sla_windows(from, to)
+    }
+
+    // Event schemas
+    fn store_event_schema(&self, schema: &EventSchema) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_event_schema(schema)
+    }
+
+    fn list_event_schemas(&self) -> Result<Vec<EventSchema>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_event_schemas()
+    }
+
+    // Maintenance mode
+    fn store_maintenance_mode(&self, mode: &MaintenanceMode) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_maintenance_mode(mode)
+    }
+
+    fn get_maintenance_mode(&self) -> Result<Option<MaintenanceMode>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_maintenance_mode()
+    }
+
+    // Managed keys
+    fn store_managed_key(&self, key: &ManagedKey) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_managed_key(key)
+    }
+
+    fn get_managed_key(&self, key_id: &Uuid) -> Result<Option<ManagedKey>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_managed_key(key_id)
+    }
+
+    fn list_managed_keys(&self) -> Result<Vec<ManagedKey>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_managed_keys()
+    }
+
+    // Custom event types
+    fn store_custom_event_type(&self, event_type: &CustomEventType) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_custom_event_type(event_type)
+    }
+
+    fn list_custom_event_types(&self) -> Result<Vec<CustomEventType>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_custom_event_types()
+    }
+
+    fn delete_custom_event_type(
+        &self,
+        circuit_id: &Uuid,
+        name: &str,
+    ) -> Result<bool, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_custom_event_type(circuit_id, name)
+    }
+
+    // Document notarizations
+    fn store_notarization(&self, notarization: &DocumentNotarization) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_notarization(notarization)
+    }
+
+    fn get_notarization(
+        &self,
+        notarization_id: &Uuid,
+    ) -> Result<Option<DocumentNotarization>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_notarization(notarization_id)
+    }
+
+    fn list_notarizations(&self) -> Result<Vec<DocumentNotarization>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_notarizations()
+    }
+
+    fn store_notarization_batch(&self, batch: &NotarizationBatch) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_notarization_batch(batch)
+    }
+
+    fn get_notarization_batch(
+        &self,
+        batch_id: &Uuid,
+    ) -> Result<Option<NotarizationBatch>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_notarization_batch(batch_id)
+    }
+
+    // Enrichment policies
+    fn store_enrichment_policy(&self, policy: &EnrichmentPolicy) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_enrichment_policy(policy)
+    }
+
+    fn get_enrichment_policy(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<EnrichmentPolicy>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_enrichment_policy(workspace_id)
+    }
+
+    // Event signing keys
+    fn store_event_signing_key(&self, key: &EventSigningKey) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_event_signing_key(key)
+    }
+
+    fn get_event_signing_key(
+        &self,
+        key_id: &Uuid,
+    ) -> Result<Option<EventSigningKey>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_event_signing_key(key_id)
+    }
+
+    fn list_event_signing_keys(&self) -> Result<Vec<EventSigningKey>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_event_signing_keys()
+    }
+
+    // Anchoring SLAs, fee samples and anchoring spend
+    fn store_anchoring_sla(&self, sla: &AnchoringSla) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_anchoring_sla(sla)
+    }
+
+    fn get_anchoring_sla(&self, circuit_id: &Uuid) -> Result<Option<AnchoringSla>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_anchoring_sla(circuit_id)
+    }
+
+    fn store_fee_sample(&self, sample: &FeeSample) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_fee_sample(sample)
+    }
+
+    fn get_latest_fee_sample(
+        &self,
+        network: &AnchoringNetwork,
+    ) -> Result<Option<FeeSample>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_latest_fee_sample(network)
+    }
+
+    fn store_anchoring_cost_record(
+        &self,
+        record: &AnchoringCostRecord,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_anchoring_cost_record(record)
+    }
+
+    fn list_anchoring_cost_records(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AnchoringCostRecord>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_anchoring_cost_records(since)
+    }
+
+    // Event causality
+    fn get_events_caused_by(&self, event_id: &Uuid) -> Result<Vec<Event>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_events_caused_by(event_id)
+    }
+
+    fn get_events_by_correlation_id(
+        &self,
+        correlation_id: &Uuid,
+    ) -> Result<Vec<Event>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_events_by_correlation_id(correlation_id)
+    }
+
+    // Item lifecycles
+    fn store_lifecycle_definition(
+        &self,
+        definition: &LifecycleDefinition,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_lifecycle_definition(definition)
+    }
+
+    fn get_lifecycle_definition(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<LifecycleDefinition>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_lifecycle_definition(workspace_id)
+    }
+
+    fn store_item_lifecycle(&self, lifecycle: &ItemLifecycle) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_item_lifecycle(lifecycle)
+    }
+
+    fn get_item_lifecycle(&self, dfid: &str) -> Result<Option<ItemLifecycle>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_item_lifecycle(dfid)
+    }
+
+    fn list_item_lifecycles(&self, workspace_id: &str) -> Result<Vec<ItemLifecycle>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_item_lifecycles(workspace_id)
+    }
+
+    // Circuit access reviews
+    fn store_access_report(&self, report: &CircuitAccessReport) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_access_report(report)
+    }
+
+    fn get_access_report(
+        &self,
+        report_id: &Uuid,
+    ) -> Result<Option<CircuitAccessReport>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_access_report(report_id)
+    }
+
+    fn list_access_reports(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<CircuitAccessReport>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_access_reports(circuit_id)
+    }
+
+    fn delete_access_report(&self, report_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_access_report(report_id)
+    }
+
+    fn store_access_review_schedule(
+        &self,
+        schedule: &AccessReviewSchedule,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_access_review_schedule(schedule)
+    }
+
+    fn get_access_review_schedule(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Option<AccessReviewSchedule>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_access_review_schedule(circuit_id)
+    }
+
+    fn delete_access_review_schedule(&self, circuit_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_access_review_schedule(circuit_id)
+    }
+
+    fn list_access_review_schedules(&self) -> Result<Vec<AccessReviewSchedule>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_access_review_schedules()
+    }
+
+    // Adapter write intents
+    fn store_adapter_intent(&self, intent: &AdapterIntent) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_adapter_intent(intent)
+    }
+
+    fn get_adapter_intent(&self, intent_id: &Uuid) -> Result<Option<AdapterIntent>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_adapter_intent(intent_id)
+    }
+
+    fn get_incomplete_adapter_intents(&self) -> Result<Vec<AdapterIntent>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_incomplete_adapter_intents()
+    }
+
+    // Circuit-scoped partner tokens
+    fn store_partner_token(&self, token: &PartnerToken) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_partner_token(token)
+    }
+
+    fn get_partner_token(&self, token_id: &Uuid) -> Result<Option<PartnerToken>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_partner_token(token_id)
+    }
+
+    fn get_partner_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PartnerToken>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_partner_token_by_hash(token_hash)
+    }
+
+    fn list_partner_tokens(&self, circuit_id: &Uuid) -> Result<Vec<PartnerToken>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_partner_tokens(circuit_id)
+    }
+
+    fn record_partner_token_usage(&self, usage: &PartnerTokenUsage) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.record_partner_token_usage(usage)
+    }
+
+    fn list_partner_token_usage(
+        &self,
+        token_id: &Uuid,
+        limit: usize,
+    ) -> Result<Vec<PartnerTokenUsage>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_partner_token_usage(token_id, limit)
+    }
+
+    // Circuit comment threads
+    fn store_circuit_comment(&self, comment: &CircuitComment) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_circuit_comment(comment)
+    }
+
+    fn get_circuit_comment(
+        &self,
+        comment_id: &Uuid,
+    ) -> Result<Option<CircuitComment>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_circuit_comment(comment_id)
+    }
+
+    fn list_circuit_comments(
+        &self,
+        circuit_id: &Uuid,
+        target: &CommentTarget,
+    ) -> Result<Vec<CircuitComment>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_circuit_comments(circuit_id, target)
+    }
+
+    fn delete_circuit_comment(&self, comment_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_circuit_comment(comment_id)
+    }
+
+    // Circuit data quality reports
+    fn store_data_quality_report(&self, report: &DataQualityReport) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_data_quality_report(report)
+    }
+
+    fn list_data_quality_reports(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<DataQualityReport>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_data_quality_reports(circuit_id)
+    }
+
+    fn delete_data_quality_report(&self, report_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_data_quality_report(report_id)
+    }
+
+    // Dashboard metric definitions
+    fn store_metric_definition(&self, definition: &MetricDefinition) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_metric_definition(definition)
+    }
+
+    fn get_metric_definition(
+        &self,
+        metric_id: &Uuid,
+    ) -> Result<Option<MetricDefinition>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_metric_definition(metric_id)
+    }
+
+    fn list_metric_definitions(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Vec<MetricDefinition>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_metric_definitions(workspace_id)
+    }
+
+    fn delete_metric_definition(&self, metric_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_metric_definition(metric_id)
+    }
+
+    // ZK setup artifacts
+    fn store_zk_setup_artifact(
+        &self,
+        artifact: &crate::zk_proof_engine::ZkSetupArtifact,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_zk_setup_artifact(artifact)
+    }
+
+    fn get_zk_setup_artifact(
+        &self,
+        artifact_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::ZkSetupArtifact>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_zk_setup_artifact(artifact_id)
+    }
+
+    fn list_zk_setup_artifacts(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkSetupArtifact>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_zk_setup_artifacts()
+    }
+
+    // Data lake garbage collection
+    fn delete_data_lake_entries(&self, entry_ids: &[Uuid]) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_data_lake_entries(entry_ids)
+    }
+
+    fn delete_receipts(&self, receipt_ids: &[Uuid]) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_receipts(receipt_ids)
+    }
+
+    fn store_data_lake_gc_report(
+        &self,
+        report: &crate::types::DataLakeGcReport,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_data_lake_gc_report(report)
+    }
+
+    fn list_data_lake_gc_reports(
+        &self,
+    ) -> Result<Vec<crate::types::DataLakeGcReport>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_data_lake_gc_reports()
+    }
+
+    // Device backups
+    fn store_device_backup(&self, backup: &crate::types::DeviceBackup) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_device_backup(backup)
+    }
+
+    fn get_device_backup(
+        &self,
+        backup_id: &Uuid,
+    ) -> Result<Option<crate::types::DeviceBackup>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_device_backup(backup_id)
+    }
+
+    fn list_device_backups(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<crate::types::DeviceBackup>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_device_backups(user_id)
+    }
+
+    fn delete_device_backup(&self, backup_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_device_backup(backup_id)
+    }
+
+    // Federation
+    fn store_federation_peer(
+        &self,
+        peer: &crate::types::FederationPeer,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_federation_peer(peer)
+    }
+
+    fn get_federation_peer(
+        &self,
+        peer_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationPeer>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_federation_peer(peer_id)
+    }
+
+    fn list_federation_peers(&self) -> Result<Vec<crate::types::FederationPeer>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_federation_peers()
+    }
+
+    fn delete_federation_peer(&self, peer_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_federation_peer(peer_id)
+    }
+
+    fn store_federation_provenance(
+        &self,
+        provenance: &crate::types::FederationProvenance,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_federation_provenance(provenance)
+    }
+
+    fn get_federation_provenance(
+        &self,
+        record_key: &str,
+    ) -> Result<Option<crate::types::FederationProvenance>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_federation_provenance(record_key)
+    }
+
+    fn store_federation_conflict(
+        &self,
+        conflict: &crate::types::FederationConflict,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_federation_conflict(conflict)
+    }
+
+    fn get_federation_conflict(
+        &self,
+        conflict_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationConflict>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_federation_conflict(conflict_id)
+    }
+
+    fn list_federation_conflicts(
+        &self,
+    ) -> Result<Vec<crate::types::FederationConflict>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_federation_conflicts()
+    }
+
+    // ZK proof revocations
+    fn store_zk_proof_revocation(
+        &self,
+        revocation: &crate::zk_proof_engine::ProofRevocation,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_zk_proof_revocation(revocation)
+    }
+
+    fn get_zk_proof_revocation(
+        &self,
+        proof_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofRevocation>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_zk_proof_revocation(proof_id)
+    }
+
+    fn list_zk_proof_revocations(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ProofRevocation>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_zk_proof_revocations()
+    }
+
+    // Selective disclosures
+    fn store_item_attribute_commitments(
+        &self,
+        commitments: &crate::types::ItemAttributeCommitments,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_item_attribute_commitments(commitments)
+    }
+
+    fn get_item_attribute_commitments(
+        &self,
+        dfid: &str,
+    ) -> Result<Option<crate::types::ItemAttributeCommitments>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_item_attribute_commitments(dfid)
+    }
+
+    fn store_selective_disclosure(
+        &self,
+        disclosure: &crate::types::SelectiveDisclosure,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_selective_disclosure(disclosure)
+    }
+
+    fn get_selective_disclosure(
+        &self,
+        disclosure_id: &Uuid,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_selective_disclosure(disclosure_id)
+    }
+
+    fn get_selective_disclosure_by_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_selective_disclosure_by_token(token_hash)
+    }
+
+    fn list_selective_disclosures(
+        &self,
+        created_by: &str,
+    ) -> Result<Vec<crate::types::SelectiveDisclosure>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_selective_disclosures(created_by)
+    }
+
+    // Custom ZK circuit definitions
+    fn store_custom_circuit_definition(
+        &self,
+        definition: &crate::zk_proof_engine::CustomCircuitDefinition,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_custom_circuit_definition(definition)
+    }
+
+    fn get_custom_circuit_definition(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::CustomCircuitDefinition>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_custom_circuit_definition(circuit_id)
+    }
+
+    fn list_custom_circuit_definitions(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::CustomCircuitDefinition>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_custom_circuit_definitions()
+    }
+
+    // ZK proof generation jobs
+    fn store_zk_proof_job(
+        &self,
+        job: &crate::zk_proof_engine::ProofJob,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_zk_proof_job(job)
+    }
+
+    fn get_zk_proof_job(
+        &self,
+        job_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofJob>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_zk_proof_job(job_id)
+    }
+
+    fn list_zk_proof_jobs(&self) -> Result<Vec<crate::zk_proof_engine::ProofJob>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_zk_proof_jobs()
+    }
+
+    // Item search projected to summaries
+    fn search_item_summaries(
+        &self,
+        query: &ItemSearchQuery,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<ItemSummary>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.search_item_summaries(query, after, limit)
+    }
+
+    // Per-workspace API usage and anomalies
+    fn store_workspace_usage_window(
+        &self,
+        window: &crate::types::WorkspaceUsageWindow,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_workspace_usage_window(window)
+    }
+
+    fn get_workspace_usage_window(
+        &self,
+        workspace_id: &str,
+        hour_start: DateTime<Utc>,
+    ) -> Result<Option<crate::types::WorkspaceUsageWindow>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_workspace_usage_window(workspace_id, hour_start)
+    }
+
+    fn list_workspace_usage_windows(
+        &self,
+        workspace_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::WorkspaceUsageWindow>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_workspace_usage_windows(workspace_id, from, to)
+    }
+
+    fn store_usage_anomaly_baseline(
+        &self,
+        baseline: &crate::types::UsageAnomalyBaseline,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_usage_anomaly_baseline(baseline)
+    }
+
+    fn get_usage_anomaly_baseline(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::UsageAnomalyBaseline>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_usage_anomaly_baseline(workspace_id)
+    }
+
+    fn store_usage_anomaly(
+        &self,
+        anomaly: &crate::types::UsageAnomaly,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_usage_anomaly(anomaly)
+    }
+
+    fn list_usage_anomalies(
+        &self,
+        workspace_id: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::UsageAnomaly>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_usage_anomalies(workspace_id, since)
+    }
+
+    // Proof share links
+    fn store_proof_share_link(
+        &self,
+        link: &crate::types::ProofShareLink,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_proof_share_link(link)
+    }
+
+    fn get_proof_share_link(
+        &self,
+        share_id: &Uuid,
+    ) -> Result<Option<crate::types::ProofShareLink>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_proof_share_link(share_id)
+    }
+
+    fn record_proof_share_view(
+        &self,
+        share_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<crate::types::ProofShareLink>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.record_proof_share_view(share_id, now)
+    }
+
+    fn list_proof_share_links(
+        &self,
+        proof_id: &Uuid,
+    ) -> Result<Vec<crate::types::ProofShareLink>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_proof_share_links(proof_id)
+    }
+
+    // Workspace dedup configs
+    fn store_workspace_dedup_config(
+        &self,
+        config: &crate::types::WorkspaceDedupConfig,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_workspace_dedup_config(config)
+    }
+
+    fn get_workspace_dedup_config(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceDedupConfig>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_workspace_dedup_config(workspace_id)
+    }
+
+    // Sensor time series
+    fn store_sensor_series(&self, series: &crate::types::SensorSeries) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_sensor_series(series)
+    }
+
+    fn get_sensor_series(
+        &self,
+        dfid: &str,
+        series: &str,
+    ) -> Result<Option<crate::types::SensorSeries>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_sensor_series(dfid, series)
+    }
+
+    fn list_sensor_series(
+        &self,
+        dfid: &str,
+    ) -> Result<Vec<crate::types::SensorSeries>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_sensor_series(dfid)
+    }
+
+    fn store_sensor_chunk(&self, chunk: &crate::types::SensorChunk) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_sensor_chunk(chunk)
+    }
+
+    fn get_sensor_chunks(
+        &self,
+        dfid: &str,
+        series: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorChunk>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_sensor_chunks(dfid, series, from, to)
+    }
+
+    fn store_sensor_rollup(&self, rollup: &crate::types::SensorRollup) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_sensor_rollup(rollup)
+    }
+
+    fn get_sensor_rollups(
+        &self,
+        dfid: &str,
+        series: &str,
+        resolution: crate::types::SensorResolution,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorRollup>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_sensor_rollups(dfid, series, resolution, from, to)
+    }
+
+    fn list_all_sensor_series(&self) -> Result<Vec<crate::types::SensorSeries>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_all_sensor_series()
+    }
+
+    // Workspace buckets
+    fn store_workspace_bucket_config(
+        &self,
+        config: &crate::types::WorkspaceBucketConfig,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_workspace_bucket_config(config)
+    }
+
+    fn get_workspace_bucket_config(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceBucketConfig>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_workspace_bucket_config(workspace_id)
+    }
+
+    fn delete_workspace_bucket_config(&self, workspace_id: &str) -> Result<bool, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_workspace_bucket_config(workspace_id)
+    }
+
+    // DFID sequence leases
+    fn lease_dfid_sequence_range(&self, day: &str, count: u64) -> Result<u64, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.lease_dfid_sequence_range(day, count)
+    }
+
+    // Re-verification jobs and merge proposals
+    fn store_reverification_job(&self, job: &ReverificationJob) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_reverification_job(job)
+    }
+
+    fn get_reverification_job(
+        &self,
+        job_id: &Uuid,
+    ) -> Result<Option<ReverificationJob>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_reverification_job(job_id)
+    }
+
+    fn list_reverification_jobs(&self) -> Result<Vec<ReverificationJob>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_reverification_jobs()
+    }
+
+    fn store_merge_proposal(&self, proposal: &MergeProposal) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_merge_proposal(proposal)
+    }
+
+    fn get_merge_proposal(
+        &self,
+        proposal_id: &Uuid,
+    ) -> Result<Option<MergeProposal>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_merge_proposal(proposal_id)
+    }
+
+    fn list_merge_proposals(&self) -> Result<Vec<MergeProposal>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_merge_proposals()
+    }
+
+    // Runbook hooks
+    fn store_runbook_hook(&self, hook: &RunbookHook) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_runbook_hook(hook)
+    }
+
+    fn get_runbook_hook(&self, hook_id: &Uuid) -> Result<Option<RunbookHook>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_runbook_hook(hook_id)
+    }
+
+    fn list_runbook_hooks(&self) -> Result<Vec<RunbookHook>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_runbook_hooks()
+    }
+
+    fn delete_runbook_hook(&self, hook_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_runbook_hook(hook_id)
+    }
+
+    // Workspace verification rules
+    fn store_workspace_verification_rules(
+        &self,
+        rules: &WorkspaceVerificationRules,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_workspace_verification_rules(rules)
+    }
+
+    fn get_workspace_verification_rules(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<WorkspaceVerificationRules>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_workspace_verification_rules(workspace_id)
+    }
+
+    // Dedup sharing agreements
+    fn store_dedup_sharing_agreement(
+        &self,
+        agreement: &DedupSharingAgreement,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_dedup_sharing_agreement(agreement)
+    }
+
+    fn get_dedup_sharing_agreement(
+        &self,
+        agreement_id: &Uuid,
+    ) -> Result<Option<DedupSharingAgreement>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_dedup_sharing_agreement(agreement_id)
+    }
+
+    fn list_dedup_sharing_agreements(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<DedupSharingAgreement>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_dedup_sharing_agreements(circuit_id)
+    }
+
+    // Paginated CID timeline
+    fn get_item_timeline_page(
+        &self,
+        dfid: &str,
+        query: &TimelineQuery,
+        after_sequence: Option<i32>,
+        limit: usize,
+    ) -> Result<Vec<TimelineEntry>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_item_timeline_page(dfid, query, after_sequence, limit)
+    }
+
+    // Circuit membership reconciliation
+    fn store_membership_reconciliation_report(
+        &self,
+        report: &MembershipReconciliationReport,
+    ) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_membership_reconciliation_report(report)
+    }
+
+    fn list_membership_reconciliation_reports(
+        &self,
+    ) -> Result<Vec<MembershipReconciliationReport>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_membership_reconciliation_reports()
+    }
+}
+
+impl Default for InMemoryStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct EncryptedFileStorage {
+    base_path: String,
+    encryption_key: Option<EncryptionKey>,
+}
+
+impl EncryptedFileStorage {
+    pub fn new(base_path: impl Into<String>) -> Self {
+        Self {
+            base_path: base_path.into(),
+            encryption_key: None,
+        }
+    }
+
+    pub fn with_encryption(mut self, key: EncryptionKey) -> Self {
+        self.encryption_key = Some(key);
+        self
+    }
+
+    fn encrypt_data(&self, data: &[u8]) -> Result<EncryptedData, StorageError> {
+        if let Some(key) = &self.encryption_key {
+            key.encrypt(data)
+        } else {
+            Ok(EncryptedData {
+                data: data.to_vec(),
+                nonce: [0u8; 12],
+            })
+        }
+    }
+
+    fn decrypt_data(&self, encrypted: &EncryptedData) -> Result<Vec<u8>, StorageError> {
+        if let Some(key) = &self.encryption_key {
+            key.decrypt(encrypted)
+        } else {
+            Ok(encrypted.data.clone())
+        }
+    }
+
+    fn ensure_directory(&self, path: &str) -> Result<(), StorageError> {
+        let dir_path = Path::new(&self.base_path).join(path);
+        fs::create_dir_all(dir_path)?;
+        Ok(())
+    }
+}
+
+impl StorageBackend for EncryptedFileStorage {
+    fn store_receipt(&self, receipt: &Receipt) -> Result<(), StorageError> {
+        self.ensure_directory("receipts")?;
+
+        let serialized = serde_json::to_vec(receipt)?;
+        let encrypted = self.encrypt_data(&serialized)?;
+        let encrypted_json = serde_json::to_vec(&encrypted)?;
+
+        let file_path = Path::new(&self.base_path)
+            .join("receipts")
+            .join(format!("{}.json", receipt.id));
+
+        fs::write(file_path, encrypted_json)?;
+        Ok(())
+    }
+
+    fn store_receipts(&self, receipts: &[Receipt]) -> Result<(), StorageError> {
+        for receipt in receipts {
+            self.store_receipt(receipt)?;
+        }
+        Ok(())
+    }
+
+    fn get_receipt(&self, id: &Uuid) -> Result<Option<Receipt>, StorageError> {
+        let file_path = Path::new(&self.base_path)
+            .join("receipts")
+            .join(format!("{id}.json"));
+
+        if !file_path.exists() {
+            return Ok(None);
+        }
+
+        let encrypted_json = fs::read(file_path)?;
+        let encrypted: EncryptedData = serde_json::from_slice(&encrypted_json)?;
+        let decrypted = self.decrypt_data(&encrypted)?;
+        let receipt: Receipt = serde_json::from_slice(&decrypted)?;
+
+        Ok(Some(receipt))
+    }
+
+    fn find_receipts_by_identifier(
+        &self,
+        _identifier: &Identifier,
+    ) -> Result<Vec<Receipt>, StorageError> {
+        let receipts = self.list_receipts()?;
+        Ok(receipts
+            .into_iter()
+            .filter(|receipt| receipt.identifiers.contains(_identifier))
+            .collect())
+    }
+
+    fn list_receipts(&self) -> Result<Vec<Receipt>, StorageError> {
+        let receipts_dir = Path::new(&self.base_path).join("receipts");
+        if !receipts_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut receipts = Vec::new();
+        for entry in fs::read_dir(receipts_dir)? {
+            let entry = entry?;
+            if entry.path().extension().and_then(|s| s.to_str()) == Some("json") {
+                let encrypted_json = fs::read(entry.path())?;
+                let encrypted: EncryptedData = serde_json::from_slice(&encrypted_json)?;
+                let decrypted = self.decrypt_data(&encrypted)?;
+                let receipt: Receipt = serde_json::from_slice(&decrypted)?;
+                receipts.push(receipt);
+            }
+        }
+
+        Ok(receipts)
+    }
+
+    fn store_log(&self, log: &LogEntry) -> Result<(), StorageError> {
+        self.ensure_directory("logs")?;
+
+        let serialized = serde_json::to_vec(log)?;
+        let encrypted = self.encrypt_data(&serialized)?;
+        let encrypted_json = serde_json::to_vec(&encrypted)?;
+
+        let file_path = Path::new(&self.base_path)
+            .join("logs")
+            .join(format!("{}.json", log.id));
+
+        fs::write(file_path, encrypted_json)?;
+        Ok(())
+    }
+
+    fn get_logs(&self) -> Result<Vec<LogEntry>, StorageError> {
+        let logs_dir = Path::new(&self.base_path).join("logs");
+        if !logs_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut logs = Vec::new();
+        for entry in fs::read_dir(logs_dir)? {
+            let entry = entry?;
+            if entry.path().extension().and_then(|s| s.to_str()) == Some("json") {
+                let encrypted_json = fs::read(entry.path())?;
+                let encrypted: EncryptedData = serde_json::from_slice(&encrypted_json)?;
+                let decrypted = self.decrypt_data(&encrypted)?;
+                let log: LogEntry = serde_json::from_slice(&decrypted)?;
+                logs.push(log);
+            }
+        }
+
+        logs.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        Ok(logs)
+    }
+
+    // For simplicity, EncryptedFileStorage will delegate to an internal InMemoryStorage for new data types
+    // In a real implementation, these would be properly encrypted and stored to files
+
+    fn store_data_lake_entry(&self, _entry: &DataLakeEntry) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Data lake operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn store_data_lake_entries(&self, _entries: &[DataLakeEntry]) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Data lake operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_data_lake_entry(&self, _entry_id: &Uuid) -> Result<Option<DataLakeEntry>, StorageError> {
+        Ok(None)
+    }
+
+    fn update_data_lake_entry(&self, _entry: &DataLakeEntry) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Data lake operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_data_lake_entries_by_status(
+        &self,
+        _status: ProcessingStatus,
+    ) -> Result<Vec<DataLakeEntry>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn list_data_lake_entries(&self) -> Result<Vec<DataLakeEntry>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn store_item(&self, _item: &Item) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Item operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_item_by_dfid(&self, _dfid: &str) -> Result<Option<Item>, StorageError> {
+        Ok(None)
+    }
+
+    fn update_item(&self, _item: &Item) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Item operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn list_items(&self) -> Result<Vec<Item>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn find_items_by_identifier(
+        &self,
+        _identifier: &Identifier,
+    ) -> Result<Vec<Item>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn find_items_by_status(&self, _status: ItemStatus) -> Result<Vec<Item>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn delete_item(&self, _dfid: &str) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn store_identifier_mapping(&self, _mapping: &IdentifierMapping) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Identifier mapping operations not yet implemented for EncryptedFileStorage"
+                .to_string(),
+        ))
+    }
+
+    fn get_identifier_mappings(
+        &self,
+        _identifier: &Identifier,
+    ) -> Result<Vec<IdentifierMapping>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn update_identifier_mapping(&self, _mapping: &IdentifierMapping) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Identifier mapping operations not yet implemented for EncryptedFileStorage"
+                .to_string(),
+        ))
+    }
+
+    fn list_identifier_mappings(&self) -> Result<Vec<IdentifierMapping>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn store_conflict_resolution(
+        &self,
+        _conflict: &ConflictResolution,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Conflict resolution operations not yet implemented for EncryptedFileStorage"
+                .to_string(),
+        ))
+    }
+
+    fn get_conflict_resolution(
+        &self,
+        _conflict_id: &Uuid,
+    ) -> Result<Option<ConflictResolution>, StorageError> {
+        Ok(None)
+    }
+
+    fn get_pending_conflicts(&self) -> Result<Vec<ConflictResolution>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    // Event operations - placeholder implementations
+    fn store_event(&self, _event: &Event) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Event operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn store_events(&self, _events: &[Event]) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Event operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_event(&self, _event_id: &Uuid) -> Result<Option<Event>, StorageError> {
+        Ok(None)
+    }
+
+    fn update_event(&self, _event: &Event) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Event operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn list_events(&self) -> Result<Vec<Event>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_events_by_dfid(&self, _dfid: &str) -> Result<Vec<Event>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_events_by_type(&self, _event_type: EventType) -> Result<Vec<Event>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_events_by_visibility(
+        &self,
+        _visibility: EventVisibility,
+    ) -> Result<Vec<Event>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_events_in_time_range(
+        &self,
+        _start: DateTime<Utc>,
+        _end: DateTime<Utc>,
+    ) -> Result<Vec<Event>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_event_by_content_hash(
+        &self,
+        _content_hash: &str,
+    ) -> Result<Option<Event>, StorageError> {
+        // EncryptedFileStorage doesn't implement event storage, return None
+        Ok(None)
+    }
+
+    // Circuit operations - placeholder implementations
+    fn store_circuit(&self, _circuit: &Circuit) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Circuit operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_circuit(&self, _circuit_id: &Uuid) -> Result<Option<Circuit>, StorageError> {
+        Ok(None)
+    }
+
+    fn update_circuit(&self, _circuit: &Circuit) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Circuit operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn list_circuits(&self) -> Result<Vec<Circuit>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_circuits_for_member(&self, _member_id: &str) -> Result<Vec<Circuit>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_archived_circuits_for_member(
+        &self,
+        _member_id: &str,
+    ) -> Result<Vec<Circuit>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_child_circuits(&self, _parent_id: &Uuid) -> Result<Vec<Circuit>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    // Circuit Operation operations - placeholder implementations
+    fn store_circuit_operation(&self, _operation: &CircuitOperation) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Circuit operation operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_circuit_operation(
+        &self,
+        _operation_id: &Uuid,
+    ) -> Result<Option<CircuitOperation>, StorageError> {
+        Ok(None)
+    }
+
+    fn update_circuit_operation(&self, _operation: &CircuitOperation) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Circuit operation operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_circuit_operations(
+        &self,
+        _circuit_id: &Uuid,
+    ) -> Result<Vec<CircuitOperation>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    // Item Share operations - Not implemented for EncryptedFileStorage yet
+    fn store_item_share(&self, _share: &ItemShare) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Item share operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_item_share(&self, _share_id: &str) -> Result<Option<ItemShare>, StorageError> {
+        Ok(None)
+    }
+
+    fn get_shares_for_user(&self, _user_id: &str) -> Result<Vec<ItemShare>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_shares_for_item(&self, _dfid: &str) -> Result<Vec<ItemShare>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn is_item_shared_with_user(&self, _dfid: &str, _user_id: &str) -> Result<bool, StorageError> {
+        Ok(false)
+    }
+
+    fn delete_item_share(&self, _share_id: &str) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn store_activity(&self, _activity: &Activity) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn get_activities_for_user(&self, _user_id: &str) -> Result<Vec<Activity>, StorageError> {
+        Ok(vec![])
+    }
+
+    fn get_activities_for_circuit(
+        &self,
+        _circuit_id: &Uuid,
+    ) -> Result<Vec<Activity>, StorageError> {
+        Ok(vec![])
+    }
+
+    fn get_all_activities(&self) -> Result<Vec<Activity>, StorageError> {
+        Ok(vec![])
+    }
+
+    fn store_circuit_item(&self, _circuit_item: &CircuitItem) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn get_circuit_items(&self, _circuit_id: &Uuid) -> Result<Vec<CircuitItem>, StorageError> {
+        Ok(vec![])
+    }
+
+    fn remove_circuit_item(&self, _circuit_id: &Uuid, _dfid: &str) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    // Pending Items operations - placeholder implementations
+    fn store_pending_item(&self, _item: &PendingItem) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Pending item operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_pending_item(&self, _pending_id: &Uuid) -> Result<Option<PendingItem>, StorageError> {
+        Ok(None)
+    }
+
+    fn list_pending_items(&self) -> Result<Vec<PendingItem>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_pending_items_by_reason(
+        &self,
+        _reason_type: &str,
+    ) -> Result<Vec<PendingItem>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_pending_items_by_user(&self, _user_id: &str) -> Result<Vec<PendingItem>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_pending_items_by_workspace(
+        &self,
+        _workspace_id: &str,
+    ) -> Result<Vec<PendingItem>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_pending_items_by_priority(
+        &self,
+        _priority: PendingPriority,
+    ) -> Result<Vec<PendingItem>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn update_pending_item(&self, _item: &PendingItem) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Pending item operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn delete_pending_item(&self, _pending_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Pending item operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_pending_items_requiring_manual_review(&self) -> Result<Vec<PendingItem>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    // Audit methods - not yet implemented for EncryptedFileStorage
+    fn store_audit_event(&self, _event: &AuditEvent) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Audit operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_audit_event(&self, _event_id: &Uuid) -> Result<Option<AuditEvent>, StorageError> {
+        Ok(None)
+    }
+
+    fn query_audit_events(&self, _query: &AuditQuery) -> Result<Vec<AuditEvent>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn list_audit_events(&self) -> Result<Vec<AuditEvent>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_audit_events_by_user(&self, _user_id: &str) -> Result<Vec<AuditEvent>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_audit_events_by_type(
+        &self,
+        _event_type: AuditEventType,
+    ) -> Result<Vec<AuditEvent>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_audit_events_by_severity(
+        &self,
+        _severity: AuditSeverity,
+    ) -> Result<Vec<AuditEvent>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_audit_events_in_time_range(
+        &self,
+        _start: DateTime<Utc>,
+        _end: DateTime<Utc>,
+    ) -> Result<Vec<AuditEvent>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn sync_audit_events(&self, _events: Vec<AuditEvent>) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn store_security_incident(&self, _incident: &SecurityIncident) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Security incident operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_security_incident(
+        &self,
+        _incident_id: &Uuid,
+    ) -> Result<Option<SecurityIncident>, StorageError> {
+        Ok(None)
+    }
+
+    fn update_security_incident(&self, _incident: &SecurityIncident) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Security incident operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn list_security_incidents(&self) -> Result<Vec<SecurityIncident>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_incidents_by_severity(
+        &self,
+        _severity: AuditSeverity,
+    ) -> Result<Vec<SecurityIncident>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_open_incidents(&self) -> Result<Vec<SecurityIncident>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_incidents_by_assignee(
+        &self,
+        _assignee: &str,
+    ) -> Result<Vec<SecurityIncident>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn store_compliance_report(&self, _report: &ComplianceReport) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Compliance report operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_compliance_report(
+        &self,
+        _report_id: &Uuid,
+    ) -> Result<Option<ComplianceReport>, StorageError> {
+        Ok(None)
+    }
+
+    fn update_compliance_report(&self, _report: &ComplianceReport) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "Compliance report operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn list_compliance_reports(&self) -> Result<Vec<ComplianceReport>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_reports_by_type(
+        &self,
+        _report_type: &str,
+    ) -> Result<Vec<ComplianceReport>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_pending_reports(&self) -> Result<Vec<ComplianceReport>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_audit_dashboard_metrics(&self) -> Result<AuditDashboardMetrics, StorageError> {
+        Ok(AuditDashboardMetrics {
+            total_events: 0,
+            events_last_24h: 0,
+            events_last_7d: 0,
+            security_incidents: SecurityIncidentSummary {
+                open: 0,
+                critical: 0,
+                resolved: 0,
+            },
+            compliance_status: ComplianceStatus {
+                gdpr_events: 0,
+                ccpa_events: 0,
+                hipaa_events: 0,
+                sox_events: 0,
+            },
+            top_users: Vec::new(),
+            anomalies: Vec::new(),
+        })
+    }
+
+    fn get_event_count_by_time_range(
+        &self,
+        _start: DateTime<Utc>,
+        _end: DateTime<Utc>,
+    ) -> Result<u64, StorageError> {
+        Ok(0)
+    }
+
+    // ZK Proof operations - placeholder implementations
+    fn store_zk_proof(&self, _proof: &crate::zk_proof_engine::ZkProof) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "ZK proof operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_zk_proof(
+        &self,
+        _proof_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ZkProof>, StorageError> {
+        Ok(None)
+    }
+
+    fn update_zk_proof(
+        &self,
+        _proof: &crate::zk_proof_engine::ZkProof,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::IoError(
+            "ZK proof operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn query_zk_proofs(
+        &self,
+        _query: &crate::api::zk_proofs::ZkProofQuery,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn list_zk_proofs(&self) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_zk_proofs_by_user(
+        &self,
+        _user_id: &str,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_zk_proofs_by_circuit_type(
+        &self,
+        _circuit_type: CircuitType,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_zk_proofs_by_status(
+        &self,
+        _status: crate::zk_proof_engine::ProofStatus,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_zk_proof_statistics(
+        &self,
+    ) -> Result<crate::api::zk_proofs::ZkProofStatistics, StorageError> {
+        Ok(crate::api::zk_proofs::ZkProofStatistics {
+            total_proofs: 0,
+            verified_proofs: 0,
+            failed_proofs: 0,
+            pending_proofs: 0,
+            proof_types: std::collections::HashMap::new(),
+        })
+    }
+
+    fn delete_zk_proof(&self, _proof_id: &Uuid) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn store_storage_history(&self, _history: &ItemStorageHistory) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Storage history operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_storage_history(&self, _dfid: &str) -> Result<Option<ItemStorageHistory>, StorageError> {
+        Ok(None)
+    }
+
+    fn add_storage_record(&self, _dfid: &str, _record: StorageRecord) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Storage history operations not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    // CID Timeline operations - not implemented for EncryptedFileStorage
+    fn add_cid_to_timeline(
+        &self,
+        _dfid: &str,
+        _cid: &str,
+        _ipcm_tx: &str,
+        _timestamp: i64,
+        _network: &str,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "CID timeline operations not implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_item_timeline(&self, _dfid: &str) -> Result<Vec<TimelineEntry>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "CID timeline operations not implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_timeline_by_sequence(
+        &self,
+        _dfid: &str,
+        _sequence: i32,
+    ) -> Result<Option<TimelineEntry>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "CID timeline operations not implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn map_event_to_cid(
+        &self,
+        _event_id: &Uuid,
+        _dfid: &str,
+        _cid: &str,
+        _sequence: i32,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "CID timeline operations not implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_event_first_cid(
+        &self,
+        _event_id: &Uuid,
+    ) -> Result<Option<EventCidMapping>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "CID timeline operations not implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_events_in_cid(&self, _cid: &str) -> Result<Vec<EventCidMapping>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "CID timeline operations not implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn update_indexing_progress(
+        &self,
+        _network: &str,
+        _last_ledger: i64,
+        _confirmed_ledger: i64,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Indexing progress operations not implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_indexing_progress(
+        &self,
+        _network: &str,
+    ) -> Result<Option<IndexingProgress>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Indexing progress operations not implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn increment_events_indexed(&self, _network: &str, _count: i64) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Indexing progress operations not implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn store_circuit_adapter_config(
+        &self,
+        _config: &CircuitAdapterConfig,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Circuit adapter config operations not yet implemented for EncryptedFileStorage"
+                .to_string(),
+        ))
+    }
+
+    fn get_circuit_adapter_config(
+        &self,
+        _circuit_id: &Uuid,
+    ) -> Result<Option<CircuitAdapterConfig>, StorageError> {
+        Ok(None)
+    }
+
+    fn update_circuit_adapter_config(
+        &self,
+        _config: &CircuitAdapterConfig,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Circuit adapter config operations not yet implemented for EncryptedFileStorage"
+                .to_string(),
+        ))
+    }
+
+    fn list_circuit_adapter_configs(&self) -> Result<Vec<CircuitAdapterConfig>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    // User Account operations (stub implementations for now)
+    fn store_user_account(&self, _user: &UserAccount) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "User account operations not yet implemented".to_string(),
+        ))
+    }
+
+    fn get_user_account(&self, _user_id: &str) -> Result<Option<UserAccount>, StorageError> {
+        Ok(None)
+    }
+
+    fn get_user_by_username(&self, _username: &str) -> Result<Option<UserAccount>, StorageError> {
+        Ok(None)
+    }
+
+    fn get_user_by_email(&self, _email: &str) -> Result<Option<UserAccount>, StorageError> {
+        Ok(None)
+    }
+
+    fn update_user_account(&self, _user: &UserAccount) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "User account operations not yet implemented".to_string(),
+        ))
+    }
+
+    fn list_user_accounts(&self) -> Result<Vec<UserAccount>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn delete_user_account(&self, _user_id: &str) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "User account operations not yet implemented".to_string(),
+        ))
+    }
+
+    fn store_password_reset_token(&self, _token: &PasswordResetToken) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Password reset tokens not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn get_password_reset_token_by_hash(
+        &self,
+        _token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Password reset tokens not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn mark_token_as_used(&self, _token_id: &str) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Password reset tokens not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn count_recent_reset_requests(
+        &self,
+        _user_id: &str,
+        _since: DateTime<Utc>,
+    ) -> Result<usize, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Password reset tokens not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    fn cleanup_expired_tokens(&self) -> Result<usize, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Password reset tokens not yet implemented for EncryptedFileStorage".to_string(),
+        ))
+    }
+
+    // Credit Transaction operations (stub implementations for now)
+    fn record_credit_transaction(
+        &self,
+        _transaction: &CreditTransaction,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Credit transaction operations not yet implemented".to_string(),
+        ))
+    }
+
+    fn get_credit_transaction(
+        &self,
+        _transaction_id: &str,
+    ) -> Result<Option<CreditTransaction>, StorageError> {
+        Ok(None)
+    }
+
+    fn get_credit_transactions(
+        &self,
+        _user_id: &str,
+        _limit: Option<usize>,
+    ) -> Result<Vec<CreditTransaction>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_credit_transactions_by_operation(
+        &self,
+        _operation_type: &str,
+    ) -> Result<Vec<CreditTransaction>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    // Admin Action operations (stub implementations for now)
+    fn record_admin_action(&self, _action: &AdminAction) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Admin action operations not yet implemented".to_string(),
+        ))
+    }
+
+    fn get_admin_actions(
+        &self,
+        _admin_id: Option<&str>,
+        _limit: Option<usize>,
+    ) -> Result<Vec<AdminAction>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    fn get_admin_actions_by_type(
+        &self,
+        _action_type: &str,
+    ) -> Result<Vec<AdminAction>, StorageError> {
+        Ok(Vec::new())
+    }
+
+    // System Statistics operations (stub implementations for now)
+    fn get_system_statistics(&self) -> Result<SystemStatistics, StorageError> {
+        Ok(SystemStatistics {
+            total_users: 0,
+            active_users_24h: 0,
+            active_users_30d: 0,
+            total_items: 0,
+            total_circuits: 0,
+            total_storage_operations: 0,
+            credits_consumed_24h: 0,
+            tier_distribution: HashMap::new(),
+            adapter_usage_stats: HashMap::new(),
+            generated_at: Utc::now(),
+        })
+    }
+
+    fn update_system_statistics(&self, _stats: &SystemStatistics) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "System statistics operations not yet implemented".to_string(),
+        ))
+    }
+
+    // Notification operations - not implemented for file storage yet
+    fn store_notification(&self, _notification: &Notification) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Notification operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_notification(
+        &self,
+        _notification_id: &str,
+    ) -> Result<Option<Notification>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Notification operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_user_notifications(
+        &self,
+        _user_id: &str,
+        _since: Option<DateTime<Utc>>,
+        _limit: Option<usize>,
+        _unread_only: bool,
+    ) -> Result<Vec<Notification>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Notification operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn update_notification(&self, _notification: &Notification) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Notification operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_notification(&self, _notification_id: &str) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Notification operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn mark_all_notifications_read(&self, _user_id: &str) -> Result<usize, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Notification operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_unread_notification_count(&self, _user_id: &str) -> Result<usize, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Notification operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Adapter Configuration Management operations - not implemented for file storage yet
+    fn store_adapter_config(&self, _config: &AdapterConfig) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Adapter config operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_adapter_config(&self, _config_id: &Uuid) -> Result<Option<AdapterConfig>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Adapter config operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn update_adapter_config(&self, _config: &AdapterConfig) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Adapter config operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_adapter_config(&self, _config_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Adapter config operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_adapter_configs(&self) -> Result<Vec<AdapterConfig>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Adapter config operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_active_adapter_configs(&self) -> Result<Vec<AdapterConfig>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Adapter config operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_adapter_configs_by_type(
+        &self,
+        _adapter_type: &AdapterType,
+    ) -> Result<Vec<AdapterConfig>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Adapter config operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_default_adapter_config(&self) -> Result<Option<AdapterConfig>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Adapter config operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn set_default_adapter(&self, _config_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Adapter config operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_adapter_test_result(&self, _result: &AdapterTestResult) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Adapter config operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_adapter_test_result(
+        &self,
+        _config_id: &Uuid,
+    ) -> Result<Option<AdapterTestResult>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Adapter config operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // LID ↔ DFID mapping operations - not implemented for file storage yet
+    fn store_lid_dfid_mapping(&self, _lid: &Uuid, _dfid: &str) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "LID-DFID mapping not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_dfid_by_lid(&self, _lid: &Uuid) -> Result<Option<String>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "LID-DFID mapping not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Canonical identifier lookups - not implemented for file storage yet
+    fn get_dfid_by_canonical(
+        &self,
+        _namespace: &str,
+        _registry: &str,
+        _value: &str,
+    ) -> Result<Option<String>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Canonical lookup not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Fingerprint mappings - not implemented for file storage yet
+    fn store_fingerprint_mapping(
+        &self,
+        _fingerprint: &str,
+        _dfid: &str,
+        _circuit_id: &Uuid,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Fingerprint mapping not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_dfid_by_fingerprint(
+        &self,
+        _fingerprint: &str,
+        _circuit_id: &Uuid,
+    ) -> Result<Option<String>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Fingerprint lookup not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Enhanced identifier mappings - not implemented for file storage yet
+    fn store_enhanced_identifier_mapping(
+        &self,
+        _identifier: &EnhancedIdentifier,
+        _dfid: &str,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Enhanced identifier mapping not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Webhook delivery operations - not implemented for file storage yet
+    fn store_webhook_delivery(&self, _delivery: &WebhookDelivery) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Webhook delivery operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_webhook_delivery(
+        &self,
+        _delivery_id: &Uuid,
+    ) -> Result<Option<WebhookDelivery>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Webhook delivery operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_webhook_deliveries_by_circuit(
+        &self,
+        _circuit_id: &Uuid,
+        _limit: Option<usize>,
+    ) -> Result<Vec<WebhookDelivery>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Webhook delivery operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_webhook_deliveries_by_webhook(
+        &self,
+        _webhook_id: &Uuid,
+        _limit: Option<usize>,
+    ) -> Result<Vec<WebhookDelivery>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Webhook delivery operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // User Activity operations
+    fn store_user_activity(&self, _activity: &UserActivity) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "User activity operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_user_activities(&self) -> Result<Vec<UserActivity>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "User activity operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn clear_user_activities(&self) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "User activity operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // State Snapshot operations - not implemented for file storage yet
+    fn store_snapshot(
+        &self,
+        _snapshot: &crate::snapshot_types::StateSnapshot,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Snapshot operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_snapshot(
+        &self,
+        _snapshot_id: &str,
+    ) -> Result<Option<crate::snapshot_types::StateSnapshot>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Snapshot operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_snapshots_for_entity(
+        &self,
+        _entity_type: crate::snapshot_types::SnapshotEntityType,
+        _entity_id: &str,
+    ) -> Result<Vec<crate::snapshot_types::StateSnapshot>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Snapshot operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_latest_snapshot(
+        &self,
+        _entity_type: crate::snapshot_types::SnapshotEntityType,
+        _entity_id: &str,
+    ) -> Result<Option<crate::snapshot_types::StateSnapshot>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Snapshot operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn update_snapshot(
+        &self,
+        _snapshot: &crate::snapshot_types::StateSnapshot,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Snapshot operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_snapshot_count(
+        &self,
+        _entity_type: crate::snapshot_types::SnapshotEntityType,
+        _entity_id: &str,
+    ) -> Result<u64, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Snapshot operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Organization directory operations - not implemented for file storage yet
+    fn store_organization_profile(
+        &self,
+        _profile: &OrganizationProfile,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Organization directory operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_organization_profile(
+        &self,
+        _user_id: &str,
+    ) -> Result<Option<OrganizationProfile>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Organization directory operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_organization_profiles(&self) -> Result<Vec<OrganizationProfile>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Organization directory operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_organization_profile(&self, _user_id: &str) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Organization directory operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Attestation operations - not implemented for file storage yet
+    fn store_attestation(&self, _attestation: &Attestation) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Attestation operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_attestation(&self, _attestation_id: &Uuid) -> Result<Option<Attestation>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Attestation operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_attestations_for_subject(
+        &self,
+        _subject_id: &str,
+    ) -> Result<Vec<Attestation>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Attestation operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_attestations_by_attester(
+        &self,
+        _attester_id: &str,
+    ) -> Result<Vec<Attestation>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Attestation operations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Saved audit query operations - not implemented for file storage yet
+    fn store_saved_audit_query(&self, _query: &SavedAuditQuery) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Saved audit queries not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_saved_audit_query(
+        &self,
+        _query_id: &Uuid,
+    ) -> Result<Option<SavedAuditQuery>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Saved audit queries not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_saved_audit_queries(
+        &self,
+        _owner_id: &str,
+    ) -> Result<Vec<SavedAuditQuery>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Saved audit queries not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_saved_audit_query(&self, _query_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Saved audit queries not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Preview environment operations - not implemented for file storage yet
+    fn store_preview_environment(&self, _preview: &PreviewEnvironment) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Preview environments not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_preview_environment(
+        &self,
+        _preview_id: &Uuid,
+    ) -> Result<Option<PreviewEnvironment>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Preview environments not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_preview_environments(
+        &self,
+        _owner_id: &str,
+    ) -> Result<Vec<PreviewEnvironment>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Preview environments not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Paginated listings (ordered by creation timestamp, then id) - not implemented for file storage yet
+    fn list_items_page(
+        &self,
+        _after: Option<&PageCursor>,
+        _limit: usize,
+    ) -> Result<Vec<Item>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Paginated listings not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_events_page(
+        &self,
+        _dfid: Option<&str>,
+        _after: Option<&PageCursor>,
+        _limit: usize,
+    ) -> Result<Vec<Event>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Paginated listings not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Legacy connector operations - not implemented for file storage yet
+    fn store_connector_config(&self, _config: &ConnectorConfig) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Legacy connectors not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_connector_config(
+        &self,
+        _connector_id: &Uuid,
+    ) -> Result<Option<ConnectorConfig>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Legacy connectors not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_connector_configs(&self) -> Result<Vec<ConnectorConfig>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Legacy connectors not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_connector_config(&self, _connector_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Legacy connectors not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_mapping_template(&self, _template: &MappingTemplate) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Legacy connectors not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_mapping_template(
+        &self,
+        _template_id: &Uuid,
+    ) -> Result<Option<MappingTemplate>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Legacy connectors not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_mapping_templates(
+        &self,
+        _owner_id: &str,
+    ) -> Result<Vec<MappingTemplate>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Legacy connectors not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_connector_state(&self, _state: &ConnectorState) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Legacy connectors not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_connector_state(
+        &self,
+        _connector_id: &Uuid,
+    ) -> Result<Option<ConnectorState>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Legacy connectors not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_connector_error(&self, _error: &ConnectorRunError) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Legacy connectors not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_connector_errors(
+        &self,
+        _connector_id: &Uuid,
+        _limit: usize,
+    ) -> Result<Vec<ConnectorRunError>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Legacy connectors not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_mapping_template(&self, _template_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Legacy connectors not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Circuit change feed operations - not implemented for file storage yet
+    fn append_change_record(&self, _record: &ChangeRecord) -> Result<ChangeRecord, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Circuit change feeds not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_change_records(
+        &self,
+        _circuit_id: &Uuid,
+        _after_sequence: u64,
+        _limit: usize,
+    ) -> Result<Vec<ChangeRecord>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Circuit change feeds not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_latest_change_sequence(&self, _circuit_id: &Uuid) -> Result<u64, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Circuit change feeds not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_change_feed_subscription(
+        &self,
+        _subscription: &ChangeFeedSubscription,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Circuit change feeds not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_change_feed_subscription(
+        &self,
+        _subscription_id: &Uuid,
+    ) -> Result<Option<ChangeFeedSubscription>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Circuit change feeds not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_change_feed_subscriptions(
+        &self,
+        _circuit_id: Option<&Uuid>,
+    ) -> Result<Vec<ChangeFeedSubscription>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Circuit change feeds not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_change_feed_subscription(&self, _subscription_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Circuit change feeds not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Item search - not implemented for file storage yet
+    fn search_items(
+        &self,
+        _query: &ItemSearchQuery,
+        _after: Option<&PageCursor>,
+        _limit: usize,
+    ) -> Result<Vec<Item>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Item search not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Announcements - not implemented for file storage yet
+    fn store_announcement(&self, _announcement: &Announcement) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Announcements not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_announcement(
+        &self,
+        _announcement_id: &Uuid,
+    ) -> Result<Option<Announcement>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Announcements not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_announcements(&self) -> Result<Vec<Announcement>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Announcements not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Workspace engagement snapshots - not implemented for file storage yet
+    fn store_workspace_engagement(
+        &self,
+        _engagement: &WorkspaceEngagement,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Workspace engagement not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_workspace_engagement(
+        &self,
+        _workspace_id: &str,
+    ) -> Result<Option<WorkspaceEngagement>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Workspace engagement not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Data export jobs - not implemented for file storage yet
+    fn store_data_export_job(&self, _job: &DataExportJob) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Data exports not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_data_export_job(&self, _job_id: &Uuid) -> Result<Option<DataExportJob>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Data exports not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_data_export_jobs(
+        &self,
+        _requested_by: &str,
+    ) -> Result<Vec<DataExportJob>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Data exports not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // SLA windows - not implemented for file storage yet
+    fn store_sla_window(&self, _window: &SlaWindow) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "SLA windows not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_sla_window(
+        &self,
+        _component: SlaComponent,
+        _hour_start: DateTime<Utc>,
+    ) -> Result<Option<SlaWindow>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "SLA windows not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_sla_windows(
+        &self,
+        _from: DateTime<Utc>,
+        _to: DateTime<Utc>,
+    ) -> Result<Vec<SlaWindow>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "SLA windows not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Event schemas - not implemented for file storage yet
+    fn store_event_schema(&self, _schema: &EventSchema) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Event schemas not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_event_schemas(&self) -> Result<Vec<EventSchema>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Event schemas not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Maintenance mode - not implemented for file storage yet
+    fn store_maintenance_mode(&self, _mode: &MaintenanceMode) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Maintenance mode not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_maintenance_mode(&self) -> Result<Option<MaintenanceMode>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Maintenance mode not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Managed keys - not implemented for file storage yet
+    fn store_managed_key(&self, _key: &ManagedKey) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Managed keys not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_managed_key(&self, _key_id: &Uuid) -> Result<Option<ManagedKey>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Managed keys not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_managed_keys(&self) -> Result<Vec<ManagedKey>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Managed keys not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Custom event types - not implemented for file storage yet
+    fn store_custom_event_type(&self, _event_type: &CustomEventType) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Custom event types not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_custom_event_types(&self) -> Result<Vec<CustomEventType>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Custom event types not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_custom_event_type(
+        &self,
+        _circuit_id: &Uuid,
+        _name: &str,
+    ) -> Result<bool, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Custom event types not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Document notarizations - not implemented for file storage yet
+    fn store_notarization(&self, _notarization: &DocumentNotarization) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Document notarizations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_notarization(
+        &self,
+        _notarization_id: &Uuid,
+    ) -> Result<Option<DocumentNotarization>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Document notarizations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_notarizations(&self) -> Result<Vec<DocumentNotarization>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Document notarizations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_notarization_batch(&self, _batch: &NotarizationBatch) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Document notarizations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_notarization_batch(
+        &self,
+        _batch_id: &Uuid,
+    ) -> Result<Option<NotarizationBatch>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Document notarizations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Enrichment policies - not implemented for file storage yet
+    fn store_enrichment_policy(&self, _policy: &EnrichmentPolicy) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Enrichment policies not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_enrichment_policy(
+        &self,
+        _workspace_id: &str,
+    ) -> Result<Option<EnrichmentPolicy>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Enrichment policies not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Event signing keys - not implemented for file storage yet
+    fn store_event_signing_key(&self, _key: &EventSigningKey) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Event signing keys not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_event_signing_key(
+        &self,
+        _key_id: &Uuid,
+    ) -> Result<Option<EventSigningKey>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Event signing keys not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_event_signing_keys(&self) -> Result<Vec<EventSigningKey>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Event signing keys not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Anchoring SLAs, fee samples and anchoring spend - not implemented for file storage yet
+    fn store_anchoring_sla(&self, _sla: &AnchoringSla) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Anchoring costs not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_anchoring_sla(&self, _circuit_id: &Uuid) -> Result<Option<AnchoringSla>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Anchoring costs not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_fee_sample(&self, _sample: &FeeSample) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Anchoring costs not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_latest_fee_sample(
+        &self,
+        _network: &AnchoringNetwork,
+    ) -> Result<Option<FeeSample>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Anchoring costs not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_anchoring_cost_record(
+        &self,
+        _record: &AnchoringCostRecord,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Anchoring costs not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_anchoring_cost_records(
+        &self,
+        _since: DateTime<Utc>,
+    ) -> Result<Vec<AnchoringCostRecord>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Anchoring costs not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Event causality - not implemented for file storage yet
+    fn get_events_caused_by(&self, _event_id: &Uuid) -> Result<Vec<Event>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Event causality not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_events_by_correlation_id(
+        &self,
+        _correlation_id: &Uuid,
+    ) -> Result<Vec<Event>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Event causality not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Item lifecycles - not implemented for file storage yet
+    fn store_lifecycle_definition(
+        &self,
+        _definition: &LifecycleDefinition,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Item lifecycles not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_lifecycle_definition(
+        &self,
+        _workspace_id: &str,
+    ) -> Result<Option<LifecycleDefinition>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Item lifecycles not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_item_lifecycle(&self, _lifecycle: &ItemLifecycle) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Item lifecycles not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_item_lifecycle(&self, _dfid: &str) -> Result<Option<ItemLifecycle>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Item lifecycles not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_item_lifecycles(
+        &self,
+        _workspace_id: &str,
+    ) -> Result<Vec<ItemLifecycle>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Item lifecycles not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Circuit access reviews - not implemented for file storage yet
+    fn store_access_report(&self, _report: &CircuitAccessReport) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Access reviews not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_access_report(
+        &self,
+        _report_id: &Uuid,
+    ) -> Result<Option<CircuitAccessReport>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Access reviews not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_access_reports(
+        &self,
+        _circuit_id: &Uuid,
+    ) -> Result<Vec<CircuitAccessReport>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Access reviews not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_access_report(&self, _report_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Access reviews not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_access_review_schedule(
+        &self,
+        _schedule: &AccessReviewSchedule,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Access reviews not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_access_review_schedule(
+        &self,
+        _circuit_id: &Uuid,
+    ) -> Result<Option<AccessReviewSchedule>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Access reviews not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_access_review_schedule(&self, _circuit_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Access reviews not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_access_review_schedules(&self) -> Result<Vec<AccessReviewSchedule>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Access reviews not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Adapter write intents - not implemented for file storage yet
+    fn store_adapter_intent(&self, _intent: &AdapterIntent) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Adapter intents not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_adapter_intent(&self, _intent_id: &Uuid) -> Result<Option<AdapterIntent>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Adapter intents not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_incomplete_adapter_intents(&self) -> Result<Vec<AdapterIntent>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Adapter intents not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Circuit-scoped partner tokens - not implemented for file storage yet
+    fn store_partner_token(&self, _token: &PartnerToken) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Partner tokens not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_partner_token(&self, _token_id: &Uuid) -> Result<Option<PartnerToken>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Partner tokens not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_partner_token_by_hash(
+        &self,
+        _token_hash: &str,
+    ) -> Result<Option<PartnerToken>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Partner tokens not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_partner_tokens(&self, _circuit_id: &Uuid) -> Result<Vec<PartnerToken>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Partner tokens not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn record_partner_token_usage(&self, _usage: &PartnerTokenUsage) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Partner tokens not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_partner_token_usage(
+        &self,
+        _token_id: &Uuid,
+        _limit: usize,
+    ) -> Result<Vec<PartnerTokenUsage>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Partner tokens not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Circuit comment threads - not implemented for file storage yet
+    fn store_circuit_comment(&self, _comment: &CircuitComment) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Circuit comments not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_circuit_comment(
+        &self,
+        _comment_id: &Uuid,
+    ) -> Result<Option<CircuitComment>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Circuit comments not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_circuit_comments(
+        &self,
+        _circuit_id: &Uuid,
+        _target: &CommentTarget,
+    ) -> Result<Vec<CircuitComment>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Circuit comments not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_circuit_comment(&self, _comment_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Circuit comments not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Circuit data quality reports - not implemented for file storage yet
+    fn store_data_quality_report(&self, _report: &DataQualityReport) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Data quality reports not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_data_quality_reports(
+        &self,
+        _circuit_id: &Uuid,
+    ) -> Result<Vec<DataQualityReport>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Data quality reports not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_data_quality_report(&self, _report_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Data quality reports not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Dashboard metric definitions - not implemented for file storage yet
+    fn store_metric_definition(&self, _definition: &MetricDefinition) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Dashboard metrics not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_metric_definition(
+        &self,
+        _metric_id: &Uuid,
+    ) -> Result<Option<MetricDefinition>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Dashboard metrics not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_metric_definitions(
+        &self,
+        _workspace_id: &str,
+    ) -> Result<Vec<MetricDefinition>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Dashboard metrics not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_metric_definition(&self, _metric_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Dashboard metrics not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // ZK setup artifacts - not implemented for file storage yet
+    fn store_zk_setup_artifact(
+        &self,
+        _artifact: &crate::zk_proof_engine::ZkSetupArtifact,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "ZK setup artifacts not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_zk_setup_artifact(
+        &self,
+        _artifact_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::ZkSetupArtifact>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "ZK setup artifacts not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_zk_setup_artifacts(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkSetupArtifact>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "ZK setup artifacts not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Data lake garbage collection - not implemented for file storage yet
+    fn delete_data_lake_entries(&self, _entry_ids: &[Uuid]) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Data lake garbage collection not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_receipts(&self, _receipt_ids: &[Uuid]) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Data lake garbage collection not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_data_lake_gc_report(
+        &self,
+        _report: &crate::types::DataLakeGcReport,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Data lake garbage collection not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_data_lake_gc_reports(
+        &self,
+    ) -> Result<Vec<crate::types::DataLakeGcReport>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Data lake garbage collection not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Device backups - not implemented for file storage yet
+    fn store_device_backup(
+        &self,
+        _backup: &crate::types::DeviceBackup,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Device backups not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_device_backup(
+        &self,
+        _backup_id: &Uuid,
+    ) -> Result<Option<crate::types::DeviceBackup>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Device backups not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_device_backups(
+        &self,
+        _user_id: &str,
+    ) -> Result<Vec<crate::types::DeviceBackup>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Device backups not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_device_backup(&self, _backup_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Device backups not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Federation - not implemented for file storage yet
+    fn store_federation_peer(
+        &self,
+        _peer: &crate::types::FederationPeer,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Federation not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_federation_peer(
+        &self,
+        _peer_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationPeer>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Federation not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_federation_peers(&self) -> Result<Vec<crate::types::FederationPeer>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Federation not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn delete_federation_peer(&self, _peer_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Federation not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_federation_provenance(
+        &self,
+        _provenance: &crate::types::FederationProvenance,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Federation not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_federation_provenance(
+        &self,
+        _record_key: &str,
+    ) -> Result<Option<crate::types::FederationProvenance>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Federation not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_federation_conflict(
+        &self,
+        _conflict: &crate::types::FederationConflict,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Federation not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_federation_conflict(
+        &self,
+        _conflict_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationConflict>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Federation not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_federation_conflicts(
+        &self,
+    ) -> Result<Vec<crate::types::FederationConflict>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Federation not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // ZK proof revocations - not implemented for file storage yet
+    fn store_zk_proof_revocation(
+        &self,
+        _revocation: &crate::zk_proof_engine::ProofRevocation,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "ZK proof revocations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_zk_proof_revocation(
+        &self,
+        _proof_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofRevocation>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "ZK proof revocations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_zk_proof_revocations(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ProofRevocation>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "ZK proof revocations not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Selective disclosures - not implemented for file storage yet
+    fn store_item_attribute_commitments(
+        &self,
+        _commitments: &crate::types::ItemAttributeCommitments,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Selective disclosures not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_item_attribute_commitments(
+        &self,
+        _dfid: &str,
+    ) -> Result<Option<crate::types::ItemAttributeCommitments>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Selective disclosures not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_selective_disclosure(
+        &self,
+        _disclosure: &crate::types::SelectiveDisclosure,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Selective disclosures not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_selective_disclosure(
+        &self,
+        _disclosure_id: &Uuid,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Selective disclosures not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_selective_disclosure_by_token(
+        &self,
+        _token_hash: &str,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Selective disclosures not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_selective_disclosures(
+        &self,
+        _created_by: &str,
+    ) -> Result<Vec<crate::types::SelectiveDisclosure>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Selective disclosures not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Custom ZK circuit definitions - not implemented for file storage yet
+    fn store_custom_circuit_definition(
+        &self,
+        _definition: &crate::zk_proof_engine::CustomCircuitDefinition,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Custom ZK circuits not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_custom_circuit_definition(
+        &self,
+        _circuit_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::CustomCircuitDefinition>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Custom ZK circuits not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_custom_circuit_definitions(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::CustomCircuitDefinition>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Custom ZK circuits not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // ZK proof generation jobs - not implemented for file storage yet
+    fn store_zk_proof_job(
+        &self,
+        _job: &crate::zk_proof_engine::ProofJob,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "ZK proof jobs not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_zk_proof_job(
+        &self,
+        _job_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofJob>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "ZK proof jobs not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_zk_proof_jobs(&self) -> Result<Vec<crate::zk_proof_engine::ProofJob>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "ZK proof jobs not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Item search projected to summaries - not implemented for file storage yet
+    fn search_item_summaries(
+        &self,
+        _query: &ItemSearchQuery,
+        _after: Option<&PageCursor>,
+        _limit: usize,
+    ) -> Result<Vec<ItemSummary>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Item summaries not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Per-workspace API usage and anomalies - not implemented for file storage yet
+    fn store_workspace_usage_window(
+        &self,
+        _window: &crate::types::WorkspaceUsageWindow,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Usage anomaly detection not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_workspace_usage_window(
+        &self,
+        _workspace_id: &str,
+        _hour_start: DateTime<Utc>,
+    ) -> Result<Option<crate::types::WorkspaceUsageWindow>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Usage anomaly detection not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_workspace_usage_windows(
+        &self,
+        _workspace_id: &str,
+        _from: DateTime<Utc>,
+        _to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::WorkspaceUsageWindow>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Usage anomaly detection not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_usage_anomaly_baseline(
+        &self,
+        _baseline: &crate::types::UsageAnomalyBaseline,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Usage anomaly detection not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_usage_anomaly_baseline(
+        &self,
+        _workspace_id: &str,
+    ) -> Result<Option<crate::types::UsageAnomalyBaseline>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Usage anomaly detection not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_usage_anomaly(
+        &self,
+        _anomaly: &crate::types::UsageAnomaly,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Usage anomaly detection not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_usage_anomalies(
+        &self,
+        _workspace_id: Option<&str>,
+        _since: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::UsageAnomaly>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Usage anomaly detection not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Proof share links - not implemented for file storage yet
+    fn store_proof_share_link(
+        &self,
+        _link: &crate::types::ProofShareLink,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Proof share links not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_proof_share_link(
+        &self,
+        _share_id: &Uuid,
+    ) -> Result<Option<crate::types::ProofShareLink>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Proof share links not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn record_proof_share_view(
+        &self,
+        _share_id: &Uuid,
+        _now: DateTime<Utc>,
+    ) -> Result<Option<crate::types::ProofShareLink>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Proof share links not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_proof_share_links(
+        &self,
+        _proof_id: &Uuid,
+    ) -> Result<Vec<crate::types::ProofShareLink>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Proof share links not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Workspace dedup configs - not implemented for file storage yet
+    fn store_workspace_dedup_config(
+        &self,
+        _config: &crate::types::WorkspaceDedupConfig,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Workspace dedup configs not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_workspace_dedup_config(
+        &self,
+        _workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceDedupConfig>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Workspace dedup configs not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Sensor time series - not implemented for file storage yet
+    fn store_sensor_series(
+        &self,
+        _series: &crate::types::SensorSeries,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Sensor time series not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_sensor_series(
+        &self,
+        _dfid: &str,
+        _series: &str,
+    ) -> Result<Option<crate::types::SensorSeries>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Sensor time series not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_sensor_series(
+        &self,
+        _dfid: &str,
+    ) -> Result<Vec<crate::types::SensorSeries>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Sensor time series not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_sensor_chunk(&self, _chunk: &crate::types::SensorChunk) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Sensor time series not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_sensor_chunks(
+        &self,
+        _dfid: &str,
+        _series: &str,
+        _from: DateTime<Utc>,
+        _to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorChunk>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Sensor time series not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn store_sensor_rollup(
+        &self,
+        _rollup: &crate::types::SensorRollup,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Sensor time series not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn get_sensor_rollups(
+        &self,
+        _dfid: &str,
+        _series: &str,
+        _resolution: crate::types::SensorResolution,
+        _from: DateTime<Utc>,
+        _to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorRollup>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Sensor time series not yet implemented for file storage".to_string(),
+        ))
+    }
 
-        let file_path = Path::new(&self.base_path)
-            .join("receipts")
-            .join(format!("{}.json", receipt.id));
+    fn list_all_sensor_series(&self) -> Result<Vec<crate::types::SensorSeries>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Sensor time series not yet implemented for file storage".to_string(),
+        ))
+    }
 
-        fs::write(file_path, encrypted_json)?;
-        Ok(())
+    // Workspace buckets - not implemented for file storage yet
+    fn store_workspace_bucket_config(
+        &self,
+        _config: &crate::types::WorkspaceBucketConfig,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Workspace buckets not yet implemented for file storage".to_string(),
+        ))
     }
 
-    fn get_receipt(&self, id: &Uuid) -> Result<Option<Receipt>, StorageError> {
-        let file_path = Path::new(&self.base_path)
-            .join("receipts")
-            .join(format!("{id}.json"));
+    fn get_workspace_bucket_config(
+        &self,
+        _workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceBucketConfig>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Workspace buckets not yet implemented for file storage".to_string(),
+        ))
+    }
 
-        if !file_path.exists() {
-            return Ok(None);
-        }
+    fn delete_workspace_bucket_config(&self, _workspace_id: &str) -> Result<bool, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Workspace buckets not yet implemented for file storage".to_string(),
+        ))
+    }
 
-        let encrypted_json = fs::read(file_path)?;
-        let encrypted: EncryptedData = serde_json::from_slice(&encrypted_json)?;
-        let decrypted = self.decrypt_data(&encrypted)?;
-        let receipt: Receipt = serde_json::from_slice(&decrypted)?;
+    // DFID sequence leases - not implemented for file storage yet
+    fn lease_dfid_sequence_range(&self, _day: &str, _count: u64) -> Result<u64, StorageError> {
+        Err(StorageError::NotImplemented(
+            "DFID sequence leases not yet implemented for file storage".to_string(),
+        ))
+    }
 
-        Ok(Some(receipt))
+    // Re-verification jobs and merge proposals - not implemented for file storage yet
+    fn store_reverification_job(&self, _job: &ReverificationJob) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Re-verification jobs not yet implemented for file storage".to_string(),
+        ))
     }
 
-    fn find_receipts_by_identifier(
+    fn get_reverification_job(
         &self,
-        _identifier: &Identifier,
-    ) -> Result<Vec<Receipt>, StorageError> {
-        let receipts = self.list_receipts()?;
-        Ok(receipts
-            .into_iter()
-            .filter(|receipt| receipt.identifiers.contains(_identifier))
-            .collect())
+        _job_id: &Uuid,
+    ) -> Result<Option<ReverificationJob>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Re-verification jobs not yet implemented for file storage".to_string(),
+        ))
     }
 
-    fn list_receipts(&self) -> Result<Vec<Receipt>, StorageError> {
-        let receipts_dir = Path::new(&self.base_path).join("receipts");
-        if !receipts_dir.exists() {
-            return Ok(Vec::new());
-        }
+    fn list_reverification_jobs(&self) -> Result<Vec<ReverificationJob>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Re-verification jobs not yet implemented for file storage".to_string(),
+        ))
+    }
 
-        let mut receipts = Vec::new();
-        for entry in fs::read_dir(receipts_dir)? {
-            let entry = entry?;
-            if entry.path().extension().and_then(|s| s.to_str()) == Some("json") {
-                let encrypted_json = fs::read(entry.path())?;
-                let encrypted: EncryptedData = serde_json::from_slice(&encrypted_json)?;
-                let decrypted = self.decrypt_data(&encrypted)?;
-                let receipt: Receipt = serde_json::from_slice(&decrypted)?;
-                receipts.push(receipt);
-            }
-        }
+    fn store_merge_proposal(&self, _proposal: &MergeProposal) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Re-verification jobs not yet implemented for file storage".to_string(),
+        ))
+    }
 
-        Ok(receipts)
+    fn get_merge_proposal(
+        &self,
+        _proposal_id: &Uuid,
+    ) -> Result<Option<MergeProposal>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Re-verification jobs not yet implemented for file storage".to_string(),
+        ))
     }
 
-    fn store_log(&self, log: &LogEntry) -> Result<(), StorageError> {
-        self.ensure_directory("logs")?;
+    fn list_merge_proposals(&self) -> Result<Vec<MergeProposal>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Re-verification jobs not yet implemented for file storage".to_string(),
+        ))
+    }
 
-        let serialized = serde_json::to_vec(log)?;
-        let encrypted = self.encrypt_data(&serialized)?;
-        let encrypted_json = serde_json::to_vec(&encrypted)?;
+    // Runbook hooks - not implemented for file storage yet
+    fn store_runbook_hook(&self, _hook: &RunbookHook) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Runbook hooks not yet implemented for file storage".to_string(),
+        ))
+    }
 
-        let file_path = Path::new(&self.base_path)
-            .join("logs")
-            .join(format!("{}.json", log.id));
+    fn get_runbook_hook(&self, _hook_id: &Uuid) -> Result<Option<RunbookHook>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Runbook hooks not yet implemented for file storage".to_string(),
+        ))
+    }
 
-        fs::write(file_path, encrypted_json)?;
-        Ok(())
+    fn list_runbook_hooks(&self) -> Result<Vec<RunbookHook>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Runbook hooks not yet implemented for file storage".to_string(),
+        ))
     }
 
-    fn get_logs(&self) -> Result<Vec<LogEntry>, StorageError> {
-        let logs_dir = Path::new(&self.base_path).join("logs");
-        if !logs_dir.exists() {
-            return Ok(Vec::new());
-        }
+    fn delete_runbook_hook(&self, _hook_id: &Uuid) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Runbook hooks not yet implemented for file storage".to_string(),
+        ))
+    }
 
-        let mut logs = Vec::new();
-        for entry in fs::read_dir(logs_dir)? {
-            let entry = entry?;
-            if entry.path().extension().and_then(|s| s.to_str()) == Some("json") {
-                let encrypted_json = fs::read(entry.path())?;
-                let encrypted: EncryptedData = serde_json::from_slice(&encrypted_json)?;
-                let decrypted = self.decrypt_data(&encrypted)?;
-                let log: LogEntry = serde_json::from_slice(&decrypted)?;
-                logs.push(log);
-            }
-        }
+    // Workspace verification rules - not implemented for file storage yet
+    fn store_workspace_verification_rules(
+        &self,
+        _rules: &WorkspaceVerificationRules,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Verification rules not yet implemented for file storage".to_string(),
+        ))
+    }
 
-        logs.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
-        Ok(logs)
+    fn get_workspace_verification_rules(
+        &self,
+        _workspace_id: &str,
+    ) -> Result<Option<WorkspaceVerificationRules>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Verification rules not yet implemented for file storage".to_string(),
+        ))
     }
 
-    // For simplicity, EncryptedFileStorage will delegate to an internal InMemoryStorage for new data types
-    // In a real implementation, these would be properly encrypted and stored to files
+    // Dedup sharing agreements - not implemented for file storage yet
+    fn store_dedup_sharing_agreement(
+        &self,
+        _agreement: &DedupSharingAgreement,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Dedup sharing agreements not yet implemented for file storage".to_string(),
+        ))
+    }
 
-    fn store_data_lake_entry(&self, _entry: &DataLakeEntry) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Data lake operations not yet implemented for EncryptedFileStorage".to_string(),
+    fn get_dedup_sharing_agreement(
+        &self,
+        _agreement_id: &Uuid,
+    ) -> Result<Option<DedupSharingAgreement>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Dedup sharing agreements not yet implemented for file storage".to_string(),
         ))
     }
 
-    fn get_data_lake_entry(&self, _entry_id: &Uuid) -> Result<Option<DataLakeEntry>, StorageError> {
-        Ok(None)
+    fn list_dedup_sharing_agreements(
+        &self,
+        _circuit_id: &Uuid,
+    ) -> Result<Vec<DedupSharingAgreement>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Dedup sharing agreements not yet implemented for file storage".to_string(),
+        ))
     }
 
-    fn update_data_lake_entry(&self, _entry: &DataLakeEntry) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Data lake operations not yet implemented for EncryptedFileStorage".to_string(),
+    // Paginated CID timeline - not implemented for file storage yet
+    fn get_item_timeline_page(
+        &self,
+        _dfid: &str,
+        _query: &TimelineQuery,
+        _after_sequence: Option<i32>,
+        _limit: usize,
+    ) -> Result<Vec<TimelineEntry>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Paginated timeline not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    // Circuit membership reconciliation - not implemented for file storage yet
+    fn store_membership_reconciliation_report(
+        &self,
+        _report: &MembershipReconciliationReport,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::NotImplemented(
+            "Membership reconciliation reports not yet implemented for file storage".to_string(),
+        ))
+    }
+
+    fn list_membership_reconciliation_reports(
+        &self,
+    ) -> Result<Vec<MembershipReconciliationReport>, StorageError> {
+        Err(StorageError::NotImplemented(
+            "Membership reconciliation reports not yet implemented for file storage".to_string(),
         ))
     }
+}
+
+// StorageBackend adapter for Arc<Mutex<PostgresStorageWithCache>>
+// StorageBackend adapter for Arc<Mutex<PostgresStorageWithCache>>
+impl StorageBackend for Arc<Mutex<PostgresStorageWithCache>> {
+    fn store_receipt(&self, receipt: &Receipt) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_receipt(receipt)
+    }
+
+    fn get_receipt(&self, id: &Uuid) -> Result<Option<Receipt>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_receipt(id)
+    }
+
+    fn find_receipts_by_identifier(
+        &self,
+        identifier: &Identifier,
+    ) -> Result<Vec<Receipt>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.find_receipts_by_identifier(identifier)
+    }
+
+    fn list_receipts(&self) -> Result<Vec<Receipt>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_receipts()
+    }
+
+    fn store_receipts(&self, receipts: &[Receipt]) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_receipts(receipts)
+    }
+
+    fn store_log(&self, log: &LogEntry) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_log(log)
+    }
+
+    fn get_logs(&self) -> Result<Vec<LogEntry>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_logs()
+    }
+
+    fn store_data_lake_entry(&self, entry: &DataLakeEntry) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_data_lake_entry(entry)
+    }
+
+    fn store_data_lake_entries(&self, entries: &[DataLakeEntry]) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_data_lake_entries(entries)
+    }
+
+    fn get_data_lake_entry(&self, entry_id: &Uuid) -> Result<Option<DataLakeEntry>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_data_lake_entry(entry_id)
+    }
+
+    fn update_data_lake_entry(&self, entry: &DataLakeEntry) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_data_lake_entry(entry)
+    }
 
     fn get_data_lake_entries_by_status(
         &self,
-        _status: ProcessingStatus,
+        status: ProcessingStatus,
     ) -> Result<Vec<DataLakeEntry>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_data_lake_entries_by_status(status)
     }
 
     fn list_data_lake_entries(&self) -> Result<Vec<DataLakeEntry>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.list_data_lake_entries()
     }
 
-    fn store_item(&self, _item: &Item) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Item operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn store_item(&self, item: &Item) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_item(item)
     }
 
-    fn get_item_by_dfid(&self, _dfid: &str) -> Result<Option<Item>, StorageError> {
-        Ok(None)
+    fn get_item_by_dfid(&self, dfid: &str) -> Result<Option<Item>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_item_by_dfid(dfid)
     }
 
-    fn update_item(&self, _item: &Item) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Item operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn update_item(&self, item: &Item) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_item(item)
     }
 
     fn list_items(&self) -> Result<Vec<Item>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.list_items()
     }
 
-    fn find_items_by_identifier(
-        &self,
-        _identifier: &Identifier,
-    ) -> Result<Vec<Item>, StorageError> {
-        Ok(Vec::new())
+    fn find_items_by_identifier(&self, identifier: &Identifier) -> Result<Vec<Item>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.find_items_by_identifier(identifier)
     }
 
-    fn find_items_by_status(&self, _status: ItemStatus) -> Result<Vec<Item>, StorageError> {
-        Ok(Vec::new())
+    fn find_items_by_status(&self, status: ItemStatus) -> Result<Vec<Item>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.find_items_by_status(status)
     }
 
-    fn delete_item(&self, _dfid: &str) -> Result<(), StorageError> {
-        Ok(())
+    fn delete_item(&self, dfid: &str) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_item(dfid)
     }
 
-    fn store_identifier_mapping(&self, _mapping: &IdentifierMapping) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Identifier mapping operations not yet implemented for EncryptedFileStorage"
-                .to_string(),
-        ))
+    fn store_identifier_mapping(&self, mapping: &IdentifierMapping) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_identifier_mapping(mapping)
     }
 
     fn get_identifier_mappings(
         &self,
-        _identifier: &Identifier,
+        identifier: &Identifier,
     ) -> Result<Vec<IdentifierMapping>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_identifier_mappings(identifier)
     }
 
-    fn update_identifier_mapping(&self, _mapping: &IdentifierMapping) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Identifier mapping operations not yet implemented for EncryptedFileStorage"
-                .to_string(),
-        ))
+    fn update_identifier_mapping(&self, mapping: &IdentifierMapping) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_identifier_mapping(mapping)
     }
 
     fn list_identifier_mappings(&self) -> Result<Vec<IdentifierMapping>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.list_identifier_mappings()
     }
 
-    fn store_conflict_resolution(
-        &self,
-        _conflict: &ConflictResolution,
-    ) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Conflict resolution operations not yet implemented for EncryptedFileStorage"
-                .to_string(),
-        ))
+    fn store_conflict_resolution(&self, conflict: &ConflictResolution) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_conflict_resolution(conflict)
     }
 
     fn get_conflict_resolution(
         &self,
-        _conflict_id: &Uuid,
+        conflict_id: &Uuid,
     ) -> Result<Option<ConflictResolution>, StorageError> {
-        Ok(None)
+        let guard = self.lock().unwrap();
+        guard.get_conflict_resolution(conflict_id)
     }
 
     fn get_pending_conflicts(&self) -> Result<Vec<ConflictResolution>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_pending_conflicts()
     }
 
-    // Event operations - placeholder implementations
-    fn store_event(&self, _event: &Event) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Event operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn store_event(&self, event: &Event) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_event(event)
     }
 
-    fn get_event(&self, _event_id: &Uuid) -> Result<Option<Event>, StorageError> {
-        Ok(None)
+    fn store_events(&self, events: &[Event]) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_events(events)
     }
 
-    fn update_event(&self, _event: &Event) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Event operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn get_event(&self, event_id: &Uuid) -> Result<Option<Event>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_event(event_id)
+    }
+
+    fn update_event(&self, event: &Event) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_event(event)
     }
 
     fn list_events(&self) -> Result<Vec<Event>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.list_events()
     }
 
-    fn get_events_by_dfid(&self, _dfid: &str) -> Result<Vec<Event>, StorageError> {
-        Ok(Vec::new())
+    fn get_events_by_dfid(&self, dfid: &str) -> Result<Vec<Event>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_events_by_dfid(dfid)
     }
 
-    fn get_events_by_type(&self, _event_type: EventType) -> Result<Vec<Event>, StorageError> {
-        Ok(Vec::new())
+    fn get_events_by_type(&self, event_type: EventType) -> Result<Vec<Event>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_events_by_type(event_type)
     }
 
     fn get_events_by_visibility(
         &self,
-        _visibility: EventVisibility,
+        visibility: EventVisibility,
     ) -> Result<Vec<Event>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_events_by_visibility(visibility)
     }
 
     fn get_events_in_time_range(
         &self,
-        _start: DateTime<Utc>,
-        _end: DateTime<Utc>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
     ) -> Result<Vec<Event>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_events_in_time_range(start, end)
     }
 
-    fn get_event_by_content_hash(
-        &self,
-        _content_hash: &str,
-    ) -> Result<Option<Event>, StorageError> {
-        // EncryptedFileStorage doesn't implement event storage, return None
-        Ok(None)
+    fn get_event_by_content_hash(&self, content_hash: &str) -> Result<Option<Event>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_event_by_content_hash(content_hash)
     }
 
-    // Circuit operations - placeholder implementations
-    fn store_circuit(&self, _circuit: &Circuit) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Circuit operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn store_circuit(&self, circuit: &Circuit) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_circuit(circuit)
     }
 
-    fn get_circuit(&self, _circuit_id: &Uuid) -> Result<Option<Circuit>, StorageError> {
-        Ok(None)
+    fn get_circuit(&self, circuit_id: &Uuid) -> Result<Option<Circuit>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_circuit(circuit_id)
     }
 
-    fn update_circuit(&self, _circuit: &Circuit) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Circuit operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn update_circuit(&self, circuit: &Circuit) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_circuit(circuit)
     }
 
     fn list_circuits(&self) -> Result<Vec<Circuit>, StorageError> {
-        Ok(Vec::new())
-    }
-
-    fn get_circuits_for_member(&self, _member_id: &str) -> Result<Vec<Circuit>, StorageError> {
-        Ok(Vec::new())
-    }
-
-    // Circuit Operation operations - placeholder implementations
-    fn store_circuit_operation(&self, _operation: &CircuitOperation) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Circuit operation operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
-    }
-
-    fn get_circuit_operation(
-        &self,
-        _operation_id: &Uuid,
-    ) -> Result<Option<CircuitOperation>, StorageError> {
-        Ok(None)
+        let guard = self.lock().unwrap();
+        guard.list_circuits()
     }
 
-    fn update_circuit_operation(&self, _operation: &CircuitOperation) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Circuit operation operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn get_circuits_for_member(&self, member_id: &str) -> Result<Vec<Circuit>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_circuits_for_member(member_id)
     }
 
-    fn get_circuit_operations(
+    fn get_archived_circuits_for_member(
         &self,
-        _circuit_id: &Uuid,
-    ) -> Result<Vec<CircuitOperation>, StorageError> {
-        Ok(Vec::new())
-    }
-
-    // Item Share operations - Not implemented for EncryptedFileStorage yet
-    fn store_item_share(&self, _share: &ItemShare) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Item share operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
-    }
-
-    fn get_item_share(&self, _share_id: &str) -> Result<Option<ItemShare>, StorageError> {
-        Ok(None)
-    }
-
-    fn get_shares_for_user(&self, _user_id: &str) -> Result<Vec<ItemShare>, StorageError> {
-        Ok(Vec::new())
-    }
-
-    fn get_shares_for_item(&self, _dfid: &str) -> Result<Vec<ItemShare>, StorageError> {
-        Ok(Vec::new())
+        member_id: &str,
+    ) -> Result<Vec<Circuit>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_archived_circuits_for_member(member_id)
     }
 
-    fn is_item_shared_with_user(&self, _dfid: &str, _user_id: &str) -> Result<bool, StorageError> {
-        Ok(false)
+    fn get_child_circuits(&self, parent_id: &Uuid) -> Result<Vec<Circuit>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_child_circuits(parent_id)
     }
 
-    fn delete_item_share(&self, _share_id: &str) -> Result<(), StorageError> {
-        Ok(())
+    fn store_circuit_operation(&self, operation: &CircuitOperation) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_circuit_operation(operation)
     }
 
-    fn store_activity(&self, _activity: &Activity) -> Result<(), StorageError> {
-        Ok(())
+    fn get_circuit_operation(
+        &self,
+        operation_id: &Uuid,
+    ) -> Result<Option<CircuitOperation>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_circuit_operation(operation_id)
     }
 
-    fn get_activities_for_user(&self, _user_id: &str) -> Result<Vec<Activity>, StorageError> {
-        Ok(vec![])
+    fn update_circuit_operation(&self, operation: &CircuitOperation) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_circuit_operation(operation)
     }
 
-    fn get_activities_for_circuit(
+    fn get_circuit_operations(
         &self,
-        _circuit_id: &Uuid,
-    ) -> Result<Vec<Activity>, StorageError> {
-        Ok(vec![])
-    }
-
-    fn get_all_activities(&self) -> Result<Vec<Activity>, StorageError> {
-        Ok(vec![])
+        circuit_id: &Uuid,
+    ) -> Result<Vec<CircuitOperation>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_circuit_operations(circuit_id)
     }
 
-    fn store_circuit_item(&self, _circuit_item: &CircuitItem) -> Result<(), StorageError> {
-        Ok(())
+    fn store_item_share(&self, share: &ItemShare) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_item_share(share)
     }
 
-    fn get_circuit_items(&self, _circuit_id: &Uuid) -> Result<Vec<CircuitItem>, StorageError> {
-        Ok(vec![])
+    fn get_item_share(&self, share_id: &str) -> Result<Option<ItemShare>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_item_share(share_id)
     }
 
-    fn remove_circuit_item(&self, _circuit_id: &Uuid, _dfid: &str) -> Result<(), StorageError> {
-        Ok(())
+    fn get_shares_for_user(&self, user_id: &str) -> Result<Vec<ItemShare>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_shares_for_user(user_id)
     }
 
-    // Pending Items operations - placeholder implementations
-    fn store_pending_item(&self, _item: &PendingItem) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Pending item operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn get_shares_for_item(&self, dfid: &str) -> Result<Vec<ItemShare>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_shares_for_item(dfid)
     }
 
-    fn get_pending_item(&self, _pending_id: &Uuid) -> Result<Option<PendingItem>, StorageError> {
-        Ok(None)
+    fn is_item_shared_with_user(&self, dfid: &str, user_id: &str) -> Result<bool, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.is_item_shared_with_user(dfid, user_id)
     }
 
-    fn list_pending_items(&self) -> Result<Vec<PendingItem>, StorageError> {
-        Ok(Vec::new())
+    fn delete_item_share(&self, share_id: &str) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_item_share(share_id)
     }
-
-    fn get_pending_items_by_reason(
-        &self,
-        _reason_type: &str,
-    ) -> Result<Vec<PendingItem>, StorageError> {
-        Ok(Vec::new())
+
+    fn store_activity(&self, activity: &Activity) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_activity(activity)
     }
 
-    fn get_pending_items_by_user(&self, _user_id: &str) -> Result<Vec<PendingItem>, StorageError> {
-        Ok(Vec::new())
+    fn get_activities_for_user(&self, user_id: &str) -> Result<Vec<Activity>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_activities_for_user(user_id)
     }
 
-    fn get_pending_items_by_workspace(
-        &self,
-        _workspace_id: &str,
-    ) -> Result<Vec<PendingItem>, StorageError> {
-        Ok(Vec::new())
+    fn get_activities_for_circuit(&self, circuit_id: &Uuid) -> Result<Vec<Activity>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_activities_for_circuit(circuit_id)
     }
 
-    fn get_pending_items_by_priority(
-        &self,
-        _priority: PendingPriority,
-    ) -> Result<Vec<PendingItem>, StorageError> {
-        Ok(Vec::new())
+    fn get_all_activities(&self) -> Result<Vec<Activity>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_all_activities()
     }
 
-    fn update_pending_item(&self, _item: &PendingItem) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Pending item operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn store_circuit_item(&self, circuit_item: &CircuitItem) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_circuit_item(circuit_item)
     }
 
-    fn delete_pending_item(&self, _pending_id: &Uuid) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Pending item operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn get_circuit_items(&self, circuit_id: &Uuid) -> Result<Vec<CircuitItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_circuit_items(circuit_id)
     }
 
-    fn get_pending_items_requiring_manual_review(&self) -> Result<Vec<PendingItem>, StorageError> {
-        Ok(Vec::new())
+    fn remove_circuit_item(&self, circuit_id: &Uuid, dfid: &str) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.remove_circuit_item(circuit_id, dfid)
     }
 
-    // Audit methods - not yet implemented for EncryptedFileStorage
-    fn store_audit_event(&self, _event: &AuditEvent) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Audit operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn store_audit_event(&self, event: &AuditEvent) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_audit_event(event)
     }
 
-    fn get_audit_event(&self, _event_id: &Uuid) -> Result<Option<AuditEvent>, StorageError> {
-        Ok(None)
+    fn get_audit_event(&self, event_id: &Uuid) -> Result<Option<AuditEvent>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_audit_event(event_id)
     }
 
-    fn query_audit_events(&self, _query: &AuditQuery) -> Result<Vec<AuditEvent>, StorageError> {
-        Ok(Vec::new())
+    fn query_audit_events(&self, query: &AuditQuery) -> Result<Vec<AuditEvent>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.query_audit_events(query)
     }
 
     fn list_audit_events(&self) -> Result<Vec<AuditEvent>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.list_audit_events()
     }
 
-    fn get_audit_events_by_user(&self, _user_id: &str) -> Result<Vec<AuditEvent>, StorageError> {
-        Ok(Vec::new())
+    fn get_audit_events_by_user(&self, user_id: &str) -> Result<Vec<AuditEvent>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_audit_events_by_user(user_id)
     }
 
     fn get_audit_events_by_type(
         &self,
-        _event_type: AuditEventType,
+        event_type: AuditEventType,
     ) -> Result<Vec<AuditEvent>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_audit_events_by_type(event_type)
     }
 
     fn get_audit_events_by_severity(
         &self,
-        _severity: AuditSeverity,
+        severity: AuditSeverity,
     ) -> Result<Vec<AuditEvent>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_audit_events_by_severity(severity)
     }
 
     fn get_audit_events_in_time_range(
         &self,
-        _start: DateTime<Utc>,
-        _end: DateTime<Utc>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
     ) -> Result<Vec<AuditEvent>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_audit_events_in_time_range(start, end)
     }
 
-    fn sync_audit_events(&self, _events: Vec<AuditEvent>) -> Result<(), StorageError> {
-        Ok(())
+    fn sync_audit_events(&self, events: Vec<AuditEvent>) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.sync_audit_events(events)
     }
 
-    fn store_security_incident(&self, _incident: &SecurityIncident) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Security incident operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn store_security_incident(&self, incident: &SecurityIncident) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_security_incident(incident)
     }
 
     fn get_security_incident(
         &self,
-        _incident_id: &Uuid,
+        incident_id: &Uuid,
     ) -> Result<Option<SecurityIncident>, StorageError> {
-        Ok(None)
+        let guard = self.lock().unwrap();
+        guard.get_security_incident(incident_id)
     }
 
-    fn update_security_incident(&self, _incident: &SecurityIncident) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Security incident operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn update_security_incident(&self, incident: &SecurityIncident) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_security_incident(incident)
     }
 
     fn list_security_incidents(&self) -> Result<Vec<SecurityIncident>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.list_security_incidents()
     }
 
     fn get_incidents_by_severity(
         &self,
-        _severity: AuditSeverity,
+        severity: AuditSeverity,
     ) -> Result<Vec<SecurityIncident>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_incidents_by_severity(severity)
     }
 
     fn get_open_incidents(&self) -> Result<Vec<SecurityIncident>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_open_incidents()
     }
 
     fn get_incidents_by_assignee(
         &self,
-        _assignee: &str,
+        assignee: &str,
     ) -> Result<Vec<SecurityIncident>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_incidents_by_assignee(assignee)
     }
 
-    fn store_compliance_report(&self, _report: &ComplianceReport) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Compliance report operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn store_compliance_report(&self, report: &ComplianceReport) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_compliance_report(report)
     }
 
     fn get_compliance_report(
         &self,
-        _report_id: &Uuid,
+        report_id: &Uuid,
     ) -> Result<Option<ComplianceReport>, StorageError> {
-        Ok(None)
+        let guard = self.lock().unwrap();
+        guard.get_compliance_report(report_id)
     }
 
-    fn update_compliance_report(&self, _report: &ComplianceReport) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "Compliance report operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn update_compliance_report(&self, report: &ComplianceReport) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_compliance_report(report)
     }
 
     fn list_compliance_reports(&self) -> Result<Vec<ComplianceReport>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.list_compliance_reports()
     }
 
     fn get_reports_by_type(
         &self,
-        _report_type: &str,
+        report_type: &str,
     ) -> Result<Vec<ComplianceReport>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_reports_by_type(report_type)
     }
 
     fn get_pending_reports(&self) -> Result<Vec<ComplianceReport>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_pending_reports()
     }
 
     fn get_audit_dashboard_metrics(&self) -> Result<AuditDashboardMetrics, StorageError> {
-        Ok(AuditDashboardMetrics {
-            total_events: 0,
-            events_last_24h: 0,
-            events_last_7d: 0,
-            security_incidents: SecurityIncidentSummary {
-                open: 0,
-                critical: 0,
-                resolved: 0,
-            },
-            compliance_status: ComplianceStatus {
-                gdpr_events: 0,
-                ccpa_events: 0,
-                hipaa_events: 0,
-                sox_events: 0,
-            },
-            top_users: Vec::new(),
-            anomalies: Vec::new(),
-        })
+        let guard = self.lock().unwrap();
+        guard.get_audit_dashboard_metrics()
     }
 
     fn get_event_count_by_time_range(
         &self,
-        _start: DateTime<Utc>,
-        _end: DateTime<Utc>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
     ) -> Result<u64, StorageError> {
-        Ok(0)
+        let guard = self.lock().unwrap();
+        guard.get_event_count_by_time_range(start, end)
     }
 
-    // ZK Proof operations - placeholder implementations
-    fn store_zk_proof(&self, _proof: &crate::zk_proof_engine::ZkProof) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "ZK proof operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn store_pending_item(&self, item: &PendingItem) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_pending_item(item)
+    }
+
+    fn get_pending_item(&self, pending_id: &Uuid) -> Result<Option<PendingItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_pending_item(pending_id)
+    }
+
+    fn list_pending_items(&self) -> Result<Vec<PendingItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_pending_items()
+    }
+
+    fn get_pending_items_by_reason(
+        &self,
+        reason_type: &str,
+    ) -> Result<Vec<PendingItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_pending_items_by_reason(reason_type)
+    }
+
+    fn get_pending_items_by_user(&self, user_id: &str) -> Result<Vec<PendingItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_pending_items_by_user(user_id)
+    }
+
+    fn get_pending_items_by_workspace(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Vec<PendingItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_pending_items_by_workspace(workspace_id)
+    }
+
+    fn get_pending_items_by_priority(
+        &self,
+        priority: PendingPriority,
+    ) -> Result<Vec<PendingItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_pending_items_by_priority(priority)
+    }
+
+    fn update_pending_item(&self, item: &PendingItem) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_pending_item(item)
+    }
+
+    fn delete_pending_item(&self, pending_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_pending_item(pending_id)
+    }
+
+    fn get_pending_items_requiring_manual_review(&self) -> Result<Vec<PendingItem>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_pending_items_requiring_manual_review()
+    }
+
+    fn store_zk_proof(&self, proof: &crate::zk_proof_engine::ZkProof) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_zk_proof(proof)
     }
 
     fn get_zk_proof(
         &self,
-        _proof_id: &Uuid,
+        proof_id: &Uuid,
     ) -> Result<Option<crate::zk_proof_engine::ZkProof>, StorageError> {
-        Ok(None)
+        let guard = self.lock().unwrap();
+        guard.get_zk_proof(proof_id)
     }
 
-    fn update_zk_proof(
-        &self,
-        _proof: &crate::zk_proof_engine::ZkProof,
-    ) -> Result<(), StorageError> {
-        Err(StorageError::IoError(
-            "ZK proof operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn update_zk_proof(&self, proof: &crate::zk_proof_engine::ZkProof) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_zk_proof(proof)
     }
 
     fn query_zk_proofs(
         &self,
-        _query: &crate::api::zk_proofs::ZkProofQuery,
+        query: &crate::api::zk_proofs::ZkProofQuery,
     ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.query_zk_proofs(query)
     }
 
     fn list_zk_proofs(&self) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.list_zk_proofs()
     }
 
     fn get_zk_proofs_by_user(
         &self,
-        _user_id: &str,
+        user_id: &str,
     ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_zk_proofs_by_user(user_id)
     }
 
     fn get_zk_proofs_by_circuit_type(
         &self,
-        _circuit_type: CircuitType,
+        circuit_type: CircuitType,
     ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_zk_proofs_by_circuit_type(circuit_type)
     }
 
     fn get_zk_proofs_by_status(
         &self,
-        _status: crate::zk_proof_engine::ProofStatus,
+        status: crate::zk_proof_engine::ProofStatus,
     ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_zk_proofs_by_status(status)
     }
 
     fn get_zk_proof_statistics(
         &self,
     ) -> Result<crate::api::zk_proofs::ZkProofStatistics, StorageError> {
-        Ok(crate::api::zk_proofs::ZkProofStatistics {
-            total_proofs: 0,
-            verified_proofs: 0,
-            failed_proofs: 0,
-            pending_proofs: 0,
-            proof_types: std::collections::HashMap::new(),
-        })
+        let guard = self.lock().unwrap();
+        guard.get_zk_proof_statistics()
     }
 
-    fn delete_zk_proof(&self, _proof_id: &Uuid) -> Result<(), StorageError> {
-        Ok(())
+    fn delete_zk_proof(&self, proof_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_zk_proof(proof_id)
     }
 
-    fn store_storage_history(&self, _history: &ItemStorageHistory) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Storage history operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn store_storage_history(&self, history: &ItemStorageHistory) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_storage_history(history)
     }
 
-    fn get_storage_history(&self, _dfid: &str) -> Result<Option<ItemStorageHistory>, StorageError> {
-        Ok(None)
+    fn get_storage_history(&self, dfid: &str) -> Result<Option<ItemStorageHistory>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_storage_history(dfid)
     }
 
-    fn add_storage_record(&self, _dfid: &str, _record: StorageRecord) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Storage history operations not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn add_storage_record(&self, dfid: &str, record: StorageRecord) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.add_storage_record(dfid, record)
     }
 
-    // CID Timeline operations - not implemented for EncryptedFileStorage
     fn add_cid_to_timeline(
         &self,
-        _dfid: &str,
-        _cid: &str,
-        _ipcm_tx: &str,
-        _timestamp: i64,
-        _network: &str,
+        dfid: &str,
+        cid: &str,
+        ipcm_tx: &str,
+        timestamp: i64,
+        network: &str,
     ) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "CID timeline operations not implemented for EncryptedFileStorage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.add_cid_to_timeline(dfid, cid, ipcm_tx, timestamp, network)
     }
 
-    fn get_item_timeline(&self, _dfid: &str) -> Result<Vec<TimelineEntry>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "CID timeline operations not implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn get_item_timeline(&self, dfid: &str) -> Result<Vec<TimelineEntry>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_item_timeline(dfid)
     }
 
     fn get_timeline_by_sequence(
         &self,
-        _dfid: &str,
-        _sequence: i32,
+        dfid: &str,
+        sequence: i32,
     ) -> Result<Option<TimelineEntry>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "CID timeline operations not implemented for EncryptedFileStorage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_timeline_by_sequence(dfid, sequence)
     }
 
     fn map_event_to_cid(
         &self,
-        _event_id: &Uuid,
-        _dfid: &str,
-        _cid: &str,
-        _sequence: i32,
+        event_id: &Uuid,
+        dfid: &str,
+        cid: &str,
+        sequence: i32,
     ) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "CID timeline operations not implemented for EncryptedFileStorage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.map_event_to_cid(event_id, dfid, cid, sequence)
     }
 
     fn get_event_first_cid(
         &self,
-        _event_id: &Uuid,
+        event_id: &Uuid,
     ) -> Result<Option<EventCidMapping>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "CID timeline operations not implemented for EncryptedFileStorage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_event_first_cid(event_id)
     }
 
-    fn get_events_in_cid(&self, _cid: &str) -> Result<Vec<EventCidMapping>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "CID timeline operations not implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn get_events_in_cid(&self, cid: &str) -> Result<Vec<EventCidMapping>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_events_in_cid(cid)
     }
 
     fn update_indexing_progress(
         &self,
-        _network: &str,
-        _last_ledger: i64,
-        _confirmed_ledger: i64,
+        network: &str,
+        last_ledger: i64,
+        confirmed_ledger: i64,
     ) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Indexing progress operations not implemented for EncryptedFileStorage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.update_indexing_progress(network, last_ledger, confirmed_ledger)
     }
 
     fn get_indexing_progress(
         &self,
-        _network: &str,
+        network: &str,
     ) -> Result<Option<IndexingProgress>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Indexing progress operations not implemented for EncryptedFileStorage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_indexing_progress(network)
     }
 
-    fn increment_events_indexed(&self, _network: &str, _count: i64) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Indexing progress operations not implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn increment_events_indexed(&self, network: &str, count: i64) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.increment_events_indexed(network, count)
     }
 
     fn store_circuit_adapter_config(
         &self,
-        _config: &CircuitAdapterConfig,
+        config: &CircuitAdapterConfig,
     ) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Circuit adapter config operations not yet implemented for EncryptedFileStorage"
-                .to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.store_circuit_adapter_config(config)
     }
 
     fn get_circuit_adapter_config(
         &self,
-        _circuit_id: &Uuid,
+        circuit_id: &Uuid,
     ) -> Result<Option<CircuitAdapterConfig>, StorageError> {
-        Ok(None)
+        let guard = self.lock().unwrap();
+        guard.get_circuit_adapter_config(circuit_id)
     }
 
     fn update_circuit_adapter_config(
         &self,
-        _config: &CircuitAdapterConfig,
+        config: &CircuitAdapterConfig,
     ) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Circuit adapter config operations not yet implemented for EncryptedFileStorage"
-                .to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.update_circuit_adapter_config(config)
     }
 
     fn list_circuit_adapter_configs(&self) -> Result<Vec<CircuitAdapterConfig>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.list_circuit_adapter_configs()
     }
 
-    // User Account operations (stub implementations for now)
-    fn store_user_account(&self, _user: &UserAccount) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "User account operations not yet implemented".to_string(),
-        ))
+    fn store_user_account(&self, user: &UserAccount) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_user_account(user)
     }
 
-    fn get_user_account(&self, _user_id: &str) -> Result<Option<UserAccount>, StorageError> {
-        Ok(None)
+    fn get_user_account(&self, user_id: &str) -> Result<Option<UserAccount>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_user_account(user_id)
     }
 
-    fn get_user_by_username(&self, _username: &str) -> Result<Option<UserAccount>, StorageError> {
-        Ok(None)
+    fn get_user_by_username(&self, username: &str) -> Result<Option<UserAccount>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_user_by_username(username)
     }
 
-    fn get_user_by_email(&self, _email: &str) -> Result<Option<UserAccount>, StorageError> {
-        Ok(None)
+    fn get_user_by_email(&self, email: &str) -> Result<Option<UserAccount>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_user_by_email(email)
     }
 
-    fn update_user_account(&self, _user: &UserAccount) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "User account operations not yet implemented".to_string(),
-        ))
+    fn update_user_account(&self, user: &UserAccount) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_user_account(user)
     }
 
     fn list_user_accounts(&self) -> Result<Vec<UserAccount>, StorageError> {
-        Ok(Vec::new())
-    }
-
-    fn delete_user_account(&self, _user_id: &str) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "User account operations not yet implemented".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.list_user_accounts()
     }
 
-    fn store_password_reset_token(&self, _token: &PasswordResetToken) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Password reset tokens not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn store_password_reset_token(&self, token: &PasswordResetToken) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_password_reset_token(token)
     }
 
     fn get_password_reset_token_by_hash(
         &self,
-        _token_hash: &str,
+        token_hash: &str,
     ) -> Result<Option<PasswordResetToken>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Password reset tokens not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_password_reset_token_by_hash(token_hash)
     }
 
-    fn mark_token_as_used(&self, _token_id: &str) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Password reset tokens not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+    fn mark_token_as_used(&self, token_id: &str) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.mark_token_as_used(token_id)
     }
 
     fn count_recent_reset_requests(
         &self,
-        _user_id: &str,
-        _since: DateTime<Utc>,
+        user_id: &str,
+        since: DateTime<Utc>,
     ) -> Result<usize, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Password reset tokens not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.count_recent_reset_requests(user_id, since)
     }
 
     fn cleanup_expired_tokens(&self) -> Result<usize, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Password reset tokens not yet implemented for EncryptedFileStorage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.cleanup_expired_tokens()
+    }
+
+    fn delete_user_account(&self, user_id: &str) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_user_account(user_id)
     }
 
-    // Credit Transaction operations (stub implementations for now)
     fn record_credit_transaction(
         &self,
-        _transaction: &CreditTransaction,
+        transaction: &CreditTransaction,
     ) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Credit transaction operations not yet implemented".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.record_credit_transaction(transaction)
     }
 
     fn get_credit_transaction(
         &self,
-        _transaction_id: &str,
+        transaction_id: &str,
     ) -> Result<Option<CreditTransaction>, StorageError> {
-        Ok(None)
+        let guard = self.lock().unwrap();
+        guard.get_credit_transaction(transaction_id)
     }
 
     fn get_credit_transactions(
         &self,
-        _user_id: &str,
-        _limit: Option<usize>,
+        user_id: &str,
+        limit: Option<usize>,
     ) -> Result<Vec<CreditTransaction>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_credit_transactions(user_id, limit)
     }
 
     fn get_credit_transactions_by_operation(
         &self,
-        _operation_type: &str,
+        operation_type: &str,
     ) -> Result<Vec<CreditTransaction>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_credit_transactions_by_operation(operation_type)
     }
 
-    // Admin Action operations (stub implementations for now)
-    fn record_admin_action(&self, _action: &AdminAction) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Admin action operations not yet implemented".to_string(),
-        ))
+    fn record_admin_action(&self, action: &AdminAction) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.record_admin_action(action)
     }
 
     fn get_admin_actions(
         &self,
-        _admin_id: Option<&str>,
-        _limit: Option<usize>,
+        admin_id: Option<&str>,
+        limit: Option<usize>,
     ) -> Result<Vec<AdminAction>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_admin_actions(admin_id, limit)
     }
 
     fn get_admin_actions_by_type(
         &self,
-        _action_type: &str,
+        action_type: &str,
     ) -> Result<Vec<AdminAction>, StorageError> {
-        Ok(Vec::new())
+        let guard = self.lock().unwrap();
+        guard.get_admin_actions_by_type(action_type)
     }
 
-    // System Statistics operations (stub implementations for now)
     fn get_system_statistics(&self) -> Result<SystemStatistics, StorageError> {
-        Ok(SystemStatistics {
-            total_users: 0,
-            active_users_24h: 0,
-            active_users_30d: 0,
-            total_items: 0,
-            total_circuits: 0,
-            total_storage_operations: 0,
-            credits_consumed_24h: 0,
-            tier_distribution: HashMap::new(),
-            adapter_usage_stats: HashMap::new(),
-            generated_at: Utc::now(),
-        })
+        let guard = self.lock().unwrap();
+        guard.get_system_statistics()
     }
 
-    fn update_system_statistics(&self, _stats: &SystemStatistics) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "System statistics operations not yet implemented".to_string(),
-        ))
+    fn update_system_statistics(&self, stats: &SystemStatistics) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_system_statistics(stats)
     }
 
-    // Notification operations - not implemented for file storage yet
-    fn store_notification(&self, _notification: &Notification) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Notification operations not yet implemented for file storage".to_string(),
-        ))
+    fn store_notification(&self, notification: &Notification) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_notification(notification)
     }
 
     fn get_notification(
         &self,
-        _notification_id: &str,
+        notification_id: &str,
     ) -> Result<Option<Notification>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Notification operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_notification(notification_id)
     }
 
     fn get_user_notifications(
         &self,
-        _user_id: &str,
-        _since: Option<DateTime<Utc>>,
-        _limit: Option<usize>,
-        _unread_only: bool,
+        user_id: &str,
+        since: Option<DateTime<Utc>>,
+        limit: Option<usize>,
+        unread_only: bool,
     ) -> Result<Vec<Notification>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Notification operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_user_notifications(user_id, since, limit, unread_only)
     }
 
-    fn update_notification(&self, _notification: &Notification) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Notification operations not yet implemented for file storage".to_string(),
-        ))
+    fn update_notification(&self, notification: &Notification) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_notification(notification)
     }
 
-    fn delete_notification(&self, _notification_id: &str) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Notification operations not yet implemented for file storage".to_string(),
-        ))
+    fn delete_notification(&self, notification_id: &str) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_notification(notification_id)
     }
 
-    fn mark_all_notifications_read(&self, _user_id: &str) -> Result<usize, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Notification operations not yet implemented for file storage".to_string(),
-        ))
+    fn mark_all_notifications_read(&self, user_id: &str) -> Result<usize, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.mark_all_notifications_read(user_id)
     }
 
-    fn get_unread_notification_count(&self, _user_id: &str) -> Result<usize, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Notification operations not yet implemented for file storage".to_string(),
-        ))
+    fn get_unread_notification_count(&self, user_id: &str) -> Result<usize, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_unread_notification_count(user_id)
     }
 
-    // Adapter Configuration Management operations - not implemented for file storage yet
-    fn store_adapter_config(&self, _config: &AdapterConfig) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Adapter config operations not yet implemented for file storage".to_string(),
-        ))
+    fn store_adapter_config(&self, config: &AdapterConfig) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_adapter_config(config)
     }
 
-    fn get_adapter_config(&self, _config_id: &Uuid) -> Result<Option<AdapterConfig>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Adapter config operations not yet implemented for file storage".to_string(),
-        ))
+    fn get_adapter_config(&self, config_id: &Uuid) -> Result<Option<AdapterConfig>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_adapter_config(config_id)
     }
 
-    fn update_adapter_config(&self, _config: &AdapterConfig) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Adapter config operations not yet implemented for file storage".to_string(),
-        ))
+    fn update_adapter_config(&self, config: &AdapterConfig) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.update_adapter_config(config)
     }
 
-    fn delete_adapter_config(&self, _config_id: &Uuid) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Adapter config operations not yet implemented for file storage".to_string(),
-        ))
+    fn delete_adapter_config(&self, config_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_adapter_config(config_id)
     }
 
     fn list_adapter_configs(&self) -> Result<Vec<AdapterConfig>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Adapter config operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.list_adapter_configs()
     }
 
     fn list_active_adapter_configs(&self) -> Result<Vec<AdapterConfig>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Adapter config operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.list_active_adapter_configs()
     }
 
     fn get_adapter_configs_by_type(
         &self,
-        _adapter_type: &AdapterType,
+        adapter_type: &AdapterType,
     ) -> Result<Vec<AdapterConfig>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Adapter config operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_adapter_configs_by_type(adapter_type)
     }
 
     fn get_default_adapter_config(&self) -> Result<Option<AdapterConfig>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Adapter config operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_default_adapter_config()
     }
 
-    fn set_default_adapter(&self, _config_id: &Uuid) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Adapter config operations not yet implemented for file storage".to_string(),
-        ))
+    fn set_default_adapter(&self, config_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.set_default_adapter(config_id)
     }
 
-    fn store_adapter_test_result(&self, _result: &AdapterTestResult) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Adapter config operations not yet implemented for file storage".to_string(),
-        ))
+    fn store_adapter_test_result(&self, result: &AdapterTestResult) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_adapter_test_result(result)
     }
 
     fn get_adapter_test_result(
         &self,
-        _config_id: &Uuid,
+        config_id: &Uuid,
     ) -> Result<Option<AdapterTestResult>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Adapter config operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_adapter_test_result(config_id)
     }
 
-    // LID ↔ DFID mapping operations - not implemented for file storage yet
-    fn store_lid_dfid_mapping(&self, _lid: &Uuid, _dfid: &str) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "LID-DFID mapping not yet implemented for file storage".to_string(),
-        ))
+    fn store_lid_dfid_mapping(&self, lid: &Uuid, dfid: &str) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_lid_dfid_mapping(lid, dfid)
     }
 
-    fn get_dfid_by_lid(&self, _lid: &Uuid) -> Result<Option<String>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "LID-DFID mapping not yet implemented for file storage".to_string(),
-        ))
+    fn get_dfid_by_lid(&self, lid: &Uuid) -> Result<Option<String>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_dfid_by_lid(lid)
     }
 
-    // Canonical identifier lookups - not implemented for file storage yet
     fn get_dfid_by_canonical(
         &self,
-        _namespace: &str,
-        _registry: &str,
-        _value: &str,
+        namespace: &str,
+        registry: &str,
+        value: &str,
     ) -> Result<Option<String>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Canonical lookup not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_dfid_by_canonical(namespace, registry, value)
     }
 
-    // Fingerprint mappings - not implemented for file storage yet
     fn store_fingerprint_mapping(
         &self,
-        _fingerprint: &str,
-        _dfid: &str,
-        _circuit_id: &Uuid,
+        fingerprint: &str,
+        dfid: &str,
+        circuit_id: &Uuid,
     ) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Fingerprint mapping not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.store_fingerprint_mapping(fingerprint, dfid, circuit_id)
     }
 
     fn get_dfid_by_fingerprint(
         &self,
-        _fingerprint: &str,
-        _circuit_id: &Uuid,
+        fingerprint: &str,
+        circuit_id: &Uuid,
     ) -> Result<Option<String>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Fingerprint lookup not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_dfid_by_fingerprint(fingerprint, circuit_id)
     }
 
-    // Enhanced identifier mappings - not implemented for file storage yet
     fn store_enhanced_identifier_mapping(
         &self,
-        _identifier: &EnhancedIdentifier,
-        _dfid: &str,
+        identifier: &EnhancedIdentifier,
+        dfid: &str,
     ) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Enhanced identifier mapping not yet implemented for file storage".to_string(),
-        ))
-    }
-
-    // Webhook delivery operations - not implemented for file storage yet
-    fn store_webhook_delivery(&self, _delivery: &WebhookDelivery) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Webhook delivery operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.store_enhanced_identifier_mapping(identifier, dfid)
+    }
+
+    fn store_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_webhook_delivery(delivery)
     }
 
     fn get_webhook_delivery(
         &self,
-        _delivery_id: &Uuid,
+        delivery_id: &Uuid,
     ) -> Result<Option<WebhookDelivery>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Webhook delivery operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_webhook_delivery(delivery_id)
     }
 
     fn get_webhook_deliveries_by_circuit(
         &self,
-        _circuit_id: &Uuid,
-        _limit: Option<usize>,
+        circuit_id: &Uuid,
+        limit: Option<usize>,
     ) -> Result<Vec<WebhookDelivery>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Webhook delivery operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_webhook_deliveries_by_circuit(circuit_id, limit)
     }
 
     fn get_webhook_deliveries_by_webhook(
         &self,
-        _webhook_id: &Uuid,
-        _limit: Option<usize>,
+        webhook_id: &Uuid,
+        limit: Option<usize>,
     ) -> Result<Vec<WebhookDelivery>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Webhook delivery operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_webhook_deliveries_by_webhook(webhook_id, limit)
     }
 
-    // User Activity operations
-    fn store_user_activity(&self, _activity: &UserActivity) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "User activity operations not yet implemented for file storage".to_string(),
-        ))
+    fn store_user_activity(&self, activity: &UserActivity) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_user_activity(activity)
     }
 
     fn list_user_activities(&self) -> Result<Vec<UserActivity>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "User activity operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.list_user_activities()
     }
 
     fn clear_user_activities(&self) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "User activity operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.clear_user_activities()
     }
 
-    // State Snapshot operations - not implemented for file storage yet
+    // State Snapshot operations
     fn store_snapshot(
         &self,
-        _snapshot: &crate::snapshot_types::StateSnapshot,
+        snapshot: &crate::snapshot_types::StateSnapshot,
     ) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Snapshot operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.store_snapshot(snapshot)
     }
 
     fn get_snapshot(
         &self,
-        _snapshot_id: &str,
+        snapshot_id: &str,
     ) -> Result<Option<crate::snapshot_types::StateSnapshot>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Snapshot operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_snapshot(snapshot_id)
     }
 
     fn get_snapshots_for_entity(
         &self,
-        _entity_type: crate::snapshot_types::SnapshotEntityType,
-        _entity_id: &str,
+        entity_type: crate::snapshot_types::SnapshotEntityType,
+        entity_id: &str,
     ) -> Result<Vec<crate::snapshot_types::StateSnapshot>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Snapshot operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_snapshots_for_entity(entity_type, entity_id)
     }
 
     fn get_latest_snapshot(
         &self,
-        _entity_type: crate::snapshot_types::SnapshotEntityType,
-        _entity_id: &str,
+        entity_type: crate::snapshot_types::SnapshotEntityType,
+        entity_id: &str,
     ) -> Result<Option<crate::snapshot_types::StateSnapshot>, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Snapshot operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_latest_snapshot(entity_type, entity_id)
     }
 
     fn update_snapshot(
         &self,
-        _snapshot: &crate::snapshot_types::StateSnapshot,
+        snapshot: &crate::snapshot_types::StateSnapshot,
     ) -> Result<(), StorageError> {
-        Err(StorageError::NotImplemented(
-            "Snapshot operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.update_snapshot(snapshot)
     }
 
     fn get_snapshot_count(
         &self,
-        _entity_type: crate::snapshot_types::SnapshotEntityType,
-        _entity_id: &str,
+        entity_type: crate::snapshot_types::SnapshotEntityType,
+        entity_id: &str,
     ) -> Result<u64, StorageError> {
-        Err(StorageError::NotImplemented(
-            "Snapshot operations not yet implemented for file storage".to_string(),
-        ))
+        let guard = self.lock().unwrap();
+        guard.get_snapshot_count(entity_type, entity_id)
     }
-}
 
-// StorageBackend adapter for Arc<Mutex<PostgresStorageWithCache>>
-// StorageBackend adapter for Arc<Mutex<PostgresStorageWithCache>>
-impl StorageBackend for Arc<Mutex<PostgresStorageWithCache>> {
-    fn store_receipt(&self, receipt: &Receipt) -> Result<(), StorageError> {
+    // Organization directory operations
+    fn store_organization_profile(
+        &self,
+        profile: &OrganizationProfile,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_receipt(receipt)
+        guard.store_organization_profile(profile)
     }
 
-    fn get_receipt(&self, id: &Uuid) -> Result<Option<Receipt>, StorageError> {
+    fn get_organization_profile(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<OrganizationProfile>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_receipt(id)
+        guard.get_organization_profile(user_id)
     }
 
-    fn find_receipts_by_identifier(
-        &self,
-        identifier: &Identifier,
-    ) -> Result<Vec<Receipt>, StorageError> {
+    fn list_organization_profiles(&self) -> Result<Vec<OrganizationProfile>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.find_receipts_by_identifier(identifier)
+        guard.list_organization_profiles()
     }
 
-    fn list_receipts(&self) -> Result<Vec<Receipt>, StorageError> {
+    fn delete_organization_profile(&self, user_id: &str) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_receipts()
+        guard.delete_organization_profile(user_id)
     }
 
-    fn store_log(&self, log: &LogEntry) -> Result<(), StorageError> {
+    // Attestation operations
+    fn store_attestation(&self, attestation: &Attestation) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_log(log)
+        guard.store_attestation(attestation)
     }
 
-    fn get_logs(&self) -> Result<Vec<LogEntry>, StorageError> {
+    fn get_attestation(&self, attestation_id: &Uuid) -> Result<Option<Attestation>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_logs()
+        guard.get_attestation(attestation_id)
     }
 
-    fn store_data_lake_entry(&self, entry: &DataLakeEntry) -> Result<(), StorageError> {
+    fn list_attestations_for_subject(
+        &self,
+        subject_id: &str,
+    ) -> Result<Vec<Attestation>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_data_lake_entry(entry)
+        guard.list_attestations_for_subject(subject_id)
     }
 
-    fn get_data_lake_entry(&self, entry_id: &Uuid) -> Result<Option<DataLakeEntry>, StorageError> {
+    fn list_attestations_by_attester(
+        &self,
+        attester_id: &str,
+    ) -> Result<Vec<Attestation>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_data_lake_entry(entry_id)
+        guard.list_attestations_by_attester(attester_id)
     }
 
-    fn update_data_lake_entry(&self, entry: &DataLakeEntry) -> Result<(), StorageError> {
+    // Saved audit query operations
+    fn store_saved_audit_query(&self, query: &SavedAuditQuery) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_data_lake_entry(entry)
+        guard.store_saved_audit_query(query)
     }
 
-    fn get_data_lake_entries_by_status(
+    fn get_saved_audit_query(
         &self,
-        status: ProcessingStatus,
-    ) -> Result<Vec<DataLakeEntry>, StorageError> {
+        query_id: &Uuid,
+    ) -> Result<Option<SavedAuditQuery>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_data_lake_entries_by_status(status)
+        guard.get_saved_audit_query(query_id)
     }
 
-    fn list_data_lake_entries(&self) -> Result<Vec<DataLakeEntry>, StorageError> {
+    fn list_saved_audit_queries(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<SavedAuditQuery>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_data_lake_entries()
+        guard.list_saved_audit_queries(owner_id)
     }
 
-    fn store_item(&self, item: &Item) -> Result<(), StorageError> {
+    fn delete_saved_audit_query(&self, query_id: &Uuid) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_item(item)
+        guard.delete_saved_audit_query(query_id)
     }
 
-    fn get_item_by_dfid(&self, dfid: &str) -> Result<Option<Item>, StorageError> {
+    // Preview environment operations
+    fn store_preview_environment(&self, preview: &PreviewEnvironment) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_item_by_dfid(dfid)
+        guard.store_preview_environment(preview)
     }
 
-    fn update_item(&self, item: &Item) -> Result<(), StorageError> {
+    fn get_preview_environment(
+        &self,
+        preview_id: &Uuid,
+    ) -> Result<Option<PreviewEnvironment>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_item(item)
+        guard.get_preview_environment(preview_id)
     }
 
-    fn list_items(&self) -> Result<Vec<Item>, StorageError> {
+    fn list_preview_environments(
+        &self,
+        owner_id: &str,
+    ) -> Result<Vec<PreviewEnvironment>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_items()
+        guard.list_preview_environments(owner_id)
     }
 
-    fn find_items_by_identifier(&self, identifier: &Identifier) -> Result<Vec<Item>, StorageError> {
+    // Paginated listings (ordered by creation timestamp, then id)
+    fn list_items_page(
+        &self,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Item>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.find_items_by_identifier(identifier)
+        guard.list_items_page(after, limit)
     }
 
-    fn find_items_by_status(&self, status: ItemStatus) -> Result<Vec<Item>, StorageError> {
+    fn list_events_page(
+        &self,
+        dfid: Option<&str>,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Event>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.find_items_by_status(status)
+        guard.list_events_page(dfid, after, limit)
     }
 
-    fn delete_item(&self, dfid: &str) -> Result<(), StorageError> {
+    // Legacy connector operations
+    fn store_connector_config(&self, config: &ConnectorConfig) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.delete_item(dfid)
+        guard.store_connector_config(config)
     }
 
-    fn store_identifier_mapping(&self, mapping: &IdentifierMapping) -> Result<(), StorageError> {
+    fn get_connector_config(
+        &self,
+        connector_id: &Uuid,
+    ) -> Result<Option<ConnectorConfig>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_identifier_mapping(mapping)
+        guard.get_connector_config(connector_id)
     }
 
-    fn get_identifier_mappings(
+    fn list_connector_configs(&self) -> Result<Vec<ConnectorConfig>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_connector_configs()
+    }
+
+    fn delete_connector_config(&self, connector_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_connector_config(connector_id)
+    }
+
+    fn store_mapping_template(&self, template: &MappingTemplate) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_mapping_template(template)
+    }
+
+    fn get_mapping_template(
         &self,
-        identifier: &Identifier,
-    ) -> Result<Vec<IdentifierMapping>, StorageError> {
+        template_id: &Uuid,
+    ) -> Result<Option<MappingTemplate>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_identifier_mappings(identifier)
+        guard.get_mapping_template(template_id)
     }
 
-    fn update_identifier_mapping(&self, mapping: &IdentifierMapping) -> Result<(), StorageError> {
+    fn list_mapping_templates(&self, owner_id: &str) -> Result<Vec<MappingTemplate>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_identifier_mapping(mapping)
+        guard.list_mapping_templates(owner_id)
     }
 
-    fn list_identifier_mappings(&self) -> Result<Vec<IdentifierMapping>, StorageError> {
+    fn store_connector_state(&self, state: &ConnectorState) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_identifier_mappings()
+        guard.store_connector_state(state)
     }
 
-    fn store_conflict_resolution(&self, conflict: &ConflictResolution) -> Result<(), StorageError> {
+    fn get_connector_state(
+        &self,
+        connector_id: &Uuid,
+    ) -> Result<Option<ConnectorState>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_conflict_resolution(conflict)
+        guard.get_connector_state(connector_id)
     }
 
-    fn get_conflict_resolution(
+    fn store_connector_error(&self, error: &ConnectorRunError) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_connector_error(error)
+    }
+
+    fn list_connector_errors(
         &self,
-        conflict_id: &Uuid,
-    ) -> Result<Option<ConflictResolution>, StorageError> {
+        connector_id: &Uuid,
+        limit: usize,
+    ) -> Result<Vec<ConnectorRunError>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_conflict_resolution(conflict_id)
+        guard.list_connector_errors(connector_id, limit)
     }
 
-    fn get_pending_conflicts(&self) -> Result<Vec<ConflictResolution>, StorageError> {
+    fn delete_mapping_template(&self, template_id: &Uuid) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_pending_conflicts()
+        guard.delete_mapping_template(template_id)
     }
 
-    fn store_event(&self, event: &Event) -> Result<(), StorageError> {
+    // Circuit change feed operations
+    fn append_change_record(&self, record: &ChangeRecord) -> Result<ChangeRecord, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_event(event)
+        guard.append_change_record(record)
     }
 
-    fn get_event(&self, event_id: &Uuid) -> Result<Option<Event>, StorageError> {
+    fn list_change_records(
+        &self,
+        circuit_id: &Uuid,
+        after_sequence: u64,
+        limit: usize,
+    ) -> Result<Vec<ChangeRecord>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_event(event_id)
+        guard.list_change_records(circuit_id, after_sequence, limit)
     }
 
-    fn update_event(&self, event: &Event) -> Result<(), StorageError> {
+    fn get_latest_change_sequence(&self, circuit_id: &Uuid) -> Result<u64, StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_event(event)
+        guard.get_latest_change_sequence(circuit_id)
     }
 
-    fn list_events(&self) -> Result<Vec<Event>, StorageError> {
+    fn store_change_feed_subscription(
+        &self,
+        subscription: &ChangeFeedSubscription,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_events()
+        guard.store_change_feed_subscription(subscription)
     }
 
-    fn get_events_by_dfid(&self, dfid: &str) -> Result<Vec<Event>, StorageError> {
+    fn get_change_feed_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> Result<Option<ChangeFeedSubscription>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_events_by_dfid(dfid)
+        guard.get_change_feed_subscription(subscription_id)
     }
 
-    fn get_events_by_type(&self, event_type: EventType) -> Result<Vec<Event>, StorageError> {
+    fn list_change_feed_subscriptions(
+        &self,
+        circuit_id: Option<&Uuid>,
+    ) -> Result<Vec<ChangeFeedSubscription>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_events_by_type(event_type)
+        guard.list_change_feed_subscriptions(circuit_id)
     }
 
-    fn get_events_by_visibility(
+    fn delete_change_feed_subscription(&self, subscription_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_change_feed_subscription(subscription_id)
+    }
+
+    // Item search
+    fn search_items(
         &self,
-        visibility: EventVisibility,
-    ) -> Result<Vec<Event>, StorageError> {
+        query: &ItemSearchQuery,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Item>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_events_by_visibility(visibility)
+        guard.search_items(query, after, limit)
     }
 
-    fn get_events_in_time_range(
+    // Announcements
+    fn store_announcement(&self, announcement: &Announcement) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_announcement(announcement)
+    }
+
+    fn get_announcement(
         &self,
-        start: DateTime<Utc>,
-        end: DateTime<Utc>,
-    ) -> Result<Vec<Event>, StorageError> {
+        announcement_id: &Uuid,
+    ) -> Result<Option<Announcement>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_events_in_time_range(start, end)
+        guard.get_announcement(announcement_id)
     }
 
-    fn get_event_by_content_hash(&self, content_hash: &str) -> Result<Option<Event>, StorageError> {
+    fn list_announcements(&self) -> Result<Vec<Announcement>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_event_by_content_hash(content_hash)
+        guard.list_announcements()
     }
 
-    fn store_circuit(&self, circuit: &Circuit) -> Result<(), StorageError> {
+    // Workspace engagement snapshots
+    fn store_workspace_engagement(
+        &self,
+        engagement: &WorkspaceEngagement,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_circuit(circuit)
+        guard.store_workspace_engagement(engagement)
     }
 
-    fn get_circuit(&self, circuit_id: &Uuid) -> Result<Option<Circuit>, StorageError> {
+    fn get_workspace_engagement(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<WorkspaceEngagement>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_circuit(circuit_id)
+        guard.get_workspace_engagement(workspace_id)
     }
 
-    fn update_circuit(&self, circuit: &Circuit) -> Result<(), StorageError> {
+    // Data export jobs
+    fn store_data_export_job(&self, job: &DataExportJob) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_circuit(circuit)
+        guard.store_data_export_job(job)
     }
 
-    fn list_circuits(&self) -> Result<Vec<Circuit>, StorageError> {
+    fn get_data_export_job(&self, job_id: &Uuid) -> Result<Option<DataExportJob>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_circuits()
+        guard.get_data_export_job(job_id)
     }
 
-    fn get_circuits_for_member(&self, member_id: &str) -> Result<Vec<Circuit>, StorageError> {
+    fn list_data_export_jobs(
+        &self,
+        requested_by: &str,
+    ) -> Result<Vec<DataExportJob>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_circuits_for_member(member_id)
+        guard.list_data_export_jobs(requested_by)
     }
 
-    fn store_circuit_operation(&self, operation: &CircuitOperation) -> Result<(), StorageError> {
+    // SLA windows
+    fn store_sla_window(&self, window: &SlaWindow) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_circuit_operation(operation)
+        guard.store_sla_window(window)
     }
 
-    fn get_circuit_operation(
+    fn get_sla_window(
         &self,
-        operation_id: &Uuid,
-    ) -> Result<Option<CircuitOperation>, StorageError> {
+        component: SlaComponent,
+        hour_start: DateTime<Utc>,
+    ) -> Result<Option<SlaWindow>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_circuit_operation(operation_id)
+        guard.get_sla_window(component, hour_start)
+    }
+
+    fn list_sla_windows(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<SlaWindow>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_sla_windows(from, to)
+    }
+
+    // Event schemas
+    fn store_event_schema(&self, schema: &EventSchema) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_event_schema(schema)
+    }
+
+    fn list_event_schemas(&self) -> Result<Vec<EventSchema>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_event_schemas()
     }
 
-    fn update_circuit_operation(&self, operation: &CircuitOperation) -> Result<(), StorageError> {
+    // Maintenance mode
+    fn store_maintenance_mode(&self, mode: &MaintenanceMode) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_circuit_operation(operation)
+        guard.store_maintenance_mode(mode)
     }
 
-    fn get_circuit_operations(
-        &self,
-        circuit_id: &Uuid,
-    ) -> Result<Vec<CircuitOperation>, StorageError> {
+    fn get_maintenance_mode(&self) -> Result<Option<MaintenanceMode>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_circuit_operations(circuit_id)
+        guard.get_maintenance_mode()
     }
 
-    fn store_item_share(&self, share: &ItemShare) -> Result<(), StorageError> {
+    // Managed keys
+    fn store_managed_key(&self, key: &ManagedKey) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_item_share(share)
+        guard.store_managed_key(key)
     }
 
-    fn get_item_share(&self, share_id: &str) -> Result<Option<ItemShare>, StorageError> {
+    fn get_managed_key(&self, key_id: &Uuid) -> Result<Option<ManagedKey>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_item_share(share_id)
+        guard.get_managed_key(key_id)
     }
 
-    fn get_shares_for_user(&self, user_id: &str) -> Result<Vec<ItemShare>, StorageError> {
+    fn list_managed_keys(&self) -> Result<Vec<ManagedKey>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_shares_for_user(user_id)
+        guard.list_managed_keys()
     }
 
-    fn get_shares_for_item(&self, dfid: &str) -> Result<Vec<ItemShare>, StorageError> {
+    // Custom event types
+    fn store_custom_event_type(&self, event_type: &CustomEventType) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_shares_for_item(dfid)
+        guard.store_custom_event_type(event_type)
     }
 
-    fn is_item_shared_with_user(&self, dfid: &str, user_id: &str) -> Result<bool, StorageError> {
+    fn list_custom_event_types(&self) -> Result<Vec<CustomEventType>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.is_item_shared_with_user(dfid, user_id)
+        guard.list_custom_event_types()
     }
 
-    fn delete_item_share(&self, share_id: &str) -> Result<(), StorageError> {
+    fn delete_custom_event_type(
+        &self,
+        circuit_id: &Uuid,
+        name: &str,
+    ) -> Result<bool, StorageError> {
         let guard = self.lock().unwrap();
-        guard.delete_item_share(share_id)
+        guard.delete_custom_event_type(circuit_id, name)
     }
 
-    fn store_activity(&self, activity: &Activity) -> Result<(), StorageError> {
+    // Document notarizations
+    fn store_notarization(&self, notarization: &DocumentNotarization) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_activity(activity)
+        guard.store_notarization(notarization)
     }
 
-    fn get_activities_for_user(&self, user_id: &str) -> Result<Vec<Activity>, StorageError> {
+    fn get_notarization(
+        &self,
+        notarization_id: &Uuid,
+    ) -> Result<Option<DocumentNotarization>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_activities_for_user(user_id)
+        guard.get_notarization(notarization_id)
     }
 
-    fn get_activities_for_circuit(&self, circuit_id: &Uuid) -> Result<Vec<Activity>, StorageError> {
+    fn list_notarizations(&self) -> Result<Vec<DocumentNotarization>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_activities_for_circuit(circuit_id)
+        guard.list_notarizations()
     }
 
-    fn get_all_activities(&self) -> Result<Vec<Activity>, StorageError> {
+    fn store_notarization_batch(&self, batch: &NotarizationBatch) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_all_activities()
+        guard.store_notarization_batch(batch)
     }
 
-    fn store_circuit_item(&self, circuit_item: &CircuitItem) -> Result<(), StorageError> {
+    fn get_notarization_batch(
+        &self,
+        batch_id: &Uuid,
+    ) -> Result<Option<NotarizationBatch>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_circuit_item(circuit_item)
+        guard.get_notarization_batch(batch_id)
     }
 
-    fn get_circuit_items(&self, circuit_id: &Uuid) -> Result<Vec<CircuitItem>, StorageError> {
+    // Enrichment policies
+    fn store_enrichment_policy(&self, policy: &EnrichmentPolicy) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_circuit_items(circuit_id)
+        guard.store_enrichment_policy(policy)
     }
 
-    fn remove_circuit_item(&self, circuit_id: &Uuid, dfid: &str) -> Result<(), StorageError> {
+    fn get_enrichment_policy(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<EnrichmentPolicy>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.remove_circuit_item(circuit_id, dfid)
+        guard.get_enrichment_policy(workspace_id)
     }
 
-    fn store_audit_event(&self, event: &AuditEvent) -> Result<(), StorageError> {
+    // Event signing keys
+    fn store_event_signing_key(&self, key: &EventSigningKey) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_audit_event(event)
+        guard.store_event_signing_key(key)
     }
 
-    fn get_audit_event(&self, event_id: &Uuid) -> Result<Option<AuditEvent>, StorageError> {
+    fn get_event_signing_key(
+        &self,
+        key_id: &Uuid,
+    ) -> Result<Option<EventSigningKey>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_audit_event(event_id)
+        guard.get_event_signing_key(key_id)
     }
 
-    fn query_audit_events(&self, query: &AuditQuery) -> Result<Vec<AuditEvent>, StorageError> {
+    fn list_event_signing_keys(&self) -> Result<Vec<EventSigningKey>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.query_audit_events(query)
+        guard.list_event_signing_keys()
     }
 
-    fn list_audit_events(&self) -> Result<Vec<AuditEvent>, StorageError> {
+    // Anchoring SLAs, fee samples and anchoring spend
+    fn store_anchoring_sla(&self, sla: &AnchoringSla) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_audit_events()
+        guard.store_anchoring_sla(sla)
     }
 
-    fn get_audit_events_by_user(&self, user_id: &str) -> Result<Vec<AuditEvent>, StorageError> {
+    fn get_anchoring_sla(&self, circuit_id: &Uuid) -> Result<Option<AnchoringSla>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_audit_events_by_user(user_id)
+        guard.get_anchoring_sla(circuit_id)
     }
 
-    fn get_audit_events_by_type(
-        &self,
-        event_type: AuditEventType,
-    ) -> Result<Vec<AuditEvent>, StorageError> {
+    fn store_fee_sample(&self, sample: &FeeSample) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_audit_events_by_type(event_type)
+        guard.store_fee_sample(sample)
     }
 
-    fn get_audit_events_by_severity(
+    fn get_latest_fee_sample(
         &self,
-        severity: AuditSeverity,
-    ) -> Result<Vec<AuditEvent>, StorageError> {
+        network: &AnchoringNetwork,
+    ) -> Result<Option<FeeSample>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_audit_events_by_severity(severity)
+        guard.get_latest_fee_sample(network)
     }
 
-    fn get_audit_events_in_time_range(
+    fn store_anchoring_cost_record(
         &self,
-        start: DateTime<Utc>,
-        end: DateTime<Utc>,
-    ) -> Result<Vec<AuditEvent>, StorageError> {
+        record: &AnchoringCostRecord,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_audit_events_in_time_range(start, end)
+        guard.store_anchoring_cost_record(record)
     }
 
-    fn sync_audit_events(&self, events: Vec<AuditEvent>) -> Result<(), StorageError> {
+    fn list_anchoring_cost_records(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AnchoringCostRecord>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.sync_audit_events(events)
+        guard.list_anchoring_cost_records(since)
     }
 
-    fn store_security_incident(&self, incident: &SecurityIncident) -> Result<(), StorageError> {
+    // Event causality
+    fn get_events_caused_by(&self, event_id: &Uuid) -> Result<Vec<Event>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_security_incident(incident)
+        guard.get_events_caused_by(event_id)
     }
 
-    fn get_security_incident(
+    fn get_events_by_correlation_id(
         &self,
-        incident_id: &Uuid,
-    ) -> Result<Option<SecurityIncident>, StorageError> {
+        correlation_id: &Uuid,
+    ) -> Result<Vec<Event>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_security_incident(incident_id)
+        guard.get_events_by_correlation_id(correlation_id)
     }
 
-    fn update_security_incident(&self, incident: &SecurityIncident) -> Result<(), StorageError> {
+    // Item lifecycles
+    fn store_lifecycle_definition(
+        &self,
+        definition: &LifecycleDefinition,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_security_incident(incident)
+        guard.store_lifecycle_definition(definition)
     }
 
-    fn list_security_incidents(&self) -> Result<Vec<SecurityIncident>, StorageError> {
+    fn get_lifecycle_definition(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<LifecycleDefinition>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_security_incidents()
+        guard.get_lifecycle_definition(workspace_id)
     }
 
-    fn get_incidents_by_severity(
-        &self,
-        severity: AuditSeverity,
-    ) -> Result<Vec<SecurityIncident>, StorageError> {
+    fn store_item_lifecycle(&self, lifecycle: &ItemLifecycle) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_incidents_by_severity(severity)
+        guard.store_item_lifecycle(lifecycle)
     }
 
-    fn get_open_incidents(&self) -> Result<Vec<SecurityIncident>, StorageError> {
+    fn get_item_lifecycle(&self, dfid: &str) -> Result<Option<ItemLifecycle>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_open_incidents()
+        guard.get_item_lifecycle(dfid)
     }
 
-    fn get_incidents_by_assignee(
-        &self,
-        assignee: &str,
-    ) -> Result<Vec<SecurityIncident>, StorageError> {
+    fn list_item_lifecycles(&self, workspace_id: &str) -> Result<Vec<ItemLifecycle>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_incidents_by_assignee(assignee)
+        guard.list_item_lifecycles(workspace_id)
     }
 
-    fn store_compliance_report(&self, report: &ComplianceReport) -> Result<(), StorageError> {
+    // Circuit access reviews
+    fn store_access_report(&self, report: &CircuitAccessReport) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_compliance_report(report)
+        guard.store_access_report(report)
     }
 
-    fn get_compliance_report(
+    fn get_access_report(
         &self,
         report_id: &Uuid,
-    ) -> Result<Option<ComplianceReport>, StorageError> {
+    ) -> Result<Option<CircuitAccessReport>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_compliance_report(report_id)
+        guard.get_access_report(report_id)
     }
 
-    fn update_compliance_report(&self, report: &ComplianceReport) -> Result<(), StorageError> {
+    fn list_access_reports(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Vec<CircuitAccessReport>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_compliance_report(report)
+        guard.list_access_reports(circuit_id)
     }
 
-    fn list_compliance_reports(&self) -> Result<Vec<ComplianceReport>, StorageError> {
+    fn delete_access_report(&self, report_id: &Uuid) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_compliance_reports()
+        guard.delete_access_report(report_id)
     }
 
-    fn get_reports_by_type(
+    fn store_access_review_schedule(
         &self,
-        report_type: &str,
-    ) -> Result<Vec<ComplianceReport>, StorageError> {
+        schedule: &AccessReviewSchedule,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_reports_by_type(report_type)
+        guard.store_access_review_schedule(schedule)
     }
 
-    fn get_pending_reports(&self) -> Result<Vec<ComplianceReport>, StorageError> {
+    fn get_access_review_schedule(
+        &self,
+        circuit_id: &Uuid,
+    ) -> Result<Option<AccessReviewSchedule>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_pending_reports()
+        guard.get_access_review_schedule(circuit_id)
     }
 
-    fn get_audit_dashboard_metrics(&self) -> Result<AuditDashboardMetrics, StorageError> {
+    fn delete_access_review_schedule(&self, circuit_id: &Uuid) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_audit_dashboard_metrics()
+        guard.delete_access_review_schedule(circuit_id)
     }
 
-    fn get_event_count_by_time_range(
-        &self,
-        start: DateTime<Utc>,
-        end: DateTime<Utc>,
-    ) -> Result<u64, StorageError> {
+    fn list_access_review_schedules(&self) -> Result<Vec<AccessReviewSchedule>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_event_count_by_time_range(start, end)
+        guard.list_access_review_schedules()
     }
 
-    fn store_pending_item(&self, item: &PendingItem) -> Result<(), StorageError> {
+    // Adapter write intents
+    fn store_adapter_intent(&self, intent: &AdapterIntent) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_pending_item(item)
+        guard.store_adapter_intent(intent)
     }
 
-    fn get_pending_item(&self, pending_id: &Uuid) -> Result<Option<PendingItem>, StorageError> {
+    fn get_adapter_intent(&self, intent_id: &Uuid) -> Result<Option<AdapterIntent>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_pending_item(pending_id)
+        guard.get_adapter_intent(intent_id)
     }
 
-    fn list_pending_items(&self) -> Result<Vec<PendingItem>, StorageError> {
+    fn get_incomplete_adapter_intents(&self) -> Result<Vec<AdapterIntent>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_pending_items()
+        guard.get_incomplete_adapter_intents()
     }
 
-    fn get_pending_items_by_reason(
-        &self,
-        reason_type: &str,
-    ) -> Result<Vec<PendingItem>, StorageError> {
+    // Circuit-scoped partner tokens
+    fn store_partner_token(&self, token: &PartnerToken) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_pending_items_by_reason(reason_type)
+        guard.store_partner_token(token)
     }
 
-    fn get_pending_items_by_user(&self, user_id: &str) -> Result<Vec<PendingItem>, StorageError> {
+    fn get_partner_token(&self, token_id: &Uuid) -> Result<Option<PartnerToken>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_pending_items_by_user(user_id)
+        guard.get_partner_token(token_id)
     }
 
-    fn get_pending_items_by_workspace(
+    fn get_partner_token_by_hash(
         &self,
-        workspace_id: &str,
-    ) -> Result<Vec<PendingItem>, StorageError> {
+        token_hash: &str,
+    ) -> Result<Option<PartnerToken>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_pending_items_by_workspace(workspace_id)
+        guard.get_partner_token_by_hash(token_hash)
     }
 
-    fn get_pending_items_by_priority(
-        &self,
-        priority: PendingPriority,
-    ) -> Result<Vec<PendingItem>, StorageError> {
+    fn list_partner_tokens(&self, circuit_id: &Uuid) -> Result<Vec<PartnerToken>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_pending_items_by_priority(priority)
+        guard.list_partner_tokens(circuit_id)
     }
 
-    fn update_pending_item(&self, item: &PendingItem) -> Result<(), StorageError> {
+    fn record_partner_token_usage(&self, usage: &PartnerTokenUsage) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_pending_item(item)
+        guard.record_partner_token_usage(usage)
     }
 
-    fn delete_pending_item(&self, pending_id: &Uuid) -> Result<(), StorageError> {
+    fn list_partner_token_usage(
+        &self,
+        token_id: &Uuid,
+        limit: usize,
+    ) -> Result<Vec<PartnerTokenUsage>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.delete_pending_item(pending_id)
+        guard.list_partner_token_usage(token_id, limit)
     }
 
-    fn get_pending_items_requiring_manual_review(&self) -> Result<Vec<PendingItem>, StorageError> {
+    // Circuit comment threads
+    fn store_circuit_comment(&self, comment: &CircuitComment) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_pending_items_requiring_manual_review()
+        guard.store_circuit_comment(comment)
     }
 
-    fn store_zk_proof(&self, proof: &crate::zk_proof_engine::ZkProof) -> Result<(), StorageError> {
+    fn get_circuit_comment(
+        &self,
+        comment_id: &Uuid,
+    ) -> Result<Option<CircuitComment>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_zk_proof(proof)
+        guard.get_circuit_comment(comment_id)
     }
 
-    fn get_zk_proof(
+    fn list_circuit_comments(
         &self,
-        proof_id: &Uuid,
-    ) -> Result<Option<crate::zk_proof_engine::ZkProof>, StorageError> {
+        circuit_id: &Uuid,
+        target: &CommentTarget,
+    ) -> Result<Vec<CircuitComment>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_zk_proof(proof_id)
+        guard.list_circuit_comments(circuit_id, target)
     }
 
-    fn update_zk_proof(&self, proof: &crate::zk_proof_engine::ZkProof) -> Result<(), StorageError> {
+    fn delete_circuit_comment(&self, comment_id: &Uuid) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_zk_proof(proof)
+        guard.delete_circuit_comment(comment_id)
     }
 
-    fn query_zk_proofs(
+    // Circuit data quality reports
+    fn store_data_quality_report(&self, report: &DataQualityReport) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.store_data_quality_report(report)
+    }
+
+    fn list_data_quality_reports(
         &self,
-        query: &crate::api::zk_proofs::ZkProofQuery,
-    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+        circuit_id: &Uuid,
+    ) -> Result<Vec<DataQualityReport>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.query_zk_proofs(query)
+        guard.list_data_quality_reports(circuit_id)
     }
 
-    fn list_zk_proofs(&self) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+    fn delete_data_quality_report(&self, report_id: &Uuid) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_zk_proofs()
+        guard.delete_data_quality_report(report_id)
     }
 
-    fn get_zk_proofs_by_user(
-        &self,
-        user_id: &str,
-    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+    // Dashboard metric definitions
+    fn store_metric_definition(&self, definition: &MetricDefinition) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_zk_proofs_by_user(user_id)
+        guard.store_metric_definition(definition)
     }
 
-    fn get_zk_proofs_by_circuit_type(
+    fn get_metric_definition(
         &self,
-        circuit_type: CircuitType,
-    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+        metric_id: &Uuid,
+    ) -> Result<Option<MetricDefinition>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_zk_proofs_by_circuit_type(circuit_type)
+        guard.get_metric_definition(metric_id)
     }
 
-    fn get_zk_proofs_by_status(
+    fn list_metric_definitions(
         &self,
-        status: crate::zk_proof_engine::ProofStatus,
-    ) -> Result<Vec<crate::zk_proof_engine::ZkProof>, StorageError> {
+        workspace_id: &str,
+    ) -> Result<Vec<MetricDefinition>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_zk_proofs_by_status(status)
+        guard.list_metric_definitions(workspace_id)
     }
 
-    fn get_zk_proof_statistics(
+    fn delete_metric_definition(&self, metric_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_metric_definition(metric_id)
+    }
+
+    // ZK setup artifacts
+    fn store_zk_setup_artifact(
         &self,
-    ) -> Result<crate::api::zk_proofs::ZkProofStatistics, StorageError> {
+        artifact: &crate::zk_proof_engine::ZkSetupArtifact,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_zk_proof_statistics()
+        guard.store_zk_setup_artifact(artifact)
     }
 
-    fn delete_zk_proof(&self, proof_id: &Uuid) -> Result<(), StorageError> {
+    fn get_zk_setup_artifact(
+        &self,
+        artifact_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::ZkSetupArtifact>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.delete_zk_proof(proof_id)
+        guard.get_zk_setup_artifact(artifact_id)
     }
 
-    fn store_storage_history(&self, history: &ItemStorageHistory) -> Result<(), StorageError> {
+    fn list_zk_setup_artifacts(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ZkSetupArtifact>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_storage_history(history)
+        guard.list_zk_setup_artifacts()
     }
 
-    fn get_storage_history(&self, dfid: &str) -> Result<Option<ItemStorageHistory>, StorageError> {
+    // Data lake garbage collection
+    fn delete_data_lake_entries(&self, entry_ids: &[Uuid]) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_storage_history(dfid)
+        guard.delete_data_lake_entries(entry_ids)
     }
 
-    fn add_storage_record(&self, dfid: &str, record: StorageRecord) -> Result<(), StorageError> {
+    fn delete_receipts(&self, receipt_ids: &[Uuid]) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.add_storage_record(dfid, record)
+        guard.delete_receipts(receipt_ids)
     }
 
-    fn add_cid_to_timeline(
+    fn store_data_lake_gc_report(
         &self,
-        dfid: &str,
-        cid: &str,
-        ipcm_tx: &str,
-        timestamp: i64,
-        network: &str,
+        report: &crate::types::DataLakeGcReport,
     ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.add_cid_to_timeline(dfid, cid, ipcm_tx, timestamp, network)
+        guard.store_data_lake_gc_report(report)
     }
 
-    fn get_item_timeline(&self, dfid: &str) -> Result<Vec<TimelineEntry>, StorageError> {
+    fn list_data_lake_gc_reports(
+        &self,
+    ) -> Result<Vec<crate::types::DataLakeGcReport>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_item_timeline(dfid)
+        guard.list_data_lake_gc_reports()
     }
 
-    fn get_timeline_by_sequence(
-        &self,
-        dfid: &str,
-        sequence: i32,
-    ) -> Result<Option<TimelineEntry>, StorageError> {
+    // Device backups
+    fn store_device_backup(&self, backup: &crate::types::DeviceBackup) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_timeline_by_sequence(dfid, sequence)
+        guard.store_device_backup(backup)
     }
 
-    fn map_event_to_cid(
+    fn get_device_backup(
         &self,
-        event_id: &Uuid,
-        dfid: &str,
-        cid: &str,
-        sequence: i32,
-    ) -> Result<(), StorageError> {
+        backup_id: &Uuid,
+    ) -> Result<Option<crate::types::DeviceBackup>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.map_event_to_cid(event_id, dfid, cid, sequence)
+        guard.get_device_backup(backup_id)
     }
 
-    fn get_event_first_cid(
+    fn list_device_backups(
         &self,
-        event_id: &Uuid,
-    ) -> Result<Option<EventCidMapping>, StorageError> {
+        user_id: &str,
+    ) -> Result<Vec<crate::types::DeviceBackup>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_event_first_cid(event_id)
+        guard.list_device_backups(user_id)
     }
 
-    fn get_events_in_cid(&self, cid: &str) -> Result<Vec<EventCidMapping>, StorageError> {
+    fn delete_device_backup(&self, backup_id: &Uuid) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_events_in_cid(cid)
+        guard.delete_device_backup(backup_id)
     }
 
-    fn update_indexing_progress(
+    // Federation
+    fn store_federation_peer(
         &self,
-        network: &str,
-        last_ledger: i64,
-        confirmed_ledger: i64,
+        peer: &crate::types::FederationPeer,
     ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_indexing_progress(network, last_ledger, confirmed_ledger)
+        guard.store_federation_peer(peer)
     }
 
-    fn get_indexing_progress(
+    fn get_federation_peer(
         &self,
-        network: &str,
-    ) -> Result<Option<IndexingProgress>, StorageError> {
+        peer_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationPeer>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_indexing_progress(network)
+        guard.get_federation_peer(peer_id)
     }
 
-    fn increment_events_indexed(&self, network: &str, count: i64) -> Result<(), StorageError> {
+    fn list_federation_peers(&self) -> Result<Vec<crate::types::FederationPeer>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.increment_events_indexed(network, count)
+        guard.list_federation_peers()
     }
 
-    fn store_circuit_adapter_config(
+    fn delete_federation_peer(&self, peer_id: &Uuid) -> Result<(), StorageError> {
+        let guard = self.lock().unwrap();
+        guard.delete_federation_peer(peer_id)
+    }
+
+    fn store_federation_provenance(
         &self,
-        config: &CircuitAdapterConfig,
+        provenance: &crate::types::FederationProvenance,
     ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_circuit_adapter_config(config)
+        guard.store_federation_provenance(provenance)
+    }
+
+    fn get_federation_provenance(
+        &self,
+        record_key: &str,
+    ) -> Result<Option<crate::types::FederationProvenance>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.get_federation_provenance(record_key)
     }
 
-    fn get_circuit_adapter_config(
+    fn store_federation_conflict(
         &self,
-        circuit_id: &Uuid,
-    ) -> Result<Option<CircuitAdapterConfig>, StorageError> {
+        conflict: &crate::types::FederationConflict,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_circuit_adapter_config(circuit_id)
+        guard.store_federation_conflict(conflict)
     }
 
-    fn update_circuit_adapter_config(
+    fn get_federation_conflict(
         &self,
-        config: &CircuitAdapterConfig,
-    ) -> Result<(), StorageError> {
+        conflict_id: &Uuid,
+    ) -> Result<Option<crate::types::FederationConflict>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_circuit_adapter_config(config)
+        guard.get_federation_conflict(conflict_id)
     }
 
-    fn list_circuit_adapter_configs(&self) -> Result<Vec<CircuitAdapterConfig>, StorageError> {
+    fn list_federation_conflicts(
+        &self,
+    ) -> Result<Vec<crate::types::FederationConflict>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_circuit_adapter_configs()
+        guard.list_federation_conflicts()
     }
 
-    fn store_user_account(&self, user: &UserAccount) -> Result<(), StorageError> {
+    // ZK proof revocations
+    fn store_zk_proof_revocation(
+        &self,
+        revocation: &crate::zk_proof_engine::ProofRevocation,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_user_account(user)
+        guard.store_zk_proof_revocation(revocation)
     }
 
-    fn get_user_account(&self, user_id: &str) -> Result<Option<UserAccount>, StorageError> {
+    fn get_zk_proof_revocation(
+        &self,
+        proof_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofRevocation>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_user_account(user_id)
+        guard.get_zk_proof_revocation(proof_id)
     }
 
-    fn get_user_by_username(&self, username: &str) -> Result<Option<UserAccount>, StorageError> {
+    fn list_zk_proof_revocations(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::ProofRevocation>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_user_by_username(username)
+        guard.list_zk_proof_revocations()
     }
 
-    fn get_user_by_email(&self, email: &str) -> Result<Option<UserAccount>, StorageError> {
+    // Selective disclosures
+    fn store_item_attribute_commitments(
+        &self,
+        commitments: &crate::types::ItemAttributeCommitments,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_user_by_email(email)
+        guard.store_item_attribute_commitments(commitments)
     }
 
-    fn update_user_account(&self, user: &UserAccount) -> Result<(), StorageError> {
+    fn get_item_attribute_commitments(
+        &self,
+        dfid: &str,
+    ) -> Result<Option<crate::types::ItemAttributeCommitments>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_user_account(user)
+        guard.get_item_attribute_commitments(dfid)
     }
 
-    fn list_user_accounts(&self) -> Result<Vec<UserAccount>, StorageError> {
+    fn store_selective_disclosure(
+        &self,
+        disclosure: &crate::types::SelectiveDisclosure,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_user_accounts()
+        guard.store_selective_disclosure(disclosure)
     }
 
-    fn store_password_reset_token(&self, token: &PasswordResetToken) -> Result<(), StorageError> {
+    fn get_selective_disclosure(
+        &self,
+        disclosure_id: &Uuid,
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_password_reset_token(token)
+        guard.get_selective_disclosure(disclosure_id)
     }
 
-    fn get_password_reset_token_by_hash(
+    fn get_selective_disclosure_by_token(
         &self,
         token_hash: &str,
-    ) -> Result<Option<PasswordResetToken>, StorageError> {
+    ) -> Result<Option<crate::types::SelectiveDisclosure>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_password_reset_token_by_hash(token_hash)
+        guard.get_selective_disclosure_by_token(token_hash)
     }
 
-    fn mark_token_as_used(&self, token_id: &str) -> Result<(), StorageError> {
+    fn list_selective_disclosures(
+        &self,
+        created_by: &str,
+    ) -> Result<Vec<crate::types::SelectiveDisclosure>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.mark_token_as_used(token_id)
+        guard.list_selective_disclosures(created_by)
     }
 
-    fn count_recent_reset_requests(
+    // Custom ZK circuit definitions
+    fn store_custom_circuit_definition(
         &self,
-        user_id: &str,
-        since: DateTime<Utc>,
-    ) -> Result<usize, StorageError> {
+        definition: &crate::zk_proof_engine::CustomCircuitDefinition,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.count_recent_reset_requests(user_id, since)
+        guard.store_custom_circuit_definition(definition)
     }
 
-    fn cleanup_expired_tokens(&self) -> Result<usize, StorageError> {
+    fn get_custom_circuit_definition(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Option<crate::zk_proof_engine::CustomCircuitDefinition>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.cleanup_expired_tokens()
+        guard.get_custom_circuit_definition(circuit_id)
     }
 
-    fn delete_user_account(&self, user_id: &str) -> Result<(), StorageError> {
+    fn list_custom_circuit_definitions(
+        &self,
+    ) -> Result<Vec<crate::zk_proof_engine::CustomCircuitDefinition>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.delete_user_account(user_id)
+        guard.list_custom_circuit_definitions()
     }
 
-    fn record_credit_transaction(
+    // ZK proof generation jobs
+    fn store_zk_proof_job(
         &self,
-        transaction: &CreditTransaction,
+        job: &crate::zk_proof_engine::ProofJob,
     ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.record_credit_transaction(transaction)
+        guard.store_zk_proof_job(job)
     }
 
-    fn get_credit_transaction(
+    fn get_zk_proof_job(
         &self,
-        transaction_id: &str,
-    ) -> Result<Option<CreditTransaction>, StorageError> {
+        job_id: &Uuid,
+    ) -> Result<Option<crate::zk_proof_engine::ProofJob>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_credit_transaction(transaction_id)
+        guard.get_zk_proof_job(job_id)
     }
 
-    fn get_credit_transactions(
+    fn list_zk_proof_jobs(&self) -> Result<Vec<crate::zk_proof_engine::ProofJob>, StorageError> {
+        let guard = self.lock().unwrap();
+        guard.list_zk_proof_jobs()
+    }
+
+    // Item search projected to summaries
+    fn search_item_summaries(
         &self,
-        user_id: &str,
-        limit: Option<usize>,
-    ) -> Result<Vec<CreditTransaction>, StorageError> {
+        query: &ItemSearchQuery,
+        after: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<ItemSummary>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_credit_transactions(user_id, limit)
+        guard.search_item_summaries(query, after, limit)
     }
 
-    fn get_credit_transactions_by_operation(
+    // Per-workspace API usage and anomalies
+    fn store_workspace_usage_window(
         &self,
-        operation_type: &str,
-    ) -> Result<Vec<CreditTransaction>, StorageError> {
+        window: &crate::types::WorkspaceUsageWindow,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_credit_transactions_by_operation(operation_type)
+        guard.store_workspace_usage_window(window)
     }
 
-    fn record_admin_action(&self, action: &AdminAction) -> Result<(), StorageError> {
+    fn get_workspace_usage_window(
+        &self,
+        workspace_id: &str,
+        hour_start: DateTime<Utc>,
+    ) -> Result<Option<crate::types::WorkspaceUsageWindow>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.record_admin_action(action)
+        guard.get_workspace_usage_window(workspace_id, hour_start)
     }
 
-    fn get_admin_actions(
+    fn list_workspace_usage_windows(
         &self,
-        admin_id: Option<&str>,
-        limit: Option<usize>,
-    ) -> Result<Vec<AdminAction>, StorageError> {
+        workspace_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::WorkspaceUsageWindow>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_admin_actions(admin_id, limit)
+        guard.list_workspace_usage_windows(workspace_id, from, to)
     }
 
-    fn get_admin_actions_by_type(
+    fn store_usage_anomaly_baseline(
         &self,
-        action_type: &str,
-    ) -> Result<Vec<AdminAction>, StorageError> {
+        baseline: &crate::types::UsageAnomalyBaseline,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_admin_actions_by_type(action_type)
+        guard.store_usage_anomaly_baseline(baseline)
     }
 
-    fn get_system_statistics(&self) -> Result<SystemStatistics, StorageError> {
+    fn get_usage_anomaly_baseline(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::UsageAnomalyBaseline>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_system_statistics()
+        guard.get_usage_anomaly_baseline(workspace_id)
     }
 
-    fn update_system_statistics(&self, stats: &SystemStatistics) -> Result<(), StorageError> {
+    fn store_usage_anomaly(
+        &self,
+        anomaly: &crate::types::UsageAnomaly,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_system_statistics(stats)
+        guard.store_usage_anomaly(anomaly)
     }
 
-    fn store_notification(&self, notification: &Notification) -> Result<(), StorageError> {
+    fn list_usage_anomalies(
+        &self,
+        workspace_id: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::UsageAnomaly>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_notification(notification)
+        guard.list_usage_anomalies(workspace_id, since)
     }
 
-    fn get_notification(
+    // Proof share links
+    fn store_proof_share_link(
         &self,
-        notification_id: &str,
-    ) -> Result<Option<Notification>, StorageError> {
+        link: &crate::types::ProofShareLink,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_notification(notification_id)
+        guard.store_proof_share_link(link)
     }
 
-    fn get_user_notifications(
+    fn get_proof_share_link(
         &self,
-        user_id: &str,
-        since: Option<DateTime<Utc>>,
-        limit: Option<usize>,
-        unread_only: bool,
-    ) -> Result<Vec<Notification>, StorageError> {
+        share_id: &Uuid,
+    ) -> Result<Option<crate::types::ProofShareLink>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_user_notifications(user_id, since, limit, unread_only)
+        guard.get_proof_share_link(share_id)
     }
 
-    fn update_notification(&self, notification: &Notification) -> Result<(), StorageError> {
+    fn record_proof_share_view(
+        &self,
+        share_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<crate::types::ProofShareLink>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_notification(notification)
+        guard.record_proof_share_view(share_id, now)
     }
 
-    fn delete_notification(&self, notification_id: &str) -> Result<(), StorageError> {
+    fn list_proof_share_links(
+        &self,
+        proof_id: &Uuid,
+    ) -> Result<Vec<crate::types::ProofShareLink>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.delete_notification(notification_id)
+        guard.list_proof_share_links(proof_id)
     }
 
-    fn mark_all_notifications_read(&self, user_id: &str) -> Result<usize, StorageError> {
+    // Workspace dedup configs
+    fn store_workspace_dedup_config(
+        &self,
+        config: &crate::types::WorkspaceDedupConfig,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.mark_all_notifications_read(user_id)
+        guard.store_workspace_dedup_config(config)
     }
 
-    fn get_unread_notification_count(&self, user_id: &str) -> Result<usize, StorageError> {
+    fn get_workspace_dedup_config(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceDedupConfig>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_unread_notification_count(user_id)
+        guard.get_workspace_dedup_config(workspace_id)
     }
 
-    fn store_adapter_config(&self, config: &AdapterConfig) -> Result<(), StorageError> {
+    // Sensor time series
+    fn store_sensor_series(&self, series: &crate::types::SensorSeries) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_adapter_config(config)
+        guard.store_sensor_series(series)
     }
 
-    fn get_adapter_config(&self, config_id: &Uuid) -> Result<Option<AdapterConfig>, StorageError> {
+    fn get_sensor_series(
+        &self,
+        dfid: &str,
+        series: &str,
+    ) -> Result<Option<crate::types::SensorSeries>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_adapter_config(config_id)
+        guard.get_sensor_series(dfid, series)
     }
 
-    fn update_adapter_config(&self, config: &AdapterConfig) -> Result<(), StorageError> {
+    fn list_sensor_series(
+        &self,
+        dfid: &str,
+    ) -> Result<Vec<crate::types::SensorSeries>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_adapter_config(config)
+        guard.list_sensor_series(dfid)
     }
 
-    fn delete_adapter_config(&self, config_id: &Uuid) -> Result<(), StorageError> {
+    fn store_sensor_chunk(&self, chunk: &crate::types::SensorChunk) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.delete_adapter_config(config_id)
+        guard.store_sensor_chunk(chunk)
     }
 
-    fn list_adapter_configs(&self) -> Result<Vec<AdapterConfig>, StorageError> {
+    fn get_sensor_chunks(
+        &self,
+        dfid: &str,
+        series: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorChunk>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_adapter_configs()
+        guard.get_sensor_chunks(dfid, series, from, to)
     }
 
-    fn list_active_adapter_configs(&self) -> Result<Vec<AdapterConfig>, StorageError> {
+    fn store_sensor_rollup(&self, rollup: &crate::types::SensorRollup) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_active_adapter_configs()
+        guard.store_sensor_rollup(rollup)
     }
 
-    fn get_adapter_configs_by_type(
+    fn get_sensor_rollups(
         &self,
-        adapter_type: &AdapterType,
-    ) -> Result<Vec<AdapterConfig>, StorageError> {
+        dfid: &str,
+        series: &str,
+        resolution: crate::types::SensorResolution,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<crate::types::SensorRollup>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_adapter_configs_by_type(adapter_type)
+        guard.get_sensor_rollups(dfid, series, resolution, from, to)
     }
 
-    fn get_default_adapter_config(&self) -> Result<Option<AdapterConfig>, StorageError> {
+    fn list_all_sensor_series(&self) -> Result<Vec<crate::types::SensorSeries>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_default_adapter_config()
+        guard.list_all_sensor_series()
     }
 
-    fn set_default_adapter(&self, config_id: &Uuid) -> Result<(), StorageError> {
+    // Workspace buckets
+    fn store_workspace_bucket_config(
+        &self,
+        config: &crate::types::WorkspaceBucketConfig,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.set_default_adapter(config_id)
+        guard.store_workspace_bucket_config(config)
     }
 
-    fn store_adapter_test_result(&self, result: &AdapterTestResult) -> Result<(), StorageError> {
+    fn get_workspace_bucket_config(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<crate::types::WorkspaceBucketConfig>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_adapter_test_result(result)
+        guard.get_workspace_bucket_config(workspace_id)
     }
 
-    fn get_adapter_test_result(
-        &self,
-        config_id: &Uuid,
-    ) -> Result<Option<AdapterTestResult>, StorageError> {
+    fn delete_workspace_bucket_config(&self, workspace_id: &str) -> Result<bool, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_adapter_test_result(config_id)
+        guard.delete_workspace_bucket_config(workspace_id)
     }
 
-    fn store_lid_dfid_mapping(&self, lid: &Uuid, dfid: &str) -> Result<(), StorageError> {
+    // DFID sequence leases
+    fn lease_dfid_sequence_range(&self, day: &str, count: u64) -> Result<u64, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_lid_dfid_mapping(lid, dfid)
+        guard.lease_dfid_sequence_range(day, count)
     }
 
-    fn get_dfid_by_lid(&self, lid: &Uuid) -> Result<Option<String>, StorageError> {
+    // Re-verification jobs and merge proposals
+    fn store_reverification_job(&self, job: &ReverificationJob) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_dfid_by_lid(lid)
+        guard.store_reverification_job(job)
     }
 
-    fn get_dfid_by_canonical(
+    fn get_reverification_job(
         &self,
-        namespace: &str,
-        registry: &str,
-        value: &str,
-    ) -> Result<Option<String>, StorageError> {
+        job_id: &Uuid,
+    ) -> Result<Option<ReverificationJob>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_dfid_by_canonical(namespace, registry, value)
+        guard.get_reverification_job(job_id)
     }
 
-    fn store_fingerprint_mapping(
-        &self,
-        fingerprint: &str,
-        dfid: &str,
-        circuit_id: &Uuid,
-    ) -> Result<(), StorageError> {
+    fn list_reverification_jobs(&self) -> Result<Vec<ReverificationJob>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_fingerprint_mapping(fingerprint, dfid, circuit_id)
+        guard.list_reverification_jobs()
     }
 
-    fn get_dfid_by_fingerprint(
-        &self,
-        fingerprint: &str,
-        circuit_id: &Uuid,
-    ) -> Result<Option<String>, StorageError> {
+    fn store_merge_proposal(&self, proposal: &MergeProposal) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_dfid_by_fingerprint(fingerprint, circuit_id)
+        guard.store_merge_proposal(proposal)
     }
 
-    fn store_enhanced_identifier_mapping(
+    fn get_merge_proposal(
         &self,
-        identifier: &EnhancedIdentifier,
-        dfid: &str,
-    ) -> Result<(), StorageError> {
+        proposal_id: &Uuid,
+    ) -> Result<Option<MergeProposal>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_enhanced_identifier_mapping(identifier, dfid)
+        guard.get_merge_proposal(proposal_id)
     }
 
-    fn store_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), StorageError> {
+    fn list_merge_proposals(&self) -> Result<Vec<MergeProposal>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_webhook_delivery(delivery)
+        guard.list_merge_proposals()
     }
 
-    fn get_webhook_delivery(
-        &self,
-        delivery_id: &Uuid,
-    ) -> Result<Option<WebhookDelivery>, StorageError> {
+    // Runbook hooks
+    fn store_runbook_hook(&self, hook: &RunbookHook) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_webhook_delivery(delivery_id)
+        guard.store_runbook_hook(hook)
     }
 
-    fn get_webhook_deliveries_by_circuit(
-        &self,
-        circuit_id: &Uuid,
-        limit: Option<usize>,
-    ) -> Result<Vec<WebhookDelivery>, StorageError> {
+    fn get_runbook_hook(&self, hook_id: &Uuid) -> Result<Option<RunbookHook>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_webhook_deliveries_by_circuit(circuit_id, limit)
+        guard.get_runbook_hook(hook_id)
     }
 
-    fn get_webhook_deliveries_by_webhook(
-        &self,
-        webhook_id: &Uuid,
-        limit: Option<usize>,
-    ) -> Result<Vec<WebhookDelivery>, StorageError> {
+    fn list_runbook_hooks(&self) -> Result<Vec<RunbookHook>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_webhook_deliveries_by_webhook(webhook_id, limit)
+        guard.list_runbook_hooks()
     }
 
-    fn store_user_activity(&self, activity: &UserActivity) -> Result<(), StorageError> {
+    fn delete_runbook_hook(&self, hook_id: &Uuid) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_user_activity(activity)
+        guard.delete_runbook_hook(hook_id)
     }
 
-    fn list_user_activities(&self) -> Result<Vec<UserActivity>, StorageError> {
+    // Workspace verification rules
+    fn store_workspace_verification_rules(
+        &self,
+        rules: &WorkspaceVerificationRules,
+    ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.list_user_activities()
+        guard.store_workspace_verification_rules(rules)
     }
 
-    fn clear_user_activities(&self) -> Result<(), StorageError> {
+    fn get_workspace_verification_rules(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<WorkspaceVerificationRules>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.clear_user_activities()
+        guard.get_workspace_verification_rules(workspace_id)
     }
 
-    // State Snapshot operations
-    fn store_snapshot(
+    // Dedup sharing agreements
+    fn store_dedup_sharing_agreement(
         &self,
-        snapshot: &crate::snapshot_types::StateSnapshot,
+        agreement: &DedupSharingAgreement,
     ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.store_snapshot(snapshot)
+        guard.store_dedup_sharing_agreement(agreement)
     }
 
-    fn get_snapshot(
+    fn get_dedup_sharing_agreement(
         &self,
-        snapshot_id: &str,
-    ) -> Result<Option<crate::snapshot_types::StateSnapshot>, StorageError> {
+        agreement_id: &Uuid,
+    ) -> Result<Option<DedupSharingAgreement>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_snapshot(snapshot_id)
+        guard.get_dedup_sharing_agreement(agreement_id)
     }
 
-    fn get_snapshots_for_entity(
+    fn list_dedup_sharing_agreements(
         &self,
-        entity_type: crate::snapshot_types::SnapshotEntityType,
-        entity_id: &str,
-    ) -> Result<Vec<crate::snapshot_types::StateSnapshot>, StorageError> {
+        circuit_id: &Uuid,
+    ) -> Result<Vec<DedupSharingAgreement>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_snapshots_for_entity(entity_type, entity_id)
+        guard.list_dedup_sharing_agreements(circuit_id)
     }
 
-    fn get_latest_snapshot(
+    // Paginated CID timeline
+    fn get_item_timeline_page(
         &self,
-        entity_type: crate::snapshot_types::SnapshotEntityType,
-        entity_id: &str,
-    ) -> Result<Option<crate::snapshot_types::StateSnapshot>, StorageError> {
+        dfid: &str,
+        query: &TimelineQuery,
+        after_sequence: Option<i32>,
+        limit: usize,
+    ) -> Result<Vec<TimelineEntry>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_latest_snapshot(entity_type, entity_id)
+        guard.get_item_timeline_page(dfid, query, after_sequence, limit)
     }
 
-    fn update_snapshot(
+    // Circuit membership reconciliation
+    fn store_membership_reconciliation_report(
         &self,
-        snapshot: &crate::snapshot_types::StateSnapshot,
+        report: &MembershipReconciliationReport,
     ) -> Result<(), StorageError> {
         let guard = self.lock().unwrap();
-        guard.update_snapshot(snapshot)
+        guard.store_membership_reconciliation_report(report)
     }
 
-    fn get_snapshot_count(
+    fn list_membership_reconciliation_reports(
         &self,
-        entity_type: crate::snapshot_types::SnapshotEntityType,
-        entity_id: &str,
-    ) -> Result<u64, StorageError> {
+    ) -> Result<Vec<MembershipReconciliationReport>, StorageError> {
         let guard = self.lock().unwrap();
-        guard.get_snapshot_count(entity_type, entity_id)
+        guard.list_membership_reconciliation_reports()
     }
 }
 
@@ -6305,6 +13198,7 @@ impl InMemoryStorage {
     /// Helper for tests to seed an item directly
     pub fn seed_item(&self, item: Item) {
         self.with_state(|s| {
+            s.item_search_index.index(&item);
             s.items.insert(item.dfid.clone(), item);
         });
     }
@@ -6323,10 +13217,17 @@ impl InMemoryStorage {
         });
     }
 
+    /// Helper for tests to make event writes fail once `events` more have
+    /// been stored; a batch that does not fit fails whole
+    pub fn fail_event_writes_after(&self, events: usize) {
+        self.with_state(|s| s.event_write_budget = Some(events));
+    }
+
     /// Helper for tests to clear all data
     pub fn clear_all(&self) {
         self.with_state(|s| {
             s.items.clear();
+            s.item_search_index.clear();
             s.circuits.clear();
             s.events.clear();
             s.receipts.clear();
@@ -6361,6 +13262,7 @@ impl InMemoryStorage {
             s.user_accounts_by_username.clear();
             s.user_accounts_by_email.clear();
             s.circuit_adapter_configs.clear();
+            s.announcements.clear();
             s.system_statistics = None;
         });
     }
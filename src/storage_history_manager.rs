@@ -38,7 +38,10 @@ impl<S: StorageBackend> StorageHistoryManager<S> {
             AdapterType::PolygonArweave => StorageLocation::Local {
                 id: storage_id.clone(),
             }, // Implementation pending
-            AdapterType::StellarTestnetIpfs | AdapterType::StellarMainnetIpfs => {
+            AdapterType::StellarTestnetIpfs
+            | AdapterType::StellarMainnetIpfs
+            | AdapterType::StellarTestnetLocal
+            | AdapterType::StellarMainnetLocal => {
                 StorageLocation::Stellar {
                     transaction_id: storage_id.clone(),
                     contract_address: "placeholder".to_string(), // Implementation pending
@@ -90,7 +93,10 @@ impl<S: StorageBackend> StorageHistoryManager<S> {
             AdapterType::PolygonArweave => StorageLocation::Local {
                 id: storage_id.clone(),
             }, // Implementation pending
-            AdapterType::StellarTestnetIpfs | AdapterType::StellarMainnetIpfs => {
+            AdapterType::StellarTestnetIpfs
+            | AdapterType::StellarMainnetIpfs
+            | AdapterType::StellarTestnetLocal
+            | AdapterType::StellarMainnetLocal => {
                 StorageLocation::Stellar {
                     transaction_id: storage_id.clone(),
                     contract_address: "placeholder".to_string(), // Implementation pending
@@ -192,7 +198,10 @@ impl<S: StorageBackend> StorageHistoryManager<S> {
                 AdapterType::PolygonArweave => StorageLocation::Arweave {
                     transaction_id: format!("migrated_{dfid}"),
                 },
-                AdapterType::StellarTestnetIpfs | AdapterType::StellarMainnetIpfs => {
+                AdapterType::StellarTestnetIpfs
+                | AdapterType::StellarMainnetIpfs
+                | AdapterType::StellarTestnetLocal
+                | AdapterType::StellarMainnetLocal => {
                     StorageLocation::Stellar {
                         transaction_id: format!("migrated_{dfid}"),
                         contract_address: "placeholder".to_string(), // Implementation pending
@@ -1,7 +1,9 @@
 use crate::adapters::base::StorageLocation;
+use crate::hashing::{self, HashAlgorithm};
 pub use crate::identifier_types::Identifier;
 use crate::identifier_types::{CircuitAliasConfig, ExternalAlias};
-use chrono::{DateTime, Utc};
+use crate::merkle_tree::MerkleProof;
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -13,6 +15,73 @@ pub struct Receipt {
     pub timestamp: DateTime<Utc>,
     pub data_size: usize,
     pub identifiers: Vec<Identifier>,
+    #[serde(default)]
+    pub priority: IngestionPriority,
+    /// When the underlying fact happened, if it predates `timestamp` (backfills)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub occurred_at: Option<DateTime<Utc>>,
+}
+
+impl Receipt {
+    pub fn time_on(&self, axis: TimeAxis) -> DateTime<Utc> {
+        match axis {
+            TimeAxis::Recorded => self.timestamp,
+            TimeAxis::Occurred => self.occurred_at.unwrap_or(self.timestamp),
+        }
+    }
+}
+
+/// Which clock a time-based query, report or retention rule operates on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeAxis {
+    /// When the platform recorded the data
+    #[default]
+    Recorded,
+    /// When the fact actually happened; falls back to the recorded time when not declared
+    Occurred,
+}
+
+/// How far ahead of the recording clock a declared occurrence may be (device clock skew)
+pub const MAX_OCCURRED_AT_FUTURE_SKEW_SECS: i64 = 300;
+/// Earliest occurrence year accepted for historical records
+pub const MIN_OCCURRED_AT_YEAR: i32 = 1900;
+
+/// Reject declared occurrence times that cannot be genuine: meaningfully in the
+/// future relative to `recorded_at`, or before `MIN_OCCURRED_AT_YEAR`.
+pub fn validate_occurred_at(
+    occurred_at: DateTime<Utc>,
+    recorded_at: DateTime<Utc>,
+) -> Result<(), String> {
+    if occurred_at > recorded_at + Duration::seconds(MAX_OCCURRED_AT_FUTURE_SKEW_SECS) {
+        return Err(format!(
+            "occurred_at {} is in the future (recorded at {})",
+            occurred_at.to_rfc3339(),
+            recorded_at.to_rfc3339()
+        ));
+    }
+    let earliest = Utc
+        .with_ymd_and_hms(MIN_OCCURRED_AT_YEAR, 1, 1, 0, 0, 0)
+        .single()
+        .unwrap_or(DateTime::<Utc>::MIN_UTC);
+    if occurred_at < earliest {
+        return Err(format!(
+            "occurred_at {} is before {MIN_OCCURRED_AT_YEAR}",
+            occurred_at.to_rfc3339()
+        ));
+    }
+    Ok(())
+}
+
+/// Priority class of submitted data, honored by verification and webhook fan-out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestionPriority {
+    /// Live traceability events, e.g. sensor readings
+    #[default]
+    Realtime,
+    /// Historical imports and backfills; processed only when no realtime work is waiting
+    Bulk,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +95,23 @@ pub struct DataLakeEntry {
     pub status: ProcessingStatus,
     pub linked_dfid: Option<String>,
     pub error_message: Option<String>,
+    #[serde(default)]
+    pub priority: IngestionPriority,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub occurred_at: Option<DateTime<Utc>>,
+    /// Where the submission came from, as declared by the submitter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Workspace of the submitter, whose verification rules apply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<String>,
+}
+
+/// Who submitted data and from where; decides which verification rules apply
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubmissionOrigin {
+    pub source: Option<String>,
+    pub workspace_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -37,6 +123,90 @@ pub enum ProcessingStatus {
     Conflicted,
 }
 
+/// What garbage collection does with orphaned data lake entries and receipts
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DataLakeGcAction {
+    /// Upload them to IPFS as one JSON document, then remove them
+    #[default]
+    Archive,
+    Delete,
+}
+
+/// Outcome of one garbage collection run over the data lake
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DataLakeGcReport {
+    pub run_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+    /// Admin who ran it, or None for the scheduled run
+    pub triggered_by: Option<String>,
+    pub action: DataLakeGcAction,
+    pub retention_days: u32,
+    /// A dry run only reports what would be removed
+    pub dry_run: bool,
+    pub entries_scanned: usize,
+    pub receipts_scanned: usize,
+    pub entries_removed: usize,
+    pub receipts_removed: usize,
+    /// Serialized size of the removed entries and receipts
+    pub reclaimed_bytes: u64,
+    /// Size of the raw data the removed entries stood for
+    pub source_data_bytes: u64,
+    /// CID of the archive document when `action` is `Archive`
+    pub archive_cid: Option<String>,
+}
+
+/// What the membership reconciler does about circuit items and push events
+/// that disagree
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MembershipRepairPolicy {
+    #[default]
+    ReportOnly,
+    /// Add or remove circuit items to match the event log
+    TrustEvents,
+    /// Record the push or removal events the log is missing
+    TrustMembership,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MembershipDiscrepancyKind {
+    /// The log's last word is a push but the circuit has no such item
+    PushEventWithoutMember,
+    /// The circuit has the item but the log never pushed it or took it out since
+    MemberWithoutPushEvent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MembershipDiscrepancy {
+    pub circuit_id: Uuid,
+    pub dfid: String,
+    pub kind: MembershipDiscrepancyKind,
+    /// The item's last effective circuit event, if any
+    pub event_id: Option<Uuid>,
+    pub repaired: bool,
+}
+
+/// Outcome of one comparison of circuit items with the event log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MembershipReconciliationReport {
+    pub run_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+    /// Admin who ran it, or None for the scheduled run
+    pub triggered_by: Option<String>,
+    pub policy: MembershipRepairPolicy,
+    /// A dry run only reports discrepancies
+    pub dry_run: bool,
+    pub circuits_scanned: usize,
+    pub memberships_scanned: usize,
+    pub events_scanned: usize,
+    pub discrepancies: Vec<MembershipDiscrepancy>,
+    pub repaired: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Item {
     pub dfid: String,
@@ -51,6 +221,11 @@ pub struct Item {
     pub source_entries: Vec<Uuid>,
     pub confidence_score: f64,
     pub status: ItemStatus,
+    /// Earliest and latest declared occurrence across the item's source data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_occurred_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_occurred_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -95,6 +270,9 @@ pub struct ConflictResolution {
     pub resolved_dfid: Option<String>,
     pub resolution_timestamp: Option<DateTime<Utc>>,
     pub requires_manual_review: bool,
+    /// Near-identical identifier values behind the conflict, most confident first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fuzzy_matches: Vec<FuzzyIdentifierMatch>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,6 +288,65 @@ pub enum ResolutionStrategy {
     MatchBest,
 }
 
+/// Re-runs matching for what an identifier mapping correction touched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReverificationJob {
+    pub job_id: Uuid,
+    /// The identifier whose mapping was corrected
+    pub identifier: Identifier,
+    pub corrected_dfid: String,
+    /// DFIDs the identifier was mapped to before the correction
+    pub previous_dfids: Vec<String>,
+    pub requested_by: String,
+    pub status: ReverificationStatus,
+    pub items_checked: usize,
+    pub receipts_checked: usize,
+    pub proposals_created: usize,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReverificationStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// A merge re-verification suggests; nothing changes until it is accepted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeProposal {
+    pub proposal_id: Uuid,
+    pub job_id: Uuid,
+    /// Item the corrected mappings point at; it survives the merge
+    pub primary_dfid: String,
+    pub secondary_dfid: String,
+    /// Identifiers of the secondary item now mapped to the primary
+    pub shared_identifiers: Vec<Identifier>,
+    /// Receipts verified into the secondary item that belong to the primary
+    #[serde(default)]
+    pub receipt_ids: Vec<Uuid>,
+    /// Share of the secondary item's identifiers mapped to the primary
+    pub confidence: f64,
+    pub status: MergeProposalStatus,
+    pub created_at: DateTime<Utc>,
+    pub decided_by: Option<String>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeProposalStatus {
+    Open,
+    Accepted,
+    Rejected,
+    /// One of its items was merged away before a decision
+    Superseded,
+}
+
 impl DataLakeEntry {
     pub fn new(
         receipt_id: Uuid,
@@ -127,9 +364,36 @@ impl DataLakeEntry {
             status: ProcessingStatus::Pending,
             linked_dfid: None,
             error_message: None,
+            priority: IngestionPriority::Realtime,
+            occurred_at: None,
+            source: None,
+            workspace_id: None,
+        }
+    }
+
+    pub fn with_origin(mut self, origin: SubmissionOrigin) -> Self {
+        self.source = origin.source;
+        self.workspace_id = origin.workspace_id;
+        self
+    }
+
+    pub fn origin(&self) -> SubmissionOrigin {
+        SubmissionOrigin {
+            source: self.source.clone(),
+            workspace_id: self.workspace_id.clone(),
         }
     }
 
+    pub fn with_priority(mut self, priority: IngestionPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_occurred_at(mut self, occurred_at: Option<DateTime<Utc>>) -> Self {
+        self.occurred_at = occurred_at;
+        self
+    }
+
     pub fn mark_processing(&mut self) {
         self.status = ProcessingStatus::Processing;
     }
@@ -164,6 +428,8 @@ impl Item {
             source_entries: vec![source_entry],
             confidence_score: 1.0,
             status: ItemStatus::Active,
+            first_occurred_at: None,
+            last_occurred_at: None,
         }
     }
 
@@ -181,6 +447,8 @@ impl Item {
             source_entries: vec![source_entry],
             confidence_score: 1.0,
             status: ItemStatus::Active,
+            first_occurred_at: None,
+            last_occurred_at: None,
         }
     }
 
@@ -190,6 +458,92 @@ impl Item {
         self.last_modified = Utc::now();
     }
 
+    /// Enrich field by field under `policy`; without one every incoming value
+    /// wins, as in `enrich`. Appending skips values the array already holds, so a
+    /// resubmitted receipt does not duplicate them.
+    pub fn enrich_with_policy(
+        &mut self,
+        data: HashMap<String, serde_json::Value>,
+        source_entry: Uuid,
+        policy: Option<&EnrichmentPolicy>,
+    ) -> MergeReport {
+        let mut incoming: Vec<(String, serde_json::Value)> = data.into_iter().collect();
+        incoming.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut fields = Vec::with_capacity(incoming.len());
+        for (field, value) in incoming {
+            let merge_policy = policy.map_or(MergePolicy::LastWins, |p| p.policy_for(&field));
+            let mut rejected_value = None;
+            let outcome = match self.enriched_data.get_mut(&field) {
+                None => {
+                    let value = match merge_policy {
+                        MergePolicy::Append => serde_json::Value::Array(vec![value]),
+                        _ => value,
+                    };
+                    self.enriched_data.insert(field.clone(), value);
+                    FieldMergeOutcome::Added
+                }
+                Some(existing) if *existing == value => FieldMergeOutcome::Kept,
+                Some(existing) => match merge_policy {
+                    MergePolicy::FirstWins => FieldMergeOutcome::Kept,
+                    MergePolicy::LastWins => {
+                        *existing = value;
+                        FieldMergeOutcome::Replaced
+                    }
+                    MergePolicy::Append => {
+                        if !existing.is_array() {
+                            *existing = serde_json::Value::Array(vec![existing.take()]);
+                        }
+                        let values = existing.as_array_mut().expect("converted to an array");
+                        if values.contains(&value) {
+                            FieldMergeOutcome::Kept
+                        } else {
+                            values.push(value);
+                            FieldMergeOutcome::Appended
+                        }
+                    }
+                    MergePolicy::RejectConflict => {
+                        rejected_value = Some(value);
+                        FieldMergeOutcome::Rejected
+                    }
+                },
+            };
+            fields.push(FieldMerge {
+                field,
+                policy: merge_policy,
+                outcome,
+                rejected_value,
+            });
+        }
+
+        self.source_entries.push(source_entry);
+        self.last_modified = Utc::now();
+        MergeReport {
+            workspace_id: policy.map(|p| p.workspace_id.clone()),
+            fields,
+        }
+    }
+
+    /// Widen the item's occurrence window to include `occurred_at`
+    pub fn record_occurrence(&mut self, occurred_at: DateTime<Utc>) {
+        if self
+            .first_occurred_at
+            .is_none_or(|first| occurred_at < first)
+        {
+            self.first_occurred_at = Some(occurred_at);
+        }
+        if self.last_occurred_at.is_none_or(|last| occurred_at > last) {
+            self.last_occurred_at = Some(occurred_at);
+        }
+    }
+
+    pub fn time_on(&self, axis: TimeAxis) -> DateTime<Utc> {
+        match axis {
+            TimeAxis::Recorded => self.creation_timestamp,
+            TimeAxis::Occurred => self.first_occurred_at.unwrap_or(self.creation_timestamp),
+        }
+    }
+
     pub fn add_identifiers(&mut self, identifiers: Vec<Identifier>) {
         for identifier in identifiers {
             if !self.identifiers.contains(&identifier) {
@@ -231,7 +585,28 @@ impl ConflictResolution {
             resolved_dfid: None,
             resolution_timestamp: None,
             requires_manual_review: true,
+            fuzzy_matches: Vec::new(),
+        }
+    }
+
+    /// A conflict raised because incoming identifiers only nearly match existing ones
+    pub fn from_fuzzy_matches(
+        incoming: Vec<Identifier>,
+        matches: Vec<FuzzyIdentifierMatch>,
+    ) -> Self {
+        let mut dfids: Vec<String> = Vec::new();
+        let mut identifiers = incoming;
+        for fuzzy in &matches {
+            if !dfids.contains(&fuzzy.dfid) {
+                dfids.push(fuzzy.dfid.clone());
+            }
+            if !identifiers.contains(&fuzzy.matched_identifier) {
+                identifiers.push(fuzzy.matched_identifier.clone());
+            }
         }
+        let mut conflict = Self::new(identifiers, dfids);
+        conflict.fuzzy_matches = matches;
+        conflict
     }
 
     pub fn resolve(&mut self, strategy: ResolutionStrategy, dfid: String) {
@@ -262,12 +637,92 @@ pub struct Event {
     /// Circuit ID where this event was pushed (None if local or created directly)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pushed_to_circuit: Option<Uuid>,
-    /// Snapshot ID (BLAKE3 hash) if this event triggered a state snapshot
+    /// Snapshot ID (BLAKE3 hash) if this event triggered a state snapshot or
+    /// was folded into an event compaction snapshot
     #[serde(skip_serializing_if = "Option::is_none")]
     pub snapshot_id: Option<String>,
     /// IPFS CID of the snapshot (populated after IPFS upload)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub snapshot_cid: Option<String>,
+    /// When the event actually happened, if declared; `timestamp` is when it was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub occurred_at: Option<DateTime<Utc>>,
+    /// Hex ed25519 signature of the asserting user over `signing_hash()`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Hex ed25519 public key the signature verifies with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer_public_key: Option<String>,
+    /// Files attached to the event, stored on IPFS
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<EventAttachment>,
+    /// Event that directly led to this one, e.g. the item state a push carried
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caused_by: Option<Uuid>,
+    /// Shared by every event of one operation (a circuit push, a receipt's verification)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<Uuid>,
+}
+
+/// Where an event sits in the causal graph: the event it follows from and
+/// the operation it was recorded by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EventCausality {
+    pub caused_by: Option<Uuid>,
+    pub correlation_id: Option<Uuid>,
+}
+
+impl EventCausality {
+    /// An event starting an operation
+    pub fn root(correlation_id: Uuid) -> Self {
+        Self {
+            caused_by: None,
+            correlation_id: Some(correlation_id),
+        }
+    }
+
+    /// An event of an operation that follows from `cause`
+    pub fn following(cause: Option<Uuid>, correlation_id: Uuid) -> Self {
+        Self {
+            caused_by: cause,
+            correlation_id: Some(correlation_id),
+        }
+    }
+}
+
+/// An event with what led to it and what followed from it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventChain {
+    pub event: Event,
+    /// Causes of the event, the root cause first
+    pub ancestors: Vec<Event>,
+    /// Events following from it directly or transitively, oldest first
+    pub descendants: Vec<Event>,
+    /// Other events recorded by the same operation, oldest first
+    pub correlated: Vec<Event>,
+    /// The walk stopped at the depth limit or at a cause missing from storage
+    pub truncated: bool,
+}
+
+/// A file (lab certificate, photo) attached to an event. The content lives on
+/// IPFS under `cid`, or in the workspace's own bucket under `bucket_object`;
+/// `content_hash` lets anyone check what they fetched.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventAttachment {
+    pub attachment_id: Uuid,
+    /// Empty for attachments kept in a workspace bucket
+    #[serde(default)]
+    pub cid: String,
+    /// Content hash in `hashing` stored form (`sha256:<hex>` for bucket objects)
+    pub content_hash: String,
+    pub mime_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    pub size: u64,
+    pub uploaded_by: String,
+    pub uploaded_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bucket_object: Option<BucketObjectRef>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -280,6 +735,57 @@ pub enum EventType {
     PulledFromCircuit,
     Updated,
     StatusChanged,
+    /// A document's hash was notarized for the item
+    DocumentAnchored,
+    /// The item was pushed from this circuit into another; it stays here too
+    ForwardedToCircuit,
+    /// The item's time in a circuit ran out and it was removed
+    RemovedFromCircuit,
+    /// Personal data past retention was masked on the item and its events
+    Anonymized,
+    /// Domain event registered for a circuit, e.g. "Harvested"
+    Custom(String),
+}
+
+impl EventType {
+    /// Types every deployment knows; custom types may not reuse their names
+    pub const BUILT_IN: [EventType; 12] = [
+        EventType::Created,
+        EventType::Enriched,
+        EventType::Merged,
+        EventType::Split,
+        EventType::PushedToCircuit,
+        EventType::PulledFromCircuit,
+        EventType::Updated,
+        EventType::StatusChanged,
+        EventType::DocumentAnchored,
+        EventType::ForwardedToCircuit,
+        EventType::RemovedFromCircuit,
+        EventType::Anonymized,
+    ];
+
+    /// Parse a name as written by `Display`; anything that is not a built-in
+    /// type is taken as custom
+    pub fn from_name(name: &str) -> EventType {
+        Self::BUILT_IN
+            .iter()
+            .find(|event_type| event_type.to_string() == name)
+            .cloned()
+            .unwrap_or_else(|| EventType::Custom(name.to_string()))
+    }
+
+    pub fn is_custom(&self) -> bool {
+        matches!(self, EventType::Custom(_))
+    }
+}
+
+impl std::fmt::Display for EventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EventType::Custom(name) => write!(f, "{name}"),
+            built_in => write!(f, "{built_in:?}"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -290,7 +796,7 @@ pub enum EventVisibility {
     Direct,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum QualitySeverity {
     Low,
     Medium,
@@ -353,6 +859,11 @@ pub struct PendingItem {
     pub manual_review_required: bool,
     pub suggested_actions: Vec<SuggestedAction>,
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Reviewer working the item in the review queue, and since when
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claimed_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claimed_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -393,6 +904,10 @@ pub enum PendingReason {
         external_system: String,
         conflict_type: String,
     },
+    /// A workspace verification rule holds this source's submissions for review
+    RoutedByRule {
+        source: String,
+    },
 }
 
 impl PendingEvent {
@@ -441,6 +956,8 @@ impl PendingItem {
             manual_review_required,
             suggested_actions,
             metadata: HashMap::new(),
+            claimed_by: None,
+            claimed_at: None,
         }
     }
 
@@ -481,6 +998,7 @@ impl PendingItem {
             PendingReason::IdentifierMappingConflict { .. } => PendingPriority::High,
             PendingReason::DuplicateDetectionAmbiguous { .. } => PendingPriority::Normal,
             PendingReason::CrossSystemConflict { .. } => PendingPriority::High,
+            PendingReason::RoutedByRule { .. } => PendingPriority::Normal,
             PendingReason::ValidationError(_) => PendingPriority::Normal,
             PendingReason::InvalidIdentifiers(_) => PendingPriority::Normal,
             PendingReason::ProcessingError(_) => PendingPriority::Low,
@@ -543,6 +1061,7 @@ impl PendingReason {
             PendingReason::IdentifierMappingConflict { .. } => true,
             PendingReason::DuplicateDetectionAmbiguous { .. } => true,
             PendingReason::CrossSystemConflict { .. } => true,
+            PendingReason::RoutedByRule { .. } => true,
             _ => false,
         }
     }
@@ -591,6 +1110,9 @@ impl PendingReason {
             } => {
                 format!("Conflict with external system {external_system}: {conflict_type}")
             }
+            PendingReason::RoutedByRule { source } => {
+                format!("Verification rules send submissions from {source} to manual review")
+            }
         }
     }
 }
@@ -650,6 +1172,29 @@ pub struct Circuit {
     pub public_settings: Option<PublicSettings>,
     pub adapter_config: Option<CircuitAdapterConfig>,
     pub post_action_settings: Option<PostActionSettings>,
+    /// Set while the circuit is archived
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub archived_by: Option<String>,
+    /// Circuit this one belongs under, e.g. the national body of a regional co-op
+    #[serde(default)]
+    pub parent_circuit_id: Option<Uuid>,
+    #[serde(default)]
+    pub parent_access: ParentAccess,
+    /// Handover the owner started and the new owner has yet to accept
+    #[serde(default)]
+    pub pending_ownership_transfer: Option<OwnershipTransfer>,
+}
+
+/// Ownership offered to another member of the circuit; it changes hands only
+/// once that member accepts, before `expires_at`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OwnershipTransfer {
+    pub new_owner_id: String,
+    pub initiated_by: String,
+    pub initiated_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -672,6 +1217,15 @@ pub struct PublicSettings {
     pub data_quality_rules: Option<String>,
     pub export_permissions: Option<ExportPermissionLevel>,
     pub public_since: Option<DateTime<Utc>>, // Timestamp when circuit became public
+    /// Opt in to the public circuit directory
+    #[serde(default)]
+    pub listed_in_directory: bool,
+    /// Where the circuit operates, shown and searchable in the directory
+    #[serde(default)]
+    pub region: Option<String>,
+    /// Kinds of items the circuit traces, e.g. "cattle" or "soy"
+    #[serde(default)]
+    pub item_categories: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -694,6 +1248,17 @@ pub struct JoinRequest {
     pub requested_at: DateTime<Utc>,
     pub message: Option<String>,
     pub status: JoinRequestStatus,
+    /// Member who approved or denied the request
+    #[serde(default)]
+    pub decided_by: Option<String>,
+    #[serde(default)]
+    pub decided_at: Option<DateTime<Utc>>,
+    /// Role the requester joined with
+    #[serde(default)]
+    pub granted_role: Option<MemberRole>,
+    /// Why the request was denied, shown to the requester
+    #[serde(default)]
+    pub decision_reason: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -730,6 +1295,64 @@ pub struct PublicCircuitInfo {
     pub created_at: DateTime<Utc>,
 }
 
+/// How someone found in the directory gets into a circuit
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DirectoryJoinMode {
+    /// Join requests are approved automatically
+    Open,
+    /// Join requests wait for a circuit admin
+    Request,
+    /// Joining needs the circuit's access password
+    Password,
+}
+
+/// A circuit as listed in the public directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitDirectoryEntry {
+    pub circuit_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub tagline: Option<String>,
+    pub logo_url: Option<String>,
+    pub region: Option<String>,
+    pub item_categories: Vec<String>,
+    pub member_count: usize,
+    pub join_mode: DirectoryJoinMode,
+    pub public_since: Option<DateTime<Utc>>,
+}
+
+impl CircuitDirectoryEntry {
+    /// Case-insensitive match on free text (name, description, tagline),
+    /// region and item category; `None` filters match everything
+    pub fn matches(
+        &self,
+        text: Option<&str>,
+        region: Option<&str>,
+        category: Option<&str>,
+    ) -> bool {
+        let contains = |field: Option<&str>, needle: &str| {
+            field.is_some_and(|field| field.to_lowercase().contains(needle))
+        };
+        let text_matches = text.map(str::to_lowercase).is_none_or(|needle| {
+            contains(Some(&self.name), &needle)
+                || contains(self.description.as_deref(), &needle)
+                || contains(self.tagline.as_deref(), &needle)
+        });
+        let region_matches = region.is_none_or(|region| {
+            self.region
+                .as_deref()
+                .is_some_and(|own| own.eq_ignore_ascii_case(region))
+        });
+        let category_matches = category.is_none_or(|category| {
+            self.item_categories
+                .iter()
+                .any(|own| own.eq_ignore_ascii_case(category))
+        });
+        text_matches && region_matches && category_matches
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CircuitMember {
     pub member_id: String,
@@ -765,6 +1388,9 @@ pub struct CircuitPermissions {
     pub require_approval_for_push: bool,
     pub require_approval_for_pull: bool,
     pub allow_public_visibility: bool,
+    /// Attestations a member must hold before pushing to the circuit
+    #[serde(default)]
+    pub required_attestations: Vec<RequiredAttestation>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -774,6 +1400,45 @@ pub enum CircuitStatus {
     Archived,
 }
 
+/// What members of a circuit's ancestors get in it
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ParentAccess {
+    /// Ancestors' members have no access and the circuit stays out of their roll-ups
+    None,
+    /// Ancestors' members can read the circuit and its items roll up into theirs
+    #[default]
+    Read,
+}
+
+impl ParentAccess {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParentAccess::None => "none",
+            ParentAccess::Read => "read",
+        }
+    }
+}
+
+/// A member of a circuit, held directly or through an ancestor
+#[derive(Debug, Clone, Serialize)]
+pub struct EffectiveMember {
+    pub member_id: String,
+    pub role: MemberRole,
+    /// Circuit the membership is held in
+    pub source_circuit_id: Uuid,
+    pub inherited: bool,
+}
+
+/// An item of a circuit or of one of its descendants
+#[derive(Debug, Clone, Serialize)]
+pub struct RolledUpItem {
+    #[serde(flatten)]
+    pub item: CircuitItem,
+    /// Levels below the queried circuit; 0 for its own items
+    pub depth: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CircuitOperation {
     pub operation_id: Uuid,
@@ -853,6 +1518,12 @@ impl Event {
             pushed_to_circuit: None,
             snapshot_id: None,
             snapshot_cid: None,
+            occurred_at: None,
+            signature: None,
+            signer_public_key: None,
+            attachments: Vec::new(),
+            caused_by: None,
+            correlation_id: None,
         }
     }
 
@@ -885,6 +1556,12 @@ impl Event {
             pushed_to_circuit: None,
             snapshot_id: None,
             snapshot_cid: None,
+            occurred_at: None,
+            signature: None,
+            signer_public_key: None,
+            attachments: Vec::new(),
+            caused_by: None,
+            correlation_id: None,
         }
     }
 
@@ -894,15 +1571,13 @@ impl Event {
         self.is_local = false;
         self.pushed_to_circuit = Some(circuit_id);
         // Recalculate content hash with the new DFID
-        self.content_hash =
-            Self::calculate_dedup_hash(&self.dfid, &self.event_type, &self.source, &self.metadata);
+        self.content_hash = self.recalculate_dedup_hash();
     }
 
     pub fn add_metadata(&mut self, key: String, value: serde_json::Value) {
         self.metadata.insert(key, value);
         // Recalculate dedup hash when metadata changes
-        self.content_hash =
-            Self::calculate_dedup_hash(&self.dfid, &self.event_type, &self.source, &self.metadata);
+        self.content_hash = self.recalculate_dedup_hash();
     }
 
     /// Merge metadata from another source, returning the list of keys that were merged
@@ -920,12 +1595,7 @@ impl Event {
         }
         if !merged_keys.is_empty() {
             // Recalculate dedup hash after merge
-            self.content_hash = Self::calculate_dedup_hash(
-                &self.dfid,
-                &self.event_type,
-                &self.source,
-                &self.metadata,
-            );
+            self.content_hash = self.recalculate_dedup_hash();
         }
         merged_keys
     }
@@ -934,15 +1604,84 @@ impl Event {
         self.is_encrypted = true;
     }
 
-    /// Calculate content hash using BLAKE3 for event integrity/audit trail
+    /// Algorithm the event's hashes were made with
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        hashing::algorithm_of(&self.content_hash)
+    }
+
+    fn recalculate_dedup_hash(&self) -> String {
+        Self::dedup_hash_with(
+            self.hash_algorithm(),
+            &self.dfid,
+            &self.event_type,
+            &self.source,
+            &self.metadata,
+        )
+    }
+
+    /// Hash a user signs to assert an event: the default hash (see `hashing`) of the compact JSON
+    /// object with keys `dfid`, `event_type`, `metadata`, `occurred_at` (RFC 3339
+    /// to whole seconds in UTC, or null) and `source`, keys sorted at every level. Server-assigned fields
+    /// (id, timestamp) are left out so the hash is known before creation.
+    pub fn calculate_signing_hash(
+        dfid: &str,
+        event_type: &EventType,
+        source: &str,
+        metadata: &HashMap<String, serde_json::Value>,
+        occurred_at: Option<DateTime<Utc>>,
+    ) -> String {
+        Self::signing_hash_with(
+            hashing::default_algorithm(),
+            dfid,
+            event_type,
+            source,
+            metadata,
+            occurred_at,
+        )
+    }
+
+    fn signing_hash_with(
+        algorithm: HashAlgorithm,
+        dfid: &str,
+        event_type: &EventType,
+        source: &str,
+        metadata: &HashMap<String, serde_json::Value>,
+        occurred_at: Option<DateTime<Utc>>,
+    ) -> String {
+        // serde_json maps are sorted, so this serialization is canonical
+        let canonical = serde_json::json!({
+            "dfid": dfid,
+            "event_type": event_type.to_string(),
+            "metadata": metadata,
+            // Whole seconds: that is the precision occurred_at is persisted with
+            "occurred_at": occurred_at.map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+            "source": source,
+        });
+        algorithm.hash(canonical.to_string().as_bytes())
+    }
+
+    /// Signing hash under the algorithm the event was created with
+    pub fn signing_hash(&self) -> String {
+        Self::signing_hash_with(
+            self.hash_algorithm(),
+            &self.dfid,
+            &self.event_type,
+            &self.source,
+            &self.metadata,
+            self.occurred_at,
+        )
+    }
+
+    /// Calculate content hash for event integrity/audit trail
     /// Hash includes: event_type + source + timestamp + metadata
     fn calculate_content_hash(
+        algorithm: HashAlgorithm,
         event_type: &EventType,
         source: &str,
         timestamp: &DateTime<Utc>,
         metadata: &HashMap<String, serde_json::Value>,
     ) -> String {
-        let mut hasher = blake3::Hasher::new();
+        let mut hasher = algorithm.hasher();
 
         // Add event_type to hash
         hasher.update(format!("{event_type:?}").as_bytes());
@@ -957,19 +1696,63 @@ impl Event {
         let metadata_json = serde_json::to_string(metadata).unwrap_or_default();
         hasher.update(metadata_json.as_bytes());
 
-        hasher.finalize().to_hex().to_string()
+        hasher.finalize()
     }
 
-    /// Calculate deduplication hash using BLAKE3
+    /// Calculate deduplication hash with the default algorithm
     /// Hash includes: dfid + event_type + source + sorted metadata (NO timestamp)
     /// This allows detecting duplicate events regardless of when they were created
+    /// Declare when the event actually happened. The occurrence is folded into the
+    /// content hash so historical readings with identical payloads stay distinct.
+    pub fn with_occurred_at(mut self, occurred_at: DateTime<Utc>) -> Self {
+        self.content_hash = Self::occurrence_dedup_hash(&self.content_hash, Some(occurred_at));
+        self.occurred_at = Some(occurred_at);
+        self
+    }
+
+    pub fn time_on(&self, axis: TimeAxis) -> DateTime<Utc> {
+        match axis {
+            TimeAxis::Recorded => self.timestamp,
+            TimeAxis::Occurred => self.occurred_at.unwrap_or(self.timestamp),
+        }
+    }
+
+    /// Extend a dedup hash with a declared occurrence; unchanged when none is declared
+    pub fn occurrence_dedup_hash(dedup_hash: &str, occurred_at: Option<DateTime<Utc>>) -> String {
+        match occurred_at {
+            Some(occurred_at) => {
+                let mut hasher = hashing::algorithm_of(dedup_hash).hasher();
+                hasher.update(dedup_hash.as_bytes());
+                hasher.update(occurred_at.to_rfc3339().as_bytes());
+                hasher.finalize()
+            }
+            None => dedup_hash.to_string(),
+        }
+    }
+
     pub fn calculate_dedup_hash(
         dfid: &str,
         event_type: &EventType,
         source: &str,
         metadata: &HashMap<String, serde_json::Value>,
     ) -> String {
-        let mut hasher = blake3::Hasher::new();
+        Self::dedup_hash_with(
+            hashing::default_algorithm(),
+            dfid,
+            event_type,
+            source,
+            metadata,
+        )
+    }
+
+    fn dedup_hash_with(
+        algorithm: HashAlgorithm,
+        dfid: &str,
+        event_type: &EventType,
+        source: &str,
+        metadata: &HashMap<String, serde_json::Value>,
+    ) -> String {
+        let mut hasher = algorithm.hasher();
 
         // Add dfid to hash (which item this event belongs to)
         hasher.update(dfid.as_bytes());
@@ -986,7 +1769,7 @@ impl Event {
         let metadata_json = serde_json::to_string(&sorted_metadata).unwrap_or_default();
         hasher.update(metadata_json.as_bytes());
 
-        hasher.finalize().to_hex().to_string()
+        hasher.finalize()
     }
 
     /// Check if a specific user can view this event based on visibility and metadata
@@ -1035,6 +1818,7 @@ impl Event {
         );
         // Recalculate hash after metadata change
         self.content_hash = Self::calculate_content_hash(
+            self.hash_algorithm(),
             &self.event_type,
             &self.source,
             &self.timestamp,
@@ -1050,6 +1834,7 @@ impl Event {
         );
         // Recalculate hash after metadata change
         self.content_hash = Self::calculate_content_hash(
+            self.hash_algorithm(),
             &self.event_type,
             &self.source,
             &self.timestamp,
@@ -1095,6 +1880,8 @@ impl Circuit {
             requires_approval: false,
             auto_migrate_existing: false,
             sponsor_adapter_access: false,
+            replication_policy: ReplicationPolicy::Single,
+            mirror_adapters: Vec::new(),
         };
 
         Self {
@@ -1114,6 +1901,11 @@ impl Circuit {
             public_settings: None,
             adapter_config: Some(default_adapter_config),
             post_action_settings: None,
+            archived_at: None,
+            archived_by: None,
+            parent_circuit_id: None,
+            parent_access: ParentAccess::default(),
+            pending_ownership_transfer: None,
         }
     }
 
@@ -1128,8 +1920,8 @@ impl Circuit {
         circuit
     }
 
-    pub fn add_member(&mut self, member_id: String, role: MemberRole) {
-        let permissions = match role {
+    fn role_permissions(role: MemberRole) -> Vec<Permission> {
+        match role {
             MemberRole::Owner => vec![
                 Permission::Push,
                 Permission::Pull,
@@ -1151,13 +1943,15 @@ impl Circuit {
             ],
             MemberRole::Member => vec![Permission::Push, Permission::Pull],
             MemberRole::Viewer => vec![Permission::Pull],
-        };
+        }
+    }
 
+    pub fn add_member(&mut self, member_id: String, role: MemberRole) {
         let member = CircuitMember {
             member_id,
             role,
             custom_role_name: None,
-            permissions,
+            permissions: Self::role_permissions(role),
             joined_timestamp: Utc::now(),
         };
 
@@ -1165,6 +1959,30 @@ impl Circuit {
         self.last_modified = Utc::now();
     }
 
+    /// Make `new_owner_id`, already a member, the owner; the previous owner
+    /// stays on as an admin
+    pub fn transfer_ownership(&mut self, new_owner_id: &str) -> Result<(), String> {
+        if !self.members.iter().any(|m| m.member_id == new_owner_id) {
+            return Err("The new owner must be a member of the circuit".to_string());
+        }
+        let previous_owner = std::mem::replace(&mut self.owner_id, new_owner_id.to_string());
+        for member in &mut self.members {
+            let role = if member.member_id == new_owner_id {
+                MemberRole::Owner
+            } else if member.member_id == previous_owner {
+                MemberRole::Admin
+            } else {
+                continue;
+            };
+            member.role = role;
+            member.custom_role_name = None;
+            member.permissions = Self::role_permissions(role);
+        }
+        self.pending_ownership_transfer = None;
+        self.last_modified = Utc::now();
+        Ok(())
+    }
+
     pub fn has_permission(&self, member_id: &str, permission: &Permission) -> bool {
         self.members
             .iter()
@@ -1186,6 +2004,11 @@ impl Circuit {
         self.members.iter().any(|m| m.member_id == member_id)
     }
 
+    /// Archived circuits keep their items and history but accept no pushes or pulls
+    pub fn is_archived(&self) -> bool {
+        matches!(self.status, CircuitStatus::Archived)
+    }
+
     pub fn has_pending_request(&self, requester_id: &str) -> bool {
         self.pending_requests.iter().any(|r| {
             r.requester_id == requester_id && matches!(r.status, JoinRequestStatus::Pending)
@@ -1212,6 +2035,10 @@ impl Circuit {
             requested_at: Utc::now(),
             message,
             status: JoinRequestStatus::Pending,
+            decided_by: None,
+            decided_at: None,
+            granted_role: None,
+            decision_reason: None,
         };
 
         self.pending_requests.push(request);
@@ -1219,39 +2046,47 @@ impl Circuit {
         Ok(())
     }
 
-    pub fn approve_join_request(
-        &mut self,
-        requester_id: &str,
-        role: MemberRole,
-    ) -> Result<(), String> {
-        // Find and update the request
-        let request = self
-            .pending_requests
+    fn pending_request_mut(&mut self, requester_id: &str) -> Result<&mut JoinRequest, String> {
+        self.pending_requests
             .iter_mut()
             .find(|r| {
                 r.requester_id == requester_id && matches!(r.status, JoinRequestStatus::Pending)
             })
-            .ok_or("No pending request found for this user")?;
-
-        request.status = JoinRequestStatus::Approved;
+            .ok_or_else(|| "No pending request found for this user".to_string())
+    }
 
-        // Add the user as a member
+    pub fn approve_join_request(
+        &mut self,
+        requester_id: &str,
+        role: MemberRole,
+        approver_id: &str,
+    ) -> Result<(), String> {
+        let now = Utc::now();
+        let request = self.pending_request_mut(requester_id)?;
+        request.status = JoinRequestStatus::Approved;
+        request.decided_by = Some(approver_id.to_string());
+        request.decided_at = Some(now);
+        request.granted_role = Some(role);
+
+        // Add the user as a member
         self.add_member(requester_id.to_string(), role);
-        self.last_modified = Utc::now();
+        self.last_modified = now;
         Ok(())
     }
 
-    pub fn reject_join_request(&mut self, requester_id: &str) -> Result<(), String> {
-        let request = self
-            .pending_requests
-            .iter_mut()
-            .find(|r| {
-                r.requester_id == requester_id && matches!(r.status, JoinRequestStatus::Pending)
-            })
-            .ok_or("No pending request found for this user")?;
-
+    pub fn reject_join_request(
+        &mut self,
+        requester_id: &str,
+        rejector_id: &str,
+        reason: Option<String>,
+    ) -> Result<(), String> {
+        let now = Utc::now();
+        let request = self.pending_request_mut(requester_id)?;
         request.status = JoinRequestStatus::Rejected;
-        self.last_modified = Utc::now();
+        request.decided_by = Some(rejector_id.to_string());
+        request.decided_at = Some(now);
+        request.decision_reason = reason;
+        self.last_modified = now;
         Ok(())
     }
 
@@ -1510,6 +2345,43 @@ impl Circuit {
         })
     }
 
+    /// The circuit's directory listing, when it is public, active and has
+    /// opted in
+    pub fn directory_entry(&self) -> Option<CircuitDirectoryEntry> {
+        let settings = self.public_settings.as_ref()?;
+        if !settings.listed_in_directory
+            || !self.is_publicly_accessible()
+            || self.is_archived()
+            || !matches!(self.status, CircuitStatus::Active)
+        {
+            return None;
+        }
+
+        let join_mode = match settings.access_mode {
+            PublicAccessMode::Protected => DirectoryJoinMode::Password,
+            _ if settings.auto_approve_members => DirectoryJoinMode::Open,
+            _ => DirectoryJoinMode::Request,
+        };
+        Some(CircuitDirectoryEntry {
+            circuit_id: self.circuit_id,
+            name: settings
+                .public_name
+                .clone()
+                .unwrap_or_else(|| self.name.clone()),
+            description: settings
+                .public_description
+                .clone()
+                .or_else(|| Some(self.description.clone()).filter(|d| !d.is_empty())),
+            tagline: settings.tagline.clone(),
+            logo_url: settings.logo_url.clone(),
+            region: settings.region.clone(),
+            item_categories: settings.item_categories.clone(),
+            member_count: self.members.len(),
+            join_mode,
+            public_since: settings.public_since,
+        })
+    }
+
     pub fn get_member_count_by_role(&self) -> std::collections::HashMap<String, usize> {
         let mut role_counts = std::collections::HashMap::new();
 
@@ -1767,6 +2639,9 @@ pub struct CircuitItem {
     pub pushed_by: String,
     pub pushed_at: DateTime<Utc>,
     pub permissions: Vec<String>,
+    /// The item is removed from the circuit once this passes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 impl CircuitItem {
@@ -1782,8 +2657,13 @@ impl CircuitItem {
             pushed_by,
             pushed_at: Utc::now(),
             permissions,
+            expires_at: None,
         }
     }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1801,6 +2681,18 @@ pub struct EnrichmentMatch {
     pub confidence: f64,
 }
 
+/// An item pushed from one circuit into another
+#[derive(Debug, Clone, Serialize)]
+pub struct ItemForward {
+    pub dfid: String,
+    pub source_circuit_id: Uuid,
+    pub target_circuit_id: Uuid,
+    /// Push into the target; pending when the target requires approval
+    pub operation: CircuitOperation,
+    /// `ForwardedToCircuit` event recorded in the source circuit
+    pub forward_event_id: Uuid,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchPushResult {
     pub success_count: usize,
@@ -1903,6 +2795,8 @@ pub enum IncidentCategory {
     SystemCompromise,
     PolicyViolation,
     DenialOfService,
+    /// A platform component stopped working properly, e.g. opened by a runbook hook
+    ServiceDegradation,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -2040,6 +2934,9 @@ pub struct AuditQuery {
     pub offset: Option<u32>,
     pub sort_by: Option<AuditSortBy>,
     pub sort_order: Option<SortOrder>,
+    /// Compiled query-language expression applied on top of the fixed filters
+    #[serde(default)]
+    pub filter: Option<AuditFilterExpr>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2055,6 +2952,168 @@ pub enum SortOrder {
     Desc,
 }
 
+/// Event field addressable from the audit query language
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditField {
+    UserId,
+    EventType,
+    Action,
+    Resource,
+    ResourceId,
+    Outcome,
+    Severity,
+    Timestamp,
+    IpAddress,
+    Detail(String),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditCompareOp {
+    Eq,
+    NotEq,
+    Contains,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+/// Parsed audit query expression, evaluated as a predicate over stored events
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditFilterExpr {
+    And(Box<AuditFilterExpr>, Box<AuditFilterExpr>),
+    Or(Box<AuditFilterExpr>, Box<AuditFilterExpr>),
+    Not(Box<AuditFilterExpr>),
+    Compare {
+        field: AuditField,
+        op: AuditCompareOp,
+        value: String,
+    },
+    /// Case-insensitive free text over action and resource
+    Text(String),
+}
+
+/// A named audit query expression kept for recurring investigations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedAuditQuery {
+    pub query_id: Uuid,
+    pub owner_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub expression: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::safe_json_numbers::u64_safe")]
+    pub run_count: u64,
+}
+
+impl AuditSeverity {
+    pub fn rank(&self) -> u8 {
+        match self {
+            AuditSeverity::Low => 0,
+            AuditSeverity::Medium => 1,
+            AuditSeverity::High => 2,
+            AuditSeverity::Critical => 3,
+        }
+    }
+}
+
+impl AuditFilterExpr {
+    pub fn matches(&self, event: &AuditEvent) -> bool {
+        match self {
+            AuditFilterExpr::And(a, b) => a.matches(event) && b.matches(event),
+            AuditFilterExpr::Or(a, b) => a.matches(event) || b.matches(event),
+            AuditFilterExpr::Not(inner) => !inner.matches(event),
+            AuditFilterExpr::Text(text) => {
+                let text = text.to_lowercase();
+                event.action.to_lowercase().contains(&text)
+                    || event.resource.to_lowercase().contains(&text)
+            }
+            AuditFilterExpr::Compare { field, op, value } => match field {
+                AuditField::Severity => match AuditFilterExpr::parse_severity(value) {
+                    Some(target) => op.holds(event.severity.rank().cmp(&target.rank())),
+                    None => false,
+                },
+                AuditField::Timestamp => match AuditFilterExpr::parse_timestamp(value) {
+                    Some(target) => op.holds(event.timestamp.cmp(&target)),
+                    None => false,
+                },
+                _ => match AuditFilterExpr::field_text(field, event) {
+                    Some(actual) => op.holds_text(&actual, value),
+                    // Missing optional fields only satisfy "!="
+                    None => *op == AuditCompareOp::NotEq,
+                },
+            },
+        }
+    }
+
+    fn field_text(field: &AuditField, event: &AuditEvent) -> Option<String> {
+        match field {
+            AuditField::UserId => Some(event.user_id.clone()),
+            AuditField::EventType => Some(format!("{:?}", event.event_type)),
+            AuditField::Action => Some(event.action.clone()),
+            AuditField::Resource => Some(event.resource.clone()),
+            AuditField::ResourceId => event.resource_id.clone(),
+            AuditField::Outcome => Some(format!("{:?}", event.outcome)),
+            AuditField::IpAddress => event.metadata.ip_address.clone(),
+            AuditField::Detail(key) => event.details.get(key).map(|v| match v {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            }),
+            AuditField::Severity | AuditField::Timestamp => None,
+        }
+    }
+
+    pub fn parse_severity(value: &str) -> Option<AuditSeverity> {
+        match value.to_lowercase().as_str() {
+            "low" => Some(AuditSeverity::Low),
+            "medium" => Some(AuditSeverity::Medium),
+            "high" => Some(AuditSeverity::High),
+            "critical" => Some(AuditSeverity::Critical),
+            _ => None,
+        }
+    }
+
+    /// Accepts RFC 3339 timestamps or plain `YYYY-MM-DD` dates (midnight UTC)
+    pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+        if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+            return Some(ts.with_timezone(&Utc));
+        }
+        chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .ok()
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|dt| dt.and_utc())
+    }
+}
+
+impl AuditCompareOp {
+    fn holds(&self, ordering: std::cmp::Ordering) -> bool {
+        use std::cmp::Ordering;
+        match self {
+            AuditCompareOp::Eq => ordering == Ordering::Equal,
+            AuditCompareOp::NotEq => ordering != Ordering::Equal,
+            AuditCompareOp::Gt => ordering == Ordering::Greater,
+            AuditCompareOp::Gte => ordering != Ordering::Less,
+            AuditCompareOp::Lt => ordering == Ordering::Less,
+            AuditCompareOp::Lte => ordering != Ordering::Greater,
+            AuditCompareOp::Contains => false,
+        }
+    }
+
+    fn holds_text(&self, actual: &str, expected: &str) -> bool {
+        let actual = actual.to_lowercase();
+        let expected = expected.to_lowercase();
+        match self {
+            AuditCompareOp::Contains => actual.contains(&expected),
+            _ => self.holds(actual.cmp(&expected)),
+        }
+    }
+}
+
 // Implementation blocks for audit types
 impl AuditEvent {
     pub fn new(
@@ -2231,6 +3290,8 @@ pub enum AdapterType {
     IpfsIpfs,
     StellarTestnetIpfs,
     StellarMainnetIpfs,
+    StellarTestnetLocal,
+    StellarMainnetLocal,
     EthereumGoerliIpfs,
     PolygonArweave,
     Custom(String),
@@ -2249,6 +3310,8 @@ impl std::fmt::Display for AdapterType {
             AdapterType::IpfsIpfs => write!(f, "ipfs-ipfs"),
             AdapterType::StellarTestnetIpfs => write!(f, "stellar_testnet-ipfs"),
             AdapterType::StellarMainnetIpfs => write!(f, "stellar_mainnet-ipfs"),
+            AdapterType::StellarTestnetLocal => write!(f, "stellar_testnet-local"),
+            AdapterType::StellarMainnetLocal => write!(f, "stellar_mainnet-local"),
             AdapterType::EthereumGoerliIpfs => write!(f, "ethereum_goerli-ipfs"),
             AdapterType::PolygonArweave => write!(f, "polygon-arweave"),
             AdapterType::Custom(name) => write!(f, "custom-{name}"),
@@ -2264,6 +3327,8 @@ impl AdapterType {
             "ipfs-ipfs" | "IpfsIpfs" => Ok(AdapterType::IpfsIpfs),
             "stellar_testnet-ipfs" | "StellarTestnetIpfs" => Ok(AdapterType::StellarTestnetIpfs),
             "stellar_mainnet-ipfs" | "StellarMainnetIpfs" => Ok(AdapterType::StellarMainnetIpfs),
+            "stellar_testnet-local" | "StellarTestnetLocal" => Ok(AdapterType::StellarTestnetLocal),
+            "stellar_mainnet-local" | "StellarMainnetLocal" => Ok(AdapterType::StellarMainnetLocal),
             "ethereum_goerli-ipfs" | "EthereumGoerliIpfs" => Ok(AdapterType::EthereumGoerliIpfs),
             "polygon-arweave" | "PolygonArweave" => Ok(AdapterType::PolygonArweave),
             custom if custom.starts_with("custom-") => {
@@ -2283,6 +3348,12 @@ impl AdapterType {
             AdapterType::StellarMainnetIpfs => {
                 "Stellar mainnet NFTs + IPFS events - production blockchain + IPFS"
             }
+            AdapterType::StellarTestnetLocal => {
+                "Stellar testnet anchors + encrypted local files - for testing on-premise storage"
+            }
+            AdapterType::StellarMainnetLocal => {
+                "Stellar mainnet anchors + encrypted local files - data stays on-premise"
+            }
             AdapterType::EthereumGoerliIpfs => {
                 "Ethereum Goerli testnet + IPFS - Ethereum ecosystem testing"
             }
@@ -2312,6 +3383,14 @@ impl AdapterType {
             AdapterType::StellarMainnetIpfs => {
                 (StorageBackendType::StellarMainnet, StorageBackendType::IPFS)
             }
+            AdapterType::StellarTestnetLocal => (
+                StorageBackendType::StellarTestnet,
+                StorageBackendType::Local,
+            ),
+            AdapterType::StellarMainnetLocal => (
+                StorageBackendType::StellarMainnet,
+                StorageBackendType::Local,
+            ),
             AdapterType::EthereumGoerliIpfs => {
                 (StorageBackendType::EthereumGoerli, StorageBackendType::IPFS)
             }
@@ -2366,6 +3445,73 @@ pub struct ItemStorageHistory {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Adapter write an intent was recorded for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdapterOperation {
+    /// First write of an item pushed into a circuit
+    CircuitPush,
+    /// Re-upload of an existing item to a circuit's adapter
+    StorageMigration,
+}
+
+impl AdapterOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdapterOperation::CircuitPush => "circuit_push",
+            AdapterOperation::StorageMigration => "storage_migration",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdapterIntentStatus {
+    /// Recorded before the adapter write; still pending after a crash
+    Pending,
+    /// Written and its storage record added
+    Completed,
+    /// The adapter write or the storage record failed
+    Failed,
+}
+
+impl AdapterIntentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdapterIntentStatus::Pending => "pending",
+            AdapterIntentStatus::Completed => "completed",
+            AdapterIntentStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Write-ahead entry for an IPFS/Stellar write, recorded before the adapter is
+/// called and completed once the item's storage record is in place
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdapterIntent {
+    pub intent_id: Uuid,
+    pub dfid: String,
+    pub circuit_id: Option<Uuid>,
+    pub adapter_type: AdapterType,
+    pub operation: AdapterOperation,
+    /// Whether the write mints the item's NFT
+    pub is_new_dfid: bool,
+    pub requested_by: String,
+    pub status: AdapterIntentStatus,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub storage_location: Option<StorageLocation>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl AdapterIntent {
+    pub fn is_complete(&self) -> bool {
+        self.status == AdapterIntentStatus::Completed
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageRecord {
     pub adapter_type: AdapterType,
@@ -2378,6 +3524,16 @@ pub struct StorageRecord {
     pub metadata: HashMap<String, serde_json::Value>, // Additional storage-specific metadata
 }
 
+impl StorageRecord {
+    /// Per-adapter replication results, when the record was written under a replication policy
+    pub fn replica_results(&self) -> Vec<ReplicaWriteResult> {
+        self.metadata
+            .get("replica_results")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ItemWithHistory {
     pub item: Item,
@@ -2394,6 +3550,37 @@ pub struct CircuitAdapterConfig {
     pub requires_approval: bool,
     pub auto_migrate_existing: bool, // Whether to migrate existing items when circuit adapter changes
     pub sponsor_adapter_access: bool, // When true, circuit sponsors adapter access for all members
+    #[serde(default)]
+    pub replication_policy: ReplicationPolicy,
+    #[serde(default)]
+    pub mirror_adapters: Vec<AdapterType>, // Written alongside adapter_type according to replication_policy
+}
+
+/// How pushed items are written when a circuit has mirror adapters
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ReplicationPolicy {
+    /// Only the primary adapter is written
+    #[default]
+    Single,
+    /// Primary and every mirror are written in parallel; all must succeed
+    WriteAll,
+    /// Primary and mirrors are written in parallel; at least `min_successes` must succeed
+    Quorum { min_successes: usize },
+    /// Primary is written before the push returns, mirrors are written in the background
+    PrimaryAsyncMirror,
+}
+
+/// Outcome of writing an item to a single adapter during replication
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplicaWriteResult {
+    pub adapter_type: AdapterType,
+    pub is_primary: bool,
+    pub success: bool,
+    pub storage_location: Option<StorageLocation>,
+    pub error: Option<String>,
+    pub latency_ms: u64,
+    pub completed_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2442,10 +3629,31 @@ pub struct WebhookConfig {
     pub auth_credentials: Option<String>, // encrypted at rest
     pub enabled: bool,
     pub retry_config: RetryConfig,
+    /// Subscriber public key; when set, payloads are sent as a compact JWE
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption_key: Option<WebhookEncryptionKey>,
+    /// Which triggers the subscriber receives; the default receives all
+    #[serde(default, skip_serializing_if = "WebhookFilter::is_empty")]
+    pub filter: WebhookFilter,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// RSA public key in JWK form (RFC 7517), registered by the webhook subscriber.
+/// Payloads are encrypted with RSA-OAEP-256 key wrapping and A256GCM content
+/// encryption.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookEncryptionKey {
+    pub kty: String,
+    /// Key ID echoed in the JWE header so subscribers can rotate keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    /// base64url modulus
+    pub n: String,
+    /// base64url public exponent
+    pub e: String,
+}
+
 impl WebhookConfig {
     pub fn new(name: String, url: String) -> Self {
         Self {
@@ -2458,12 +3666,97 @@ impl WebhookConfig {
             auth_credentials: None,
             enabled: true,
             retry_config: RetryConfig::default(),
+            encryption_key: None,
+            filter: WebhookFilter::default(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
     }
 }
 
+/// Per-webhook subscription filter, evaluated before a delivery is enqueued.
+/// Every criterion that is set must match; unset criteria match anything.
+/// Event criteria apply to the item event the circuit operation follows.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct WebhookFilter {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<PostActionTrigger>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub event_types: Vec<EventType>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub visibility: Vec<EventVisibility>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dfid_prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub metadata: Vec<MetadataPredicate>,
+}
+
+impl WebhookFilter {
+    pub fn is_empty(&self) -> bool {
+        *self == WebhookFilter::default()
+    }
+
+    pub fn matches(&self, trigger: PostActionTrigger, dfid: &str, event: Option<&Event>) -> bool {
+        if !self.triggers.is_empty() && !self.triggers.contains(&trigger) {
+            return false;
+        }
+        if let Some(prefix) = &self.dfid_prefix {
+            if !dfid.starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+        if self.event_types.is_empty() && self.visibility.is_empty() && self.metadata.is_empty() {
+            return true;
+        }
+        let Some(event) = event else {
+            return false;
+        };
+        (self.event_types.is_empty() || self.event_types.contains(&event.event_type))
+            && (self.visibility.is_empty() || self.visibility.contains(&event.visibility))
+            && self
+                .metadata
+                .iter()
+                .all(|predicate| predicate.matches(&event.metadata))
+    }
+}
+
+/// A condition on one event metadata key
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetadataPredicate {
+    pub key: String,
+    #[serde(flatten)]
+    pub condition: MetadataCondition,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "op", content = "value", rename_all = "snake_case")]
+pub enum MetadataCondition {
+    Exists,
+    Equals(serde_json::Value),
+    NotEquals(serde_json::Value),
+    OneOf(Vec<serde_json::Value>),
+    GreaterThan(f64),
+    LessThan(f64),
+}
+
+impl MetadataPredicate {
+    pub fn matches(&self, metadata: &HashMap<String, serde_json::Value>) -> bool {
+        let value = metadata.get(&self.key);
+        match &self.condition {
+            MetadataCondition::Exists => value.is_some(),
+            MetadataCondition::Equals(expected) => value == Some(expected),
+            MetadataCondition::NotEquals(expected) => value != Some(expected),
+            MetadataCondition::OneOf(options) => value.is_some_and(|v| options.contains(v)),
+            MetadataCondition::GreaterThan(bound) => {
+                value.and_then(|v| v.as_f64()).is_some_and(|v| v > *bound)
+            }
+            MetadataCondition::LessThan(bound) => {
+                value.and_then(|v| v.as_f64()).is_some_and(|v| v < *bound)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum HttpMethod {
     Post,
@@ -2496,6 +3789,10 @@ pub enum PostActionTrigger {
     ItemApproved,
     ItemTokenized,
     ItemPublished,
+    /// A ZK proof about an item of the circuit was revoked
+    ProofRevoked,
+    /// A queued ZK proof about an item of the circuit finished generating
+    ProofGenerated,
 }
 
 impl PostActionTrigger {
@@ -2505,6 +3802,8 @@ impl PostActionTrigger {
             PostActionTrigger::ItemApproved => "item_approved",
             PostActionTrigger::ItemTokenized => "item_tokenized",
             PostActionTrigger::ItemPublished => "item_published",
+            PostActionTrigger::ProofRevoked => "proof_revoked",
+            PostActionTrigger::ProofGenerated => "proof_generated",
         }
     }
 }
@@ -2570,6 +3869,24 @@ impl WebhookDelivery {
     }
 }
 
+/// Outcome of re-sending a past delivery to a test URL; replays are not stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookReplayResult {
+    /// The replayed delivery, whose id the replay carries
+    pub delivery_id: Uuid,
+    pub webhook_id: Uuid,
+    pub target_url: String,
+    pub payload_bytes: usize,
+    /// Whether the replay carried a signature header
+    pub signed: bool,
+    pub replayed_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub response_code: Option<u16>,
+    /// First 4 KiB of the response
+    pub response_body: Option<String>,
+    pub error_message: Option<String>,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum DeliveryStatus {
     Pending,
@@ -2590,6 +3907,8 @@ pub struct WebhookPayload {
     pub storage: Option<WebhookStorageData>,
     pub operation_id: String,
     pub status: String,
+    #[serde(default)]
+    pub priority: IngestionPriority,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2631,6 +3950,16 @@ impl UserTier {
         }
     }
 
+    /// Committed monthly availability, in percent; internal tiers have none
+    pub fn sla_target_percent(&self) -> Option<f64> {
+        match self {
+            UserTier::Basic => Some(99.0),
+            UserTier::Professional => Some(99.5),
+            UserTier::Enterprise => Some(99.9),
+            UserTier::Admin => None,
+        }
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<Self, String> {
         match s.to_lowercase().as_str() {
@@ -2669,6 +3998,9 @@ pub struct UserAccount {
     pub is_admin: bool,
     pub workspace_id: Option<String>,
     pub available_adapters: Option<Vec<AdapterType>>, // None = use tier defaults
+    /// BCP 47 locale for notifications and emails; None = server default
+    #[serde(default)]
+    pub locale: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2918,6 +4250,65 @@ impl CreditCosts {
     }
 }
 
+/// How large and how complex one event may be. Enforced when events are
+/// validated so a single integrator cannot inflate snapshots and anchoring costs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventPayloadLimits {
+    /// Serialized size of the metadata
+    pub max_metadata_bytes: usize,
+    /// Nesting of objects and arrays below the top-level metadata keys
+    pub max_metadata_depth: usize,
+    /// Keys and array elements across the whole metadata tree
+    pub max_metadata_fields: usize,
+    pub max_attachments: usize,
+    /// Entries of the `identifiers` metadata key
+    pub max_identifiers: usize,
+}
+
+impl EventPayloadLimits {
+    pub fn for_tier(tier: &UserTier) -> Self {
+        match tier {
+            UserTier::Basic => EventPayloadLimits {
+                max_metadata_bytes: 16 * 1024,
+                max_metadata_depth: 8,
+                max_metadata_fields: 500,
+                max_attachments: 5,
+                max_identifiers: 20,
+            },
+            UserTier::Professional => EventPayloadLimits {
+                max_metadata_bytes: 64 * 1024,
+                max_metadata_depth: 16,
+                max_metadata_fields: 2_000,
+                max_attachments: 20,
+                max_identifiers: 100,
+            },
+            UserTier::Enterprise => EventPayloadLimits {
+                max_metadata_bytes: 256 * 1024,
+                max_metadata_depth: 32,
+                max_metadata_fields: 10_000,
+                max_attachments: 50,
+                max_identifiers: 500,
+            },
+            UserTier::Admin => EventPayloadLimits {
+                max_metadata_bytes: 1024 * 1024,
+                max_metadata_depth: 64,
+                max_metadata_fields: 50_000,
+                max_attachments: 100,
+                max_identifiers: 2_000,
+            },
+        }
+    }
+}
+
+/// Events of one tier rejected for exceeding one kind of payload limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayloadRejectionCount {
+    pub tier: UserTier,
+    pub limit: String,
+    pub rejections: u64,
+    pub last_rejected_at: DateTime<Utc>,
+}
+
 // ============================================================================
 // USAGE TRACKING & ANALYTICS
 // ============================================================================
@@ -2949,6 +4340,63 @@ pub struct SystemStatistics {
     pub generated_at: DateTime<Utc>,
 }
 
+impl SystemStatistics {
+    /// Statistics as of `now` over the records a backend holds
+    pub fn tally<'a>(
+        users: &[UserAccount],
+        credit_transactions: impl IntoIterator<Item = &'a CreditTransaction>,
+        storage_records: impl IntoIterator<Item = &'a StorageRecord>,
+        total_items: i64,
+        total_circuits: i64,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let day_ago = now - chrono::Duration::hours(24);
+        let month_ago = now - chrono::Duration::days(30);
+        let active_since = |since: DateTime<Utc>| {
+            users
+                .iter()
+                .filter(|user| user.last_login.is_some_and(|login| login > since))
+                .count() as i64
+        };
+
+        let mut tier_distribution = HashMap::new();
+        for user in users {
+            *tier_distribution.entry(user.tier.clone()).or_insert(0) += 1;
+        }
+
+        let mut adapter_usage_stats = HashMap::new();
+        let mut total_storage_operations = 0;
+        for record in storage_records {
+            *adapter_usage_stats
+                .entry(record.adapter_type.clone())
+                .or_insert(0) += 1;
+            total_storage_operations += 1;
+        }
+
+        // Consumption amounts are negative
+        let credits_consumed_24h = credit_transactions
+            .into_iter()
+            .filter(|t| {
+                t.transaction_type == CreditTransactionType::Consumption && t.timestamp > day_ago
+            })
+            .map(|t| -t.amount)
+            .sum();
+
+        Self {
+            total_users: users.len() as i64,
+            active_users_24h: active_since(day_ago),
+            active_users_30d: active_since(month_ago),
+            total_items,
+            total_circuits,
+            total_storage_operations,
+            credits_consumed_24h,
+            tier_distribution,
+            adapter_usage_stats,
+            generated_at: now,
+        }
+    }
+}
+
 // ============================================================================
 // NOTIFICATION SYSTEM
 // ============================================================================
@@ -2973,6 +4421,14 @@ pub enum NotificationType {
     CircuitItemPendingApproval,
     CircuitItemApproved,
     CircuitItemRejected,
+    Announcement,
+    AccessReviewReady,
+    CircuitItemExpired,
+    CommentMention,
+    DataQualityDigest,
+    OwnershipTransferRequested,
+    OwnershipTransferred,
+    UsageAnomalyDetected,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -3133,6 +4589,47 @@ impl Default for AdapterConnectionDetails {
     }
 }
 
+/// Retry behaviour for adapter calls that fail with transient network errors
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts including the first call
+    pub max_attempts: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    pub backoff_multiplier: f64,
+    /// Fraction of each delay randomized to spread out concurrent retries (0.0 - 1.0)
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff_ms: 250,
+            max_backoff_ms: 10_000,
+            backoff_multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Use the adapter's configured retry count on top of the default backoff
+    pub fn from_connection_details(details: &AdapterConnectionDetails) -> Self {
+        Self {
+            max_attempts: details.retry_attempts + 1,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (1-based), without jitter
+    pub fn base_delay_ms(&self, retry: u32) -> u64 {
+        let exponent = retry.saturating_sub(1) as i32;
+        let delay = self.initial_backoff_ms as f64 * self.backoff_multiplier.powi(exponent);
+        delay.min(self.max_backoff_ms as f64) as u64
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum AuthType {
     None,
@@ -3226,33 +4723,177 @@ pub struct AdapterTestResult {
     pub contract_tests: Vec<ContractTestResult>,
     pub error_message: Option<String>,
     pub latency_ms: Option<u64>,
+    /// Runtime metrics recorded for this adapter type at test time
+    #[serde(default)]
+    pub metrics: Option<AdapterMetrics>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ConnectionTestResult {
-    pub success: bool,
-    pub endpoint_reachable: bool,
-    pub authentication_valid: bool,
-    pub latency_ms: u64,
-    pub error: Option<String>,
+/// Latency and outcome counters for a single adapter operation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AdapterOperationMetrics {
+    #[serde(with = "crate::safe_json_numbers::u64_safe")]
+    pub calls: u64,
+    #[serde(with = "crate::safe_json_numbers::u64_safe")]
+    pub successes: u64,
+    #[serde(with = "crate::safe_json_numbers::u64_safe")]
+    pub failures: u64,
+    #[serde(with = "crate::safe_json_numbers::u64_safe")]
+    pub total_latency_ms: u64,
+    pub max_latency_ms: u64,
+    pub last_latency_ms: u64,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ContractTestResult {
-    pub contract_type: String,
-    pub contract_address: String,
-    pub is_valid: bool,
-    pub methods_verified: Vec<String>,
-    pub error: Option<String>,
-}
+impl AdapterOperationMetrics {
+    pub fn average_latency_ms(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.total_latency_ms as f64 / self.calls as f64
+        }
+    }
 
-// ============================================================================
-// CID Timeline Types
-// ============================================================================
+    pub fn error_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.failures as f64 / self.calls as f64
+        }
+    }
+}
 
-/// Timeline entry representing a single CID version for an item
+/// Per-operation metrics for one adapter type since process start (or last reset)
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TimelineEntry {
+pub struct AdapterMetrics {
+    pub adapter_type: AdapterType,
+    pub since: DateTime<Utc>,
+    pub operations: HashMap<String, AdapterOperationMetrics>,
+}
+
+impl AdapterMetrics {
+    pub fn new(adapter_type: AdapterType) -> Self {
+        Self {
+            adapter_type,
+            since: Utc::now(),
+            operations: HashMap::new(),
+        }
+    }
+
+    pub fn total_calls(&self) -> u64 {
+        self.operations.values().map(|o| o.calls).sum()
+    }
+
+    pub fn total_failures(&self) -> u64 {
+        self.operations.values().map(|o| o.failures).sum()
+    }
+
+    pub fn error_rate(&self) -> f64 {
+        let calls = self.total_calls();
+        if calls == 0 {
+            0.0
+        } else {
+            self.total_failures() as f64 / calls as f64
+        }
+    }
+
+    /// Most recent error across all operations, with the operation that produced it
+    pub fn last_error(&self) -> Option<(&str, &AdapterOperationMetrics)> {
+        self.operations
+            .iter()
+            .filter(|(_, o)| o.last_error_at.is_some())
+            .max_by_key(|(_, o)| o.last_error_at)
+            .map(|(name, o)| (name.as_str(), o))
+    }
+}
+
+/// A write an adapter would perform, reported by a dry run instead of being committed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedWrite {
+    pub target: String,    // "ipfs", "stellar_nft", "stellar_ipcm"
+    pub operation: String, // e.g. "upload_json", "mint_nft", "emit_update_event"
+    pub payload_bytes: Option<usize>,
+    pub description: String,
+}
+
+/// Outcome of validating one adapter for a write without committing anything
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdapterDryRunReport {
+    pub adapter_type: AdapterType,
+    pub config_id: Option<Uuid>, // Active AdapterConfig the instance was built from, if any
+    pub config_valid: bool,
+    pub connectivity_ok: bool,
+    pub planned_writes: Vec<PlannedWrite>,
+    pub estimated_fee_stroops: Option<u64>, // Stellar adapters only
+    pub warnings: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+impl AdapterDryRunReport {
+    pub fn new(adapter_type: AdapterType) -> Self {
+        Self {
+            adapter_type,
+            config_id: None,
+            config_valid: true,
+            connectivity_ok: false,
+            planned_writes: Vec::new(),
+            estimated_fee_stroops: None,
+            warnings: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Record a configuration problem that would make the real write fail
+    pub fn config_error(&mut self, error: impl Into<String>) {
+        self.config_valid = false;
+        self.errors.push(error.into());
+    }
+
+    /// Whether the real write is expected to succeed
+    pub fn is_ready(&self) -> bool {
+        self.config_valid && self.connectivity_ok && self.errors.is_empty()
+    }
+}
+
+/// Dry run of a candidate CircuitAdapterConfig against a sample item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitDryRunReport {
+    pub circuit_id: Uuid,
+    pub dfid: String,
+    pub replication_policy: ReplicationPolicy,
+    pub adapters: Vec<AdapterDryRunReport>,
+    pub estimated_fee_stroops: Option<u64>,
+    pub errors: Vec<String>, // Problems with the circuit config itself (e.g. unreachable quorum)
+    pub ready: bool,
+    pub generated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionTestResult {
+    pub success: bool,
+    pub endpoint_reachable: bool,
+    pub authentication_valid: bool,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractTestResult {
+    pub contract_type: String,
+    pub contract_address: String,
+    pub is_valid: bool,
+    pub methods_verified: Vec<String>,
+    pub error: Option<String>,
+}
+
+// ============================================================================
+// CID Timeline Types
+// ============================================================================
+
+/// Timeline entry representing a single CID version for an item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEntry {
     pub id: Uuid,
     pub dfid: String,
     pub cid: String,
@@ -3263,6 +4904,74 @@ pub struct TimelineEntry {
     pub created_at: DateTime<Utc>,
 }
 
+/// Which part of an item's CID timeline to read. Bounds are inclusive; dates
+/// are compared with the blockchain timestamp. `latest` keeps the newest N
+/// entries within the other bounds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimelineQuery {
+    pub from_sequence: Option<i32>,
+    pub to_sequence: Option<i32>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub latest: Option<usize>,
+}
+
+impl TimelineQuery {
+    pub fn validate(&self) -> Result<(), String> {
+        if let (Some(from), Some(to)) = (self.from_sequence, self.to_sequence) {
+            if from > to {
+                return Err("from_sequence must not be after to_sequence".to_string());
+            }
+        }
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            if from > to {
+                return Err("from must not be after to".to_string());
+            }
+        }
+        if self.latest == Some(0) {
+            return Err("latest must be at least 1".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether the entry is within the sequence and date bounds
+    pub fn matches(&self, entry: &TimelineEntry) -> bool {
+        self.from_sequence
+            .is_none_or(|from| entry.event_sequence >= from)
+            && self.to_sequence.is_none_or(|to| entry.event_sequence <= to)
+            && self
+                .from
+                .is_none_or(|from| entry.blockchain_timestamp >= from.timestamp())
+            && self
+                .to
+                .is_none_or(|to| entry.blockchain_timestamp <= to.timestamp())
+    }
+}
+
+/// One page of an item's CID timeline in sequence order
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelinePage {
+    pub entries: Vec<TimelineEntry>,
+    /// Pass back as `after_sequence` for the next page; `None` on the last page
+    pub next_after_sequence: Option<i32>,
+}
+
+impl TimelinePage {
+    /// Page from up to `limit + 1` entries read after the previous page
+    pub fn from_fetched(mut entries: Vec<TimelineEntry>, limit: usize) -> Self {
+        let next_after_sequence = if entries.len() > limit {
+            entries.truncate(limit);
+            entries.last().map(|entry| entry.event_sequence)
+        } else {
+            None
+        };
+        Self {
+            entries,
+            next_after_sequence,
+        }
+    }
+}
+
 /// Maps an event to the CID where it first appeared
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventCidMapping {
@@ -3287,6 +4996,53 @@ pub struct IndexingProgress {
     pub last_error_at: Option<DateTime<Utc>>,
 }
 
+/// Operational incident a runbook hook reacts to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunbookTrigger {
+    /// A network's indexing progress has not moved for `threshold` minutes
+    IndexerStalled,
+    /// Webhook deliveries that exhausted their retries grew by `threshold`
+    WebhookDlqGrowing,
+    /// An adapter's error rate reached `threshold`
+    AdapterUnhealthy,
+}
+
+/// What a runbook hook does when its incident is detected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum RemediationAction {
+    /// Restart a background worker running in this process
+    RestartWorker {
+        worker: String,
+    },
+    /// Move circuits writing through the unhealthy adapter to another one
+    SwitchAdapter {
+        to: AdapterType,
+    },
+    OpenIncident {
+        severity: AuditSeverity,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunbookHook {
+    pub hook_id: Uuid,
+    pub trigger: RunbookTrigger,
+    pub actions: Vec<RemediationAction>,
+    /// Trigger-specific; the trigger's default when None
+    pub threshold: Option<f64>,
+    /// The hook does not fire again for this long after firing
+    pub cooldown_minutes: u32,
+    pub enabled: bool,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub last_fired_at: Option<DateTime<Utc>>,
+    /// Dead-lettered deliveries when the DLQ baseline was last reset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dlq_baseline: Option<usize>,
+}
+
 // ============================================================================
 // User Activity Tracking System
 // ============================================================================
@@ -3449,3 +5205,2697 @@ pub struct UserActivityListResponse {
     pub per_page: usize,
     pub total_pages: usize,
 }
+
+// ============================================================================
+// ORGANIZATION DIRECTORY
+// ============================================================================
+
+/// Kind of organization behind a circuit member
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum OrganizationKind {
+    Farm,
+    Cooperative,
+    Processor,
+    Distributor,
+    Retailer,
+    Certifier,
+    Laboratory,
+    Other,
+}
+
+impl OrganizationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrganizationKind::Farm => "farm",
+            OrganizationKind::Cooperative => "cooperative",
+            OrganizationKind::Processor => "processor",
+            OrganizationKind::Distributor => "distributor",
+            OrganizationKind::Retailer => "retailer",
+            OrganizationKind::Certifier => "certifier",
+            OrganizationKind::Laboratory => "laboratory",
+            OrganizationKind::Other => "organization",
+        }
+    }
+}
+
+/// Certification held by an organization (e.g. organic, GlobalG.A.P.)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HeldCertification {
+    pub name: String,
+    pub issuer: Option<String>,
+    pub certificate_id: Option<String>,
+    pub valid_until: Option<DateTime<Utc>>,
+}
+
+impl HeldCertification {
+    pub fn is_valid_at(&self, at: DateTime<Utc>) -> bool {
+        self.valid_until.map(|until| until > at).unwrap_or(true)
+    }
+}
+
+/// Directory profile for a user/organization taking part in circuits.
+/// Keyed by user_id so it attaches to every circuit membership of that user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationProfile {
+    pub user_id: String,
+    pub workspace_id: Option<String>,
+    pub display_name: String,
+    pub org_type: OrganizationKind,
+    pub region: Option<String>,
+    pub certifications: Vec<HeldCertification>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OrganizationProfile {
+    pub fn new(user_id: String, display_name: String, org_type: OrganizationKind) -> Self {
+        let now = Utc::now();
+        Self {
+            user_id,
+            workspace_id: None,
+            display_name,
+            org_type,
+            region: None,
+            certifications: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Certifications that have not expired yet
+    pub fn active_certifications(&self) -> Vec<&HeldCertification> {
+        let now = Utc::now();
+        self.certifications
+            .iter()
+            .filter(|c| c.is_valid_at(now))
+            .collect()
+    }
+
+    pub fn holds_certification(&self, name: &str) -> bool {
+        self.active_certifications()
+            .iter()
+            .any(|c| c.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Human readable description for provenance views,
+    /// e.g. "certified organic processor in Region X"
+    pub fn summary(&self) -> String {
+        let certifications: Vec<String> = self
+            .active_certifications()
+            .iter()
+            .map(|c| c.name.to_lowercase())
+            .collect();
+
+        let mut summary = if certifications.is_empty() {
+            self.org_type.as_str().to_string()
+        } else {
+            format!(
+                "certified {} {}",
+                certifications.join(" & "),
+                self.org_type.as_str()
+            )
+        };
+
+        if let Some(region) = &self.region {
+            summary.push_str(&format!(" in {region}"));
+        }
+
+        summary
+    }
+
+    /// Case-insensitive free-text match over name, region and certifications
+    pub fn matches_text(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        self.display_name.to_lowercase().contains(&query)
+            || self
+                .region
+                .as_ref()
+                .is_some_and(|r| r.to_lowercase().contains(&query))
+            || self
+                .certifications
+                .iter()
+                .any(|c| c.name.to_lowercase().contains(&query))
+    }
+}
+
+/// Circuit membership joined with the member's directory profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitMemberProfile {
+    pub member: CircuitMember,
+    pub profile: Option<OrganizationProfile>,
+    pub summary: Option<String>,
+}
+
+// ============================================================================
+// MEMBER ATTESTATIONS
+// ============================================================================
+
+/// A signed statement by one member (the attester) about a claim held by
+/// another member (the subject), e.g. a certifier endorsing a farm's organic status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attestation {
+    pub attestation_id: Uuid,
+    pub attester_id: String,
+    pub subject_id: String,
+    pub claim_type: String,
+    pub claim_value: Option<String>,
+    pub circuit_id: Option<Uuid>,
+    pub zk_proof_id: Option<Uuid>,
+    pub statement: Option<String>,
+    /// BLAKE3 hash of the canonical signing payload
+    pub content_hash: String,
+    /// Hex-encoded ed25519 public key of the attester
+    pub public_key: String,
+    /// Hex-encoded ed25519 signature over `content_hash`
+    pub signature: String,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revocation_reason: Option<String>,
+}
+
+impl Attestation {
+    /// Canonical payload the attester signs. Field order is fixed so that
+    /// clients can reproduce the hash independently.
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate_content_hash(
+        attester_id: &str,
+        subject_id: &str,
+        claim_type: &str,
+        claim_value: Option<&str>,
+        circuit_id: Option<&Uuid>,
+        zk_proof_id: Option<&Uuid>,
+        statement: Option<&str>,
+        expires_at: Option<&DateTime<Utc>>,
+    ) -> String {
+        let payload = serde_json::json!({
+            "attester_id": attester_id,
+            "subject_id": subject_id,
+            "claim_type": claim_type,
+            "claim_value": claim_value,
+            "circuit_id": circuit_id,
+            "zk_proof_id": zk_proof_id,
+            "statement": statement,
+            "expires_at": expires_at.map(|e| e.to_rfc3339()),
+        });
+        hashing::hash(payload.to_string().as_bytes())
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        !self.is_revoked() && self.expires_at.is_none_or(|e| e > now)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active_at(Utc::now())
+    }
+}
+
+/// Push policy entry: the pusher must hold an active attestation for `claim_type`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RequiredAttestation {
+    pub claim_type: String,
+    /// Only count attestations issued by circuit members holding the Certify permission
+    #[serde(default)]
+    pub require_certifier: bool,
+}
+
+// ============================================================================
+// PREVIEW ENVIRONMENTS
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum PreviewStatus {
+    Active,
+    Promoted,
+    Discarded,
+}
+
+/// A production circuit and its configuration-only clone in a preview environment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewCircuit {
+    pub source_circuit_id: Uuid,
+    pub preview_circuit_id: Uuid,
+    /// Hash of the production configuration at clone time, used to detect drift
+    pub source_fingerprint: String,
+}
+
+/// Staging namespace holding configuration clones (no items or events) of production circuits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewEnvironment {
+    pub preview_id: Uuid,
+    pub namespace: String,
+    pub workspace_id: Option<String>,
+    pub owner_id: String,
+    pub circuits: Vec<PreviewCircuit>,
+    pub status: PreviewStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub promoted_at: Option<DateTime<Utc>>,
+}
+
+/// One configuration field that differs between a preview circuit and production
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewConfigChange {
+    pub source_circuit_id: Uuid,
+    pub preview_circuit_id: Uuid,
+    pub field: String,
+    pub production: serde_json::Value,
+    pub preview: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewDiff {
+    pub preview_id: Uuid,
+    pub changes: Vec<PreviewConfigChange>,
+    /// Production circuits whose configuration changed after the preview was cloned
+    pub drifted_circuits: Vec<Uuid>,
+    pub generated_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// SCALING SIGNALS
+// ============================================================================
+
+/// Background work queues whose pressure drives worker autoscaling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkQueue {
+    /// Realtime data lake entries waiting for verification
+    Verification,
+    /// Bulk (backfill) data lake entries waiting for verification
+    BulkVerification,
+    /// Realtime webhook deliveries waiting to be sent
+    WebhookDelivery,
+    /// Bulk webhook deliveries waiting to be sent
+    BulkWebhookDelivery,
+    /// Asynchronous adapter writes (mirrors) waiting to be anchored
+    AnchoringOutbox,
+    /// Queued ZK proofs waiting to be generated
+    ProofGeneration,
+}
+
+impl WorkQueue {
+    pub const ALL: [WorkQueue; 6] = [
+        WorkQueue::Verification,
+        WorkQueue::BulkVerification,
+        WorkQueue::WebhookDelivery,
+        WorkQueue::BulkWebhookDelivery,
+        WorkQueue::AnchoringOutbox,
+        WorkQueue::ProofGeneration,
+    ];
+
+    pub fn verification(priority: IngestionPriority) -> Self {
+        match priority {
+            IngestionPriority::Realtime => WorkQueue::Verification,
+            IngestionPriority::Bulk => WorkQueue::BulkVerification,
+        }
+    }
+
+    pub fn webhook_delivery(priority: IngestionPriority) -> Self {
+        match priority {
+            IngestionPriority::Realtime => WorkQueue::WebhookDelivery,
+            IngestionPriority::Bulk => WorkQueue::BulkWebhookDelivery,
+        }
+    }
+}
+
+/// Point-in-time pressure on one work queue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueSignal {
+    pub queue: WorkQueue,
+    /// Jobs enqueued but not yet completed
+    pub depth: u64,
+    #[serde(with = "crate::safe_json_numbers::u64_safe")]
+    pub enqueued_total: u64,
+    #[serde(with = "crate::safe_json_numbers::u64_safe")]
+    pub completed_total: u64,
+    #[serde(with = "crate::safe_json_numbers::u64_safe")]
+    pub failed_total: u64,
+    /// Jobs completed per second over the sampling window
+    pub processing_rate: f64,
+    /// Jobs enqueued per second over the sampling window
+    pub arrival_rate: f64,
+    pub average_latency_ms: f64,
+    /// Seconds to drain the current depth at the current processing rate
+    pub estimated_drain_seconds: Option<f64>,
+}
+
+/// Machine-readable queue pressure for orchestrators scaling worker replicas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScalingSignals {
+    pub generated_at: DateTime<Utc>,
+    pub window_seconds: u64,
+    pub queues: Vec<QueueSignal>,
+    pub total_depth: u64,
+    /// Slowest queue to drain; `None` if a backlogged queue processed nothing in the window
+    pub max_drain_seconds: Option<f64>,
+}
+
+// ============================================================================
+// LEGACY CONNECTORS
+// ============================================================================
+
+/// Where a legacy connector pulls records from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConnectorSource {
+    /// CSV exports dropped into the landing folder of an SFTP server
+    SftpCsv {
+        drop_folder: String,
+        #[serde(default = "default_csv_delimiter")]
+        delimiter: char,
+        /// Only files ending with this suffix are picked up
+        #[serde(default = "default_csv_suffix")]
+        file_suffix: String,
+    },
+    /// JSON endpoint polled on a schedule, resuming from a watermark
+    RestPoller {
+        url: String,
+        /// Sent verbatim as the `Authorization` header
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        authorization: Option<String>,
+        /// Dot-separated path to the record array; the body itself when absent
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        records_path: Option<String>,
+        /// Record field whose greatest value becomes the next watermark
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        watermark_field: Option<String>,
+        /// Query parameter the watermark is sent back in
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        watermark_param: Option<String>,
+    },
+}
+
+fn default_csv_delimiter() -> char {
+    ','
+}
+
+fn default_csv_suffix() -> String {
+    ".csv".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectorConfig {
+    pub connector_id: Uuid,
+    pub owner_id: String,
+    pub name: String,
+    pub source: ConnectorSource,
+    pub mapping_template_id: Uuid,
+    pub poll_interval_secs: u64,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Value clean-up step; a field's transforms run in order before it is mapped
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum FieldTransform {
+    Trim,
+    Uppercase,
+    Lowercase,
+    Replace {
+        from: String,
+        to: String,
+    },
+    Prefix {
+        value: String,
+    },
+    Suffix {
+        value: String,
+    },
+    /// Parse as a number; a decimal comma (`410,5`) is accepted
+    Number,
+    /// Parse `true/false`, `yes/no`, `sim/nao`, `s/n` or `1/0`
+    Boolean,
+    /// Re-format a date written in `format` (strftime syntax) as `YYYY-MM-DD`
+    Date {
+        format: String,
+    },
+    /// Translate coded values, e.g. `M` -> `male`; unknown codes use `default` or fail
+    Lookup {
+        values: HashMap<String, String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        default: Option<String>,
+    },
+}
+
+/// Maps one source field to a receipt identifier
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IdentifierFieldMapping {
+    pub source_field: String,
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transforms: Vec<FieldTransform>,
+    /// Used when the source field is missing or blank
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+/// Maps one source field to a (renamed, transformed) receipt payload field
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EnrichedFieldMapping {
+    pub source_field: String,
+    pub target_field: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transforms: Vec<FieldTransform>,
+    /// Used when the source field is missing or blank
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    /// Reject the record when the field is missing and there is no default
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Saved, reusable mapping from a legacy ERP export row to a receipt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MappingTemplate {
+    pub template_id: Uuid,
+    pub owner_id: String,
+    pub name: String,
+    pub identifier_fields: Vec<IdentifierFieldMapping>,
+    /// Payload fields produced with transforms/defaults; their source fields are not
+    /// copied through separately
+    #[serde(default)]
+    pub enriched_fields: Vec<EnrichedFieldMapping>,
+    /// Source field -> payload field; unlisted fields are kept under their source name
+    #[serde(default)]
+    pub field_renames: HashMap<String, String>,
+    /// Source fields left out of the receipt payload
+    #[serde(default)]
+    pub excluded_fields: Vec<String>,
+    /// Source field holding when the record actually happened
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub occurred_at_field: Option<String>,
+    #[serde(default = "default_connector_priority")]
+    pub priority: IngestionPriority,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn default_connector_priority() -> IngestionPriority {
+    IngestionPriority::Bulk
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectorRunStatus {
+    #[default]
+    Idle,
+    Running,
+    Succeeded,
+    /// The run finished but some records were rejected
+    PartiallyFailed,
+    Failed,
+}
+
+/// Progress and health of one connector, persisted between runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConnectorState {
+    pub connector_id: Uuid,
+    pub status: ConnectorRunStatus,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// Source-specific resume point, e.g. the greatest `updated_at` seen by a REST poller
+    pub watermark: Option<String>,
+    pub records_ingested: u64,
+    pub records_failed: u64,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+/// A run- or record-level failure shown on the connector error dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectorRunError {
+    pub error_id: Uuid,
+    pub connector_id: Uuid,
+    pub at: DateTime<Utc>,
+    /// Source record reference (e.g. `export.csv#12`); `None` for run-level failures
+    pub record_ref: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectorRunSummary {
+    pub connector_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub records_seen: usize,
+    pub receipts_created: usize,
+    pub records_failed: usize,
+    pub status: ConnectorRunStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectorDashboard {
+    pub connector: ConnectorConfig,
+    pub state: ConnectorState,
+    pub recent_errors: Vec<ConnectorRunError>,
+}
+
+/// What a mapping template makes of one sample record, without storing anything
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptPreview {
+    pub record_ref: String,
+    pub identifiers: Vec<Identifier>,
+    /// Receipt payload as it would be hashed
+    pub payload: serde_json::Value,
+    pub hash: String,
+    pub data_size: usize,
+    pub priority: IngestionPriority,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub occurred_at: Option<DateTime<Utc>>,
+    /// Non-fatal findings, e.g. an identifier failing its registry's format check
+    pub warnings: Vec<String>,
+    /// Why the record would be rejected; identifiers and payload are empty then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ============================================================================
+// CIRCUIT CHANGE FEEDS
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// Snapshot of the circuit taken when its first feed subscription was created
+    ItemSnapshot,
+    MemberSnapshot,
+    ItemAdded,
+    /// An item already in the circuit was pushed again
+    ItemUpdated,
+    Event,
+    MemberAdded,
+    MemberUpdated,
+}
+
+/// One entry of a circuit's change feed. `sequence` is gap-free and strictly
+/// increasing per circuit, so it doubles as the replay cursor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeRecord {
+    pub circuit_id: Uuid,
+    pub sequence: u64,
+    pub kind: ChangeKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dfid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member_id: Option<String>,
+    /// The changed item, event or member as stored
+    pub data: serde_json::Value,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl ChangeRecord {
+    /// A record not yet appended; storage assigns the sequence
+    pub fn new(circuit_id: Uuid, kind: ChangeKind, data: serde_json::Value) -> Self {
+        Self {
+            circuit_id,
+            sequence: 0,
+            kind,
+            dfid: None,
+            member_id: None,
+            data,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    pub fn with_dfid(mut self, dfid: &str) -> Self {
+        self.dfid = Some(dfid.to_string());
+        self
+    }
+
+    pub fn with_member(mut self, member_id: &str) -> Self {
+        self.member_id = Some(member_id.to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeFeedStatus {
+    Active,
+    Paused,
+    /// The last delivery failed; retried with backoff
+    Failing,
+}
+
+/// Pushes a circuit's change feed, in order and in batches, to an external endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeFeedSubscription {
+    pub subscription_id: Uuid,
+    pub circuit_id: Uuid,
+    pub owner_id: String,
+    pub url: String,
+    /// Key for the `X-Change-Feed-Signature` header; redacted in API responses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub batch_size: usize,
+    /// Sequence of the last record the endpoint acknowledged
+    pub cursor: u64,
+    pub status: ChangeFeedStatus,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub last_delivered_at: Option<DateTime<Utc>>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Body of a change-feed delivery, also returned by the pull endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeBatch {
+    pub circuit_id: Uuid,
+    /// Records after this sequence are included
+    pub cursor: u64,
+    /// Cursor to resume from once this batch is applied
+    pub next_cursor: u64,
+    pub records: Vec<ChangeRecord>,
+    pub has_more: bool,
+}
+
+// ============================================================================
+// ITEM SEARCH
+// ============================================================================
+
+/// Identifier filter; without `value` any identifier with this key matches
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IdentifierFilter {
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+/// Structured item search; all given criteria must match
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ItemSearchQuery {
+    /// Free text over enriched_data keys and values; every word must match as a prefix
+    pub text: Option<String>,
+    pub identifiers: Vec<IdentifierFilter>,
+    pub status: Option<ItemStatus>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    /// Items whose declared occurrence span overlaps this range
+    pub occurred_from: Option<DateTime<Utc>>,
+    pub occurred_to: Option<DateTime<Utc>>,
+    /// Only items pushed to this circuit
+    pub circuit_id: Option<Uuid>,
+}
+
+/// Columns an item listing reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ItemProjection {
+    /// Whole items with identifiers, source entries and enriched data
+    #[default]
+    Full,
+    /// Only what list views show; see [`ItemSummary`]
+    Summary,
+}
+
+/// An item as list views and dashboards show it, read without its
+/// identifiers, source entries or enriched data
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ItemSummary {
+    pub dfid: String,
+    pub status: ItemStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<&Item> for ItemSummary {
+    fn from(item: &Item) -> Self {
+        Self {
+            dfid: item.dfid.clone(),
+            status: item.status.clone(),
+            created_at: item.creation_timestamp,
+            updated_at: item.last_modified,
+        }
+    }
+}
+
+// ============================================================================
+// ANNOUNCEMENTS
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnouncementCategory {
+    Maintenance,
+    Feature,
+    Policy,
+    General,
+}
+
+/// Who receives an announcement; resolved to users when it is published
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AnnouncementAudience {
+    All,
+    Tiers { tiers: Vec<UserTier> },
+    Workspace { workspace_id: String },
+    Circuit { circuit_id: Uuid },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnouncementStatus {
+    Scheduled,
+    Published,
+    Cancelled,
+}
+
+/// Admin-authored broadcast, delivered to each recipient as an
+/// `Announcement` notification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Announcement {
+    pub announcement_id: Uuid,
+    pub title: String,
+    pub message: String,
+    pub category: AnnouncementCategory,
+    pub audience: AnnouncementAudience,
+    pub created_by: String,
+    pub publish_at: DateTime<Utc>,
+    /// Hidden from recipients after this time
+    pub expires_at: Option<DateTime<Utc>>,
+    pub status: AnnouncementStatus,
+    pub published_at: Option<DateTime<Utc>>,
+    /// Recipient user_id -> notification id; read state lives on the notification
+    #[serde(default)]
+    pub deliveries: HashMap<String, String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Announcement {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+// ============================================================================
+// WORKSPACE ENGAGEMENT
+// ============================================================================
+
+/// Why a member shows up in a workspace's "needs attention" list
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EngagementFlag {
+    /// No recorded activity in the window
+    Inactive,
+    /// Active, but pushed nothing to a circuit
+    NoSubmissions,
+    /// At least half of the reviewed submissions were rejected
+    HighRejectionRate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DailyActiveMembers {
+    pub date: NaiveDate,
+    pub active_members: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberEngagement {
+    pub user_id: String,
+    pub username: String,
+    pub active_days: usize,
+    pub last_active_at: Option<DateTime<Utc>>,
+    /// Circuit pushes requested in the window
+    pub submissions: usize,
+    pub approved_submissions: usize,
+    pub rejected_submissions: usize,
+    /// Median minutes from push to approval or rejection
+    pub median_minutes_to_verification: Option<f64>,
+    pub flags: Vec<EngagementFlag>,
+}
+
+/// Periodic engagement snapshot of a workspace's members
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceEngagement {
+    pub workspace_id: String,
+    pub computed_at: DateTime<Utc>,
+    pub window_days: i64,
+    pub member_count: usize,
+    /// One entry per day of the window, oldest first
+    pub daily_active_members: Vec<DailyActiveMembers>,
+    pub average_daily_active_members: f64,
+    pub submissions_per_member: f64,
+    pub median_minutes_to_verification: Option<f64>,
+    /// Actions by weekday (Monday first) and UTC hour
+    pub activity_heatmap: Vec<Vec<usize>>,
+    pub members: Vec<MemberEngagement>,
+}
+
+// ============================================================================
+// DATA EXPORTS
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DataExportDataset {
+    Items,
+    Events,
+    Receipts,
+    AuditEvents,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DataExportFormat {
+    Csv,
+    Jsonl,
+    Parquet,
+}
+
+impl DataExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DataExportFormat::Csv => "csv",
+            DataExportFormat::Jsonl => "jsonl",
+            DataExportFormat::Parquet => "parquet",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            DataExportFormat::Csv => "text/csv; charset=utf-8",
+            DataExportFormat::Jsonl => "application/x-ndjson",
+            DataExportFormat::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DataExportStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Background export of one dataset of a workspace, polled for progress and
+/// downloaded once completed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataExportJob {
+    pub job_id: Uuid,
+    pub requested_by: String,
+    pub workspace_id: String,
+    pub dataset: DataExportDataset,
+    pub format: DataExportFormat,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub status: DataExportStatus,
+    /// Known once the rows in scope have been collected
+    pub total_rows: Option<usize>,
+    pub rows_written: usize,
+    pub size_bytes: u64,
+    /// Written file on the API host; not exposed to clients
+    pub output_path: Option<String>,
+    /// Where the file went instead when the workspace has its own bucket
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bucket_object: Option<BucketObjectRef>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl DataExportJob {
+    /// Percentage of rows written, 0-100
+    pub fn progress(&self) -> f64 {
+        match (self.status, self.total_rows) {
+            (DataExportStatus::Completed, _) => 100.0,
+            (_, Some(0)) | (_, None) => 0.0,
+            (_, Some(total)) => (self.rows_written as f64 / total as f64 * 100.0).min(100.0),
+        }
+    }
+
+    pub fn file_name(&self) -> String {
+        let dataset = match self.dataset {
+            DataExportDataset::Items => "items",
+            DataExportDataset::Events => "events",
+            DataExportDataset::Receipts => "receipts",
+            DataExportDataset::AuditEvents => "audit_events",
+        };
+        format!(
+            "{}-{}-{}.{}",
+            self.workspace_id,
+            dataset,
+            self.created_at.format("%Y%m%dT%H%M%SZ"),
+            self.format.extension()
+        )
+    }
+}
+
+// ============================================================================
+// SLA / UPTIME
+// ============================================================================
+
+/// Part of the service whose availability is tracked
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum SlaComponent {
+    /// HTTP API; 5xx responses count as errors
+    Api,
+    /// Storage adapter operations
+    Adapters,
+    /// Asynchronous mirror writes to anchoring adapters
+    Anchoring,
+}
+
+impl SlaComponent {
+    pub const ALL: [SlaComponent; 3] = [
+        SlaComponent::Api,
+        SlaComponent::Adapters,
+        SlaComponent::Anchoring,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentStatus {
+    Operational,
+    Degraded,
+    Outage,
+}
+
+/// One hour of availability measurements for a component
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SlaWindow {
+    pub component: SlaComponent,
+    pub hour_start: DateTime<Utc>,
+    pub requests: u64,
+    pub errors: u64,
+    /// Minutes the recorder was running in this hour
+    pub observed_minutes: u32,
+    /// Observed minutes whose error rate made the component unavailable
+    pub down_minutes: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentStatusSummary {
+    pub component: SlaComponent,
+    pub status: ComponentStatus,
+    /// Error rate over the recent minutes the status is based on
+    pub recent_error_rate: f64,
+    pub uptime_24h_percent: f64,
+    pub uptime_30d_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceStatus {
+    pub status: ComponentStatus,
+    pub generated_at: DateTime<Utc>,
+    pub components: Vec<ComponentStatusSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DailyUptime {
+    pub date: NaiveDate,
+    pub component: SlaComponent,
+    pub uptime_percent: f64,
+    pub requests: u64,
+    pub errors: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentAvailability {
+    pub component: SlaComponent,
+    pub uptime_percent: f64,
+    pub error_rate: f64,
+    pub downtime_minutes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TierSlaAttainment {
+    pub tier: UserTier,
+    pub target_percent: f64,
+    pub attained_percent: f64,
+    pub met: bool,
+    pub allowed_downtime_minutes: u64,
+    pub downtime_minutes: u64,
+}
+
+/// SLA attainment for one calendar month (UTC)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlaMonthlyReport {
+    pub month: String,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub components: Vec<ComponentAvailability>,
+    pub tiers: Vec<TierSlaAttainment>,
+}
+
+// ============================================================================
+// EVENT SCHEMAS
+// ============================================================================
+
+/// JSON Schema a circuit requires of one event type's metadata. Registering
+/// again adds a version; the latest one applies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventSchema {
+    pub schema_id: Uuid,
+    pub circuit_id: Uuid,
+    pub event_type: EventType,
+    pub version: u32,
+    pub schema: serde_json::Value,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One way event metadata fails its schema
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SchemaViolation {
+    /// Location in the metadata, e.g. `$.weight_kg`
+    pub path: String,
+    pub message: String,
+}
+
+// ============================================================================
+// MAINTENANCE MODE
+// ============================================================================
+
+/// Operator-controlled write freeze. While active, mutating API requests are
+/// rejected; reads and anchor verification keep running.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct MaintenanceMode {
+    pub enabled: bool,
+    pub message: String,
+    /// Start of the scheduled window; `None` freezes as soon as enabled
+    pub starts_at: Option<DateTime<Utc>>,
+    /// Expected end of the window; the freeze lifts on its own afterwards
+    pub ends_at: Option<DateTime<Utc>>,
+    /// Announcement sent to users when the window was set
+    pub announcement_id: Option<Uuid>,
+    pub updated_by: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl MaintenanceMode {
+    /// Whether writes are frozen at `now`
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.enabled
+            && self.starts_at.is_none_or(|starts_at| starts_at <= now)
+            && self.ends_at.is_none_or(|ends_at| now < ends_at)
+    }
+}
+
+// ============================================================================
+// CONFIGURATION BUNDLES
+// ============================================================================
+
+/// Limits and SLA target of one tier as the exporting deployment defines them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TierConfig {
+    pub tier: UserTier,
+    pub limits: TierLimits,
+    pub sla_target_percent: Option<f64>,
+}
+
+/// A circuit's permission and adapter policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitPolicyConfig {
+    pub circuit_id: Uuid,
+    pub circuit_name: String,
+    pub permissions: CircuitPermissions,
+    pub adapter_config: Option<CircuitAdapterConfig>,
+}
+
+/// A circuit's webhooks; credentials are secret references
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitWebhookConfig {
+    pub circuit_id: Uuid,
+    pub circuit_name: String,
+    pub post_action_settings: PostActionSettings,
+}
+
+/// Everything a deployment is configured with, minus its data. Secrets are
+/// replaced by `secret-ref:` references the importer resolves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigBundleContents {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub exported_by: String,
+    pub source_environment: Option<String>,
+    pub adapter_configs: Vec<AdapterConfig>,
+    pub tiers: Vec<TierConfig>,
+    pub circuit_policies: Vec<CircuitPolicyConfig>,
+    pub event_schemas: Vec<EventSchema>,
+    pub webhooks: Vec<CircuitWebhookConfig>,
+}
+
+/// Signed, encrypted envelope around `ConfigBundleContents`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub format_version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Hex Ed25519 public key of the exporting deployment
+    pub signer_public_key: String,
+    /// Base64 AES-256-GCM nonce
+    pub nonce: String,
+    /// Base64 AES-256-GCM ciphertext of the contents
+    pub ciphertext: String,
+    /// Hex Ed25519 signature over `signing_input()`
+    pub signature: String,
+}
+
+impl ConfigBundle {
+    pub fn signing_input(&self) -> String {
+        format!(
+            "{}.{}.{}.{}",
+            self.format_version, self.signer_public_key, self.nonce, self.ciphertext
+        )
+    }
+}
+
+// ============================================================================
+// EVENT REPLAY
+// ============================================================================
+
+/// An event a replay could not apply
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplaySkip {
+    pub event_id: Uuid,
+    pub reason: String,
+}
+
+/// An item rebuilt from its event history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemReplay {
+    pub item: Item,
+    pub events_applied: usize,
+    pub skipped: Vec<ReplaySkip>,
+    /// `enriched_data` keys the rebuild adds, drops or changes
+    pub added_fields: Vec<String>,
+    pub removed_fields: Vec<String>,
+    pub changed_fields: Vec<String>,
+    pub status_changed: bool,
+}
+
+// ============================================================================
+// EVENT COMPACTION
+// ============================================================================
+
+/// An item's state read from its latest compaction snapshot plus the events
+/// recorded after it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactedItemState {
+    pub item: Item,
+    /// Compaction snapshot the read started from; None folds the full history
+    pub snapshot: Option<crate::snapshot_types::StateSnapshot>,
+    /// Events after the snapshot, in the order they were applied
+    pub tail_events: Vec<Event>,
+    pub skipped: Vec<ReplaySkip>,
+}
+
+// ============================================================================
+// KEY CEREMONIES
+// ============================================================================
+
+/// What a ceremony-managed key is used for
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyPurpose {
+    /// Signs anchoring transactions on Stellar testnet
+    StellarTestnetAnchoring,
+    /// Signs anchoring transactions on Stellar mainnet
+    StellarMainnetAnchoring,
+    /// Ed25519 key signing audit records and provenance manifests
+    AuditSigning,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ManagedKeyStatus {
+    /// Generated and waiting for confirmations
+    Pending,
+    Active,
+    /// Replaced by a newer active key for the same purpose
+    Retired,
+}
+
+/// An admin's confirmation that they checked a key's fingerprint
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct KeyConfirmation {
+    pub admin_id: String,
+    pub confirmed_at: DateTime<Utc>,
+}
+
+/// A key produced by a key ceremony. Only the public half is kept; the secret
+/// exists as Shamir shares and encrypted backups held by custodians.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManagedKey {
+    pub key_id: Uuid,
+    pub purpose: KeyPurpose,
+    /// `G...` address for Stellar keys, hex Ed25519 key otherwise
+    pub public_key: String,
+    /// BLAKE3 of the raw public key, hex
+    pub fingerprint: String,
+    pub status: ManagedKeyStatus,
+    /// Shares needed to restore the secret, out of `share_count`
+    pub share_threshold: u8,
+    pub share_count: u8,
+    pub required_confirmations: u32,
+    pub confirmations: Vec<KeyConfirmation>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub activated_at: Option<DateTime<Utc>>,
+    pub retired_at: Option<DateTime<Utc>>,
+}
+
+/// A key secret sealed under a custodian's passphrase
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct KeyBackup {
+    pub format_version: u32,
+    pub key_id: Uuid,
+    pub purpose: KeyPurpose,
+    pub fingerprint: String,
+    /// Base64 bcrypt salt the passphrase key is derived with
+    pub salt: String,
+    /// Base64 AES-256-GCM nonce
+    pub nonce: String,
+    /// Base64 AES-256-GCM ciphertext of the 32-byte secret
+    pub ciphertext: String,
+}
+
+// ============================================================================
+// CUSTOM EVENT TYPES
+// ============================================================================
+
+/// A domain event type a circuit accepts, with how clients should display it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomEventType {
+    pub circuit_id: Uuid,
+    /// Name events carry as `EventType::Custom`, e.g. "ColdChainBreach"
+    pub name: String,
+    pub display_name: String,
+    pub description: Option<String>,
+    /// Icon identifier for clients, e.g. "snowflake"
+    pub icon: Option<String>,
+    /// Hex color, e.g. "#1e88e5"
+    pub color: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// DOCUMENT NOTARIZATION
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotarizationStatus {
+    /// Hashed and recorded, waiting for the next anchoring batch
+    Pending,
+    Anchored,
+}
+
+/// Proof of existence of a document: only its hash is kept, never the content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentNotarization {
+    pub notarization_id: Uuid,
+    /// BLAKE3 of the document, hex
+    pub document_hash: String,
+    pub file_name: Option<String>,
+    pub content_type: Option<String>,
+    pub size: usize,
+    /// Item the document belongs to, which then carries a DocumentAnchored event
+    pub dfid: Option<String>,
+    pub event_id: Option<Uuid>,
+    pub receipt_id: Uuid,
+    pub status: NotarizationStatus,
+    pub batch_id: Option<Uuid>,
+    /// Path from the document hash to the batch's Merkle root
+    pub proof: Option<MerkleProof>,
+    pub submitted_by: String,
+    pub submitted_at: DateTime<Utc>,
+    pub anchored_at: Option<DateTime<Utc>>,
+}
+
+/// Notarizations anchored together under one Merkle root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotarizationBatch {
+    pub batch_id: Uuid,
+    pub merkle_root: String,
+    pub notarization_ids: Vec<Uuid>,
+    /// Where the root was written, e.g. IPFS and the Stellar transaction; empty
+    /// when no adapter is configured and the root is only kept in storage
+    pub locations: Vec<StorageLocation>,
+    pub created_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// ENRICHMENT MERGE POLICIES
+// ============================================================================
+
+/// How an incoming value for a field is combined with the value an item already has
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MergePolicy {
+    /// Keep the existing value
+    FirstWins,
+    /// Take the incoming value (the behavior before policies existed)
+    #[default]
+    LastWins,
+    /// Collect values into an array
+    Append,
+    /// Keep the existing value and report a differing incoming one as a conflict
+    RejectConflict,
+}
+
+/// A workspace's merge policies for enriching its items
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EnrichmentPolicy {
+    pub workspace_id: String,
+    pub default_policy: MergePolicy,
+    /// Overrides of `default_policy` by field name
+    pub field_policies: HashMap<String, MergePolicy>,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl EnrichmentPolicy {
+    pub fn policy_for(&self, field: &str) -> MergePolicy {
+        self.field_policies
+            .get(field)
+            .copied()
+            .unwrap_or(self.default_policy)
+    }
+}
+
+/// How identifier values are compared when looking for near duplicates
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FuzzyAlgorithm {
+    /// Edit distance over the values without case or separators
+    #[default]
+    NormalizedLevenshtein,
+    /// Tokens sorted, numbers without leading zeros, then edit distance
+    TokenSort,
+}
+
+impl FuzzyAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FuzzyAlgorithm::NormalizedLevenshtein => "normalized_levenshtein",
+            FuzzyAlgorithm::TokenSort => "token_sort",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct FuzzyMatchConfig {
+    #[serde(default)]
+    pub algorithm: FuzzyAlgorithm,
+    /// Similarity (0-1] from which a value counts as a likely duplicate
+    pub min_confidence: f64,
+}
+
+/// An incoming identifier whose value nearly equals one already mapped to an item
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FuzzyIdentifierMatch {
+    pub identifier: Identifier,
+    pub matched_identifier: Identifier,
+    pub dfid: String,
+    pub confidence: f64,
+    pub algorithm: FuzzyAlgorithm,
+}
+
+/// The dedup strategy verification uses for a workspace's entries; circuits
+/// may override it in their alias config
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkspaceDedupConfig {
+    pub workspace_id: String,
+    pub strategy: crate::identifier_types::DedupStrategyKind,
+    /// Off unless set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fuzzy_matching: Option<FuzzyMatchConfig>,
+    /// Without one, single matches always link and conflicts go to the item
+    /// with the most source entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_link: Option<AutoLinkPolicy>,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupSharingStatus {
+    /// Waiting for the other circuit's consent
+    Proposed,
+    Active,
+    Revoked,
+}
+
+/// A circuit's consent to a dedup sharing agreement
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DedupSharingConsent {
+    pub circuit_id: Uuid,
+    pub granted_by: String,
+    pub granted_at: DateTime<Utc>,
+}
+
+/// Two circuits sharing one dedup namespace, so the same identifier set pushed
+/// to either resolves to the same item. Fingerprints of both circuits are kept
+/// under `agreement_id` while the agreement is active.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DedupSharingAgreement {
+    pub agreement_id: Uuid,
+    pub circuits: [Uuid; 2],
+    pub status: DedupSharingStatus,
+    pub proposed_by: String,
+    pub proposed_at: DateTime<Utc>,
+    pub consents: Vec<DedupSharingConsent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activated_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl DedupSharingAgreement {
+    pub fn involves(&self, circuit_id: &Uuid) -> bool {
+        self.circuits.contains(circuit_id)
+    }
+
+    pub fn has_consent_of(&self, circuit_id: &Uuid) -> bool {
+        self.consents.iter().any(|c| &c.circuit_id == circuit_id)
+    }
+}
+
+/// One of a workspace's verification rules. `source` is what a submission
+/// declares it comes from (e.g. `sisbov-connector`); a rule without one
+/// applies to every source.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum VerificationRule {
+    /// Reject submissions without an identifier with this key
+    RequireIdentifier {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        source: Option<String>,
+        key: String,
+    },
+    /// Reject submissions whose JSON payload lacks any of these fields;
+    /// nested fields are written as dotted paths (`animal.weight`)
+    RequireFields {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        source: Option<String>,
+        fields: Vec<String>,
+    },
+    /// Hold submissions in the review queue instead of verifying them
+    ManualReview { source: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkspaceVerificationRules {
+    pub workspace_id: String,
+    pub rules: Vec<VerificationRule>,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// How sure verification must be before linking an entry to an existing item
+/// on its own; less certain matches wait in the conflict review queue
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct AutoLinkPolicy {
+    /// Confidence (0-1] from which a match links without review
+    pub min_confidence: f64,
+    /// Whether an entry matching several items may be merged into the leading
+    /// one when it is confident enough
+    #[serde(default)]
+    pub auto_merge_conflicts: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldMergeOutcome {
+    /// The item had no value for the field
+    Added,
+    Replaced,
+    Appended,
+    /// The incoming value was dropped, or equalled the existing one
+    Kept,
+    /// The incoming value differed under `RejectConflict` and was dropped
+    Rejected,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FieldMerge {
+    pub field: String,
+    pub policy: MergePolicy,
+    pub outcome: FieldMergeOutcome,
+    /// Value dropped by a rejected conflict, kept so it can be reviewed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejected_value: Option<serde_json::Value>,
+}
+
+/// What an enrichment did to each incoming field, attached to its Enriched event
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct MergeReport {
+    /// Workspace whose policy applied; None means the default last-wins policy
+    pub workspace_id: Option<String>,
+    pub fields: Vec<FieldMerge>,
+}
+
+impl MergeReport {
+    pub fn conflicts(&self) -> impl Iterator<Item = &FieldMerge> {
+        self.fields
+            .iter()
+            .filter(|field| field.outcome == FieldMergeOutcome::Rejected)
+    }
+}
+
+// ============================================================================
+// EVENT SIGNING KEYS
+// ============================================================================
+
+/// Who may sign events with a key: one user, or any member of a workspace
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SigningKeyOwner {
+    User { user_id: String },
+    Workspace { workspace_id: String },
+}
+
+/// A public key registered for signing events. The private half never reaches
+/// the server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventSigningKey {
+    pub key_id: Uuid,
+    pub owner: SigningKeyOwner,
+    /// Hex ed25519 public key
+    pub public_key: String,
+    pub registered_by: String,
+    pub registered_at: DateTime<Utc>,
+    /// Revoked keys sign no new events; earlier signatures stay verifiable
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+// ============================================================================
+// ANCHORING COSTS
+// ============================================================================
+
+/// Chain an anchoring transaction is written to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AnchoringNetwork {
+    StellarTestnet,
+    StellarMainnet,
+}
+
+impl AnchoringNetwork {
+    /// Network an adapter anchors on, if it anchors on one
+    pub fn for_adapter(adapter_type: &AdapterType) -> Option<Self> {
+        match adapter_type {
+            AdapterType::StellarMainnetIpfs | AdapterType::StellarMainnetLocal => {
+                Some(AnchoringNetwork::StellarMainnet)
+            }
+            AdapterType::StellarTestnetIpfs | AdapterType::StellarTestnetLocal => {
+                Some(AnchoringNetwork::StellarTestnet)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Inclusion fee percentile bid on anchoring transactions
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeStrategy {
+    /// p10: cheapest, may wait several ledgers under load
+    Economy,
+    /// p50
+    Standard,
+    /// p90: included in the next ledger almost always
+    Priority,
+}
+
+/// How quickly and how expensively a circuit's data must be anchored
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AnchoringSla {
+    pub circuit_id: Uuid,
+    /// Longest a pushed record may wait before it is anchored
+    pub max_anchor_delay_secs: u64,
+    /// Spend above this per anchored event is reported as over budget
+    pub max_fee_per_event_stroops: Option<u64>,
+    /// Networks the circuit accepts anchors on; empty means its adapter's network
+    #[serde(default)]
+    pub allowed_networks: Vec<AnchoringNetwork>,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Inclusion fees recently charged on a network, in stroops
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeeSample {
+    pub network: AnchoringNetwork,
+    pub p10_stroops: u64,
+    pub p50_stroops: u64,
+    pub p90_stroops: u64,
+    pub sampled_at: DateTime<Utc>,
+}
+
+impl FeeSample {
+    pub fn inclusion_fee(&self, strategy: FeeStrategy) -> u64 {
+        match strategy {
+            FeeStrategy::Economy => self.p10_stroops,
+            FeeStrategy::Standard => self.p50_stroops,
+            FeeStrategy::Priority => self.p90_stroops,
+        }
+    }
+}
+
+/// When and how the optimizer recommends anchoring a circuit's data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchoringPlan {
+    pub circuit_id: Uuid,
+    pub network: AnchoringNetwork,
+    pub fee_strategy: FeeStrategy,
+    /// Records anchored together under one Merkle root per transaction
+    pub batch_size: u64,
+    /// Longest a batch stays open before it is anchored, full or not
+    pub flush_interval_secs: u64,
+    pub expected_events_per_hour: f64,
+    pub estimated_fee_per_batch_stroops: u64,
+    pub estimated_fee_per_event_stroops: u64,
+    /// False when even the cheapest plan meeting the delay exceeds the SLA's fee cap
+    pub within_fee_cap: bool,
+    pub reasons: Vec<String>,
+    pub planned_at: DateTime<Utc>,
+}
+
+/// On-chain spend of one anchoring write
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AnchoringCostRecord {
+    pub record_id: Uuid,
+    /// None for anchors not tied to a circuit (e.g. notarization batches)
+    pub circuit_id: Option<Uuid>,
+    pub network: AnchoringNetwork,
+    pub transactions: u32,
+    pub anchored_events: u64,
+    /// Estimated from the fee sample current at the time of the write
+    pub fee_stroops: u64,
+    pub anchored_at: DateTime<Utc>,
+}
+
+/// Anchoring spend of one day
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AnchoringCostPeriod {
+    pub date: NaiveDate,
+    pub transactions: u64,
+    pub anchored_events: u64,
+    pub fee_stroops: u64,
+    pub fee_per_event_stroops: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchoringCostReport {
+    pub circuit_id: Option<Uuid>,
+    pub network: Option<AnchoringNetwork>,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub periods: Vec<AnchoringCostPeriod>,
+    pub total_transactions: u64,
+    pub total_anchored_events: u64,
+    pub total_fee_stroops: u64,
+    pub fee_per_event_stroops: Option<f64>,
+    /// Average daily spend over the report window, times 30
+    pub projected_monthly_fee_stroops: u64,
+    pub projected_monthly_xlm: f64,
+    pub generated_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// ITEM LIFECYCLE
+// ============================================================================
+
+/// A stage of a lifecycle, reached when an event of one of its types is recorded
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LifecycleStage {
+    pub name: String,
+    pub entered_by: Vec<EventType>,
+}
+
+/// A workspace's lifecycle, e.g. planted → harvested → processed → shipped → sold.
+/// Items advance through `stages` in order.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LifecycleDefinition {
+    pub workspace_id: String,
+    pub stages: Vec<LifecycleStage>,
+    /// Whether items may advance past stages they never reached; moving back is
+    /// always out of order
+    pub allow_skipping: bool,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl LifecycleDefinition {
+    /// Index of the stage an event of this type enters
+    pub fn stage_for(&self, event_type: &EventType) -> Option<usize> {
+        self.stages
+            .iter()
+            .position(|stage| stage.entered_by.contains(event_type))
+    }
+
+    /// Whether moving from stage `from` (None before the first) to stage `to` keeps order
+    pub fn is_in_order(&self, from: Option<usize>, to: usize) -> bool {
+        let next = from.map_or(0, |from| from + 1);
+        to == next || (self.allow_skipping && to > next)
+    }
+}
+
+/// One move of an item between stages
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LifecycleTransition {
+    pub from_stage: Option<String>,
+    pub to_stage: String,
+    /// Event that triggered the move; None for a stage set by hand
+    pub event_id: Option<Uuid>,
+    pub at: DateTime<Utc>,
+    /// False for out-of-order moves, which are held for review instead of applied
+    pub applied: bool,
+    /// Review entry of a held move
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub set_by: Option<String>,
+}
+
+/// Where an item stands in its workspace's lifecycle
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ItemLifecycle {
+    pub dfid: String,
+    pub workspace_id: String,
+    pub stage: Option<String>,
+    pub stage_entered_at: Option<DateTime<Utc>>,
+    pub transitions: Vec<LifecycleTransition>,
+}
+
+// ============================================================================
+// CIRCUIT ACCESS REVIEWS
+// ============================================================================
+
+/// How a principal came to have access to a circuit
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessPrincipalKind {
+    Owner,
+    Member,
+    /// API key acting for a member
+    ApiKey,
+    /// Recipient of an item shared out of the circuit
+    ItemShare,
+    /// External partner holding a circuit-scoped read token
+    PartnerToken,
+    /// Anyone reaching the circuit's published items
+    Public,
+}
+
+/// One principal with effective access to a circuit
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccessGrant {
+    pub kind: AccessPrincipalKind,
+    /// User id, API key id, or "public"
+    pub principal_id: String,
+    /// Member an API key acts for, or who shared an item
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub granted_by: Option<String>,
+    /// Permission names, e.g. "Push", or "read" for shared items
+    pub permissions: Vec<String>,
+    /// Items the grant is limited to; empty for the whole circuit
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<String>,
+    /// Human-readable account of the grant, e.g. "custom role Auditor"
+    pub granted_via: String,
+    pub granted_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Everyone with access to a circuit at one moment, for access reviews
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitAccessReport {
+    pub report_id: Uuid,
+    pub circuit_id: Uuid,
+    pub circuit_name: String,
+    pub generated_at: DateTime<Utc>,
+    /// User who asked for it, or None when the review schedule produced it
+    pub generated_by: Option<String>,
+    pub grants: Vec<AccessGrant>,
+}
+
+/// Read-only token a circuit owner mints for an external partner. It reaches
+/// only the circuit's items, through the partner API, without a user account.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PartnerToken {
+    pub token_id: Uuid,
+    pub circuit_id: Uuid,
+    pub partner_name: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    /// First characters of the token, for telling tokens apart
+    pub token_prefix: String,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoked_by: Option<String>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub use_count: u64,
+}
+
+impl PartnerToken {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+}
+
+/// One request made with a partner token
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PartnerTokenUsage {
+    pub usage_id: Uuid,
+    pub token_id: Uuid,
+    pub circuit_id: Uuid,
+    /// "get_circuit", "list_items" or "get_item"
+    pub action: String,
+    pub dfid: Option<String>,
+    pub client_ip: Option<String>,
+    pub used_at: DateTime<Utc>,
+}
+
+/// What a circuit comment thread is about
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CommentTarget {
+    Event {
+        event_id: Uuid,
+    },
+    /// A push or pull waiting for approval
+    PendingOperation {
+        operation_id: Uuid,
+    },
+}
+
+/// A comment in a circuit's thread on an event or pending operation. Only
+/// members of the circuit can read or write it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CircuitComment {
+    pub comment_id: Uuid,
+    pub circuit_id: Uuid,
+    pub target: CommentTarget,
+    pub author_id: String,
+    pub body: String,
+    /// Members mentioned as `@member_id`; each was notified
+    pub mentions: Vec<String>,
+    /// Comment in the same thread this one answers
+    pub reply_to: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Recurring access review of a circuit
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccessReviewSchedule {
+    pub circuit_id: Uuid,
+    pub interval_days: u32,
+    pub next_run_at: DateTime<Utc>,
+    pub last_report_id: Option<Uuid>,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// CIRCUIT DATA QUALITY
+// ============================================================================
+
+/// Kind of data quality problem found in a circuit
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DataQualityIssueKind {
+    /// Items lacking identifiers the circuit requires
+    MissingIdentifiers,
+    /// Items that reached a lifecycle stage without an event entering it
+    MissingStageEvents,
+    /// Incoming data about the circuit's items that failed verification
+    VerificationFailures,
+}
+
+/// Something members can fix to raise the circuit's score
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DataQualityIssue {
+    pub kind: DataQualityIssueKind,
+    pub severity: QualitySeverity,
+    pub message: String,
+    pub affected_items: usize,
+    /// Some of the affected items, to start fixing from
+    pub sample_dfids: Vec<String>,
+}
+
+/// How many of the items that reached a lifecycle stage carry an event entering it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StageCoverage {
+    pub stage: String,
+    pub items_reached: usize,
+    pub items_with_events: usize,
+}
+
+impl StageCoverage {
+    pub fn coverage(&self) -> f64 {
+        if self.items_reached == 0 {
+            1.0
+        } else {
+            self.items_with_events as f64 / self.items_reached as f64
+        }
+    }
+}
+
+/// Data quality of a circuit's items at one moment. Ratios are between 0 and
+/// 1 and are None when there was nothing to measure.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DataQualityReport {
+    pub report_id: Uuid,
+    pub circuit_id: Uuid,
+    pub generated_at: DateTime<Utc>,
+    /// User who asked for it, or None for the weekly run
+    pub generated_by: Option<String>,
+    pub item_count: usize,
+    /// Share of items carrying every identifier the circuit requires
+    pub identifier_completeness: Option<f64>,
+    pub stage_coverage: Vec<StageCoverage>,
+    /// Share of reached lifecycle stages backed by an event, over all stages
+    pub event_coverage: Option<f64>,
+    pub verification_attempts: usize,
+    pub verification_failures: usize,
+    pub verification_failure_rate: Option<f64>,
+    /// 0 to 100; None when the circuit has no items
+    pub score: Option<f64>,
+    /// Score difference from the previous report
+    pub score_change: Option<f64>,
+    pub issues: Vec<DataQualityIssue>,
+}
+
+// ============================================================================
+// DASHBOARD METRICS
+// ============================================================================
+
+/// What a custom dashboard metric measures over a circuit's events
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MetricMeasure {
+    /// Number of events of one type
+    EventCount { event_type: EventType },
+    /// Mean of a numeric metadata field, over events of one type or all events
+    MetadataAverage {
+        field: String,
+        event_type: Option<EventType>,
+    },
+}
+
+/// Length of the buckets a metric is evaluated in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricPeriod {
+    Day,
+    /// Weeks start on Monday
+    Week,
+    Month,
+}
+
+/// Metric a workspace owner defined for the workspace's dashboards
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetricDefinition {
+    pub metric_id: Uuid,
+    pub workspace_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub circuit_id: Uuid,
+    pub measure: MetricMeasure,
+    pub period: MetricPeriod,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Value of a metric over one period; averages of periods without samples are None
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetricPoint {
+    pub period_start: DateTime<Utc>,
+    pub value: Option<f64>,
+    pub sample_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetricSeries {
+    pub metric_id: Uuid,
+    pub name: String,
+    pub period: MetricPeriod,
+    pub points: Vec<MetricPoint>,
+}
+
+// ============================================================================
+// DEVICE BACKUPS
+// ============================================================================
+
+/// Encrypted copy of a field device's local database. The server stores the
+/// blob as uploaded and never sees its key.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeviceBackup {
+    pub backup_id: Uuid,
+    pub user_id: String,
+    pub device_id: String,
+    /// 1 for a device's first backup, counting up
+    pub version: u32,
+    pub size_bytes: u64,
+    /// BLAKE3 hex of the encrypted blob
+    pub content_hash: String,
+    /// Cipher the client declared, e.g. "aes-256-gcm"
+    pub encryption: String,
+    /// Blob file on the API host; not exposed to clients
+    pub blob_path: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// FEDERATION
+// ============================================================================
+
+/// Kinds of data a federation agreement lets cross into or out of a circuit
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum FederationDataClass {
+    /// Items with their identifiers, aliases and status
+    Items,
+    /// The `enriched_data` of items
+    EnrichedData,
+    /// Public and circuit-only events
+    Events,
+    /// Private and direct events
+    PrivateEvents,
+}
+
+impl FederationDataClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FederationDataClass::Items => "items",
+            FederationDataClass::EnrichedData => "enriched_data",
+            FederationDataClass::Events => "events",
+            FederationDataClass::PrivateEvents => "private_events",
+        }
+    }
+
+    /// Agreement of circuits mapped without naming their data classes:
+    /// everything but private events
+    pub fn default_agreement() -> Vec<FederationDataClass> {
+        vec![
+            FederationDataClass::Items,
+            FederationDataClass::EnrichedData,
+            FederationDataClass::Events,
+        ]
+    }
+}
+
+/// A circuit exchanged with a peer. Each deployment has its own circuit ids, so
+/// both sides are named.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FederatedCircuit {
+    pub local_circuit_id: Uuid,
+    pub remote_circuit_id: Uuid,
+    /// Sequence of the peer's change feed imported up to
+    #[serde(default)]
+    pub cursor: u64,
+    /// The circuit's federation agreement: data classes served to and accepted
+    /// from the peer
+    #[serde(default = "FederationDataClass::default_agreement")]
+    pub data_classes: Vec<FederationDataClass>,
+}
+
+impl FederatedCircuit {
+    pub fn allows(&self, class: FederationDataClass) -> bool {
+        self.data_classes.contains(&class)
+    }
+}
+
+/// How far a peer's node key is trusted. Only trusted peers are synced with.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FederationTrust {
+    /// Known from a handshake, waiting for an admin to confirm its key
+    Pending,
+    #[default]
+    Trusted,
+    /// Refused; handshakes from the node are rejected
+    Revoked,
+}
+
+impl FederationTrust {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FederationTrust::Pending => "pending",
+            FederationTrust::Trusted => "trusted",
+            FederationTrust::Revoked => "revoked",
+        }
+    }
+}
+
+/// Another deployment this node exchanges circuits with
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FederationPeer {
+    pub peer_id: Uuid,
+    pub name: String,
+    /// The peer's `FEDERATION_NODE_ID`
+    pub node_id: String,
+    /// Hex-encoded ed25519 key the peer signs its requests and records with
+    pub public_key: String,
+    /// Base URL of the peer's API
+    pub url: String,
+    pub circuits: Vec<FederatedCircuit>,
+    pub enabled: bool,
+    #[serde(default)]
+    pub trust: FederationTrust,
+    pub last_synced_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FederationPeer {
+    pub fn circuit_by_local(&self, circuit_id: &Uuid) -> Option<&FederatedCircuit> {
+        self.circuits
+            .iter()
+            .find(|circuit| circuit.local_circuit_id == *circuit_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FederatedRecordKind {
+    Item,
+    Event,
+}
+
+/// An item or event as exchanged between nodes, signed by the node that
+/// produced this version of it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FederatedRecord {
+    pub kind: FederatedRecordKind,
+    /// DFID of an item, event id of an event
+    pub id: String,
+    /// Position in the serving node's change feed
+    pub sequence: u64,
+    pub data: serde_json::Value,
+    /// BLAKE3 hex of the record's content
+    pub content_hash: String,
+    pub origin_node: String,
+    /// Hex ed25519 signature of `origin_node` over the record's provenance message
+    pub signature: String,
+}
+
+impl FederatedRecord {
+    /// Key provenance and conflicts are tracked under, e.g. `item:DFID-1`
+    pub fn record_key(&self) -> String {
+        match self.kind {
+            FederatedRecordKind::Item => format!("item:{}", self.id),
+            FederatedRecordKind::Event => format!("event:{}", self.id),
+        }
+    }
+}
+
+/// Page of a circuit's changes served to a peer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FederationBatch {
+    /// Node serving the batch
+    pub node_id: String,
+    pub circuit_id: Uuid,
+    pub cursor: u64,
+    pub next_cursor: u64,
+    pub records: Vec<FederatedRecord>,
+    pub has_more: bool,
+}
+
+/// Sent by a node introducing itself to another deployment. The signature over
+/// the handshake message proves the sender holds the key it presents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FederationHandshake {
+    pub node_id: String,
+    pub public_key: String,
+    /// Base URL the receiving node can pull from
+    pub url: String,
+    /// Unix seconds
+    pub timestamp: i64,
+    pub nonce: String,
+    pub signature: String,
+}
+
+/// The receiving node's answer: its own identity, signed over the requester's
+/// nonce
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FederationHandshakeReply {
+    pub node_id: String,
+    pub public_key: String,
+    pub nonce: String,
+    pub signature: String,
+}
+
+/// Which node produced the version of a record this node holds
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FederationProvenance {
+    pub record_key: String,
+    pub origin_node: String,
+    pub content_hash: String,
+    pub signature: String,
+    /// Peer the record was imported from
+    pub peer_id: Uuid,
+    pub imported_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FederationConflictStatus {
+    Open,
+    AcceptedRemote,
+    KeptLocal,
+}
+
+/// A remote version that was not applied because the local record changed
+/// independently
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FederationConflict {
+    pub conflict_id: Uuid,
+    pub peer_id: Uuid,
+    pub local_circuit_id: Uuid,
+    pub record_key: String,
+    pub local_hash: String,
+    pub remote: FederatedRecord,
+    pub status: FederationConflictStatus,
+    pub detected_at: DateTime<Utc>,
+    pub resolved_by: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// Current attribute commitments of an item. The root is notarized for the
+/// item, so disclosures made from it can be checked against the anchor.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ItemAttributeCommitments {
+    pub dfid: String,
+    pub commitments: crate::zk_proof_engine::AttributeCommitments,
+    /// Notarization of the root
+    pub notarization_id: Uuid,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Attributes of an item an owner chose to reveal to a recipient. Only the
+/// token's BLAKE3 hash is stored; the token itself is returned once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectiveDisclosure {
+    pub disclosure_id: Uuid,
+    pub token_hash: String,
+    pub dfid: String,
+    /// Commitment root the attributes open against
+    pub root: String,
+    pub notarization_id: Uuid,
+    pub attributes: Vec<crate::zk_proof_engine::DisclosedAttribute>,
+    /// Who the disclosure is meant for, as noted by the owner
+    pub recipient: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// One hour of a workspace's API usage, with request counts per endpoint
+/// (`"METHOD /route/:param"`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkspaceUsageWindow {
+    pub workspace_id: String,
+    pub hour_start: DateTime<Utc>,
+    pub requests: u64,
+    /// Successful DELETE requests
+    pub deletions: u64,
+    pub endpoints: HashMap<String, u64>,
+}
+
+/// Tunable thresholds of a workspace's usage anomaly detection. Workspaces
+/// without one use the defaults of `usage_anomaly_engine`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UsageAnomalyBaseline {
+    pub workspace_id: String,
+    /// An hour is a spike at this multiple of the baseline hourly average
+    pub spike_factor: f64,
+    /// Hours with fewer requests are never spikes
+    pub min_spike_requests: u64,
+    /// Deletions in one hour that count as a mass deletion
+    pub mass_deletion_threshold: u64,
+    /// Hours of history the baseline is computed over
+    pub baseline_hours: u32,
+    pub alert_novel_endpoints: bool,
+    pub updated_by: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageAnomalyKind {
+    RequestSpike,
+    NovelEndpoint,
+    MassDeletion,
+}
+
+impl UsageAnomalyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageAnomalyKind::RequestSpike => "request_spike",
+            UsageAnomalyKind::NovelEndpoint => "novel_endpoint",
+            UsageAnomalyKind::MassDeletion => "mass_deletion",
+        }
+    }
+}
+
+/// A sudden change in a workspace's API usage, raised at most once per kind
+/// (and endpoint) per hour
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UsageAnomaly {
+    pub anomaly_id: Uuid,
+    pub workspace_id: String,
+    pub kind: UsageAnomalyKind,
+    pub hour_start: DateTime<Utc>,
+    /// The endpoint a novel endpoint anomaly is about
+    pub endpoint: Option<String>,
+    pub observed: u64,
+    /// What the baseline led to expect, e.g. the average hourly requests
+    pub expected: f64,
+    pub description: String,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// A signed, expiring link that opens one ZK proof without an account. The
+/// link's token is not stored: it is the share id and expiry signed with a
+/// key derived from the server secret.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProofShareLink {
+    pub share_id: Uuid,
+    pub proof_id: Uuid,
+    pub created_by: String,
+    /// Who the link is meant for, as noted by the prover
+    pub recipient: Option<String>,
+    /// Whether the public events of the proof's item are returned
+    pub include_timeline: bool,
+    /// Opens allowed before the link stops working; unlimited if `None`
+    pub max_views: Option<u32>,
+    pub views: u32,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub last_viewed_at: Option<DateTime<Utc>>,
+}
+
+/// Bucket size of sensor rollups
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SensorResolution {
+    Minute,
+    Hour,
+    Day,
+}
+
+impl SensorResolution {
+    pub const ALL: [SensorResolution; 3] = [
+        SensorResolution::Minute,
+        SensorResolution::Hour,
+        SensorResolution::Day,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SensorResolution::Minute => "minute",
+            SensorResolution::Hour => "hour",
+            SensorResolution::Day => "day",
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        match self {
+            SensorResolution::Minute => Duration::minutes(1),
+            SensorResolution::Hour => Duration::hours(1),
+            SensorResolution::Day => Duration::days(1),
+        }
+    }
+
+    /// Start of the bucket `at` falls in (UTC)
+    pub fn bucket_start(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let span = self.duration().num_seconds();
+        let secs = at.timestamp();
+        DateTime::from_timestamp(secs - secs.rem_euclid(span), 0).unwrap_or(at)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct SensorReading {
+    pub timestamp: DateTime<Utc>,
+    pub value: f64,
+}
+
+/// The raw readings of one series within one hour, stored as a single block
+/// so high-frequency data does not become a row (or an event) per reading
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SensorChunk {
+    pub dfid: String,
+    pub series: String,
+    pub chunk_start: DateTime<Utc>,
+    /// Oldest first, at most one per timestamp
+    pub readings: Vec<SensorReading>,
+}
+
+/// Aggregate of a series' readings within one bucket
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SensorRollup {
+    pub dfid: String,
+    pub series: String,
+    pub resolution: SensorResolution,
+    pub bucket_start: DateTime<Utc>,
+    pub count: u64,
+    pub min: f64,
+    pub max: f64,
+    pub sum: f64,
+}
+
+impl SensorRollup {
+    pub fn avg(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum / self.count as f64
+        }
+    }
+}
+
+/// Where a series' latest reading stands against its thresholds
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SensorThresholdState {
+    #[default]
+    Normal,
+    Above,
+    Below,
+}
+
+impl SensorThresholdState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SensorThresholdState::Normal => "normal",
+            SensorThresholdState::Above => "above",
+            SensorThresholdState::Below => "below",
+        }
+    }
+}
+
+/// A named stream of readings about an item, e.g. "temperature" of a
+/// container. Only crossings of its thresholds are recorded as events.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SensorSeries {
+    pub dfid: String,
+    pub series: String,
+    pub unit: Option<String>,
+    pub upper_threshold: Option<f64>,
+    pub lower_threshold: Option<f64>,
+    pub state: SensorThresholdState,
+    pub last_reading: Option<SensorReading>,
+    pub reading_count: u64,
+    /// End of the last day summarized into an event
+    #[serde(default)]
+    pub summarized_through: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// WORKSPACE BUCKETS
+// ============================================================================
+
+/// An Enterprise workspace's own S3-compatible bucket. Attachments and exports
+/// of the workspace are written there through presigned URLs and the engine
+/// keeps only references and hashes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkspaceBucketConfig {
+    pub workspace_id: String,
+    pub bucket: String,
+    pub region: String,
+    /// Base URL of an S3-compatible service (MinIO, R2); AWS S3 when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    pub access_key_id: String,
+    /// Never returned by the API
+    pub secret_access_key: String,
+    /// Prepended to every object key, e.g. `defarm/`
+    #[serde(default)]
+    pub key_prefix: String,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An object in a workspace bucket
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BucketObjectRef {
+    pub bucket: String,
+    pub key: String,
+    /// `sha256:<hex>`, checked by the bucket on upload
+    pub content_hash: String,
+    pub size: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(user_id: &str, tier: UserTier, last_login: Option<DateTime<Utc>>) -> UserAccount {
+        UserAccount {
+            user_id: user_id.to_string(),
+            username: user_id.to_string(),
+            email: format!("{user_id}@example.com"),
+            password_hash: "hash".to_string(),
+            tier: tier.clone(),
+            status: AccountStatus::Active,
+            credits: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_login,
+            subscription: None,
+            limits: TierLimits::for_tier(&tier),
+            is_admin: false,
+            workspace_id: None,
+            available_adapters: None,
+            locale: None,
+        }
+    }
+
+    fn transaction(
+        transaction_type: CreditTransactionType,
+        amount: i64,
+        timestamp: DateTime<Utc>,
+    ) -> CreditTransaction {
+        CreditTransaction {
+            transaction_id: Uuid::new_v4().to_string(),
+            user_id: "alice".to_string(),
+            amount,
+            transaction_type,
+            description: String::new(),
+            operation_type: None,
+            operation_id: None,
+            timestamp,
+            balance_after: 0,
+        }
+    }
+
+    fn record(adapter_type: AdapterType) -> StorageRecord {
+        StorageRecord {
+            adapter_type,
+            storage_location: StorageLocation::Local {
+                id: Uuid::new_v4().to_string(),
+            },
+            stored_at: Utc::now(),
+            triggered_by: "item_creation".to_string(),
+            triggered_by_id: None,
+            events_range: None,
+            is_active: true,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_system_statistics_tally_counts() {
+        let now = Utc::now();
+        let users = vec![
+            user("alice", UserTier::Basic, Some(now - Duration::hours(1))),
+            user("bob", UserTier::Basic, Some(now - Duration::days(3))),
+            user(
+                "carol",
+                UserTier::Enterprise,
+                Some(now - Duration::days(45)),
+            ),
+            user("dave", UserTier::Professional, None),
+        ];
+        let transactions = vec![
+            transaction(
+                CreditTransactionType::Consumption,
+                -5,
+                now - Duration::hours(2),
+            ),
+            transaction(
+                CreditTransactionType::Consumption,
+                -7,
+                now - Duration::hours(23),
+            ),
+            transaction(
+                CreditTransactionType::Consumption,
+                -11,
+                now - Duration::hours(25),
+            ),
+            transaction(
+                CreditTransactionType::Purchase,
+                100,
+                now - Duration::hours(1),
+            ),
+        ];
+        let records = vec![
+            record(AdapterType::IpfsIpfs),
+            record(AdapterType::IpfsIpfs),
+            record(AdapterType::StellarTestnetIpfs),
+        ];
+
+        let stats = SystemStatistics::tally(&users, &transactions, &records, 12, 3, now);
+
+        assert_eq!(stats.total_users, 4);
+        assert_eq!(stats.active_users_24h, 1);
+        assert_eq!(stats.active_users_30d, 2);
+        assert_eq!(stats.total_items, 12);
+        assert_eq!(stats.total_circuits, 3);
+        assert_eq!(stats.total_storage_operations, 3);
+        assert_eq!(stats.credits_consumed_24h, 12);
+        assert_eq!(stats.tier_distribution.get(&UserTier::Basic), Some(&2));
+        assert_eq!(stats.tier_distribution.get(&UserTier::Enterprise), Some(&1));
+        assert_eq!(
+            stats.tier_distribution.get(&UserTier::Professional),
+            Some(&1)
+        );
+        assert_eq!(
+            stats.adapter_usage_stats.get(&AdapterType::IpfsIpfs),
+            Some(&2)
+        );
+        assert_eq!(
+            stats
+                .adapter_usage_stats
+                .get(&AdapterType::StellarTestnetIpfs),
+            Some(&1)
+        );
+        assert_eq!(stats.generated_at, now);
+    }
+}
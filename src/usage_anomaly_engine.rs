@@ -0,0 +1,622 @@
+//! Per-workspace API usage anomaly detection.
+//!
+//! Requests are counted in a process-wide registry, like SLA outcomes, by
+//! workspace and endpoint. Once a minute the monitor folds the counts into
+//! that hour's `WorkspaceUsageWindow` and compares the hour with the
+//! workspace's recent history: a request spike, an endpoint it never called
+//! before or a burst of deletions raises a `UsageAnomaly`, a Security audit
+//! event and a notification to every admin. Where per-user audit anomalies
+//! look at one account, this catches a workspace whose integration, or keys,
+//! suddenly behave differently.
+
+use crate::audit_engine::AuditEngine;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{
+    AuditEventType, AuditOutcome, AuditSeverity, Notification, NotificationType, UsageAnomaly,
+    UsageAnomalyBaseline, UsageAnomalyKind, WorkspaceUsageWindow,
+};
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+use uuid::Uuid;
+
+pub const DEFAULT_SPIKE_FACTOR: f64 = 10.0;
+pub const DEFAULT_MIN_SPIKE_REQUESTS: u64 = 100;
+pub const DEFAULT_MASS_DELETION_THRESHOLD: u64 = 50;
+/// One week of hourly history
+pub const DEFAULT_BASELINE_HOURS: u32 = 168;
+pub const MAX_BASELINE_HOURS: u32 = 24 * 90;
+/// Hours with traffic needed before spikes and novel endpoints are judged;
+/// a new workspace has no normal to deviate from
+pub const MIN_HISTORY_HOURS: usize = 24;
+
+#[derive(Debug)]
+pub enum UsageAnomalyError {
+    StorageError(StorageError),
+    ValidationError(String),
+}
+
+impl From<StorageError> for UsageAnomalyError {
+    fn from(err: StorageError) -> Self {
+        UsageAnomalyError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for UsageAnomalyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UsageAnomalyError::StorageError(e) => write!(f, "Storage error: {e}"),
+            UsageAnomalyError::ValidationError(e) => write!(f, "Validation error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for UsageAnomalyError {}
+
+/// Who made counted requests. JWTs carry the workspace; API key requests
+/// only name the user, whose workspace is looked up when usage is recorded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UsageSubject {
+    Workspace(String),
+    User(String),
+}
+
+/// Requests counted since the monitor last ran
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PendingUsage {
+    pub requests: u64,
+    pub deletions: u64,
+    pub endpoints: HashMap<String, u64>,
+}
+
+impl PendingUsage {
+    fn merge(&mut self, other: &PendingUsage) {
+        self.requests += other.requests;
+        self.deletions += other.deletions;
+        for (endpoint, count) in &other.endpoints {
+            *self.endpoints.entry(endpoint.clone()).or_default() += count;
+        }
+    }
+}
+
+fn registry() -> &'static Mutex<HashMap<UsageSubject, PendingUsage>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<UsageSubject, PendingUsage>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Count one request to `endpoint` (`"METHOD /route/:param"`)
+pub fn record_request(subject: UsageSubject, endpoint: &str, deletion: bool) {
+    let mut pending = registry().lock().unwrap();
+    let usage = pending.entry(subject).or_default();
+    usage.requests += 1;
+    if deletion {
+        usage.deletions += 1;
+    }
+    *usage.endpoints.entry(endpoint.to_string()).or_default() += 1;
+}
+
+/// Take the counts recorded since the last call
+pub fn take_pending() -> Vec<(UsageSubject, PendingUsage)> {
+    registry().lock().unwrap().drain().collect()
+}
+
+/// Changes to a workspace's thresholds; omitted fields keep their value
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UsageBaselineInput {
+    pub spike_factor: Option<f64>,
+    pub min_spike_requests: Option<u64>,
+    pub mass_deletion_threshold: Option<u64>,
+    pub baseline_hours: Option<u32>,
+    pub alert_novel_endpoints: Option<bool>,
+}
+
+fn default_baseline(workspace_id: &str) -> UsageAnomalyBaseline {
+    UsageAnomalyBaseline {
+        workspace_id: workspace_id.to_string(),
+        spike_factor: DEFAULT_SPIKE_FACTOR,
+        min_spike_requests: DEFAULT_MIN_SPIKE_REQUESTS,
+        mass_deletion_threshold: DEFAULT_MASS_DELETION_THRESHOLD,
+        baseline_hours: DEFAULT_BASELINE_HOURS,
+        alert_novel_endpoints: true,
+        updated_by: None,
+        updated_at: None,
+    }
+}
+
+fn hour_of(time: DateTime<Utc>) -> DateTime<Utc> {
+    time.duration_trunc(Duration::hours(1)).unwrap_or(time)
+}
+
+/// Average requests per hour since the workspace's first recorded hour in
+/// the baseline period. Hours without a window had no traffic.
+fn expected_hourly(history: &[WorkspaceUsageWindow], hour_start: DateTime<Utc>) -> Option<f64> {
+    if history.len() < MIN_HISTORY_HOURS {
+        return None;
+    }
+    let first = history.iter().map(|w| w.hour_start).min()?;
+    let hours = (hour_start - first).num_hours().max(1);
+    let total: u64 = history.iter().map(|w| w.requests).sum();
+    Some(total as f64 / hours as f64)
+}
+
+/// Anomalies of `window` against the hours before it
+pub fn detect(
+    baseline: &UsageAnomalyBaseline,
+    window: &WorkspaceUsageWindow,
+    history: &[WorkspaceUsageWindow],
+    now: DateTime<Utc>,
+) -> Vec<UsageAnomaly> {
+    let anomaly = |kind, endpoint: Option<String>, observed, expected, description| UsageAnomaly {
+        anomaly_id: Uuid::new_v4(),
+        workspace_id: window.workspace_id.clone(),
+        kind,
+        hour_start: window.hour_start,
+        endpoint,
+        observed,
+        expected,
+        description,
+        detected_at: now,
+    };
+    let mut anomalies = Vec::new();
+
+    if let Some(expected) = expected_hourly(history, window.hour_start) {
+        if window.requests >= baseline.min_spike_requests
+            && window.requests as f64 >= baseline.spike_factor * expected
+        {
+            anomalies.push(anomaly(
+                UsageAnomalyKind::RequestSpike,
+                None,
+                window.requests,
+                expected,
+                format!(
+                    "{} requests this hour against a baseline of {:.1} per hour",
+                    window.requests, expected
+                ),
+            ));
+        }
+
+        if baseline.alert_novel_endpoints {
+            let known: HashSet<&String> = history.iter().flat_map(|w| w.endpoints.keys()).collect();
+            let mut novel: Vec<(&String, &u64)> = window
+                .endpoints
+                .iter()
+                .filter(|(endpoint, _)| !known.contains(endpoint))
+                .collect();
+            novel.sort();
+            for (endpoint, count) in novel {
+                anomalies.push(anomaly(
+                    UsageAnomalyKind::NovelEndpoint,
+                    Some(endpoint.clone()),
+                    *count,
+                    0.0,
+                    format!(
+                        "First calls to {endpoint} in {} hours",
+                        baseline.baseline_hours
+                    ),
+                ));
+            }
+        }
+    }
+
+    if window.deletions >= baseline.mass_deletion_threshold {
+        anomalies.push(anomaly(
+            UsageAnomalyKind::MassDeletion,
+            None,
+            window.deletions,
+            baseline.mass_deletion_threshold as f64,
+            format!("{} deletions this hour", window.deletions),
+        ));
+    }
+    anomalies
+}
+
+pub struct UsageAnomalyEngine<S: StorageBackend> {
+    storage: S,
+    audit: AuditEngine<S>,
+}
+
+impl<S: StorageBackend + Clone + 'static> UsageAnomalyEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            audit: AuditEngine::new(storage.clone()),
+            storage,
+        }
+    }
+
+    /// The workspace's thresholds, or the defaults if none were set
+    pub fn baseline(&self, workspace_id: &str) -> Result<UsageAnomalyBaseline, UsageAnomalyError> {
+        Ok(self
+            .storage
+            .get_usage_anomaly_baseline(workspace_id)?
+            .unwrap_or_else(|| default_baseline(workspace_id)))
+    }
+
+    pub fn set_baseline(
+        &self,
+        admin_id: &str,
+        workspace_id: &str,
+        input: UsageBaselineInput,
+        now: DateTime<Utc>,
+    ) -> Result<UsageAnomalyBaseline, UsageAnomalyError> {
+        let mut baseline = self.baseline(workspace_id)?;
+        if let Some(spike_factor) = input.spike_factor {
+            if !spike_factor.is_finite() || spike_factor <= 1.0 {
+                return Err(UsageAnomalyError::ValidationError(
+                    "spike_factor must be greater than 1".to_string(),
+                ));
+            }
+            baseline.spike_factor = spike_factor;
+        }
+        if let Some(threshold) = input.mass_deletion_threshold {
+            if threshold == 0 {
+                return Err(UsageAnomalyError::ValidationError(
+                    "mass_deletion_threshold must be at least 1".to_string(),
+                ));
+            }
+            baseline.mass_deletion_threshold = threshold;
+        }
+        if let Some(hours) = input.baseline_hours {
+            if !(1..=MAX_BASELINE_HOURS).contains(&hours) {
+                return Err(UsageAnomalyError::ValidationError(format!(
+                    "baseline_hours must be between 1 and {MAX_BASELINE_HOURS}"
+                )));
+            }
+            baseline.baseline_hours = hours;
+        }
+        if let Some(min_requests) = input.min_spike_requests {
+            baseline.min_spike_requests = min_requests;
+        }
+        if let Some(alert) = input.alert_novel_endpoints {
+            baseline.alert_novel_endpoints = alert;
+        }
+        baseline.updated_by = Some(admin_id.to_string());
+        baseline.updated_at = Some(now);
+        self.storage.store_usage_anomaly_baseline(&baseline)?;
+
+        if let Err(e) = self.audit.log_event(
+            admin_id.to_string(),
+            AuditEventType::Security,
+            "usage_anomaly_baseline_updated".to_string(),
+            format!("workspace:{workspace_id}"),
+            AuditOutcome::Success,
+            AuditSeverity::Medium,
+            Some(HashMap::from([("baseline".to_string(), json!(baseline))])),
+            None,
+            None,
+        ) {
+            tracing::warn!("Failed to audit usage baseline of {}: {}", workspace_id, e);
+        }
+        Ok(baseline)
+    }
+
+    pub fn usage(
+        &self,
+        workspace_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<WorkspaceUsageWindow>, UsageAnomalyError> {
+        Ok(self
+            .storage
+            .list_workspace_usage_windows(workspace_id, from, to)?)
+    }
+
+    /// Anomalies since `since`, newest first
+    pub fn anomalies(
+        &self,
+        workspace_id: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<UsageAnomaly>, UsageAnomalyError> {
+        Ok(self.storage.list_usage_anomalies(workspace_id, since)?)
+    }
+
+    /// Add counted requests to the current hour of their workspaces and raise
+    /// the anomalies not yet raised this hour. Requests of users without a
+    /// workspace are dropped.
+    pub fn record_usage(
+        &self,
+        pending: Vec<(UsageSubject, PendingUsage)>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<UsageAnomaly>, UsageAnomalyError> {
+        let mut workspaces: HashMap<String, PendingUsage> = HashMap::new();
+        for (subject, usage) in pending {
+            let workspace_id = match subject {
+                UsageSubject::Workspace(workspace_id) => Some(workspace_id),
+                UsageSubject::User(user_id) => self
+                    .storage
+                    .get_user_account(&user_id)?
+                    .and_then(|account| account.workspace_id),
+            };
+            if let Some(workspace_id) = workspace_id {
+                workspaces.entry(workspace_id).or_default().merge(&usage);
+            }
+        }
+
+        let hour_start = hour_of(now);
+        let mut raised = Vec::new();
+        for (workspace_id, usage) in workspaces {
+            let mut window = self
+                .storage
+                .get_workspace_usage_window(&workspace_id, hour_start)?
+                .unwrap_or(WorkspaceUsageWindow {
+                    workspace_id: workspace_id.clone(),
+                    hour_start,
+                    requests: 0,
+                    deletions: 0,
+                    endpoints: HashMap::new(),
+                });
+            window.requests += usage.requests;
+            window.deletions += usage.deletions;
+            for (endpoint, count) in usage.endpoints {
+                *window.endpoints.entry(endpoint).or_default() += count;
+            }
+            self.storage.store_workspace_usage_window(&window)?;
+            raised.extend(self.check_window(&window, now)?);
+        }
+        Ok(raised)
+    }
+
+    fn check_window(
+        &self,
+        window: &WorkspaceUsageWindow,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<UsageAnomaly>, UsageAnomalyError> {
+        let baseline = self.baseline(&window.workspace_id)?;
+        let from = window.hour_start - Duration::hours(i64::from(baseline.baseline_hours));
+        let history = self.storage.list_workspace_usage_windows(
+            &window.workspace_id,
+            from,
+            window.hour_start,
+        )?;
+        let already: HashSet<(UsageAnomalyKind, Option<String>)> = self
+            .storage
+            .list_usage_anomalies(Some(&window.workspace_id), window.hour_start)?
+            .into_iter()
+            .filter(|a| a.hour_start == window.hour_start)
+            .map(|a| (a.kind, a.endpoint))
+            .collect();
+
+        let anomalies: Vec<UsageAnomaly> = detect(&baseline, window, &history, now)
+            .into_iter()
+            .filter(|a| !already.contains(&(a.kind, a.endpoint.clone())))
+            .collect();
+        for anomaly in &anomalies {
+            self.raise(anomaly)?;
+        }
+        Ok(anomalies)
+    }
+
+    fn raise(&self, anomaly: &UsageAnomaly) -> Result<(), UsageAnomalyError> {
+        self.storage.store_usage_anomaly(anomaly)?;
+        tracing::warn!(
+            "🚨 Usage anomaly in workspace {}: {}",
+            anomaly.workspace_id,
+            anomaly.description
+        );
+
+        let severity = match anomaly.kind {
+            UsageAnomalyKind::MassDeletion => AuditSeverity::High,
+            UsageAnomalyKind::RequestSpike => AuditSeverity::Medium,
+            UsageAnomalyKind::NovelEndpoint => AuditSeverity::Low,
+        };
+        let details = HashMap::from([
+            ("anomaly_id".to_string(), json!(anomaly.anomaly_id)),
+            ("kind".to_string(), json!(anomaly.kind)),
+            ("hour_start".to_string(), json!(anomaly.hour_start)),
+            ("endpoint".to_string(), json!(anomaly.endpoint)),
+            ("observed".to_string(), json!(anomaly.observed)),
+            ("expected".to_string(), json!(anomaly.expected)),
+        ]);
+        if let Err(e) = self.audit.log_event(
+            "system".to_string(),
+            AuditEventType::Security,
+            format!("usage_anomaly_{}", anomaly.kind.as_str()),
+            format!("workspace:{}", anomaly.workspace_id),
+            AuditOutcome::Warning,
+            severity,
+            Some(details),
+            None,
+            None,
+        ) {
+            tracing::warn!(
+                "Failed to audit usage anomaly {}: {}",
+                anomaly.anomaly_id,
+                e
+            );
+        }
+
+        for admin in self
+            .storage
+            .list_user_accounts()?
+            .into_iter()
+            .filter(|account| account.is_admin)
+        {
+            let notification = Notification::new(
+                admin.user_id.clone(),
+                NotificationType::UsageAnomalyDetected,
+                format!("API usage anomaly in workspace {}", anomaly.workspace_id),
+                anomaly.description.clone(),
+                json!({
+                    "anomaly_id": anomaly.anomaly_id,
+                    "workspace_id": anomaly.workspace_id,
+                    "kind": anomaly.kind,
+                }),
+            );
+            if let Err(e) = self.storage.store_notification(&notification) {
+                tracing::warn!("Failed to notify {} of usage anomaly: {}", admin.user_id, e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Fold counted requests into storage and check them every `tick`
+    /// (normally 60s)
+    pub fn spawn_monitor(storage: S, tick: std::time::Duration) -> tokio::task::JoinHandle<()>
+    where
+        S: Send + Sync,
+    {
+        tokio::spawn(async move {
+            let engine = UsageAnomalyEngine::new(storage);
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                let pending = take_pending();
+                if pending.is_empty() {
+                    continue;
+                }
+                if let Err(e) = engine.record_usage(pending, Utc::now()) {
+                    tracing::warn!("⚠️  Failed to record workspace API usage: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::{AccountStatus, TierLimits, UserAccount, UserTier};
+    use chrono::TimeZone;
+    use std::sync::Arc;
+
+    fn account(user_id: &str, is_admin: bool, workspace_id: Option<&str>) -> UserAccount {
+        let tier = UserTier::Basic;
+        UserAccount {
+            user_id: user_id.to_string(),
+            username: user_id.to_string(),
+            email: format!("{user_id}@example.com"),
+            password_hash: "hash".to_string(),
+            limits: TierLimits::for_tier(&tier),
+            tier,
+            status: AccountStatus::Active,
+            credits: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_login: None,
+            subscription: None,
+            is_admin,
+            workspace_id: workspace_id.map(str::to_string),
+            available_adapters: None,
+            locale: None,
+        }
+    }
+
+    fn usage(requests: u64, deletions: u64, endpoints: &[(&str, u64)]) -> PendingUsage {
+        PendingUsage {
+            requests,
+            deletions,
+            endpoints: endpoints
+                .iter()
+                .map(|(endpoint, count)| (endpoint.to_string(), *count))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_spikes_novel_endpoints_and_deletions_are_raised_once() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        storage
+            .store_user_account(&account("admin", true, None))
+            .unwrap();
+        storage
+            .store_user_account(&account("integration", false, Some("acme")))
+            .unwrap();
+        let engine = UsageAnomalyEngine::new(Arc::clone(&storage));
+        let now = Utc.with_ymd_and_hms(2026, 10, 1, 12, 30, 0).unwrap();
+
+        // A day of steady reads
+        for hours_ago in 1..=30 {
+            let history = vec![(
+                UsageSubject::Workspace("acme".to_string()),
+                usage(20, 0, &[("GET /api/items", 20)]),
+            )];
+            let raised = engine
+                .record_usage(history, now - Duration::hours(hours_ago))
+                .unwrap();
+            assert!(raised.is_empty());
+        }
+
+        // Below the spike factor nothing is raised
+        let quiet = vec![(
+            UsageSubject::User("integration".to_string()),
+            usage(150, 0, &[("GET /api/items", 150)]),
+        )];
+        assert!(engine.record_usage(quiet, now).unwrap().is_empty());
+
+        // An API key suddenly deleting items across the workspace
+        let burst = vec![(
+            UsageSubject::User("integration".to_string()),
+            usage(
+                100,
+                60,
+                &[("GET /api/items", 40), ("DELETE /api/items/:dfid", 60)],
+            ),
+        )];
+        let raised = engine.record_usage(burst.clone(), now).unwrap();
+        let kinds: HashSet<UsageAnomalyKind> = raised.iter().map(|a| a.kind).collect();
+        assert_eq!(
+            kinds,
+            HashSet::from([
+                UsageAnomalyKind::RequestSpike,
+                UsageAnomalyKind::NovelEndpoint,
+                UsageAnomalyKind::MassDeletion,
+            ])
+        );
+        let novel = raised
+            .iter()
+            .find(|a| a.kind == UsageAnomalyKind::NovelEndpoint)
+            .unwrap();
+        assert_eq!(novel.endpoint.as_deref(), Some("DELETE /api/items/:dfid"));
+
+        // Raised once per hour, with audit events and admin notifications
+        assert!(engine.record_usage(burst, now).unwrap().is_empty());
+        assert_eq!(engine.anomalies(Some("acme"), now).unwrap().len(), 3);
+        let notifications = storage
+            .get_user_notifications("admin", None, None, false)
+            .unwrap();
+        assert_eq!(notifications.len(), 3);
+        assert!(storage
+            .list_audit_events()
+            .unwrap()
+            .iter()
+            .any(|event| event.action == "usage_anomaly_mass_deletion"));
+
+        // Thresholds are tunable per workspace
+        assert!(matches!(
+            engine.set_baseline(
+                "admin",
+                "acme",
+                UsageBaselineInput {
+                    spike_factor: Some(0.5),
+                    ..Default::default()
+                },
+                now
+            ),
+            Err(UsageAnomalyError::ValidationError(_))
+        ));
+        let baseline = engine
+            .set_baseline(
+                "admin",
+                "acme",
+                UsageBaselineInput {
+                    mass_deletion_threshold: Some(1_000),
+                    ..Default::default()
+                },
+                now,
+            )
+            .unwrap();
+        assert_eq!(baseline.spike_factor, DEFAULT_SPIKE_FACTOR);
+        let next_hour = now + Duration::hours(1);
+        let deletions = vec![(
+            UsageSubject::Workspace("acme".to_string()),
+            usage(60, 60, &[("DELETE /api/items/:dfid", 60)]),
+        )];
+        assert!(engine
+            .record_usage(deletions, next_hour)
+            .unwrap()
+            .iter()
+            .all(|a| a.kind != UsageAnomalyKind::MassDeletion));
+    }
+}
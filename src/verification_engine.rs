@@ -1,18 +1,34 @@
+use crate::dedup_sharing_engine::shared_dedup_scope;
+use crate::dedup_strategy::{
+    resolve_strategy_kind, strategy_for, DedupCandidate, DedupStrategy, ExactIdentifierStrategy,
+};
 use crate::dfid_engine::DfidEngine;
+use crate::enrichment_policy_engine::enrich_with_policy;
+use crate::events_engine::EventsEngine;
+use crate::fuzzy_identifier_matching::find_fuzzy_matches;
 use crate::logging::{LogEntry, LoggingEngine};
+use crate::scaling_signals;
 use crate::storage::{StorageBackend, StorageError};
 use crate::types::{
-    ConflictResolution, DataLakeEntry, Identifier, IdentifierMapping, Item, MappingStatus,
-    ProcessingStatus,
+    AutoLinkPolicy, ConflictResolution, DataLakeEntry, EventCausality, EventType, EventVisibility,
+    FuzzyIdentifierMatch, FuzzyMatchConfig, Identifier, IdentifierMapping, IngestionPriority, Item,
+    PendingItem, PendingReason, ProcessingStatus, SubmissionOrigin, WorkQueue,
 };
+use crate::verification_rules::{evaluate_rules, RuleVerdict};
+use serde::Serialize;
 use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Bulk entries verified between checks for newly arrived realtime entries
+pub const BULK_VERIFICATION_BATCH_SIZE: usize = 50;
+
 #[derive(Debug)]
 pub enum VerificationError {
     StorageError(StorageError),
-    ConflictDetected(ConflictResolution),
+    ConflictDetected(Box<ConflictResolution>),
     ProcessingError(String),
+    /// The submission breaks its workspace's verification rules
+    RuleViolation(String),
 }
 
 impl std::fmt::Display for VerificationError {
@@ -23,6 +39,7 @@ impl std::fmt::Display for VerificationError {
                 write!(f, "Conflict detected: {:?}", c.conflict_id)
             }
             VerificationError::ProcessingError(e) => write!(f, "Processing error: {e}"),
+            VerificationError::RuleViolation(e) => write!(f, "Rule violation: {e}"),
         }
     }
 }
@@ -35,13 +52,20 @@ impl From<StorageError> for VerificationError {
     }
 }
 
+/// Source recorded on the events verification emits
+const VERIFICATION_SOURCE: &str = "verification_engine";
+
 pub struct VerificationEngine<S: StorageBackend> {
     storage: S,
     dfid_engine: DfidEngine,
+    events_engine: EventsEngine<S>,
     logger: LoggingEngine,
+    dedup: Box<dyn DedupStrategy<S>>,
+    fuzzy: Option<FuzzyMatchConfig>,
+    auto_link: Option<AutoLinkPolicy>,
 }
 
-impl<S: StorageBackend> VerificationEngine<S> {
+impl<S: StorageBackend + Clone + 'static> VerificationEngine<S> {
     pub fn new(storage: S, dfid_engine: DfidEngine) -> Self {
         let mut logger = LoggingEngine::new();
         logger.info(
@@ -51,18 +75,128 @@ impl<S: StorageBackend> VerificationEngine<S> {
         );
 
         Self {
+            events_engine: EventsEngine::new(storage.clone()),
             storage,
             dfid_engine,
             logger,
+            dedup: Box::new(ExactIdentifierStrategy),
+            fuzzy: None,
+            auto_link: None,
         }
     }
 
+    pub fn with_dedup_strategy(mut self, strategy: Box<dyn DedupStrategy<S>>) -> Self {
+        self.dedup = strategy;
+        self
+    }
+
+    /// Entries no item matches exactly are checked for near-identical
+    /// identifier values and held as conflicts instead of becoming new items
+    pub fn with_fuzzy_matching(mut self, config: FuzzyMatchConfig) -> Self {
+        self.fuzzy = Some(config);
+        self
+    }
+
+    /// Matches less confident than the policy asks for are held for review;
+    /// near matches and conflicts confident enough are linked instead
+    pub fn with_auto_link_policy(mut self, policy: AutoLinkPolicy) -> Self {
+        self.auto_link = Some(policy);
+        self
+    }
+
+    /// Match entries with the strategy the circuit, or else the workspace,
+    /// chose, and with the workspace's fuzzy matching and auto-link policy.
+    /// Fingerprints are looked up in the circuit's shared dedup namespace
+    /// while it has an active sharing agreement.
+    pub fn with_dedup_scope(
+        mut self,
+        workspace_id: Option<&str>,
+        circuit_id: Option<&Uuid>,
+    ) -> Result<Self, VerificationError> {
+        let kind = resolve_strategy_kind(&self.storage, workspace_id, circuit_id)?;
+        if let Some(workspace_id) = workspace_id {
+            let config = self.storage.get_workspace_dedup_config(workspace_id)?;
+            self.fuzzy = config.as_ref().and_then(|config| config.fuzzy_matching);
+            self.auto_link = config.and_then(|config| config.auto_link);
+        }
+        let scope = circuit_id
+            .map(|circuit_id| shared_dedup_scope(&self.storage, circuit_id))
+            .transpose()?;
+        Ok(self.with_dedup_strategy(strategy_for(kind, scope)))
+    }
+
+    /// Verify every pending entry. Pending entries are loaded once; realtime
+    /// entries go first and bulk entries follow in small batches, so a
+    /// historical import never delays live events.
     pub fn process_pending_entries(
         &mut self,
     ) -> Result<Vec<VerificationResult>, VerificationError> {
-        let pending_entries = self
+        let (realtime, bulk) = self.pending_entries()?;
+
+        let mut results = Vec::new();
+        if !realtime.is_empty() {
+            results.extend(self.process_batch(realtime, IngestionPriority::Realtime)?);
+        }
+
+        let mut bulk = bulk.into_iter().peekable();
+        while bulk.peek().is_some() {
+            let batch: Vec<_> = bulk.by_ref().take(BULK_VERIFICATION_BATCH_SIZE).collect();
+            results.extend(self.process_batch(batch, IngestionPriority::Bulk)?);
+        }
+
+        Ok(results)
+    }
+
+    /// Up to `limit` pending entries, realtime before bulk, oldest first
+    pub fn next_pending_batch(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<DataLakeEntry>, VerificationError> {
+        let (mut batch, bulk) = self.pending_entries()?;
+        batch.extend(bulk);
+        batch.truncate(limit);
+        Ok(batch)
+    }
+
+    /// Verify the given entries, realtime ones first
+    pub fn process_entries(
+        &mut self,
+        entries: Vec<DataLakeEntry>,
+    ) -> Result<Vec<VerificationResult>, VerificationError> {
+        let (realtime, bulk): (Vec<_>, Vec<_>) = entries
+            .into_iter()
+            .partition(|entry| entry.priority == IngestionPriority::Realtime);
+
+        let mut results = Vec::new();
+        for (entries, priority) in [
+            (realtime, IngestionPriority::Realtime),
+            (bulk, IngestionPriority::Bulk),
+        ] {
+            if !entries.is_empty() {
+                results.extend(self.process_batch(entries, priority)?);
+            }
+        }
+        Ok(results)
+    }
+
+    /// Pending entries split into the realtime and bulk lanes, each oldest first
+    fn pending_entries(
+        &self,
+    ) -> Result<(Vec<DataLakeEntry>, Vec<DataLakeEntry>), VerificationError> {
+        let mut entries = self
             .storage
             .get_data_lake_entries_by_status(ProcessingStatus::Pending)?;
+        entries.sort_by_key(|entry| entry.timestamp);
+        Ok(entries
+            .into_iter()
+            .partition(|entry| entry.priority == IngestionPriority::Realtime))
+    }
+
+    fn process_batch(
+        &mut self,
+        entries: Vec<DataLakeEntry>,
+        priority: IngestionPriority,
+    ) -> Result<Vec<VerificationResult>, VerificationError> {
         let mut results = Vec::new();
 
         self.logger
@@ -71,13 +205,22 @@ impl<S: StorageBackend> VerificationEngine<S> {
                 "batch_processing",
                 "Processing pending data lake entries",
             )
-            .with_context("entries_count", pending_entries.len().to_string());
+            .with_context("entries_count", entries.len().to_string())
+            .with_context("priority", format!("{priority:?}"));
 
-        for mut entry in pending_entries {
+        for mut entry in entries {
             entry.mark_processing();
             self.storage.update_data_lake_entry(&entry)?;
 
-            match self.process_entry(&mut entry) {
+            let start = std::time::Instant::now();
+            let outcome = self.process_entry(&mut entry);
+            scaling_signals::record_completed(
+                WorkQueue::verification(priority),
+                start.elapsed(),
+                outcome.is_ok(),
+            );
+
+            match outcome {
                 Ok(result) => {
                     results.push(result);
                     self.storage.update_data_lake_entry(&entry)?;
@@ -112,59 +255,179 @@ impl<S: StorageBackend> VerificationEngine<S> {
                 "Processing data lake entry",
             )
             .with_context("entry_id", entry.entry_id.to_string())
-            .with_context("identifiers_count", entry.identifiers.len().to_string());
+            .with_context("identifiers_count", entry.identifiers.len().to_string())
+            .with_context("dedup_strategy", self.dedup.kind().as_str().to_string());
+
+        match self.check_rules(&entry.origin(), &entry.identifiers, None)? {
+            RuleVerdict::Accept => {}
+            RuleVerdict::Reject { violations } => {
+                return Err(VerificationError::RuleViolation(violations.join("; ")))
+            }
+            RuleVerdict::ManualReview { source } => return self.route_to_review(entry, source),
+        }
 
-        // Check for existing mappings for each identifier
         let identifier_analysis = self.analyze_identifiers(&entry.identifiers)?;
 
         match identifier_analysis {
             IdentifierAnalysis::AllNew => self.create_new_item(entry),
-            IdentifierAnalysis::ExistingSingle(dfid) => self.enrich_existing_item(entry, &dfid),
+            IdentifierAnalysis::FuzzyMatch(matches) => self.handle_fuzzy_match(entry, matches),
+            IdentifierAnalysis::ExistingSingle(candidate) => {
+                let confidence = match_confidence(&candidate);
+                if self.is_confident(confidence) {
+                    self.enrich_existing_item(entry, &candidate.dfid, None)
+                } else {
+                    self.hold_for_review(entry, candidate, confidence)
+                }
+            }
             IdentifierAnalysis::Conflict(conflict_info) => {
                 self.handle_conflict(entry, conflict_info)
             }
         }
     }
 
-    fn analyze_identifiers(
+    /// The verdict of the origin workspace's verification rules. Payload
+    /// field rules are skipped without a payload, which entries do not keep.
+    pub fn check_rules(
         &self,
+        origin: &SubmissionOrigin,
         identifiers: &[Identifier],
-    ) -> Result<IdentifierAnalysis, VerificationError> {
-        let mut dfid_map: HashMap<String, Vec<Identifier>> = HashMap::new();
+        payload: Option<&[u8]>,
+    ) -> Result<RuleVerdict, VerificationError> {
+        let Some(workspace_id) = &origin.workspace_id else {
+            return Ok(RuleVerdict::Accept);
+        };
+        Ok(
+            match self
+                .storage
+                .get_workspace_verification_rules(workspace_id)?
+            {
+                Some(rules) => {
+                    evaluate_rules(&rules.rules, origin.source.as_deref(), identifiers, payload)
+                }
+                None => RuleVerdict::Accept,
+            },
+        )
+    }
 
-        for identifier in identifiers {
-            let mappings = self.storage.get_identifier_mappings(identifier)?;
-            let active_mappings: Vec<_> = mappings
-                .into_iter()
-                .filter(|m| matches!(m.status, MappingStatus::Active))
-                .collect();
+    /// What verifying a submission of `payload` with `identifiers` would do
+    /// right now, under the same rules, strategy and policy, without storing
+    /// anything
+    pub fn preview_verification(
+        &self,
+        payload: &[u8],
+        identifiers: &[Identifier],
+        origin: &SubmissionOrigin,
+    ) -> Result<VerificationPreview, VerificationError> {
+        let outcome = match self.check_rules(origin, identifiers, Some(payload))? {
+            RuleVerdict::Reject { violations } => Some(PreviewOutcome::Rejected { violations }),
+            RuleVerdict::ManualReview { source } => Some(PreviewOutcome::ManualReview { source }),
+            RuleVerdict::Accept => None,
+        };
+        let outcome = match outcome {
+            Some(outcome) => outcome,
+            None => self.preview_matching(identifiers)?,
+        };
+
+        Ok(VerificationPreview {
+            data_hash: crate::hashing::hash(payload),
+            data_size: payload.len(),
+            outcome,
+        })
+    }
 
-            if active_mappings.is_empty() {
-                continue; // This identifier is new
+    fn preview_matching(
+        &self,
+        identifiers: &[Identifier],
+    ) -> Result<PreviewOutcome, VerificationError> {
+        Ok(match self.analyze_identifiers(identifiers)? {
+            IdentifierAnalysis::AllNew => PreviewOutcome::NewItem,
+            IdentifierAnalysis::ExistingSingle(candidate) => {
+                let confidence = match_confidence(&candidate);
+                if self.is_confident(confidence) {
+                    PreviewOutcome::EnrichExisting {
+                        dfid: candidate.dfid,
+                        confidence,
+                        conflicting_dfids: Vec::new(),
+                    }
+                } else {
+                    PreviewOutcome::Conflict {
+                        conflicting_dfids: vec![candidate.dfid],
+                        confidence,
+                        fuzzy_matches: Vec::new(),
+                    }
+                }
             }
-
-            for mapping in active_mappings {
-                dfid_map
-                    .entry(mapping.dfid.clone())
-                    .or_default()
-                    .push(identifier.clone());
+            IdentifierAnalysis::FuzzyMatch(matches) => match self.fuzzy_link_target(&matches) {
+                Some(best) => PreviewOutcome::EnrichExisting {
+                    dfid: best.dfid.clone(),
+                    confidence: best.confidence,
+                    conflicting_dfids: Vec::new(),
+                },
+                None => {
+                    let conflict =
+                        ConflictResolution::from_fuzzy_matches(identifiers.to_vec(), matches);
+                    PreviewOutcome::Conflict {
+                        conflicting_dfids: conflict.conflicting_dfids,
+                        confidence: conflict.fuzzy_matches[0].confidence,
+                        fuzzy_matches: conflict.fuzzy_matches,
+                    }
+                }
+            },
+            IdentifierAnalysis::Conflict(conflict_info) => {
+                match self.attempt_auto_resolution(&conflict_info)? {
+                    Some(dfid) => PreviewOutcome::EnrichExisting {
+                        dfid,
+                        confidence: conflict_info.leader_confidence,
+                        conflicting_dfids: conflict_info.conflicting_dfids,
+                    },
+                    None => PreviewOutcome::Conflict {
+                        conflicting_dfids: conflict_info.conflicting_dfids,
+                        confidence: conflict_info.leader_confidence,
+                        fuzzy_matches: Vec::new(),
+                    },
+                }
             }
-        }
+        })
+    }
 
-        match dfid_map.len() {
-            0 => Ok(IdentifierAnalysis::AllNew),
-            1 => {
-                let (dfid, _) = dfid_map.into_iter().next().unwrap();
-                Ok(IdentifierAnalysis::ExistingSingle(dfid))
-            }
+    fn analyze_identifiers(
+        &self,
+        identifiers: &[Identifier],
+    ) -> Result<IdentifierAnalysis, VerificationError> {
+        let mut candidates = self.dedup.candidates(&self.storage, identifiers)?;
+
+        match candidates.len() {
+            0 => match &self.fuzzy {
+                Some(config) => {
+                    let matches = find_fuzzy_matches(&self.storage, identifiers, config)?;
+                    if matches.is_empty() {
+                        Ok(IdentifierAnalysis::AllNew)
+                    } else {
+                        Ok(IdentifierAnalysis::FuzzyMatch(matches))
+                    }
+                }
+                None => Ok(IdentifierAnalysis::AllNew),
+            },
+            1 => Ok(IdentifierAnalysis::ExistingSingle(candidates.remove(0))),
             _ => {
-                let dfids: Vec<String> = dfid_map.keys().cloned().collect();
-                let conflict_identifiers: Vec<Identifier> =
-                    dfid_map.values().flatten().cloned().collect();
+                // Candidates come best first; the leader's share of the total
+                // score is how sure verification is it is the right one
+                let total: f64 = candidates.iter().map(|c| c.score).sum();
+                let leader_confidence = if total > 0.0 {
+                    candidates[0].score / total
+                } else {
+                    0.0
+                };
+                let dfids: Vec<String> = candidates.iter().map(|c| c.dfid.clone()).collect();
+                let conflict_identifiers: Vec<Identifier> = candidates
+                    .into_iter()
+                    .flat_map(|c| c.matched_identifiers)
+                    .collect();
 
                 Ok(IdentifierAnalysis::Conflict(ConflictInfo {
                     conflicting_dfids: dfids,
                     conflicting_identifiers: conflict_identifiers,
+                    leader_confidence,
                 }))
             }
         }
@@ -174,7 +437,7 @@ impl<S: StorageBackend> VerificationEngine<S> {
         &mut self,
         entry: &mut DataLakeEntry,
     ) -> Result<VerificationResult, VerificationError> {
-        let dfid = self.dfid_engine.generate_dfid();
+        let dfid = self.dfid_engine.generate_dfid()?;
 
         self.logger
             .info("VerificationEngine", "item_creation", "Creating new item")
@@ -182,7 +445,10 @@ impl<S: StorageBackend> VerificationEngine<S> {
             .with_context("entry_id", entry.entry_id.to_string());
 
         // Create new item
-        let item = Item::new(dfid.clone(), entry.identifiers.clone(), entry.entry_id);
+        let mut item = Item::new(dfid.clone(), entry.identifiers.clone(), entry.entry_id);
+        if let Some(occurred_at) = entry.occurred_at {
+            item.record_occurrence(occurred_at);
+        }
         self.storage.store_item(&item)?;
 
         // Create identifier mappings
@@ -191,6 +457,10 @@ impl<S: StorageBackend> VerificationEngine<S> {
                 IdentifierMapping::new(identifier.clone(), dfid.clone(), "primary".to_string());
             self.storage.store_identifier_mapping(&mapping)?;
         }
+        self.dedup
+            .record_item(&self.storage, &dfid, &entry.identifiers)?;
+
+        self.record_created_event(&dfid, entry)?;
 
         entry.mark_completed(dfid.clone());
 
@@ -205,10 +475,12 @@ impl<S: StorageBackend> VerificationEngine<S> {
         Ok(VerificationResult::NewItemCreated { dfid })
     }
 
+    /// `conflict_id` is set when the item was chosen by resolving a conflict
     fn enrich_existing_item(
         &mut self,
         entry: &mut DataLakeEntry,
         dfid: &str,
+        conflict_id: Option<Uuid>,
     ) -> Result<VerificationResult, VerificationError> {
         self.logger
             .info(
@@ -237,8 +509,17 @@ impl<S: StorageBackend> VerificationEngine<S> {
             serde_json::Value::Number(entry.data_size.into()),
         );
 
-        item.enrich(enriched_data, entry.entry_id);
-        self.storage.update_item(&item)?;
+        if let Some(occurred_at) = entry.occurred_at {
+            item.record_occurrence(occurred_at);
+        }
+        let enriched_keys: Vec<serde_json::Value> = enriched_data
+            .keys()
+            .cloned()
+            .map(serde_json::Value::String)
+            .collect();
+        // Receipts are anonymous, so the policy of the item's workspace applies
+        let enrichment =
+            enrich_with_policy(&self.storage, item, enriched_data, entry.entry_id, None)?;
 
         // Create mappings for any new identifiers
         for identifier in &entry.identifiers {
@@ -252,6 +533,34 @@ impl<S: StorageBackend> VerificationEngine<S> {
                 self.storage.store_identifier_mapping(&mapping)?;
             }
         }
+        self.dedup
+            .record_item(&self.storage, dfid, &entry.identifiers)?;
+
+        // The Enriched event joins the entry's operation, noting the keys the
+        // entry offered and the conflict it settled if any
+        let event_id = enrichment.event.event_id;
+        let mut metadata = HashMap::from([
+            (
+                "enriched_keys".to_string(),
+                serde_json::Value::Array(enriched_keys),
+            ),
+            (
+                "source_entry".to_string(),
+                serde_json::Value::String(entry.entry_id.to_string()),
+            ),
+        ]);
+        if let Some(conflict_id) = conflict_id {
+            metadata.insert(
+                "conflict_id".to_string(),
+                serde_json::Value::String(conflict_id.to_string()),
+            );
+        }
+        self.events_engine
+            .add_event_metadata(&event_id, metadata)
+            .map_err(|e| VerificationError::ProcessingError(e.to_string()))?;
+        self.events_engine
+            .link_event(&event_id, EventCausality::root(entry.receipt_id))
+            .map_err(|e| VerificationError::ProcessingError(e.to_string()))?;
 
         entry.mark_completed(dfid.to_string());
 
@@ -304,7 +613,11 @@ impl<S: StorageBackend> VerificationEngine<S> {
                 )
                 .with_context("resolved_dfid", resolved_dfid.clone());
 
-            return self.enrich_existing_item(entry, &resolved_dfid);
+            return self.enrich_existing_item(
+                entry,
+                &resolved_dfid,
+                Some(conflict_resolution.conflict_id),
+            );
         }
 
         self.logger
@@ -321,10 +634,153 @@ impl<S: StorageBackend> VerificationEngine<S> {
         })
     }
 
-    fn attempt_auto_resolution(
+    /// Near matches wait for a reviewer, who sees how confident each match
+    /// is, unless the auto-link policy trusts the best one and no other item
+    /// matched
+    fn handle_fuzzy_match(
         &mut self,
+        entry: &mut DataLakeEntry,
+        matches: Vec<FuzzyIdentifierMatch>,
+    ) -> Result<VerificationResult, VerificationError> {
+        if let Some(best) = self.fuzzy_link_target(&matches) {
+            let dfid = best.dfid.clone();
+            self.logger
+                .info(
+                    "VerificationEngine",
+                    "fuzzy_match_auto_linked",
+                    "Near match linked under the auto-link policy",
+                )
+                .with_context("entry_id", entry.entry_id.to_string())
+                .with_context("dfid", dfid.clone())
+                .with_context("confidence", format!("{:.2}", best.confidence));
+            return self.enrich_existing_item(entry, &dfid, None);
+        }
+
+        let conflict_resolution =
+            ConflictResolution::from_fuzzy_matches(entry.identifiers.clone(), matches);
+
+        self.logger
+            .warn(
+                "VerificationEngine",
+                "fuzzy_match_detected",
+                "Identifiers nearly match an existing item",
+            )
+            .with_context("entry_id", entry.entry_id.to_string())
+            .with_context("conflict_id", conflict_resolution.conflict_id.to_string())
+            .with_context(
+                "best_confidence",
+                format!("{:.2}", conflict_resolution.fuzzy_matches[0].confidence),
+            );
+
+        self.storage
+            .store_conflict_resolution(&conflict_resolution)?;
+        entry.mark_conflicted();
+
+        Ok(VerificationResult::ConflictDetected {
+            conflict_id: conflict_resolution.conflict_id,
+            conflicting_dfids: conflict_resolution.conflicting_dfids,
+        })
+    }
+
+    /// A single match the auto-link policy does not trust enough
+    fn hold_for_review(
+        &mut self,
+        entry: &mut DataLakeEntry,
+        candidate: DedupCandidate,
+        confidence: f64,
+    ) -> Result<VerificationResult, VerificationError> {
+        let conflict_resolution =
+            ConflictResolution::new(candidate.matched_identifiers, vec![candidate.dfid]);
+
+        self.logger
+            .warn(
+                "VerificationEngine",
+                "match_below_auto_link_confidence",
+                "Match is not confident enough to link without review",
+            )
+            .with_context("entry_id", entry.entry_id.to_string())
+            .with_context("conflict_id", conflict_resolution.conflict_id.to_string())
+            .with_context("confidence", format!("{confidence:.2}"));
+
+        self.storage
+            .store_conflict_resolution(&conflict_resolution)?;
+        entry.mark_conflicted();
+
+        Ok(VerificationResult::ConflictDetected {
+            conflict_id: conflict_resolution.conflict_id,
+            conflicting_dfids: conflict_resolution.conflicting_dfids,
+        })
+    }
+
+    /// A source the workspace's rules send to a person instead of matching
+    fn route_to_review(
+        &mut self,
+        entry: &mut DataLakeEntry,
+        source: String,
+    ) -> Result<VerificationResult, VerificationError> {
+        let pending = PendingItem::new(
+            entry.identifiers.clone(),
+            None,
+            entry.entry_id,
+            PendingReason::RoutedByRule {
+                source: source.clone(),
+            },
+            None,
+            entry.workspace_id.clone(),
+        );
+
+        self.logger
+            .info(
+                "VerificationEngine",
+                "routed_to_review",
+                "Verification rules hold the entry for manual review",
+            )
+            .with_context("entry_id", entry.entry_id.to_string())
+            .with_context("pending_id", pending.pending_id.to_string())
+            .with_context("source", source);
+
+        self.storage.store_pending_item(&pending)?;
+        entry.mark_conflicted();
+
+        Ok(VerificationResult::HeldForReview {
+            pending_id: pending.pending_id,
+        })
+    }
+
+    /// The best near match, if the auto-link policy trusts it and no other
+    /// item matched
+    fn fuzzy_link_target<'a>(
+        &self,
+        matches: &'a [FuzzyIdentifierMatch],
+    ) -> Option<&'a FuzzyIdentifierMatch> {
+        let best = matches.first()?;
+        (self.auto_link.is_some()
+            && self.is_confident(best.confidence)
+            && matches.iter().all(|fuzzy| fuzzy.dfid == best.dfid))
+        .then_some(best)
+    }
+
+    /// Every match is trusted without an auto-link policy
+    fn is_confident(&self, confidence: f64) -> bool {
+        self.auto_link
+            .is_none_or(|policy| confidence >= policy.min_confidence)
+    }
+
+    fn attempt_auto_resolution(
+        &self,
         conflict_info: &ConflictInfo,
     ) -> Result<Option<String>, VerificationError> {
+        // Under an auto-link policy only a confident leader is merged into
+        if let Some(policy) = self.auto_link {
+            let leader = conflict_info.conflicting_dfids.first();
+            return Ok(leader
+                .filter(|_| {
+                    policy.auto_merge_conflicts
+                        && conflict_info.leader_confidence >= policy.min_confidence
+                })
+                .cloned());
+        }
+
         // Simple confidence-based resolution: prefer the item with more source entries
         let mut best_dfid = None;
         let mut max_sources = 0;
@@ -341,6 +797,35 @@ impl<S: StorageBackend> VerificationEngine<S> {
         Ok(best_dfid)
     }
 
+    /// The events of one entry share its receipt as correlation id, so an
+    /// audit can trace an item back to the submission that created or changed it
+    fn record_created_event(
+        &mut self,
+        dfid: &str,
+        entry: &DataLakeEntry,
+    ) -> Result<(), VerificationError> {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "identifiers".to_string(),
+            serde_json::to_value(&entry.identifiers).unwrap_or_default(),
+        );
+        metadata.insert(
+            "source_entry".to_string(),
+            serde_json::Value::String(entry.entry_id.to_string()),
+        );
+        self.events_engine
+            .create_linked_event(
+                dfid.to_string(),
+                EventType::Created,
+                VERIFICATION_SOURCE.to_string(),
+                EventVisibility::Public,
+                metadata,
+                EventCausality::root(entry.receipt_id),
+            )
+            .map_err(|e| VerificationError::ProcessingError(e.to_string()))?;
+        Ok(())
+    }
+
     pub fn get_logs(&self) -> &[LogEntry] {
         self.logger.get_logs()
     }
@@ -353,14 +838,61 @@ impl<S: StorageBackend> VerificationEngine<S> {
 #[derive(Debug)]
 enum IdentifierAnalysis {
     AllNew,
-    ExistingSingle(String), // DFID
+    /// No exact match, but values close to existing identifiers
+    FuzzyMatch(Vec<FuzzyIdentifierMatch>),
+    ExistingSingle(DedupCandidate),
     Conflict(ConflictInfo),
 }
 
 #[derive(Debug)]
 struct ConflictInfo {
+    /// Best candidate first
     conflicting_dfids: Vec<String>,
     conflicting_identifiers: Vec<Identifier>,
+    leader_confidence: f64,
+}
+
+/// Candidate scores add up matched identifiers by weight; one canonical
+/// identifier or the exact identifier set is full confidence
+fn match_confidence(candidate: &DedupCandidate) -> f64 {
+    candidate.score.min(1.0)
+}
+
+/// A dry run of verification, for warning users before they submit
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerificationPreview {
+    /// Hash the receipt of the payload would carry
+    pub data_hash: String,
+    pub data_size: usize,
+    #[serde(flatten)]
+    pub outcome: PreviewOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum PreviewOutcome {
+    NewItem,
+    /// `conflicting_dfids` lists the items of a conflict that would be
+    /// resolved in favour of `dfid`
+    EnrichExisting {
+        dfid: String,
+        confidence: f64,
+        conflicting_dfids: Vec<String>,
+    },
+    /// The submission would wait in the conflict review queue
+    Conflict {
+        conflicting_dfids: Vec<String>,
+        confidence: f64,
+        fuzzy_matches: Vec<FuzzyIdentifierMatch>,
+    },
+    /// The workspace's verification rules would refuse the submission
+    Rejected {
+        violations: Vec<String>,
+    },
+    /// The workspace's verification rules would hold it for manual review
+    ManualReview {
+        source: String,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -375,13 +907,17 @@ pub enum VerificationResult {
         conflict_id: Uuid,
         conflicting_dfids: Vec<String>,
     },
+    /// Waiting in the review queue, as the workspace's rules ask
+    HeldForReview {
+        pending_id: Uuid,
+    },
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::storage::InMemoryStorage;
-    use crate::types::ProcessingStatus;
+    use crate::types::{ProcessingStatus, VerificationRule};
     use std::sync::Arc;
     use uuid::Uuid;
 
@@ -424,6 +960,7 @@ mod tests {
         let (storage, mut engine) = new_engine();
         let dfid = "DFID-EXISTING-001".to_string();
         let base_identifier = Identifier::new("user_id", "12345");
+        let occurred_at = chrono::Utc::now() - chrono::Duration::days(400);
 
         {
             let guard = storage.lock().unwrap();
@@ -444,7 +981,8 @@ mod tests {
             ],
             "hash2".to_string(),
             256,
-        );
+        )
+        .with_occurred_at(Some(occurred_at));
 
         let result = engine.process_entry(&mut entry).unwrap();
 
@@ -454,8 +992,574 @@ mod tests {
             } => {
                 assert_eq!(enriched_dfid, dfid);
                 assert_eq!(entry.status, ProcessingStatus::Completed);
+                let guard = storage.lock().unwrap();
+                let item = guard.get_item_by_dfid(&dfid).unwrap().unwrap();
+                assert_eq!(item.first_occurred_at, Some(occurred_at));
+                assert_eq!(item.last_occurred_at, Some(occurred_at));
+            }
+            other => panic!("expected VerificationResult::ItemEnriched, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_auto_resolved_conflict_links_enriched_event() {
+        let (storage, mut engine) = new_engine();
+        let tag = Identifier::new("ear_tag", "BR-77");
+        let lot = Identifier::new("lot", "L-9");
+        {
+            let guard = storage.lock().unwrap();
+            for (dfid, identifier) in [("DFID-A", &tag), ("DFID-B", &lot)] {
+                let item = Item::new(dfid.to_string(), vec![identifier.clone()], Uuid::new_v4());
+                guard.store_item(&item).unwrap();
+                let mapping =
+                    IdentifierMapping::new(identifier.clone(), dfid.to_string(), "primary".into());
+                guard.store_identifier_mapping(&mapping).unwrap();
+            }
+        }
+
+        let mut entry = DataLakeEntry::new(
+            Uuid::new_v4(),
+            vec![tag.clone(), lot.clone()],
+            "hash-conflict".to_string(),
+            64,
+        );
+        let dfid = match engine.process_entry(&mut entry).unwrap() {
+            VerificationResult::ItemEnriched { dfid } => dfid,
+            other => panic!("expected VerificationResult::ItemEnriched, got {other:?}"),
+        };
+
+        let events = storage.get_events_by_dfid(&dfid).unwrap();
+        let enriched = events
+            .iter()
+            .find(|event| event.event_type == EventType::Enriched)
+            .expect("enrichment records an Enriched event");
+        assert_eq!(
+            events
+                .iter()
+                .filter(|event| event.event_type == EventType::Enriched)
+                .count(),
+            1
+        );
+        assert_eq!(enriched.correlation_id, Some(entry.receipt_id));
+        assert_eq!(
+            enriched.metadata.get("source_entry"),
+            Some(&serde_json::json!(entry.entry_id.to_string()))
+        );
+        let enriched_keys = enriched.metadata["enriched_keys"].as_array().unwrap();
+        assert!(enriched_keys.contains(&serde_json::json!("data_hash")));
+        assert!(enriched_keys.contains(&serde_json::json!("data_size")));
+
+        let conflict_id: Uuid = enriched.metadata["conflict_id"]
+            .as_str()
+            .and_then(|id| id.parse().ok())
+            .expect("the Enriched event names the conflict it settled");
+        assert!(storage
+            .get_conflict_resolution(&conflict_id)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_dedup_scope_uses_workspace_and_circuit_strategy() {
+        use crate::identifier_types::{CircuitAliasConfig, DedupStrategyKind};
+        use crate::types::{Circuit, WorkspaceDedupConfig};
+
+        let (storage, _) = new_engine();
+        let sisbov = Identifier::canonical("bovino", "sisbov", "BR0001");
+        let lot = Identifier::contextual("bovino", "lote", "L-1");
+        let circuit = {
+            let guard = storage.lock().unwrap();
+            for (identifier, dfid) in [(&lot, "DFID-LOT"), (&sisbov, "DFID-ANIMAL")] {
+                guard
+                    .store_item(&Item::new(
+                        dfid.to_string(),
+                        vec![identifier.clone()],
+                        Uuid::new_v4(),
+                    ))
+                    .unwrap();
+                guard
+                    .store_identifier_mapping(&IdentifierMapping::new(
+                        identifier.clone(),
+                        dfid.to_string(),
+                        "primary".into(),
+                    ))
+                    .unwrap();
+            }
+            guard
+                .store_workspace_dedup_config(&WorkspaceDedupConfig {
+                    workspace_id: "ws-1".to_string(),
+                    strategy: DedupStrategyKind::CanonicalFirst,
+                    fuzzy_matching: None,
+                    auto_link: None,
+                    updated_by: "owner".to_string(),
+                    updated_at: chrono::Utc::now(),
+                })
+                .unwrap();
+
+            let mut circuit = Circuit::new(
+                "Cattle".to_string(),
+                "Traceability".to_string(),
+                "owner".to_string(),
+            );
+            circuit.alias_config = Some(CircuitAliasConfig {
+                dedup_strategy: Some(DedupStrategyKind::Fingerprint),
+                ..Default::default()
+            });
+            guard.store_circuit(&circuit).unwrap();
+            circuit
+        };
+
+        // The shared lot number no longer conflicts with the animal's SISBOV
+        let mut engine = VerificationEngine::new(Arc::clone(&storage), DfidEngine::new())
+            .with_dedup_scope(Some("ws-1"), None)
+            .unwrap();
+        let mut entry = DataLakeEntry::new(
+            Uuid::new_v4(),
+            vec![lot.clone(), sisbov.clone()],
+            "hash-animal".to_string(),
+            64,
+        );
+        match engine.process_entry(&mut entry).unwrap() {
+            VerificationResult::ItemEnriched { dfid } => assert_eq!(dfid, "DFID-ANIMAL"),
+            other => panic!("expected VerificationResult::ItemEnriched, got {other:?}"),
+        }
+
+        // The circuit's own choice wins over the workspace's
+        let mut engine = VerificationEngine::new(Arc::clone(&storage), DfidEngine::new())
+            .with_dedup_scope(Some("ws-1"), Some(&circuit.circuit_id))
+            .unwrap();
+        let mut entry = DataLakeEntry::new(
+            Uuid::new_v4(),
+            vec![lot, sisbov],
+            "hash-circuit".to_string(),
+            64,
+        );
+        match engine.process_entry(&mut entry).unwrap() {
+            VerificationResult::NewItemCreated { .. } => {}
+            other => panic!("expected VerificationResult::NewItemCreated, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_near_duplicate_lot_is_held_as_conflict() {
+        use crate::types::FuzzyAlgorithm;
+
+        let (storage, engine) = new_engine();
+        let existing = Identifier::new("lot", "LOT-00123");
+        {
+            let guard = storage.lock().unwrap();
+            guard
+                .store_item(&Item::new(
+                    "DFID-LOT".to_string(),
+                    vec![existing.clone()],
+                    Uuid::new_v4(),
+                ))
+                .unwrap();
+            guard
+                .store_identifier_mapping(&IdentifierMapping::new(
+                    existing.clone(),
+                    "DFID-LOT".to_string(),
+                    "primary".into(),
+                ))
+                .unwrap();
+        }
+        let mut engine = engine.with_fuzzy_matching(FuzzyMatchConfig {
+            algorithm: FuzzyAlgorithm::TokenSort,
+            min_confidence: 0.9,
+        });
+
+        let mut entry = DataLakeEntry::new(
+            Uuid::new_v4(),
+            vec![Identifier::new("lot", "LOT 123")],
+            "hash-lot".to_string(),
+            64,
+        );
+        let conflict_id = match engine.process_entry(&mut entry).unwrap() {
+            VerificationResult::ConflictDetected {
+                conflict_id,
+                conflicting_dfids,
+            } => {
+                assert_eq!(conflicting_dfids, vec!["DFID-LOT".to_string()]);
+                conflict_id
+            }
+            other => panic!("expected VerificationResult::ConflictDetected, got {other:?}"),
+        };
+        assert_eq!(entry.status, ProcessingStatus::Conflicted);
+
+        let guard = storage.lock().unwrap();
+        let conflict = guard
+            .get_conflict_resolution(&conflict_id)
+            .unwrap()
+            .unwrap();
+        assert!(conflict.requires_manual_review);
+        assert_eq!(conflict.fuzzy_matches.len(), 1);
+        assert_eq!(conflict.fuzzy_matches[0].matched_identifier, existing);
+        assert_eq!(conflict.fuzzy_matches[0].confidence, 1.0);
+        assert_eq!(guard.list_items().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_auto_link_policy_holds_weak_matches_for_review() {
+        use crate::identifier_types::DedupStrategyKind;
+        use crate::types::WorkspaceDedupConfig;
+
+        let (storage, _) = new_engine();
+        let lot = Identifier::contextual("bovino", "lote", "L-7");
+        let farm = Identifier::contextual("bovino", "fazenda", "F-1");
+        {
+            let guard = storage.lock().unwrap();
+            guard
+                .store_item(&Item::new(
+                    "DFID-LOT".to_string(),
+                    vec![lot.clone(), farm.clone()],
+                    Uuid::new_v4(),
+                ))
+                .unwrap();
+            for identifier in [&lot, &farm] {
+                guard
+                    .store_identifier_mapping(&IdentifierMapping::new(
+                        identifier.clone(),
+                        "DFID-LOT".to_string(),
+                        "primary".into(),
+                    ))
+                    .unwrap();
+            }
+            guard
+                .store_workspace_dedup_config(&WorkspaceDedupConfig {
+                    workspace_id: "ws-1".to_string(),
+                    strategy: DedupStrategyKind::CompositeScoring,
+                    fuzzy_matching: None,
+                    auto_link: Some(AutoLinkPolicy {
+                        min_confidence: 0.9,
+                        auto_merge_conflicts: false,
+                    }),
+                    updated_by: "owner".to_string(),
+                    updated_at: chrono::Utc::now(),
+                })
+                .unwrap();
+        }
+
+        // Two contextual identifiers score 0.8, short of the workspace's 0.9
+        let mut engine = VerificationEngine::new(Arc::clone(&storage), DfidEngine::new())
+            .with_dedup_scope(Some("ws-1"), None)
+            .unwrap();
+        let mut entry = DataLakeEntry::new(
+            Uuid::new_v4(),
+            vec![lot.clone(), farm.clone()],
+            "hash-weak".to_string(),
+            64,
+        );
+        let conflict_id = match engine.process_entry(&mut entry).unwrap() {
+            VerificationResult::ConflictDetected {
+                conflict_id,
+                conflicting_dfids,
+            } => {
+                assert_eq!(conflicting_dfids, vec!["DFID-LOT".to_string()]);
+                conflict_id
             }
+            other => panic!("expected VerificationResult::ConflictDetected, got {other:?}"),
+        };
+        assert_eq!(entry.status, ProcessingStatus::Conflicted);
+        assert!(
+            storage
+                .lock()
+                .unwrap()
+                .get_conflict_resolution(&conflict_id)
+                .unwrap()
+                .unwrap()
+                .requires_manual_review
+        );
+
+        // A laxer policy links the same match
+        let mut engine = engine.with_auto_link_policy(AutoLinkPolicy {
+            min_confidence: 0.75,
+            auto_merge_conflicts: false,
+        });
+        let mut entry = DataLakeEntry::new(
+            Uuid::new_v4(),
+            vec![lot, farm],
+            "hash-linked".to_string(),
+            64,
+        );
+        match engine.process_entry(&mut entry).unwrap() {
+            VerificationResult::ItemEnriched { dfid } => assert_eq!(dfid, "DFID-LOT"),
             other => panic!("expected VerificationResult::ItemEnriched, got {other:?}"),
         }
     }
+
+    #[test]
+    fn test_preview_reports_outcome_without_storing() {
+        let (storage, engine) = new_engine();
+        let lot = Identifier::new("lot", "LOT-1");
+        {
+            let guard = storage.lock().unwrap();
+            for dfid in ["DFID-A", "DFID-B"] {
+                guard
+                    .store_item(&Item::new(
+                        dfid.to_string(),
+                        vec![lot.clone()],
+                        Uuid::new_v4(),
+                    ))
+                    .unwrap();
+            }
+            guard
+                .store_identifier_mapping(&IdentifierMapping::new(
+                    lot.clone(),
+                    "DFID-A".to_string(),
+                    "primary".into(),
+                ))
+                .unwrap();
+        }
+
+        let preview = engine
+            .preview_verification(
+                b"weight",
+                std::slice::from_ref(&lot),
+                &SubmissionOrigin::default(),
+            )
+            .unwrap();
+        assert_eq!(preview.data_size, 6);
+        assert_eq!(preview.data_hash, crate::hashing::hash(b"weight"));
+        assert_eq!(
+            preview.outcome,
+            PreviewOutcome::EnrichExisting {
+                dfid: "DFID-A".to_string(),
+                confidence: 1.0,
+                conflicting_dfids: Vec::new(),
+            }
+        );
+
+        let fresh = Identifier::new("lot", "LOT-2");
+        let preview = engine
+            .preview_verification(
+                b"weight",
+                std::slice::from_ref(&fresh),
+                &SubmissionOrigin::default(),
+            )
+            .unwrap();
+        assert_eq!(preview.outcome, PreviewOutcome::NewItem);
+
+        // A lot mapped to two items would conflict; a strict policy keeps it for review
+        storage
+            .lock()
+            .unwrap()
+            .store_identifier_mapping(&IdentifierMapping::new(
+                lot.clone(),
+                "DFID-B".to_string(),
+                "primary".into(),
+            ))
+            .unwrap();
+        let engine = engine.with_auto_link_policy(AutoLinkPolicy {
+            min_confidence: 0.9,
+            auto_merge_conflicts: true,
+        });
+        match engine
+            .preview_verification(b"weight", &[lot], &SubmissionOrigin::default())
+            .unwrap()
+            .outcome
+        {
+            PreviewOutcome::Conflict {
+                conflicting_dfids,
+                confidence,
+                ..
+            } => {
+                assert_eq!(conflicting_dfids.len(), 2);
+                assert_eq!(confidence, 0.5);
+            }
+            other => panic!("expected PreviewOutcome::Conflict, got {other:?}"),
+        }
+
+        let guard = storage.lock().unwrap();
+        assert_eq!(guard.list_items().unwrap().len(), 2);
+        assert!(guard.get_identifier_mappings(&fresh).unwrap().is_empty());
+        assert!(guard.get_pending_conflicts().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_realtime_entries_verified_before_bulk() {
+        let (storage, mut engine) = new_engine();
+
+        // The backfill entry is older but must still wait for the live one
+        let bulk = DataLakeEntry::new(
+            Uuid::new_v4(),
+            vec![Identifier::new("batch_id", "historical")],
+            "hash-bulk".to_string(),
+            64,
+        )
+        .with_priority(IngestionPriority::Bulk);
+        let realtime = DataLakeEntry::new(
+            Uuid::new_v4(),
+            vec![Identifier::new("sensor_id", "live")],
+            "hash-live".to_string(),
+            64,
+        );
+        {
+            let guard = storage.lock().unwrap();
+            guard.store_data_lake_entry(&bulk).unwrap();
+            guard.store_data_lake_entry(&realtime).unwrap();
+        }
+
+        let results = engine.process_pending_entries().unwrap();
+        assert_eq!(results.len(), 2);
+
+        let guard = storage.lock().unwrap();
+        let live_dfid = guard
+            .get_data_lake_entry(&realtime.entry_id)
+            .unwrap()
+            .unwrap()
+            .linked_dfid
+            .unwrap();
+        match &results[0] {
+            VerificationResult::NewItemCreated { dfid } => assert_eq!(dfid, &live_dfid),
+            other => panic!("expected VerificationResult::NewItemCreated, got {other:?}"),
+        }
+        assert_eq!(
+            guard
+                .get_data_lake_entry(&bulk.entry_id)
+                .unwrap()
+                .unwrap()
+                .status,
+            ProcessingStatus::Completed
+        );
+    }
+
+    #[test]
+    fn test_every_bulk_entry_is_verified_across_batches() {
+        let (storage, mut engine) = new_engine();
+
+        let count = BULK_VERIFICATION_BATCH_SIZE * 2 + 1;
+        {
+            let guard = storage.lock().unwrap();
+            for n in 0..count {
+                let entry = DataLakeEntry::new(
+                    Uuid::new_v4(),
+                    vec![Identifier::new("batch_id", format!("historical-{n}"))],
+                    format!("hash-{n}"),
+                    64,
+                )
+                .with_priority(IngestionPriority::Bulk);
+                guard.store_data_lake_entry(&entry).unwrap();
+            }
+        }
+
+        let results = engine.process_pending_entries().unwrap();
+        assert_eq!(results.len(), count);
+        assert!(storage
+            .lock()
+            .unwrap()
+            .get_data_lake_entries_by_status(ProcessingStatus::Pending)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_next_pending_batch_puts_realtime_first() {
+        let (storage, engine) = new_engine();
+
+        let bulk: Vec<_> = (0..3)
+            .map(|n| {
+                DataLakeEntry::new(
+                    Uuid::new_v4(),
+                    vec![Identifier::new("batch_id", format!("historical-{n}"))],
+                    format!("hash-bulk-{n}"),
+                    64,
+                )
+                .with_priority(IngestionPriority::Bulk)
+            })
+            .collect();
+        let realtime = DataLakeEntry::new(
+            Uuid::new_v4(),
+            vec![Identifier::new("sensor_id", "live")],
+            "hash-live".to_string(),
+            64,
+        );
+        {
+            let guard = storage.lock().unwrap();
+            for entry in bulk.iter().chain(std::iter::once(&realtime)) {
+                guard.store_data_lake_entry(entry).unwrap();
+            }
+        }
+
+        let batch = engine.next_pending_batch(2).unwrap();
+        let ids: Vec<_> = batch.iter().map(|entry| entry.entry_id).collect();
+        assert_eq!(ids, vec![realtime.entry_id, bulk[0].entry_id]);
+    }
+
+    #[test]
+    fn test_workspace_rules_reject_and_route_submissions() {
+        let (storage, mut engine) = new_engine();
+        storage
+            .store_workspace_verification_rules(&crate::types::WorkspaceVerificationRules {
+                workspace_id: "ws-1".to_string(),
+                rules: vec![
+                    VerificationRule::RequireIdentifier {
+                        source: Some("field-app".to_string()),
+                        key: "sisbov".to_string(),
+                    },
+                    VerificationRule::RequireFields {
+                        source: None,
+                        fields: vec!["animal.weight".to_string()],
+                    },
+                    VerificationRule::ManualReview {
+                        source: "lab".to_string(),
+                    },
+                ],
+                updated_by: "owner".to_string(),
+                updated_at: chrono::Utc::now(),
+            })
+            .unwrap();
+        let origin = |source: &str| SubmissionOrigin {
+            source: Some(source.to_string()),
+            workspace_id: Some("ws-1".to_string()),
+        };
+        let lot = Identifier::new("lot", "L-1");
+
+        // Payload fields are checked while the payload is at hand
+        match engine
+            .check_rules(
+                &origin("lab"),
+                std::slice::from_ref(&lot),
+                Some(br#"{"animal": {}}"#),
+            )
+            .unwrap()
+        {
+            RuleVerdict::Reject { violations } => {
+                assert_eq!(
+                    violations,
+                    vec!["Payload field 'animal.weight' is required"]
+                );
+            }
+            other => panic!("expected RuleVerdict::Reject, got {other:?}"),
+        }
+
+        let mut missing = DataLakeEntry::new(Uuid::new_v4(), vec![lot.clone()], "h1".into(), 1)
+            .with_origin(origin("field-app"));
+        assert!(matches!(
+            engine.process_entry(&mut missing),
+            Err(VerificationError::RuleViolation(_))
+        ));
+
+        let mut reviewed = DataLakeEntry::new(Uuid::new_v4(), vec![lot.clone()], "h2".into(), 1)
+            .with_origin(origin("lab"));
+        let pending_id = match engine.process_entry(&mut reviewed).unwrap() {
+            VerificationResult::HeldForReview { pending_id } => pending_id,
+            other => panic!("expected VerificationResult::HeldForReview, got {other:?}"),
+        };
+        assert_eq!(reviewed.status, ProcessingStatus::Conflicted);
+        let pending = storage.get_pending_item(&pending_id).unwrap().unwrap();
+        assert_eq!(pending.workspace_id.as_deref(), Some("ws-1"));
+        assert!(pending.manual_review_required);
+        assert!(storage.list_items().unwrap().is_empty());
+
+        // Other workspaces are not bound by ws-1's rules
+        let mut elsewhere = DataLakeEntry::new(Uuid::new_v4(), vec![lot], "h3".into(), 1)
+            .with_origin(SubmissionOrigin {
+                source: Some("lab".to_string()),
+                workspace_id: Some("ws-2".to_string()),
+            });
+        assert!(matches!(
+            engine.process_entry(&mut elsewhere).unwrap(),
+            VerificationResult::NewItemCreated { .. }
+        ));
+    }
 }
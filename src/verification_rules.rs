@@ -0,0 +1,219 @@
+//! Per-workspace verification rules.
+//!
+//! A workspace lists declarative rules about what its submissions must look
+//! like: identifiers a source has to send, payload fields that must be
+//! present, sources whose data a person reviews before it becomes an item.
+//! Submissions breaking a rule are rejected at intake, where the payload is
+//! still at hand; identifier and review rules are checked again when the
+//! verification engine takes the entry, since rules may have changed since.
+
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{Identifier, VerificationRule, WorkspaceVerificationRules};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Rules a workspace may keep
+pub const MAX_RULES: usize = 100;
+
+#[derive(Debug)]
+pub enum VerificationRulesError {
+    StorageError(StorageError),
+    ValidationError(String),
+    PermissionDenied(String),
+}
+
+impl From<StorageError> for VerificationRulesError {
+    fn from(err: StorageError) -> Self {
+        VerificationRulesError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for VerificationRulesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerificationRulesError::StorageError(e) => write!(f, "Storage error: {e}"),
+            VerificationRulesError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            VerificationRulesError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for VerificationRulesError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct VerificationRulesInput {
+    /// Replaces the workspace's rules; empty clears them
+    pub rules: Vec<VerificationRule>,
+}
+
+/// What the rules say about a submission
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "verdict", rename_all = "snake_case")]
+pub enum RuleVerdict {
+    Accept,
+    Reject { violations: Vec<String> },
+    ManualReview { source: String },
+}
+
+fn applies_to(rule_source: Option<&String>, source: Option<&str>) -> bool {
+    rule_source.is_none_or(|rule_source| Some(rule_source.as_str()) == source)
+}
+
+fn has_field(payload: &Value, path: &str) -> bool {
+    path.split('.')
+        .try_fold(payload, |value, segment| value.get(segment))
+        .is_some_and(|value| !value.is_null())
+}
+
+/// Judge a submission from `source` by `rules`. Field rules are skipped
+/// without a payload; a rejection wins over routing to review.
+pub fn evaluate_rules(
+    rules: &[VerificationRule],
+    source: Option<&str>,
+    identifiers: &[Identifier],
+    payload: Option<&[u8]>,
+) -> RuleVerdict {
+    let parsed: Option<Option<Value>> =
+        payload.map(|payload| serde_json::from_slice::<Value>(payload).ok());
+    let mut violations = Vec::new();
+    let mut review_source = None;
+
+    for rule in rules {
+        match rule {
+            VerificationRule::RequireIdentifier {
+                source: rule_source,
+                key,
+            } if applies_to(rule_source.as_ref(), source)
+                && !identifiers.iter().any(|id| &id.key == key) =>
+            {
+                violations.push(format!("Identifier '{key}' is required"));
+            }
+            VerificationRule::RequireFields {
+                source: rule_source,
+                fields,
+            } if applies_to(rule_source.as_ref(), source) => match &parsed {
+                None => {}
+                Some(None) => violations.push("Payload must be a JSON document".to_string()),
+                Some(Some(payload)) => {
+                    for field in fields {
+                        if !has_field(payload, field) {
+                            violations.push(format!("Payload field '{field}' is required"));
+                        }
+                    }
+                }
+            },
+            VerificationRule::ManualReview {
+                source: rule_source,
+            } if Some(rule_source.as_str()) == source => {
+                review_source.get_or_insert_with(|| rule_source.clone());
+            }
+            _ => {}
+        }
+    }
+
+    if !violations.is_empty() {
+        violations.dedup();
+        return RuleVerdict::Reject { violations };
+    }
+    match review_source {
+        Some(source) => RuleVerdict::ManualReview { source },
+        None => RuleVerdict::Accept,
+    }
+}
+
+fn validate(rules: &[VerificationRule]) -> Result<(), VerificationRulesError> {
+    if rules.len() > MAX_RULES {
+        return Err(VerificationRulesError::ValidationError(format!(
+            "A workspace may have at most {MAX_RULES} rules"
+        )));
+    }
+    let blank_source = |source: Option<&String>| source.is_some_and(|s| s.trim().is_empty());
+    for rule in rules {
+        let valid = match rule {
+            VerificationRule::RequireIdentifier { source, key } => {
+                !blank_source(source.as_ref()) && !key.trim().is_empty()
+            }
+            VerificationRule::RequireFields { source, fields } => {
+                !blank_source(source.as_ref())
+                    && !fields.is_empty()
+                    && fields
+                        .iter()
+                        .all(|f| f.split('.').all(|segment| !segment.trim().is_empty()))
+            }
+            VerificationRule::ManualReview { source } => !source.trim().is_empty(),
+        };
+        if !valid {
+            return Err(VerificationRulesError::ValidationError(format!(
+                "Incomplete rule: {}",
+                serde_json::to_string(rule).unwrap_or_default()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Reads and replaces the rules of a workspace on behalf of its members
+pub struct VerificationRulesEngine<S: StorageBackend> {
+    storage: S,
+}
+
+impl<S: StorageBackend> VerificationRulesEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// The workspace's rules; none if it never set any
+    pub fn get_rules(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+    ) -> Result<WorkspaceVerificationRules, VerificationRulesError> {
+        self.check_member(user_id, workspace_id)?;
+        Ok(self
+            .storage
+            .get_workspace_verification_rules(workspace_id)?
+            .unwrap_or_else(|| WorkspaceVerificationRules {
+                workspace_id: workspace_id.to_string(),
+                rules: Vec::new(),
+                updated_by: "system".to_string(),
+                updated_at: DateTime::<Utc>::UNIX_EPOCH,
+            }))
+    }
+
+    pub fn set_rules(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+        input: VerificationRulesInput,
+        now: DateTime<Utc>,
+    ) -> Result<WorkspaceVerificationRules, VerificationRulesError> {
+        self.check_member(user_id, workspace_id)?;
+        validate(&input.rules)?;
+        let rules = WorkspaceVerificationRules {
+            workspace_id: workspace_id.to_string(),
+            rules: input.rules,
+            updated_by: user_id.to_string(),
+            updated_at: now,
+        };
+        self.storage.store_workspace_verification_rules(&rules)?;
+        Ok(rules)
+    }
+
+    fn check_member(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+    ) -> Result<(), VerificationRulesError> {
+        let account = self.storage.get_user_account(user_id)?.ok_or_else(|| {
+            VerificationRulesError::PermissionDenied(format!("Unknown user {user_id}"))
+        })?;
+        if account.is_admin || account.workspace_id.as_deref() == Some(workspace_id) {
+            Ok(())
+        } else {
+            Err(VerificationRulesError::PermissionDenied(
+                "Only workspace members can manage its verification rules".to_string(),
+            ))
+        }
+    }
+}
@@ -0,0 +1,449 @@
+//! Background verification of pending data lake entries.
+//!
+//! Every `interval_secs` the worker takes up to `batch_size` pending entries,
+//! realtime before bulk, and verifies them on up to `concurrency` blocking
+//! threads; while a full batch keeps coming back it goes again without
+//! waiting. Entries that share an identifier are always verified on the same
+//! thread, so two of them cannot both create an item.
+//!
+//! Settings, the pause flag and progress counters live in a process-wide
+//! registry, like scaling signals, so the admin endpoints reach the running
+//! worker. They start from the environment and reset on restart.
+
+use crate::dfid_engine::DfidEngine;
+use crate::storage::{StorageBackend, StorageError};
+use crate::types::{DataLakeEntry, IngestionPriority, ProcessingStatus};
+use crate::verification_engine::{VerificationEngine, VerificationResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+
+pub const DEFAULT_INTERVAL_SECS: u64 = 10;
+pub const DEFAULT_BATCH_SIZE: usize = 100;
+pub const DEFAULT_CONCURRENCY: usize = 4;
+pub const MAX_BATCH_SIZE: usize = 10_000;
+pub const MAX_CONCURRENCY: usize = 32;
+
+#[derive(Debug)]
+pub enum VerificationSchedulerError {
+    StorageError(StorageError),
+    ValidationError(String),
+    VerificationError(String),
+}
+
+impl From<StorageError> for VerificationSchedulerError {
+    fn from(err: StorageError) -> Self {
+        VerificationSchedulerError::StorageError(err)
+    }
+}
+
+impl std::fmt::Display for VerificationSchedulerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerificationSchedulerError::StorageError(e) => write!(f, "Storage error: {e}"),
+            VerificationSchedulerError::ValidationError(e) => write!(f, "Validation error: {e}"),
+            VerificationSchedulerError::VerificationError(e) => {
+                write!(f, "Verification error: {e}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VerificationSchedulerError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationSchedulerConfig {
+    pub interval_secs: u64,
+    pub batch_size: usize,
+    pub concurrency: usize,
+}
+
+impl Default for VerificationSchedulerConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: DEFAULT_INTERVAL_SECS,
+            batch_size: DEFAULT_BATCH_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+}
+
+impl VerificationSchedulerConfig {
+    /// `VERIFICATION_INTERVAL_SECS`, `VERIFICATION_BATCH_SIZE` and
+    /// `VERIFICATION_CONCURRENCY`; unset or invalid values fall back to the
+    /// defaults
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let config = Self {
+            interval_secs: env_or("VERIFICATION_INTERVAL_SECS", default.interval_secs),
+            batch_size: env_or("VERIFICATION_BATCH_SIZE", default.batch_size),
+            concurrency: env_or("VERIFICATION_CONCURRENCY", default.concurrency),
+        };
+        if config.validate().is_ok() {
+            config
+        } else {
+            default
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), VerificationSchedulerError> {
+        if self.interval_secs == 0 {
+            return Err(VerificationSchedulerError::ValidationError(
+                "Interval must be at least 1 second".to_string(),
+            ));
+        }
+        if !(1..=MAX_BATCH_SIZE).contains(&self.batch_size) {
+            return Err(VerificationSchedulerError::ValidationError(format!(
+                "Batch size must be between 1 and {MAX_BATCH_SIZE}"
+            )));
+        }
+        if !(1..=MAX_CONCURRENCY).contains(&self.concurrency) {
+            return Err(VerificationSchedulerError::ValidationError(format!(
+                "Concurrency must be between 1 and {MAX_CONCURRENCY}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Outcome of one batch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct VerificationSchedulerRun {
+    pub entries: usize,
+    pub items_created: usize,
+    pub items_enriched: usize,
+    pub conflicts: usize,
+    /// Entries verification rules sent to the review queue
+    pub held_for_review: usize,
+    pub failures: usize,
+}
+
+/// Worker settings and progress since the process started
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VerificationSchedulerStatus {
+    pub config: VerificationSchedulerConfig,
+    pub paused: bool,
+    pub paused_by: Option<String>,
+    pub paused_at: Option<DateTime<Utc>>,
+    pub runs: u64,
+    pub entries_processed: u64,
+    pub items_created: u64,
+    pub items_enriched: u64,
+    pub conflicts: u64,
+    pub held_for_review: u64,
+    pub failures: u64,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_run_entries: usize,
+    pub last_run_ms: u64,
+    pub last_error: Option<String>,
+    /// Entries still waiting, read from storage when the status is requested
+    pub pending_realtime: usize,
+    pub pending_bulk: usize,
+}
+
+fn registry() -> &'static Mutex<VerificationSchedulerStatus> {
+    static REGISTRY: OnceLock<Mutex<VerificationSchedulerStatus>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        Mutex::new(VerificationSchedulerStatus {
+            config: VerificationSchedulerConfig::from_env(),
+            ..Default::default()
+        })
+    })
+}
+
+pub fn config() -> VerificationSchedulerConfig {
+    registry().lock().unwrap().config
+}
+
+/// Replace the worker's settings; they apply from its next batch
+pub fn configure(config: VerificationSchedulerConfig) -> Result<(), VerificationSchedulerError> {
+    config.validate()?;
+    registry().lock().unwrap().config = config;
+    Ok(())
+}
+
+pub fn is_paused() -> bool {
+    registry().lock().unwrap().paused
+}
+
+/// Stop taking new batches; a batch already running finishes
+pub fn pause(paused_by: &str, now: DateTime<Utc>) {
+    let mut status = registry().lock().unwrap();
+    if !status.paused {
+        status.paused = true;
+        status.paused_by = Some(paused_by.to_string());
+        status.paused_at = Some(now);
+    }
+}
+
+pub fn resume() {
+    let mut status = registry().lock().unwrap();
+    status.paused = false;
+    status.paused_by = None;
+    status.paused_at = None;
+}
+
+/// Registry state plus the current pending backlog
+pub fn status<S: StorageBackend>(
+    storage: &S,
+) -> Result<VerificationSchedulerStatus, VerificationSchedulerError> {
+    let pending = storage.get_data_lake_entries_by_status(ProcessingStatus::Pending)?;
+    let mut status = registry().lock().unwrap().clone();
+    status.pending_realtime = pending
+        .iter()
+        .filter(|entry| entry.priority == IngestionPriority::Realtime)
+        .count();
+    status.pending_bulk = pending.len() - status.pending_realtime;
+    Ok(status)
+}
+
+fn record_run(
+    run: &VerificationSchedulerRun,
+    at: DateTime<Utc>,
+    elapsed: std::time::Duration,
+    error: Option<String>,
+) {
+    let mut status = registry().lock().unwrap();
+    status.runs += 1;
+    status.entries_processed += run.entries as u64;
+    status.items_created += run.items_created as u64;
+    status.items_enriched += run.items_enriched as u64;
+    status.conflicts += run.conflicts as u64;
+    status.held_for_review += run.held_for_review as u64;
+    status.failures += run.failures as u64;
+    status.last_run_at = Some(at);
+    status.last_run_entries = run.entries;
+    status.last_run_ms = elapsed.as_millis() as u64;
+    if error.is_some() {
+        status.last_error = error;
+    }
+}
+
+pub struct VerificationScheduler<S: StorageBackend> {
+    storage: S,
+    /// Shared by all lanes and runs, leasing through the storage
+    dfid_engine: DfidEngine,
+}
+
+impl<S: StorageBackend + Clone + Send + Sync + 'static> VerificationScheduler<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            dfid_engine: DfidEngine::coordinated(storage.clone()),
+            storage,
+        }
+    }
+
+    /// Verify one batch of pending entries with the given settings
+    pub async fn run_once(
+        &self,
+        config: VerificationSchedulerConfig,
+    ) -> Result<VerificationSchedulerRun, VerificationSchedulerError> {
+        let started_at = Utc::now();
+        let start = std::time::Instant::now();
+        let batch = VerificationEngine::new(self.storage.clone(), self.dfid_engine.clone())
+            .next_pending_batch(config.batch_size)
+            .map_err(|e| VerificationSchedulerError::VerificationError(e.to_string()))?;
+
+        let mut run = VerificationSchedulerRun {
+            entries: batch.len(),
+            ..Default::default()
+        };
+        if batch.is_empty() {
+            return Ok(run);
+        }
+
+        let workers: Vec<_> = partition(batch, config.concurrency)
+            .into_iter()
+            .map(|lane| {
+                let storage = self.storage.clone();
+                let dfid_engine = self.dfid_engine.clone();
+                let lane_size = lane.len();
+                let handle = tokio::task::spawn_blocking(move || {
+                    VerificationEngine::new(storage, dfid_engine).process_entries(lane)
+                });
+                (lane_size, handle)
+            })
+            .collect();
+
+        let mut errors = Vec::new();
+        for (lane_size, handle) in workers {
+            let results = match handle.await {
+                Ok(Ok(results)) => results,
+                Ok(Err(e)) => {
+                    errors.push(e.to_string());
+                    Vec::new()
+                }
+                Err(e) => {
+                    errors.push(format!("Verification worker panicked: {e}"));
+                    Vec::new()
+                }
+            };
+            run.failures += lane_size - results.len();
+            for result in results {
+                match result {
+                    VerificationResult::NewItemCreated { .. } => run.items_created += 1,
+                    VerificationResult::ItemEnriched { .. } => run.items_enriched += 1,
+                    VerificationResult::ConflictDetected { .. } => run.conflicts += 1,
+                    VerificationResult::HeldForReview { .. } => run.held_for_review += 1,
+                }
+            }
+        }
+
+        record_run(&run, started_at, start.elapsed(), errors.first().cloned());
+        Ok(run)
+    }
+
+    /// Drain pending entries in the background until the process exits,
+    /// re-reading the settings and pause flag before every batch
+    pub fn spawn_worker(storage: S) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let scheduler = VerificationScheduler::new(storage);
+            let mut backlog = false;
+            loop {
+                let config = config();
+                if !backlog {
+                    tokio::time::sleep(std::time::Duration::from_secs(config.interval_secs)).await;
+                }
+                backlog = false;
+                if is_paused() {
+                    continue;
+                }
+                match scheduler.run_once(config).await {
+                    Ok(run) => {
+                        if run.entries > 0 {
+                            tracing::info!(
+                                "🔎 Verified {} pending entries ({} created, {} enriched, {} conflicts, {} failed)",
+                                run.entries,
+                                run.items_created,
+                                run.items_enriched,
+                                run.conflicts,
+                                run.failures
+                            );
+                        }
+                        // A full batch means more may be waiting
+                        backlog = run.entries == config.batch_size && run.failures < run.entries;
+                    }
+                    Err(e) => tracing::warn!("⚠️  Verification batch failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+/// Namespace, key and value of an identifier
+type IdentifierKey = (String, String, String);
+
+/// Split a batch into at most `lanes` groups without separating entries that
+/// share an identifier, keeping realtime before bulk and oldest first in each
+fn partition(entries: Vec<DataLakeEntry>, lanes: usize) -> Vec<Vec<DataLakeEntry>> {
+    let mut groups: Vec<(HashSet<IdentifierKey>, Vec<DataLakeEntry>)> = Vec::new();
+    for entry in entries {
+        let keys: HashSet<_> = entry
+            .identifiers
+            .iter()
+            .map(|id| (id.namespace.clone(), id.key.clone(), id.value.clone()))
+            .collect();
+        let mut merged = (keys, vec![entry]);
+        let mut i = 0;
+        while i < groups.len() {
+            if groups[i].0.is_disjoint(&merged.0) {
+                i += 1;
+            } else {
+                let (keys, entries) = groups.swap_remove(i);
+                merged.0.extend(keys);
+                merged.1.extend(entries);
+            }
+        }
+        groups.push(merged);
+    }
+
+    // Largest groups first, each onto the lane with the fewest entries
+    groups.sort_by_key(|(_, entries)| std::cmp::Reverse(entries.len()));
+    let mut result: Vec<Vec<DataLakeEntry>> = vec![Vec::new(); lanes.max(1)];
+    for (_, entries) in groups {
+        if let Some(lane) = result.iter_mut().min_by_key(|lane| lane.len()) {
+            lane.extend(entries);
+        }
+    }
+    result.retain(|lane| !lane.is_empty());
+    for lane in &mut result {
+        lane.sort_by_key(|entry| {
+            (
+                entry.priority != IngestionPriority::Realtime,
+                entry.timestamp,
+            )
+        });
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::types::Identifier;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_scheduler_drains_pending_entries_in_batches() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        // Two entries share a lot, so they must end up on one item
+        for (key, value) in [("lot", "A"), ("lot", "A"), ("lot", "B"), ("animal", "1")] {
+            storage
+                .store_data_lake_entry(&DataLakeEntry::new(
+                    Uuid::new_v4(),
+                    vec![Identifier::new(key, value)],
+                    format!("hash-{key}-{value}"),
+                    64,
+                ))
+                .unwrap();
+        }
+        let lanes = partition(
+            storage
+                .get_data_lake_entries_by_status(ProcessingStatus::Pending)
+                .unwrap(),
+            4,
+        );
+        assert_eq!(lanes.len(), 3);
+
+        let scheduler = VerificationScheduler::new(Arc::clone(&storage));
+        let config = VerificationSchedulerConfig {
+            interval_secs: 1,
+            batch_size: 3,
+            concurrency: 4,
+        };
+        let first = scheduler.run_once(config).await.unwrap();
+        let second = scheduler.run_once(config).await.unwrap();
+        assert_eq!((first.entries, second.entries), (3, 1));
+        assert_eq!(
+            first.items_created + second.items_created,
+            3,
+            "the shared lot must not create a second item"
+        );
+        assert_eq!(first.items_enriched + second.items_enriched, 1);
+        assert_eq!(scheduler.run_once(config).await.unwrap().entries, 0);
+
+        let status = status(&storage).unwrap();
+        assert_eq!((status.pending_realtime, status.pending_bulk), (0, 0));
+        assert!(status.entries_processed >= 4);
+
+        pause("admin-1", Utc::now());
+        assert!(is_paused());
+        resume();
+        assert!(!is_paused());
+        assert!(configure(VerificationSchedulerConfig {
+            concurrency: 0,
+            ..config
+        })
+        .is_err());
+    }
+}
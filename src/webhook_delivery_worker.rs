@@ -1,4 +1,8 @@
-use crate::types::{DeliveryStatus, HttpMethod, WebhookConfig};
+use crate::scaling_signals;
+use crate::types::{
+    DeliveryStatus, HttpMethod, IngestionPriority, WebhookConfig, WebhookDelivery,
+    WebhookReplayResult, WorkQueue,
+};
 use chrono::Utc;
 use std::time::Duration;
 use tokio::sync::mpsc;
@@ -9,29 +13,68 @@ pub struct DeliveryTask {
     pub webhook: WebhookConfig,
     pub payload: serde_json::Value,
     pub delivery_id: Uuid,
+    pub priority: IngestionPriority,
 }
 
+/// Delivery queue with one lane per ingestion priority, so bulk fan-out cannot
+/// hold up realtime deliveries
 pub struct WebhookDeliveryQueue {
-    tx: mpsc::Sender<DeliveryTask>,
+    realtime_tx: mpsc::Sender<DeliveryTask>,
+    bulk_tx: mpsc::Sender<DeliveryTask>,
+}
+
+/// Receiving side of a [`WebhookDeliveryQueue`]; always drains the realtime lane first
+pub struct DeliveryReceiver {
+    realtime_rx: mpsc::Receiver<DeliveryTask>,
+    bulk_rx: mpsc::Receiver<DeliveryTask>,
+}
+
+impl DeliveryReceiver {
+    /// Next task, preferring realtime; `None` once both lanes are closed and empty
+    pub async fn recv(&mut self) -> Option<DeliveryTask> {
+        tokio::select! {
+            biased;
+            Some(task) = self.realtime_rx.recv() => Some(task),
+            Some(task) = self.bulk_rx.recv() => Some(task),
+            else => None,
+        }
+    }
 }
 
 impl WebhookDeliveryQueue {
-    pub fn new(buffer_size: usize) -> (Self, mpsc::Receiver<DeliveryTask>) {
-        let (tx, rx) = mpsc::channel(buffer_size);
-        (Self { tx }, rx)
+    /// `buffer_size` applies to each lane
+    pub fn new(buffer_size: usize) -> (Self, DeliveryReceiver) {
+        let (realtime_tx, realtime_rx) = mpsc::channel(buffer_size);
+        let (bulk_tx, bulk_rx) = mpsc::channel(buffer_size);
+        (
+            Self {
+                realtime_tx,
+                bulk_tx,
+            },
+            DeliveryReceiver {
+                realtime_rx,
+                bulk_rx,
+            },
+        )
     }
 
     pub async fn enqueue(&self, task: DeliveryTask) -> Result<(), String> {
-        self.tx
-            .send(task)
+        let priority = task.priority;
+        let tx = match priority {
+            IngestionPriority::Realtime => &self.realtime_tx,
+            IngestionPriority::Bulk => &self.bulk_tx,
+        };
+        tx.send(task)
             .await
-            .map_err(|e| format!("Failed to enqueue webhook delivery: {e}"))
+            .map_err(|e| format!("Failed to enqueue webhook delivery: {e}"))?;
+        scaling_signals::record_enqueued(WorkQueue::webhook_delivery(priority), 1);
+        Ok(())
     }
 }
 
 /// Background worker that processes webhook deliveries
 pub async fn webhook_delivery_worker(
-    mut rx: mpsc::Receiver<DeliveryTask>,
+    mut rx: DeliveryReceiver,
     storage_tx: mpsc::Sender<DeliveryStatusUpdate>,
 ) {
     let http_client = reqwest::Client::builder()
@@ -40,6 +83,7 @@ pub async fn webhook_delivery_worker(
         .expect("Failed to create HTTP client");
 
     while let Some(task) = rx.recv().await {
+        let start = std::time::Instant::now();
         let result = deliver_webhook_with_retry(
             &http_client,
             &task.webhook,
@@ -48,6 +92,11 @@ pub async fn webhook_delivery_worker(
             &storage_tx,
         )
         .await;
+        scaling_signals::record_completed(
+            WorkQueue::webhook_delivery(task.priority),
+            start.elapsed(),
+            result.is_ok(),
+        );
 
         if let Err(e) = result {
             eprintln!("Webhook delivery failed for {}: {}", task.delivery_id, e);
@@ -67,46 +116,87 @@ pub struct DeliveryStatusUpdate {
     pub next_retry_at: Option<chrono::DateTime<Utc>>,
 }
 
-async fn deliver_webhook_with_retry(
-    http_client: &reqwest::Client,
+/// Keyed BLAKE3 signature of `"{timestamp}.{body}"`, hex; sent when
+/// `WEBHOOK_SIGNING_SECRET` is set
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+/// Unix seconds the signature was made at
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+pub const DELIVERY_ID_HEADER: &str = "X-Webhook-Delivery";
+/// "true" on replays of a past delivery, which keep its delivery id
+pub const REPLAY_HEADER: &str = "X-Webhook-Replay";
+/// Longest response body kept from a replay
+const MAX_REPLAY_RESPONSE_BYTES: usize = 4096;
+
+/// Request body as sent: the JSON payload, or a compact JWE when the
+/// subscriber registered an encryption key
+pub struct WebhookBody {
+    pub content_type: &'static str,
+    pub bytes: Vec<u8>,
+}
+
+pub fn encode_body(
     webhook: &WebhookConfig,
     payload: &serde_json::Value,
-    delivery_id: Uuid,
-    storage_tx: &mpsc::Sender<DeliveryStatusUpdate>,
-) -> Result<(), String> {
-    let max_retries = webhook.retry_config.max_retries;
-    let mut attempt = 0;
+) -> Result<WebhookBody, String> {
+    match &webhook.encryption_key {
+        Some(key) => crate::webhook_encryption::encrypt_payload(key, payload)
+            .map(|jwe| WebhookBody {
+                content_type: crate::webhook_encryption::JWE_CONTENT_TYPE,
+                bytes: jwe.into_bytes(),
+            })
+            .map_err(|e| e.to_string()),
+        None => serde_json::to_vec(payload)
+            .map(|bytes| WebhookBody {
+                content_type: "application/json",
+                bytes,
+            })
+            .map_err(|e| format!("Failed to serialize payload: {e}")),
+    }
+}
 
-    loop {
-        attempt += 1;
+/// Signature of a body sent at `timestamp`, for [`SIGNATURE_HEADER`]
+pub fn sign_delivery(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut signed = format!("{timestamp}.").into_bytes();
+    signed.extend_from_slice(body);
+    crate::change_feed_engine::sign_payload(secret, &signed)
+}
 
-        // Update delivery status to in progress
-        let _ = storage_tx
-            .send(DeliveryStatusUpdate {
-                delivery_id,
-                status: DeliveryStatus::InProgress,
-                attempts: attempt,
-                response_code: None,
-                response_body: None,
-                error_message: None,
-                delivered_at: None,
-                next_retry_at: None,
-            })
-            .await;
+/// Secret deliveries are signed with, from `WEBHOOK_SIGNING_SECRET`
+fn signing_secret() -> Option<String> {
+    std::env::var("WEBHOOK_SIGNING_SECRET")
+        .ok()
+        .filter(|secret| !secret.is_empty())
+}
 
-        // Build HTTP request
-        let mut request = match webhook.method {
-            HttpMethod::Post => http_client.post(&webhook.url),
-            HttpMethod::Put => http_client.put(&webhook.url),
-            HttpMethod::Patch => http_client.patch(&webhook.url),
-        };
+/// Request for one attempt, signed afresh. Replays to a test URL leave out the
+/// webhook's credentials, which are meant for the subscriber only.
+fn build_request(
+    http_client: &reqwest::Client,
+    webhook: &WebhookConfig,
+    url: &str,
+    body: &WebhookBody,
+    delivery_id: Uuid,
+    signing_secret: Option<&str>,
+    with_credentials: bool,
+) -> reqwest::RequestBuilder {
+    let mut request = match webhook.method {
+        HttpMethod::Post => http_client.post(url),
+        HttpMethod::Put => http_client.put(url),
+        HttpMethod::Patch => http_client.patch(url),
+    };
 
-        // Add headers
+    // Custom headers double as credentials for the CustomHeader auth type
+    let custom_header_auth = matches!(
+        webhook.auth_type,
+        crate::types::WebhookAuthType::CustomHeader
+    );
+    if with_credentials || !custom_header_auth {
         for (key, value) in &webhook.headers {
             request = request.header(key, value);
         }
+    }
 
-        // Add authentication
+    if with_credentials {
         request = match &webhook.auth_type {
             crate::types::WebhookAuthType::None => request,
             crate::types::WebhookAuthType::BearerToken => {
@@ -140,11 +230,132 @@ async fn deliver_webhook_with_retry(
                 request
             }
         };
+    }
 
-        // Set content type and body
+    request = request.header(DELIVERY_ID_HEADER, delivery_id.to_string());
+    if let Some(secret) = signing_secret {
+        let timestamp = Utc::now().timestamp();
         request = request
-            .header("Content-Type", "application/json")
-            .json(payload);
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(
+                SIGNATURE_HEADER,
+                sign_delivery(secret, timestamp, &body.bytes),
+            );
+    }
+    request
+        .header("Content-Type", body.content_type)
+        .body(body.bytes.clone())
+}
+
+/// Send a past delivery's payload once to `target_url`, marked as a replay.
+/// The body is encoded as for the original delivery; nothing is stored.
+pub async fn replay_delivery(
+    http_client: &reqwest::Client,
+    webhook: &WebhookConfig,
+    delivery: &WebhookDelivery,
+    target_url: &str,
+) -> Result<WebhookReplayResult, String> {
+    let body = encode_body(webhook, &delivery.payload)?;
+    let secret = signing_secret();
+    let request = build_request(
+        http_client,
+        webhook,
+        target_url,
+        &body,
+        delivery.id,
+        secret.as_deref(),
+        false,
+    )
+    .header(REPLAY_HEADER, "true");
+
+    let replayed_at = Utc::now();
+    let start = std::time::Instant::now();
+    let (response_code, response_body, error_message) = match request.send().await {
+        Ok(response) => {
+            let status_code = response.status().as_u16();
+            let mut text = response.text().await.unwrap_or_default();
+            if text.len() > MAX_REPLAY_RESPONSE_BYTES {
+                let mut end = MAX_REPLAY_RESPONSE_BYTES;
+                while !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+                text.truncate(end);
+            }
+            (Some(status_code), Some(text), None)
+        }
+        Err(e) => (None, None, Some(format!("Network error: {e}"))),
+    };
+
+    Ok(WebhookReplayResult {
+        delivery_id: delivery.id,
+        webhook_id: webhook.id,
+        target_url: target_url.to_string(),
+        payload_bytes: body.bytes.len(),
+        signed: secret.is_some(),
+        replayed_at,
+        duration_ms: start.elapsed().as_millis() as u64,
+        response_code,
+        response_body,
+        error_message,
+    })
+}
+
+async fn deliver_webhook_with_retry(
+    http_client: &reqwest::Client,
+    webhook: &WebhookConfig,
+    payload: &serde_json::Value,
+    delivery_id: Uuid,
+    storage_tx: &mpsc::Sender<DeliveryStatusUpdate>,
+) -> Result<(), String> {
+    let max_retries = webhook.retry_config.max_retries;
+    let mut attempt = 0;
+
+    let secret = signing_secret();
+    let body = match encode_body(webhook, payload) {
+        Ok(body) => body,
+        Err(e) => {
+            let _ = storage_tx
+                .send(DeliveryStatusUpdate {
+                    delivery_id,
+                    status: DeliveryStatus::Failed,
+                    attempts: 0,
+                    response_code: None,
+                    response_body: None,
+                    error_message: Some(e.clone()),
+                    delivered_at: None,
+                    next_retry_at: None,
+                })
+                .await;
+            return Err(e);
+        }
+    };
+
+    loop {
+        attempt += 1;
+
+        // Update delivery status to in progress
+        let _ = storage_tx
+            .send(DeliveryStatusUpdate {
+                delivery_id,
+                status: DeliveryStatus::InProgress,
+                attempts: attempt,
+                response_code: None,
+                response_body: None,
+                error_message: None,
+                delivered_at: None,
+                next_retry_at: None,
+            })
+            .await;
+
+        let request = build_request(
+            http_client,
+            webhook,
+            &webhook.url,
+            &body,
+            delivery_id,
+            secret.as_deref(),
+            true,
+        );
 
         // Send request
         match request.send().await {
@@ -1,12 +1,15 @@
 use crate::logging::LoggingEngine;
 use crate::storage::StorageBackend;
 use crate::types::{
-    DeliveryStatus, PostActionTrigger, WebhookConfig, WebhookDelivery, WebhookPayload,
+    DeliveryStatus, Event, PostActionTrigger, WebhookConfig, WebhookDelivery, WebhookFilter,
+    WebhookPayload, WebhookReplayResult,
 };
 use crate::webhook_delivery_worker::{DeliveryTask, WebhookDeliveryQueue};
 use std::sync::Arc;
 use uuid::Uuid;
 
+const MAX_FILTER_PREDICATES: usize = 20;
+
 #[derive(Debug)]
 pub enum WebhookError {
     StorageError(String),
@@ -52,12 +55,14 @@ impl<S: StorageBackend + 'static> WebhookEngine<S> {
         self
     }
 
-    /// Trigger webhooks for a given event
+    /// Trigger webhooks for a given event. `subject` is the item event the
+    /// operation follows, which webhook filters are evaluated against.
     pub async fn trigger_webhooks(
         &mut self,
         circuit_id: &Uuid,
         trigger_event: PostActionTrigger,
         payload: WebhookPayload,
+        subject: Option<&Event>,
     ) -> Result<Vec<Uuid>, WebhookError> {
         self.logger
             .info(
@@ -88,11 +93,25 @@ impl<S: StorageBackend + 'static> WebhookEngine<S> {
             return Ok(vec![]);
         }
 
-        let webhooks: Vec<_> = post_settings
+        let (webhooks, filtered_out): (Vec<_>, Vec<_>) = post_settings
             .webhooks
             .into_iter()
             .filter(|w| w.enabled)
-            .collect();
+            .partition(|w| w.filter.matches(trigger_event, &payload.item.dfid, subject));
+
+        if !filtered_out.is_empty() {
+            self.logger
+                .info(
+                    "webhook_engine",
+                    "webhook_filtered",
+                    format!(
+                        "{} webhooks filtered out for {}",
+                        filtered_out.len(),
+                        payload.item.dfid
+                    ),
+                )
+                .with_context("count", filtered_out.len().to_string());
+        }
 
         if webhooks.is_empty() {
             return Ok(vec![]);
@@ -128,6 +147,8 @@ impl<S: StorageBackend + 'static> WebhookEngine<S> {
         trigger_event: PostActionTrigger,
         payload: WebhookPayload,
     ) -> Result<Uuid, WebhookError> {
+        let priority = payload.priority;
+
         // Serialize payload
         let payload_value = serde_json::to_value(&payload).map_err(|e| {
             WebhookError::DeliveryError(format!("Failed to serialize payload: {e}"))
@@ -148,6 +169,7 @@ impl<S: StorageBackend + 'static> WebhookEngine<S> {
                 webhook: webhook.clone(),
                 payload: payload_value,
                 delivery_id,
+                priority,
             };
 
             if let Err(e) = queue.enqueue(task).await {
@@ -198,6 +220,79 @@ impl<S: StorageBackend + 'static> WebhookEngine<S> {
             .map_err(|e| WebhookError::StorageError(e.to_string()))
     }
 
+    /// Re-send a delivery of the circuit to `target_url` with its original
+    /// payload, so integrators can debug a consumer against it. The webhook
+    /// must still be configured, as its method and encryption key are reused.
+    pub async fn replay_delivery(
+        &self,
+        circuit_id: &Uuid,
+        delivery_id: &Uuid,
+        target_url: &str,
+    ) -> Result<WebhookReplayResult, WebhookError> {
+        Self::validate_webhook_url(target_url)?;
+        let delivery = self
+            .get_delivery(delivery_id)?
+            .filter(|delivery| delivery.circuit_id == *circuit_id)
+            .ok_or_else(|| {
+                WebhookError::ConfigurationError(format!("Delivery {delivery_id} not found"))
+            })?;
+        let webhook = self
+            .storage
+            .get_circuit(circuit_id)
+            .map_err(|e| WebhookError::StorageError(e.to_string()))?
+            .and_then(|circuit| circuit.post_action_settings)
+            .and_then(|settings| {
+                settings
+                    .webhooks
+                    .into_iter()
+                    .find(|webhook| webhook.id == delivery.webhook_id)
+            })
+            .ok_or_else(|| {
+                WebhookError::ConfigurationError(format!(
+                    "Webhook {} of the delivery no longer exists",
+                    delivery.webhook_id
+                ))
+            })?;
+
+        let http_client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .map_err(|e| WebhookError::NetworkError(e.to_string()))?;
+        crate::webhook_delivery_worker::replay_delivery(
+            &http_client,
+            &webhook,
+            &delivery,
+            target_url,
+        )
+        .await
+        .map_err(WebhookError::DeliveryError)
+    }
+
+    /// Reject filters that could never be meant: blank prefixes or keys, and
+    /// unbounded predicate lists
+    pub fn validate_filter(filter: &WebhookFilter) -> Result<(), WebhookError> {
+        if filter
+            .dfid_prefix
+            .as_ref()
+            .is_some_and(|prefix| prefix.trim().is_empty())
+        {
+            return Err(WebhookError::ValidationError(
+                "DFID prefix must not be blank".to_string(),
+            ));
+        }
+        if filter.metadata.len() > MAX_FILTER_PREDICATES {
+            return Err(WebhookError::ValidationError(format!(
+                "At most {MAX_FILTER_PREDICATES} metadata predicates are allowed"
+            )));
+        }
+        if filter.metadata.iter().any(|p| p.key.trim().is_empty()) {
+            return Err(WebhookError::ValidationError(
+                "Metadata predicate keys must not be blank".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Validate webhook URL (basic validation to prevent SSRF)
     pub fn validate_webhook_url(url: &str) -> Result<(), WebhookError> {
         let parsed = url::Url::parse(url)
@@ -232,3 +327,127 @@ impl<S: StorageBackend + 'static> WebhookEngine<S> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EventType, EventVisibility, MetadataCondition, MetadataPredicate};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_filter_matches_event_type_prefix_and_metadata() {
+        let filter = WebhookFilter {
+            event_types: vec![EventType::Custom("Harvested".to_string())],
+            visibility: vec![EventVisibility::Public],
+            dfid_prefix: Some("DFID-2026".to_string()),
+            metadata: vec![MetadataPredicate {
+                key: "weight_kg".to_string(),
+                condition: MetadataCondition::GreaterThan(100.0),
+            }],
+            ..Default::default()
+        };
+        let event = Event::new_with_metadata(
+            "DFID-2026-0001".to_string(),
+            EventType::Custom("Harvested".to_string()),
+            "farmer".to_string(),
+            EventVisibility::Public,
+            HashMap::from([("weight_kg".to_string(), 250.into())]),
+        );
+
+        let trigger = PostActionTrigger::ItemPushed;
+        assert!(filter.matches(trigger, "DFID-2026-0001", Some(&event)));
+        assert!(!filter.matches(trigger, "DFID-2025-0001", Some(&event)));
+        // Event criteria never match when the operation follows no event
+        assert!(!filter.matches(trigger, "DFID-2026-0001", None));
+        assert!(WebhookFilter::default().matches(trigger, "DFID-2025-0001", None));
+
+        let light = Event::new_with_metadata(
+            "DFID-2026-0001".to_string(),
+            EventType::Custom("Harvested".to_string()),
+            "farmer".to_string(),
+            EventVisibility::Public,
+            HashMap::from([("weight_kg".to_string(), 40.into())]),
+        );
+        assert!(!filter.matches(trigger, "DFID-2026-0001", Some(&light)));
+
+        let parsed: MetadataPredicate = serde_json::from_value(
+            serde_json::json!({"key": "grade", "op": "one_of", "value": ["A", "B"]}),
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.condition,
+            MetadataCondition::OneOf(vec!["A".into(), "B".into()])
+        );
+        assert!(
+            WebhookEngine::<crate::storage::InMemoryStorage>::validate_filter(&WebhookFilter {
+                dfid_prefix: Some(" ".to_string()),
+                ..Default::default()
+            })
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replay_resends_original_payload_without_credentials() {
+        use crate::webhook_delivery_worker::{
+            replay_delivery, sign_delivery, DELIVERY_ID_HEADER, REPLAY_HEADER,
+        };
+        use std::sync::{Arc, Mutex};
+
+        type Received = Arc<Mutex<Option<(axum::http::HeaderMap, Vec<u8>)>>>;
+        let received: Received = Arc::default();
+        let app = axum::Router::new().route(
+            "/hook",
+            axum::routing::post({
+                let received = Arc::clone(&received);
+                move |headers: axum::http::HeaderMap, body: axum::body::Bytes| async move {
+                    *received.lock().unwrap() = Some((headers, body.to_vec()));
+                    "ok"
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let mut webhook = WebhookConfig::new(
+            "erp".to_string(),
+            "https://erp.example.com/hook".to_string(),
+        );
+        webhook.auth_type = crate::types::WebhookAuthType::BearerToken;
+        webhook.auth_credentials = Some("production-token".to_string());
+        let payload = serde_json::json!({"item": {"dfid": "DFID-2026-0001"}, "n": 1.5});
+        let delivery = WebhookDelivery::new(
+            webhook.id,
+            Uuid::new_v4(),
+            PostActionTrigger::ItemPushed,
+            payload.clone(),
+        );
+
+        let result = replay_delivery(
+            &reqwest::Client::new(),
+            &webhook,
+            &delivery,
+            &format!("http://{address}/hook"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.response_code, Some(200));
+        assert_eq!(result.response_body.as_deref(), Some("ok"));
+
+        let (headers, body) = received.lock().unwrap().take().unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+            payload
+        );
+        assert_eq!(headers[REPLAY_HEADER], "true");
+        assert_eq!(headers[DELIVERY_ID_HEADER], delivery.id.to_string());
+        assert!(headers.get("authorization").is_none());
+
+        // Signatures cover the timestamp, so each replay is signed anew
+        assert_ne!(
+            sign_delivery("secret", 1, &body),
+            sign_delivery("secret", 2, &body)
+        );
+    }
+}
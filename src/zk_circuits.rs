@@ -0,0 +1,702 @@
+//! Groth16 circuits (BN254) behind the agricultural circuit templates.
+//!
+//! Private values are bound to a public MiMC commitment together with the
+//! public context (certification body, regulatory or grading standard) and a
+//! salt, so a proof states a property of the committed data rather than of
+//! any data:
+//!
+//! - Organic certification: the committed certificate of the public body is
+//!   valid on the public day.
+//! - Pesticide threshold: every committed reading is at most the public limit.
+//! - Quality grade: every committed metric is at least the public minimum.
+//! - Cold-chain compliance: every committed temperature reading lies within
+//!   the public range.
+//! - Carbon footprint: the committed emissions add up to at most the public
+//!   limit.
+//!
+//! Readings and scores are fixed point with [`FIXED_POINT_SCALE`] and must fit
+//! in [`VALUE_BITS`] bits; temperatures enter in kelvin so that readings below
+//! freezing stay non-negative.
+//!
+//! Custom circuits registered by workspaces reuse the threshold and range
+//! circuits through their [`ConstraintTemplate`].
+
+use crate::zk_proof_engine::{CircuitType, ConstraintTemplate, ZkProofError};
+use ark_bn254::{Bn254, Fr, G1Projective};
+use ark_ec::pairing::Pairing;
+use ark_ec::CurveGroup;
+use ark_ff::{Field, PrimeField, Zero};
+use ark_groth16::{prepare_verifying_key, Groth16, Proof, ProvingKey, VerifyingKey};
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::prelude::{AllocVar, Boolean, EqGadget, FieldVar};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_snark::SNARK;
+use ark_std::UniformRand;
+use rand::rngs::OsRng;
+use std::sync::OnceLock;
+
+/// Bumped whenever a circuit's constraints change; setup artifacts of an
+/// older version cannot prove or verify against the current circuits
+pub const CIRCUIT_VERSION: &str = "groth16-bn254-mimc-v1";
+pub const FIXED_POINT_SCALE: f64 = 1_000_000.0;
+pub const VALUE_BITS: usize = 48;
+/// Readings, metrics or emission sources per proof
+pub const MAX_COMMITTED_VALUES: usize = 8;
+const ABSOLUTE_ZERO_CELSIUS: f64 = -273.15;
+/// Certificate validity is compared in days since the Unix epoch
+const DAY_BITS: usize = 32;
+/// ⌈log₅(p)⌉ rounds of x ↦ (x + k + cᵢ)⁵ over the BN254 scalar field
+const MIMC_ROUNDS: usize = 110;
+
+fn mimc_constants() -> &'static [Fr] {
+    static CONSTANTS: OnceLock<Vec<Fr>> = OnceLock::new();
+    CONSTANTS.get_or_init(|| {
+        (0..MIMC_ROUNDS)
+            .map(|round| {
+                let seed = format!("defarm.zk.mimc.{round}");
+                Fr::from_le_bytes_mod_order(blake3::hash(seed.as_bytes()).as_bytes())
+            })
+            .collect()
+    })
+}
+
+fn mimc(mut x: Fr, key: Fr) -> Fr {
+    for constant in mimc_constants() {
+        let t = x + key + constant;
+        x = t.square().square() * t;
+    }
+    x + key
+}
+
+/// Miyaguchi–Preneel chaining of MiMC over `inputs`
+pub fn mimc_commit(inputs: &[Fr]) -> Fr {
+    inputs.iter().fold(Fr::zero(), |state, input| {
+        mimc(*input, state) + state + input
+    })
+}
+
+fn mimc_gadget(mut x: FpVar<Fr>, key: &FpVar<Fr>) -> Result<FpVar<Fr>, SynthesisError> {
+    for constant in mimc_constants() {
+        let t = &x + key + *constant;
+        x = t.square()?.square()? * &t;
+    }
+    Ok(x + key)
+}
+
+fn mimc_commit_gadget(inputs: &[FpVar<Fr>]) -> Result<FpVar<Fr>, SynthesisError> {
+    let mut state = FpVar::zero();
+    for input in inputs {
+        state = mimc_gadget(input.clone(), &state)? + &state + input;
+    }
+    Ok(state)
+}
+
+/// Constrain `value` to `bits` bits, proving it non-negative and small
+/// enough that differences of such values cannot wrap around the field
+fn enforce_range(
+    cs: &ConstraintSystemRef<Fr>,
+    value: &FpVar<Fr>,
+    native: Option<u64>,
+    bits: usize,
+) -> Result<(), SynthesisError> {
+    let mut sum = FpVar::zero();
+    let mut coefficient = Fr::from(1u64);
+    for i in 0..bits {
+        let bit = Boolean::new_witness(cs.clone(), || {
+            native
+                .map(|value| (value >> i) & 1 == 1)
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        sum += FpVar::from(bit) * coefficient;
+        coefficient.double_in_place();
+    }
+    sum.enforce_equal(value)
+}
+
+fn witness(cs: &ConstraintSystemRef<Fr>, value: Option<Fr>) -> Result<FpVar<Fr>, SynthesisError> {
+    FpVar::new_witness(cs.clone(), || {
+        value.ok_or(SynthesisError::AssignmentMissing)
+    })
+}
+
+/// Public strings (certification body, standards) enter the circuits hashed
+pub fn field_from_str(value: &str) -> Fr {
+    Fr::from_le_bytes_mod_order(blake3::hash(value.as_bytes()).as_bytes())
+}
+
+pub fn field_to_hex(value: &Fr) -> String {
+    let mut bytes = Vec::new();
+    value
+        .serialize_compressed(&mut bytes)
+        .expect("field elements serialize into a Vec");
+    hex::encode(bytes)
+}
+
+pub fn field_from_hex(value: &str) -> Option<Fr> {
+    let bytes = hex::decode(value).ok()?;
+    Fr::deserialize_compressed(bytes.as_slice()).ok()
+}
+
+pub fn random_field() -> Fr {
+    Fr::rand(&mut OsRng)
+}
+
+/// `value` in fixed point, when it is a finite, non-negative number that fits
+/// in [`VALUE_BITS`] bits
+pub fn to_fixed_point(value: f64) -> Option<u64> {
+    let scaled = (value * FIXED_POINT_SCALE).round();
+    (value.is_finite() && scaled >= 0.0 && scaled < (1u64 << VALUE_BITS) as f64)
+        .then_some(scaled as u64)
+}
+
+/// A temperature in degrees Celsius as fixed-point kelvin
+pub fn celsius_to_fixed_point(celsius: f64) -> Option<u64> {
+    to_fixed_point(celsius - ABSOLUTE_ZERO_CELSIUS)
+}
+
+// ============================================================================
+// CIRCUITS
+// ============================================================================
+
+/// The certificate issued by `body` stays valid until `valid_until_day`,
+/// which is not before `as_of_day`
+#[derive(Clone)]
+pub struct CertificationCircuit {
+    pub certificate: Option<Fr>,
+    pub valid_until_day: Option<u64>,
+    pub salt: Option<Fr>,
+    pub body: Fr,
+    pub as_of_day: u64,
+    pub commitment: Fr,
+}
+
+impl CertificationCircuit {
+    /// Builds the circuit and its commitment from the private values
+    pub fn new(certificate: Fr, valid_until_day: u64, salt: Fr, body: Fr, as_of_day: u64) -> Self {
+        let commitment = mimc_commit(&[certificate, body, Fr::from(valid_until_day), salt]);
+        Self {
+            certificate: Some(certificate),
+            valid_until_day: Some(valid_until_day),
+            salt: Some(salt),
+            body,
+            as_of_day,
+            commitment,
+        }
+    }
+
+    pub fn public_inputs(body: Fr, as_of_day: u64, commitment: Fr) -> Vec<Fr> {
+        vec![body, Fr::from(as_of_day), commitment]
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for CertificationCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let body = FpVar::new_input(cs.clone(), || Ok(self.body))?;
+        let as_of_day = FpVar::new_input(cs.clone(), || Ok(Fr::from(self.as_of_day)))?;
+        let commitment = FpVar::new_input(cs.clone(), || Ok(self.commitment))?;
+
+        let certificate = witness(&cs, self.certificate)?;
+        let valid_until_day = witness(&cs, self.valid_until_day.map(Fr::from))?;
+        let salt = witness(&cs, self.salt)?;
+
+        mimc_commit_gadget(&[certificate, body, valid_until_day.clone(), salt])?
+            .enforce_equal(&commitment)?;
+        enforce_range(&cs, &valid_until_day, self.valid_until_day, DAY_BITS)?;
+        let remaining_days = self
+            .valid_until_day
+            .and_then(|day| day.checked_sub(self.as_of_day));
+        enforce_range(
+            &cs,
+            &(valid_until_day - as_of_day),
+            remaining_days,
+            DAY_BITS,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundKind {
+    AtMost,
+    AtLeast,
+    /// The committed values add up to at most the bound
+    TotalAtMost,
+}
+
+/// Every committed value, or their total, lies on the `kind` side of `bound`
+#[derive(Clone)]
+pub struct ThresholdCircuit {
+    pub kind: BoundKind,
+    pub values: Vec<Option<u64>>,
+    pub salt: Option<Fr>,
+    pub standard: Fr,
+    pub bound: u64,
+    pub commitment: Fr,
+}
+
+impl ThresholdCircuit {
+    /// Builds the circuit and its commitment from the private values. Unused
+    /// slots are filled with the bound itself, which satisfies a per-value
+    /// bound, or with zero, which leaves a total unchanged.
+    pub fn new(kind: BoundKind, values: &[u64], salt: Fr, standard: Fr, bound: u64) -> Self {
+        let padding = match kind {
+            BoundKind::AtMost | BoundKind::AtLeast => bound,
+            BoundKind::TotalAtMost => 0,
+        };
+        let values: Vec<Option<u64>> = values
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(padding))
+            .take(MAX_COMMITTED_VALUES)
+            .map(Some)
+            .collect();
+        let commitment = Self::commit(&values, standard, salt);
+        Self {
+            kind,
+            values,
+            salt: Some(salt),
+            standard,
+            bound,
+            commitment,
+        }
+    }
+
+    fn commit(values: &[Option<u64>], standard: Fr, salt: Fr) -> Fr {
+        let mut inputs: Vec<Fr> = values
+            .iter()
+            .map(|value| Fr::from(value.unwrap_or_default()))
+            .collect();
+        inputs.push(standard);
+        inputs.push(salt);
+        mimc_commit(&inputs)
+    }
+
+    pub fn public_inputs(standard: Fr, bound: u64, commitment: Fr) -> Vec<Fr> {
+        vec![standard, Fr::from(bound), commitment]
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for ThresholdCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let standard = FpVar::new_input(cs.clone(), || Ok(self.standard))?;
+        let bound = FpVar::new_input(cs.clone(), || Ok(Fr::from(self.bound)))?;
+        let commitment = FpVar::new_input(cs.clone(), || Ok(self.commitment))?;
+
+        let mut committed = Vec::with_capacity(MAX_COMMITTED_VALUES + 2);
+        for native in &self.values {
+            let value = witness(&cs, native.map(Fr::from))?;
+            enforce_range(&cs, &value, *native, VALUE_BITS)?;
+            let per_value_margin = match self.kind {
+                BoundKind::AtMost => Some((
+                    &bound - &value,
+                    native.and_then(|value| self.bound.checked_sub(value)),
+                )),
+                BoundKind::AtLeast => Some((
+                    &value - &bound,
+                    native.and_then(|value| value.checked_sub(self.bound)),
+                )),
+                BoundKind::TotalAtMost => None,
+            };
+            if let Some((margin, native_margin)) = per_value_margin {
+                enforce_range(&cs, &margin, native_margin, VALUE_BITS)?;
+            }
+            committed.push(value);
+        }
+        if self.kind == BoundKind::TotalAtMost {
+            // At most MAX_COMMITTED_VALUES values of VALUE_BITS bits each, so
+            // the total cannot wrap around the field either
+            let total = committed
+                .iter()
+                .fold(FpVar::zero(), |total, value| total + value);
+            let native_total = self
+                .values
+                .iter()
+                .copied()
+                .sum::<Option<u64>>()
+                .and_then(|total| self.bound.checked_sub(total));
+            enforce_range(&cs, &(&bound - &total), native_total, VALUE_BITS)?;
+        }
+        committed.push(standard);
+        committed.push(witness(&cs, self.salt)?);
+        mimc_commit_gadget(&committed)?.enforce_equal(&commitment)
+    }
+}
+
+/// Every committed value lies between `min` and `max`, both included
+#[derive(Clone)]
+pub struct RangeCircuit {
+    pub values: Vec<Option<u64>>,
+    pub salt: Option<Fr>,
+    pub standard: Fr,
+    pub min: u64,
+    pub max: u64,
+    pub commitment: Fr,
+}
+
+impl RangeCircuit {
+    /// Builds the circuit and its commitment from the private values. Unused
+    /// slots are filled with `min`.
+    pub fn new(values: &[u64], salt: Fr, standard: Fr, min: u64, max: u64) -> Self {
+        let values: Vec<Option<u64>> = values
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(min))
+            .take(MAX_COMMITTED_VALUES)
+            .map(Some)
+            .collect();
+        let commitment = ThresholdCircuit::commit(&values, standard, salt);
+        Self {
+            values,
+            salt: Some(salt),
+            standard,
+            min,
+            max,
+            commitment,
+        }
+    }
+
+    pub fn public_inputs(standard: Fr, min: u64, max: u64, commitment: Fr) -> Vec<Fr> {
+        vec![standard, Fr::from(min), Fr::from(max), commitment]
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for RangeCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let standard = FpVar::new_input(cs.clone(), || Ok(self.standard))?;
+        let min = FpVar::new_input(cs.clone(), || Ok(Fr::from(self.min)))?;
+        let max = FpVar::new_input(cs.clone(), || Ok(Fr::from(self.max)))?;
+        let commitment = FpVar::new_input(cs.clone(), || Ok(self.commitment))?;
+
+        let mut committed = Vec::with_capacity(MAX_COMMITTED_VALUES + 2);
+        for native in &self.values {
+            let value = witness(&cs, native.map(Fr::from))?;
+            enforce_range(&cs, &value, *native, VALUE_BITS)?;
+            enforce_range(
+                &cs,
+                &(&value - &min),
+                native.and_then(|value| value.checked_sub(self.min)),
+                VALUE_BITS,
+            )?;
+            enforce_range(
+                &cs,
+                &(&max - &value),
+                native.and_then(|value| self.max.checked_sub(value)),
+                VALUE_BITS,
+            )?;
+            committed.push(value);
+        }
+        committed.push(standard);
+        committed.push(witness(&cs, self.salt)?);
+        mimc_commit_gadget(&committed)?.enforce_equal(&commitment)
+    }
+}
+
+/// The circuit of a template, either blank (for the setup) or with its witness
+#[derive(Clone)]
+pub enum TemplateCircuit {
+    Certification(CertificationCircuit),
+    Threshold(ThresholdCircuit),
+    Range(RangeCircuit),
+}
+
+impl TemplateCircuit {
+    /// The shape of the circuit of a constraint template
+    pub fn blank_for(template: ConstraintTemplate) -> Self {
+        let threshold = |kind| {
+            TemplateCircuit::Threshold(ThresholdCircuit {
+                kind,
+                values: vec![None; MAX_COMMITTED_VALUES],
+                salt: None,
+                standard: Fr::zero(),
+                bound: 0,
+                commitment: Fr::zero(),
+            })
+        };
+        match template {
+            ConstraintTemplate::AtMost => threshold(BoundKind::AtMost),
+            ConstraintTemplate::AtLeast => threshold(BoundKind::AtLeast),
+            ConstraintTemplate::TotalAtMost => threshold(BoundKind::TotalAtMost),
+            ConstraintTemplate::Range => TemplateCircuit::Range(RangeCircuit {
+                values: vec![None; MAX_COMMITTED_VALUES],
+                salt: None,
+                standard: Fr::zero(),
+                min: 0,
+                max: 0,
+                commitment: Fr::zero(),
+            }),
+        }
+    }
+
+    /// The shape of the circuit for a built-in `circuit_type`, when it is
+    /// provable. Custom circuits take the shape of their definition's template.
+    pub fn blank(circuit_type: &CircuitType) -> Option<Self> {
+        match circuit_type {
+            CircuitType::OrganicCertification => {
+                Some(TemplateCircuit::Certification(CertificationCircuit {
+                    certificate: None,
+                    valid_until_day: None,
+                    salt: None,
+                    body: Fr::zero(),
+                    as_of_day: 0,
+                    commitment: Fr::zero(),
+                }))
+            }
+            CircuitType::PesticideThreshold => Some(Self::blank_for(ConstraintTemplate::AtMost)),
+            CircuitType::QualityGrade => Some(Self::blank_for(ConstraintTemplate::AtLeast)),
+            CircuitType::ColdChainCompliance => Some(Self::blank_for(ConstraintTemplate::Range)),
+            CircuitType::CarbonFootprintThreshold => {
+                Some(Self::blank_for(ConstraintTemplate::TotalAtMost))
+            }
+            _ => None,
+        }
+    }
+
+    /// A satisfiable instance of this blank circuit, with its public inputs,
+    /// to check setup keys against
+    fn sample(self) -> (Self, Vec<Fr>) {
+        let salt = random_field();
+        match self {
+            TemplateCircuit::Certification(_) => {
+                let circuit = CertificationCircuit::new(Fr::from(1u64), 1, salt, Fr::zero(), 0);
+                let public_inputs =
+                    CertificationCircuit::public_inputs(Fr::zero(), 0, circuit.commitment);
+                (TemplateCircuit::Certification(circuit), public_inputs)
+            }
+            TemplateCircuit::Threshold(blank) => {
+                // Strictly inside the bound: at the bound both kinds accept
+                // the same proofs
+                let (value, bound) = match blank.kind {
+                    BoundKind::AtMost | BoundKind::TotalAtMost => (1, 2),
+                    BoundKind::AtLeast => (2, 1),
+                };
+                let circuit = ThresholdCircuit::new(blank.kind, &[value], salt, Fr::zero(), bound);
+                let public_inputs =
+                    ThresholdCircuit::public_inputs(Fr::zero(), bound, circuit.commitment);
+                (TemplateCircuit::Threshold(circuit), public_inputs)
+            }
+            TemplateCircuit::Range(_) => {
+                let circuit = RangeCircuit::new(&[2], salt, Fr::zero(), 1, 3);
+                let public_inputs =
+                    RangeCircuit::public_inputs(Fr::zero(), 1, 3, circuit.commitment);
+                (TemplateCircuit::Range(circuit), public_inputs)
+            }
+        }
+    }
+
+    pub fn commitment(&self) -> Fr {
+        match self {
+            TemplateCircuit::Certification(circuit) => circuit.commitment,
+            TemplateCircuit::Threshold(circuit) => circuit.commitment,
+            TemplateCircuit::Range(circuit) => circuit.commitment,
+        }
+    }
+
+    /// Context, bound(s) or day, and commitment
+    pub fn public_input_count(&self) -> usize {
+        match self {
+            TemplateCircuit::Certification(_) | TemplateCircuit::Threshold(_) => 3,
+            TemplateCircuit::Range(_) => 4,
+        }
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for TemplateCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        match self {
+            TemplateCircuit::Certification(circuit) => circuit.generate_constraints(cs),
+            TemplateCircuit::Threshold(circuit) => circuit.generate_constraints(cs),
+            TemplateCircuit::Range(circuit) => circuit.generate_constraints(cs),
+        }
+    }
+}
+
+// ============================================================================
+// GROTH16
+// ============================================================================
+
+/// Compressed proving and verifying keys of a circuit-specific setup
+pub struct SetupKeys {
+    pub proving_key: Vec<u8>,
+    pub verifying_key: Vec<u8>,
+}
+
+fn serialize<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(value.compressed_size());
+    value
+        .serialize_compressed(&mut bytes)
+        .expect("keys and proofs serialize into a Vec");
+    bytes
+}
+
+/// Runs a single-party setup for `circuit`. Its toxic waste only lives in
+/// this call; deployments that cannot trust the server import the keys of a
+/// multi-party ceremony instead.
+pub fn setup(circuit: TemplateCircuit) -> Result<SetupKeys, ZkProofError> {
+    let (proving_key, verifying_key) =
+        Groth16::<Bn254>::circuit_specific_setup(circuit, &mut OsRng)
+            .map_err(|e| ZkProofError::ProofGenerationError(format!("Setup failed: {e}")))?;
+    Ok(SetupKeys {
+        proving_key: serialize(&proving_key),
+        verifying_key: serialize(&verifying_key),
+    })
+}
+
+/// Checks imported keys: both must decode, belong together, and prove and
+/// verify a sample statement of `blank`, the circuit for `circuit_type`
+pub fn validate_setup_keys(
+    circuit_type: &CircuitType,
+    blank: TemplateCircuit,
+    keys: &SetupKeys,
+) -> Result<(), ZkProofError> {
+    let (sample, public_inputs) = blank.sample();
+    let invalid = |e: ark_serialize::SerializationError| {
+        ZkProofError::InvalidInput(format!("Malformed setup key: {e}"))
+    };
+    // Subgroup checks on every proving key point would take longer than the
+    // sample proof below, which a bad proving key fails anyway
+    let proving_key =
+        ProvingKey::<Bn254>::deserialize_compressed_unchecked(keys.proving_key.as_slice())
+            .map_err(invalid)?;
+    let verifying_key =
+        VerifyingKey::<Bn254>::deserialize_compressed(keys.verifying_key.as_slice())
+            .map_err(invalid)?;
+    if proving_key.vk != verifying_key {
+        return Err(ZkProofError::InvalidInput(
+            "Proving key does not match the verifying key".to_string(),
+        ));
+    }
+    let input_count = sample.public_input_count();
+    if verifying_key.gamma_abc_g1.len() != input_count + 1 {
+        return Err(ZkProofError::InvalidInput(format!(
+            "Verifying key expects {} public inputs, the circuit has {input_count}",
+            verifying_key.gamma_abc_g1.len().saturating_sub(1)
+        )));
+    }
+    let sample_verifies = prove(&keys.proving_key, sample)
+        .and_then(|proof| verify(&keys.verifying_key, &public_inputs, &proof))
+        .unwrap_or(false);
+    if !sample_verifies {
+        return Err(ZkProofError::InvalidInput(format!(
+            "Setup keys do not fit the {circuit_type:?} circuit"
+        )));
+    }
+    Ok(())
+}
+
+pub fn prove(proving_key: &[u8], circuit: TemplateCircuit) -> Result<Vec<u8>, ZkProofError> {
+    // Stored keys were validated when generated or imported
+    let proving_key = ProvingKey::<Bn254>::deserialize_compressed_unchecked(proving_key)
+        .map_err(|e| ZkProofError::ProofGenerationError(format!("Corrupt proving key: {e}")))?;
+    let proof = Groth16::<Bn254>::prove(&proving_key, circuit, &mut OsRng)
+        .map_err(|e| ZkProofError::ProofGenerationError(e.to_string()))?;
+    Ok(serialize(&proof))
+}
+
+/// `Ok(false)` for proofs that do not verify, including malformed ones
+pub fn verify(
+    verifying_key: &[u8],
+    public_inputs: &[Fr],
+    proof: &[u8],
+) -> Result<bool, ZkProofError> {
+    let verifying_key = VerifyingKey::<Bn254>::deserialize_compressed(verifying_key)
+        .map_err(|e| ZkProofError::VerificationError(format!("Corrupt verifying key: {e}")))?;
+    let Ok(proof) = Proof::<Bn254>::deserialize_compressed(proof) else {
+        return Ok(false);
+    };
+    Groth16::<Bn254>::verify(&verifying_key, public_inputs, &proof)
+        .map_err(|e| ZkProofError::VerificationError(e.to_string()))
+}
+
+/// Outcome of [`verify_batch`], in the order of the batch
+pub struct BatchVerification {
+    pub results: Vec<bool>,
+    /// Whether the combined check settled the batch, without falling back
+    /// to verifying proofs one by one
+    pub combined: bool,
+}
+
+/// Verifies proofs made with one verifying key together. For random rᵢ,
+/// Σ rᵢ·e(Aᵢ, Bᵢ) = e(Σ rᵢ·α, β) + e(Σ rᵢ·ICᵢ, γ) + e(Σ rᵢ·Cᵢ, δ) holds for
+/// valid proofs and, except with negligible probability, for no batch with
+/// an invalid one; it costs one multi-pairing instead of one per proof.
+/// When it fails, proofs are verified one by one to find the bad ones.
+pub fn verify_batch(
+    verifying_key: &[u8],
+    batch: &[(Vec<Fr>, &[u8])],
+) -> Result<BatchVerification, ZkProofError> {
+    let verifying_key = VerifyingKey::<Bn254>::deserialize_compressed(verifying_key)
+        .map_err(|e| ZkProofError::VerificationError(format!("Corrupt verifying key: {e}")))?;
+    let prepared_key = prepare_verifying_key(&verifying_key);
+
+    let mut results = vec![false; batch.len()];
+    let mut candidates = Vec::with_capacity(batch.len());
+    for (index, (public_inputs, proof)) in batch.iter().enumerate() {
+        let Ok(proof) = Proof::<Bn254>::deserialize_compressed(*proof) else {
+            continue;
+        };
+        let Ok(prepared_inputs) = Groth16::<Bn254>::prepare_inputs(&prepared_key, public_inputs)
+        else {
+            continue;
+        };
+        candidates.push((index, proof, prepared_inputs));
+    }
+    if candidates.is_empty() {
+        return Ok(BatchVerification {
+            results,
+            combined: false,
+        });
+    }
+
+    if combined_check(&verifying_key, &candidates) {
+        for (index, _, _) in &candidates {
+            results[*index] = true;
+        }
+        return Ok(BatchVerification {
+            results,
+            combined: true,
+        });
+    }
+    for (index, proof, prepared_inputs) in &candidates {
+        results[*index] = Groth16::<Bn254>::verify_proof_with_prepared_inputs(
+            &prepared_key,
+            proof,
+            prepared_inputs,
+        )
+        .map_err(|e| ZkProofError::VerificationError(e.to_string()))?;
+    }
+    Ok(BatchVerification {
+        results,
+        combined: false,
+    })
+}
+
+fn combined_check(
+    verifying_key: &VerifyingKey<Bn254>,
+    candidates: &[(usize, Proof<Bn254>, G1Projective)],
+) -> bool {
+    let mut g1 = Vec::with_capacity(candidates.len() + 3);
+    let mut g2 = Vec::with_capacity(candidates.len() + 3);
+    let mut weight_sum = Fr::zero();
+    let mut inputs_sum = G1Projective::zero();
+    let mut c_sum = G1Projective::zero();
+    for (_, proof, prepared_inputs) in candidates {
+        let weight = Fr::rand(&mut OsRng);
+        g1.push((proof.a * weight).into_affine());
+        g2.push(proof.b);
+        weight_sum += weight;
+        inputs_sum += *prepared_inputs * weight;
+        c_sum += proof.c * weight;
+    }
+    g1.push((verifying_key.alpha_g1 * -weight_sum).into_affine());
+    g2.push(verifying_key.beta_g2);
+    g1.push((-inputs_sum).into_affine());
+    g2.push(verifying_key.gamma_g2);
+    g1.push((-c_sum).into_affine());
+    g2.push(verifying_key.delta_g2);
+    Bn254::multi_pairing(g1, g2).is_zero()
+}
+
+/// Content address of a verifying key, used as the setup artifact id
+pub fn verifying_key_id(verifying_key: &[u8]) -> String {
+    blake3::hash(verifying_key).to_hex().to_string()
+}
@@ -1,10 +1,33 @@
+use crate::anchoring_cost_engine::record_anchoring;
+use crate::hashing::HashAlgorithm;
+use crate::item_access::can_read_item;
+use crate::key_ceremony_engine::check_stellar_signer;
+use crate::merkle_tree::{MerkleProof, MerkleTree};
+use crate::stellar_client::{
+    StellarClient, StellarNetwork, MAINNET_IPCM_CONTRACT, TESTNET_IPCM_CONTRACT,
+};
 use crate::storage::{StorageBackend, StorageError};
-use crate::types::Item;
-use chrono::{DateTime, Utc};
+use crate::types::{AnchoringNetwork, Item, UserTier};
+use crate::zk_circuits::{
+    self, BoundKind, CertificationCircuit, RangeCircuit, SetupKeys, TemplateCircuit,
+    ThresholdCircuit, CIRCUIT_VERSION, MAX_COMMITTED_VALUES,
+};
+use ark_bn254::Fr;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+/// Verifier recorded on proofs the worker pool verifies after generating them
+pub const PROOF_WORKER_ID: &str = "proof_worker";
+
+pub const MAX_BATCH_SIZE: usize = 1000;
+
+/// Serializes setups, so concurrent first proofs of a circuit share one
+static SETUP_LOCK: Mutex<()> = Mutex::new(());
+
 // ============================================================================
 // ZK PROOF TYPES AND STRUCTURES
 // ============================================================================
@@ -12,9 +35,13 @@ use uuid::Uuid;
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ProofStatus {
     Pending,
+    /// Queued proof being generated by a worker; see [`ProofJob`]
+    Generating,
     Verified,
     Failed,
     Expired,
+    /// Withdrawn by the prover's workspace; see [`ProofRevocation`]
+    Revoked,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -24,6 +51,10 @@ pub enum CircuitType {
     QualityGrade,
     OwnershipProof,
     TimestampFreshness,
+    /// Temperatures stayed within a range during transport and storage
+    ColdChainCompliance,
+    /// Total greenhouse gas emissions stay under a limit
+    CarbonFootprintThreshold,
     Custom(String),
 }
 
@@ -32,6 +63,10 @@ pub struct ZkProof {
     pub proof_id: Uuid,
     pub circuit_type: CircuitType,
     pub item_id: Option<Uuid>,
+    /// Item the proof is about: its `item_dfid` public input, kept only when
+    /// the prover could read that item on submission
+    #[serde(default)]
+    pub item_dfid: Option<String>,
     pub prover_id: String,
     pub proof_data: Vec<u8>,
     pub public_inputs: HashMap<String, serde_json::Value>,
@@ -40,7 +75,71 @@ pub struct ZkProof {
     pub created_at: DateTime<Utc>,
     pub verified_at: Option<DateTime<Utc>>,
     pub expires_at: Option<DateTime<Utc>>,
+    /// Start of the validity window; verification is refused before it
+    #[serde(default)]
+    pub valid_from: Option<DateTime<Utc>>,
     pub verification_result: Option<VerificationResult>,
+    /// Setup whose proving key produced `proof_data`; `None` for proofs made
+    /// before real proving, which no longer verify
+    #[serde(default)]
+    pub setup_artifact_id: Option<String>,
+    /// Set once the proof's digest is published on Stellar
+    #[serde(default)]
+    pub anchor: Option<ProofAnchor>,
+}
+
+impl ZkProof {
+    /// Status as of `now`: a proof past its expiry reads as expired even before
+    /// a verification attempt records it
+    pub fn status_at(&self, now: DateTime<Utc>) -> ProofStatus {
+        match self.status {
+            ProofStatus::Revoked | ProofStatus::Expired => self.status.clone(),
+            _ if self.expires_at.is_some_and(|expires_at| now > expires_at) => ProofStatus::Expired,
+            _ => self.status.clone(),
+        }
+    }
+}
+
+/// Validity window requested when submitting a proof. Without `expires_at`
+/// the circuit type's default lifetime applies.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProofValidity {
+    pub valid_from: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Proof generated in the background by the worker pool. The job moves from
+/// `Pending` (queued) to `Generating` to `Verified` or `Failed`; private
+/// inputs travel with the queued task only and are never stored.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProofJob {
+    pub job_id: Uuid,
+    pub circuit_type: CircuitType,
+    pub prover_id: String,
+    /// Item the proof is about, from the `item_dfid` public input; circuits
+    /// holding it are notified when the job completes
+    pub item_dfid: Option<String>,
+    pub status: ProofStatus,
+    /// The generated proof, once there is one
+    pub proof_id: Option<Uuid>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Entry of the revocation registry. The prover or a member of the prover's
+/// workspace revokes a proof, e.g. when the certificate behind it is
+/// withdrawn; a revocation is final.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProofRevocation {
+    pub proof_id: Uuid,
+    pub prover_id: String,
+    /// Workspace whose registry lists the revocation
+    pub workspace_id: Option<String>,
+    pub revoked_by: String,
+    pub reason: String,
+    pub revoked_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,6 +151,262 @@ pub struct VerificationResult {
     pub metadata: HashMap<String, serde_json::Value>,
 }
 
+/// Public record that a verified proof existed: its [`anchor_digest`] was
+/// emitted as an IPCM update event under `ipcm_key`, so anyone can look the
+/// transaction up on Stellar and recompute the digest from the proof.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProofAnchor {
+    pub network: AnchoringNetwork,
+    pub ipcm_key: String,
+    pub digest: String,
+    pub tx_hash: String,
+    pub anchored_at: DateTime<Utc>,
+}
+
+/// BLAKE3 hex of the proof id's 16 bytes followed by the compressed proof.
+/// Always BLAKE3, whatever the configured hashing default, so published
+/// digests stay reproducible.
+pub fn anchor_digest(proof: &ZkProof) -> String {
+    let mut hasher = HashAlgorithm::Blake3.hasher();
+    hasher
+        .update(proof.proof_id.as_bytes())
+        .update(&proof.proof_data);
+    hasher.finalize()
+}
+
+/// IPCM key a proof's digest is published under
+pub fn anchor_key(proof_id: &Uuid) -> String {
+    format!("zkproof:{proof_id}")
+}
+
+/// Salted BLAKE3 commitment to one attribute value. The field name is bound
+/// in, so a commitment cannot be passed off as another attribute, and the
+/// salt keeps values that are easy to guess hidden.
+pub fn commit_attribute(field: &str, value: &serde_json::Value, salt: &str) -> String {
+    let mut hasher = HashAlgorithm::Blake3.hasher();
+    hasher
+        .update(field.as_bytes())
+        .update(&[0])
+        .update(value.to_string().as_bytes())
+        .update(&[0])
+        .update(salt.as_bytes());
+    hasher.finalize()
+}
+
+/// Commitments to every attribute of an item under one Merkle root. Only the
+/// root is published; an attribute is disclosed with its value, salt and
+/// inclusion proof, revealing nothing about the others.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttributeCommitments {
+    pub root: String,
+    /// Field -> commitment; the commitments are the tree's leaves
+    pub commitments: BTreeMap<String, String>,
+    pub salts: BTreeMap<String, String>,
+}
+
+impl AttributeCommitments {
+    /// Commit to `attributes` with fresh random salts
+    pub fn commit(attributes: &HashMap<String, serde_json::Value>) -> Self {
+        let salts = attributes
+            .keys()
+            .map(|field| (field.clone(), hex::encode(rand::random::<[u8; 16]>())))
+            .collect();
+        Self::with_salts(attributes, salts).expect("every attribute has a salt")
+    }
+
+    /// Commit again with known salts; `None` if an attribute has none
+    pub fn with_salts(
+        attributes: &HashMap<String, serde_json::Value>,
+        mut salts: BTreeMap<String, String>,
+    ) -> Option<Self> {
+        salts.retain(|field, _| attributes.contains_key(field));
+        let commitments: BTreeMap<String, String> = attributes
+            .iter()
+            .map(|(field, value)| {
+                let salt = salts.get(field)?;
+                Some((field.clone(), commit_attribute(field, value, salt)))
+            })
+            .collect::<Option<_>>()?;
+        let root = MerkleTree::from_leaves(commitments.values().cloned().collect())
+            .root()
+            .map(str::to_string)
+            .unwrap_or_else(MerkleTree::empty_hash);
+        Some(Self {
+            root,
+            commitments,
+            salts,
+        })
+    }
+
+    /// `value` as committed to under `field`, with its path to the root
+    pub fn disclose(&self, field: &str, value: &serde_json::Value) -> Option<DisclosedAttribute> {
+        let commitment = self.commitments.get(field)?;
+        let proof = MerkleTree::from_leaves(self.commitments.values().cloned().collect())
+            .generate_proof_by_hash(commitment)
+            .ok()?;
+        Some(DisclosedAttribute {
+            field: field.to_string(),
+            value: value.clone(),
+            salt: self.salts.get(field)?.clone(),
+            proof,
+        })
+    }
+}
+
+/// One attribute opened from its commitment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisclosedAttribute {
+    pub field: String,
+    pub value: serde_json::Value,
+    pub salt: String,
+    pub proof: MerkleProof,
+}
+
+impl DisclosedAttribute {
+    /// The value matches the proved leaf and the leaf lies under `root`
+    pub fn verify(&self, root: &str) -> bool {
+        self.proof.leaf_hash == commit_attribute(&self.field, &self.value, &self.salt)
+            && MerkleTree::verify_proof(&self.proof, root)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchProofResult {
+    pub proof_id: Uuid,
+    pub is_valid: bool,
+    /// Status after the batch; `None` for proofs that were not found
+    pub status: Option<ProofStatus>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchVerificationReport {
+    pub results: Vec<BatchProofResult>,
+    pub total: usize,
+    pub valid: usize,
+    pub invalid: usize,
+    pub errors: usize,
+    /// Proofs settled by a combined pairing check of their setup
+    pub batched_proofs: usize,
+    pub elapsed_ms: f64,
+    pub average_ms: f64,
+}
+
+/// Format of [`ProofBundle`]s this build writes and can verify
+pub const PROOF_BUNDLE_VERSION: u32 = 1;
+pub const PROOF_SYSTEM: &str = "groth16-bn254";
+
+/// Self-contained copy of a proof for partners to check offline with
+/// [`verify_bundle`]. Binary fields are base64 of compressed arkworks
+/// encodings. A bundle proves nothing about who made it: partners should
+/// compare `setup_artifact_id` with a verifying key id they trust (see
+/// `GET /api/proofs/setup`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofBundle {
+    pub bundle_version: u32,
+    pub proof_system: String,
+    pub proof_id: Uuid,
+    pub circuit_type: CircuitType,
+    pub circuit_version: String,
+    pub circuit: Option<ProofBundleCircuit>,
+    /// Definition of a custom circuit, which verifiers need to rebuild its
+    /// public inputs
+    #[serde(default)]
+    pub custom_circuit: Option<CustomCircuitDefinition>,
+    pub item_id: Option<Uuid>,
+    pub public_inputs: HashMap<String, serde_json::Value>,
+    pub proof: String,
+    pub verifying_key: String,
+    /// BLAKE3 of the verifying key
+    pub setup_artifact_id: String,
+    /// Status on this server when exported
+    pub status: ProofStatus,
+    pub created_at: DateTime<Utc>,
+    pub verified_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub valid_from: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Set if the proof was revoked before export
+    #[serde(default)]
+    pub revocation: Option<ProofRevocation>,
+    pub anchor: Option<ProofAnchor>,
+    pub exported_at: DateTime<Utc>,
+}
+
+/// What the proof's circuit states, from its template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofBundleCircuit {
+    pub template_id: String,
+    pub name: String,
+    pub description: String,
+    pub public_parameters: Vec<String>,
+}
+
+/// Check a bundle's proof against its public inputs and verifying key,
+/// without storage or network access. `Ok(false)` means the proof does not
+/// hold; errors mean the bundle itself is unreadable or inconsistent. Expiry
+/// is not checked, as it is the verifier's policy whether old proofs count.
+pub fn verify_bundle(bundle: &ProofBundle) -> Result<bool, ZkProofError> {
+    if bundle.bundle_version != PROOF_BUNDLE_VERSION || bundle.proof_system != PROOF_SYSTEM {
+        return Err(ZkProofError::InvalidInput(format!(
+            "Unsupported bundle: version {} of {}",
+            bundle.bundle_version, bundle.proof_system
+        )));
+    }
+    let decode = |name: &str, value: &str| {
+        BASE64
+            .decode(value)
+            .map_err(|e| ZkProofError::InvalidInput(format!("{name} is not base64: {e}")))
+    };
+    let verifying_key = decode("verifying_key", &bundle.verifying_key)?;
+    let proof = decode("proof", &bundle.proof)?;
+    if zk_circuits::verifying_key_id(&verifying_key) != bundle.setup_artifact_id {
+        return Err(ZkProofError::InvalidInput(
+            "verifying_key does not match setup_artifact_id".to_string(),
+        ));
+    }
+    if let (CircuitType::Custom(circuit_id), Some(definition)) =
+        (&bundle.circuit_type, &bundle.custom_circuit)
+    {
+        if *circuit_id != definition.circuit_id {
+            return Err(ZkProofError::InvalidInput(
+                "custom_circuit does not match circuit_type".to_string(),
+            ));
+        }
+    }
+    let Some(public_inputs) = circuit_public_inputs(
+        &bundle.circuit_type,
+        bundle.custom_circuit.as_ref(),
+        &bundle.public_inputs,
+    ) else {
+        return Ok(false);
+    };
+    zk_circuits::verify(&verifying_key, &public_inputs, &proof)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SetupSource {
+    /// Single-party setup run by this server
+    Generated,
+    /// Keys of an external (multi-party) ceremony, imported by an admin
+    Imported,
+}
+
+/// Groth16 keys of a circuit. Only the active artifact of a circuit type
+/// proves; retired ones still verify the proofs they produced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZkSetupArtifact {
+    /// BLAKE3 of the verifying key
+    pub artifact_id: String,
+    pub circuit_type: CircuitType,
+    pub circuit_version: String,
+    pub proving_key: Vec<u8>,
+    pub verifying_key: Vec<u8>,
+    pub source: SetupSource,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CircuitTemplate {
     pub template_id: String,
@@ -74,6 +429,37 @@ pub struct CircuitInput {
     pub constraints: Option<String>,
 }
 
+impl CircuitInput {
+    /// An input only the prover sees
+    pub fn private(
+        name: &str,
+        input_type: &str,
+        description: &str,
+        constraints: Option<&str>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            input_type: input_type.to_string(),
+            description: description.to_string(),
+            is_public: false,
+            constraints: constraints.map(str::to_string),
+        }
+    }
+
+    /// An input stored with the proof, which verifiers check it against
+    pub fn public(
+        name: &str,
+        input_type: &str,
+        description: &str,
+        constraints: Option<&str>,
+    ) -> Self {
+        Self {
+            is_public: true,
+            ..Self::private(name, input_type, description, constraints)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgriculturalContext {
     pub domain: String,         // "organic", "pesticide", "quality", etc.
@@ -82,6 +468,422 @@ pub struct AgriculturalContext {
     pub certification_bodies: Vec<String>,
 }
 
+impl AgriculturalContext {
+    pub fn new(
+        domain: &str,
+        standards: &[&str],
+        applicable_crops: &[&str],
+        certification_bodies: &[&str],
+    ) -> Self {
+        let strings = |values: &[&str]| values.iter().map(|value| value.to_string()).collect();
+        Self {
+            domain: domain.to_string(),
+            standards: strings(standards),
+            applicable_crops: strings(applicable_crops),
+            certification_bodies: strings(certification_bodies),
+        }
+    }
+}
+
+// ============================================================================
+// CUSTOM CIRCUITS
+// ============================================================================
+
+/// Constraint templates a custom circuit can instantiate. Each binds the
+/// committed values to a public standard like the built-in templates do.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConstraintTemplate {
+    /// Every value is at most the bound
+    AtMost,
+    /// Every value is at least the bound
+    AtLeast,
+    /// The values add up to at most the bound
+    TotalAtMost,
+    /// Every value lies between the two bounds, both included
+    Range,
+}
+
+impl ConstraintTemplate {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConstraintTemplate::AtMost => "at_most",
+            ConstraintTemplate::AtLeast => "at_least",
+            ConstraintTemplate::TotalAtMost => "total_at_most",
+            ConstraintTemplate::Range => "range",
+        }
+    }
+
+    /// Public bounds the template takes: min and max for a range, one bound
+    /// otherwise
+    pub fn bound_count(&self) -> usize {
+        match self {
+            ConstraintTemplate::Range => 2,
+            _ => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomCircuitParameters {
+    /// Most values one proof commits to, up to [`MAX_COMMITTED_VALUES`]
+    pub max_values: usize,
+    /// Added to every value and bound before fixed-point encoding, e.g. 273.15
+    /// to prove over readings in °C
+    #[serde(default)]
+    pub offset: f64,
+}
+
+/// An input of a custom circuit. Limits are checked when a proof is submitted,
+/// before anything is committed to.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomInputField {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+}
+
+impl CustomInputField {
+    fn check(&self, value: f64) -> Result<(), ZkProofError> {
+        if self.minimum.is_some_and(|minimum| value < minimum)
+            || self.maximum.is_some_and(|maximum| value > maximum)
+        {
+            return Err(ZkProofError::InvalidInput(format!(
+                "{} is outside its allowed range",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomInputSchema {
+    /// Private input with the committed numbers: an array, or an object of
+    /// named numbers
+    pub values: CustomInputField,
+    /// Public string naming the standard the bounds come from
+    pub standard: CustomInputField,
+    /// Public numbers in the order of the template: min then max for a range
+    pub bounds: Vec<CustomInputField>,
+}
+
+/// A circuit a workspace registered through the API. Proofs name it as
+/// `CircuitType::Custom(circuit_id)` and get their own Groth16 setup.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomCircuitDefinition {
+    pub circuit_id: String,
+    pub workspace_id: String,
+    pub name: String,
+    pub description: String,
+    pub template: ConstraintTemplate,
+    pub parameters: CustomCircuitParameters,
+    pub input_schema: CustomInputSchema,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomCircuitInput {
+    pub circuit_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub template: ConstraintTemplate,
+    pub parameters: CustomCircuitParameters,
+    pub input_schema: CustomInputSchema,
+}
+
+/// Inputs the engine reads besides the schema's
+const RESERVED_INPUTS: [&str; 2] = ["commitment", "commitment_salt"];
+
+impl CustomCircuitDefinition {
+    fn validate(&self) -> Result<(), ZkProofError> {
+        let invalid = |message: String| Err(ZkProofError::InvalidCircuit(message));
+        let id_ok = (3..=64).contains(&self.circuit_id.len())
+            && self
+                .circuit_id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+        if !id_ok {
+            return invalid(
+                "circuit_id must be 3 to 64 lowercase letters, digits, '_' or '-'".to_string(),
+            );
+        }
+        if self.name.trim().is_empty() {
+            return invalid("A custom circuit needs a name".to_string());
+        }
+        if !(1..=MAX_COMMITTED_VALUES).contains(&self.parameters.max_values) {
+            return invalid(format!(
+                "max_values must be between 1 and {MAX_COMMITTED_VALUES}"
+            ));
+        }
+        if !self.parameters.offset.is_finite() {
+            return invalid("offset must be a finite number".to_string());
+        }
+        let schema = &self.input_schema;
+        if schema.bounds.len() != self.template.bound_count() {
+            return invalid(format!(
+                "The {} template takes {} bound input(s)",
+                self.template.as_str(),
+                self.template.bound_count()
+            ));
+        }
+        let fields: Vec<&CustomInputField> = [&schema.values, &schema.standard]
+            .into_iter()
+            .chain(&schema.bounds)
+            .collect();
+        for (index, field) in fields.iter().enumerate() {
+            if field.name.trim().is_empty() || RESERVED_INPUTS.contains(&field.name.as_str()) {
+                return invalid(format!("Invalid input name '{}'", field.name));
+            }
+            if fields[..index].iter().any(|other| other.name == field.name) {
+                return invalid(format!("Input '{}' is declared twice", field.name));
+            }
+            if let (Some(minimum), Some(maximum)) = (field.minimum, field.maximum) {
+                if minimum > maximum {
+                    return invalid(format!("{} has a minimum above its maximum", field.name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check submitted inputs against the schema: every input present with the
+    /// right shape and within its limits, and nothing the schema does not name
+    pub fn validate_inputs(
+        &self,
+        public_inputs: &HashMap<String, serde_json::Value>,
+        private_inputs: &HashMap<String, serde_json::Value>,
+    ) -> Result<(), ZkProofError> {
+        let schema = &self.input_schema;
+        if let Some(unknown) = public_inputs.keys().find(|name| {
+            **name != schema.standard.name && !schema.bounds.iter().any(|b| b.name == **name)
+        }) {
+            return Err(ZkProofError::InvalidInput(format!(
+                "{unknown} is not a public input of {}",
+                self.circuit_id
+            )));
+        }
+        if let Some(unknown) = private_inputs
+            .keys()
+            .find(|name| **name != schema.values.name && *name != "commitment_salt")
+        {
+            return Err(ZkProofError::InvalidInput(format!(
+                "{unknown} is not a private input of {}",
+                self.circuit_id
+            )));
+        }
+        let required = |inputs: &'_ HashMap<String, serde_json::Value>, name: &str| {
+            inputs.get(name).cloned().ok_or_else(|| {
+                ZkProofError::InvalidInput(format!("Missing required input: {name}"))
+            })
+        };
+
+        let standard = required(public_inputs, &schema.standard.name)?;
+        if str_input(&standard, &schema.standard.name)?
+            .trim()
+            .is_empty()
+        {
+            return Err(ZkProofError::InvalidInput(format!(
+                "{} must not be empty",
+                schema.standard.name
+            )));
+        }
+        for bound in &schema.bounds {
+            let value = required(public_inputs, &bound.name)?;
+            bound.check(number_input(&value, &bound.name)?)?;
+        }
+        let values = self.values(&required(private_inputs, &schema.values.name)?)?;
+        if values.len() > self.parameters.max_values {
+            return Err(ZkProofError::InvalidInput(format!(
+                "{} must hold between 1 and {} values",
+                schema.values.name, self.parameters.max_values
+            )));
+        }
+        for value in values {
+            schema.values.check(value)?;
+        }
+        Ok(())
+    }
+
+    fn values(&self, value: &serde_json::Value) -> Result<Vec<f64>, ZkProofError> {
+        let name = &self.input_schema.values.name;
+        let values = match value {
+            serde_json::Value::Array(values) => values.iter().collect::<Vec<_>>(),
+            serde_json::Value::Object(values) => values.values().collect(),
+            _ => {
+                return Err(ZkProofError::InvalidInput(format!(
+                    "{name} must be an array or an object of numbers"
+                )))
+            }
+        };
+        check_value_count(values.len(), name)?;
+        values
+            .into_iter()
+            .map(|value| number_input(value, name))
+            .collect()
+    }
+
+    fn encode(&self, value: f64, name: &str) -> Result<u64, ZkProofError> {
+        zk_circuits::to_fixed_point(value + self.parameters.offset).ok_or_else(|| {
+            ZkProofError::InvalidInput(format!(
+                "{name} must not be negative after adding the circuit's offset"
+            ))
+        })
+    }
+
+    /// The circuit with its witness, for inputs that passed [`Self::validate_inputs`]
+    fn circuit(
+        &self,
+        public_inputs: &HashMap<String, serde_json::Value>,
+        private_inputs: &HashMap<String, serde_json::Value>,
+        salt: Fr,
+    ) -> Result<TemplateCircuit, ZkProofError> {
+        let schema = &self.input_schema;
+        let standard = zk_circuits::field_from_str(str_input(
+            &public_inputs[&schema.standard.name],
+            &schema.standard.name,
+        )?);
+        let bounds = schema
+            .bounds
+            .iter()
+            .map(|bound| {
+                self.encode(
+                    number_input(&public_inputs[&bound.name], &bound.name)?,
+                    &bound.name,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let values = self
+            .values(&private_inputs[&schema.values.name])?
+            .into_iter()
+            .map(|value| self.encode(value, &schema.values.name))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let unmet = |what: &str| {
+            Err(ZkProofError::InvalidInput(format!(
+                "{} {what}",
+                schema.values.name
+            )))
+        };
+        let threshold = |kind| {
+            TemplateCircuit::Threshold(ThresholdCircuit::new(
+                kind, &values, salt, standard, bounds[0],
+            ))
+        };
+        match self.template {
+            ConstraintTemplate::AtMost => {
+                if values.iter().any(|value| *value > bounds[0]) {
+                    return unmet(&format!("has a value above {}", schema.bounds[0].name));
+                }
+                Ok(threshold(BoundKind::AtMost))
+            }
+            ConstraintTemplate::AtLeast => {
+                if values.iter().any(|value| *value < bounds[0]) {
+                    return unmet(&format!("has a value below {}", schema.bounds[0].name));
+                }
+                Ok(threshold(BoundKind::AtLeast))
+            }
+            ConstraintTemplate::TotalAtMost => {
+                if values.iter().sum::<u64>() > bounds[0] {
+                    return unmet(&format!("add up to more than {}", schema.bounds[0].name));
+                }
+                Ok(threshold(BoundKind::TotalAtMost))
+            }
+            ConstraintTemplate::Range => {
+                let (min, max) = (bounds[0], bounds[1]);
+                if min > max {
+                    return Err(ZkProofError::InvalidInput(format!(
+                        "{} must not exceed {}",
+                        schema.bounds[0].name, schema.bounds[1].name
+                    )));
+                }
+                if values.iter().any(|value| *value < min || *value > max) {
+                    return unmet("has a value outside the allowed range");
+                }
+                Ok(TemplateCircuit::Range(RangeCircuit::new(
+                    &values, salt, standard, min, max,
+                )))
+            }
+        }
+    }
+
+    /// Field elements a proof of this circuit is verified against
+    fn public_field_inputs(
+        &self,
+        public_inputs: &HashMap<String, serde_json::Value>,
+        commitment: Fr,
+    ) -> Option<Vec<Fr>> {
+        let schema = &self.input_schema;
+        let standard =
+            zk_circuits::field_from_str(public_inputs.get(&schema.standard.name)?.as_str()?);
+        let bounds = schema
+            .bounds
+            .iter()
+            .map(|bound| {
+                zk_circuits::to_fixed_point(
+                    public_inputs.get(&bound.name)?.as_f64()? + self.parameters.offset,
+                )
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(match self.template {
+            ConstraintTemplate::Range => {
+                RangeCircuit::public_inputs(standard, bounds[0], bounds[1], commitment)
+            }
+            _ => ThresholdCircuit::public_inputs(standard, bounds[0], commitment),
+        })
+    }
+
+    /// The definition as a template, listed next to the built-in ones
+    pub fn to_template(&self) -> CircuitTemplate {
+        let schema = &self.input_schema;
+        let limits = |field: &CustomInputField| match (field.minimum, field.maximum) {
+            (None, None) => None,
+            (minimum, maximum) => Some(format!(
+                "BETWEEN {} AND {}",
+                minimum.map_or("-".to_string(), |v| v.to_string()),
+                maximum.map_or("-".to_string(), |v| v.to_string())
+            )),
+        };
+        let mut required_inputs = vec![
+            CircuitInput::private(
+                &schema.values.name,
+                "array",
+                &schema.values.description,
+                limits(&schema.values).as_deref(),
+            ),
+            CircuitInput::public(
+                &schema.standard.name,
+                "string",
+                &schema.standard.description,
+                None,
+            ),
+        ];
+        required_inputs.extend(schema.bounds.iter().map(|bound| {
+            CircuitInput::public(
+                &bound.name,
+                "number",
+                &bound.description,
+                limits(bound).as_deref(),
+            )
+        }));
+        CircuitTemplate {
+            template_id: self.circuit_id.clone(),
+            circuit_type: CircuitType::Custom(self.circuit_id.clone()),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            version: "1.0.0".to_string(),
+            required_inputs,
+            public_parameters: vec![schema.standard.name.clone()],
+            verification_constraints: vec![self.template.as_str().to_string()],
+            agricultural_context: AgriculturalContext::new("custom", &[], &[], &[]),
+        }
+    }
+}
+
 // ============================================================================
 // ERRORS
 // ============================================================================
@@ -93,7 +895,14 @@ pub enum ZkProofError {
     VerificationError(String),
     InvalidCircuit(String),
     ExpiredProof(Uuid),
+    RevokedProof(Uuid),
+    /// The proof's validity window has not started yet
+    NotYetValid(Uuid),
     InvalidInput(String),
+    PermissionDenied(String),
+    AnchoringError(String),
+    /// The proof generation queue cannot take more jobs right now
+    QueueFull,
 }
 
 impl std::fmt::Display for ZkProofError {
@@ -104,7 +913,12 @@ impl std::fmt::Display for ZkProofError {
             ZkProofError::VerificationError(e) => write!(f, "Verification error: {e}"),
             ZkProofError::InvalidCircuit(e) => write!(f, "Invalid circuit: {e}"),
             ZkProofError::ExpiredProof(id) => write!(f, "Proof expired: {id}"),
+            ZkProofError::RevokedProof(id) => write!(f, "Proof revoked: {id}"),
+            ZkProofError::NotYetValid(id) => write!(f, "Proof not yet valid: {id}"),
             ZkProofError::InvalidInput(e) => write!(f, "Invalid input: {e}"),
+            ZkProofError::PermissionDenied(e) => write!(f, "Permission denied: {e}"),
+            ZkProofError::AnchoringError(e) => write!(f, "Anchoring error: {e}"),
+            ZkProofError::QueueFull => write!(f, "Proof queue is full; retry later"),
         }
     }
 }
@@ -125,6 +939,7 @@ impl From<StorageError> for ZkProofError {
 pub struct ZkProofEngine<S: StorageBackend> {
     storage: S,
     circuit_templates: HashMap<String, CircuitTemplate>,
+    stellar: Option<(AnchoringNetwork, Arc<StellarClient>)>,
 }
 
 impl<S: StorageBackend> ZkProofEngine<S> {
@@ -132,6 +947,7 @@ impl<S: StorageBackend> ZkProofEngine<S> {
         let mut engine = Self {
             storage,
             circuit_templates: HashMap::new(),
+            stellar: None,
         };
 
         // Initialize pre-built agricultural circuit templates
@@ -139,6 +955,69 @@ impl<S: StorageBackend> ZkProofEngine<S> {
         engine
     }
 
+    /// Publish the digest of each proof this engine anchors through `client`
+    pub fn with_stellar_anchoring(
+        mut self,
+        network: AnchoringNetwork,
+        client: Arc<StellarClient>,
+    ) -> Self {
+        self.stellar = Some((network, client));
+        self
+    }
+
+    /// Anchor on the network named by `ZK_PROOF_ANCHOR_NETWORK` ("testnet" or
+    /// "mainnet"), signing with that network's `STELLAR_*_SECRET`. Anchoring
+    /// stays off when the variable is unset or the keypair is missing.
+    pub fn with_stellar_anchoring_from_env(self) -> Self {
+        let (network, stellar_network, env_prefix, default_contract) =
+            match std::env::var("ZK_PROOF_ANCHOR_NETWORK").as_deref() {
+                Ok("testnet") => (
+                    AnchoringNetwork::StellarTestnet,
+                    StellarNetwork::Testnet,
+                    "STELLAR_TESTNET",
+                    TESTNET_IPCM_CONTRACT,
+                ),
+                Ok("mainnet") => (
+                    AnchoringNetwork::StellarMainnet,
+                    StellarNetwork::Mainnet,
+                    "STELLAR_MAINNET",
+                    MAINNET_IPCM_CONTRACT,
+                ),
+                Ok(other) => {
+                    tracing::warn!(
+                        "⚠️  Unknown ZK_PROOF_ANCHOR_NETWORK '{}', not anchoring",
+                        other
+                    );
+                    return self;
+                }
+                Err(_) => return self,
+            };
+        let contract_address = std::env::var(format!("{env_prefix}_IPCM_CONTRACT"))
+            .unwrap_or_else(|_| default_contract.to_string());
+        let client = std::env::var(format!("{env_prefix}_SECRET"))
+            .map_err(|e| e.to_string())
+            .and_then(|secret| {
+                StellarClient::new(stellar_network, contract_address)
+                    .with_keypair(&secret)
+                    .map_err(|e| e.to_string())
+            });
+        match client {
+            Ok(client) => self.with_stellar_anchoring(network, Arc::new(client)),
+            Err(e) => {
+                tracing::warn!(
+                    "⚠️  ZK proof anchoring disabled, no usable {}_SECRET: {}",
+                    env_prefix,
+                    e
+                );
+                self
+            }
+        }
+    }
+
+    pub fn anchoring_enabled(&self) -> bool {
+        self.stellar.is_some()
+    }
+
     // ============================================================================
     // PROOF LIFECYCLE MANAGEMENT
     // ============================================================================
@@ -151,22 +1030,60 @@ impl<S: StorageBackend> ZkProofEngine<S> {
         private_inputs: HashMap<String, serde_json::Value>,
         item_id: Option<Uuid>,
     ) -> Result<Uuid, ZkProofError> {
-        let proof_id = Uuid::new_v4();
-
-        // Validate inputs against circuit template
-        self.validate_proof_inputs(&circuit_type, &public_inputs, &private_inputs)?;
+        self.submit_proof_with_validity(
+            circuit_type,
+            prover_id,
+            public_inputs,
+            private_inputs,
+            item_id,
+            ProofValidity::default(),
+        )
+    }
 
-        // Generate proof data (simplified for now - in real implementation would use actual ZK library)
-        let proof_data =
-            self.generate_proof_data(&circuit_type, &public_inputs, &private_inputs)?;
+    pub fn submit_proof_with_validity(
+        &self,
+        circuit_type: CircuitType,
+        prover_id: String,
+        mut public_inputs: HashMap<String, serde_json::Value>,
+        private_inputs: HashMap<String, serde_json::Value>,
+        item_id: Option<Uuid>,
+        validity: ProofValidity,
+    ) -> Result<Uuid, ZkProofError> {
+        let proof_id = Uuid::new_v4();
+        let custom = self.check_submission(
+            &circuit_type,
+            &prover_id,
+            &public_inputs,
+            &private_inputs,
+            &validity,
+        )?;
+
+        let circuit = self.build_circuit(
+            &circuit_type,
+            custom.as_ref(),
+            &mut public_inputs,
+            &private_inputs,
+            Utc::now(),
+        )?;
+        public_inputs.insert(
+            "commitment".to_string(),
+            serde_json::Value::String(zk_circuits::field_to_hex(&circuit.commitment())),
+        );
+        let setup = self.active_setup_artifact(&circuit_type)?;
+        let proof_data = zk_circuits::prove(&setup.proving_key, circuit)?;
 
         // Hash private inputs for privacy
         let private_inputs_hash = self.hash_private_inputs(&private_inputs);
+        let item_dfid = match public_inputs.get("item_dfid").and_then(|v| v.as_str()) {
+            Some(dfid) if can_read_item(&self.storage, dfid, &prover_id)? => Some(dfid.to_string()),
+            _ => None,
+        };
 
         let proof = ZkProof {
             proof_id,
             circuit_type: circuit_type.clone(),
             item_id,
+            item_dfid,
             prover_id,
             proof_data,
             public_inputs,
@@ -174,8 +1091,13 @@ impl<S: StorageBackend> ZkProofEngine<S> {
             status: ProofStatus::Pending,
             created_at: Utc::now(),
             verified_at: None,
-            expires_at: self.calculate_expiry(&circuit_type),
+            expires_at: validity
+                .expires_at
+                .or_else(|| self.calculate_expiry(&circuit_type)),
+            valid_from: validity.valid_from,
             verification_result: None,
+            setup_artifact_id: Some(setup.artifact_id),
+            anchor: None,
         };
 
         // Store proof
@@ -186,6 +1108,163 @@ impl<S: StorageBackend> ZkProofEngine<S> {
         Ok(proof_id)
     }
 
+    /// Checks a submission can be proven before any work is done: the validity
+    /// window, access to a custom circuit, and the inputs against the template.
+    /// Returns the custom circuit's definition.
+    fn check_submission(
+        &self,
+        circuit_type: &CircuitType,
+        prover_id: &str,
+        public_inputs: &HashMap<String, serde_json::Value>,
+        private_inputs: &HashMap<String, serde_json::Value>,
+        validity: &ProofValidity,
+    ) -> Result<Option<CustomCircuitDefinition>, ZkProofError> {
+        if let Some(expires_at) = validity.expires_at {
+            if expires_at <= validity.valid_from.unwrap_or_else(Utc::now) {
+                return Err(ZkProofError::InvalidInput(
+                    "expires_at must be after valid_from and in the future".to_string(),
+                ));
+            }
+        }
+
+        let custom = match circuit_type {
+            CircuitType::Custom(circuit_id) => {
+                let definition = self.require_custom_circuit(circuit_id)?;
+                if self.workspace_of(prover_id)?.as_ref() != Some(&definition.workspace_id) {
+                    return Err(ZkProofError::PermissionDenied(format!(
+                        "{circuit_id} belongs to another workspace"
+                    )));
+                }
+                Some(definition)
+            }
+            _ => None,
+        };
+
+        // Validate inputs against circuit template
+        self.validate_proof_inputs(circuit_type, custom.as_ref(), public_inputs, private_inputs)?;
+        Ok(custom)
+    }
+
+    // ============================================================================
+    // PROOF GENERATION JOBS
+    // ============================================================================
+
+    /// Record a job for generating a proof in the background. Inputs are
+    /// checked now so that bad requests fail before anything is queued.
+    pub fn create_proof_job(
+        &self,
+        circuit_type: CircuitType,
+        prover_id: String,
+        public_inputs: &HashMap<String, serde_json::Value>,
+        private_inputs: &HashMap<String, serde_json::Value>,
+        validity: &ProofValidity,
+    ) -> Result<ProofJob, ZkProofError> {
+        self.check_submission(
+            &circuit_type,
+            &prover_id,
+            public_inputs,
+            private_inputs,
+            validity,
+        )?;
+        let job = ProofJob {
+            job_id: Uuid::new_v4(),
+            circuit_type,
+            prover_id,
+            item_dfid: public_inputs
+                .get("item_dfid")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            status: ProofStatus::Pending,
+            proof_id: None,
+            error: None,
+            created_at: Utc::now(),
+            started_at: None,
+            completed_at: None,
+        };
+        self.storage.store_zk_proof_job(&job)?;
+        Ok(job)
+    }
+
+    /// Generate and verify the job's proof, recording each step on the job.
+    /// Failures end up on the job rather than being returned.
+    pub fn run_proof_job(
+        &self,
+        mut job: ProofJob,
+        public_inputs: HashMap<String, serde_json::Value>,
+        private_inputs: HashMap<String, serde_json::Value>,
+        validity: ProofValidity,
+    ) -> Result<ProofJob, ZkProofError> {
+        job.status = ProofStatus::Generating;
+        job.started_at = Some(Utc::now());
+        self.storage.store_zk_proof_job(&job)?;
+
+        let outcome = self
+            .submit_proof_with_validity(
+                job.circuit_type.clone(),
+                job.prover_id.clone(),
+                public_inputs,
+                private_inputs,
+                None,
+                validity,
+            )
+            .and_then(|proof_id| {
+                job.proof_id = Some(proof_id);
+                self.verify_proof(proof_id, PROOF_WORKER_ID.to_string())
+            });
+        match outcome {
+            Ok(verification) if verification.is_valid => job.status = ProofStatus::Verified,
+            Ok(_) => {
+                job.status = ProofStatus::Failed;
+                job.error = Some("The generated proof did not verify".to_string());
+            }
+            // Not yet valid proofs were generated fine; they verify later
+            Err(ZkProofError::NotYetValid(_)) => job.status = ProofStatus::Verified,
+            Err(e) => {
+                job.status = ProofStatus::Failed;
+                job.error = Some(e.to_string());
+            }
+        }
+        job.completed_at = Some(Utc::now());
+        self.storage.store_zk_proof_job(&job)?;
+        Ok(job)
+    }
+
+    /// Mark the job failed without running it, e.g. when it could not be queued
+    pub fn fail_proof_job(&self, mut job: ProofJob, error: &str) -> Result<ProofJob, ZkProofError> {
+        job.status = ProofStatus::Failed;
+        job.error = Some(error.to_string());
+        job.completed_at = Some(Utc::now());
+        self.storage.store_zk_proof_job(&job)?;
+        Ok(job)
+    }
+
+    /// A job, if the user submitted it
+    pub fn get_proof_job(
+        &self,
+        job_id: &Uuid,
+        user_id: &str,
+    ) -> Result<Option<ProofJob>, ZkProofError> {
+        Ok(self
+            .storage
+            .get_zk_proof_job(job_id)?
+            .filter(|job| job.prover_id == user_id))
+    }
+
+    /// Fail jobs left queued or generating by a previous process: their
+    /// private inputs were held in memory only and are gone
+    pub fn recover_proof_jobs(&self) -> Result<usize, ZkProofError> {
+        let interrupted: Vec<ProofJob> = self
+            .storage
+            .list_zk_proof_jobs()?
+            .into_iter()
+            .filter(|job| matches!(job.status, ProofStatus::Pending | ProofStatus::Generating))
+            .collect();
+        for job in &interrupted {
+            self.fail_proof_job(job.clone(), "Interrupted by a restart; submit it again")?;
+        }
+        Ok(interrupted.len())
+    }
+
     pub fn verify_proof(
         &self,
         proof_id: Uuid,
@@ -197,24 +1276,173 @@ impl<S: StorageBackend> ZkProofEngine<S> {
                 .ok_or_else(|| ZkProofError::VerificationError("Proof not found".to_string()))?
         };
 
-        // Check if proof is expired
+        self.check_validity(&mut proof)?;
+        let is_valid = self.perform_verification(&proof)?;
+        self.record_verification(&mut proof, is_valid, verifier_id)
+    }
+
+    /// Verify many proofs at once. Proofs sharing a setup are checked with
+    /// one combined pairing check; missing or expired proofs are reported in
+    /// their result instead of failing the batch.
+    pub fn verify_proofs_batch(
+        &self,
+        proof_ids: &[Uuid],
+        verifier_id: String,
+    ) -> Result<BatchVerificationReport, ZkProofError> {
+        let started = std::time::Instant::now();
+        let mut proof_ids = proof_ids.to_vec();
+        let mut seen = std::collections::HashSet::new();
+        proof_ids.retain(|proof_id| seen.insert(*proof_id));
+        if proof_ids.is_empty() || proof_ids.len() > MAX_BATCH_SIZE {
+            return Err(ZkProofError::InvalidInput(format!(
+                "A batch holds between 1 and {MAX_BATCH_SIZE} proofs"
+            )));
+        }
+
+        let mut results: Vec<BatchProofResult> = Vec::with_capacity(proof_ids.len());
+        // artifact_id -> (proof, public inputs, index into results)
+        let mut groups: HashMap<String, Vec<(ZkProof, Vec<Fr>, usize)>> = HashMap::new();
+        for proof_id in proof_ids {
+            let index = results.len();
+            results.push(BatchProofResult {
+                proof_id,
+                is_valid: false,
+                status: None,
+                error: None,
+            });
+            let mut proof = match self.storage.get_zk_proof(&proof_id)? {
+                Some(proof) => proof,
+                None => {
+                    results[index].error = Some("Proof not found".to_string());
+                    continue;
+                }
+            };
+            if let Err(e) = self.check_validity(&mut proof) {
+                results[index].status = Some(proof.status);
+                results[index].error = Some(e.to_string());
+                continue;
+            }
+            let inputs = circuit_public_inputs(
+                &proof.circuit_type,
+                self.custom_circuit_of(&proof.circuit_type)?.as_ref(),
+                &proof.public_inputs,
+            );
+            match (proof.setup_artifact_id.clone(), inputs) {
+                (Some(artifact_id), Some(inputs)) => {
+                    groups
+                        .entry(artifact_id)
+                        .or_default()
+                        .push((proof, inputs, index));
+                }
+                _ => {
+                    let recorded =
+                        self.record_verification(&mut proof, false, verifier_id.clone())?;
+                    results[index].status = Some(proof.status);
+                    results[index].is_valid = recorded.is_valid;
+                }
+            }
+        }
+
+        let mut batched_proofs = 0;
+        for (artifact_id, members) in groups {
+            let artifact = self
+                .storage
+                .get_zk_setup_artifact(&artifact_id)?
+                .filter(|artifact| {
+                    members
+                        .iter()
+                        .all(|(proof, _, _)| proof.circuit_type == artifact.circuit_type)
+                });
+            let Some(artifact) = artifact else {
+                for (_, _, index) in &members {
+                    results[*index].error = Some(format!("Unknown setup artifact {artifact_id}"));
+                }
+                continue;
+            };
+            let batch: Vec<(Vec<Fr>, &[u8])> = members
+                .iter()
+                .map(|(proof, inputs, _)| (inputs.clone(), proof.proof_data.as_slice()))
+                .collect();
+            let outcome = zk_circuits::verify_batch(&artifact.verifying_key, &batch)?;
+            if outcome.combined {
+                batched_proofs += members.len();
+            }
+            for ((mut proof, _, index), is_valid) in members.into_iter().zip(outcome.results) {
+                self.record_verification(&mut proof, is_valid, verifier_id.clone())?;
+                results[index].is_valid = is_valid;
+                results[index].status = Some(proof.status);
+            }
+        }
+
+        let elapsed = started.elapsed();
+        let valid = results.iter().filter(|result| result.is_valid).count();
+        let errors = results
+            .iter()
+            .filter(|result| result.error.is_some())
+            .count();
+        Ok(BatchVerificationReport {
+            total: results.len(),
+            valid,
+            invalid: results.len() - valid - errors,
+            errors,
+            batched_proofs,
+            elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+            average_ms: elapsed.as_secs_f64() * 1000.0 / results.len() as f64,
+            results,
+        })
+    }
+
+    /// Refuses revoked proofs and proofs whose window has not started; marks
+    /// and stores the proof as expired once past its expiry
+    fn check_validity(&self, proof: &mut ZkProof) -> Result<(), ZkProofError> {
+        if proof.status == ProofStatus::Revoked
+            || self
+                .storage
+                .get_zk_proof_revocation(&proof.proof_id)?
+                .is_some()
+        {
+            if proof.status != ProofStatus::Revoked {
+                proof.status = ProofStatus::Revoked;
+                self.storage.update_zk_proof(proof)?;
+            }
+            return Err(ZkProofError::RevokedProof(proof.proof_id));
+        }
+        if proof.valid_from.is_some_and(|from| Utc::now() < from) {
+            return Err(ZkProofError::NotYetValid(proof.proof_id));
+        }
         if let Some(expires_at) = proof.expires_at {
             if Utc::now() > expires_at {
                 proof.status = ProofStatus::Expired;
-                self.storage.update_zk_proof(&proof)?;
-                return Err(ZkProofError::ExpiredProof(proof_id));
+                self.storage.update_zk_proof(proof)?;
+                return Err(ZkProofError::ExpiredProof(proof.proof_id));
             }
         }
+        Ok(())
+    }
 
-        // Perform verification (simplified - real implementation would use ZK verification)
-        let is_valid = self.perform_verification(&proof)?;
-
+    fn record_verification(
+        &self,
+        proof: &mut ZkProof,
+        is_valid: bool,
+        verifier_id: String,
+    ) -> Result<VerificationResult, ZkProofError> {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "proof_system".to_string(),
+            serde_json::Value::String(PROOF_SYSTEM.to_string()),
+        );
+        if let Some(artifact_id) = &proof.setup_artifact_id {
+            metadata.insert(
+                "setup_artifact_id".to_string(),
+                serde_json::Value::String(artifact_id.clone()),
+            );
+        }
         let verification_result = VerificationResult {
             is_valid,
             verification_timestamp: Utc::now(),
             verifier_id,
-            confidence_score: if is_valid { 0.95 } else { 0.0 },
-            metadata: HashMap::new(),
+            confidence_score: if is_valid { 1.0 } else { 0.0 },
+            metadata,
         };
 
         // Update proof status
@@ -225,12 +1453,74 @@ impl<S: StorageBackend> ZkProofEngine<S> {
         };
         proof.verified_at = Some(verification_result.verification_timestamp);
         proof.verification_result = Some(verification_result.clone());
+        self.storage.update_zk_proof(proof)?;
 
-        {
-            self.storage.update_zk_proof(&proof)?;
+        Ok(verification_result)
+    }
+
+    // ============================================================================
+    // ON-CHAIN ANCHORING
+    // ============================================================================
+
+    /// Publish a verified proof's digest on Stellar and record the transaction
+    /// on the proof. A proof is anchored once; later calls return its anchor.
+    pub async fn anchor_proof(&self, proof_id: Uuid) -> Result<ProofAnchor, ZkProofError> {
+        let proof = self
+            .storage
+            .get_zk_proof(&proof_id)?
+            .ok_or_else(|| ZkProofError::InvalidInput(format!("Proof {proof_id} not found")))?;
+        if let Some(anchor) = proof.anchor {
+            return Ok(anchor);
         }
+        if proof.status != ProofStatus::Verified {
+            return Err(ZkProofError::InvalidInput(format!(
+                "Proof {proof_id} is {:?}; only verified proofs are anchored",
+                proof.status
+            )));
+        }
+        let (network, client) = self.stellar.as_ref().ok_or_else(|| {
+            ZkProofError::AnchoringError("Stellar anchoring is not configured".to_string())
+        })?;
+        check_stellar_signer(&self.storage, client)
+            .map_err(|e| ZkProofError::AnchoringError(e.to_string()))?;
+
+        let ipcm_key = anchor_key(&proof_id);
+        let digest = anchor_digest(&proof);
+        let tx_hash = client
+            .emit_update_event(&ipcm_key, &digest)
+            .await
+            .map_err(|e| ZkProofError::AnchoringError(e.to_string()))?;
+        let anchor = ProofAnchor {
+            network: *network,
+            ipcm_key,
+            digest,
+            tx_hash,
+            anchored_at: Utc::now(),
+        };
+        self.record_anchor(proof_id, anchor.clone())?;
 
-        Ok(verification_result)
+        if let Err(e) = record_anchoring(&self.storage, None, *network, 1, 1, anchor.anchored_at) {
+            tracing::warn!("Failed to record proof anchoring cost: {}", e);
+        }
+        tracing::info!(
+            "⚓ ZK proof {} anchored on {:?}: TX={}",
+            proof_id,
+            network,
+            anchor.tx_hash
+        );
+        Ok(anchor)
+    }
+
+    /// Re-reads the proof, which may have changed while the transaction was
+    /// in flight, before attaching the anchor
+    fn record_anchor(&self, proof_id: Uuid, anchor: ProofAnchor) -> Result<(), ZkProofError> {
+        let mut proof = self
+            .storage
+            .get_zk_proof(&proof_id)?
+            .ok_or_else(|| ZkProofError::InvalidInput(format!("Proof {proof_id} not found")))?;
+        proof.anchor = Some(anchor);
+        self.storage.update_zk_proof(&proof)?;
+        Ok(())
     }
 
     pub fn get_proof(&self, proof_id: &Uuid) -> Result<Option<ZkProof>, ZkProofError> {
@@ -280,31 +1570,202 @@ impl<S: StorageBackend> ZkProofEngine<S> {
             .map_err(ZkProofError::StorageError)
     }
 
-    pub fn delete_proof(&self, proof_id: &Uuid) -> Result<(), ZkProofError> {
-        self.storage
-            .delete_zk_proof(proof_id)
-            .map_err(ZkProofError::StorageError)
-    }
+    pub fn delete_proof(&self, proof_id: &Uuid) -> Result<(), ZkProofError> {
+        self.storage
+            .delete_zk_proof(proof_id)
+            .map_err(ZkProofError::StorageError)
+    }
+
+    // ============================================================================
+    // CIRCUIT TEMPLATE MANAGEMENT
+    // ============================================================================
+
+    pub fn get_circuit_templates(&self) -> Vec<CircuitTemplate> {
+        self.circuit_templates.values().cloned().collect()
+    }
+
+    pub fn get_circuit_template(&self, template_id: &str) -> Option<&CircuitTemplate> {
+        self.circuit_templates.get(template_id)
+    }
+
+    pub fn add_custom_circuit_template(
+        &mut self,
+        template: CircuitTemplate,
+    ) -> Result<(), ZkProofError> {
+        self.circuit_templates
+            .insert(template.template_id.clone(), template);
+        Ok(())
+    }
+
+    // ============================================================================
+    // TRUSTED SETUP ARTIFACTS
+    // ============================================================================
+
+    pub fn list_setup_artifacts(&self) -> Result<Vec<ZkSetupArtifact>, ZkProofError> {
+        Ok(self.storage.list_zk_setup_artifacts()?)
+    }
+
+    pub fn get_setup_artifact(
+        &self,
+        artifact_id: &str,
+    ) -> Result<Option<ZkSetupArtifact>, ZkProofError> {
+        Ok(self.storage.get_zk_setup_artifact(artifact_id)?)
+    }
+
+    /// Package a proof with its verifying key for offline verification
+    pub fn export_bundle(&self, proof_id: &Uuid) -> Result<ProofBundle, ZkProofError> {
+        let proof = self
+            .storage
+            .get_zk_proof(proof_id)?
+            .ok_or_else(|| ZkProofError::InvalidInput(format!("Proof {proof_id} not found")))?;
+        let artifact_id = proof.setup_artifact_id.clone().ok_or_else(|| {
+            ZkProofError::InvalidInput(format!(
+                "Proof {proof_id} predates Groth16 proving and cannot be verified"
+            ))
+        })?;
+        let artifact = self
+            .storage
+            .get_zk_setup_artifact(&artifact_id)?
+            .ok_or_else(|| {
+                ZkProofError::VerificationError(format!("Unknown setup artifact {artifact_id}"))
+            })?;
+        let custom_circuit = self.custom_circuit_of(&proof.circuit_type)?;
+        let circuit = self
+            .circuit_templates
+            .values()
+            .find(|template| template.circuit_type == proof.circuit_type)
+            .cloned()
+            .or_else(|| custom_circuit.as_ref().map(|d| d.to_template()))
+            .map(|template| ProofBundleCircuit {
+                template_id: template.template_id.clone(),
+                name: template.name.clone(),
+                description: template.description.clone(),
+                public_parameters: template.public_parameters.clone(),
+            });
+        let status = proof.status_at(Utc::now());
+
+        Ok(ProofBundle {
+            bundle_version: PROOF_BUNDLE_VERSION,
+            proof_system: PROOF_SYSTEM.to_string(),
+            proof_id: proof.proof_id,
+            circuit_type: proof.circuit_type,
+            circuit_version: artifact.circuit_version,
+            circuit,
+            custom_circuit,
+            item_id: proof.item_id,
+            public_inputs: proof.public_inputs,
+            proof: BASE64.encode(&proof.proof_data),
+            verifying_key: BASE64.encode(&artifact.verifying_key),
+            setup_artifact_id: artifact_id,
+            status,
+            created_at: proof.created_at,
+            verified_at: proof.verified_at,
+            valid_from: proof.valid_from,
+            expires_at: proof.expires_at,
+            revocation: self.storage.get_zk_proof_revocation(proof_id)?,
+            anchor: proof.anchor,
+            exported_at: Utc::now(),
+        })
+    }
+
+    fn find_active_setup(
+        &self,
+        circuit_type: &CircuitType,
+    ) -> Result<Option<ZkSetupArtifact>, ZkProofError> {
+        Ok(self
+            .storage
+            .list_zk_setup_artifacts()?
+            .into_iter()
+            .rev()
+            .find(|artifact| {
+                artifact.active
+                    && artifact.circuit_type == *circuit_type
+                    && artifact.circuit_version == CIRCUIT_VERSION
+            }))
+    }
+
+    /// The artifact new proofs of `circuit_type` use. Without one for the
+    /// current circuit version, a single-party setup is run and stored.
+    pub fn active_setup_artifact(
+        &self,
+        circuit_type: &CircuitType,
+    ) -> Result<ZkSetupArtifact, ZkProofError> {
+        if let Some(artifact) = self.find_active_setup(circuit_type)? {
+            return Ok(artifact);
+        }
+        let _guard = SETUP_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(artifact) = self.find_active_setup(circuit_type)? {
+            return Ok(artifact);
+        }
 
-    // ============================================================================
-    // CIRCUIT TEMPLATE MANAGEMENT
-    // ============================================================================
+        let keys = zk_circuits::setup(self.blank_circuit(circuit_type)?)?;
+        let artifact = self.activate_setup(circuit_type, keys, SetupSource::Generated)?;
+        tracing::info!(
+            "🔐 Generated Groth16 setup {} for {:?}",
+            artifact.artifact_id,
+            circuit_type
+        );
+        Ok(artifact)
+    }
 
-    pub fn get_circuit_templates(&self) -> Vec<CircuitTemplate> {
-        self.circuit_templates.values().cloned().collect()
+    /// Replace the active setup of `circuit_type` with ceremony keys. The keys
+    /// must prove and verify a sample statement of the circuit.
+    pub fn import_setup_artifact(
+        &self,
+        circuit_type: &CircuitType,
+        proving_key: Vec<u8>,
+        verifying_key: Vec<u8>,
+    ) -> Result<ZkSetupArtifact, ZkProofError> {
+        let keys = SetupKeys {
+            proving_key,
+            verifying_key,
+        };
+        zk_circuits::validate_setup_keys(circuit_type, self.blank_circuit(circuit_type)?, &keys)?;
+        let _guard = SETUP_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        self.activate_setup(circuit_type, keys, SetupSource::Imported)
     }
 
-    pub fn get_circuit_template(&self, template_id: &str) -> Option<&CircuitTemplate> {
-        self.circuit_templates.get(template_id)
+    fn blank_circuit(&self, circuit_type: &CircuitType) -> Result<TemplateCircuit, ZkProofError> {
+        let blank = match circuit_type {
+            CircuitType::Custom(circuit_id) => self
+                .storage
+                .get_custom_circuit_definition(circuit_id)?
+                .map(|definition| TemplateCircuit::blank_for(definition.template)),
+            _ => TemplateCircuit::blank(circuit_type),
+        };
+        blank.ok_or_else(|| {
+            ZkProofError::InvalidCircuit(format!("{circuit_type:?} has no proving circuit"))
+        })
     }
 
-    pub fn add_custom_circuit_template(
-        &mut self,
-        template: CircuitTemplate,
-    ) -> Result<(), ZkProofError> {
-        self.circuit_templates
-            .insert(template.template_id.clone(), template);
-        Ok(())
+    fn activate_setup(
+        &self,
+        circuit_type: &CircuitType,
+        keys: SetupKeys,
+        source: SetupSource,
+    ) -> Result<ZkSetupArtifact, ZkProofError> {
+        let artifact_id = zk_circuits::verifying_key_id(&keys.verifying_key);
+        for mut retired in self.storage.list_zk_setup_artifacts()? {
+            if retired.active
+                && retired.circuit_type == *circuit_type
+                && retired.artifact_id != artifact_id
+            {
+                retired.active = false;
+                self.storage.store_zk_setup_artifact(&retired)?;
+            }
+        }
+        let artifact = ZkSetupArtifact {
+            artifact_id,
+            circuit_type: circuit_type.clone(),
+            circuit_version: CIRCUIT_VERSION.to_string(),
+            proving_key: keys.proving_key,
+            verifying_key: keys.verifying_key,
+            source,
+            active: true,
+            created_at: Utc::now(),
+        };
+        self.storage.store_zk_setup_artifact(&artifact)?;
+        Ok(artifact)
     }
 
     // ============================================================================
@@ -364,7 +1825,7 @@ impl<S: StorageBackend> ZkProofEngine<S> {
             description:
                 "Prove organic certification status without revealing sensitive farming data"
                     .to_string(),
-            version: "1.0.0".to_string(),
+            version: "2.0.0".to_string(),
             required_inputs: vec![
                 CircuitInput {
                     name: "certification_number".to_string(),
@@ -373,6 +1834,13 @@ impl<S: StorageBackend> ZkProofEngine<S> {
                     is_public: false,
                     constraints: Some("USDA_ORGANIC_FORMAT".to_string()),
                 },
+                CircuitInput {
+                    name: "valid_until".to_string(),
+                    input_type: "date".to_string(),
+                    description: "Last day the certification is valid (YYYY-MM-DD)".to_string(),
+                    is_public: false,
+                    constraints: Some("NOT_BEFORE_PROOF_DATE".to_string()),
+                },
                 CircuitInput {
                     name: "certification_body".to_string(),
                     input_type: "string".to_string(),
@@ -406,7 +1874,7 @@ impl<S: StorageBackend> ZkProofEngine<S> {
             description:
                 "Prove pesticide levels are below threshold without revealing exact values"
                     .to_string(),
-            version: "1.0.0".to_string(),
+            version: "2.0.0".to_string(),
             required_inputs: vec![
                 CircuitInput {
                     name: "pesticide_levels".to_string(),
@@ -422,6 +1890,13 @@ impl<S: StorageBackend> ZkProofEngine<S> {
                     is_public: true,
                     constraints: None,
                 },
+                CircuitInput {
+                    name: "threshold_ppm".to_string(),
+                    input_type: "number".to_string(),
+                    description: "Maximum residue level of the standard in PPM".to_string(),
+                    is_public: true,
+                    constraints: Some("NON_NEGATIVE".to_string()),
+                },
             ],
             public_parameters: vec!["item_dfid".to_string(), "threshold_standard".to_string()],
             verification_constraints: vec![
@@ -447,7 +1922,7 @@ impl<S: StorageBackend> ZkProofEngine<S> {
             name: "Quality Grade Verification".to_string(),
             description: "Prove product meets quality grade without revealing specific metrics"
                 .to_string(),
-            version: "1.0.0".to_string(),
+            version: "2.0.0".to_string(),
             required_inputs: vec![
                 CircuitInput {
                     name: "quality_metrics".to_string(),
@@ -463,6 +1938,13 @@ impl<S: StorageBackend> ZkProofEngine<S> {
                     is_public: true,
                     constraints: None,
                 },
+                CircuitInput {
+                    name: "minimum_score".to_string(),
+                    input_type: "number".to_string(),
+                    description: "Score every metric must reach for the grade".to_string(),
+                    is_public: true,
+                    constraints: Some("NON_NEGATIVE".to_string()),
+                },
             ],
             public_parameters: vec!["item_dfid".to_string(), "grading_standard".to_string()],
             verification_constraints: vec!["meets_grade_requirements".to_string()],
@@ -474,20 +1956,113 @@ impl<S: StorageBackend> ZkProofEngine<S> {
             },
         };
 
-        self.circuit_templates
-            .insert(organic_template.template_id.clone(), organic_template);
-        self.circuit_templates
-            .insert(pesticide_template.template_id.clone(), pesticide_template);
-        self.circuit_templates
-            .insert(quality_template.template_id.clone(), quality_template);
+        // Cold-Chain Compliance Template
+        let cold_chain_template = CircuitTemplate {
+            template_id: "cold_chain_compliance_v1".to_string(),
+            circuit_type: CircuitType::ColdChainCompliance,
+            name: "Cold-Chain Compliance".to_string(),
+            description:
+                "Prove temperatures stayed within the required range without revealing the readings"
+                    .to_string(),
+            version: "1.0.0".to_string(),
+            required_inputs: vec![
+                CircuitInput::private(
+                    "temperature_readings",
+                    "array",
+                    "Logged temperatures in °C",
+                    Some("WITHIN_RANGE"),
+                ),
+                CircuitInput::public(
+                    "temperature_standard",
+                    "string",
+                    "Cold-chain standard applied",
+                    None,
+                ),
+                CircuitInput::public(
+                    "min_temperature_c",
+                    "number",
+                    "Lowest temperature allowed in °C",
+                    None,
+                ),
+                CircuitInput::public(
+                    "max_temperature_c",
+                    "number",
+                    "Highest temperature allowed in °C",
+                    Some("NOT_BELOW_MIN"),
+                ),
+            ],
+            public_parameters: vec!["item_dfid".to_string(), "temperature_standard".to_string()],
+            verification_constraints: vec!["within_temperature_range".to_string()],
+            agricultural_context: AgriculturalContext::new(
+                "cold_chain",
+                &["HACCP", "EU_GDP", "FSMA"],
+                &["meat", "dairy", "fruits", "vegetables"],
+                &["FDA", "EFSA"],
+            ),
+        };
+
+        // Carbon Footprint Threshold Template
+        let carbon_template = CircuitTemplate {
+            template_id: "carbon_footprint_threshold_v1".to_string(),
+            circuit_type: CircuitType::CarbonFootprintThreshold,
+            name: "Carbon Footprint Threshold".to_string(),
+            description:
+                "Prove total emissions stay under a limit without revealing the emission sources"
+                    .to_string(),
+            version: "1.0.0".to_string(),
+            required_inputs: vec![
+                CircuitInput::private(
+                    "emission_sources",
+                    "object",
+                    "Emissions per source (fertilizer, fuel, transport, ...) in kg CO2e",
+                    Some("NON_NEGATIVE"),
+                ),
+                CircuitInput::public(
+                    "footprint_standard",
+                    "string",
+                    "Carbon accounting standard applied",
+                    None,
+                ),
+                CircuitInput::public(
+                    "max_kg_co2e",
+                    "number",
+                    "Largest total footprint allowed in kg CO2e",
+                    Some("NON_NEGATIVE"),
+                ),
+            ],
+            public_parameters: vec!["item_dfid".to_string(), "footprint_standard".to_string()],
+            verification_constraints: vec!["total_below_threshold".to_string()],
+            agricultural_context: AgriculturalContext::new(
+                "carbon",
+                &["GHG_PROTOCOL", "ISO_14067", "PAS_2050"],
+                &["all"],
+                &["Carbon_Trust", "Verra"],
+            ),
+        };
+
+        for template in [
+            organic_template,
+            pesticide_template,
+            quality_template,
+            cold_chain_template,
+            carbon_template,
+        ] {
+            self.circuit_templates
+                .insert(template.template_id.clone(), template);
+        }
     }
 
     fn validate_proof_inputs(
         &self,
         circuit_type: &CircuitType,
+        custom: Option<&CustomCircuitDefinition>,
         public_inputs: &HashMap<String, serde_json::Value>,
         private_inputs: &HashMap<String, serde_json::Value>,
     ) -> Result<(), ZkProofError> {
+        if let Some(definition) = custom {
+            return definition.validate_inputs(public_inputs, private_inputs);
+        }
+
         // Find matching template
         let template = self
             .circuit_templates
@@ -515,22 +2090,307 @@ impl<S: StorageBackend> ZkProofEngine<S> {
         Ok(())
     }
 
-    fn generate_proof_data(
+    /// The template's circuit with its witness. Adds the public values the
+    /// engine fixes (the proof day of a certification) to `public_inputs`.
+    fn build_circuit(
         &self,
         circuit_type: &CircuitType,
-        public_inputs: &HashMap<String, serde_json::Value>,
+        custom: Option<&CustomCircuitDefinition>,
+        public_inputs: &mut HashMap<String, serde_json::Value>,
         private_inputs: &HashMap<String, serde_json::Value>,
-    ) -> Result<Vec<u8>, ZkProofError> {
-        // Simplified proof generation - in real implementation would use actual ZK library
-        // like arkworks, bellman, or similar
-        let proof_data = format!(
-            "PROOF_{}_{}_{}",
-            circuit_type_to_string(circuit_type),
-            public_inputs.len(),
-            private_inputs.len()
+        now: DateTime<Utc>,
+    ) -> Result<TemplateCircuit, ZkProofError> {
+        // A salt of the prover's choosing lets them open the commitment later
+        let salt = match private_inputs.get("commitment_salt") {
+            Some(salt) => zk_circuits::field_from_str(str_input(salt, "commitment_salt")?),
+            None => zk_circuits::random_field(),
+        };
+        if let Some(definition) = custom {
+            return definition.circuit(public_inputs, private_inputs, salt);
+        }
+
+        match circuit_type {
+            CircuitType::OrganicCertification => {
+                let certificate = str_input(
+                    &private_inputs["certification_number"],
+                    "certification_number",
+                )?;
+                let body = zk_circuits::field_from_str(str_input(
+                    &public_inputs["certification_body"],
+                    "certification_body",
+                )?);
+                let valid_until = str_input(&private_inputs["valid_until"], "valid_until")?;
+                let valid_until_day = NaiveDate::parse_from_str(valid_until, "%Y-%m-%d")
+                    .ok()
+                    .and_then(day_number)
+                    .ok_or_else(|| {
+                        ZkProofError::InvalidInput(
+                            "valid_until must be a YYYY-MM-DD date".to_string(),
+                        )
+                    })?;
+                let as_of_day = day_number(now.date_naive()).unwrap_or_default();
+                if valid_until_day < as_of_day {
+                    return Err(ZkProofError::InvalidInput(
+                        "Certification is no longer valid".to_string(),
+                    ));
+                }
+                public_inputs.insert("as_of_day".to_string(), serde_json::json!(as_of_day));
+                Ok(TemplateCircuit::Certification(CertificationCircuit::new(
+                    zk_circuits::field_from_str(certificate),
+                    valid_until_day,
+                    salt,
+                    body,
+                    as_of_day,
+                )))
+            }
+            CircuitType::PesticideThreshold => {
+                let standard =
+                    str_input(&public_inputs["threshold_standard"], "threshold_standard")?;
+                let threshold =
+                    fixed_point_input(&public_inputs["threshold_ppm"], "threshold_ppm")?;
+                let levels = match &private_inputs["pesticide_levels"] {
+                    serde_json::Value::Array(levels) => levels
+                        .iter()
+                        .map(|level| fixed_point_input(level, "pesticide_levels"))
+                        .collect::<Result<Vec<_>, _>>()?,
+                    _ => {
+                        return Err(ZkProofError::InvalidInput(
+                            "pesticide_levels must be an array of numbers".to_string(),
+                        ))
+                    }
+                };
+                check_value_count(levels.len(), "pesticide_levels")?;
+                if levels.iter().any(|level| *level > threshold) {
+                    return Err(ZkProofError::InvalidInput(
+                        "A pesticide level exceeds the threshold".to_string(),
+                    ));
+                }
+                Ok(TemplateCircuit::Threshold(ThresholdCircuit::new(
+                    BoundKind::AtMost,
+                    &levels,
+                    salt,
+                    zk_circuits::field_from_str(standard),
+                    threshold,
+                )))
+            }
+            CircuitType::QualityGrade => {
+                let standard = str_input(&public_inputs["grading_standard"], "grading_standard")?;
+                let minimum = fixed_point_input(&public_inputs["minimum_score"], "minimum_score")?;
+                let metrics = match &private_inputs["quality_metrics"] {
+                    serde_json::Value::Object(metrics) => metrics
+                        .values()
+                        .map(|metric| fixed_point_input(metric, "quality_metrics"))
+                        .collect::<Result<Vec<_>, _>>()?,
+                    _ => {
+                        return Err(ZkProofError::InvalidInput(
+                            "quality_metrics must be an object of numbers".to_string(),
+                        ))
+                    }
+                };
+                check_value_count(metrics.len(), "quality_metrics")?;
+                if metrics.iter().any(|metric| *metric < minimum) {
+                    return Err(ZkProofError::InvalidInput(
+                        "A quality metric is below the minimum score".to_string(),
+                    ));
+                }
+                Ok(TemplateCircuit::Threshold(ThresholdCircuit::new(
+                    BoundKind::AtLeast,
+                    &metrics,
+                    salt,
+                    zk_circuits::field_from_str(standard),
+                    minimum,
+                )))
+            }
+            CircuitType::ColdChainCompliance => {
+                let standard = str_input(
+                    &public_inputs["temperature_standard"],
+                    "temperature_standard",
+                )?;
+                let min =
+                    temperature_input(&public_inputs["min_temperature_c"], "min_temperature_c")?;
+                let max =
+                    temperature_input(&public_inputs["max_temperature_c"], "max_temperature_c")?;
+                if min > max {
+                    return Err(ZkProofError::InvalidInput(
+                        "min_temperature_c must not exceed max_temperature_c".to_string(),
+                    ));
+                }
+                let readings = match &private_inputs["temperature_readings"] {
+                    serde_json::Value::Array(readings) => readings
+                        .iter()
+                        .map(|reading| temperature_input(reading, "temperature_readings"))
+                        .collect::<Result<Vec<_>, _>>()?,
+                    _ => {
+                        return Err(ZkProofError::InvalidInput(
+                            "temperature_readings must be an array of numbers".to_string(),
+                        ))
+                    }
+                };
+                check_value_count(readings.len(), "temperature_readings")?;
+                if readings
+                    .iter()
+                    .any(|reading| *reading < min || *reading > max)
+                {
+                    return Err(ZkProofError::InvalidInput(
+                        "A temperature reading is outside the allowed range".to_string(),
+                    ));
+                }
+                Ok(TemplateCircuit::Range(RangeCircuit::new(
+                    &readings,
+                    salt,
+                    zk_circuits::field_from_str(standard),
+                    min,
+                    max,
+                )))
+            }
+            CircuitType::CarbonFootprintThreshold => {
+                let standard =
+                    str_input(&public_inputs["footprint_standard"], "footprint_standard")?;
+                let limit = fixed_point_input(&public_inputs["max_kg_co2e"], "max_kg_co2e")?;
+                let emissions = match &private_inputs["emission_sources"] {
+                    serde_json::Value::Object(sources) => sources
+                        .values()
+                        .map(|emission| fixed_point_input(emission, "emission_sources"))
+                        .collect::<Result<Vec<_>, _>>()?,
+                    _ => {
+                        return Err(ZkProofError::InvalidInput(
+                            "emission_sources must be an object of numbers".to_string(),
+                        ))
+                    }
+                };
+                check_value_count(emissions.len(), "emission_sources")?;
+                if emissions.iter().sum::<u64>() > limit {
+                    return Err(ZkProofError::InvalidInput(
+                        "Total emissions exceed the footprint limit".to_string(),
+                    ));
+                }
+                Ok(TemplateCircuit::Threshold(ThresholdCircuit::new(
+                    BoundKind::TotalAtMost,
+                    &emissions,
+                    salt,
+                    zk_circuits::field_from_str(standard),
+                    limit,
+                )))
+            }
+            other => Err(ZkProofError::InvalidCircuit(format!(
+                "{other:?} has no proving circuit"
+            ))),
+        }
+    }
+
+    fn workspace_of(&self, user_id: &str) -> Result<Option<String>, ZkProofError> {
+        Ok(self
+            .storage
+            .get_user_account(user_id)?
+            .and_then(|account| account.workspace_id))
+    }
+
+    fn require_custom_circuit(
+        &self,
+        circuit_id: &str,
+    ) -> Result<CustomCircuitDefinition, ZkProofError> {
+        self.storage
+            .get_custom_circuit_definition(circuit_id)?
+            .ok_or_else(|| {
+                ZkProofError::InvalidCircuit(format!("Unknown custom circuit {circuit_id}"))
+            })
+    }
+
+    fn custom_circuit_of(
+        &self,
+        circuit_type: &CircuitType,
+    ) -> Result<Option<CustomCircuitDefinition>, ZkProofError> {
+        match circuit_type {
+            CircuitType::Custom(circuit_id) => {
+                Ok(self.storage.get_custom_circuit_definition(circuit_id)?)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Register a circuit for the user's workspace. Custom circuits are part
+    /// of the Enterprise tier and need the user to be in a workspace.
+    pub fn register_custom_circuit(
+        &self,
+        user_id: &str,
+        input: CustomCircuitInput,
+    ) -> Result<CustomCircuitDefinition, ZkProofError> {
+        let account = self
+            .storage
+            .get_user_account(user_id)?
+            .ok_or_else(|| ZkProofError::PermissionDenied(format!("Unknown user {user_id}")))?;
+        if !matches!(account.tier, UserTier::Enterprise | UserTier::Admin) {
+            return Err(ZkProofError::PermissionDenied(
+                "Custom circuits need an Enterprise workspace".to_string(),
+            ));
+        }
+        let workspace_id = account.workspace_id.ok_or_else(|| {
+            ZkProofError::PermissionDenied(
+                "Custom circuits belong to a workspace; join one first".to_string(),
+            )
+        })?;
+
+        let definition = CustomCircuitDefinition {
+            circuit_id: input.circuit_id,
+            workspace_id,
+            name: input.name.trim().to_string(),
+            description: input.description,
+            template: input.template,
+            parameters: input.parameters,
+            input_schema: input.input_schema,
+            created_by: user_id.to_string(),
+            created_at: Utc::now(),
+        };
+        definition.validate()?;
+        let taken = self.circuit_templates.contains_key(&definition.circuit_id)
+            || self
+                .storage
+                .get_custom_circuit_definition(&definition.circuit_id)?
+                .is_some();
+        if taken {
+            return Err(ZkProofError::InvalidCircuit(format!(
+                "Circuit {} already exists",
+                definition.circuit_id
+            )));
+        }
+        self.storage.store_custom_circuit_definition(&definition)?;
+        tracing::info!(
+            "🧩 Custom circuit {} registered for workspace {}",
+            definition.circuit_id,
+            definition.workspace_id
         );
+        Ok(definition)
+    }
+
+    /// Custom circuits of the user's workspace
+    pub fn list_custom_circuits(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<CustomCircuitDefinition>, ZkProofError> {
+        let Some(workspace_id) = self.workspace_of(user_id)? else {
+            return Ok(Vec::new());
+        };
+        let mut definitions: Vec<_> = self
+            .storage
+            .list_custom_circuit_definitions()?
+            .into_iter()
+            .filter(|definition| definition.workspace_id == workspace_id)
+            .collect();
+        definitions.sort_by(|a, b| a.circuit_id.cmp(&b.circuit_id));
+        Ok(definitions)
+    }
 
-        Ok(proof_data.into_bytes())
+    /// A custom circuit, if it belongs to the user's workspace
+    pub fn get_custom_circuit(
+        &self,
+        user_id: &str,
+        circuit_id: &str,
+    ) -> Result<Option<CustomCircuitDefinition>, ZkProofError> {
+        let workspace_id = self.workspace_of(user_id)?;
+        Ok(self
+            .storage
+            .get_custom_circuit_definition(circuit_id)?
+            .filter(|definition| workspace_id.as_ref() == Some(&definition.workspace_id)))
     }
 
     fn hash_private_inputs(&self, private_inputs: &HashMap<String, serde_json::Value>) -> String {
@@ -552,6 +2412,8 @@ impl<S: StorageBackend> ZkProofEngine<S> {
             CircuitType::QualityGrade => 720,      // 30 days
             CircuitType::PesticideThreshold => 2160, // 90 days
             CircuitType::OwnershipProof => 8760,   // 1 year
+            CircuitType::ColdChainCompliance => 720, // 30 days, about one shipment
+            CircuitType::CarbonFootprintThreshold => 8760, // 1 year, one reporting period
             CircuitType::Custom(_) => 720,         // 30 days default
         };
 
@@ -559,28 +2421,208 @@ impl<S: StorageBackend> ZkProofEngine<S> {
     }
 
     fn perform_verification(&self, proof: &ZkProof) -> Result<bool, ZkProofError> {
-        // Simplified verification - in real implementation would use ZK verification
-        // This would involve checking the proof against the circuit and public inputs
-
-        // Basic checks
-        if proof.proof_data.is_empty() {
+        let Some(artifact_id) = &proof.setup_artifact_id else {
+            // Placeholder proofs from before real proving
             return Ok(false);
-        }
+        };
+        let artifact = self
+            .storage
+            .get_zk_setup_artifact(artifact_id)?
+            .filter(|artifact| artifact.circuit_type == proof.circuit_type)
+            .ok_or_else(|| {
+                ZkProofError::VerificationError(format!("Unknown setup artifact {artifact_id}"))
+            })?;
+        let custom = self.custom_circuit_of(&proof.circuit_type)?;
+        let Some(public_inputs) =
+            circuit_public_inputs(&proof.circuit_type, custom.as_ref(), &proof.public_inputs)
+        else {
+            return Ok(false);
+        };
 
-        // Check if we have a template for this circuit type
-        let has_template = self
-            .circuit_templates
-            .values()
-            .any(|t| t.circuit_type == proof.circuit_type);
+        zk_circuits::verify(&artifact.verifying_key, &public_inputs, &proof.proof_data)
+    }
+}
 
-        if !has_template {
-            return Err(ZkProofError::InvalidCircuit(
-                "Unknown circuit type".to_string(),
+// ============================================================================
+// REVOCATION REGISTRY
+// ============================================================================
+
+impl<S: StorageBackend + Clone + 'static> ZkProofEngine<S> {
+    /// Revoke a proof on behalf of its prover's workspace. Circuits holding
+    /// the proven item are notified through their `proof_revoked` webhooks.
+    /// Revoking twice returns the original revocation.
+    pub async fn revoke_proof(
+        &self,
+        proof_id: &Uuid,
+        revoked_by: &str,
+        reason: &str,
+    ) -> Result<ProofRevocation, ZkProofError> {
+        let mut proof = self
+            .storage
+            .get_zk_proof(proof_id)?
+            .ok_or_else(|| ZkProofError::InvalidInput(format!("Proof {proof_id} not found")))?;
+        let workspace_id = self.workspace_of(&proof.prover_id)?;
+        let allowed = revoked_by == proof.prover_id
+            || (workspace_id.is_some() && self.workspace_of(revoked_by)? == workspace_id);
+        if !allowed {
+            return Err(ZkProofError::PermissionDenied(
+                "Only the prover's workspace can revoke a proof".to_string(),
+            ));
+        }
+        if let Some(existing) = self.storage.get_zk_proof_revocation(proof_id)? {
+            return Ok(existing);
+        }
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(ZkProofError::InvalidInput(
+                "Give a reason for the revocation".to_string(),
             ));
         }
 
-        // Simplified: assume proof is valid if it has proper structure
-        Ok(true)
+        let revocation = ProofRevocation {
+            proof_id: *proof_id,
+            prover_id: proof.prover_id.clone(),
+            workspace_id,
+            revoked_by: revoked_by.to_string(),
+            reason: reason.to_string(),
+            revoked_at: Utc::now(),
+        };
+        self.storage.store_zk_proof_revocation(&revocation)?;
+        proof.status = ProofStatus::Revoked;
+        self.storage.update_zk_proof(&proof)?;
+        tracing::info!("🚫 ZK proof {} revoked by {}", proof_id, revoked_by);
+
+        self.notify_revocation(&proof, &revocation).await;
+        Ok(revocation)
+    }
+
+    /// The revocation registry of the user's workspace (or of the user alone
+    /// without one), newest first
+    pub fn list_revocations(&self, user_id: &str) -> Result<Vec<ProofRevocation>, ZkProofError> {
+        let workspace_id = self.workspace_of(user_id)?;
+        let mut revocations: Vec<_> = self
+            .storage
+            .list_zk_proof_revocations()?
+            .into_iter()
+            .filter(|revocation| match &workspace_id {
+                Some(workspace_id) => revocation.workspace_id.as_ref() == Some(workspace_id),
+                None => revocation.prover_id == user_id,
+            })
+            .collect();
+        revocations.sort_by_key(|revocation| std::cmp::Reverse(revocation.revoked_at));
+        Ok(revocations)
+    }
+
+    pub fn get_revocation(&self, proof_id: &Uuid) -> Result<Option<ProofRevocation>, ZkProofError> {
+        Ok(self.storage.get_zk_proof_revocation(proof_id)?)
+    }
+
+    /// Webhook failures are logged; the revocation itself stands
+    async fn notify_revocation(&self, proof: &ZkProof, revocation: &ProofRevocation) {
+        let Some(dfid) = proof
+            .public_inputs
+            .get("item_dfid")
+            .and_then(|v| v.as_str())
+        else {
+            return;
+        };
+        self.notify_circuits(
+            dfid,
+            crate::types::PostActionTrigger::ProofRevoked,
+            revocation.proof_id.to_string(),
+            &revocation.revoked_by,
+            "revoked",
+            revocation.revoked_at,
+        )
+        .await;
+    }
+
+    /// Tell circuits holding the job's item that its proof is ready (or
+    /// failed); webhook failures are logged
+    pub async fn notify_proof_job(&self, job: &ProofJob) {
+        let Some(dfid) = &job.item_dfid else {
+            return;
+        };
+        let status = if job.status == ProofStatus::Verified {
+            "verified"
+        } else {
+            "failed"
+        };
+        self.notify_circuits(
+            dfid,
+            crate::types::PostActionTrigger::ProofGenerated,
+            job.proof_id.unwrap_or(job.job_id).to_string(),
+            &job.prover_id,
+            status,
+            job.completed_at.unwrap_or_else(Utc::now),
+        )
+        .await;
+    }
+
+    /// Trigger the webhooks of circuits subscribed to `trigger` that hold the
+    /// item `dfid`
+    async fn notify_circuits(
+        &self,
+        dfid: &str,
+        trigger: crate::types::PostActionTrigger,
+        operation_id: String,
+        actor: &str,
+        status: &str,
+        timestamp: DateTime<Utc>,
+    ) {
+        let circuits = match self.storage.list_circuits() {
+            Ok(circuits) => circuits,
+            Err(e) => {
+                tracing::warn!("Failed to list circuits for {}: {}", trigger.as_str(), e);
+                return;
+            }
+        };
+
+        let mut webhooks = crate::webhook_engine::WebhookEngine::new(self.storage.clone());
+        for circuit in circuits {
+            let subscribed = circuit
+                .post_action_settings
+                .as_ref()
+                .is_some_and(|settings| {
+                    settings.enabled && settings.trigger_events.contains(&trigger)
+                });
+            let holds_item = subscribed
+                && self
+                    .storage
+                    .get_circuit_items(&circuit.circuit_id)
+                    .is_ok_and(|items| items.iter().any(|item| item.dfid == dfid));
+            if !holds_item {
+                continue;
+            }
+
+            let payload = crate::types::WebhookPayload {
+                event_type: trigger.as_str().to_string(),
+                circuit_id: circuit.circuit_id.to_string(),
+                circuit_name: circuit.name.clone(),
+                timestamp,
+                item: crate::types::WebhookItemData {
+                    dfid: dfid.to_string(),
+                    local_id: None,
+                    identifiers: Vec::new(),
+                    pushed_by: actor.to_string(),
+                },
+                storage: None,
+                operation_id: operation_id.clone(),
+                status: status.to_string(),
+                priority: Default::default(),
+            };
+            if let Err(e) = webhooks
+                .trigger_webhooks(&circuit.circuit_id, trigger, payload, None)
+                .await
+            {
+                tracing::warn!(
+                    "Failed to trigger {} webhooks for circuit {}: {}",
+                    trigger.as_str(),
+                    circuit.circuit_id,
+                    e
+                );
+            }
+        }
     }
 }
 
@@ -588,13 +2630,704 @@ impl<S: StorageBackend> ZkProofEngine<S> {
 // UTILITY FUNCTIONS
 // ============================================================================
 
-fn circuit_type_to_string(circuit_type: &CircuitType) -> String {
+fn str_input<'a>(value: &'a serde_json::Value, name: &str) -> Result<&'a str, ZkProofError> {
+    value
+        .as_str()
+        .ok_or_else(|| ZkProofError::InvalidInput(format!("{name} must be a string")))
+}
+
+fn fixed_point_input(value: &serde_json::Value, name: &str) -> Result<u64, ZkProofError> {
+    value
+        .as_f64()
+        .and_then(zk_circuits::to_fixed_point)
+        .ok_or_else(|| ZkProofError::InvalidInput(format!("{name} must hold non-negative numbers")))
+}
+
+fn number_input(value: &serde_json::Value, name: &str) -> Result<f64, ZkProofError> {
+    value
+        .as_f64()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| ZkProofError::InvalidInput(format!("{name} must hold numbers")))
+}
+
+fn temperature_input(value: &serde_json::Value, name: &str) -> Result<u64, ZkProofError> {
+    value
+        .as_f64()
+        .and_then(zk_circuits::celsius_to_fixed_point)
+        .ok_or_else(|| ZkProofError::InvalidInput(format!("{name} must hold temperatures in °C")))
+}
+
+fn check_value_count(count: usize, name: &str) -> Result<(), ZkProofError> {
+    if count == 0 || count > MAX_COMMITTED_VALUES {
+        return Err(ZkProofError::InvalidInput(format!(
+            "{name} must hold between 1 and {MAX_COMMITTED_VALUES} values"
+        )));
+    }
+    Ok(())
+}
+
+fn day_number(date: NaiveDate) -> Option<u64> {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1)?;
+    u64::try_from((date - epoch).num_days()).ok()
+}
+
+/// Field elements the verifier checks a proof against, rebuilt from its
+/// stored public inputs; `None` when they are missing or malformed
+fn circuit_public_inputs(
+    circuit_type: &CircuitType,
+    custom: Option<&CustomCircuitDefinition>,
+    public_inputs: &HashMap<String, serde_json::Value>,
+) -> Option<Vec<Fr>> {
+    let text = |name: &str| public_inputs.get(name)?.as_str();
+    let fixed_point = |name: &str| zk_circuits::to_fixed_point(public_inputs.get(name)?.as_f64()?);
+    let temperature =
+        |name: &str| zk_circuits::celsius_to_fixed_point(public_inputs.get(name)?.as_f64()?);
+    let commitment = zk_circuits::field_from_hex(text("commitment")?)?;
     match circuit_type {
-        CircuitType::OrganicCertification => "ORGANIC".to_string(),
-        CircuitType::PesticideThreshold => "PESTICIDE".to_string(),
-        CircuitType::QualityGrade => "QUALITY".to_string(),
-        CircuitType::OwnershipProof => "OWNERSHIP".to_string(),
-        CircuitType::TimestampFreshness => "FRESHNESS".to_string(),
-        CircuitType::Custom(name) => format!("CUSTOM_{}", name.to_uppercase()),
+        CircuitType::OrganicCertification => Some(CertificationCircuit::public_inputs(
+            zk_circuits::field_from_str(text("certification_body")?),
+            public_inputs.get("as_of_day")?.as_u64()?,
+            commitment,
+        )),
+        CircuitType::PesticideThreshold => Some(ThresholdCircuit::public_inputs(
+            zk_circuits::field_from_str(text("threshold_standard")?),
+            fixed_point("threshold_ppm")?,
+            commitment,
+        )),
+        CircuitType::QualityGrade => Some(ThresholdCircuit::public_inputs(
+            zk_circuits::field_from_str(text("grading_standard")?),
+            fixed_point("minimum_score")?,
+            commitment,
+        )),
+        CircuitType::ColdChainCompliance => Some(RangeCircuit::public_inputs(
+            zk_circuits::field_from_str(text("temperature_standard")?),
+            temperature("min_temperature_c")?,
+            temperature("max_temperature_c")?,
+            commitment,
+        )),
+        CircuitType::CarbonFootprintThreshold => Some(ThresholdCircuit::public_inputs(
+            zk_circuits::field_from_str(text("footprint_standard")?),
+            fixed_point("max_kg_co2e")?,
+            commitment,
+        )),
+        CircuitType::Custom(_) => custom?.public_field_inputs(public_inputs, commitment),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    type Storage = Arc<Mutex<InMemoryStorage>>;
+
+    fn inputs(pairs: &[(&str, serde_json::Value)]) -> HashMap<String, serde_json::Value> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_batch_verification_isolates_invalid_proofs() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let engine = ZkProofEngine::new(Arc::clone(&storage));
+        let proof_ids: Vec<Uuid> = [json!([0.1, 0.2]), json!([0.3])]
+            .into_iter()
+            .map(|levels| {
+                engine
+                    .submit_proof(
+                        CircuitType::PesticideThreshold,
+                        "lab".to_string(),
+                        inputs(&[
+                            ("threshold_standard", json!("Codex")),
+                            ("threshold_ppm", json!(0.4)),
+                        ]),
+                        inputs(&[("pesticide_levels", levels)]),
+                        None,
+                    )
+                    .unwrap()
+            })
+            .collect();
+
+        let report = engine
+            .verify_proofs_batch(&proof_ids, "auditor".to_string())
+            .unwrap();
+        assert_eq!((report.valid, report.batched_proofs), (2, 2));
+
+        // A tampered proof fails the combined check; the rest still verify
+        let mut tampered = engine.get_proof(&proof_ids[1]).unwrap().unwrap();
+        tampered
+            .public_inputs
+            .insert("threshold_standard".to_string(), json!("EU_MRL"));
+        storage.update_zk_proof(&tampered).unwrap();
+        let missing = Uuid::new_v4();
+        let report = engine
+            .verify_proofs_batch(
+                &[proof_ids[0], proof_ids[1], missing],
+                "auditor".to_string(),
+            )
+            .unwrap();
+        assert_eq!(
+            (
+                report.valid,
+                report.invalid,
+                report.errors,
+                report.batched_proofs
+            ),
+            (1, 1, 1, 0)
+        );
+        assert!(report.results[0].is_valid);
+        assert_eq!(report.results[1].status, Some(ProofStatus::Failed));
+        assert_eq!(report.results[2].proof_id, missing);
+        assert!(report.results[2].error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_anchoring_records_digest_once() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let engine = ZkProofEngine::new(Arc::clone(&storage));
+        let mut proof = ZkProof {
+            proof_id: Uuid::new_v4(),
+            circuit_type: CircuitType::PesticideThreshold,
+            item_id: None,
+            item_dfid: None,
+            prover_id: "lab".to_string(),
+            proof_data: vec![7; 128],
+            public_inputs: HashMap::new(),
+            private_inputs_hash: String::new(),
+            status: ProofStatus::Pending,
+            created_at: Utc::now(),
+            verified_at: None,
+            expires_at: None,
+            valid_from: None,
+            verification_result: None,
+            setup_artifact_id: None,
+            anchor: None,
+        };
+        storage.store_zk_proof(&proof).unwrap();
+        assert!(matches!(
+            engine.anchor_proof(proof.proof_id).await,
+            Err(ZkProofError::InvalidInput(_))
+        ));
+
+        proof.status = ProofStatus::Verified;
+        storage.update_zk_proof(&proof).unwrap();
+        assert!(matches!(
+            engine.anchor_proof(proof.proof_id).await,
+            Err(ZkProofError::AnchoringError(_))
+        ));
+
+        // The digest covers the proof bytes, so a third party can recompute it
+        let digest = anchor_digest(&proof);
+        let mut other = proof.clone();
+        other.proof_data[0] = 8;
+        assert_ne!(digest, anchor_digest(&other));
+
+        let anchor = ProofAnchor {
+            network: AnchoringNetwork::StellarTestnet,
+            ipcm_key: anchor_key(&proof.proof_id),
+            digest,
+            tx_hash: "abc123".to_string(),
+            anchored_at: Utc::now(),
+        };
+        engine
+            .record_anchor(proof.proof_id, anchor.clone())
+            .unwrap();
+        assert_eq!(engine.anchor_proof(proof.proof_id).await.unwrap(), anchor);
+        let stored = engine.get_proof(&proof.proof_id).unwrap().unwrap();
+        assert_eq!(stored.anchor, Some(anchor));
+        assert_eq!(stored.status, ProofStatus::Verified);
+    }
+
+    #[tokio::test]
+    async fn test_revocation_is_kept_per_workspace_and_notifies_circuits() {
+        use crate::types::{
+            AccountStatus, Circuit, CircuitItem, PostActionSettings, PostActionTrigger, TierLimits,
+            UserAccount, UserTier, WebhookConfig,
+        };
+
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let engine = ZkProofEngine::new(Arc::clone(&storage));
+        for (user_id, workspace_id) in [("lab", "ws-lab"), ("lab-qa", "ws-lab"), ("rival", "ws-2")]
+        {
+            storage
+                .store_user_account(&UserAccount {
+                    user_id: user_id.to_string(),
+                    username: user_id.to_string(),
+                    email: format!("{user_id}@example.com"),
+                    password_hash: "hash".to_string(),
+                    limits: TierLimits::for_tier(&UserTier::Professional),
+                    tier: UserTier::Professional,
+                    status: AccountStatus::Active,
+                    credits: 0,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                    last_login: None,
+                    subscription: None,
+                    is_admin: false,
+                    workspace_id: Some(workspace_id.to_string()),
+                    available_adapters: None,
+                    locale: None,
+                })
+                .unwrap();
+        }
+
+        let mut circuit = Circuit::new(
+            "Organic".to_string(),
+            "Organic supply".to_string(),
+            "owner".to_string(),
+        );
+        circuit.post_action_settings = Some(PostActionSettings {
+            enabled: true,
+            webhooks: vec![WebhookConfig::new(
+                "erp".to_string(),
+                "https://erp.example.com/hook".to_string(),
+            )],
+            trigger_events: vec![PostActionTrigger::ProofRevoked],
+            ..Default::default()
+        });
+        storage.store_circuit(&circuit).unwrap();
+        storage
+            .store_circuit_item(&CircuitItem::new(
+                "DFID-2026-0001".to_string(),
+                circuit.circuit_id,
+                "owner".to_string(),
+                vec![],
+            ))
+            .unwrap();
+
+        // Windows must end after they start
+        let now = Utc::now();
+        assert!(matches!(
+            engine.submit_proof_with_validity(
+                CircuitType::OrganicCertification,
+                "lab".to_string(),
+                HashMap::new(),
+                HashMap::new(),
+                None,
+                ProofValidity {
+                    valid_from: Some(now + chrono::Duration::days(2)),
+                    expires_at: Some(now + chrono::Duration::days(1)),
+                },
+            ),
+            Err(ZkProofError::InvalidInput(_))
+        ));
+
+        let mut proof = ZkProof {
+            proof_id: Uuid::new_v4(),
+            circuit_type: CircuitType::OrganicCertification,
+            item_id: None,
+            item_dfid: None,
+            prover_id: "lab".to_string(),
+            proof_data: vec![7; 128],
+            public_inputs: inputs(&[("item_dfid", json!("DFID-2026-0001"))]),
+            private_inputs_hash: String::new(),
+            status: ProofStatus::Pending,
+            created_at: now,
+            verified_at: None,
+            expires_at: Some(now + chrono::Duration::days(30)),
+            valid_from: Some(now + chrono::Duration::days(1)),
+            verification_result: None,
+            setup_artifact_id: None,
+            anchor: None,
+        };
+        storage.store_zk_proof(&proof).unwrap();
+        assert!(matches!(
+            engine.verify_proof(proof.proof_id, "auditor".to_string()),
+            Err(ZkProofError::NotYetValid(_))
+        ));
+        assert_eq!(
+            proof.status_at(now + chrono::Duration::days(31)),
+            ProofStatus::Expired
+        );
+        proof.valid_from = None;
+        storage.update_zk_proof(&proof).unwrap();
+
+        // Only the prover's workspace may revoke
+        assert!(matches!(
+            engine
+                .revoke_proof(&proof.proof_id, "rival", "not theirs")
+                .await,
+            Err(ZkProofError::PermissionDenied(_))
+        ));
+        let revocation = engine
+            .revoke_proof(&proof.proof_id, "lab-qa", "certificate withdrawn")
+            .await
+            .unwrap();
+        assert_eq!(revocation.workspace_id.as_deref(), Some("ws-lab"));
+        let again = engine
+            .revoke_proof(&proof.proof_id, "lab", "duplicate")
+            .await
+            .unwrap();
+        assert_eq!(again, revocation);
+
+        assert!(matches!(
+            engine.verify_proof(proof.proof_id, "auditor".to_string()),
+            Err(ZkProofError::RevokedProof(_))
+        ));
+        assert_eq!(
+            engine.get_proof(&proof.proof_id).unwrap().unwrap().status,
+            ProofStatus::Revoked
+        );
+        assert_eq!(engine.list_revocations("lab").unwrap(), vec![revocation]);
+        assert!(engine.list_revocations("rival").unwrap().is_empty());
+
+        let deliveries = storage
+            .get_webhook_deliveries_by_circuit(&circuit.circuit_id, None)
+            .unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].trigger_event, PostActionTrigger::ProofRevoked);
+        assert_eq!(deliveries[0].payload["status"], json!("revoked"));
+    }
+
+    fn pesticide_inputs() -> HashMap<String, serde_json::Value> {
+        inputs(&[
+            ("threshold_standard", json!("EU_MRL")),
+            ("threshold_ppm", json!(0.5)),
+        ])
+    }
+
+    /// A lab's proof that every residue is within 0.5 ppm
+    fn pesticide_proof() -> (Storage, ZkProofEngine<Storage>, Uuid) {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let engine = ZkProofEngine::new(Arc::clone(&storage));
+        let proof_id = engine
+            .submit_proof(
+                CircuitType::PesticideThreshold,
+                "lab".to_string(),
+                pesticide_inputs(),
+                inputs(&[("pesticide_levels", json!([0.12, 0.5, 0.03]))]),
+                None,
+            )
+            .unwrap();
+        (storage, engine, proof_id)
+    }
+
+    #[test]
+    fn test_pesticide_threshold_proofs_verify_with_groth16() {
+        let (_, engine, proof_id) = pesticide_proof();
+
+        let result = engine.verify_proof(proof_id, "buyer".to_string()).unwrap();
+        assert!(result.is_valid);
+    }
+
+    #[test]
+    fn test_proof_does_not_carry_over_to_a_stricter_threshold() {
+        let (storage, engine, proof_id) = pesticide_proof();
+
+        let mut proof = engine.get_proof(&proof_id).unwrap().unwrap();
+        proof
+            .public_inputs
+            .insert("threshold_ppm".to_string(), json!(0.1));
+        storage.update_zk_proof(&proof).unwrap();
+        let result = engine.verify_proof(proof_id, "buyer".to_string()).unwrap();
+        assert!(!result.is_valid);
+    }
+
+    #[test]
+    fn test_levels_over_the_threshold_cannot_be_proven() {
+        let engine = ZkProofEngine::new(Arc::new(Mutex::new(InMemoryStorage::new())));
+
+        assert!(matches!(
+            engine.submit_proof(
+                CircuitType::PesticideThreshold,
+                "lab".to_string(),
+                pesticide_inputs(),
+                inputs(&[("pesticide_levels", json!([0.7]))]),
+                None,
+            ),
+            Err(ZkProofError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_setup_keys_do_not_fit_another_circuit() {
+        let (_, engine, _) = pesticide_proof();
+
+        // One setup per circuit
+        let artifacts = engine.list_setup_artifacts().unwrap();
+        assert_eq!(artifacts.len(), 1);
+        assert!(matches!(
+            engine.import_setup_artifact(
+                &CircuitType::QualityGrade,
+                artifacts[0].proving_key.clone(),
+                artifacts[0].verifying_key.clone(),
+            ),
+            Err(ZkProofError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_exported_bundle_verifies_offline() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let engine = ZkProofEngine::new(Arc::clone(&storage));
+        let proof_id = engine
+            .submit_proof(
+                CircuitType::PesticideThreshold,
+                "lab".to_string(),
+                inputs(&[
+                    ("threshold_standard", json!("EU_MRL")),
+                    ("threshold_ppm", json!(0.5)),
+                ]),
+                inputs(&[("pesticide_levels", json!([0.12, 0.5, 0.03]))]),
+                None,
+            )
+            .unwrap();
+
+        // The bundle survives a JSON round trip and verifies on its own
+        let bundle = engine.export_bundle(&proof_id).unwrap();
+        let bundle: ProofBundle =
+            serde_json::from_str(&serde_json::to_string(&bundle).unwrap()).unwrap();
+        assert!(verify_bundle(&bundle).unwrap());
+        assert_eq!(bundle.proof_id, proof_id);
+        assert_eq!(
+            bundle.circuit.as_ref().unwrap().template_id,
+            "pesticide_threshold_v1"
+        );
+
+        // A tampered public input no longer matches the proof
+        let mut stricter = bundle.clone();
+        stricter
+            .public_inputs
+            .insert("threshold_ppm".to_string(), json!(0.1));
+        assert!(!verify_bundle(&stricter).unwrap());
+
+        // An id that is not the key's own is rejected outright
+        let mut relabeled = bundle.clone();
+        relabeled.setup_artifact_id = "0".repeat(64);
+        assert!(matches!(
+            verify_bundle(&relabeled),
+            Err(ZkProofError::InvalidInput(_))
+        ));
+
+        // So is the id of another setup, even a genuine one
+        let other = engine
+            .active_setup_artifact(&CircuitType::ColdChainCompliance)
+            .unwrap();
+        assert_ne!(other.artifact_id, bundle.setup_artifact_id);
+        let mut mismatched = bundle.clone();
+        mismatched.setup_artifact_id = other.artifact_id.clone();
+        assert!(matches!(
+            verify_bundle(&mismatched),
+            Err(ZkProofError::InvalidInput(_))
+        ));
+
+        // Swapping in the other setup's key together with its id does not
+        // make the proof verify either
+        mismatched.verifying_key = BASE64.encode(&other.verifying_key);
+        assert!(!matches!(verify_bundle(&mismatched), Ok(true)));
+
+        // Bundles of an unknown format are refused
+        let mut future = bundle;
+        future.bundle_version = PROOF_BUNDLE_VERSION + 1;
+        assert!(verify_bundle(&future).is_err());
+    }
+
+    #[test]
+    fn test_cold_chain_and_carbon_footprint_proofs_verify() {
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let engine = ZkProofEngine::new(Arc::clone(&storage));
+
+        // Frozen goods: readings below zero are fine as long as they are in range
+        let cold_chain_inputs = inputs(&[
+            ("temperature_standard", json!("HACCP")),
+            ("min_temperature_c", json!(-25.0)),
+            ("max_temperature_c", json!(-18.0)),
+        ]);
+        let proof_id = engine
+            .submit_proof(
+                CircuitType::ColdChainCompliance,
+                "carrier".to_string(),
+                cold_chain_inputs.clone(),
+                inputs(&[("temperature_readings", json!([-21.4, -19.0, -18.0]))]),
+                None,
+            )
+            .unwrap();
+        assert!(
+            engine
+                .verify_proof(proof_id, "buyer".to_string())
+                .unwrap()
+                .is_valid
+        );
+        let mut proof = engine.get_proof(&proof_id).unwrap().unwrap();
+        proof
+            .public_inputs
+            .insert("max_temperature_c".to_string(), json!(-20.0));
+        storage.update_zk_proof(&proof).unwrap();
+        assert!(
+            !engine
+                .verify_proof(proof_id, "buyer".to_string())
+                .unwrap()
+                .is_valid
+        );
+        assert!(matches!(
+            engine.submit_proof(
+                CircuitType::ColdChainCompliance,
+                "carrier".to_string(),
+                cold_chain_inputs,
+                inputs(&[("temperature_readings", json!([-19.0, -12.5]))]),
+                None,
+            ),
+            Err(ZkProofError::InvalidInput(_))
+        ));
+
+        // The limit applies to the total, not to each source
+        let carbon_inputs = inputs(&[
+            ("footprint_standard", json!("ISO_14067")),
+            ("max_kg_co2e", json!(100.0)),
+        ]);
+        let proof_id = engine
+            .submit_proof(
+                CircuitType::CarbonFootprintThreshold,
+                "farm".to_string(),
+                carbon_inputs.clone(),
+                inputs(&[(
+                    "emission_sources",
+                    json!({"fertilizer": 60.0, "transport": 40.0}),
+                )]),
+                None,
+            )
+            .unwrap();
+        assert!(
+            engine
+                .verify_proof(proof_id, "buyer".to_string())
+                .unwrap()
+                .is_valid
+        );
+        assert!(matches!(
+            engine.submit_proof(
+                CircuitType::CarbonFootprintThreshold,
+                "farm".to_string(),
+                carbon_inputs,
+                inputs(&[(
+                    "emission_sources",
+                    json!({"fertilizer": 60.0, "transport": 40.5}),
+                )]),
+                None,
+            ),
+            Err(ZkProofError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_custom_circuit_registration_and_proofs() {
+        use crate::types::{AccountStatus, TierLimits, UserAccount};
+
+        let storage = Arc::new(Mutex::new(InMemoryStorage::new()));
+        let engine = ZkProofEngine::new(Arc::clone(&storage));
+        for (user_id, tier, workspace_id) in [
+            ("coop", UserTier::Enterprise, "ws-coop"),
+            ("farmer", UserTier::Professional, "ws-coop"),
+            ("rival", UserTier::Enterprise, "ws-2"),
+        ] {
+            storage
+                .store_user_account(&UserAccount {
+                    user_id: user_id.to_string(),
+                    username: user_id.to_string(),
+                    email: format!("{user_id}@example.com"),
+                    password_hash: "hash".to_string(),
+                    limits: TierLimits::for_tier(&tier),
+                    tier,
+                    status: AccountStatus::Active,
+                    credits: 0,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                    last_login: None,
+                    subscription: None,
+                    is_admin: false,
+                    workspace_id: Some(workspace_id.to_string()),
+                    available_adapters: None,
+                    locale: None,
+                })
+                .unwrap();
+        }
+        let field = |name: &str, minimum: Option<f64>, maximum: Option<f64>| CustomInputField {
+            name: name.to_string(),
+            description: String::new(),
+            minimum,
+            maximum,
+        };
+        let input = CustomCircuitInput {
+            circuit_id: "moisture-max".to_string(),
+            name: "Grain moisture".to_string(),
+            description: "Every lot is dry enough for storage".to_string(),
+            template: ConstraintTemplate::AtMost,
+            parameters: CustomCircuitParameters {
+                max_values: 4,
+                offset: 0.0,
+            },
+            input_schema: CustomInputSchema {
+                values: field("moisture_pct", Some(0.0), Some(100.0)),
+                standard: field("moisture_standard", None, None),
+                bounds: vec![field("max_moisture_pct", Some(0.0), Some(100.0))],
+            },
+        };
+
+        // Only Enterprise workspaces register circuits, and ids are unique
+        assert!(matches!(
+            engine.register_custom_circuit("farmer", input.clone()),
+            Err(ZkProofError::PermissionDenied(_))
+        ));
+        engine
+            .register_custom_circuit("coop", input.clone())
+            .unwrap();
+        assert!(matches!(
+            engine.register_custom_circuit("coop", input),
+            Err(ZkProofError::InvalidCircuit(_))
+        ));
+        assert_eq!(engine.list_custom_circuits("farmer").unwrap().len(), 1);
+        assert!(engine.list_custom_circuits("rival").unwrap().is_empty());
+
+        let circuit_type = CircuitType::Custom("moisture-max".to_string());
+        let public = inputs(&[
+            ("moisture_standard", json!("GAFTA")),
+            ("max_moisture_pct", json!(14.0)),
+        ]);
+        let submit = |prover: &str, public, private| {
+            engine.submit_proof(
+                circuit_type.clone(),
+                prover.to_string(),
+                public,
+                private,
+                None,
+            )
+        };
+        let proof_id = submit(
+            "farmer",
+            public.clone(),
+            inputs(&[("moisture_pct", json!([12.5, 13.9]))]),
+        )
+        .unwrap();
+        assert!(
+            engine
+                .verify_proof(proof_id, "buyer".to_string())
+                .unwrap()
+                .is_valid
+        );
+        let bundle = engine.export_bundle(&proof_id).unwrap();
+        assert!(bundle.custom_circuit.is_some());
+        assert!(verify_bundle(&bundle).unwrap());
+
+        // Inputs outside the schema, unmet constraints and other workspaces
+        let mut extra = public.clone();
+        extra.insert("lot".to_string(), json!("A"));
+        for (prover, public, values) in [
+            ("farmer", extra, json!([12.0])),
+            ("farmer", public.clone(), json!([101.0])),
+            ("farmer", public.clone(), json!([14.5])),
+        ] {
+            assert!(matches!(
+                submit(prover, public, inputs(&[("moisture_pct", values)])),
+                Err(ZkProofError::InvalidInput(_))
+            ));
+        }
+        assert!(matches!(
+            submit("rival", public, inputs(&[("moisture_pct", json!([12.0]))])),
+            Err(ZkProofError::PermissionDenied(_))
+        ));
     }
 }
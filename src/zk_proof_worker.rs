@@ -0,0 +1,220 @@
+//! Background generation of ZK proofs. Proving is CPU-heavy, so
+//! `POST /api/proofs/jobs` records a [`ProofJob`] and queues it here; a
+//! pool of workers proves and verifies it on blocking threads while the
+//! client polls the job.
+
+use crate::scaling_signals;
+use crate::storage::StorageBackend;
+use crate::types::WorkQueue;
+use crate::zk_proof_engine::{ProofJob, ProofValidity, ZkProofEngine, ZkProofError};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+
+/// Jobs waiting for a worker before new ones are turned away
+const DEFAULT_QUEUE_SIZE: usize = 256;
+
+/// A queued job with the inputs to prove it, which exist only in memory
+#[derive(Debug)]
+pub struct ProofJobTask {
+    pub job: ProofJob,
+    pub public_inputs: HashMap<String, serde_json::Value>,
+    pub private_inputs: HashMap<String, serde_json::Value>,
+    pub validity: ProofValidity,
+}
+
+/// Sending side of the proof generation queue
+#[derive(Clone)]
+pub struct ProofJobQueue {
+    tx: mpsc::Sender<ProofJobTask>,
+}
+
+impl ProofJobQueue {
+    /// Start `workers` workers proving jobs against `storage`. Jobs a previous
+    /// process left unfinished are failed first.
+    pub fn start<S: StorageBackend + Clone + 'static>(storage: S, workers: usize) -> Self {
+        let (tx, rx) = mpsc::channel(DEFAULT_QUEUE_SIZE);
+        match ZkProofEngine::new(storage.clone()).recover_proof_jobs() {
+            Ok(0) => {}
+            Ok(count) => {
+                tracing::warn!("⚠️  Failed {} proof job(s) interrupted by a restart", count)
+            }
+            Err(e) => tracing::warn!("⚠️  Failed to recover proof jobs: {}", e),
+        }
+
+        let rx = Arc::new(Mutex::new(rx));
+        for _ in 0..workers.max(1) {
+            tokio::spawn(proof_worker(storage.clone(), Arc::clone(&rx)));
+        }
+        Self { tx }
+    }
+
+    /// Worker count from `ZK_PROOF_WORKERS`, by default 2
+    pub fn workers_from_env() -> usize {
+        std::env::var("ZK_PROOF_WORKERS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(2)
+    }
+
+    /// Queue a task without waiting; a full queue is reported, not awaited
+    pub fn enqueue(&self, task: ProofJobTask) -> Result<(), ZkProofError> {
+        self.tx
+            .try_send(task)
+            .map_err(|_| ZkProofError::QueueFull)?;
+        scaling_signals::record_enqueued(WorkQueue::ProofGeneration, 1);
+        Ok(())
+    }
+}
+
+/// One worker of the pool: takes the next task, proves it on a blocking
+/// thread and notifies the item's circuits
+async fn proof_worker<S: StorageBackend + Clone + 'static>(
+    storage: S,
+    rx: Arc<Mutex<mpsc::Receiver<ProofJobTask>>>,
+) {
+    loop {
+        let Some(task) = rx.lock().await.recv().await else {
+            return;
+        };
+        let started = std::time::Instant::now();
+        let queued = task.job.clone();
+        let engine = ZkProofEngine::new(storage.clone());
+        let prover = engine.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            prover.run_proof_job(
+                task.job,
+                task.public_inputs,
+                task.private_inputs,
+                task.validity,
+            )
+        })
+        .await;
+
+        let job = match result {
+            Ok(Ok(job)) => job,
+            Ok(Err(e)) => {
+                tracing::warn!(
+                    "⚠️  Proof job {} could not be recorded: {}",
+                    queued.job_id,
+                    e
+                );
+                scaling_signals::record_completed(
+                    WorkQueue::ProofGeneration,
+                    started.elapsed(),
+                    false,
+                );
+                continue;
+            }
+            Err(e) => {
+                tracing::error!("❌ Proof job {} panicked: {}", queued.job_id, e);
+                match engine.fail_proof_job(queued, "Proof generation crashed") {
+                    Ok(job) => job,
+                    Err(_) => continue,
+                }
+            }
+        };
+        scaling_signals::record_completed(
+            WorkQueue::ProofGeneration,
+            started.elapsed(),
+            job.error.is_none(),
+        );
+        tracing::info!(
+            "🔐 Proof job {} finished as {:?} in {:?}",
+            job.job_id,
+            job.status,
+            started.elapsed()
+        );
+        engine.notify_proof_job(&job).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+    use crate::zk_proof_engine::{CircuitType, ProofStatus};
+    use serde_json::json;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_queued_proofs_are_generated_and_polled() {
+        let storage = Arc::new(std::sync::Mutex::new(InMemoryStorage::new()));
+        let engine = ZkProofEngine::new(Arc::clone(&storage));
+        let inputs = |pairs: &[(&str, serde_json::Value)]| -> HashMap<_, _> {
+            pairs
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect()
+        };
+        let public_inputs = inputs(&[
+            ("threshold_standard", json!("Codex")),
+            ("threshold_ppm", json!(0.4)),
+        ]);
+        let private_inputs = inputs(&[("pesticide_levels", json!([0.1, 0.2]))]);
+
+        // A job interrupted by a restart is failed when the queue starts
+        let stale = engine
+            .create_proof_job(
+                CircuitType::PesticideThreshold,
+                "lab".to_string(),
+                &public_inputs,
+                &private_inputs,
+                &ProofValidity::default(),
+            )
+            .unwrap();
+        let queue = ProofJobQueue::start(Arc::clone(&storage), 1);
+        let stale = engine.get_proof_job(&stale.job_id, "lab").unwrap().unwrap();
+        assert_eq!(stale.status, ProofStatus::Failed);
+
+        // Bad inputs are rejected before anything is queued
+        assert!(matches!(
+            engine.create_proof_job(
+                CircuitType::PesticideThreshold,
+                "lab".to_string(),
+                &public_inputs,
+                &HashMap::new(),
+                &ProofValidity::default(),
+            ),
+            Err(ZkProofError::InvalidInput(_))
+        ));
+
+        let job = engine
+            .create_proof_job(
+                CircuitType::PesticideThreshold,
+                "lab".to_string(),
+                &public_inputs,
+                &private_inputs,
+                &ProofValidity::default(),
+            )
+            .unwrap();
+        assert_eq!(job.status, ProofStatus::Pending);
+        queue
+            .enqueue(ProofJobTask {
+                job: job.clone(),
+                public_inputs,
+                private_inputs,
+                validity: ProofValidity::default(),
+            })
+            .unwrap();
+
+        let mut polled = job.clone();
+        for _ in 0..600 {
+            polled = engine.get_proof_job(&job.job_id, "lab").unwrap().unwrap();
+            if polled.completed_at.is_some() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+        assert_eq!(polled.status, ProofStatus::Verified, "{:?}", polled.error);
+        let proof = engine
+            .get_proof(&polled.proof_id.unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(proof.status, ProofStatus::Verified);
+        // Jobs are only visible to their prover
+        assert!(engine
+            .get_proof_job(&job.job_id, "other")
+            .unwrap()
+            .is_none());
+    }
+}